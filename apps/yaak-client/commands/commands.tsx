@@ -1,6 +1,7 @@
 import { createWorkspaceModel, type Folder, modelTypeLabel } from "@yaakapp-internal/models";
-import { applySync, calculateSync } from "@yaakapp-internal/sync";
+import { applySync, calculateSync, type SyncOp } from "@yaakapp-internal/sync";
 import { Button } from "../components/core/Button";
+import { SyncConflictDialog } from "../components/SyncConflictDialog";
 import {
   Banner,
   InlineCode,
@@ -67,6 +68,8 @@ export const syncWorkspace = createFastMutation<
     console.log("Syncing workspace", workspaceId, syncDir, ops);
 
     const dbOps = ops.filter((o) => o.type.startsWith("db"));
+    const conflictOps = ops.filter((o) => o.type === "conflict");
+    conflictOps.forEach((op) => showSyncConflict(workspaceId, syncDir, op));
 
     if (dbOps.length === 0) {
       await applySync(workspaceId, syncDir, ops);
@@ -133,6 +136,11 @@ export const syncWorkspace = createFastMutation<
                   name = resolvedModelNameWithFolders(op.fs.model);
                   color = "text-info";
                   model = modelTypeLabel(op.fs.model);
+                } else if (op.type === "dbMerge") {
+                  label = "merge";
+                  name = resolvedModelNameWithFolders(op.model);
+                  color = "text-notice";
+                  model = modelTypeLabel(op.model);
                 } else if (op.type === "dbDelete") {
                   label = "delete";
                   name = resolvedModelNameWithFolders(op.model);
@@ -168,3 +176,16 @@ export const syncWorkspace = createFastMutation<
     });
   },
 });
+
+function showSyncConflict(workspaceId: string, syncDir: string, op: SyncOp) {
+  if (op.type !== "conflict") return;
+  const id = `sync-conflict-${op.fs.model.id}`;
+  showDialog({
+    id,
+    title: "Resolve Sync Conflict",
+    size: "md",
+    render: ({ hide }) => (
+      <SyncConflictDialog workspaceId={workspaceId} syncDir={syncDir} op={op} onDone={hide} />
+    ),
+  });
+}
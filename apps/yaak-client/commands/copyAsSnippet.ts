@@ -0,0 +1,27 @@
+import type { HttpRequest } from "@yaakapp-internal/models";
+import type { CodegenLanguage } from "@yaakapp-internal/plugins";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { copyToClipboard } from "../lib/copy";
+import { invokeCmd } from "../lib/tauri";
+import { showErrorToast } from "../lib/toast";
+
+export function getCodegenLanguages() {
+  return invokeCmd<CodegenLanguage[]>("cmd_codegen_languages");
+}
+
+export async function copyAsSnippet(request: HttpRequest, language: CodegenLanguage) {
+  try {
+    const snippet = await invokeCmd<string>("cmd_export_as_snippet", {
+      requestId: request.id,
+      environmentId: getActiveEnvironment()?.id ?? null,
+      language: language.name,
+    });
+    copyToClipboard(snippet);
+  } catch (err) {
+    showErrorToast({
+      id: "copy-as-snippet",
+      title: `Failed to copy as ${language.label}`,
+      message: err,
+    });
+  }
+}
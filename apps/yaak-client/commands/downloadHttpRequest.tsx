@@ -0,0 +1,42 @@
+import { save } from "@tauri-apps/plugin-dialog";
+import type { HttpRequest, HttpResponse } from "@yaakapp-internal/models";
+import { InlineCode } from "@yaakapp-internal/ui";
+import slugify from "slugify";
+import { getActiveCookieJar } from "../hooks/useActiveCookieJar";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { invokeCmd } from "../lib/tauri";
+import { showErrorToast, showToast } from "../lib/toast";
+
+/** Send a request and stream its response body straight to a file picked by the user */
+export async function downloadHttpRequest(request: HttpRequest) {
+  const downloadPath = await save({
+    defaultPath: slugify(request.name || "download", { lower: true }),
+    title: "Download Response",
+  });
+  if (downloadPath == null) return;
+
+  try {
+    const response = await invokeCmd<HttpResponse>("cmd_send_http_request", {
+      request,
+      environmentId: getActiveEnvironment()?.id,
+      cookieJarId: getActiveCookieJar()?.id,
+      downloadPath,
+    });
+    if (response.error) {
+      throw response.error;
+    }
+    showToast({
+      message: (
+        <>
+          Response downloaded to <InlineCode>{downloadPath}</InlineCode>
+        </>
+      ),
+    });
+  } catch (err) {
+    showErrorToast({
+      id: "download-http-request",
+      title: "Failed to download response",
+      message: err,
+    });
+  }
+}
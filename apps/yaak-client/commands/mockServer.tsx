@@ -0,0 +1,16 @@
+import { MockServerDialog } from "../components/MockServerDialog";
+import { activeWorkspaceIdAtom } from "../hooks/useActiveWorkspace";
+import { showDialog } from "../lib/dialog";
+import { jotaiStore } from "../lib/jotai";
+
+export function openMockServerDialog() {
+  const workspaceId = jotaiStore.get(activeWorkspaceIdAtom);
+  if (workspaceId == null) return;
+
+  showDialog({
+    id: "mock-server",
+    title: "Mock Server",
+    size: "sm",
+    render: () => <MockServerDialog workspaceId={workspaceId} />,
+  });
+}
@@ -0,0 +1,32 @@
+import type { Folder, HttpRequest, Monitor } from "@yaakapp-internal/models";
+import { createWorkspaceModel } from "@yaakapp-internal/models";
+import { MonitorsDialog } from "../components/MonitorsDialog";
+import { getActiveCookieJar } from "../hooks/useActiveCookieJar";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { showDialog } from "../lib/dialog";
+import { resolvedModelName } from "../lib/resolvedModelName";
+
+export function openMonitorsDialog() {
+  showDialog({
+    id: "monitors",
+    title: "Monitors",
+    size: "lg",
+    render: () => <MonitorsDialog />,
+  });
+}
+
+export async function createMonitor(target: Folder | HttpRequest) {
+  await createWorkspaceModel<Monitor>({
+    model: "monitor",
+    workspaceId: target.workspaceId,
+    name: resolvedModelName(target),
+    folderId: target.model === "folder" ? target.id : null,
+    requestId: target.model === "http_request" ? target.id : null,
+    enabled: true,
+    interval: 300,
+    failureThreshold: 3,
+    environmentId: getActiveEnvironment()?.id ?? null,
+    cookieJarId: getActiveCookieJar()?.id ?? null,
+  });
+  openMonitorsDialog();
+}
@@ -1,4 +1,10 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 
 import { MoveToWorkspaceDialog } from "../components/MoveToWorkspaceDialog";
 import { activeWorkspaceIdAtom } from "../hooks/useActiveWorkspace";
@@ -9,7 +15,9 @@ import { jotaiStore } from "../lib/jotai";
 
 export const moveToWorkspace = createFastMutation({
   mutationKey: ["move_workspace"],
-  mutationFn: async (requests: (HttpRequest | GrpcRequest | WebsocketRequest)[]) => {
+  mutationFn: async (
+    requests: (HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest)[],
+  ) => {
     const activeWorkspaceId = jotaiStore.get(activeWorkspaceIdAtom);
     if (activeWorkspaceId == null) return;
     if (requests.length === 0) return;
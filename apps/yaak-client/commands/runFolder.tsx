@@ -0,0 +1,16 @@
+import { getModel } from "@yaakapp-internal/models";
+import { RunFolderDialog } from "../components/RunFolderDialog";
+import { showDialog } from "../lib/dialog";
+import { resolvedModelName } from "../lib/resolvedModelName";
+
+export function openRunFolderDialog(folderId: string) {
+  const folder = getModel("folder", folderId);
+  if (folder == null) return;
+  showDialog({
+    id: "run-folder",
+    title: `Run ${resolvedModelName(folder)}`,
+    size: "md",
+    className: "h-[40rem]",
+    render: () => <RunFolderDialog folder={folder} />,
+  });
+}
@@ -0,0 +1,175 @@
+import type { Assertion, AssertionKind, HttpRequest } from "@yaakapp-internal/models";
+import { patchModel } from "@yaakapp-internal/models";
+import {
+  HStack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import { useCallback } from "react";
+import { fireAndForget } from "../lib/fireAndForget";
+import { generateId } from "../lib/generateId";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+import { Select } from "./core/Select";
+
+interface Props {
+  request: HttpRequest;
+}
+
+export function AssertionsEditor({ request }: Props) {
+  const assertions = request.assertions;
+
+  const handleChange = useCallback(
+    (assertions: Assertion[]) => {
+      fireAndForget(patchModel(request, { assertions }));
+    },
+    [request],
+  );
+
+  const handleAdd = useCallback(() => {
+    const newAssertion: Assertion = {
+      enabled: true,
+      kind: "status_equals",
+      path: "",
+      value: "200",
+      id: generateId(),
+    };
+    handleChange([...assertions, newAssertion]);
+  }, [assertions, handleChange]);
+
+  const handleUpdate = useCallback(
+    (index: number, update: Partial<Assertion>) => {
+      handleChange(assertions.map((a, i) => (i === index ? { ...a, ...update } : a)));
+    },
+    [assertions, handleChange],
+  );
+
+  const handleDelete = useCallback(
+    (index: number) => {
+      handleChange(assertions.filter((_, i) => i !== index));
+    },
+    [assertions, handleChange],
+  );
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        Check every response after it arrives. Results are saved with the response and shown in
+        its Tests tab.
+      </div>
+
+      {assertions.length > 0 && (
+        <Table>
+          <TableHead>
+            <TableRow>
+              <TableHeaderCell className="w-8" />
+              <TableHeaderCell>Check</TableHeaderCell>
+              <TableHeaderCell>Path or Header</TableHeaderCell>
+              <TableHeaderCell>Expected</TableHeaderCell>
+              <TableHeaderCell className="w-10" />
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {assertions.map((assertion, index) => (
+              <AssertionRow
+                // oxlint-disable-next-line react/no-array-index-key
+                key={assertion.id ?? index}
+                assertion={assertion}
+                onUpdate={(update) => handleUpdate(index, update)}
+                onDelete={() => handleDelete(index)}
+              />
+            ))}
+          </TableBody>
+        </Table>
+      )}
+
+      <HStack>
+        <Button size="xs" color="secondary" variant="border" onClick={handleAdd}>
+          Add Assertion
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
+
+interface AssertionRowProps {
+  assertion: Assertion;
+  onUpdate: (update: Partial<Assertion>) => void;
+  onDelete: () => void;
+}
+
+function AssertionRow({ assertion, onUpdate, onDelete }: AssertionRowProps) {
+  const usesPath =
+    assertion.kind === "json_path_equals" ||
+    assertion.kind === "json_path_matches" ||
+    assertion.kind === "header_exists";
+  const usesValue = assertion.kind !== "header_exists";
+
+  return (
+    <TableRow>
+      <TableCell>
+        <Checkbox
+          hideLabel
+          title={assertion.enabled ? "Disable assertion" : "Enable assertion"}
+          checked={assertion.enabled ?? true}
+          onChange={(enabled) => onUpdate({ enabled })}
+        />
+      </TableCell>
+      <TableCell>
+        <Select<AssertionKind>
+          hideLabel
+          size="sm"
+          name="kind"
+          label="Check"
+          value={assertion.kind}
+          onChange={(kind) => onUpdate({ kind })}
+          options={[
+            { label: "Status equals", value: "status_equals" },
+            { label: "JSONPath equals", value: "json_path_equals" },
+            { label: "JSONPath matches", value: "json_path_matches" },
+            { label: "Header exists", value: "header_exists" },
+            { label: "Latency under (ms)", value: "latency_under" },
+          ]}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Path or header"
+          disabled={!usesPath}
+          placeholder={assertion.kind === "header_exists" ? "Content-Type" : "$.id"}
+          defaultValue={assertion.path}
+          onChange={(path) => onUpdate({ path })}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Expected"
+          disabled={!usesValue}
+          placeholder={assertion.kind === "json_path_matches" ? "^[a-z]+$" : "200"}
+          defaultValue={assertion.value}
+          onChange={(value) => onUpdate({ value })}
+        />
+      </TableCell>
+      <TableCell>
+        <IconButton
+          size="xs"
+          iconSize="sm"
+          icon="trash"
+          title="Delete assertion"
+          onClick={onDelete}
+        />
+      </TableCell>
+    </TableRow>
+  );
+}
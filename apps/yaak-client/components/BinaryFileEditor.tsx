@@ -3,6 +3,7 @@ import { Banner, HStack, InlineCode, VStack } from "@yaakapp-internal/ui";
 import mime from "mime";
 import { useKeyValue } from "../hooks/useKeyValue";
 import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
 import { SelectFile } from "./SelectFile";
 
 type Props = {
@@ -28,16 +29,25 @@ export function BinaryFileEditor({
 
   const handleChange = async ({ filePath }: { filePath: string | null }) => {
     await ignoreContentType.set(false);
-    onChange({ filePath: filePath ?? undefined });
+    onChange({ ...body, filePath: filePath ?? undefined });
   };
 
   const filePath = typeof body.filePath === "string" ? body.filePath : null;
+  const sniffContentType = body.sniffContentType === true;
   const mimeType = mime.getType(filePath ?? "") ?? "application/octet-stream";
+  const showContentTypeBanner =
+    filePath != null && !sniffContentType && mimeType !== contentType && !ignoreContentType.value;
 
   return (
     <VStack space={2}>
       <SelectFile onChange={handleChange} filePath={filePath} />
-      {filePath != null && mimeType !== contentType && !ignoreContentType.value && (
+      <Checkbox
+        checked={sniffContentType}
+        title="Detect Content-Type when sending"
+        help="Set the Content-Type header from the file's contents unless the request sets one."
+        onChange={(sniffContentType) => onChange({ ...body, sniffContentType })}
+      />
+      {showContentTypeBanner && (
         <Banner className="mt-3 !py-5">
           <div className="mb-4 text-center">
             <div>Set Content-Type header</div>
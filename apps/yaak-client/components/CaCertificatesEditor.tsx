@@ -0,0 +1,157 @@
+import type { CaCertificate } from "@yaakapp-internal/models";
+import { fireAndForget } from "../lib/fireAndForget";
+import {
+  HStack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import classNames from "classnames";
+import type { ReactNode } from "react";
+import { useCallback, useId, useMemo } from "react";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { SelectFile } from "./SelectFile";
+
+interface Props {
+  certificates: CaCertificate[];
+  onChange: (certificates: CaCertificate[]) => Promise<unknown>;
+  description: ReactNode;
+}
+
+interface CaCertificateWithId extends CaCertificate {
+  _id: string;
+}
+
+export function CaCertificatesEditor({ certificates, onChange, description }: Props) {
+  const reactId = useId();
+
+  // Ensure each certificate has an internal ID for React keys
+  const certificatesWithIds = useMemo<CaCertificateWithId[]>(() => {
+    return certificates.map((certificate, index) => ({
+      ...certificate,
+      _id: `${reactId}-${index}`,
+    }));
+  }, [certificates, reactId]);
+
+  const handleChange = useCallback(
+    (certificates: CaCertificate[]) => {
+      fireAndForget(onChange(certificates));
+    },
+    [onChange],
+  );
+
+  const handleAdd = useCallback(() => {
+    const newCertificate: CaCertificate = { file: null, pem: null, enabled: true };
+    handleChange([...certificates, newCertificate]);
+  }, [certificates, handleChange]);
+
+  const handleUpdate = useCallback(
+    (index: number, update: Partial<CaCertificate>) => {
+      const updated = certificates.map((c, i) => (i === index ? { ...c, ...update } : c));
+      handleChange(updated);
+    },
+    [certificates, handleChange],
+  );
+
+  const handleDelete = useCallback(
+    (index: number) => {
+      const updated = certificates.filter((_, i) => i !== index);
+      handleChange(updated);
+    },
+    [certificates, handleChange],
+  );
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">{description}</div>
+
+      {certificatesWithIds.length > 0 && (
+        <Table>
+          <TableHead>
+            <TableRow>
+              <TableHeaderCell className="w-8" />
+              <TableHeaderCell>PEM File</TableHeaderCell>
+              <TableHeaderCell>Inline PEM</TableHeaderCell>
+              <TableHeaderCell className="w-10" />
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {certificatesWithIds.map((certificate, index) => (
+              <CaCertificateRow
+                key={certificate._id}
+                certificate={certificate}
+                onUpdate={(update) => handleUpdate(index, update)}
+                onDelete={() => handleDelete(index)}
+              />
+            ))}
+          </TableBody>
+        </Table>
+      )}
+
+      <HStack>
+        <Button size="xs" color="secondary" variant="border" onClick={handleAdd}>
+          Add CA Certificate
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
+
+interface CaCertificateRowProps {
+  certificate: CaCertificate;
+  onUpdate: (update: Partial<CaCertificate>) => void;
+  onDelete: () => void;
+}
+
+function CaCertificateRow({ certificate, onUpdate, onDelete }: CaCertificateRowProps) {
+  return (
+    <TableRow>
+      <TableCell className="align-top">
+        <Checkbox
+          hideLabel
+          title={certificate.enabled ? "Disable certificate" : "Enable certificate"}
+          checked={certificate.enabled ?? true}
+          onChange={(enabled) => onUpdate({ enabled })}
+        />
+      </TableCell>
+      <TableCell className="align-top">
+        <SelectFile
+          hideLabel
+          label="PEM file"
+          noun="Certificate"
+          size="sm"
+          filePath={certificate.file}
+          onChange={({ filePath }) => onUpdate({ file: filePath })}
+        />
+      </TableCell>
+      <TableCell>
+        <textarea
+          aria-label="Inline PEM"
+          className={classNames(
+            "x-theme-input w-full min-w-0 min-h-[3rem] rounded-md bg-transparent resize-y",
+            "border border-border-subtle outline-none focus:border-border-focus",
+            "px-2 py-1 text-xs font-mono cursor-text placeholder:text-placeholder",
+          )}
+          placeholder="-----BEGIN CERTIFICATE-----"
+          defaultValue={certificate.pem ?? ""}
+          onChange={(event) => onUpdate({ pem: event.target.value || null })}
+        />
+      </TableCell>
+      <TableCell className="align-top">
+        <IconButton
+          size="xs"
+          iconSize="sm"
+          icon="trash"
+          title="Delete certificate"
+          onClick={onDelete}
+        />
+      </TableCell>
+    </TableRow>
+  );
+}
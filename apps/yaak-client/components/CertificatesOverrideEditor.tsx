@@ -0,0 +1,148 @@
+import type {
+  ClientCertificate,
+  InheritedCaCertificatesSetting,
+  InheritedClientCertificateSetting,
+} from "@yaakapp-internal/models";
+import { VStack } from "@yaakapp-internal/ui";
+import { CaCertificatesEditor } from "./CaCertificatesEditor";
+import { PlainInput } from "./core/PlainInput";
+import { SettingRowSelect, SettingsList, SettingsSection } from "./core/SettingRow";
+import { SelectFile } from "./SelectFile";
+
+type CaMode = "inherit" | "custom";
+type ClientCertificateMode = "inherit" | "host" | "custom" | "none";
+
+interface Props {
+  caCertificates: InheritedCaCertificatesSetting;
+  clientCertificate: InheritedClientCertificateSetting;
+  onChange: (patch: {
+    settingCaCertificates?: InheritedCaCertificatesSetting;
+    settingClientCertificate?: InheritedClientCertificateSetting;
+  }) => Promise<unknown>;
+}
+
+export function CertificatesOverrideEditor({ caCertificates, clientCertificate, onChange }: Props) {
+  const certificate = clientCertificate.value;
+  const clientCertificateMode: ClientCertificateMode = !clientCertificate.enabled
+    ? "inherit"
+    : certificate == null
+      ? "host"
+      : certificate.enabled === false
+        ? "none"
+        : "custom";
+
+  const patchCertificate = (patch: Partial<ClientCertificate>) =>
+    onChange({
+      settingClientCertificate: {
+        enabled: true,
+        value: { ...(certificate ?? defaultCertificate()), ...patch },
+      },
+    });
+
+  const hasPfx = Boolean(certificate?.pfxFile);
+  const hasCrtKey = Boolean(certificate?.crtFile || certificate?.keyFile);
+
+  return (
+    <SettingsList className="space-y-8">
+      <SettingsSection title="CA Certificates">
+        <SettingRowSelect<CaMode>
+          name="caCertificates"
+          title="Trusted CAs"
+          description="Certificate authorities trusted when validating server certificates."
+          value={caCertificates.enabled ? "custom" : "inherit"}
+          onChange={(v) =>
+            onChange({ settingCaCertificates: { ...caCertificates, enabled: v === "custom" } })
+          }
+          options={[
+            { label: "Use the parent's CAs", value: "inherit" },
+            { label: "Custom CAs", value: "custom" },
+          ]}
+          selectClassName="!w-64"
+        />
+        {caCertificates.enabled && (
+          <div className="pt-3">
+            <CaCertificatesEditor
+              certificates={caCertificates.value}
+              onChange={(value) => onChange({ settingCaCertificates: { enabled: true, value } })}
+              description="Trusted in place of the CAs from the workspace or parent folder."
+            />
+          </div>
+        )}
+      </SettingsSection>
+
+      <SettingsSection title="Client Certificate">
+        <SettingRowSelect<ClientCertificateMode>
+          name="clientCertificate"
+          title="Client certificate"
+          description="Certificate presented to servers that ask for one, for mutual TLS."
+          value={clientCertificateMode}
+          onChange={(v) => {
+            if (v === "inherit") {
+              return onChange({
+                settingClientCertificate: { ...clientCertificate, enabled: false },
+              });
+            } else if (v === "host") {
+              return onChange({ settingClientCertificate: { enabled: true, value: null } });
+            } else {
+              return patchCertificate({ enabled: v === "custom" });
+            }
+          }}
+          options={[
+            { label: "Use the parent's certificate", value: "inherit" },
+            { label: "Match by host from settings", value: "host" },
+            { label: "Custom certificate", value: "custom" },
+            { label: "No certificate", value: "none" },
+          ]}
+          selectClassName="!w-64"
+        />
+        {clientCertificateMode === "custom" && certificate != null && (
+          <VStack space={2} className="pt-3 pb-3">
+            <SelectFile
+              label="CRT File"
+              noun="Cert"
+              filePath={certificate.crtFile ?? null}
+              size="sm"
+              disabled={hasPfx}
+              onChange={({ filePath }) => patchCertificate({ crtFile: filePath })}
+            />
+            <SelectFile
+              label="KEY File"
+              noun="Key"
+              filePath={certificate.keyFile ?? null}
+              size="sm"
+              disabled={hasPfx}
+              onChange={({ filePath }) => patchCertificate({ keyFile: filePath })}
+            />
+            <SelectFile
+              label="PFX File"
+              noun="Key"
+              filePath={certificate.pfxFile ?? null}
+              size="sm"
+              disabled={hasCrtKey}
+              onChange={({ filePath }) => patchCertificate({ pfxFile: filePath })}
+            />
+            <PlainInput
+              label="Passphrase"
+              size="sm"
+              type="password"
+              defaultValue={certificate.passphrase ?? ""}
+              onChange={(passphrase) => patchCertificate({ passphrase: passphrase || null })}
+            />
+          </VStack>
+        )}
+      </SettingsSection>
+    </SettingsList>
+  );
+}
+
+function defaultCertificate(): ClientCertificate {
+  return {
+    host: "",
+    port: null,
+    crtFile: null,
+    keyFile: null,
+    pfxFile: null,
+    passphrase: null,
+    enabled: true,
+  };
+}
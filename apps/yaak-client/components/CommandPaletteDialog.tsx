@@ -14,6 +14,8 @@ import {
 } from "react";
 import { createFolder } from "../commands/commands";
 import { createSubEnvironmentAndActivate } from "../commands/createEnvironment";
+import { openMockServerDialog } from "../commands/mockServer";
+import { openMonitorsDialog } from "../commands/monitors";
 import { openSettings } from "../commands/openSettings";
 import { openWorkspaceSettings } from "../commands/openWorkspaceSettings";
 import { switchWorkspace } from "../commands/switchWorkspace";
@@ -105,6 +107,16 @@ export function CommandPaletteDialog({ onClose }: { onClose: () => void }) {
         action: "workspace_settings.show",
         onSelect: () => openWorkspaceSettings(),
       },
+      {
+        key: "monitors.open",
+        label: "Open Monitors",
+        onSelect: openMonitorsDialog,
+      },
+      {
+        key: "mock_server.open",
+        label: "Open Mock Server",
+        onSelect: openMockServerDialog,
+      },
       {
         key: "app.create",
         label: "Create Workspace",
@@ -125,6 +137,16 @@ export function CommandPaletteDialog({ onClose }: { onClose: () => void }) {
         label: "Create Websocket Request",
         onSelect: () => createRequestAndNavigate({ model: "websocket_request", workspaceId }),
       },
+      {
+        key: "mqtt_request.create",
+        label: "Create MQTT Request",
+        onSelect: () => createRequestAndNavigate({ model: "mqtt_request", workspaceId }),
+      },
+      {
+        key: "socket_request.create",
+        label: "Create TCP / UDP Request",
+        onSelect: () => createRequestAndNavigate({ model: "socket_request", workspaceId }),
+      },
       {
         key: "folder.create",
         label: "Create Folder",
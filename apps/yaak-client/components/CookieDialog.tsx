@@ -1,5 +1,10 @@
 import type { Cookie } from "@yaakapp-internal/models";
-import { cookieJarsAtom, patchModel } from "@yaakapp-internal/models";
+import {
+  clearCookies,
+  cookieJarsAtom,
+  deleteCookie,
+  upsertCookie,
+} from "@yaakapp-internal/models";
 import { formatDate } from "date-fns/format";
 import { useAtomValue } from "jotai";
 import {
@@ -122,15 +127,10 @@ export const CookieDialog = ({ cookieJarId }: Props) => {
     }
 
     const nextCookieKey = cookieKey(nextCookie);
-    const nextCookies = cookieJar.cookies.filter((cookie) => {
-      const key = cookieKey(cookie);
-      if (editingCookieKey != null && key === editingCookieKey) {
-        return false;
-      }
-      return key !== nextCookieKey;
-    });
+    const previousCookie =
+      cookieJar.cookies.find((cookie) => cookieKey(cookie) === editingCookieKey) ?? null;
 
-    patchModel(cookieJar, { cookies: [...nextCookies, nextCookie] });
+    upsertCookie(cookieJar.id, nextCookie, previousCookie);
     setSelectedCookieKey(nextCookieKey);
     setEditingCookieKey(null);
     setDraftCookie(null);
@@ -210,7 +210,7 @@ export const CookieDialog = ({ cookieJarId }: Props) => {
                           setEditingCookieKey(null);
                           setDraftCookie(null);
                           setDraftExpiresInput("");
-                          patchModel(cookieJar, { cookies: [] });
+                          clearCookies(cookieJar.id);
                         }}
                       />
                     </TableHeaderCell>
@@ -276,11 +276,7 @@ export const CookieDialog = ({ cookieJarId }: Props) => {
                                 setDraftCookie(null);
                                 setDraftExpiresInput("");
                               }
-                              patchModel(cookieJar, {
-                                cookies: cookieJar.cookies.filter(
-                                  (c2: Cookie) => cookieKey(c2) !== key,
-                                ),
-                              });
+                              deleteCookie(cookieJar.id, c);
                             }}
                           />
                         </TableCell>
@@ -1,4 +1,6 @@
+import { open, save } from "@tauri-apps/plugin-dialog";
 import { cookieJarsAtom, patchModel } from "@yaakapp-internal/models";
+import slugify from "slugify";
 import { useAtomValue } from "jotai";
 import { memo, useMemo } from "react";
 import { useActiveCookieJar } from "../hooks/useActiveCookieJar";
@@ -6,6 +8,8 @@ import { useCreateCookieJar } from "../hooks/useCreateCookieJar";
 import { deleteModelWithConfirm } from "../lib/deleteModelWithConfirm";
 import { showPrompt } from "../lib/prompt";
 import { setWorkspaceSearchParams } from "../lib/setWorkspaceSearchParams";
+import { invokeCmd } from "../lib/tauri";
+import { showToast } from "../lib/toast";
 import { CookieDialog } from "./CookieDialog";
 import { Dropdown, type DropdownItem } from "./core/Dropdown";
 import { Icon, InlineCode } from "@yaakapp-internal/ui";
@@ -38,6 +42,47 @@ export const CookieDropdown = memo(function CookieDropdown() {
                 CookieDialog.show(activeCookieJar.id);
               },
             },
+            {
+              key: "import",
+              label: "Import Cookies",
+              leftSlot: <Icon icon="folder_input" />,
+              onSelect: async () => {
+                const filePath = await open({
+                  title: "Import Cookies",
+                  multiple: false,
+                  filters: [{ name: "Netscape Cookies", extensions: ["txt"] }],
+                });
+                if (filePath == null) return;
+                await invokeCmd("cmd_import_cookies", {
+                  cookieJarId: activeCookieJar.id,
+                  filePath,
+                });
+                showToast({ color: "success", message: "Cookies imported" });
+              },
+            },
+            {
+              key: "export",
+              label: "Export Cookies",
+              leftSlot: <Icon icon="folder_output" />,
+              onSelect: async () => {
+                const exportPath = await save({
+                  title: "Export Cookies",
+                  defaultPath: `${slugify(activeCookieJar.name, { lower: true })}-cookies.txt`,
+                });
+                if (exportPath == null) return;
+                await invokeCmd("cmd_export_cookies", {
+                  cookieJarId: activeCookieJar.id,
+                  exportPath,
+                });
+                showToast({
+                  message: (
+                    <>
+                      Cookies exported to <InlineCode>{exportPath}</InlineCode>
+                    </>
+                  ),
+                });
+              },
+            },
             {
               key: "rename",
               label: "Rename",
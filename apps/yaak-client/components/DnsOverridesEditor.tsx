@@ -76,7 +76,7 @@ export function DnsOverridesEditor({ workspace }: Props) {
       <div className="text-text-subtle text-sm">
         Override DNS resolution for specific hostnames. This works like{" "}
         <code className="text-text-subtlest bg-surface-highlight px-1 rounded">/etc/hosts</code> but
-        only for requests made from this workspace.
+        only for HTTP, gRPC, and WebSocket requests made from this workspace.
       </div>
 
       {overridesWithIds.length > 0 && (
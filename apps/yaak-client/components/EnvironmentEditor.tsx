@@ -1,8 +1,9 @@
 import type { Environment } from "@yaakapp-internal/models";
-import { patchModel } from "@yaakapp-internal/models";
+import { cookieJarsAtom, patchModel } from "@yaakapp-internal/models";
 import type { GenericCompletionOption } from "@yaakapp-internal/plugins";
 import { Heading } from "@yaakapp-internal/ui";
 import classNames from "classnames";
+import { useAtomValue } from "jotai";
 import { useCallback, useMemo } from "react";
 import { useEnvironmentsBreakdown } from "../hooks/useEnvironmentsBreakdown";
 import { useIsEncryptionEnabled } from "../hooks/useIsEncryptionEnabled";
@@ -20,6 +21,7 @@ import type { PairEditorHandle, PairWithId } from "./core/PairEditor";
 import { ensurePairId } from "./core/PairEditor.util";
 import { PairOrBulkEditor } from "./core/PairOrBulkEditor";
 import { PillButton } from "./core/PillButton";
+import { Select } from "./core/Select";
 import { EnvironmentColorIndicator } from "./EnvironmentColorIndicator";
 import { EnvironmentSharableTooltip } from "./EnvironmentSharableTooltip";
 
@@ -33,6 +35,7 @@ interface Props {
 export function EnvironmentEditor({ environment, hideName, className, setRef }: Props) {
   const workspaceId = environment.workspaceId;
   const isEncryptionEnabled = useIsEncryptionEnabled();
+  const cookieJars = useAtomValue(cookieJarsAtom);
   const valueVisibility = useKeyValue<boolean>({
     namespace: "global",
     key: ["environmentValueVisibility", workspaceId],
@@ -40,7 +43,17 @@ export function EnvironmentEditor({ environment, hideName, className, setRef }:
   });
   const { allEnvironments } = useEnvironmentsBreakdown();
   const handleChange = useCallback(
-    (variables: PairWithId[]) => patchModel(environment, { variables }),
+    async (variables: PairWithId[]) => {
+      // Secret values are encrypted with the workspace key when saved, so make sure there is one
+      const hasNewSecret = variables.some(
+        (v) => v.secret && !environment.variables.some((e) => e.id === v.id && e.secret),
+      );
+      if (hasNewSecret) {
+        withEncryptionEnabled(() => patchModel(environment, { variables }));
+        return;
+      }
+      await patchModel(environment, { variables });
+    },
     [environment],
   );
   const [forceUpdateKey, regenerateForceUpdateKey] = useRandomKey();
@@ -80,7 +93,9 @@ export function EnvironmentEditor({ environment, hideName, className, setRef }:
   const valueType = !isEncryptionEnabled && valueVisibility.value ? "text" : "password";
   const allVariableAreEncrypted = useMemo(
     () =>
-      environment.variables.every((v) => v.value === "" || analyzeTemplate(v.value) !== "insecure"),
+      environment.variables.every(
+        (v) => v.value === "" || v.secret || analyzeTemplate(v.value) !== "insecure",
+      ),
     [environment.variables],
   );
 
@@ -88,7 +103,10 @@ export function EnvironmentEditor({ environment, hideName, className, setRef }:
     withEncryptionEnabled(async () => {
       const encryptedVariables: PairWithId[] = [];
       for (const variable of environment.variables) {
-        const value = variable.value ? await convertTemplateToSecure(variable.value) : "";
+        const value =
+          variable.value && !variable.secret
+            ? await convertTemplateToSecure(variable.value)
+            : variable.value;
         encryptedVariables.push(ensurePairId({ ...variable, value }));
       }
       await handleChange(encryptedVariables);
@@ -135,6 +153,21 @@ export function EnvironmentEditor({ environment, hideName, className, setRef }:
           >
             {environment.public ? "Sharable" : "Private"}
           </PillButton>
+          <Select
+            hideLabel
+            name="environment-cookie-jar"
+            label="Cookie Jar"
+            value={environment.cookieJarId ?? ""}
+            size="xs"
+            className="ml-auto w-auto"
+            options={[
+              { label: "Active Cookie Jar", value: "" },
+              ...(cookieJars ?? []).map((j) => ({ label: j.name, value: j.id })),
+            ]}
+            onChange={async (cookieJarId) => {
+              await patchModel(environment, { cookieJarId: cookieJarId || null });
+            }}
+          />
         </Heading>
         {environment.public && (!isEncryptionEnabled || !allVariableAreEncrypted) && (
           <DismissibleBanner
@@ -157,6 +190,7 @@ export function EnvironmentEditor({ environment, hideName, className, setRef }:
         setRef={setRef}
         className="h-full"
         allowMultilineValues
+        allowSecretValues
         preferenceName="environment"
         nameAutocomplete={nameAutocomplete}
         namePlaceholder="VAR_NAME"
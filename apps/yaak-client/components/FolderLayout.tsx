@@ -1,15 +1,24 @@
-import type { Folder, GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  Folder,
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { foldersAtom } from "@yaakapp-internal/models";
 import { Heading, HStack, Icon, LoadingIcon } from "@yaakapp-internal/ui";
 import classNames from "classnames";
 import { useAtomValue } from "jotai";
 import type { CSSProperties, ReactNode } from "react";
 import { useCallback, useMemo } from "react";
+import { openRunFolderDialog } from "../commands/runFolder";
 import { allRequestsAtom } from "../hooks/useAllRequests";
 import { useFolderActions } from "../hooks/useFolderActions";
 import { useLatestHttpResponse } from "../hooks/useLatestHttpResponse";
 import { sendAnyHttpRequest } from "../hooks/useSendAnyHttpRequest";
 import { showDialog } from "../lib/dialog";
+import { isResponseDone } from "../lib/model_util";
 import { resolvedModelName } from "../lib/resolvedModelName";
 import { router } from "../lib/router";
 import { Button } from "./core/Button";
@@ -50,7 +59,7 @@ export function FolderLayout({ folder, style }: Props) {
       <HStack space={2} alignItems="center">
         <Icon icon="folder" size="xl" color="secondary" />
         <Heading level={1}>{resolvedModelName(folder)}</Heading>
-        <HStack className="ml-auto" alignItems="center">
+        <HStack className="ml-auto" space={1.5} alignItems="center">
           <Button
             rightSlot={<Icon icon="send_horizontal" />}
             color="secondary"
@@ -61,6 +70,15 @@ export function FolderLayout({ folder, style }: Props) {
           >
             Send All
           </Button>
+          <Button
+            rightSlot={<Icon icon="circle_play" />}
+            color="secondary"
+            size="sm"
+            variant="border"
+            onClick={() => openRunFolderDialog(folder.id)}
+          >
+            Run
+          </Button>
         </HStack>
       </HStack>
       <Separator className="mt-3 mb-8" />
@@ -73,7 +91,11 @@ export function FolderLayout({ folder, style }: Props) {
   );
 }
 
-function ChildCard({ child }: { child: Folder | HttpRequest | GrpcRequest | WebsocketRequest }) {
+function ChildCard({
+  child,
+}: {
+  child: Folder | HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest;
+}) {
   let card: ReactNode;
   if (child.model === "folder") {
     card = <FolderCard folder={child} />;
@@ -83,6 +105,10 @@ function ChildCard({ child }: { child: Folder | HttpRequest | GrpcRequest | Webs
     card = <RequestCard request={child} />;
   } else if (child.model === "websocket_request") {
     card = <RequestCard request={child} />;
+  } else if (child.model === "mqtt_request") {
+    card = <RequestCard request={child} />;
+  } else if (child.model === "socket_request") {
+    card = <RequestCard request={child} />;
   } else {
     card = <div>Unknown model</div>;
   }
@@ -154,7 +180,11 @@ function FolderCard({ folder }: { folder: Folder }) {
   );
 }
 
-function RequestCard({ request }: { request: HttpRequest | GrpcRequest | WebsocketRequest }) {
+function RequestCard({
+  request,
+}: {
+  request: HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest;
+}) {
   return <div>TODO {request.id}</div>;
 }
 
@@ -193,7 +223,7 @@ function HttpRequestCard({ request }: { request: HttpRequest }) {
               "font-mono text-editor border rounded px-1.5 py-0.5 truncate w-full",
             )}
           >
-            {latestResponse.state !== "closed" && <LoadingIcon size="sm" />}
+            {!isResponseDone(latestResponse) && <LoadingIcon size="sm" />}
             <HttpStatusTag showReason response={latestResponse} />
             <span>&bull;</span>
             <HttpResponseDurationTag response={latestResponse} />
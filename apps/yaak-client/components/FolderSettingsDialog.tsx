@@ -10,6 +10,7 @@ import { useModelAncestors } from "../hooks/useModelAncestors";
 import { deleteModelWithConfirm } from "../lib/deleteModelWithConfirm";
 import { hideDialog } from "../lib/dialog";
 import { CopyIconButton } from "./CopyIconButton";
+import { CertificatesOverrideEditor } from "./CertificatesOverrideEditor";
 import { Button } from "./core/Button";
 import { CountBadge } from "./core/CountBadge";
 import { Input } from "./core/Input";
@@ -22,6 +23,7 @@ import { HeadersEditor } from "./HeadersEditor";
 import { HttpAuthenticationEditor } from "./HttpAuthenticationEditor";
 import { MarkdownEditor } from "./MarkdownEditor";
 import { countOverriddenSettings, ModelSettingsEditor } from "./ModelSettingsEditor";
+import { SshTunnelEditor } from "./SshTunnelEditor";
 
 interface Props {
   folderId: string | null;
@@ -29,16 +31,20 @@ interface Props {
 }
 
 const TAB_AUTH = "auth";
+const TAB_CERTIFICATES = "certificates";
 const TAB_HEADERS = "headers";
 const TAB_SETTINGS = "settings";
+const TAB_SSH = "ssh";
 const TAB_VARIABLES = "variables";
 const TAB_GENERAL = "general";
 
 export type FolderSettingsTab =
   | typeof TAB_AUTH
+  | typeof TAB_CERTIFICATES
   | typeof TAB_HEADERS
   | typeof TAB_GENERAL
   | typeof TAB_SETTINGS
+  | typeof TAB_SSH
   | typeof TAB_VARIABLES;
 
 export function FolderSettingsDialog({ folderId, tab }: Props) {
@@ -76,6 +82,14 @@ export function FolderSettingsDialog({ folderId, tab }: Props) {
         label: "Variables",
         rightSlot: numVars > 0 ? <CountBadge count={numVars} /> : null,
       },
+      {
+        value: TAB_SSH,
+        label: "SSH Tunnel",
+      },
+      {
+        value: TAB_CERTIFICATES,
+        label: "Certificates",
+      },
     ];
   }, [authTab, folder, headersTab, numSettingsOverrides, numVars]);
 
@@ -171,6 +185,22 @@ export function FolderSettingsDialog({ folderId, tab }: Props) {
         <TabContent value={TAB_SETTINGS} className="overflow-y-auto h-full px-4">
           <ModelSettingsEditor model={folder} />
         </TabContent>
+        <TabContent value={TAB_SSH} className="overflow-y-auto h-full px-4">
+          <SshTunnelEditor
+            setting={folder.settingSshTunnel}
+            inheritLabel="Use the parent's SSH tunnel"
+            onChange={async (settingSshTunnel) => {
+              await patchModel(folder, { settingSshTunnel });
+            }}
+          />
+        </TabContent>
+        <TabContent value={TAB_CERTIFICATES} className="overflow-y-auto h-full px-4">
+          <CertificatesOverrideEditor
+            caCertificates={folder.settingCaCertificates}
+            clientCertificate={folder.settingClientCertificate}
+            onChange={(patch) => patchModel(folder, patch)}
+          />
+        </TabContent>
         <TabContent value={TAB_VARIABLES} className="overflow-y-auto h-full px-4">
           {folderEnvironment == null ? (
             <EmptyStateText>
@@ -14,6 +14,7 @@ import {
 import { useCallback, useEffect, useMemo, useState } from "react";
 import type { ReflectResponseService } from "../hooks/useGrpc";
 import { showAlert } from "../lib/alert";
+import { showConfirm } from "../lib/confirm";
 import { showDialog } from "../lib/dialog";
 import { pluralizeCount } from "../lib/pluralize";
 import { Button } from "./core/Button";
@@ -121,11 +122,50 @@ export function GrpcEditor({
     [],
   );
 
+  const example = useMemo(
+    () =>
+      services
+        ?.find((s) => s.name === request.service)
+        ?.methods.find((m) => m.name === request.method)?.example ?? null,
+    [services, request.service, request.method],
+  );
+
+  const handleInsertExample = useCallback(async () => {
+    if (editorView == null || example == null) return;
+
+    if (editorView.state.doc.toString().trim() !== "") {
+      const confirmed = await showConfirm({
+        id: "grpc-replace-message",
+        title: "Replace Message",
+        description: "Replace the current message with an example generated from the schema?",
+        confirmText: "Replace",
+        color: "primary",
+      });
+      if (!confirmed) return;
+    }
+
+    editorView.dispatch({
+      changes: { from: 0, to: editorView.state.doc.length, insert: example },
+    });
+  }, [editorView, example]);
+
   const reflectionUnavailable = reflectionError?.match(/unimplemented/i);
   reflectionError = reflectionUnavailable ? undefined : reflectionError;
 
   const actions = useMemo(
     () => [
+      example != null && (
+        <Button
+          key="example"
+          size="xs"
+          color="secondary"
+          variant="border"
+          title="Fill in an example message generated from the schema"
+          onClick={handleInsertExample}
+        >
+          Example
+        </Button>
+      ),
       <div key="reflection" className={classNames(services == null && "!opacity-100")}>
         <Button
           size="xs"
@@ -162,7 +202,15 @@ export function GrpcEditor({
         </Button>
       </div>,
     ],
-    [protoFiles.length, reflectionError, reflectionLoading, reflectionUnavailable, services],
+    [
+      example,
+      handleInsertExample,
+      protoFiles.length,
+      reflectionError,
+      reflectionLoading,
+      reflectionUnavailable,
+      services,
+    ],
   );
 
   return (
@@ -26,7 +26,7 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
   const grpc = useGrpc(request, null, protoFiles);
   const services = grpc.reflect.data;
   const serverReflection = protoFiles.length === 0 && services != null;
-  let reflectError = grpc.reflect.error ?? null;
+  let reflectError = grpc.refreshReflection.error ?? grpc.reflect.error ?? null;
   const reflectionUnimplemented = String(reflectError).match(/unimplemented/i);
 
   if (reflectionUnimplemented) {
@@ -48,7 +48,9 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
             const selected = await open({
               title: "Select Proto Files",
               multiple: true,
-              filters: [{ name: "Proto Files", extensions: ["proto"] }],
+              filters: [
+                { name: "Proto Files", extensions: ["proto", ...descriptorSetExtensions] },
+              ],
             });
             if (selected == null) return;
 
@@ -76,11 +78,11 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
           Add Import Folders
         </Button>
         <Button
-          isLoading={grpc.reflect.isFetching}
-          disabled={grpc.reflect.isFetching}
+          isLoading={grpc.reflect.isFetching || grpc.refreshReflection.isPending}
+          disabled={grpc.reflect.isFetching || grpc.refreshReflection.isPending}
           variant="border"
           color="secondary"
-          onClick={() => grpc.reflect.refetch()}
+          onClick={() => grpc.refreshReflection.mutate()}
         >
           Refresh Schema
         </Button>
@@ -123,7 +125,7 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
                 );
               })}
               . You can override this schema by manually selecting <InlineCode>*.proto</InlineCode>{" "}
-              files.
+              files or compiled <InlineCode>*.binpb</InlineCode> descriptor sets.
             </p>
           </Banner>
         )}
@@ -144,7 +146,7 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
                 return (
                   <tr key={f + i} className="group">
                     <td>
-                      <Icon icon={f.endsWith(".proto") ? "file_code" : "folder_code"} />
+                      <Icon icon={isProtoFile(f) ? "file_code" : "folder_code"} />
                     </td>
                     <td className="pl-1 font-mono text-sm" title={f}>
                       {parts.length > 3 && ".../"}
@@ -174,10 +176,19 @@ function GrpcProtoSelectionDialogWithRequest({ request }: Props & { request: Grp
             <Link href="https://github.com/grpc/grpc/blob/9aa3c5835a4ed6afae9455b63ed45c761d695bca/doc/server-reflection.md">
               Server Reflection
             </Link>{" "}
-            . Please manually add the <InlineCode>.proto</InlineCode> file to get started.
+            . Please manually add the <InlineCode>.proto</InlineCode> file or a compiled{" "}
+            <InlineCode>.binpb</InlineCode> descriptor set to get started.
           </Banner>
         )}
       </VStack>
     </VStack>
   );
 }
+
+/** Compiled `FileDescriptorSet`s, which are loaded as-is instead of compiled */
+const descriptorSetExtensions = ["binpb", "pb", "protoset", "desc"];
+
+function isProtoFile(path: string) {
+  const extension = path.split(".").pop()?.toLowerCase() ?? "";
+  return extension === "proto" || descriptorSetExtensions.includes(extension);
+}
@@ -8,7 +8,9 @@ import type { ReflectResponseService } from "../hooks/useGrpc";
 import { useHeadersTab } from "../hooks/useHeadersTab";
 import { useInheritedHeaders } from "../hooks/useInheritedHeaders";
 import { useRequestUpdateKey } from "../hooks/useRequestUpdateKey";
+import { showConfirm } from "../lib/confirm";
 import { resolvedModelName } from "../lib/resolvedModelName";
+import { CertificatesOverrideEditor } from "./CertificatesOverrideEditor";
 import { Button } from "./core/Button";
 import { CountBadge } from "./core/CountBadge";
 import { IconButton } from "./core/IconButton";
@@ -21,6 +23,7 @@ import { HeadersEditor } from "./HeadersEditor";
 import { HttpAuthenticationEditor } from "./HttpAuthenticationEditor";
 import { MarkdownEditor } from "./MarkdownEditor";
 import { countOverriddenSettings, ModelSettingsEditor } from "./ModelSettingsEditor";
+import { PreRequestStepsEditor } from "./PreRequestStepsEditor";
 import { UrlBar } from "./UrlBar";
 
 interface Props {
@@ -45,9 +48,13 @@ interface Props {
   services: ReflectResponseService[] | null;
 }
 
+/** Standard health checking service, which is added to every schema */
+const HEALTH_SERVICE = "grpc.health.v1.Health";
+
 const TAB_MESSAGE = "message";
 const TAB_METADATA = "metadata";
 const TAB_AUTH = "auth";
+const TAB_PRE_REQUEST = "pre_request";
 const TAB_SETTINGS = "settings";
 const TAB_DESCRIPTION = "description";
 
@@ -108,6 +115,30 @@ export function GrpcRequestPane({
     [activeRequest],
   );
 
+  const handleHealthCheck = useCallback(
+    async (method: "Check" | "Watch") => {
+      const isHealthRequest = activeRequest.service === HEALTH_SERVICE;
+      if (!isHealthRequest && activeRequest.message.trim() !== "") {
+        const confirmed = await showConfirm({
+          id: "grpc-health-check",
+          title: "Health Check",
+          description: `Replace the current message to call ${HEALTH_SERVICE}/${method}?`,
+          confirmText: "Replace",
+          color: "primary",
+        });
+        if (!confirmed) return;
+      }
+
+      await patchModel(activeRequest, {
+        service: HEALTH_SERVICE,
+        method,
+        message: isHealthRequest ? activeRequest.message : '{\n  "service": ""\n}',
+      });
+      onGo();
+    },
+    [activeRequest, onGo],
+  );
+
   const handleConnect = useCallback(async () => {
     if (activeRequest == null) return;
 
@@ -131,6 +162,11 @@ export function GrpcRequestPane({
       { value: TAB_MESSAGE, label: "Message" },
       ...metadataTab,
       ...authTab,
+      {
+        value: TAB_PRE_REQUEST,
+        label: "Before",
+        rightSlot: <CountBadge count={activeRequest.preRequestSteps.length} />,
+      },
       {
         value: TAB_SETTINGS,
         label: "Settings",
@@ -142,7 +178,13 @@ export function GrpcRequestPane({
         rightSlot: activeRequest.description && <CountBadge count={true} />,
       },
     ],
-    [activeRequest.description, authTab, metadataTab, numSettingsOverrides],
+    [
+      activeRequest.description,
+      activeRequest.preRequestSteps.length,
+      authTab,
+      metadataTab,
+      numSettingsOverrides,
+    ],
   );
 
   const handleMetadataChange = useCallback(
@@ -188,6 +230,18 @@ export function GrpcRequestPane({
               shortLabel: o.label,
             }))}
             itemsAfter={[
+              {
+                label: "Health Check",
+                type: "default",
+                leftSlot: <Icon size="sm" icon="badge_check" />,
+                onSelect: () => handleHealthCheck("Check"),
+              },
+              {
+                label: "Watch Health",
+                type: "default",
+                leftSlot: <Icon size="sm" icon="eye" />,
+                onSelect: () => handleHealthCheck("Watch"),
+              },
               {
                 label: "Refresh",
                 type: "default",
@@ -286,8 +340,18 @@ export function GrpcRequestPane({
             onChange={handleMetadataChange}
           />
         </TabContent>
+        <TabContent value={TAB_PRE_REQUEST}>
+          <PreRequestStepsEditor request={activeRequest} />
+        </TabContent>
         <TabContent value={TAB_SETTINGS}>
-          <ModelSettingsEditor model={activeRequest} />
+          <div className="space-y-8">
+            <ModelSettingsEditor model={activeRequest} />
+            <CertificatesOverrideEditor
+              caCertificates={activeRequest.settingCaCertificates}
+              clientCertificate={activeRequest.settingClientCertificate}
+              onChange={(patch) => patchModel(activeRequest, patch)}
+            />
+          </div>
         </TabContent>
         <TabContent value={TAB_DESCRIPTION}>
           <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
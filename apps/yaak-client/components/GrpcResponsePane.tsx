@@ -10,6 +10,7 @@ import {
   useGrpcEvents,
 } from "../hooks/usePinnedGrpcConnection";
 import { useStateWithDeps } from "../hooks/useStateWithDeps";
+import { isResponseDone } from "../lib/model_util";
 import { Button } from "./core/Button";
 import { Editor } from "./core/Editor/LazyEditor";
 import { EventDetailHeader, EventViewer } from "./core/EventViewer";
@@ -69,7 +70,12 @@ export function GrpcResponsePane({ style, methodType, activeRequest }: Props) {
     <HStack className="pl-3 mb-1 font-mono text-sm text-text-subtle overflow-x-auto hide-scrollbars">
       <HStack space={2}>
         <span className="whitespace-nowrap">{events.length} Messages</span>
-        {activeConnection.state !== "closed" && (
+        {activeConnection.responseEncoding != null && (
+          <span className="whitespace-nowrap" title="Compression used by the server">
+            {activeConnection.responseEncoding}
+          </span>
+        )}
+        {!isResponseDone(activeConnection) && (
           <LoadingIcon size="sm" className="text-text-subtlest" />
         )}
       </HStack>
@@ -209,7 +215,19 @@ function GrpcEventDetail({
           {event.error}
         </div>
       )}
-      <div className="py-2 h-full">
+      <div className="py-2 h-full overflow-y-auto space-y-3">
+        {event.errorDetails.length > 0 && (
+          <VStack space={2}>
+            {event.errorDetails.map((detail, i) => (
+              <div key={i}>
+                <div className="text-sm font-semibold">{detail.typeName}</div>
+                <pre className="select-text cursor-text text-xs font-mono whitespace-pre-wrap text-text-subtle">
+                  {detail.value != null ? JSON.stringify(detail.value, null, 2) : detail.raw}
+                </pre>
+              </div>
+            ))}
+          </VStack>
+        )}
         {Object.keys(event.metadata).length === 0 ? (
           <EmptyStateText>
             No {event.eventType === "connection_end" ? "trailers" : "metadata"}
@@ -29,6 +29,7 @@ import {
   BODY_TYPE_OTHER,
   BODY_TYPE_XML,
   getContentTypeFromHeaders,
+  isResponseDone,
 } from "../lib/model_util";
 import { prepareImportQuerystring } from "../lib/prepareImportQuerystring";
 import { resolvedModelName } from "../lib/resolvedModelName";
@@ -38,7 +39,7 @@ import { ConfirmLargeRequestBody } from "./ConfirmLargeRequestBody";
 import { CountBadge } from "./core/CountBadge";
 import type { GenericCompletionConfig } from "./core/Editor/genericCompletion";
 import { Editor } from "./core/Editor/LazyEditor";
-import { InlineCode } from "@yaakapp-internal/ui";
+import { Icon, InlineCode } from "@yaakapp-internal/ui";
 import type { Pair } from "./core/PairEditor";
 import { PlainInput } from "./core/PlainInput";
 import type { TabItem, TabsRef } from "./core/Tabs/Tabs";
@@ -47,11 +48,14 @@ import { EmptyStateText } from "./EmptyStateText";
 import { FormMultipartEditor } from "./FormMultipartEditor";
 import { FormUrlencodedEditor } from "./FormUrlencodedEditor";
 import { HeadersEditor } from "./HeadersEditor";
+import { AssertionsEditor } from "./AssertionsEditor";
 import { HttpAuthenticationEditor } from "./HttpAuthenticationEditor";
 import { JsonBodyEditor } from "./JsonBodyEditor";
 import { MarkdownEditor } from "./MarkdownEditor";
 import { RequestMethodDropdown } from "./RequestMethodDropdown";
+import { ResponseExtractionsEditor } from "./ResponseExtractionsEditor";
 import { countOverriddenSettings, ModelSettingsEditor } from "./ModelSettingsEditor";
+import { PreRequestStepsEditor } from "./PreRequestStepsEditor";
 import { UrlBar } from "./UrlBar";
 import { UrlParametersEditor } from "./UrlParameterEditor";
 
@@ -66,10 +70,21 @@ interface Props {
   activeRequest: HttpRequest;
 }
 
+const BODY_COMPRESSIONS = [
+  { label: "None", value: null },
+  { label: "Gzip", value: "gzip" },
+  { label: "Deflate", value: "deflate" },
+  { label: "Brotli", value: "br" },
+  { label: "Zstandard", value: "zstd" },
+];
+
 const TAB_BODY = "body";
 const TAB_PARAMS = "params";
 const TAB_HEADERS = "headers";
 const TAB_AUTH = "auth";
+const TAB_PRE_REQUEST = "pre_request";
+const TAB_EXTRACT = "extract";
+const TAB_TESTS = "tests";
 const TAB_SETTINGS = "settings";
 const TAB_DESCRIPTION = "description";
 const TABS_STORAGE_KEY = "http_request_tabs";
@@ -181,6 +196,25 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
             { label: "Binary File", value: BODY_TYPE_BINARY },
             { label: "No Body", shortLabel: "Body", value: BODY_TYPE_NONE },
           ],
+          itemsAfter: [
+            {
+              label: "Compression",
+              hidden: activeRequest.bodyType === BODY_TYPE_NONE,
+              leftSlot: <Icon icon="archive" />,
+              submenu: BODY_COMPRESSIONS.map((c) => ({
+                label: c.label,
+                leftSlot: (
+                  <Icon
+                    icon={(activeRequest.body?.compression ?? null) === c.value ? "check" : "empty"}
+                  />
+                ),
+                onSelect: () =>
+                  patchModel(activeRequest, {
+                    body: { ...activeRequest.body, compression: c.value ?? undefined },
+                  }),
+              })),
+            },
+          ],
           onChange: async (bodyType) => {
             if (bodyType === activeRequest.bodyType) return;
 
@@ -237,6 +271,21 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
       },
       ...headersTab,
       ...authTab,
+      {
+        value: TAB_PRE_REQUEST,
+        label: "Before",
+        rightSlot: <CountBadge count={activeRequest.preRequestSteps.length} />,
+      },
+      {
+        value: TAB_EXTRACT,
+        label: "Extract",
+        rightSlot: <CountBadge count={activeRequest.responseExtractions.length} />,
+      },
+      {
+        value: TAB_TESTS,
+        label: "Tests",
+        rightSlot: <CountBadge count={activeRequest.assertions.length} />,
+      },
       {
         value: TAB_SETTINGS,
         label: "Settings",
@@ -351,7 +400,7 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
               </div>
             }
             forceUpdateKey={updateKey}
-            isLoading={activeResponse != null && activeResponse.state !== "closed"}
+            isLoading={activeResponse != null && !isResponseDone(activeResponse)}
           />
           <Tabs
             ref={tabsRef}
@@ -381,6 +430,15 @@ export function HttpRequestPane({ style, fullHeight, className, activeRequest }:
                 onChange={(urlParameters) => patchModel(activeRequest, { urlParameters })}
               />
             </TabContent>
+            <TabContent value={TAB_PRE_REQUEST}>
+              <PreRequestStepsEditor request={activeRequest} />
+            </TabContent>
+            <TabContent value={TAB_EXTRACT}>
+              <ResponseExtractionsEditor request={activeRequest} />
+            </TabContent>
+            <TabContent value={TAB_TESTS}>
+              <AssertionsEditor request={activeRequest} />
+            </TabContent>
             <TabContent value={TAB_SETTINGS}>
               <ModelSettingsEditor model={activeRequest} />
             </TabContent>
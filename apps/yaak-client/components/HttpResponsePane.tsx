@@ -1,5 +1,6 @@
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
 import type { HttpResponse, HttpResponseEvent } from "@yaakapp-internal/models";
-import { Banner, HStack, Icon, LoadingIcon, VStack } from "@yaakapp-internal/ui";
+import { Banner, HStack, Icon, InlineCode, LoadingIcon, VStack } from "@yaakapp-internal/ui";
 import classNames from "classnames";
 import type { ComponentType, CSSProperties } from "react";
 import { lazy, Suspense, useMemo } from "react";
@@ -7,10 +8,12 @@ import { useCancelHttpResponse } from "../hooks/useCancelHttpResponse";
 import { useHttpResponseEvents } from "../hooks/useHttpResponseEvents";
 import { usePinnedHttpResponse } from "../hooks/usePinnedHttpResponse";
 import { useResponseBodyBytes, useResponseBodyText } from "../hooks/useResponseBodyText";
+import { type RequestViewMode, useRequestViewMode } from "../hooks/useRequestViewMode";
 import { useResponseViewMode } from "../hooks/useResponseViewMode";
 import { useTimelineViewMode } from "../hooks/useTimelineViewMode";
 import { getMimeTypeFromContentType } from "../lib/contentType";
-import { getContentTypeFromHeaders, getCookieCounts } from "../lib/model_util";
+import { getContentTypeFromHeaders, getCookieCounts, isResponseDone } from "../lib/model_util";
+import { revealInFinderText } from "../lib/reveal";
 import { ConfirmLargeResponse } from "./ConfirmLargeResponse";
 import { ConfirmLargeResponseRequest } from "./ConfirmLargeResponseRequest";
 import { Button } from "./core/Button";
@@ -26,10 +29,14 @@ import { Tooltip } from "./core/Tooltip";
 import { EmptyStateText } from "./EmptyStateText";
 import { ErrorBoundary } from "./ErrorBoundary";
 import { HttpResponseTimeline } from "./HttpResponseTimeline";
+import { HttpResponseTimings } from "./HttpResponseTimings";
 import { RecentHttpResponsesDropdown } from "./RecentHttpResponsesDropdown";
 import { RequestBodyViewer } from "./RequestBodyViewer";
+import { RequestRawViewer } from "./RequestRawViewer";
+import { ResponseAssertions } from "./ResponseAssertions";
 import { ResponseCookies } from "./ResponseCookies";
 import { ResponseHeaders } from "./ResponseHeaders";
+import { ResponseTls } from "./ResponseTls";
 import { AudioViewer } from "./responseViewers/AudioViewer";
 import { CsvViewer } from "./responseViewers/CsvViewer";
 import { EventStreamViewer } from "./responseViewers/EventStreamViewer";
@@ -54,8 +61,10 @@ const TAB_REQUEST = "request";
 const TAB_HEADERS = "headers";
 const TAB_COOKIES = "cookies";
 const TAB_TIMELINE = "timeline";
+const TAB_TESTS = "tests";
+const TAB_TLS = "tls";
 
-export type TimelineViewMode = "timeline" | "text";
+export type TimelineViewMode = "timeline" | "text" | "timings";
 
 interface RedirectDropWarning {
   droppedBodyCount: number;
@@ -66,6 +75,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
   const { activeResponse, setPinnedResponseId, responses } = usePinnedHttpResponse(activeRequestId);
   const [viewMode, setViewMode] = useResponseViewMode(activeResponse?.requestId);
   const [timelineViewMode, setTimelineViewMode] = useTimelineViewMode();
+  const [requestViewMode, setRequestViewMode] = useRequestViewMode();
   const contentType = getContentTypeFromHeaders(activeResponse?.headers ?? null);
   const mimeType = contentType == null ? null : getMimeTypeFromContentType(contentType).essence;
 
@@ -75,7 +85,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
     [responseEvents.data],
   );
   const shouldShowRedirectDropWarning =
-    activeResponse?.state === "closed" && redirectDropWarning != null;
+    activeResponse != null && isResponseDone(activeResponse) && redirectDropWarning != null;
 
   const cookieCounts = useMemo(() => getCookieCounts(responseEvents.data), [responseEvents.data]);
 
@@ -100,6 +110,14 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
         label: "Request",
         rightSlot:
           (activeResponse?.requestContentLength ?? 0) > 0 ? <CountBadge count={true} /> : null,
+        options: {
+          value: requestViewMode,
+          onChange: (v) => setRequestViewMode((v as RequestViewMode) ?? "body"),
+          items: [
+            { label: "Request", value: "body" },
+            { label: "Request (Raw)", shortLabel: "Raw", value: "raw" },
+          ],
+        },
       },
       {
         value: TAB_HEADERS,
@@ -120,6 +138,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
             <CountBadge count={cookieCounts.sent} count2={cookieCounts.received} showZero />
           ) : null,
       },
+      ...(activeResponse?.tls != null ? [{ value: TAB_TLS, label: "TLS" }] : []),
       {
         value: TAB_TIMELINE,
         rightSlot: <CountBadge count={responseEvents.data?.length ?? 0} />,
@@ -129,14 +148,29 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
           items: [
             { label: "Timeline", value: "timeline" },
             { label: "Timeline (Text)", shortLabel: "Timeline", value: "text" },
+            { label: "Timings", value: "timings" },
           ],
         },
       },
+      {
+        value: TAB_TESTS,
+        label: "Tests",
+        rightSlot: (activeResponse?.assertionResults.length ?? 0) > 0 && (
+          <CountBadge
+            count={activeResponse?.assertionResults.filter((r) => r.passed).length ?? 0}
+            count2={activeResponse?.assertionResults.length ?? 0}
+            color={activeResponse?.assertionResults.every((r) => r.passed) ? "success" : "danger"}
+            showZero
+          />
+        ),
+      },
     ],
     [
+      activeResponse?.assertionResults,
       activeResponse?.headers,
       activeResponse?.requestContentLength,
       activeResponse?.requestHeaders.length,
+      activeResponse?.tls,
       cookieCounts.sent,
       cookieCounts.received,
       mimeType,
@@ -145,6 +179,8 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
       viewMode,
       timelineViewMode,
       setTimelineViewMode,
+      requestViewMode,
+      setRequestViewMode,
     ],
   );
 
@@ -181,7 +217,7 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
                 )}
               >
                 <HStack space={2} className="w-full flex-shrink-0">
-                  {activeResponse.state !== "closed" && <LoadingIcon size="sm" />}
+                  {!isResponseDone(activeResponse) && <LoadingIcon size="sm" />}
                   <HttpStatusTag showReason response={activeResponse} />
                   <span>&bull;</span>
                   <HttpResponseDurationTag response={activeResponse} />
@@ -265,52 +301,72 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
               <TabContent value={TAB_BODY}>
                 <ErrorBoundary name="Http Response Viewer">
                   <Suspense>
-                    <ConfirmLargeResponse response={activeResponse}>
-                      {activeResponse.state === "initialized" ? (
-                        <EmptyStateText>
-                          <VStack space={3}>
-                            <HStack space={3}>
-                              <LoadingIcon className="text-text-subtlest" />
-                              Sending Request
-                            </HStack>
-                            <Button size="sm" variant="border" onClick={() => cancel.mutate()}>
-                              Cancel
-                            </Button>
-                          </VStack>
-                        </EmptyStateText>
-                      ) : activeResponse.state === "closed" &&
-                        (activeResponse.contentLength ?? 0) === 0 ? (
-                        <EmptyStateText>Empty</EmptyStateText>
-                      ) : mimeType?.match(/^text\/event-stream/i) && viewMode === "pretty" ? (
-                        <EventStreamViewer response={activeResponse} />
-                      ) : mimeType?.match(/^image\/svg/) ? (
-                        <HttpSvgViewer response={activeResponse} />
-                      ) : mimeType?.match(/^image/i) ? (
-                        <EnsureCompleteResponse response={activeResponse} Component={ImageViewer} />
-                      ) : mimeType?.match(/^audio/i) ? (
-                        <EnsureCompleteResponse response={activeResponse} Component={AudioViewer} />
-                      ) : mimeType?.match(/^video/i) ? (
-                        <EnsureCompleteResponse response={activeResponse} Component={VideoViewer} />
-                      ) : mimeType?.match(/^multipart/i) && viewMode === "pretty" ? (
-                        <HttpMultipartViewer response={activeResponse} />
-                      ) : mimeType?.match(/pdf/i) ? (
-                        <EnsureCompleteResponse response={activeResponse} Component={PdfViewer} />
-                      ) : mimeType?.match(/csv|tab-separated/i) && viewMode === "pretty" ? (
-                        <HttpCsvViewer className="pb-2" response={activeResponse} />
-                      ) : (
-                        <HTMLOrTextViewer
-                          textViewerClassName="-mr-2 bg-surface" // Pull to the right
-                          response={activeResponse}
-                          pretty={viewMode === "pretty"}
-                        />
-                      )}
-                    </ConfirmLargeResponse>
+                    {activeResponse.downloadPath != null ? (
+                      <DownloadedResponse response={activeResponse} />
+                    ) : (
+                      <ConfirmLargeResponse response={activeResponse}>
+                        {activeResponse.state === "initialized" ? (
+                          <EmptyStateText>
+                            <VStack space={3}>
+                              <HStack space={3}>
+                                <LoadingIcon className="text-text-subtlest" />
+                                Sending Request
+                              </HStack>
+                              <Button size="sm" variant="border" onClick={() => cancel.mutate()}>
+                                Cancel
+                              </Button>
+                            </VStack>
+                          </EmptyStateText>
+                        ) : isResponseDone(activeResponse) &&
+                          (activeResponse.contentLength ?? 0) === 0 ? (
+                          <EmptyStateText>Empty</EmptyStateText>
+                        ) : (mimeType?.match(/^text\/event-stream/i) ||
+                            // GraphQL subscriptions are stored as an event stream of results
+                            activeResponse.status === 101) &&
+                          viewMode === "pretty" ? (
+                          <EventStreamViewer response={activeResponse} />
+                        ) : mimeType?.match(/^image\/svg/) ? (
+                          <HttpSvgViewer response={activeResponse} />
+                        ) : mimeType?.match(/^image/i) ? (
+                          <EnsureCompleteResponse
+                            response={activeResponse}
+                            Component={ImageViewer}
+                          />
+                        ) : mimeType?.match(/^audio/i) ? (
+                          <EnsureCompleteResponse
+                            response={activeResponse}
+                            Component={AudioViewer}
+                          />
+                        ) : mimeType?.match(/^video/i) ? (
+                          <EnsureCompleteResponse
+                            response={activeResponse}
+                            Component={VideoViewer}
+                          />
+                        ) : mimeType?.match(/^multipart/i) && viewMode === "pretty" ? (
+                          <HttpMultipartViewer response={activeResponse} />
+                        ) : mimeType?.match(/pdf/i) ? (
+                          <EnsureCompleteResponse response={activeResponse} Component={PdfViewer} />
+                        ) : mimeType?.match(/csv|tab-separated/i) && viewMode === "pretty" ? (
+                          <HttpCsvViewer className="pb-2" response={activeResponse} />
+                        ) : (
+                          <HTMLOrTextViewer
+                            textViewerClassName="-mr-2 bg-surface" // Pull to the right
+                            response={activeResponse}
+                            pretty={viewMode === "pretty"}
+                          />
+                        )}
+                      </ConfirmLargeResponse>
+                    )}
                   </Suspense>
                 </ErrorBoundary>
               </TabContent>
               <TabContent value={TAB_REQUEST}>
                 <ConfirmLargeResponseRequest response={activeResponse}>
-                  <RequestBodyViewer response={activeResponse} />
+                  {requestViewMode === "raw" ? (
+                    <RequestRawViewer response={activeResponse} />
+                  ) : (
+                    <RequestBodyViewer response={activeResponse} />
+                  )}
                 </ConfirmLargeResponseRequest>
               </TabContent>
               <TabContent value={TAB_HEADERS}>
@@ -319,8 +375,18 @@ export function HttpResponsePane({ style, className, activeRequestId }: Props) {
               <TabContent value={TAB_COOKIES}>
                 <ResponseCookies response={activeResponse} />
               </TabContent>
+              <TabContent value={TAB_TLS}>
+                <ResponseTls response={activeResponse} />
+              </TabContent>
               <TabContent value={TAB_TIMELINE}>
-                <HttpResponseTimeline response={activeResponse} viewMode={timelineViewMode} />
+                {timelineViewMode === "timings" ? (
+                  <HttpResponseTimings response={activeResponse} />
+                ) : (
+                  <HttpResponseTimeline response={activeResponse} viewMode={timelineViewMode} />
+                )}
+              </TabContent>
+              <TabContent value={TAB_TESTS}>
+                <ResponseAssertions response={activeResponse} />
               </TabContent>
             </Tabs>
           </div>
@@ -378,6 +444,31 @@ function getRedirectWarningLabel(warning: RedirectDropWarning): string {
   return "Dropped headers";
 }
 
+function DownloadedResponse({ response }: { response: HttpResponse }) {
+  const downloadPath = response.downloadPath ?? "";
+  return (
+    <EmptyStateText>
+      <VStack space={3} className="items-center">
+        {isResponseDone(response) ? (
+          <span>
+            Downloaded to <InlineCode>{downloadPath}</InlineCode>
+          </span>
+        ) : (
+          <HStack space={3}>
+            <LoadingIcon className="text-text-subtlest" />
+            Downloading to <InlineCode>{downloadPath}</InlineCode>
+          </HStack>
+        )}
+        {isResponseDone(response) && (
+          <Button size="sm" variant="border" onClick={() => revealItemInDir(downloadPath)}>
+            {revealInFinderText}
+          </Button>
+        )}
+      </VStack>
+    </EmptyStateText>
+  );
+}
+
 function EnsureCompleteResponse({
   response,
   Component,
@@ -390,7 +481,7 @@ function EnsureCompleteResponse({
   }
 
   // Wait until the response has been fully-downloaded
-  if (response.state !== "closed") {
+  if (!isResponseDone(response)) {
     return (
       <EmptyStateText>
         <LoadingIcon />
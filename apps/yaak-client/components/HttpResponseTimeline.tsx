@@ -81,6 +81,11 @@ function Inner({ response, viewMode }: Props) {
   );
 }
 
+function formatTransferProgress(bytes: bigint, total: bigint | null): string {
+  if (total == null) return formatBytes(Number(bytes));
+  return `${formatBytes(Number(bytes))} of ${formatBytes(Number(total))}`;
+}
+
 function formatBytes(bytes: number): string {
   if (bytes < 1024) return `${bytes} B`;
   if (bytes < 1024 * 1024) return `${(bytes / 1024).toFixed(1)} KB`;
@@ -131,6 +136,16 @@ function EventDetails({
         return "Data Received";
       case "dns_resolved":
         return e.overridden ? "DNS Override" : "DNS Resolution";
+      case "connected":
+        return "Connection";
+      case "server_sent_event":
+        return "Server-Sent Event";
+      case "retry":
+        return "Retry";
+      case "download_progress":
+        return "Download";
+      case "upload_progress":
+        return "Upload";
       default:
         return label;
     }
@@ -247,6 +262,33 @@ function EventDetails({
       );
     }
 
+    // Server-sent events - show event metadata and data
+    if (e.type === "server_sent_event") {
+      return (
+        <KeyValueRows>
+          <KeyValueRow label="Event">{e.event_type}</KeyValueRow>
+          {e.id != null ? <KeyValueRow label="ID">{e.id}</KeyValueRow> : null}
+          {e.retry != null ? (
+            <KeyValueRow label="Retry">{`${String(e.retry)}ms`}</KeyValueRow>
+          ) : null}
+          <KeyValueRow label="Data">
+            <span className="font-mono whitespace-pre-wrap">{e.data}</span>
+          </KeyValueRow>
+        </KeyValueRows>
+      );
+    }
+
+    // Retries - show why the attempt failed and how long until the next one
+    if (e.type === "retry") {
+      return (
+        <KeyValueRows>
+          <KeyValueRow label="Reason">{e.reason}</KeyValueRow>
+          <KeyValueRow label="Attempt">{`${e.attempt} of ${e.max_attempts}`}</KeyValueRow>
+          <KeyValueRow label="Delay">{`${String(e.delay)}ms`}</KeyValueRow>
+        </KeyValueRows>
+      );
+    }
+
     // Default - use summary
     const { summary } = getEventDisplay(event.event);
     return <div className="font-mono text-editor">{summary}</div>;
@@ -313,6 +355,25 @@ function getEventTextParts(event: HttpResponseEventData): EventTextParts {
         prefix: "*",
         text: `DNS resolved ${event.hostname} to ${event.addresses.join(", ")} (${event.duration}ms)`,
       };
+    case "connected":
+      return { prefix: "*", text: `Connection established (${event.duration}ms)` };
+    case "server_sent_event":
+      return { prefix: "<", text: `event: ${event.event_type}\ndata: ${event.data}` };
+    case "retry":
+      return {
+        prefix: "*",
+        text: `Retrying in ${event.delay}ms after ${event.reason} (attempt ${event.attempt} of ${event.max_attempts})`,
+      };
+    case "download_progress":
+      return {
+        prefix: "*",
+        text: `Downloaded ${formatTransferProgress(event.bytes, event.total)}`,
+      };
+    case "upload_progress":
+      return {
+        prefix: "*",
+        text: `Uploaded ${formatTransferProgress(event.bytes, event.total)}`,
+      };
     default:
       return { prefix: "*", text: "[unknown event]" };
   }
@@ -455,6 +516,41 @@ function getEventDisplay(event: HttpResponseEventData): EventDisplay {
           ? `${event.hostname} → ${event.addresses.join(", ")} (overridden)`
           : `${event.hostname} → ${event.addresses.join(", ")} (${event.duration}ms)`,
       };
+    case "connected":
+      return {
+        icon: "plug",
+        color: "secondary",
+        label: "Connect",
+        summary: `Connection established (${event.duration}ms)`,
+      };
+    case "server_sent_event":
+      return {
+        icon: "arrow_big_down_dash",
+        color: "info",
+        label: "Event",
+        summary: `${event.event_type}: ${event.data}`,
+      };
+    case "retry":
+      return {
+        icon: "refresh",
+        color: "warning",
+        label: "Retry",
+        summary: `Attempt ${event.attempt} of ${event.max_attempts} in ${event.delay}ms (${event.reason})`,
+      };
+    case "download_progress":
+      return {
+        icon: "download",
+        color: "info",
+        label: "Download",
+        summary: formatTransferProgress(event.bytes, event.total),
+      };
+    case "upload_progress":
+      return {
+        icon: "upload",
+        color: "info",
+        label: "Upload",
+        summary: formatTransferProgress(event.bytes, event.total),
+      };
     default:
       return {
         icon: "info",
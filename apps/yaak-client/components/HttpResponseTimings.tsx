@@ -0,0 +1,94 @@
+import type { HttpResponse } from "@yaakapp-internal/models";
+import classNames from "classnames";
+import { KeyValueRow, KeyValueRows } from "./core/KeyValueRow";
+
+interface Props {
+  response: HttpResponse;
+}
+
+interface Phase {
+  label: string;
+  description: string;
+  duration: number;
+  className: string;
+}
+
+export function HttpResponseTimings({ response }: Props) {
+  const phases = getResponsePhases(response);
+  const total = Math.max(response.elapsed, 1);
+
+  let offset = 0;
+  return (
+    <div className="overflow-auto h-full pb-4">
+      <KeyValueRows>
+        {phases.map((phase) => {
+          const start = offset;
+          offset += phase.duration;
+          return (
+            <KeyValueRow key={phase.label} labelColor="info" label={phase.label}>
+              <div className="flex items-center gap-3" title={phase.description}>
+                <div className="relative h-2 flex-1 min-w-24">
+                  {phase.duration > 0 && (
+                    <div
+                      className={classNames("absolute h-full rounded-sm", phase.className)}
+                      style={{
+                        left: `${(start / total) * 100}%`,
+                        width: `max(${(phase.duration / total) * 100}%, 2px)`,
+                      }}
+                    />
+                  )}
+                </div>
+                <span className="w-16 text-right">{phase.duration} ms</span>
+              </div>
+            </KeyValueRow>
+          );
+        })}
+        <KeyValueRow labelColor="info" label="Total">
+          <div className="text-right">{response.elapsed} ms</div>
+        </KeyValueRow>
+      </KeyValueRows>
+    </div>
+  );
+}
+
+/** Split a response's elapsed time into consecutive phases, in the order they happened */
+function getResponsePhases(response: HttpResponse): Phase[] {
+  const dns = Math.max(response.elapsedDns, 0);
+  const connect = Math.max(response.elapsedConnect, 0);
+  const send = Math.max(response.elapsedSend, 0);
+  const wait = Math.max(response.elapsedHeaders - dns - connect - send, 0);
+  const download = Math.max(response.elapsed - response.elapsedHeaders, 0);
+
+  return [
+    {
+      label: "DNS Lookup",
+      description: "Resolving the hostname",
+      duration: dns,
+      className: "bg-notice",
+    },
+    {
+      label: "Connect",
+      description: "Opening the connection, including the TLS handshake and any proxy setup",
+      duration: connect,
+      className: "bg-warning",
+    },
+    {
+      label: "Send",
+      description: "Writing the request body",
+      duration: send,
+      className: "bg-primary",
+    },
+    {
+      label: "Waiting",
+      description: "Waiting for the first byte of the response",
+      duration: wait,
+      className: "bg-info",
+    },
+    {
+      label: "Download",
+      description: "Receiving the response body",
+      duration: download,
+      className: "bg-success",
+    },
+  ];
+}
@@ -0,0 +1,103 @@
+import { useQuery } from "@tanstack/react-query";
+import { HStack, VStack } from "@yaakapp-internal/ui";
+import { useState } from "react";
+import { copyToClipboard } from "../lib/copy";
+import { queryClient } from "../lib/queryClient";
+import { invokeCmd } from "../lib/tauri";
+import { Button } from "./core/Button";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+
+interface Props {
+  workspaceId: string;
+}
+
+export function MockServerDialog({ workspaceId }: Props) {
+  const queryKey = ["mock_server_port", workspaceId];
+  const runningPort = useQuery({
+    queryKey,
+    queryFn: () => invokeCmd<number | null>("cmd_mock_server_port", { workspaceId }),
+  });
+  const [port, setPort] = useState<string>("");
+  const [allowedOrigins, setAllowedOrigins] = useState<string>("");
+  const [busy, setBusy] = useState<boolean>(false);
+
+  const handleToggle = async () => {
+    setBusy(true);
+    try {
+      if (runningPort.data != null) {
+        await invokeCmd("cmd_mock_server_stop", { workspaceId });
+        queryClient.setQueryData(queryKey, null);
+      } else {
+        const requested = Number.parseInt(port, 10);
+        const started = await invokeCmd<number>("cmd_mock_server_start", {
+          workspaceId,
+          port: Number.isNaN(requested) ? null : requested,
+          allowedOrigins: allowedOrigins
+            .split(",")
+            .map((o) => o.trim())
+            .filter((o) => o !== ""),
+        });
+        queryClient.setQueryData(queryKey, started);
+      }
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const url = runningPort.data == null ? null : `http://localhost:${runningPort.data}`;
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        The mock server answers each request with the last successful response saved for the
+        request matching its method and path. Path parameters like <code>:id</code> match any
+        value.
+      </div>
+      {url == null && (
+        <PlainInput
+          size="sm"
+          name="allowedOrigins"
+          label="Allowed origins"
+          placeholder="http://localhost:5173, http://localhost:3000"
+          help="Comma-separated origins of pages allowed to call the server from a browser"
+          defaultValue={allowedOrigins}
+          onChange={setAllowedOrigins}
+        />
+      )}
+      <HStack space={2} alignItems="end">
+        {url == null ? (
+          <PlainInput
+            size="sm"
+            type="number"
+            name="port"
+            label="Port"
+            placeholder="Any free port"
+            defaultValue={port}
+            onChange={setPort}
+          />
+        ) : (
+          <HStack space={1} className="flex-1 font-mono text-sm">
+            <span className="select-text">{url}</span>
+            <IconButton
+              size="xs"
+              iconSize="sm"
+              icon="copy"
+              title="Copy URL"
+              onClick={() => copyToClipboard(url)}
+            />
+          </HStack>
+        )}
+        <Button
+          size="sm"
+          color={url == null ? "primary" : "danger"}
+          isLoading={busy}
+          disabled={runningPort.isLoading}
+          onClick={handleToggle}
+        >
+          {url == null ? "Start" : "Stop"}
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
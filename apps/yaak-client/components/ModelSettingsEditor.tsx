@@ -1,9 +1,19 @@
 import type {
   Folder,
+  GrpcCompression,
+  GrpcProtocol,
   GrpcRequest,
   HttpRequest,
+  HttpVersion,
   InheritedBoolSetting,
+  InheritedHttpVersionSetting,
   InheritedIntSetting,
+  InheritedRetryPolicySetting,
+  MqttRequest,
+  RetryPolicy,
+  SocketIoSettings,
+  WebsocketProtocol,
+  WebsocketReconnectPolicy,
   WebsocketRequest,
   Workspace,
 } from "@yaakapp-internal/models";
@@ -12,7 +22,14 @@ import { useModelAncestors } from "../hooks/useModelAncestors";
 import {
   modelSupportsSetting,
   type RequestSettingDefinition,
+  SETTING_CONNECT_TIMEOUT,
+  SETTING_DECOMPRESS_RESPONSE,
   SETTING_FOLLOW_REDIRECTS,
+  SETTING_HTTP_VERSION,
+  SETTING_MAX_REDIRECTS,
+  SETTING_READ_TIMEOUT,
+  SETTING_REDIRECT_RESEND_BODY,
+  SETTING_REDIRECT_STRIP_AUTHORIZATION,
   SETTING_REQUEST_TIMEOUT,
   SETTING_SEND_COOKIES,
   SETTING_STORE_COOKIES,
@@ -20,10 +37,14 @@ import {
 } from "../lib/requestSettings";
 import { Checkbox } from "./core/Checkbox";
 import { PlainInput } from "./core/PlainInput";
+import type { RadioDropdownItem } from "./core/RadioDropdown";
 import {
   SettingOverrideRow,
   SettingRowBoolean,
   SettingRowNumber,
+  SettingRowSelect,
+  SettingRowText,
+  SettingSelectControl,
   SettingsList,
   SettingsSection,
 } from "./core/SettingRow";
@@ -33,19 +54,43 @@ interface Props {
   model: ModelWithSettings;
 }
 
-type ModelWithSettings = Workspace | Folder | HttpRequest | WebsocketRequest | GrpcRequest;
+type ModelWithSettings =
+  | Workspace
+  | Folder
+  | HttpRequest
+  | WebsocketRequest
+  | GrpcRequest
+  | MqttRequest;
 type ModelWithHttpSettings = Workspace | Folder | HttpRequest;
-type ModelWithTlsSettings = Workspace | Folder | HttpRequest | WebsocketRequest | GrpcRequest;
+type ModelWithTimeoutSettings = Workspace | Folder | HttpRequest | GrpcRequest;
+type ModelWithTlsSettings =
+  | Workspace
+  | Folder
+  | HttpRequest
+  | WebsocketRequest
+  | GrpcRequest
+  | MqttRequest;
 type ModelWithCookieSettings = Workspace | Folder | HttpRequest | WebsocketRequest;
+type ModelWithRetrySettings = Folder | HttpRequest;
 type BooleanSetting = boolean | InheritedBoolSetting;
 type IntegerSetting = number | InheritedIntSetting;
+type HttpVersionSetting = HttpVersion | InheritedHttpVersionSetting;
 type CookieSettingsPatch = {
   settingSendCookies?: ModelWithCookieSettings["settingSendCookies"];
   settingStoreCookies?: ModelWithCookieSettings["settingStoreCookies"];
 };
 type HttpSettingsPatch = {
+  settingDecompressResponse?: ModelWithHttpSettings["settingDecompressResponse"];
   settingFollowRedirects?: ModelWithHttpSettings["settingFollowRedirects"];
-  settingRequestTimeout?: ModelWithHttpSettings["settingRequestTimeout"];
+  settingHttpVersion?: ModelWithHttpSettings["settingHttpVersion"];
+  settingMaxRedirects?: ModelWithHttpSettings["settingMaxRedirects"];
+  settingRedirectResendBody?: ModelWithHttpSettings["settingRedirectResendBody"];
+  settingRedirectStripAuthorization?: ModelWithHttpSettings["settingRedirectStripAuthorization"];
+};
+type TimeoutSettingsPatch = {
+  settingRequestTimeout?: ModelWithTimeoutSettings["settingRequestTimeout"];
+  settingConnectTimeout?: ModelWithTimeoutSettings["settingConnectTimeout"];
+  settingReadTimeout?: ModelWithTimeoutSettings["settingReadTimeout"];
 };
 type TlsSettingsPatch = {
   settingValidateCertificates?: ModelWithTlsSettings["settingValidateCertificates"];
@@ -54,14 +99,16 @@ type TlsSettingsPatch = {
 export function ModelSettingsEditor({ model, showSectionTitles = false }: Props) {
   const ancestors = useModelAncestors(model);
   const supportsHttpSettings = modelSupportsHttpSettings(model);
+  const supportsTimeoutSettings = modelSupportsTimeoutSettings(model);
   const supportsCookieSettings = modelSupportsCookieSettings(model);
   const supportsTlsSettings = modelSupportsTlsSettings(model);
+  const supportsRetrySettings = modelSupportsRetrySettings(model);
 
   return (
     <SettingsList className="space-y-8">
       {supportsTlsSettings && (
         <SettingsSection title={showSectionTitles ? "Requests" : null}>
-          {supportsHttpSettings && (
+          {supportsTimeoutSettings && (
             <IntegerSettingRow
               settingDefinition={SETTING_REQUEST_TIMEOUT}
               setting={model.settingRequestTimeout}
@@ -71,12 +118,44 @@ export function ModelSettingsEditor({ model, showSectionTitles = false }: Props)
                 model.settingRequestTimeout,
               )}
               onChange={(settingRequestTimeout) =>
-                patchHttpSettings(model, {
+                patchTimeoutSettings(model, {
                   settingRequestTimeout,
                 })
               }
             />
           )}
+          {supportsTimeoutSettings && (
+            <IntegerSettingRow
+              settingDefinition={SETTING_CONNECT_TIMEOUT}
+              setting={model.settingConnectTimeout}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_CONNECT_TIMEOUT.modelKey,
+                model.settingConnectTimeout,
+              )}
+              onChange={(settingConnectTimeout) =>
+                patchTimeoutSettings(model, {
+                  settingConnectTimeout,
+                })
+              }
+            />
+          )}
+          {supportsTimeoutSettings && (
+            <IntegerSettingRow
+              settingDefinition={SETTING_READ_TIMEOUT}
+              setting={model.settingReadTimeout}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_READ_TIMEOUT.modelKey,
+                model.settingReadTimeout,
+              )}
+              onChange={(settingReadTimeout) =>
+                patchTimeoutSettings(model, {
+                  settingReadTimeout,
+                })
+              }
+            />
+          )}
           <BooleanSettingRow
             settingDefinition={SETTING_VALIDATE_CERTIFICATES}
             setting={model.settingValidateCertificates}
@@ -107,6 +186,95 @@ export function ModelSettingsEditor({ model, showSectionTitles = false }: Props)
               }
             />
           )}
+          {supportsHttpSettings && (
+            <IntegerSettingRow
+              settingDefinition={SETTING_MAX_REDIRECTS}
+              setting={model.settingMaxRedirects}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_MAX_REDIRECTS.modelKey,
+                model.settingMaxRedirects,
+              )}
+              onChange={(settingMaxRedirects) =>
+                patchHttpSettings(model, {
+                  settingMaxRedirects,
+                })
+              }
+            />
+          )}
+          {supportsHttpSettings && (
+            <BooleanSettingRow
+              settingDefinition={SETTING_REDIRECT_RESEND_BODY}
+              setting={model.settingRedirectResendBody}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_REDIRECT_RESEND_BODY.modelKey,
+                model.settingRedirectResendBody,
+              )}
+              onChange={(settingRedirectResendBody) =>
+                patchHttpSettings(model, {
+                  settingRedirectResendBody,
+                })
+              }
+            />
+          )}
+          {supportsHttpSettings && (
+            <BooleanSettingRow
+              settingDefinition={SETTING_REDIRECT_STRIP_AUTHORIZATION}
+              setting={model.settingRedirectStripAuthorization}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_REDIRECT_STRIP_AUTHORIZATION.modelKey,
+                model.settingRedirectStripAuthorization,
+              )}
+              onChange={(settingRedirectStripAuthorization) =>
+                patchHttpSettings(model, {
+                  settingRedirectStripAuthorization,
+                })
+              }
+            />
+          )}
+          {supportsHttpSettings && (
+            <HttpVersionSettingRow
+              setting={model.settingHttpVersion}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_HTTP_VERSION.modelKey,
+                model.settingHttpVersion,
+              )}
+              onChange={(settingHttpVersion) =>
+                patchHttpSettings(model, {
+                  settingHttpVersion,
+                })
+              }
+            />
+          )}
+          {supportsHttpSettings && (
+            <BooleanSettingRow
+              settingDefinition={SETTING_DECOMPRESS_RESPONSE}
+              setting={model.settingDecompressResponse}
+              inheritedValue={resolveInheritedValue(
+                ancestors,
+                SETTING_DECOMPRESS_RESPONSE.modelKey,
+                model.settingDecompressResponse,
+              )}
+              onChange={(settingDecompressResponse) =>
+                patchHttpSettings(model, {
+                  settingDecompressResponse,
+                })
+              }
+            />
+          )}
+          {model.model === "http_request" && (
+            <SettingRowText
+              name="unixSocket"
+              title="Unix socket"
+              description="Send the request over this Unix domain socket instead of TCP. The URL still sets the Host header and path."
+              placeholder="/var/run/docker.sock"
+              value={model.unixSocket ?? ""}
+              onChange={(unixSocket) => patchModel(model, { unixSocket: unixSocket || null })}
+            />
+          )}
         </SettingsSection>
       )}
       {supportsCookieSettings && (
@@ -141,12 +309,48 @@ export function ModelSettingsEditor({ model, showSectionTitles = false }: Props)
           />
         </SettingsSection>
       )}
+      {model.model === "grpc_request" && (
+        <SettingsSection title="Protocol">
+          <SettingRowSelect<GrpcProtocol>
+            name="protocol"
+            title="Protocol"
+            description="Use gRPC-Web for servers behind gateways like Envoy, or Connect for Buf-based services. gRPC-Web only supports unary and server streaming methods, and Connect only supports unary methods."
+            value={model.protocol}
+            options={GRPC_PROTOCOL_OPTIONS}
+            onChange={(protocol) => patchModel(model, { protocol })}
+          />
+        </SettingsSection>
+      )}
+      {model.model === "grpc_request" && (
+        <SettingsSection title="Compression">
+          <GrpcCompressionSettingRows request={model} />
+        </SettingsSection>
+      )}
+      {model.model === "websocket_request" && (
+        <SettingsSection title="Protocol">
+          <WebsocketProtocolSettingRows request={model} />
+        </SettingsSection>
+      )}
+      {model.model === "websocket_request" && (
+        <SettingsSection title="Reconnect">
+          <WebsocketReconnectSettingRows request={model} />
+        </SettingsSection>
+      )}
+      {supportsRetrySettings && (
+        <SettingsSection title="Retries">
+          <RetryPolicySettingRows
+            setting={model.settingRetryPolicy}
+            inheritedValue={resolveInheritedRetryPolicy(ancestors)}
+            onChange={(settingRetryPolicy) => patchRetrySettings(model, settingRetryPolicy)}
+          />
+        </SettingsSection>
+      )}
     </SettingsList>
   );
 }
 
 export function countOverriddenSettings(model: ModelWithSettings) {
-  const settings: (BooleanSetting | IntegerSetting)[] = [];
+  const settings: (BooleanSetting | IntegerSetting | HttpVersionSetting)[] = [];
 
   if (modelSupportsCookieSettings(model)) {
     settings.push(model.settingSendCookies, model.settingStoreCookies);
@@ -154,12 +358,32 @@ export function countOverriddenSettings(model: ModelWithSettings) {
 
   settings.push(model.settingValidateCertificates);
 
+  if (modelSupportsTimeoutSettings(model)) {
+    settings.push(
+      model.settingRequestTimeout,
+      model.settingConnectTimeout,
+      model.settingReadTimeout,
+    );
+  }
+
   if (modelSupportsHttpSettings(model)) {
-    settings.push(model.settingFollowRedirects, model.settingRequestTimeout);
+    settings.push(
+      model.settingFollowRedirects,
+      model.settingMaxRedirects,
+      model.settingRedirectResendBody,
+      model.settingRedirectStripAuthorization,
+      model.settingHttpVersion,
+      model.settingDecompressResponse,
+    );
   }
 
-  return settings.filter((setting) => isInheritedSetting(setting) && setting.enabled === true)
-    .length;
+  const retryOverridden = modelSupportsRetrySettings(model) && model.settingRetryPolicy.enabled;
+  const reconnectEnabled = model.model === "websocket_request" && model.reconnect.enabled;
+  return (
+    settings.filter((setting) => isInheritedSetting(setting) && setting.enabled === true).length +
+    (retryOverridden ? 1 : 0) +
+    (reconnectEnabled ? 1 : 0)
+  );
 }
 
 function patchCookieSettings(model: ModelWithCookieSettings, patch: Partial<CookieSettingsPatch>) {
@@ -177,16 +401,41 @@ function patchHttpSettings(model: ModelWithHttpSettings, patch: Partial<HttpSett
   return patchModel(model, patch as Partial<HttpRequest>);
 }
 
+function patchTimeoutSettings(
+  model: ModelWithTimeoutSettings,
+  patch: Partial<TimeoutSettingsPatch>,
+) {
+  if (model.model === "workspace") return patchModel(model, patch as Partial<Workspace>);
+  if (model.model === "folder") return patchModel(model, patch as Partial<Folder>);
+  if (model.model === "http_request") return patchModel(model, patch as Partial<HttpRequest>);
+  return patchModel(model, patch as Partial<GrpcRequest>);
+}
+
 function patchTlsSettings(model: ModelWithTlsSettings, patch: Partial<TlsSettingsPatch>) {
   if (model.model === "workspace") return patchModel(model, patch as Partial<Workspace>);
   if (model.model === "folder") return patchModel(model, patch as Partial<Folder>);
   if (model.model === "http_request") return patchModel(model, patch as Partial<HttpRequest>);
   if (model.model === "websocket_request")
     return patchModel(model, patch as Partial<WebsocketRequest>);
+  if (model.model === "mqtt_request") return patchModel(model, patch as Partial<MqttRequest>);
   return patchModel(model, patch as Partial<GrpcRequest>);
 }
 
+function patchRetrySettings(
+  model: ModelWithRetrySettings,
+  settingRetryPolicy: InheritedRetryPolicySetting,
+) {
+  if (model.model === "folder") return patchModel(model, { settingRetryPolicy });
+  return patchModel(model, { settingRetryPolicy });
+}
+
 function modelSupportsHttpSettings(model: ModelWithSettings): model is ModelWithHttpSettings {
+  return modelSupportsSetting(model, SETTING_FOLLOW_REDIRECTS);
+}
+
+function modelSupportsTimeoutSettings(
+  model: ModelWithSettings,
+): model is ModelWithTimeoutSettings {
   return modelSupportsSetting(model, SETTING_REQUEST_TIMEOUT);
 }
 
@@ -198,6 +447,10 @@ function modelSupportsTlsSettings(model: ModelWithSettings): model is ModelWithT
   return modelSupportsSetting(model, SETTING_VALIDATE_CERTIFICATES);
 }
 
+function modelSupportsRetrySettings(model: ModelWithSettings): model is ModelWithRetrySettings {
+  return model.model === "folder" || model.model === "http_request";
+}
+
 function BooleanSettingRow({
   inheritedValue,
   setting,
@@ -250,7 +503,7 @@ function IntegerSettingRow({
 }: {
   inheritedValue: number;
   setting: IntegerSetting;
-  settingDefinition: RequestSettingDefinition<"settingRequestTimeout">;
+  settingDefinition: RequestSettingDefinition<IntegerSettingKey>;
   onChange: (setting: IntegerSetting) => void;
 }) {
   const inherited = isInheritedSetting(setting);
@@ -300,6 +553,329 @@ function IntegerSettingRow({
   );
 }
 
+const DEFAULT_RETRY_POLICY: RetryPolicy = {
+  maxAttempts: 1,
+  retryOnServerError: true,
+  retryOnConnectionError: true,
+  retryOnTimeout: true,
+  initialDelay: 500,
+  maxDelay: 10000,
+  jitter: true,
+};
+
+const RETRY_POLICY_NUMBER_FIELDS: {
+  key: "maxAttempts" | "initialDelay" | "maxDelay";
+  title: string;
+  description: string;
+}[] = [
+  {
+    key: "maxAttempts",
+    title: "Max Attempts",
+    description: "Times to send the request, including the first. 1 disables retries.",
+  },
+  {
+    key: "initialDelay",
+    title: "Initial Delay",
+    description: "Milliseconds to wait before the first retry, doubled for every retry after it.",
+  },
+  {
+    key: "maxDelay",
+    title: "Max Delay",
+    description: "Upper bound in milliseconds for the delay between attempts.",
+  },
+];
+
+const RETRY_POLICY_BOOLEAN_FIELDS: {
+  key: "retryOnServerError" | "retryOnConnectionError" | "retryOnTimeout" | "jitter";
+  title: string;
+  description: string;
+}[] = [
+  {
+    key: "retryOnServerError",
+    title: "Retry on server errors",
+    description: "Retry when the server responds with a 5xx status.",
+  },
+  {
+    key: "retryOnConnectionError",
+    title: "Retry on connection errors",
+    description: "Retry when a connection to the server can't be established.",
+  },
+  {
+    key: "retryOnTimeout",
+    title: "Retry on timeouts",
+    description: "Retry when a connect, read, or request timeout elapses.",
+  },
+  {
+    key: "jitter",
+    title: "Jitter",
+    description: "Wait a random fraction of each delay so clients retrying together spread out.",
+  },
+];
+
+function RetryPolicySettingRows({
+  inheritedValue,
+  setting,
+  onChange,
+}: {
+  inheritedValue: RetryPolicy;
+  setting: InheritedRetryPolicySetting;
+  onChange: (setting: InheritedRetryPolicySetting) => void;
+}) {
+  const overridden = setting.enabled === true;
+  const value = overridden ? setting.value : inheritedValue;
+  const update = <K extends keyof RetryPolicy>(key: K, fieldValue: RetryPolicy[K]) =>
+    onChange({ enabled: true, value: { ...value, [key]: fieldValue } });
+  const reset = () => onChange({ ...setting, enabled: false });
+
+  return (
+    <>
+      {RETRY_POLICY_NUMBER_FIELDS.map(({ key, title, description }) => (
+        <SettingOverrideRow
+          key={key}
+          title={title}
+          description={description}
+          overridden={overridden}
+          onResetOverride={reset}
+        >
+          <PlainInput
+            hideLabel
+            name={`retryPolicy.${key}`}
+            label={title}
+            size="sm"
+            type="number"
+            placeholder={`${DEFAULT_RETRY_POLICY[key]}`}
+            defaultValue={`${value[key]}`}
+            containerClassName="!w-48"
+            validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+            onChange={(v) => update(key, Number.parseInt(v, 10) || 0)}
+          />
+        </SettingOverrideRow>
+      ))}
+      {RETRY_POLICY_BOOLEAN_FIELDS.map(({ key, title, description }) => (
+        <SettingOverrideRow
+          key={key}
+          title={title}
+          description={description}
+          overridden={overridden}
+          onResetOverride={reset}
+        >
+          <Checkbox
+            hideLabel
+            size="md"
+            title={title}
+            checked={value[key]}
+            onChange={(checked) => update(key, checked)}
+          />
+        </SettingOverrideRow>
+      ))}
+    </>
+  );
+}
+
+const GRPC_PROTOCOL_OPTIONS: { label: string; value: GrpcProtocol }[] = [
+  { label: "gRPC (HTTP/2)", value: "grpc" },
+  { label: "gRPC-Web (HTTP/1.1)", value: "grpc_web" },
+  { label: "Connect (JSON)", value: "connect_json" },
+  { label: "Connect (Protobuf)", value: "connect_proto" },
+];
+
+const GRPC_COMPRESSION_OPTIONS: { label: string; value: GrpcCompression }[] = [
+  { label: "gzip", value: "gzip" },
+  { label: "zstd", value: "zstd" },
+];
+
+function GrpcCompressionSettingRows({ request }: { request: GrpcRequest }) {
+  return (
+    <>
+      <SettingRowSelect<GrpcCompression | "none">
+        name="sendCompression"
+        title="Send compression"
+        description="Compress messages sent to the server."
+        value={request.sendCompression ?? "none"}
+        options={[{ label: "None", value: "none" }, ...GRPC_COMPRESSION_OPTIONS]}
+        onChange={(v) => patchModel(request, { sendCompression: v === "none" ? null : v })}
+      />
+      {GRPC_COMPRESSION_OPTIONS.map(({ label, value }) => (
+        <SettingRowBoolean
+          key={value}
+          title={`Accept ${label}`}
+          description={`Let the server compress the messages it sends back with ${label}.`}
+          checked={request.acceptCompression.includes(value)}
+          onChange={(checked) =>
+            patchModel(request, {
+              acceptCompression: checked
+                ? [...request.acceptCompression, value]
+                : request.acceptCompression.filter((c) => c !== value),
+            })
+          }
+        />
+      ))}
+    </>
+  );
+}
+
+const WEBSOCKET_PROTOCOL_OPTIONS: { label: string; value: WebsocketProtocol }[] = [
+  { label: "WebSocket", value: "websocket" },
+  { label: "Socket.IO", value: "socket_io" },
+];
+
+function WebsocketProtocolSettingRows({ request }: { request: WebsocketRequest }) {
+  const { socketIo } = request;
+  const update = (patch: Partial<SocketIoSettings>) =>
+    patchModel(request, { socketIo: { ...socketIo, ...patch } });
+
+  return (
+    <>
+      <SettingRowSelect<WebsocketProtocol>
+        name="protocol"
+        title="Protocol"
+        description="Use Socket.IO for servers built on it. The Engine.IO handshake, pings, and namespace connection are handled for you, and messages are sent as events."
+        value={request.protocol}
+        options={WEBSOCKET_PROTOCOL_OPTIONS}
+        onChange={(protocol) => patchModel(request, { protocol })}
+      />
+      {request.protocol === "socket_io" && (
+        <>
+          <SettingRowText
+            name="socketIo.namespace"
+            title="Namespace"
+            description="Namespace to connect to."
+            placeholder="/"
+            value={socketIo.namespace}
+            onChange={(namespace) => update({ namespace })}
+          />
+          <SettingRowText
+            name="socketIo.event"
+            title="Event name"
+            description="Event the message is emitted as. The message is sent as its JSON argument."
+            placeholder="message"
+            value={socketIo.event}
+            onChange={(event) => update({ event })}
+          />
+          <SettingRowBoolean
+            title="Request acknowledgements"
+            description="Ask the server to acknowledge each event. Acknowledgements show up in the message list."
+            checked={socketIo.ack}
+            onChange={(ack) => update({ ack })}
+          />
+          <SettingRowText
+            name="socketIo.auth"
+            title="Auth payload"
+            description="JSON sent when connecting to the namespace, available to the server as socket.handshake.auth."
+            placeholder='{"token": "..."}'
+            value={socketIo.auth}
+            onChange={(auth) => update({ auth })}
+          />
+        </>
+      )}
+    </>
+  );
+}
+
+function WebsocketReconnectSettingRows({ request }: { request: WebsocketRequest }) {
+  const { reconnect } = request;
+  const update = (patch: Partial<WebsocketReconnectPolicy>) =>
+    patchModel(request, { reconnect: { ...reconnect, ...patch } });
+
+  return (
+    <>
+      <SettingRowBoolean
+        title="Reconnect automatically"
+        description="Connect again when the connection drops without being closed, waiting twice as long after each failed attempt."
+        checked={reconnect.enabled}
+        onChange={(enabled) => update({ enabled })}
+      />
+      <SettingRowNumber
+        name="reconnect.maxAttempts"
+        title="Max attempts"
+        description="Attempts to make before giving up. Set to 0 to keep trying."
+        disabled={!reconnect.enabled}
+        value={reconnect.maxAttempts}
+        validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+        onChange={(maxAttempts) => update({ maxAttempts })}
+      />
+      <SettingRowNumber
+        name="reconnect.initialDelayMs"
+        title="Initial delay (ms)"
+        description="Time to wait before the first attempt."
+        disabled={!reconnect.enabled}
+        value={reconnect.initialDelayMs}
+        validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+        onChange={(initialDelayMs) => update({ initialDelayMs })}
+      />
+      <SettingRowNumber
+        name="reconnect.maxDelayMs"
+        title="Max delay (ms)"
+        description="Longest time to wait between attempts."
+        disabled={!reconnect.enabled}
+        value={reconnect.maxDelayMs}
+        validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+        onChange={(maxDelayMs) => update({ maxDelayMs })}
+      />
+      <SettingRowText
+        name="reconnect.handshakeMessage"
+        title="Handshake message"
+        description="Sent after connecting and again after every reconnect, like a login or subscribe message. Supports template tags."
+        disabled={!reconnect.enabled}
+        placeholder='{"type": "subscribe"}'
+        value={reconnect.handshakeMessage}
+        onChange={(handshakeMessage) => update({ handshakeMessage })}
+      />
+    </>
+  );
+}
+
+const HTTP_VERSION_OPTIONS: RadioDropdownItem<HttpVersion>[] = [
+  { label: "Auto", value: "auto" },
+  { label: "HTTP/1.1", value: "http1" },
+  { label: "HTTP/2", value: "http2" },
+  { label: "HTTP/3", value: "http3" },
+];
+
+function HttpVersionSettingRow({
+  inheritedValue,
+  setting,
+  onChange,
+}: {
+  inheritedValue: HttpVersion;
+  setting: HttpVersionSetting;
+  onChange: (setting: HttpVersionSetting) => void;
+}) {
+  const inherited = isInheritedSetting(setting);
+  const overridden = inherited ? setting.enabled === true : false;
+  const value = inherited ? (overridden ? setting.value : inheritedValue) : setting;
+
+  if (!inherited) {
+    return (
+      <SettingRowSelect
+        name={SETTING_HTTP_VERSION.modelKey}
+        title={SETTING_HTTP_VERSION.title}
+        description={SETTING_HTTP_VERSION.description}
+        value={value}
+        options={HTTP_VERSION_OPTIONS}
+        onChange={(value) => onChange(value)}
+      />
+    );
+  }
+
+  return (
+    <SettingOverrideRow
+      title={SETTING_HTTP_VERSION.title}
+      description={SETTING_HTTP_VERSION.description}
+      overridden={overridden}
+      onResetOverride={() => onChange({ ...setting, enabled: false })}
+    >
+      <SettingSelectControl
+        name={SETTING_HTTP_VERSION.modelKey}
+        label={SETTING_HTTP_VERSION.title}
+        value={value}
+        options={HTTP_VERSION_OPTIONS}
+        onChange={(value) => onChange({ ...setting, enabled: true, value })}
+      />
+    </SettingOverrideRow>
+  );
+}
+
 function isInheritedSetting<T>(
   setting: T | { enabled?: boolean; value: T },
 ): setting is { enabled?: boolean; value: T } {
@@ -308,9 +884,14 @@ function isInheritedSetting<T>(
 
 function resolveInheritedValue(
   ancestors: (Folder | Workspace)[],
-  key: "settingRequestTimeout",
+  key: IntegerSettingKey,
   fallback: IntegerSetting,
 ): number;
+function resolveInheritedValue(
+  ancestors: (Folder | Workspace)[],
+  key: "settingHttpVersion",
+  fallback: HttpVersionSetting,
+): HttpVersion;
 function resolveInheritedValue(
   ancestors: (Folder | Workspace)[],
   key: BooleanWorkspaceSettingKey,
@@ -319,10 +900,10 @@ function resolveInheritedValue(
 function resolveInheritedValue(
   ancestors: (Folder | Workspace)[],
   key: keyof WorkspaceSettings,
-  fallback: BooleanSetting | IntegerSetting,
+  fallback: BooleanSetting | IntegerSetting | HttpVersionSetting,
 ) {
   for (const ancestor of ancestors) {
-    const setting = ancestor[key] as BooleanSetting | IntegerSetting;
+    const setting = ancestor[key] as BooleanSetting | IntegerSetting | HttpVersionSetting;
     if (isInheritedSetting(setting)) {
       if (setting.enabled === true) {
         return setting.value;
@@ -335,13 +916,38 @@ function resolveInheritedValue(
   return isInheritedSetting(fallback) ? fallback.value : fallback;
 }
 
+function resolveInheritedRetryPolicy(ancestors: (Folder | Workspace)[]): RetryPolicy {
+  for (const ancestor of ancestors) {
+    if (ancestor.model === "folder" && ancestor.settingRetryPolicy.enabled === true) {
+      return ancestor.settingRetryPolicy.value;
+    }
+  }
+  return DEFAULT_RETRY_POLICY;
+}
+
 type WorkspaceSettings = Pick<
   Workspace,
+  | "settingConnectTimeout"
+  | "settingDecompressResponse"
   | "settingFollowRedirects"
+  | "settingHttpVersion"
+  | "settingMaxRedirects"
+  | "settingReadTimeout"
+  | "settingRedirectResendBody"
+  | "settingRedirectStripAuthorization"
   | "settingRequestTimeout"
   | "settingSendCookies"
   | "settingStoreCookies"
   | "settingValidateCertificates"
 >;
 
-type BooleanWorkspaceSettingKey = Exclude<keyof WorkspaceSettings, "settingRequestTimeout">;
+type IntegerSettingKey =
+  | "settingConnectTimeout"
+  | "settingMaxRedirects"
+  | "settingReadTimeout"
+  | "settingRequestTimeout";
+
+type BooleanWorkspaceSettingKey = Exclude<
+  keyof WorkspaceSettings,
+  "settingHttpVersion" | IntegerSettingKey
+>;
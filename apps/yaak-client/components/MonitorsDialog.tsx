@@ -0,0 +1,227 @@
+import type { Monitor, MonitorRun } from "@yaakapp-internal/models";
+import {
+  environmentsAtom,
+  getModel,
+  monitorRunsAtom,
+  monitorsAtom,
+  patchModel,
+} from "@yaakapp-internal/models";
+import {
+  HStack,
+  Icon,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import classNames from "classnames";
+import { formatDistanceToNowStrict } from "date-fns";
+import { useAtomValue } from "jotai";
+import { useState } from "react";
+import { deleteModelWithConfirm } from "../lib/deleteModelWithConfirm";
+import { fireAndForget } from "../lib/fireAndForget";
+import { resolvedModelName } from "../lib/resolvedModelName";
+import { invokeCmd } from "../lib/tauri";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+import { Select } from "./core/Select";
+import { EmptyStateText } from "./EmptyStateText";
+
+const INTERVAL_OPTIONS = [
+  { label: "Every minute", value: "60" },
+  { label: "Every 5 minutes", value: "300" },
+  { label: "Every 15 minutes", value: "900" },
+  { label: "Every 30 minutes", value: "1800" },
+  { label: "Every hour", value: "3600" },
+  { label: "Every 6 hours", value: "21600" },
+  { label: "Every day", value: "86400" },
+];
+
+export function MonitorsDialog() {
+  const monitors = useAtomValue(monitorsAtom);
+
+  if (monitors.length === 0) {
+    return (
+      <EmptyStateText>
+        Choose &ldquo;Monitor&rdquo; on a folder or HTTP request to send it on an interval
+      </EmptyStateText>
+    );
+  }
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        Monitors send their requests in the background while Yaak is open. A monitor is flagged
+        as down once enough runs in a row have failed.
+      </div>
+      <Table>
+        <TableHead>
+          <TableRow>
+            <TableHeaderCell className="w-8" />
+            <TableHeaderCell>Monitor</TableHeaderCell>
+            <TableHeaderCell>Environment</TableHeaderCell>
+            <TableHeaderCell>Interval</TableHeaderCell>
+            <TableHeaderCell>Down After</TableHeaderCell>
+            <TableHeaderCell>Recent Runs</TableHeaderCell>
+            <TableHeaderCell className="w-16" />
+          </TableRow>
+        </TableHead>
+        <TableBody>
+          {monitors.map((monitor) => (
+            <MonitorRow key={monitor.id} monitor={monitor} />
+          ))}
+        </TableBody>
+      </Table>
+    </VStack>
+  );
+}
+
+function MonitorRow({ monitor }: { monitor: Monitor }) {
+  const runs = useAtomValue(monitorRunsAtom).filter((r) => r.monitorId === monitor.id);
+  const environments = useAtomValue(environmentsAtom);
+  const [running, setRunning] = useState<boolean>(false);
+  const down = monitor.consecutiveFailures >= Math.max(1, monitor.failureThreshold);
+
+  const handleRunNow = async () => {
+    setRunning(true);
+    try {
+      await invokeCmd<MonitorRun>("cmd_run_monitor", { monitorId: monitor.id });
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  return (
+    <TableRow>
+      <TableCell>
+        <Checkbox
+          hideLabel
+          title={monitor.enabled ? "Pause monitor" : "Resume monitor"}
+          checked={monitor.enabled}
+          onChange={(enabled) => fireAndForget(patchModel(monitor, { enabled }))}
+        />
+      </TableCell>
+      <TableCell>
+        <HStack space={1.5} className="min-w-0">
+          <Icon
+            icon={down ? "alert_triangle" : "dot"}
+            color={down ? "danger" : runs[0]?.passed ? "success" : "secondary"}
+            title={
+              down
+                ? `Failed ${monitor.consecutiveFailures} times in a row`
+                : monitor.lastRunAt == null
+                  ? "Not run yet"
+                  : `Last ran ${formatDistanceToNowStrict(`${monitor.lastRunAt}Z`)} ago`
+            }
+          />
+          <span className={classNames("truncate", !monitor.enabled && "text-text-subtle")}>
+            {monitorName(monitor)}
+          </span>
+        </HStack>
+      </TableCell>
+      <TableCell>
+        <Select
+          hideLabel
+          size="sm"
+          name="environment"
+          label="Environment"
+          value={monitor.environmentId ?? ""}
+          onChange={(environmentId) =>
+            fireAndForget(patchModel(monitor, { environmentId: environmentId || null }))
+          }
+          options={[
+            { label: "No Environment", value: "" },
+            ...environments.map((e) => ({ label: e.name, value: e.id })),
+          ]}
+        />
+      </TableCell>
+      <TableCell>
+        <Select
+          hideLabel
+          size="sm"
+          name="interval"
+          label="Interval"
+          value={`${monitor.interval}`}
+          onChange={(interval) =>
+            fireAndForget(patchModel(monitor, { interval: Number.parseInt(interval, 10) }))
+          }
+          options={INTERVAL_OPTIONS}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          type="number"
+          name="failureThreshold"
+          label="Failures before down"
+          defaultValue={`${monitor.failureThreshold}`}
+          validate={(value) => Number.parseInt(value, 10) >= 1}
+          onChange={(value) =>
+            fireAndForget(
+              patchModel(monitor, {
+                failureThreshold: Math.max(1, Number.parseInt(value, 10) || 1),
+              }),
+            )
+          }
+        />
+      </TableCell>
+      <TableCell>
+        <HStack space={0.5}>
+          {[...runs].reverse().map((run) => (
+            <div
+              key={run.id}
+              title={runSummary(run)}
+              className={classNames(
+                "w-1.5 h-4 rounded-sm",
+                run.passed ? "bg-success" : "bg-danger",
+              )}
+            />
+          ))}
+        </HStack>
+      </TableCell>
+      <TableCell>
+        <HStack space={0.5}>
+          <IconButton
+            size="xs"
+            iconSize="sm"
+            icon="circle_play"
+            title="Run now"
+            spin={running}
+            disabled={running}
+            onClick={handleRunNow}
+          />
+          <IconButton
+            size="xs"
+            iconSize="sm"
+            icon="trash"
+            title="Delete monitor"
+            onClick={() => fireAndForget(deleteModelWithConfirm(monitor))}
+          />
+        </HStack>
+      </TableCell>
+    </TableRow>
+  );
+}
+
+function monitorName(monitor: Monitor): string {
+  if (monitor.name) return monitor.name;
+  if (monitor.requestId != null) {
+    return resolvedModelName(getModel("http_request", monitor.requestId));
+  }
+  if (monitor.folderId != null) return resolvedModelName(getModel("folder", monitor.folderId));
+  return "Monitor";
+}
+
+function runSummary(run: MonitorRun): string {
+  const failed = run.results.filter((r) => !r.passed);
+  const when = `${formatDistanceToNowStrict(`${run.createdAt}Z`)} ago`;
+  if (run.passed) return `Passed ${when} in ${run.elapsed} ms`;
+  if (run.results.length === 0) return `Failed ${when}: nothing to send`;
+  const reasons = failed.map((r) => r.error ?? `${r.requestName} (${r.status})`);
+  return `Failed ${when}: ${reasons.join(", ")}`;
+}
@@ -1,4 +1,10 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { patchModel, workspacesAtom } from "@yaakapp-internal/models";
 import { InlineCode, VStack } from "@yaakapp-internal/ui";
 import { useAtomValue } from "jotai";
@@ -12,7 +18,7 @@ import { Select } from "./core/Select";
 
 interface Props {
   activeWorkspaceId: string;
-  requests: (HttpRequest | GrpcRequest | WebsocketRequest)[];
+  requests: (HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest)[];
   onDone: () => void;
 }
 
@@ -0,0 +1,49 @@
+import type { MqttRequest } from "@yaakapp-internal/models";
+import classNames from "classnames";
+import { useAtomValue } from "jotai";
+import type { CSSProperties } from "react";
+
+import { SplitLayout } from "@yaakapp-internal/ui";
+import { activeWorkspaceAtom } from "../hooks/useActiveWorkspace";
+import { workspaceLayoutAtom } from "../lib/atoms";
+import { MqttRequestPane } from "./MqttRequestPane";
+import { MqttResponsePane } from "./MqttResponsePane";
+
+interface Props {
+  activeRequest: MqttRequest;
+  style: CSSProperties;
+}
+
+export function MqttRequestLayout({ activeRequest, style }: Props) {
+  const workspaceLayout = useAtomValue(workspaceLayoutAtom);
+  const activeWorkspace = useAtomValue(activeWorkspaceAtom);
+  const wsId = activeWorkspace?.id ?? "n/a";
+  return (
+    <SplitLayout
+      storageKey={`mqtt_layout::${wsId}`}
+      className="p-3 gap-1.5"
+      layout={workspaceLayout}
+      style={style}
+      firstSlot={({ orientation, style }) => (
+        <MqttRequestPane
+          style={style}
+          activeRequest={activeRequest}
+          fullHeight={orientation === "horizontal"}
+        />
+      )}
+      secondSlot={({ style }) => (
+        <div
+          style={style}
+          className={classNames(
+            "x-theme-responsePane",
+            "max-h-full h-full grid grid-rows-[minmax(0,1fr)] grid-cols-1",
+            "bg-surface rounded-md border border-border-subtle",
+            "shadow relative",
+          )}
+        >
+          <MqttResponsePane activeRequest={activeRequest} />
+        </div>
+      )}
+    />
+  );
+}
@@ -0,0 +1,270 @@
+import type { MqttRequest } from "@yaakapp-internal/models";
+import { patchModel } from "@yaakapp-internal/models";
+import { closeMqtt, connectMqtt, publishMqtt } from "@yaakapp-internal/mqtt";
+import { HStack, VStack } from "@yaakapp-internal/ui";
+import classNames from "classnames";
+import { useAtomValue } from "jotai";
+import type { CSSProperties } from "react";
+import { useCallback, useMemo } from "react";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { activeMqttConnectionAtom } from "../hooks/useMqttConnection";
+import { useRequestUpdateKey } from "../hooks/useRequestUpdateKey";
+import { languageFromContentType } from "../lib/contentType";
+import { resolvedModelName } from "../lib/resolvedModelName";
+import { Checkbox } from "./core/Checkbox";
+import { CountBadge } from "./core/CountBadge";
+import { Editor } from "./core/Editor/LazyEditor";
+import { IconButton } from "./core/IconButton";
+import { Input } from "./core/Input";
+import { PlainInput } from "./core/PlainInput";
+import { Select } from "./core/Select";
+import type { TabItem } from "./core/Tabs/Tabs";
+import { TabContent, Tabs } from "./core/Tabs/Tabs";
+import { MarkdownEditor } from "./MarkdownEditor";
+import { countOverriddenSettings, ModelSettingsEditor } from "./ModelSettingsEditor";
+import { MQTT_QOS_OPTIONS, MqttSubscriptionsEditor } from "./MqttSubscriptionsEditor";
+import { UrlBar } from "./UrlBar";
+
+interface Props {
+  style: CSSProperties;
+  fullHeight: boolean;
+  className?: string;
+  activeRequest: MqttRequest;
+}
+
+const TAB_PUBLISH = "publish";
+const TAB_SUBSCRIPTIONS = "subscriptions";
+const TAB_CONNECTION = "connection";
+const TAB_SETTINGS = "settings";
+const TAB_DESCRIPTION = "description";
+const TABS_STORAGE_KEY = "mqtt_request_tabs";
+
+export function MqttRequestPane({ style, fullHeight, className, activeRequest }: Props) {
+  const activeRequestId = activeRequest.id;
+  const forceUpdateKey = useRequestUpdateKey(activeRequest.id);
+  const numSettingsOverrides = countOverriddenSettings(activeRequest);
+  const connection = useAtomValue(activeMqttConnectionAtom);
+  const numSubscriptions = activeRequest.subscriptions.filter(
+    (s) => (s.enabled ?? true) && s.topic !== "",
+  ).length;
+
+  const tabs = useMemo<TabItem[]>(() => {
+    return [
+      {
+        value: TAB_PUBLISH,
+        label: "Publish",
+      },
+      {
+        value: TAB_SUBSCRIPTIONS,
+        label: "Subscriptions",
+        rightSlot: <CountBadge count={numSubscriptions} />,
+      },
+      {
+        value: TAB_CONNECTION,
+        label: "Connection",
+      },
+      {
+        value: TAB_SETTINGS,
+        label: "Settings",
+        rightSlot: <CountBadge count={numSettingsOverrides} />,
+      },
+      {
+        value: TAB_DESCRIPTION,
+        label: "Info",
+      },
+    ];
+  }, [numSettingsOverrides, numSubscriptions]);
+
+  const handleConnect = useCallback(async () => {
+    await connectMqtt({
+      requestId: activeRequest.id,
+      environmentId: getActiveEnvironment()?.id ?? null,
+    });
+  }, [activeRequest.id]);
+
+  const handlePublish = useCallback(async () => {
+    if (connection == null) return;
+    await publishMqtt({
+      connectionId: connection.id,
+      environmentId: getActiveEnvironment()?.id ?? null,
+    });
+  }, [connection]);
+
+  const handleClose = useCallback(async () => {
+    if (connection == null) return;
+    await closeMqtt({ connectionId: connection.id });
+  }, [connection]);
+
+  const handleUrlChange = useCallback(
+    (url: string) => patchModel(activeRequest, { url }),
+    [activeRequest],
+  );
+
+  const isConnected = connection !== null && connection.state !== "closed";
+
+  return (
+    <div
+      style={style}
+      className={classNames(className, "h-full grid grid-rows-[auto_minmax(0,1fr)] grid-cols-1")}
+    >
+      <div className="grid grid-cols-[minmax(0,1fr)_auto]">
+        <UrlBar
+          stateKey={`url.${activeRequest.id}`}
+          key={forceUpdateKey}
+          url={activeRequest.url}
+          submitIcon={isConnected ? "send_horizontal" : "arrow_up_down"}
+          rightSlot={
+            isConnected && (
+              <IconButton
+                size="xs"
+                title="Close connection"
+                icon="x"
+                iconColor="secondary"
+                className="w-8 mr-0.5 !h-full"
+                onClick={handleClose}
+              />
+            )
+          }
+          placeholder="mqtt://broker.example.com"
+          onSend={isConnected ? handlePublish : handleConnect}
+          onCancel={handleClose}
+          onUrlChange={handleUrlChange}
+          forceUpdateKey={forceUpdateKey}
+          isLoading={connection?.state === "initialized"}
+        />
+      </div>
+      <Tabs
+        label="Request"
+        tabs={tabs}
+        tabListClassName="mt-1 !mb-1.5"
+        storageKey={TABS_STORAGE_KEY}
+        activeTabKey={activeRequestId}
+      >
+        <TabContent value={TAB_PUBLISH}>
+          <div className="h-full grid grid-rows-[auto_minmax(0,1fr)] gap-2">
+            <HStack space={2} alignItems="center">
+              <Input
+                label="Topic"
+                hideLabel
+                size="sm"
+                placeholder="sensors/temperature"
+                stateKey={`topic.${activeRequest.id}`}
+                forceUpdateKey={forceUpdateKey}
+                defaultValue={activeRequest.topic}
+                autocompleteFunctions
+                autocompleteVariables
+                onChange={(topic) => patchModel(activeRequest, { topic })}
+              />
+              <Select
+                hideLabel
+                name="qos"
+                label="QoS"
+                size="sm"
+                className="!w-auto"
+                value={activeRequest.qos}
+                options={MQTT_QOS_OPTIONS}
+                onChange={(qos) => patchModel(activeRequest, { qos })}
+              />
+              <Checkbox
+                title="Retain"
+                className="!mr-0"
+                checked={activeRequest.retain}
+                onChange={(retain) => patchModel(activeRequest, { retain })}
+              />
+            </HStack>
+            <Editor
+              forceUpdateKey={forceUpdateKey}
+              autocompleteFunctions
+              autocompleteVariables
+              placeholder="..."
+              heightMode={fullHeight ? "full" : "auto"}
+              defaultValue={activeRequest.payload}
+              language={languageFromContentType(null, activeRequest.payload)}
+              onChange={(payload) => patchModel(activeRequest, { payload })}
+              stateKey={`payload.${activeRequest.id}`}
+            />
+          </div>
+        </TabContent>
+        <TabContent value={TAB_SUBSCRIPTIONS}>
+          <MqttSubscriptionsEditor request={activeRequest} />
+        </TabContent>
+        <TabContent value={TAB_CONNECTION}>
+          <VStack space={3} className="pb-3">
+            <Input
+              label="Client ID"
+              help="Brokers drop existing connections that share a client ID. Leave empty to generate one."
+              placeholder="Generated"
+              stateKey={`client_id.${activeRequest.id}`}
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.clientId}
+              autocompleteFunctions
+              autocompleteVariables
+              onChange={(clientId) => patchModel(activeRequest, { clientId })}
+            />
+            <Input
+              label="Username"
+              stateKey={`username.${activeRequest.id}`}
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.username}
+              autocompleteFunctions
+              autocompleteVariables
+              onChange={(username) => patchModel(activeRequest, { username })}
+            />
+            <Input
+              label="Password"
+              type="password"
+              stateKey={`password.${activeRequest.id}`}
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.password}
+              autocompleteFunctions
+              autocompleteVariables
+              onChange={(password) => patchModel(activeRequest, { password })}
+            />
+            <PlainInput
+              label="Keep Alive (seconds)"
+              type="number"
+              placeholder="60"
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.keepAlive > 0 ? `${activeRequest.keepAlive}` : ""}
+              validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+              onChange={(value) =>
+                patchModel(activeRequest, { keepAlive: Number.parseInt(value, 10) || 0 })
+              }
+            />
+            <Checkbox
+              title="Persistent session"
+              help="Ask the broker to keep subscriptions and queued messages for this client ID between connections."
+              checked={activeRequest.persistentSession}
+              onChange={(persistentSession) => patchModel(activeRequest, { persistentSession })}
+            />
+          </VStack>
+        </TabContent>
+        <TabContent value={TAB_SETTINGS}>
+          <ModelSettingsEditor model={activeRequest} />
+        </TabContent>
+        <TabContent value={TAB_DESCRIPTION}>
+          <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
+            <PlainInput
+              label="Request Name"
+              hideLabel
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.name}
+              className="font-sans !text-xl !px-0"
+              containerClassName="border-0"
+              placeholder={resolvedModelName(activeRequest)}
+              onChange={(name) => patchModel(activeRequest, { name })}
+            />
+            <MarkdownEditor
+              name="request-description"
+              placeholder="Request description"
+              defaultValue={activeRequest.description}
+              stateKey={`description.${activeRequest.id}`}
+              forceUpdateKey={forceUpdateKey}
+              onChange={(description) => patchModel(activeRequest, { description })}
+            />
+          </div>
+        </TabContent>
+      </Tabs>
+    </div>
+  );
+}
@@ -0,0 +1,233 @@
+import { formatSize } from "@yaakapp-internal/lib/formatSize";
+import type { MqttConnection, MqttEvent, MqttQos, MqttRequest } from "@yaakapp-internal/models";
+import { HStack, Icon, LoadingIcon } from "@yaakapp-internal/ui";
+import { hexy } from "hexy";
+import { useAtomValue } from "jotai";
+import { useMemo, useState } from "react";
+import { useFormatText } from "../hooks/useFormatText";
+import { activeMqttConnectionAtom, useMqttEvents } from "../hooks/useMqttConnection";
+import { languageFromContentType } from "../lib/contentType";
+import { Editor } from "./core/Editor/LazyEditor";
+import { type EventDetailAction, EventDetailHeader, EventViewer } from "./core/EventViewer";
+import { EventViewerRow } from "./core/EventViewerRow";
+import { HotkeyList } from "./core/HotkeyList";
+import { EmptyStateText } from "./EmptyStateText";
+import { ErrorBoundary } from "./ErrorBoundary";
+
+interface Props {
+  activeRequest: MqttRequest;
+}
+
+const QOS_LABELS: Record<MqttQos, string> = {
+  at_most_once: "QoS 0",
+  at_least_once: "QoS 1",
+  exactly_once: "QoS 2",
+};
+
+export function MqttResponsePane({ activeRequest }: Props) {
+  const [hexDumps, setHexDumps] = useState<Record<number, boolean>>({});
+  const activeConnection = useAtomValue(activeMqttConnectionAtom);
+  const events = useMqttEvents(activeConnection?.id ?? null);
+
+  if (activeConnection == null || activeConnection.requestId !== activeRequest.id) {
+    return (
+      <HotkeyList hotkeys={["request.send", "model.create", "sidebar.focus", "url_bar.focus"]} />
+    );
+  }
+
+  const numMessages = events.filter((e) => e.eventType === "publish").length;
+
+  const header = (
+    <HStack className="pl-3 mb-1 font-mono text-sm text-text-subtle">
+      <HStack space={2}>
+        {activeConnection.state !== "closed" && (
+          <LoadingIcon size="sm" className="text-text-subtlest" />
+        )}
+        <MqttStatusTag connection={activeConnection} />
+        <span>&bull;</span>
+        <span>{numMessages} Messages</span>
+        {activeConnection.clientId && (
+          <>
+            <span>&bull;</span>
+            <span className="truncate">{activeConnection.clientId}</span>
+          </>
+        )}
+      </HStack>
+    </HStack>
+  );
+
+  return (
+    <ErrorBoundary name="MQTT Events">
+      <EventViewer
+        events={events}
+        getEventKey={(event) => event.id}
+        error={activeConnection.error}
+        header={header}
+        splitLayoutStorageKey="mqtt_events"
+        defaultRatio={0.4}
+        renderRow={({ event, isActive, onClick }) => (
+          <MqttEventRow event={event} isActive={isActive} onClick={onClick} />
+        )}
+        renderDetail={({ event, index, onClose }) => (
+          <MqttEventDetail
+            event={event}
+            hexDump={hexDumps[index] ?? false}
+            setHexDump={(v) => setHexDumps({ ...hexDumps, [index]: v })}
+            onClose={onClose}
+          />
+        )}
+      />
+    </ErrorBoundary>
+  );
+}
+
+function MqttStatusTag({ connection }: { connection: MqttConnection }) {
+  const { state, error } = connection;
+  if (error) return <span className="font-mono text-danger">ERROR</span>;
+  if (state === "connected") return <span className="font-mono text-success">CONNECTED</span>;
+  if (state === "closing") return <span className="font-mono text-text-subtle">CLOSING</span>;
+  if (state === "closed") return <span className="font-mono text-warning">CLOSED</span>;
+  return <span className="font-mono text-text-subtle">CONNECTING</span>;
+}
+
+function MqttEventRow({
+  event,
+  isActive,
+  onClick,
+}: {
+  event: MqttEvent;
+  isActive: boolean;
+  onClick: () => void;
+}) {
+  const { eventType, isServer, topic } = event;
+  const message = decodeMessage(event.message);
+
+  const iconColor =
+    eventType === "error"
+      ? "danger"
+      : eventType !== "publish"
+        ? "secondary"
+        : isServer
+          ? "info"
+          : "primary";
+
+  const icon =
+    eventType !== "publish" ? "info" : isServer ? "arrow_big_down_dash" : "arrow_big_up_dash";
+
+  const content =
+    eventType === "connect" ? (
+      message || "Connected to broker"
+    ) : eventType === "disconnect" ? (
+      message || "Disconnected from broker"
+    ) : eventType === "subscribe" ? (
+      <span className="text-xs">
+        Subscribed to <span className="font-mono">{topic}</span>
+      </span>
+    ) : eventType === "unsubscribe" ? (
+      <span className="text-xs">
+        Unsubscribed from <span className="font-mono">{topic}</span>
+      </span>
+    ) : eventType === "error" ? (
+      <span className="text-xs">
+        <span className="font-mono">{topic}</span> {message}
+      </span>
+    ) : (
+      <span className="text-xs">
+        <span className="font-mono text-text-subtle">{topic}</span>{" "}
+        {message === "" ? (
+          <em className="italic text-text-subtlest">No content</em>
+        ) : (
+          message.slice(0, 1000)
+        )}
+      </span>
+    );
+
+  return (
+    <EventViewerRow
+      isActive={isActive}
+      onClick={onClick}
+      icon={<Icon color={iconColor} icon={icon} />}
+      content={content}
+      timestamp={event.createdAt}
+    />
+  );
+}
+
+function MqttEventDetail({
+  event,
+  hexDump,
+  setHexDump,
+  onClose,
+}: {
+  event: MqttEvent;
+  hexDump: boolean;
+  setHexDump: (v: boolean) => void;
+  onClose: () => void;
+}) {
+  const message = useMemo(() => {
+    if (hexDump) {
+      return event.message ? hexy(event.message) : "";
+    }
+    return decodeMessage(event.message);
+  }, [event.message, hexDump]);
+
+  const language = languageFromContentType(null, message);
+  const formattedMessage = useFormatText({ language, text: message, pretty: true });
+
+  const direction = event.isServer ? "Received" : "Published";
+  const publishDetails = [
+    QOS_LABELS[event.qos],
+    event.retain ? "retained" : null,
+    formatSize(event.message.length),
+  ]
+    .filter((d) => d != null)
+    .join(", ");
+  const title =
+    event.eventType === "publish"
+      ? `${direction} on ${event.topic} (${publishDetails})`
+      : event.eventType === "subscribe"
+        ? `Subscribed to ${event.topic} (${QOS_LABELS[event.qos]})`
+        : event.eventType === "unsubscribe"
+          ? `Unsubscribed from ${event.topic}`
+          : event.eventType === "connect"
+            ? "Connected"
+            : event.eventType === "disconnect"
+              ? "Disconnected"
+              : "Error";
+
+  const actions: EventDetailAction[] = [];
+  if (message !== "") {
+    actions.push({
+      key: "toggle-hexdump",
+      label: hexDump ? "Show Message" : "Show Hexdump",
+      onClick: () => setHexDump(!hexDump),
+    });
+  }
+
+  return (
+    <div className="h-full grid grid-rows-[auto_minmax(0,1fr)]">
+      <EventDetailHeader
+        title={title}
+        timestamp={event.createdAt}
+        actions={actions}
+        copyText={formattedMessage || undefined}
+        onClose={onClose}
+      />
+      {event.message.length === 0 ? (
+        <EmptyStateText>No Content</EmptyStateText>
+      ) : (
+        <Editor
+          language={language}
+          defaultValue={formattedMessage ?? ""}
+          wrapLines={false}
+          readOnly={true}
+          stateKey={null}
+        />
+      )}
+    </div>
+  );
+}
+
+function decodeMessage(bytes: number[]) {
+  return bytes ? new TextDecoder("utf-8").decode(Uint8Array.from(bytes)) : "";
+}
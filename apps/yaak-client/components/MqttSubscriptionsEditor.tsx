@@ -0,0 +1,156 @@
+import type { MqttQos, MqttRequest, MqttSubscription } from "@yaakapp-internal/models";
+import { patchModel } from "@yaakapp-internal/models";
+import {
+  HStack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import { useCallback } from "react";
+import { fireAndForget } from "../lib/fireAndForget";
+import { generateId } from "../lib/generateId";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+import type { RadioDropdownItem } from "./core/RadioDropdown";
+import { Select } from "./core/Select";
+
+export const MQTT_QOS_OPTIONS: RadioDropdownItem<MqttQos>[] = [
+  { label: "QoS 0", value: "at_most_once" },
+  { label: "QoS 1", value: "at_least_once" },
+  { label: "QoS 2", value: "exactly_once" },
+];
+
+interface Props {
+  request: MqttRequest;
+}
+
+export function MqttSubscriptionsEditor({ request }: Props) {
+  const subscriptions = request.subscriptions;
+
+  const handleChange = useCallback(
+    (subscriptions: MqttSubscription[]) => {
+      fireAndForget(patchModel(request, { subscriptions }));
+    },
+    [request],
+  );
+
+  const handleAdd = useCallback(() => {
+    const newSubscription: MqttSubscription = {
+      enabled: true,
+      topic: "",
+      qos: "at_most_once",
+      id: generateId(),
+    };
+    handleChange([...subscriptions, newSubscription]);
+  }, [subscriptions, handleChange]);
+
+  const handleUpdate = useCallback(
+    (index: number, update: Partial<MqttSubscription>) => {
+      handleChange(subscriptions.map((s, i) => (i === index ? { ...s, ...update } : s)));
+    },
+    [subscriptions, handleChange],
+  );
+
+  const handleDelete = useCallback(
+    (index: number) => {
+      handleChange(subscriptions.filter((_, i) => i !== index));
+    },
+    [subscriptions, handleChange],
+  );
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        Topics to subscribe to after connecting. Filters may use <code>+</code> to match one level
+        and <code>#</code> to match the rest of the topic.
+      </div>
+
+      {subscriptions.length > 0 && (
+        <Table>
+          <TableHead>
+            <TableRow>
+              <TableHeaderCell className="w-8" />
+              <TableHeaderCell>Topic</TableHeaderCell>
+              <TableHeaderCell>QoS</TableHeaderCell>
+              <TableHeaderCell className="w-10" />
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {subscriptions.map((subscription, index) => (
+              <MqttSubscriptionRow
+                // oxlint-disable-next-line react/no-array-index-key
+                key={subscription.id ?? index}
+                subscription={subscription}
+                onUpdate={(update) => handleUpdate(index, update)}
+                onDelete={() => handleDelete(index)}
+              />
+            ))}
+          </TableBody>
+        </Table>
+      )}
+
+      <HStack>
+        <Button size="xs" color="secondary" variant="border" onClick={handleAdd}>
+          Add Subscription
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
+
+interface MqttSubscriptionRowProps {
+  subscription: MqttSubscription;
+  onUpdate: (update: Partial<MqttSubscription>) => void;
+  onDelete: () => void;
+}
+
+function MqttSubscriptionRow({ subscription, onUpdate, onDelete }: MqttSubscriptionRowProps) {
+  return (
+    <TableRow>
+      <TableCell>
+        <Checkbox
+          hideLabel
+          title={subscription.enabled ? "Disable subscription" : "Enable subscription"}
+          checked={subscription.enabled ?? true}
+          onChange={(enabled) => onUpdate({ enabled })}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Topic"
+          placeholder="sensors/#"
+          defaultValue={subscription.topic}
+          onChange={(topic) => onUpdate({ topic })}
+        />
+      </TableCell>
+      <TableCell>
+        <Select<MqttQos>
+          hideLabel
+          size="sm"
+          name="qos"
+          label="QoS"
+          value={subscription.qos}
+          onChange={(qos) => onUpdate({ qos })}
+          options={MQTT_QOS_OPTIONS}
+        />
+      </TableCell>
+      <TableCell>
+        <IconButton
+          size="xs"
+          iconSize="sm"
+          icon="trash"
+          title="Delete subscription"
+          onClick={onDelete}
+        />
+      </TableCell>
+    </TableRow>
+  );
+}
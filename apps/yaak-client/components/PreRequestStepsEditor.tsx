@@ -0,0 +1,236 @@
+import type {
+  GrpcRequest,
+  HttpRequest,
+  PreRequestStep,
+  PreRequestStepKind,
+} from "@yaakapp-internal/models";
+import { httpRequestsAtom, patchModel } from "@yaakapp-internal/models";
+import {
+  HStack,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import { useAtomValue } from "jotai";
+import { useCallback } from "react";
+import { fireAndForget } from "../lib/fireAndForget";
+import { generateId } from "../lib/generateId";
+import { resolvedModelNameWithFolders } from "../lib/resolvedModelName";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+import { Select } from "./core/Select";
+
+interface Props {
+  request: HttpRequest | GrpcRequest;
+}
+
+export function PreRequestStepsEditor({ request }: Props) {
+  const steps = request.preRequestSteps;
+
+  const handleChange = useCallback(
+    (preRequestSteps: PreRequestStep[]) => {
+      fireAndForget(patchModel(request, { preRequestSteps }));
+    },
+    [request],
+  );
+
+  const handleAdd = useCallback(() => {
+    const newStep: PreRequestStep = {
+      enabled: true,
+      kind: "send_request",
+      ifEmpty: "",
+      requestId: "",
+      name: "",
+      value: "",
+      plugin: "",
+      action: "",
+      id: generateId(),
+    };
+    handleChange([...steps, newStep]);
+  }, [steps, handleChange]);
+
+  const handleUpdate = useCallback(
+    (index: number, update: Partial<PreRequestStep>) => {
+      handleChange(steps.map((s, i) => (i === index ? { ...s, ...update } : s)));
+    },
+    [steps, handleChange],
+  );
+
+  const handleDelete = useCallback(
+    (index: number) => {
+      handleChange(steps.filter((_, i) => i !== index));
+    },
+    [steps, handleChange],
+  );
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        Steps run in order before every send, like sending a login request first. Set a variable
+        under &ldquo;Only If Empty&rdquo; to skip a step once that variable has a value.
+      </div>
+
+      {steps.length > 0 && (
+        <Table>
+          <TableHead>
+            <TableRow>
+              <TableHeaderCell className="w-8" />
+              <TableHeaderCell>Step</TableHeaderCell>
+              <TableHeaderCell>Details</TableHeaderCell>
+              <TableHeaderCell>Only If Empty</TableHeaderCell>
+              <TableHeaderCell className="w-10" />
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {steps.map((step, index) => (
+              <PreRequestStepRow
+                // oxlint-disable-next-line react/no-array-index-key
+                key={step.id ?? index}
+                request={request}
+                step={step}
+                onUpdate={(update) => handleUpdate(index, update)}
+                onDelete={() => handleDelete(index)}
+              />
+            ))}
+          </TableBody>
+        </Table>
+      )}
+
+      <HStack>
+        <Button size="xs" color="secondary" variant="border" onClick={handleAdd}>
+          Add Step
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
+
+interface PreRequestStepRowProps {
+  request: HttpRequest | GrpcRequest;
+  step: PreRequestStep;
+  onUpdate: (update: Partial<PreRequestStep>) => void;
+  onDelete: () => void;
+}
+
+function PreRequestStepRow({ request, step, onUpdate, onDelete }: PreRequestStepRowProps) {
+  return (
+    <TableRow>
+      <TableCell>
+        <Checkbox
+          hideLabel
+          title={step.enabled ? "Disable step" : "Enable step"}
+          checked={step.enabled ?? true}
+          onChange={(enabled) => onUpdate({ enabled })}
+        />
+      </TableCell>
+      <TableCell>
+        <Select<PreRequestStepKind>
+          hideLabel
+          size="sm"
+          name="kind"
+          label="Step"
+          value={step.kind}
+          onChange={(kind) => onUpdate({ kind })}
+          options={[
+            { label: "Send request", value: "send_request" },
+            { label: "Set variable", value: "set_variable" },
+            { label: "Plugin action", value: "plugin_action" },
+          ]}
+        />
+      </TableCell>
+      <TableCell>
+        <PreRequestStepDetails request={request} step={step} onUpdate={onUpdate} />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Only if empty"
+          placeholder="token"
+          defaultValue={step.ifEmpty}
+          onChange={(ifEmpty) => onUpdate({ ifEmpty })}
+        />
+      </TableCell>
+      <TableCell>
+        <IconButton size="xs" iconSize="sm" icon="trash" title="Delete step" onClick={onDelete} />
+      </TableCell>
+    </TableRow>
+  );
+}
+
+function PreRequestStepDetails({
+  request,
+  step,
+  onUpdate,
+}: Omit<PreRequestStepRowProps, "onDelete">) {
+  const httpRequests = useAtomValue(httpRequestsAtom);
+
+  if (step.kind === "send_request") {
+    return (
+      <Select<string>
+        hideLabel
+        size="sm"
+        name="requestId"
+        label="Request"
+        value={step.requestId}
+        onChange={(requestId) => onUpdate({ requestId })}
+        options={[
+          { label: "Select request", value: "" },
+          ...httpRequests
+            .filter((r) => r.workspaceId === request.workspaceId && r.id !== request.id)
+            .map((r) => ({ label: resolvedModelNameWithFolders(r), value: r.id })),
+        ]}
+      />
+    );
+  }
+
+  if (step.kind === "set_variable") {
+    return (
+      <HStack space={1}>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Variable"
+          placeholder="timestamp"
+          defaultValue={step.name}
+          onChange={(name) => onUpdate({ name })}
+        />
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Value"
+          placeholder="${[ timestamp.unix() ]}"
+          defaultValue={step.value}
+          onChange={(value) => onUpdate({ value })}
+        />
+      </HStack>
+    );
+  }
+
+  return (
+    <HStack space={1}>
+      <PlainInput
+        hideLabel
+        size="sm"
+        label="Plugin"
+        placeholder="@yaak/plugin-name"
+        defaultValue={step.plugin}
+        onChange={(plugin) => onUpdate({ plugin })}
+      />
+      <PlainInput
+        hideLabel
+        size="sm"
+        label="Action"
+        placeholder="Action label"
+        defaultValue={step.action}
+        onChange={(action) => onUpdate({ action })}
+      />
+    </HStack>
+  );
+}
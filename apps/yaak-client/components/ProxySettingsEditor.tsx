@@ -0,0 +1,165 @@
+import type { ProxySetting } from "@yaakapp-internal/models";
+import { InlineCode } from "@yaakapp-internal/ui";
+import {
+  SettingRowBoolean,
+  SettingRowSelect,
+  SettingRowText,
+  SettingsList,
+  SettingsSection,
+} from "./core/SettingRow";
+
+interface Props {
+  proxy: ProxySetting | null;
+  onChange: (proxy: ProxySetting | null) => Promise<void>;
+  /** Label for the option that leaves the proxy unset */
+  automaticLabel: string;
+}
+
+export function ProxySettingsEditor({ proxy: value, onChange, automaticLabel }: Props) {
+  const proxy = enabledProxyOrDefault(value);
+
+  const patchProxy = async (patch: Partial<EnabledProxySetting>) => {
+    await onChange({
+      ...proxy,
+      ...patch,
+      auth: Object.hasOwn(patch, "auth") ? (patch.auth ?? null) : proxy.auth,
+    });
+  };
+
+  return (
+    <SettingsList className="space-y-8">
+      <SettingsSection title="Proxy">
+        <SettingRowSelect
+          title="Proxy"
+          description="Choose how Yaak should discover or use proxy settings."
+          name="proxy"
+          value={value?.type ?? "automatic"}
+          onChange={async (v) => {
+            if (v === "automatic") {
+              await onChange(null);
+            } else if (v === "enabled") {
+              await onChange(proxy);
+            } else {
+              await onChange({ type: "disabled" });
+            }
+          }}
+          options={[
+            { label: automaticLabel, value: "automatic" },
+            { label: "Custom proxy configuration", value: "enabled" },
+            { label: "No proxy", value: "disabled" },
+          ]}
+          selectClassName="!w-64"
+        />
+      </SettingsSection>
+
+      {value?.type === "enabled" && (
+        <>
+          <SettingsSection title="Custom Proxy">
+            <SettingRowBoolean
+              checked={!value.disabled}
+              title="Enable proxy"
+              description="Temporarily disable the proxy without losing the configuration."
+              onChange={(enabled) => patchProxy({ disabled: !enabled })}
+            />
+            <SettingRowText
+              name="proxyHttp"
+              title={
+                <>
+                  Proxy for <InlineCode>http://</InlineCode> traffic
+                </>
+              }
+              description={
+                <>
+                  Proxy host used for unencrypted traffic. Prefix with{" "}
+                  <InlineCode>socks5://</InlineCode> to use a SOCKS5 proxy.
+                </>
+              }
+              value={value.http}
+              placeholder="localhost:9090"
+              onChange={(http) => patchProxy({ http })}
+            />
+            <SettingRowText
+              name="proxyHttps"
+              title={
+                <>
+                  Proxy for <InlineCode>https://</InlineCode> traffic
+                </>
+              }
+              description="Proxy host used for HTTPS traffic, tunneled with CONNECT."
+              value={value.https}
+              placeholder="localhost:9090"
+              onChange={(https) => patchProxy({ https })}
+            />
+            <SettingRowText
+              name="proxyBypass"
+              title="Proxy Bypass"
+              description={
+                <>
+                  Comma-separated list of hosts that should bypass the proxy, like{" "}
+                  <InlineCode>NO_PROXY</InlineCode>.
+                </>
+              }
+              value={value.bypass}
+              placeholder="127.0.0.1, *.example.com, localhost:3000"
+              inputWidthClassName="!w-96"
+              onChange={(bypass) => patchProxy({ bypass })}
+            />
+          </SettingsSection>
+
+          <SettingsSection title="Authentication">
+            <SettingRowBoolean
+              checked={value.auth != null}
+              title="Enable authentication"
+              description="Send proxy credentials with proxied requests."
+              onChange={(enabled) =>
+                patchProxy({ auth: enabled ? { user: "", password: "" } : null })
+              }
+            />
+
+            {value.auth != null && (
+              <>
+                <SettingRowText
+                  required
+                  name="proxyUser"
+                  title="User"
+                  description="Username for proxy authentication."
+                  value={value.auth.user}
+                  placeholder="myUser"
+                  onChange={(user) =>
+                    patchProxy({ auth: { user, password: value.auth?.password ?? "" } })
+                  }
+                />
+                <SettingRowText
+                  name="proxyPassword"
+                  title="Password"
+                  description="Password for proxy authentication."
+                  value={value.auth.password}
+                  placeholder="s3cretPassw0rd"
+                  type="password"
+                  onChange={(password) =>
+                    patchProxy({ auth: { user: value.auth?.user ?? "", password } })
+                  }
+                />
+              </>
+            )}
+          </SettingsSection>
+        </>
+      )}
+    </SettingsList>
+  );
+}
+
+type EnabledProxySetting = Extract<ProxySetting, { type: "enabled" }>;
+
+function enabledProxyOrDefault(proxy: ProxySetting | null): EnabledProxySetting {
+  if (proxy?.type === "enabled") return proxy;
+
+  return {
+    disabled: false,
+    type: "enabled",
+    http: "",
+    https: "",
+    auth: { user: "", password: "" },
+    bypass: "",
+  };
+}
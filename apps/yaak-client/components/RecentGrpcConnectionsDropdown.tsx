@@ -41,6 +41,9 @@ export function RecentGrpcConnectionsDropdown({
             <HStack space={2}>
               {formatDistanceToNowStrict(`${c.createdAt}Z`)} ago &bull;{" "}
               <span className="font-mono text-sm">{c.elapsed}ms</span>
+              {c.state === "timed_out" && (
+                <span className="text-sm text-warning">Deadline exceeded</span>
+              )}
             </HStack>
           ),
           leftSlot: activeConnection?.id === c.id ? <Icon icon="check" /> : <Icon icon="empty" />,
@@ -4,6 +4,7 @@ import { HStack, Icon } from "@yaakapp-internal/ui";
 import { useCopyHttpResponse } from "../hooks/useCopyHttpResponse";
 import { useDeleteHttpResponses } from "../hooks/useDeleteHttpResponses";
 import { useSaveResponse } from "../hooks/useSaveResponse";
+import { isResponseDone } from "../lib/model_util";
 import { pluralize } from "../lib/pluralize";
 import { Dropdown } from "./core/Dropdown";
 import { HttpStatusTag } from "./core/HttpStatusTag";
@@ -34,14 +35,14 @@ export const RecentHttpResponsesDropdown = function ResponsePane({
           onSelect: saveResponse.mutate,
           leftSlot: <Icon icon="save" />,
           hidden: responses.length === 0 || !!activeResponse.error,
-          disabled: activeResponse.state !== "closed" && activeResponse.status >= 100,
+          disabled: !isResponseDone(activeResponse) && activeResponse.status >= 100,
         },
         {
           label: "Copy Body",
           onSelect: copyResponse.mutate,
           leftSlot: <Icon icon="copy" />,
           hidden: responses.length === 0 || !!activeResponse.error,
-          disabled: activeResponse.state !== "closed" && activeResponse.status >= 100,
+          disabled: !isResponseDone(activeResponse) && activeResponse.status >= 100,
         },
         {
           label: "Delete",
@@ -0,0 +1,37 @@
+import type { HttpResponse } from "@yaakapp-internal/models";
+import { LoadingIcon } from "@yaakapp-internal/ui";
+import { useHttpRequestBody } from "../hooks/useHttpRequestBody";
+import { Editor } from "./core/Editor/LazyEditor";
+import { EmptyStateText } from "./EmptyStateText";
+
+interface Props {
+  response: HttpResponse;
+}
+
+/** The request as it was written to the connection, with its headers and body */
+export function RequestRawViewer({ response }: Props) {
+  return <RequestRawViewerInner key={response.id} response={response} />;
+}
+
+function RequestRawViewerInner({ response }: Props) {
+  const { data, isLoading, error } = useHttpRequestBody(response);
+
+  if (response.requestHead == null) {
+    return <EmptyStateText>The raw request was not recorded</EmptyStateText>;
+  }
+
+  if (isLoading) {
+    return (
+      <EmptyStateText>
+        <LoadingIcon />
+      </EmptyStateText>
+    );
+  }
+
+  if (error) {
+    return <EmptyStateText>Error loading request body: {error.message}</EmptyStateText>;
+  }
+
+  const raw = response.requestHead.replaceAll("\r\n", "\n") + (data?.bodyText ?? "");
+  return <Editor language="text" defaultValue={raw} readOnly stateKey={null} hideGutter />;
+}
@@ -0,0 +1,34 @@
+import type { HttpResponse } from "@yaakapp-internal/models";
+import { Icon } from "@yaakapp-internal/ui";
+import { EmptyStateText } from "./EmptyStateText";
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function ResponseAssertions({ response }: Props) {
+  if (response.assertionResults.length === 0) {
+    return <EmptyStateText>No assertions were checked for this response</EmptyStateText>;
+  }
+
+  return (
+    <div className="overflow-auto h-full pb-4 flex flex-col gap-y-1 pr-0.5">
+      {response.assertionResults.map((result, index) => (
+        <div
+          // oxlint-disable-next-line react/no-array-index-key
+          key={result.assertionId ?? index}
+          className="flex items-center gap-2 font-mono text-editor"
+        >
+          <Icon
+            icon={result.passed ? "check" : "x"}
+            color={result.passed ? "success" : "danger"}
+          />
+          <span className="select-text cursor-text">{result.name}</span>
+          <span className="text-text-subtle select-text cursor-text truncate">
+            {result.message}
+          </span>
+        </div>
+      ))}
+    </div>
+  );
+}
@@ -0,0 +1,189 @@
+import type {
+  HttpRequest,
+  ResponseExtraction,
+  ResponseExtractionSource,
+  ResponseExtractionTarget,
+} from "@yaakapp-internal/models";
+import { patchModel } from "@yaakapp-internal/models";
+import {
+  HStack,
+  InlineCode,
+  Table,
+  TableBody,
+  TableCell,
+  TableHead,
+  TableHeaderCell,
+  TableRow,
+  VStack,
+} from "@yaakapp-internal/ui";
+import { useCallback } from "react";
+import { fireAndForget } from "../lib/fireAndForget";
+import { generateId } from "../lib/generateId";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { IconButton } from "./core/IconButton";
+import { PlainInput } from "./core/PlainInput";
+import { Select } from "./core/Select";
+
+interface Props {
+  request: HttpRequest;
+}
+
+export function ResponseExtractionsEditor({ request }: Props) {
+  const extractions = request.responseExtractions;
+
+  const handleChange = useCallback(
+    (responseExtractions: ResponseExtraction[]) => {
+      fireAndForget(patchModel(request, { responseExtractions }));
+    },
+    [request],
+  );
+
+  const handleAdd = useCallback(() => {
+    const newExtraction: ResponseExtraction = {
+      enabled: true,
+      name: "",
+      source: "body",
+      path: "",
+      target: "environment",
+      id: generateId(),
+    };
+    handleChange([...extractions, newExtraction]);
+  }, [extractions, handleChange]);
+
+  const handleUpdate = useCallback(
+    (index: number, update: Partial<ResponseExtraction>) => {
+      handleChange(extractions.map((e, i) => (i === index ? { ...e, ...update } : e)));
+    },
+    [extractions, handleChange],
+  );
+
+  const handleDelete = useCallback(
+    (index: number) => {
+      handleChange(extractions.filter((_, i) => i !== index));
+    },
+    [extractions, handleChange],
+  );
+
+  return (
+    <VStack space={3} className="pb-3">
+      <div className="text-text-subtle text-sm">
+        Copy values out of every response into variables, like a token from a login request. Body
+        paths are JSONPath (<InlineCode>$.auth.token</InlineCode>) or, for XML and HTML responses,
+        XPath (<InlineCode>//token</InlineCode>).
+      </div>
+
+      {extractions.length > 0 && (
+        <Table>
+          <TableHead>
+            <TableRow>
+              <TableHeaderCell className="w-8" />
+              <TableHeaderCell>Variable</TableHeaderCell>
+              <TableHeaderCell>Source</TableHeaderCell>
+              <TableHeaderCell>Path or Header</TableHeaderCell>
+              <TableHeaderCell>Save To</TableHeaderCell>
+              <TableHeaderCell className="w-10" />
+            </TableRow>
+          </TableHead>
+          <TableBody>
+            {extractions.map((extraction, index) => (
+              <ResponseExtractionRow
+                // oxlint-disable-next-line react/no-array-index-key
+                key={extraction.id ?? index}
+                extraction={extraction}
+                onUpdate={(update) => handleUpdate(index, update)}
+                onDelete={() => handleDelete(index)}
+              />
+            ))}
+          </TableBody>
+        </Table>
+      )}
+
+      <HStack>
+        <Button size="xs" color="secondary" variant="border" onClick={handleAdd}>
+          Add Extraction
+        </Button>
+      </HStack>
+    </VStack>
+  );
+}
+
+interface ResponseExtractionRowProps {
+  extraction: ResponseExtraction;
+  onUpdate: (update: Partial<ResponseExtraction>) => void;
+  onDelete: () => void;
+}
+
+function ResponseExtractionRow({ extraction, onUpdate, onDelete }: ResponseExtractionRowProps) {
+  return (
+    <TableRow>
+      <TableCell>
+        <Checkbox
+          hideLabel
+          title={extraction.enabled ? "Disable extraction" : "Enable extraction"}
+          checked={extraction.enabled ?? true}
+          onChange={(enabled) => onUpdate({ enabled })}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Variable"
+          placeholder="token"
+          defaultValue={extraction.name}
+          onChange={(name) => onUpdate({ name })}
+        />
+      </TableCell>
+      <TableCell>
+        <Select<ResponseExtractionSource>
+          hideLabel
+          size="sm"
+          name="source"
+          label="Source"
+          value={extraction.source}
+          onChange={(source) => onUpdate({ source })}
+          options={[
+            { label: "Body", value: "body" },
+            { label: "Header", value: "header" },
+            { label: "Status", value: "status" },
+          ]}
+        />
+      </TableCell>
+      <TableCell>
+        <PlainInput
+          hideLabel
+          size="sm"
+          label="Path or header"
+          disabled={extraction.source === "status"}
+          placeholder={extraction.source === "header" ? "Authorization" : "$.token"}
+          defaultValue={extraction.path}
+          onChange={(path) => onUpdate({ path })}
+        />
+      </TableCell>
+      <TableCell>
+        <Select<ResponseExtractionTarget>
+          hideLabel
+          size="sm"
+          name="target"
+          label="Save to"
+          value={extraction.target}
+          onChange={(target) => onUpdate({ target })}
+          options={[
+            { label: "Environment", value: "environment" },
+            { label: "Chained values", value: "chained" },
+          ]}
+        />
+      </TableCell>
+      <TableCell>
+        <IconButton
+          size="xs"
+          iconSize="sm"
+          icon="trash"
+          title="Delete extraction"
+          onClick={onDelete}
+        />
+      </TableCell>
+    </TableRow>
+  );
+}
@@ -38,6 +38,18 @@ export function ResponseInfo({ response }: Props) {
             </div>
           }
         </KeyValueRow>
+        {response.redirects.map((redirect, i) => (
+          <KeyValueRow
+            // oxlint-disable-next-line react/no-array-index-key -- redirects can repeat a URL
+            key={i}
+            labelColor="secondary"
+            label={`Redirect ${i + 1}`}
+          >
+            <span className="select-text cursor-text">
+              {redirect.status} {redirect.method} {redirect.url} → {redirect.location}
+            </span>
+          </KeyValueRow>
+        ))}
       </KeyValueRows>
     </div>
   );
@@ -0,0 +1,98 @@
+import type { HttpResponse, HttpResponseCertificate } from "@yaakapp-internal/models";
+import { DetailsBanner } from "./core/DetailsBanner";
+import { KeyValueRow, KeyValueRows } from "./core/KeyValueRow";
+
+interface Props {
+  response: HttpResponse;
+}
+
+export function ResponseTls({ response }: Props) {
+  const tls = response.tls;
+  if (tls == null) {
+    return <div className="p-4 text-text-subtlest">The response was not received over TLS</div>;
+  }
+
+  return (
+    <div className="overflow-auto h-full pb-4 gap-y-3 flex flex-col pr-0.5">
+      <KeyValueRows>
+        <KeyValueRow labelColor="info" label="Version">
+          {tls.version ?? <span className="text-text-subtlest">--</span>}
+        </KeyValueRow>
+        <KeyValueRow labelColor="info" label="ALPN">
+          {tls.alpn ?? <span className="text-text-subtlest">--</span>}
+        </KeyValueRow>
+      </KeyValueRows>
+      {tls.certificates.map((certificate, i) => (
+        <DetailsBanner
+          key={certificate.sha256Fingerprint}
+          defaultOpen={i === 0}
+          summary={
+            <h2 className="flex items-center">
+              {i === 0 ? "Server Certificate" : `Intermediate Certificate ${i}`}
+              {!isCurrentlyValid(certificate) && (
+                <span className="ml-2 text-xs font-sans text-danger bg-danger/10 px-1.5 py-0.5 rounded">
+                  Not valid now
+                </span>
+              )}
+            </h2>
+          }
+        >
+          <CertificateDetails certificate={certificate} />
+        </DetailsBanner>
+      ))}
+    </div>
+  );
+}
+
+function CertificateDetails({ certificate }: { certificate: HttpResponseCertificate }) {
+  return (
+    <KeyValueRows selectable>
+      <KeyValueRow labelColor="primary" label="Subject">
+        {certificate.subject || <span className="text-text-subtlest">--</span>}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="Issuer">
+        {certificate.issuer || <span className="text-text-subtlest">--</span>}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="Alt Names">
+        {certificate.subjectAltNames.length > 0 ? (
+          certificate.subjectAltNames.join(", ")
+        ) : (
+          <span className="text-text-subtlest">--</span>
+        )}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="Valid From">
+        {formatDate(certificate.notBefore)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="Valid Until">
+        {formatDate(certificate.notAfter)}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="Serial Number">
+        {certificate.serialNumber || <span className="text-text-subtlest">--</span>}
+      </KeyValueRow>
+      <KeyValueRow labelColor="primary" label="SHA-256" enableCopy>
+        {certificate.sha256Fingerprint}
+      </KeyValueRow>
+    </KeyValueRows>
+  );
+}
+
+function isCurrentlyValid(certificate: HttpResponseCertificate): boolean {
+  const now = Date.now();
+  if (certificate.notBefore != null && parseDate(certificate.notBefore).getTime() > now) {
+    return false;
+  }
+  if (certificate.notAfter != null && parseDate(certificate.notAfter).getTime() < now) {
+    return false;
+  }
+  return true;
+}
+
+// Dates are stored in UTC without a timezone
+function parseDate(date: string): Date {
+  return new Date(`${date}Z`);
+}
+
+function formatDate(date: string | null) {
+  if (date == null) return <span className="text-text-subtlest">--</span>;
+  return parseDate(date).toUTCString();
+}
@@ -0,0 +1,139 @@
+import { event } from "@tauri-apps/api";
+import type { CollectionRun, Folder } from "@yaakapp-internal/models";
+import { collectionRunsAtom, getModel } from "@yaakapp-internal/models";
+import { HStack, Icon, LoadingIcon, VStack } from "@yaakapp-internal/ui";
+import { useAtomValue } from "jotai";
+import { useState } from "react";
+import { getActiveCookieJar } from "../hooks/useActiveCookieJar";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { fireAndForget } from "../lib/fireAndForget";
+import { resolvedModelName } from "../lib/resolvedModelName";
+import { invokeCmd } from "../lib/tauri";
+import { Button } from "./core/Button";
+import { Checkbox } from "./core/Checkbox";
+import { HttpStatusTagRaw } from "./core/HttpStatusTag";
+import { PlainInput } from "./core/PlainInput";
+import { EmptyStateText } from "./EmptyStateText";
+import { SelectFile } from "./SelectFile";
+
+interface Props {
+  folder: Folder;
+}
+
+export function RunFolderDialog({ folder }: Props) {
+  const latestRun = useAtomValue(collectionRunsAtom).find((r) => r.folderId === folder.id);
+  const [concurrency, setConcurrency] = useState<number>(latestRun?.concurrency ?? 1);
+  const [delay, setDelay] = useState<number>(latestRun?.delay ?? 0);
+  const [stopOnFailure, setStopOnFailure] = useState<boolean>(latestRun?.stopOnFailure ?? false);
+  const [dataFile, setDataFile] = useState<string | null>(latestRun?.dataFile ?? null);
+  const running = latestRun?.state === "running";
+
+  const handleRun = () => {
+    fireAndForget(
+      invokeCmd("cmd_run_folder", {
+        folderId: folder.id,
+        environmentId: getActiveEnvironment()?.id,
+        cookieJarId: getActiveCookieJar()?.id,
+        concurrency,
+        delay,
+        stopOnFailure,
+        dataFile,
+      }),
+    );
+  };
+
+  return (
+    <VStack space={4} className="pb-4 h-full">
+      <HStack space={2} alignItems="end">
+        <PlainInput
+          name="concurrency"
+          label="Concurrency"
+          size="sm"
+          type="number"
+          defaultValue={`${concurrency}`}
+          validate={(value) => Number.parseInt(value, 10) >= 1}
+          onChange={(value) => setConcurrency(Math.max(1, Number.parseInt(value, 10) || 1))}
+        />
+        <PlainInput
+          name="delay"
+          label="Delay (ms)"
+          size="sm"
+          type="number"
+          defaultValue={`${delay}`}
+          validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+          onChange={(value) => setDelay(Math.max(0, Number.parseInt(value, 10) || 0))}
+        />
+      </HStack>
+      <SelectFile
+        label="Data File"
+        noun="CSV or JSON File"
+        help="Run the folder once per row, with each column available as a variable"
+        filePath={dataFile}
+        onChange={({ filePath }) => setDataFile(filePath)}
+      />
+      <Checkbox checked={stopOnFailure} title="Stop on failure" onChange={setStopOnFailure} />
+      <HStack space={2}>
+        <Button
+          color="primary"
+          size="sm"
+          disabled={running}
+          rightSlot={<Icon icon="send_horizontal" />}
+          onClick={handleRun}
+        >
+          Run
+        </Button>
+        {running && (
+          <Button
+            color="secondary"
+            variant="border"
+            size="sm"
+            onClick={() => fireAndForget(event.emit(`cancel_collection_run_${folder.id}`))}
+          >
+            Cancel
+          </Button>
+        )}
+      </HStack>
+      {latestRun == null ? (
+        <EmptyStateText>This folder has not been run yet</EmptyStateText>
+      ) : (
+        <CollectionRunResults run={latestRun} />
+      )}
+    </VStack>
+  );
+}
+
+function CollectionRunResults({ run }: { run: CollectionRun }) {
+  const passed = run.results.filter((r) => r.passed).length;
+  return (
+    <VStack space={1} className="overflow-auto min-h-0">
+      <HStack space={2} className="text-text-subtle text-sm">
+        {run.state === "running" && <LoadingIcon size="sm" />}
+        <span>
+          {passed}/{run.results.length} passed in {run.elapsed} ms
+          {run.state === "stopped" && " (stopped early)"}
+        </span>
+      </HStack>
+      {run.results.map((result) => (
+        <HStack
+          key={`${result.iteration}-${result.requestId}`}
+          space={2}
+          className="font-mono text-editor"
+        >
+          <Icon
+            icon={result.passed ? "check" : "x"}
+            color={result.passed ? "success" : "danger"}
+          />
+          {run.dataFile != null && (
+            <span className="text-text-subtle">#{result.iteration + 1}</span>
+          )}
+          <span className="truncate">
+            {result.requestName || resolvedModelName(getModel("http_request", result.requestId))}
+          </span>
+          {result.responseId != null && <HttpStatusTagRaw status={result.status} />}
+          <span className="text-text-subtle">{result.elapsed} ms</span>
+          {result.error && <span className="text-danger truncate">{result.error}</span>}
+        </HStack>
+      ))}
+    </VStack>
+  );
+}
@@ -6,7 +6,12 @@ import { activeWorkspaceAtom } from "../../hooks/useActiveWorkspace";
 import { useCheckForUpdates } from "../../hooks/useCheckForUpdates";
 import { appInfo } from "../../lib/appInfo";
 import {
+  SETTING_CONNECT_TIMEOUT,
   SETTING_FOLLOW_REDIRECTS,
+  SETTING_MAX_REDIRECTS,
+  SETTING_READ_TIMEOUT,
+  SETTING_REDIRECT_RESEND_BODY,
+  SETTING_REDIRECT_STRIP_AUTHORIZATION,
   SETTING_REQUEST_TIMEOUT,
   SETTING_SEND_COOKIES,
   SETTING_STORE_COOKIES,
@@ -128,6 +133,26 @@ export function SettingsGeneral() {
             validate={(value) => Number.parseInt(value, 10) >= 0}
           />
 
+          <ModelSettingRowNumber
+            model={workspace}
+            modelKey={SETTING_CONNECT_TIMEOUT.modelKey}
+            title={SETTING_CONNECT_TIMEOUT.title}
+            description={SETTING_CONNECT_TIMEOUT.description}
+            placeholder={`${SETTING_CONNECT_TIMEOUT.defaultValue}`}
+            required
+            validate={(value) => Number.parseInt(value, 10) >= 0}
+          />
+
+          <ModelSettingRowNumber
+            model={workspace}
+            modelKey={SETTING_READ_TIMEOUT.modelKey}
+            title={SETTING_READ_TIMEOUT.title}
+            description={SETTING_READ_TIMEOUT.description}
+            placeholder={`${SETTING_READ_TIMEOUT.defaultValue}`}
+            required
+            validate={(value) => Number.parseInt(value, 10) >= 0}
+          />
+
           <ModelSettingRowBoolean
             model={workspace}
             modelKey={SETTING_VALIDATE_CERTIFICATES.modelKey}
@@ -142,6 +167,30 @@ export function SettingsGeneral() {
             description={SETTING_FOLLOW_REDIRECTS.description}
           />
 
+          <ModelSettingRowNumber
+            model={workspace}
+            modelKey={SETTING_MAX_REDIRECTS.modelKey}
+            title={SETTING_MAX_REDIRECTS.title}
+            description={SETTING_MAX_REDIRECTS.description}
+            placeholder={`${SETTING_MAX_REDIRECTS.defaultValue}`}
+            required
+            validate={(value) => Number.parseInt(value, 10) >= 0}
+          />
+
+          <ModelSettingRowBoolean
+            model={workspace}
+            modelKey={SETTING_REDIRECT_RESEND_BODY.modelKey}
+            title={SETTING_REDIRECT_RESEND_BODY.title}
+            description={SETTING_REDIRECT_RESEND_BODY.description}
+          />
+
+          <ModelSettingRowBoolean
+            model={workspace}
+            modelKey={SETTING_REDIRECT_STRIP_AUTHORIZATION.modelKey}
+            title={SETTING_REDIRECT_STRIP_AUTHORIZATION.title}
+            description={SETTING_REDIRECT_STRIP_AUTHORIZATION.description}
+          />
+
           <ModelSettingRowBoolean
             model={workspace}
             modelKey={SETTING_SEND_COOKIES.modelKey}
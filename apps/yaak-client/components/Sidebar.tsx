@@ -9,6 +9,8 @@ import type {
   GrpcRequest,
   HttpRequest,
   ModelPayload,
+  MqttRequest,
+  SocketRequest,
   WebsocketRequest,
   Workspace,
 } from "@yaakapp-internal/models";
@@ -19,7 +21,9 @@ import {
   getModel,
   grpcConnectionsAtom,
   httpResponsesAtom,
+  mqttConnectionsAtom,
   patchModel,
+  socketResponsesAtom,
   websocketConnectionsAtom,
   workspacesAtom,
 } from "@yaakapp-internal/models";
@@ -28,8 +32,12 @@ import { atom, useAtomValue } from "jotai";
 import { atomFamily } from "jotai-family";
 import { selectAtom } from "jotai/utils";
 import { memo, useCallback, useEffect, useMemo, useRef } from "react";
+import { copyAsSnippet, getCodegenLanguages } from "../commands/copyAsSnippet";
+import { downloadHttpRequest } from "../commands/downloadHttpRequest";
+import { createMonitor } from "../commands/monitors";
 import { moveToWorkspace } from "../commands/moveToWorkspace";
 import { openFolderSettings } from "../commands/openFolderSettings";
+import { openRunFolderDialog } from "../commands/runFolder";
 import { activeFolderIdAtom } from "../hooks/useActiveFolderId";
 import { activeRequestIdAtom } from "../hooks/useActiveRequestId";
 import {
@@ -57,6 +65,7 @@ import {
   gitWorktreeStatusFamily,
 } from "../lib/gitWorktreeStatus";
 import { jotaiStore } from "../lib/jotai";
+import { isResponseDone } from "../lib/model_util";
 import { resolvedModelName } from "../lib/resolvedModelName";
 import { isSidebarFocused } from "../lib/scopes";
 import { navigateToRequestOrFolderOrWorkspace } from "../lib/setWorkspaceSearchParams";
@@ -90,12 +99,21 @@ const collapsedFamily = atomFamily((treeId: string) => {
   return atomWithKVStorage<Record<string, boolean>>(key, {});
 });
 
-type SidebarModel = Workspace | Folder | HttpRequest | GrpcRequest | WebsocketRequest;
+type SidebarModel =
+  | Workspace
+  | Folder
+  | HttpRequest
+  | GrpcRequest
+  | WebsocketRequest
+  | MqttRequest
+  | SocketRequest;
 function isSidebarLeafModel(m: AnyModel): boolean {
   const modelMap: Record<Exclude<SidebarModel["model"], "workspace">, null> = {
     http_request: null,
     grpc_request: null,
     websocket_request: null,
+    mqtt_request: null,
+    socket_request: null,
     folder: null,
   };
   return m.model in modelMap;
@@ -280,8 +298,12 @@ function Sidebar({ className }: { className?: string }) {
 
   const handleMoveSelected = useCallback((items: SidebarModel[]) => {
     const requests = items.filter(
-      (i): i is HttpRequest | GrpcRequest | WebsocketRequest =>
-        i.model === "http_request" || i.model === "grpc_request" || i.model === "websocket_request",
+      (i): i is HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest =>
+        i.model === "http_request" ||
+        i.model === "grpc_request" ||
+        i.model === "websocket_request" ||
+        i.model === "mqtt_request" ||
+        i.model === "socket_request",
     );
     if (requests.length > 0) {
       moveToWorkspace.mutate(requests);
@@ -391,14 +413,19 @@ function Sidebar({ className }: { className?: string }) {
       }
 
       const workspaces = jotaiStore.get(workspacesAtom);
-      const syncDir = jotaiStore.get(activeWorkspaceMetaAtom)?.settingSyncDir;
+      const workspaceMeta = jotaiStore.get(activeWorkspaceMetaAtom);
+      const syncDir = workspaceMeta?.settingSyncDir;
+      const excludedFromSync =
+        workspaceMeta?.settingSyncExcludeFolderIds.includes(child.id) ?? false;
       const gitItems = getGitContextMenuItems({ items, syncDir });
       const onlyHttpRequests = items.every((i) => i.model === "http_request");
       const requestItems = items.filter(
         (i) =>
           i.model === "http_request" ||
           i.model === "grpc_request" ||
-          i.model === "websocket_request",
+          i.model === "websocket_request" ||
+          i.model === "mqtt_request" ||
+          i.model === "socket_request",
       );
 
       const initialItems: ContextMenuProps["items"] = [
@@ -408,6 +435,41 @@ function Sidebar({ className }: { className?: string }) {
           leftSlot: <Icon icon="folder_cog" />,
           onSelect: () => openFolderSettings(child.id),
         },
+        {
+          label: "Run Folder",
+          hidden: !(items.length === 1 && child.model === "folder"),
+          leftSlot: <Icon icon="circle_play" />,
+          onSelect: () => openRunFolderDialog(child.id),
+        },
+        {
+          label: excludedFromSync ? "Include in Sync" : "Exclude from Sync",
+          hidden: !(items.length === 1 && child.model === "folder" && syncDir),
+          leftSlot: <Icon icon="folder_sync" />,
+          onSelect: async () => {
+            if (workspaceMeta == null) return;
+            const folderIds = workspaceMeta.settingSyncExcludeFolderIds.filter(
+              (id) => id !== child.id,
+            );
+            await patchModel(workspaceMeta, {
+              settingSyncExcludeFolderIds: excludedFromSync ? folderIds : [...folderIds, child.id],
+            });
+          },
+        },
+        {
+          label: "Monitor",
+          hidden: !(
+            items.length === 1 &&
+            (child.model === "folder" || child.model === "http_request")
+          ),
+          leftSlot: <Icon icon="alarm_clock" />,
+          onSelect: async () => {
+            const target =
+              child.model === "folder"
+                ? getModel("folder", child.id)
+                : getModel("http_request", child.id);
+            if (target != null) await createMonitor(target);
+          },
+        },
         {
           label: "Send",
           hotKeyAction: "request.send",
@@ -416,6 +478,30 @@ function Sidebar({ className }: { className?: string }) {
           leftSlot: <Icon icon="send_horizontal" />,
           onSelect: () => handleSendSelected(items),
         },
+        {
+          label: "Send and Download…",
+          hidden: !(items.length === 1 && child.model === "http_request"),
+          leftSlot: <Icon icon="download" />,
+          onSelect: async () => {
+            const request = getModel("http_request", child.id);
+            if (request != null) await downloadHttpRequest(request);
+          },
+        },
+        {
+          label: "Copy As",
+          hidden: !(items.length === 1 && child.model === "http_request"),
+          leftSlot: <Icon icon="copy" />,
+          submenu: (items.length === 1 && child.model === "http_request"
+            ? await getCodegenLanguages()
+            : []
+          ).map((language) => ({
+            label: language.label,
+            onSelect: async () => {
+              const request = getModel("http_request", child.id);
+              if (request != null) await copyAsSnippet(request, language);
+            },
+          })),
+        },
         ...(items.length === 1 && child.model === "http_request"
           ? await getHttpRequestActions()
           : []
@@ -990,6 +1076,8 @@ const SidebarInnerItem = memo(function SidebarInnerItem({
             ...get(grpcConnectionsAtom),
             ...get(httpResponsesAtom),
             ...get(websocketConnectionsAtom),
+            ...get(mqttConnectionsAtom),
+            ...get(socketResponsesAtom),
           ]),
           (responses) => responses.find((r) => r.requestId === item.id),
           (a, b) => a?.state === b?.state && a?.id === b?.id, // Only update when the response state changes updated
@@ -1012,7 +1100,7 @@ const SidebarInnerItem = memo(function SidebarInnerItem({
       </div>
       {response != null && (
         <div className="ml-auto">
-          {response.state !== "closed" ? (
+          {!isResponseDone(response) ? (
             <LoadingIcon size="sm" className="text-text-subtlest" />
           ) : response.model === "http_response" ? (
             <HttpStatusTag short className="text-xs" response={response} />
@@ -1044,6 +1132,8 @@ function getItemFields(node: TreeNode<SidebarModel>): Record<string, string> {
   fields.type = "http";
   if (item.model === "grpc_request") fields.type = "grpc";
   else if (item.model === "websocket_request") fields.type = "ws";
+  else if (item.model === "mqtt_request") fields.type = "mqtt";
+  else if (item.model === "socket_request") fields.type = item.protocol;
 
   if (node.parent?.item.model === "folder") {
     fields.folder = node.parent.item.name;
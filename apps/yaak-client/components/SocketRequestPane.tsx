@@ -0,0 +1,181 @@
+import type { SocketPayloadFormat, SocketProtocol, SocketRequest } from "@yaakapp-internal/models";
+import { patchModel } from "@yaakapp-internal/models";
+import { sendSocket } from "@yaakapp-internal/socket";
+import { VStack } from "@yaakapp-internal/ui";
+import classNames from "classnames";
+import { useAtomValue } from "jotai";
+import type { CSSProperties } from "react";
+import { useCallback, useMemo } from "react";
+import { getActiveEnvironment } from "../hooks/useActiveEnvironment";
+import { useRequestUpdateKey } from "../hooks/useRequestUpdateKey";
+import { activeSocketResponseAtom } from "../hooks/useSocketResponses";
+import { languageFromContentType } from "../lib/contentType";
+import { isResponseDone } from "../lib/model_util";
+import { resolvedModelName } from "../lib/resolvedModelName";
+import { Button } from "./core/Button";
+import { Editor } from "./core/Editor/LazyEditor";
+import { HttpMethodTag, HttpMethodTagRaw } from "./core/HttpMethodTag";
+import { PlainInput } from "./core/PlainInput";
+import type { RadioDropdownItem } from "./core/RadioDropdown";
+import { RadioDropdown } from "./core/RadioDropdown";
+import type { TabItem } from "./core/Tabs/Tabs";
+import { TabContent, Tabs } from "./core/Tabs/Tabs";
+import { MarkdownEditor } from "./MarkdownEditor";
+import { UrlBar } from "./UrlBar";
+
+interface Props {
+  style: CSSProperties;
+  fullHeight: boolean;
+  className?: string;
+  activeRequest: SocketRequest;
+}
+
+const TAB_PAYLOAD = "payload";
+const TAB_OPTIONS = "options";
+const TAB_DESCRIPTION = "description";
+const TABS_STORAGE_KEY = "socket_request_tabs";
+
+const protocolItems: RadioDropdownItem<SocketProtocol>[] = (["tcp", "udp"] as const).map((p) => ({
+  value: p,
+  label: <HttpMethodTagRaw method={p} />,
+}));
+
+export function SocketRequestPane({ style, fullHeight, className, activeRequest }: Props) {
+  const activeRequestId = activeRequest.id;
+  const forceUpdateKey = useRequestUpdateKey(activeRequest.id);
+  const activeResponse = useAtomValue(activeSocketResponseAtom);
+
+  const tabs = useMemo<TabItem[]>(() => {
+    return [
+      {
+        value: TAB_PAYLOAD,
+        label: "Payload",
+        options: {
+          value: activeRequest.payloadFormat,
+          items: [
+            { label: "Text", value: "text" },
+            { label: "Hex", value: "hex" },
+          ],
+          onChange: (payloadFormat) =>
+            patchModel(activeRequest, { payloadFormat: payloadFormat as SocketPayloadFormat }),
+        },
+      },
+      {
+        value: TAB_OPTIONS,
+        label: "Options",
+      },
+      {
+        value: TAB_DESCRIPTION,
+        label: "Info",
+      },
+    ];
+  }, [activeRequest]);
+
+  const handleSend = useCallback(async () => {
+    await sendSocket({
+      requestId: activeRequest.id,
+      environmentId: getActiveEnvironment()?.id ?? null,
+    });
+  }, [activeRequest.id]);
+
+  const handleUrlChange = useCallback(
+    (url: string) => patchModel(activeRequest, { url }),
+    [activeRequest],
+  );
+
+  return (
+    <div
+      style={style}
+      className={classNames(className, "h-full grid grid-rows-[auto_minmax(0,1fr)] grid-cols-1")}
+    >
+      <UrlBar
+        stateKey={`url.${activeRequest.id}`}
+        key={forceUpdateKey}
+        url={activeRequest.url}
+        placeholder="localhost:7"
+        onSend={handleSend}
+        // Sends can't be cancelled, but always finish once the read timeout passes
+        onCancel={() => {}}
+        onUrlChange={handleUrlChange}
+        leftSlot={
+          <div className="py-0.5">
+            <RadioDropdown
+              value={activeRequest.protocol}
+              items={protocolItems}
+              onChange={(protocol) => patchModel(activeRequest, { protocol })}
+            >
+              <Button size="xs" className="ml-0.5 !h-full text-text-subtle hover:text">
+                <HttpMethodTag request={activeRequest} />
+              </Button>
+            </RadioDropdown>
+          </div>
+        }
+        forceUpdateKey={forceUpdateKey}
+        isLoading={activeResponse != null && !isResponseDone(activeResponse)}
+      />
+      <Tabs
+        label="Request"
+        tabs={tabs}
+        tabListClassName="mt-1 !mb-1.5"
+        storageKey={TABS_STORAGE_KEY}
+        activeTabKey={activeRequestId}
+      >
+        <TabContent value={TAB_PAYLOAD}>
+          <Editor
+            forceUpdateKey={forceUpdateKey}
+            autocompleteFunctions
+            autocompleteVariables
+            placeholder={activeRequest.payloadFormat === "hex" ? "50 49 4e 47 0d 0a" : "..."}
+            heightMode={fullHeight ? "full" : "auto"}
+            defaultValue={activeRequest.payload}
+            language={
+              activeRequest.payloadFormat === "hex"
+                ? "text"
+                : languageFromContentType(null, activeRequest.payload)
+            }
+            onChange={(payload) => patchModel(activeRequest, { payload })}
+            stateKey={`payload.${activeRequest.id}`}
+          />
+        </TabContent>
+        <TabContent value={TAB_OPTIONS}>
+          <VStack space={3} className="pb-3">
+            <PlainInput
+              label="Read Timeout (ms)"
+              help="How long to wait for more data after the last bytes were received before finishing."
+              type="number"
+              placeholder="1000"
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.readTimeout > 0 ? `${activeRequest.readTimeout}` : ""}
+              validate={(value) => value === "" || Number.parseInt(value, 10) >= 0}
+              onChange={(value) =>
+                patchModel(activeRequest, { readTimeout: Number.parseInt(value, 10) || 0 })
+              }
+            />
+          </VStack>
+        </TabContent>
+        <TabContent value={TAB_DESCRIPTION}>
+          <div className="grid grid-rows-[auto_minmax(0,1fr)] h-full">
+            <PlainInput
+              label="Request Name"
+              hideLabel
+              forceUpdateKey={forceUpdateKey}
+              defaultValue={activeRequest.name}
+              className="font-sans !text-xl !px-0"
+              containerClassName="border-0"
+              placeholder={resolvedModelName(activeRequest)}
+              onChange={(name) => patchModel(activeRequest, { name })}
+            />
+            <MarkdownEditor
+              name="request-description"
+              placeholder="Request description"
+              defaultValue={activeRequest.description}
+              stateKey={`description.${activeRequest.id}`}
+              forceUpdateKey={forceUpdateKey}
+              onChange={(description) => patchModel(activeRequest, { description })}
+            />
+          </div>
+        </TabContent>
+      </Tabs>
+    </div>
+  );
+}
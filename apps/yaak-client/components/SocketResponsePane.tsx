@@ -0,0 +1,185 @@
+import type { SocketRequest, SocketResponse } from "@yaakapp-internal/models";
+import { deleteModel } from "@yaakapp-internal/models";
+import { deleteSocketResponses } from "@yaakapp-internal/socket";
+import { Banner, HStack, Icon, LoadingIcon } from "@yaakapp-internal/ui";
+import { formatDistanceToNowStrict } from "date-fns";
+import { hexy } from "hexy";
+import { useAtomValue } from "jotai";
+import { useMemo, useState } from "react";
+import { useFormatText } from "../hooks/useFormatText";
+import { activeSocketResponsesAtom } from "../hooks/useSocketResponses";
+import { languageFromContentType } from "../lib/contentType";
+import { isResponseDone } from "../lib/model_util";
+import { pluralizeCount } from "../lib/pluralize";
+import { Dropdown } from "./core/Dropdown";
+import { Editor } from "./core/Editor/LazyEditor";
+import { HotkeyList } from "./core/HotkeyList";
+import { IconButton } from "./core/IconButton";
+import { SizeTag } from "./core/SizeTag";
+import { EmptyStateText } from "./EmptyStateText";
+import { ErrorBoundary } from "./ErrorBoundary";
+
+interface Props {
+  activeRequest: SocketRequest;
+}
+
+export function SocketResponsePane({ activeRequest }: Props) {
+  const [pinnedResponseId, setPinnedResponseId] = useState<string | null>(null);
+  const [hexDump, setHexDump] = useState<boolean>(false);
+  const responses = useAtomValue(activeSocketResponsesAtom);
+  const activeResponse = responses.find((r) => r.id === pinnedResponseId) ?? responses[0] ?? null;
+
+  if (activeResponse == null || activeResponse.requestId !== activeRequest.id) {
+    return (
+      <HotkeyList hotkeys={["request.send", "model.create", "sidebar.focus", "url_bar.focus"]} />
+    );
+  }
+
+  const isDone = isResponseDone(activeResponse);
+
+  return (
+    <ErrorBoundary name="Socket Response">
+      <div className="h-full w-full grid grid-rows-[auto_minmax(0,1fr)] grid-cols-1 pt-1">
+        <HStack className="pl-3 mb-1 font-mono text-sm text-text-subtle">
+          <HStack space={2}>
+            {!isDone && <LoadingIcon size="sm" className="text-text-subtlest" />}
+            <SocketStatusTag response={activeResponse} />
+            {isDone && (
+              <>
+                <span>&bull;</span>
+                <span
+                  className="font-mono"
+                  title={
+                    `CONNECT: ${activeResponse.elapsedConnect} ms\n` +
+                    `FIRST BYTE: ${activeResponse.elapsedFirstByte ?? "--"} ms\n` +
+                    `TOTAL: ${activeResponse.elapsed} ms`
+                  }
+                >
+                  {activeResponse.elapsed} ms
+                </span>
+                <span>&bull;</span>
+                <span title={`${activeResponse.bytesSent} bytes sent`}>
+                  <SizeTag contentLength={activeResponse.body.length} />
+                </span>
+              </>
+            )}
+            {activeResponse.remoteAddr && (
+              <>
+                <span>&bull;</span>
+                <span className="truncate">{activeResponse.remoteAddr}</span>
+              </>
+            )}
+          </HStack>
+          <HStack space={0.5} className="ml-auto">
+            <IconButton
+              title={hexDump ? "Show text" : "Show hexdump"}
+              icon={hexDump ? "text" : "code"}
+              className="m-0.5 text-text-subtle"
+              size="sm"
+              iconSize="md"
+              onClick={() => setHexDump(!hexDump)}
+            />
+            <RecentSocketResponsesDropdown
+              responses={responses}
+              activeResponse={activeResponse}
+              onPinnedResponseId={setPinnedResponseId}
+            />
+          </HStack>
+        </HStack>
+        <div className="grid grid-rows-[auto_minmax(0,1fr)] min-h-0">
+          {activeResponse.error ? (
+            <Banner color="danger" className="mx-3 mt-1 mb-2">
+              {activeResponse.error}
+            </Banner>
+          ) : (
+            <span />
+          )}
+          <SocketResponseBody response={activeResponse} hexDump={hexDump} />
+        </div>
+      </div>
+    </ErrorBoundary>
+  );
+}
+
+function SocketStatusTag({ response }: { response: SocketResponse }) {
+  if (response.error) return <span className="font-mono text-danger">ERROR</span>;
+  if (response.state === "closed") return <span className="font-mono text-success">DONE</span>;
+  return <span className="font-mono text-text-subtle">SENDING</span>;
+}
+
+function SocketResponseBody({ response, hexDump }: { response: SocketResponse; hexDump: boolean }) {
+  const body = useMemo(() => {
+    if (hexDump) {
+      return response.body.length > 0 ? hexy(response.body) : "";
+    }
+    return new TextDecoder("utf-8").decode(Uint8Array.from(response.body));
+  }, [response.body, hexDump]);
+
+  const language = hexDump ? "text" : languageFromContentType(null, body);
+  const formattedBody = useFormatText({ language, text: body, pretty: true });
+
+  if (response.body.length === 0) {
+    return (
+      <EmptyStateText>{isResponseDone(response) ? "No Response Data" : "Waiting"}</EmptyStateText>
+    );
+  }
+
+  return (
+    <Editor
+      language={language}
+      defaultValue={formattedBody ?? ""}
+      wrapLines={false}
+      readOnly={true}
+      stateKey={null}
+    />
+  );
+}
+
+function RecentSocketResponsesDropdown({
+  activeResponse,
+  responses,
+  onPinnedResponseId,
+}: {
+  responses: SocketResponse[];
+  activeResponse: SocketResponse;
+  onPinnedResponseId: (id: string | null) => void;
+}) {
+  const latestResponseId = responses[0]?.id ?? "n/a";
+
+  return (
+    <Dropdown
+      items={[
+        {
+          label: "Clear Response",
+          onSelect: () => deleteModel(activeResponse),
+          disabled: responses.length === 0,
+        },
+        {
+          label: `Clear ${pluralizeCount("Response", responses.length)}`,
+          onSelect: () => deleteSocketResponses(activeResponse.requestId),
+          hidden: responses.length <= 1,
+          disabled: responses.length === 0,
+        },
+        { type: "separator", label: "History" },
+        ...responses.map((r) => ({
+          label: (
+            <HStack space={2}>
+              {formatDistanceToNowStrict(`${r.createdAt}Z`)} ago &bull;{" "}
+              <span className="font-mono text-sm">{r.elapsed}ms</span>
+            </HStack>
+          ),
+          leftSlot: activeResponse.id === r.id ? <Icon icon="check" /> : <Icon icon="empty" />,
+          onSelect: () => onPinnedResponseId(r.id === latestResponseId ? null : r.id),
+        })),
+      ]}
+    >
+      <IconButton
+        title="Show response history"
+        icon={activeResponse.id === latestResponseId ? "history" : "pin"}
+        className="m-0.5 text-text-subtle"
+        size="sm"
+        iconSize="md"
+      />
+    </Dropdown>
+  );
+}
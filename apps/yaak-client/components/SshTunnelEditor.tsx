@@ -0,0 +1,156 @@
+import type { InheritedSshTunnelSetting, SshTunnel } from "@yaakapp-internal/models";
+import { InlineCode } from "@yaakapp-internal/ui";
+import {
+  SettingRowBoolean,
+  SettingRowNumber,
+  SettingRowSelect,
+  SettingRowText,
+  SettingsList,
+  SettingsSection,
+} from "./core/SettingRow";
+
+type TunnelMode = "inherit" | "enabled" | "none";
+
+interface Props {
+  setting: InheritedSshTunnelSetting;
+  onChange: (setting: InheritedSshTunnelSetting) => Promise<void>;
+  /** Label for the option that uses the parent's tunnel, for models that have a parent */
+  inheritLabel?: string;
+}
+
+export function SshTunnelEditor({ setting, onChange, inheritLabel }: Props) {
+  const inherited = inheritLabel != null && !setting.enabled;
+  const tunnel = setting.value;
+
+  const patchTunnel = async (patch: Partial<SshTunnel>) => {
+    await onChange({ enabled: true, value: { ...(tunnel ?? defaultTunnel()), ...patch } });
+  };
+
+  return (
+    <SettingsList className="space-y-8">
+      <SettingsSection title="SSH Tunnel">
+        <SettingRowSelect<TunnelMode>
+          title="SSH tunnel"
+          description="Send HTTP and gRPC requests through an SSH server, like a bastion in front of private APIs."
+          name="sshTunnel"
+          value={inherited ? "inherit" : tunnel == null ? "none" : "enabled"}
+          onChange={async (v) => {
+            if (v === "inherit") {
+              await onChange({ enabled: false, value: tunnel });
+            } else if (v === "enabled") {
+              await onChange({ enabled: true, value: tunnel ?? defaultTunnel() });
+            } else {
+              await onChange({ enabled: true, value: null });
+            }
+          }}
+          options={[
+            ...(inheritLabel != null ? [{ label: inheritLabel, value: "inherit" }] : []),
+            { label: "Custom SSH tunnel", value: "enabled" },
+            { label: "No tunnel", value: "none" },
+          ]}
+          selectClassName="!w-64"
+        />
+      </SettingsSection>
+
+      {!inherited && tunnel != null && (
+        <>
+          <SettingsSection title="Server">
+            <SettingRowBoolean
+              checked={!tunnel.disabled}
+              title="Enable tunnel"
+              description="Temporarily connect directly without losing the configuration."
+              onChange={(enabled) => patchTunnel({ disabled: !enabled })}
+            />
+            <SettingRowText
+              required
+              name="sshHost"
+              title="Host"
+              description="SSH server to connect to. Its key must already be in your known_hosts."
+              value={tunnel.host}
+              placeholder="bastion.example.com"
+              onChange={(host) => patchTunnel({ host })}
+            />
+            <SettingRowNumber
+              name="sshPort"
+              title="Port"
+              description="SSH port on the server."
+              value={tunnel.port ?? 22}
+              validate={(value) => {
+                const port = Number.parseInt(value, 10);
+                return port > 0 && port <= 65535;
+              }}
+              onChange={(port) => patchTunnel({ port })}
+            />
+            <SettingRowText
+              name="sshDestination"
+              title="Destination"
+              description={
+                <>
+                  Comma-separated hosts reached through the tunnel, like{" "}
+                  <InlineCode>api.internal:8443</InlineCode>. Every request is tunneled when empty.
+                </>
+              }
+              value={tunnel.destination}
+              placeholder="api.internal, *.svc.internal"
+              inputWidthClassName="!w-96"
+              onChange={(destination) => patchTunnel({ destination })}
+            />
+          </SettingsSection>
+
+          <SettingsSection title="Authentication">
+            <SettingRowText
+              required
+              name="sshUsername"
+              title="Username"
+              description="User to log in to the SSH server as."
+              value={tunnel.username}
+              placeholder="deploy"
+              onChange={(username) => patchTunnel({ username })}
+            />
+            <SettingRowText
+              name="sshPrivateKeyFile"
+              title="Private key file"
+              description="Key to authenticate with. Leave empty to use a password."
+              value={tunnel.privateKeyFile}
+              placeholder="~/.ssh/id_ed25519"
+              inputWidthClassName="!w-96"
+              onChange={(privateKeyFile) => patchTunnel({ privateKeyFile })}
+            />
+            {tunnel.privateKeyFile.trim() === "" ? (
+              <SettingRowText
+                name="sshPassword"
+                title="Password"
+                description="Password for the SSH user."
+                value={tunnel.password}
+                type="password"
+                onChange={(password) => patchTunnel({ password })}
+              />
+            ) : (
+              <SettingRowText
+                name="sshPassphrase"
+                title="Passphrase"
+                description="Passphrase of an encrypted private key."
+                value={tunnel.passphrase}
+                type="password"
+                onChange={(passphrase) => patchTunnel({ passphrase })}
+              />
+            )}
+          </SettingsSection>
+        </>
+      )}
+    </SettingsList>
+  );
+}
+
+function defaultTunnel(): SshTunnel {
+  return {
+    host: "",
+    port: null,
+    username: "",
+    password: "",
+    privateKeyFile: "",
+    passphrase: "",
+    destination: "",
+    disabled: false,
+  };
+}
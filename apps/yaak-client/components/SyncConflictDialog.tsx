@@ -0,0 +1,72 @@
+import type { ConflictResolution, MergeSide, SyncOp } from "@yaakapp-internal/sync";
+import { resolveSyncConflict } from "@yaakapp-internal/sync";
+import { InlineCode, VStack } from "@yaakapp-internal/ui";
+import { useState } from "react";
+import { resolvedModelNameWithFolders } from "../lib/resolvedModelName";
+import { Button } from "./core/Button";
+import { SegmentedControl } from "./core/SegmentedControl";
+
+type ConflictOp = Extract<SyncOp, { type: "conflict" }>;
+
+interface Props {
+  workspaceId: string;
+  syncDir: string;
+  op: ConflictOp;
+  onDone: () => void;
+}
+
+export function SyncConflictDialog({ workspaceId, syncDir, op, onDone }: Props) {
+  const [sides, setSides] = useState<Record<string, MergeSide>>({});
+
+  return (
+    <VStack space={4} className="mb-4">
+      <p>
+        <InlineCode>{resolvedModelNameWithFolders(op.fs.model)}</InlineCode> was changed in both the
+        workspace and the directory. Choose which value to keep for each field.
+      </p>
+      {op.merge.conflicts.map((conflict) => {
+        const key = conflict.path.join(".");
+        return (
+          <VStack key={key} space={1}>
+            <SegmentedControl<MergeSide>
+              name={key}
+              label={key}
+              value={sides[key] ?? "ours"}
+              onChange={(side) => setSides((s) => ({ ...s, [key]: side }))}
+              options={[
+                { value: "ours", label: "Workspace" },
+                { value: "theirs", label: "Directory" },
+              ]}
+            />
+            <pre className="text-sm text-text-subtle whitespace-pre-wrap break-all">
+              {formatValue((sides[key] ?? "ours") === "ours" ? conflict.ours : conflict.theirs)}
+            </pre>
+          </VStack>
+        );
+      })}
+      <footer className="flex flex-row-reverse items-center gap-3">
+        <Button
+          color="primary"
+          onClick={async () => {
+            const resolutions: ConflictResolution[] = op.merge.conflicts.map((c) => ({
+              path: c.path,
+              side: sides[c.path.join(".")] ?? "ours",
+            }));
+            await resolveSyncConflict(workspaceId, syncDir, op, resolutions);
+            onDone();
+          }}
+        >
+          Resolve
+        </Button>
+        <Button onClick={onDone} color="secondary">
+          Skip
+        </Button>
+      </footer>
+    </VStack>
+  );
+}
+
+function formatValue(value: unknown) {
+  if (value == null) return "(removed)";
+  return typeof value === "string" ? value : JSON.stringify(value, null, 2);
+}
@@ -1,4 +1,4 @@
-import type { WebsocketRequest } from "@yaakapp-internal/models";
+import type { WebsocketMessageFormat, WebsocketRequest } from "@yaakapp-internal/models";
 import { patchModel } from "@yaakapp-internal/models";
 import type { GenericCompletionOption } from "@yaakapp-internal/plugins";
 import { closeWebsocket, connectWebsocket, sendWebsocket } from "@yaakapp-internal/ws";
@@ -29,12 +29,14 @@ import { Editor } from "./core/Editor/LazyEditor";
 import { IconButton } from "./core/IconButton";
 import type { Pair } from "./core/PairEditor";
 import { PlainInput } from "./core/PlainInput";
+import type { RadioDropdownItem } from "./core/RadioDropdown";
 import type { TabItem, TabsRef } from "./core/Tabs/Tabs";
 import { setActiveTab, TabContent, Tabs } from "./core/Tabs/Tabs";
 import { HeadersEditor } from "./HeadersEditor";
 import { HttpAuthenticationEditor } from "./HttpAuthenticationEditor";
 import { MarkdownEditor } from "./MarkdownEditor";
 import { countOverriddenSettings, ModelSettingsEditor } from "./ModelSettingsEditor";
+import { SelectFile } from "./SelectFile";
 import { UrlBar } from "./UrlBar";
 import { UrlParametersEditor } from "./UrlParameterEditor";
 
@@ -63,6 +65,15 @@ const nonActiveRequestUrlsAtom = atom((get) => {
 
 const memoNotActiveRequestUrlsAtom = deepEqualAtom(nonActiveRequestUrlsAtom);
 
+const MESSAGE_FORMAT_ITEMS: RadioDropdownItem<string | null>[] = [
+  { type: "separator", label: "Text Frame" },
+  { label: "Text", shortLabel: "Message", value: "text" },
+  { type: "separator", label: "Binary Frame" },
+  { label: "Hex", value: "hex" },
+  { label: "Base64", value: "base64" },
+  { label: "Binary File", value: "file" },
+];
+
 export function WebsocketRequestPane({ style, fullHeight, className, activeRequest }: Props) {
   const activeRequestId = activeRequest.id;
   const tabsRef = useRef<TabsRef>(null);
@@ -103,8 +114,13 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
     return [
       {
         value: TAB_MESSAGE,
-        label: "Message",
-      } as TabItem,
+        options: {
+          value: activeRequest.messageFormat,
+          items: MESSAGE_FORMAT_ITEMS,
+          onChange: (messageFormat) =>
+            patchModel(activeRequest, { messageFormat: messageFormat as WebsocketMessageFormat }),
+        },
+      },
       {
         value: TAB_PARAMS,
         rightSlot: <CountBadge count={urlParameterPairs.length} />,
@@ -122,7 +138,7 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
         label: "Info",
       },
     ];
-  }, [authTab, headersTab, numSettingsOverrides, urlParameterPairs.length]);
+  }, [activeRequest, authTab, headersTab, numSettingsOverrides, urlParameterPairs.length]);
 
   const { activeResponse } = usePinnedHttpResponse(activeRequestId);
   const { mutate: cancelResponse } = useCancelHttpResponse(activeResponse?.id ?? null);
@@ -194,7 +210,10 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
     [activeRequest, activeRequestId, forceParamsRefresh, forceUrlRefresh],
   );
 
-  const messageLanguage = languageFromContentType(null, activeRequest.message);
+  const messageLanguage =
+    activeRequest.messageFormat === "text"
+      ? languageFromContentType(null, activeRequest.message)
+      : "text";
 
   const isLoading = connection !== null && connection.state !== "closed";
 
@@ -262,17 +281,30 @@ export function WebsocketRequestPane({ style, fullHeight, className, activeReque
               />
             </TabContent>
             <TabContent value={TAB_MESSAGE}>
-              <Editor
-                forceUpdateKey={forceUpdateKey}
-                autocompleteFunctions
-                autocompleteVariables
-                placeholder="..."
-                heightMode={fullHeight ? "full" : "auto"}
-                defaultValue={activeRequest.message}
-                language={messageLanguage}
-                onChange={(message) => patchModel(activeRequest, { message })}
-                stateKey={`json.${activeRequest.id}`}
-              />
+              {activeRequest.messageFormat === "file" ? (
+                <SelectFile
+                  filePath={activeRequest.messageFile}
+                  onChange={({ filePath }) => patchModel(activeRequest, { messageFile: filePath })}
+                />
+              ) : (
+                <Editor
+                  forceUpdateKey={forceUpdateKey}
+                  autocompleteFunctions
+                  autocompleteVariables
+                  placeholder={
+                    activeRequest.messageFormat === "hex"
+                      ? "48 65 6c 6c 6f"
+                      : activeRequest.messageFormat === "base64"
+                        ? "SGVsbG8="
+                        : "..."
+                  }
+                  heightMode={fullHeight ? "full" : "auto"}
+                  defaultValue={activeRequest.message}
+                  language={messageLanguage}
+                  onChange={(message) => patchModel(activeRequest, { message })}
+                  stateKey={`json.${activeRequest.id}`}
+                />
+              )}
             </TabContent>
             <TabContent value={TAB_SETTINGS}>
               <ModelSettingsEditor model={activeRequest} />
@@ -1,5 +1,7 @@
+import { formatSize } from "@yaakapp-internal/lib/formatSize";
 import type { WebsocketEvent, WebsocketRequest } from "@yaakapp-internal/models";
 import { HStack, Icon, LoadingIcon, VStack } from "@yaakapp-internal/ui";
+import { replayWebsocketEvent } from "@yaakapp-internal/ws";
 import { hexy } from "hexy";
 import { useAtomValue } from "jotai";
 import { useMemo, useState } from "react";
@@ -11,7 +13,10 @@ import {
   useWebsocketEvents,
 } from "../hooks/usePinnedWebsocketConnection";
 import { useStateWithDeps } from "../hooks/useStateWithDeps";
+import { guessBinaryType } from "../lib/binaryType";
 import { languageFromContentType } from "../lib/contentType";
+import { fireAndForget } from "../lib/fireAndForget";
+import { describeSocketIoFrame } from "../lib/socketIo";
 import { Button } from "./core/Button";
 import { Editor } from "./core/Editor/LazyEditor";
 import { type EventDetailAction, EventDetailHeader, EventViewer } from "./core/EventViewer";
@@ -35,6 +40,10 @@ export function WebsocketResponsePane({ activeRequest }: Props) {
   const connections = useAtomValue(activeWebsocketConnectionsAtom);
   const events = useWebsocketEvents(activeConnection?.id ?? null);
 
+  // Sent messages are replayed on the latest connection, even when viewing an older one
+  const latestConnection = connections[0] ?? null;
+  const replayConnectionId = latestConnection?.state === "connected" ? latestConnection.id : null;
+
   if (activeConnection == null) {
     return (
       <HotkeyList hotkeys={["request.send", "model.create", "sidebar.focus", "url_bar.focus"]} />
@@ -71,7 +80,12 @@ export function WebsocketResponsePane({ activeRequest }: Props) {
         splitLayoutStorageKey="websocket_events"
         defaultRatio={0.4}
         renderRow={({ event, isActive, onClick }) => (
-          <WebsocketEventRow event={event} isActive={isActive} onClick={onClick} />
+          <WebsocketEventRow
+            event={event}
+            isActive={isActive}
+            socketIo={activeRequest.protocol === "socket_io"}
+            onClick={onClick}
+          />
         )}
         renderDetail={({ event, index, onClose }) => (
           <WebsocketEventDetail
@@ -82,6 +96,7 @@ export function WebsocketResponsePane({ activeRequest }: Props) {
             showingLarge={showingLarge}
             setShowLarge={setShowLarge}
             setShowingLarge={setShowingLarge}
+            replayConnectionId={replayConnectionId}
             onClose={onClose}
           />
         )}
@@ -93,34 +108,50 @@ export function WebsocketResponsePane({ activeRequest }: Props) {
 function WebsocketEventRow({
   event,
   isActive,
+  socketIo,
   onClick,
 }: {
   event: WebsocketEvent;
   isActive: boolean;
+  socketIo: boolean;
   onClick: () => void;
 }) {
   const { message: messageBytes, isServer, messageType } = event;
   const message = messageBytes
     ? new TextDecoder("utf-8").decode(Uint8Array.from(messageBytes))
     : "";
+  const socketIoSummary =
+    socketIo && messageType === "text" ? describeSocketIoFrame(message) : null;
 
   const iconColor =
-    messageType === "close" || messageType === "open" ? "secondary" : isServer ? "info" : "primary";
+    messageType === "close" || messageType === "open" || messageType === "reconnect"
+      ? "secondary"
+      : isServer
+        ? "info"
+        : "primary";
 
   const icon =
-    messageType === "close" || messageType === "open"
-      ? "info"
-      : isServer
-        ? "arrow_big_down_dash"
-        : "arrow_big_up_dash";
+    messageType === "reconnect"
+      ? "refresh"
+      : messageType === "close" || messageType === "open"
+        ? "info"
+        : isServer
+          ? "arrow_big_down_dash"
+          : "arrow_big_up_dash";
 
   const content =
     messageType === "close" ? (
       "Disconnected from server"
     ) : messageType === "open" ? (
       "Connected to server"
+    ) : messageType === "reconnect" ? (
+      message
     ) : message === "" ? (
       <em className="italic text-text-subtlest">No content</em>
+    ) : messageType === "binary" ? (
+      <span className="text-xs font-mono text-text-subtle">{binarySummary(messageBytes)}</span>
+    ) : socketIoSummary != null ? (
+      <span className="text-xs">{socketIoSummary.slice(0, 1000)}</span>
     ) : (
       <span className="text-xs">{message.slice(0, 1000)}</span>
     );
@@ -144,6 +175,7 @@ function WebsocketEventDetail({
   showingLarge,
   setShowLarge,
   setShowingLarge,
+  replayConnectionId,
   onClose,
 }: {
   event: WebsocketEvent;
@@ -153,6 +185,7 @@ function WebsocketEventDetail({
   showingLarge: boolean;
   setShowLarge: (v: boolean) => void;
   setShowingLarge: (v: boolean) => void;
+  replayConnectionId: string | null;
   onClose: () => void;
 }) {
   const message = useMemo(() => {
@@ -165,23 +198,41 @@ function WebsocketEventDetail({
   const language = languageFromContentType(null, message);
   const formattedMessage = useFormatText({ language, text: message, pretty: true });
 
+  const direction = event.isServer ? "Received" : "Sent";
   const title =
     event.messageType === "close"
       ? "Connection Closed"
       : event.messageType === "open"
         ? "Connection Open"
-        : `Message ${event.isServer ? "Received" : "Sent"}`;
-
-  const actions: EventDetailAction[] =
-    message !== ""
-      ? [
-          {
-            key: "toggle-hexdump",
-            label: hexDump ? "Show Message" : "Show Hexdump",
-            onClick: () => setHexDump(!hexDump),
-          },
-        ]
-      : [];
+        : event.messageType === "reconnect"
+          ? "Reconnect"
+          : event.messageType === "binary"
+            ? `Binary Message ${direction} (${binarySummary(event.message)})`
+            : `Message ${direction}`;
+
+  const actions: EventDetailAction[] = [];
+  if (
+    replayConnectionId != null &&
+    !event.isServer &&
+    (event.messageType === "text" || event.messageType === "binary")
+  ) {
+    actions.push({
+      key: "replay",
+      label: "Replay",
+      icon: <Icon icon="rotate_ccw" size="sm" />,
+      onClick: () =>
+        fireAndForget(
+          replayWebsocketEvent({ connectionId: replayConnectionId, eventId: event.id }),
+        ),
+    });
+  }
+  if (message !== "") {
+    actions.push({
+      key: "toggle-hexdump",
+      label: hexDump ? "Show Message" : "Show Hexdump",
+      onClick: () => setHexDump(!hexDump),
+    });
+  }
 
   return (
     <div className="h-full grid grid-rows-[auto_minmax(0,1fr)]">
@@ -227,3 +278,7 @@ function WebsocketEventDetail({
     </div>
   );
 }
+
+function binarySummary(bytes: number[]) {
+  return `${formatSize(bytes.length)}, ${guessBinaryType(bytes)}`;
+}
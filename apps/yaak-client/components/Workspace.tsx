@@ -40,8 +40,10 @@ import { ErrorBoundary } from "./ErrorBoundary";
 import { FolderLayout } from "./FolderLayout";
 import { GrpcConnectionLayout } from "./GrpcConnectionLayout";
 import { HttpRequestLayout } from "./HttpRequestLayout";
+import { MqttRequestLayout } from "./MqttRequestLayout";
 import Sidebar from "./Sidebar";
 import { SidebarActions } from "./SidebarActions";
+import { SocketRequestLayout } from "./SocketRequestLayout";
 import { WebsocketRequestLayout } from "./WebsocketRequestLayout";
 import { WorkspaceHeader } from "./WorkspaceHeader";
 
@@ -176,6 +178,12 @@ function WorkspaceBody() {
   if (activeRequest?.model === "websocket_request") {
     return <WebsocketRequestLayout style={body} activeRequest={activeRequest} />;
   }
+  if (activeRequest?.model === "mqtt_request") {
+    return <MqttRequestLayout style={body} activeRequest={activeRequest} />;
+  }
+  if (activeRequest?.model === "socket_request") {
+    return <SocketRequestLayout style={body} activeRequest={activeRequest} />;
+  }
   if (activeRequest?.model === "http_request") {
     return <HttpRequestLayout activeRequest={activeRequest} style={body} />;
   }
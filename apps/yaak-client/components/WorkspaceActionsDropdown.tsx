@@ -1,9 +1,17 @@
-import { open } from "@tauri-apps/plugin-dialog";
+import { open, save } from "@tauri-apps/plugin-dialog";
 import { revealItemInDir } from "@tauri-apps/plugin-opener";
-import { getModel, settingsAtom, workspacesAtom } from "@yaakapp-internal/models";
+import {
+  duplicateModel,
+  getModel,
+  settingsAtom,
+  workspacesAtom,
+} from "@yaakapp-internal/models";
 import classNames from "classnames";
+import slugify from "slugify";
 import { useAtomValue } from "jotai";
 import { memo, useCallback, useMemo } from "react";
+import { openMockServerDialog } from "../commands/mockServer";
+import { openMonitorsDialog } from "../commands/monitors";
 import { openWorkspaceFromSyncDir } from "../commands/openWorkspaceFromSyncDir";
 import { openWorkspaceSettings } from "../commands/openWorkspaceSettings";
 import { switchWorkspace } from "../commands/switchWorkspace";
@@ -18,11 +26,13 @@ import { useWorkspaceActions } from "../hooks/useWorkspaceActions";
 import { showDialog } from "../lib/dialog";
 import { jotaiStore } from "../lib/jotai";
 import { revealInFinderText } from "../lib/reveal";
+import { invokeCmd } from "../lib/tauri";
+import { showToast } from "../lib/toast";
 import { CloneGitRepositoryDialog } from "./CloneGitRepositoryDialog";
 import type { ButtonProps } from "./core/Button";
 import { Button } from "./core/Button";
 import type { DropdownItem } from "./core/Dropdown";
-import { Icon } from "@yaakapp-internal/ui";
+import { Icon, InlineCode } from "@yaakapp-internal/ui";
 import type { RadioDropdownItem } from "./core/RadioDropdown";
 import { RadioDropdown } from "./core/RadioDropdown";
 import { SwitchWorkspaceDialog } from "./SwitchWorkspaceDialog";
@@ -49,6 +59,27 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
     });
   }, []);
 
+  const exportWorkspaceAs = useCallback(
+    async (cmd: "cmd_export_postman" | "cmd_export_openapi", name: string, suffix: string) => {
+      if (workspace == null) return;
+      const exportPath = await save({
+        title: `Export ${name}`,
+        defaultPath: `${slugify(workspace.name, { lower: true })}.${suffix}.json`,
+        filters: [{ name, extensions: ["json"] }],
+      });
+      if (exportPath == null) return;
+      await invokeCmd(cmd, { workspaceId: workspace.id, exportPath });
+      showToast({
+        message: (
+          <>
+            {name} exported to <InlineCode>{exportPath}</InlineCode>
+          </>
+        ),
+      });
+    },
+    [workspace],
+  );
+
   const { workspaceItems, itemsAfter, itemsBefore } = useMemo<{
     workspaceItems: RadioDropdownItem[];
     itemsAfter: DropdownItem[];
@@ -108,6 +139,40 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
         hotKeyAction: "workspace_settings.show",
         onSelect: openWorkspaceSettings,
       },
+      {
+        label: "Duplicate Workspace",
+        leftSlot: <Icon icon="copy" />,
+        onSelect: async () => {
+          if (workspace == null) return;
+          const workspaceId = await duplicateModel(workspace);
+          switchWorkspace.mutate({ workspaceId, inNewWindow: false });
+        },
+      },
+      {
+        label: "Export As",
+        leftSlot: <Icon icon="folder_output" />,
+        submenu: [
+          {
+            label: "Postman Collection",
+            onSelect: () =>
+              exportWorkspaceAs("cmd_export_postman", "Postman Collection", "postman_collection"),
+          },
+          {
+            label: "OpenAPI",
+            onSelect: () => exportWorkspaceAs("cmd_export_openapi", "OpenAPI Document", "openapi"),
+          },
+        ],
+      },
+      {
+        label: "Monitors",
+        leftSlot: <Icon icon="alarm_clock" />,
+        onSelect: openMonitorsDialog,
+      },
+      {
+        label: "Mock Server",
+        leftSlot: <Icon icon="flask" />,
+        onSelect: openMockServerDialog,
+      },
       {
         label: revealInFinderText,
         hidden: workspaceMeta == null || workspaceMeta.settingSyncDir == null,
@@ -132,6 +197,7 @@ export const WorkspaceActionsDropdown = memo(function WorkspaceActionsDropdown({
     deleteSendHistory,
     createWorkspace,
     openCloneGitRepositoryDialog,
+    exportWorkspaceAs,
     workspace?.id,
     workspace,
     workspaceActions.map,
@@ -7,17 +7,25 @@ import { useInheritedHeaders } from "../hooks/useInheritedHeaders";
 import { deleteModelWithConfirm } from "../lib/deleteModelWithConfirm";
 import { showDialog } from "../lib/dialog";
 import { router } from "../lib/router";
+import { CaCertificatesEditor } from "./CaCertificatesEditor";
 import { CopyIconButton } from "./CopyIconButton";
 import { Button } from "./core/Button";
 import { CountBadge } from "./core/CountBadge";
 import { PlainInput } from "./core/PlainInput";
-import { SettingsList, SettingsSection } from "./core/SettingRow";
+import {
+  ModelSettingRowBoolean,
+  SettingRowNumber,
+  SettingsList,
+  SettingsSection,
+} from "./core/SettingRow";
 import { TabContent, Tabs } from "./core/Tabs/Tabs";
 import { DnsOverridesEditor } from "./DnsOverridesEditor";
 import { HeadersEditor } from "./HeadersEditor";
 import { HttpAuthenticationEditor } from "./HttpAuthenticationEditor";
 import { MarkdownEditor } from "./MarkdownEditor";
 import { ModelSettingsEditor } from "./ModelSettingsEditor";
+import { ProxySettingsEditor } from "./ProxySettingsEditor";
+import { SshTunnelEditor } from "./SshTunnelEditor";
 import { SyncToFilesystemSetting } from "./SyncToFilesystemSetting";
 import { WorkspaceEncryptionSetting } from "./WorkspaceEncryptionSetting";
 
@@ -28,17 +36,23 @@ interface Props {
 }
 
 const TAB_AUTH = "auth";
+const TAB_CERTIFICATES = "certificates";
 const TAB_DNS = "dns";
 const TAB_HEADERS = "headers";
 const TAB_GENERAL = "general";
+const TAB_PROXY = "proxy";
 const TAB_SETTINGS = "settings";
+const TAB_SSH = "ssh";
 
 export type WorkspaceSettingsTab =
   | typeof TAB_AUTH
+  | typeof TAB_CERTIFICATES
   | typeof TAB_DNS
   | typeof TAB_HEADERS
   | typeof TAB_GENERAL
-  | typeof TAB_SETTINGS;
+  | typeof TAB_PROXY
+  | typeof TAB_SETTINGS
+  | typeof TAB_SSH;
 
 const DEFAULT_TAB: WorkspaceSettingsTab = TAB_GENERAL;
 
@@ -87,6 +101,16 @@ export function WorkspaceSettingsDialog({ workspaceId, hide, tab }: Props) {
               <CountBadge count={workspace.settingDnsOverrides.length} />
             ) : null,
         },
+        { value: TAB_PROXY, label: "Proxy" },
+        { value: TAB_SSH, label: "SSH Tunnel" },
+        {
+          value: TAB_CERTIFICATES,
+          label: "Certificates",
+          rightSlot:
+            workspace.settingCaCertificates.length > 0 ? (
+              <CountBadge count={workspace.settingCaCertificates.length} />
+            ) : null,
+        },
       ]}
       storageKey="workspace_settings_tabs"
     >
@@ -112,9 +136,54 @@ export function WorkspaceSettingsDialog({ workspaceId, hide, tab }: Props) {
               onCreateNewWorkspace={hide}
               onChange={({ filePath }) => patchModel(workspaceMeta, { settingSyncDir: filePath })}
             />
+            {workspaceMeta.settingSyncDir && (
+              <ModelSettingRowBoolean
+                model={workspaceMeta}
+                modelKey="settingSyncExcludeSecretValues"
+                title="Exclude secret values"
+                description="Write secret environment variables to the sync directory without their values."
+              />
+            )}
             <WorkspaceEncryptionSetting layout="settings" size="xs" />
           </SettingsSection>
           <ModelSettingsEditor model={workspace} showSectionTitles />
+          <SettingsSection title="Response History">
+            <SettingRowNumber
+              name="settingMaxResponsesPerRequest"
+              title="Responses per request"
+              description="Keep at most this many responses for each request. Set to 0 for the default of 20."
+              value={workspace.settingMaxResponsesPerRequest ?? 0}
+              onChange={(value) =>
+                patchModel(workspace, { settingMaxResponsesPerRequest: value > 0 ? value : null })
+              }
+            />
+            <SettingRowNumber
+              name="settingResponseRetentionDays"
+              title="Delete after days"
+              description="Delete responses older than this many days. Set to 0 to keep them."
+              value={workspace.settingResponseRetentionDays ?? 0}
+              onChange={(value) =>
+                patchModel(workspace, { settingResponseRetentionDays: value > 0 ? value : null })
+              }
+            />
+            <ModelSettingRowBoolean
+              model={workspace}
+              modelKey="settingIndexResponseBodies"
+              title="Search response bodies"
+              description="Add response bodies to the search index so workspace search can find them."
+            />
+            <SettingRowNumber
+              name="settingContentAddressedBodyThreshold"
+              title="Deduplicate bodies over bytes"
+              description="Store identical response bodies larger than this many bytes only once. Set to 0 for the default of 4 MB."
+              value={workspace.settingContentAddressedBodyThreshold ?? 0}
+              onChange={(value) =>
+                patchModel(workspace, {
+                  settingContentAddressedBodyThreshold: value > 0 ? value : null,
+                })
+              }
+            />
+          </SettingsSection>
         </SettingsList>
       </TabContent>
       <TabContent value={TAB_GENERAL} className="overflow-y-auto h-full px-4">
@@ -172,6 +241,30 @@ export function WorkspaceSettingsDialog({ workspaceId, hide, tab }: Props) {
       <TabContent value={TAB_DNS} className="overflow-y-auto h-full px-4">
         <DnsOverridesEditor workspace={workspace} />
       </TabContent>
+      <TabContent value={TAB_CERTIFICATES} className="overflow-y-auto h-full px-4">
+        <CaCertificatesEditor
+          certificates={workspace.settingCaCertificates}
+          onChange={(settingCaCertificates) => patchModel(workspace, { settingCaCertificates })}
+          description="Trust additional certificate authorities, like an internal corporate CA, when validating server certificates for HTTP, gRPC, and WebSocket requests in this workspace. Folders and gRPC requests can use their own instead, and validation can still be turned off in their settings."
+        />
+      </TabContent>
+      <TabContent value={TAB_PROXY} className="overflow-y-auto h-full px-4">
+        <ProxySettingsEditor
+          proxy={workspace.settingProxy}
+          automaticLabel="Use global proxy settings"
+          onChange={async (settingProxy) => {
+            await patchModel(workspace, { settingProxy });
+          }}
+        />
+      </TabContent>
+      <TabContent value={TAB_SSH} className="overflow-y-auto h-full px-4">
+        <SshTunnelEditor
+          setting={{ enabled: true, value: workspace.settingSshTunnel }}
+          onChange={async ({ value }) => {
+            await patchModel(workspace, { settingSshTunnel: value });
+          }}
+        />
+      </TabContent>
     </Tabs>
   );
 }
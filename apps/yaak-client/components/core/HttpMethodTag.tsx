@@ -1,11 +1,17 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { settingsAtom } from "@yaakapp-internal/models";
 import classNames from "classnames";
 import { useAtomValue } from "jotai";
 import { memo } from "react";
 
 interface Props {
-  request: HttpRequest | GrpcRequest | WebsocketRequest;
+  request: HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest;
   className?: string;
   short?: boolean;
   noAlias?: boolean;
@@ -23,6 +29,9 @@ const methodNames: Record<string, string> = {
   graphql: "GQL",
   grpc: "GRPC",
   websocket: "WS",
+  mqtt: "MQTT",
+  tcp: "TCP",
+  udp: "UDP",
 };
 
 export const HttpMethodTag = memo(function HttpMethodTag({
@@ -38,7 +47,11 @@ export const HttpMethodTag = memo(function HttpMethodTag({
         ? "grpc"
         : request.model === "websocket_request"
           ? "websocket"
-          : request.method;
+          : request.model === "mqtt_request"
+            ? "mqtt"
+            : request.model === "socket_request"
+              ? request.protocol
+              : request.method;
 
   return <HttpMethodTagRaw method={method} className={className} short={short} />;
 });
@@ -72,6 +85,9 @@ export function HttpMethodTagRaw({
         !colored && "text-text-subtle",
         colored && m === "GRAPHQL" && "text-info",
         colored && m === "WEBSOCKET" && "text-info",
+        colored && m === "MQTT" && "text-info",
+        colored && m === "TCP" && "text-info",
+        colored && m === "UDP" && "text-info",
         colored && m === "GRPC" && "text-info",
         colored && m === "QUERY" && "text-text-subtle",
         colored && m === "OPTIONS" && "text-info",
@@ -1,5 +1,6 @@
 import type { HttpResponse } from "@yaakapp-internal/models";
 import { useEffect, useRef, useState } from "react";
+import { isResponseDone } from "../../lib/model_util";
 
 interface Props {
   response: HttpResponse;
@@ -12,7 +13,7 @@ export function HttpResponseDurationTag({ response }: Props) {
   // Calculate the duration of the response for use when the response hasn't finished yet
   useEffect(() => {
     clearInterval(timeout.current);
-    if (response.state === "closed") return;
+    if (isResponseDone(response)) return;
     timeout.current = setInterval(() => {
       setFallbackElapsed(Date.now() - new Date(`${response.createdAt}Z`).getTime());
     }, 100);
@@ -20,9 +21,10 @@ export function HttpResponseDurationTag({ response }: Props) {
   }, [response.createdAt, response.state]);
 
   const dnsValue = response.elapsedDns > 0 ? formatMillis(response.elapsedDns) : "--";
-  const title = `DNS: ${dnsValue}\nHEADER: ${formatMillis(response.elapsedHeaders)}\nTOTAL: ${formatMillis(response.elapsed)}`;
+  const connectValue = response.elapsedConnect > 0 ? formatMillis(response.elapsedConnect) : "--";
+  const title = `DNS: ${dnsValue}\nCONNECT: ${connectValue}\nHEADER: ${formatMillis(response.elapsedHeaders)}\nTOTAL: ${formatMillis(response.elapsed)}`;
 
-  const elapsed = response.state === "closed" ? response.elapsed : fallbackElapsed;
+  const elapsed = isResponseDone(response) ? response.elapsed : fallbackElapsed;
 
   return (
     <span className="font-mono" title={title}>
@@ -32,6 +32,9 @@ export function HttpStatusTagRaw({
   if (state === "initialized") {
     label = short ? "CONN" : "CONNECTING";
     colorClass = "text-text-subtle";
+  } else if (state === "timed_out") {
+    label = short ? "TIME" : "TIMED OUT";
+    colorClass = "text-danger";
   } else if (statusN < 100) {
     label = short ? "ERR" : "ERROR";
     colorClass = "text-danger";
@@ -43,6 +43,7 @@ export interface PairEditorHandle {
 export type PairEditorProps = {
   allowFileValues?: boolean;
   allowMultilineValues?: boolean;
+  allowSecretValues?: boolean;
   className?: string;
   forcedEnvironmentId?: string;
   forceUpdateKey?: string;
@@ -73,6 +74,7 @@ export type Pair = {
   filename?: string;
   isFile?: boolean;
   readOnlyName?: boolean;
+  secret?: boolean;
 };
 
 export type PairWithId = Pair & {
@@ -85,6 +87,7 @@ const MAX_INITIAL_PAIRS = 30;
 export function PairEditor({
   allowFileValues,
   allowMultilineValues,
+  allowSecretValues,
   className,
   forcedEnvironmentId,
   forceUpdateKey,
@@ -300,6 +303,7 @@ export function PairEditor({
                   setRef={initPairEditorRow}
                   allowFileValues={allowFileValues}
                   allowMultilineValues={allowMultilineValues}
+                  allowSecretValues={allowSecretValues}
                   className="py-1"
                   forcedEnvironmentId={forcedEnvironmentId}
                   forceUpdateKey={localForceUpdateKey}
@@ -380,6 +384,7 @@ type PairEditorRowProps = {
   PairEditorProps,
   | "allowFileValues"
   | "allowMultilineValues"
+  | "allowSecretValues"
   | "forcedEnvironmentId"
   | "forceUpdateKey"
   | "nameAutocomplete"
@@ -404,6 +409,7 @@ interface RowHandle {
 export function PairEditorRow({
   allowFileValues,
   allowMultilineValues,
+  allowSecretValues,
   className,
   disableDrag,
   disabled,
@@ -493,6 +499,20 @@ export function PairEditorRow({
     [onChange, pair],
   );
 
+  // Saved secrets can't be revealed, so they're shown empty and typing replaces them
+  const isEncryptedSecret = !!pair.secret && pair.value.startsWith("YENC_");
+
+  // An encrypted value is meaningless once unmarked, so it's cleared for the user to re-enter
+  const handleToggleSecret = useCallback(
+    () =>
+      onChange?.({
+        ...pair,
+        secret: !pair.secret,
+        value: isEncryptedSecret ? "" : pair.value,
+      }),
+    [isEncryptedSecret, onChange, pair],
+  );
+
   const handleChangeValueFilename = useMemo(
     () => (filename: string) => onChange?.({ ...pair, filename }),
     [onChange, pair],
@@ -523,13 +543,25 @@ export function PairEditorRow({
         onSelect: handleEditMultiLineValue,
         hidden: !allowMultilineValues,
       },
+      {
+        label: pair.secret ? "Unmark Secret" : "Mark as Secret",
+        onSelect: handleToggleSecret,
+        hidden: !allowSecretValues,
+      },
       {
         label: "Delete",
         onSelect: handleDelete,
         color: "danger",
       },
     ],
-    [allowMultilineValues, handleDelete, handleEditMultiLineValue],
+    [
+      allowMultilineValues,
+      allowSecretValues,
+      handleDelete,
+      handleEditMultiLineValue,
+      handleToggleSecret,
+      pair.secret,
+    ],
   );
 
   const { attributes, listeners, setNodeRef: setDraggableRef } = useDraggable({ id: pair.id });
@@ -646,13 +678,21 @@ export function PairEditorRow({
               validate={valueValidate}
               forcedEnvironmentId={forcedEnvironmentId}
               forceUpdateKey={forceUpdateKey}
-              defaultValue={pair.value}
+              defaultValue={isEncryptedSecret ? "" : pair.value}
               label="Value"
               name={`value[${index}]`}
               onChange={handleChangeValueText}
               onFocus={handleFocusValue}
-              type={isLast ? "text" : typeof valueType === "function" ? valueType(pair) : valueType}
-              placeholder={valuePlaceholder ?? "value"}
+              type={
+                isLast
+                  ? "text"
+                  : pair.secret
+                    ? "password"
+                    : typeof valueType === "function"
+                      ? valueType(pair)
+                      : valueType
+              }
+              placeholder={isEncryptedSecret ? "encrypted" : (valuePlaceholder ?? "value")}
               autocomplete={valueAutocomplete?.(pair.name)}
               autocompleteFunctions={valueAutocompleteFunctions}
               autocompleteVariables={valueAutocompleteVariablesFiltered}
@@ -38,16 +38,21 @@ function GraphQLEditorInner({ request, onChange, baseRequest, ...extraEditorProp
   const [currentBody, setCurrentBody] = useStateWithDeps<{
     query: string;
     variables: string | undefined;
+    operationName?: string;
   }>(() => {
     // Migrate text bodies to GraphQL format
     // NOTE: This is how GraphQL used to be stored
     if ("text" in request.body) {
       const b = tryParseJson(request.body.text, {});
       const variables = JSON.stringify(b.variables || undefined, null, 2);
-      return { query: b.query ?? "", variables };
+      return { query: b.query ?? "", variables, operationName: b.operationName };
     }
 
-    return { query: request.body.query ?? "", variables: request.body.variables ?? "" };
+    return {
+      query: request.body.query ?? "",
+      variables: request.body.variables ?? "",
+      operationName: request.body.operationName,
+    };
   }, [extraEditorProps.forceUpdateKey]);
 
   const [isDocOpenRecord, setGraphqlDocStateAtomValue] = useAtom(showGraphQLDocExplorerAtom);
@@ -55,8 +60,8 @@ function GraphQLEditorInner({ request, onChange, baseRequest, ...extraEditorProp
 
   const handleChangeQuery = useCallback(
     (query: string) => {
-      setCurrentBody(({ variables }) => {
-        const newBody = { query, variables };
+      setCurrentBody(({ variables, operationName }) => {
+        const newBody = { query, variables, operationName };
         onChange(newBody);
         return newBody;
       });
@@ -66,8 +71,8 @@ function GraphQLEditorInner({ request, onChange, baseRequest, ...extraEditorProp
 
   const handleChangeVariables = useCallback(
     (variables: string) => {
-      setCurrentBody(({ query }) => {
-        const newBody = { query, variables: variables || undefined };
+      setCurrentBody(({ query, operationName }) => {
+        const newBody = { query, variables: variables || undefined, operationName };
         onChange(newBody);
         return newBody;
       });
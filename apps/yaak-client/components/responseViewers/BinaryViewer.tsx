@@ -1,6 +1,6 @@
 import type { HttpResponse } from "@yaakapp-internal/models";
 import { useSaveResponse } from "../../hooks/useSaveResponse";
-import { getContentTypeFromHeaders } from "../../lib/model_util";
+import { getContentTypeFromHeaders, isResponseDone } from "../../lib/model_util";
 import { Button } from "../core/Button";
 import { Banner, InlineCode, LoadingIcon } from "@yaakapp-internal/ui";
 import { EmptyStateText } from "../EmptyStateText";
@@ -14,7 +14,7 @@ export function BinaryViewer({ response }: Props) {
   const contentType = getContentTypeFromHeaders(response.headers) ?? "unknown";
 
   // Wait until the response has been fully-downloaded
-  if (response.state !== "closed") {
+  if (!isResponseDone(response)) {
     return (
       <EmptyStateText>
         <LoadingIcon size="sm" />
@@ -1,5 +1,7 @@
 import type { HttpResponse } from "@yaakapp-internal/models";
-import { useMemo, useState } from "react";
+import { httpRequestsAtom, patchModel } from "@yaakapp-internal/models";
+import { useAtomValue } from "jotai";
+import { useCallback, useMemo, useState } from "react";
 import { useResponseBodyText } from "../../hooks/useResponseBodyText";
 import { languageFromContentType } from "../../lib/contentType";
 import { getContentTypeFromHeaders } from "../../lib/model_util";
@@ -49,6 +51,7 @@ interface HttpTextViewerProps {
 }
 
 function HttpTextViewer({ response, text, language, pretty, className }: HttpTextViewerProps) {
+  const request = useAtomValue(httpRequestsAtom).find((r) => r.id === response.requestId);
   const [currentFilter, setCurrentFilter] = useState<string | null>(null);
   const filteredBody = useResponseBodyText({ response, filter: currentFilter });
 
@@ -64,12 +67,22 @@ function HttpTextViewer({ response, text, language, pretty, className }: HttpTex
     [filteredBody],
   );
 
+  const saveFilter = useCallback(
+    async (responseFilter: string | null) => {
+      if (request == null || request.responseFilter === responseFilter) return;
+      await patchModel(request, { responseFilter });
+    },
+    [request],
+  );
+
   return (
     <TextViewer
       text={text}
       language={language}
       stateKey={`response.body.${response.id}`}
       filterStateKey={`response.body.${response.requestId}`}
+      savedFilter={request?.responseFilter}
+      onSaveFilter={saveFilter}
       pretty={pretty}
       className={className}
       onFilter={filterCallback}
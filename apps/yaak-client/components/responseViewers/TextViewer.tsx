@@ -17,6 +17,9 @@ interface Props {
   language: EditorProps["language"];
   stateKey: string | null;
   filterStateKey?: string | null;
+  /** Filter restored when the viewer opens, like the one saved on the request */
+  savedFilter?: string | null;
+  onSaveFilter?: (filter: string | null) => void;
   pretty?: boolean;
   className?: string;
   onFilter?: (filter: string) => {
@@ -33,20 +36,27 @@ export function TextViewer({
   text,
   stateKey,
   filterStateKey,
+  savedFilter,
+  onSaveFilter,
   pretty,
   className,
   onFilter,
 }: Props) {
   const filterKey = filterStateKey ?? stateKey;
   const [filterTextMap, setFilterTextMap] = useFilterText();
-  const filterText = filterKey ? (filterTextMap[filterKey] ?? null) : null;
+  const filterText = filterKey
+    ? Object.hasOwn(filterTextMap, filterKey)
+      ? (filterTextMap[filterKey] ?? null)
+      : savedFilter || null
+    : null;
   const debouncedFilterText = useDebouncedValue(filterText);
   const setFilterText = useCallback(
     (v: string | null) => {
       if (!filterKey) return;
       setFilterTextMap((m) => ({ ...m, [filterKey]: v }));
+      onSaveFilter?.(v || null);
     },
-    [filterKey, setFilterTextMap],
+    [filterKey, onSaveFilter, setFilterTextMap],
   );
 
   const isSearching = filterText != null;
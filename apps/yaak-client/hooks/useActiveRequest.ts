@@ -1,4 +1,10 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { atom, useAtomValue } from "jotai";
 import { activeRequestIdAtom } from "./useActiveRequestId";
 import { allRequestsAtom } from "./useAllRequests";
@@ -13,6 +19,8 @@ interface TypeMap {
   http_request: HttpRequest;
   grpc_request: GrpcRequest;
   websocket_request: WebsocketRequest;
+  mqtt_request: MqttRequest;
+  socket_request: SocketRequest;
 }
 
 export function useActiveRequest<T extends keyof TypeMap>(model?: T): TypeMap[T] | null {
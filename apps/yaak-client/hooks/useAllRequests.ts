@@ -1,6 +1,8 @@
 import {
   grpcRequestsAtom,
   httpRequestsAtom,
+  mqttRequestsAtom,
+  socketRequestsAtom,
   websocketRequestsAtom,
 } from "@yaakapp-internal/models";
 import { atom, useAtomValue } from "jotai";
@@ -9,6 +11,8 @@ export const allRequestsAtom = atom((get) => [
   ...get(httpRequestsAtom),
   ...get(grpcRequestsAtom),
   ...get(websocketRequestsAtom),
+  ...get(mqttRequestsAtom),
+  ...get(socketRequestsAtom),
 ]);
 
 export function useAllRequests() {
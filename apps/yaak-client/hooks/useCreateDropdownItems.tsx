@@ -1,4 +1,9 @@
-import type { HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import type { GrpcRequest } from "@yaakapp-internal/sync";
 import { useAtomValue } from "jotai";
 import { useMemo } from "react";
@@ -42,9 +47,21 @@ export function getCreateDropdownItems({
   hideIcons?: boolean;
   folderId?: string | null;
   workspaceId: string | null;
-  activeRequest: HttpRequest | GrpcRequest | WebsocketRequest | null;
+  activeRequest:
+    | HttpRequest
+    | GrpcRequest
+    | WebsocketRequest
+    | MqttRequest
+    | SocketRequest
+    | null;
   onCreate?: (
-    model: "http_request" | "grpc_request" | "websocket_request" | "folder",
+    model:
+      | "http_request"
+      | "grpc_request"
+      | "websocket_request"
+      | "mqtt_request"
+      | "socket_request"
+      | "folder",
     id: string,
   ) => void;
 }): DropdownItem[] {
@@ -99,6 +116,26 @@ export function getCreateDropdownItems({
         onCreate?.("websocket_request", id);
       },
     },
+    {
+      label: "MQTT",
+      leftSlot: hideIcons ? undefined : <Icon icon="plus" />,
+      onSelect: async () => {
+        const id = await createRequestAndNavigate({ model: "mqtt_request", workspaceId, folderId });
+        onCreate?.("mqtt_request", id);
+      },
+    },
+    {
+      label: "TCP / UDP",
+      leftSlot: hideIcons ? undefined : <Icon icon="plus" />,
+      onSelect: async () => {
+        const id = await createRequestAndNavigate({
+          model: "socket_request",
+          workspaceId,
+          folderId,
+        });
+        onCreate?.("socket_request", id);
+      },
+    },
     ...((hideFolder
       ? []
       : [
@@ -1,15 +1,22 @@
-import { useMutation, useQuery } from "@tanstack/react-query";
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
 import { emit } from "@tauri-apps/api/event";
 import type { GrpcConnection, GrpcRequest } from "@yaakapp-internal/models";
 import { jotaiStore } from "../lib/jotai";
 import { minPromiseMillis } from "../lib/minPromiseMillis";
+import { isResponseDone } from "../lib/model_util";
 import { invokeCmd } from "../lib/tauri";
 import { activeEnvironmentIdAtom, useActiveEnvironment } from "./useActiveEnvironment";
 import { useDebouncedValue } from "@yaakapp-internal/ui";
 
 export interface ReflectResponseService {
   name: string;
-  methods: { name: string; schema: string; serverStreaming: boolean; clientStreaming: boolean }[];
+  methods: {
+    name: string;
+    schema: string;
+    example: string;
+    serverStreaming: boolean;
+    clientStreaming: boolean;
+  }[];
 }
 
 export function useGrpc(
@@ -32,6 +39,15 @@ export function useGrpc(
       emit(`grpc_client_msg_${conn?.id ?? "none"}`, { Message: message }),
   });
 
+  const replayTemplates = useMutation({
+    mutationKey: ["grpc_replay_templates", conn?.id],
+    mutationFn: ({ templateIds }: { templateIds?: string[] }) =>
+      invokeCmd<void>("cmd_grpc_replay_message_templates", {
+        connectionId: conn?.id ?? "n/a",
+        templateIds,
+      }),
+  });
+
   const cancel = useMutation({
     mutationKey: ["grpc_cancel", conn?.id ?? "n/a"],
     mutationFn: () => emit(`grpc_client_msg_${conn?.id ?? "none"}`, "Cancel"),
@@ -44,9 +60,11 @@ export function useGrpc(
 
   const debouncedUrl = useDebouncedValue<string>(req?.url ?? "", 1000);
 
+  const queryClient = useQueryClient();
+  const reflectQueryKey = ["grpc_reflect", req?.id ?? "n/a", debouncedUrl, protoFiles];
   const reflect = useQuery<ReflectResponseService[], string>({
     enabled: req != null,
-    queryKey: ["grpc_reflect", req?.id ?? "n/a", debouncedUrl, protoFiles],
+    queryKey: reflectQueryKey,
     staleTime: Infinity,
     refetchOnMount: false,
     refetchOnWindowFocus: false,
@@ -60,12 +78,27 @@ export function useGrpc(
     },
   });
 
+  // Bypass the descriptor cache and reflect against the server again
+  const refreshReflection = useMutation<ReflectResponseService[], string>({
+    mutationKey: ["grpc_reflect_refresh", requestId],
+    mutationFn: () => {
+      const environmentId = jotaiStore.get(activeEnvironmentIdAtom);
+      return minPromiseMillis<ReflectResponseService[]>(
+        invokeCmd("cmd_grpc_reflect", { requestId, protoFiles, environmentId, force: true }),
+        300,
+      );
+    },
+    onSuccess: (services) => queryClient.setQueryData(reflectQueryKey, services),
+  });
+
   return {
     go,
     reflect,
+    refreshReflection,
     cancel,
     commit,
-    isStreaming: conn != null && conn.state !== "closed",
+    replayTemplates,
+    isStreaming: conn != null && !isResponseDone(conn),
     send,
   };
 }
@@ -2,19 +2,14 @@ import { useQuery, useQueryClient } from "@tanstack/react-query";
 import { invoke } from "@tauri-apps/api/core";
 import type { GraphQlIntrospection, HttpRequest } from "@yaakapp-internal/models";
 import type { GraphQLSchema, IntrospectionQuery } from "graphql";
-import { buildClientSchema, getIntrospectionQuery } from "graphql";
+import { buildClientSchema } from "graphql";
 import { useCallback, useEffect, useMemo, useState } from "react";
 import { minPromiseMillis } from "../lib/minPromiseMillis";
-import { getResponseBodyText } from "../lib/responseBody";
-import { sendEphemeralRequest } from "../lib/sendEphemeralRequest";
+import { invokeCmd } from "../lib/tauri";
+import { getActiveCookieJar } from "./useActiveCookieJar";
 import { useActiveEnvironment } from "./useActiveEnvironment";
 import { useDebouncedValue } from "@yaakapp-internal/ui";
 
-const introspectionRequestBody = JSON.stringify({
-  query: getIntrospectionQuery(),
-  operationName: "IntrospectionQuery",
-});
-
 export function useIntrospectGraphQL(
   baseRequest: HttpRequest,
   options: { disabled?: boolean } = {},
@@ -35,6 +30,7 @@ export function useIntrospectGraphQL(
       const v = await invoke<GraphQlIntrospection>("models_upsert_graphql_introspection", {
         requestId: baseRequest.id,
         workspaceId: baseRequest.workspaceId,
+        url: null,
         content: content ?? "",
       });
 
@@ -44,44 +40,34 @@ export function useIntrospectGraphQL(
     [baseRequest.id, baseRequest.workspaceId, queryClient],
   );
 
-  const refetch = useCallback(async () => {
-    try {
-      setIsLoading(true);
-      setError(undefined);
-
-      const args = {
-        ...baseRequest,
-        bodyType: "application/json",
-        body: { text: introspectionRequestBody },
-      };
-      const response = await minPromiseMillis(
-        sendEphemeralRequest(args, activeEnvironment?.id ?? null),
-        700,
-      );
-
-      if (response.error) {
-        return setError(response.error);
-      }
-
-      const bodyText = await getResponseBodyText({ response, filter: null });
-      if (response.status < 200 || response.status >= 300) {
-        return setError(
-          `Request failed with status ${response.status}.\nThe response text is:\n\n${bodyText}`,
+  const introspect = useCallback(
+    async (force: boolean) => {
+      try {
+        setIsLoading(true);
+        setError(undefined);
+
+        // The backend caches schemas per endpoint URL, so this only hits the network when
+        // forced or when no request has introspected the endpoint yet
+        const v = await minPromiseMillis(
+          invokeCmd<GraphQlIntrospection>("cmd_graphql_introspect", {
+            request: baseRequest,
+            environmentId: activeEnvironment?.id ?? null,
+            cookieJarId: getActiveCookieJar()?.id,
+            force,
+          }),
+          force ? 700 : 0,
         );
+        queryClient.setQueryData(["introspection", baseRequest.id], v);
+      } catch (err) {
+        setError(String(err));
+      } finally {
+        setIsLoading(false);
       }
+    },
+    [activeEnvironment?.id, baseRequest, queryClient],
+  );
 
-      if (bodyText === null) {
-        return setError("Empty body returned in response");
-      }
-
-      console.log(`Got introspection response for ${baseRequest.url}`, bodyText);
-      await upsertIntrospection(bodyText);
-    } catch (err) {
-      setError(String(err));
-    } finally {
-      setIsLoading(false);
-    }
-  }, [activeEnvironment?.id, baseRequest, upsertIntrospection]);
+  const refetch = useCallback(() => introspect(true), [introspect]);
 
   // oxlint-disable-next-line react-hooks/exhaustive-deps
   useEffect(() => {
@@ -90,7 +76,7 @@ export function useIntrospectGraphQL(
       return;
     }
 
-    refetch().catch(console.error);
+    introspect(false).catch(console.error);
   }, [baseRequest.id, debouncedRequest.url, debouncedRequest.method, activeEnvironment?.id]);
 
   const clear = useCallback(async () => {
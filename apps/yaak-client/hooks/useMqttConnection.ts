@@ -0,0 +1,44 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { MqttConnection, MqttEvent } from "@yaakapp-internal/models";
+import {
+  mergeModelsInStore,
+  mqttConnectionsAtom,
+  mqttEventsAtom,
+  replaceModelsInStore,
+} from "@yaakapp-internal/models";
+import { atom, useAtomValue } from "jotai";
+import { useEffect, useMemo } from "react";
+import { fireAndForget } from "../lib/fireAndForget";
+import { activeRequestIdAtom } from "./useActiveRequestId";
+
+export const activeMqttConnectionsAtom = atom<MqttConnection[]>((get) => {
+  const activeRequestId = get(activeRequestIdAtom) ?? "n/a";
+  return get(mqttConnectionsAtom).filter((c) => c.requestId === activeRequestId);
+});
+
+export const activeMqttConnectionAtom = atom<MqttConnection | null>((get) => {
+  return get(activeMqttConnectionsAtom)[0] ?? null;
+});
+
+export function useMqttEvents(connectionId: string | null) {
+  const allEvents = useAtomValue(mqttEventsAtom);
+
+  useEffect(() => {
+    if (connectionId == null) {
+      replaceModelsInStore("mqtt_event", []);
+      return;
+    }
+
+    // Fetch events from database, filtering out events from other connections and merging atomically
+    fireAndForget(
+      invoke<MqttEvent[]>("models_mqtt_events", { connectionId }).then((events) =>
+        mergeModelsInStore("mqtt_event", events, (e) => e.connectionId === connectionId),
+      ),
+    );
+  }, [connectionId]);
+
+  return useMemo(
+    () => allEvents.filter((e) => e.connectionId === connectionId),
+    [allEvents, connectionId],
+  );
+}
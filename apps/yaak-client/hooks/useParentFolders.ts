@@ -1,9 +1,18 @@
-import type { Folder, GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  Folder,
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { foldersAtom } from "@yaakapp-internal/models";
 import { useAtomValue } from "jotai";
 import { useMemo } from "react";
 
-export function useParentFolders(m: Folder | HttpRequest | GrpcRequest | WebsocketRequest | null) {
+export function useParentFolders(
+  m: Folder | HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest | null,
+) {
   const folders = useAtomValue(foldersAtom);
 
   return useMemo(() => getParentFolders(folders, m), [folders, m]);
@@ -11,7 +20,14 @@ export function useParentFolders(m: Folder | HttpRequest | GrpcRequest | Websock
 
 function getParentFolders(
   folders: Folder[],
-  currentModel: Folder | HttpRequest | GrpcRequest | WebsocketRequest | null,
+  currentModel:
+    | Folder
+    | HttpRequest
+    | GrpcRequest
+    | WebsocketRequest
+    | MqttRequest
+    | SocketRequest
+    | null,
 ): Folder[] {
   if (currentModel == null) return [];
 
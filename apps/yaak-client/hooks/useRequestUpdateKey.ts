@@ -13,7 +13,9 @@ getCurrentWebviewWindow()
     if (
       (payload.model.model === "http_request" ||
         payload.model.model === "grpc_request" ||
-        payload.model.model === "websocket_request") &&
+        payload.model.model === "websocket_request" ||
+        payload.model.model === "mqtt_request" ||
+        payload.model.model === "socket_request") &&
       ((payload.updateSource.type === "window" &&
         payload.updateSource.label !== getCurrentWebviewWindow().label) ||
         payload.updateSource.type !== "window")
@@ -0,0 +1,10 @@
+import { useLocalStorage } from "react-use";
+
+export type RequestViewMode = "body" | "raw";
+
+const DEFAULT_VIEW_MODE: RequestViewMode = "body";
+
+export function useRequestViewMode(): [RequestViewMode, (m: RequestViewMode) => void] {
+  const [value, setValue] = useLocalStorage<RequestViewMode>("request_view_mode");
+  return [value ?? DEFAULT_VIEW_MODE, setValue];
+}
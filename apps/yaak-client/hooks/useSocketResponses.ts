@@ -0,0 +1,13 @@
+import type { SocketResponse } from "@yaakapp-internal/models";
+import { socketResponsesAtom } from "@yaakapp-internal/models";
+import { atom } from "jotai";
+import { activeRequestIdAtom } from "./useActiveRequestId";
+
+export const activeSocketResponsesAtom = atom<SocketResponse[]>((get) => {
+  const activeRequestId = get(activeRequestIdAtom) ?? "n/a";
+  return get(socketResponsesAtom).filter((r) => r.requestId === activeRequestId);
+});
+
+export const activeSocketResponseAtom = atom<SocketResponse | null>((get) => {
+  return get(activeSocketResponsesAtom)[0] ?? null;
+});
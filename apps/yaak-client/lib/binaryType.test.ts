@@ -0,0 +1,14 @@
+import { describe, expect, test } from "vite-plus/test";
+import { guessBinaryType } from "./binaryType";
+
+describe("guessBinaryType", () => {
+  test("detects known signatures", () => {
+    expect(guessBinaryType([0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a])).toBe("image/png");
+    expect(guessBinaryType([0x1f, 0x8b, 0x08])).toBe("application/gzip");
+  });
+
+  test("falls back to text or octet-stream", () => {
+    expect(guessBinaryType([0x68, 0x69])).toBe("text/plain");
+    expect(guessBinaryType([0xde, 0xad, 0xbe, 0xef])).toBe("application/octet-stream");
+  });
+});
@@ -0,0 +1,30 @@
+const SIGNATURES: { mimeType: string; bytes: number[]; offset?: number }[] = [
+  { mimeType: "image/png", bytes: [0x89, 0x50, 0x4e, 0x47] },
+  { mimeType: "image/jpeg", bytes: [0xff, 0xd8, 0xff] },
+  { mimeType: "image/gif", bytes: [0x47, 0x49, 0x46, 0x38] },
+  { mimeType: "image/webp", bytes: [0x57, 0x45, 0x42, 0x50], offset: 8 },
+  { mimeType: "application/pdf", bytes: [0x25, 0x50, 0x44, 0x46] },
+  { mimeType: "application/zip", bytes: [0x50, 0x4b, 0x03, 0x04] },
+  { mimeType: "application/gzip", bytes: [0x1f, 0x8b] },
+  { mimeType: "application/zstd", bytes: [0x28, 0xb5, 0x2f, 0xfd] },
+  { mimeType: "application/wasm", bytes: [0x00, 0x61, 0x73, 0x6d] },
+];
+
+/**
+ * Guess the type of a binary payload from its leading bytes, falling back to text when it decodes
+ * as UTF-8 and to `application/octet-stream` otherwise.
+ */
+export function guessBinaryType(bytes: number[]): string {
+  for (const { mimeType, bytes: signature, offset = 0 } of SIGNATURES) {
+    if (signature.every((b, i) => bytes[offset + i] === b)) {
+      return mimeType;
+    }
+  }
+
+  try {
+    new TextDecoder("utf-8", { fatal: true }).decode(Uint8Array.from(bytes));
+    return "text/plain";
+  } catch {
+    return "application/octet-stream";
+  }
+}
@@ -1,11 +1,17 @@
-import type { GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { createWorkspaceModel } from "@yaakapp-internal/models";
 import { activeRequestAtom } from "../hooks/useActiveRequest";
 import { jotaiStore } from "./jotai";
 import { router } from "./router";
 
 export async function createRequestAndNavigate<
-  T extends HttpRequest | GrpcRequest | WebsocketRequest,
+  T extends HttpRequest | GrpcRequest | WebsocketRequest | MqttRequest | SocketRequest,
 >(patch: Partial<T> & Pick<T, "model" | "workspaceId">) {
   const activeRequest = jotaiStore.get(activeRequestAtom);
 
@@ -1,11 +1,25 @@
-import type { Folder, GrpcRequest, HttpRequest, WebsocketRequest } from "@yaakapp-internal/models";
+import type {
+  Folder,
+  GrpcRequest,
+  HttpRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+} from "@yaakapp-internal/models";
 import { duplicateModel } from "@yaakapp-internal/models";
 import { activeWorkspaceIdAtom } from "../hooks/useActiveWorkspace";
 import { jotaiStore } from "./jotai";
 import { navigateToRequestOrFolderOrWorkspace } from "./setWorkspaceSearchParams";
 
 export async function duplicateRequestOrFolderAndNavigate(
-  model: Folder | HttpRequest | GrpcRequest | WebsocketRequest | null,
+  model:
+    | Folder
+    | HttpRequest
+    | GrpcRequest
+    | WebsocketRequest
+    | MqttRequest
+    | SocketRequest
+    | null,
 ) {
   if (model == null) {
     throw new Error("Cannot duplicate null item");
@@ -2,8 +2,13 @@ import type {
   AnyModel,
   Cookie,
   Environment,
+  GrpcConnection,
+  HttpResponse,
   HttpResponseEvent,
   HttpResponseHeader,
+  MqttConnection,
+  SocketResponse,
+  WebsocketConnection,
 } from "@yaakapp-internal/models";
 import { getMimeTypeFromContentType } from "./contentType";
 
@@ -42,6 +47,16 @@ export function modelsEq(a: AnyModel, b: AnyModel) {
   return false;
 }
 
+/** Whether a response or connection has finished, either normally or by timing out */
+export function isResponseDone(
+  response: Pick<
+    HttpResponse | GrpcConnection | WebsocketConnection | MqttConnection | SocketResponse,
+    "state"
+  >,
+): boolean {
+  return response.state === "closed" || response.state === "timed_out";
+}
+
 export function getContentTypeFromHeaders(headers: HttpResponseHeader[] | null): string | null {
   return headers?.find((h) => h.name.toLowerCase() === "content-type")?.value ?? null;
 }
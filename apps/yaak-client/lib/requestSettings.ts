@@ -4,7 +4,14 @@ type ModelType = AnyModel["model"];
 
 type WorkspaceRequestSettings = Pick<
   Workspace,
+  | "settingConnectTimeout"
+  | "settingDecompressResponse"
   | "settingFollowRedirects"
+  | "settingHttpVersion"
+  | "settingMaxRedirects"
+  | "settingReadTimeout"
+  | "settingRedirectResendBody"
+  | "settingRedirectStripAuthorization"
   | "settingRequestTimeout"
   | "settingSendCookies"
   | "settingStoreCookies"
@@ -35,17 +42,49 @@ function defineRequestSetting<const K extends RequestSettingKey>(
 
 export const SETTING_REQUEST_TIMEOUT = defineRequestSetting({
   defaultValue: 0,
-  description: "Maximum request duration in milliseconds. Set to 0 to disable.",
+  description:
+    "Maximum request duration in milliseconds, also sent to gRPC servers as the call deadline. Set to 0 to disable.",
   modelKey: "settingRequestTimeout",
-  models: ["workspace", "folder", "http_request"],
+  models: ["workspace", "folder", "http_request", "grpc_request"],
   title: "Request Timeout",
 });
 
+export const SETTING_CONNECT_TIMEOUT = defineRequestSetting({
+  defaultValue: 0,
+  description: "Maximum time in milliseconds to establish a connection. Set to 0 to disable.",
+  modelKey: "settingConnectTimeout",
+  models: ["workspace", "folder", "http_request", "grpc_request"],
+  title: "Connect Timeout",
+});
+
+export const SETTING_READ_TIMEOUT = defineRequestSetting({
+  defaultValue: 0,
+  description: "Maximum time in milliseconds to wait between reads. Set to 0 to disable.",
+  modelKey: "settingReadTimeout",
+  models: ["workspace", "folder", "http_request", "grpc_request"],
+  title: "Read Timeout",
+});
+
+export const SETTING_HTTP_VERSION = defineRequestSetting({
+  defaultValue: "auto",
+  description: "HTTP version to send requests with. Auto negotiates with the server.",
+  modelKey: "settingHttpVersion",
+  models: ["workspace", "folder", "http_request"],
+  title: "HTTP Version",
+});
+
 export const SETTING_VALIDATE_CERTIFICATES = defineRequestSetting({
   defaultValue: true,
   description: "When disabled, skip validation of server certificates.",
   modelKey: "settingValidateCertificates",
-  models: ["workspace", "folder", "http_request", "websocket_request", "grpc_request"],
+  models: [
+    "workspace",
+    "folder",
+    "http_request",
+    "websocket_request",
+    "grpc_request",
+    "mqtt_request",
+  ],
   title: "Validate TLS certificates",
 });
 
@@ -57,6 +96,38 @@ export const SETTING_FOLLOW_REDIRECTS = defineRequestSetting({
   title: "Follow redirects",
 });
 
+export const SETTING_MAX_REDIRECTS = defineRequestSetting({
+  defaultValue: 10,
+  description: "Maximum number of redirects to follow before the request fails.",
+  modelKey: "settingMaxRedirects",
+  models: ["workspace", "folder", "http_request"],
+  title: "Max redirects",
+});
+
+export const SETTING_REDIRECT_RESEND_BODY = defineRequestSetting({
+  defaultValue: true,
+  description: "Send the request body again when following a 307 or 308 redirect.",
+  modelKey: "settingRedirectResendBody",
+  models: ["workspace", "folder", "http_request"],
+  title: "Resend body on redirect",
+});
+
+export const SETTING_REDIRECT_STRIP_AUTHORIZATION = defineRequestSetting({
+  defaultValue: true,
+  description: "Remove the Authorization header when a redirect leads to a different origin.",
+  modelKey: "settingRedirectStripAuthorization",
+  models: ["workspace", "folder", "http_request"],
+  title: "Strip authorization on cross-origin redirect",
+});
+
+export const SETTING_DECOMPRESS_RESPONSE = defineRequestSetting({
+  defaultValue: true,
+  description: "Decode gzip, deflate, brotli, and zstd response bodies. Disable to keep raw bytes.",
+  modelKey: "settingDecompressResponse",
+  models: ["workspace", "folder", "http_request"],
+  title: "Decompress responses",
+});
+
 export const SETTING_SEND_COOKIES = defineRequestSetting({
   defaultValue: true,
   description: "Attach matching cookies from the active cookie jar to outgoing requests.",
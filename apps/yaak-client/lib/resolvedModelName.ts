@@ -23,7 +23,11 @@ export function resolvedModelName(r: AnyModel | null): string {
         : "HTTP Request"
       : r.model === "websocket_request"
         ? "WebSocket Request"
-        : "gRPC Request";
+        : r.model === "mqtt_request"
+          ? "MQTT Request"
+          : r.model === "socket_request"
+            ? "Socket Request"
+            : "gRPC Request";
   }
 
   // GRPC gets nice short names
@@ -33,7 +37,7 @@ export function resolvedModelName(r: AnyModel | null): string {
   }
 
   // Strip unnecessary protocol
-  const withoutProto = withoutVariables.replace(/^(http|https|ws|wss):\/\//, "");
+  const withoutProto = withoutVariables.replace(/^(http|https|ws|wss|mqtt|mqtts):\/\//, "");
 
   return withoutProto;
 }
@@ -1,4 +1,11 @@
-import type { Folder, GrpcRequest, WebsocketRequest, Workspace } from "@yaakapp-internal/models";
+import type {
+  Folder,
+  GrpcRequest,
+  MqttRequest,
+  SocketRequest,
+  WebsocketRequest,
+  Workspace,
+} from "@yaakapp-internal/models";
 import type { HttpRequest } from "@yaakapp-internal/sync";
 import { router } from "./router.js";
 
@@ -35,7 +42,15 @@ export function setWorkspaceSearchParams(
 
 export function navigateToRequestOrFolderOrWorkspace(
   id: string,
-  model: (Workspace | Folder | HttpRequest | GrpcRequest | WebsocketRequest)["model"],
+  model: (
+    | Workspace
+    | Folder
+    | HttpRequest
+    | GrpcRequest
+    | WebsocketRequest
+    | MqttRequest
+    | SocketRequest
+  )["model"],
 ) {
   if (model === "workspace") {
     setWorkspaceSearchParams({ request_id: null, folder_id: null });
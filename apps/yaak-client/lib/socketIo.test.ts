@@ -0,0 +1,23 @@
+import { describe, expect, test } from "vite-plus/test";
+import { describeSocketIoFrame } from "./socketIo";
+
+describe("describeSocketIoFrame", () => {
+  test("describes events and acks", () => {
+    expect(describeSocketIoFrame('42["chat",{"text":"hi"}]')).toBe('event "chat" [{"text":"hi"}]');
+    expect(describeSocketIoFrame('42/admin,3["chat"]')).toBe('event "chat" on /admin (ack 3)');
+    expect(describeSocketIoFrame('43/admin,3["ok"]')).toBe('ack 3 ["ok"] on /admin');
+  });
+
+  test("describes connection packets", () => {
+    expect(describeSocketIoFrame('0{"sid":"abc"}')).toBe("handshake");
+    expect(describeSocketIoFrame("40")).toBe("connect");
+    expect(describeSocketIoFrame('44{"message":"Not allowed"}')).toBe(
+      'connect error {"message":"Not allowed"}',
+    );
+  });
+
+  test("ignores frames that aren't Socket.IO packets", () => {
+    expect(describeSocketIoFrame("hello")).toBeNull();
+    expect(describeSocketIoFrame("42not json")).toBeNull();
+  });
+});
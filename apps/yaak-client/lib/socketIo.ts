@@ -0,0 +1,47 @@
+const PACKET_TYPES = [
+  "connect",
+  "disconnect",
+  "event",
+  "ack",
+  "connect error",
+  "binary event",
+  "binary ack",
+];
+
+/**
+ * Summarize a Socket.IO frame for the message list, like `event "chat" ["hi"] (ack 3)`. Returns
+ * null for frames that aren't Socket.IO packets, so they can be shown as-is.
+ */
+export function describeSocketIoFrame(frame: string): string | null {
+  const match = frame.match(/^4(\d)(?:\d+-)?(\/[^,]*)?,?(\d*)([\s\S]*)$/);
+  if (match == null) {
+    if (frame.startsWith("0")) return "handshake";
+    if (frame === "1") return "close";
+    return null;
+  }
+
+  const [, type = "", namespace = "/", ackId = "", rest = ""] = match;
+  const packetType = PACKET_TYPES[Number.parseInt(type, 10)];
+  if (packetType == null) return null;
+
+  let data: unknown = null;
+  try {
+    data = rest === "" ? null : JSON.parse(rest);
+  } catch {
+    return null;
+  }
+
+  const parts = [packetType];
+  if (packetType.endsWith("event") && Array.isArray(data)) {
+    const [event, ...args] = data;
+    parts.push(JSON.stringify(event));
+    if (args.length > 0) parts.push(JSON.stringify(args));
+  } else if (packetType.endsWith("ack")) {
+    parts.push(ackId, JSON.stringify(data ?? []));
+  } else if (data != null) {
+    parts.push(JSON.stringify(data));
+  }
+  if (namespace !== "/") parts.push(`on ${namespace}`);
+  if (packetType.endsWith("event") && ackId !== "") parts.push(`(ack ${ackId})`);
+  return parts.join(" ");
+}
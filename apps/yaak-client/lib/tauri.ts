@@ -9,6 +9,7 @@ type TauriCmd =
   | "cmd_call_workspace_action"
   | "cmd_call_folder_action"
   | "cmd_check_for_updates"
+  | "cmd_codegen_languages"
   | "cmd_curl_to_request"
   | "cmd_decrypt_template"
   | "cmd_default_headers"
@@ -16,7 +17,13 @@ type TauriCmd =
   | "cmd_delete_all_http_responses"
   | "cmd_delete_send_history"
   | "cmd_dismiss_notification"
+  | "cmd_export_as_curl"
+  | "cmd_export_as_snippet"
+  | "cmd_export_cookies"
   | "cmd_export_data"
+  | "cmd_export_har"
+  | "cmd_export_openapi"
+  | "cmd_export_postman"
   | "cmd_format_graphql"
   | "cmd_format_json"
   | "cmd_get_http_authentication_config"
@@ -27,8 +34,10 @@ type TauriCmd =
   | "cmd_get_workspace_meta"
   | "cmd_git_add_credential"
   | "cmd_git_clone"
+  | "cmd_graphql_introspect"
   | "cmd_grpc_go"
   | "cmd_grpc_reflect"
+  | "cmd_grpc_replay_message_templates"
   | "cmd_grpc_request_actions"
   | "cmd_http_request_actions"
   | "cmd_websocket_request_actions"
@@ -36,8 +45,12 @@ type TauriCmd =
   | "cmd_folder_actions"
   | "cmd_http_request_body"
   | "cmd_http_response_body"
+  | "cmd_import_cookies"
   | "cmd_import_data"
   | "cmd_metadata"
+  | "cmd_mock_server_port"
+  | "cmd_mock_server_start"
+  | "cmd_mock_server_stop"
   | "cmd_restart"
   | "cmd_new_child_window"
   | "cmd_new_main_window"
@@ -45,6 +58,8 @@ type TauriCmd =
   | "cmd_plugin_init_errors"
   | "cmd_reload_plugins"
   | "cmd_render_template"
+  | "cmd_run_folder"
+  | "cmd_run_monitor"
   | "cmd_save_response"
   | "cmd_secure_template"
   | "cmd_send_ephemeral_request"
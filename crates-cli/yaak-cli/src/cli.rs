@@ -62,6 +62,9 @@ pub enum Commands {
     /// Send a request, folder, or workspace by ID
     Send(SendArgs),
 
+    /// Run every HTTP request in a folder, failing if any request or assertion fails
+    Run(RunArgs),
+
     /// Cookie jar commands
     CookieJar(CookieJarArgs),
 
@@ -76,6 +79,12 @@ pub enum Commands {
 
     /// Environment commands
     Environment(EnvironmentArgs),
+
+    /// Version a workspace as files in a Git repository
+    Git(GitArgs),
+
+    /// Inspect and maintain the local database
+    Db(DbArgs),
 }
 
 #[derive(Args)]
@@ -92,6 +101,40 @@ pub struct SendArgs {
     pub fail_fast: bool,
 }
 
+#[derive(Args)]
+pub struct RunArgs {
+    /// Folder ID or name
+    pub folder: String,
+
+    /// Load the workspace from an exported JSON or YAML file instead of the Yaak database
+    #[arg(long, value_name = "PATH")]
+    pub workspace_file: Option<PathBuf>,
+
+    /// Maximum number of requests in flight at once
+    #[arg(long, default_value_t = 1)]
+    pub concurrency: i32,
+
+    /// Milliseconds to wait before sending each request after the first
+    #[arg(long, default_value_t = 0)]
+    pub delay: i32,
+
+    /// Stop sending requests after the first failure
+    #[arg(long)]
+    pub fail_fast: bool,
+
+    /// CSV or JSON file whose rows each run the folder once, with columns bound to variables
+    #[arg(long, value_name = "PATH")]
+    pub data: Option<PathBuf>,
+
+    /// Write a JUnit XML report of the run to this path
+    #[arg(long, value_name = "PATH")]
+    pub junit: Option<PathBuf>,
+
+    /// Write a JSON report of the run's results, assertions, and timings to this path
+    #[arg(long, value_name = "PATH")]
+    pub json_report: Option<PathBuf>,
+}
+
 #[derive(Args)]
 #[command(disable_help_subcommand = true)]
 pub struct CookieJarArgs {
@@ -168,6 +211,24 @@ pub enum WorkspaceCommands {
         #[arg(short, long)]
         yes: bool,
     },
+
+    /// Export a workspace to a portable file that can be imported elsewhere
+    Export {
+        /// Workspace ID
+        workspace_id: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// Include private environments in the export
+        #[arg(long)]
+        include_private_environments: bool,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
 #[derive(Args)]
@@ -197,6 +258,30 @@ pub enum RequestCommands {
         request_id: String,
     },
 
+    /// Print an HTTP request as a curl command, with templates and authentication applied
+    Curl {
+        /// Request ID
+        request_id: String,
+    },
+
+    /// Print the variables available to an HTTP request as JSON, merged from the workspace, the
+    /// selected environment, and every ancestor folder
+    Variables {
+        /// Request ID
+        request_id: String,
+    },
+
+    /// Print an HTTP request as a code snippet, with templates and authentication applied
+    Snippet {
+        /// Request ID
+        request_id: String,
+
+        /// Language to generate, e.g. javascript-fetch, python-requests, go-net-http,
+        /// rust-reqwest, or http
+        #[arg(long, short)]
+        language: String,
+    },
+
     /// Output JSON schema for request create/update payloads
     Schema {
         #[arg(value_enum)]
@@ -258,6 +343,16 @@ pub enum RequestSchemaType {
     Websocket,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Yaml,
+    /// Postman Collection v2.1, for sharing with Postman users
+    Postman,
+    /// OpenAPI 3.1 skeleton, for bootstrapping API docs
+    Openapi,
+}
+
 #[derive(Clone, Copy, Debug, ValueEnum)]
 pub enum LogLevel {
     Error,
@@ -407,6 +502,92 @@ pub enum EnvironmentCommands {
     },
 }
 
+#[derive(Args)]
+#[command(disable_help_subcommand = true)]
+pub struct GitArgs {
+    #[command(subcommand)]
+    pub command: GitCommands,
+}
+
+#[derive(Subcommand)]
+pub enum GitCommands {
+    /// Set a workspace's sync directory and initialize a Git repository there
+    Init {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+    },
+
+    /// Write workspace changes to the sync directory and load external edits from it
+    Sync {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+
+        /// Keep running and sync whenever files in the directory or the workspace change
+        #[arg(long)]
+        watch: bool,
+    },
+
+    /// Show the current branch and changed files
+    Status {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+    },
+
+    /// Sync the workspace and commit all changed files
+    Commit {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+
+        /// Commit message
+        #[arg(short, long)]
+        message: String,
+    },
+
+    /// Pull from the default remote and load the changes into the workspace
+    Pull {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+    },
+
+    /// Push the current branch to the default remote
+    Push {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+    },
+
+    /// List local branches, or create one when a name is given
+    Branch {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+
+        /// Name of the branch to create
+        name: Option<String>,
+    },
+
+    /// Switch branches and load the branch's files into the workspace
+    Checkout {
+        #[command(flatten)]
+        target: GitWorkspaceArg,
+
+        /// Branch name
+        branch: String,
+
+        /// Discard local changes when switching
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Args, Clone)]
+pub struct GitWorkspaceArg {
+    /// Workspace ID
+    pub workspace_id: String,
+
+    /// Sync directory (defaults to the workspace's configured sync directory)
+    #[arg(long, value_name = "PATH")]
+    pub dir: Option<PathBuf>,
+}
+
 #[derive(Args)]
 #[command(disable_help_subcommand = true)]
 pub struct AuthArgs {
@@ -473,3 +654,22 @@ pub struct InstallPluginArgs {
     /// Local plugin directory path, or registry plugin spec (@org/plugin[@version])
     pub source: String,
 }
+
+#[derive(Args)]
+#[command(disable_help_subcommand = true)]
+pub struct DbArgs {
+    #[command(subcommand)]
+    pub command: DbCommands,
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Show the size of the database by table and by workspace
+    Stats,
+
+    /// Give space freed by deleted data back to the filesystem. Close the app first
+    Vacuum,
+
+    /// Check the database for corruption
+    Check,
+}
@@ -0,0 +1,90 @@
+use crate::cli::{DbArgs, DbCommands};
+use crate::context::CliContext;
+
+type CommandResult<T = ()> = std::result::Result<T, String>;
+
+pub fn run(ctx: &CliContext, args: DbArgs) -> i32 {
+    let result = match args.command {
+        DbCommands::Stats => stats(ctx),
+        DbCommands::Vacuum => vacuum(ctx),
+        DbCommands::Check => check(ctx),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            1
+        }
+    }
+}
+
+fn stats(ctx: &CliContext) -> CommandResult {
+    let stats = ctx.db().db_stats().map_err(|e| format!("Failed to get database stats: {e}"))?;
+
+    println!(
+        "Database: {} ({} free)",
+        format_bytes(stats.total_bytes),
+        format_bytes(stats.free_bytes)
+    );
+
+    println!();
+    println!("Tables:");
+    for table in stats.tables.iter().filter(|t| t.rows > 0) {
+        println!("  {:<32} {:>10} {:>10} rows", table.name, format_bytes(table.bytes), table.rows);
+    }
+
+    if !stats.workspaces.is_empty() {
+        println!();
+        println!("Workspaces:");
+    }
+    for workspace in stats.workspaces {
+        let name = match ctx.db().get_workspace(&workspace.workspace_id) {
+            Ok(w) => format!("{} ({})", w.name, w.id),
+            Err(_) => format!("{} (deleted)", workspace.workspace_id),
+        };
+        println!(
+            "  {:<32} {:>10} {:>10} rows",
+            name,
+            format_bytes(workspace.bytes),
+            workspace.rows
+        );
+    }
+
+    Ok(())
+}
+
+fn vacuum(ctx: &CliContext) -> CommandResult {
+    let reclaimed = ctx.db().vacuum().map_err(|e| format!("Failed to vacuum database: {e}"))?;
+    println!("Reclaimed {}", format_bytes(reclaimed));
+    Ok(())
+}
+
+fn check(ctx: &CliContext) -> CommandResult {
+    let problems =
+        ctx.db().integrity_check().map_err(|e| format!("Failed to check database: {e}"))?;
+    if problems.is_empty() {
+        println!("No problems found");
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{problem}");
+    }
+    Err(format!("Found {} problems in the database", problems.len()))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
@@ -109,10 +109,11 @@ fn create(
         if environment.parent_model.is_empty() {
             environment.parent_model = "environment".to_string();
         }
+        encrypt_secret_variables(ctx, &mut environment)?;
 
         let created = ctx
             .db()
-            .upsert_environment(&environment, &UpdateSource::Sync)
+            .upsert_environment(&environment, &UpdateSource::Cli)
             .map_err(|e| format!("Failed to create environment: {e}"))?;
 
         println!("Created environment: {}", created.id);
@@ -134,7 +135,7 @@ fn create(
 
     let created = ctx
         .db()
-        .upsert_environment(&environment, &UpdateSource::Sync)
+        .upsert_environment(&environment, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to create environment: {e}"))?;
 
     println!("Created environment: {}", created.id);
@@ -149,17 +150,24 @@ fn update(ctx: &CliContext, json: Option<String>, json_input: Option<String>) ->
         .db()
         .get_environment(&id)
         .map_err(|e| format!("Failed to get environment for update: {e}"))?;
-    let updated = apply_merge_patch(&existing, &patch, &id, "environment update")?;
+    let mut updated = apply_merge_patch(&existing, &patch, &id, "environment update")?;
+    encrypt_secret_variables(ctx, &mut updated)?;
 
     let saved = ctx
         .db()
-        .upsert_environment(&updated, &UpdateSource::Sync)
+        .upsert_environment(&updated, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to update environment: {e}"))?;
 
     println!("Updated environment: {}", saved.id);
     Ok(())
 }
 
+fn encrypt_secret_variables(ctx: &CliContext, environment: &mut Environment) -> CommandResult {
+    ctx.encryption_manager
+        .encrypt_secret_variables(environment)
+        .map_err(|e| format!("Failed to encrypt secret variables: {e}"))
+}
+
 fn delete(ctx: &CliContext, environment_id: &str, yes: bool) -> CommandResult {
     if !yes && !confirm_delete("environment", environment_id) {
         println!("Aborted");
@@ -168,7 +176,7 @@ fn delete(ctx: &CliContext, environment_id: &str, yes: bool) -> CommandResult {
 
     let deleted = ctx
         .db()
-        .delete_environment_by_id(environment_id, &UpdateSource::Sync)
+        .delete_environment_by_id(environment_id, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to delete environment: {e}"))?;
 
     println!("Deleted environment: {}", deleted.id);
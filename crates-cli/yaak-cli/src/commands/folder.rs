@@ -86,9 +86,11 @@ fn create(
             "folder create",
         )?;
 
+        ctx.store_authentication_secrets::<Folder>(&mut folder.id, &mut folder.authentication)?;
+
         let created = ctx
             .db()
-            .upsert_folder(&folder, &UpdateSource::Sync)
+            .upsert_folder(&folder, &UpdateSource::Cli)
             .map_err(|e| format!("Failed to create folder: {e}"))?;
 
         println!("Created folder: {}", created.id);
@@ -104,7 +106,7 @@ fn create(
 
     let created = ctx
         .db()
-        .upsert_folder(&folder, &UpdateSource::Sync)
+        .upsert_folder(&folder, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to create folder: {e}"))?;
 
     println!("Created folder: {}", created.id);
@@ -117,11 +119,12 @@ fn update(ctx: &CliContext, json: Option<String>, json_input: Option<String>) ->
 
     let existing =
         ctx.db().get_folder(&id).map_err(|e| format!("Failed to get folder for update: {e}"))?;
-    let updated = apply_merge_patch(&existing, &patch, &id, "folder update")?;
+    let mut updated = apply_merge_patch(&existing, &patch, &id, "folder update")?;
+    ctx.store_authentication_secrets::<Folder>(&mut updated.id, &mut updated.authentication)?;
 
     let saved = ctx
         .db()
-        .upsert_folder(&updated, &UpdateSource::Sync)
+        .upsert_folder(&updated, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to update folder: {e}"))?;
 
     println!("Updated folder: {}", saved.id);
@@ -136,7 +139,7 @@ fn delete(ctx: &CliContext, folder_id: &str, yes: bool) -> CommandResult {
 
     let deleted = ctx
         .db()
-        .delete_folder_by_id(folder_id, &UpdateSource::Sync)
+        .delete_folder_by_id(folder_id, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to delete folder: {e}"))?;
 
     println!("Deleted folder: {}", deleted.id);
@@ -0,0 +1,316 @@
+use crate::cli::{GitArgs, GitCommands, GitWorkspaceArg};
+use crate::context::CliContext;
+use crate::version::cli_version;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use yaak_git::{
+    GitStatus, PullResult, PushResult, git_add, git_branch_info, git_checkout_branch, git_commit,
+    git_create_branch, git_init, git_pull, git_push, git_worktree_status,
+};
+use yaak_models::util::UpdateSource;
+use yaak_sync::sync::{
+    FsCandidate, SyncOp, apply_sync_ops, apply_sync_state_ops, compute_sync_ops, get_db_candidates,
+    get_fs_candidates,
+};
+use yaak_sync::watch::watch_directory;
+
+type CommandResult<T = ()> = std::result::Result<T, String>;
+
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
+const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub async fn run(ctx: &CliContext, args: GitArgs) -> i32 {
+    let result = match args.command {
+        GitCommands::Init { target } => init(ctx, target),
+        GitCommands::Sync { target, watch: false } => sync(ctx, target),
+        GitCommands::Sync { target, watch: true } => sync_watch(ctx, target).await,
+        GitCommands::Status { target } => status(ctx, target),
+        GitCommands::Commit { target, message } => commit(ctx, target, &message).await,
+        GitCommands::Pull { target } => pull(ctx, target).await,
+        GitCommands::Push { target } => push(ctx, target).await,
+        GitCommands::Branch { target, name } => branch(ctx, target, name).await,
+        GitCommands::Checkout { target, branch, force } => {
+            checkout(ctx, target, &branch, force).await
+        }
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            1
+        }
+    }
+}
+
+fn init(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    let mut workspace_meta = ctx
+        .db()
+        .get_or_create_workspace_meta(&target.workspace_id)
+        .map_err(|e| format!("Failed to get workspace meta: {e}"))?;
+    let sync_dir = dir.to_string_lossy().to_string();
+    if workspace_meta.setting_sync_dir.as_deref() != Some(sync_dir.as_str()) {
+        workspace_meta.setting_sync_dir = Some(sync_dir);
+        ctx.db()
+            .upsert_workspace_meta(&workspace_meta, &UpdateSource::Cli)
+            .map_err(|e| format!("Failed to save sync directory: {e}"))?;
+    }
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create directory {}: {e}", dir.display()))?;
+    if git_branch_info(&dir).is_err() {
+        git_init(&dir).map_err(|e| format!("Failed to initialize Git repository: {e}"))?;
+    }
+
+    let changes = sync_workspace(ctx, &target.workspace_id, &dir)?;
+    println!("Initialized {} ({changes} changes synced)", dir.display());
+    Ok(())
+}
+
+fn sync(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    let changes = sync_workspace(ctx, &target.workspace_id, &dir)?;
+    println!("Synced {changes} changes with {}", dir.display());
+    Ok(())
+}
+
+async fn sync_watch(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    let changes = sync_workspace(ctx, &target.workspace_id, &dir)?;
+    println!("Synced {changes} changes with {}", dir.display());
+
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let (_cancel_tx, cancel_rx) = watch::channel(());
+    watch_directory(
+        &dir,
+        move |event| {
+            let _ = event_tx.send(event);
+        },
+        cancel_rx,
+    )
+    .await
+    .map_err(|e| format!("Failed to watch {}: {e}", dir.display()))?;
+    println!("Watching {} for changes (Ctrl+C to stop)", dir.display());
+
+    // The app may edit the workspace from another process, so the database is polled as well
+    let mut poll = tokio::time::interval(WATCH_POLL_INTERVAL);
+    loop {
+        tokio::select! {
+            Some(_) = event_rx.recv() => {
+                // Editors and `git checkout` touch many files at once, so let them settle
+                tokio::time::sleep(WATCH_DEBOUNCE).await;
+                while event_rx.try_recv().is_ok() {}
+            }
+            _ = poll.tick() => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+
+        match sync_workspace(ctx, &target.workspace_id, &dir) {
+            Ok(0) => {}
+            Ok(changes) => println!("Synced {changes} changes"),
+            Err(error) => eprintln!("Error: {error}"),
+        }
+    }
+}
+
+fn status(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    sync_workspace(ctx, &target.workspace_id, &dir)?;
+
+    let info = git_branch_info(&dir).map_err(|e| format!("Failed to get branch info: {e}"))?;
+    let branch = info.head_ref_shorthand.unwrap_or_else(|| "(detached)".to_string());
+    if info.ahead > 0 || info.behind > 0 {
+        println!("On branch {branch} (ahead {}, behind {})", info.ahead, info.behind);
+    } else {
+        println!("On branch {branch}");
+    }
+
+    let worktree =
+        git_worktree_status(&dir).map_err(|e| format!("Failed to get Git status: {e}"))?;
+    if worktree.entries.is_empty() {
+        println!("Nothing to commit");
+    }
+    for entry in worktree.entries {
+        let label = match entry.status {
+            GitStatus::Untracked => "untracked",
+            GitStatus::Conflict => "conflict",
+            GitStatus::Current => continue,
+            GitStatus::Modified => "modified",
+            GitStatus::Removed => "removed",
+            GitStatus::Renamed => "renamed",
+            GitStatus::TypeChange => "typechange",
+        };
+        let staged = if entry.staged { " (staged)" } else { "" };
+        println!("{label:<10} {}{staged}", entry.rela_path);
+    }
+    Ok(())
+}
+
+async fn commit(ctx: &CliContext, target: GitWorkspaceArg, message: &str) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    sync_workspace(ctx, &target.workspace_id, &dir)?;
+
+    let worktree =
+        git_worktree_status(&dir).map_err(|e| format!("Failed to get Git status: {e}"))?;
+    if worktree.entries.is_empty() {
+        println!("Nothing to commit");
+        return Ok(());
+    }
+    for entry in &worktree.entries {
+        git_add(&dir, Path::new(&entry.rela_path))
+            .map_err(|e| format!("Failed to stage {}: {e}", entry.rela_path))?;
+    }
+
+    git_commit(&dir, message).await.map_err(|e| format!("Failed to commit: {e}"))?;
+    println!("Committed {} files", worktree.entries.len());
+    Ok(())
+}
+
+async fn pull(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+
+    // Flush pending workspace changes first so Git can refuse to pull over them
+    sync_workspace(ctx, &target.workspace_id, &dir)?;
+
+    match git_pull(&dir).await.map_err(|e| format!("Failed to pull: {e}"))? {
+        PullResult::Success { message } => {
+            let changes = sync_workspace(ctx, &target.workspace_id, &dir)?;
+            println!("{}", message.trim());
+            println!("Synced {changes} changes into the workspace");
+            Ok(())
+        }
+        PullResult::UpToDate => {
+            println!("Already up to date");
+            Ok(())
+        }
+        PullResult::NeedsCredentials { url, error } => Err(credentials_error(&url, error)),
+        PullResult::Diverged { remote, branch } => Err(format!(
+            "Local branch has diverged from {remote}/{branch}. Merge or reset with git directly"
+        )),
+        PullResult::UncommittedChanges => {
+            Err("Commit or discard local changes before pulling".to_string())
+        }
+    }
+}
+
+async fn push(ctx: &CliContext, target: GitWorkspaceArg) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    match git_push(&dir).await.map_err(|e| format!("Failed to push: {e}"))? {
+        PushResult::Success { message } => {
+            println!("{}", message.trim());
+            Ok(())
+        }
+        PushResult::UpToDate => {
+            println!("Everything up to date");
+            Ok(())
+        }
+        PushResult::NeedsCredentials { url, error } => Err(credentials_error(&url, error)),
+    }
+}
+
+async fn branch(ctx: &CliContext, target: GitWorkspaceArg, name: Option<String>) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    if let Some(name) = name {
+        git_create_branch(&dir, &name, None)
+            .await
+            .map_err(|e| format!("Failed to create branch: {e}"))?;
+        println!("Created branch: {name}");
+        return Ok(());
+    }
+
+    let info = git_branch_info(&dir).map_err(|e| format!("Failed to get branch info: {e}"))?;
+    for local_branch in info.local_branches {
+        let marker = if info.head_ref_shorthand.as_deref() == Some(local_branch.as_str()) {
+            "*"
+        } else {
+            " "
+        };
+        println!("{marker} {local_branch}");
+    }
+    Ok(())
+}
+
+async fn checkout(
+    ctx: &CliContext,
+    target: GitWorkspaceArg,
+    branch: &str,
+    force: bool,
+) -> CommandResult {
+    let dir = target_dir(ctx, &target)?;
+    sync_workspace(ctx, &target.workspace_id, &dir)?;
+
+    let branch = git_checkout_branch(&dir, branch, force)
+        .await
+        .map_err(|e| format!("Failed to check out branch: {e}"))?;
+    let changes = sync_workspace(ctx, &target.workspace_id, &dir)?;
+    println!("Switched to branch: {branch} ({changes} changes synced)");
+    Ok(())
+}
+
+/// Resolve the sync directory from `--dir`, falling back to the one configured on the workspace
+fn target_dir(ctx: &CliContext, target: &GitWorkspaceArg) -> CommandResult<PathBuf> {
+    ctx.db()
+        .get_workspace(&target.workspace_id)
+        .map_err(|e| format!("Failed to get workspace: {e}"))?;
+
+    if let Some(dir) = &target.dir {
+        return std::path::absolute(dir)
+            .map_err(|e| format!("Invalid directory {}: {e}", dir.display()));
+    }
+
+    ctx.db()
+        .get_workspace_meta(&target.workspace_id)
+        .and_then(|m| m.setting_sync_dir)
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            format!(
+                "Workspace {} has no sync directory. Pass --dir or run `yaak git init {} --dir <PATH>`",
+                target.workspace_id, target.workspace_id
+            )
+        })
+}
+
+/// Reconcile the workspace in the database with its files in the sync directory, returning the
+/// number of models written in either direction
+fn sync_workspace(ctx: &CliContext, workspace_id: &str, dir: &Path) -> CommandResult<usize> {
+    let db = ctx.db();
+    let db_candidates = get_db_candidates(&db, cli_version(), workspace_id, dir)
+        .map_err(|e| format!("Failed to read workspace: {e}"))?;
+    let fs_candidates = get_fs_candidates(dir)
+        .map_err(|e| format!("Failed to read {}: {e}", dir.display()))?
+        .into_iter()
+        // Only keep items in the same workspace
+        .filter(|fs| fs.model.workspace_id() == workspace_id)
+        .collect::<Vec<FsCandidate>>();
+
+    let ops = compute_sync_ops(db_candidates, fs_candidates);
+    for op in &ops {
+        if let SyncOp::Conflict { fs, merge, .. } = op {
+            let paths = merge.conflicts.iter().map(|c| c.path.join(".")).collect::<Vec<_>>();
+            eprintln!(
+                "Skipped {} because it changed in both places ({}). Resolve it in the app.",
+                fs.rel_path.display(),
+                paths.join(", ")
+            );
+        }
+    }
+    let changes = ops
+        .iter()
+        .filter(|op| !matches!(op, SyncOp::IgnorePrivate { .. } | SyncOp::Conflict { .. }))
+        .count();
+    let sync_state_ops = apply_sync_ops(&db, workspace_id, dir, ops)
+        .map_err(|e| format!("Failed to sync workspace: {e}"))?;
+    apply_sync_state_ops(&db, workspace_id, dir, sync_state_ops)
+        .map_err(|e| format!("Failed to save sync state: {e}"))?;
+    Ok(changes)
+}
+
+fn credentials_error(url: &str, error: Option<String>) -> String {
+    match error {
+        Some(error) => format!("Git credentials required for {url}: {error}"),
+        None => format!("Git credentials required for {url}. Configure a credential helper"),
+    }
+}
@@ -1,8 +1,11 @@
 pub mod auth;
 pub mod cookie_jar;
+pub mod db;
 pub mod environment;
 pub mod folder;
+pub mod git;
 pub mod plugin;
 pub mod request;
+pub mod run;
 pub mod send;
 pub mod workspace;
@@ -9,9 +9,11 @@ use crate::utils::schema::append_agent_hints;
 use crate::utils::workspace::resolve_workspace_id;
 use schemars::schema_for;
 use serde_json::{Map, Value, json};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use tokio::sync::mpsc;
+use yaak::codegen::{ExportAsSnippetParams, export_as_snippet};
+use yaak::curl::{ExportAsCurlParams, export_as_curl};
 use yaak::send::{SendHttpRequestByIdWithPluginsParams, send_http_request_by_id_with_plugins};
 use yaak_http::sender::HttpResponseEvent as SenderHttpResponseEvent;
 use yaak_models::models::{GrpcRequest, HttpRequest, WebsocketRequest};
@@ -42,6 +44,25 @@ pub async fn run(
                 }
             };
         }
+        RequestCommands::Curl { request_id } => {
+            return match curl(ctx, &request_id, environment).await {
+                Ok(()) => 0,
+                Err(error) => {
+                    eprintln!("Error: {error}");
+                    1
+                }
+            };
+        }
+        RequestCommands::Variables { request_id } => variables(ctx, &request_id, environment),
+        RequestCommands::Snippet { request_id, language } => {
+            return match snippet(ctx, &request_id, &language, environment).await {
+                Ok(()) => 0,
+                Err(error) => {
+                    eprintln!("Error: {error}");
+                    1
+                }
+            };
+        }
         RequestCommands::Schema { request_type, pretty } => {
             return match schema(ctx, request_type, pretty).await {
                 Ok(()) => 0,
@@ -367,9 +388,14 @@ fn create(
             "request create",
         )?;
 
+        ctx.store_authentication_secrets::<HttpRequest>(
+            &mut request.id,
+            &mut request.authentication,
+        )?;
+
         let created = ctx
             .db()
-            .upsert_http_request(&request, &UpdateSource::Sync)
+            .upsert_http_request(&request, &UpdateSource::Cli)
             .map_err(|e| format!("Failed to create request: {e}"))?;
 
         println!("Created request: {}", created.id);
@@ -391,7 +417,7 @@ fn create(
 
     let created = ctx
         .db()
-        .upsert_http_request(&request, &UpdateSource::Sync)
+        .upsert_http_request(&request, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to create request: {e}"))?;
 
     println!("Created request: {}", created.id);
@@ -406,11 +432,12 @@ fn update(ctx: &CliContext, json: Option<String>, json_input: Option<String>) ->
         .db()
         .get_http_request(&id)
         .map_err(|e| format!("Failed to get request for update: {e}"))?;
-    let updated = apply_merge_patch(&existing, &patch, &id, "request update")?;
+    let mut updated = apply_merge_patch(&existing, &patch, &id, "request update")?;
+    ctx.store_authentication_secrets::<HttpRequest>(&mut updated.id, &mut updated.authentication)?;
 
     let saved = ctx
         .db()
-        .upsert_http_request(&updated, &UpdateSource::Sync)
+        .upsert_http_request(&updated, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to update request: {e}"))?;
 
     println!("Updated request: {}", saved.id);
@@ -434,12 +461,73 @@ fn delete(ctx: &CliContext, request_id: &str, yes: bool) -> CommandResult {
 
     let deleted = ctx
         .db()
-        .delete_http_request_by_id(request_id, &UpdateSource::Sync)
+        .delete_http_request_by_id(request_id, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to delete request: {e}"))?;
     println!("Deleted request: {}", deleted.id);
     Ok(())
 }
 
+/// Print a request as a cURL command with templates and auth fully resolved.
+async fn curl(ctx: &CliContext, request_id: &str, environment: Option<&str>) -> CommandResult {
+    let request =
+        ctx.db().get_http_request(request_id).map_err(|e| format!("Failed to get request: {e}"))?;
+    let plugin_context =
+        PluginContext::new(Some("cli".to_string()), Some(request.workspace_id.clone()));
+
+    let command = export_as_curl(ExportAsCurlParams {
+        query_manager: ctx.query_manager(),
+        request_id,
+        environment_id: environment,
+        plugin_manager: ctx.plugin_manager(),
+        encryption_manager: ctx.encryption_manager.clone(),
+        plugin_context: &plugin_context,
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    println!("{command}");
+    Ok(())
+}
+
+fn variables(ctx: &CliContext, request_id: &str, environment: Option<&str>) -> CommandResult {
+    let request =
+        ctx.db().get_http_request(request_id).map_err(|e| format!("Failed to get request: {e}"))?;
+    let variables = ctx
+        .db()
+        .resolve_variables_for_http_request(&request, environment)
+        .map_err(|e| format!("Failed to resolve variables: {e}"))?;
+    let variables: BTreeMap<String, String> = variables.into_iter().collect();
+    let output = serde_json::to_string_pretty(&variables)
+        .map_err(|e| format!("Failed to serialize variables: {e}"))?;
+    println!("{output}");
+    Ok(())
+}
+
+async fn snippet(
+    ctx: &CliContext,
+    request_id: &str,
+    language: &str,
+    environment: Option<&str>,
+) -> CommandResult {
+    let request =
+        ctx.db().get_http_request(request_id).map_err(|e| format!("Failed to get request: {e}"))?;
+    let plugin_context =
+        PluginContext::new(Some("cli".to_string()), Some(request.workspace_id.clone()));
+
+    let snippet = export_as_snippet(ExportAsSnippetParams {
+        query_manager: ctx.query_manager(),
+        request_id,
+        environment_id: environment,
+        plugin_manager: ctx.plugin_manager(),
+        encryption_manager: ctx.encryption_manager.clone(),
+        plugin_context: &plugin_context,
+        language,
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    println!("{snippet}");
+    Ok(())
+}
+
 /// Send a request by ID and print response in the same format as legacy `send`.
 pub async fn send_request_by_id(
     ctx: &CliContext,
@@ -0,0 +1,195 @@
+use crate::cli::RunArgs;
+use crate::commands::request;
+use crate::context::{CliContext, CliExecutionContext};
+use std::path::Path;
+use yaak::run_report::run_report;
+use yaak::runner::{CollectionRunParams, run_collection};
+use yaak_models::models::{CollectionRun, CollectionRunState, Folder};
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::PluginContext;
+
+/// The folder, environment, and cookie jar a run resolved to
+pub struct RunTarget {
+    pub folder: Folder,
+    pub environment_id: Option<String>,
+    pub cookie_jar_id: Option<String>,
+}
+
+impl RunTarget {
+    pub fn execution_context(&self) -> CliExecutionContext {
+        CliExecutionContext {
+            request_id: None,
+            workspace_id: Some(self.folder.workspace_id.clone()),
+            environment_id: self.environment_id.clone(),
+            cookie_jar_id: self.cookie_jar_id.clone(),
+        }
+    }
+}
+
+/// Load the workspace file (if any) and resolve what to run, before plugins are started
+pub fn prepare(
+    ctx: &CliContext,
+    args: &RunArgs,
+    environment: Option<&str>,
+    cookie_jar_id: Option<&str>,
+) -> Result<RunTarget, String> {
+    if let Some(path) = &args.workspace_file {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read workspace file {}: {e}", path.display()))?;
+        ctx.db()
+            .import_workspace(&contents, &UpdateSource::Sync)
+            .map_err(|e| format!("Failed to load workspace file {}: {e}", path.display()))?;
+    }
+
+    let folder = resolve_folder(ctx, &args.folder)?;
+    let environment_id = match environment {
+        Some(environment) => Some(resolve_environment_id(ctx, &folder.workspace_id, environment)?),
+        None => None,
+    };
+    let cookie_jar_id = request::resolve_cookie_jar_id(ctx, &folder.workspace_id, cookie_jar_id)?;
+
+    Ok(RunTarget { folder, environment_id, cookie_jar_id })
+}
+
+pub async fn run(ctx: &CliContext, args: RunArgs, target: RunTarget) -> i32 {
+    let plugin_context =
+        PluginContext::new(Some("cli".to_string()), Some(target.folder.workspace_id.clone()));
+    let response_dir = ctx.data_dir().join("responses");
+    let RunArgs { concurrency, delay, fail_fast, data, junit, json_report, .. } = args;
+    let data_file = data.map(|p| p.to_string_lossy().to_string());
+
+    let result = run_collection(CollectionRunParams {
+        query_manager: ctx.query_manager(),
+        blob_manager: ctx.blob_manager(),
+        folder_id: &target.folder.id,
+        environment_id: target.environment_id.as_deref(),
+        update_source: UpdateSource::Sync,
+        cookie_jar_id: target.cookie_jar_id,
+        response_dir: &response_dir,
+        plugin_manager: ctx.plugin_manager(),
+        encryption_manager: ctx.encryption_manager.clone(),
+        plugin_context: &plugin_context,
+        cancelled_rx: None,
+        connection_manager: None,
+        concurrency,
+        delay,
+        stop_on_failure: fail_fast,
+        data_file,
+    })
+    .await;
+
+    match result {
+        Ok(run) => {
+            print_run(ctx, &run);
+            if let Err(error) = write_reports(ctx, &run, junit.as_deref(), json_report.as_deref()) {
+                eprintln!("Error: {error}");
+                return 1;
+            }
+            let failed = run.results.iter().any(|r| !r.passed);
+            if failed || run.state != CollectionRunState::Completed { 1 } else { 0 }
+        }
+        Err(error) => {
+            eprintln!("Error: {error}");
+            1
+        }
+    }
+}
+
+fn print_run(ctx: &CliContext, run: &CollectionRun) {
+    for result in &run.results {
+        let label = if result.passed { "PASS" } else { "FAIL" };
+        let iteration = match run.data_file {
+            Some(_) => format!("#{} ", result.iteration + 1),
+            None => String::new(),
+        };
+        let name = if result.request_name.is_empty() {
+            result.request_id.as_str()
+        } else {
+            result.request_name.as_str()
+        };
+        println!("{label}  {iteration}{name} ({} in {} ms)", result.status, result.elapsed);
+
+        if let Some(error) = &result.error {
+            println!("      {error}");
+        }
+        let response =
+            result.response_id.as_deref().and_then(|id| ctx.db().get_http_response(id).ok());
+        for assertion in response.iter().flat_map(|r| &r.assertion_results) {
+            if !assertion.passed {
+                println!("      {}: {}", assertion.name, assertion.message);
+            }
+        }
+    }
+
+    let passed = run.results.iter().filter(|r| r.passed).count();
+    let failed = run.results.len() - passed;
+    let stopped = if run.state == CollectionRunState::Stopped { " (stopped early)" } else { "" };
+    println!("Run summary: {passed} passed, {failed} failed in {} ms{stopped}", run.elapsed);
+}
+
+fn write_reports(
+    ctx: &CliContext,
+    run: &CollectionRun,
+    junit: Option<&Path>,
+    json_report: Option<&Path>,
+) -> Result<(), String> {
+    if junit.is_none() && json_report.is_none() {
+        return Ok(());
+    }
+
+    let report = run_report(ctx.query_manager(), run)
+        .map_err(|e| format!("Failed to build run report: {e}"))?;
+    if let Some(path) = junit {
+        std::fs::write(path, report.to_junit_xml())
+            .map_err(|e| format!("Failed to write JUnit report {}: {e}", path.display()))?;
+    }
+    if let Some(path) = json_report {
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| format!("Failed to serialize JSON report: {e}"))?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Failed to write JSON report {}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Find a folder by ID, or by name when it's unique across workspaces
+fn resolve_folder(ctx: &CliContext, id_or_name: &str) -> Result<Folder, String> {
+    if let Ok(folder) = ctx.db().get_folder(id_or_name) {
+        return Ok(folder);
+    }
+
+    let workspaces =
+        ctx.db().list_workspaces().map_err(|e| format!("Failed to list workspaces: {e}"))?;
+    let mut matches = Vec::new();
+    for workspace in workspaces {
+        let folders = ctx
+            .db()
+            .list_folders(&workspace.id)
+            .map_err(|e| format!("Failed to list folders: {e}"))?;
+        matches.extend(folders.into_iter().filter(|f| f.name == id_or_name));
+    }
+
+    match matches.len() {
+        0 => Err(format!("Could not find folder '{id_or_name}'")),
+        1 => Ok(matches.remove(0)),
+        n => Err(format!("Found {n} folders named '{id_or_name}'; use the folder ID instead")),
+    }
+}
+
+/// Find an environment in a workspace by ID or name
+fn resolve_environment_id(
+    ctx: &CliContext,
+    workspace_id: &str,
+    id_or_name: &str,
+) -> Result<String, String> {
+    let environments = ctx
+        .db()
+        .list_environments_ensure_base(workspace_id)
+        .map_err(|e| format!("Failed to list environments: {e}"))?;
+    environments
+        .iter()
+        .find(|e| e.id == id_or_name)
+        .or_else(|| environments.iter().find(|e| e.name == id_or_name))
+        .map(|e| e.id.clone())
+        .ok_or(format!("Could not find environment '{id_or_name}'"))
+}
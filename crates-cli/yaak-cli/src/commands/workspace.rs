@@ -1,4 +1,4 @@
-use crate::cli::{WorkspaceArgs, WorkspaceCommands};
+use crate::cli::{ExportFormat, WorkspaceArgs, WorkspaceCommands};
 use crate::context::CliContext;
 use crate::utils::confirm::confirm_delete;
 use crate::utils::json::{
@@ -6,7 +6,11 @@ use crate::utils::json::{
 };
 use crate::utils::schema::append_agent_hints;
 use schemars::schema_for;
+use std::path::PathBuf;
+use yaak::openapi::export_openapi;
+use yaak::postman::export_postman_collection;
 use yaak_models::models::Workspace;
+use yaak_models::queries::WorkspaceExportFormat;
 use yaak_models::util::UpdateSource;
 
 type CommandResult<T = ()> = std::result::Result<T, String>;
@@ -19,6 +23,12 @@ pub fn run(ctx: &CliContext, args: WorkspaceArgs) -> i32 {
         WorkspaceCommands::Create { name, json, json_input } => create(ctx, name, json, json_input),
         WorkspaceCommands::Update { json, json_input } => update(ctx, json, json_input),
         WorkspaceCommands::Delete { workspace_id, yes } => delete(ctx, &workspace_id, yes),
+        WorkspaceCommands::Export {
+            workspace_id,
+            format,
+            include_private_environments,
+            output,
+        } => export(ctx, &workspace_id, format, include_private_environments, output),
     };
 
     match result {
@@ -80,12 +90,17 @@ fn create(
         }
 
         validate_create_id(&payload, "workspace")?;
-        let workspace: Workspace = serde_json::from_value(payload)
+        let mut workspace: Workspace = serde_json::from_value(payload)
             .map_err(|e| format!("Failed to parse workspace create JSON: {e}"))?;
 
+        ctx.store_authentication_secrets::<Workspace>(
+            &mut workspace.id,
+            &mut workspace.authentication,
+        )?;
+
         let created = ctx
             .db()
-            .upsert_workspace(&workspace, &UpdateSource::Sync)
+            .upsert_workspace(&workspace, &UpdateSource::Cli)
             .map_err(|e| format!("Failed to create workspace: {e}"))?;
         println!("Created workspace: {}", created.id);
         return Ok(());
@@ -98,7 +113,7 @@ fn create(
     let workspace = Workspace { name, ..Default::default() };
     let created = ctx
         .db()
-        .upsert_workspace(&workspace, &UpdateSource::Sync)
+        .upsert_workspace(&workspace, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to create workspace: {e}"))?;
     println!("Created workspace: {}", created.id);
     Ok(())
@@ -112,11 +127,12 @@ fn update(ctx: &CliContext, json: Option<String>, json_input: Option<String>) ->
         .db()
         .get_workspace(&id)
         .map_err(|e| format!("Failed to get workspace for update: {e}"))?;
-    let updated = apply_merge_patch(&existing, &patch, &id, "workspace update")?;
+    let mut updated = apply_merge_patch(&existing, &patch, &id, "workspace update")?;
+    ctx.store_authentication_secrets::<Workspace>(&mut updated.id, &mut updated.authentication)?;
 
     let saved = ctx
         .db()
-        .upsert_workspace(&updated, &UpdateSource::Sync)
+        .upsert_workspace(&updated, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to update workspace: {e}"))?;
 
     println!("Updated workspace: {}", saved.id);
@@ -131,8 +147,57 @@ fn delete(ctx: &CliContext, workspace_id: &str, yes: bool) -> CommandResult {
 
     let deleted = ctx
         .db()
-        .delete_workspace_by_id(workspace_id, &UpdateSource::Sync)
+        .delete_workspace_by_id(workspace_id, &UpdateSource::Cli)
         .map_err(|e| format!("Failed to delete workspace: {e}"))?;
     println!("Deleted workspace: {}", deleted.id);
     Ok(())
 }
+
+fn export(
+    ctx: &CliContext,
+    workspace_id: &str,
+    format: ExportFormat,
+    include_private_environments: bool,
+    output: Option<PathBuf>,
+) -> CommandResult {
+    let format = match format {
+        ExportFormat::Json => WorkspaceExportFormat::Json,
+        ExportFormat::Yaml => WorkspaceExportFormat::Yaml,
+        ExportFormat::Postman => {
+            let collection = export_postman_collection(ctx.query_manager(), workspace_id)
+                .map_err(|e| format!("Failed to export workspace: {e}"))?;
+            let contents = serde_json::to_string_pretty(&collection)
+                .map_err(|e| format!("Failed to serialize collection: {e}"))?;
+            return write_export(contents, output);
+        }
+        ExportFormat::Openapi => {
+            let document = export_openapi(ctx.query_manager(), workspace_id)
+                .map_err(|e| format!("Failed to export workspace: {e}"))?;
+            let contents = serde_json::to_string_pretty(&document)
+                .map_err(|e| format!("Failed to serialize document: {e}"))?;
+            return write_export(contents, output);
+        }
+    };
+    let contents = ctx
+        .db()
+        .export_workspace(
+            workspace_id,
+            crate::version::cli_version(),
+            format,
+            include_private_environments,
+        )
+        .map_err(|e| format!("Failed to export workspace: {e}"))?;
+    write_export(contents, output)
+}
+
+fn write_export(contents: String, output: Option<PathBuf>) -> CommandResult {
+    match output {
+        Some(path) => {
+            std::fs::write(&path, contents)
+                .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+            println!("Exported workspace: {}", path.display());
+        }
+        None => println!("{contents}"),
+    }
+    Ok(())
+}
@@ -1,12 +1,16 @@
 use crate::plugin_events::CliPluginEventBridge;
 use include_dir::{Dir, include_dir};
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 use yaak_crypto::manager::EncryptionManager;
+use yaak_crypto::secrets::{KeychainSecretStore, MemorySecretStore};
 use yaak_models::blob_manager::BlobManager;
 use yaak_models::client_db::ClientDb;
+use yaak_models::models::UpsertModelInfo;
 use yaak_models::query_manager::QueryManager;
 use yaak_plugins::events::PluginContext;
 use yaak_plugins::manager::PluginManager;
@@ -47,7 +51,13 @@ impl CliContext {
                     std::process::exit(1);
                 }
             };
-        let encryption_manager = Arc::new(EncryptionManager::new(query_manager.clone(), app_id));
+        let mut encryption_manager = EncryptionManager::new(query_manager.clone(), app_id);
+        // Headless machines often have no keychain, so keep secrets for this process instead
+        if !KeychainSecretStore::new(app_id).is_available() {
+            encryption_manager =
+                encryption_manager.with_secret_store(Arc::new(MemorySecretStore::default()));
+        }
+        let encryption_manager = Arc::new(encryption_manager);
 
         Self {
             data_dir,
@@ -120,6 +130,17 @@ impl CliContext {
         &self.blob_manager
     }
 
+    /// Keep secret authentication values, like a client secret, out of the database
+    pub fn store_authentication_secrets<M: UpsertModelInfo>(
+        &self,
+        id: &mut String,
+        authentication: &mut BTreeMap<String, Value>,
+    ) -> Result<(), String> {
+        self.encryption_manager
+            .store_authentication_secrets::<M>(id, authentication)
+            .map_err(|e| format!("Failed to store authentication secrets: {e}"))
+    }
+
     pub fn plugin_manager(&self) -> Arc<PluginManager> {
         self.plugin_manager.clone().expect("Plugin manager was not initialized for this command")
     }
@@ -81,6 +81,38 @@ async fn main() {
                 }
             }
         }
+        Commands::Run(args) => {
+            // A workspace file is loaded into a throwaway data directory so CI runs never touch
+            // (or depend on) the local Yaak database
+            let run_data_dir = match &args.workspace_file {
+                Some(_) => std::env::temp_dir().join(format!("yaak-run-{}", std::process::id())),
+                None => data_dir.clone(),
+            };
+            let is_temporary = args.workspace_file.is_some();
+            let mut context = CliContext::new(run_data_dir.clone(), app_id);
+            let exit_code = match commands::run::prepare(
+                &context,
+                &args,
+                environment.as_deref(),
+                cookie_jar.as_deref(),
+            ) {
+                Ok(target) => {
+                    context.init_plugins(target.execution_context()).await;
+                    let exit_code = commands::run::run(&context, args, target).await;
+                    context.shutdown().await;
+                    exit_code
+                }
+                Err(error) => {
+                    eprintln!("Error: {error}");
+                    1
+                }
+            };
+            drop(context);
+            if is_temporary {
+                let _ = std::fs::remove_dir_all(&run_data_dir);
+            }
+            exit_code
+        }
         Commands::CookieJar(args) => {
             let context = CliContext::new(data_dir.clone(), app_id);
             let exit_code = commands::cookie_jar::run(&context, args);
@@ -96,7 +128,9 @@ async fn main() {
         Commands::Request(args) => {
             let mut context = CliContext::new(data_dir.clone(), app_id);
             let execution_context_result = match &args.command {
-                RequestCommands::Send { request_id } => resolve_request_execution_context(
+                RequestCommands::Send { request_id }
+                | RequestCommands::Curl { request_id }
+                | RequestCommands::Snippet { request_id, .. } => resolve_request_execution_context(
                     &context,
                     request_id,
                     environment.as_deref(),
@@ -108,7 +142,10 @@ async fn main() {
                 Ok(execution_context) => {
                     let with_plugins = matches!(
                         &args.command,
-                        RequestCommands::Send { .. } | RequestCommands::Schema { .. }
+                        RequestCommands::Send { .. }
+                            | RequestCommands::Curl { .. }
+                            | RequestCommands::Snippet { .. }
+                            | RequestCommands::Schema { .. }
                     );
                     if with_plugins {
                         context.init_plugins(execution_context).await;
@@ -142,6 +179,18 @@ async fn main() {
             context.shutdown().await;
             exit_code
         }
+        Commands::Git(args) => {
+            let context = CliContext::new(data_dir.clone(), app_id);
+            let exit_code = commands::git::run(&context, args).await;
+            context.shutdown().await;
+            exit_code
+        }
+        Commands::Db(args) => {
+            let context = CliContext::new(data_dir.clone(), app_id);
+            let exit_code = commands::db::run(&context, args);
+            context.shutdown().await;
+            exit_code
+        }
     };
 
     if exit_code != 0 {
@@ -28,7 +28,7 @@ use yaak_plugins::events::{
     RenderGrpcRequestResponse, RenderHttpRequestResponse, SendHttpRequestResponse,
     TemplateRenderResponse, WindowInfoResponse, WorkspaceInfo,
 };
-use yaak_plugins::manager::PluginManager;
+use yaak_plugins::manager::{PluginManager, PromptTextHandler};
 use yaak_plugins::template_callback::PluginTemplateCallback;
 use yaak_templates::{RenderOptions, TemplateCallback, render_json_value_raw};
 
@@ -55,6 +55,11 @@ impl CliPluginEventBridge {
         data_dir: PathBuf,
         execution_context: CliExecutionContext,
     ) -> Self {
+        // Native template functions like `prompt()` ask in the terminal, same as plugins
+        let prompt_text_handler: PromptTextHandler =
+            Arc::new(|_plugin_context, req| Box::pin(async move { prompt_text_for_cli(&req) }));
+        plugin_manager.set_prompt_text_handler(prompt_text_handler).await;
+
         let (rx_id, mut rx) = plugin_manager.subscribe("cli").await;
         let rx_id_for_task = rx_id.clone();
         let pm = plugin_manager.clone();
@@ -215,6 +220,8 @@ async fn build_plugin_reply(
                     plugin_context: &plugin_context,
                     cancelled_rx: None,
                     connection_manager: None,
+                    variable_overrides: Vec::new(),
+                    download_path: None,
                 })
                 .await
                 {
@@ -0,0 +1,29 @@
+mod common;
+
+use common::{cli_cmd, seed_request, seed_workspace};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn stats_vacuum_and_check() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+    seed_request(data_dir, "wk_test", "rq_test");
+
+    cli_cmd(data_dir)
+        .args(["db", "stats"])
+        .assert()
+        .success()
+        .stdout(contains("Tables:"))
+        .stdout(contains("http_requests"))
+        .stdout(contains("Seed Workspace (wk_test)"));
+
+    cli_cmd(data_dir).args(["db", "vacuum"]).assert().success().stdout(contains("Reclaimed"));
+
+    cli_cmd(data_dir)
+        .args(["db", "check"])
+        .assert()
+        .success()
+        .stdout(contains("No problems found"));
+}
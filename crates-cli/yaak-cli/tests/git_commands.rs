@@ -0,0 +1,78 @@
+mod common;
+
+use common::{cli_cmd, query_manager, seed_request, seed_workspace};
+use predicates::str::contains;
+use tempfile::TempDir;
+
+#[test]
+fn init_writes_workspace_files_and_reports_status() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    let sync_dir = data_dir.join("repo");
+    seed_workspace(data_dir, "wk_test");
+    seed_request(data_dir, "wk_test", "rq_test");
+
+    cli_cmd(data_dir)
+        .args(["git", "init", "wk_test", "--dir"])
+        .arg(&sync_dir)
+        .assert()
+        .success()
+        .stdout(contains("2 changes synced"));
+
+    assert!(sync_dir.join(".git").is_dir());
+    assert!(sync_dir.join("yaak.wk_test.yaml").is_file());
+    assert!(sync_dir.join("yaak.rq_test.yaml").is_file());
+
+    let workspace_meta = query_manager(data_dir)
+        .connect()
+        .get_workspace_meta("wk_test")
+        .expect("Expected workspace meta");
+    assert_eq!(workspace_meta.setting_sync_dir, Some(sync_dir.to_string_lossy().to_string()));
+
+    // The sync directory is remembered, so --dir is no longer needed
+    cli_cmd(data_dir)
+        .args(["git", "status", "wk_test"])
+        .assert()
+        .success()
+        .stdout(contains("On branch main"))
+        .stdout(contains("untracked  yaak.rq_test.yaml"));
+}
+
+#[test]
+fn sync_loads_external_edits_into_workspace() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    let sync_dir = data_dir.join("repo");
+    seed_workspace(data_dir, "wk_test");
+    seed_request(data_dir, "wk_test", "rq_test");
+
+    cli_cmd(data_dir).args(["git", "init", "wk_test", "--dir"]).arg(&sync_dir).assert().success();
+
+    let request_path = sync_dir.join("yaak.rq_test.yaml");
+    let contents = std::fs::read_to_string(&request_path).expect("Failed to read request file");
+    std::fs::write(&request_path, contents.replace("Seeded Request", "Edited Request"))
+        .expect("Failed to write request file");
+
+    cli_cmd(data_dir)
+        .args(["git", "sync", "wk_test"])
+        .assert()
+        .success()
+        .stdout(contains("Synced 1 changes"));
+
+    let request =
+        query_manager(data_dir).connect().get_http_request("rq_test").expect("Expected request");
+    assert_eq!(request.name, "Edited Request");
+}
+
+#[test]
+fn requires_a_sync_directory() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+
+    cli_cmd(data_dir)
+        .args(["git", "status", "wk_test"])
+        .assert()
+        .failure()
+        .stderr(contains("has no sync directory"));
+}
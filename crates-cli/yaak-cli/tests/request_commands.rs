@@ -2,12 +2,13 @@ mod common;
 
 use common::http_server::TestHttpServer;
 use common::{
-    cli_cmd, parse_created_id, query_manager, seed_grpc_request, seed_request,
+    cli_cmd, parse_created_id, query_manager, seed_folder, seed_grpc_request, seed_request,
     seed_websocket_request, seed_workspace,
 };
 use predicates::str::contains;
 use tempfile::TempDir;
-use yaak_models::models::HttpResponseState;
+use yaak_models::models::{Environment, EnvironmentVariable, HttpRequest, HttpResponseState};
+use yaak_models::util::UpdateSource;
 
 #[test]
 fn show_and_delete_yes_round_trip() {
@@ -289,3 +290,134 @@ fn request_send_websocket_returns_explicit_nyi_error() {
         .code(1)
         .stderr(contains("WebSocket request send is not implemented yet in yaak-cli"));
 }
+
+#[test]
+fn request_curl_prints_resolved_command() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+
+    let create_assert = cli_cmd(data_dir)
+        .args([
+            "request",
+            "create",
+            "wk_test",
+            "--json",
+            r#"{
+                "method": "POST",
+                "url": "https://example.com/items",
+                "headers": [{ "name": "Accept", "value": "application/json" }],
+                "bodyType": "text/plain",
+                "body": { "text": "it's" }
+            }"#,
+        ])
+        .assert()
+        .success();
+    let request_id = parse_created_id(&create_assert.get_output().stdout, "request create");
+
+    cli_cmd(data_dir)
+        .args(["request", "curl", &request_id])
+        .assert()
+        .success()
+        .stdout(contains("curl -X POST 'https://example.com/items'"))
+        .stdout(contains("--header 'Accept: application/json'"))
+        .stdout(contains(r#"--data-raw 'it'\''s'"#));
+}
+
+#[test]
+fn request_variables_prints_merged_variables() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+    seed_folder(data_dir, "wk_test", "fl_test");
+
+    let db = query_manager(data_dir);
+    let db = db.connect();
+    let variable = |name: &str, value: &str| EnvironmentVariable {
+        enabled: true,
+        name: name.to_string(),
+        value: value.to_string(),
+        ..Default::default()
+    };
+    let base = db.get_base_environment("wk_test").expect("Failed to get base environment");
+    db.upsert_environment(
+        &Environment {
+            variables: vec![variable("host", "example.com"), variable("base_path", "/")],
+            ..base
+        },
+        &UpdateSource::Sync,
+    )
+    .expect("Failed to update base environment");
+    db.upsert_environment(
+        &Environment {
+            workspace_id: "wk_test".to_string(),
+            parent_model: "folder".to_string(),
+            parent_id: Some("fl_test".to_string()),
+            variables: vec![variable("base_path", "/api")],
+            ..Default::default()
+        },
+        &UpdateSource::Sync,
+    )
+    .expect("Failed to create folder environment");
+    db.upsert_http_request(
+        &HttpRequest {
+            id: "rq_test".to_string(),
+            workspace_id: "wk_test".to_string(),
+            folder_id: Some("fl_test".to_string()),
+            ..Default::default()
+        },
+        &UpdateSource::Sync,
+    )
+    .expect("Failed to seed request");
+
+    cli_cmd(data_dir)
+        .args(["request", "variables", "rq_test"])
+        .assert()
+        .success()
+        .stdout(contains(r#""base_path": "/api""#))
+        .stdout(contains(r#""host": "example.com""#));
+}
+
+#[test]
+fn request_snippet_prints_python_requests() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+
+    let create_assert = cli_cmd(data_dir)
+        .args([
+            "request",
+            "create",
+            "wk_test",
+            "--json",
+            r#"{
+                "method": "POST",
+                "url": "https://example.com/items",
+                "bodyType": "text/plain",
+                "body": { "text": "hello" }
+            }"#,
+        ])
+        .assert()
+        .success();
+    let request_id = parse_created_id(&create_assert.get_output().stdout, "request create");
+
+    cli_cmd(data_dir)
+        .args([
+            "request",
+            "snippet",
+            &request_id,
+            "--language",
+            "python-requests",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("import requests"))
+        .stdout(contains(r#""https://example.com/items","#))
+        .stdout(contains(r#"data="hello","#));
+
+    cli_cmd(data_dir)
+        .args(["request", "snippet", &request_id, "--language", "cobol"])
+        .assert()
+        .failure()
+        .stderr(contains("No code generator for language cobol"));
+}
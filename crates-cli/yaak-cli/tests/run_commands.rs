@@ -0,0 +1,145 @@
+mod common;
+
+use common::http_server::TestHttpServer;
+use common::{cli_cmd, query_manager, seed_folder, seed_workspace};
+use predicates::str::contains;
+use tempfile::TempDir;
+use yaak_models::models::{Assertion, AssertionKind, HttpRequest};
+use yaak_models::queries::WorkspaceExportFormat;
+use yaak_models::util::UpdateSource;
+
+fn seed_folder_request(data_dir: &std::path::Path, url: &str, assertions: Vec<Assertion>) {
+    let request = HttpRequest {
+        id: "rq_folder_run".to_string(),
+        workspace_id: "wk_test".to_string(),
+        folder_id: Some("fl_test".to_string()),
+        name: "Folder Run".to_string(),
+        method: "GET".to_string(),
+        url: url.to_string(),
+        assertions,
+        ..Default::default()
+    };
+    query_manager(data_dir)
+        .connect()
+        .upsert_http_request(&request, &UpdateSource::Sync)
+        .expect("Failed to seed folder request");
+}
+
+fn status_equals(value: &str) -> Assertion {
+    Assertion {
+        enabled: true,
+        kind: AssertionKind::StatusEquals,
+        value: value.to_string(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn run_folder_passes_and_prints_summary() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+    seed_folder(data_dir, "wk_test", "fl_test");
+
+    let server = TestHttpServer::spawn_ok("folder run");
+    seed_folder_request(data_dir, &server.url, vec![status_equals("200")]);
+
+    cli_cmd(data_dir)
+        .args(["run", "fl_test"])
+        .assert()
+        .success()
+        .stdout(contains("PASS  Folder Run (200"))
+        .stdout(contains("Run summary: 1 passed, 0 failed"));
+}
+
+#[test]
+fn run_folder_by_name_fails_on_assertion_failure() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+    seed_folder(data_dir, "wk_test", "fl_test");
+
+    let server = TestHttpServer::spawn_ok("folder run");
+    seed_folder_request(data_dir, &server.url, vec![status_equals("201")]);
+
+    cli_cmd(data_dir)
+        .args(["run", "Seed Folder"])
+        .assert()
+        .failure()
+        .code(1)
+        .stdout(contains("FAIL  Folder Run (200"))
+        .stdout(contains("Run summary: 0 passed, 1 failed"));
+}
+
+#[test]
+fn run_folder_writes_junit_and_json_reports() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+    seed_workspace(data_dir, "wk_test");
+    seed_folder(data_dir, "wk_test", "fl_test");
+
+    let server = TestHttpServer::spawn_ok("folder run");
+    seed_folder_request(data_dir, &server.url, vec![status_equals("201")]);
+
+    let junit_path = data_dir.join("junit.xml");
+    let json_path = data_dir.join("report.json");
+    cli_cmd(data_dir)
+        .args(["run", "fl_test", "--junit"])
+        .arg(&junit_path)
+        .arg("--json-report")
+        .arg(&json_path)
+        .assert()
+        .failure()
+        .code(1);
+
+    let junit = std::fs::read_to_string(&junit_path).expect("Failed to read JUnit report");
+    assert!(junit.contains(r#"<testsuite name="Seed Folder" tests="1" failures="1" errors="0""#));
+    assert!(junit.contains(r#"<failure message="status == 201""#));
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).expect("Failed to read report"))
+            .expect("Failed to parse JSON report");
+    assert_eq!(json["failed"], 1);
+    assert_eq!(json["results"][0]["requestName"], "Folder Run");
+    assert_eq!(json["results"][0]["assertions"][0]["passed"], false);
+}
+
+#[test]
+fn run_folder_from_workspace_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let source_dir = temp_dir.path().join("source");
+    std::fs::create_dir_all(&source_dir).expect("Failed to create source dir");
+    seed_workspace(&source_dir, "wk_test");
+    seed_folder(&source_dir, "wk_test", "fl_test");
+
+    let server = TestHttpServer::spawn_ok("folder run");
+    seed_folder_request(&source_dir, &server.url, vec![status_equals("200")]);
+
+    let export = query_manager(&source_dir)
+        .connect()
+        .export_workspace("wk_test", "1.0.0", WorkspaceExportFormat::Json, false)
+        .expect("Failed to export workspace");
+    let workspace_file = temp_dir.path().join("workspace.json");
+    std::fs::write(&workspace_file, export).expect("Failed to write workspace file");
+
+    let data_dir = temp_dir.path().join("empty");
+    cli_cmd(&data_dir)
+        .args(["run", "fl_test", "--workspace-file"])
+        .arg(&workspace_file)
+        .assert()
+        .success()
+        .stdout(contains("Run summary: 1 passed, 0 failed"));
+}
+
+#[test]
+fn run_unknown_folder_fails_with_clear_error() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+
+    cli_cmd(data_dir)
+        .args(["run", "does_not_exist"])
+        .assert()
+        .failure()
+        .code(1)
+        .stderr(contains("Could not find folder 'does_not_exist'"));
+}
@@ -75,3 +75,41 @@ fn workspace_schema_outputs_json_schema() {
         ))
         .stdout(contains("\"name\""));
 }
+
+#[test]
+fn workspace_export_outputs_json_and_yaml() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let data_dir = temp_dir.path();
+
+    let create_assert =
+        cli_cmd(data_dir).args(["workspace", "create", "--name", "Export Me"]).assert().success();
+    let workspace_id = parse_created_id(&create_assert.get_output().stdout, "workspace create");
+
+    cli_cmd(data_dir)
+        .args(["workspace", "export", &workspace_id])
+        .assert()
+        .success()
+        .stdout(contains("\"yaakSchema\""))
+        .stdout(contains("\"name\": \"Export Me\""));
+
+    cli_cmd(data_dir)
+        .args(["workspace", "export", &workspace_id, "--format", "yaml"])
+        .assert()
+        .success()
+        .stdout(contains("yaakSchema:"))
+        .stdout(contains("name: Export Me"));
+
+    cli_cmd(data_dir)
+        .args(["workspace", "export", &workspace_id, "--format", "postman"])
+        .assert()
+        .success()
+        .stdout(contains("collection/v2.1.0/collection.json"))
+        .stdout(contains("\"name\": \"Export Me\""));
+
+    cli_cmd(data_dir)
+        .args(["workspace", "export", &workspace_id, "--format", "openapi"])
+        .assert()
+        .success()
+        .stdout(contains("\"openapi\": \"3.1.0\""))
+        .stdout(contains("\"title\": \"Export Me\""));
+}
@@ -28,6 +28,12 @@ pub enum Error {
     #[error(transparent)]
     WebsocketError(#[from] yaak_ws::error::Error),
 
+    #[error(transparent)]
+    MqttError(#[from] yaak_mqtt::error::Error),
+
+    #[error(transparent)]
+    SocketError(#[from] yaak_socket::error::Error),
+
     #[cfg(feature = "license")]
     #[error(transparent)]
     LicenseError(#[from] yaak_license::error::Error),
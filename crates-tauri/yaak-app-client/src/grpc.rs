@@ -1,14 +1,32 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use crate::PluginContextExt;
+use crate::error::Error::GenericError;
 use crate::error::Result;
 use crate::models_ext::QueryManagerExt;
+use crate::render::{render_proxy_auth, render_ssh_tunnel};
 use KeyAndValueRef::{Ascii, Binary};
-use tauri::{Manager, Runtime, WebviewWindow};
-use yaak_grpc::{KeyAndValueRef, MetadataMap};
-use yaak_models::models::GrpcRequest;
+use log::warn;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+use yaak_grpc::dns::GrpcDnsOverrides;
+use yaak_grpc::manager::{GrpcHandle, response_encoding};
+use yaak_grpc::proxy::{GrpcProxy, GrpcProxyAuth};
+use yaak_grpc::{
+    CompressionEncoding, KeyAndValueRef, MetadataMap, ServiceDefinition, StatusDetail, WireProtocol,
+};
+use yaak_http::dns::parse_dns_overrides;
+use yaak_http::manager::HttpConnectionManager;
+use yaak_http::ssh_tunnel::tunnels_url;
+use yaak_models::models::{
+    Environment, GrpcCompression, GrpcConnection, GrpcErrorDetail, GrpcProtocol, GrpcRequest,
+    ProxySetting,
+};
+use yaak_models::util::UpdateSource;
 use yaak_plugins::events::{CallHttpAuthenticationRequest, HttpHeader};
 use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::{RenderErrorBehavior, RenderOptions};
 
 pub(crate) fn metadata_to_map(metadata: MetadataMap) -> BTreeMap<String, String> {
     let mut entries = BTreeMap::new();
@@ -21,6 +39,124 @@ pub(crate) fn metadata_to_map(metadata: MetadataMap) -> BTreeMap<String, String>
     entries
 }
 
+/// Record the compression the server used for its messages, if it compressed them
+pub(crate) fn record_response_encoding<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    connection_id: &str,
+    metadata: &MetadataMap,
+    source: &UpdateSource,
+) -> Result<()> {
+    let Some(encoding) = response_encoding(metadata) else {
+        return Ok(());
+    };
+    app_handle.with_tx(|c| {
+        let connection = c.get_grpc_connection(connection_id)?;
+        c.upsert_grpc_connection(
+            &GrpcConnection { response_encoding: Some(encoding), ..connection },
+            source,
+        )
+    })?;
+    Ok(())
+}
+
+/// Convert decoded status details for storing on an event, keeping the raw bytes only for details
+/// that couldn't be decoded
+pub(crate) fn grpc_error_details(details: Vec<StatusDetail>) -> Vec<GrpcErrorDetail> {
+    details
+        .into_iter()
+        .map(|d| GrpcErrorDetail {
+            raw: if d.value.is_none() { Some(d.raw) } else { None },
+            type_name: d.type_name,
+            value: d.value,
+        })
+        .collect()
+}
+
+pub(crate) fn compression_encoding(compression: GrpcCompression) -> CompressionEncoding {
+    match compression {
+        GrpcCompression::Gzip => CompressionEncoding::Gzip,
+        GrpcCompression::Zstd => CompressionEncoding::Zstd,
+    }
+}
+
+pub(crate) fn wire_protocol(protocol: GrpcProtocol) -> WireProtocol {
+    match protocol {
+        GrpcProtocol::Grpc => WireProtocol::Grpc,
+        GrpcProtocol::GrpcWeb => WireProtocol::GrpcWeb,
+        GrpcProtocol::ConnectJson => WireProtocol::ConnectJson,
+        GrpcProtocol::ConnectProto => WireProtocol::ConnectProto,
+    }
+}
+
+/// Resolve the proxy that connections for the request go through, from the workspace's proxy
+/// setting or the global one. gRPC has no system proxy lookup, so anything other than an enabled
+/// proxy connects directly. Requests routed through an SSH tunnel use the tunnel's local SOCKS5
+/// proxy instead, opening the tunnel if needed.
+pub(crate) async fn resolve_grpc_proxy<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &GrpcRequest,
+    environment_chain: Vec<Environment>,
+    template_callback: &PluginTemplateCallback,
+) -> Result<Option<GrpcProxy>> {
+    let resolved_settings = window.db().resolve_settings_for_grpc_request(request)?;
+    if let Some(tunnel) =
+        resolved_settings.ssh_tunnel.value.filter(|t| tunnels_url(t, &request.url))
+    {
+        let tunnel = render_ssh_tunnel(
+            &tunnel,
+            environment_chain,
+            template_callback,
+            &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
+        )
+        .await?;
+        let connection_manager = window.state::<HttpConnectionManager>();
+        let tunnel_proxy = connection_manager.open_ssh_tunnel(&tunnel).await?;
+        return Ok(Some(GrpcProxy {
+            http: tunnel_proxy.url(),
+            https: tunnel_proxy.url(),
+            auth: Some(GrpcProxyAuth { user: tunnel_proxy.user, password: tunnel_proxy.password }),
+            bypass: String::new(),
+        }));
+    }
+
+    let workspace = window.db().get_workspace(&request.workspace_id)?;
+    let Some(ProxySetting::Enabled { http, https, auth, bypass, disabled: false }) =
+        window.db().resolve_proxy_for_workspace(&workspace)
+    else {
+        return Ok(None);
+    };
+    let auth = match auth {
+        Some(auth) => Some(
+            render_proxy_auth(
+                &auth,
+                environment_chain,
+                template_callback,
+                &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
+            )
+            .await?,
+        ),
+        None => None,
+    };
+    Ok(Some(GrpcProxy {
+        http,
+        https,
+        bypass,
+        auth: auth.map(|a| GrpcProxyAuth { user: a.user, password: a.password }),
+    }))
+}
+
+/// Resolve the workspace's DNS overrides, so overridden hostnames connect to fixed addresses
+pub(crate) fn resolve_grpc_dns_overrides<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &GrpcRequest,
+) -> Result<GrpcDnsOverrides> {
+    let workspace = window.db().get_workspace(&request.workspace_id)?;
+    let overrides = parse_dns_overrides(workspace.setting_dns_overrides);
+    Ok(GrpcDnsOverrides::new(
+        overrides.into_iter().map(|(hostname, resolved)| (hostname, resolved.addresses())),
+    ))
+}
+
 pub(crate) fn resolve_grpc_request<R: Runtime>(
     window: &WebviewWindow<R>,
     request: &GrpcRequest,
@@ -96,3 +232,56 @@ pub(crate) async fn build_metadata<R: Runtime>(
 
     Ok(metadata)
 }
+
+/// Load services from the proto files attached to the request's workspace, reusing the descriptor
+/// set they last compiled to. The pool is also registered as the request's server reflection pool,
+/// so connecting doesn't try to reflect against a server that doesn't support it. Returns `None`
+/// when the workspace has no proto files.
+pub(crate) async fn services_from_workspace_proto_files<R: Runtime>(
+    window: &WebviewWindow<R>,
+    handle: &mut GrpcHandle,
+    request: &GrpcRequest,
+    uri: &str,
+) -> Result<Option<Vec<ServiceDefinition>>> {
+    let proto_files = match window.db().get_proto_files(&request.workspace_id)? {
+        Some(p) if !p.paths.is_empty() => p,
+        _ => return Ok(None),
+    };
+    let sources: Vec<PathBuf> = proto_files.sources().into_iter().map(PathBuf::from).collect();
+
+    let mut compiled = false;
+    if let Some(descriptor_set) = &proto_files.descriptor_set {
+        match handle.load_pool(&request.id, uri, &sources, descriptor_set) {
+            Ok(()) => compiled = true,
+            Err(e) => warn!("Failed to load compiled proto files, recompiling: {e}"),
+        }
+    }
+
+    // Compiling proto files doesn't touch the network, so no metadata, TLS, proxy, or DNS settings
+    // are needed
+    let services = handle
+        .services(
+            &request.id,
+            uri,
+            &sources,
+            &BTreeMap::new(),
+            true,
+            None,
+            &[],
+            None,
+            GrpcDnsOverrides::default(),
+        )
+        .await
+        .map_err(|e| GenericError(e.to_string()))?;
+
+    if let Some(descriptor_set) = handle.encode_pool(&request.id, uri, &sources) {
+        if !compiled {
+            window.db().set_proto_files_descriptor_set(&request.workspace_id, &descriptor_set)?;
+        }
+        handle
+            .load_pool(&request.id, uri, &Vec::new(), &descriptor_set)
+            .map_err(|e| GenericError(e.to_string()))?;
+    }
+
+    Ok(Some(services))
+}
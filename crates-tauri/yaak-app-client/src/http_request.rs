@@ -4,6 +4,7 @@ use crate::error::Result;
 use crate::models_ext::BlobManagerExt;
 use crate::models_ext::QueryManagerExt;
 use log::warn;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
@@ -69,6 +70,7 @@ pub async fn send_http_request<R: Runtime>(
     environment: Option<Environment>,
     cookie_jar: Option<CookieJar>,
     cancelled_rx: &mut Receiver<bool>,
+    download_path: Option<&Path>,
 ) -> Result<HttpResponse> {
     send_http_request_with_context(
         window,
@@ -78,6 +80,7 @@ pub async fn send_http_request<R: Runtime>(
         cookie_jar,
         cancelled_rx,
         &window.plugin_context(),
+        download_path,
     )
     .await
 }
@@ -90,6 +93,7 @@ pub async fn send_http_request_with_context<R: Runtime>(
     cookie_jar: Option<CookieJar>,
     cancelled_rx: &Receiver<bool>,
     plugin_context: &PluginContext,
+    download_path: Option<&Path>,
 ) -> Result<HttpResponse> {
     let app_handle = window.app_handle().clone();
     let update_source = UpdateSource::from_window_label(window.label());
@@ -106,6 +110,7 @@ pub async fn send_http_request_with_context<R: Runtime>(
         cancelled_rx,
         plugin_context,
         &mut response_ctx,
+        download_path,
     )
     .await;
 
@@ -116,7 +121,10 @@ pub async fn send_http_request_with_context<R: Runtime>(
             let elapsed = start.elapsed().as_millis() as i32;
             warn!("Failed to send request: {error:?}");
             let _ = response_ctx.update(|r| {
-                r.state = HttpResponseState::Closed;
+                // Keep the timed out state recorded by the sender
+                if !matches!(r.state, HttpResponseState::TimedOut) {
+                    r.state = HttpResponseState::Closed;
+                }
                 r.elapsed = elapsed;
                 if r.elapsed_headers == 0 {
                     r.elapsed_headers = elapsed;
@@ -136,6 +144,7 @@ async fn send_http_request_inner<R: Runtime>(
     cancelled_rx: &Receiver<bool>,
     plugin_context: &PluginContext,
     response_ctx: &mut ResponseContext<R>,
+    download_path: Option<&Path>,
 ) -> Result<HttpResponse> {
     let app_handle = window.app_handle().clone();
     let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
@@ -161,6 +170,8 @@ async fn send_http_request_inner<R: Runtime>(
         plugin_context,
         cancelled_rx: Some(cancelled_rx.clone()),
         connection_manager: Some(connection_manager.inner()),
+        variable_overrides: Vec::new(),
+        download_path,
     })
     .await
     .map_err(|e| GenericError(e.to_string()))?;
@@ -182,5 +193,8 @@ pub fn resolve_http_request<R: Runtime>(
     let headers = window.db().resolve_headers_for_http_request(request)?;
     new_request.headers = headers;
 
+    let url_parameters = window.db().resolve_query_params_for_http_request(request)?;
+    new_request.url_parameters = url_parameters;
+
     Ok((new_request, authentication_context_id))
 }
@@ -8,7 +8,7 @@ use std::io::ErrorKind;
 use tauri::{Manager, Runtime, WebviewWindow};
 use yaak_core::WorkspaceContext;
 use yaak_models::models::{
-    Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workspace,
+    CookieJar, Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workspace,
 };
 use yaak_models::util::{BatchUpsertResult, UpdateSource, maybe_gen_id, maybe_gen_id_opt};
 use yaak_plugins::manager::PluginManager;
@@ -111,16 +111,29 @@ pub(crate) async fn import_data<R: Runtime>(
         })
         .collect();
 
+    let cookie_jars: Vec<CookieJar> = resources
+        .cookie_jars
+        .into_iter()
+        .map(|mut v| {
+            v.id = maybe_gen_id::<CookieJar>(&ctx, v.id.as_str(), &mut id_map);
+            v.workspace_id = maybe_gen_id::<Workspace>(&ctx, v.workspace_id.as_str(), &mut id_map);
+            v
+        })
+        .collect();
+
     info!("Importing data");
 
     let upserted = window.with_tx(|tx| {
         tx.batch_upsert(
-            workspaces,
-            environments,
-            folders,
-            http_requests,
-            grpc_requests,
-            websocket_requests,
+            BatchUpsertResult {
+                workspaces,
+                environments,
+                folders,
+                http_requests,
+                grpc_requests,
+                websocket_requests,
+                cookie_jars,
+            },
             &UpdateSource::Import,
         )
     })?;
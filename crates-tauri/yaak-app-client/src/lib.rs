@@ -2,7 +2,11 @@ extern crate core;
 use crate::encoding::read_response_body;
 use crate::error::Error::GenericError;
 use crate::error::Result;
-use crate::grpc::{build_metadata, metadata_to_map, resolve_grpc_request};
+use crate::grpc::{
+    build_metadata, compression_encoding, grpc_error_details, metadata_to_map,
+    record_response_encoding, resolve_grpc_dns_overrides, resolve_grpc_proxy, resolve_grpc_request,
+    services_from_workspace_proto_files, wire_protocol,
+};
 use crate::http_request::{resolve_http_request, send_http_request};
 use crate::import::import_data;
 use crate::models_ext::{BlobManagerExt, QueryManagerExt};
@@ -15,7 +19,7 @@ use eventsource_client::{EventParser, SSE};
 use log::{debug, error, info, warn};
 use std::collections::HashMap;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
@@ -31,29 +35,48 @@ use tauri_plugin_window_state::{AppHandleExt, StateFlags};
 use tokio::sync::Mutex;
 use tokio::task::block_in_place;
 use tokio::time;
+use yaak::codegen::{ExportAsSnippetParams, export_as_snippet, list_codegen_languages};
+use yaak::curl::{ExportAsCurlParams, export_as_curl};
+use yaak::graphql::{introspection_request, validate_introspection_response};
+use yaak::har::export_har;
+use yaak::jsonpath::filter_json_file;
+use yaak::oauth2::{OAUTH2_AUTHENTICATION_TYPE, delete_oauth2_token, oauth2_config_hash};
+use yaak::openapi::export_openapi;
+use yaak::postman::export_postman_collection;
+use yaak::pre_request::{PreRequestParams, run_pre_request_steps};
+use yaak::runner::{CollectionRunParams, run_collection};
+use yaak::xpath::filter_markup;
 use yaak_common::command::new_checked_command;
 use yaak_crypto::manager::EncryptionManager;
-use yaak_grpc::manager::{GrpcConfig, GrpcHandle};
-use yaak_grpc::{Code, ServiceDefinition};
+use yaak_grpc::manager::{GrpcConfig, GrpcHandle, GrpcStreamError, reflection_cache_key};
+use yaak_grpc::{Code, ServiceDefinition, Status};
+use yaak_http::cookies::{cookies_from_netscape, cookies_to_netscape};
+use yaak_http::curl::parse_curl_command;
+use yaak_http::manager::HttpConnectionManager;
 use yaak_mac_window::AppHandleMacWindowExt;
 use yaak_models::models::{
-    AnyModel, CookieJar, Environment, GrpcConnection, GrpcConnectionState, GrpcEvent,
-    GrpcEventType, HttpRequest, HttpResponse, HttpResponseEvent, HttpResponseState, Workspace,
-    WorkspaceMeta,
+    AnyModel, CollectionRun, CookieJar, Environment, GraphQlIntrospection, GrpcConnection,
+    GrpcConnectionState, GrpcEvent, GrpcEventType, HttpRequest, HttpResponse, HttpResponseEvent,
+    HttpResponseState, MonitorRun, Workspace, WorkspaceMeta,
+};
+use yaak_models::queries::any_request::AnyRequest;
+use yaak_models::util::{
+    BatchUpsertResult, UpdateSource, generate_id, get_workspace_export_resources,
+    redact_secret_variables,
 };
-use yaak_models::util::{BatchUpsertResult, UpdateSource, get_workspace_export_resources};
 use yaak_plugins::events::{
     CallFolderActionArgs, CallFolderActionRequest, CallGrpcRequestActionArgs,
     CallGrpcRequestActionRequest, CallHttpRequestActionArgs, CallHttpRequestActionRequest,
     CallWebsocketRequestActionArgs, CallWebsocketRequestActionRequest, CallWorkspaceActionArgs,
-    CallWorkspaceActionRequest, Color, FilterResponse, GetFolderActionsResponse,
+    CallWorkspaceActionRequest, CodegenLanguage, Color, FilterResponse, GetFolderActionsResponse,
     GetGrpcRequestActionsResponse, GetHttpAuthenticationConfigResponse,
     GetHttpAuthenticationSummaryResponse, GetHttpRequestActionsResponse,
     GetTemplateFunctionConfigResponse, GetTemplateFunctionSummaryResponse,
     GetWebsocketRequestActionsResponse, GetWorkspaceActionsResponse, InternalEvent,
-    InternalEventPayload, JsonPrimitive, PluginContext, RenderPurpose, ShowToastRequest,
+    InternalEventPayload, JsonPrimitive, PluginContext, PromptTextRequest, RenderPurpose,
+    ShowToastRequest,
 };
-use yaak_plugins::manager::PluginManager;
+use yaak_plugins::manager::{PluginManager, PromptTextHandler};
 use yaak_plugins::plugin_meta::PluginMetadata;
 use yaak_plugins::template_callback::PluginTemplateCallback;
 use yaak_sse::sse::ServerSentEvent;
@@ -61,7 +84,7 @@ use yaak_tauri_utils::window::WorkspaceWindowTrait;
 use yaak_templates::format_json::format_json;
 use yaak_templates::strip_json_comments::strip_json_comments;
 use yaak_templates::{RenderErrorBehavior, RenderOptions, Tokens, transform_args};
-use yaak_tls::find_client_certificate;
+use yaak_tls::{find_ca_certificates, select_client_certificate};
 
 mod commands;
 mod encoding;
@@ -72,11 +95,15 @@ mod grpc;
 mod history;
 mod http_request;
 mod import;
+mod mock_servers;
 mod models_ext;
+mod monitors;
+mod mqtt_ext;
 mod notifications;
 mod plugin_events;
 mod plugins_ext;
 mod render;
+mod socket_ext;
 mod sync_ext;
 mod updates;
 mod uri_scheme;
@@ -278,11 +305,15 @@ async fn cmd_dismiss_notification<R: Runtime>(
     Ok(yaak_notifier.lock().await.seen(&window, notification_id).await?)
 }
 
+/// How long server reflection results are reused before reflecting again
+const GRPC_DESCRIPTOR_CACHE_TTL: chrono::Duration = chrono::Duration::hours(24);
+
 #[tauri::command]
 async fn cmd_grpc_reflect<R: Runtime>(
     request_id: &str,
     environment_id: Option<&str>,
     proto_files: Vec<String>,
+    force: Option<bool>,
     window: WebviewWindow<R>,
     app_handle: AppHandle<R>,
     grpc_handle: State<'_, Mutex<GrpcHandle>>,
@@ -299,15 +330,16 @@ async fn cmd_grpc_reflect<R: Runtime>(
 
     let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
     let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let template_callback = PluginTemplateCallback::new(
+        plugin_manager,
+        encryption_manager,
+        &PluginContext::new(Some(window.label().to_string()), window.workspace_id()),
+        RenderPurpose::Send,
+    );
     let req = render_grpc_request(
         &resolved_request,
-        environment_chain,
-        &PluginTemplateCallback::new(
-            plugin_manager,
-            encryption_manager,
-            &PluginContext::new(Some(window.label().to_string()), window.workspace_id()),
-            RenderPurpose::Send,
-        ),
+        environment_chain.clone(),
+        &template_callback,
         &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
     )
     .await?;
@@ -315,26 +347,114 @@ async fn cmd_grpc_reflect<R: Runtime>(
     let uri = safe_uri(&req.url);
     let metadata = build_metadata(&window, &req, &auth_context_id).await?;
     let settings = window.db().get_settings();
-    let client_certificate =
-        find_client_certificate(req.url.as_str(), &settings.client_certificates);
+    let client_certificate = select_client_certificate(
+        req.url.as_str(),
+        resolved_settings.client_certificate.value.as_ref(),
+        &settings.client_certificates,
+    );
+    let ca_certificates = find_ca_certificates(&resolved_settings.ca_certificates.value);
     let proto_files: Vec<PathBuf> =
         proto_files.iter().map(|p| PathBuf::from_str(p).unwrap()).collect();
 
-    // Always invalidate cached pool when this command is called, to force re-reflection
+    let validate_certificates = resolved_settings.validate_certificates.value;
+    let proxy = resolve_grpc_proxy(&window, &req, environment_chain, &template_callback).await?;
+    let dns_overrides = resolve_grpc_dns_overrides(&window, &req)?;
+
+    // Server reflection results are shared across requests to the same host, TLS configuration,
+    // and metadata, so they're looked up there before hitting the network
+    let cache_key = match proto_files.is_empty() {
+        true => {
+            reflection_cache_key(&uri, validate_certificates, &client_certificate, &metadata).ok()
+        }
+        false => None,
+    };
+
+    // Always invalidate the in-memory pool when this command is called, to pick up the cache
     let mut handle = grpc_handle.lock().await;
     handle.invalidate_pool(&req.id, &uri, &proto_files);
 
-    Ok(handle
+    let mut loaded_from_cache = false;
+    if let Some((host, tls_key)) = &cache_key {
+        if force.unwrap_or_default() {
+            window.db().delete_grpc_descriptor_caches(host)?;
+        } else if let Some(cache) = window.db().get_grpc_descriptor_cache(host, tls_key) {
+            match handle.load_pool(&req.id, &uri, &proto_files, &cache.descriptor_set) {
+                Ok(()) => loaded_from_cache = true,
+                Err(e) => warn!("Failed to load cached gRPC descriptors for {host}: {e}"),
+            }
+        }
+    }
+
+    let services = match handle
         .services(
             &req.id,
             &uri,
             &proto_files,
             &metadata,
-            resolved_settings.validate_certificates.value,
+            validate_certificates,
             client_certificate,
+            &ca_certificates,
+            proxy,
+            dns_overrides,
         )
         .await
-        .map_err(|e| GenericError(e.to_string()))?)
+    {
+        Ok(services) => services,
+        // Servers without reflection can still be used through the workspace's proto files
+        Err(e) if proto_files.is_empty() => {
+            return match services_from_workspace_proto_files(&window, &mut handle, &req, &uri)
+                .await?
+            {
+                Some(services) => Ok(services),
+                None => Err(GenericError(e.to_string())),
+            };
+        }
+        Err(e) => return Err(GenericError(e.to_string())),
+    };
+
+    let descriptor_set = match loaded_from_cache {
+        true => None,
+        false => handle.encode_pool(&req.id, &uri, &proto_files),
+    };
+    if let (Some((host, tls_key)), Some(descriptor_set)) = (&cache_key, descriptor_set) {
+        window.db().set_grpc_descriptor_cache(
+            host,
+            tls_key,
+            &descriptor_set,
+            GRPC_DESCRIPTOR_CACHE_TTL,
+        )?;
+    }
+
+    Ok(services)
+}
+
+/// Send the request's saved message templates over an open streaming connection, in order. When
+/// `template_ids` is given, only those templates are sent.
+#[tauri::command]
+async fn cmd_grpc_replay_message_templates<R: Runtime>(
+    connection_id: &str,
+    template_ids: Option<Vec<String>>,
+    app_handle: AppHandle<R>,
+) -> YaakResult<()> {
+    let connection = app_handle.db().get_grpc_connection(connection_id)?;
+    if connection.state.is_closed() {
+        return Err(GenericError("gRPC connection is closed".to_string()));
+    }
+
+    let request = app_handle.db().get_grpc_request(&connection.request_id)?;
+    let event_name = format!("grpc_client_msg_{}", connection.id);
+    for template in request.message_templates {
+        let selected = match (&template_ids, &template.id) {
+            (None, _) => true,
+            (Some(ids), Some(id)) => ids.contains(id),
+            (Some(_), None) => false,
+        };
+        if selected {
+            app_handle.emit(&event_name, serde_json::json!({ "Message": template.message }))?;
+        }
+    }
+
+    Ok(())
 }
 
 #[tauri::command]
@@ -347,6 +467,32 @@ async fn cmd_grpc_go<R: Runtime>(
     grpc_handle: State<'_, Mutex<GrpcHandle>>,
 ) -> YaakResult<String> {
     let unrendered_request = app_handle.db().get_grpc_request(request_id)?;
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let plugin_context = window.plugin_context();
+
+    // Run pre-request steps before rendering, since they can set the variables the request uses
+    let response_dir = app_handle.path().app_data_dir()?.join("responses");
+    run_pre_request_steps(
+        &PreRequestParams {
+            query_manager: app_handle.db_manager().inner(),
+            blob_manager: app_handle.blob_manager().inner(),
+            environment_id,
+            update_source: UpdateSource::from_window_label(window.label()),
+            cookie_jar_id: None,
+            response_dir: &response_dir,
+            plugin_manager: plugin_manager.clone(),
+            encryption_manager: encryption_manager.clone(),
+            plugin_context: &plugin_context,
+            cancelled_rx: None,
+            connection_manager: Some(app_handle.state::<HttpConnectionManager>().inner()),
+            variable_overrides: Vec::new(),
+        },
+        &AnyRequest::GrpcRequest(unrendered_request.clone()),
+    )
+    .await
+    .map_err(|e| GenericError(e.to_string()))?;
+
     let (resolved_request, auth_context_id) = resolve_grpc_request(&window, &unrendered_request)?;
     let environment_chain = app_handle.db().resolve_environments(
         &unrendered_request.workspace_id,
@@ -354,18 +500,21 @@ async fn cmd_grpc_go<R: Runtime>(
         environment_id,
     )?;
     let resolved_settings = app_handle.db().resolve_settings_for_grpc_request(&unrendered_request)?;
-
-    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
-    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let timeout = |ms: i32| (ms > 0).then(|| Duration::from_millis(ms.unsigned_abs() as u64));
+    let request_timeout = timeout(resolved_settings.request_timeout.value);
+    let connect_timeout = timeout(resolved_settings.connect_timeout.value);
+    let read_timeout = timeout(resolved_settings.read_timeout.value);
+
+    let template_callback = PluginTemplateCallback::new(
+        plugin_manager.clone(),
+        encryption_manager.clone(),
+        &plugin_context,
+        RenderPurpose::Send,
+    );
     let request = render_grpc_request(
         &resolved_request,
         environment_chain.clone(),
-        &PluginTemplateCallback::new(
-            plugin_manager.clone(),
-            encryption_manager.clone(),
-            &PluginContext::new(Some(window.label().to_string()), window.workspace_id()),
-            RenderPurpose::Send,
-        ),
+        &template_callback,
         &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
     )
     .await?;
@@ -374,7 +523,12 @@ async fn cmd_grpc_go<R: Runtime>(
 
     // Find matching client certificate for this URL
     let settings = app_handle.db().get_settings();
-    let client_cert = find_client_certificate(&request.url, &settings.client_certificates);
+    let client_cert = select_client_certificate(
+        &request.url,
+        resolved_settings.client_certificate.value.as_ref(),
+        &settings.client_certificates,
+    );
+    let ca_certificates = find_ca_certificates(&resolved_settings.ca_certificates.value);
 
     let conn = app_handle.db().upsert_grpc_connection(
         &GrpcConnection {
@@ -414,6 +568,10 @@ async fn cmd_grpc_go<R: Runtime>(
         }
     };
 
+    // Resolved before locking the handle, since opening an SSH tunnel can take a while
+    let proxy =
+        resolve_grpc_proxy(&window, &request, environment_chain.clone(), &template_callback)
+            .await?;
     let start = std::time::Instant::now();
     let connection = grpc_handle
         .lock()
@@ -425,11 +583,19 @@ async fn cmd_grpc_go<R: Runtime>(
             &metadata,
             resolved_settings.validate_certificates.value,
             client_cert.clone(),
+            &ca_certificates,
+            proxy,
+            resolve_grpc_dns_overrides(&window, &request)?,
+            connect_timeout,
+            wire_protocol(request.protocol),
         )
         .await;
 
     let connection = match connection {
-        Ok(c) => c,
+        Ok(c) => c.with_compression(
+            request.send_compression.map(compression_encoding),
+            request.accept_compression.iter().copied().map(compression_encoding).collect(),
+        ),
         Err(err) => {
             app_handle.db().upsert_grpc_connection(
                 &GrpcConnection {
@@ -590,6 +756,7 @@ async fn cmd_grpc_go<R: Runtime>(
                                     &method,
                                     in_msg_stream,
                                     &metadata,
+                                    request_timeout,
                                     client_cert.clone(),
                                     on_message.clone(),
                                 )
@@ -606,6 +773,7 @@ async fn cmd_grpc_go<R: Runtime>(
                                     &method,
                                     in_msg_stream,
                                     &metadata,
+                                    request_timeout,
                                     client_cert.clone(),
                                     on_message.clone(),
                                 )
@@ -613,17 +781,39 @@ async fn cmd_grpc_go<R: Runtime>(
                         ),
                     ),
                     (false, true) => (
-                        Some(connection.server_streaming(&service, &method, &msg, &metadata).await),
-                        None,
-                    ),
-                    (false, false) => (
-                        None,
                         Some(
                             connection
-                                .unary(&service, &method, &msg, &metadata, client_cert.clone())
+                                .server_streaming(
+                                    &service,
+                                    &method,
+                                    &msg,
+                                    &metadata,
+                                    request_timeout,
+                                )
                                 .await,
                         ),
+                        None,
                     ),
+                    (false, false) => {
+                        let unary = connection.unary(
+                            &service,
+                            &method,
+                            &msg,
+                            &metadata,
+                            request_timeout,
+                            client_cert.clone(),
+                        );
+                        let result = match read_timeout {
+                            Some(d) => time::timeout(d, unary).await.unwrap_or_else(|_| {
+                                Err(yaak_grpc::error::Error::GrpcStreamError(GrpcStreamError {
+                                    message: format!("Read timeout of {d:?} reached"),
+                                    status: Some(read_timeout_status(d)),
+                                }))
+                            }),
+                            None => unary.await,
+                        };
+                        (None, Some(result))
+                    }
                 };
 
             if !method_desc.is_client_streaming() {
@@ -642,6 +832,14 @@ async fn cmd_grpc_go<R: Runtime>(
 
             match maybe_msg {
                 Some(Ok(msg)) => {
+                    if let Err(e) = record_response_encoding(
+                        &app_handle,
+                        &base_event.connection_id,
+                        msg.metadata(),
+                        &UpdateSource::from_window_label(window.label()),
+                    ) {
+                        warn!("Failed to record gRPC response encoding {e:?}");
+                    }
                     app_handle
                         .db()
                         .upsert_grpc_event(
@@ -707,6 +905,10 @@ async fn cmd_grpc_go<R: Runtime>(
                         .unwrap();
                 }
                 Some(Err(yaak_grpc::error::Error::GrpcStreamError(e))) => {
+                    let error_details = match &e.status {
+                        Some(s) => connection.status_details(s).await,
+                        None => Vec::new(),
+                    };
                     app_handle
                         .db()
                         .upsert_grpc_event(
@@ -714,8 +916,9 @@ async fn cmd_grpc_go<R: Runtime>(
                                 Some(s) => GrpcEvent {
                                     error: Some(s.message().to_string()),
                                     status: Some(s.code() as i32),
-                                    content: "Failed to connect".to_string(),
+                                    content: connection_failed_content(s.code()),
                                     metadata: metadata_to_map(s.metadata().clone()),
+                                    error_details: grpc_error_details(error_details),
                                     event_type: GrpcEventType::ConnectionEnd,
                                     ..base_event.clone()
                                 },
@@ -753,6 +956,14 @@ async fn cmd_grpc_go<R: Runtime>(
 
             let mut stream = match maybe_stream {
                 Some(Ok(stream)) => {
+                    if let Err(e) = record_response_encoding(
+                        &app_handle,
+                        &base_event.connection_id,
+                        stream.metadata(),
+                        &UpdateSource::from_window_label(window.label()),
+                    ) {
+                        warn!("Failed to record gRPC response encoding {e:?}");
+                    }
                     app_handle
                         .db()
                         .upsert_grpc_event(
@@ -774,6 +985,10 @@ async fn cmd_grpc_go<R: Runtime>(
                 }
                 Some(Err(yaak_grpc::error::Error::GrpcStreamError(e))) => {
                     warn!("GRPC stream error {e:?}");
+                    let error_details = match &e.status {
+                        Some(s) => connection.status_details(s).await,
+                        None => Vec::new(),
+                    };
                     app_handle
                         .db()
                         .upsert_grpc_event(
@@ -781,8 +996,9 @@ async fn cmd_grpc_go<R: Runtime>(
                                 Some(s) => GrpcEvent {
                                     error: Some(s.message().to_string()),
                                     status: Some(s.code() as i32),
-                                    content: "Failed to connect".to_string(),
+                                    content: connection_failed_content(s.code()),
                                     metadata: metadata_to_map(s.metadata().clone()),
+                                    error_details: grpc_error_details(error_details),
                                     event_type: GrpcEventType::ConnectionEnd,
                                     ..base_event.clone()
                                 },
@@ -819,7 +1035,13 @@ async fn cmd_grpc_go<R: Runtime>(
             };
 
             loop {
-                match stream.message().await {
+                let next = match read_timeout {
+                    Some(d) => time::timeout(d, stream.message())
+                        .await
+                        .unwrap_or_else(|_| Err(read_timeout_status(d))),
+                    None => stream.message().await,
+                };
+                match next {
                     Ok(Some(msg)) => {
                         let message = match connection
                             .serialize_message(&msg, &metadata, client_cert.clone())
@@ -874,6 +1096,7 @@ async fn cmd_grpc_go<R: Runtime>(
                         break;
                     }
                     Err(status) => {
+                        let error_details = connection.status_details(&status).await;
                         app_handle
                             .db()
                             .upsert_grpc_event(
@@ -881,12 +1104,16 @@ async fn cmd_grpc_go<R: Runtime>(
                                     content: status.to_string(),
                                     status: Some(status.code() as i32),
                                     metadata: metadata_to_map(status.metadata().clone()),
+                                    error_details: grpc_error_details(error_details),
                                     event_type: GrpcEventType::ConnectionEnd,
                                     ..base_event.clone()
                                 },
                                 &UpdateSource::from_window_label(window.label()),
                             )
                             .unwrap();
+                        if status.code() == Code::DeadlineExceeded {
+                            break;
+                        }
                     }
                 }
             }
@@ -897,6 +1124,12 @@ async fn cmd_grpc_go<R: Runtime>(
         let conn_id = conn_id.clone();
         tauri::async_runtime::spawn(async move {
             let w = app_handle.clone();
+            let request_timeout_elapsed = async {
+                match request_timeout {
+                    Some(d) => time::sleep(d).await,
+                    None => std::future::pending().await,
+                }
+            };
             tokio::select! {
                 _ = grpc_listen => {
                     let events = w.db().list_grpc_events(&conn_id).unwrap();
@@ -904,12 +1137,40 @@ async fn cmd_grpc_go<R: Runtime>(
                         .iter()
                         .find(|e| GrpcEventType::ConnectionEnd == e.event_type);
                     let closed_status = closed_event.and_then(|e| e.status).unwrap_or(Code::Unavailable as i32);
+                    let state = if closed_status == Code::DeadlineExceeded as i32 {
+                        GrpcConnectionState::TimedOut
+                    } else {
+                        GrpcConnectionState::Closed
+                    };
                     w.with_tx(|c| {
                         c.upsert_grpc_connection(
                             &GrpcConnection{
                                 elapsed: start.elapsed().as_millis() as i32,
                                 status: closed_status,
-                                state: GrpcConnectionState::Closed,
+                                state,
+                                ..c.get_grpc_connection( &conn_id).unwrap().clone()
+                            },
+                            &UpdateSource::from_window_label(window.label()),
+                        )
+                    }).unwrap();
+                },
+                _ = request_timeout_elapsed => {
+                    let d = request_timeout.unwrap_or_default();
+                    w.db().upsert_grpc_event(
+                        &GrpcEvent {
+                            content: format!("Timeout of {d:?} reached"),
+                            event_type: GrpcEventType::ConnectionEnd,
+                            status: Some(Code::DeadlineExceeded as i32),
+                            ..base_msg.clone()
+                        },
+                        &UpdateSource::from_window_label(window.label()),
+                    ).unwrap();
+                    w.with_tx(|c| {
+                        c.upsert_grpc_connection(
+                            &GrpcConnection{
+                                elapsed: start.elapsed().as_millis() as i32,
+                                status: Code::DeadlineExceeded as i32,
+                                state: GrpcConnectionState::TimedOut,
                                 ..c.get_grpc_connection( &conn_id).unwrap().clone()
                             },
                             &UpdateSource::from_window_label(window.label()),
@@ -946,6 +1207,17 @@ async fn cmd_grpc_go<R: Runtime>(
     Ok(conn.id)
 }
 
+fn connection_failed_content(code: Code) -> String {
+    match code {
+        Code::DeadlineExceeded => "Deadline exceeded".to_string(),
+        _ => "Failed to connect".to_string(),
+    }
+}
+
+fn read_timeout_status(timeout: Duration) -> Status {
+    Status::deadline_exceeded(format!("Read timeout of {timeout:?} reached"))
+}
+
 #[tauri::command]
 async fn cmd_restart<R: Runtime>(app_handle: AppHandle<R>) -> YaakResult<()> {
     app_handle.request_restart();
@@ -978,7 +1250,8 @@ async fn cmd_send_ephemeral_request<R: Runtime>(
         }
     });
 
-    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx).await
+    send_http_request(&window, &request, &response, environment, cookie_jar, &mut cancel_rx, None)
+        .await
 }
 
 #[tauri::command]
@@ -994,6 +1267,95 @@ async fn cmd_format_graphql(text: &str) -> YaakResult<String> {
     }
 }
 
+#[tauri::command]
+async fn cmd_graphql_introspect<R: Runtime>(
+    request: HttpRequest,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    force: Option<bool>,
+    window: WebviewWindow<R>,
+    app_handle: AppHandle<R>,
+) -> YaakResult<GraphQlIntrospection> {
+    let source = UpdateSource::from_window_label(window.label());
+    let environment_chain = app_handle.db().resolve_environments(
+        &request.workspace_id,
+        request.folder_id.as_deref(),
+        environment_id,
+    )?;
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let url = render_template(
+        &request.url,
+        environment_chain,
+        &PluginTemplateCallback::new(
+            plugin_manager,
+            encryption_manager,
+            &PluginContext::new(Some(window.label().to_string()), window.workspace_id()),
+            RenderPurpose::Send,
+        ),
+        &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
+    )
+    .await?;
+
+    // Schemas are cached per endpoint, so any request targeting the same URL can reuse one
+    let cached = match force {
+        Some(true) => None,
+        _ => app_handle.db().get_graphql_introspection_for_url(&request.workspace_id, &url),
+    };
+    if let Some(cached) = cached {
+        if cached.request_id == request.id {
+            return Ok(cached);
+        }
+        return Ok(app_handle.db().upsert_graphql_introspection(
+            &request.workspace_id,
+            &request.id,
+            Some(url),
+            cached.content,
+            &source,
+        )?);
+    }
+
+    let environment = match environment_id {
+        Some(id) => Some(app_handle.db().get_environment(id)?),
+        None => None,
+    };
+    let cookie_jar = match cookie_jar_id {
+        Some(id) => Some(app_handle.db().get_cookie_jar(id)?),
+        None => None,
+    };
+    let (_cancel_tx, mut cancel_rx) = tokio::sync::watch::channel(false);
+    let response = send_http_request(
+        &window,
+        &introspection_request(&request),
+        &HttpResponse::default(),
+        environment,
+        cookie_jar,
+        &mut cancel_rx,
+        None,
+    )
+    .await?;
+    if let Some(error) = response.error {
+        return Err(GenericError(error));
+    }
+
+    // The body file is left in place, since content-addressed bodies can be shared with other
+    // responses
+    let body = match &response.body_path {
+        Some(body_path) => fs::read_to_string(body_path)?,
+        None => return Err(GenericError("Empty body returned in response".to_string())),
+    };
+    validate_introspection_response(response.status, &body)
+        .map_err(|e| GenericError(e.to_string()))?;
+
+    Ok(app_handle.db().upsert_graphql_introspection(
+        &request.workspace_id,
+        &request.id,
+        Some(url),
+        Some(body),
+        &source,
+    )?)
+}
+
 #[tauri::command]
 async fn cmd_http_response_body<R: Runtime>(
     window: WebviewWindow<R>,
@@ -1016,15 +1378,36 @@ async fn cmd_http_response_body<R: Runtime>(
         })
         .unwrap_or_default();
 
+    // Filters are evaluated here first, so large bodies don't have to be sent to the plugin
+    // runtime as text. Anything the backend can't handle (eg. JSONPath syntax that only the
+    // plugin supports) still goes to the filter plugin.
+    let ct = content_type.to_lowercase();
+    let is_markup = ct.contains("xml") || ct.contains("html");
+    let filter = filter.filter(|f| !f.is_empty());
+    if let Some(filter) = filter.filter(|_| !is_markup) {
+        match filter_json_file(&body_path, filter) {
+            Ok(content) => return Ok(FilterResponse { content, error: None }),
+            Err(e) => debug!("Falling back to filter plugin for {filter}: {e}"),
+        }
+    }
+
     let body = read_response_body(&body_path, content_type)
         .await
         .ok_or(GenericError("Failed to find response body".to_string()))?;
 
     match filter {
-        Some(filter) if !filter.is_empty() => Ok(plugin_manager
-            .filter_data(&window.plugin_context(), filter, &body, content_type)
-            .await?),
-        _ => Ok(FilterResponse { content: body, error: None }),
+        Some(filter) => {
+            if is_markup {
+                match filter_markup(&body, filter, ct.contains("html")) {
+                    Ok(content) => return Ok(FilterResponse { content, error: None }),
+                    Err(e) => debug!("Falling back to filter plugin for {filter}: {e}"),
+                }
+            }
+            Ok(plugin_manager
+                .filter_data(&window.plugin_context(), filter, &body, content_type)
+                .await?)
+        }
+        None => Ok(FilterResponse { content: body, error: None }),
     }
 }
 
@@ -1344,6 +1727,19 @@ async fn cmd_call_http_authentication_action<R: Runtime>(
     // Convert back to HashMap<String, JsonPrimitive>
     let rendered_values: HashMap<String, JsonPrimitive> = serde_json::from_value(rendered_json)?;
 
+    // Actions like "Delete Token" only clear the plugin's token, so drop the stored one too or
+    // it would keep being sent until it expires
+    if auth_name == OAUTH2_AUTHENTICATION_TYPE {
+        let context_id = format!("{:x}", md5::compute(model.id()));
+        let values = serde_json::from_value(serde_json::to_value(&rendered_values)?)?;
+        delete_oauth2_token(
+            app_handle.db_manager().inner(),
+            encryption_manager.secrets(),
+            &oauth2_config_hash(&context_id, &values),
+        )
+        .map_err(GenericError)?;
+    }
+
     Ok(plugin_manager
         .call_http_authentication_action(
             &window.plugin_context(),
@@ -1362,19 +1758,75 @@ async fn cmd_curl_to_request<R: Runtime>(
     plugin_manager: State<'_, PluginManager>,
     workspace_id: &str,
 ) -> YaakResult<HttpRequest> {
-    let import_result = plugin_manager.import_data(&window.plugin_context(), command).await?;
+    let mut request = match parse_curl_command(command) {
+        Ok(r) => r,
+        Err(e) => {
+            // Fall back to importer plugins for commands the native parser can't handle
+            warn!("Failed to parse curl command, trying plugins: {e}");
+            let import_result =
+                plugin_manager.import_data(&window.plugin_context(), command).await?;
+            import_result
+                .resources
+                .http_requests
+                .get(0)
+                .cloned()
+                .ok_or(GenericError("No curl command found".to_string()))?
+        }
+    };
 
-    Ok(import_result
-        .resources
-        .http_requests
-        .get(0)
-        .ok_or(GenericError("No curl command found".to_string()))
-        .map(|r| {
-            let mut request = r.clone();
-            request.workspace_id = workspace_id.into();
-            request.id = "".to_string();
-            request
-        })?)
+    request.workspace_id = workspace_id.into();
+    request.id = "".to_string();
+    Ok(request)
+}
+
+#[tauri::command]
+async fn cmd_export_as_curl<R: Runtime>(
+    window: WebviewWindow<R>,
+    request_id: &str,
+    environment_id: Option<&str>,
+    plugin_manager: State<'_, PluginManager>,
+    encryption_manager: State<'_, EncryptionManager>,
+) -> YaakResult<String> {
+    Ok(export_as_curl(ExportAsCurlParams {
+        query_manager: window.app_handle().db_manager().inner(),
+        request_id,
+        environment_id,
+        plugin_manager: Arc::new((*plugin_manager).clone()),
+        encryption_manager: Arc::new((*encryption_manager).clone()),
+        plugin_context: &window.plugin_context(),
+    })
+    .await
+    .map_err(|e| GenericError(e.to_string()))?)
+}
+
+#[tauri::command]
+async fn cmd_export_as_snippet<R: Runtime>(
+    window: WebviewWindow<R>,
+    request_id: &str,
+    environment_id: Option<&str>,
+    language: &str,
+    plugin_manager: State<'_, PluginManager>,
+    encryption_manager: State<'_, EncryptionManager>,
+) -> YaakResult<String> {
+    Ok(export_as_snippet(ExportAsSnippetParams {
+        query_manager: window.app_handle().db_manager().inner(),
+        request_id,
+        environment_id,
+        plugin_manager: Arc::new((*plugin_manager).clone()),
+        encryption_manager: Arc::new((*encryption_manager).clone()),
+        plugin_context: &window.plugin_context(),
+        language,
+    })
+    .await
+    .map_err(|e| GenericError(e.to_string()))?)
+}
+
+#[tauri::command]
+async fn cmd_codegen_languages<R: Runtime>(
+    window: WebviewWindow<R>,
+    plugin_manager: State<'_, PluginManager>,
+) -> YaakResult<Vec<CodegenLanguage>> {
+    Ok(list_codegen_languages(&plugin_manager, &window.plugin_context()).await)
 }
 
 #[tauri::command]
@@ -1386,8 +1838,9 @@ async fn cmd_export_data<R: Runtime>(
 ) -> YaakResult<()> {
     let db = app_handle.db();
     let version = app_handle.package_info().version.to_string();
-    let export_data =
+    let mut export_data =
         get_workspace_export_resources(&db, &version, workspace_ids, include_private_environments)?;
+    redact_secret_variables(&mut export_data.resources.environments);
     let f = File::options()
         .create(true)
         .truncate(true)
@@ -1404,6 +1857,74 @@ async fn cmd_export_data<R: Runtime>(
     Ok(())
 }
 
+#[tauri::command]
+async fn cmd_export_har<R: Runtime>(
+    app_handle: AppHandle<R>,
+    export_path: &str,
+    response_ids: Vec<String>,
+) -> YaakResult<()> {
+    let version = app_handle.package_info().version.to_string();
+    let har = export_har(
+        app_handle.db_manager().inner(),
+        app_handle.blob_manager().inner(),
+        &response_ids,
+        &version,
+    )
+    .map_err(|e| GenericError(e.to_string()))?;
+    fs::write(export_path, serde_json::to_string_pretty(&har)?)?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_export_postman<R: Runtime>(
+    app_handle: AppHandle<R>,
+    export_path: &str,
+    workspace_id: &str,
+) -> YaakResult<()> {
+    let collection = export_postman_collection(app_handle.db_manager().inner(), workspace_id)
+        .map_err(|e| GenericError(e.to_string()))?;
+    fs::write(export_path, serde_json::to_string_pretty(&collection)?)?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_export_openapi<R: Runtime>(
+    app_handle: AppHandle<R>,
+    export_path: &str,
+    workspace_id: &str,
+) -> YaakResult<()> {
+    let document = export_openapi(app_handle.db_manager().inner(), workspace_id)
+        .map_err(|e| GenericError(e.to_string()))?;
+    fs::write(export_path, serde_json::to_string_pretty(&document)?)?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_export_cookies<R: Runtime>(
+    app_handle: AppHandle<R>,
+    cookie_jar_id: &str,
+    export_path: &str,
+) -> YaakResult<()> {
+    let cookie_jar = app_handle.db().get_cookie_jar(cookie_jar_id)?;
+    fs::write(export_path, cookies_to_netscape(&cookie_jar.cookies))?;
+    Ok(())
+}
+
+#[tauri::command]
+async fn cmd_import_cookies<R: Runtime>(
+    window: WebviewWindow<R>,
+    cookie_jar_id: &str,
+    file_path: &str,
+) -> YaakResult<CookieJar> {
+    let cookies = cookies_from_netscape(&fs::read_to_string(file_path)?);
+    if cookies.is_empty() {
+        return Err(GenericError("No cookies found in file".to_string()));
+    }
+
+    let source = &UpdateSource::from_window_label(window.label());
+    Ok(window.db().upsert_cookies(cookie_jar_id, &cookies, source)?)
+}
+
 #[tauri::command]
 async fn cmd_save_response<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -1419,6 +1940,63 @@ async fn cmd_save_response<R: Runtime>(
     Ok(())
 }
 
+#[tauri::command]
+async fn cmd_run_monitor<R: Runtime>(
+    window: WebviewWindow<R>,
+    monitor_id: &str,
+) -> YaakResult<MonitorRun> {
+    let update_source = UpdateSource::from_window_label(window.label());
+    monitors::run_monitor_now(window.app_handle(), monitor_id, update_source).await
+}
+
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+async fn cmd_run_folder<R: Runtime>(
+    window: WebviewWindow<R>,
+    folder_id: &str,
+    environment_id: Option<&str>,
+    cookie_jar_id: Option<&str>,
+    concurrency: i32,
+    delay: i32,
+    stop_on_failure: bool,
+    data_file: Option<String>,
+) -> YaakResult<CollectionRun> {
+    let app_handle = window.app_handle();
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let response_dir = app_handle.path().app_data_dir()?.join("responses");
+
+    let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+    app_handle.listen_any(format!("cancel_collection_run_{folder_id}"), move |_event| {
+        if let Err(e) = cancel_tx.send(true) {
+            warn!("Failed to send cancel event for collection run {e:?}");
+        }
+    });
+
+    let run = run_collection(CollectionRunParams {
+        query_manager: app_handle.db_manager().inner(),
+        blob_manager: app_handle.blob_manager().inner(),
+        folder_id,
+        environment_id,
+        update_source: UpdateSource::from_window_label(window.label()),
+        cookie_jar_id: cookie_jar_id.map(|id| id.to_string()),
+        response_dir: &response_dir,
+        plugin_manager,
+        encryption_manager,
+        plugin_context: &window.plugin_context(),
+        cancelled_rx: Some(cancel_rx),
+        connection_manager: Some(app_handle.state::<HttpConnectionManager>().inner()),
+        concurrency,
+        delay,
+        stop_on_failure,
+        data_file,
+    })
+    .await
+    .map_err(|e| GenericError(e.to_string()))?;
+
+    Ok(run)
+}
+
 #[tauri::command]
 async fn cmd_send_http_request<R: Runtime>(
     app_handle: AppHandle<R>,
@@ -1429,6 +2007,7 @@ async fn cmd_send_http_request<R: Runtime>(
     //   condition where the user may have just edited a field before sending
     //   that has not yet been saved in the DB.
     request: HttpRequest,
+    download_path: Option<&str>,
 ) -> YaakResult<HttpResponse> {
     let blobs = app_handle.blob_manager();
     let response = app_handle.db().upsert_http_response(
@@ -1471,6 +2050,7 @@ async fn cmd_send_http_request<R: Runtime>(
         environment,
         cookie_jar,
         &mut cancel_rx,
+        download_path.map(Path::new),
     )
     .await
     {
@@ -1479,7 +2059,10 @@ async fn cmd_send_http_request<R: Runtime>(
             let resp = app_handle.db().get_http_response(&response.id)?;
             app_handle.db().upsert_http_response(
                 &HttpResponse {
-                    state: HttpResponseState::Closed,
+                    state: match resp.state {
+                        HttpResponseState::TimedOut => HttpResponseState::TimedOut,
+                        _ => HttpResponseState::Closed,
+                    },
                     error: Some(e.to_string()),
                     ..resp
                 },
@@ -1542,6 +2125,8 @@ async fn cmd_delete_send_history<R: Runtime>(
         tx.delete_all_http_responses_for_workspace(workspace_id, source)?;
         tx.delete_all_grpc_connections_for_workspace(workspace_id, source)?;
         tx.delete_all_websocket_connections_for_workspace(workspace_id, source)?;
+        tx.delete_all_mqtt_connections_for_workspace(workspace_id, source)?;
+        tx.delete_all_socket_responses_for_workspace(workspace_id, source)?;
         Ok(())
     })?)
 }
@@ -1748,6 +2333,12 @@ pub fn run() {
             let ws_manager = yaak_ws::WebsocketManager::new();
             app.manage(Mutex::new(ws_manager));
 
+            // Add MQTT manager
+            app.manage(yaak_mqtt::MqttManager::new());
+
+            // Add mock servers, which are started per workspace
+            app.manage(mock_servers::MockServers::default());
+
             // Specific settings
             let settings = app.db().get_settings();
             app.app_handle().set_native_titlebar(settings.use_native_titlebar);
@@ -1764,12 +2355,19 @@ pub fn run() {
             cmd_call_folder_action,
             cmd_call_grpc_request_action,
             cmd_check_for_updates,
+            cmd_codegen_languages,
             cmd_curl_to_request,
             cmd_delete_all_grpc_connections,
             cmd_delete_all_http_responses,
             cmd_delete_send_history,
             cmd_dismiss_notification,
+            cmd_export_as_curl,
+            cmd_export_as_snippet,
             cmd_export_data,
+            cmd_export_cookies,
+            cmd_export_har,
+            cmd_export_postman,
+            cmd_export_openapi,
             cmd_http_request_body,
             cmd_http_response_body,
             cmd_format_json,
@@ -1779,13 +2377,16 @@ pub fn run() {
             cmd_get_sse_events,
             cmd_get_http_response_events,
             cmd_get_workspace_meta,
+            cmd_graphql_introspect,
             cmd_grpc_go,
             cmd_grpc_reflect,
+            cmd_grpc_replay_message_templates,
             cmd_grpc_request_actions,
             cmd_http_request_actions,
             cmd_websocket_request_actions,
             cmd_workspace_actions,
             cmd_folder_actions,
+            cmd_import_cookies,
             cmd_import_data,
             cmd_metadata,
             cmd_new_child_window,
@@ -1794,6 +2395,8 @@ pub fn run() {
             cmd_reload_plugins,
             cmd_render_template,
             cmd_restart,
+            cmd_run_folder,
+            cmd_run_monitor,
             cmd_save_response,
             cmd_send_ephemeral_request,
             cmd_send_http_request,
@@ -1813,11 +2416,41 @@ pub fn run() {
             crate::commands::cmd_set_workspace_key,
             //
             // Models commands
+            models_ext::models_append_grpc_message_template,
+            models_ext::models_attach_proto_files,
             models_ext::models_delete,
+            models_ext::models_delete_grpc_message_template,
+            models_ext::models_upsert_cookie,
+            models_ext::models_delete_cookie,
+            models_ext::models_clear_cookies,
+            models_ext::models_delete_tag,
+            models_ext::models_detach_proto_files,
+            models_ext::models_diff_requests,
+            models_ext::models_diff_responses,
             models_ext::models_duplicate,
+            models_ext::models_empty_trash,
+            models_ext::models_filter_http_responses,
+            models_ext::models_filter_websocket_events,
             models_ext::models_get_graphql_introspection,
+            models_ext::models_get_proto_files,
             models_ext::models_get_settings,
             models_ext::models_grpc_events,
+            models_ext::models_list_pinned_items,
+            models_ext::models_list_request_revisions,
+            models_ext::models_list_workspace_activity,
+            models_ext::models_list_requests_by_tag,
+            models_ext::models_list_tags,
+            models_ext::models_list_trash,
+            models_ext::models_mqtt_events,
+            models_ext::models_pin_request,
+            models_ext::models_rename_tag,
+            models_ext::models_reorder_children,
+            models_ext::models_reorder_grpc_message_templates,
+            models_ext::models_restore,
+            models_ext::models_restore_request_revision,
+            models_ext::models_search_workspace,
+            models_ext::models_trash,
+            models_ext::models_unpin_request,
             models_ext::models_upsert,
             models_ext::models_upsert_graphql_introspection,
             models_ext::models_websocket_events,
@@ -1827,6 +2460,7 @@ pub fn run() {
             sync_ext::cmd_sync_calculate,
             sync_ext::cmd_sync_calculate_fs,
             sync_ext::cmd_sync_apply,
+            sync_ext::cmd_sync_resolve_conflict,
             sync_ext::cmd_sync_watch,
             //
             // Git commands
@@ -1861,6 +2495,11 @@ pub fn run() {
             git_ext::cmd_git_add_remote,
             git_ext::cmd_git_rm_remote,
             //
+            // Mock server commands
+            mock_servers::cmd_mock_server_port,
+            mock_servers::cmd_mock_server_start,
+            mock_servers::cmd_mock_server_stop,
+            //
             // Plugin commands
             plugins_ext::cmd_plugin_init_errors,
             plugins_ext::cmd_plugins_install_from_directory,
@@ -1875,6 +2514,17 @@ pub fn run() {
             ws_ext::cmd_ws_send,
             ws_ext::cmd_ws_close,
             ws_ext::cmd_ws_connect,
+            ws_ext::cmd_ws_replay_event,
+            //
+            // MQTT commands
+            mqtt_ext::cmd_mqtt_delete_connections,
+            mqtt_ext::cmd_mqtt_publish,
+            mqtt_ext::cmd_mqtt_close,
+            mqtt_ext::cmd_mqtt_connect,
+            //
+            // Socket commands
+            socket_ext::cmd_socket_delete_responses,
+            socket_ext::cmd_socket_send,
         ])
         .build(tauri::generate_context!())
         .expect("error while running tauri application")
@@ -1902,7 +2552,12 @@ pub fn run() {
                         let _ = db.cancel_pending_http_responses();
                         let _ = db.cancel_pending_grpc_connections();
                         let _ = db.cancel_pending_websocket_connections();
+                        let _ = db.cancel_pending_mqtt_connections();
+                        let _ = db.cancel_pending_socket_responses();
+                        let _ = db.cancel_pending_collection_runs();
                     });
+
+                    monitors::start_monitor_scheduler(app_handle);
                 }
                 RunEvent::WindowEvent { event: WindowEvent::Focused(true), label, .. } => {
                     if cfg!(feature = "updater") {
@@ -1968,6 +2623,15 @@ fn monitor_plugin_events<R: Runtime>(app_handle: &AppHandle<R>) {
     let app_handle = app_handle.clone();
     tauri::async_runtime::spawn(async move {
         let plugin_manager: State<'_, PluginManager> = app_handle.state();
+
+        // Native template functions like `prompt()` ask the user through the focused window
+        let prompt_app_handle = app_handle.clone();
+        let prompt_text_handler: PromptTextHandler = Arc::new(move |plugin_context, req| {
+            let app_handle = prompt_app_handle.clone();
+            Box::pin(async move { prompt_text_in_window(&app_handle, &plugin_context, req).await })
+        });
+        plugin_manager.set_prompt_text_handler(prompt_text_handler).await;
+
         let (rx_id, mut rx) = plugin_manager.subscribe("app").await;
 
         while let Some(event) = rx.recv().await {
@@ -2039,6 +2703,27 @@ async fn call_frontend<R: Runtime>(
     v.to_owned()
 }
 
+async fn prompt_text_in_window<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    plugin_context: &PluginContext,
+    req: PromptTextRequest,
+) -> std::result::Result<Option<String>, String> {
+    let window =
+        get_window_from_plugin_context(app_handle, plugin_context).map_err(|e| e.to_string())?;
+    let event = InternalEvent {
+        id: generate_id(),
+        plugin_ref_id: "".to_string(),
+        plugin_name: "".to_string(),
+        reply_id: None,
+        context: plugin_context.clone(),
+        payload: InternalEventPayload::PromptTextRequest(req),
+    };
+    match call_frontend(&window, &event).await {
+        Some(InternalEventPayload::PromptTextResponse(resp)) => Ok(resp.value),
+        _ => Ok(None),
+    }
+}
+
 fn get_window_from_plugin_context<R: Runtime>(
     app_handle: &AppHandle<R>,
     plugin_context: &PluginContext,
@@ -0,0 +1,58 @@
+//! Tauri commands for serving a workspace's saved responses from a local mock server.
+
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::models_ext::QueryManagerExt;
+use std::collections::HashMap;
+use tauri::{AppHandle, Runtime, State, command};
+use tokio::sync::Mutex;
+use yaak::mock_server::{MockServerHandle, start_mock_server};
+
+/// Running mock servers, keyed by workspace ID
+#[derive(Default)]
+pub(crate) struct MockServers(Mutex<HashMap<String, MockServerHandle>>);
+
+/// Start a workspace's mock server, returning the port it listens on. A server that's already
+/// running is kept unless a different port is requested. Pages can only call the server from
+/// the allowed origins, like `http://localhost:5173`.
+#[command]
+pub(crate) async fn cmd_mock_server_start<R: Runtime>(
+    app_handle: AppHandle<R>,
+    mock_servers: State<'_, MockServers>,
+    workspace_id: &str,
+    port: Option<u16>,
+    allowed_origins: Option<Vec<String>>,
+) -> Result<u16> {
+    let mut servers = mock_servers.0.lock().await;
+    if let Some(server) = servers.get(workspace_id).filter(|s| port.is_none_or(|p| p == s.port)) {
+        return Ok(server.port);
+    }
+    servers.remove(workspace_id);
+
+    let query_manager = app_handle.db_manager().inner().clone();
+    let allowed_origins = allowed_origins.unwrap_or_default();
+    let server = start_mock_server(query_manager, workspace_id, port.unwrap_or(0), allowed_origins)
+        .await
+        .map_err(|e| GenericError(e.to_string()))?;
+    let port = server.port;
+    servers.insert(workspace_id.to_string(), server);
+    Ok(port)
+}
+
+#[command]
+pub(crate) async fn cmd_mock_server_stop(
+    mock_servers: State<'_, MockServers>,
+    workspace_id: &str,
+) -> Result<()> {
+    mock_servers.0.lock().await.remove(workspace_id);
+    Ok(())
+}
+
+/// Port of a workspace's running mock server, if it has one
+#[command]
+pub(crate) async fn cmd_mock_server_port(
+    mock_servers: State<'_, MockServers>,
+    workspace_id: &str,
+) -> Result<Option<u16>> {
+    Ok(mock_servers.0.lock().await.get(workspace_id).map(|s| s.port))
+}
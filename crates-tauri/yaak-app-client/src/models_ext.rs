@@ -3,8 +3,11 @@
 //! This module provides the Tauri plugin initialization and extension traits
 //! that allow accessing QueryManager and BlobManager from Tauri's Manager types.
 
+use crate::commands::EncryptionManagerExt;
 use chrono::Utc;
 use log::error;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::time::Duration;
 use tauri::plugin::TauriPlugin;
 use tauri::{Emitter, Manager, Runtime, State};
@@ -12,12 +15,24 @@ use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
 use yaak_models::blob_manager::BlobManager;
 use yaak_models::client_db::ClientDb;
 use yaak_models::error::Result;
-use yaak_models::models::{AnyModel, GraphQlIntrospection, GrpcEvent, Settings, WebsocketEvent};
+use yaak_models::models::{
+    AnyModel, Cookie, CookieJar, Folder, GraphQlIntrospection, GrpcEvent, GrpcRequest,
+    HttpRequest, HttpResponse, MqttEvent, ProtoFiles, Settings, UpsertModelInfo, WebsocketEvent,
+    WebsocketRequest, Workspace,
+};
+use yaak_models::queries::{
+    AuditLogEntry, HttpResponseFilter, RequestDiff, RequestDiffTarget, RequestRevision,
+    ResponseDiff, ResponseDiffOptions, SearchResult, WebsocketEventFilter,
+};
 use yaak_models::query_manager::QueryManager;
+use yaak_models::response_cleanup::spawn_response_cleanup;
 use yaak_models::util::UpdateSource;
 use yaak_plugins::manager::PluginManager;
 
 const MODEL_CHANGES_RETENTION_HOURS: i64 = 1;
+const AUDIT_LOG_RETENTION_DAYS: i64 = 90;
+const RESPONSE_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const WORKSPACE_ACTIVITY_LIMIT: usize = 200;
 const MODEL_CHANGES_POLL_INTERVAL_MS: u64 = 1000;
 const MODEL_CHANGES_POLL_BATCH_SIZE: usize = 200;
 
@@ -145,16 +160,41 @@ pub(crate) fn models_upsert<R: Runtime>(
     let source = &UpdateSource::from_window_label(window.label());
     let id = match model {
         AnyModel::CookieJar(m) => db.upsert_cookie_jar(&m, source)?.id,
-        AnyModel::Environment(m) => db.upsert_environment(&m, source)?.id,
-        AnyModel::Folder(m) => db.upsert_folder(&m, source)?.id,
-        AnyModel::GrpcRequest(m) => db.upsert_grpc_request(&m, source)?.id,
-        AnyModel::HttpRequest(m) => db.upsert_http_request(&m, source)?.id,
+        AnyModel::Environment(mut m) => {
+            // Secret values never reach the database unencrypted
+            window
+                .crypto()
+                .encrypt_secret_variables(&mut m)
+                .map_err(|e| GenericError(e.to_string()))?;
+            db.upsert_environment(&m, source)?.id
+        }
+        AnyModel::Folder(mut m) => {
+            store_authentication_secrets::<_, Folder>(&window, &mut m.id, &mut m.authentication)?;
+            db.upsert_folder(&m, source)?.id
+        }
+        AnyModel::GrpcRequest(mut m) => {
+            store_authentication_secrets::<_, GrpcRequest>(&window, &mut m.id, &mut m.authentication)?;
+            db.upsert_grpc_request(&m, source)?.id
+        }
+        AnyModel::HttpRequest(mut m) => {
+            store_authentication_secrets::<_, HttpRequest>(&window, &mut m.id, &mut m.authentication)?;
+            db.upsert_http_request(&m, source)?.id
+        }
         AnyModel::HttpResponse(m) => db.upsert_http_response(&m, source, &blobs)?.id,
         AnyModel::KeyValue(m) => db.upsert_key_value(&m, source)?.id,
+        AnyModel::Monitor(m) => db.upsert_monitor(&m, source)?.id,
+        AnyModel::MqttRequest(m) => db.upsert_mqtt_request(&m, source)?.id,
         AnyModel::Plugin(m) => db.upsert_plugin(&m, source)?.id,
         AnyModel::Settings(m) => db.upsert_settings(&m, source)?.id,
-        AnyModel::WebsocketRequest(m) => db.upsert_websocket_request(&m, source)?.id,
-        AnyModel::Workspace(m) => db.upsert_workspace(&m, source)?.id,
+        AnyModel::SocketRequest(m) => db.upsert_socket_request(&m, source)?.id,
+        AnyModel::WebsocketRequest(mut m) => {
+            store_authentication_secrets::<_, WebsocketRequest>(&window, &mut m.id, &mut m.authentication)?;
+            db.upsert_websocket_request(&m, source)?.id
+        }
+        AnyModel::Workspace(mut m) => {
+            store_authentication_secrets::<_, Workspace>(&window, &mut m.id, &mut m.authentication)?;
+            db.upsert_workspace(&m, source)?.id
+        }
         AnyModel::WorkspaceMeta(m) => db.upsert_workspace_meta(&m, source)?.id,
         a => return Err(GenericError(format!("Cannot upsert AnyModel {a:?})"))),
     };
@@ -162,6 +202,18 @@ pub(crate) fn models_upsert<R: Runtime>(
     Ok(id)
 }
 
+/// Keep secret authentication values, like a client secret, out of the database
+fn store_authentication_secrets<R: Runtime, M: UpsertModelInfo>(
+    window: &WebviewWindow<R>,
+    id: &mut String,
+    authentication: &mut BTreeMap<String, Value>,
+) -> Result<()> {
+    window
+        .crypto()
+        .store_authentication_secrets::<M>(id, authentication)
+        .map_err(|e| yaak_models::error::Error::GenericError(e.to_string()))
+}
+
 #[tauri::command]
 pub(crate) fn models_delete<R: Runtime>(
     window: WebviewWindow<R>,
@@ -174,6 +226,7 @@ pub(crate) fn models_delete<R: Runtime>(
     window.with_tx(|tx| {
         let source = &UpdateSource::from_window_label(window.label());
         let id = match model {
+            AnyModel::CollectionRun(m) => tx.delete_collection_run(&m, source)?.id,
             AnyModel::CookieJar(m) => tx.delete_cookie_jar(&m, source)?.id,
             AnyModel::Environment(m) => tx.delete_environment(&m, source)?.id,
             AnyModel::Folder(m) => tx.delete_folder(&m, source)?.id,
@@ -181,7 +234,13 @@ pub(crate) fn models_delete<R: Runtime>(
             AnyModel::GrpcRequest(m) => tx.delete_grpc_request(&m, source)?.id,
             AnyModel::HttpRequest(m) => tx.delete_http_request(&m, source)?.id,
             AnyModel::HttpResponse(m) => tx.delete_http_response(&m, source, &blobs)?.id,
+            AnyModel::Monitor(m) => tx.delete_monitor(&m, source)?.id,
+            AnyModel::MonitorRun(m) => tx.delete_monitor_run(&m, source)?.id,
+            AnyModel::MqttConnection(m) => tx.delete_mqtt_connection(&m, source)?.id,
+            AnyModel::MqttRequest(m) => tx.delete_mqtt_request(&m, source)?.id,
             AnyModel::Plugin(m) => tx.delete_plugin(&m, source)?.id,
+            AnyModel::SocketRequest(m) => tx.delete_socket_request(&m, source)?.id,
+            AnyModel::SocketResponse(m) => tx.delete_socket_response(&m, source)?.id,
             AnyModel::WebsocketConnection(m) => tx.delete_websocket_connection(&m, source)?.id,
             AnyModel::WebsocketRequest(m) => tx.delete_websocket_request(&m, source)?.id,
             AnyModel::Workspace(m) => tx.delete_workspace(&m, source)?.id,
@@ -206,7 +265,10 @@ pub(crate) fn models_duplicate<R: Runtime>(
             AnyModel::Folder(m) => tx.duplicate_folder(&m, source)?.id,
             AnyModel::GrpcRequest(m) => tx.duplicate_grpc_request(&m, source)?.id,
             AnyModel::HttpRequest(m) => tx.duplicate_http_request(&m, source)?.id,
+            AnyModel::MqttRequest(m) => tx.duplicate_mqtt_request(&m, source)?.id,
+            AnyModel::SocketRequest(m) => tx.duplicate_socket_request(&m, source)?.id,
             AnyModel::WebsocketRequest(m) => tx.duplicate_websocket_request(&m, source)?.id,
+            AnyModel::Workspace(m) => tx.duplicate_workspace(&m, source)?.id,
             a => return Err(GenericError(format!("Cannot duplicate AnyModel {a:?})"))),
         };
 
@@ -214,6 +276,315 @@ pub(crate) fn models_duplicate<R: Runtime>(
     })
 }
 
+#[tauri::command]
+pub(crate) fn models_trash<R: Runtime>(
+    window: WebviewWindow<R>,
+    model: AnyModel,
+) -> Result<String> {
+    use yaak_models::error::Error::GenericError;
+
+    // Use transaction because trashing a folder recurses into its children
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        let id = match model {
+            AnyModel::Folder(m) => tx.trash_folder(&m, source)?.id,
+            AnyModel::GrpcRequest(m) => tx.trash_grpc_request(&m, source)?.id,
+            AnyModel::HttpRequest(m) => tx.trash_http_request(&m, source)?.id,
+            AnyModel::MqttRequest(m) => tx.trash_mqtt_request(&m, source)?.id,
+            AnyModel::SocketRequest(m) => tx.trash_socket_request(&m, source)?.id,
+            AnyModel::WebsocketRequest(m) => tx.trash_websocket_request(&m, source)?.id,
+            a => return Err(GenericError(format!("Cannot trash AnyModel {a:?})"))),
+        };
+
+        Ok(id)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_restore<R: Runtime>(
+    window: WebviewWindow<R>,
+    model: AnyModel,
+) -> Result<String> {
+    use yaak_models::error::Error::GenericError;
+
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        let id = match model {
+            AnyModel::Folder(m) => tx.restore_folder(&m, source)?.id,
+            AnyModel::GrpcRequest(m) => tx.restore_grpc_request(&m, source)?.id,
+            AnyModel::HttpRequest(m) => tx.restore_http_request(&m, source)?.id,
+            AnyModel::MqttRequest(m) => tx.restore_mqtt_request(&m, source)?.id,
+            AnyModel::SocketRequest(m) => tx.restore_socket_request(&m, source)?.id,
+            AnyModel::WebsocketRequest(m) => tx.restore_websocket_request(&m, source)?.id,
+            a => return Err(GenericError(format!("Cannot restore AnyModel {a:?})"))),
+        };
+
+        Ok(id)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_list_trash<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+) -> Result<Vec<AnyModel>> {
+    Ok(app_handle.db().list_trash(workspace_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_empty_trash<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+) -> Result<()> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.empty_trash(workspace_id, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_reorder_children<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+    folder_id: Option<&str>,
+    ordered_ids: Vec<String>,
+) -> Result<Vec<AnyModel>> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.reorder_children(workspace_id, folder_id, &ordered_ids, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_filter_http_responses<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    request_id: &str,
+    filter: HttpResponseFilter,
+) -> Result<Vec<HttpResponse>> {
+    Ok(app_handle.db().filter_http_responses_for_request(request_id, &filter)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_diff_requests<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    a: RequestDiffTarget,
+    b: RequestDiffTarget,
+) -> Result<RequestDiff> {
+    Ok(app_handle.db().diff_requests(&a, &b)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_diff_responses<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    a_id: &str,
+    b_id: &str,
+    options: ResponseDiffOptions,
+) -> Result<ResponseDiff> {
+    Ok(app_handle.db().diff_responses(a_id, b_id, &options)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_list_request_revisions<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    request_id: &str,
+) -> Result<Vec<RequestRevision>> {
+    Ok(app_handle.db().list_request_revisions(request_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_list_workspace_activity<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+) -> Result<Vec<AuditLogEntry>> {
+    Ok(app_handle.db().list_workspace_activity(workspace_id, WORKSPACE_ACTIVITY_LIMIT)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_restore_request_revision<R: Runtime>(
+    window: WebviewWindow<R>,
+    id: i64,
+) -> Result<String> {
+    let source = &UpdateSource::from_window_label(window.label());
+    Ok(window.db().restore_request_revision(id, source)?.id().to_string())
+}
+
+#[tauri::command]
+pub(crate) fn models_search_workspace<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    query: &str,
+) -> Result<Vec<SearchResult>> {
+    Ok(app_handle.db().search_workspace(workspace_id, query)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_list_pinned_items<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+) -> Result<Vec<AnyModel>> {
+    Ok(app_handle.db().list_pinned_items(workspace_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_pin_request<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    request_id: &str,
+) -> Result<()> {
+    Ok(app_handle.db().pin_request(workspace_id, request_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_unpin_request<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    request_id: &str,
+) -> Result<()> {
+    Ok(app_handle.db().unpin_request(workspace_id, request_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_append_grpc_message_template<R: Runtime>(
+    window: WebviewWindow<R>,
+    request_id: &str,
+    name: &str,
+    message: &str,
+) -> Result<GrpcRequest> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.append_grpc_message_template(request_id, name, message, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_reorder_grpc_message_templates<R: Runtime>(
+    window: WebviewWindow<R>,
+    request_id: &str,
+    template_ids: Vec<String>,
+) -> Result<GrpcRequest> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.reorder_grpc_message_templates(request_id, &template_ids, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_delete_grpc_message_template<R: Runtime>(
+    window: WebviewWindow<R>,
+    request_id: &str,
+    template_id: &str,
+) -> Result<GrpcRequest> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.delete_grpc_message_template(request_id, template_id, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_upsert_cookie<R: Runtime>(
+    window: WebviewWindow<R>,
+    cookie_jar_id: &str,
+    cookie: Cookie,
+    previous: Option<Cookie>,
+) -> Result<CookieJar> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.upsert_cookie(cookie_jar_id, &cookie, previous.as_ref(), source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_delete_cookie<R: Runtime>(
+    window: WebviewWindow<R>,
+    cookie_jar_id: &str,
+    cookie: Cookie,
+) -> Result<CookieJar> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.delete_cookie(cookie_jar_id, &cookie, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_clear_cookies<R: Runtime>(
+    window: WebviewWindow<R>,
+    cookie_jar_id: &str,
+) -> Result<CookieJar> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.clear_cookies(cookie_jar_id, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_get_proto_files<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+) -> Result<Option<ProtoFiles>> {
+    Ok(app_handle.db().get_proto_files(workspace_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_attach_proto_files<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    paths: Vec<String>,
+    include_dirs: Vec<String>,
+) -> Result<ProtoFiles> {
+    Ok(app_handle.db().attach_proto_files(workspace_id, &paths, &include_dirs)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_detach_proto_files<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    paths: Vec<String>,
+) -> Result<ProtoFiles> {
+    Ok(app_handle.db().detach_proto_files(workspace_id, &paths)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_list_tags<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+) -> Result<Vec<String>> {
+    Ok(app_handle.db().list_tags(workspace_id)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_list_requests_by_tag<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    workspace_id: &str,
+    tag: &str,
+) -> Result<Vec<AnyModel>> {
+    Ok(app_handle.db().list_requests_by_tag(workspace_id, tag)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_rename_tag<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+    old_tag: &str,
+    new_tag: &str,
+) -> Result<()> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.rename_tag(workspace_id, old_tag, new_tag, source)
+    })
+}
+
+#[tauri::command]
+pub(crate) fn models_delete_tag<R: Runtime>(
+    window: WebviewWindow<R>,
+    workspace_id: &str,
+    tag: &str,
+) -> Result<()> {
+    window.with_tx(|tx| {
+        let source = &UpdateSource::from_window_label(window.label());
+        tx.delete_tag(workspace_id, tag, source)
+    })
+}
+
 #[tauri::command]
 pub(crate) fn models_websocket_events<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
@@ -222,6 +593,23 @@ pub(crate) fn models_websocket_events<R: Runtime>(
     Ok(app_handle.db().list_websocket_events(connection_id)?)
 }
 
+#[tauri::command]
+pub(crate) fn models_filter_websocket_events<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    connection_id: &str,
+    filter: WebsocketEventFilter,
+) -> Result<Vec<WebsocketEvent>> {
+    Ok(app_handle.db().filter_websocket_events(connection_id, &filter)?)
+}
+
+#[tauri::command]
+pub(crate) fn models_mqtt_events<R: Runtime>(
+    app_handle: tauri::AppHandle<R>,
+    connection_id: &str,
+) -> Result<Vec<MqttEvent>> {
+    Ok(app_handle.db().list_mqtt_events(connection_id)?)
+}
+
 #[tauri::command]
 pub(crate) fn models_grpc_events<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
@@ -248,11 +636,18 @@ pub(crate) fn models_upsert_graphql_introspection<R: Runtime>(
     app_handle: tauri::AppHandle<R>,
     request_id: &str,
     workspace_id: &str,
+    url: Option<String>,
     content: Option<String>,
     window: WebviewWindow<R>,
 ) -> Result<GraphQlIntrospection> {
     let source = UpdateSource::from_window_label(window.label());
-    Ok(app_handle.db().upsert_graphql_introspection(workspace_id, request_id, content, &source)?)
+    Ok(app_handle.db().upsert_graphql_introspection(
+        workspace_id,
+        request_id,
+        url,
+        content,
+        &source,
+    )?)
 }
 
 #[tauri::command]
@@ -282,6 +677,7 @@ pub(crate) async fn models_workspace_models<R: Runtime>(
     // Add the workspace children
     if let Some(wid) = workspace_id {
         let db = window.db();
+        l.append(&mut db.list_collection_runs(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_cookie_jars(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_environments_ensure_base(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_folders(wid)?.into_iter().map(Into::into).collect());
@@ -289,6 +685,12 @@ pub(crate) async fn models_workspace_models<R: Runtime>(
         l.append(&mut db.list_grpc_requests(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_http_requests(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_http_responses(wid, None)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_monitors(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_monitor_runs(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_mqtt_connections(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_mqtt_requests(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_socket_requests(wid)?.into_iter().map(Into::into).collect());
+        l.append(&mut db.list_socket_responses(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_websocket_connections(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_websocket_requests(wid)?.into_iter().map(Into::into).collect());
         l.append(&mut db.list_workspace_metas(wid)?.into_iter().map(Into::into).collect());
@@ -348,9 +750,18 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             {
                 error!("Failed to prune model_changes rows on startup: {err:?}");
             }
+            if let Err(err) = db.prune_audit_logs_older_than_days(AUDIT_LOG_RETENTION_DAYS) {
+                error!("Failed to prune audit_logs rows on startup: {err:?}");
+            }
             // Only stream writes that happen after this app launch.
             let cursor = ModelChangeCursor::from_launch_time();
 
+            spawn_response_cleanup(
+                query_manager.clone(),
+                blob_manager.clone(),
+                RESPONSE_CLEANUP_INTERVAL,
+            );
+
             let poll_query_manager = query_manager.clone();
 
             app_handle.manage(query_manager);
@@ -0,0 +1,125 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::models_ext::{BlobManagerExt, QueryManagerExt};
+use chrono::Utc;
+use log::{debug, warn};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use yaak::monitor::{MonitorRunParams, monitor_is_down, monitor_is_due, run_monitor};
+use yaak_crypto::manager::EncryptionManager;
+use yaak_http::manager::HttpConnectionManager;
+use yaak_models::models::{Monitor, MonitorRun};
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::{Color, PluginContext, ShowToastRequest};
+use yaak_plugins::manager::PluginManager;
+
+// How often to look for monitors whose interval has come around
+const MONITOR_CHECK_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Run enabled monitors in the background for as long as the app is open. A monitor that's still
+/// running when it next comes due is skipped rather than started twice.
+pub(crate) fn start_monitor_scheduler<R: Runtime>(app_handle: &AppHandle<R>) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let in_flight: Arc<Mutex<HashSet<String>>> = Default::default();
+        let mut interval = tokio::time::interval(MONITOR_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let monitors = match due_monitors(&app_handle) {
+                Ok(monitors) => monitors,
+                Err(e) => {
+                    warn!("Failed to list monitors {e:?}");
+                    continue;
+                }
+            };
+
+            for monitor in monitors {
+                if !in_flight.lock().unwrap().insert(monitor.id.clone()) {
+                    continue;
+                }
+                let app_handle = app_handle.clone();
+                let in_flight = in_flight.clone();
+                tauri::async_runtime::spawn(async move {
+                    debug!("Running monitor {}", monitor.id);
+                    if let Err(e) =
+                        run_monitor_now(&app_handle, &monitor.id, UpdateSource::Background).await
+                    {
+                        warn!("Failed to run monitor {}: {e:?}", monitor.id);
+                    }
+                    in_flight.lock().unwrap().remove(&monitor.id);
+                });
+            }
+        }
+    });
+}
+
+fn due_monitors<R: Runtime>(app_handle: &AppHandle<R>) -> Result<Vec<Monitor>> {
+    let db = app_handle.db();
+    let now = Utc::now().naive_utc();
+    let mut monitors = Vec::new();
+    for workspace in db.list_workspaces()? {
+        let workspace_monitors = db.list_monitors(&workspace.id)?;
+        monitors.extend(workspace_monitors.into_iter().filter(|m| monitor_is_due(m, now)));
+    }
+    Ok(monitors)
+}
+
+/// Run a monitor once, showing a toast if this run is the one that flags it as down
+pub(crate) async fn run_monitor_now<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    monitor_id: &str,
+    update_source: UpdateSource,
+) -> Result<MonitorRun> {
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    let response_dir = app_handle.path().app_data_dir()?.join("responses");
+    let before = app_handle.db().get_monitor(monitor_id)?;
+    let plugin_context = PluginContext::new(None, Some(before.workspace_id.clone()));
+
+    let (monitor, run) = run_monitor(MonitorRunParams {
+        query_manager: app_handle.db_manager().inner(),
+        blob_manager: app_handle.blob_manager().inner(),
+        monitor_id,
+        update_source,
+        response_dir: &response_dir,
+        plugin_manager,
+        encryption_manager,
+        plugin_context: &plugin_context,
+        connection_manager: Some(app_handle.state::<HttpConnectionManager>().inner()),
+    })
+    .await
+    .map_err(|e| GenericError(e.to_string()))?;
+
+    if !monitor_is_down(&before) && monitor_is_down(&monitor) {
+        let _ = app_handle.emit(
+            "show_toast",
+            ShowToastRequest {
+                message: format!(
+                    "Monitor {} is down after failing {} times in a row",
+                    monitor_name(app_handle, &monitor),
+                    monitor.consecutive_failures
+                ),
+                color: Some(Color::Danger),
+                icon: None,
+                timeout: None,
+            },
+        );
+    }
+
+    Ok(run)
+}
+
+fn monitor_name<R: Runtime>(app_handle: &AppHandle<R>, monitor: &Monitor) -> String {
+    if !monitor.name.is_empty() {
+        return monitor.name.clone();
+    }
+    let db = app_handle.db();
+    let name = match (&monitor.request_id, &monitor.folder_id) {
+        (Some(id), _) => db.get_http_request(id).map(|r| r.name).ok(),
+        (None, Some(id)) => db.get_folder(id).map(|f| f.name).ok(),
+        (None, None) => None,
+    };
+    name.filter(|n| !n.is_empty()).unwrap_or_else(|| monitor.id.clone())
+}
@@ -0,0 +1,293 @@
+//! MQTT Tauri command wrappers
+//! These wrap the core yaak-mqtt functionality for Tauri IPC.
+
+use crate::PluginContextExt;
+use crate::error::Result;
+use crate::models_ext::QueryManagerExt;
+use log::{info, warn};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime, State, WebviewWindow, command};
+use tokio::sync::mpsc;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_models::models::{
+    MqttConnection, MqttConnectionState, MqttEvent, MqttEventType, MqttRequest,
+};
+use yaak_models::util::{UpdateSource, generate_id};
+use yaak_mqtt::{MqttClientEvent, MqttConnectOptions, MqttManager, render_mqtt_request};
+use yaak_plugins::events::RenderPurpose;
+use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::{RenderErrorBehavior, RenderOptions};
+use yaak_tls::{find_ca_certificates, select_client_certificate};
+
+#[command]
+pub async fn cmd_mqtt_delete_connections<R: Runtime>(
+    request_id: &str,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+) -> Result<()> {
+    Ok(app_handle.db().delete_all_mqtt_connections_for_request(
+        request_id,
+        &UpdateSource::from_window_label(window.label()),
+    )?)
+}
+
+#[command]
+pub async fn cmd_mqtt_publish<R: Runtime>(
+    connection_id: &str,
+    environment_id: Option<&str>,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    mqtt_manager: State<'_, MqttManager>,
+) -> Result<MqttEvent> {
+    let connection = app_handle.db().get_mqtt_connection(connection_id)?;
+    let unrendered_request = app_handle.db().get_mqtt_request(&connection.request_id)?;
+    let request = render_request(&app_handle, &window, &unrendered_request, environment_id).await?;
+
+    mqtt_manager
+        .publish(
+            &connection.id,
+            &request.topic,
+            request.payload.clone().into_bytes(),
+            request.qos,
+            request.retain,
+        )
+        .await?;
+
+    Ok(app_handle.db().upsert_mqtt_event(
+        &MqttEvent {
+            connection_id: connection.id.clone(),
+            request_id: request.id.clone(),
+            workspace_id: connection.workspace_id.clone(),
+            is_server: false,
+            event_type: MqttEventType::Publish,
+            topic: request.topic,
+            message: request.payload.into_bytes(),
+            qos: request.qos,
+            retain: request.retain,
+            ..Default::default()
+        },
+        &UpdateSource::from_window_label(window.label()),
+    )?)
+}
+
+#[command]
+pub async fn cmd_mqtt_close<R: Runtime>(
+    connection_id: &str,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    mqtt_manager: State<'_, MqttManager>,
+) -> Result<MqttConnection> {
+    let connection = {
+        let db = app_handle.db();
+        let connection = db.get_mqtt_connection(connection_id)?;
+        db.upsert_mqtt_connection(
+            &MqttConnection { state: MqttConnectionState::Closing, ..connection },
+            &UpdateSource::from_window_label(window.label()),
+        )?
+    };
+
+    if let Err(e) = mqtt_manager.close(&connection.id).await {
+        warn!("Failed to close MQTT connection: {e:?}");
+    };
+
+    Ok(connection)
+}
+
+#[command]
+pub async fn cmd_mqtt_connect<R: Runtime>(
+    request_id: &str,
+    environment_id: Option<&str>,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    mqtt_manager: State<'_, MqttManager>,
+) -> Result<MqttConnection> {
+    let unrendered_request = app_handle.db().get_mqtt_request(request_id)?;
+    let resolved_settings =
+        app_handle.db().resolve_settings_for_mqtt_request(&unrendered_request)?;
+    let settings = app_handle.db().get_settings();
+    let request = render_request(&app_handle, &window, &unrendered_request, environment_id).await?;
+
+    // Brokers drop a client when another connects with the same ID, so make one up if needed
+    let client_id = match request.client_id.trim() {
+        "" => format!("yaak-{}", generate_id()),
+        id => id.to_string(),
+    };
+
+    let connection = app_handle.db().upsert_mqtt_connection(
+        &MqttConnection {
+            workspace_id: request.workspace_id.clone(),
+            request_id: request_id.to_string(),
+            url: request.url.clone(),
+            client_id: client_id.clone(),
+            ..Default::default()
+        },
+        &UpdateSource::from_window_label(window.label()),
+    )?;
+
+    let options = MqttConnectOptions {
+        url: request.url.clone(),
+        client_id,
+        username: request.username.clone(),
+        password: request.password.clone(),
+        keep_alive: Duration::from_secs(request.keep_alive.max(0) as u64),
+        clean_session: !request.persistent_session,
+        validate_certificates: resolved_settings.validate_certificates.value,
+        client_cert: select_client_certificate(
+            &request.url,
+            resolved_settings.client_certificate.value.as_ref(),
+            &settings.client_certificates,
+        ),
+        ca_certificates: find_ca_certificates(&resolved_settings.ca_certificates.value),
+    };
+
+    let start = Instant::now();
+    let (event_tx, mut event_rx) = mpsc::channel::<MqttClientEvent>(128);
+    let session_present = match mqtt_manager.connect(&connection.id, &options, event_tx).await {
+        Ok(session_present) => session_present,
+        Err(e) => {
+            return Ok(app_handle.db().upsert_mqtt_connection(
+                &MqttConnection {
+                    error: Some(e.to_string()),
+                    elapsed: start.elapsed().as_millis() as i32,
+                    state: MqttConnectionState::Closed,
+                    ..connection
+                },
+                &UpdateSource::from_window_label(window.label()),
+            )?);
+        }
+    };
+
+    let upsert_event = {
+        let app_handle = app_handle.clone();
+        let connection = connection.clone();
+        let window_label = window.label().to_string();
+        move |event: MqttEvent| {
+            app_handle
+                .db()
+                .upsert_mqtt_event(
+                    &MqttEvent {
+                        connection_id: connection.id.clone(),
+                        request_id: connection.request_id.clone(),
+                        workspace_id: connection.workspace_id.clone(),
+                        ..event
+                    },
+                    &UpdateSource::from_window_label(&window_label),
+                )
+                .unwrap();
+        }
+    };
+
+    let message = match session_present {
+        true => "Resumed existing session",
+        false => "",
+    };
+    upsert_event(MqttEvent {
+        event_type: MqttEventType::Connect,
+        message: message.as_bytes().to_vec(),
+        ..Default::default()
+    });
+
+    for subscription in request.subscriptions.iter().filter(|s| s.enabled && !s.topic.is_empty()) {
+        let message = match mqtt_manager
+            .subscribe(&connection.id, &subscription.topic, subscription.qos)
+            .await
+        {
+            Ok(()) => Vec::new(),
+            Err(e) => e.to_string().into_bytes(),
+        };
+        upsert_event(MqttEvent {
+            event_type: match message.is_empty() {
+                true => MqttEventType::Subscribe,
+                false => MqttEventType::Error,
+            },
+            topic: subscription.topic.clone(),
+            message,
+            qos: subscription.qos,
+            ..Default::default()
+        });
+    }
+
+    let connection = app_handle.db().upsert_mqtt_connection(
+        &MqttConnection {
+            state: MqttConnectionState::Connected,
+            elapsed: start.elapsed().as_millis() as i32,
+            ..connection
+        },
+        &UpdateSource::from_window_label(window.label()),
+    )?;
+
+    {
+        let connection_id = connection.id.clone();
+        let window_label = window.label().to_string();
+        tokio::spawn(async move {
+            while let Some(event) = event_rx.recv().await {
+                match event {
+                    MqttClientEvent::Message { topic, payload, qos, retain } => {
+                        upsert_event(MqttEvent {
+                            is_server: true,
+                            event_type: MqttEventType::Publish,
+                            topic,
+                            message: payload,
+                            qos,
+                            retain,
+                            ..Default::default()
+                        });
+                    }
+                    MqttClientEvent::Disconnected { error } => {
+                        upsert_event(MqttEvent {
+                            is_server: error.is_some(),
+                            event_type: MqttEventType::Disconnect,
+                            message: error.clone().unwrap_or_default().into_bytes(),
+                            ..Default::default()
+                        });
+                        let current = app_handle.db().get_mqtt_connection(&connection_id).unwrap();
+                        app_handle
+                            .db()
+                            .upsert_mqtt_connection(
+                                &MqttConnection {
+                                    state: MqttConnectionState::Closed,
+                                    error,
+                                    ..current
+                                },
+                                &UpdateSource::from_window_label(&window_label),
+                            )
+                            .unwrap();
+                        break;
+                    }
+                }
+            }
+            info!("MQTT connection closed");
+        });
+    }
+
+    Ok(connection)
+}
+
+async fn render_request<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    window: &WebviewWindow<R>,
+    request: &MqttRequest,
+    environment_id: Option<&str>,
+) -> Result<MqttRequest> {
+    let environment_chain = app_handle.db().resolve_environments(
+        &request.workspace_id,
+        request.folder_id.as_deref(),
+        environment_id,
+    )?;
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    Ok(render_mqtt_request(
+        request,
+        environment_chain,
+        &PluginTemplateCallback::new(
+            plugin_manager,
+            encryption_manager,
+            &window.plugin_context(),
+            RenderPurpose::Send,
+        ),
+        &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
+    )
+    .await?)
+}
@@ -310,6 +310,7 @@ async fn handle_host_plugin_request<R: Runtime>(
                 cookie_jar,
                 &mut tokio::sync::watch::channel(false).1,
                 plugin_context,
+                None,
             )
             .await?;
 
@@ -1,5 +1,7 @@
 use serde_json::Value;
-pub use yaak::render::{render_grpc_request, render_http_request};
+pub use yaak::render::{
+    render_grpc_request, render_http_request, render_proxy_auth, render_ssh_tunnel,
+};
 use yaak_models::models::Environment;
 use yaak_models::render::make_vars_hashmap;
 use yaak_templates::{RenderOptions, TemplateCallback, parse_and_render, render_json_value_raw};
@@ -0,0 +1,113 @@
+//! Raw TCP/UDP socket Tauri command wrappers
+//! These wrap the core yaak-socket functionality for Tauri IPC.
+
+use crate::PluginContextExt;
+use crate::error::Result;
+use crate::models_ext::QueryManagerExt;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow, command};
+use yaak_crypto::manager::EncryptionManager;
+use yaak_models::models::{SocketRequest, SocketResponse, SocketResponseState};
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::RenderPurpose;
+use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_socket::{SocketSendOptions, render_socket_request, send_socket, socket_payload};
+use yaak_templates::{RenderErrorBehavior, RenderOptions};
+
+#[command]
+pub async fn cmd_socket_delete_responses<R: Runtime>(
+    request_id: &str,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+) -> Result<()> {
+    Ok(app_handle.db().delete_all_socket_responses_for_request(
+        request_id,
+        &UpdateSource::from_window_label(window.label()),
+    )?)
+}
+
+#[command]
+pub async fn cmd_socket_send<R: Runtime>(
+    request_id: &str,
+    environment_id: Option<&str>,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+) -> Result<SocketResponse> {
+    let unrendered_request = app_handle.db().get_socket_request(request_id)?;
+    let request = render_request(&app_handle, &window, &unrendered_request, environment_id).await?;
+
+    let response = app_handle.db().upsert_socket_response(
+        &SocketResponse {
+            workspace_id: request.workspace_id.clone(),
+            request_id: request_id.to_string(),
+            protocol: request.protocol,
+            url: request.url.clone(),
+            ..Default::default()
+        },
+        &UpdateSource::from_window_label(window.label()),
+    )?;
+
+    let result = match socket_payload(request.payload_format, &request.payload) {
+        Ok(payload) => {
+            send_socket(SocketSendOptions {
+                protocol: request.protocol,
+                url: request.url.clone(),
+                payload,
+                read_timeout: request.read_timeout,
+            })
+            .await
+        }
+        Err(e) => Err(e),
+    };
+
+    let response = match result {
+        Ok(exchange) => SocketResponse {
+            state: SocketResponseState::Closed,
+            remote_addr: exchange.remote_addr,
+            error: exchange.error,
+            elapsed_connect: exchange.elapsed_connect,
+            elapsed_first_byte: exchange.elapsed_first_byte,
+            elapsed: exchange.elapsed,
+            bytes_sent: exchange.bytes_sent,
+            body: exchange.body,
+            ..response
+        },
+        Err(e) => SocketResponse {
+            state: SocketResponseState::Closed,
+            error: Some(e.to_string()),
+            ..response
+        },
+    };
+
+    Ok(app_handle
+        .db()
+        .upsert_socket_response(&response, &UpdateSource::from_window_label(window.label()))?)
+}
+
+async fn render_request<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    window: &WebviewWindow<R>,
+    request: &SocketRequest,
+    environment_id: Option<&str>,
+) -> Result<SocketRequest> {
+    let environment_chain = app_handle.db().resolve_environments(
+        &request.workspace_id,
+        request.folder_id.as_deref(),
+        environment_id,
+    )?;
+    let plugin_manager = Arc::new((*app_handle.state::<PluginManager>()).clone());
+    let encryption_manager = Arc::new((*app_handle.state::<EncryptionManager>()).clone());
+    Ok(render_socket_request(
+        request,
+        environment_chain,
+        &PluginTemplateCallback::new(
+            plugin_manager,
+            encryption_manager,
+            &window.plugin_context(),
+            RenderPurpose::Send,
+        ),
+        &RenderOptions { error_behavior: RenderErrorBehavior::Throw },
+    )
+    .await?)
+}
@@ -12,10 +12,12 @@ use tauri::ipc::Channel;
 use tauri::{AppHandle, Listener, Runtime, command};
 use tokio::sync::watch;
 use ts_rs::TS;
+use yaak_models::models::SyncState;
 use yaak_sync::error::Error::InvalidSyncDirectory;
+use yaak_sync::merge::{ConflictResolution, MergeResult};
 use yaak_sync::sync::{
     FsCandidate, SyncOp, apply_sync_ops, apply_sync_state_ops, compute_sync_ops, get_db_candidates,
-    get_fs_candidates,
+    get_fs_candidates, resolve_sync_conflict,
 };
 use yaak_sync::watch::{WatchEvent, watch_directory};
 
@@ -60,6 +62,22 @@ pub(crate) async fn cmd_sync_apply<R: Runtime>(
     Ok(())
 }
 
+#[command]
+pub(crate) async fn cmd_sync_resolve_conflict<R: Runtime>(
+    app_handle: AppHandle<R>,
+    state: SyncState,
+    merge: MergeResult,
+    resolutions: Vec<ConflictResolution>,
+    sync_dir: &Path,
+    workspace_id: &str,
+) -> Result<()> {
+    let db = app_handle.db();
+    let sync_op = resolve_sync_conflict(state, &merge, &resolutions)?;
+    let sync_state_ops = apply_sync_ops(&db, workspace_id, sync_dir, vec![sync_op])?;
+    apply_sync_state_ops(&db, workspace_id, sync_dir, sync_state_ops)?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "index.ts")]
@@ -2,23 +2,26 @@
 //! These wrap the core yaak-ws functionality for Tauri IPC.
 
 use crate::PluginContextExt;
+use crate::error::Error::GenericError;
 use crate::error::Result;
 use crate::models_ext::QueryManagerExt;
 use http::HeaderMap;
 use log::{debug, info, warn};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tauri::http::HeaderValue;
 use tauri::{AppHandle, Manager, Runtime, State, WebviewWindow, command};
 use tokio::sync::{Mutex, mpsc};
 use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::tungstenite::handshake::client::Response;
 use url::Url;
 use yaak_crypto::manager::EncryptionManager;
 use yaak_http::cookies::CookieStore;
 use yaak_http::path_placeholders::apply_path_placeholders;
 use yaak_models::models::{
     HttpResponseHeader, WebsocketConnection, WebsocketConnectionState, WebsocketEvent,
-    WebsocketEventType, WebsocketRequest,
+    WebsocketEventType, WebsocketMessageFormat, WebsocketProtocol, WebsocketRequest,
 };
 use yaak_models::util::UpdateSource;
 use yaak_plugins::events::{CallHttpAuthenticationRequest, HttpHeader, RenderPurpose};
@@ -26,8 +29,11 @@ use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
 use yaak_templates::strip_json_comments::maybe_strip_json_comments;
 use yaak_templates::{RenderErrorBehavior, RenderOptions};
-use yaak_tls::find_client_certificate;
-use yaak_ws::{WebsocketManager, render_websocket_request};
+use yaak_tls::{find_ca_certificates, select_client_certificate};
+use yaak_ws::{
+    ConnectionEvent, ReconnectPolicy, SocketIoSession, WebsocketManager, render_websocket_request,
+    socket_io_url, websocket_message,
+};
 
 #[command]
 pub async fn cmd_ws_delete_connections<R: Runtime>(
@@ -73,10 +79,33 @@ pub async fn cmd_ws_send<R: Runtime>(
     )
     .await?;
 
-    let message = maybe_strip_json_comments(&request.message);
-
     let mut ws_manager = ws_manager.lock().await;
-    ws_manager.send(&connection.id, Message::Text(message.clone().into())).await?;
+    let message = match request.protocol {
+        WebsocketProtocol::Websocket => {
+            let message = websocket_message(
+                request.message_format,
+                &request.message,
+                request.message_file.as_deref(),
+            )
+            .await?;
+            ws_manager.send(&connection.id, message.clone()).await?;
+            message
+        }
+        WebsocketProtocol::SocketIo => {
+            if request.message_format != WebsocketMessageFormat::Text {
+                return Err(GenericError(
+                    "Socket.IO events can only be sent from text messages".to_string(),
+                ));
+            }
+            let socket_io = &request.socket_io;
+            let args = socket_io_args(&request.message);
+            ws_manager.emit(&connection.id, &socket_io.event, args, socket_io.ack).await?
+        }
+    };
+    let message_type = match message {
+        Message::Binary(_) => WebsocketEventType::Binary,
+        _ => WebsocketEventType::Text,
+    };
 
     app_handle.db().upsert_websocket_event(
         &WebsocketEvent {
@@ -84,8 +113,8 @@ pub async fn cmd_ws_send<R: Runtime>(
             request_id: request.id.clone(),
             workspace_id: connection.workspace_id.clone(),
             is_server: false,
-            message_type: WebsocketEventType::Text,
-            message: message.into(),
+            message_type,
+            message: message.into_data().into(),
             ..Default::default()
         },
         &UpdateSource::from_window_label(window.label()),
@@ -94,6 +123,50 @@ pub async fn cmd_ws_send<R: Runtime>(
     Ok(connection)
 }
 
+/// Send a previously sent message again, as it was sent. The connection doesn't need to be the one
+/// the message was first sent on, so messages from earlier connections can be replayed too.
+#[command]
+pub async fn cmd_ws_replay_event<R: Runtime>(
+    connection_id: &str,
+    event_id: &str,
+    app_handle: AppHandle<R>,
+    window: WebviewWindow<R>,
+    ws_manager: State<'_, Mutex<WebsocketManager>>,
+) -> Result<WebsocketEvent> {
+    let connection = app_handle.db().get_websocket_connection(connection_id)?;
+    if !matches!(connection.state, WebsocketConnectionState::Connected) {
+        return Err(GenericError("WebSocket connection is not open".to_string()));
+    }
+
+    let event = app_handle.db().get_websocket_event(event_id)?;
+    if event.is_server {
+        return Err(GenericError("Only sent messages can be replayed".to_string()));
+    }
+    let message = match event.message_type {
+        WebsocketEventType::Text => {
+            Message::Text(String::from_utf8_lossy(&event.message).to_string().into())
+        }
+        WebsocketEventType::Binary => Message::Binary(event.message.clone().into()),
+        _ => return Err(GenericError("Only text and binary messages can be replayed".to_string())),
+    };
+
+    let mut ws_manager = ws_manager.lock().await;
+    ws_manager.send(&connection.id, message).await?;
+
+    Ok(app_handle.db().upsert_websocket_event(
+        &WebsocketEvent {
+            connection_id: connection.id.clone(),
+            request_id: connection.request_id.clone(),
+            workspace_id: connection.workspace_id.clone(),
+            is_server: false,
+            message_type: event.message_type,
+            message: event.message,
+            ..Default::default()
+        },
+        &UpdateSource::from_window_label(window.label()),
+    )?)
+}
+
 #[command]
 pub async fn cmd_ws_close<R: Runtime>(
     connection_id: &str,
@@ -272,7 +345,7 @@ pub async fn cmd_ws_connect<R: Runtime>(
         }
     }
 
-    let (receive_tx, mut receive_rx) = mpsc::channel::<Message>(128);
+    let (receive_tx, mut receive_rx) = mpsc::channel::<ConnectionEvent>(128);
     let mut ws_manager = ws_manager.lock().await;
 
     {
@@ -289,7 +362,46 @@ pub async fn cmd_ws_connect<R: Runtime>(
         }
     }
 
-    let client_cert = find_client_certificate(url.as_str(), &settings.client_certificates);
+    let socket_io = match request.protocol {
+        WebsocketProtocol::Websocket => None,
+        WebsocketProtocol::SocketIo => {
+            socket_io_url(&mut url);
+            let auth = match maybe_strip_json_comments(&request.socket_io.auth).trim() {
+                "" => None,
+                auth => match serde_json::from_str(auth) {
+                    Ok(auth) => Some(auth),
+                    Err(e) => {
+                        return Ok(app_handle.db().upsert_websocket_connection(
+                            &WebsocketConnection {
+                                error: Some(format!("Invalid Socket.IO auth payload: {e}")),
+                                state: WebsocketConnectionState::Closed,
+                                ..connection
+                            },
+                            &UpdateSource::from_window_label(window.label()),
+                        )?);
+                    }
+                },
+            };
+            Some(SocketIoSession::new(&request.socket_io.namespace, auth))
+        }
+    };
+
+    let client_cert = select_client_certificate(
+        url.as_str(),
+        resolved_settings.client_certificate.value.as_ref(),
+        &settings.client_certificates,
+    );
+    let workspace = app_handle.db().get_workspace(&request.workspace_id)?;
+    let ca_certificates = find_ca_certificates(&resolved_settings.ca_certificates.value);
+    let reconnect = request.reconnect.enabled.then(|| {
+        let handshake = maybe_strip_json_comments(&request.reconnect.handshake_message);
+        ReconnectPolicy {
+            max_attempts: request.reconnect.max_attempts,
+            initial_delay: Duration::from_millis(request.reconnect.initial_delay_ms.into()),
+            max_delay: Duration::from_millis(request.reconnect.max_delay_ms.into()),
+            handshake: (!handshake.is_empty()).then(|| Message::Text(handshake.into())),
+        }
+    });
 
     let response = match ws_manager
         .connect(
@@ -299,6 +411,10 @@ pub async fn cmd_ws_connect<R: Runtime>(
             receive_tx,
             resolved_settings.validate_certificates.value,
             client_cert,
+            ca_certificates,
+            workspace.setting_dns_overrides,
+            reconnect,
+            socket_io,
         )
         .await
     {
@@ -327,14 +443,7 @@ pub async fn cmd_ws_connect<R: Runtime>(
         &UpdateSource::from_window_label(window.label()),
     )?;
 
-    let response_headers = response
-        .headers()
-        .into_iter()
-        .map(|(name, value)| HttpResponseHeader {
-            name: name.to_string(),
-            value: value.to_str().unwrap().to_string(),
-        })
-        .collect::<Vec<HttpResponseHeader>>();
+    let response_headers = response_headers(&response);
 
     if let (true, Some(cookie_jar), Some(store)) =
         (resolved_settings.store_cookies.value, cookie_jar.as_mut(), cookie_store.as_ref())
@@ -368,55 +477,118 @@ pub async fn cmd_ws_connect<R: Runtime>(
         let connection_id = connection.id.clone();
         let request_id = request.id.to_string();
         let workspace_id = request.workspace_id.clone();
-        let connection = connection.clone();
+        let mut connection = connection.clone();
         let window_label = window.label().to_string();
+        let max_attempts = request.reconnect.max_attempts;
         let mut has_written_close = false;
         tokio::spawn(async move {
-            while let Some(message) = receive_rx.recv().await {
+            let upsert_event =
+                |is_server: bool, message_type: WebsocketEventType, message: Vec<u8>| {
+                    app_handle
+                        .db()
+                        .upsert_websocket_event(
+                            &WebsocketEvent {
+                                connection_id: connection_id.clone(),
+                                request_id: request_id.clone(),
+                                workspace_id: workspace_id.clone(),
+                                is_server,
+                                message_type,
+                                message,
+                                ..Default::default()
+                            },
+                            &UpdateSource::from_window_label(&window_label),
+                        )
+                        .unwrap();
+                };
+
+            while let Some(event) = receive_rx.recv().await {
+                let message = match event {
+                    ConnectionEvent::Message(message) => message,
+                    ConnectionEvent::Sent(message) => {
+                        upsert_event(false, WebsocketEventType::Text, message.into_data().into());
+                        continue;
+                    }
+                    ConnectionEvent::Reconnecting { attempt, delay } => {
+                        let of = match max_attempts {
+                            0 => String::new(),
+                            n => format!(" of {n}"),
+                        };
+                        let msg = format!(
+                            "Reconnecting in {:.1}s (attempt {attempt}{of})",
+                            delay.as_secs_f32()
+                        );
+                        upsert_event(false, WebsocketEventType::Reconnect, msg.into_bytes());
+
+                        // Don't undo a close that was requested while the connection was down
+                        let current =
+                            app_handle.db().get_websocket_connection(&connection_id).unwrap();
+                        if matches!(current.state, WebsocketConnectionState::Connected) {
+                            connection = app_handle
+                                .db()
+                                .upsert_websocket_connection(
+                                    &WebsocketConnection {
+                                        state: WebsocketConnectionState::Initialized,
+                                        ..current
+                                    },
+                                    &UpdateSource::from_window_label(&window_label),
+                                )
+                                .unwrap();
+                        }
+                        continue;
+                    }
+                    ConnectionEvent::ReconnectFailed { attempt, error, will_retry } => {
+                        let msg = match will_retry {
+                            true => format!("Attempt {attempt} failed: {error}"),
+                            false => format!("Attempt {attempt} failed, giving up: {error}"),
+                        };
+                        upsert_event(false, WebsocketEventType::Reconnect, msg.into_bytes());
+                        continue;
+                    }
+                    ConnectionEvent::Reconnected { attempt, response } => {
+                        let msg = format!("Reconnected after {attempt} attempt(s)");
+                        upsert_event(false, WebsocketEventType::Reconnect, msg.into_bytes());
+                        has_written_close = false;
+                        let current =
+                            app_handle.db().get_websocket_connection(&connection_id).unwrap();
+                        if matches!(current.state, WebsocketConnectionState::Initialized) {
+                            connection = app_handle
+                                .db()
+                                .upsert_websocket_connection(
+                                    &WebsocketConnection {
+                                        state: WebsocketConnectionState::Connected,
+                                        headers: response_headers(&response),
+                                        status: response.status().as_u16() as i32,
+                                        ..current
+                                    },
+                                    &UpdateSource::from_window_label(&window_label),
+                                )
+                                .unwrap();
+                        }
+                        continue;
+                    }
+                };
+
                 if let Message::Close(_) = message {
                     has_written_close = true;
                 }
 
-                app_handle
-                    .db()
-                    .upsert_websocket_event(
-                        &WebsocketEvent {
-                            connection_id: connection_id.clone(),
-                            request_id: request_id.clone(),
-                            workspace_id: workspace_id.clone(),
-                            is_server: true,
-                            message_type: match message {
-                                Message::Text(_) => WebsocketEventType::Text,
-                                Message::Binary(_) => WebsocketEventType::Binary,
-                                Message::Ping(_) => WebsocketEventType::Ping,
-                                Message::Pong(_) => WebsocketEventType::Pong,
-                                Message::Close(_) => WebsocketEventType::Close,
-                                // Raw frame will never happen during a read
-                                Message::Frame(_) => WebsocketEventType::Frame,
-                            },
-                            message: message.into_data().into(),
-                            ..Default::default()
-                        },
-                        &UpdateSource::from_window_label(&window_label),
-                    )
-                    .unwrap();
+                upsert_event(
+                    true,
+                    match message {
+                        Message::Text(_) => WebsocketEventType::Text,
+                        Message::Binary(_) => WebsocketEventType::Binary,
+                        Message::Ping(_) => WebsocketEventType::Ping,
+                        Message::Pong(_) => WebsocketEventType::Pong,
+                        Message::Close(_) => WebsocketEventType::Close,
+                        // Raw frame will never happen during a read
+                        Message::Frame(_) => WebsocketEventType::Frame,
+                    },
+                    message.into_data().into(),
+                );
             }
             info!("Websocket connection closed");
             if !has_written_close {
-                app_handle
-                    .db()
-                    .upsert_websocket_event(
-                        &WebsocketEvent {
-                            connection_id: connection_id.clone(),
-                            request_id: request_id.clone(),
-                            workspace_id: workspace_id.clone(),
-                            is_server: true,
-                            message_type: WebsocketEventType::Close,
-                            ..Default::default()
-                        },
-                        &UpdateSource::from_window_label(&window_label),
-                    )
-                    .unwrap();
+                upsert_event(true, WebsocketEventType::Close, Vec::new());
             }
             app_handle
                 .db()
@@ -436,7 +608,7 @@ pub async fn cmd_ws_connect<R: Runtime>(
     Ok(connection)
 }
 
-/// Resolve inherited authentication and headers for a websocket request
+/// Resolve inherited authentication, headers, and URL parameters for a websocket request
 fn resolve_websocket_request<R: Runtime>(
     window: &WebviewWindow<R>,
     request: &WebsocketRequest,
@@ -451,9 +623,33 @@ fn resolve_websocket_request<R: Runtime>(
     let headers = window.db().resolve_headers_for_websocket_request(request)?;
     new_request.headers = headers;
 
+    let url_parameters = window.db().resolve_query_params_for_websocket_request(request)?;
+    new_request.url_parameters = url_parameters;
+
     Ok((new_request, authentication_context_id))
 }
 
+/// Arguments for a Socket.IO event. The message is sent as a single JSON argument, or as a string
+/// when it isn't valid JSON, and an empty message sends the event without arguments.
+fn socket_io_args(message: &str) -> Vec<serde_json::Value> {
+    let message = maybe_strip_json_comments(message);
+    match message.trim() {
+        "" => Vec::new(),
+        m => vec![serde_json::from_str(m).unwrap_or_else(|_| m.into())],
+    }
+}
+
+fn response_headers(response: &Response) -> Vec<HttpResponseHeader> {
+    response
+        .headers()
+        .into_iter()
+        .map(|(name, value)| HttpResponseHeader {
+            name: name.to_string(),
+            value: value.to_str().unwrap().to_string(),
+        })
+        .collect()
+}
+
 /// Convert WS URL to HTTP URL for cookie filtering
 /// WebSocket upgrade requests are HTTP requests initially, so HttpOnly cookies should apply
 fn convert_ws_url_to_http(ws_url: &Url) -> Url {
@@ -4,8 +4,7 @@ use crate::error::Result;
 use crate::traits::UpsertModelInfo;
 use crate::update_source::UpdateSource;
 use sea_query::{
-    Asterisk, Expr, Func, IntoColumnRef, IntoIden, OnConflict, Query, SimpleExpr,
-    SqliteQueryBuilder,
+    Asterisk, Expr, IntoColumnRef, IntoIden, OnConflict, Query, SimpleExpr, SqliteQueryBuilder,
 };
 use sea_query_rusqlite::RusqliteBinder;
 use std::fmt::Debug;
@@ -124,6 +123,17 @@ impl<'a> DbContext<'a> {
         let id_val = model.get_id();
         let other_values = model.clone().insert_values(source)?;
 
+        // Checked up front because `last_insert_rowid()` is shared by every table, so comparing it
+        // against the row's rowid can mistake an update for an insert
+        let exists = !id_val.is_empty() && {
+            let (sql, params) = Query::select()
+                .expr(Expr::val(1))
+                .from(M::table_name())
+                .cond_where(Expr::col(id_iden.clone()).eq(id_val.as_str()))
+                .build_rusqlite(SqliteQueryBuilder);
+            self.conn.resolve().prepare(sql.as_str())?.exists(&*params.as_params())?
+        };
+
         let mut column_vec = vec![id_iden.clone()];
         let mut value_vec =
             vec![if id_val.is_empty() { M::generate_id().into() } else { id_val.into() }];
@@ -141,23 +151,13 @@ impl<'a> DbContext<'a> {
             .columns(column_vec)
             .values_panic(value_vec)
             .on_conflict(on_conflict)
-            .returning(Query::returning().exprs(vec![
-                Expr::col(Asterisk),
-                Expr::expr(Func::cust("last_insert_rowid")),
-                Expr::col("rowid"),
-            ]))
+            .returning_all()
             .build_rusqlite(SqliteQueryBuilder);
 
         let mut stmt = self.conn.resolve().prepare(sql.as_str())?;
-        let (m, created): (M, bool) = stmt.query_row(&*params.as_params(), |row| {
-            M::from_row(row).and_then(|m| {
-                let rowid: i64 = row.get("rowid")?;
-                let last_rowid: i64 = row.get("last_insert_rowid()")?;
-                Ok((m, rowid == last_rowid))
-            })
-        })?;
-
-        Ok((m, created))
+        let m = stmt.query_row(&*params.as_params(), M::from_row)?;
+
+        Ok((m, !exists))
     }
 
     /// Delete a model by its ID. Returns the number of rows deleted.
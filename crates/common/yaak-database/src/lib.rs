@@ -1,6 +1,7 @@
 pub mod connection_or_tx;
 pub mod db_context;
 pub mod error;
+pub mod maintenance;
 pub mod migrate;
 pub mod traits;
 pub mod update_source;
@@ -10,6 +11,7 @@ pub mod util;
 pub use connection_or_tx::ConnectionOrTx;
 pub use db_context::DbContext;
 pub use error::{Error, Result};
+pub use maintenance::{DbStats, TableStats, WorkspaceStats};
 pub use migrate::run_migrations;
 pub use traits::{UpsertModelInfo, upsert_date};
 pub use update_source::{ModelChangeEvent, UpdateSource};
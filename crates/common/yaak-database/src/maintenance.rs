@@ -0,0 +1,158 @@
+use crate::db_context::DbContext;
+use crate::error::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DbStats {
+    /// Size of the database file
+    pub total_bytes: u64,
+    /// Space held by free pages, which `vacuum()` gives back to the filesystem
+    pub free_bytes: u64,
+    /// Tables, largest first
+    pub tables: Vec<TableStats>,
+    /// Workspaces, largest first
+    pub workspaces: Vec<WorkspaceStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TableStats {
+    pub name: String,
+    pub rows: u64,
+    /// Space on disk, including the table's indexes
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceStats {
+    pub workspace_id: String,
+    /// Rows across every table with a `workspace_id` column
+    pub rows: u64,
+    /// Size of the data in those rows. Smaller than the space on disk, which also holds indexes
+    /// and page overhead.
+    pub bytes: u64,
+}
+
+impl<'a> DbContext<'a> {
+    /// Report how much space each table and workspace takes up
+    pub fn db_stats(&self) -> Result<DbStats> {
+        let conn = self.conn().resolve();
+        let page_size: u64 = conn.query_row("PRAGMA page_size", [], |r| r.get(0))?;
+        let page_count: u64 = conn.query_row("PRAGMA page_count", [], |r| r.get(0))?;
+        let free_pages: u64 = conn.query_row("PRAGMA freelist_count", [], |r| r.get(0))?;
+
+        // Virtual tables (rootpage 0) have no storage of their own. Their shadow tables are
+        // listed separately.
+        let mut stmt = conn.prepare(
+            r#"
+                SELECT name FROM sqlite_schema
+                WHERE type = 'table' AND rootpage > 0 AND name NOT LIKE 'sqlite_%'
+                ORDER BY name
+            "#,
+        )?;
+        let table_names = stmt
+            .query_map([], |r| r.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        // Sizes are per b-tree, so indexes are added to the table they belong to
+        let mut stmt = conn.prepare(
+            r#"
+                SELECT s.tbl_name, SUM(d.pgsize)
+                FROM dbstat d
+                JOIN sqlite_schema s ON s.name = d.name
+                GROUP BY s.tbl_name
+            "#,
+        )?;
+        let table_bytes = stmt
+            .query_map([], |r| Ok((r.get::<_, String>(0)?, r.get::<_, u64>(1)?)))?
+            .collect::<std::result::Result<HashMap<_, _>, rusqlite::Error>>()?;
+
+        let mut tables = Vec::new();
+        let mut workspaces: HashMap<String, WorkspaceStats> = HashMap::new();
+        for name in table_names {
+            let table = quote_ident(&name);
+            let rows: u64 =
+                conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |r| r.get(0))?;
+            let bytes = table_bytes.get(&name).copied().unwrap_or_default();
+            tables.push(TableStats { name: name.clone(), rows, bytes });
+
+            let columns = self.table_columns(&name)?;
+            if !columns.iter().any(|c| c == "workspace_id") {
+                continue;
+            }
+            let row_size = columns
+                .iter()
+                .map(|c| format!("COALESCE(LENGTH(CAST({} AS BLOB)), 0)", quote_ident(c)))
+                .collect::<Vec<_>>()
+                .join(" + ");
+            let mut stmt = conn.prepare(&format!(
+                "SELECT workspace_id, COUNT(*), SUM({row_size}) FROM {table} GROUP BY workspace_id"
+            ))?;
+            let rows = stmt.query_map([], |r| {
+                Ok((r.get::<_, Option<String>>(0)?, r.get::<_, u64>(1)?, r.get::<_, u64>(2)?))
+            })?;
+            for row in rows {
+                let (workspace_id, rows, bytes) = row?;
+                let Some(workspace_id) = workspace_id.filter(|id| !id.is_empty()) else {
+                    continue;
+                };
+                let stats = workspaces.entry(workspace_id.clone()).or_insert(WorkspaceStats {
+                    workspace_id,
+                    rows: 0,
+                    bytes: 0,
+                });
+                stats.rows += rows;
+                stats.bytes += bytes;
+            }
+        }
+
+        tables.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.name.cmp(&b.name)));
+        let mut workspaces: Vec<WorkspaceStats> = workspaces.into_values().collect();
+        workspaces.sort_by(|a, b| {
+            b.bytes.cmp(&a.bytes).then_with(|| a.workspace_id.cmp(&b.workspace_id))
+        });
+
+        Ok(DbStats {
+            total_bytes: page_count * page_size,
+            free_bytes: free_pages * page_size,
+            tables,
+            workspaces,
+        })
+    }
+
+    /// Rebuild the database file to give free space back to the filesystem, returning the number
+    /// of bytes reclaimed. Can't run inside a transaction.
+    pub fn vacuum(&self) -> Result<u64> {
+        let conn = self.conn().resolve();
+        let size = |sql: &str| conn.query_row(sql, [], |r| r.get::<_, u64>(0));
+        let before = size("PRAGMA page_count")? * size("PRAGMA page_size")?;
+        conn.execute_batch("VACUUM")?;
+        let after = size("PRAGMA page_count")? * size("PRAGMA page_size")?;
+        Ok(before.saturating_sub(after))
+    }
+
+    /// Check the database for corruption, returning the problems found. Empty when the database
+    /// is healthy.
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        let mut stmt = self.conn().prepare("PRAGMA integrity_check")?;
+        let messages = stmt
+            .query_map([], |r| r.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+        Ok(messages.into_iter().filter(|m| m != "ok").collect())
+    }
+
+    fn table_columns(&self, table: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn().prepare("SELECT name FROM pragma_table_info(?1)")?;
+        let columns = stmt
+            .query_map([table], |r| r.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+        Ok(columns)
+    }
+}
+
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
@@ -4,4 +4,5 @@ pub mod encryption;
 pub mod error;
 pub mod manager;
 mod master_key;
+pub mod secrets;
 mod workspace_key;
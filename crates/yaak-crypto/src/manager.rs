@@ -3,13 +3,15 @@ use crate::error::Error::{
 };
 use crate::error::{Error, Result};
 use crate::master_key::MasterKey;
+use crate::secrets::{store_authentication_secrets, KeychainSecretStore, SecretStore};
 use crate::workspace_key::WorkspaceKey;
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use log::{info, warn};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::{Arc, Mutex};
-use yaak_models::models::{EncryptedKey, Workspace, WorkspaceMeta};
+use yaak_models::models::{EncryptedKey, Environment, UpsertModelInfo, Workspace, WorkspaceMeta};
 use yaak_models::query_manager::QueryManager;
 use yaak_models::util::{generate_id_of_length, UpdateSource};
 
@@ -20,19 +22,32 @@ pub struct EncryptionManager {
     cached_master_key: Arc<Mutex<Option<MasterKey>>>,
     cached_workspace_keys: Arc<Mutex<HashMap<String, WorkspaceKey>>>,
     query_manager: QueryManager,
+    secrets: Arc<dyn SecretStore>,
     app_id: String,
 }
 
 impl EncryptionManager {
     pub fn new(query_manager: QueryManager, app_id: impl Into<String>) -> Self {
+        let app_id = app_id.into();
         Self {
             cached_master_key: Default::default(),
             cached_workspace_keys: Default::default(),
             query_manager,
-            app_id: app_id.into(),
+            secrets: Arc::new(KeychainSecretStore::new(&app_id)),
+            app_id,
         }
     }
 
+    /// Use a different store for secrets than the OS keychain
+    pub fn with_secret_store(self, secrets: Arc<dyn SecretStore>) -> Self {
+        Self { secrets, ..self }
+    }
+
+    /// Where secrets that shouldn't be written to the database are kept
+    pub fn secrets(&self) -> &dyn SecretStore {
+        self.secrets.as_ref()
+    }
+
     pub fn encrypt(&self, workspace_id: &str, data: &[u8]) -> Result<Vec<u8>> {
         let workspace_secret = self.get_workspace_key(workspace_id)?;
         workspace_secret.encrypt(data)
@@ -43,6 +58,34 @@ impl EncryptionManager {
         workspace_secret.decrypt(data)
     }
 
+    /// Encrypt the values of an environment's secret variables, skipping ones that are empty or
+    /// already encrypted
+    pub fn encrypt_secret_variables(&self, environment: &mut Environment) -> Result<()> {
+        for variable in environment.variables.iter_mut() {
+            if !variable.secret || variable.value.is_empty() || variable.value.starts_with("YENC_")
+            {
+                continue;
+            }
+            let encrypted = self.encrypt(&environment.workspace_id, variable.value.as_bytes())?;
+            variable.value = format!("YENC_{}", BASE64_STANDARD.encode(encrypted));
+        }
+        Ok(())
+    }
+
+    /// Move secret authentication values, like a client secret or password, into the secret
+    /// store so they aren't written to the database. New models are given an ID first, since
+    /// the stored secrets are keyed by it.
+    pub fn store_authentication_secrets<M: UpsertModelInfo>(
+        &self,
+        model_id: &mut String,
+        authentication: &mut BTreeMap<String, Value>,
+    ) -> Result<()> {
+        if model_id.is_empty() {
+            *model_id = M::generate_id();
+        }
+        store_authentication_secrets(self.secrets(), model_id, authentication)
+    }
+
     pub fn reveal_workspace_key(&self, workspace_id: &str) -> Result<String> {
         let key = self.get_workspace_key(workspace_id)?;
         key.to_human()
@@ -0,0 +1,202 @@
+//! Storage for secrets that shouldn't be written to the database file, like OAuth 2.0 tokens.
+
+use crate::error::Result;
+use keyring::{Entry, Error};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+pub trait SecretStore: Debug + Send + Sync {
+    fn get_secret(&self, key: &str) -> Result<Option<String>>;
+    fn set_secret(&self, key: &str, value: &str) -> Result<()>;
+    /// Remove a secret, doing nothing if it doesn't exist
+    fn delete_secret(&self, key: &str) -> Result<()>;
+    /// Template tag that reads a secret back at render time, or `None` if the store doesn't
+    /// outlive the process
+    fn template_reference(&self, _key: &str) -> Option<String> {
+        None
+    }
+}
+
+/// Authentication values that are moved to the secret store, like an OAuth 2.0 client secret
+const AUTHENTICATION_SECRET_KEYS: [&str; 9] = [
+    "clientAssertionSecret",
+    "clientSecret",
+    "consumerSecret",
+    "password",
+    "secret",
+    "secretAccessKey",
+    "sessionToken",
+    "token",
+    "tokenSecret",
+];
+
+/// Move secret values out of a model's authentication into the store, leaving a template tag
+/// that reads them back when the request is rendered. Values that are empty or already templates
+/// are left alone, as is everything when the store can't be referenced from a template.
+pub fn store_authentication_secrets(
+    store: &dyn SecretStore,
+    model_id: &str,
+    authentication: &mut BTreeMap<String, Value>,
+) -> Result<()> {
+    for name in AUTHENTICATION_SECRET_KEYS {
+        let Some(Value::String(value)) = authentication.get(name) else {
+            continue;
+        };
+        if value.is_empty() || value.contains("${[") {
+            continue;
+        }
+        let key = format!("auth.{model_id}.{name}");
+        let Some(reference) = store.template_reference(&key) else {
+            return Ok(());
+        };
+        store.set_secret(&key, value)?;
+        authentication.insert(name.to_string(), Value::String(reference));
+    }
+    Ok(())
+}
+
+/// Keeps secrets in the OS keychain, which is the Keychain on macOS, Credential Manager on
+/// Windows, and the Secret Service (libsecret) on Linux
+#[derive(Debug, Clone)]
+pub struct KeychainSecretStore {
+    service: String,
+}
+
+impl KeychainSecretStore {
+    pub fn new(app_id: &str) -> Self {
+        Self { service: format!("{app_id}.Secrets") }
+    }
+
+    /// Whether the OS keychain can be reached, which usually isn't the case on headless machines
+    pub fn is_available(&self) -> bool {
+        match Entry::new(&self.service, "availability").and_then(|e| e.get_password()) {
+            Ok(_) | Err(Error::NoEntry) => true,
+            Err(_) => false,
+        }
+    }
+}
+
+impl SecretStore for KeychainSecretStore {
+    fn get_secret(&self, key: &str) -> Result<Option<String>> {
+        match Entry::new(&self.service, key)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set_secret(&self, key: &str, value: &str) -> Result<()> {
+        Ok(Entry::new(&self.service, key)?.set_password(value)?)
+    }
+
+    fn delete_secret(&self, key: &str) -> Result<()> {
+        match Entry::new(&self.service, key)?.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn template_reference(&self, key: &str) -> Option<String> {
+        Some(format!("${{[ keychain(service='{}', account='{key}') ]}}", self.service))
+    }
+}
+
+/// Keeps secrets in memory, for tests and anywhere a keychain isn't available
+#[derive(Debug, Default)]
+pub struct MemorySecretStore {
+    secrets: Mutex<HashMap<String, String>>,
+}
+
+impl SecretStore for MemorySecretStore {
+    fn get_secret(&self, key: &str) -> Result<Option<String>> {
+        Ok(self.secrets.lock().unwrap().get(key).cloned())
+    }
+
+    fn set_secret(&self, key: &str, value: &str) -> Result<()> {
+        self.secrets.lock().unwrap().insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete_secret(&self, key: &str) -> Result<()> {
+        self.secrets.lock().unwrap().remove(key);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stores_secrets_in_memory() -> Result<()> {
+        let store = MemorySecretStore::default();
+        assert_eq!(store.get_secret("token")?, None);
+
+        store.set_secret("token", "abc")?;
+        assert_eq!(store.get_secret("token")?, Some("abc".to_string()));
+
+        store.delete_secret("token")?;
+        store.delete_secret("token")?;
+        assert_eq!(store.get_secret("token")?, None);
+        Ok(())
+    }
+
+    /// Memory store that can be referenced from templates, like the keychain can
+    #[derive(Debug, Default)]
+    struct ReferencedSecretStore(MemorySecretStore);
+
+    impl SecretStore for ReferencedSecretStore {
+        fn get_secret(&self, key: &str) -> Result<Option<String>> {
+            self.0.get_secret(key)
+        }
+
+        fn set_secret(&self, key: &str, value: &str) -> Result<()> {
+            self.0.set_secret(key, value)
+        }
+
+        fn delete_secret(&self, key: &str) -> Result<()> {
+            self.0.delete_secret(key)
+        }
+
+        fn template_reference(&self, key: &str) -> Option<String> {
+            Some(format!("${{[ secret('{key}') ]}}"))
+        }
+    }
+
+    fn authentication() -> BTreeMap<String, Value> {
+        BTreeMap::from([
+            ("clientId".to_string(), Value::from("client")),
+            ("clientSecret".to_string(), Value::from("shh")),
+            ("password".to_string(), Value::from("${[ PASSWORD ]}")),
+            ("token".to_string(), Value::from("")),
+        ])
+    }
+
+    #[test]
+    fn moves_authentication_secrets_to_store() -> Result<()> {
+        let store = ReferencedSecretStore::default();
+        let mut auth = authentication();
+        store_authentication_secrets(&store, "rq_1", &mut auth)?;
+
+        assert_eq!(auth["clientId"], "client");
+        assert_eq!(auth["clientSecret"], "${[ secret('auth.rq_1.clientSecret') ]}");
+        assert_eq!(store.get_secret("auth.rq_1.clientSecret")?, Some("shh".to_string()));
+        assert_eq!(auth["password"], "${[ PASSWORD ]}");
+        assert_eq!(store.get_secret("auth.rq_1.password")?, None);
+        assert_eq!(auth["token"], "");
+        Ok(())
+    }
+
+    #[test]
+    fn keeps_authentication_secrets_without_template_reference() -> Result<()> {
+        let store = MemorySecretStore::default();
+        let mut auth = authentication();
+        store_authentication_secrets(&store, "rq_1", &mut auth)?;
+
+        assert_eq!(auth, authentication());
+        assert_eq!(store.get_secret("auth.rq_1.clientSecret")?, None);
+        Ok(())
+    }
+}
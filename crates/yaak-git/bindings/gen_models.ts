@@ -1,5 +1,45 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * A check run against every response a request receives
+ */
+export type Assertion = {
+  enabled?: boolean;
+  kind: AssertionKind;
+  /**
+   * JSONPath expression for JSONPath assertions, or the header name for header assertions
+   */
+  path: string;
+  /**
+   * Expected status or value, a regular expression for matches, or a latency in milliseconds
+   */
+  value: string;
+  id?: string;
+};
+
+export type AssertionKind =
+  | "status_equals"
+  | "json_path_equals"
+  | "json_path_matches"
+  | "header_exists"
+  | "latency_under";
+
+/**
+ * A CA certificate trusted in addition to the platform's certificate store, either read from a
+ * PEM file or pasted inline
+ */
+export type CaCertificate = { file: string | null; pem: string | null; enabled?: boolean };
+
+export type ClientCertificate = {
+  host: string;
+  port: number | null;
+  crtFile: string | null;
+  keyFile: string | null;
+  pfxFile: string | null;
+  passphrase: string | null;
+  enabled?: boolean;
+};
+
 export type DnsOverride = {
   hostname: string;
   ipv4: Array<string>;
@@ -24,9 +64,23 @@ export type Environment = {
   variables: Array<EnvironmentVariable>;
   color: string | null;
   sortPriority: number;
+  /**
+   * Cookie jar to use while this environment is active, instead of the selected one.
+   */
+  cookieJarId: string | null;
 };
 
-export type EnvironmentVariable = { enabled?: boolean; name: string; value: string; id?: string };
+export type EnvironmentVariable = {
+  enabled?: boolean;
+  name: string;
+  value: string;
+  id?: string;
+  /**
+   * Whether the value is encrypted with the workspace key before it's stored, and left out of
+   * exports
+   */
+  secret?: boolean;
+};
 
 export type Folder = {
   model: "folder";
@@ -38,16 +92,49 @@ export type Folder = {
   authentication: Record<string, any>;
   authenticationType: string | null;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   headers: Array<HttpRequestHeader>;
+  /**
+   * Default URL parameters inherited by requests in this folder
+   */
+  urlParameters: Array<HttpUrlParameter>;
   name: string;
   sortPriority: number;
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
   settingFollowRedirects: InheritedBoolSetting;
+  settingMaxRedirects: InheritedIntSetting;
+  settingRedirectResendBody: InheritedBoolSetting;
+  settingRedirectStripAuthorization: InheritedBoolSetting;
+  settingDecompressResponse: InheritedBoolSetting;
   settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingHttpVersion: InheritedHttpVersionSetting;
+  settingRetryPolicy: InheritedRetryPolicySetting;
+  settingSshTunnel: InheritedSshTunnelSetting;
+  settingCaCertificates: InheritedCaCertificatesSetting;
+  settingClientCertificate: InheritedClientCertificateSetting;
+  /**
+   * Set when the folder has been moved to the trash. Trashed folders are hidden from
+   * listings until they are restored or the trash is emptied.
+   */
+  deletedAt: string | null;
 };
 
+export type GrpcCompression = "gzip" | "zstd";
+
+export type GrpcMessageTemplate = { name: string; message: string; id?: string };
+
+/**
+ * Wire protocol for gRPC calls
+ */
+export type GrpcProtocol = "grpc" | "grpc_web" | "connect_json" | "connect_proto";
+
 export type GrpcRequest = {
   model: "grpc_request";
   id: string;
@@ -58,7 +145,15 @@ export type GrpcRequest = {
   authenticationType: string | null;
   authentication: Record<string, any>;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   message: string;
+  /**
+   * Named messages that can be replayed in order during a streaming session
+   */
+  messageTemplates: Array<GrpcMessageTemplate>;
   metadata: Array<HttpRequestHeader>;
   method: string | null;
   name: string;
@@ -68,7 +163,32 @@ export type GrpcRequest = {
    * Server URL (http for plaintext or https for secure)
    */
   url: string;
+  /**
+   * Wire protocol used to call the server
+   */
+  protocol: GrpcProtocol;
   settingValidateCertificates: InheritedBoolSetting;
+  settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingCaCertificates: InheritedCaCertificatesSetting;
+  settingClientCertificate: InheritedClientCertificateSetting;
+  /**
+   * Compression applied to messages sent to the server
+   */
+  sendCompression: GrpcCompression | null;
+  /**
+   * Compression the server may apply to the messages it sends back
+   */
+  acceptCompression: Array<GrpcCompression>;
+  /**
+   * Steps that run before the request is sent
+   */
+  preRequestSteps: Array<PreRequestStep>;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type HttpRequest = {
@@ -83,6 +203,10 @@ export type HttpRequest = {
   body: Record<string, any>;
   bodyType: string | null;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   headers: Array<HttpRequestHeader>;
   method: string;
   name: string;
@@ -92,11 +216,44 @@ export type HttpRequest = {
    * URL parameters used for both path placeholders (`:id`) and query string entries.
    */
   urlParameters: Array<HttpUrlParameter>;
+  /**
+   * Path of a Unix domain socket to send the request over. The URL is still used for the
+   * Host header and path.
+   */
+  unixSocket: string | null;
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
   settingFollowRedirects: InheritedBoolSetting;
+  settingMaxRedirects: InheritedIntSetting;
+  settingRedirectResendBody: InheritedBoolSetting;
+  settingRedirectStripAuthorization: InheritedBoolSetting;
+  settingDecompressResponse: InheritedBoolSetting;
   settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingHttpVersion: InheritedHttpVersionSetting;
+  settingRetryPolicy: InheritedRetryPolicySetting;
+  /**
+   * JSONPath or XPath expression last used to filter this request's responses
+   */
+  responseFilter: string | null;
+  /**
+   * Values copied from each response into variables after the request is sent
+   */
+  responseExtractions: Array<ResponseExtraction>;
+  /**
+   * Steps that run before the request is sent
+   */
+  preRequestSteps: Array<PreRequestStep>;
+  /**
+   * Checks run against each response after the request is sent
+   */
+  assertions: Array<Assertion>;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type HttpRequestHeader = { enabled?: boolean; name: string; value: string; id?: string };
@@ -112,10 +269,207 @@ export type HttpUrlParameter = {
   id?: string;
 };
 
+/**
+ * HTTP version requests are sent with
+ */
+export type HttpVersion = "auto" | "http1" | "http2" | "http3";
+
 export type InheritedBoolSetting = { enabled?: boolean; value: boolean };
 
+export type InheritedCaCertificatesSetting = {
+  enabled?: boolean;
+  /**
+   * CA certificates trusted in place of the workspace's
+   */
+  value: Array<CaCertificate>;
+};
+
+export type InheritedClientCertificateSetting = {
+  enabled?: boolean;
+  /**
+   * Certificate presented to every server regardless of its host, or `None` to match one by
+   * host from the app settings
+   */
+  value: ClientCertificate | null;
+};
+
+export type InheritedHttpVersionSetting = { enabled?: boolean; value: HttpVersion };
+
 export type InheritedIntSetting = { enabled?: boolean; value: number };
 
+export type InheritedRetryPolicySetting = { enabled?: boolean; value: RetryPolicy };
+
+export type InheritedSshTunnelSetting = {
+  enabled?: boolean;
+  /**
+   * Tunnel for requests in the folder, or `None` to connect directly
+   */
+  value: SshTunnel | null;
+};
+
+/**
+ * Work to do before a request is sent, like sending a login request first or running a plugin
+ * action. Steps run in order and a failing step stops the send.
+ */
+export type PreRequestStep = {
+  enabled?: boolean;
+  kind: PreRequestStepKind;
+  /**
+   * Only run the step when this variable is empty or undefined. Runs every time when blank.
+   */
+  ifEmpty: string;
+  /**
+   * HTTP request to send for send-request steps
+   */
+  requestId: string;
+  /**
+   * Chained value to write for set-variable steps
+   */
+  name: string;
+  /**
+   * Template rendered into the variable for set-variable steps
+   */
+  value: string;
+  /**
+   * Plugin name and action label for plugin-action steps
+   */
+  plugin: string;
+  action: string;
+  id?: string;
+};
+
+export type PreRequestStepKind = "send_request" | "set_variable" | "plugin_action";
+
+export type ProxySetting =
+  | {
+      type: "enabled";
+      http: string;
+      https: string;
+      auth: ProxySettingAuth | null;
+      bypass: string;
+      disabled: boolean;
+    }
+  | { type: "disabled" };
+
+export type ProxySettingAuth = { user: string; password: string };
+
+/**
+ * A rule that copies a value out of each response a request receives into a variable, so
+ * one request (like a login) can feed values to the ones that follow it
+ */
+export type ResponseExtraction = {
+  enabled?: boolean;
+  /**
+   * Name of the variable to write
+   */
+  name: string;
+  source: ResponseExtractionSource;
+  /**
+   * JSONPath or XPath expression for body sources, or the header name for header sources
+   */
+  path: string;
+  /**
+   * Environment targets write to the active environment, or the base environment if none is
+   * active. Chained targets write to the workspace's chained values, which every request can
+   * use but which are overridden by environment variables with the same name.
+   */
+  target: ResponseExtractionTarget;
+  id?: string;
+};
+
+export type ResponseExtractionSource = "body" | "header" | "status";
+
+export type ResponseExtractionTarget = "environment" | "chained";
+
+/**
+ * When and how often a failed request is sent again
+ */
+export type RetryPolicy = {
+  /**
+   * Total number of attempts, including the first. 1 disables retries.
+   */
+  maxAttempts: number;
+  /**
+   * Retry when the server responds with a 5xx status
+   */
+  retryOnServerError: boolean;
+  /**
+   * Retry when a connection to the server can't be established
+   */
+  retryOnConnectionError: boolean;
+  /**
+   * Retry when a connect, read, or total timeout elapses
+   */
+  retryOnTimeout: boolean;
+  /**
+   * Milliseconds to wait before the first retry, doubled for every retry after it
+   */
+  initialDelay: number;
+  /**
+   * Upper bound in milliseconds for the delay between attempts
+   */
+  maxDelay: number;
+  /**
+   * Wait a random fraction of each delay, so clients retrying together spread out
+   */
+  jitter: boolean;
+};
+
+/**
+ * How messages are sent to a Socket.IO server
+ */
+export type SocketIoSettings = {
+  /**
+   * Namespace to connect to, like `/chat`
+   */
+  namespace: string;
+  /**
+   * Name of the event the message is emitted as
+   */
+  event: string;
+  /**
+   * Ask the server to acknowledge each emitted event
+   */
+  ack: boolean;
+  /**
+   * JSON payload sent when connecting to the namespace, often used for auth tokens
+   */
+  auth: string;
+};
+
+/**
+ * SSH connection that requests are tunneled through, like a bastion in front of private APIs
+ */
+export type SshTunnel = {
+  /**
+   * SSH server to connect to, like `bastion.example.com`
+   */
+  host: string;
+  /**
+   * SSH port, or 22 when unset
+   */
+  port: number | null;
+  username: string;
+  /**
+   * Password to authenticate with when no private key file is set
+   */
+  password: string;
+  /**
+   * Path of a private key file to authenticate with, like `~/.ssh/id_ed25519`
+   */
+  privateKeyFile: string;
+  /**
+   * Passphrase of an encrypted private key
+   */
+  passphrase: string;
+  /**
+   * Comma-separated hosts reached through the tunnel, optionally with a port (eg.
+   * `api.internal, 10.0.0.5:8443`). Every request is tunneled when empty.
+   */
+  destination: string;
+  disabled: boolean;
+};
+
 export type SyncModel =
   | ({ type: "workspace" } & Workspace)
   | ({ type: "environment" } & Environment)
@@ -124,6 +478,41 @@ export type SyncModel =
   | ({ type: "grpc_request" } & GrpcRequest)
   | ({ type: "websocket_request" } & WebsocketRequest);
 
+/**
+ * Kind of frame a WebSocket message is sent as
+ */
+export type WebsocketMessageFormat = "text" | "hex" | "base64" | "file";
+
+/**
+ * Protocol spoken over a WebSocket connection
+ */
+export type WebsocketProtocol = "websocket" | "socket_io";
+
+/**
+ * How a dropped WebSocket connection is re-established. Attempts are spaced by a delay that
+ * doubles after each one, up to `max_delay_ms`.
+ */
+export type WebsocketReconnectPolicy = {
+  enabled: boolean;
+  /**
+   * Attempts to make before giving up, or 0 to keep trying
+   */
+  maxAttempts: number;
+  /**
+   * Delay before the first attempt
+   */
+  initialDelayMs: number;
+  /**
+   * Longest delay between attempts
+   */
+  maxDelayMs: number;
+  /**
+   * Message sent after connecting and again after every reconnect, such as a subscription or
+   * login message. Nothing is sent when empty.
+   */
+  handshakeMessage: string;
+};
+
 export type WebsocketRequest = {
   model: "websocket_request";
   id: string;
@@ -136,6 +525,14 @@ export type WebsocketRequest = {
   description: string;
   headers: Array<HttpRequestHeader>;
   message: string;
+  /**
+   * How the message is sent, as text or as the bytes it describes
+   */
+  messageFormat: WebsocketMessageFormat;
+  /**
+   * File whose contents are sent as a binary frame when `message_format` is `file`
+   */
+  messageFile: string | null;
   name: string;
   sortPriority: number;
   url: string;
@@ -146,6 +543,22 @@ export type WebsocketRequest = {
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
+  /**
+   * Re-establishes the connection when it drops without being closed from the app
+   */
+  reconnect: WebsocketReconnectPolicy;
+  /**
+   * Protocol spoken over the connection
+   */
+  protocol: WebsocketProtocol;
+  /**
+   * Used when `protocol` is `socket_io`
+   */
+  socketIo: SocketIoSettings;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type Workspace = {
@@ -157,12 +570,65 @@ export type Workspace = {
   authenticationType: string | null;
   description: string;
   headers: Array<HttpRequestHeader>;
+  /**
+   * Default URL parameters inherited by every request in the workspace
+   */
+  urlParameters: Array<HttpUrlParameter>;
   name: string;
   encryptionKeyChallenge: string | null;
   settingValidateCertificates: boolean;
   settingFollowRedirects: boolean;
+  /**
+   * Stop following redirects after this many hops and fail the request
+   */
+  settingMaxRedirects: number;
+  /**
+   * Send the request body again when a 307 or 308 redirect preserves the method
+   */
+  settingRedirectResendBody: boolean;
+  /**
+   * Remove the Authorization header when a redirect leads to a different origin
+   */
+  settingRedirectStripAuthorization: boolean;
+  /**
+   * Decode compressed response bodies, or keep them exactly as the server sent them
+   */
+  settingDecompressResponse: boolean;
   settingRequestTimeout: number;
+  /**
+   * Milliseconds to wait for a connection to be established, or 0 to wait indefinitely
+   */
+  settingConnectTimeout: number;
+  /**
+   * Milliseconds to wait for data from the server between reads, or 0 to wait indefinitely
+   */
+  settingReadTimeout: number;
+  settingHttpVersion: HttpVersion;
   settingDnsOverrides: Array<DnsOverride>;
+  /**
+   * CA certificates trusted when validating server certificates, for internal CAs
+   */
+  settingCaCertificates: Array<CaCertificate>;
   settingSendCookies: boolean;
   settingStoreCookies: boolean;
+  /**
+   * Proxy used for requests in this workspace, overriding the global proxy setting when set
+   */
+  settingProxy: ProxySetting | null;
+  /**
+   * SSH tunnel used for requests in this workspace, unless a folder overrides it
+   */
+  settingSshTunnel: SshTunnel | null;
+  /**
+   * Keep at most this many responses per request, or the default history size when unset
+   */
+  settingMaxResponsesPerRequest: number | null;
+  /**
+   * Delete responses older than this many days, or keep them regardless of age when unset
+   */
+  settingResponseRetentionDays: number | null;
+  /**
+   * Add response bodies to the search index so workspace search can find them
+   */
+  settingIndexResponseBodies: boolean;
 };
@@ -38,7 +38,7 @@ pub use repository::{GitRepositoryPaths, git_path_is_ignored, git_repository_pat
 pub use reset::git_reset_changes;
 pub use restore::{git_restore, git_restore_file_from_commit};
 pub use status::{
-    GitBranchInfo, GitStatusSummary, GitWorktreeStatus, git_branch_info, git_status,
+    GitBranchInfo, GitStatus, GitStatusSummary, GitWorktreeStatus, git_branch_info, git_status,
     git_worktree_status,
 };
 pub use unstage::git_unstage;
@@ -1,11 +1,12 @@
+use crate::dns::GrpcDnsOverrides;
 use crate::error::Error::GenericError;
 use crate::error::Result;
 use crate::manager::decorate_req;
+use crate::proxy::{GrpcProxy, ProxyConnector};
 use crate::transport::get_transport;
 use async_recursion::async_recursion;
 use hyper_rustls::HttpsConnector;
 use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
 use log::debug;
 use std::collections::BTreeMap;
 use tokio_stream::StreamExt;
@@ -20,9 +21,9 @@ use tonic_reflection::pb::v1::{
 };
 use tonic_reflection::pb::v1::{ExtensionRequest, FileDescriptorResponse};
 use tonic_reflection::pb::{v1, v1alpha};
-use yaak_tls::ClientCertificateConfig;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig};
 
-pub struct AutoReflectionClient<T = Client<HttpsConnector<HttpConnector>, BoxBody>> {
+pub struct AutoReflectionClient<T = Client<HttpsConnector<ProxyConnector>, BoxBody>> {
     use_v1alpha: bool,
     client_v1: v1::server_reflection_client::ServerReflectionClient<T>,
     client_v1alpha: v1alpha::server_reflection_client::ServerReflectionClient<T>,
@@ -33,13 +34,30 @@ impl AutoReflectionClient {
         uri: &Uri,
         validate_certificates: bool,
         client_cert: Option<ClientCertificateConfig>,
+        ca_certificates: &[CaCertificateConfig],
+        proxy: Option<GrpcProxy>,
+        dns_overrides: GrpcDnsOverrides,
     ) -> Result<Self> {
         let client_v1 = v1::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(validate_certificates, client_cert.clone())?,
+            get_transport(
+                validate_certificates,
+                client_cert.clone(),
+                ca_certificates,
+                proxy.clone(),
+                dns_overrides.clone(),
+                None,
+            )?,
             uri.clone(),
         );
         let client_v1alpha = v1alpha::server_reflection_client::ServerReflectionClient::with_origin(
-            get_transport(validate_certificates, client_cert.clone())?,
+            get_transport(
+                validate_certificates,
+                client_cert.clone(),
+                ca_certificates,
+                proxy.clone(),
+                dns_overrides.clone(),
+                None,
+            )?,
             uri.clone(),
         );
         Ok(AutoReflectionClient { use_v1alpha: false, client_v1, client_v1alpha })
@@ -0,0 +1,336 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::manager::{GrpcStreamError, decorate_req};
+use crate::reflection::method_desc_to_path;
+use crate::status::encode_status_details;
+use crate::transport::HttpClient;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_NO_PAD;
+use http_body_util::{BodyExt, Full};
+use prost::Message;
+use prost_reflect::{DynamicMessage, MethodDescriptor};
+use serde::Deserialize;
+use serde_json::Deserializer;
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tonic::body::BoxBody;
+use tonic::codegen::Bytes;
+use tonic::codegen::http::{self, HeaderValue, StatusCode, header};
+use tonic::metadata::MetadataMap;
+use tonic::transport::Uri;
+use tonic::{Code, Extensions, Request, Response, Status};
+
+/// Body of a failed Connect call
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ConnectError {
+    code: String,
+    message: String,
+    details: Vec<ConnectErrorDetail>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ConnectErrorDetail {
+    #[serde(rename = "type")]
+    type_name: String,
+    /// Base64 of the encoded detail message, without padding
+    value: String,
+}
+
+/// Make a unary call with the Connect protocol, which is a plain HTTP POST of the message encoded
+/// as JSON or binary protobuf. Failures are returned as a [`GrpcStreamError`] with the equivalent
+/// gRPC status, so they're reported like any other call.
+pub(crate) async fn connect_unary(
+    client: &HttpClient<BoxBody>,
+    json: bool,
+    uri: &Uri,
+    method: &MethodDescriptor,
+    message: DynamicMessage,
+    metadata: &BTreeMap<String, String>,
+    deadline: Option<Duration>,
+) -> Result<Response<DynamicMessage>> {
+    let mut metadata_req = Request::new(());
+    decorate_req(metadata, &mut metadata_req)?;
+
+    let (content_type, body) = match json {
+        true => ("application/json", serde_json::to_vec(&message)?),
+        false => ("application/proto", message.encode_to_vec()),
+    };
+
+    let url = format!("{}{}", uri.to_string().trim_end_matches('/'), method_desc_to_path(method));
+    let mut req = http::Request::post(url)
+        .body(tonic::body::boxed(Full::new(Bytes::from(body))))
+        .map_err(|e| GenericError(format!("Invalid Connect request: {e}")))?;
+    let headers = req.headers_mut();
+    headers.extend(metadata_req.metadata().clone().into_headers());
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert("connect-protocol-version", HeaderValue::from_static("1"));
+    if let Some(deadline) = deadline {
+        headers.insert("connect-timeout-ms", HeaderValue::from(deadline.as_millis() as u64));
+    }
+
+    let res =
+        client.request(req).await.map_err(|e| GenericError(format!("Failed to connect: {e}")))?;
+    let (parts, body) = res.into_parts();
+    let bytes = body
+        .collect()
+        .await
+        .map_err(|e| GenericError(format!("Failed to read response: {e}")))?
+        .to_bytes();
+    let response_metadata = MetadataMap::from_headers(parts.headers.clone());
+
+    if !parts.status.is_success() {
+        let error: ConnectError = serde_json::from_slice(&bytes).unwrap_or_default();
+        let code =
+            code_from_connect(&error.code).unwrap_or_else(|| code_from_http_status(parts.status));
+        let message = match error.message.is_empty() {
+            true => parts.status.to_string(),
+            false => error.message,
+        };
+        let details = error
+            .details
+            .into_iter()
+            .filter_map(|d| {
+                let value = STANDARD_NO_PAD.decode(d.value.trim_end_matches('=')).ok()?;
+                Some((d.type_name, value))
+            })
+            .collect::<Vec<_>>();
+        let details = match details.is_empty() {
+            true => Bytes::new(),
+            false => Bytes::from(encode_status_details(code, &message, details)),
+        };
+        let status = Status::with_details_and_metadata(code, message, details, response_metadata);
+        return Err(GrpcStreamError::from(status).into());
+    }
+
+    let response_json = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.starts_with("application/json"))
+        .unwrap_or(json);
+    let message = match response_json {
+        true => {
+            let mut deserializer = Deserializer::from_slice(&bytes);
+            let message = DynamicMessage::deserialize(method.output(), &mut deserializer)?;
+            deserializer.end()?;
+            message
+        }
+        false => DynamicMessage::decode(method.output(), bytes)?,
+    };
+
+    Ok(Response::from_parts(response_metadata, message, Extensions::default()))
+}
+
+fn code_from_connect(code: &str) -> Option<Code> {
+    Some(match code {
+        "canceled" => Code::Cancelled,
+        "unknown" => Code::Unknown,
+        "invalid_argument" => Code::InvalidArgument,
+        "deadline_exceeded" => Code::DeadlineExceeded,
+        "not_found" => Code::NotFound,
+        "already_exists" => Code::AlreadyExists,
+        "permission_denied" => Code::PermissionDenied,
+        "resource_exhausted" => Code::ResourceExhausted,
+        "failed_precondition" => Code::FailedPrecondition,
+        "aborted" => Code::Aborted,
+        "out_of_range" => Code::OutOfRange,
+        "unimplemented" => Code::Unimplemented,
+        "internal" => Code::Internal,
+        "unavailable" => Code::Unavailable,
+        "data_loss" => Code::DataLoss,
+        "unauthenticated" => Code::Unauthenticated,
+        _ => return None,
+    })
+}
+
+/// The code Connect clients infer when a failed response has no Connect error body, such as one
+/// from a proxy in front of the server
+fn code_from_http_status(status: StatusCode) -> Code {
+    match status.as_u16() {
+        400 => Code::Internal,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 => Code::Unimplemented,
+        429 | 502 | 503 | 504 => Code::Unavailable,
+        _ => Code::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dns::GrpcDnsOverrides;
+    use crate::transport::{GrpcChannel, WireProtocol, get_channel};
+    use prost_reflect::DescriptorPool;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, MethodDescriptorProto,
+        ServiceDescriptorProto,
+    };
+    use std::str::FromStr;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn hello_method() -> MethodDescriptor {
+        let mut pool = DescriptorPool::new();
+        pool.add_file_descriptor_proto(FileDescriptorProto {
+            name: Some("greeter.proto".to_string()),
+            package: Some("greeter".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Hello".to_string()),
+                field: vec![FieldDescriptorProto {
+                    name: Some("name".to_string()),
+                    json_name: Some("name".to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::String as i32),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            service: vec![ServiceDescriptorProto {
+                name: Some("Greeter".to_string()),
+                method: vec![MethodDescriptorProto {
+                    name: Some("Hello".to_string()),
+                    input_type: Some(".greeter.Hello".to_string()),
+                    output_type: Some(".greeter.Hello".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        pool.get_service_by_name("greeter.Greeter").unwrap().methods().next().unwrap()
+    }
+
+    /// Serve a single request with `response`, returning the raw request that was received
+    async fn serve_once(response: &'static str) -> (Uri, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = vec![0u8; 4096];
+            // Read until the whole body named by the request's content-length has arrived
+            loop {
+                let n = socket.read(&mut buf).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                let Some((head, body)) = text.split_once("\r\n\r\n") else {
+                    continue;
+                };
+                let length = head
+                    .lines()
+                    .find_map(|l| l.strip_prefix("content-length: "))
+                    .and_then(|l| l.parse::<usize>().ok())
+                    .unwrap_or_default();
+                if body.len() >= length {
+                    break;
+                }
+            }
+            socket.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).to_string()
+        });
+        (Uri::from_str(&format!("http://{addr}")).unwrap(), server)
+    }
+
+    fn client() -> HttpClient<BoxBody> {
+        let channel = get_channel(
+            false,
+            None,
+            &[],
+            None,
+            GrpcDnsOverrides::default(),
+            None,
+            WireProtocol::ConnectJson,
+        )
+        .unwrap();
+        match channel {
+            GrpcChannel::Connect { client, .. } => client,
+            _ => panic!("expected a Connect channel"),
+        }
+    }
+
+    fn hello(method: &MethodDescriptor, name: &str) -> DynamicMessage {
+        let value = serde_json::json!({ "name": name });
+        DynamicMessage::deserialize(method.input(), value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn sends_unary_json_calls() {
+        let (uri, server) = serve_once(
+            "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nx-served-by: test\r\n\
+             content-length: 16\r\n\r\n{\"name\":\"Yaak!\"}",
+        )
+        .await;
+        let method = hello_method();
+
+        let metadata = BTreeMap::from([("x-trace".to_string(), "abc".to_string())]);
+        let res = connect_unary(
+            &client(),
+            true,
+            &uri,
+            &method,
+            hello(&method, "Yaak"),
+            &metadata,
+            Some(Duration::from_secs(2)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(res.metadata().get("x-served-by").unwrap(), "test");
+        assert_eq!(res.into_inner(), hello(&method, "Yaak!"));
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("POST /greeter.Greeter/Hello HTTP/1.1\r\n"));
+        assert!(request.contains("content-type: application/json\r\n"));
+        assert!(request.contains("connect-protocol-version: 1\r\n"));
+        assert!(request.contains("connect-timeout-ms: 2000\r\n"));
+        assert!(request.contains("x-trace: abc\r\n"));
+        assert!(request.ends_with("{\"name\":\"Yaak\"}"));
+    }
+
+    #[tokio::test]
+    async fn maps_connect_errors_to_statuses() {
+        let (uri, _server) = serve_once(
+            "HTTP/1.1 400 Bad Request\r\ncontent-type: application/json\r\n\
+             content-length: 48\r\n\r\n{\"code\":\"invalid_argument\",\"message\":\"bad name\"}",
+        )
+        .await;
+        let method = hello_method();
+
+        let err = connect_unary(
+            &client(),
+            true,
+            &uri,
+            &method,
+            hello(&method, ""),
+            &BTreeMap::new(),
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        let crate::error::Error::GrpcStreamError(e) = err else {
+            panic!("expected a status error, got {err:?}");
+        };
+        let status = e.status.unwrap();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert_eq!(status.message(), "bad name");
+    }
+
+    #[test]
+    fn infers_codes_from_http_statuses() {
+        assert_eq!(code_from_http_status(StatusCode::UNAUTHORIZED), Code::Unauthenticated);
+        assert_eq!(code_from_http_status(StatusCode::NOT_FOUND), Code::Unimplemented);
+        assert_eq!(code_from_http_status(StatusCode::BAD_GATEWAY), Code::Unavailable);
+        assert_eq!(code_from_http_status(StatusCode::IM_A_TEAPOT), Code::Unknown);
+    }
+}
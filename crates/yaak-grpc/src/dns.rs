@@ -0,0 +1,99 @@
+//! DNS overrides for gRPC connections.
+//!
+//! Like curl's `--resolve`, an overridden hostname connects to fixed addresses instead of going
+//! through system DNS, while TLS and the `:authority` header still use the original hostname.
+
+use hyper_util::client::legacy::connect::dns::{GaiResolver, Name};
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Addresses to connect to for specific hostnames, bypassing system DNS
+#[derive(Clone, Debug, Default)]
+pub struct GrpcDnsOverrides(Arc<HashMap<String, Vec<IpAddr>>>);
+
+impl GrpcDnsOverrides {
+    pub fn new(overrides: impl IntoIterator<Item = (String, Vec<IpAddr>)>) -> Self {
+        Self(Arc::new(
+            overrides
+                .into_iter()
+                .filter(|(_, addresses)| !addresses.is_empty())
+                .map(|(hostname, addresses)| (hostname.to_lowercase(), addresses))
+                .collect(),
+        ))
+    }
+
+    fn get(&self, hostname: &str) -> Option<&Vec<IpAddr>> {
+        self.0.get(&hostname.to_lowercase())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Resolver that answers overridden hostnames itself and falls back to system DNS for the rest
+#[derive(Clone)]
+pub(crate) struct OverrideResolver {
+    overrides: GrpcDnsOverrides,
+    fallback: GaiResolver,
+}
+
+impl OverrideResolver {
+    pub(crate) fn new(overrides: GrpcDnsOverrides) -> Self {
+        Self { overrides, fallback: GaiResolver::new() }
+    }
+}
+
+impl Service<Name> for OverrideResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.fallback.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        if let Some(addresses) = self.overrides.get(name.as_str()) {
+            // Port 0 is replaced with the destination's port by the connector
+            let addrs: Vec<SocketAddr> =
+                addresses.iter().map(|ip| SocketAddr::new(*ip, 0)).collect();
+            return Box::pin(async move { Ok(addrs.into_iter()) });
+        }
+
+        let resolving = self.fallback.call(name);
+        Box::pin(async move { Ok(resolving.await?.collect::<Vec<_>>().into_iter()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_override_resolver_uses_overridden_addresses() {
+        let ip = IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3));
+        let overrides = GrpcDnsOverrides::new([("API.example.com".to_string(), vec![ip])]);
+        let mut resolver = OverrideResolver::new(overrides);
+
+        let addrs: Vec<SocketAddr> =
+            resolver.call(Name::from_str("api.example.com").unwrap()).await.unwrap().collect();
+        assert_eq!(addrs, vec![SocketAddr::new(ip, 0)]);
+    }
+
+    #[test]
+    fn test_overrides_without_addresses_are_skipped() {
+        let overrides = GrpcDnsOverrides::new([("example.com".to_string(), vec![])]);
+        assert!(overrides.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::ValidationError;
 use crate::manager::GrpcStreamError;
 use prost::DecodeError;
 use serde::{Serialize, Serializer};
@@ -35,6 +36,12 @@ pub enum Error {
     #[error(transparent)]
     IOError(#[from] io::Error),
 
+    #[error(
+        "Invalid message:\n{}",
+        .0.iter().map(|e| format!("  {e}")).collect::<Vec<_>>().join("\n")
+    )]
+    InvalidMessage(Vec<ValidationError>),
+
     #[error("GRPC error: {0}")]
     GenericError(String),
 }
@@ -0,0 +1,215 @@
+use prost_reflect::{FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor};
+use serde_json::{Map, Value, json};
+
+/// Build an example JSON body for a message, with every field filled in so it can be edited
+/// rather than written from memory. Scalars get their default value, enums their first value,
+/// repeated fields and maps a single entry, and each oneof only its first field. Fields are keyed
+/// by their proto name, matching the message's JSON schema.
+pub fn message_example(msg: &MessageDescriptor) -> Value {
+    message_example_value(msg, &mut Vec::new())
+}
+
+fn message_example_value(msg: &MessageDescriptor, stack: &mut Vec<String>) -> Value {
+    if let Some(value) = well_known_example(msg.full_name()) {
+        return value;
+    }
+
+    // Recursive messages would never end, so they're expanded once and deeper occurrences are
+    // left empty
+    if stack.iter().filter(|n| *n == msg.full_name()).count() > 1 {
+        return json!({});
+    }
+    stack.push(msg.full_name().to_string());
+
+    let mut out = Map::new();
+    for field in msg.fields() {
+        if let Some(oneof) = field.containing_oneof() {
+            let first = oneof.fields().next().map(|f| f.number());
+            if !is_synthetic_oneof(&oneof) && first != Some(field.number()) {
+                continue;
+            }
+        }
+        out.insert(field.name().to_string(), field_example(&field, stack));
+    }
+
+    stack.pop();
+    Value::Object(out)
+}
+
+/// Proto3 `optional` fields are wrapped in a oneof of their own, which isn't a real choice
+pub(crate) fn is_synthetic_oneof(oneof: &OneofDescriptor) -> bool {
+    oneof.fields().all(|f| f.field_descriptor_proto().proto3_optional())
+}
+
+fn field_example(field: &FieldDescriptor, stack: &mut Vec<String>) -> Value {
+    if field.is_map() {
+        let Kind::Message(entry) = field.kind() else {
+            return json!({});
+        };
+        let key = entry.map_entry_key_field();
+        let value = entry.map_entry_value_field();
+        let key = match kind_example(&key.kind(), stack) {
+            Value::String(s) if s.is_empty() => "key".to_string(),
+            Value::String(s) => s,
+            other => other.to_string(),
+        };
+        return json!({ key: kind_example(&value.kind(), stack) });
+    }
+
+    let value = kind_example(&field.kind(), stack);
+    match field.is_list() {
+        true => json!([value]),
+        false => value,
+    }
+}
+
+fn kind_example(kind: &Kind, stack: &mut Vec<String>) -> Value {
+    match kind {
+        Kind::Bool => json!(false),
+        Kind::Double | Kind::Float => json!(0.0),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 | Kind::Uint32 | Kind::Fixed32 => json!(0),
+        // 64-bit integers are strings in JSON, to avoid losing precision
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 | Kind::Uint64 | Kind::Fixed64 => json!("0"),
+        Kind::String | Kind::Bytes => json!(""),
+        Kind::Enum(e) => json!(e.values().next().map(|v| v.name().to_string()).unwrap_or_default()),
+        Kind::Message(m) => message_example_value(m, stack),
+    }
+}
+
+/// Well-known types have their own JSON representations rather than being objects of their fields
+fn well_known_example(full_name: &str) -> Option<Value> {
+    Some(match full_name {
+        "google.protobuf.Timestamp" => json!("1970-01-01T00:00:00Z"),
+        "google.protobuf.Duration" => json!("0s"),
+        "google.protobuf.FieldMask" => json!(""),
+        "google.protobuf.StringValue" | "google.protobuf.BytesValue" => json!(""),
+        "google.protobuf.Int32Value" | "google.protobuf.UInt32Value" => json!(0),
+        "google.protobuf.Int64Value" | "google.protobuf.UInt64Value" => json!("0"),
+        "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => json!(0.0),
+        "google.protobuf.BoolValue" => json!(false),
+        "google.protobuf.Empty" | "google.protobuf.Struct" => json!({}),
+        "google.protobuf.ListValue" => json!([]),
+        "google.protobuf.Value" => Value::Null,
+        "google.protobuf.Any" => json!({ "@type": "" }),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_reflect::DescriptorPool;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, MessageOptions, OneofDescriptorProto,
+    };
+
+    fn field(
+        name: &str,
+        number: i32,
+        r#type: Type,
+        type_name: Option<&str>,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            type_name: type_name.map(|n| n.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn repeated(field: FieldDescriptorProto) -> FieldDescriptorProto {
+        FieldDescriptorProto { label: Some(Label::Repeated as i32), ..field }
+    }
+
+    fn in_oneof(field: FieldDescriptorProto, index: i32) -> FieldDescriptorProto {
+        FieldDescriptorProto { oneof_index: Some(index), ..field }
+    }
+
+    fn pool() -> DescriptorPool {
+        let mut pool = DescriptorPool::global();
+        pool.add_file_descriptor_proto(FileDescriptorProto {
+            name: Some("shop.proto".to_string()),
+            package: Some("shop".to_string()),
+            syntax: Some("proto3".to_string()),
+            dependency: vec!["google/protobuf/timestamp.proto".to_string()],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Size".to_string()),
+                value: vec![
+                    EnumValueDescriptorProto {
+                        name: Some("SIZE_UNSPECIFIED".to_string()),
+                        number: Some(0),
+                        ..Default::default()
+                    },
+                    EnumValueDescriptorProto {
+                        name: Some("SIZE_LARGE".to_string()),
+                        number: Some(1),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }],
+            message_type: vec![DescriptorProto {
+                name: Some("Order".to_string()),
+                field: vec![
+                    field("id", 1, Type::Int64, None),
+                    field("note", 2, Type::String, None),
+                    field("size", 3, Type::Enum, Some(".shop.Size")),
+                    repeated(field("tags", 4, Type::String, None)),
+                    field("placed_at", 5, Type::Message, Some(".google.protobuf.Timestamp")),
+                    repeated(field("labels", 6, Type::Message, Some(".shop.Order.LabelsEntry"))),
+                    field("parent", 7, Type::Message, Some(".shop.Order")),
+                    in_oneof(field("card", 8, Type::String, None), 0),
+                    in_oneof(field("cash", 9, Type::Bool, None), 0),
+                ],
+                nested_type: vec![DescriptorProto {
+                    name: Some("LabelsEntry".to_string()),
+                    field: vec![
+                        field("key", 1, Type::String, None),
+                        field("value", 2, Type::Int32, None),
+                    ],
+                    options: Some(MessageOptions { map_entry: Some(true), ..Default::default() }),
+                    ..Default::default()
+                }],
+                oneof_decl: vec![OneofDescriptorProto {
+                    name: Some("payment".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        pool
+    }
+
+    #[test]
+    fn fills_in_every_field() {
+        let msg = pool().get_message_by_name("shop.Order").unwrap();
+        assert_eq!(
+            message_example(&msg),
+            json!({
+                "id": "0",
+                "note": "",
+                "size": "SIZE_UNSPECIFIED",
+                "tags": [""],
+                "placed_at": "1970-01-01T00:00:00Z",
+                "labels": { "key": 0 },
+                "parent": {
+                    "id": "0",
+                    "note": "",
+                    "size": "SIZE_UNSPECIFIED",
+                    "tags": [""],
+                    "placed_at": "1970-01-01T00:00:00Z",
+                    "labels": { "key": 0 },
+                    "parent": {},
+                    "card": "",
+                },
+                "card": "",
+            })
+        );
+    }
+}
@@ -0,0 +1,113 @@
+use log::warn;
+use prost_reflect::DescriptorPool;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FileDescriptorProto, MethodDescriptorProto, ServiceDescriptorProto,
+};
+
+/// Name of the standard health checking service from `grpc/health/v1/health.proto`
+pub const HEALTH_SERVICE: &str = "grpc.health.v1.Health";
+
+/// Add the health checking service to a pool that doesn't already have it. Servers implement it
+/// far more often than they expose it through reflection or ship its proto file, and having it in
+/// every pool means a health probe is just another call.
+pub(crate) fn add_health_service(pool: &mut DescriptorPool) {
+    if pool.get_service_by_name(HEALTH_SERVICE).is_some() {
+        return;
+    }
+    if let Err(e) = pool.add_file_descriptor_proto(health_file()) {
+        warn!("Failed to add gRPC health service to pool: {e}");
+    }
+}
+
+fn health_file() -> FileDescriptorProto {
+    let status_value = |name: &str, number: i32| EnumValueDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        ..Default::default()
+    };
+    let method = |name: &str, server_streaming: bool| MethodDescriptorProto {
+        name: Some(name.to_string()),
+        input_type: Some(".grpc.health.v1.HealthCheckRequest".to_string()),
+        output_type: Some(".grpc.health.v1.HealthCheckResponse".to_string()),
+        server_streaming: Some(server_streaming),
+        ..Default::default()
+    };
+
+    FileDescriptorProto {
+        name: Some("grpc/health/v1/health.proto".to_string()),
+        package: Some("grpc.health.v1".to_string()),
+        syntax: Some("proto3".to_string()),
+        message_type: vec![
+            DescriptorProto {
+                name: Some("HealthCheckRequest".to_string()),
+                field: vec![FieldDescriptorProto {
+                    name: Some("service".to_string()),
+                    json_name: Some("service".to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::String as i32),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            DescriptorProto {
+                name: Some("HealthCheckResponse".to_string()),
+                field: vec![FieldDescriptorProto {
+                    name: Some("status".to_string()),
+                    json_name: Some("status".to_string()),
+                    number: Some(1),
+                    label: Some(Label::Optional as i32),
+                    r#type: Some(Type::Enum as i32),
+                    type_name: Some(
+                        ".grpc.health.v1.HealthCheckResponse.ServingStatus".to_string(),
+                    ),
+                    ..Default::default()
+                }],
+                enum_type: vec![EnumDescriptorProto {
+                    name: Some("ServingStatus".to_string()),
+                    value: vec![
+                        status_value("UNKNOWN", 0),
+                        status_value("SERVING", 1),
+                        status_value("NOT_SERVING", 2),
+                        status_value("SERVICE_UNKNOWN", 3),
+                    ],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("Health".to_string()),
+            method: vec![method("Check", false), method("Watch", true)],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_health_service() {
+        let mut pool = DescriptorPool::new();
+        add_health_service(&mut pool);
+
+        let service = pool.get_service_by_name(HEALTH_SERVICE).unwrap();
+        let methods = service.methods().map(|m| m.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(methods, vec!["Check", "Watch"]);
+        assert!(service.methods().nth(1).unwrap().is_server_streaming());
+    }
+
+    #[test]
+    fn keeps_existing_health_service() {
+        let mut pool = DescriptorPool::new();
+        add_health_service(&mut pool);
+        add_health_service(&mut pool);
+
+        assert_eq!(pool.files().count(), 1);
+    }
+}
@@ -5,14 +5,26 @@ use serde_json::Deserializer;
 mod any;
 mod client;
 mod codec;
+mod connect;
+pub mod dns;
 pub mod error;
+mod example;
+mod health;
 mod json_schema;
 pub mod manager;
+pub mod proxy;
 mod reflection;
+mod status;
 mod transport;
+mod validate;
 
-pub use tonic::Code;
+pub use health::HEALTH_SERVICE;
+pub use status::StatusDetail;
+pub use tonic::codec::CompressionEncoding;
 pub use tonic::metadata::*;
+pub use tonic::{Code, Status};
+pub use transport::WireProtocol;
+pub use validate::ValidationError;
 
 pub fn serialize_options() -> SerializeOptions {
     SerializeOptions::new().skip_default_fields(false)
@@ -30,6 +42,8 @@ pub struct ServiceDefinition {
 pub struct MethodDefinition {
     pub name: String,
     pub schema: String,
+    /// Example input message with every field filled in, as pretty-printed JSON
+    pub example: String,
     pub client_streaming: bool,
     pub server_streaming: bool,
 }
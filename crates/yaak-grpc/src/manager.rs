@@ -1,21 +1,25 @@
 use crate::codec::DynamicCodec;
+use crate::connect::connect_unary;
+use crate::dns::GrpcDnsOverrides;
 use crate::error::Error::GenericError;
 use crate::error::Result;
+use crate::health::add_health_service;
+use crate::proxy::GrpcProxy;
 use crate::reflection::{
     fill_pool_from_files, fill_pool_from_reflection, method_desc_to_path,
     reflect_types_for_dynamic_message, reflect_types_for_message,
 };
-use crate::transport::get_transport;
-use crate::{MethodDefinition, ServiceDefinition, json_schema};
-use hyper_rustls::HttpsConnector;
-use hyper_util::client::legacy::Client;
-use hyper_util::client::legacy::connect::HttpConnector;
+use crate::status::{StatusDetail, decode_status_details};
+use crate::transport::{GrpcChannel, WireProtocol, get_channel};
+use crate::validate::parse_message;
+use crate::{MethodDefinition, ServiceDefinition, example, json_schema};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD_NO_PAD;
 use log::{info, warn};
 pub use prost_reflect::DynamicMessage;
 use prost_reflect::ReflectMessage;
 use prost_reflect::prost::Message;
 use prost_reflect::{DescriptorPool, MethodDescriptor, ServiceDescriptor};
-use serde_json::Deserializer;
 use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::error::Error;
@@ -24,21 +28,27 @@ use std::fmt::Display;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use tokio_stream::StreamExt;
 use tokio_stream::wrappers::ReceiverStream;
-use tonic::body::BoxBody;
-use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::client::Grpc;
+use tonic::codec::CompressionEncoding;
+use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 use tonic::transport::Uri;
 use tonic::{IntoRequest, IntoStreamingRequest, Request, Response, Status, Streaming};
-use yaak_tls::ClientCertificateConfig;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig};
 
 #[derive(Clone)]
 pub struct GrpcConnection {
     pool: Arc<RwLock<DescriptorPool>>,
-    conn: Client<HttpsConnector<HttpConnector>, BoxBody>,
+    conn: GrpcChannel,
     pub uri: Uri,
     use_reflection: bool,
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
+    send_compression: Option<CompressionEncoding>,
+    accept_compression: Vec<CompressionEncoding>,
 }
 
 #[derive(Default, Debug)]
@@ -71,6 +81,53 @@ impl From<Status> for GrpcStreamError {
 }
 
 impl GrpcConnection {
+    /// Compress messages sent on this connection with `send`, and tell the server it may
+    /// compress the messages it sends back with any of `accept`
+    pub fn with_compression(
+        mut self,
+        send: Option<CompressionEncoding>,
+        accept: Vec<CompressionEncoding>,
+    ) -> Self {
+        self.send_compression = send;
+        self.accept_compression = accept;
+        self
+    }
+
+    fn client(&self) -> Grpc<GrpcChannel> {
+        let mut client = Grpc::with_origin(self.conn.clone(), self.uri.clone());
+        if let Some(encoding) = self.send_compression {
+            client = client.send_compressed(encoding);
+        }
+        for encoding in &self.accept_compression {
+            client = client.accept_compressed(*encoding);
+        }
+        client
+    }
+
+    /// Decode the rich error details a server attached to a failed call's status, using the
+    /// connection's descriptors for any custom detail types
+    pub async fn status_details(&self, status: &Status) -> Vec<StatusDetail> {
+        if status.details().is_empty() {
+            return Vec::new();
+        }
+        let pool = self.pool.read().await;
+        decode_status_details(&pool, status.details())
+    }
+
+    /// gRPC-Web and Connect requests are plain HTTP/1.1 requests, so clients can only send a
+    /// single message
+    fn ensure_client_streaming_supported(&self) -> Result<()> {
+        match self.conn {
+            GrpcChannel::GrpcWeb(_) => Err(GenericError(
+                "gRPC-Web only supports unary and server streaming methods".to_string(),
+            )),
+            GrpcChannel::Connect { .. } => {
+                Err(GenericError("Connect only supports unary methods".to_string()))
+            }
+            GrpcChannel::Grpc(_) => Ok(()),
+        }
+    }
+
     pub async fn method(&self, service: &str, method: &str) -> Result<MethodDescriptor> {
         let service = self.service(service).await?;
         let method = service
@@ -94,29 +151,50 @@ impl GrpcConnection {
         method: &str,
         message: &str,
         metadata: &BTreeMap<String, String>,
+        deadline: Option<Duration>,
         client_cert: Option<ClientCertificateConfig>,
     ) -> Result<Response<DynamicMessage>> {
         if self.use_reflection {
-            reflect_types_for_message(self.pool.clone(), &self.uri, message, metadata, client_cert)
-                .await?;
+            reflect_types_for_message(
+                self.pool.clone(),
+                &self.uri,
+                message,
+                metadata,
+                client_cert,
+                self.proxy.clone(),
+                self.dns_overrides.clone(),
+            )
+            .await?;
         }
         let method = &self.method(&service, &method).await?;
         let input_message = method.input();
 
-        let mut deserializer = Deserializer::from_str(message);
-        let req_message = DynamicMessage::deserialize(input_message, &mut deserializer)?;
-        deserializer.end()?;
+        let req_message = parse_message(input_message, message)?;
 
-        let mut client = tonic::client::Grpc::with_origin(self.conn.clone(), self.uri.clone());
+        if let GrpcChannel::Connect { client, json } = &self.conn {
+            return connect_unary(
+                client,
+                *json,
+                &self.uri,
+                method,
+                req_message,
+                metadata,
+                deadline,
+            )
+            .await;
+        }
+
+        let mut client = self.client();
 
         let mut req = req_message.into_request();
         decorate_req(metadata, &mut req)?;
+        set_deadline(deadline, &mut req);
 
         let path = method_desc_to_path(method);
         let codec = DynamicCodec::new(method.clone());
         client.ready().await.map_err(|e| GenericError(format!("Failed to connect: {}", e)))?;
 
-        Ok(client.unary(req, path, codec).await?)
+        Ok(client.unary(req, path, codec).await.map_err(GrpcStreamError::from)?)
     }
 
     pub async fn serialize_message(
@@ -132,6 +210,8 @@ impl GrpcConnection {
                 message,
                 metadata,
                 client_cert,
+                self.proxy.clone(),
+                self.dns_overrides.clone(),
             )
             .await?;
 
@@ -157,12 +237,14 @@ impl GrpcConnection {
         method: &str,
         stream: ReceiverStream<String>,
         metadata: &BTreeMap<String, String>,
+        deadline: Option<Duration>,
         client_cert: Option<ClientCertificateConfig>,
         on_message: F,
     ) -> Result<Response<Streaming<DynamicMessage>>>
     where
         F: Fn(std::result::Result<String, String>) + Send + Sync + Clone + 'static,
     {
+        self.ensure_client_streaming_supported()?;
         let method = &self.method(&service, &method).await?;
         let mapped_stream = {
             let input_message = method.input();
@@ -171,6 +253,8 @@ impl GrpcConnection {
             let md = metadata.clone();
             let use_reflection = self.use_reflection.clone();
             let client_cert = client_cert.clone();
+            let proxy = self.proxy.clone();
+            let dns_overrides = self.dns_overrides.clone();
             stream
                 .then(move |json| {
                     let pool = pool.clone();
@@ -179,18 +263,27 @@ impl GrpcConnection {
                     let md = md.clone();
                     let use_reflection = use_reflection.clone();
                     let client_cert = client_cert.clone();
+                    let proxy = proxy.clone();
+                    let dns_overrides = dns_overrides.clone();
                     let on_message = on_message.clone();
                     let json_clone = json.clone();
                     async move {
                         if use_reflection {
-                            if let Err(e) =
-                                reflect_types_for_message(pool, &uri, &json, &md, client_cert).await
+                            if let Err(e) = reflect_types_for_message(
+                                pool,
+                                &uri,
+                                &json,
+                                &md,
+                                client_cert,
+                                proxy,
+                                dns_overrides,
+                            )
+                            .await
                             {
                                 warn!("Failed to resolve Any types: {e}");
                             }
                         }
-                        let mut de = Deserializer::from_str(&json);
-                        match DynamicMessage::deserialize(input_message, &mut de) {
+                        match parse_message(input_message, &json) {
                             Ok(m) => {
                                 on_message(Ok(json_clone));
                                 Some(m)
@@ -206,15 +299,16 @@ impl GrpcConnection {
                 .filter_map(|x| x)
         };
 
-        let mut client = tonic::client::Grpc::with_origin(self.conn.clone(), self.uri.clone());
+        let mut client = self.client();
         let path = method_desc_to_path(method);
         let codec = DynamicCodec::new(method.clone());
 
         let mut req = mapped_stream.into_streaming_request();
         decorate_req(metadata, &mut req)?;
+        set_deadline(deadline, &mut req);
 
         client.ready().await.map_err(|e| GenericError(format!("Failed to connect: {}", e)))?;
-        Ok(client.streaming(req, path, codec).await?)
+        Ok(client.streaming(req, path, codec).await.map_err(GrpcStreamError::from)?)
     }
 
     pub async fn client_streaming<F>(
@@ -223,12 +317,14 @@ impl GrpcConnection {
         method: &str,
         stream: ReceiverStream<String>,
         metadata: &BTreeMap<String, String>,
+        deadline: Option<Duration>,
         client_cert: Option<ClientCertificateConfig>,
         on_message: F,
     ) -> Result<Response<DynamicMessage>>
     where
         F: Fn(std::result::Result<String, String>) + Send + Sync + Clone + 'static,
     {
+        self.ensure_client_streaming_supported()?;
         let method = &self.method(&service, &method).await?;
         let mapped_stream = {
             let input_message = method.input();
@@ -237,6 +333,8 @@ impl GrpcConnection {
             let md = metadata.clone();
             let use_reflection = self.use_reflection.clone();
             let client_cert = client_cert.clone();
+            let proxy = self.proxy.clone();
+            let dns_overrides = self.dns_overrides.clone();
             stream
                 .then(move |json| {
                     let pool = pool.clone();
@@ -245,18 +343,27 @@ impl GrpcConnection {
                     let md = md.clone();
                     let use_reflection = use_reflection.clone();
                     let client_cert = client_cert.clone();
+                    let proxy = proxy.clone();
+                    let dns_overrides = dns_overrides.clone();
                     let on_message = on_message.clone();
                     let json_clone = json.clone();
                     async move {
                         if use_reflection {
-                            if let Err(e) =
-                                reflect_types_for_message(pool, &uri, &json, &md, client_cert).await
+                            if let Err(e) = reflect_types_for_message(
+                                pool,
+                                &uri,
+                                &json,
+                                &md,
+                                client_cert,
+                                proxy,
+                                dns_overrides,
+                            )
+                            .await
                             {
                                 warn!("Failed to resolve Any types: {e}");
                             }
                         }
-                        let mut de = Deserializer::from_str(&json);
-                        match DynamicMessage::deserialize(input_message, &mut de) {
+                        match parse_message(input_message, &json) {
                             Ok(m) => {
                                 on_message(Ok(json_clone));
                                 Some(m)
@@ -272,18 +379,16 @@ impl GrpcConnection {
                 .filter_map(|x| x)
         };
 
-        let mut client = tonic::client::Grpc::with_origin(self.conn.clone(), self.uri.clone());
+        let mut client = self.client();
         let path = method_desc_to_path(method);
         let codec = DynamicCodec::new(method.clone());
 
         let mut req = mapped_stream.into_streaming_request();
         decorate_req(metadata, &mut req)?;
+        set_deadline(deadline, &mut req);
 
         client.ready().await.map_err(|e| GenericError(format!("Failed to connect: {}", e)))?;
-        Ok(client
-            .client_streaming(req, path, codec)
-            .await
-            .map_err(|e| GrpcStreamError { message: e.message().to_string(), status: Some(e) })?)
+        Ok(client.client_streaming(req, path, codec).await.map_err(GrpcStreamError::from)?)
     }
 
     pub async fn server_streaming(
@@ -292,23 +397,26 @@ impl GrpcConnection {
         method: &str,
         message: &str,
         metadata: &BTreeMap<String, String>,
+        deadline: Option<Duration>,
     ) -> Result<Response<Streaming<DynamicMessage>>> {
+        if let GrpcChannel::Connect { .. } = self.conn {
+            return Err(GenericError("Connect only supports unary methods".to_string()));
+        }
         let method = &self.method(&service, &method).await?;
         let input_message = method.input();
 
-        let mut deserializer = Deserializer::from_str(message);
-        let req_message = DynamicMessage::deserialize(input_message, &mut deserializer)?;
-        deserializer.end()?;
+        let req_message = parse_message(input_message, message)?;
 
-        let mut client = tonic::client::Grpc::with_origin(self.conn.clone(), self.uri.clone());
+        let mut client = self.client();
 
         let mut req = req_message.into_request();
         decorate_req(metadata, &mut req)?;
+        set_deadline(deadline, &mut req);
 
         let path = method_desc_to_path(method);
         let codec = DynamicCodec::new(method.clone());
         client.ready().await.map_err(|e| GenericError(format!("Failed to connect: {}", e)))?;
-        Ok(client.server_streaming(req, path, codec).await?)
+        Ok(client.server_streaming(req, path, codec).await.map_err(GrpcStreamError::from)?)
     }
 }
 
@@ -340,6 +448,7 @@ impl GrpcHandle {
         self.pools.remove(&key);
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn reflect(
         &mut self,
         id: &str,
@@ -348,6 +457,9 @@ impl GrpcHandle {
         metadata: &BTreeMap<String, String>,
         validate_certificates: bool,
         client_cert: Option<ClientCertificateConfig>,
+        ca_certificates: &[CaCertificateConfig],
+        proxy: Option<GrpcProxy>,
+        dns_overrides: GrpcDnsOverrides,
     ) -> Result<bool> {
         let server_reflection = proto_files.is_empty();
         let key = make_pool_key(id, uri, proto_files);
@@ -357,17 +469,28 @@ impl GrpcHandle {
             return Ok(server_reflection);
         }
 
-        let pool = if server_reflection {
+        let mut pool = if server_reflection {
             let full_uri = uri_from_str(uri)?;
-            fill_pool_from_reflection(&full_uri, metadata, validate_certificates, client_cert).await
+            fill_pool_from_reflection(
+                &full_uri,
+                metadata,
+                validate_certificates,
+                client_cert,
+                ca_certificates,
+                proxy,
+                dns_overrides,
+            )
+            .await
         } else {
             fill_pool_from_files(&self.config, proto_files).await
         }?;
+        add_health_service(&mut pool);
 
         self.pools.insert(key, pool.clone());
         Ok(server_reflection)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn services(
         &mut self,
         id: &str,
@@ -376,12 +499,25 @@ impl GrpcHandle {
         metadata: &BTreeMap<String, String>,
         validate_certificates: bool,
         client_cert: Option<ClientCertificateConfig>,
+        ca_certificates: &[CaCertificateConfig],
+        proxy: Option<GrpcProxy>,
+        dns_overrides: GrpcDnsOverrides,
     ) -> Result<Vec<ServiceDefinition>> {
         // Ensure we have a pool; reflect only if missing
         if self.get_pool(id, uri, proto_files).is_none() {
             info!("Reflecting gRPC services for {} at {}", id, uri);
-            self.reflect(id, uri, proto_files, metadata, validate_certificates, client_cert)
-                .await?;
+            self.reflect(
+                id,
+                uri,
+                proto_files,
+                metadata,
+                validate_certificates,
+                client_cert,
+                ca_certificates,
+                proxy,
+                dns_overrides,
+            )
+            .await?;
         }
 
         let pool = self
@@ -397,6 +533,7 @@ impl GrpcHandle {
                     ServiceDefinition { name: s.full_name().to_string(), methods: vec![] };
                 for method in s.methods() {
                     let input_message = method.input();
+                    let example = example::message_example(&input_message);
                     def.methods.push(MethodDefinition {
                         name: method.name().to_string(),
                         server_streaming: method.is_server_streaming(),
@@ -406,6 +543,8 @@ impl GrpcHandle {
                             input_message,
                         ))
                         .expect("Failed to serialize JSON schema"),
+                        example: serde_json::to_string_pretty(&example)
+                            .expect("Failed to serialize example message"),
                     })
                 }
                 def
@@ -413,6 +552,7 @@ impl GrpcHandle {
             .collect::<Vec<_>>()
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn connect(
         &mut self,
         id: &str,
@@ -421,6 +561,11 @@ impl GrpcHandle {
         metadata: &BTreeMap<String, String>,
         validate_certificates: bool,
         client_cert: Option<ClientCertificateConfig>,
+        ca_certificates: &[CaCertificateConfig],
+        proxy: Option<GrpcProxy>,
+        dns_overrides: GrpcDnsOverrides,
+        connect_timeout: Option<Duration>,
+        protocol: WireProtocol,
     ) -> Result<GrpcConnection> {
         let use_reflection = proto_files.is_empty();
         if self.get_pool(id, uri, proto_files).is_none() {
@@ -431,6 +576,9 @@ impl GrpcHandle {
                 metadata,
                 validate_certificates,
                 client_cert.clone(),
+                ca_certificates,
+                proxy.clone(),
+                dns_overrides.clone(),
             )
             .await?;
         }
@@ -439,8 +587,45 @@ impl GrpcHandle {
             .ok_or(GenericError("Failed to get pool".to_string()))?
             .clone();
         let uri = uri_from_str(uri)?;
-        let conn = get_transport(validate_certificates, client_cert.clone())?;
-        Ok(GrpcConnection { pool: Arc::new(RwLock::new(pool)), use_reflection, conn, uri })
+        let conn = get_channel(
+            validate_certificates,
+            client_cert.clone(),
+            ca_certificates,
+            proxy.clone(),
+            dns_overrides.clone(),
+            connect_timeout,
+            protocol,
+        )?;
+        Ok(GrpcConnection {
+            pool: Arc::new(RwLock::new(pool)),
+            use_reflection,
+            conn,
+            uri,
+            proxy,
+            dns_overrides,
+            send_compression: None,
+            accept_compression: Vec::new(),
+        })
+    }
+
+    /// Seed the pool for the given key from an encoded `FileDescriptorSet`, such as one returned
+    /// by [`GrpcHandle::encode_pool`], so that later calls don't need to reflect.
+    pub fn load_pool(
+        &mut self,
+        id: &str,
+        uri: &str,
+        proto_files: &Vec<PathBuf>,
+        descriptor_set: &[u8],
+    ) -> Result<()> {
+        let mut pool = DescriptorPool::decode(descriptor_set)?;
+        add_health_service(&mut pool);
+        self.pools.insert(make_pool_key(id, uri, proto_files), pool);
+        Ok(())
+    }
+
+    /// Encode the pool for the given key as a `FileDescriptorSet`, if one has been loaded
+    pub fn encode_pool(&self, id: &str, uri: &str, proto_files: &Vec<PathBuf>) -> Option<Vec<u8>> {
+        self.get_pool(id, uri, proto_files).map(|pool| pool.encode_to_vec())
     }
 
     fn get_pool(&self, id: &str, uri: &str, proto_files: &Vec<PathBuf>) -> Option<&DescriptorPool> {
@@ -453,12 +638,41 @@ pub(crate) fn decorate_req<T>(
     req: &mut Request<T>,
 ) -> Result<()> {
     for (k, v) in metadata {
-        req.metadata_mut()
-            .insert(MetadataKey::from_str(k.as_str())?, MetadataValue::from_str(v.as_str())?);
+        if k.to_lowercase().ends_with("-bin") {
+            req.metadata_mut().insert_bin(
+                MetadataKey::from_bytes(k.as_bytes())?,
+                MetadataValue::from_bytes(&decode_binary_metadata(k, v)?),
+            );
+        } else {
+            req.metadata_mut()
+                .insert(MetadataKey::from_str(k.as_str())?, MetadataValue::from_str(v.as_str())?);
+        }
     }
     Ok(())
 }
 
+/// Values of `-bin` metadata are entered as base64, with or without padding, and sent as the
+/// raw bytes they encode
+fn decode_binary_metadata(key: &str, value: &str) -> Result<Vec<u8>> {
+    STANDARD_NO_PAD
+        .decode(value.trim().trim_end_matches('='))
+        .map_err(|e| GenericError(format!("Invalid base64 value for metadata {key}: {e}")))
+}
+
+/// Tell the server how long the call may take by sending a `grpc-timeout` header. The server
+/// ends the call with `DEADLINE_EXCEEDED` once it runs out.
+fn set_deadline<T>(deadline: Option<Duration>, req: &mut Request<T>) {
+    if let Some(deadline) = deadline {
+        req.set_timeout(deadline);
+    }
+}
+
+/// Compression the server used for the messages it sent, from the `grpc-encoding` header of its
+/// response
+pub fn response_encoding(metadata: &MetadataMap) -> Option<String> {
+    metadata.get("grpc-encoding").and_then(|v| v.to_str().ok()).map(|v| v.to_string())
+}
+
 fn uri_from_str(uri_str: &str) -> Result<Uri> {
     match Uri::from_str(uri_str) {
         Ok(uri) => Ok(uri),
@@ -469,6 +683,41 @@ fn uri_from_str(uri_str: &str) -> Result<Uri> {
     }
 }
 
+/// Build the `(host, tls_key)` pair under which server reflection results for a URI can be shared.
+/// Every setting that affects the TLS handshake is folded into the key, along with the request
+/// metadata, so a descriptor set is only reused for connections that would have reached the server
+/// the same way and with the same credentials.
+pub fn reflection_cache_key(
+    uri: &str,
+    validate_certificates: bool,
+    client_cert: &Option<ClientCertificateConfig>,
+    metadata: &BTreeMap<String, String>,
+) -> Result<(String, String)> {
+    let uri = uri_from_str(uri)?;
+    let host = uri.authority().map(|a| a.to_string()).unwrap_or_default();
+    let cert_files = match client_cert {
+        Some(c) => [&c.crt_file, &c.key_file, &c.pfx_file]
+            .iter()
+            .map(|f| f.as_deref().unwrap_or_default())
+            .collect::<Vec<&str>>()
+            .join(":"),
+        None => String::new(),
+    };
+    let metadata = metadata
+        .iter()
+        .map(|(k, v)| format!("{}={}", k.to_lowercase(), v))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let tls_key = format!(
+        "{}::{}::{}::{}",
+        uri.scheme_str().unwrap_or_default(),
+        validate_certificates,
+        cert_files,
+        metadata
+    );
+    Ok((host, format!("{:x}", md5::compute(tls_key))))
+}
+
 fn make_pool_key(id: &str, uri: &str, proto_files: &Vec<PathBuf>) -> String {
     let pool_key = format!(
         "{}::{}::{}",
@@ -483,3 +732,131 @@ fn make_pool_key(id: &str, uri: &str, proto_files: &Vec<PathBuf>) -> String {
 
     format!("{:x}", md5::compute(pool_key))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::health::HEALTH_SERVICE;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn decodes_binary_metadata() {
+        let metadata = BTreeMap::from([
+            ("x-trace-bin".to_string(), "AAEC/w==".to_string()),
+            ("x-unpadded-bin".to_string(), "AAEC/w".to_string()),
+            ("x-plain".to_string(), "AAEC/w==".to_string()),
+        ]);
+        let mut req = Request::new(());
+        decorate_req(&metadata, &mut req).unwrap();
+
+        let md = req.metadata();
+        assert_eq!(md.get_bin("x-trace-bin").unwrap().to_bytes().unwrap().as_ref(), [0, 1, 2, 255]);
+        assert_eq!(
+            md.get_bin("x-unpadded-bin").unwrap().to_bytes().unwrap().as_ref(),
+            [0, 1, 2, 255]
+        );
+        assert_eq!(md.get("x-plain").unwrap(), "AAEC/w==");
+    }
+
+    #[test]
+    fn rejects_invalid_binary_metadata() {
+        let metadata = BTreeMap::from([("x-trace-bin".to_string(), "not base64!".to_string())]);
+        let mut req = Request::new(());
+        assert!(decorate_req(&metadata, &mut req).is_err());
+    }
+
+    #[test]
+    fn sets_grpc_timeout_header_for_deadline() {
+        let mut req = Request::new(());
+        set_deadline(None, &mut req);
+        assert!(req.metadata().get("grpc-timeout").is_none());
+
+        set_deadline(Some(Duration::from_millis(1500)), &mut req);
+        assert!(req.metadata().get("grpc-timeout").is_some());
+    }
+
+    #[test]
+    fn reflection_cache_key_includes_metadata() {
+        let uri = "https://localhost:50051";
+        let anonymous = reflection_cache_key(uri, true, &None, &BTreeMap::new()).unwrap();
+        let alice = BTreeMap::from([("authorization".to_string(), "Bearer alice".to_string())]);
+        let bob = BTreeMap::from([("authorization".to_string(), "Bearer bob".to_string())]);
+
+        let alice_key = reflection_cache_key(uri, true, &None, &alice).unwrap();
+        assert_eq!(alice_key, reflection_cache_key(uri, true, &None, &alice).unwrap());
+        assert_eq!(alice_key.0, anonymous.0);
+        assert_ne!(alice_key.1, anonymous.1);
+        assert_ne!(alice_key.1, reflection_cache_key(uri, true, &None, &bob).unwrap().1);
+    }
+
+    /// Send a health check over gRPC-Web and return the request head the server received
+    async fn capture_health_check(connection: GrpcConnection) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let connection =
+            GrpcConnection { uri: Uri::from_str(&format!("http://{addr}")).unwrap(), ..connection };
+        // The server hangs up without responding, so only the request matters
+        let _ = connection.unary(HEALTH_SERVICE, "Check", "{}", &BTreeMap::new(), None, None).await;
+
+        server.await.unwrap().to_lowercase()
+    }
+
+    fn health_connection() -> GrpcConnection {
+        let mut pool = DescriptorPool::new();
+        add_health_service(&mut pool);
+        GrpcConnection {
+            pool: Arc::new(RwLock::new(pool)),
+            conn: get_channel(
+                false,
+                None,
+                &[],
+                None,
+                GrpcDnsOverrides::default(),
+                None,
+                WireProtocol::GrpcWeb,
+            )
+            .unwrap(),
+            uri: Uri::from_static("http://localhost"),
+            use_reflection: false,
+            proxy: None,
+            dns_overrides: GrpcDnsOverrides::default(),
+            send_compression: None,
+            accept_compression: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn client_applies_compression() {
+        let connection = health_connection().with_compression(
+            Some(CompressionEncoding::Gzip),
+            vec![CompressionEncoding::Gzip, CompressionEncoding::Zstd],
+        );
+        let request = capture_health_check(connection).await;
+        assert!(request.contains("grpc-encoding: gzip\r\n"));
+        assert!(request.contains("grpc-accept-encoding: gzip,zstd,identity\r\n"));
+    }
+
+    #[tokio::test]
+    async fn client_skips_compression_by_default() {
+        let request = capture_health_check(health_connection()).await;
+        assert!(!request.contains("grpc-encoding:"));
+        assert!(!request.contains("grpc-accept-encoding:"));
+    }
+
+    #[test]
+    fn reads_response_encoding() {
+        let mut metadata = MetadataMap::new();
+        assert_eq!(response_encoding(&metadata), None);
+
+        metadata.insert("grpc-encoding", MetadataValue::from_static("zstd"));
+        assert_eq!(response_encoding(&metadata), Some("zstd".to_string()));
+    }
+}
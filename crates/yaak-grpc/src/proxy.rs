@@ -0,0 +1,430 @@
+//! Proxy support for gRPC connections.
+//!
+//! Tonic connects through a hyper connector, so proxying happens at the connection level:
+//! [`ProxyConnector`] opens a TCP connection to the proxy and establishes a tunnel to the
+//! destination (HTTP `CONNECT` or SOCKS5) before TLS and HTTP/2 run over it.
+
+use crate::dns::OverrideResolver;
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::TokioIo;
+use std::future::Future;
+use std::net::IpAddr;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tonic::transport::Uri;
+use tower_service::Service;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+const MAX_CONNECT_RESPONSE_SIZE: usize = 8 * 1024;
+
+#[derive(Clone, Debug, Default)]
+pub struct GrpcProxy {
+    /// Proxy for plaintext connections, like `localhost:8080` or `socks5://localhost:1080`
+    pub http: String,
+    /// Proxy for TLS connections
+    pub https: String,
+    pub auth: Option<GrpcProxyAuth>,
+    /// Comma-separated hosts to connect to directly, following `NO_PROXY` conventions
+    pub bypass: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct GrpcProxyAuth {
+    pub user: String,
+    pub password: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProxyKind {
+    Connect,
+    Socks5,
+}
+
+#[derive(Debug)]
+struct ProxyTarget {
+    kind: ProxyKind,
+    /// Address of the proxy itself, with an explicit port
+    address: Uri,
+}
+
+impl GrpcProxy {
+    /// Pick the proxy to use for a destination, or `None` to connect directly
+    fn for_uri(&self, dst: &Uri) -> Result<Option<ProxyTarget>> {
+        let proxy = if dst.scheme_str() == Some("https") { &self.https } else { &self.http };
+        let proxy = proxy.trim();
+        if proxy.is_empty() || bypasses(&self.bypass, dst) {
+            return Ok(None);
+        }
+
+        let proxy =
+            if proxy.contains("://") { proxy.to_string() } else { format!("http://{proxy}") };
+        let uri = Uri::from_str(&proxy)
+            .map_err(|e| GenericError(format!("Invalid proxy URL {proxy}: {e}")))?;
+        let (kind, default_port) = match uri.scheme_str() {
+            Some("http") => (ProxyKind::Connect, 80),
+            Some("socks5") | Some("socks5h") => (ProxyKind::Socks5, 1080),
+            _ => return Err(GenericError(format!("Unsupported proxy scheme in {proxy}"))),
+        };
+        let host = uri.host().ok_or(GenericError(format!("Missing proxy host in {proxy}")))?;
+        let port = uri.port_u16().unwrap_or(default_port);
+        let address = Uri::from_str(&format!("http://{host}:{port}"))
+            .map_err(|e| GenericError(format!("Invalid proxy URL {proxy}: {e}")))?;
+
+        Ok(Some(ProxyTarget { kind, address }))
+    }
+}
+
+/// Connector that tunnels through the configured proxy, falling back to a direct connection for
+/// destinations without a proxy.
+#[derive(Clone)]
+pub(crate) struct ProxyConnector {
+    http: HttpConnector<OverrideResolver>,
+    proxy: Option<GrpcProxy>,
+}
+
+impl ProxyConnector {
+    pub(crate) fn new(http: HttpConnector<OverrideResolver>, proxy: Option<GrpcProxy>) -> Self {
+        Self { http, proxy }
+    }
+}
+
+impl Service<Uri> for ProxyConnector {
+    type Response = TokioIo<TcpStream>;
+    type Error = BoxError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.http.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let mut http = self.http.clone();
+        let proxy = self.proxy.clone();
+        Box::pin(async move {
+            let target = match &proxy {
+                Some(proxy) => proxy.for_uri(&dst)?,
+                None => None,
+            };
+            let Some(target) = target else {
+                return Ok(http.call(dst).await?);
+            };
+
+            let host = dst.host().unwrap_or_default().trim_matches(['[', ']']).to_string();
+            let port =
+                dst.port_u16().unwrap_or(if dst.scheme_str() == Some("https") { 443 } else { 80 });
+            let auth = proxy.and_then(|p| p.auth);
+
+            let mut stream = http.call(target.address).await?.into_inner();
+            match target.kind {
+                ProxyKind::Connect => {
+                    connect_tunnel(&mut stream, &host, port, auth.as_ref()).await?
+                }
+                ProxyKind::Socks5 => socks5_tunnel(&mut stream, &host, port, auth.as_ref()).await?,
+            }
+            Ok(TokioIo::new(stream))
+        })
+    }
+}
+
+/// Whether a destination matches the bypass list. Entries are hostnames (matching subdomains
+/// too), IP addresses, or CIDR ranges, optionally with a port, and `*` bypasses everything.
+fn bypasses(bypass: &str, dst: &Uri) -> bool {
+    let Some(host) = dst.host() else {
+        return false;
+    };
+    let host = host.trim_matches(['[', ']']).to_lowercase();
+    let port = dst.port_u16();
+
+    bypass.split(',').map(|e| e.trim().to_lowercase()).filter(|e| !e.is_empty()).any(|entry| {
+        if entry == "*" {
+            return true;
+        }
+        if let Some(matched) = ip_entry_matches(&entry, &host) {
+            return matched;
+        }
+
+        let (domain, entry_port) = match entry.rsplit_once(':') {
+            Some((domain, p)) => (domain, p.parse::<u16>().ok()),
+            None => (entry.as_str(), None),
+        };
+        if entry_port.is_some() && entry_port != port {
+            return false;
+        }
+
+        let domain = domain.strip_prefix('*').unwrap_or(domain);
+        let bare = domain.trim_start_matches('.');
+        host == bare || host.ends_with(&format!(".{bare}"))
+    })
+}
+
+/// Match an IP or CIDR entry against the host, or `None` if the entry isn't an IP
+fn ip_entry_matches(entry: &str, host: &str) -> Option<bool> {
+    let (network, prefix) = match entry.split_once('/') {
+        Some((network, prefix)) => (network, Some(prefix.parse::<u32>().ok()?)),
+        None => (entry, None),
+    };
+    let network = IpAddr::from_str(network.trim_matches(['[', ']'])).ok()?;
+    let Ok(ip) = IpAddr::from_str(host) else {
+        return Some(false);
+    };
+
+    Some(match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let prefix = prefix.unwrap_or(32).min(32);
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let prefix = prefix.unwrap_or(128).min(128);
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    })
+}
+
+async fn connect_tunnel(
+    stream: &mut TcpStream,
+    host: &str,
+    port: u16,
+    auth: Option<&GrpcProxyAuth>,
+) -> Result<()> {
+    let authority =
+        if host.contains(':') { format!("[{host}]:{port}") } else { format!("{host}:{port}") };
+    let mut request = format!("CONNECT {authority} HTTP/1.1\r\nHost: {authority}\r\n");
+    if let Some(GrpcProxyAuth { user, password }) = auth {
+        let credentials = STANDARD.encode(format!("{user}:{password}"));
+        request.push_str(&format!("Proxy-Authorization: Basic {credentials}\r\n"));
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    let mut response = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !response.windows(4).any(|w| w == b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 {
+            return Err(GenericError("Proxy closed the connection during CONNECT".to_string()));
+        }
+        response.extend_from_slice(&buf[..n]);
+        if response.len() > MAX_CONNECT_RESPONSE_SIZE {
+            return Err(GenericError("Proxy CONNECT response was too large".to_string()));
+        }
+    }
+
+    let response = String::from_utf8_lossy(&response);
+    let status_line = response.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        Some("407") => Err(GenericError("Proxy authentication required".to_string())),
+        _ => Err(GenericError(format!("Proxy CONNECT failed: {status_line}"))),
+    }
+}
+
+async fn socks5_tunnel(
+    stream: &mut TcpStream,
+    host: &str,
+    port: u16,
+    auth: Option<&GrpcProxyAuth>,
+) -> Result<()> {
+    // Greeting, offering username/password auth when credentials are configured
+    let methods: &[u8] = if auth.is_some() { &[0x00, 0x02] } else { &[0x00] };
+    let mut greeting = vec![0x05, methods.len() as u8];
+    greeting.extend_from_slice(methods);
+    stream.write_all(&greeting).await?;
+
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply).await?;
+    match (reply[1], auth) {
+        (0x00, _) => {}
+        (0x02, Some(GrpcProxyAuth { user, password })) => {
+            if user.len() > 255 || password.len() > 255 {
+                return Err(GenericError("SOCKS5 credentials are too long".to_string()));
+            }
+            let mut request = vec![0x01, user.len() as u8];
+            request.extend_from_slice(user.as_bytes());
+            request.push(password.len() as u8);
+            request.extend_from_slice(password.as_bytes());
+            stream.write_all(&request).await?;
+
+            stream.read_exact(&mut reply).await?;
+            if reply[1] != 0x00 {
+                return Err(GenericError("SOCKS5 proxy authentication failed".to_string()));
+            }
+        }
+        _ => {
+            return Err(GenericError(
+                "SOCKS5 proxy rejected the authentication methods".to_string(),
+            ));
+        }
+    }
+
+    let mut request = vec![0x05, 0x01, 0x00];
+    match IpAddr::from_str(host) {
+        Ok(IpAddr::V4(ip)) => {
+            request.push(0x01);
+            request.extend_from_slice(&ip.octets());
+        }
+        Ok(IpAddr::V6(ip)) => {
+            request.push(0x04);
+            request.extend_from_slice(&ip.octets());
+        }
+        Err(_) => {
+            if host.len() > 255 {
+                return Err(GenericError("SOCKS5 destination host is too long".to_string()));
+            }
+            request.push(0x03);
+            request.push(host.len() as u8);
+            request.extend_from_slice(host.as_bytes());
+        }
+    }
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request).await?;
+
+    let mut header = [0u8; 4];
+    stream.read_exact(&mut header).await?;
+    if header[1] != 0x00 {
+        return Err(GenericError(format!("SOCKS5 proxy failed to connect (code {})", header[1])));
+    }
+
+    // Skip the bound address, which isn't needed
+    let address_len = match header[3] {
+        0x01 => 4,
+        0x04 => 16,
+        0x03 => stream.read_u8().await? as usize,
+        t => return Err(GenericError(format!("Invalid SOCKS5 address type {t}"))),
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound).await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    fn uri(s: &str) -> Uri {
+        Uri::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn matches_bypass_entries() {
+        let bypass = "localhost, .internal.test, *.example.com, 10.0.0.0/8, ::1, api.test:8443";
+        assert!(bypasses(bypass, &uri("http://localhost:50051")));
+        assert!(bypasses(bypass, &uri("https://svc.internal.test")));
+        assert!(bypasses(bypass, &uri("https://internal.test")));
+        assert!(bypasses(bypass, &uri("https://a.b.example.com")));
+        assert!(bypasses(bypass, &uri("http://10.1.2.3:9000")));
+        assert!(bypasses(bypass, &uri("http://[::1]:9000")));
+        assert!(bypasses(bypass, &uri("https://api.test:8443")));
+
+        assert!(!bypasses(bypass, &uri("https://api.test:443")));
+        assert!(!bypasses(bypass, &uri("https://notexample.com")));
+        assert!(!bypasses(bypass, &uri("http://11.0.0.1")));
+        assert!(bypasses("*", &uri("https://anything.dev")));
+        assert!(!bypasses("", &uri("https://anything.dev")));
+    }
+
+    #[test]
+    fn picks_proxy_by_scheme() {
+        let proxy = GrpcProxy {
+            http: "localhost:8080".to_string(),
+            https: "socks5://proxy.test".to_string(),
+            bypass: "localhost".to_string(),
+            ..Default::default()
+        };
+
+        let target = proxy.for_uri(&uri("http://grpc.test")).unwrap().unwrap();
+        assert_eq!(target.kind, ProxyKind::Connect);
+        assert_eq!(target.address, uri("http://localhost:8080"));
+
+        let target = proxy.for_uri(&uri("https://grpc.test")).unwrap().unwrap();
+        assert_eq!(target.kind, ProxyKind::Socks5);
+        assert_eq!(target.address, uri("http://proxy.test:1080"));
+
+        assert!(proxy.for_uri(&uri("http://localhost:50051")).unwrap().is_none());
+        assert!(GrpcProxy::default().for_uri(&uri("http://grpc.test")).unwrap().is_none());
+        let unsupported = GrpcProxy { http: "ftp://proxy.test".to_string(), ..Default::default() };
+        assert!(unsupported.for_uri(&uri("http://grpc.test")).is_err());
+    }
+
+    #[tokio::test]
+    async fn tunnels_through_http_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 1024];
+            let n = socket.read(&mut buf).await.unwrap();
+            socket.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n").await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let auth = GrpcProxyAuth { user: "user".to_string(), password: "pass".to_string() };
+        connect_tunnel(&mut stream, "grpc.test", 443, Some(&auth)).await.unwrap();
+
+        let request = server.await.unwrap();
+        assert!(request.starts_with("CONNECT grpc.test:443 HTTP/1.1\r\n"));
+        assert!(request.contains("Proxy-Authorization: Basic dXNlcjpwYXNz\r\n"));
+    }
+
+    #[tokio::test]
+    async fn reports_rejected_http_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            socket.write_all(b"HTTP/1.1 407 Proxy Authentication Required\r\n\r\n").await.unwrap();
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let err = connect_tunnel(&mut stream, "grpc.test", 443, None).await.unwrap_err();
+        assert_eq!(err.to_string(), "GRPC error: Proxy authentication required");
+    }
+
+    #[tokio::test]
+    async fn tunnels_through_socks5_with_auth() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut greeting = [0u8; 4];
+            socket.read_exact(&mut greeting).await.unwrap();
+            assert_eq!(greeting, [0x05, 0x02, 0x00, 0x02]);
+            socket.write_all(&[0x05, 0x02]).await.unwrap();
+
+            let mut credentials = [0u8; 11];
+            socket.read_exact(&mut credentials).await.unwrap();
+            assert_eq!(&credentials, b"\x01\x04user\x04pass");
+            socket.write_all(&[0x01, 0x00]).await.unwrap();
+
+            let mut request = [0u8; 16];
+            socket.read_exact(&mut request).await.unwrap();
+            socket.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0x1f, 0x90]).await.unwrap();
+            request
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let auth = GrpcProxyAuth { user: "user".to_string(), password: "pass".to_string() };
+        socks5_tunnel(&mut stream, "grpc.test", 443, Some(&auth)).await.unwrap();
+
+        let request = server.await.unwrap();
+        assert_eq!(&request[..5], &[0x05, 0x01, 0x00, 0x03, 9]);
+        assert_eq!(&request[5..14], b"grpc.test");
+        assert_eq!(&request[14..], &443u16.to_be_bytes());
+    }
+}
@@ -1,8 +1,10 @@
 use crate::any::collect_any_types;
 use crate::client::AutoReflectionClient;
+use crate::dns::GrpcDnsOverrides;
 use crate::error::Error::GenericError;
 use crate::error::Result;
 use crate::manager::GrpcConfig;
+use crate::proxy::GrpcProxy;
 use anyhow::anyhow;
 use async_recursion::async_recursion;
 use log::{debug, info, warn};
@@ -22,13 +24,33 @@ use tonic::transport::Uri;
 use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
 use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
 use yaak_common::command::new_xplatform_command;
-use yaak_tls::ClientCertificateConfig;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig};
+
+/// File extensions of compiled `FileDescriptorSet`s, which are loaded as-is instead of compiled
+const DESCRIPTOR_SET_EXTENSIONS: [&str; 4] = ["binpb", "pb", "protoset", "desc"];
+
+pub(crate) fn is_descriptor_set_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| DESCRIPTOR_SET_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+}
 
 pub async fn fill_pool_from_files(
     config: &GrpcConfig,
     paths: &Vec<PathBuf>,
 ) -> Result<DescriptorPool> {
     let mut pool = DescriptorPool::new();
+
+    let (descriptor_sets, paths): (Vec<&PathBuf>, Vec<&PathBuf>) =
+        paths.iter().partition(|p| p.is_file() && is_descriptor_set_path(p));
+    for p in descriptor_sets {
+        let bytes = fs::read(p).await?;
+        let fdp = FileDescriptorSet::decode(bytes.deref()).map_err(|e| {
+            GenericError(format!("Failed to read descriptor set {}: {e}", p.display()))
+        })?;
+        pool.add_file_descriptor_set(fdp)?;
+    }
+
     let random_file_name = format!("{}.desc", uuid::Uuid::new_v4());
     let desc_path = temp_dir().join(random_file_name);
 
@@ -81,6 +103,11 @@ pub async fn fill_pool_from_files(
         include_protos.insert(p.to_string_lossy().to_string());
     }
 
+    // Only descriptor sets and import folders were given, so there's nothing to compile
+    if include_protos.is_empty() {
+        return Ok(pool);
+    }
+
     for d in include_dirs.clone() {
         args.push("-I".to_string());
         args.push(d);
@@ -119,9 +146,19 @@ pub async fn fill_pool_from_reflection(
     metadata: &BTreeMap<String, String>,
     validate_certificates: bool,
     client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
 ) -> Result<DescriptorPool> {
     let mut pool = DescriptorPool::new();
-    let mut client = AutoReflectionClient::new(uri, validate_certificates, client_cert)?;
+    let mut client = AutoReflectionClient::new(
+        uri,
+        validate_certificates,
+        client_cert,
+        ca_certificates,
+        proxy,
+        dns_overrides,
+    )?;
 
     for service in list_services(&mut client, metadata).await? {
         if service == "grpc.reflection.v1alpha.ServerReflection" {
@@ -192,6 +229,8 @@ pub(crate) async fn reflect_types_for_message(
     json: &str,
     metadata: &BTreeMap<String, String>,
     client_cert: Option<ClientCertificateConfig>,
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
 ) -> Result<()> {
     // 1. Collect all Any types in the JSON
     let mut extra_types = Vec::new();
@@ -201,7 +240,7 @@ pub(crate) async fn reflect_types_for_message(
         return Ok(()); // nothing to do
     }
 
-    let mut client = AutoReflectionClient::new(uri, false, client_cert)?;
+    let mut client = AutoReflectionClient::new(uri, false, client_cert, &[], proxy, dns_overrides)?;
     for extra_type in extra_types {
         {
             let guard = pool.read().await;
@@ -239,6 +278,8 @@ pub(crate) async fn reflect_types_for_dynamic_message(
     message: &DynamicMessage,
     metadata: &BTreeMap<String, String>,
     client_cert: Option<ClientCertificateConfig>,
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
 ) -> Result<()> {
     let mut extra_types = HashSet::new();
     collect_any_types_from_dynamic_message(message, &mut extra_types);
@@ -247,7 +288,7 @@ pub(crate) async fn reflect_types_for_dynamic_message(
         return Ok(());
     }
 
-    let mut client = AutoReflectionClient::new(uri, false, client_cert)?;
+    let mut client = AutoReflectionClient::new(uri, false, client_cert, &[], proxy, dns_overrides)?;
     for extra_type in extra_types {
         {
             let guard = pool.read().await;
@@ -527,3 +568,60 @@ fn find_parent_proto_dir(start_path: impl AsRef<Path>) -> Option<PathBuf> {
         dir = parent.to_path_buf();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_types::{DescriptorProto, MethodDescriptorProto, ServiceDescriptorProto};
+
+    fn greeter_descriptor_set() -> FileDescriptorSet {
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("greeter.proto".to_string()),
+                package: Some("greeter".to_string()),
+                syntax: Some("proto3".to_string()),
+                message_type: vec![DescriptorProto {
+                    name: Some("Empty".to_string()),
+                    ..Default::default()
+                }],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("Greeter".to_string()),
+                    method: vec![MethodDescriptorProto {
+                        name: Some("Hello".to_string()),
+                        input_type: Some(".greeter.Empty".to_string()),
+                        output_type: Some(".greeter.Empty".to_string()),
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn detects_descriptor_set_paths() {
+        assert!(is_descriptor_set_path(Path::new("api/greeter.binpb")));
+        assert!(is_descriptor_set_path(Path::new("api/greeter.PB")));
+        assert!(is_descriptor_set_path(Path::new("api/greeter.protoset")));
+        assert!(!is_descriptor_set_path(Path::new("api/greeter.proto")));
+        assert!(!is_descriptor_set_path(Path::new("api")));
+    }
+
+    #[tokio::test]
+    async fn loads_descriptor_sets_without_compiling() {
+        let path = temp_dir().join(format!("{}.binpb", uuid::Uuid::new_v4()));
+        fs::write(&path, greeter_descriptor_set().encode_to_vec()).await.unwrap();
+
+        // The compiler paths don't exist, so this would fail if it tried to run protoc
+        let config = GrpcConfig {
+            protoc_include_dir: PathBuf::from("/nonexistent/include"),
+            protoc_bin_path: PathBuf::from("/nonexistent/protoc"),
+        };
+        let pool = fill_pool_from_files(&config, &vec![path.clone()]).await;
+        fs::remove_file(&path).await.unwrap();
+
+        let pool = pool.unwrap();
+        assert!(pool.get_service_by_name("greeter.Greeter").is_some());
+    }
+}
@@ -0,0 +1,297 @@
+use crate::SERIALIZE_OPTIONS;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    Any, DescriptorProto, FieldDescriptorProto, FileDescriptorProto, MessageOptions,
+};
+use std::sync::LazyLock;
+use tonic::Code;
+
+/// A detail message attached to a failed call's `google.rpc.Status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusDetail {
+    pub type_name: String,
+    /// The message as JSON, or `None` when no descriptor for the type was available
+    pub value: Option<serde_json::Value>,
+    /// The encoded message, as base64
+    pub raw: String,
+}
+
+/// `google.rpc.Status`, as sent in the `grpc-status-details-bin` trailer
+#[derive(Clone, PartialEq, Message)]
+struct RpcStatus {
+    #[prost(int32, tag = "1")]
+    code: i32,
+    #[prost(string, tag = "2")]
+    message: String,
+    #[prost(message, repeated, tag = "3")]
+    details: Vec<Any>,
+}
+
+/// Descriptors for the well-known detail types from `google/rpc/error_details.proto`, which
+/// servers rarely expose through reflection or ship with their proto files
+static ERROR_DETAILS_POOL: LazyLock<DescriptorPool> = LazyLock::new(|| {
+    let mut pool = DescriptorPool::global();
+    pool.add_file_descriptor_proto(error_details_file()).expect("error_details.proto to be valid");
+    pool
+});
+
+/// Decode the details of an encoded `google.rpc.Status`, looking message types up in `pool` first
+/// and then in the well-known error detail types
+pub(crate) fn decode_status_details(pool: &DescriptorPool, details: &[u8]) -> Vec<StatusDetail> {
+    let status = match RpcStatus::decode(details) {
+        Ok(s) => s,
+        Err(_) => return Vec::new(),
+    };
+
+    status
+        .details
+        .into_iter()
+        .map(|any| {
+            let type_name = any.type_url.rsplit('/').next().unwrap_or_default().to_string();
+            let value = pool
+                .get_message_by_name(&type_name)
+                .or_else(|| ERROR_DETAILS_POOL.get_message_by_name(&type_name))
+                .and_then(|desc| message_to_json(desc, &any.value));
+            StatusDetail { type_name, value, raw: STANDARD.encode(&any.value) }
+        })
+        .collect()
+}
+
+/// Encode a `google.rpc.Status` carrying the given `(type name, encoded message)` details, for
+/// protocols that report details separately from the status
+pub(crate) fn encode_status_details(
+    code: Code,
+    message: &str,
+    details: Vec<(String, Vec<u8>)>,
+) -> Vec<u8> {
+    RpcStatus {
+        code: code as i32,
+        message: message.to_string(),
+        details: details
+            .into_iter()
+            .map(|(type_name, value)| Any {
+                type_url: format!("type.googleapis.com/{type_name}"),
+                value,
+            })
+            .collect(),
+    }
+    .encode_to_vec()
+}
+
+fn message_to_json(desc: MessageDescriptor, bytes: &[u8]) -> Option<serde_json::Value> {
+    let message = DynamicMessage::decode(desc, bytes).ok()?;
+    message.serialize_with_options(serde_json::value::Serializer, SERIALIZE_OPTIONS).ok()
+}
+
+fn error_details_file() -> FileDescriptorProto {
+    let string = |name: &str, number: i32| field(name, number, Type::String, None);
+    let messages = |name: &str, number: i32, type_name: &str| FieldDescriptorProto {
+        label: Some(Label::Repeated as i32),
+        ..field(name, number, Type::Message, Some(type_name))
+    };
+    let message = |name: &str, fields: Vec<FieldDescriptorProto>| DescriptorProto {
+        name: Some(name.to_string()),
+        field: fields,
+        ..Default::default()
+    };
+    let with_nested = |parent: DescriptorProto, nested: DescriptorProto| DescriptorProto {
+        nested_type: vec![nested],
+        ..parent
+    };
+
+    let metadata_entry = DescriptorProto {
+        options: Some(MessageOptions { map_entry: Some(true), ..Default::default() }),
+        ..message("MetadataEntry", vec![string("key", 1), string("value", 2)])
+    };
+
+    FileDescriptorProto {
+        name: Some("google/rpc/error_details.proto".to_string()),
+        package: Some("google.rpc".to_string()),
+        dependency: vec!["google/protobuf/duration.proto".to_string()],
+        syntax: Some("proto3".to_string()),
+        message_type: vec![
+            with_nested(
+                message(
+                    "ErrorInfo",
+                    vec![
+                        string("reason", 1),
+                        string("domain", 2),
+                        messages("metadata", 3, ".google.rpc.ErrorInfo.MetadataEntry"),
+                    ],
+                ),
+                metadata_entry,
+            ),
+            message(
+                "RetryInfo",
+                vec![field(
+                    "retry_delay",
+                    1,
+                    Type::Message,
+                    Some(".google.protobuf.Duration"),
+                )],
+            ),
+            message(
+                "DebugInfo",
+                vec![
+                    FieldDescriptorProto {
+                        label: Some(Label::Repeated as i32),
+                        ..string("stack_entries", 1)
+                    },
+                    string("detail", 2),
+                ],
+            ),
+            with_nested(
+                message(
+                    "QuotaFailure",
+                    vec![messages(
+                        "violations",
+                        1,
+                        ".google.rpc.QuotaFailure.Violation",
+                    )],
+                ),
+                message("Violation", vec![string("subject", 1), string("description", 2)]),
+            ),
+            with_nested(
+                message(
+                    "PreconditionFailure",
+                    vec![messages(
+                        "violations",
+                        1,
+                        ".google.rpc.PreconditionFailure.Violation",
+                    )],
+                ),
+                message(
+                    "Violation",
+                    vec![
+                        string("type", 1),
+                        string("subject", 2),
+                        string("description", 3),
+                    ],
+                ),
+            ),
+            with_nested(
+                message(
+                    "BadRequest",
+                    vec![messages(
+                        "field_violations",
+                        1,
+                        ".google.rpc.BadRequest.FieldViolation",
+                    )],
+                ),
+                message("FieldViolation", vec![string("field", 1), string("description", 2)]),
+            ),
+            message("RequestInfo", vec![string("request_id", 1), string("serving_data", 2)]),
+            message(
+                "ResourceInfo",
+                vec![
+                    string("resource_type", 1),
+                    string("resource_name", 2),
+                    string("owner", 3),
+                    string("description", 4),
+                ],
+            ),
+            with_nested(
+                message("Help", vec![messages("links", 1, ".google.rpc.Help.Link")]),
+                message("Link", vec![string("description", 1), string("url", 2)]),
+            ),
+            message("LocalizedMessage", vec![string("locale", 1), string("message", 2)]),
+        ],
+        ..Default::default()
+    }
+}
+
+fn field(name: &str, number: i32, r#type: Type, type_name: Option<&str>) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.to_string()),
+        number: Some(number),
+        label: Some(Label::Optional as i32),
+        r#type: Some(r#type as i32),
+        type_name: type_name.map(|n| n.to_string()),
+        json_name: Some(json_name(name)),
+        ..Default::default()
+    }
+}
+
+fn json_name(name: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in name.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_status(details: Vec<Any>) -> Vec<u8> {
+        RpcStatus { code: 3, message: "invalid".to_string(), details }.encode_to_vec()
+    }
+
+    fn any_of(type_name: &str, message: DynamicMessage) -> Any {
+        Any { type_url: format!("type.googleapis.com/{type_name}"), value: message.encode_to_vec() }
+    }
+
+    #[test]
+    fn decodes_well_known_error_details() {
+        let desc = ERROR_DETAILS_POOL.get_message_by_name("google.rpc.BadRequest").unwrap();
+        let bad_request = DynamicMessage::deserialize(
+            desc,
+            serde_json::json!({
+                "fieldViolations": [{ "field": "name", "description": "required" }]
+            }),
+        )
+        .unwrap();
+
+        let details = decode_status_details(
+            &DescriptorPool::new(),
+            &encode_status(vec![any_of("google.rpc.BadRequest", bad_request)]),
+        );
+
+        assert_eq!(details.len(), 1);
+        assert_eq!(details[0].type_name, "google.rpc.BadRequest");
+        assert_eq!(
+            details[0].value,
+            Some(serde_json::json!({
+                "fieldViolations": [{ "field": "name", "description": "required" }]
+            }))
+        );
+    }
+
+    #[test]
+    fn keeps_raw_bytes_of_unknown_details() {
+        let details = decode_status_details(
+            &DescriptorPool::new(),
+            &encode_status(vec![Any {
+                type_url: "type.googleapis.com/acme.Unknown".to_string(),
+                value: vec![0, 1, 2, 255],
+            }]),
+        );
+
+        assert_eq!(
+            details,
+            vec![StatusDetail {
+                type_name: "acme.Unknown".to_string(),
+                value: None,
+                raw: "AAEC/w==".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_status() {
+        assert!(decode_status_details(&DescriptorPool::new(), &[0xff, 0xff]).is_empty());
+    }
+}
@@ -1,29 +1,99 @@
+use crate::dns::{GrpcDnsOverrides, OverrideResolver};
 use crate::error::Result;
+use crate::proxy::{GrpcProxy, ProxyConnector};
 use hyper_rustls::{HttpsConnector, HttpsConnectorBuilder};
 use hyper_util::client::legacy::Client;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::rt::TokioExecutor;
 use log::info;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 use tonic::body::BoxBody;
-use yaak_tls::{ClientCertificateConfig, get_tls_config};
+use tonic::codegen::StdError;
+use tonic::codegen::http::{Request, Response};
+use tonic_web::{GrpcWebCall, GrpcWebClientService};
+use tower_service::Service;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig, get_tls_config};
 
 // I think ALPN breaks this because we're specifying http2_only
 const WITH_ALPN: bool = false;
 
+pub(crate) type HttpClient<B> = Client<HttpsConnector<ProxyConnector>, B>;
+
+/// Protocol used for calls on a connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireProtocol {
+    /// Native gRPC over HTTP/2
+    #[default]
+    Grpc,
+    /// gRPC-Web over HTTP/1.1, for servers behind gateways like Envoy
+    GrpcWeb,
+    /// Connect unary calls with JSON messages
+    ConnectJson,
+    /// Connect unary calls with binary protobuf messages
+    ConnectProto,
+}
+
+/// Transport for calls on a connection. Native gRPC and gRPC-Web calls go through the channel as
+/// a tonic service, while Connect calls are plain HTTP requests made with its client.
+#[derive(Clone)]
+pub(crate) enum GrpcChannel {
+    Grpc(HttpClient<BoxBody>),
+    GrpcWeb(GrpcWebClientService<HttpClient<GrpcWebCall<BoxBody>>>),
+    Connect {
+        client: HttpClient<BoxBody>,
+        json: bool,
+    },
+}
+
+impl Service<Request<BoxBody>> for GrpcChannel {
+    type Response = Response<BoxBody>;
+    type Error = StdError;
+    type Future =
+        Pin<Box<dyn Future<Output = std::result::Result<Self::Response, StdError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), StdError>> {
+        match self {
+            GrpcChannel::Grpc(client) | GrpcChannel::Connect { client, .. } => {
+                Service::poll_ready(client, cx).map_err(Into::into)
+            }
+            GrpcChannel::GrpcWeb(client) => client.poll_ready(cx).map_err(Into::into),
+        }
+    }
+
+    fn call(&mut self, req: Request<BoxBody>) -> Self::Future {
+        match self {
+            GrpcChannel::Grpc(client) | GrpcChannel::Connect { client, .. } => {
+                let response = client.request(req);
+                Box::pin(async move { Ok(response.await?.map(tonic::body::boxed)) })
+            }
+            GrpcChannel::GrpcWeb(client) => {
+                let response = client.call(req);
+                Box::pin(async move { Ok(response.await?.map(tonic::body::boxed)) })
+            }
+        }
+    }
+}
+
 pub(crate) fn get_transport(
     validate_certificates: bool,
     client_cert: Option<ClientCertificateConfig>,
-) -> Result<Client<HttpsConnector<HttpConnector>, BoxBody>> {
-    let tls_config = get_tls_config(validate_certificates, WITH_ALPN, client_cert.clone())?;
-
-    let mut http = HttpConnector::new();
-    http.enforce_http(false);
-
-    let connector = HttpsConnectorBuilder::new()
-        .with_tls_config(tls_config)
-        .https_or_http()
-        .enable_http2()
-        .build();
+    ca_certificates: &[CaCertificateConfig],
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
+    connect_timeout: Option<Duration>,
+) -> Result<HttpClient<BoxBody>> {
+    let connector = get_connector(
+        validate_certificates,
+        client_cert.clone(),
+        ca_certificates,
+        proxy.clone(),
+        dns_overrides.clone(),
+        connect_timeout,
+        false,
+    )?;
 
     let client = Client::builder(TokioExecutor::new())
         .pool_max_idle_per_host(0)
@@ -31,10 +101,132 @@ pub(crate) fn get_transport(
         .build(connector);
 
     info!(
-        "Created gRPC client validate_certs={} client_cert={}",
+        "Created gRPC client validate_certs={} client_cert={} ca_certs={} proxy={} dns_overrides={}",
         validate_certificates,
-        client_cert.is_some()
+        client_cert.is_some(),
+        ca_certificates.len(),
+        proxy.is_some(),
+        !dns_overrides.is_empty()
     );
 
     Ok(client)
 }
+
+/// Build the transport for calls made with `protocol`. Server reflection always goes through
+/// [`get_transport`], since neither gRPC-Web nor Connect can carry its bidirectional stream.
+pub(crate) fn get_channel(
+    validate_certificates: bool,
+    client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
+    connect_timeout: Option<Duration>,
+    protocol: WireProtocol,
+) -> Result<GrpcChannel> {
+    if protocol == WireProtocol::Grpc {
+        return Ok(GrpcChannel::Grpc(get_transport(
+            validate_certificates,
+            client_cert,
+            ca_certificates,
+            proxy,
+            dns_overrides,
+            connect_timeout,
+        )?));
+    }
+
+    let connector = get_connector(
+        validate_certificates,
+        client_cert.clone(),
+        ca_certificates,
+        proxy.clone(),
+        dns_overrides.clone(),
+        connect_timeout,
+        true,
+    )?;
+    let mut client = Client::builder(TokioExecutor::new());
+    client.pool_max_idle_per_host(0);
+
+    info!(
+        "Created {:?} client validate_certs={} client_cert={} ca_certs={} proxy={} dns_overrides={}",
+        protocol,
+        validate_certificates,
+        client_cert.is_some(),
+        ca_certificates.len(),
+        proxy.is_some(),
+        !dns_overrides.is_empty()
+    );
+
+    Ok(match protocol {
+        WireProtocol::ConnectJson | WireProtocol::ConnectProto => GrpcChannel::Connect {
+            client: client.build(connector),
+            json: protocol == WireProtocol::ConnectJson,
+        },
+        _ => GrpcChannel::GrpcWeb(GrpcWebClientService::new(client.build(connector))),
+    })
+}
+
+fn get_connector(
+    validate_certificates: bool,
+    client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    proxy: Option<GrpcProxy>,
+    dns_overrides: GrpcDnsOverrides,
+    connect_timeout: Option<Duration>,
+    http1: bool,
+) -> Result<HttpsConnector<ProxyConnector>> {
+    let tls_config =
+        get_tls_config(validate_certificates, WITH_ALPN, client_cert, ca_certificates)?;
+
+    let mut http = HttpConnector::new_with_resolver(OverrideResolver::new(dns_overrides));
+    http.enforce_http(false);
+    http.set_connect_timeout(connect_timeout);
+
+    let builder = HttpsConnectorBuilder::new().with_tls_config(tls_config).https_or_http();
+    let connector = match http1 {
+        true => builder.enable_http1().wrap_connector(ProxyConnector::new(http, proxy)),
+        false => builder.enable_http2().wrap_connector(ProxyConnector::new(http, proxy)),
+    };
+    Ok(connector)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn sends_grpc_web_over_http1() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = socket.read(&mut buf).await.unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let mut channel = get_channel(
+            false,
+            None,
+            &[],
+            None,
+            GrpcDnsOverrides::default(),
+            None,
+            WireProtocol::GrpcWeb,
+        )
+        .unwrap();
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("http://{addr}/greeter.Greeter/Hello"))
+            .header("content-type", "application/grpc")
+            .body(tonic::body::empty_body())
+            .unwrap();
+        // The server hangs up without responding, so only the request matters
+        let _ = channel.call(req).await;
+
+        let request = server.await.unwrap().to_lowercase();
+        assert!(request.starts_with("post /greeter.greeter/hello http/1.1\r\n"));
+        assert!(request.contains("content-type: application/grpc-web"));
+    }
+}
@@ -0,0 +1,448 @@
+use crate::error::Error::InvalidMessage;
+use crate::error::Result;
+use crate::example::is_synthetic_oneof;
+use prost_reflect::{
+    Cardinality, DynamicMessage, FieldDescriptor, Kind, MessageDescriptor, OneofDescriptor,
+};
+use serde::Serialize;
+use serde_json::{Deserializer, Value};
+use std::fmt::{Display, Formatter};
+
+/// A problem found when checking a JSON message against its descriptor
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationError {
+    /// Location of the problem, like `items[0].name`, or empty for the message itself
+    pub path: String,
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.path.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.path, self.message),
+        }
+    }
+}
+
+/// Check a JSON message against the descriptor it will be encoded with, so mistakes can be
+/// reported by field before the call is made. This covers unknown fields, values of the wrong
+/// type, unknown enum values, oneofs with more than one field set, and missing required fields
+/// and oneofs.
+pub fn validate_message(desc: &MessageDescriptor, value: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    validate_message_value(desc, value, "", &mut errors);
+    errors
+}
+
+/// Parse a JSON message for `desc`, validating it first so problems are reported with the paths
+/// of the fields involved rather than as the first encoding failure
+pub(crate) fn parse_message(desc: MessageDescriptor, message: &str) -> Result<DynamicMessage> {
+    let value: Value = serde_json::from_str(message)?;
+    let errors = validate_message(&desc, &value);
+    if !errors.is_empty() {
+        return Err(InvalidMessage(errors));
+    }
+
+    let mut deserializer = Deserializer::from_str(message);
+    let message = DynamicMessage::deserialize(desc, &mut deserializer)?;
+    deserializer.end()?;
+    Ok(message)
+}
+
+fn validate_message_value(
+    desc: &MessageDescriptor,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    if let Some(expected) = validate_well_known(desc.full_name(), value) {
+        if !expected.is_empty() {
+            push(errors, path, format!("expected {expected}"));
+        }
+        return;
+    }
+
+    let Value::Object(fields) = value else {
+        push(errors, path, format!("expected an object for {}", desc.full_name()));
+        return;
+    };
+
+    for (name, value) in fields {
+        let Some(field) =
+            desc.get_field_by_name(name).or_else(|| desc.get_field_by_json_name(name))
+        else {
+            push(errors, &join(path, name), format!("unknown field for {}", desc.full_name()));
+            continue;
+        };
+        // Null is accepted for every field and means the default value
+        if value.is_null() {
+            continue;
+        }
+        validate_field(&field, value, &join(path, name), errors);
+    }
+
+    let is_set = |field: &FieldDescriptor| {
+        [field.name(), field.json_name()]
+            .iter()
+            .any(|n| fields.get(*n).map(|v| !v.is_null()).unwrap_or(false))
+    };
+
+    for field in desc.fields() {
+        if field.cardinality() == Cardinality::Required && !is_set(&field) {
+            push(errors, &join(path, field.name()), "missing required field".to_string());
+        }
+    }
+
+    for oneof in desc.oneofs().filter(|o| !is_synthetic_oneof(o)) {
+        let set = oneof.fields().filter(|f| is_set(f)).map(|f| f.name().to_string());
+        let set = set.collect::<Vec<_>>();
+        if set.len() > 1 {
+            let message = format!("only one of {} can be set for oneof", set.join(", "));
+            push(errors, &join(path, oneof.name()), message);
+        } else if set.is_empty() && is_required_oneof(&oneof) {
+            let names = oneof.fields().map(|f| f.name().to_string()).collect::<Vec<_>>();
+            let message = format!("one of {} is required", names.join(", "));
+            push(errors, &join(path, oneof.name()), message);
+        }
+    }
+}
+
+fn validate_field(
+    field: &FieldDescriptor,
+    value: &Value,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    if field.is_map() {
+        let Kind::Message(entry) = field.kind() else {
+            return;
+        };
+        let Value::Object(entries) = value else {
+            push(errors, path, "expected an object".to_string());
+            return;
+        };
+        let key_kind = entry.map_entry_key_field().kind();
+        let value_kind = entry.map_entry_value_field().kind();
+        for (key, value) in entries {
+            let path = format!("{path}[{key:?}]");
+            if let Some(expected) = validate_map_key(&key_kind, key) {
+                push(errors, &path, format!("invalid key, expected {expected}"));
+            }
+            validate_kind(&value_kind, value, &path, errors);
+        }
+        return;
+    }
+
+    if field.is_list() {
+        let Value::Array(items) = value else {
+            push(errors, path, "expected an array".to_string());
+            return;
+        };
+        for (i, item) in items.iter().enumerate() {
+            validate_kind(&field.kind(), item, &format!("{path}[{i}]"), errors);
+        }
+        return;
+    }
+
+    validate_kind(&field.kind(), value, path, errors);
+}
+
+fn validate_kind(kind: &Kind, value: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let expected = match kind {
+        Kind::Message(desc) => return validate_message_value(desc, value, path, errors),
+        Kind::Enum(desc) => match value {
+            Value::String(name) if desc.get_value_by_name(name).is_none() => {
+                let message = format!("unknown value {name:?} for enum {}", desc.full_name());
+                return push(errors, path, message);
+            }
+            Value::String(_) => return,
+            Value::Number(n) if n.as_i64().map(|n| i32::try_from(n).is_ok()).unwrap_or(false) => {
+                return;
+            }
+            _ => format!("a value name of enum {}", desc.full_name()),
+        },
+        _ => match validate_scalar(kind, value) {
+            Some(expected) => expected.to_string(),
+            None => return,
+        },
+    };
+    push(errors, path, format!("expected {expected}"));
+}
+
+/// Check a scalar value, returning a description of what was expected when it doesn't fit. As in
+/// the protobuf JSON mapping, numbers may also be written as strings.
+fn validate_scalar(kind: &Kind, value: &Value) -> Option<&'static str> {
+    let valid = match kind {
+        Kind::Bool => value.is_boolean(),
+        Kind::String => value.is_string(),
+        // Bytes are base64 strings
+        Kind::Bytes => value.is_string(),
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+            integer(value).map(|n| i32::try_from(n).is_ok()).unwrap_or(false)
+        }
+        Kind::Uint32 | Kind::Fixed32 => {
+            integer(value).map(|n| u32::try_from(n).is_ok()).unwrap_or(false)
+        }
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => {
+            integer(value).map(|n| i64::try_from(n).is_ok()).unwrap_or(false)
+        }
+        Kind::Uint64 | Kind::Fixed64 => {
+            integer(value).map(|n| u64::try_from(n).is_ok()).unwrap_or(false)
+        }
+        Kind::Float | Kind::Double => match value {
+            Value::Number(_) => true,
+            Value::String(s) => {
+                matches!(s.as_str(), "NaN" | "Infinity" | "-Infinity") || s.parse::<f64>().is_ok()
+            }
+            _ => false,
+        },
+        Kind::Message(_) | Kind::Enum(_) => true,
+    };
+    if valid {
+        return None;
+    }
+
+    Some(match kind {
+        Kind::Bool => "a boolean",
+        Kind::String => "a string",
+        Kind::Bytes => "a base64 string",
+        Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => "a 32-bit integer",
+        Kind::Uint32 | Kind::Fixed32 => "an unsigned 32-bit integer",
+        Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => "a 64-bit integer",
+        Kind::Uint64 | Kind::Fixed64 => "an unsigned 64-bit integer",
+        _ => "a number",
+    })
+}
+
+fn validate_map_key(kind: &Kind, key: &str) -> Option<&'static str> {
+    match kind {
+        Kind::Bool if key != "true" && key != "false" => Some("true or false"),
+        Kind::String | Kind::Bool => None,
+        _ => validate_scalar(kind, &Value::String(key.to_string())),
+    }
+}
+
+/// Well-known types have their own JSON representations. Returns `None` for other messages, and
+/// otherwise a description of what was expected, which is empty when the value fits.
+fn validate_well_known(full_name: &str, value: &Value) -> Option<&'static str> {
+    let (valid, expected) = match full_name {
+        "google.protobuf.Timestamp" => (value.is_string(), "an RFC 3339 timestamp string"),
+        "google.protobuf.Duration" => (value.is_string(), "a duration string like \"1.5s\""),
+        "google.protobuf.FieldMask" => (value.is_string(), "a comma-separated field mask string"),
+        "google.protobuf.StringValue" => (value.is_string(), "a string"),
+        "google.protobuf.BytesValue" => (value.is_string(), "a base64 string"),
+        "google.protobuf.BoolValue" => (value.is_boolean(), "a boolean"),
+        "google.protobuf.Int32Value" => return validate_scalar(&Kind::Int32, value).or(Some("")),
+        "google.protobuf.UInt32Value" => return validate_scalar(&Kind::Uint32, value).or(Some("")),
+        "google.protobuf.Int64Value" => return validate_scalar(&Kind::Int64, value).or(Some("")),
+        "google.protobuf.UInt64Value" => return validate_scalar(&Kind::Uint64, value).or(Some("")),
+        "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => {
+            return validate_scalar(&Kind::Double, value).or(Some(""));
+        }
+        "google.protobuf.Struct" | "google.protobuf.Empty" => (value.is_object(), "an object"),
+        "google.protobuf.ListValue" => (value.is_array(), "an array"),
+        "google.protobuf.Value" => (true, ""),
+        "google.protobuf.Any" => (
+            value.get("@type").map(|t| t.is_string()).unwrap_or(false),
+            "an object with a string \"@type\"",
+        ),
+        _ => return None,
+    };
+    Some(if valid { "" } else { expected })
+}
+
+/// Whether a oneof is marked required with protovalidate's `(buf.validate.oneof).required`, which
+/// is only known when the server's descriptors include it
+fn is_required_oneof(oneof: &OneofDescriptor) -> bool {
+    oneof.options().extensions().any(|(ext, value)| {
+        ext.full_name() == "buf.validate.oneof"
+            && value
+                .as_message()
+                .and_then(|m| m.get_field_by_name("required"))
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false)
+    })
+}
+
+/// Integer value of a JSON number or numeric string, allowing whole-number floats like `1.0`
+fn integer(value: &Value) -> Option<i128> {
+    match value {
+        Value::Number(n) => n
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+            .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i128)),
+        Value::String(s) => s.parse::<i128>().ok(),
+        _ => None,
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    match path.is_empty() {
+        true => name.to_string(),
+        false => format!("{path}.{name}"),
+    }
+}
+
+fn push(errors: &mut Vec<ValidationError>, path: &str, message: String) {
+    errors.push(ValidationError { path: path.to_string(), message });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost_reflect::DescriptorPool;
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+        FileDescriptorProto, MessageOptions, OneofDescriptorProto,
+    };
+    use serde_json::json;
+
+    fn field(
+        name: &str,
+        number: i32,
+        r#type: Type,
+        type_name: Option<&str>,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(r#type as i32),
+            type_name: type_name.map(|n| n.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn order() -> MessageDescriptor {
+        let mut pool = DescriptorPool::global();
+        pool.add_file_descriptor_proto(FileDescriptorProto {
+            name: Some("shop.proto".to_string()),
+            package: Some("shop".to_string()),
+            syntax: Some("proto3".to_string()),
+            dependency: vec!["google/protobuf/timestamp.proto".to_string()],
+            enum_type: vec![EnumDescriptorProto {
+                name: Some("Size".to_string()),
+                value: vec![EnumValueDescriptorProto {
+                    name: Some("SIZE_LARGE".to_string()),
+                    number: Some(0),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            message_type: vec![DescriptorProto {
+                name: Some("Order".to_string()),
+                field: vec![
+                    field("order_id", 1, Type::Int64, None),
+                    field("size", 2, Type::Enum, Some(".shop.Size")),
+                    FieldDescriptorProto {
+                        label: Some(Label::Repeated as i32),
+                        ..field("items", 3, Type::Message, Some(".shop.Order"))
+                    },
+                    FieldDescriptorProto {
+                        label: Some(Label::Repeated as i32),
+                        ..field("counts", 4, Type::Message, Some(".shop.Order.CountsEntry"))
+                    },
+                    field("placed_at", 5, Type::Message, Some(".google.protobuf.Timestamp")),
+                    FieldDescriptorProto {
+                        oneof_index: Some(0),
+                        ..field("card", 6, Type::String, None)
+                    },
+                    FieldDescriptorProto {
+                        oneof_index: Some(0),
+                        ..field("cash", 7, Type::Bool, None)
+                    },
+                ],
+                nested_type: vec![DescriptorProto {
+                    name: Some("CountsEntry".to_string()),
+                    field: vec![
+                        field("key", 1, Type::Uint32, None),
+                        field("value", 2, Type::Int32, None),
+                    ],
+                    options: Some(MessageOptions { map_entry: Some(true), ..Default::default() }),
+                    ..Default::default()
+                }],
+                oneof_decl: vec![OneofDescriptorProto {
+                    name: Some("payment".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        })
+        .unwrap();
+        pool.get_message_by_name("shop.Order").unwrap()
+    }
+
+    fn paths(errors: Vec<ValidationError>) -> Vec<String> {
+        errors.into_iter().map(|e| e.to_string()).collect()
+    }
+
+    #[test]
+    fn accepts_valid_messages() {
+        let value = json!({
+            "orderId": "12",
+            "size": "SIZE_LARGE",
+            "items": [{ "order_id": 3, "size": 0 }],
+            "counts": { "7": 1 },
+            "placed_at": "2024-01-01T00:00:00Z",
+            "card": "visa",
+            "cash": null,
+        });
+        assert_eq!(validate_message(&order(), &value), vec![]);
+    }
+
+    #[test]
+    fn reports_errors_with_field_paths() {
+        let value = json!({
+            "order_id": "twelve",
+            "size": "SIZE_HUGE",
+            "items": [{ "nope": true }],
+            "counts": { "x": "1" },
+            "placed_at": 12,
+            "card": "visa",
+            "cash": true,
+        });
+        let mut errors = paths(validate_message(&order(), &value));
+        errors.sort();
+        assert_eq!(
+            errors,
+            vec![
+                "counts[\"x\"]: invalid key, expected an unsigned 32-bit integer",
+                "items[0].nope: unknown field for shop.Order",
+                "order_id: expected a 64-bit integer",
+                "payment: only one of card, cash can be set for oneof",
+                "placed_at: expected an RFC 3339 timestamp string",
+                "size: unknown value \"SIZE_HUGE\" for enum shop.Size",
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_messages() {
+        let err = parse_message(order(), r#"{"size": true, "extra": 1}"#).unwrap_err();
+        let InvalidMessage(mut errors) = err else {
+            panic!("expected validation errors, got {err:?}");
+        };
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(
+            paths(errors),
+            vec![
+                "extra: unknown field for shop.Order",
+                "size: expected a value name of enum shop.Size",
+            ]
+        );
+
+        assert!(parse_message(order(), r#"{"size": "SIZE_LARGE"}"#).is_ok());
+    }
+
+    #[test]
+    fn requires_an_object() {
+        assert_eq!(
+            paths(validate_message(&order(), &json!([]))),
+            vec!["expected an object for shop.Order".to_string()]
+        );
+    }
+}
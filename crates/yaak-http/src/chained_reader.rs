@@ -1,13 +1,18 @@
+use std::future::Future;
 use std::io;
 use std::pin::Pin;
 use std::task::{Context, Poll};
 use tokio::io::{AsyncRead, ReadBuf};
 
+type OpenFile = Pin<Box<dyn Future<Output = io::Result<tokio::fs::File>> + Send>>;
+
 /// A stream that chains multiple AsyncRead sources together
 pub(crate) struct ChainedReader {
     readers: Vec<ReaderType>,
     current_index: usize,
     current_reader: Option<Box<dyn AsyncRead + Send + Unpin + 'static>>,
+    /// File that is being opened before it becomes the current reader
+    opening: Option<OpenFile>,
 }
 
 #[derive(Clone)]
@@ -18,7 +23,7 @@ pub(crate) enum ReaderType {
 
 impl ChainedReader {
     pub(crate) fn new(readers: Vec<ReaderType>) -> Self {
-        Self { readers, current_index: 0, current_reader: None }
+        Self { readers, current_index: 0, current_reader: None, opening: None }
     }
 }
 
@@ -46,6 +51,16 @@ impl AsyncRead for ChainedReader {
                 };
             }
 
+            if let Some(opening) = self.opening.as_mut() {
+                let result = match opening.as_mut().poll(cx) {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => return Poll::Pending,
+                };
+                self.opening = None;
+                self.current_reader = Some(Box::new(result?));
+                continue;
+            }
+
             // We need to get the next reader
             if self.current_index >= self.readers.len() {
                 // No more readers
@@ -61,16 +76,8 @@ impl AsyncRead for ChainedReader {
                     self.current_reader = Some(Box::new(io::Cursor::new(bytes)));
                 }
                 ReaderType::FilePath(path) => {
-                    // We need to handle file opening synchronously in poll_read
-                    // This is a limitation - we'll use blocking file open
-                    match std::fs::File::open(&path) {
-                        Ok(file) => {
-                            // Convert std File to tokio File
-                            let tokio_file = tokio::fs::File::from_std(file);
-                            self.current_reader = Some(Box::new(tokio_file));
-                        }
-                        Err(e) => return Poll::Ready(Err(e)),
-                    }
+                    // Files are opened as they're reached so only one is held open at a time
+                    self.opening = Some(Box::pin(tokio::fs::File::open(path)));
                 }
             }
         }
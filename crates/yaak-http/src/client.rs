@@ -2,9 +2,13 @@ use crate::dns::LocalhostResolver;
 use crate::error::Result;
 use log::{debug, info, warn};
 use reqwest::{Client, ClientBuilder, Proxy, redirect};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use yaak_models::models::DnsOverride;
-use yaak_tls::{ClientCertificateConfig, get_tls_config};
+use std::time::Duration;
+use yaak_models::models::{DnsOverride, HttpVersion};
+use yaak_tls::{
+    CaCertificateConfig, ClientCertificateConfig, TlsHandshakes, get_recording_tls_config,
+};
 
 pub const HTTP2_MAX_RESPONSE_HEADER_LIST_SIZE: u32 = 1024 * 1024;
 
@@ -15,20 +19,27 @@ fn client_builder() -> ClientBuilder {
 #[derive(Clone)]
 pub struct ConfiguredClient {
     inner: Client,
+    tls_handshakes: TlsHandshakes,
 }
 
 impl ConfiguredClient {
     pub(crate) fn build_default() -> Result<Self> {
-        Ok(Self { inner: client_builder().build()? })
+        Ok(Self { inner: client_builder().build()?, tls_handshakes: TlsHandshakes::default() })
     }
 
-    pub(crate) fn from_inner(inner: Client) -> Self {
-        Self { inner }
+    pub(crate) fn from_inner(inner: Client, tls_handshakes: TlsHandshakes) -> Self {
+        Self { inner, tls_handshakes }
     }
 
     pub(crate) fn inner(&self) -> &Client {
         &self.inner
     }
+
+    /// Handshakes made by the client, which are only recorded when certificates are
+    /// verified with rustls
+    pub(crate) fn tls_handshakes(&self) -> &TlsHandshakes {
+        &self.tls_handshakes
+    }
 }
 
 /// Build a native-tls connector for maximum compatibility when certificate
@@ -81,13 +92,13 @@ fn build_native_tls_identity(
     Ok(None)
 }
 
-#[derive(Clone)]
+#[derive(Clone, Hash)]
 pub struct HttpConnectionProxySettingAuth {
     pub user: String,
     pub password: String,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Hash)]
 pub enum HttpConnectionProxySetting {
     Disabled,
     System,
@@ -99,13 +110,27 @@ pub enum HttpConnectionProxySetting {
     },
 }
 
-#[derive(Clone)]
+/// Everything that goes into building a client. Clients are cached by a hash of all of these, so
+/// changing any of them gets a new client.
+#[derive(Clone, Hash)]
 pub struct HttpConnectionOptions {
     pub id: String,
     pub validate_certificates: bool,
     pub proxy: HttpConnectionProxySetting,
     pub client_certificate: Option<ClientCertificateConfig>,
+    /// Extra CA certificates trusted when validating server certificates
+    pub ca_certificates: Vec<CaCertificateConfig>,
     pub dns_overrides: Vec<DnsOverride>,
+    pub http_version: HttpVersion,
+    /// Connect every request through this Unix domain socket instead of over TCP
+    pub unix_socket: Option<PathBuf>,
+    /// Maximum time to wait for a connection to be established
+    pub connect_timeout: Option<Duration>,
+    /// Maximum time to wait between reads of the response
+    pub read_timeout: Option<Duration>,
+    /// Keep connections open between requests, for authentication schemes like NTLM that
+    /// authenticate the connection rather than each request
+    pub connection_auth: bool,
 }
 
 impl HttpConnectionOptions {
@@ -124,12 +149,48 @@ impl HttpConnectionOptions {
             .tls_info(true)
             // Disable connection pooling to ensure DNS resolution happens on each request
             // This is needed so we can emit DNS timing events for each request
-            .pool_max_idle_per_host(0);
+            .pool_max_idle_per_host(if self.connection_auth { 1 } else { 0 });
+
+        if let Some(d) = self.connect_timeout.filter(|d| !d.is_zero()) {
+            client = client.connect_timeout(d);
+        }
+        if let Some(d) = self.read_timeout.filter(|d| !d.is_zero()) {
+            client = client.read_timeout(d);
+        }
+
+        // Connection-based auth isn't supported over HTTP/2
+        if self.connection_auth {
+            client = client.http1_only();
+        } else {
+            client = match self.http_version {
+                HttpVersion::Auto => client,
+                HttpVersion::Http1 => client.http1_only(),
+                HttpVersion::Http2 => client.http2_prior_knowledge(),
+                HttpVersion::Http3 => client.http3_prior_knowledge(),
+            };
+        }
 
-        // Configure TLS
-        if self.validate_certificates {
+        // Configure TLS, recording rustls handshakes so their details can be shown on responses
+        let tls_handshakes = TlsHandshakes::default();
+        if self.http_version == HttpVersion::Http3 && !self.connection_auth {
+            // QUIC needs rustls, so native TLS can't be used for legacy servers here
+            let config = get_recording_tls_config(
+                self.validate_certificates,
+                false,
+                self.client_certificate.clone(),
+                &self.ca_certificates,
+                &tls_handshakes,
+            )?;
+            client = client.use_preconfigured_tls(config);
+        } else if self.validate_certificates {
             // Use rustls with platform certificate verification (TLS 1.2+ only)
-            let config = get_tls_config(true, true, self.client_certificate.clone())?;
+            let config = get_recording_tls_config(
+                true,
+                true,
+                self.client_certificate.clone(),
+                &self.ca_certificates,
+                &tls_handshakes,
+            )?;
             client = client.use_preconfigured_tls(config);
         } else {
             // Use native TLS for maximum compatibility (supports TLS 1.0+)
@@ -140,6 +201,11 @@ impl HttpConnectionOptions {
         // Configure DNS resolver - keep a reference to configure per-request
         let resolver = LocalhostResolver::new(self.dns_overrides.clone());
         client = client.dns_resolver(resolver.clone());
+        client = client.connector_layer(resolver.connect_timing_layer());
+
+        if let Some(path) = &self.unix_socket {
+            client = with_unix_socket(client, path)?;
+        }
 
         // Configure proxy
         match self.proxy.clone() {
@@ -155,15 +221,29 @@ impl HttpConnectionOptions {
         }
 
         info!(
-            "Building new HTTP client validate_certificates={} client_cert={}",
+            "Building new HTTP client validate_certificates={} client_cert={} ca_certificates={}",
             self.validate_certificates,
-            self.client_certificate.is_some()
+            self.client_certificate.is_some(),
+            self.ca_certificates.len()
         );
 
-        Ok((ConfiguredClient::from_inner(client.build()?), resolver))
+        Ok((ConfiguredClient::from_inner(client.build()?, tls_handshakes), resolver))
     }
 }
 
+#[cfg(unix)]
+fn with_unix_socket(client: ClientBuilder, path: &Path) -> Result<ClientBuilder> {
+    debug!("Connecting through Unix socket {}", path.display());
+    Ok(client.unix_socket(path.to_path_buf()))
+}
+
+#[cfg(not(unix))]
+fn with_unix_socket(_client: ClientBuilder, _path: &Path) -> Result<ClientBuilder> {
+    Err(crate::error::Error::RequestError(
+        "Unix domain sockets aren't supported on this platform".to_string(),
+    ))
+}
+
 fn build_enabled_proxy(
     http: String,
     https: String,
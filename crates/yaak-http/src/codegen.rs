@@ -0,0 +1,673 @@
+//! Code snippets that send an HTTP request from other languages and tools
+//!
+//! Snippets are generated from a request that's already been rendered, with inherited values and
+//! authentication applied, so they send the same thing Yaak would. Plugins can add more languages
+//! on top of the ones listed in [`CODEGEN_LANGUAGES`].
+
+use crate::curl::to_curl_command;
+use crate::types::{build_url, graphql_body_text};
+use std::collections::BTreeSet;
+use std::path::Path;
+use url::Url;
+use yaak_common::serde::{get_bool, get_str, get_str_map};
+use yaak_models::models::HttpRequest;
+
+const MULTIPART_BOUNDARY: &str = "YaakFormBoundary";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CodegenLanguage {
+    pub name: &'static str,
+    pub label: &'static str,
+}
+
+/// Languages with a built-in generator, in the order they should be listed
+pub const CODEGEN_LANGUAGES: &[CodegenLanguage] = &[
+    CodegenLanguage { name: "curl", label: "cURL" },
+    CodegenLanguage { name: "javascript-fetch", label: "JavaScript (fetch)" },
+    CodegenLanguage { name: "python-requests", label: "Python (requests)" },
+    CodegenLanguage { name: "go-net-http", label: "Go (net/http)" },
+    CodegenLanguage { name: "rust-reqwest", label: "Rust (reqwest)" },
+    CodegenLanguage { name: "http", label: "HTTP" },
+];
+
+/// Generate a snippet for one of the [`CODEGEN_LANGUAGES`], or `None` if there's no built-in
+/// generator for the language
+pub fn generate_snippet(language: &str, r: &HttpRequest) -> Option<String> {
+    let snippet = match language {
+        "curl" => to_curl_command(r),
+        "javascript-fetch" => to_javascript_fetch(r),
+        "python-requests" => to_python_requests(r),
+        "go-net-http" => to_go_net_http(r),
+        "rust-reqwest" => to_rust_reqwest(r),
+        "http" => to_raw_http(r),
+        _ => return None,
+    };
+    Some(snippet)
+}
+
+enum SnippetBody {
+    None,
+    Text(String),
+    Form(Vec<(String, String)>),
+    Multipart(Vec<MultipartField>),
+    File(String),
+}
+
+struct MultipartField {
+    name: String,
+    value: String,
+    /// Path of the file to upload, in place of `value`
+    file: Option<String>,
+    filename: String,
+    content_type: String,
+}
+
+impl SnippetBody {
+    fn uses_files(&self) -> bool {
+        match self {
+            SnippetBody::File(_) => true,
+            SnippetBody::Multipart(fields) => fields.iter().any(|f| f.file.is_some()),
+            _ => false,
+        }
+    }
+}
+
+fn snippet_body(r: &HttpRequest) -> SnippetBody {
+    let enabled_form = || {
+        r.body
+            .get("form")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|p| get_bool(p, "enabled", true))
+            .filter(|p| !get_str(p, "name").is_empty())
+    };
+
+    match r.body_type.as_deref() {
+        None => SnippetBody::None,
+        Some("binary") => match get_str_map(&r.body, "filePath") {
+            "" => SnippetBody::None,
+            file => SnippetBody::File(file.to_string()),
+        },
+        Some("graphql") if r.method.to_lowercase() == "get" => SnippetBody::None,
+        Some("graphql") => SnippetBody::Text(graphql_body_text(&r.body)),
+        Some("application/x-www-form-urlencoded") => SnippetBody::Form(
+            enabled_form()
+                .map(|p| (get_str(p, "name").to_string(), get_str(p, "value").to_string()))
+                .collect(),
+        ),
+        Some("multipart/form-data") => SnippetBody::Multipart(
+            enabled_form()
+                .map(|p| {
+                    let file = Some(get_str(p, "file")).filter(|f| !f.is_empty());
+                    let filename = match get_str(p, "filename") {
+                        "" => file.map(file_name).unwrap_or_default(),
+                        filename => filename.to_string(),
+                    };
+                    MultipartField {
+                        name: get_str(p, "name").to_string(),
+                        value: get_str(p, "value").to_string(),
+                        file: file.map(|f| f.to_string()),
+                        filename,
+                        content_type: get_str(p, "contentType").to_string(),
+                    }
+                })
+                .collect(),
+        ),
+        Some(_) => match get_str_map(&r.body, "text") {
+            "" => SnippetBody::None,
+            text => SnippetBody::Text(text.to_string()),
+        },
+    }
+}
+
+/// Enabled headers, leaving out `Content-Type` for multipart bodies because HTTP clients need to
+/// set it themselves to include the boundary
+fn snippet_headers<'a>(r: &'a HttpRequest, body: &SnippetBody) -> Vec<(&'a str, &'a str)> {
+    r.headers
+        .iter()
+        .filter(|h| h.enabled && !h.name.is_empty())
+        .filter(|h| {
+            !matches!(body, SnippetBody::Multipart(_))
+                || !h.name.eq_ignore_ascii_case("content-type")
+        })
+        .map(|h| (h.name.as_str(), h.value.as_str()))
+        .collect()
+}
+
+fn to_javascript_fetch(r: &HttpRequest) -> String {
+    let body = snippet_body(r);
+    let mut lines = Vec::new();
+    if body.uses_files() {
+        lines.push(r#"import fs from "node:fs";"#.to_string());
+        lines.push(String::new());
+    }
+
+    if let SnippetBody::Multipart(fields) = &body {
+        lines.push("const body = new FormData();".to_string());
+        for f in fields {
+            let Some(file) = &f.file else {
+                lines.push(format!("body.append({}, {});", lit(&f.name), lit(&f.value)));
+                continue;
+            };
+            let blob = match f.content_type.as_str() {
+                "" => format!("await fs.openAsBlob({})", lit(file)),
+                t => format!("await fs.openAsBlob({}, {{ type: {} }})", lit(file), lit(t)),
+            };
+            lines.push(format!("body.append({}, {blob}, {});", lit(&f.name), lit(&f.filename)));
+        }
+        lines.push(String::new());
+    }
+
+    lines.push(format!("const response = await fetch({}, {{", lit(&build_url(r))));
+    lines.push(format!("  method: {},", lit(&r.method.to_uppercase())));
+    let headers = snippet_headers(r, &body);
+    if !headers.is_empty() {
+        lines.push("  headers: {".to_string());
+        for (name, value) in headers {
+            lines.push(format!("    {}: {},", lit(name), lit(value)));
+        }
+        lines.push("  },".to_string());
+    }
+    match &body {
+        SnippetBody::None => {}
+        SnippetBody::Text(text) => lines.push(format!("  body: {},", lit(text))),
+        SnippetBody::Form(fields) => {
+            lines.push("  body: new URLSearchParams([".to_string());
+            for (name, value) in fields {
+                lines.push(format!("    [{}, {}],", lit(name), lit(value)));
+            }
+            lines.push("  ]),".to_string());
+        }
+        SnippetBody::Multipart(_) => lines.push("  body,".to_string()),
+        SnippetBody::File(file) => {
+            lines.push(format!("  body: await fs.openAsBlob({}),", lit(file)));
+        }
+    }
+    lines.push("});".to_string());
+    lines.push(String::new());
+    lines.push("console.log(await response.text());".to_string());
+    lines.join("\n")
+}
+
+fn to_python_requests(r: &HttpRequest) -> String {
+    let body = snippet_body(r);
+    let mut lines = vec![
+        "import requests".to_string(),
+        String::new(),
+        "response = requests.request(".to_string(),
+        format!("    {},", lit(&r.method.to_uppercase())),
+        format!("    {},", lit(&build_url(r))),
+    ];
+
+    let headers = snippet_headers(r, &body);
+    if !headers.is_empty() {
+        lines.push("    headers={".to_string());
+        for (name, value) in headers {
+            lines.push(format!("        {}: {},", lit(name), lit(value)));
+        }
+        lines.push("    },".to_string());
+    }
+    match &body {
+        SnippetBody::None => {}
+        SnippetBody::Text(text) => lines.push(format!("    data={},", lit(text))),
+        SnippetBody::Form(fields) => {
+            lines.push("    data=[".to_string());
+            for (name, value) in fields {
+                lines.push(format!("        ({}, {}),", lit(name), lit(value)));
+            }
+            lines.push("    ],".to_string());
+        }
+        SnippetBody::Multipart(fields) => {
+            lines.push("    files=[".to_string());
+            for f in fields {
+                let part = match &f.file {
+                    None => format!("(None, {})", lit(&f.value)),
+                    Some(file) if f.content_type.is_empty() => {
+                        format!("({}, open({}, \"rb\"))", lit(&f.filename), lit(file))
+                    }
+                    Some(file) => format!(
+                        "({}, open({}, \"rb\"), {})",
+                        lit(&f.filename),
+                        lit(file),
+                        lit(&f.content_type)
+                    ),
+                };
+                lines.push(format!("        ({}, {part}),", lit(&f.name)));
+            }
+            lines.push("    ],".to_string());
+        }
+        SnippetBody::File(file) => lines.push(format!("    data=open({}, \"rb\"),", lit(file))),
+    }
+    lines.push(")".to_string());
+    lines.push(String::new());
+    lines.push("print(response.text)".to_string());
+    lines.join("\n")
+}
+
+fn to_go_net_http(r: &HttpRequest) -> String {
+    let body = snippet_body(r);
+    let mut imports = BTreeSet::from(["fmt", "io", "net/http"]);
+    let mut setup = Vec::new();
+    let body_var = match &body {
+        SnippetBody::None => "nil",
+        SnippetBody::Text(text) => {
+            imports.insert("strings");
+            setup.push(format!("body := strings.NewReader({})", lit(text)));
+            "body"
+        }
+        SnippetBody::Form(fields) => {
+            imports.extend(["net/url", "strings"]);
+            setup.push("form := url.Values{}".to_string());
+            for (name, value) in fields {
+                setup.push(format!("form.Add({}, {})", lit(name), lit(value)));
+            }
+            setup.push("body := strings.NewReader(form.Encode())".to_string());
+            "body"
+        }
+        SnippetBody::Multipart(fields) => {
+            imports.extend(["bytes", "mime/multipart"]);
+            setup.push("body := &bytes.Buffer{}".to_string());
+            setup.push("writer := multipart.NewWriter(body)".to_string());
+            let mut file_count = 0;
+            for f in fields {
+                let Some(file) = &f.file else {
+                    setup.push(format!("writer.WriteField({}, {})", lit(&f.name), lit(&f.value)));
+                    continue;
+                };
+                // Number the variables after the first file so `:=` always declares a new one
+                imports.insert("os");
+                file_count += 1;
+                let n = if file_count == 1 { String::new() } else { file_count.to_string() };
+                setup.push(format!("file{n}, err := os.Open({})", lit(file)));
+                setup.extend(go_check_err());
+                setup.push(format!("defer file{n}.Close()"));
+                setup.push(format!(
+                    "part{n}, err := writer.CreateFormFile({}, {})",
+                    lit(&f.name),
+                    lit(&f.filename)
+                ));
+                setup.extend(go_check_err());
+                setup.push(format!("io.Copy(part{n}, file{n})"));
+            }
+            setup.push("writer.Close()".to_string());
+            "body"
+        }
+        SnippetBody::File(file) => {
+            imports.insert("os");
+            setup.push(format!("body, err := os.Open({})", lit(file)));
+            setup.extend(go_check_err());
+            setup.push("defer body.Close()".to_string());
+            "body"
+        }
+    };
+
+    let mut main = setup;
+    if !main.is_empty() {
+        main.push(String::new());
+    }
+    main.push(format!(
+        "req, err := http.NewRequest({}, {}, {body_var})",
+        lit(&r.method.to_uppercase()),
+        lit(&build_url(r))
+    ));
+    main.extend(go_check_err());
+    for (name, value) in snippet_headers(r, &body) {
+        main.push(format!("req.Header.Add({}, {})", lit(name), lit(value)));
+    }
+    if matches!(body, SnippetBody::Multipart(_)) {
+        main.push(r#"req.Header.Set("Content-Type", writer.FormDataContentType())"#.to_string());
+    }
+    main.push(String::new());
+    main.push("res, err := http.DefaultClient.Do(req)".to_string());
+    main.extend(go_check_err());
+    main.push("defer res.Body.Close()".to_string());
+    main.push(String::new());
+    main.push("resBody, err := io.ReadAll(res.Body)".to_string());
+    main.extend(go_check_err());
+    main.push("fmt.Println(string(resBody))".to_string());
+
+    let mut lines = vec![
+        "package main".to_string(),
+        String::new(),
+        "import (".to_string(),
+    ];
+    lines.extend(imports.iter().map(|i| format!("\t\"{i}\"")));
+    lines.push(")".to_string());
+    lines.push(String::new());
+    lines.push("func main() {".to_string());
+    lines.extend(main.into_iter().map(|l| if l.is_empty() { l } else { format!("\t{l}") }));
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+fn go_check_err() -> [String; 3] {
+    [
+        "if err != nil {".to_string(),
+        "\tpanic(err)".to_string(),
+        "}".to_string(),
+    ]
+}
+
+fn to_rust_reqwest(r: &HttpRequest) -> String {
+    let body = snippet_body(r);
+    let mut lines = vec![
+        "fn main() -> Result<(), Box<dyn std::error::Error>> {".to_string(),
+        "    let client = reqwest::blocking::Client::new();".to_string(),
+    ];
+
+    if let SnippetBody::Multipart(fields) = &body {
+        lines.push("    let form = reqwest::blocking::multipart::Form::new()".to_string());
+        for f in fields {
+            let Some(file) = &f.file else {
+                lines.push(format!("        .text({}, {})", rust_lit(&f.name), rust_lit(&f.value)));
+                continue;
+            };
+            let mut part = format!("reqwest::blocking::multipart::Part::file({})?", rust_lit(file));
+            part.push_str(&format!(".file_name({})", rust_lit(&f.filename)));
+            if !f.content_type.is_empty() {
+                part.push_str(&format!(".mime_str({})?", rust_lit(&f.content_type)));
+            }
+            lines.push(format!("        .part({}, {part})", rust_lit(&f.name)));
+        }
+        if let Some(last) = lines.last_mut() {
+            last.push(';');
+        }
+    }
+
+    let url = rust_lit(&build_url(r));
+    let method = r.method.to_lowercase();
+    lines.push("    let response = client".to_string());
+    lines.push(match method.as_str() {
+        "get" | "post" | "put" | "patch" | "delete" | "head" => format!("        .{method}({url})"),
+        _ => format!(
+            "        .request(reqwest::Method::from_bytes(b{})?, {url})",
+            rust_lit(&r.method.to_uppercase())
+        ),
+    });
+    for (name, value) in snippet_headers(r, &body) {
+        lines.push(format!("        .header({}, {})", rust_lit(name), rust_lit(value)));
+    }
+    match &body {
+        SnippetBody::None => {}
+        SnippetBody::Text(text) => lines.push(format!("        .body({})", rust_lit(text))),
+        SnippetBody::Form(fields) => {
+            lines.push("        .form(&[".to_string());
+            for (name, value) in fields {
+                lines.push(format!("            ({}, {}),", rust_lit(name), rust_lit(value)));
+            }
+            lines.push("        ])".to_string());
+        }
+        SnippetBody::Multipart(_) => lines.push("        .multipart(form)".to_string()),
+        SnippetBody::File(file) => {
+            lines.push(format!("        .body(std::fs::File::open({})?)", rust_lit(file)));
+        }
+    }
+    lines.push("        .send()?;".to_string());
+    lines.push(String::new());
+    lines.push("    println!(\"{}\", response.text()?);".to_string());
+    lines.push("    Ok(())".to_string());
+    lines.push("}".to_string());
+    lines.join("\n")
+}
+
+/// The request as it would be written on the wire. Files are shown as `< path`.
+fn to_raw_http(r: &HttpRequest) -> String {
+    let body = snippet_body(r);
+    let url = build_url(r);
+    let (target, host) = match Url::parse(&url) {
+        Ok(u) => {
+            let mut target = u.path().to_string();
+            if let Some(query) = u.query() {
+                target.push('?');
+                target.push_str(query);
+            }
+            let host = u.host_str().map(|h| match u.port() {
+                Some(port) => format!("{h}:{port}"),
+                None => h.to_string(),
+            });
+            (target, host)
+        }
+        Err(_) => (url.clone(), None),
+    };
+
+    let mut lines = vec![format!("{} {target} HTTP/1.1", r.method.to_uppercase())];
+    let headers = snippet_headers(r, &body);
+    let has_host_header = headers.iter().any(|(name, _)| name.eq_ignore_ascii_case("host"));
+    if let Some(host) = host.filter(|_| !has_host_header) {
+        lines.push(format!("Host: {host}"));
+    }
+    for (name, value) in headers {
+        lines.push(format!("{name}: {value}"));
+    }
+    if matches!(body, SnippetBody::Multipart(_)) {
+        lines.push(format!("Content-Type: multipart/form-data; boundary={MULTIPART_BOUNDARY}"));
+    }
+
+    let body_text = match &body {
+        SnippetBody::None => return lines.join("\n"),
+        SnippetBody::Text(text) => text.clone(),
+        SnippetBody::Form(fields) => fields
+            .iter()
+            .map(|(name, value)| {
+                format!("{}={}", urlencoding::encode(name), urlencoding::encode(value))
+            })
+            .collect::<Vec<_>>()
+            .join("&"),
+        SnippetBody::Multipart(fields) => {
+            let mut parts = Vec::new();
+            for f in fields {
+                parts.push(format!("--{MULTIPART_BOUNDARY}"));
+                match &f.file {
+                    None => {
+                        parts.push(format!("Content-Disposition: form-data; name=\"{}\"", f.name));
+                        parts.push(String::new());
+                        parts.push(f.value.clone());
+                    }
+                    Some(file) => {
+                        parts.push(format!(
+                            "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"",
+                            f.name, f.filename
+                        ));
+                        if !f.content_type.is_empty() {
+                            parts.push(format!("Content-Type: {}", f.content_type));
+                        }
+                        parts.push(String::new());
+                        parts.push(format!("< {file}"));
+                    }
+                }
+            }
+            parts.push(format!("--{MULTIPART_BOUNDARY}--"));
+            parts.join("\n")
+        }
+        SnippetBody::File(file) => format!("< {file}"),
+    };
+    lines.push(String::new());
+    lines.push(body_text);
+    lines.join("\n")
+}
+
+/// Quote a string literal for JavaScript, Python, and Go, which all accept JSON's escapes
+fn lit(s: &str) -> String {
+    serde_json::to_string(s).unwrap_or_default()
+}
+
+/// Quote a string literal for Rust, which doesn't accept all of JSON's escapes
+fn rust_lit(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path).file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::BTreeMap;
+    use yaak_models::models::HttpRequestHeader;
+
+    fn header(name: &str, value: &str) -> HttpRequestHeader {
+        HttpRequestHeader {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        }
+    }
+
+    fn json_request() -> HttpRequest {
+        HttpRequest {
+            method: "post".to_string(),
+            url: "https://example.com/users?page=2".to_string(),
+            headers: vec![header("Content-Type", "application/json")],
+            body_type: Some("application/json".to_string()),
+            body: BTreeMap::from([("text".to_string(), json!("{\"name\": \"Ada\"}"))]),
+            ..Default::default()
+        }
+    }
+
+    fn multipart_request() -> HttpRequest {
+        HttpRequest {
+            method: "POST".to_string(),
+            url: "https://example.com/upload".to_string(),
+            headers: vec![header("Content-Type", "multipart/form-data")],
+            body_type: Some("multipart/form-data".to_string()),
+            body: BTreeMap::from([(
+                "form".to_string(),
+                json!([
+                    { "name": "title", "value": "Cat" },
+                    { "name": "photo", "file": "/tmp/cat.png", "contentType": "image/png" },
+                    { "name": "skipped", "value": "x", "enabled": false },
+                ]),
+            )]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_javascript_fetch() {
+        assert_eq!(
+            generate_snippet("javascript-fetch", &json_request()).unwrap(),
+            r#"const response = await fetch("https://example.com/users?page=2", {
+  method: "POST",
+  headers: {
+    "Content-Type": "application/json",
+  },
+  body: "{\"name\": \"Ada\"}",
+});
+
+console.log(await response.text());"#
+        );
+
+        let snippet = generate_snippet("javascript-fetch", &multipart_request()).unwrap();
+        assert!(snippet.starts_with("import fs from \"node:fs\";"));
+        assert!(snippet.contains(r#"body.append("title", "Cat");"#));
+        assert!(snippet.contains(
+            r#"await fs.openAsBlob("/tmp/cat.png", { type: "image/png" }), "cat.png");"#
+        ));
+        assert!(!snippet.contains("skipped"));
+        assert!(!snippet.contains("Content-Type"));
+    }
+
+    #[test]
+    fn test_python_requests() {
+        assert_eq!(
+            generate_snippet("python-requests", &json_request()).unwrap(),
+            r#"import requests
+
+response = requests.request(
+    "POST",
+    "https://example.com/users?page=2",
+    headers={
+        "Content-Type": "application/json",
+    },
+    data="{\"name\": \"Ada\"}",
+)
+
+print(response.text)"#
+        );
+
+        let snippet = generate_snippet("python-requests", &multipart_request()).unwrap();
+        assert!(snippet.contains(r#"("title", (None, "Cat")),"#));
+        assert!(
+            snippet.contains(r#"("photo", ("cat.png", open("/tmp/cat.png", "rb"), "image/png")),"#)
+        );
+    }
+
+    #[test]
+    fn test_go_net_http() {
+        let snippet = generate_snippet("go-net-http", &json_request()).unwrap();
+        assert!(snippet.starts_with(
+            "package main\n\nimport (\n\t\"fmt\"\n\t\"io\"\n\t\"net/http\"\n\t\"strings\"\n)"
+        ));
+        assert!(snippet.contains("\tbody := strings.NewReader(\"{\\\"name\\\": \\\"Ada\\\"}\")\n"));
+        assert!(snippet.contains(
+            "\treq, err := http.NewRequest(\"POST\", \"https://example.com/users?page=2\", body)\n"
+        ));
+        assert!(snippet.contains("\treq.Header.Add(\"Content-Type\", \"application/json\")\n"));
+
+        let snippet = generate_snippet("go-net-http", &multipart_request()).unwrap();
+        assert!(snippet.contains("\t\"mime/multipart\"\n"));
+        assert!(snippet.contains("\tpart, err := writer.CreateFormFile(\"photo\", \"cat.png\")\n"));
+        assert!(snippet.contains("writer.FormDataContentType()"));
+    }
+
+    #[test]
+    fn test_rust_reqwest() {
+        assert_eq!(
+            generate_snippet("rust-reqwest", &json_request()).unwrap(),
+            r#"fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .post("https://example.com/users?page=2")
+        .header("Content-Type", "application/json")
+        .body("{\"name\": \"Ada\"}")
+        .send()?;
+
+    println!("{}", response.text()?);
+    Ok(())
+}"#
+        );
+
+        let r = HttpRequest { method: "PURGE".to_string(), ..Default::default() };
+        let snippet = generate_snippet("rust-reqwest", &r).unwrap();
+        assert!(snippet.contains(".request(reqwest::Method::from_bytes(b\"PURGE\")?, "));
+        assert_eq!(rust_lit("a\"b\\\u{8}"), r#""a\"b\\\u{8}""#);
+    }
+
+    #[test]
+    fn test_raw_http() {
+        assert_eq!(
+            generate_snippet("http", &json_request()).unwrap(),
+            "POST /users?page=2 HTTP/1.1\n\
+             Host: example.com\n\
+             Content-Type: application/json\n\
+             \n\
+             {\"name\": \"Ada\"}"
+        );
+
+        let snippet = generate_snippet("http", &multipart_request()).unwrap();
+        assert!(snippet.contains("Content-Type: multipart/form-data; boundary=YaakFormBoundary\n"));
+        assert!(
+            snippet.contains("filename=\"cat.png\"\nContent-Type: image/png\n\n< /tmp/cat.png")
+        );
+        assert!(snippet.ends_with("--YaakFormBoundary--"));
+
+        assert_eq!(generate_snippet("cobol", &json_request()), None);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::decompress::ContentEncoding;
+use crate::error::{Error, Result};
+use async_compression::tokio::bufread::{BrotliEncoder, GzipEncoder, ZlibEncoder, ZstdEncoder};
+use flate2::Compression;
+use flate2::write::{GzEncoder, ZlibEncoder as SyncZlibEncoder};
+use std::io::Write;
+use std::pin::Pin;
+use tokio::io::{AsyncRead, BufReader};
+
+/// Compress a request body that's already in memory, so its compressed length is known up front.
+/// HTTP's `deflate` is the zlib format (RFC 9110), not a raw deflate stream.
+pub fn compress(data: &[u8], encoding: ContentEncoding) -> Result<Vec<u8>> {
+    let err = |e: std::io::Error| {
+        Error::CompressionError(format!("{} compression failed: {}", encoding.as_header(), e))
+    };
+    match encoding {
+        ContentEncoding::Identity => Ok(data.to_vec()),
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).map_err(err)?;
+            encoder.finish().map_err(err)
+        }
+        ContentEncoding::Deflate => {
+            let mut encoder = SyncZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(data).map_err(err)?;
+            encoder.finish().map_err(err)
+        }
+        ContentEncoding::Brotli => {
+            let mut compressed = Vec::new();
+            {
+                let mut writer = brotli::CompressorWriter::new(&mut compressed, 4096, 5, 22);
+                writer.write_all(data).map_err(err)?;
+            }
+            Ok(compressed)
+        }
+        ContentEncoding::Zstd => zstd::stream::encode_all(data, 3).map_err(err),
+    }
+}
+
+/// Wrap a streamed request body so it's compressed on-the-fly as it's sent. The compressed
+/// length isn't known ahead of time, so these bodies go out without a Content-Length.
+pub fn streaming_encoder(
+    reader: Pin<Box<dyn AsyncRead + Send + 'static>>,
+    encoding: ContentEncoding,
+) -> Pin<Box<dyn AsyncRead + Send + 'static>> {
+    let reader = BufReader::new(reader);
+    match encoding {
+        ContentEncoding::Identity => Box::pin(reader),
+        ContentEncoding::Gzip => Box::pin(GzipEncoder::new(reader)),
+        ContentEncoding::Deflate => Box::pin(ZlibEncoder::new(reader)),
+        ContentEncoding::Brotli => Box::pin(BrotliEncoder::new(reader)),
+        ContentEncoding::Zstd => Box::pin(ZstdEncoder::new(reader)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decompress::decompress;
+    use std::io::Read;
+    use tokio::io::AsyncReadExt;
+
+    const ORIGINAL: &[u8] = b"hello world, this is a request body that gets compressed";
+
+    #[test]
+    fn test_compress_round_trips() {
+        for encoding in [
+            ContentEncoding::Gzip,
+            ContentEncoding::Brotli,
+            ContentEncoding::Zstd,
+        ] {
+            let compressed = compress(ORIGINAL, encoding).unwrap();
+            assert_eq!(decompress(compressed, encoding).unwrap().data, ORIGINAL);
+        }
+    }
+
+    #[test]
+    fn test_compress_deflate_uses_zlib_format() {
+        let compressed = compress(ORIGINAL, ContentEncoding::Deflate).unwrap();
+        let mut decompressed = Vec::new();
+        flate2::read::ZlibDecoder::new(compressed.as_slice())
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, ORIGINAL);
+    }
+
+    #[tokio::test]
+    async fn test_streaming_encoder_gzip() {
+        let reader: Pin<Box<dyn AsyncRead + Send>> = Box::pin(std::io::Cursor::new(ORIGINAL));
+        let mut encoder = streaming_encoder(reader, ContentEncoding::Gzip);
+        let mut compressed = Vec::new();
+        encoder.read_to_end(&mut compressed).await.unwrap();
+
+        assert_ne!(compressed, ORIGINAL);
+        assert_eq!(decompress(compressed, ContentEncoding::Gzip).unwrap().data, ORIGINAL);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::sender::HttpResponseEvent;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tokio::sync::{RwLock, mpsc};
+use tower_layer::Layer;
+use tower_service::Service;
+
+type EventSender = Arc<RwLock<Option<mpsc::Sender<HttpResponseEvent>>>>;
+
+/// Connector layer that reports how long each new connection took to establish. The
+/// connector resolves DNS, opens the socket, and runs any proxy and TLS handshakes, so the
+/// reported duration covers all of them.
+#[derive(Clone)]
+pub(crate) struct ConnectTimingLayer {
+    event_tx: EventSender,
+}
+
+impl ConnectTimingLayer {
+    pub(crate) fn new(event_tx: EventSender) -> Self {
+        Self { event_tx }
+    }
+}
+
+impl<S> Layer<S> for ConnectTimingLayer {
+    type Service = ConnectTiming<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ConnectTiming { inner, event_tx: self.event_tx.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ConnectTiming<S> {
+    inner: S,
+    event_tx: EventSender,
+}
+
+impl<S, R> Service<R> for ConnectTiming<S>
+where
+    S: Service<R>,
+    S::Response: Send,
+    S::Error: Send,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: R) -> Self::Future {
+        let event_tx = self.event_tx.clone();
+        let start = Instant::now();
+        let connecting = self.inner.call(req);
+
+        Box::pin(async move {
+            let conn = connecting.await?;
+            let duration = start.elapsed().as_millis() as u64;
+
+            let guard = event_tx.read().await;
+            if let Some(tx) = guard.as_ref() {
+                let _ = tx.send(HttpResponseEvent::Connected { duration }).await;
+            }
+
+            Ok(conn)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::future::{Ready, ready};
+
+    #[derive(Clone)]
+    struct Echo;
+
+    impl Service<u8> for Echo {
+        type Response = u8;
+        type Error = Infallible;
+        type Future = Ready<Result<u8, Infallible>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: u8) -> Self::Future {
+            ready(Ok(req))
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_each_connection() {
+        let slot: EventSender = Arc::new(RwLock::new(None));
+        let mut service = ConnectTimingLayer::new(slot.clone()).layer(Echo);
+
+        // Nothing is reported until a request sets its event sender
+        assert_eq!(service.call(1).await.unwrap(), 1);
+
+        let (tx, mut rx) = mpsc::channel(4);
+        *slot.write().await = Some(tx);
+        assert_eq!(service.call(2).await.unwrap(), 2);
+
+        assert!(matches!(rx.try_recv(), Ok(HttpResponseEvent::Connected { .. })));
+        assert!(rx.try_recv().is_err());
+    }
+}
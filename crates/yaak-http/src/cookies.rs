@@ -137,6 +137,94 @@ pub fn get_cookie_value_from_jar(
     })
 }
 
+const NETSCAPE_HEADER: &str = "# Netscape HTTP Cookie File";
+const NETSCAPE_HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+/// Serialize cookies to the Netscape cookies.txt format used by curl and browser extensions.
+/// Cookies without a domain can't be represented and are skipped.
+pub fn cookies_to_netscape(cookies: &[Cookie]) -> String {
+    let mut lines = vec![NETSCAPE_HEADER.to_string(), String::new()];
+    for cookie in cookies {
+        let (domain, include_subdomains) = match &cookie.domain {
+            CookieDomain::HostOnly(domain) => (domain.clone(), false),
+            CookieDomain::Suffix(domain) => (format!(".{domain}"), true),
+            CookieDomain::NotPresent | CookieDomain::Empty => continue,
+        };
+        let expires = match &cookie.expires {
+            CookieExpires::AtUtc(expires) => expires.parse::<i64>().unwrap_or_default(),
+            CookieExpires::SessionEnd => 0,
+        };
+        let http_only = if cookie.http_only { NETSCAPE_HTTP_ONLY_PREFIX } else { "" };
+        lines.push(format!(
+            "{http_only}{domain}\t{}\t{}\t{}\t{expires}\t{}\t{}",
+            netscape_bool(include_subdomains),
+            cookie.path,
+            netscape_bool(cookie.secure),
+            cookie.name,
+            cookie.value,
+        ));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Parse cookies from the Netscape cookies.txt format, skipping comments and malformed lines
+pub fn cookies_from_netscape(contents: &str) -> Vec<Cookie> {
+    contents.lines().filter_map(parse_netscape_line).collect()
+}
+
+fn parse_netscape_line(line: &str) -> Option<Cookie> {
+    let (line, http_only) = match line.strip_prefix(NETSCAPE_HTTP_ONLY_PREFIX) {
+        Some(line) => (line, true),
+        None => (line, false),
+    };
+    if line.trim().is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let fields: Vec<&str> = line.split('\t').collect();
+    let &[
+        domain,
+        include_subdomains,
+        path,
+        secure,
+        expires,
+        name,
+        value,
+    ] = fields.as_slice()
+    else {
+        return None;
+    };
+    if name.is_empty() {
+        return None;
+    }
+
+    let domain = domain.trim_start_matches('.').to_lowercase();
+    let domain = if include_subdomains.eq_ignore_ascii_case("TRUE") {
+        CookieDomain::Suffix(domain)
+    } else {
+        CookieDomain::HostOnly(domain)
+    };
+    let expires = match expires.parse::<i64>().ok()? {
+        0 => CookieExpires::SessionEnd,
+        expires => CookieExpires::AtUtc(expires.to_string()),
+    };
+
+    Some(Cookie {
+        name: name.to_string(),
+        value: value.to_string(),
+        domain,
+        expires,
+        path: path.to_string(),
+        secure: secure.eq_ignore_ascii_case("TRUE"),
+        http_only,
+        same_site: None,
+    })
+}
+
+fn netscape_bool(value: bool) -> &'static str {
+    if value { "TRUE" } else { "FALSE" }
+}
+
 /// Parse name=value from a cookie string (raw_cookie format)
 #[cfg(test)]
 fn parse_cookie_name_value(raw_cookie: &str) -> Option<(String, String)> {
@@ -578,4 +666,36 @@ mod tests {
         assert_eq!(store.get_all_cookies().len(), 1);
         assert!(store.get_cookie_header(&url).is_some());
     }
+
+    #[test]
+    fn test_netscape_round_trip() {
+        let mut session = cookie("session=abc", CookieDomain::HostOnly("example.com".to_string()));
+        session.http_only = true;
+        let mut theme = cookie("theme=dark", CookieDomain::Suffix("example.com".to_string()));
+        theme.secure = true;
+        theme.path = "/app".to_string();
+        theme.expires = CookieExpires::AtUtc("1893456000".to_string());
+        let cookies = vec![session, theme, cookie("skip=me", CookieDomain::NotPresent)];
+
+        let contents = cookies_to_netscape(&cookies);
+        assert_eq!(
+            contents,
+            "# Netscape HTTP Cookie File\n\n\
+             #HttpOnly_example.com\tFALSE\t/\tFALSE\t0\tsession\tabc\n\
+             .example.com\tTRUE\t/app\tTRUE\t1893456000\ttheme\tdark\n"
+        );
+        assert_eq!(cookies_from_netscape(&contents), cookies[..2]);
+    }
+
+    #[test]
+    fn test_netscape_skips_comments_and_malformed_lines() {
+        let contents = "# comment\r\n\
+                        example.com\tFALSE\t/\tFALSE\t0\tempty\t\r\n\
+                        example.com\tFALSE\t/\tFALSE\tsoon\tbad\tvalue\n\
+                        example.com\tFALSE\t/\n";
+        let cookies = cookies_from_netscape(contents);
+        assert_eq!(cookies.len(), 1);
+        assert_eq!(cookies[0].name, "empty");
+        assert_eq!(cookies[0].value, "");
+    }
 }
@@ -0,0 +1,864 @@
+//! Conversion between `curl` command lines and Yaak HTTP requests
+//!
+//! Commands are split into arguments with a small POSIX-like shell tokenizer, so commands copied
+//! from browser dev tools or documentation (with `\` line continuations, single, double, and
+//! `$'...'` quoting) are understood the same way a shell would understand them.
+
+use crate::error::Error::CurlParseError;
+use crate::error::Result;
+use crate::types::{build_url, graphql_body_text};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::str::Chars;
+use yaak_common::serde::{get_bool, get_str, get_str_map};
+use yaak_models::models::{HttpRequest, HttpRequestHeader, HttpUrlParameter};
+
+/// Long options that consume the following argument. Options not listed here (and not in
+/// [`SHORT_VALUE_OPTIONS`]) are treated as boolean flags.
+const LONG_VALUE_OPTIONS: &[&str] = &[
+    "cookie",
+    "data",
+    "data-ascii",
+    "data-binary",
+    "data-raw",
+    "data-urlencode",
+    "form",
+    "form-string",
+    "header",
+    "referer",
+    "request",
+    "unix-socket",
+    "upload-file",
+    "url",
+    "url-query",
+    "user",
+    "user-agent",
+    // Unsupported, but their values must still be skipped
+    "cacert",
+    "capath",
+    "cert",
+    "cert-type",
+    "ciphers",
+    "config",
+    "connect-timeout",
+    "connect-to",
+    "cookie-jar",
+    "dump-header",
+    "key",
+    "key-type",
+    "limit-rate",
+    "max-filesize",
+    "max-redirs",
+    "max-time",
+    "output",
+    "pass",
+    "proxy",
+    "proxy-user",
+    "resolve",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "trace",
+    "trace-ascii",
+    "write-out",
+];
+
+/// Short options that consume the following argument, either separately (`-X POST`) or squished
+/// into the same argument (`-XPOST`)
+const SHORT_VALUE_OPTIONS: &str = "AbcdeEFHKmoruTwxXyYz";
+
+/// Split a shell command into its arguments. Parsing stops at the first unquoted command
+/// separator (newline, `;`, `|`, or `&`) so only the first command is returned.
+pub fn tokenize(command: &str) -> Result<Vec<String>> {
+    let mut args: Vec<String> = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut chars = command.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // Line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(escaped) => {
+                    current.push(escaped);
+                    in_arg = true;
+                }
+                None => {}
+            },
+            '\'' => {
+                read_single_quoted(&mut chars, &mut current)?;
+                in_arg = true;
+            }
+            '"' => {
+                read_double_quoted(&mut chars, &mut current)?;
+                in_arg = true;
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                read_ansi_c_quoted(&mut chars, &mut current)?;
+                in_arg = true;
+            }
+            '#' if !in_arg => {
+                // Comments run until the end of the line
+                while chars.next_if(|c| *c != '\n').is_some() {}
+            }
+            '\n' | ';' | '|' | '&' => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+                if !args.is_empty() {
+                    break;
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if in_arg {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+fn read_single_quoted(chars: &mut Peekable<Chars>, out: &mut String) -> Result<()> {
+    for c in chars.by_ref() {
+        if c == '\'' {
+            return Ok(());
+        }
+        out.push(c);
+    }
+    Err(CurlParseError("Unterminated single quote".to_string()))
+}
+
+fn read_double_quoted(chars: &mut Peekable<Chars>, out: &mut String) -> Result<()> {
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Ok(()),
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some(c @ ('"' | '\\' | '$' | '`')) => out.push(c),
+                Some(c) => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err(CurlParseError("Unterminated double quote".to_string()))
+}
+
+fn read_ansi_c_quoted(chars: &mut Peekable<Chars>, out: &mut String) -> Result<()> {
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => return Ok(()),
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('r') => out.push('\r'),
+                Some('t') => out.push('\t'),
+                Some('0') => out.push('\0'),
+                Some('x') => {
+                    let mut hex = String::new();
+                    while hex.len() < 2 {
+                        match chars.next_if(|c| c.is_ascii_hexdigit()) {
+                            Some(c) => hex.push(c),
+                            None => break,
+                        }
+                    }
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(c) => out.push(c),
+                        None => out.push_str("\\x"),
+                    }
+                }
+                Some(c) => out.push(c),
+                None => break,
+            },
+            c => out.push(c),
+        }
+    }
+    Err(CurlParseError("Unterminated $' quote".to_string()))
+}
+
+/// A data argument along with the option that provided it
+struct DataArg {
+    option: String,
+    value: String,
+}
+
+/// Parse a `curl` command line into an unsaved HTTP request
+pub fn parse_curl_command(command: &str) -> Result<HttpRequest> {
+    let args = tokenize(command)?;
+    match args.first() {
+        Some(program) if program == "curl" || program.ends_with("/curl") => {}
+        _ => return Err(CurlParseError("Command does not start with curl".to_string())),
+    }
+
+    let mut options: Vec<(String, Option<String>)> = Vec::new();
+    let mut positionals: Vec<String> = Vec::new();
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            positionals.extend(args.by_ref());
+            break;
+        }
+        if let Some(name) = arg.strip_prefix("--") {
+            let value = if LONG_VALUE_OPTIONS.contains(&name) { args.next() } else { None };
+            options.push((name.to_string(), value));
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            // Short options can be combined (eg. `-sSL`), and the last may take a value
+            // either from the rest of the argument (`-XPOST`) or the next argument
+            let flags: Vec<char> = arg.chars().skip(1).collect();
+            for (i, flag) in flags.iter().enumerate() {
+                let name = long_option_name(*flag);
+                if !SHORT_VALUE_OPTIONS.contains(*flag) {
+                    options.push((name, None));
+                    continue;
+                }
+                let rest: String = flags[i + 1..].iter().collect();
+                let value = if rest.is_empty() { args.next() } else { Some(rest) };
+                options.push((name, value));
+                break;
+            }
+        } else {
+            positionals.push(arg);
+        }
+    }
+
+    let mut url = String::new();
+    let mut method: Option<String> = None;
+    let mut headers: Vec<HttpRequestHeader> = Vec::new();
+    let mut url_parameters: Vec<HttpUrlParameter> = Vec::new();
+    let mut cookies: Vec<String> = Vec::new();
+    let mut data: Vec<DataArg> = Vec::new();
+    let mut form: Vec<Value> = Vec::new();
+    let mut upload_file: Option<String> = None;
+    let mut unix_socket: Option<String> = None;
+    let mut user: Option<String> = None;
+    let mut digest = false;
+    let mut data_as_query = false;
+
+    for (name, value) in options {
+        let value = value.unwrap_or_default();
+        match name.as_str() {
+            "url" => url = value,
+            "request" => method = Some(value.to_uppercase()),
+            "head" => method = Some("HEAD".to_string()),
+            "get" => data_as_query = true,
+            "header" => {
+                if let Some(h) = parse_header(&value) {
+                    headers.push(h);
+                }
+            }
+            "user-agent" => headers.push(header("User-Agent", &value)),
+            "referer" => headers.push(header("Referer", &value)),
+            // Values without a `=` are cookie files, which can't be imported
+            "cookie" if value.contains('=') => cookies.push(value),
+            "data" | "data-ascii" | "data-binary" | "data-raw" | "data-urlencode" => {
+                data.push(DataArg { option: name.clone(), value })
+            }
+            "form" | "form-string" => form.push(parse_form_field(&value, name == "form")),
+            "upload-file" => upload_file = Some(value),
+            "unix-socket" => unix_socket = Some(value),
+            "url-query" => url_parameters.extend(parse_query(&value)),
+            "user" => user = Some(value),
+            "digest" => digest = true,
+            _ => {}
+        }
+    }
+
+    if url.is_empty() {
+        url = positionals.into_iter().next().unwrap_or_default();
+    }
+    let url = match url.split_once('?') {
+        Some((base, query)) => {
+            let mut params = parse_query(query);
+            params.append(&mut url_parameters);
+            url_parameters = params;
+            base.to_string()
+        }
+        None => url,
+    };
+
+    if !cookies.is_empty() {
+        let cookies = cookies.join("; ");
+        match headers.iter_mut().find(|h| h.name.eq_ignore_ascii_case("cookie")) {
+            Some(h) => h.value = format!("{}; {}", h.value, cookies),
+            None => headers.push(header("Cookie", &cookies)),
+        }
+    }
+
+    // Authentication, either from -u/--user or an Authorization header
+    let (mut authentication_type, mut authentication) = match user {
+        Some(user) => {
+            let (username, password) = user.split_once(':').unwrap_or((user.as_str(), ""));
+            let auth_type = if digest { "digest" } else { "basic" };
+            (Some(auth_type.to_string()), credentials(username, password))
+        }
+        None => (None, BTreeMap::new()),
+    };
+    let authorization = headers
+        .iter()
+        .position(|h| h.name.eq_ignore_ascii_case("authorization"))
+        .and_then(|idx| Some((idx, parse_authorization(&headers[idx].value)?)));
+    if let Some((idx, (auth_type, auth))) = authorization {
+        headers.remove(idx);
+        authentication_type = Some(auth_type);
+        authentication = auth;
+    }
+
+    let content_type = headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case("content-type"))
+        .map(|h| h.value.clone());
+    let mime_type = content_type
+        .as_ref()
+        .map(|ct| ct.split(';').next().unwrap_or_default().trim().to_lowercase());
+
+    let mut body: BTreeMap<String, Value> = BTreeMap::new();
+    let mut body_type: Option<String> = None;
+
+    if let Some(file) = upload_file {
+        body_type = Some("binary".to_string());
+        body.insert("filePath".to_string(), json!(file));
+    } else if !form.is_empty() {
+        body_type = Some("multipart/form-data".to_string());
+        body.insert("form".to_string(), json!(form));
+    } else if data_as_query {
+        for d in &data {
+            url_parameters.extend(parse_data_params(d));
+        }
+    } else if let Some(file) = data_file(&data) {
+        body_type = Some("binary".to_string());
+        body.insert("filePath".to_string(), json!(file));
+    } else if !data.is_empty() {
+        let text = data.iter().map(data_to_text).collect::<Vec<_>>().join("&");
+        let boundary = content_type.as_deref().and_then(multipart_boundary);
+        let multipart = match (mime_type.as_deref(), boundary) {
+            (Some("multipart/form-data"), Some(boundary)) => parse_multipart(&text, boundary),
+            _ => None,
+        };
+
+        if let Some(multipart) = multipart {
+            body_type = Some("multipart/form-data".to_string());
+            body.insert("form".to_string(), json!(multipart));
+        } else if mime_type.is_none() && is_json(&text) {
+            // curl defaults to a form content type, but JSON sent without a header is
+            // almost certainly meant to be JSON
+            body_type = Some("application/json".to_string());
+            body.insert("text".to_string(), json!(text));
+            headers.push(header("Content-Type", "application/json"));
+        } else if mime_type.is_none()
+            || mime_type.as_deref() == Some("application/x-www-form-urlencoded")
+        {
+            let params: Vec<Value> = data
+                .iter()
+                .flat_map(parse_data_params)
+                .map(|p| json!({ "name": p.name, "value": p.value, "enabled": true }))
+                .collect();
+            body_type = Some("application/x-www-form-urlencoded".to_string());
+            body.insert("form".to_string(), json!(params));
+            if mime_type.is_none() {
+                headers.push(header("Content-Type", "application/x-www-form-urlencoded"));
+            }
+        } else {
+            body_type = Some(match mime_type.as_deref() {
+                Some(m @ ("application/json" | "text/xml" | "text/plain")) => m.to_string(),
+                _ => "other".to_string(),
+            });
+            body.insert("text".to_string(), json!(text));
+        }
+    }
+
+    let method =
+        method.unwrap_or_else(|| if body_type.is_some() { "POST" } else { "GET" }.to_string());
+
+    Ok(HttpRequest {
+        model: "http_request".to_string(),
+        url,
+        method,
+        headers,
+        url_parameters,
+        unix_socket,
+        body,
+        body_type,
+        authentication,
+        authentication_type,
+        ..Default::default()
+    })
+}
+
+fn long_option_name(flag: char) -> String {
+    match flag {
+        'A' => "user-agent",
+        'b' => "cookie",
+        'd' => "data",
+        'e' => "referer",
+        'F' => "form",
+        'G' => "get",
+        'H' => "header",
+        'I' => "head",
+        'T' => "upload-file",
+        'u' => "user",
+        'X' => "request",
+        _ => return format!("-{flag}"),
+    }
+    .to_string()
+}
+
+fn header(name: &str, value: &str) -> HttpRequestHeader {
+    HttpRequestHeader { enabled: true, name: name.to_string(), value: value.to_string(), id: None }
+}
+
+fn parse_header(value: &str) -> Option<HttpRequestHeader> {
+    match value.split_once(':') {
+        Some((name, value)) => Some(header(name.trim(), value.trim())),
+        // `-H "X-Empty;"` sends an empty header, while `-H "X-Remove"` removes one
+        None => value.trim().strip_suffix(';').map(|name| header(name.trim(), "")),
+    }
+}
+
+fn credentials(username: &str, password: &str) -> BTreeMap<String, Value> {
+    BTreeMap::from([
+        ("username".to_string(), json!(username)),
+        ("password".to_string(), json!(password)),
+    ])
+}
+
+fn parse_authorization(value: &str) -> Option<(String, BTreeMap<String, Value>)> {
+    let (scheme, credential) = value.trim().split_once(' ')?;
+    let credential = credential.trim();
+    match scheme.to_lowercase().as_str() {
+        "bearer" => Some((
+            "bearer".to_string(),
+            BTreeMap::from([
+                ("token".to_string(), json!(credential)),
+                ("prefix".to_string(), json!("Bearer")),
+            ]),
+        )),
+        "basic" => {
+            let decoded = BASE64_STANDARD.decode(credential).ok()?;
+            let decoded = String::from_utf8(decoded).ok()?;
+            let (username, password) = decoded.split_once(':')?;
+            Some(("basic".to_string(), credentials(username, password)))
+        }
+        _ => None,
+    }
+}
+
+fn parse_form_field(value: &str, allow_files: bool) -> Value {
+    let (name, value) = value.split_once('=').unwrap_or((value, ""));
+    let mut field = json!({ "name": name, "enabled": true });
+
+    let file = match value.strip_prefix('@').or_else(|| value.strip_prefix('<')) {
+        Some(file) if allow_files => file,
+        _ => {
+            field["value"] = json!(value);
+            return field;
+        }
+    };
+
+    // Files may carry extra attributes, like `@photo.png;type=image/png;filename=a.png`
+    let mut attributes = file.split(';');
+    field["file"] = json!(attributes.next().unwrap_or_default());
+    for attribute in attributes {
+        match attribute.split_once('=') {
+            Some(("type", v)) => field["contentType"] = json!(v),
+            Some(("filename", v)) => field["filename"] = json!(v.trim_matches('"')),
+            _ => {}
+        }
+    }
+    field
+}
+
+fn parse_query(query: &str) -> Vec<HttpUrlParameter> {
+    query
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|p| {
+            let (name, value) = p.split_once('=').unwrap_or((p, ""));
+            HttpUrlParameter { enabled: true, name: decode(name), value: decode(value), id: None }
+        })
+        .collect()
+}
+
+/// Split a data argument into name/value pairs, decoding them unless they came from
+/// `--data-urlencode`, whose values curl encodes itself.
+fn parse_data_params(data: &DataArg) -> Vec<HttpUrlParameter> {
+    if data.option != "data-urlencode" {
+        return parse_query(&data.value);
+    }
+    let (name, value) = data.value.split_once('=').unwrap_or(("", &data.value));
+    vec![HttpUrlParameter {
+        enabled: true,
+        name: name.to_string(),
+        value: value.to_string(),
+        id: None,
+    }]
+}
+
+/// A single `-d @file` argument sends the contents of a file as the body
+fn data_file(data: &[DataArg]) -> Option<&str> {
+    match data {
+        [d] if d.option != "data-urlencode" => d.value.strip_prefix('@'),
+        _ => None,
+    }
+}
+
+fn data_to_text(data: &DataArg) -> String {
+    if data.option != "data-urlencode" {
+        return data.value.clone();
+    }
+    match data.value.split_once('=') {
+        Some((name, value)) => format!("{}={}", name, urlencoding::encode(value)),
+        None => urlencoding::encode(&data.value).to_string(),
+    }
+}
+
+fn decode(value: &str) -> String {
+    let value = value.replace('+', " ");
+    urlencoding::decode(&value).map(|v| v.to_string()).unwrap_or(value)
+}
+
+fn is_json(text: &str) -> bool {
+    let text = text.trim_start();
+    (text.starts_with('{') || text.starts_with('[')) && serde_json::from_str::<Value>(text).is_ok()
+}
+
+fn multipart_boundary(content_type: &str) -> Option<&str> {
+    content_type
+        .split(';')
+        .filter_map(|p| p.trim().split_once('='))
+        .find(|(k, _)| k.eq_ignore_ascii_case("boundary"))
+        .map(|(_, v)| v.trim_matches('"'))
+}
+
+/// Parse a raw multipart body, as exported by browser dev tools with `--data-raw`, back into
+/// form fields. File contents can't be recovered, so file fields keep only their file name.
+fn parse_multipart(body: &str, boundary: &str) -> Option<Vec<Value>> {
+    let mut fields = Vec::new();
+    for part in body.split(&format!("--{boundary}")) {
+        let Some((head, content)) = part.split_once("\r\n\r\n") else {
+            continue;
+        };
+        let content = content.strip_suffix("\r\n").unwrap_or(content);
+
+        let Some(disposition) =
+            head.lines().find(|l| l.to_lowercase().starts_with("content-disposition:"))
+        else {
+            continue;
+        };
+        let mut name = None;
+        let mut filename = None;
+        for attribute in disposition.split(';').skip(1) {
+            match attribute.trim().split_once('=') {
+                Some(("name", v)) => name = Some(v.trim_matches('"')),
+                Some(("filename", v)) => filename = Some(v.trim_matches('"')),
+                _ => {}
+            }
+        }
+
+        let Some(name) = name else {
+            continue;
+        };
+        fields.push(match filename {
+            Some(filename) => json!({ "name": name, "file": filename, "enabled": true }),
+            None => json!({ "name": name, "value": content, "enabled": true }),
+        });
+    }
+
+    if fields.is_empty() { None } else { Some(fields) }
+}
+
+/// Separator placed between arguments of an exported command, so each goes on its own line
+const EXPORT_LINE_SEPARATOR: &str = " \\\n  ";
+
+/// Convert a rendered request into a copy-pasteable `curl` command. Authentication is not
+/// applied here, so callers wanting it should add the resulting headers or query parameters
+/// to the request first.
+pub fn to_curl_command(r: &HttpRequest) -> String {
+    let mut lines = vec![format!(
+        "curl -X {} {}",
+        r.method.to_uppercase(),
+        quote(&build_url(r))
+    )];
+
+    if let Some(path) = r.unix_socket.as_deref().filter(|p| !p.is_empty()) {
+        lines.push(format!("--unix-socket {}", quote(path)));
+    }
+
+    for h in r.headers.iter().filter(|h| h.enabled && !h.name.is_empty()) {
+        lines.push(format!("--header {}", quote(&format!("{}: {}", h.name, h.value))));
+    }
+
+    let enabled_form = || {
+        r.body
+            .get("form")
+            .and_then(|f| f.as_array())
+            .into_iter()
+            .flatten()
+            .filter(|p| get_bool(p, "enabled", true))
+            .filter(|p| !get_str(p, "name").is_empty())
+    };
+
+    match r.body_type.as_deref() {
+        None => {}
+        Some("binary") => {
+            let file = get_str_map(&r.body, "filePath");
+            if !file.is_empty() {
+                lines.push(format!("--data-binary {}", quote(&format!("@{file}"))));
+            }
+        }
+        Some("graphql") => {
+            if r.method.to_lowercase() != "get" {
+                lines.push(format!("--data-raw {}", quote(&graphql_body_text(&r.body))));
+            }
+        }
+        Some("application/x-www-form-urlencoded") => {
+            for p in enabled_form() {
+                let field = format!("{}={}", get_str(p, "name"), get_str(p, "value"));
+                lines.push(format!("--data-urlencode {}", quote(&field)));
+            }
+        }
+        Some("multipart/form-data") => {
+            for p in enabled_form() {
+                let (name, file) = (get_str(p, "name"), get_str(p, "file"));
+                if file.is_empty() {
+                    // Values starting with `@` or `<` would be read as files by `--form`
+                    let value = get_str(p, "value");
+                    let option =
+                        if value.starts_with(['@', '<']) { "--form-string" } else { "--form" };
+                    lines.push(format!("{option} {}", quote(&format!("{name}={value}"))));
+                    continue;
+                }
+                let mut field = format!("{name}=@{file}");
+                for (attribute, key) in [("type", "contentType"), ("filename", "filename")] {
+                    let value = get_str(p, key);
+                    if !value.is_empty() {
+                        field.push_str(&format!(";{attribute}={value}"));
+                    }
+                }
+                lines.push(format!("--form {}", quote(&field)));
+            }
+        }
+        Some(_) => {
+            let text = get_str_map(&r.body, "text");
+            if !text.is_empty() {
+                lines.push(format!("--data-raw {}", quote(text)));
+            }
+        }
+    }
+
+    lines.join(EXPORT_LINE_SEPARATOR)
+}
+
+/// Quote an argument for POSIX shells
+fn quote(arg: &str) -> String {
+    format!("'{}'", arg.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_quoting_and_continuations() {
+        let args = tokenize(
+            "curl 'https://example.com' \\\n  -H \"X-Quote: \\\"hi\\\" \\$HOME\" \\\r\n  --data $'a\\nb' plain\\ arg",
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            vec![
+                "curl",
+                "https://example.com",
+                "-H",
+                "X-Quote: \"hi\" $HOME",
+                "--data",
+                "a\nb",
+                "plain arg",
+            ]
+        );
+
+        // Only the first command is returned
+        let args = tokenize("\n# comment\ncurl https://a.com; curl https://b.com").unwrap();
+        assert_eq!(args, vec!["curl", "https://a.com"]);
+
+        assert!(tokenize("curl 'https://example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_curl_method_headers_and_auth() {
+        let r = parse_curl_command(
+            "curl -sSL -XPUT 'https://example.com/api?foo=bar%20baz' \
+             -H 'Accept: application/json' -H 'Authorization: Bearer abc' \
+             -b 'a=1' --cookie b=2 --url-query x=y",
+        )
+        .unwrap();
+        assert_eq!(r.method, "PUT");
+        assert_eq!(r.url, "https://example.com/api");
+        let params: Vec<(&str, &str)> =
+            r.url_parameters.iter().map(|p| (p.name.as_str(), p.value.as_str())).collect();
+        assert_eq!(params, vec![("foo", "bar baz"), ("x", "y")]);
+        let headers: Vec<(&str, &str)> =
+            r.headers.iter().map(|h| (h.name.as_str(), h.value.as_str())).collect();
+        assert_eq!(headers, vec![("Accept", "application/json"), ("Cookie", "a=1; b=2")]);
+        assert_eq!(r.authentication_type.as_deref(), Some("bearer"));
+        assert_eq!(r.authentication.get("token"), Some(&json!("abc")));
+
+        let r = parse_curl_command("curl -u user:p:ss --digest https://example.com").unwrap();
+        assert_eq!(r.method, "GET");
+        assert_eq!(r.authentication_type.as_deref(), Some("digest"));
+        assert_eq!(r.authentication.get("password"), Some(&json!("p:ss")));
+
+        assert!(parse_curl_command("wget https://example.com").is_err());
+    }
+
+    #[test]
+    fn test_parse_curl_bodies() {
+        let r =
+            parse_curl_command("curl https://example.com -d 'a=1&b=two%20words' -d c=3").unwrap();
+        assert_eq!(r.method, "POST");
+        assert_eq!(r.body_type.as_deref(), Some("application/x-www-form-urlencoded"));
+        assert_eq!(
+            r.body["form"][1],
+            json!({ "name": "b", "value": "two words", "enabled": true })
+        );
+        assert_eq!(r.body["form"][2]["name"], json!("c"));
+
+        let r = parse_curl_command("curl https://example.com --data-raw '{\"id\": 1}'").unwrap();
+        assert_eq!(r.body_type.as_deref(), Some("application/json"));
+        assert_eq!(r.body["text"], json!("{\"id\": 1}"));
+
+        let r = parse_curl_command(
+            "curl https://example.com -H 'Content-Type: text/xml' --data-binary '<a/>'",
+        )
+        .unwrap();
+        assert_eq!(r.body_type.as_deref(), Some("text/xml"));
+        assert_eq!(r.body["text"], json!("<a/>"));
+
+        let r = parse_curl_command("curl https://example.com --data-binary @./photo.png").unwrap();
+        assert_eq!(r.body_type.as_deref(), Some("binary"));
+        assert_eq!(r.body["filePath"], json!("./photo.png"));
+
+        let r = parse_curl_command(
+            "curl https://example.com -F name=Yaak -F 'photo=@a.png;type=image/png'",
+        )
+        .unwrap();
+        assert_eq!(r.body_type.as_deref(), Some("multipart/form-data"));
+        assert_eq!(r.body["form"][0], json!({ "name": "name", "value": "Yaak", "enabled": true }));
+        assert_eq!(r.body["form"][1]["file"], json!("a.png"));
+        assert_eq!(r.body["form"][1]["contentType"], json!("image/png"));
+
+        let r = parse_curl_command(
+            "curl https://example.com \
+             -H 'Content-Type: multipart/form-data; boundary=----abc' \
+             --data-raw $'------abc\\r\\nContent-Disposition: form-data; name=\"a\"\\r\\n\\r\\n1\\r\\n------abc--\\r\\n'",
+        )
+        .unwrap();
+        assert_eq!(r.body_type.as_deref(), Some("multipart/form-data"));
+        assert_eq!(r.body["form"][0], json!({ "name": "a", "value": "1", "enabled": true }));
+
+        let r = parse_curl_command("curl -G https://example.com -d q=search").unwrap();
+        assert_eq!(r.method, "GET");
+        assert_eq!(r.body_type, None);
+        assert_eq!(r.url_parameters[0].name, "q");
+    }
+
+    #[test]
+    fn test_to_curl_command_round_trips() {
+        let r = HttpRequest {
+            method: "patch".to_string(),
+            url: "https://example.com/users/:id".to_string(),
+            url_parameters: vec![
+                HttpUrlParameter {
+                    enabled: true,
+                    name: ":id".to_string(),
+                    value: "42".to_string(),
+                    id: None,
+                },
+                HttpUrlParameter {
+                    enabled: true,
+                    name: "q".to_string(),
+                    value: "it's".to_string(),
+                    id: None,
+                },
+            ],
+            headers: vec![
+                header("Content-Type", "application/json"),
+                HttpRequestHeader { enabled: false, ..header("X-Disabled", "1") },
+            ],
+            body_type: Some("application/json".to_string()),
+            body: BTreeMap::from([("text".to_string(), json!("{\"name\": \"O'Brien\"}"))]),
+            ..Default::default()
+        };
+
+        let command = to_curl_command(&r);
+        assert_eq!(
+            command,
+            "curl -X PATCH 'https://example.com/users/42?q=it%27s' \\\n  \
+             --header 'Content-Type: application/json' \\\n  \
+             --data-raw '{\"name\": \"O'\\''Brien\"}'"
+        );
+
+        let parsed = parse_curl_command(&command).unwrap();
+        assert_eq!(parsed.method, "PATCH");
+        assert_eq!(parsed.url, "https://example.com/users/42");
+        assert_eq!(parsed.url_parameters[0].value, "it's");
+        assert_eq!(parsed.headers, vec![header("Content-Type", "application/json")]);
+        assert_eq!(parsed.body, r.body);
+
+        let r = HttpRequest {
+            method: "GET".to_string(),
+            url: "http://localhost/containers/json".to_string(),
+            unix_socket: Some("/var/run/docker.sock".to_string()),
+            ..Default::default()
+        };
+        let command = to_curl_command(&r);
+        assert_eq!(
+            command,
+            "curl -X GET 'http://localhost/containers/json' \\\n  \
+             --unix-socket '/var/run/docker.sock'"
+        );
+        assert_eq!(parse_curl_command(&command).unwrap().unix_socket, r.unix_socket);
+
+        let r = HttpRequest {
+            method: "POST".to_string(),
+            url: "example.com".to_string(),
+            body_type: Some("multipart/form-data".to_string()),
+            body: BTreeMap::from([(
+                "form".to_string(),
+                json!([
+                    { "name": "a", "value": "@literal" },
+                    { "name": "b", "file": "/tmp/b.png", "contentType": "image/png" },
+                    { "name": "c", "value": "skipped", "enabled": false },
+                ]),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_curl_command(&r),
+            "curl -X POST 'http://example.com' \\\n  \
+             --form-string 'a=@literal' \\\n  \
+             --form 'b=@/tmp/b.png;type=image/png'"
+        );
+    }
+}
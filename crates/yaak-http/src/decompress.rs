@@ -29,6 +29,17 @@ impl ContentEncoding {
             _ => ContentEncoding::Identity,
         }
     }
+
+    /// The Content-Encoding header value for this encoding
+    pub fn as_header(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Deflate => "deflate",
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Zstd => "zstd",
+            ContentEncoding::Identity => "identity",
+        }
+    }
 }
 
 /// Result of decompression, containing both the decompressed data and size info
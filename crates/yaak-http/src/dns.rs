@@ -1,3 +1,4 @@
+use crate::connect_timing::ConnectTimingLayer;
 use crate::sender::HttpResponseEvent;
 use hyper_util::client::legacy::connect::dns::{
     GaiResolver as HyperGaiResolver, Name as HyperName,
@@ -20,6 +21,39 @@ pub struct ResolvedOverride {
     pub ipv6: Vec<Ipv6Addr>,
 }
 
+impl ResolvedOverride {
+    /// All addresses for the hostname, IPv4 first
+    pub fn addresses(&self) -> Vec<IpAddr> {
+        let ipv4 = self.ipv4.iter().map(|ip| IpAddr::V4(*ip));
+        let ipv6 = self.ipv6.iter().map(|ip| IpAddr::V6(*ip));
+        ipv4.chain(ipv6).collect()
+    }
+}
+
+/// Parse enabled DNS overrides into a lookup map keyed by lowercase hostname, skipping any
+/// override without at least one valid address
+pub fn parse_dns_overrides(dns_overrides: Vec<DnsOverride>) -> HashMap<String, ResolvedOverride> {
+    let mut overrides = HashMap::new();
+    for o in dns_overrides {
+        if !o.enabled {
+            continue;
+        }
+        let hostname = o.hostname.to_lowercase();
+
+        let ipv4: Vec<Ipv4Addr> =
+            o.ipv4.iter().filter_map(|s| s.parse::<Ipv4Addr>().ok()).collect();
+
+        let ipv6: Vec<Ipv6Addr> =
+            o.ipv6.iter().filter_map(|s| s.parse::<Ipv6Addr>().ok()).collect();
+
+        // Only add if at least one address is valid
+        if !ipv4.is_empty() || !ipv6.is_empty() {
+            overrides.insert(hostname, ResolvedOverride { ipv4, ipv6 });
+        }
+    }
+    overrides
+}
+
 #[derive(Clone)]
 pub struct LocalhostResolver {
     fallback: HyperGaiResolver,
@@ -32,24 +66,7 @@ impl LocalhostResolver {
         let resolver = HyperGaiResolver::new();
 
         // Pre-parse DNS overrides into a lookup map
-        let mut overrides = HashMap::new();
-        for o in dns_overrides {
-            if !o.enabled {
-                continue;
-            }
-            let hostname = o.hostname.to_lowercase();
-
-            let ipv4: Vec<Ipv4Addr> =
-                o.ipv4.iter().filter_map(|s| s.parse::<Ipv4Addr>().ok()).collect();
-
-            let ipv6: Vec<Ipv6Addr> =
-                o.ipv6.iter().filter_map(|s| s.parse::<Ipv6Addr>().ok()).collect();
-
-            // Only add if at least one address is valid
-            if !ipv4.is_empty() || !ipv6.is_empty() {
-                overrides.insert(hostname, ResolvedOverride { ipv4, ipv6 });
-            }
-        }
+        let overrides = parse_dns_overrides(dns_overrides);
 
         Arc::new(Self {
             fallback: resolver,
@@ -65,6 +82,11 @@ impl LocalhostResolver {
         let mut guard = self.event_tx.write().await;
         *guard = tx;
     }
+
+    /// Report when each new connection is established to the same channel as DNS events
+    pub(crate) fn connect_timing_layer(&self) -> ConnectTimingLayer {
+        ConnectTimingLayer::new(self.event_tx.clone())
+    }
 }
 
 impl Resolve for LocalhostResolver {
@@ -79,17 +101,8 @@ impl Resolve for LocalhostResolver {
         if let Some(resolved) = overrides.get(&host) {
             log::debug!("DNS override found for: {}", host);
             let hostname = host.clone();
-            let mut addrs: Vec<SocketAddr> = Vec::new();
-
-            // Add IPv4 addresses
-            for ip in &resolved.ipv4 {
-                addrs.push(SocketAddr::new(IpAddr::V4(*ip), 0));
-            }
-
-            // Add IPv6 addresses
-            for ip in &resolved.ipv6 {
-                addrs.push(SocketAddr::new(IpAddr::V6(*ip), 0));
-            }
+            let addrs: Vec<SocketAddr> =
+                resolved.addresses().into_iter().map(|ip| SocketAddr::new(ip, 0)).collect();
 
             let addresses: Vec<String> = addrs.iter().map(|a| a.ip().to_string()).collect();
 
@@ -184,3 +197,41 @@ impl Resolve for LocalhostResolver {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dns_overrides() {
+        let overrides = parse_dns_overrides(vec![
+            DnsOverride {
+                hostname: "API.example.com".to_string(),
+                ipv4: vec!["10.0.0.1".to_string(), "not-an-ip".to_string()],
+                ipv6: vec!["::1".to_string()],
+                enabled: true,
+            },
+            DnsOverride {
+                hostname: "disabled.example.com".to_string(),
+                ipv4: vec!["10.0.0.2".to_string()],
+                ipv6: vec![],
+                enabled: false,
+            },
+            DnsOverride {
+                hostname: "invalid.example.com".to_string(),
+                ipv4: vec!["999.0.0.1".to_string()],
+                ipv6: vec![],
+                enabled: true,
+            },
+        ]);
+
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(
+            overrides["api.example.com"].addresses(),
+            vec![
+                IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+                IpAddr::V6(Ipv6Addr::LOCALHOST)
+            ]
+        );
+    }
+}
@@ -24,11 +24,54 @@ pub enum Error {
     #[error("Timeout of {0:?} reached")]
     RequestTimeout(std::time::Duration),
 
+    #[error("Connect timeout of {0:?} reached")]
+    ConnectTimeout(std::time::Duration),
+
+    #[error("Read timeout of {0:?} reached")]
+    ReadTimeout(std::time::Duration),
+
     #[error("Decompression error: {0}")]
     DecompressionError(String),
 
+    #[error("Compression error: {0}")]
+    CompressionError(String),
+
     #[error("Failed to read response body: {0}")]
     BodyReadError(String),
+
+    #[error("Failed to parse curl command: {0}")]
+    CurlParseError(String),
+
+    #[error("NTLM authentication failed: {0}")]
+    NtlmError(String),
+
+    #[error("SSH tunnel error: {0}")]
+    SshTunnelError(String),
+}
+
+impl Error {
+    /// Whether the request failed because one of its timeouts elapsed
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            Error::RequestTimeout(_) | Error::ConnectTimeout(_) | Error::ReadTimeout(_) => true,
+            Error::IoError(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            _ => false,
+        }
+    }
+
+    /// Whether the request failed because a connection to the server couldn't be established
+    pub fn is_connect(&self) -> bool {
+        match self {
+            Error::Client(e) => e.is_connect() && !e.is_timeout(),
+            Error::IoError(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+            ),
+            _ => false,
+        }
+    }
 }
 
 impl Serialize for Error {
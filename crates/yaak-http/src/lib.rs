@@ -1,13 +1,20 @@
 mod chained_reader;
 pub mod client;
+pub mod codegen;
+pub mod compress;
+mod connect_timing;
 pub mod cookies;
+pub mod curl;
 pub mod decompress;
 pub mod dns;
 pub mod error;
 pub mod manager;
+pub mod ntlm;
 pub mod path_placeholders;
+mod progress_reader;
 mod proto;
 pub mod sender;
+pub mod ssh_tunnel;
 pub mod tee_reader;
 pub mod transaction;
 pub mod types;
@@ -1,10 +1,13 @@
 use crate::client::{ConfiguredClient, HttpConnectionOptions};
 use crate::dns::LocalhostResolver;
 use crate::error::Result;
+use crate::ssh_tunnel::{SshTunnelManager, SshTunnelProxy};
 use std::collections::BTreeMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
+use yaak_models::models::SshTunnel;
 
 /// A cached HTTP client along with its DNS resolver.
 /// The resolver is needed to set the event sender per-request.
@@ -16,6 +19,7 @@ pub struct CachedClient {
 pub struct HttpConnectionManager {
     connections: Arc<RwLock<BTreeMap<String, (CachedClient, Instant)>>>,
     ttl: Duration,
+    ssh_tunnels: SshTunnelManager,
 }
 
 impl HttpConnectionManager {
@@ -23,12 +27,18 @@ impl HttpConnectionManager {
         Self {
             connections: Arc::new(RwLock::new(BTreeMap::new())),
             ttl: Duration::from_secs(10 * 60),
+            ssh_tunnels: SshTunnelManager::default(),
         }
     }
 
+    /// Open an SSH tunnel, or reuse an open one, returning its local SOCKS5 proxy
+    pub async fn open_ssh_tunnel(&self, tunnel: &SshTunnel) -> Result<SshTunnelProxy> {
+        self.ssh_tunnels.open(tunnel).await
+    }
+
     pub async fn get_client(&self, opt: &HttpConnectionOptions) -> Result<CachedClient> {
         let mut connections = self.connections.write().await;
-        let id = opt.id.clone();
+        let id = cache_key(opt);
 
         // Clean old connections
         connections.retain(|_, (_, last_used)| last_used.elapsed() <= self.ttl);
@@ -43,8 +53,69 @@ impl HttpConnectionManager {
 
         let (client, resolver) = opt.build_client()?;
         let cached = CachedClient { client: client.clone(), resolver: resolver.clone() };
-        connections.insert(id.into(), (cached, Instant::now()));
+        connections.insert(id, (cached, Instant::now()));
 
         Ok(CachedClient { client, resolver })
     }
 }
+
+/// Key a client by its ID and a hash of every option it was built with, so a client is only
+/// reused for requests that would have built an identical one
+fn cache_key(opt: &HttpConnectionOptions) -> String {
+    let mut hasher = DefaultHasher::new();
+    opt.hash(&mut hasher);
+    format!("{}_{:x}", opt.id, hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::HttpConnectionProxySetting;
+    use yaak_models::models::HttpVersion;
+    use yaak_tls::CaCertificateConfig;
+
+    fn options() -> HttpConnectionOptions {
+        HttpConnectionOptions {
+            id: "window_1".to_string(),
+            validate_certificates: true,
+            proxy: HttpConnectionProxySetting::System,
+            client_certificate: None,
+            ca_certificates: Vec::new(),
+            dns_overrides: Vec::new(),
+            http_version: HttpVersion::Auto,
+            unix_socket: None,
+            connect_timeout: None,
+            read_timeout: None,
+            connection_auth: false,
+        }
+    }
+
+    #[test]
+    fn cache_key_covers_every_option() {
+        let key = cache_key(&options());
+        assert_eq!(key, cache_key(&options()));
+
+        let with_ca = HttpConnectionOptions {
+            ca_certificates: vec![CaCertificateConfig {
+                file: Some("/tmp/ca.pem".to_string()),
+                pem: None,
+            }],
+            ..options()
+        };
+        let with_proxy = HttpConnectionOptions {
+            proxy: HttpConnectionProxySetting::Enabled {
+                http: "http://proxy:8080".to_string(),
+                https: "http://proxy:8080".to_string(),
+                auth: None,
+                bypass: String::new(),
+            },
+            ..options()
+        };
+        let without_validation =
+            HttpConnectionOptions { validate_certificates: false, ..options() };
+
+        for other in [with_ca, with_proxy, without_validation] {
+            assert_ne!(cache_key(&other), key);
+        }
+    }
+}
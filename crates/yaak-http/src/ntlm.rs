@@ -0,0 +1,328 @@
+use crate::error::{Error, Result};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use hmac::{Hmac, Mac};
+use md4::{Digest, Md4};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SIGNATURE: &[u8; 8] = b"NTLMSSP\0";
+
+const NEGOTIATE_UNICODE: u32 = 0x0000_0001;
+const NEGOTIATE_OEM: u32 = 0x0000_0002;
+const REQUEST_TARGET: u32 = 0x0000_0004;
+const NEGOTIATE_NTLM: u32 = 0x0000_0200;
+const NEGOTIATE_ALWAYS_SIGN: u32 = 0x0000_8000;
+const NEGOTIATE_EXTENDED_SESSION_SECURITY: u32 = 0x0008_0000;
+const NEGOTIATE_TARGET_INFO: u32 = 0x0080_0000;
+const NEGOTIATE_128: u32 = 0x2000_0000;
+const NEGOTIATE_KEY_EXCH: u32 = 0x4000_0000;
+const NEGOTIATE_56: u32 = 0x8000_0000;
+
+const NEGOTIATE_FLAGS: u32 = NEGOTIATE_UNICODE
+    | NEGOTIATE_OEM
+    | REQUEST_TARGET
+    | NEGOTIATE_NTLM
+    | NEGOTIATE_ALWAYS_SIGN
+    | NEGOTIATE_EXTENDED_SESSION_SECURITY
+    | NEGOTIATE_128
+    | NEGOTIATE_56;
+
+/// AV pair in the challenge's target info that carries the server time
+const MSV_AV_TIMESTAMP: u16 = 7;
+
+/// Seconds between the Windows FILETIME epoch (1601) and the Unix epoch
+const FILETIME_EPOCH_OFFSET_SECS: u64 = 11_644_473_600;
+
+/// Credentials for NTLM (Windows) authentication. The username may include the domain as
+/// `DOMAIN\user` when no domain is set.
+#[derive(Default, Clone)]
+pub struct NtlmCredentials {
+    pub username: String,
+    pub password: String,
+    pub domain: String,
+    pub workstation: String,
+}
+
+impl NtlmCredentials {
+    fn user_and_domain(&self) -> (&str, &str) {
+        match self.username.split_once('\\') {
+            Some((domain, user)) if self.domain.is_empty() => (user, domain),
+            _ => (self.username.as_str(), self.domain.as_str()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Challenge {
+    flags: u32,
+    server_challenge: [u8; 8],
+    target_info: Vec<u8>,
+}
+
+/// Build the `Authorization` header value for the first (negotiate) leg of the handshake
+pub(crate) fn negotiate_header() -> String {
+    let mut msg = Vec::with_capacity(32);
+    msg.extend_from_slice(SIGNATURE);
+    msg.extend_from_slice(&1u32.to_le_bytes());
+    msg.extend_from_slice(&NEGOTIATE_FLAGS.to_le_bytes());
+    // Empty domain and workstation buffers
+    msg.extend_from_slice(&[0u8; 16]);
+    format!("NTLM {}", BASE64_STANDARD.encode(msg))
+}
+
+/// Find the NTLM challenge in `WWW-Authenticate` response headers. Servers may send it with the
+/// `NTLM` scheme or wrapped in `Negotiate`, and several schemes may share one header.
+pub(crate) fn find_challenge(headers: &[(String, String)]) -> Option<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("www-authenticate"))
+        .flat_map(|(_, value)| value.split(','))
+        .filter_map(|v| v.trim().split_once(' '))
+        .find(|(scheme, token)| {
+            (scheme.eq_ignore_ascii_case("ntlm") || scheme.eq_ignore_ascii_case("negotiate"))
+                && !token.trim().is_empty()
+        })
+        .map(|(scheme, token)| (scheme.to_string(), token.trim().to_string()))
+}
+
+/// Build the `Authorization` header value for the final (authenticate) leg of the handshake,
+/// answering the server's challenge with an NTLMv2 response
+pub(crate) fn authenticate_header(
+    scheme: &str,
+    challenge: &str,
+    credentials: &NtlmCredentials,
+) -> Result<String> {
+    let challenge = BASE64_STANDARD
+        .decode(challenge)
+        .map_err(|e| Error::NtlmError(format!("Invalid challenge encoding: {e}")))?;
+    let challenge = parse_challenge(&challenge)?;
+    let client_challenge: [u8; 8] = rand::random();
+    let msg = authenticate_message(&challenge, credentials, client_challenge, filetime_now());
+    Ok(format!("{scheme} {}", BASE64_STANDARD.encode(msg)))
+}
+
+fn parse_challenge(msg: &[u8]) -> Result<Challenge> {
+    if msg.len() < 32 || &msg[..8] != SIGNATURE || read_u32(msg, 8) != 2 {
+        return Err(Error::NtlmError("Server sent an invalid challenge message".to_string()));
+    }
+
+    let mut server_challenge = [0u8; 8];
+    server_challenge.copy_from_slice(&msg[24..32]);
+
+    // Target info is only present in NTLMv2 capable challenges
+    let target_info = if msg.len() >= 48 {
+        let len = read_u16(msg, 40) as usize;
+        let offset = read_u32(msg, 44) as usize;
+        msg.get(offset..offset + len).map(|b| b.to_vec()).unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    Ok(Challenge { flags: read_u32(msg, 20), server_challenge, target_info })
+}
+
+fn authenticate_message(
+    challenge: &Challenge,
+    credentials: &NtlmCredentials,
+    client_challenge: [u8; 8],
+    timestamp: u64,
+) -> Vec<u8> {
+    let (user, domain) = credentials.user_and_domain();
+    let response_key = nt_owf_v2(user, domain, &credentials.password);
+
+    // Use the server's clock if it sent one, in which case the LM response must be empty
+    let server_timestamp = find_av_pair(&challenge.target_info, MSV_AV_TIMESTAMP)
+        .and_then(|v| v.try_into().ok())
+        .map(u64::from_le_bytes);
+
+    let mut temp = vec![0x01, 0x01, 0, 0, 0, 0, 0, 0];
+    temp.extend_from_slice(&server_timestamp.unwrap_or(timestamp).to_le_bytes());
+    temp.extend_from_slice(&client_challenge);
+    temp.extend_from_slice(&[0u8; 4]);
+    temp.extend_from_slice(&challenge.target_info);
+    temp.extend_from_slice(&[0u8; 4]);
+
+    let nt_proof = hmac_md5(&response_key, &[challenge.server_challenge.as_slice(), &temp]);
+    let nt_response = [nt_proof.as_slice(), temp.as_slice()].concat();
+    let lm_response = match server_timestamp {
+        Some(_) => vec![0u8; 24],
+        None => lm_v2_response(&response_key, &challenge.server_challenge, &client_challenge),
+    };
+
+    // Only ask for what's needed to authenticate, since no session key is exchanged
+    let flags = (challenge.flags & NEGOTIATE_FLAGS & !NEGOTIATE_KEY_EXCH & !NEGOTIATE_OEM)
+        | NEGOTIATE_UNICODE
+        | NEGOTIATE_NTLM
+        | (challenge.flags & NEGOTIATE_TARGET_INFO);
+
+    let fields = [
+        lm_response,
+        nt_response,
+        utf16le(domain),
+        utf16le(user),
+        utf16le(&credentials.workstation),
+        Vec::new(), // Encrypted random session key
+    ];
+
+    let header_len = 64;
+    let mut msg = Vec::with_capacity(header_len + fields.iter().map(Vec::len).sum::<usize>());
+    msg.extend_from_slice(SIGNATURE);
+    msg.extend_from_slice(&3u32.to_le_bytes());
+    let mut offset = header_len;
+    for field in &fields {
+        msg.extend_from_slice(&(field.len() as u16).to_le_bytes());
+        msg.extend_from_slice(&(field.len() as u16).to_le_bytes());
+        msg.extend_from_slice(&(offset as u32).to_le_bytes());
+        offset += field.len();
+    }
+    msg.extend_from_slice(&flags.to_le_bytes());
+    for field in &fields {
+        msg.extend_from_slice(field);
+    }
+    msg
+}
+
+fn nt_owf_v2(user: &str, domain: &str, password: &str) -> [u8; 16] {
+    let nt_hash = Md4::digest(utf16le(password));
+    let identity = utf16le(&format!("{}{}", user.to_uppercase(), domain));
+    hmac_md5(&nt_hash, &[identity.as_slice()])
+}
+
+fn lm_v2_response(
+    response_key: &[u8; 16],
+    server_challenge: &[u8; 8],
+    client_challenge: &[u8; 8],
+) -> Vec<u8> {
+    let proof = hmac_md5(response_key, &[server_challenge.as_slice(), client_challenge.as_slice()]);
+    [proof.as_slice(), client_challenge.as_slice()].concat()
+}
+
+fn hmac_md5(key: &[u8], data: &[&[u8]]) -> [u8; 16] {
+    let mut mac = Hmac::<md5::Md5>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    for d in data {
+        mac.update(d);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+fn find_av_pair(target_info: &[u8], id: u16) -> Option<&[u8]> {
+    let mut i = 0;
+    while i + 4 <= target_info.len() {
+        let av_id = read_u16(target_info, i);
+        let len = read_u16(target_info, i + 2) as usize;
+        if av_id == 0 {
+            break;
+        }
+        let value = target_info.get(i + 4..i + 4 + len)?;
+        if av_id == id {
+            return Some(value);
+        }
+        i += 4 + len;
+    }
+    None
+}
+
+/// The current time as a Windows FILETIME (100ns intervals since 1601)
+fn filetime_now() -> u64 {
+    let since_epoch = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    (since_epoch.as_secs() + FILETIME_EPOCH_OFFSET_SECS) * 10_000_000
+        + u64::from(since_epoch.subsec_nanos() / 100)
+}
+
+fn utf16le(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+fn read_u16(b: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([b[offset], b[offset + 1]])
+}
+
+fn read_u32(b: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([b[offset], b[offset + 1], b[offset + 2], b[offset + 3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(b: &[u8]) -> String {
+        b.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn computes_ntlmv2_keys_from_spec_vectors() {
+        // Test vectors from MS-NLMP section 4.2.4
+        let key = nt_owf_v2("User", "Domain", "Password");
+        assert_eq!(hex(&key), "0c868a403bfd7a93a3001ef22ef02e3f");
+
+        let lm =
+            lm_v2_response(&key, &[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef], &[0xaa; 8]);
+        assert_eq!(hex(&lm), "86c35097ac9cec102554764a57cccc19aaaaaaaaaaaaaaaa");
+    }
+
+    #[test]
+    fn finds_challenge_in_www_authenticate_headers() {
+        let headers = vec![
+            ("WWW-Authenticate".to_string(), "Negotiate".to_string()),
+            (
+                "www-authenticate".to_string(),
+                "Basic realm=\"x\", NTLM TlRMTVNTUAACAA==".to_string(),
+            ),
+        ];
+        assert_eq!(
+            find_challenge(&headers),
+            Some(("NTLM".to_string(), "TlRMTVNTUAACAA==".to_string()))
+        );
+
+        let headers = vec![("WWW-Authenticate".to_string(), "NTLM".to_string())];
+        assert_eq!(find_challenge(&headers), None);
+    }
+
+    #[test]
+    fn builds_authenticate_message_for_challenge() {
+        let target_info = [
+            [2u8, 0, 12, 0].as_slice(),
+            utf16le("Domain").as_slice(),
+            [7, 0, 8, 0].as_slice(),
+            42u64.to_le_bytes().as_slice(),
+            [0, 0, 0, 0].as_slice(),
+        ]
+        .concat();
+        let mut challenge = SIGNATURE.to_vec();
+        challenge.extend_from_slice(&2u32.to_le_bytes());
+        challenge.extend_from_slice(&[0u8; 8]); // Target name
+        challenge.extend_from_slice(&(NEGOTIATE_FLAGS | NEGOTIATE_TARGET_INFO).to_le_bytes());
+        challenge.extend_from_slice(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef]);
+        challenge.extend_from_slice(&[0u8; 8]);
+        challenge.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        challenge.extend_from_slice(&(target_info.len() as u16).to_le_bytes());
+        challenge.extend_from_slice(&48u32.to_le_bytes());
+        challenge.extend_from_slice(&target_info);
+
+        let challenge = parse_challenge(&challenge).unwrap();
+        assert_eq!(challenge.target_info, target_info);
+
+        let credentials = NtlmCredentials {
+            username: "CORP\\alice".to_string(),
+            password: "secret".to_string(),
+            ..Default::default()
+        };
+        let msg = authenticate_message(&challenge, &credentials, [0xaa; 8], 0);
+        assert_eq!(&msg[..8], SIGNATURE);
+        assert_eq!(read_u32(&msg, 8), 3);
+
+        let field = |i: usize| {
+            let len = read_u16(&msg, 12 + i * 8) as usize;
+            let offset = read_u32(&msg, 16 + i * 8) as usize;
+            &msg[offset..offset + len]
+        };
+        // The server sent a timestamp, so it's used and the LM response is empty
+        assert_eq!(field(0), &[0u8; 24]);
+        assert_eq!(&field(1)[24..32], &42u64.to_le_bytes());
+        assert_eq!(field(2), utf16le("CORP").as_slice());
+        assert_eq!(field(3), utf16le("alice").as_slice());
+        assert_eq!(read_u32(&msg, 60) & NEGOTIATE_KEY_EXCH, 0);
+
+        assert!(parse_challenge(b"NTLMSSP\0\x01\0\0\0").is_err());
+    }
+}
@@ -0,0 +1,103 @@
+use crate::sender::HttpResponseEvent;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::mpsc;
+
+const UPLOAD_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A reader that reports how much of a request body has been sent, at most once per interval
+/// plus once more when the body is finished
+pub(crate) struct UploadProgressReader<R> {
+    inner: R,
+    event_tx: mpsc::Sender<HttpResponseEvent>,
+    total: Option<u64>,
+    bytes: u64,
+    last_reported_at: Instant,
+    finished: bool,
+}
+
+impl<R> UploadProgressReader<R> {
+    pub(crate) fn new(
+        inner: R,
+        event_tx: mpsc::Sender<HttpResponseEvent>,
+        total: Option<u64>,
+    ) -> Self {
+        Self { inner, event_tx, total, bytes: 0, last_reported_at: Instant::now(), finished: false }
+    }
+
+    fn report(&mut self) {
+        self.last_reported_at = Instant::now();
+        // Progress is best-effort, so it's fine to drop an update when the channel is full
+        let _ = self
+            .event_tx
+            .try_send(HttpResponseEvent::UploadProgress { bytes: self.bytes, total: self.total });
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for UploadProgressReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let before_len = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = result {
+            let read = (buf.filled().len() - before_len) as u64;
+            self.bytes += read;
+
+            let eof = read == 0 && buf.remaining() > 0;
+            if (eof || self.total == Some(self.bytes)) && !self.finished {
+                self.finished = true;
+                self.report();
+            } else if read > 0 && self.last_reported_at.elapsed() >= UPLOAD_PROGRESS_INTERVAL {
+                self.report();
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use tokio::io::AsyncReadExt;
+
+    fn progress_events(rx: &mut mpsc::Receiver<HttpResponseEvent>) -> Vec<(u64, Option<u64>)> {
+        let mut events = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let HttpResponseEvent::UploadProgress { bytes, total } = event {
+                events.push((bytes, total));
+            }
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_upload_progress_reports_completion_once() {
+        let data = b"Hello, World!".to_vec();
+        let (tx, mut rx) = mpsc::channel(10);
+
+        let mut reader = UploadProgressReader::new(Cursor::new(data.clone()), tx, Some(13));
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output).await.unwrap();
+
+        assert_eq!(output, data);
+        assert_eq!(progress_events(&mut rx), vec![(13, Some(13))]);
+    }
+
+    #[tokio::test]
+    async fn test_upload_progress_without_known_length() {
+        let (tx, mut rx) = mpsc::channel(10);
+
+        let mut reader = UploadProgressReader::new(Cursor::new(b"abc".to_vec()), tx, None);
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output).await.unwrap();
+
+        assert_eq!(progress_events(&mut rx), vec![(3, None)]);
+    }
+}
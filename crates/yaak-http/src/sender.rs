@@ -1,18 +1,25 @@
 use crate::decompress::{ContentEncoding, streaming_decoder};
 use crate::error::{Error, Result};
+use crate::progress_reader::UploadProgressReader;
 use crate::types::{SendableBody, SendableHttpRequest};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures_util::StreamExt;
 use http_body::{Body as HttpBody, Frame, SizeHint};
+use reqwest::header::{CONTENT_LENGTH, HOST, HeaderMap, TRANSFER_ENCODING};
 use reqwest::{Method, Version};
 use std::fmt::Display;
 use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::task::{Context, Poll};
-use std::time::Duration;
+use std::time::Instant;
 use tokio::io::{AsyncRead, AsyncReadExt, BufReader, ReadBuf};
 use tokio::sync::mpsc;
 use tokio_util::io::StreamReader;
+use url::Host;
+use yaak_models::models::{HttpResponseTls, HttpVersion};
+use yaak_tls::{TlsHandshakes, describe_certificate};
 
 #[derive(Debug, Clone)]
 pub enum RedirectBehavior {
@@ -22,6 +29,17 @@ pub enum RedirectBehavior {
     DropBody,
 }
 
+/// A redirect response that was followed on the way to the final response
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedirectHop {
+    /// URL of the request that was redirected
+    pub url: String,
+    pub method: String,
+    pub status: u16,
+    /// URL the redirect pointed to
+    pub location: String,
+}
+
 #[derive(Debug, Clone)]
 pub enum HttpResponseEvent {
     Setting {
@@ -68,6 +86,23 @@ pub enum HttpResponseEvent {
         duration: u64,
         overridden: bool,
     },
+    Connected {
+        duration: u64,
+    },
+    Retry {
+        attempt: u32,
+        max_attempts: u32,
+        delay: u64,
+        reason: String,
+    },
+    DownloadProgress {
+        bytes: u64,
+        total: Option<u64>,
+    },
+    UploadProgress {
+        bytes: u64,
+        total: Option<u64>,
+    },
 }
 
 impl Display for HttpResponseEvent {
@@ -146,6 +181,24 @@ impl Display for HttpResponseEvent {
                     )
                 }
             }
+            HttpResponseEvent::Connected { duration } => {
+                write!(f, "* Connection established ({}ms)", duration)
+            }
+            HttpResponseEvent::Retry { attempt, max_attempts, delay, reason } => {
+                write!(
+                    f,
+                    "* Retrying in {}ms after {} (attempt {} of {})",
+                    delay, reason, attempt, max_attempts
+                )
+            }
+            HttpResponseEvent::DownloadProgress { bytes, total } => match total {
+                Some(total) => write!(f, "* Downloaded {} of {} bytes", bytes, total),
+                None => write!(f, "* Downloaded {} bytes", bytes),
+            },
+            HttpResponseEvent::UploadProgress { bytes, total } => match total {
+                Some(total) => write!(f, "* Uploaded {} of {} bytes", bytes, total),
+                None => write!(f, "* Uploaded {} bytes", bytes),
+            },
         }
     }
 }
@@ -197,6 +250,16 @@ impl From<HttpResponseEvent> for yaak_models::models::HttpResponseEventData {
             HttpResponseEvent::DnsResolved { hostname, addresses, duration, overridden } => {
                 D::DnsResolved { hostname, addresses, duration, overridden }
             }
+            HttpResponseEvent::Connected { duration } => D::Connected { duration },
+            HttpResponseEvent::Retry { attempt, max_attempts, delay, reason } => {
+                D::Retry { attempt, max_attempts, delay, reason }
+            }
+            HttpResponseEvent::DownloadProgress { bytes, total } => {
+                D::DownloadProgress { bytes, total }
+            }
+            HttpResponseEvent::UploadProgress { bytes, total } => {
+                D::UploadProgress { bytes, total }
+            }
         }
     }
 }
@@ -245,6 +308,27 @@ impl<R: AsyncRead + Unpin> AsyncRead for TrackingRead<R> {
     }
 }
 
+/// An AsyncRead wrapper that counts the bytes read through it into a shared counter
+struct CountingRead<R> {
+    inner: R,
+    count: Arc<AtomicU64>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for CountingRead<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let before = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if let Poll::Ready(Ok(())) = &result {
+            self.count.fetch_add((buf.filled().len() - before) as u64, Ordering::Relaxed);
+        }
+        result
+    }
+}
+
 /// Type alias for the body stream
 type BodyStream = Pin<Box<dyn AsyncRead + Send>>;
 
@@ -268,11 +352,19 @@ pub struct HttpResponse {
     pub remote_addr: Option<String>,
     /// HTTP version (e.g., "HTTP/1.1", "HTTP/2")
     pub version: Option<String>,
+    /// Redirects followed before this response, in order
+    pub redirects: Vec<RedirectHop>,
+    /// TLS connection details, for HTTPS responses
+    pub tls: Option<HttpResponseTls>,
+    /// Request line and headers of the request, as written to the connection
+    pub request_head: Option<String>,
 
     /// The body stream (consumed when calling bytes(), text(), write_to_file(), or drain())
     body_stream: Option<BodyStream>,
     /// Content-Encoding for decompression
     encoding: ContentEncoding,
+    /// Body bytes read off the wire so far, before decompression
+    compressed_bytes: Arc<AtomicU64>,
 }
 
 impl std::fmt::Debug for HttpResponse {
@@ -285,8 +377,12 @@ impl std::fmt::Debug for HttpResponse {
             .field("url", &self.url)
             .field("remote_addr", &self.remote_addr)
             .field("version", &self.version)
+            .field("redirects", &self.redirects)
+            .field("tls", &self.tls)
+            .field("request_head", &self.request_head)
             .field("body_stream", &"<stream>")
             .field("encoding", &self.encoding)
+            .field("compressed_bytes", &self.compressed_bytes)
             .finish()
     }
 }
@@ -306,6 +402,9 @@ impl HttpResponse {
         body_stream: BodyStream,
         encoding: ContentEncoding,
     ) -> Self {
+        let compressed_bytes = Arc::new(AtomicU64::new(0));
+        let body_stream: BodyStream =
+            Box::pin(CountingRead { inner: body_stream, count: compressed_bytes.clone() });
         Self {
             status,
             status_reason,
@@ -315,11 +414,21 @@ impl HttpResponse {
             url,
             remote_addr,
             version,
+            redirects: Vec::new(),
+            tls: None,
+            request_head: None,
             body_stream: Some(body_stream),
             encoding,
+            compressed_bytes,
         }
     }
 
+    /// Number of body bytes read off the wire so far, before decompression. The counter is
+    /// shared, so it keeps counting after the body stream is taken with `into_body_stream()`.
+    pub fn compressed_bytes_counter(&self) -> Arc<AtomicU64> {
+        self.compressed_bytes.clone()
+    }
+
     /// Consume the body and return it as bytes (loads entire body into memory).
     /// Also decompresses the body if Content-Encoding is set.
     pub async fn bytes(mut self) -> Result<(Vec<u8>, BodyStats)> {
@@ -331,16 +440,12 @@ impl HttpResponse {
         let mut decoder = streaming_decoder(buf_reader, self.encoding);
 
         let mut decompressed = Vec::new();
-        let mut bytes_read = 0u64;
-
-        // Read through the decoder in chunks to track compressed size
         let mut buf = [0u8; 8192];
         loop {
             match decoder.read(&mut buf).await {
                 Ok(0) => break,
                 Ok(n) => {
                     decompressed.extend_from_slice(&buf[..n]);
-                    bytes_read += n as u64;
                 }
                 Err(e) => {
                     return Err(Error::BodyReadError(e.to_string()));
@@ -349,9 +454,7 @@ impl HttpResponse {
         }
 
         let stats = BodyStats {
-            // For now, we can't easily track compressed size when streaming through decoder
-            // Use content_length as an approximation, or decompressed size if identity encoding
-            size_compressed: self.content_length.unwrap_or(bytes_read),
+            size_compressed: self.compressed_bytes.load(Ordering::Relaxed),
             size_decompressed: decompressed.len() as u64,
         };
 
@@ -464,6 +567,11 @@ impl HttpSender for ReqwestSender {
             req_builder = req_builder.header(&header.0, &header.1);
         }
 
+        // HTTP/3 is only used when asked for by the request itself
+        if request.options.http_version == HttpVersion::Http3 {
+            req_builder = req_builder.version(Version::HTTP_3);
+        }
+
         // Configure timeout
         if let Some(d) = request.options.timeout
             && !d.is_zero()
@@ -472,6 +580,11 @@ impl HttpSender for ReqwestSender {
         }
 
         // Add body
+        let body_length = match &request.body {
+            None => None,
+            Some(SendableBody::Bytes(bytes)) => Some(Some(bytes.len() as u64)),
+            Some(SendableBody::Stream { content_length, .. }) => Some(*content_length),
+        };
         match request.body {
             None => {}
             Some(SendableBody::Bytes(bytes)) => {
@@ -481,6 +594,7 @@ impl HttpSender for ReqwestSender {
                 // Convert AsyncRead stream to reqwest Body. If content length is
                 // known, wrap with a SizedBody so hyper can set Content-Length
                 // automatically (for both HTTP/1.1 and HTTP/2).
+                let data = UploadProgressReader::new(data, event_tx.clone(), content_length);
                 let stream = tokio_util::io::ReaderStream::new(data);
                 let body = if let Some(len) = content_length {
                     reqwest::Body::wrap(SizedBody::new(stream, len))
@@ -505,6 +619,10 @@ impl HttpSender for ReqwestSender {
             fragment: sendable_req.url().fragment().unwrap_or_default().to_string(),
         });
 
+        let sent_method = sendable_req.method().clone();
+        let sent_url = sendable_req.url().clone();
+        let sent_headers = sendable_req.headers().clone();
+
         let mut request_headers = Vec::new();
         for (name, value) in sendable_req.headers() {
             let v = value.to_str().unwrap_or_default().to_string();
@@ -514,11 +632,10 @@ impl HttpSender for ReqwestSender {
         send_event(HttpResponseEvent::Info("Sending request to server".to_string()));
 
         // Map some errors to our own, so they look nicer
+        let started_at = Instant::now();
         let response = self.client.inner().execute(sendable_req).await.map_err(|e| {
             if reqwest::Error::is_timeout(&e) {
-                Error::RequestTimeout(
-                    request.options.timeout.unwrap_or(Duration::from_secs(0)).clone(),
-                )
+                request.options.timeout_error(started_at.elapsed(), e.is_connect())
             } else {
                 Error::Client(e)
             }
@@ -530,6 +647,9 @@ impl HttpSender for ReqwestSender {
         let remote_addr = response.remote_addr().map(|a| a.to_string());
         let version = Some(version_to_str(&response.version()));
         let content_length = response.content_length();
+        let tls = response_tls(&response, self.client.tls_handshakes());
+        let request_head =
+            request_head(&sent_method, &sent_url, &sent_headers, response.version(), body_length);
 
         send_event(HttpResponseEvent::ReceiveUrl {
             version: response.version(),
@@ -545,28 +665,38 @@ impl HttpSender for ReqwestSender {
             }
         }
 
-        // Determine content encoding for decompression
+        // Determine content encoding for decompression, unless the body should be kept as-is
         // HTTP headers are case-insensitive, so we need to search for any casing
-        let encoding = ContentEncoding::from_header(
-            headers
-                .iter()
-                .find(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
-                .map(|(_, v)| v.as_str()),
-        );
+        let encoding = if request.options.skip_decompression {
+            ContentEncoding::Identity
+        } else {
+            ContentEncoding::from_header(
+                headers
+                    .iter()
+                    .find(|(k, _)| k.eq_ignore_ascii_case("content-encoding"))
+                    .map(|(_, v)| v.as_str()),
+            )
+        };
 
         // Get the byte stream instead of loading into memory
         let byte_stream = response.bytes_stream();
 
         // Convert the stream to an AsyncRead
-        let stream_reader = StreamReader::new(
-            byte_stream.map(|result| result.map_err(|e| std::io::Error::other(e))),
-        );
+        let stream_reader = StreamReader::new(byte_stream.map(|result| {
+            result.map_err(|e| {
+                if e.is_timeout() {
+                    std::io::Error::new(std::io::ErrorKind::TimedOut, e)
+                } else {
+                    std::io::Error::other(e)
+                }
+            })
+        }));
 
         // Wrap the stream with tracking to emit chunk received events via the same channel
         let tracking_reader = TrackingRead::new(stream_reader, event_tx);
         let body_stream: BodyStream = Box::pin(tracking_reader);
 
-        Ok(HttpResponse::new(
+        let mut response = HttpResponse::new(
             status,
             status_reason,
             headers,
@@ -577,10 +707,101 @@ impl HttpSender for ReqwestSender {
             version,
             body_stream,
             encoding,
-        ))
+        );
+        response.tls = tls;
+        response.request_head = Some(request_head);
+        Ok(response)
     }
 }
 
+/// Rebuild the request line and headers the client wrote for a request. The client adds the
+/// `Host` header and body framing headers on its own, so they're added here the same way.
+/// HTTP/2 and HTTP/3 headers are written as text too, even though they're binary frames on the
+/// wire. `body_length` is `None` without a body, or `Some(None)` for a body of unknown length.
+fn request_head(
+    method: &Method,
+    url: &reqwest::Url,
+    headers: &HeaderMap,
+    version: Version,
+    body_length: Option<Option<u64>>,
+) -> String {
+    let mut target = url.path().to_string();
+    if let Some(query) = url.query() {
+        target.push('?');
+        target.push_str(query);
+    }
+
+    let mut head = format!("{} {} {}\r\n", method, target, version_to_str(&version));
+    if !headers.contains_key(HOST) {
+        let host = url.host_str().unwrap_or_default();
+        match url.port() {
+            Some(port) => head.push_str(&format!("host: {}:{}\r\n", host, port)),
+            None => head.push_str(&format!("host: {}\r\n", host)),
+        }
+    }
+    for (name, value) in headers {
+        head.push_str(&format!("{}: {}\r\n", name, String::from_utf8_lossy(value.as_bytes())));
+    }
+
+    let framed = headers.contains_key(CONTENT_LENGTH) || headers.contains_key(TRANSFER_ENCODING);
+    match body_length {
+        Some(Some(length)) if !framed && length > 0 => {
+            head.push_str(&format!("content-length: {}\r\n", length));
+        }
+        Some(None) if !framed && version < Version::HTTP_2 => {
+            head.push_str("transfer-encoding: chunked\r\n");
+        }
+        _ => {}
+    }
+
+    head.push_str("\r\n");
+    head
+}
+
+/// TLS details of an HTTPS response. They come from the client's recorded handshake with the
+/// host, or only the server certificate when the handshake wasn't recorded, like when
+/// certificates aren't verified.
+fn response_tls(
+    response: &reqwest::Response,
+    handshakes: &TlsHandshakes,
+) -> Option<HttpResponseTls> {
+    if response.url().scheme() != "https" {
+        return None;
+    }
+
+    let host = match response.url().host()? {
+        Host::Domain(domain) => domain.to_string(),
+        Host::Ipv4(ip) => ip.to_string(),
+        Host::Ipv6(ip) => ip.to_string(),
+    };
+    let handshake = handshakes.get(&host).filter(|h| !h.certificates.is_empty());
+    let certificates = match &handshake {
+        Some(handshake) => {
+            handshake.certificates.iter().map(|c| describe_certificate(c.as_ref())).collect()
+        }
+        None => response
+            .extensions()
+            .get::<reqwest::tls::TlsInfo>()
+            .and_then(|info| info.peer_certificate())
+            .map(|der| vec![describe_certificate(der)])
+            .unwrap_or_default(),
+    };
+
+    // HTTP/2 and HTTP/3 are only spoken over TLS once agreed on with ALPN, while HTTP/1.1 is
+    // also used by servers that don't support ALPN at all
+    let alpn = match response.version() {
+        Version::HTTP_2 => Some("h2".to_string()),
+        Version::HTTP_3 => Some("h3".to_string()),
+        _ => None,
+    };
+
+    Some(HttpResponseTls {
+        version: handshake.and_then(|h| h.version).map(|v| v.to_string()),
+        alpn,
+        certificates,
+    })
+}
+
 /// A wrapper around a byte stream that reports a known content length via
 /// `size_hint()`. This lets hyper set the `Content-Length` header
 /// automatically based on the body size, without us having to add it as an
@@ -639,3 +860,71 @@ fn version_to_str(version: &Version) -> String {
         _ => "unknown".to_string(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compress::compress;
+
+    fn gzip_response(body: &[u8], encoding: ContentEncoding) -> (HttpResponse, u64) {
+        let compressed = compress(body, ContentEncoding::Gzip).unwrap();
+        let compressed_len = compressed.len() as u64;
+        let body_stream: BodyStream = Box::pin(std::io::Cursor::new(compressed));
+        let response = HttpResponse::new(
+            200,
+            None,
+            vec![("Content-Encoding".to_string(), "gzip".to_string())],
+            Vec::new(),
+            None,
+            "https://example.com".to_string(),
+            None,
+            None,
+            body_stream,
+            encoding,
+        );
+        (response, compressed_len)
+    }
+
+    #[test]
+    fn test_request_head_adds_host_and_framing_headers() {
+        let url = reqwest::Url::parse("https://example.com:8443/items?page=2").unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-signature", "abc".parse().unwrap());
+
+        let head = request_head(&Method::POST, &url, &headers, Version::HTTP_11, Some(Some(5)));
+        assert_eq!(
+            head,
+            "POST /items?page=2 HTTP/1.1\r\nhost: example.com:8443\r\nx-signature: abc\r\n\
+             content-length: 5\r\n\r\n"
+        );
+
+        let head = request_head(&Method::PUT, &url, &headers, Version::HTTP_11, Some(None));
+        assert!(head.ends_with("transfer-encoding: chunked\r\n\r\n"));
+
+        let head = request_head(&Method::PUT, &url, &headers, Version::HTTP_2, Some(None));
+        assert!(!head.contains("transfer-encoding"));
+    }
+
+    #[tokio::test]
+    async fn test_bytes_reports_compressed_and_decompressed_sizes() {
+        let original = b"hello hello hello hello hello hello hello hello".repeat(10);
+        let (response, compressed_len) = gzip_response(&original, ContentEncoding::Gzip);
+
+        let (body, stats) = response.bytes().await.unwrap();
+        assert_eq!(body, original);
+        assert_eq!(stats.size_compressed, compressed_len);
+        assert_eq!(stats.size_decompressed, original.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_compressed_bytes_counter_tracks_body_stream() {
+        let original = b"hello hello hello hello hello hello hello hello".repeat(10);
+        let (mut response, compressed_len) = gzip_response(&original, ContentEncoding::Identity);
+        let counter = response.compressed_bytes_counter();
+
+        let mut body = Vec::new();
+        response.into_body_stream().unwrap().read_to_end(&mut body).await.unwrap();
+        assert_eq!(body.len() as u64, compressed_len);
+        assert_eq!(counter.load(Ordering::Relaxed), compressed_len);
+    }
+}
@@ -0,0 +1,445 @@
+//! SSH tunnels for reaching hosts behind a bastion.
+//!
+//! A tunnel is an SSH session plus a local SOCKS5 listener, like `ssh -D`. Every connection the
+//! listener accepts is forwarded through the session with a `direct-tcpip` channel, so HTTP and
+//! gRPC clients use an open tunnel like any other `socks5h://` proxy. Other local processes can
+//! reach the listener too, so it requires a random username and password that only the tunnel's
+//! users are given.
+
+use crate::error::Error::SshTunnelError;
+use crate::error::Result;
+use log::{debug, info, warn};
+use rand::Rng;
+use rand::distr::Alphanumeric;
+use russh::client::{self, Handle};
+use russh::keys::{PrivateKeyWithHashAlg, check_known_hosts, load_secret_key, ssh_key};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+use url::Url;
+use yaak_models::models::SshTunnel;
+
+const DEFAULT_SSH_PORT: u16 = 22;
+const SOCKS_CREDENTIAL_LENGTH: usize = 32;
+
+/// Local SOCKS5 proxy of an open tunnel, with the credentials it requires
+#[derive(Debug, Clone)]
+pub struct SshTunnelProxy {
+    pub address: SocketAddr,
+    pub user: String,
+    pub password: String,
+}
+
+impl SshTunnelProxy {
+    pub fn url(&self) -> String {
+        format!("socks5h://{}", self.address)
+    }
+}
+
+/// Open tunnels, keyed by their configuration so requests sharing a tunnel share the session
+#[derive(Default)]
+pub struct SshTunnelManager {
+    tunnels: Mutex<HashMap<String, OpenTunnel>>,
+}
+
+impl SshTunnelManager {
+    /// Local SOCKS5 proxy for the tunnel, connecting the tunnel first if it isn't open yet or its
+    /// session was closed
+    pub async fn open(&self, tunnel: &SshTunnel) -> Result<SshTunnelProxy> {
+        let key = serde_json::to_string(tunnel).unwrap_or_default();
+        let mut tunnels = self.tunnels.lock().await;
+        if let Some(open) = tunnels.get(&key) {
+            if !open.session.is_closed() {
+                return Ok(open.proxy.clone());
+            }
+            debug!("SSH session to {} was closed, reconnecting", tunnel.host);
+            tunnels.remove(&key);
+        }
+
+        let open = OpenTunnel::connect(tunnel).await?;
+        let proxy = open.proxy.clone();
+        tunnels.insert(key, open);
+        Ok(proxy)
+    }
+}
+
+struct OpenTunnel {
+    proxy: SshTunnelProxy,
+    session: Arc<Handle<HostKeyCheck>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl Drop for OpenTunnel {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+impl OpenTunnel {
+    async fn connect(tunnel: &SshTunnel) -> Result<Self> {
+        let host = tunnel.host.trim().to_string();
+        if host.is_empty() {
+            return Err(SshTunnelError("Missing SSH host".to_string()));
+        }
+        let port = tunnel.port.and_then(|p| u16::try_from(p).ok()).unwrap_or(DEFAULT_SSH_PORT);
+
+        let config = Arc::new(client::Config {
+            keepalive_interval: Some(Duration::from_secs(30)),
+            ..Default::default()
+        });
+        let handler = HostKeyCheck { host: host.clone(), port };
+        let mut session =
+            client::connect(config, (host.as_str(), port), handler).await.map_err(|e| match e {
+                russh::Error::UnknownKey => SshTunnelError(format!(
+                    "Host key for {host} is missing from or doesn't match known_hosts. Connect \
+                     once with ssh to trust it."
+                )),
+                e => SshTunnelError(format!("Failed to connect to {host}:{port}: {e}")),
+            })?;
+        authenticate(&mut session, tunnel).await?;
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await?;
+        let proxy = SshTunnelProxy {
+            address: listener.local_addr()?,
+            user: random_credential(),
+            password: random_credential(),
+        };
+        let session = Arc::new(session);
+        let accept_task =
+            tokio::spawn(accept_connections(listener, session.clone(), Arc::new(proxy.clone())));
+        info!("Opened SSH tunnel through {host}:{port} on {}", proxy.address);
+
+        Ok(Self { proxy, session, accept_task })
+    }
+}
+
+/// Trusts servers whose key matches the user's `known_hosts`, like `StrictHostKeyChecking=yes`
+struct HostKeyCheck {
+    host: String,
+    port: u16,
+}
+
+impl client::Handler for HostKeyCheck {
+    type Error = russh::Error;
+
+    async fn check_server_key(
+        &mut self,
+        server_public_key: &ssh_key::PublicKey,
+    ) -> std::result::Result<bool, Self::Error> {
+        match check_known_hosts(&self.host, self.port, server_public_key) {
+            Ok(known) => Ok(known),
+            Err(e) => {
+                warn!("Rejected host key for {}: {e}", self.host);
+                Ok(false)
+            }
+        }
+    }
+}
+
+async fn authenticate(session: &mut Handle<HostKeyCheck>, tunnel: &SshTunnel) -> Result<()> {
+    let username = tunnel.username.trim();
+    let key_file = tunnel.private_key_file.trim();
+    let result = if key_file.is_empty() {
+        session.authenticate_password(username, &tunnel.password).await
+    } else {
+        let path = expand_home(key_file);
+        let passphrase = Some(tunnel.passphrase.as_str()).filter(|p| !p.is_empty());
+        let key = load_secret_key(&path, passphrase).map_err(|e| {
+            SshTunnelError(format!("Failed to load private key {}: {e}", path.display()))
+        })?;
+        let hash_alg = session
+            .best_supported_rsa_hash()
+            .await
+            .map_err(|e| SshTunnelError(e.to_string()))?
+            .flatten();
+        session
+            .authenticate_publickey(username, PrivateKeyWithHashAlg::new(Arc::new(key), hash_alg))
+            .await
+    }
+    .map_err(|e| SshTunnelError(format!("Failed to authenticate as {username}: {e}")))?;
+
+    if !result.success() {
+        return Err(SshTunnelError(format!("SSH server rejected authentication as {username}")));
+    }
+    Ok(())
+}
+
+fn random_credential() -> String {
+    rand::rng().sample_iter(&Alphanumeric).take(SOCKS_CREDENTIAL_LENGTH).map(char::from).collect()
+}
+
+async fn accept_connections(
+    listener: TcpListener,
+    session: Arc<Handle<HostKeyCheck>>,
+    proxy: Arc<SshTunnelProxy>,
+) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                warn!("Failed to accept SSH tunnel connection: {e}");
+                continue;
+            }
+        };
+        let session = session.clone();
+        let proxy = proxy.clone();
+        tokio::spawn(async move {
+            if let Err(e) = forward_connection(stream, &session, &proxy).await {
+                debug!("SSH tunnel connection ended: {e}");
+            }
+        });
+    }
+}
+
+async fn forward_connection(
+    mut stream: TcpStream,
+    session: &Handle<HostKeyCheck>,
+    proxy: &SshTunnelProxy,
+) -> Result<()> {
+    let (host, port) = socks5_accept(&mut stream, proxy).await?;
+    let channel =
+        match session.channel_open_direct_tcpip(host.as_str(), port as u32, "127.0.0.1", 0).await {
+            Ok(channel) => channel,
+            Err(e) => {
+                // Host unreachable
+                stream.write_all(&socks5_reply(0x04)).await?;
+                return Err(SshTunnelError(format!("Failed to forward to {host}:{port}: {e}")));
+            }
+        };
+    stream.write_all(&socks5_reply(0x00)).await?;
+
+    let mut channel = channel.into_stream();
+    tokio::io::copy_bidirectional(&mut stream, &mut channel).await?;
+    Ok(())
+}
+
+/// Server side of a SOCKS5 handshake, returning the destination the client asked for. Only
+/// `CONNECT` with the proxy's username and password (RFC 1929) is supported.
+async fn socks5_accept(stream: &mut TcpStream, proxy: &SshTunnelProxy) -> Result<(String, u16)> {
+    let mut greeting = [0u8; 2];
+    stream.read_exact(&mut greeting).await?;
+    if greeting[0] != 0x05 {
+        return Err(SshTunnelError(format!("Unsupported SOCKS version {}", greeting[0])));
+    }
+    let mut methods = vec![0u8; greeting[1] as usize];
+    stream.read_exact(&mut methods).await?;
+    if !methods.contains(&0x02) {
+        stream.write_all(&[0x05, 0xff]).await?;
+        return Err(SshTunnelError("SOCKS client doesn't support password auth".to_string()));
+    }
+    stream.write_all(&[0x05, 0x02]).await?;
+
+    let version = stream.read_u8().await?;
+    let mut user = vec![0u8; stream.read_u8().await? as usize];
+    stream.read_exact(&mut user).await?;
+    let mut password = vec![0u8; stream.read_u8().await? as usize];
+    stream.read_exact(&mut password).await?;
+    if version != 0x01 || user != proxy.user.as_bytes() || password != proxy.password.as_bytes() {
+        stream.write_all(&[0x01, 0x01]).await?;
+        return Err(SshTunnelError("SOCKS client sent the wrong credentials".to_string()));
+    }
+    stream.write_all(&[0x01, 0x00]).await?;
+
+    let mut request = [0u8; 4];
+    stream.read_exact(&mut request).await?;
+    if request[1] != 0x01 {
+        // Command not supported
+        stream.write_all(&socks5_reply(0x07)).await?;
+        return Err(SshTunnelError(format!("Unsupported SOCKS command {}", request[1])));
+    }
+
+    let host = match request[3] {
+        0x01 => {
+            let mut ip = [0u8; 4];
+            stream.read_exact(&mut ip).await?;
+            Ipv4Addr::from(ip).to_string()
+        }
+        0x04 => {
+            let mut ip = [0u8; 16];
+            stream.read_exact(&mut ip).await?;
+            Ipv6Addr::from(ip).to_string()
+        }
+        0x03 => {
+            let mut name = vec![0u8; stream.read_u8().await? as usize];
+            stream.read_exact(&mut name).await?;
+            String::from_utf8(name)
+                .map_err(|_| SshTunnelError("Invalid SOCKS destination host".to_string()))?
+        }
+        t => {
+            // Address type not supported
+            stream.write_all(&socks5_reply(0x08)).await?;
+            return Err(SshTunnelError(format!("Unsupported SOCKS address type {t}")));
+        }
+    };
+    let port = stream.read_u16().await?;
+
+    Ok((host, port))
+}
+
+/// Reply to a SOCKS5 request, with an unspecified bound address
+fn socks5_reply(code: u8) -> [u8; 10] {
+    [0x05, code, 0x00, 0x01, 0, 0, 0, 0, 0, 0]
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Whether requests to the URL go through the tunnel. Destination entries are hostnames (with
+/// `*.` or `.` matching subdomains) or IP addresses, optionally with a port, and an empty
+/// destination tunnels everything.
+pub fn tunnels_url(tunnel: &SshTunnel, url: &str) -> bool {
+    if tunnel.disabled {
+        return false;
+    }
+
+    let url = if url.contains("://") { url.to_string() } else { format!("http://{url}") };
+    let Ok(url) = Url::parse(&url) else {
+        return false;
+    };
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let host = host.trim_matches(['[', ']']).to_lowercase();
+    let port = url.port_or_known_default();
+
+    let destination = tunnel.destination.to_lowercase();
+    let mut entries = destination.split(',').map(str::trim).filter(|e| !e.is_empty()).peekable();
+    if entries.peek().is_none() {
+        return true;
+    }
+
+    entries.any(|entry| {
+        let (domain, entry_port) = split_port(entry);
+        if entry_port.is_some() && entry_port != port {
+            return false;
+        }
+
+        match domain.strip_prefix("*.").or_else(|| domain.strip_prefix('.')) {
+            Some(parent) => host == parent || host.ends_with(&format!(".{parent}")),
+            None => host == domain,
+        }
+    })
+}
+
+/// Split an optional port off a destination entry, like `host:443` or `[::1]:443`
+fn split_port(entry: &str) -> (&str, Option<u16>) {
+    if let Some(rest) = entry.strip_prefix('[') {
+        return match rest.split_once(']') {
+            Some((ip, after)) => (ip, after.strip_prefix(':').and_then(|p| p.parse().ok())),
+            None => (rest, None),
+        };
+    }
+    match entry.split_once(':') {
+        // More than one colon is a bare IPv6 address
+        Some((domain, port)) if !port.contains(':') => (domain, port.parse().ok()),
+        _ => (entry, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_destination_entries() {
+        let tunnel = SshTunnel {
+            destination: "api.internal, *.svc.test, 10.0.0.5:8443, [::1]:9000".to_string(),
+            ..Default::default()
+        };
+        assert!(tunnels_url(&tunnel, "https://api.internal/v1"));
+        assert!(tunnels_url(&tunnel, "https://API.internal"));
+        assert!(tunnels_url(&tunnel, "http://users.svc.test"));
+        assert!(tunnels_url(&tunnel, "https://10.0.0.5:8443"));
+        assert!(tunnels_url(&tunnel, "http://[::1]:9000"));
+        assert!(tunnels_url(&tunnel, "grpc.svc.test:50051"));
+
+        assert!(!tunnels_url(&tunnel, "https://10.0.0.5"));
+        assert!(!tunnels_url(&tunnel, "http://[::1]:9001"));
+        assert!(!tunnels_url(&tunnel, "https://example.com"));
+        assert!(tunnels_url(&SshTunnel::default(), "https://example.com"));
+
+        let disabled = SshTunnel { disabled: true, ..Default::default() };
+        assert!(!tunnels_url(&disabled, "https://example.com"));
+    }
+
+    fn password_auth(user: &str, password: &str) -> Vec<u8> {
+        let mut auth = vec![0x01, user.len() as u8];
+        auth.extend_from_slice(user.as_bytes());
+        auth.push(password.len() as u8);
+        auth.extend_from_slice(password.as_bytes());
+        auth
+    }
+
+    #[tokio::test]
+    async fn accepts_socks5_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = SshTunnelProxy {
+            address: listener.local_addr().unwrap(),
+            user: random_credential(),
+            password: random_credential(),
+        };
+        let (addr, user, password) = (proxy.address, proxy.user.clone(), proxy.password.clone());
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(&[0x05, 0x01, 0x02]).await.unwrap();
+            let mut reply = [0u8; 2];
+            stream.read_exact(&mut reply).await.unwrap();
+            assert_eq!(reply, [0x05, 0x02]);
+            stream.write_all(&password_auth(&user, &password)).await.unwrap();
+            stream.read_exact(&mut reply).await.unwrap();
+            assert_eq!(reply, [0x01, 0x00]);
+
+            let mut request = vec![0x05, 0x01, 0x00, 0x03, 12];
+            request.extend_from_slice(b"api.internal");
+            request.extend_from_slice(&8443u16.to_be_bytes());
+            stream.write_all(&request).await.unwrap();
+        });
+
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let destination = socks5_accept(&mut stream, &proxy).await.unwrap();
+        client.await.unwrap();
+        assert_eq!(destination, ("api.internal".to_string(), 8443));
+    }
+
+    #[tokio::test]
+    async fn rejects_socks5_without_credentials() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = SshTunnelProxy {
+            address: listener.local_addr().unwrap(),
+            user: random_credential(),
+            password: random_credential(),
+        };
+        let (addr, user) = (proxy.address, proxy.user.clone());
+        let client = tokio::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(&[0x05, 0x01, 0x00]).await.unwrap();
+            let mut reply = [0u8; 2];
+            stream.read_exact(&mut reply).await.unwrap();
+            assert_eq!(reply, [0x05, 0xff]);
+
+            let mut stream = TcpStream::connect(addr).await.unwrap();
+            stream.write_all(&[0x05, 0x01, 0x02]).await.unwrap();
+            stream.read_exact(&mut reply).await.unwrap();
+            stream.write_all(&password_auth(&user, "guess")).await.unwrap();
+            stream.read_exact(&mut reply).await.unwrap();
+            assert_eq!(reply, [0x01, 0x01]);
+        });
+
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            assert!(socks5_accept(&mut stream, &proxy).await.is_err());
+        }
+        client.await.unwrap();
+    }
+}
@@ -1,7 +1,8 @@
 use crate::cookies::CookieStore;
 use crate::error::Result;
-use crate::sender::{HttpResponse, HttpResponseEvent, HttpSender, RedirectBehavior};
-use crate::types::{SendableBody, SendableHttpRequest};
+use crate::ntlm;
+use crate::sender::{HttpResponse, HttpResponseEvent, HttpSender, RedirectBehavior, RedirectHop};
+use crate::types::{SendableBody, SendableHttpRequest, SendableHttpRequestOptions};
 use log::debug;
 use tokio::sync::mpsc;
 use tokio::sync::watch::Receiver;
@@ -90,7 +91,8 @@ impl<S: HttpSender> HttpTransaction<S> {
         mut cancelled_rx: Receiver<bool>,
         event_tx: mpsc::Sender<HttpResponseEvent>,
     ) -> Result<HttpResponse> {
-        let mut redirect_count = 0;
+        let max_redirects = request.options.max_redirects.unwrap_or(self.max_redirects);
+        let mut redirects: Vec<RedirectHop> = Vec::new();
         let mut current_url = request.url;
         let mut current_method = request.method;
         let mut current_headers = request.headers;
@@ -108,7 +110,7 @@ impl<S: HttpSender> HttpTransaction<S> {
             }
 
             // Inject cookies into headers if we have a cookie store
-            let headers_with_cookies = if self.send_cookies {
+            let mut headers_with_cookies = if self.send_cookies {
                 let mut headers = current_headers.clone();
                 if let (Some(cookie_store), Ok(url)) =
                     (&self.cookie_store, Url::parse(&current_url))
@@ -130,6 +132,22 @@ impl<S: HttpSender> HttpTransaction<S> {
                 current_headers.clone()
             };
 
+            // NTLM authenticates the connection, so the handshake runs again for every hop
+            if request.options.ntlm.is_some() {
+                let authorization = self
+                    .ntlm_authorization(
+                        &current_url,
+                        &current_method,
+                        &headers_with_cookies,
+                        &request.options,
+                        &mut cancelled_rx,
+                        &event_tx,
+                    )
+                    .await?;
+                headers_with_cookies.retain(|h| !h.0.eq_ignore_ascii_case("authorization"));
+                headers_with_cookies.push(("Authorization".to_string(), authorization));
+            }
+
             // Build request for this iteration
             let preserved_body = match &current_body {
                 Some(SendableBody::Bytes(b)) => Some(SendableBody::Bytes(b.clone())),
@@ -145,7 +163,7 @@ impl<S: HttpSender> HttpTransaction<S> {
             };
 
             // Execute with cancellation support
-            let response = tokio::select! {
+            let mut response = tokio::select! {
                 result = self.sender.send(req, event_tx.clone()) => result?,
                 _ = cancelled_rx.changed() => {
                     return Err(crate::error::Error::RequestCanceledError);
@@ -171,23 +189,20 @@ impl<S: HttpSender> HttpTransaction<S> {
                 }
             }
 
-            if !Self::is_redirect(response.status) {
-                // Not a redirect - return the response for caller to consume body
-                return Ok(response);
-            }
-
-            if !request.options.follow_redirects {
-                // Redirects disabled - return the redirect response as-is
+            if !Self::is_redirect(response.status) || !request.options.follow_redirects {
+                // Not a redirect, or redirects are disabled - return the response for caller to
+                // consume body
+                response.redirects = redirects;
                 return Ok(response);
             }
 
             // Check if we've exceeded max redirects
-            if redirect_count >= self.max_redirects {
+            if redirects.len() >= max_redirects {
                 // Drain the response before returning error
                 let _ = response.drain().await;
                 return Err(crate::error::Error::RequestError(format!(
                     "Maximum redirect limit ({}) exceeded",
-                    self.max_redirects
+                    max_redirects
                 )));
             }
 
@@ -214,6 +229,7 @@ impl<S: HttpSender> HttpTransaction<S> {
 
             // Update the request URL
             let previous_url = current_url.clone();
+            let previous_method = current_method.clone();
             current_url = if location.starts_with("http://") || location.starts_with("https://") {
                 // Absolute URL
                 location
@@ -240,14 +256,22 @@ impl<S: HttpSender> HttpTransaction<S> {
                 RedirectBehavior::Preserve
             };
 
-            let mut dropped_headers =
-                Self::remove_sensitive_headers(&mut current_headers, &previous_url, &current_url);
+            let mut dropped_headers = Self::remove_sensitive_headers(
+                &mut current_headers,
+                &previous_url,
+                &current_url,
+                request.options.redirect_keep_authorization,
+            );
+
+            // The body is also dropped from 307/308 redirects when resending it is turned off
+            let drop_body = matches!(behavior, RedirectBehavior::DropBody)
+                || (request_had_body && request.options.redirect_drop_body);
 
             // Handle method changes for certain redirect codes
-            if matches!(behavior, RedirectBehavior::DropBody) {
-                if current_method != "GET" {
-                    current_method = "GET".to_string();
-                }
+            if matches!(behavior, RedirectBehavior::DropBody) && current_method != "GET" {
+                current_method = "GET".to_string();
+            }
+            if drop_body {
                 // Remove content-related headers
                 current_headers.retain(|h| {
                     let name_lower = h.0.to_lowercase();
@@ -260,9 +284,9 @@ impl<S: HttpSender> HttpTransaction<S> {
                 });
             }
 
-            // Restore body for Preserve redirects (307/308), drop for others.
+            // Restore the body unless it's being dropped.
             // Stream bodies can't be replayed (same limitation as reqwest).
-            current_body = if matches!(behavior, RedirectBehavior::Preserve) {
+            current_body = if !drop_body {
                 if request_had_body && preserved_body.is_none() {
                     // Stream body was consumed and can't be replayed (same as reqwest)
                     return Err(crate::error::Error::RequestError(
@@ -286,18 +310,92 @@ impl<S: HttpSender> HttpTransaction<S> {
                 dropped_headers,
             });
 
-            redirect_count += 1;
+            redirects.push(RedirectHop {
+                url: previous_url,
+                method: previous_method,
+                status,
+                location: current_url.clone(),
+            });
         }
     }
 
+    /// Send the negotiate leg of an NTLM handshake and answer the server's challenge, returning
+    /// the `Authorization` header for the real request. The negotiate request has no body, and
+    /// the sender must reuse its connection for the real request.
+    async fn ntlm_authorization(
+        &self,
+        url: &str,
+        method: &str,
+        headers: &[(String, String)],
+        options: &SendableHttpRequestOptions,
+        cancelled_rx: &mut Receiver<bool>,
+        event_tx: &mpsc::Sender<HttpResponseEvent>,
+    ) -> Result<String> {
+        let Some(credentials) = &options.ntlm else {
+            return Err(crate::error::Error::NtlmError("Missing NTLM credentials".to_string()));
+        };
+        let mut negotiate_headers: Vec<(String, String)> = headers
+            .iter()
+            .filter(|h| {
+                let name = h.0.to_lowercase();
+                name != "authorization"
+                    && !name.starts_with("content-")
+                    && name != "transfer-encoding"
+            })
+            .cloned()
+            .collect();
+        negotiate_headers.push(("Authorization".to_string(), ntlm::negotiate_header()));
+
+        let _ = event_tx.try_send(HttpResponseEvent::Info("Negotiating NTLM".to_string()));
+        let negotiate = SendableHttpRequest {
+            url: url.to_string(),
+            method: method.to_string(),
+            headers: negotiate_headers,
+            body: None,
+            options: SendableHttpRequestOptions {
+                timeout: options.timeout,
+                connect_timeout: options.connect_timeout,
+                read_timeout: options.read_timeout,
+                follow_redirects: false,
+                max_redirects: None,
+                redirect_drop_body: false,
+                redirect_keep_authorization: false,
+                skip_decompression: options.skip_decompression,
+                http_version: options.http_version,
+                unix_socket: options.unix_socket.clone(),
+                ntlm: None,
+            },
+        };
+        let response = tokio::select! {
+            result = self.sender.send(negotiate, event_tx.clone()) => result?,
+            _ = cancelled_rx.changed() => {
+                return Err(crate::error::Error::RequestCanceledError);
+            }
+        };
+
+        let challenge = ntlm::find_challenge(&response.headers);
+        // The body must be read for the connection to be reused for the next leg
+        response.drain().await?;
+        let (scheme, challenge) = challenge.ok_or_else(|| {
+            crate::error::Error::NtlmError(
+                "Unable to find NTLM challenge in WWW-Authenticate response headers".to_string(),
+            )
+        })?;
+
+        let _ = event_tx.try_send(HttpResponseEvent::Info("Answering NTLM challenge".to_string()));
+        ntlm::authenticate_header(&scheme, &challenge, credentials)
+    }
+
     /// Remove sensitive headers when redirecting to a different host.
     /// This matches reqwest's `remove_sensitive_headers()` behavior and prevents
     /// credentials from being forwarded to third-party servers (e.g., an
     /// Authorization header sent from an API redirect to an S3 bucket).
+    /// `keep_authorization` leaves the Authorization header in place for servers that expect it.
     fn remove_sensitive_headers(
         headers: &mut Vec<(String, String)>,
         previous_url: &str,
         next_url: &str,
+        keep_authorization: bool,
     ) -> Vec<String> {
         let mut dropped_headers = Vec::new();
         let previous_host = Url::parse(previous_url).ok().and_then(|u| {
@@ -309,7 +407,7 @@ impl<S: HttpSender> HttpTransaction<S> {
         if previous_host != next_host {
             headers.retain(|h| {
                 let name_lower = h.0.to_lowercase();
-                let should_drop = name_lower == "authorization"
+                let should_drop = (name_lower == "authorization" && !keep_authorization)
                     || name_lower == "cookie"
                     || name_lower == "cookie2"
                     || name_lower == "proxy-authorization"
@@ -385,6 +483,7 @@ mod tests {
         url: String,
         method: String,
         headers: Vec<(String, String)>,
+        has_body: bool,
     }
 
     /// Mock sender for testing
@@ -421,6 +520,7 @@ mod tests {
                 url: request.url.clone(),
                 method: request.method.clone(),
                 headers: request.headers.clone(),
+                has_body: request.body.is_some(),
             });
 
             let mut responses = self.responses.lock().await;
@@ -1086,4 +1186,198 @@ mod tests {
             "Redirected request to same host should preserve Authorization header"
         );
     }
+
+    #[tokio::test]
+    async fn test_ntlm_handshake_before_request() {
+        let responses = vec![
+            MockResponse {
+                status: 401,
+                headers: vec![(
+                    "WWW-Authenticate".to_string(),
+                    "NTLM TlRMTVNTUAACAAAAAAAAAAAAAAABgggAASNFZ4mrze8=".to_string(),
+                )],
+                body: vec![],
+            },
+            MockResponse { status: 200, headers: Vec::new(), body: b"OK".to_vec() },
+        ];
+
+        let sender = MockSender::new(responses);
+        let captured = sender.captured_requests.clone();
+        let transaction = HttpTransaction::new(sender);
+
+        let request = SendableHttpRequest {
+            url: "https://intranet.example.com/api".to_string(),
+            method: "POST".to_string(),
+            headers: vec![
+                ("Authorization".to_string(), "Bearer stale".to_string()),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ],
+            body: Some(SendableBody::Bytes(bytes::Bytes::from_static(b"{}"))),
+            options: crate::types::SendableHttpRequestOptions {
+                ntlm: Some(crate::ntlm::NtlmCredentials {
+                    username: "CORP\\alice".to_string(),
+                    password: "secret".to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+        };
+
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let (event_tx, _event_rx) = mpsc::channel(100);
+        let result = transaction.execute_with_cancellation(request, rx, event_tx).await.unwrap();
+        assert_eq!(result.status, 200);
+
+        let requests = captured.lock().await;
+        assert_eq!(requests.len(), 2);
+        let authorization = |i: usize| -> Vec<String> {
+            requests[i]
+                .headers
+                .iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case("authorization"))
+                .map(|(_, v)| v.clone())
+                .collect()
+        };
+
+        // The negotiate leg is sent without the body or its headers
+        assert_eq!(authorization(0).len(), 1);
+        assert!(authorization(0)[0].starts_with("NTLM TlRMTVNTUAAB"));
+        assert!(!requests[0].headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-type")));
+
+        // The real request answers the challenge
+        assert_eq!(authorization(1).len(), 1);
+        assert!(authorization(1)[0].starts_with("NTLM TlRMTVNTUAAD"));
+        assert!(requests[1].headers.iter().any(|(k, _)| k.eq_ignore_ascii_case("content-type")));
+    }
+
+    #[tokio::test]
+    async fn test_ntlm_handshake_without_challenge() {
+        let responses = vec![MockResponse {
+            status: 401,
+            headers: vec![("WWW-Authenticate".to_string(), "Basic realm=\"intranet\"".to_string())],
+            body: vec![],
+        }];
+
+        let transaction = HttpTransaction::new(MockSender::new(responses));
+        let request = SendableHttpRequest {
+            url: "https://intranet.example.com/api".to_string(),
+            method: "GET".to_string(),
+            options: crate::types::SendableHttpRequestOptions {
+                ntlm: Some(Default::default()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let (event_tx, _event_rx) = mpsc::channel(100);
+        let err = transaction.execute_with_cancellation(request, rx, event_tx).await.unwrap_err();
+        assert!(matches!(err, crate::error::Error::NtlmError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_redirect_chain_and_per_request_limit() {
+        let redirect = |location: &str| MockResponse {
+            status: 301,
+            headers: vec![("Location".to_string(), location.to_string())],
+            body: vec![],
+        };
+        let responses = vec![
+            redirect("/b"),
+            redirect("https://other.example.com/c"),
+            MockResponse { status: 200, headers: Vec::new(), body: b"OK".to_vec() },
+        ];
+        let transaction = HttpTransaction::new(MockSender::new(responses));
+        let request = SendableHttpRequest {
+            url: "https://example.com/a".to_string(),
+            method: "GET".to_string(),
+            options: crate::types::SendableHttpRequestOptions {
+                follow_redirects: true,
+                max_redirects: Some(2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let (event_tx, _event_rx) = mpsc::channel(100);
+        let result = transaction.execute_with_cancellation(request, rx, event_tx).await.unwrap();
+        assert_eq!(
+            result.redirects,
+            vec![
+                RedirectHop {
+                    url: "https://example.com/a".to_string(),
+                    method: "GET".to_string(),
+                    status: 301,
+                    location: "https://example.com/b".to_string(),
+                },
+                RedirectHop {
+                    url: "https://example.com/b".to_string(),
+                    method: "GET".to_string(),
+                    status: 301,
+                    location: "https://other.example.com/c".to_string(),
+                },
+            ]
+        );
+
+        let responses = vec![redirect("/b"), redirect("/c")];
+        let transaction = HttpTransaction::new(MockSender::new(responses));
+        let request = SendableHttpRequest {
+            url: "https://example.com/a".to_string(),
+            method: "GET".to_string(),
+            options: crate::types::SendableHttpRequestOptions {
+                follow_redirects: true,
+                max_redirects: Some(1),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let (event_tx, _event_rx) = mpsc::channel(100);
+        let err = transaction.execute_with_cancellation(request, rx, event_tx).await.unwrap_err();
+        assert!(err.to_string().contains("Maximum redirect limit (1)"));
+    }
+
+    #[tokio::test]
+    async fn test_redirect_can_keep_auth_and_drop_body() {
+        let responses = vec![
+            MockResponse {
+                status: 307,
+                headers: vec![(
+                    "Location".to_string(),
+                    "https://upload.example.net/files".to_string(),
+                )],
+                body: vec![],
+            },
+            MockResponse { status: 200, headers: Vec::new(), body: vec![] },
+        ];
+        let sender = MockSender::new(responses);
+        let captured = sender.captured_requests.clone();
+        let transaction = HttpTransaction::new(sender);
+        let request = SendableHttpRequest {
+            url: "https://api.example.com/files".to_string(),
+            method: "PUT".to_string(),
+            headers: vec![
+                ("Authorization".to_string(), "Bearer token".to_string()),
+                ("Content-Type".to_string(), "text/plain".to_string()),
+            ],
+            body: Some(SendableBody::Bytes(bytes::Bytes::from_static(b"hello"))),
+            options: crate::types::SendableHttpRequestOptions {
+                follow_redirects: true,
+                redirect_drop_body: true,
+                redirect_keep_authorization: true,
+                ..Default::default()
+            },
+        };
+
+        let (_tx, rx) = tokio::sync::watch::channel(false);
+        let (event_tx, _event_rx) = mpsc::channel(100);
+        transaction.execute_with_cancellation(request, rx, event_tx).await.unwrap();
+
+        let requests = captured.lock().await;
+        assert_eq!(requests[1].method, "PUT");
+        assert!(!requests[1].has_body);
+        assert!(requests[1].headers.iter().any(|(k, _)| k == "Authorization"));
+        assert!(!requests[1].headers.iter().any(|(k, _)| k == "Content-Type"));
+    }
 }
@@ -1,16 +1,20 @@
 use crate::chained_reader::{ChainedReader, ReaderType};
+use crate::compress::{compress, streaming_encoder};
+use crate::decompress::ContentEncoding;
 use crate::error::Error::RequestError;
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::ntlm::NtlmCredentials;
 use crate::path_placeholders::apply_path_placeholders;
 use crate::proto::ensure_proto;
 use bytes::Bytes;
 use log::warn;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::pin::Pin;
 use std::time::Duration;
 use tokio::io::AsyncRead;
 use yaak_common::serde::{get_bool, get_bool_map, get_str, get_str_map};
-use yaak_models::models::HttpRequest;
+use yaak_models::models::{HttpRequest, HttpVersion};
 use yaak_templates::strip_json_comments::{maybe_strip_json_comments, strip_json_comments};
 
 pub(crate) const MULTIPART_BOUNDARY: &str = "------YaakFormBoundary";
@@ -57,7 +61,41 @@ pub struct SendableHttpRequest {
 #[derive(Default, Clone)]
 pub struct SendableHttpRequestOptions {
     pub timeout: Option<Duration>,
+    /// Connect timeout the client was built with, used to report which timeout elapsed
+    pub connect_timeout: Option<Duration>,
+    /// Read timeout the client was built with, used to report which timeout elapsed
+    pub read_timeout: Option<Duration>,
     pub follow_redirects: bool,
+    /// Maximum number of redirects to follow, overriding the transaction's limit when set
+    pub max_redirects: Option<usize>,
+    /// Drop the body instead of sending it again when a 307 or 308 redirect keeps the method
+    pub redirect_drop_body: bool,
+    /// Keep the Authorization header when a redirect leads to a different origin
+    pub redirect_keep_authorization: bool,
+    /// Keep compressed response bodies exactly as the server sent them instead of decoding them
+    pub skip_decompression: bool,
+    /// HTTP version to send with. The client must be built for the same version.
+    pub http_version: HttpVersion,
+    /// Unix domain socket to connect to instead of the URL's host. Like the HTTP version, this
+    /// is configured on the client, so the client must be built for the same socket.
+    pub unix_socket: Option<PathBuf>,
+    /// Authenticate with an NTLM handshake before sending. The handshake authenticates the
+    /// connection, so the sender must keep it open between legs.
+    pub ntlm: Option<NtlmCredentials>,
+}
+
+impl SendableHttpRequestOptions {
+    /// Build the error for a timeout that elapsed after `elapsed`. The total timeout wins once
+    /// it has passed, since the other timeouts can only fire before it.
+    pub fn timeout_error(&self, elapsed: Duration, connecting: bool) -> Error {
+        let set = |d: Option<Duration>| d.filter(|d| !d.is_zero());
+        match (set(self.timeout), set(self.connect_timeout), set(self.read_timeout)) {
+            (Some(total), _, _) if elapsed >= total => Error::RequestTimeout(total),
+            (_, Some(connect), _) if connecting => Error::ConnectTimeout(connect),
+            (_, _, Some(read)) if !connecting => Error::ReadTimeout(read),
+            (total, _, _) => Error::RequestTimeout(total.unwrap_or_default()),
+        }
+    }
 }
 
 impl SendableHttpRequest {
@@ -77,6 +115,21 @@ impl SendableHttpRequest {
         })
     }
 
+    /// Build the URL, method, and headers of a request without its body, which avoids reading
+    /// body files when only the request line and headers are needed.
+    pub fn from_http_request_without_body(
+        r: &HttpRequest,
+        options: SendableHttpRequestOptions,
+    ) -> Self {
+        Self {
+            url: build_url(r),
+            method: r.method.to_uppercase(),
+            headers: build_headers(r),
+            body: None,
+            options,
+        }
+    }
+
     pub fn insert_header(&mut self, header: (String, String)) {
         if let Some(existing) =
             self.headers.iter_mut().find(|h| h.0.to_lowercase() == header.0.to_lowercase())
@@ -169,7 +222,7 @@ fn strip_query_params(url: &str, names: &[&str]) -> String {
     }
 }
 
-fn build_url(r: &HttpRequest) -> String {
+pub(crate) fn build_url(r: &HttpRequest) -> String {
     let (url_string, params) = apply_path_placeholders(&ensure_proto(&r.url), &r.url_parameters);
     let mut url = append_query_params(
         &url_string,
@@ -191,12 +244,16 @@ fn build_url(r: &HttpRequest) -> String {
 fn append_graphql_query_params(url: &str, body: &BTreeMap<String, serde_json::Value>) -> String {
     let query = get_str_map(body, "query").to_string();
     let variables = strip_json_comments(&get_str_map(body, "variables"));
+    let operation_name = get_str_map(body, "operationName").trim();
     let mut params = vec![("query".to_string(), query)];
+    if !operation_name.is_empty() {
+        params.push(("operationName".to_string(), operation_name.to_string()));
+    }
     if !variables.trim().is_empty() {
         params.push(("variables".to_string(), variables));
     }
-    // Strip existing query/variables params to avoid duplicates
-    let url = strip_query_params(url, &["query", "variables"]);
+    // Strip existing GraphQL params to avoid duplicates
+    let url = strip_query_params(url, &["query", "operationName", "variables"]);
     append_query_params(&url, params)
 }
 
@@ -224,8 +281,11 @@ async fn build_body(
         Some(t) => t,
     };
 
+    let compression = ContentEncoding::from_header(Some(get_str_map(body, "compression")));
     let (body, content_type) = match body_type.as_str() {
-        "binary" => (build_binary_body(&body).await?, None),
+        "binary" => {
+            (build_binary_body(body).await?, sniff_binary_content_type(body, &headers).await)
+        }
         "graphql" => (build_graphql_body(&method, &body), None),
         "application/x-www-form-urlencoded" => (build_form_body(&body), None),
         "multipart/form-data" => build_multipart_body(&body, &headers).await?,
@@ -239,13 +299,17 @@ async fn build_body(
     // Add or update the Content-Type header
     let mut headers = headers;
     if let Some(ct) = content_type {
-        if let Some(existing) = headers.iter_mut().find(|h| h.0.to_lowercase() == "content-type") {
-            existing.1 = ct;
-        } else {
-            headers.push(("Content-Type".to_string(), ct));
-        }
+        set_header(&mut headers, "Content-Type", &ct);
     }
 
+    let body = match body {
+        Some(body) if compression != ContentEncoding::Identity => {
+            set_header(&mut headers, "Content-Encoding", compression.as_header());
+            Some(compress_body(body, compression)?)
+        }
+        body => body,
+    };
+
     // NOTE: Content-Length is NOT set as an explicit header here. Instead, the
     // body's content length is carried via SendableBody::Stream { content_length }
     // and used by the sender to set the body size hint. This lets hyper handle
@@ -255,6 +319,31 @@ async fn build_body(
     Ok((body.map(|b| b.into()), headers))
 }
 
+fn set_header(headers: &mut Vec<(String, String)>, name: &str, value: &str) {
+    if let Some(existing) = headers.iter_mut().find(|h| h.0.eq_ignore_ascii_case(name)) {
+        existing.1 = value.to_string();
+    } else {
+        headers.push((name.to_string(), value.to_string()));
+    }
+}
+
+/// Compress a body for sending with a Content-Encoding. In-memory bodies keep an exact length,
+/// while streams are compressed as they're read and sent without one.
+fn compress_body(
+    body: SendableBodyWithMeta,
+    encoding: ContentEncoding,
+) -> Result<SendableBodyWithMeta> {
+    Ok(match body {
+        SendableBodyWithMeta::Bytes(bytes) => {
+            SendableBodyWithMeta::Bytes(Bytes::from(compress(&bytes, encoding)?))
+        }
+        SendableBodyWithMeta::Stream { data, .. } => SendableBodyWithMeta::Stream {
+            data: streaming_encoder(data, encoding),
+            content_length: None,
+        },
+    })
+}
+
 fn build_form_body(body: &BTreeMap<String, serde_json::Value>) -> Option<SendableBodyWithMeta> {
     let form_params = match body.get("form").map(|f| f.as_array()) {
         Some(Some(f)) => f,
@@ -304,6 +393,54 @@ async fn build_binary_body(
     }))
 }
 
+/// Guess the Content-Type of a binary body's file when sniffing is turned on and the request
+/// doesn't already set one, looking at the leading bytes first and then the file extension
+async fn sniff_binary_content_type(
+    body: &BTreeMap<String, serde_json::Value>,
+    headers: &[(String, String)],
+) -> Option<String> {
+    if !get_bool_map(body, "sniffContentType", false)
+        || headers.iter().any(|h| h.0.eq_ignore_ascii_case("content-type"))
+    {
+        return None;
+    }
+    let file_path = get_str_map(body, "filePath");
+    if file_path.is_empty() {
+        return None;
+    }
+
+    let mut head = [0u8; 16];
+    let head_len = match tokio::fs::File::open(file_path).await {
+        Ok(mut file) => {
+            use tokio::io::AsyncReadExt;
+            file.read(&mut head).await.unwrap_or(0)
+        }
+        Err(_) => 0,
+    };
+
+    let content_type = content_type_from_magic_bytes(&head[..head_len])
+        .map(|ct| ct.to_string())
+        .unwrap_or_else(|| mime_guess::from_path(file_path).first_or_octet_stream().to_string());
+    Some(content_type)
+}
+
+fn content_type_from_magic_bytes(head: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"\x00asm", "application/wasm"),
+    ];
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+    SIGNATURES.iter().find(|(magic, _)| head.starts_with(magic)).map(|(_, ct)| *ct)
+}
+
 fn build_text_body(
     body: &BTreeMap<String, serde_json::Value>,
     body_type: &str,
@@ -327,25 +464,32 @@ fn build_graphql_body(
     method: &str,
     body: &BTreeMap<String, serde_json::Value>,
 ) -> Option<SendableBodyWithMeta> {
-    let query = get_str_map(body, "query");
-    let variables = strip_json_comments(&get_str_map(body, "variables"));
-
     if method.to_lowercase() == "get" {
         // GraphQL GET requests use query parameters, not a body
         return None;
     }
 
-    let body = if variables.trim().is_empty() {
-        format!(r#"{{"query":{}}}"#, serde_json::to_string(&query).unwrap_or_default())
-    } else {
-        format!(
-            r#"{{"query":{},"variables":{}}}"#,
-            serde_json::to_string(&query).unwrap_or_default(),
-            variables
-        )
-    };
+    Some(SendableBodyWithMeta::Bytes(Bytes::from(graphql_body_text(body))))
+}
 
-    Some(SendableBodyWithMeta::Bytes(Bytes::from(body)))
+/// Serialize a GraphQL body into the JSON document sent for non-GET requests
+pub(crate) fn graphql_body_text(body: &BTreeMap<String, serde_json::Value>) -> String {
+    let query = get_str_map(body, "query");
+    let variables = strip_json_comments(&get_str_map(body, "variables"));
+    let operation_name = get_str_map(body, "operationName").trim();
+
+    let mut text = format!(r#"{{"query":{}"#, serde_json::to_string(&query).unwrap_or_default());
+    if !operation_name.is_empty() {
+        text.push_str(&format!(
+            r#","operationName":{}"#,
+            serde_json::to_string(operation_name).unwrap_or_default()
+        ));
+    }
+    if !variables.trim().is_empty() {
+        text.push_str(&format!(r#","variables":{}"#, variables));
+    }
+    text.push('}');
+    text
 }
 
 async fn build_multipart_body(
@@ -904,6 +1048,23 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_graphql_body_with_operation_name() {
+        let mut body = BTreeMap::new();
+        body.insert("query".to_string(), json!("query A { a } query B { b }"));
+        body.insert("operationName".to_string(), json!(" B "));
+        body.insert("variables".to_string(), json!(r#"{"id": 1}"#));
+
+        let result = build_graphql_body("POST", &body);
+        match result {
+            Some(SendableBodyWithMeta::Bytes(bytes)) => {
+                let expected = r#"{"query":"query A { a } query B { b }","operationName":"B","variables":{"id": 1}}"#;
+                assert_eq!(bytes, Bytes::from(expected));
+            }
+            _ => panic!("Expected Some(SendableBody::Bytes)"),
+        }
+    }
+
     #[tokio::test]
     async fn test_graphql_body_get_method() {
         let mut body = BTreeMap::new();
@@ -989,6 +1150,59 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_multipart_body_mixed_text_and_file_parts() -> Result<()> {
+        let mut body = BTreeMap::new();
+        body.insert(
+            "form".to_string(),
+            json!([
+                { "enabled": true, "name": "before", "value": "a" },
+                { "enabled": true, "name": "upload", "file": "./tests/test.txt" },
+                { "enabled": true, "name": "after", "value": "{}", "contentType": "application/json" },
+            ]),
+        );
+
+        let (result, _) = build_multipart_body(&body, &vec![]).await?;
+        match result {
+            Some(SendableBodyWithMeta::Stream { data: mut stream, content_length }) => {
+                let mut buf = Vec::new();
+                use tokio::io::AsyncReadExt;
+                stream.read_to_end(&mut buf).await.expect("Failed to read stream");
+                let body_str = String::from_utf8_lossy(&buf);
+                assert_eq!(
+                    body_str,
+                    "--------YaakFormBoundary\r\nContent-Disposition: form-data; name=\"before\"\r\n\r\na\r\n--------YaakFormBoundary\r\nContent-Disposition: form-data; name=\"upload\"; filename=\"test.txt\"\r\nContent-Type: text/plain\r\n\r\nThis is a test file!\n\r\n--------YaakFormBoundary\r\nContent-Disposition: form-data; name=\"after\"\r\nContent-Type: application/json\r\n\r\n{}\r\n--------YaakFormBoundary--\r\n"
+                );
+                assert_eq!(content_length, Some(body_str.len()));
+            }
+            _ => panic!("Expected Some(SendableBody::Stream)"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_binary_body_sniffs_content_type() {
+        let mut body = BTreeMap::new();
+        body.insert("filePath".to_string(), json!("./tests/test.txt"));
+        assert_eq!(sniff_binary_content_type(&body, &vec![]).await, None);
+
+        body.insert("sniffContentType".to_string(), json!(true));
+        assert_eq!(sniff_binary_content_type(&body, &vec![]).await, Some("text/plain".to_string()));
+
+        // A Content-Type set on the request always wins
+        let headers = vec![("content-type".to_string(), "application/x-custom".to_string())];
+        assert_eq!(sniff_binary_content_type(&body, &headers).await, None);
+    }
+
+    #[test]
+    fn test_content_type_from_magic_bytes() {
+        assert_eq!(content_type_from_magic_bytes(b"\x89PNG\r\n\x1a\n...."), Some("image/png"));
+        assert_eq!(content_type_from_magic_bytes(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(content_type_from_magic_bytes(b"RIFF\0\0\0\0WEBPVP8 "), Some("image/webp"));
+        assert_eq!(content_type_from_magic_bytes(b"hello"), None);
+    }
+
     #[tokio::test]
     async fn test_multipart_body_empty() -> Result<()> {
         let body = BTreeMap::new();
@@ -1071,4 +1285,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_build_body_compresses_with_content_encoding() -> Result<()> {
+        let mut body = BTreeMap::new();
+        body.insert("text".to_string(), json!("Hello, World!"));
+        body.insert("compression".to_string(), json!("gzip"));
+
+        let (result, result_headers) =
+            build_body("POST", &Some("text/plain".to_string()), &body, vec![]).await?;
+
+        assert!(result_headers.contains(&("Content-Encoding".to_string(), "gzip".to_string())));
+        match result {
+            Some(SendableBody::Bytes(bytes)) => {
+                let decompressed =
+                    crate::decompress::decompress(bytes.to_vec(), ContentEncoding::Gzip)?;
+                assert_eq!(decompressed.data, b"Hello, World!");
+            }
+            _ => panic!("Expected Some(SendableBody::Bytes)"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_timeout_error_reports_which_timeout_elapsed() {
+        let options = SendableHttpRequestOptions {
+            timeout: Some(Duration::from_secs(10)),
+            connect_timeout: Some(Duration::from_secs(2)),
+            read_timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+
+        let err = options.timeout_error(Duration::from_secs(2), true);
+        assert!(matches!(err, Error::ConnectTimeout(d) if d == Duration::from_secs(2)));
+        let err = options.timeout_error(Duration::from_secs(6), false);
+        assert!(matches!(err, Error::ReadTimeout(d) if d == Duration::from_secs(5)));
+        let err = options.timeout_error(Duration::from_secs(10), false);
+        assert!(matches!(err, Error::RequestTimeout(d) if d == Duration::from_secs(10)));
+        assert!(err.is_timeout());
+    }
 }
@@ -1,4 +1,140 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
-import type { Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workspace } from "./gen_models";
+import type { ModelChangeEvent } from "./ModelChangeEvent";
+import type { AnyModel, CookieJar, Environment, Folder, GrpcRequest, HttpRequest, UpdateSource, WebsocketEventType, WebsocketRequest, Workspace } from "./gen_models";
 
-export type BatchUpsertResult = { workspaces: Array<Workspace>, environments: Array<Environment>, folders: Array<Folder>, httpRequests: Array<HttpRequest>, grpcRequests: Array<GrpcRequest>, websocketRequests: Array<WebsocketRequest>, };
+export type AuditLogEntry = { id: bigint, createdAt: string, workspaceId: string, model: string, modelId: string, 
+/**
+ * Name of the model at the time of the change, so deleted models can still be shown
+ */
+modelName: string, change: ModelChangeEvent, 
+/**
+ * Who or what made the change
+ */
+updateSource: UpdateSource, };
+
+export type BatchUpsertResult = { workspaces: Array<Workspace>, environments: Array<Environment>, folders: Array<Folder>, httpRequests: Array<HttpRequest>, grpcRequests: Array<GrpcRequest>, websocketRequests: Array<WebsocketRequest>, cookieJars: Array<CookieJar>, };
+
+/**
+ * A value that was added, removed, or changed. Missing sides are `null`.
+ */
+export type FieldChange = { name: string, before: any, after: any, };
+
+/**
+ * Pagination and filters for listing a request's response history
+ */
+export type HttpResponseFilter = { limit: number | null, offset: number | null, 
+/**
+ * Minimum status code, inclusive
+ */
+statusMin: number | null, 
+/**
+ * Maximum status code, inclusive
+ */
+statusMax: number | null, 
+/**
+ * Only include responses created at or after this time
+ */
+createdAfter: string | null, 
+/**
+ * Only include responses created before this time
+ */
+createdBefore: string | null, };
+
+/**
+ * A header or URL parameter that was added, removed, toggled, or changed
+ */
+export type KeyValueChange = { name: string, before: KeyValueEntry | null, after: KeyValueEntry | null, };
+
+export type KeyValueEntry = { value: string, enabled: boolean, };
+
+/**
+ * Everything that differs between two HTTP requests. Unchanged fields and entries are left out,
+ * so an empty diff means the requests send the same thing.
+ */
+export type RequestDiff = { 
+/**
+ * Changes to the method, URL, body type, and authentication type
+ */
+fields: Array<FieldChange>, urlParameters: Array<KeyValueChange>, headers: Array<KeyValueChange>, 
+/**
+ * Changes to body keys, like `text` or `form`
+ */
+body: Array<FieldChange>, 
+/**
+ * Changes to authentication config keys, like `token` or `username`
+ */
+authentication: Array<FieldChange>, };
+
+/**
+ * One side of a request diff: a request as it is now, or one of its saved revisions
+ */
+export type RequestDiffTarget = { "type": "request", id: string, } | { "type": "revision", id: bigint, };
+
+export type RequestRevision = { id: bigint, createdAt: string, requestId: string, 
+/**
+ * The full request as it was after the change
+ */
+snapshot: AnyModel, };
+
+/**
+ * What differs between two stored responses. JSON bodies are compared value by value, and any
+ * other body is compared as a whole.
+ */
+export type ResponseDiff = { 
+/**
+ * Changes to the status code
+ */
+fields: Array<FieldChange>, 
+/**
+ * Changes to header values, matched by name regardless of case. Repeated headers are joined
+ * with a comma.
+ */
+headers: Array<FieldChange>, 
+/**
+ * Whether both bodies were JSON, in which case body changes are named by JSONPath
+ */
+json: boolean, 
+/**
+ * Changes within the body, like `$.users[0].name`, or a single `$` change holding both bodies
+ * when they aren't JSON
+ */
+body: Array<FieldChange>, };
+
+export type ResponseDiffOptions = { 
+/**
+ * Compare JSON arrays as unordered collections, so reordered items aren't reported
+ */
+ignoreArrayOrder: boolean, 
+/**
+ * Header names to leave out of the diff, like `date` or `x-request-id`
+ */
+ignoredHeaders: Array<string>, };
+
+export type SearchResult = { 
+/**
+ * Model type of the match (eg. `http_request` or `http_response`)
+ */
+model: string, id: string, 
+/**
+ * Request the match belongs to. Same as `id` when the match is a request.
+ */
+requestId: string, 
+/**
+ * Excerpt of the text surrounding the match
+ */
+snippet: string, };
+
+/**
+ * Pagination and filters for listing a connection's message history
+ */
+export type WebsocketEventFilter = { limit: number | null, offset: number | null, 
+/**
+ * Only include messages received from the server (`true`) or sent by the client (`false`)
+ */
+isServer: boolean | null, 
+/**
+ * Only include events of these types, or events of any type when empty
+ */
+messageTypes: Array<WebsocketEventType>, };
+
+export type WorkspaceExportFormat = "json" | "yaml";
@@ -7,6 +7,7 @@ import { newStoreData } from "./util";
 
 export const modelStoreDataAtom = atom(newStoreData());
 
+export const collectionRunsAtom = createOrderedModelAtom("collection_run", "createdAt", "desc");
 export const cookieJarsAtom = createOrderedModelAtom("cookie_jar", "name", "asc");
 export const environmentsAtom = createOrderedModelAtom("environment", "sortPriority", "asc");
 export const foldersAtom = createModelAtom("folder");
@@ -21,8 +22,15 @@ export const httpResponseEventsAtom = createOrderedModelAtom(
   "asc",
 );
 export const keyValuesAtom = createModelAtom("key_value");
+export const monitorsAtom = createOrderedModelAtom("monitor", "createdAt", "asc");
+export const monitorRunsAtom = createOrderedModelAtom("monitor_run", "createdAt", "desc");
+export const mqttConnectionsAtom = createOrderedModelAtom("mqtt_connection", "createdAt", "desc");
+export const mqttEventsAtom = createOrderedModelAtom("mqtt_event", "createdAt", "asc");
+export const mqttRequestsAtom = createModelAtom("mqtt_request");
 export const pluginsAtom = createModelAtom("plugin");
 export const settingsAtom = createSingularModelAtom("settings");
+export const socketRequestsAtom = createModelAtom("socket_request");
+export const socketResponsesAtom = createOrderedModelAtom("socket_response", "createdAt", "desc");
 export const websocketRequestsAtom = createModelAtom("websocket_request");
 export const websocketEventsAtom = createOrderedModelAtom("websocket_event", "createdAt", "asc");
 export const websocketConnectionsAtom = createOrderedModelAtom(
@@ -1,7 +1,27 @@
 import { invoke } from "@tauri-apps/api/core";
 import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
 import { resolvedModelName } from "@yaakapp/yaak-client/lib/resolvedModelName";
-import { AnyModel, ModelPayload } from "../bindings/gen_models";
+import {
+  AnyModel,
+  Cookie,
+  CookieJar,
+  GrpcRequest,
+  HttpResponse,
+  ModelPayload,
+  ProtoFiles,
+  WebsocketEvent,
+} from "../bindings/gen_models";
+import {
+  AuditLogEntry,
+  HttpResponseFilter,
+  RequestDiff,
+  RequestDiffTarget,
+  RequestRevision,
+  ResponseDiff,
+  ResponseDiffOptions,
+  SearchResult,
+  WebsocketEventFilter,
+} from "../bindings/gen_util";
 import { modelStoreDataAtom } from "./atoms";
 import { ExtractModel, JotaiStore, ModelStoreData } from "./types";
 import { newStoreData } from "./util";
@@ -16,7 +36,9 @@ export function initModelStore(store: JotaiStore) {
       if (shouldIgnoreModel(payload)) return;
 
       mustStore().set(modelStoreDataAtom, (prev: ModelStoreData) => {
-        if (payload.change.type === "upsert") {
+        // Trashed models are written as upserts but should disappear from the store
+        const trashed = "deletedAt" in payload.model && payload.model.deletedAt != null;
+        if (payload.change.type === "upsert" && !trashed) {
           return {
             ...prev,
             [payload.model.model]: {
@@ -137,6 +159,164 @@ export async function deleteModel<M extends AnyModel["model"], T extends Extract
   await invoke<string>("models_delete", { model });
 }
 
+export async function trashModel<M extends AnyModel["model"], T extends ExtractModel<AnyModel, M>>(
+  model: T | null,
+) {
+  if (model == null) {
+    throw new Error("Failed to trash null model");
+  }
+  await invoke<string>("models_trash", { model });
+}
+
+export async function restoreModel<M extends AnyModel["model"], T extends ExtractModel<AnyModel, M>>(
+  model: T,
+) {
+  await invoke<string>("models_restore", { model });
+}
+
+export async function listTrash(workspaceId: string): Promise<AnyModel[]> {
+  return invoke<AnyModel[]>("models_list_trash", { workspaceId });
+}
+
+export async function emptyTrash(workspaceId: string) {
+  await invoke("models_empty_trash", { workspaceId });
+}
+
+export async function reorderChildren(
+  workspaceId: string,
+  folderId: string | null,
+  orderedIds: string[],
+): Promise<AnyModel[]> {
+  return invoke<AnyModel[]>("models_reorder_children", { workspaceId, folderId, orderedIds });
+}
+
+export async function filterHttpResponses(
+  requestId: string,
+  filter: Partial<HttpResponseFilter>,
+): Promise<HttpResponse[]> {
+  return invoke<HttpResponse[]>("models_filter_http_responses", { requestId, filter });
+}
+
+export async function filterWebsocketEvents(
+  connectionId: string,
+  filter: Partial<WebsocketEventFilter>,
+): Promise<WebsocketEvent[]> {
+  return invoke<WebsocketEvent[]>("models_filter_websocket_events", { connectionId, filter });
+}
+
+export async function diffRequests(
+  a: RequestDiffTarget,
+  b: RequestDiffTarget,
+): Promise<RequestDiff> {
+  return invoke<RequestDiff>("models_diff_requests", { a, b });
+}
+
+export async function diffResponses(
+  aId: string,
+  bId: string,
+  options: Partial<ResponseDiffOptions> = {},
+): Promise<ResponseDiff> {
+  return invoke<ResponseDiff>("models_diff_responses", { aId, bId, options });
+}
+
+export async function listRequestRevisions(requestId: string): Promise<RequestRevision[]> {
+  return invoke<RequestRevision[]>("models_list_request_revisions", { requestId });
+}
+
+export async function listWorkspaceActivity(workspaceId: string): Promise<AuditLogEntry[]> {
+  return invoke<AuditLogEntry[]>("models_list_workspace_activity", { workspaceId });
+}
+
+export async function restoreRequestRevision(id: number): Promise<string> {
+  return invoke<string>("models_restore_request_revision", { id });
+}
+
+export async function searchWorkspace(workspaceId: string, query: string): Promise<SearchResult[]> {
+  return invoke<SearchResult[]>("models_search_workspace", { workspaceId, query });
+}
+
+export async function listPinnedItems(workspaceId: string): Promise<AnyModel[]> {
+  return invoke<AnyModel[]>("models_list_pinned_items", { workspaceId });
+}
+
+export async function pinRequest(workspaceId: string, requestId: string) {
+  await invoke("models_pin_request", { workspaceId, requestId });
+}
+
+export async function unpinRequest(workspaceId: string, requestId: string) {
+  await invoke("models_unpin_request", { workspaceId, requestId });
+}
+
+export async function appendGrpcMessageTemplate(
+  requestId: string,
+  name: string,
+  message: string,
+): Promise<GrpcRequest> {
+  return invoke<GrpcRequest>("models_append_grpc_message_template", { requestId, name, message });
+}
+
+export async function reorderGrpcMessageTemplates(
+  requestId: string,
+  templateIds: string[],
+): Promise<GrpcRequest> {
+  return invoke<GrpcRequest>("models_reorder_grpc_message_templates", { requestId, templateIds });
+}
+
+export async function deleteGrpcMessageTemplate(
+  requestId: string,
+  templateId: string,
+): Promise<GrpcRequest> {
+  return invoke<GrpcRequest>("models_delete_grpc_message_template", { requestId, templateId });
+}
+
+export async function upsertCookie(
+  cookieJarId: string,
+  cookie: Cookie,
+  previous: Cookie | null = null,
+): Promise<CookieJar> {
+  return invoke<CookieJar>("models_upsert_cookie", { cookieJarId, cookie, previous });
+}
+
+export async function deleteCookie(cookieJarId: string, cookie: Cookie): Promise<CookieJar> {
+  return invoke<CookieJar>("models_delete_cookie", { cookieJarId, cookie });
+}
+
+export async function clearCookies(cookieJarId: string): Promise<CookieJar> {
+  return invoke<CookieJar>("models_clear_cookies", { cookieJarId });
+}
+
+export async function getProtoFiles(workspaceId: string): Promise<ProtoFiles | null> {
+  return invoke<ProtoFiles | null>("models_get_proto_files", { workspaceId });
+}
+
+export async function attachProtoFiles(
+  workspaceId: string,
+  paths: string[],
+  includeDirs: string[],
+): Promise<ProtoFiles> {
+  return invoke<ProtoFiles>("models_attach_proto_files", { workspaceId, paths, includeDirs });
+}
+
+export async function detachProtoFiles(workspaceId: string, paths: string[]): Promise<ProtoFiles> {
+  return invoke<ProtoFiles>("models_detach_proto_files", { workspaceId, paths });
+}
+
+export async function listTags(workspaceId: string): Promise<string[]> {
+  return invoke<string[]>("models_list_tags", { workspaceId });
+}
+
+export async function listRequestsByTag(workspaceId: string, tag: string): Promise<AnyModel[]> {
+  return invoke<AnyModel[]>("models_list_requests_by_tag", { workspaceId, tag });
+}
+
+export async function renameTag(workspaceId: string, oldTag: string, newTag: string) {
+  await invoke("models_rename_tag", { workspaceId, oldTag, newTag });
+}
+
+export async function deleteTag(workspaceId: string, tag: string) {
+  await invoke("models_delete_tag", { workspaceId, tag });
+}
+
 export function duplicateModel<M extends AnyModel["model"], T extends ExtractModel<AnyModel, M>>(
   model: T | null,
 ) {
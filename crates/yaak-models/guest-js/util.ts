@@ -2,6 +2,7 @@ import { ModelStoreData } from "./types";
 
 export function newStoreData(): ModelStoreData {
   return {
+    collection_run: {},
     cookie_jar: {},
     environment: {},
     folder: {},
@@ -13,8 +14,15 @@ export function newStoreData(): ModelStoreData {
     http_response: {},
     http_response_event: {},
     key_value: {},
+    monitor: {},
+    monitor_run: {},
+    mqtt_connection: {},
+    mqtt_event: {},
+    mqtt_request: {},
     plugin: {},
     settings: {},
+    socket_request: {},
+    socket_response: {},
     sync_state: {},
     websocket_connection: {},
     websocket_event: {},
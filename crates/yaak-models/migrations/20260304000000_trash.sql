@@ -0,0 +1,8 @@
+-- Folders, HTTP requests, and WebSocket requests already have a (previously unused) deleted_at
+-- column, so only gRPC requests need one for trash support.
+ALTER TABLE grpc_requests ADD COLUMN deleted_at DATETIME;
+
+-- Clear any stale values so nothing unexpectedly shows up in the trash
+UPDATE folders SET deleted_at = NULL WHERE deleted_at IS NOT NULL;
+UPDATE http_requests SET deleted_at = NULL WHERE deleted_at IS NOT NULL;
+UPDATE websocket_requests SET deleted_at = NULL WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,167 @@
+-- Full-text index of request content, kept in sync with the request tables by triggers below
+CREATE VIRTUAL TABLE search_requests USING fts5
+(
+    id UNINDEXED,
+    model UNINDEXED,
+    workspace_id UNINDEXED,
+    name,
+    url,
+    body,
+    headers
+);
+
+-- Full-text index of response bodies. Rows share a rowid with http_responses and are written by
+-- the app once a response finishes, since bodies live on disk rather than in the database.
+CREATE VIRTUAL TABLE search_http_responses USING fts5
+(
+    workspace_id UNINDEXED,
+    request_id UNINDEXED,
+    body
+);
+
+---------------------------
+-- HTTP request triggers --
+---------------------------
+
+CREATE TRIGGER search_http_requests_insert
+    AFTER INSERT
+    ON http_requests
+    WHEN NEW.deleted_at IS NULL
+BEGIN
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    VALUES (NEW.id, 'http_request', NEW.workspace_id, NEW.name, NEW.url,
+            COALESCE(json_extract(NEW.body, '$.text'), NEW.body),
+            (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+             FROM json_each(NEW.headers) h));
+END;
+
+CREATE TRIGGER search_http_requests_update
+    AFTER UPDATE
+    ON http_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    SELECT NEW.id, 'http_request', NEW.workspace_id, NEW.name, NEW.url,
+           COALESCE(json_extract(NEW.body, '$.text'), NEW.body),
+           (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+            FROM json_each(NEW.headers) h)
+    WHERE NEW.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER search_http_requests_delete
+    AFTER DELETE
+    ON http_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+END;
+
+---------------------------
+-- gRPC request triggers --
+---------------------------
+
+CREATE TRIGGER search_grpc_requests_insert
+    AFTER INSERT
+    ON grpc_requests
+    WHEN NEW.deleted_at IS NULL
+BEGIN
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    VALUES (NEW.id, 'grpc_request', NEW.workspace_id, NEW.name,
+            NEW.url || ' ' || COALESCE(NEW.service, '') || ' ' || COALESCE(NEW.method, ''),
+            NEW.message,
+            (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+             FROM json_each(NEW.metadata) h));
+END;
+
+CREATE TRIGGER search_grpc_requests_update
+    AFTER UPDATE
+    ON grpc_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    SELECT NEW.id, 'grpc_request', NEW.workspace_id, NEW.name,
+           NEW.url || ' ' || COALESCE(NEW.service, '') || ' ' || COALESCE(NEW.method, ''),
+           NEW.message,
+           (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+            FROM json_each(NEW.metadata) h)
+    WHERE NEW.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER search_grpc_requests_delete
+    AFTER DELETE
+    ON grpc_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+END;
+
+--------------------------------
+-- WebSocket request triggers --
+--------------------------------
+
+CREATE TRIGGER search_websocket_requests_insert
+    AFTER INSERT
+    ON websocket_requests
+    WHEN NEW.deleted_at IS NULL
+BEGIN
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    VALUES (NEW.id, 'websocket_request', NEW.workspace_id, NEW.name, NEW.url, NEW.message,
+            (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+             FROM json_each(NEW.headers) h));
+END;
+
+CREATE TRIGGER search_websocket_requests_update
+    AFTER UPDATE
+    ON websocket_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+    INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+    SELECT NEW.id, 'websocket_request', NEW.workspace_id, NEW.name, NEW.url, NEW.message,
+           (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+            FROM json_each(NEW.headers) h)
+    WHERE NEW.deleted_at IS NULL;
+END;
+
+CREATE TRIGGER search_websocket_requests_delete
+    AFTER DELETE
+    ON websocket_requests
+BEGIN
+    DELETE FROM search_requests WHERE id = OLD.id;
+END;
+
+----------------------------
+-- HTTP response triggers --
+----------------------------
+
+CREATE TRIGGER search_http_responses_delete
+    AFTER DELETE
+    ON http_responses
+BEGIN
+    DELETE FROM search_http_responses WHERE rowid = OLD.rowid;
+END;
+
+---------------------------------
+-- Index existing request data --
+---------------------------------
+
+INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+SELECT r.id, 'http_request', r.workspace_id, r.name, r.url,
+       COALESCE(json_extract(r.body, '$.text'), r.body),
+       (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+        FROM json_each(r.headers) h)
+FROM http_requests r
+WHERE r.deleted_at IS NULL;
+
+INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+SELECT r.id, 'grpc_request', r.workspace_id, r.name,
+       r.url || ' ' || COALESCE(r.service, '') || ' ' || COALESCE(r.method, ''),
+       r.message,
+       (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+        FROM json_each(r.metadata) h)
+FROM grpc_requests r
+WHERE r.deleted_at IS NULL;
+
+INSERT INTO search_requests(id, model, workspace_id, name, url, body, headers)
+SELECT r.id, 'websocket_request', r.workspace_id, r.name, r.url, r.message,
+       (SELECT group_concat(json_extract(h.value, '$.name') || ' ' || json_extract(h.value, '$.value'), ' ')
+        FROM json_each(r.headers) h)
+FROM websocket_requests r
+WHERE r.deleted_at IS NULL;
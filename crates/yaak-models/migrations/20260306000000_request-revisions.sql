@@ -0,0 +1,11 @@
+CREATE TABLE request_revisions
+(
+    id           INTEGER PRIMARY KEY AUTOINCREMENT,
+    model        TEXT                                                    NOT NULL,
+    request_id   TEXT                                                    NOT NULL,
+    workspace_id TEXT                                                    NOT NULL,
+    snapshot     TEXT                                                    NOT NULL,
+    created_at   DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL
+);
+
+CREATE INDEX idx_request_revisions_request_id ON request_revisions (request_id, id);
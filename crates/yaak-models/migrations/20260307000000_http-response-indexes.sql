@@ -0,0 +1,2 @@
+CREATE INDEX idx_http_responses_request_id_created_at ON http_responses (request_id, created_at);
+CREATE INDEX idx_http_responses_workspace_id_created_at ON http_responses (workspace_id, created_at);
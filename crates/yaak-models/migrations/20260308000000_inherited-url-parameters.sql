@@ -0,0 +1,3 @@
+ALTER TABLE workspaces ADD COLUMN url_parameters TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE folders ADD COLUMN url_parameters TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,5 @@
+ALTER TABLE folders ADD COLUMN tags TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE http_requests ADD COLUMN tags TEXT DEFAULT '[]' NOT NULL;
+
+ALTER TABLE grpc_requests ADD COLUMN tags TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,37 @@
+CREATE TABLE pins
+(
+    workspace_id TEXT                                                    NOT NULL,
+    model_id     TEXT                                                    NOT NULL,
+    created_at   DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    PRIMARY KEY (workspace_id, model_id)
+);
+
+-- Pins are dropped along with the request they point to
+
+CREATE TRIGGER pins_http_requests_delete
+    AFTER DELETE
+    ON http_requests
+BEGIN
+    DELETE FROM pins WHERE model_id = OLD.id;
+END;
+
+CREATE TRIGGER pins_grpc_requests_delete
+    AFTER DELETE
+    ON grpc_requests
+BEGIN
+    DELETE FROM pins WHERE model_id = OLD.id;
+END;
+
+CREATE TRIGGER pins_websocket_requests_delete
+    AFTER DELETE
+    ON websocket_requests
+BEGIN
+    DELETE FROM pins WHERE model_id = OLD.id;
+END;
+
+CREATE TRIGGER pins_workspaces_delete
+    AFTER DELETE
+    ON workspaces
+BEGIN
+    DELETE FROM pins WHERE workspace_id = OLD.id;
+END;
@@ -0,0 +1,13 @@
+-- Descriptor sets fetched through gRPC server reflection, shared by every request that talks to
+-- the same host with the same TLS configuration
+CREATE TABLE grpc_descriptor_caches
+(
+    model          TEXT     DEFAULT 'grpc_descriptor_cache' NOT NULL,
+    created_at     DATETIME DEFAULT CURRENT_TIMESTAMP       NOT NULL,
+    updated_at     DATETIME DEFAULT CURRENT_TIMESTAMP       NOT NULL,
+    host           TEXT                                     NOT NULL,
+    tls_key        TEXT                                     NOT NULL,
+    descriptor_set BLOB                                     NOT NULL,
+    expires_at     DATETIME                                 NOT NULL,
+    PRIMARY KEY (host, tls_key)
+);
@@ -0,0 +1,16 @@
+-- Local .proto sources attached to a workspace, used to build gRPC requests when the server
+-- doesn't support reflection
+CREATE TABLE proto_files
+(
+    workspace_id   TEXT                               NOT NULL
+        PRIMARY KEY
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    model          TEXT     DEFAULT 'proto_files'     NOT NULL,
+    created_at     DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at     DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    paths          TEXT     DEFAULT '[]'              NOT NULL,
+    include_dirs   TEXT     DEFAULT '[]'              NOT NULL,
+    descriptor_set BLOB                               NULL,
+    compiled_at    DATETIME                           NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE grpc_requests ADD COLUMN message_templates TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE graphql_introspections ADD COLUMN url TEXT NULL;
+
+CREATE INDEX idx_graphql_introspections_url ON graphql_introspections (workspace_id, url);
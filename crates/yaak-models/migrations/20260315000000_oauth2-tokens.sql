@@ -0,0 +1,16 @@
+-- OAuth 2.0 tokens obtained by auth plugins, keyed by a hash of the auth configuration so they
+-- can be refreshed before sending instead of failing with a 401
+CREATE TABLE oauth2_tokens
+(
+    model         TEXT     DEFAULT 'oauth2_token'     NOT NULL,
+    created_at    DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at    DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    config_hash   TEXT                               NOT NULL
+        PRIMARY KEY,
+    access_token  TEXT                               NOT NULL,
+    id_token      TEXT                               NULL,
+    refresh_token TEXT                               NULL,
+    token_type    TEXT                               NULL,
+    scopes        TEXT     DEFAULT '[]'              NOT NULL,
+    expires_at    DATETIME                           NULL
+);
@@ -0,0 +1 @@
+ALTER TABLE environments ADD COLUMN cookie_jar_id TEXT NULL;
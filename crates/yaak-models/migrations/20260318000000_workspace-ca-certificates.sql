@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_ca_certificates TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN response_filter TEXT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN response_extractions TEXT DEFAULT '[]' NOT NULL;
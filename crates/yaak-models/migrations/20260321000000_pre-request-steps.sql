@@ -0,0 +1,2 @@
+ALTER TABLE http_requests ADD COLUMN pre_request_steps TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE grpc_requests ADD COLUMN pre_request_steps TEXT DEFAULT '[]' NOT NULL;
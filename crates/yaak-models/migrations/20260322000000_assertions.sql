@@ -0,0 +1,2 @@
+ALTER TABLE http_requests ADD COLUMN assertions TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE http_responses ADD COLUMN assertion_results TEXT DEFAULT '[]' NOT NULL;
@@ -0,0 +1,22 @@
+CREATE TABLE collection_runs
+(
+    id               TEXT                                                    NOT NULL
+        PRIMARY KEY,
+    model            TEXT     DEFAULT 'collection_run'                       NOT NULL,
+    workspace_id     TEXT                                                    NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    folder_id        TEXT                                                    NOT NULL
+        REFERENCES folders
+            ON DELETE CASCADE,
+    created_at       DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    updated_at       DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    state            TEXT                                                    NOT NULL,
+    concurrency      INTEGER  DEFAULT 1                                      NOT NULL,
+    delay            INTEGER  DEFAULT 0                                      NOT NULL,
+    stop_on_failure  BOOLEAN  DEFAULT FALSE                                  NOT NULL,
+    elapsed          INTEGER  DEFAULT 0                                      NOT NULL,
+    results          TEXT     DEFAULT '[]'                                   NOT NULL
+);
+
+CREATE INDEX idx_collection_runs_folder_id ON collection_runs (folder_id);
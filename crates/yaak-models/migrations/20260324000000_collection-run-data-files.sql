@@ -0,0 +1 @@
+ALTER TABLE collection_runs ADD COLUMN data_file TEXT;
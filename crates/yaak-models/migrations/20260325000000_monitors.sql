@@ -0,0 +1,45 @@
+CREATE TABLE monitors
+(
+    id                   TEXT                                                    NOT NULL
+        PRIMARY KEY,
+    model                TEXT     DEFAULT 'monitor'                              NOT NULL,
+    workspace_id         TEXT                                                    NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    created_at           DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    updated_at           DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    name                 TEXT     DEFAULT ''                                     NOT NULL,
+    enabled              BOOLEAN  DEFAULT TRUE                                   NOT NULL,
+    folder_id            TEXT     NULL
+        REFERENCES folders
+            ON DELETE CASCADE,
+    request_id           TEXT     NULL
+        REFERENCES http_requests
+            ON DELETE CASCADE,
+    environment_id       TEXT     NULL,
+    cookie_jar_id        TEXT     NULL,
+    interval             INTEGER  DEFAULT 300                                    NOT NULL,
+    failure_threshold    INTEGER  DEFAULT 3                                      NOT NULL,
+    consecutive_failures INTEGER  DEFAULT 0                                      NOT NULL,
+    last_run_at          DATETIME NULL
+);
+
+CREATE TABLE monitor_runs
+(
+    id           TEXT                                                    NOT NULL
+        PRIMARY KEY,
+    model        TEXT     DEFAULT 'monitor_run'                          NOT NULL,
+    workspace_id TEXT                                                    NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    monitor_id   TEXT                                                    NOT NULL
+        REFERENCES monitors
+            ON DELETE CASCADE,
+    created_at   DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    updated_at   DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    passed       BOOLEAN  DEFAULT FALSE                                  NOT NULL,
+    elapsed      INTEGER  DEFAULT 0                                      NOT NULL,
+    results      TEXT     DEFAULT '[]'                                   NOT NULL
+);
+
+CREATE INDEX idx_monitor_runs_monitor_id ON monitor_runs (monitor_id);
@@ -0,0 +1,2 @@
+ALTER TABLE workspace_metas ADD COLUMN setting_sync_exclude_folder_ids TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE workspace_metas ADD COLUMN setting_sync_exclude_secret_values BOOLEAN DEFAULT FALSE NOT NULL;
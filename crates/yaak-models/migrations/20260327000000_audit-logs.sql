@@ -0,0 +1,14 @@
+CREATE TABLE audit_logs
+(
+    id            INTEGER PRIMARY KEY AUTOINCREMENT,
+    workspace_id  TEXT                                                    NOT NULL,
+    model         TEXT                                                    NOT NULL,
+    model_id      TEXT                                                    NOT NULL,
+    model_name    TEXT                                                    NOT NULL,
+    change        TEXT                                                    NOT NULL,
+    update_source TEXT                                                    NOT NULL,
+    created_at    DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL
+);
+
+CREATE INDEX idx_audit_logs_workspace_id_created_at ON audit_logs (workspace_id, created_at);
+CREATE INDEX idx_audit_logs_model_id ON audit_logs (model_id);
@@ -0,0 +1,2 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_responses_per_request INTEGER NULL;
+ALTER TABLE workspaces ADD COLUMN setting_response_retention_days INTEGER NULL;
@@ -0,0 +1,12 @@
+ALTER TABLE workspaces ADD COLUMN setting_connect_timeout INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_read_timeout INTEGER DEFAULT 0 NOT NULL;
+
+ALTER TABLE folders ADD COLUMN setting_connect_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+ALTER TABLE folders ADD COLUMN setting_read_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+
+ALTER TABLE http_requests ADD COLUMN setting_connect_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+ALTER TABLE http_requests ADD COLUMN setting_read_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+
+ALTER TABLE grpc_requests ADD COLUMN setting_request_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+ALTER TABLE grpc_requests ADD COLUMN setting_connect_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
+ALTER TABLE grpc_requests ADD COLUMN setting_read_timeout TEXT DEFAULT '{"enabled":false,"value":0}' NOT NULL;
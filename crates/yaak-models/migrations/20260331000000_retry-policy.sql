@@ -0,0 +1,3 @@
+ALTER TABLE folders ADD COLUMN setting_retry_policy TEXT DEFAULT '{"enabled":false}' NOT NULL;
+
+ALTER TABLE http_requests ADD COLUMN setting_retry_policy TEXT DEFAULT '{"enabled":false}' NOT NULL;
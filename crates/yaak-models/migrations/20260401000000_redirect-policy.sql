@@ -0,0 +1,13 @@
+ALTER TABLE workspaces ADD COLUMN setting_max_redirects INTEGER DEFAULT 10 NOT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_redirect_resend_body BOOLEAN DEFAULT TRUE NOT NULL;
+ALTER TABLE workspaces ADD COLUMN setting_redirect_strip_authorization BOOLEAN DEFAULT TRUE NOT NULL;
+
+ALTER TABLE folders ADD COLUMN setting_max_redirects TEXT DEFAULT '{"enabled":false,"value":10}' NOT NULL;
+ALTER TABLE folders ADD COLUMN setting_redirect_resend_body TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
+ALTER TABLE folders ADD COLUMN setting_redirect_strip_authorization TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
+
+ALTER TABLE http_requests ADD COLUMN setting_max_redirects TEXT DEFAULT '{"enabled":false,"value":10}' NOT NULL;
+ALTER TABLE http_requests ADD COLUMN setting_redirect_resend_body TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
+ALTER TABLE http_requests ADD COLUMN setting_redirect_strip_authorization TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
+
+ALTER TABLE http_responses ADD COLUMN redirects TEXT DEFAULT '[]' NOT NULL;
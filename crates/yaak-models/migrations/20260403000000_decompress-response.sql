@@ -0,0 +1,3 @@
+ALTER TABLE workspaces ADD COLUMN setting_decompress_response BOOLEAN DEFAULT TRUE NOT NULL;
+ALTER TABLE folders ADD COLUMN setting_decompress_response TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
+ALTER TABLE http_requests ADD COLUMN setting_decompress_response TEXT DEFAULT '{"enabled":false,"value":true}' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE http_requests ADD COLUMN unix_socket TEXT;
@@ -0,0 +1,3 @@
+ALTER TABLE workspaces ADD COLUMN setting_ssh_tunnel TEXT NULL;
+
+ALTER TABLE folders ADD COLUMN setting_ssh_tunnel TEXT DEFAULT '{"enabled":false}' NOT NULL;
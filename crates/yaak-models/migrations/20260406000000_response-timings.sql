@@ -0,0 +1,3 @@
+-- Add connection and request body timing to http_responses
+ALTER TABLE http_responses ADD COLUMN elapsed_connect INTEGER DEFAULT 0 NOT NULL;
+ALTER TABLE http_responses ADD COLUMN elapsed_send INTEGER DEFAULT 0 NOT NULL;
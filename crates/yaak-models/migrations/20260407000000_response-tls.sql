@@ -0,0 +1 @@
+ALTER TABLE http_responses ADD COLUMN tls TEXT NULL;
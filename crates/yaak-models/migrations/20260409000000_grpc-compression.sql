@@ -0,0 +1,3 @@
+ALTER TABLE grpc_requests ADD COLUMN send_compression TEXT NULL;
+ALTER TABLE grpc_requests ADD COLUMN accept_compression TEXT DEFAULT '[]' NOT NULL;
+ALTER TABLE grpc_connections ADD COLUMN response_encoding TEXT NULL;
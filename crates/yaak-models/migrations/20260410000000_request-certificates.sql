@@ -0,0 +1,5 @@
+ALTER TABLE folders ADD COLUMN setting_ca_certificates TEXT DEFAULT '{"enabled":false}' NOT NULL;
+ALTER TABLE folders ADD COLUMN setting_client_certificate TEXT DEFAULT '{"enabled":false}' NOT NULL;
+
+ALTER TABLE grpc_requests ADD COLUMN setting_ca_certificates TEXT DEFAULT '{"enabled":false}' NOT NULL;
+ALTER TABLE grpc_requests ADD COLUMN setting_client_certificate TEXT DEFAULT '{"enabled":false}' NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE websocket_requests ADD COLUMN reconnect TEXT DEFAULT '{}' NOT NULL;
@@ -0,0 +1,2 @@
+ALTER TABLE websocket_requests ADD COLUMN message_format TEXT DEFAULT 'text' NOT NULL;
+ALTER TABLE websocket_requests ADD COLUMN message_file TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE websocket_requests ADD COLUMN protocol TEXT DEFAULT 'websocket' NOT NULL;
+ALTER TABLE websocket_requests ADD COLUMN socket_io TEXT DEFAULT '{}' NOT NULL;
@@ -0,0 +1,74 @@
+CREATE TABLE mqtt_requests
+(
+    id                            TEXT                               NOT NULL
+        PRIMARY KEY,
+    model                         TEXT     DEFAULT 'mqtt_request'    NOT NULL,
+    workspace_id                  TEXT                               NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    folder_id                     TEXT
+        REFERENCES folders
+            ON DELETE CASCADE,
+    created_at                    DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at                    DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    deleted_at                    DATETIME,
+    description                   TEXT     DEFAULT ''                NOT NULL,
+    name                          TEXT     DEFAULT ''                NOT NULL,
+    sort_priority                 REAL     DEFAULT 0                 NOT NULL,
+    url                           TEXT     DEFAULT ''                NOT NULL,
+    client_id                     TEXT     DEFAULT ''                NOT NULL,
+    username                      TEXT     DEFAULT ''                NOT NULL,
+    password                      TEXT     DEFAULT ''                NOT NULL,
+    keep_alive                    INTEGER  DEFAULT 0                 NOT NULL,
+    persistent_session            BOOLEAN  DEFAULT FALSE             NOT NULL,
+    subscriptions                 TEXT     DEFAULT '[]'              NOT NULL,
+    topic                         TEXT     DEFAULT ''                NOT NULL,
+    payload                       TEXT     DEFAULT ''                NOT NULL,
+    qos                           TEXT     DEFAULT 'at_most_once'    NOT NULL,
+    retain                        BOOLEAN  DEFAULT FALSE             NOT NULL,
+    setting_validate_certificates TEXT     DEFAULT '{"enabled":false,"value":true}' NOT NULL
+);
+
+CREATE TABLE mqtt_connections
+(
+    id           TEXT                               NOT NULL
+        PRIMARY KEY,
+    model        TEXT     DEFAULT 'mqtt_connection' NOT NULL,
+    workspace_id TEXT                               NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    request_id   TEXT                               NOT NULL
+        REFERENCES mqtt_requests
+            ON DELETE CASCADE,
+    created_at   DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at   DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    url          TEXT                               NOT NULL,
+    client_id    TEXT     DEFAULT ''                NOT NULL,
+    state        TEXT                               NOT NULL,
+    error        TEXT                               NULL,
+    elapsed      INTEGER  DEFAULT 0                 NOT NULL
+);
+
+CREATE TABLE mqtt_events
+(
+    id            TEXT                                                    NOT NULL
+        PRIMARY KEY,
+    model         TEXT     DEFAULT 'mqtt_event'                           NOT NULL,
+    workspace_id  TEXT                                                    NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    request_id    TEXT                                                    NOT NULL
+        REFERENCES mqtt_requests
+            ON DELETE CASCADE,
+    connection_id TEXT                                                    NOT NULL
+        REFERENCES mqtt_connections
+            ON DELETE CASCADE,
+    created_at    DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    updated_at    DATETIME DEFAULT (STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')) NOT NULL,
+    is_server     BOOLEAN                                                 NOT NULL,
+    event_type    TEXT                                                    NOT NULL,
+    topic         TEXT     DEFAULT ''                                     NOT NULL,
+    message       BLOB                                                    NOT NULL,
+    qos           TEXT     DEFAULT 'at_most_once'                         NOT NULL,
+    retain        BOOLEAN  DEFAULT FALSE                                  NOT NULL
+);
@@ -0,0 +1,48 @@
+CREATE TABLE socket_requests
+(
+    id             TEXT                               NOT NULL
+        PRIMARY KEY,
+    model          TEXT     DEFAULT 'socket_request'  NOT NULL,
+    workspace_id   TEXT                               NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    folder_id      TEXT
+        REFERENCES folders
+            ON DELETE CASCADE,
+    created_at     DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at     DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    deleted_at     DATETIME,
+    description    TEXT     DEFAULT ''                NOT NULL,
+    name           TEXT     DEFAULT ''                NOT NULL,
+    sort_priority  REAL     DEFAULT 0                 NOT NULL,
+    protocol       TEXT     DEFAULT 'tcp'             NOT NULL,
+    url            TEXT     DEFAULT ''                NOT NULL,
+    payload        TEXT     DEFAULT ''                NOT NULL,
+    payload_format TEXT     DEFAULT 'text'            NOT NULL,
+    read_timeout   INTEGER  DEFAULT 0                 NOT NULL
+);
+
+CREATE TABLE socket_responses
+(
+    id                 TEXT                               NOT NULL
+        PRIMARY KEY,
+    model              TEXT     DEFAULT 'socket_response' NOT NULL,
+    workspace_id       TEXT                               NOT NULL
+        REFERENCES workspaces
+            ON DELETE CASCADE,
+    request_id         TEXT                               NOT NULL
+        REFERENCES socket_requests
+            ON DELETE CASCADE,
+    created_at         DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    updated_at         DATETIME DEFAULT CURRENT_TIMESTAMP NOT NULL,
+    protocol           TEXT     DEFAULT 'tcp'             NOT NULL,
+    url                TEXT                               NOT NULL,
+    remote_addr        TEXT                               NULL,
+    state              TEXT                               NOT NULL,
+    error              TEXT                               NULL,
+    elapsed_connect    INTEGER  DEFAULT 0                 NOT NULL,
+    elapsed_first_byte INTEGER                            NULL,
+    elapsed            INTEGER  DEFAULT 0                 NOT NULL,
+    bytes_sent         INTEGER  DEFAULT 0                 NOT NULL,
+    body               BLOB     DEFAULT X''               NOT NULL
+);
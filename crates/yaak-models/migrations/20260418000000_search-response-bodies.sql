@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_index_response_bodies BOOLEAN DEFAULT TRUE NOT NULL;
@@ -0,0 +1 @@
+ALTER TABLE sync_states ADD COLUMN base TEXT;
@@ -0,0 +1 @@
+ALTER TABLE workspaces ADD COLUMN setting_content_addressed_body_threshold INTEGER NULL;
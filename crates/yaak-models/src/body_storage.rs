@@ -0,0 +1,55 @@
+use crate::error::Result;
+use crate::models::Workspace;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Response bodies larger than this are moved into content-addressed storage by default. Smaller
+/// bodies stay in a file named after their response, since hashing them costs more than the disk
+/// space deduplication would save. Workspaces can override it with
+/// `setting_content_addressed_body_threshold`.
+pub const DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+/// Threshold for moving a workspace's response bodies into content-addressed storage
+pub fn content_addressed_body_threshold(workspace: &Workspace) -> u64 {
+    workspace
+        .setting_content_addressed_body_threshold
+        .filter(|t| *t > 0)
+        .map_or(DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD, |t| t as u64)
+}
+
+/// Name of the directory, inside the responses directory, that holds content-addressed bodies
+const CONTENT_ADDRESSED_DIR: &str = "by-hash";
+
+/// Compute the hex-encoded SHA-256 digest of a file without loading it all into memory.
+pub fn hash_file(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Path of a content-addressed body within the responses directory. Bodies are sharded by the
+/// first two characters of their hash to keep directory sizes reasonable.
+pub fn content_addressed_body_path(response_dir: &Path, hash: &str) -> PathBuf {
+    let shard = &hash[..2.min(hash.len())];
+    response_dir.join(CONTENT_ADDRESSED_DIR).join(shard).join(hash)
+}
+
+/// Move a body file into content-addressed storage, returning its new location. If an identical
+/// body is already stored, the original file is removed and the existing copy is reused.
+pub fn move_to_content_addressed(response_dir: &Path, body_path: &Path) -> Result<PathBuf> {
+    let hash = hash_file(body_path)?;
+    let dest = content_addressed_body_path(response_dir, &hash);
+    if dest.exists() {
+        std::fs::remove_file(body_path)?;
+        return Ok(dest);
+    }
+
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(body_path, &dest)?;
+    Ok(dest)
+}
@@ -80,6 +80,7 @@ impl<'a> ClientDb<'a> {
         };
 
         self.record_model_change(&payload)?;
+        self.record_audit_log(&payload)?;
         let _ = self.events_tx.send(payload);
 
         Ok(m)
@@ -98,6 +99,7 @@ impl<'a> ClientDb<'a> {
         };
 
         self.record_model_change(&payload)?;
+        self.record_audit_log(&payload)?;
         let _ = self.events_tx.send(payload);
 
         Ok(m.clone())
@@ -12,6 +12,7 @@ use std::sync::mpsc;
 use std::time::Duration;
 
 pub mod blob_manager;
+pub mod body_storage;
 pub mod client_db;
 mod connection_or_tx;
 pub mod error;
@@ -20,6 +21,7 @@ pub mod models;
 pub mod queries;
 pub mod query_manager;
 pub mod render;
+pub mod response_cleanup;
 pub mod util;
 
 /// Initialize the database managers for standalone (non-Tauri) usage.
@@ -1,9 +1,12 @@
 use crate::error::Result;
 use crate::models::HttpRequestIden::{
-    Authentication, AuthenticationType, Body, BodyType, CreatedAt, Description, FolderId, Headers,
-    Method, Name, SettingFollowRedirects, SettingRequestTimeout, SettingSendCookies,
-    SettingStoreCookies, SettingValidateCertificates, SortPriority, UpdatedAt, Url, UrlParameters,
-    WorkspaceId,
+    Assertions, Authentication, AuthenticationType, Body, BodyType, CreatedAt, DeletedAt,
+    Description, FolderId, Headers, Method, Name, PreRequestSteps, ResponseExtractions,
+    ResponseFilter, SettingConnectTimeout, SettingDecompressResponse, SettingFollowRedirects,
+    SettingHttpVersion, SettingMaxRedirects, SettingReadTimeout, SettingRedirectResendBody,
+    SettingRedirectStripAuthorization, SettingRequestTimeout, SettingRetryPolicy,
+    SettingSendCookies, SettingStoreCookies, SettingValidateCertificates, SortPriority, Tags,
+    UnixSocket, UpdatedAt, Url, UrlParameters, WorkspaceId,
 };
 use crate::util::generate_prefixed_id;
 use chrono::{NaiveDateTime, Utc};
@@ -32,7 +35,7 @@ macro_rules! impl_model {
     };
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase", tag = "type")]
 #[ts(export, export_to = "gen_models.ts")]
 pub enum ProxySetting {
@@ -50,7 +53,7 @@ pub enum ProxySetting {
     Disabled,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 pub struct ProxySettingAuth {
@@ -58,7 +61,31 @@ pub struct ProxySettingAuth {
     pub password: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+impl ProxySetting {
+    /// Clear the proxy's username and password, so they stay out of exports and the sync directory
+    pub fn clear_credentials(&mut self) {
+        if let ProxySetting::Enabled { auth: Some(auth), .. } = self {
+            auth.user.clear();
+            auth.password.clear();
+        }
+    }
+
+    /// Put back credentials that [`ProxySetting::clear_credentials`] removed
+    pub fn restore_credentials(&mut self, existing: &ProxySetting) {
+        let (
+            ProxySetting::Enabled { auth: Some(auth), .. },
+            ProxySetting::Enabled { auth: Some(existing), .. },
+        ) = (self, existing)
+        else {
+            return;
+        };
+        if auth.user.is_empty() && auth.password.is_empty() {
+            *auth = existing.clone();
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 pub struct ClientCertificate {
@@ -78,9 +105,24 @@ pub struct ClientCertificate {
     pub enabled: bool,
 }
 
+/// A CA certificate trusted in addition to the platform's certificate store, either read from a
+/// PEM file or pasted inline
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
+pub struct CaCertificate {
+    #[serde(default)]
+    pub file: Option<String>,
+    #[serde(default)]
+    pub pem: Option<String>,
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Hash, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
 pub struct DnsOverride {
     pub hostname: String,
     #[serde(default)]
@@ -92,6 +134,46 @@ pub struct DnsOverride {
     pub enabled: bool,
 }
 
+/// SSH connection that requests are tunneled through, like a bastion in front of private APIs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct SshTunnel {
+    /// SSH server to connect to, like `bastion.example.com`
+    pub host: String,
+    /// SSH port, or 22 when unset
+    pub port: Option<i32>,
+    pub username: String,
+    /// Password to authenticate with when no private key file is set
+    pub password: String,
+    /// Path of a private key file to authenticate with, like `~/.ssh/id_ed25519`
+    pub private_key_file: String,
+    /// Passphrase of an encrypted private key
+    pub passphrase: String,
+    /// Comma-separated hosts reached through the tunnel, optionally with a port (eg.
+    /// `api.internal, 10.0.0.5:8443`). Every request is tunneled when empty.
+    pub destination: String,
+    pub disabled: bool,
+}
+
+impl SshTunnel {
+    /// Clear the password and passphrase, so they stay out of exports and the sync directory
+    pub fn clear_credentials(&mut self) {
+        self.password.clear();
+        self.passphrase.clear();
+    }
+
+    /// Put back credentials that [`SshTunnel::clear_credentials`] removed, keeping any that are set
+    pub fn restore_credentials(&mut self, existing: &SshTunnel) {
+        if self.password.is_empty() {
+            self.password = existing.password.clone();
+        }
+        if self.passphrase.is_empty() {
+            self.passphrase = existing.passphrase.clone();
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ResolvedSetting<T> {
     pub value: T,
@@ -115,11 +197,26 @@ impl<T> ResolvedSetting<T> {
     }
 }
 
+/// Number of redirects followed before a request fails, unless a setting says otherwise
+pub const DEFAULT_MAX_REDIRECTS: i32 = 10;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ResolvedHttpRequestSettings {
     pub validate_certificates: ResolvedSetting<bool>,
     pub follow_redirects: ResolvedSetting<bool>,
+    pub max_redirects: ResolvedSetting<i32>,
+    pub redirect_resend_body: ResolvedSetting<bool>,
+    pub redirect_strip_authorization: ResolvedSetting<bool>,
+    pub decompress_response: ResolvedSetting<bool>,
     pub request_timeout: ResolvedSetting<i32>,
+    pub connect_timeout: ResolvedSetting<i32>,
+    pub read_timeout: ResolvedSetting<i32>,
+    pub http_version: ResolvedSetting<HttpVersion>,
+    pub retry_policy: ResolvedSetting<RetryPolicy>,
+    pub ssh_tunnel: ResolvedSetting<Option<SshTunnel>>,
+    pub ca_certificates: ResolvedSetting<Vec<CaCertificate>>,
+    /// Certificate presented to every server, or `None` to match one by host from the app settings
+    pub client_certificate: ResolvedSetting<Option<ClientCertificate>>,
     pub send_cookies: ResolvedSetting<bool>,
     pub store_cookies: ResolvedSetting<bool>,
 }
@@ -129,7 +226,18 @@ impl Default for ResolvedHttpRequestSettings {
         Self {
             validate_certificates: ResolvedSetting::default_source(true),
             follow_redirects: ResolvedSetting::default_source(true),
+            max_redirects: ResolvedSetting::default_source(DEFAULT_MAX_REDIRECTS),
+            redirect_resend_body: ResolvedSetting::default_source(true),
+            redirect_strip_authorization: ResolvedSetting::default_source(true),
+            decompress_response: ResolvedSetting::default_source(true),
             request_timeout: ResolvedSetting::default_source(0),
+            connect_timeout: ResolvedSetting::default_source(0),
+            read_timeout: ResolvedSetting::default_source(0),
+            http_version: ResolvedSetting::default_source(HttpVersion::Auto),
+            retry_policy: ResolvedSetting::default_source(RetryPolicy::default()),
+            ssh_tunnel: ResolvedSetting::default_source(None),
+            ca_certificates: ResolvedSetting::default_source(Vec::new()),
+            client_certificate: ResolvedSetting::default_source(None),
             send_cookies: ResolvedSetting::default_source(true),
             store_cookies: ResolvedSetting::default_source(true),
         }
@@ -170,6 +278,116 @@ impl Default for InheritedIntSetting {
     }
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct InheritedHttpVersionSetting {
+    #[serde(default)]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub value: HttpVersion,
+}
+
+/// HTTP version requests are sent with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Hash, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpVersion {
+    /// Negotiate with the server, using HTTP/2 when it's offered over TLS
+    #[default]
+    Auto,
+    /// HTTP/1.1 only
+    Http1,
+    /// HTTP/2 only, including over plain HTTP
+    Http2,
+    /// HTTP/3 over QUIC
+    Http3,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct InheritedSshTunnelSetting {
+    #[serde(default)]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Tunnel for requests in the folder, or `None` to connect directly
+    #[serde(default)]
+    pub value: Option<SshTunnel>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct InheritedCaCertificatesSetting {
+    #[serde(default)]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// CA certificates trusted in place of the workspace's
+    #[serde(default)]
+    pub value: Vec<CaCertificate>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct InheritedClientCertificateSetting {
+    #[serde(default)]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Certificate presented to every server regardless of its host, or `None` to match one by
+    /// host from the app settings
+    #[serde(default)]
+    pub value: Option<ClientCertificate>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct InheritedRetryPolicySetting {
+    #[serde(default)]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub value: RetryPolicy,
+}
+
+/// When and how often a failed request is sent again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first. 1 disables retries.
+    pub max_attempts: i32,
+    /// Retry when the server responds with a 5xx status
+    pub retry_on_server_error: bool,
+    /// Retry when a connection to the server can't be established
+    pub retry_on_connection_error: bool,
+    /// Retry when a connect, read, or total timeout elapses
+    pub retry_on_timeout: bool,
+    /// Milliseconds to wait before the first retry, doubled for every retry after it
+    pub initial_delay: i32,
+    /// Upper bound in milliseconds for the delay between attempts
+    pub max_delay: i32,
+    /// Wait a random fraction of each delay, so clients retrying together spread out
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            retry_on_server_error: true,
+            retry_on_connection_error: true,
+            retry_on_timeout: true,
+            initial_delay: 500,
+            max_delay: 10_000,
+            jitter: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -391,6 +609,8 @@ pub struct Workspace {
     pub authentication_type: Option<String>,
     pub description: String,
     pub headers: Vec<HttpRequestHeader>,
+    /// Default URL parameters inherited by every request in the workspace
+    pub url_parameters: Vec<HttpUrlParameter>,
     pub name: String,
     pub encryption_key_challenge: Option<String>,
 
@@ -399,13 +619,55 @@ pub struct Workspace {
     pub setting_validate_certificates: bool,
     #[serde(default = "default_true")]
     pub setting_follow_redirects: bool,
+    /// Stop following redirects after this many hops and fail the request
+    #[serde(default = "default_max_redirects")]
+    pub setting_max_redirects: i32,
+    /// Send the request body again when a 307 or 308 redirect preserves the method
+    #[serde(default = "default_true")]
+    pub setting_redirect_resend_body: bool,
+    /// Remove the Authorization header when a redirect leads to a different origin
+    #[serde(default = "default_true")]
+    pub setting_redirect_strip_authorization: bool,
+    /// Decode compressed response bodies, or keep them exactly as the server sent them
+    #[serde(default = "default_true")]
+    pub setting_decompress_response: bool,
     pub setting_request_timeout: i32,
+    /// Milliseconds to wait for a connection to be established, or 0 to wait indefinitely
+    #[serde(default)]
+    pub setting_connect_timeout: i32,
+    /// Milliseconds to wait for data from the server between reads, or 0 to wait indefinitely
+    #[serde(default)]
+    pub setting_read_timeout: i32,
+    #[serde(default)]
+    pub setting_http_version: HttpVersion,
     #[serde(default)]
     pub setting_dns_overrides: Vec<DnsOverride>,
+    /// CA certificates trusted when validating server certificates, for internal CAs
+    #[serde(default)]
+    pub setting_ca_certificates: Vec<CaCertificate>,
     #[serde(default = "default_true")]
     pub setting_send_cookies: bool,
     #[serde(default = "default_true")]
     pub setting_store_cookies: bool,
+    /// Proxy used for requests in this workspace, overriding the global proxy setting when set
+    #[serde(default)]
+    pub setting_proxy: Option<ProxySetting>,
+    /// SSH tunnel used for requests in this workspace, unless a folder overrides it
+    #[serde(default)]
+    pub setting_ssh_tunnel: Option<SshTunnel>,
+    /// Keep at most this many responses per request, or the default history size when unset
+    #[serde(default)]
+    pub setting_max_responses_per_request: Option<i32>,
+    /// Delete responses older than this many days, or keep them regardless of age when unset
+    #[serde(default)]
+    pub setting_response_retention_days: Option<i32>,
+    /// Add response bodies to the search index so workspace search can find them
+    #[serde(default = "default_true")]
+    pub setting_index_response_bodies: bool,
+    /// Store response bodies larger than this many bytes once per unique body, or use the
+    /// default threshold when unset
+    #[serde(default)]
+    pub setting_content_addressed_body_threshold: Option<i32>,
 }
 
 impl UpsertModelInfo for Workspace {
@@ -434,6 +696,14 @@ impl UpsertModelInfo for Workspace {
         source: &UpdateSource,
     ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
         use WorkspaceIden::*;
+        let setting_proxy = match self.setting_proxy {
+            None => None,
+            Some(p) => Some(serde_json::to_string(&p)?),
+        };
+        let setting_ssh_tunnel = match self.setting_ssh_tunnel {
+            None => None,
+            Some(t) => Some(serde_json::to_string(&t)?),
+        };
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
@@ -441,14 +711,32 @@ impl UpsertModelInfo for Workspace {
             (Authentication, serde_json::to_string(&self.authentication)?.into()),
             (AuthenticationType, self.authentication_type.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (UrlParameters, serde_json::to_string(&self.url_parameters)?.into()),
             (Description, self.description.into()),
             (EncryptionKeyChallenge, self.encryption_key_challenge.into()),
             (SettingFollowRedirects, self.setting_follow_redirects.into()),
+            (SettingMaxRedirects, self.setting_max_redirects.into()),
+            (SettingRedirectResendBody, self.setting_redirect_resend_body.into()),
+            (SettingRedirectStripAuthorization, self.setting_redirect_strip_authorization.into()),
+            (SettingDecompressResponse, self.setting_decompress_response.into()),
             (SettingRequestTimeout, self.setting_request_timeout.into()),
+            (SettingConnectTimeout, self.setting_connect_timeout.into()),
+            (SettingReadTimeout, self.setting_read_timeout.into()),
+            (SettingHttpVersion, serde_json::to_value(&self.setting_http_version)?.as_str().into()),
             (SettingValidateCertificates, self.setting_validate_certificates.into()),
             (SettingDnsOverrides, serde_json::to_string(&self.setting_dns_overrides)?.into()),
+            (SettingCaCertificates, serde_json::to_string(&self.setting_ca_certificates)?.into()),
             (SettingSendCookies, self.setting_send_cookies.into()),
             (SettingStoreCookies, self.setting_store_cookies.into()),
+            (SettingProxy, setting_proxy.into()),
+            (SettingSshTunnel, setting_ssh_tunnel.into()),
+            (SettingMaxResponsesPerRequest, self.setting_max_responses_per_request.into()),
+            (SettingResponseRetentionDays, self.setting_response_retention_days.into()),
+            (SettingIndexResponseBodies, self.setting_index_response_bodies.into()),
+            (
+                SettingContentAddressedBodyThreshold,
+                self.setting_content_addressed_body_threshold.into(),
+            ),
         ])
     }
 
@@ -459,15 +747,30 @@ impl UpsertModelInfo for Workspace {
             WorkspaceIden::Authentication,
             WorkspaceIden::AuthenticationType,
             WorkspaceIden::Headers,
+            WorkspaceIden::UrlParameters,
             WorkspaceIden::Description,
             WorkspaceIden::EncryptionKeyChallenge,
             WorkspaceIden::SettingRequestTimeout,
             WorkspaceIden::SettingFollowRedirects,
+            WorkspaceIden::SettingMaxRedirects,
+            WorkspaceIden::SettingRedirectResendBody,
+            WorkspaceIden::SettingRedirectStripAuthorization,
+            WorkspaceIden::SettingDecompressResponse,
             WorkspaceIden::SettingRequestTimeout,
+            WorkspaceIden::SettingConnectTimeout,
+            WorkspaceIden::SettingReadTimeout,
+            WorkspaceIden::SettingHttpVersion,
             WorkspaceIden::SettingValidateCertificates,
             WorkspaceIden::SettingDnsOverrides,
+            WorkspaceIden::SettingCaCertificates,
             WorkspaceIden::SettingSendCookies,
             WorkspaceIden::SettingStoreCookies,
+            WorkspaceIden::SettingProxy,
+            WorkspaceIden::SettingSshTunnel,
+            WorkspaceIden::SettingMaxResponsesPerRequest,
+            WorkspaceIden::SettingResponseRetentionDays,
+            WorkspaceIden::SettingIndexResponseBodies,
+            WorkspaceIden::SettingContentAddressedBodyThreshold,
         ]
     }
 
@@ -477,7 +780,12 @@ impl UpsertModelInfo for Workspace {
     {
         let headers: String = row.get("headers")?;
         let authentication: String = row.get("authentication")?;
+        let url_parameters: String = row.get("url_parameters")?;
         let setting_dns_overrides: String = row.get("setting_dns_overrides")?;
+        let setting_ca_certificates: String = row.get("setting_ca_certificates")?;
+        let setting_proxy: Option<String> = row.get("setting_proxy")?;
+        let setting_ssh_tunnel: Option<String> = row.get("setting_ssh_tunnel")?;
+        let setting_http_version: String = row.get("setting_http_version")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -487,14 +795,33 @@ impl UpsertModelInfo for Workspace {
             description: row.get("description")?,
             encryption_key_challenge: row.get("encryption_key_challenge")?,
             headers: serde_json::from_str(&headers).unwrap_or_default(),
+            url_parameters: serde_json::from_str(&url_parameters).unwrap_or_default(),
             authentication: serde_json::from_str(&authentication).unwrap_or_default(),
             authentication_type: row.get("authentication_type")?,
             setting_follow_redirects: row.get("setting_follow_redirects")?,
+            setting_max_redirects: row.get("setting_max_redirects")?,
+            setting_redirect_resend_body: row.get("setting_redirect_resend_body")?,
+            setting_redirect_strip_authorization: row
+                .get("setting_redirect_strip_authorization")?,
+            setting_decompress_response: row.get("setting_decompress_response")?,
             setting_request_timeout: row.get("setting_request_timeout")?,
+            setting_connect_timeout: row.get("setting_connect_timeout")?,
+            setting_read_timeout: row.get("setting_read_timeout")?,
+            setting_http_version: serde_json::from_str(&format!(r#""{setting_http_version}""#))
+                .unwrap_or_default(),
             setting_validate_certificates: row.get("setting_validate_certificates")?,
             setting_dns_overrides: serde_json::from_str(&setting_dns_overrides).unwrap_or_default(),
+            setting_ca_certificates: serde_json::from_str(&setting_ca_certificates)
+                .unwrap_or_default(),
             setting_send_cookies: row.get("setting_send_cookies")?,
             setting_store_cookies: row.get("setting_store_cookies")?,
+            setting_proxy: setting_proxy.and_then(|p| serde_json::from_str(&p).ok()),
+            setting_ssh_tunnel: setting_ssh_tunnel.and_then(|t| serde_json::from_str(&t).ok()),
+            setting_max_responses_per_request: row.get("setting_max_responses_per_request")?,
+            setting_response_retention_days: row.get("setting_response_retention_days")?,
+            setting_index_response_bodies: row.get("setting_index_response_bodies")?,
+            setting_content_addressed_body_threshold: row
+                .get("setting_content_addressed_body_threshold")?,
         })
     }
 }
@@ -519,6 +846,10 @@ pub struct WorkspaceMeta {
     pub updated_at: NaiveDateTime,
     pub encryption_key: Option<EncryptedKey>,
     pub setting_sync_dir: Option<String>,
+    /// Folders that, along with everything inside them, are never written to the sync directory
+    pub setting_sync_exclude_folder_ids: Vec<String>,
+    /// Write secret environment variables to the sync directory without their values
+    pub setting_sync_exclude_secret_values: bool,
 }
 
 impl UpsertModelInfo for WorkspaceMeta {
@@ -553,6 +884,11 @@ impl UpsertModelInfo for WorkspaceMeta {
             (WorkspaceId, self.workspace_id.into()),
             (EncryptionKey, self.encryption_key.map(|e| serde_json::to_string(&e).unwrap()).into()),
             (SettingSyncDir, self.setting_sync_dir.into()),
+            (
+                SettingSyncExcludeFolderIds,
+                serde_json::to_string(&self.setting_sync_exclude_folder_ids)?.into(),
+            ),
+            (SettingSyncExcludeSecretValues, self.setting_sync_exclude_secret_values.into()),
         ])
     }
 
@@ -561,6 +897,8 @@ impl UpsertModelInfo for WorkspaceMeta {
             WorkspaceMetaIden::UpdatedAt,
             WorkspaceMetaIden::EncryptionKey,
             WorkspaceMetaIden::SettingSyncDir,
+            WorkspaceMetaIden::SettingSyncExcludeFolderIds,
+            WorkspaceMetaIden::SettingSyncExcludeSecretValues,
         ]
     }
 
@@ -569,6 +907,7 @@ impl UpsertModelInfo for WorkspaceMeta {
         Self: Sized,
     {
         let encryption_key: Option<String> = row.get("encryption_key")?;
+        let setting_sync_exclude_folder_ids: String = row.get("setting_sync_exclude_folder_ids")?;
         Ok(Self {
             id: row.get("id")?,
             workspace_id: row.get("workspace_id")?,
@@ -577,11 +916,14 @@ impl UpsertModelInfo for WorkspaceMeta {
             updated_at: row.get("updated_at")?,
             encryption_key: encryption_key.map(|e| serde_json::from_str(&e).unwrap()),
             setting_sync_dir: row.get("setting_sync_dir")?,
+            setting_sync_exclude_folder_ids: serde_json::from_str(&setting_sync_exclude_folder_ids)
+                .unwrap_or_default(),
+            setting_sync_exclude_secret_values: row.get("setting_sync_exclude_secret_values")?,
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "gen_models.ts")]
 pub enum CookieDomain {
     HostOnly(String),
@@ -590,14 +932,14 @@ pub enum CookieDomain {
     Empty,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "gen_models.ts")]
 pub enum CookieExpires {
     AtUtc(String),
     SessionEnd,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
 #[ts(export, export_to = "gen_models.ts")]
 pub enum CookieSameSite {
     Strict,
@@ -605,7 +947,7 @@ pub enum CookieSameSite {
     None,
 }
 
-#[derive(Debug, Clone, Serialize, TS)]
+#[derive(Debug, Clone, PartialEq, Serialize, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
 pub struct Cookie {
@@ -619,6 +961,14 @@ pub struct Cookie {
     pub same_site: Option<CookieSameSite>,
 }
 
+impl Cookie {
+    /// Whether both cookies share a name, domain, and path, meaning one replaces the other when
+    /// stored in a jar.
+    pub fn is_same_cookie(&self, other: &Cookie) -> bool {
+        self.name == other.name && self.domain == other.domain && self.path == other.path
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct CookieFields {
@@ -824,6 +1174,8 @@ pub struct Environment {
     pub variables: Vec<EnvironmentVariable>,
     pub color: Option<String>,
     pub sort_priority: f64,
+    /// Cookie jar to use while this environment is active, instead of the selected one.
+    pub cookie_jar_id: Option<String>,
 }
 
 impl UpsertModelInfo for Environment {
@@ -863,6 +1215,7 @@ impl UpsertModelInfo for Environment {
             (Public, self.public.into()),
             (SortPriority, self.sort_priority.into()),
             (Variables, serde_json::to_string(&self.variables)?.into()),
+            (CookieJarId, self.cookie_jar_id.into()),
         ])
     }
 
@@ -876,6 +1229,7 @@ impl UpsertModelInfo for Environment {
             EnvironmentIden::Public,
             EnvironmentIden::Variables,
             EnvironmentIden::SortPriority,
+            EnvironmentIden::CookieJarId,
         ]
     }
 
@@ -899,6 +1253,7 @@ impl UpsertModelInfo for Environment {
             public: row.get("public")?,
             variables: serde_json::from_str(variables.as_str()).unwrap_or_default(),
             sort_priority: row.get("sort_priority")?,
+            cookie_jar_id: row.get("cookie_jar_id")?,
 
             // Deprecated field, but we need to keep it around for a couple of versions
             // for compatibility because sync/export don't have a schema field
@@ -919,6 +1274,10 @@ pub struct EnvironmentVariable {
     pub value: String,
     #[ts(optional, as = "Option<String>")]
     pub id: Option<String>,
+    /// Whether the value is encrypted with the workspace key before it's stored, and left out of
+    /// exports
+    #[ts(optional, as = "Option<bool>")]
+    pub secret: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
@@ -954,14 +1313,32 @@ pub struct Folder {
     pub authentication: BTreeMap<String, Value>,
     pub authentication_type: Option<String>,
     pub description: String,
+    /// Labels used to group requests across folders (eg. `smoke-test`)
+    pub tags: Vec<String>,
     pub headers: Vec<HttpRequestHeader>,
+    /// Default URL parameters inherited by requests in this folder
+    pub url_parameters: Vec<HttpUrlParameter>,
     pub name: String,
     pub sort_priority: f64,
     pub setting_send_cookies: InheritedBoolSetting,
     pub setting_store_cookies: InheritedBoolSetting,
     pub setting_validate_certificates: InheritedBoolSetting,
     pub setting_follow_redirects: InheritedBoolSetting,
+    pub setting_max_redirects: InheritedIntSetting,
+    pub setting_redirect_resend_body: InheritedBoolSetting,
+    pub setting_redirect_strip_authorization: InheritedBoolSetting,
+    pub setting_decompress_response: InheritedBoolSetting,
     pub setting_request_timeout: InheritedIntSetting,
+    pub setting_connect_timeout: InheritedIntSetting,
+    pub setting_read_timeout: InheritedIntSetting,
+    pub setting_http_version: InheritedHttpVersionSetting,
+    pub setting_retry_policy: InheritedRetryPolicySetting,
+    pub setting_ssh_tunnel: InheritedSshTunnelSetting,
+    pub setting_ca_certificates: InheritedCaCertificatesSetting,
+    pub setting_client_certificate: InheritedClientCertificateSetting,
+    /// Set when the folder has been moved to the trash. Trashed folders are hidden from
+    /// listings until they are restored or the trash is emptied.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl UpsertModelInfo for Folder {
@@ -998,7 +1375,9 @@ impl UpsertModelInfo for Folder {
             (Authentication, serde_json::to_string(&self.authentication)?.into()),
             (AuthenticationType, self.authentication_type.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
+            (UrlParameters, serde_json::to_string(&self.url_parameters)?.into()),
             (Description, self.description.into()),
+            (Tags, serde_json::to_string(&self.tags)?.into()),
             (Name, self.name.trim().into()),
             (SortPriority, self.sort_priority.into()),
             (SettingSendCookies, serde_json::to_string(&self.setting_send_cookies)?.into()),
@@ -1008,7 +1387,31 @@ impl UpsertModelInfo for Folder {
                 serde_json::to_string(&self.setting_validate_certificates)?.into(),
             ),
             (SettingFollowRedirects, serde_json::to_string(&self.setting_follow_redirects)?.into()),
+            (SettingMaxRedirects, serde_json::to_string(&self.setting_max_redirects)?.into()),
+            (
+                SettingRedirectResendBody,
+                serde_json::to_string(&self.setting_redirect_resend_body)?.into(),
+            ),
+            (
+                SettingRedirectStripAuthorization,
+                serde_json::to_string(&self.setting_redirect_strip_authorization)?.into(),
+            ),
+            (
+                SettingDecompressResponse,
+                serde_json::to_string(&self.setting_decompress_response)?.into(),
+            ),
             (SettingRequestTimeout, serde_json::to_string(&self.setting_request_timeout)?.into()),
+            (SettingConnectTimeout, serde_json::to_string(&self.setting_connect_timeout)?.into()),
+            (SettingReadTimeout, serde_json::to_string(&self.setting_read_timeout)?.into()),
+            (SettingHttpVersion, serde_json::to_string(&self.setting_http_version)?.into()),
+            (SettingRetryPolicy, serde_json::to_string(&self.setting_retry_policy)?.into()),
+            (SettingSshTunnel, serde_json::to_string(&self.setting_ssh_tunnel)?.into()),
+            (SettingCaCertificates, serde_json::to_string(&self.setting_ca_certificates)?.into()),
+            (
+                SettingClientCertificate,
+                serde_json::to_string(&self.setting_client_certificate)?.into(),
+            ),
+            (DeletedAt, self.deleted_at.into()),
         ])
     }
 
@@ -1019,14 +1422,28 @@ impl UpsertModelInfo for Folder {
             FolderIden::Authentication,
             FolderIden::AuthenticationType,
             FolderIden::Headers,
+            FolderIden::UrlParameters,
             FolderIden::Description,
+            FolderIden::Tags,
             FolderIden::FolderId,
             FolderIden::SortPriority,
             FolderIden::SettingSendCookies,
             FolderIden::SettingStoreCookies,
             FolderIden::SettingValidateCertificates,
             FolderIden::SettingFollowRedirects,
+            FolderIden::SettingMaxRedirects,
+            FolderIden::SettingRedirectResendBody,
+            FolderIden::SettingRedirectStripAuthorization,
+            FolderIden::SettingDecompressResponse,
             FolderIden::SettingRequestTimeout,
+            FolderIden::SettingConnectTimeout,
+            FolderIden::SettingReadTimeout,
+            FolderIden::SettingHttpVersion,
+            FolderIden::SettingRetryPolicy,
+            FolderIden::SettingSshTunnel,
+            FolderIden::SettingCaCertificates,
+            FolderIden::SettingClientCertificate,
+            FolderIden::DeletedAt,
         ]
     }
 
@@ -1035,12 +1452,26 @@ impl UpsertModelInfo for Folder {
         Self: Sized,
     {
         let headers: String = row.get("headers")?;
+        let url_parameters: String = row.get("url_parameters")?;
+        let tags: String = row.get("tags")?;
         let authentication: String = row.get("authentication")?;
         let setting_send_cookies: String = row.get("setting_send_cookies")?;
         let setting_store_cookies: String = row.get("setting_store_cookies")?;
         let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
         let setting_follow_redirects: String = row.get("setting_follow_redirects")?;
+        let setting_max_redirects: String = row.get("setting_max_redirects")?;
+        let setting_redirect_resend_body: String = row.get("setting_redirect_resend_body")?;
+        let setting_redirect_strip_authorization: String =
+            row.get("setting_redirect_strip_authorization")?;
+        let setting_decompress_response: String = row.get("setting_decompress_response")?;
         let setting_request_timeout: String = row.get("setting_request_timeout")?;
+        let setting_connect_timeout: String = row.get("setting_connect_timeout")?;
+        let setting_read_timeout: String = row.get("setting_read_timeout")?;
+        let setting_http_version: String = row.get("setting_http_version")?;
+        let setting_retry_policy: String = row.get("setting_retry_policy")?;
+        let setting_ssh_tunnel: String = row.get("setting_ssh_tunnel")?;
+        let setting_ca_certificates: String = row.get("setting_ca_certificates")?;
+        let setting_client_certificate: String = row.get("setting_client_certificate")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -1051,7 +1482,9 @@ impl UpsertModelInfo for Folder {
             folder_id: row.get("folder_id")?,
             name: row.get("name")?,
             description: row.get("description")?,
+            tags: serde_json::from_str(&tags).unwrap_or_default(),
             headers: serde_json::from_str(&headers).unwrap_or_default(),
+            url_parameters: serde_json::from_str(&url_parameters).unwrap_or_default(),
             authentication_type: row.get("authentication_type")?,
             authentication: serde_json::from_str(&authentication).unwrap_or_default(),
             setting_send_cookies: serde_json::from_str(&setting_send_cookies).unwrap_or_default(),
@@ -1060,8 +1493,28 @@ impl UpsertModelInfo for Folder {
                 .unwrap_or_default(),
             setting_follow_redirects: serde_json::from_str(&setting_follow_redirects)
                 .unwrap_or_default(),
+            setting_max_redirects: serde_json::from_str(&setting_max_redirects).unwrap_or_default(),
+            setting_redirect_resend_body: serde_json::from_str(&setting_redirect_resend_body)
+                .unwrap_or_default(),
+            setting_redirect_strip_authorization: serde_json::from_str(
+                &setting_redirect_strip_authorization,
+            )
+            .unwrap_or_default(),
+            setting_decompress_response: serde_json::from_str(&setting_decompress_response)
+                .unwrap_or_default(),
             setting_request_timeout: serde_json::from_str(&setting_request_timeout)
                 .unwrap_or_default(),
+            setting_connect_timeout: serde_json::from_str(&setting_connect_timeout)
+                .unwrap_or_default(),
+            setting_read_timeout: serde_json::from_str(&setting_read_timeout).unwrap_or_default(),
+            setting_http_version: serde_json::from_str(&setting_http_version).unwrap_or_default(),
+            setting_retry_policy: serde_json::from_str(&setting_retry_policy).unwrap_or_default(),
+            setting_ssh_tunnel: serde_json::from_str(&setting_ssh_tunnel).unwrap_or_default(),
+            setting_ca_certificates: serde_json::from_str(&setting_ca_certificates)
+                .unwrap_or_default(),
+            setting_client_certificate: serde_json::from_str(&setting_client_certificate)
+                .unwrap_or_default(),
+            deleted_at: row.get("deleted_at")?,
         })
     }
 }
@@ -1094,6 +1547,125 @@ pub struct HttpUrlParameter {
     pub id: Option<String>,
 }
 
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum ResponseExtractionSource {
+    #[default]
+    Body,
+    Header,
+    Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum ResponseExtractionTarget {
+    #[default]
+    Environment,
+    Chained,
+}
+
+/// A rule that copies a value out of each response a request receives into a variable, so
+/// one request (like a login) can feed values to the ones that follow it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct ResponseExtraction {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Name of the variable to write
+    pub name: String,
+    pub source: ResponseExtractionSource,
+    /// JSONPath or XPath expression for body sources, or the header name for header sources
+    pub path: String,
+    /// Environment targets write to the active environment, or the base environment if none is
+    /// active. Chained targets write to the workspace's chained values, which every request can
+    /// use but which are overridden by environment variables with the same name.
+    pub target: ResponseExtractionTarget,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum PreRequestStepKind {
+    #[default]
+    SendRequest,
+    SetVariable,
+    PluginAction,
+}
+
+/// Work to do before a request is sent, like sending a login request first or running a plugin
+/// action. Steps run in order and a failing step stops the send.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct PreRequestStep {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    pub kind: PreRequestStepKind,
+    /// Only run the step when this variable is empty or undefined. Runs every time when blank.
+    pub if_empty: String,
+    /// HTTP request to send for send-request steps
+    pub request_id: String,
+    /// Chained value to write for set-variable steps
+    pub name: String,
+    /// Template rendered into the variable for set-variable steps
+    pub value: String,
+    /// Plugin name and action label for plugin-action steps
+    pub plugin: String,
+    pub action: String,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum AssertionKind {
+    #[default]
+    StatusEquals,
+    JsonPathEquals,
+    JsonPathMatches,
+    HeaderExists,
+    LatencyUnder,
+}
+
+/// A check run against every response a request receives
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct Assertion {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    pub kind: AssertionKind,
+    /// JSONPath expression for JSONPath assertions, or the header name for header assertions
+    pub path: String,
+    /// Expected status or value, a regular expression for matches, or a latency in milliseconds
+    pub value: String,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct AssertionResult {
+    /// ID of the assertion that produced this result
+    #[ts(optional, as = "Option<String>")]
+    pub assertion_id: Option<String>,
+    /// What was checked, like `status == 200`
+    pub name: String,
+    pub passed: bool,
+    /// The value that was seen, or why the check couldn't run
+    pub message: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -1114,6 +1686,8 @@ pub struct HttpRequest {
     pub body: BTreeMap<String, Value>,
     pub body_type: Option<String>,
     pub description: String,
+    /// Labels used to group requests across folders (eg. `smoke-test`)
+    pub tags: Vec<String>,
     pub headers: Vec<HttpRequestHeader>,
     #[serde(default = "default_http_method")]
     pub method: String,
@@ -1122,11 +1696,32 @@ pub struct HttpRequest {
     pub url: String,
     /// URL parameters used for both path placeholders (`:id`) and query string entries.
     pub url_parameters: Vec<HttpUrlParameter>,
+    /// Path of a Unix domain socket to send the request over. The URL is still used for the
+    /// Host header and path.
+    pub unix_socket: Option<String>,
     pub setting_send_cookies: InheritedBoolSetting,
     pub setting_store_cookies: InheritedBoolSetting,
     pub setting_validate_certificates: InheritedBoolSetting,
     pub setting_follow_redirects: InheritedBoolSetting,
+    pub setting_max_redirects: InheritedIntSetting,
+    pub setting_redirect_resend_body: InheritedBoolSetting,
+    pub setting_redirect_strip_authorization: InheritedBoolSetting,
+    pub setting_decompress_response: InheritedBoolSetting,
     pub setting_request_timeout: InheritedIntSetting,
+    pub setting_connect_timeout: InheritedIntSetting,
+    pub setting_read_timeout: InheritedIntSetting,
+    pub setting_http_version: InheritedHttpVersionSetting,
+    pub setting_retry_policy: InheritedRetryPolicySetting,
+    /// JSONPath or XPath expression last used to filter this request's responses
+    pub response_filter: Option<String>,
+    /// Values copied from each response into variables after the request is sent
+    pub response_extractions: Vec<ResponseExtraction>,
+    /// Steps that run before the request is sent
+    pub pre_request_steps: Vec<PreRequestStep>,
+    /// Checks run against each response after the request is sent
+    pub assertions: Vec<Assertion>,
+    /// Set when the request has been moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
 impl UpsertModelInfo for HttpRequest {
@@ -1161,8 +1756,10 @@ impl UpsertModelInfo for HttpRequest {
             (FolderId, self.folder_id.into()),
             (Name, self.name.trim().into()),
             (Description, self.description.into()),
+            (Tags, serde_json::to_string(&self.tags)?.into()),
             (Url, self.url.into()),
             (UrlParameters, serde_json::to_string(&self.url_parameters)?.into()),
+            (UnixSocket, self.unix_socket.into()),
             (Method, self.method.into()),
             (Body, serde_json::to_string(&self.body)?.into()),
             (BodyType, self.body_type.into()),
@@ -1177,7 +1774,29 @@ impl UpsertModelInfo for HttpRequest {
                 serde_json::to_string(&self.setting_validate_certificates)?.into(),
             ),
             (SettingFollowRedirects, serde_json::to_string(&self.setting_follow_redirects)?.into()),
+            (SettingMaxRedirects, serde_json::to_string(&self.setting_max_redirects)?.into()),
+            (
+                SettingRedirectResendBody,
+                serde_json::to_string(&self.setting_redirect_resend_body)?.into(),
+            ),
+            (
+                SettingRedirectStripAuthorization,
+                serde_json::to_string(&self.setting_redirect_strip_authorization)?.into(),
+            ),
+            (
+                SettingDecompressResponse,
+                serde_json::to_string(&self.setting_decompress_response)?.into(),
+            ),
             (SettingRequestTimeout, serde_json::to_string(&self.setting_request_timeout)?.into()),
+            (SettingConnectTimeout, serde_json::to_string(&self.setting_connect_timeout)?.into()),
+            (SettingReadTimeout, serde_json::to_string(&self.setting_read_timeout)?.into()),
+            (SettingHttpVersion, serde_json::to_string(&self.setting_http_version)?.into()),
+            (SettingRetryPolicy, serde_json::to_string(&self.setting_retry_policy)?.into()),
+            (ResponseFilter, self.response_filter.into()),
+            (ResponseExtractions, serde_json::to_string(&self.response_extractions)?.into()),
+            (PreRequestSteps, serde_json::to_string(&self.pre_request_steps)?.into()),
+            (Assertions, serde_json::to_string(&self.assertions)?.into()),
+            (DeletedAt, self.deleted_at.into()),
         ])
     }
 
@@ -1187,6 +1806,7 @@ impl UpsertModelInfo for HttpRequest {
             WorkspaceId,
             Name,
             Description,
+            Tags,
             FolderId,
             Method,
             Headers,
@@ -1196,12 +1816,26 @@ impl UpsertModelInfo for HttpRequest {
             AuthenticationType,
             Url,
             UrlParameters,
+            UnixSocket,
             SortPriority,
             SettingSendCookies,
             SettingStoreCookies,
             SettingValidateCertificates,
             SettingFollowRedirects,
             SettingRequestTimeout,
+            SettingMaxRedirects,
+            SettingRedirectResendBody,
+            SettingRedirectStripAuthorization,
+            SettingDecompressResponse,
+            SettingConnectTimeout,
+            SettingReadTimeout,
+            SettingHttpVersion,
+            SettingRetryPolicy,
+            ResponseFilter,
+            ResponseExtractions,
+            PreRequestSteps,
+            Assertions,
+            DeletedAt,
         ]
     }
 
@@ -1210,11 +1844,24 @@ impl UpsertModelInfo for HttpRequest {
         let body: String = row.get("body")?;
         let authentication: String = row.get("authentication")?;
         let headers: String = row.get("headers")?;
+        let tags: String = row.get("tags")?;
         let setting_send_cookies: String = row.get("setting_send_cookies")?;
         let setting_store_cookies: String = row.get("setting_store_cookies")?;
         let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
         let setting_follow_redirects: String = row.get("setting_follow_redirects")?;
+        let setting_max_redirects: String = row.get("setting_max_redirects")?;
+        let setting_redirect_resend_body: String = row.get("setting_redirect_resend_body")?;
+        let setting_redirect_strip_authorization: String =
+            row.get("setting_redirect_strip_authorization")?;
+        let setting_decompress_response: String = row.get("setting_decompress_response")?;
         let setting_request_timeout: String = row.get("setting_request_timeout")?;
+        let setting_connect_timeout: String = row.get("setting_connect_timeout")?;
+        let setting_read_timeout: String = row.get("setting_read_timeout")?;
+        let setting_http_version: String = row.get("setting_http_version")?;
+        let setting_retry_policy: String = row.get("setting_retry_policy")?;
+        let response_extractions: String = row.get("response_extractions")?;
+        let pre_request_steps: String = row.get("pre_request_steps")?;
+        let assertions: String = row.get("assertions")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -1226,6 +1873,7 @@ impl UpsertModelInfo for HttpRequest {
             body: serde_json::from_str(body.as_str()).unwrap_or_default(),
             body_type: row.get("body_type")?,
             description: row.get("description")?,
+            tags: serde_json::from_str(tags.as_str()).unwrap_or_default(),
             folder_id: row.get("folder_id")?,
             headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
             method: row.get("method")?,
@@ -1233,14 +1881,34 @@ impl UpsertModelInfo for HttpRequest {
             sort_priority: row.get("sort_priority")?,
             url: row.get("url")?,
             url_parameters: serde_json::from_str(url_parameters.as_str()).unwrap_or_default(),
+            unix_socket: row.get("unix_socket")?,
             setting_send_cookies: serde_json::from_str(&setting_send_cookies).unwrap_or_default(),
             setting_store_cookies: serde_json::from_str(&setting_store_cookies).unwrap_or_default(),
             setting_validate_certificates: serde_json::from_str(&setting_validate_certificates)
                 .unwrap_or_default(),
             setting_follow_redirects: serde_json::from_str(&setting_follow_redirects)
                 .unwrap_or_default(),
+            setting_max_redirects: serde_json::from_str(&setting_max_redirects).unwrap_or_default(),
+            setting_redirect_resend_body: serde_json::from_str(&setting_redirect_resend_body)
+                .unwrap_or_default(),
+            setting_redirect_strip_authorization: serde_json::from_str(
+                &setting_redirect_strip_authorization,
+            )
+            .unwrap_or_default(),
+            setting_decompress_response: serde_json::from_str(&setting_decompress_response)
+                .unwrap_or_default(),
             setting_request_timeout: serde_json::from_str(&setting_request_timeout)
                 .unwrap_or_default(),
+            setting_connect_timeout: serde_json::from_str(&setting_connect_timeout)
+                .unwrap_or_default(),
+            setting_read_timeout: serde_json::from_str(&setting_read_timeout).unwrap_or_default(),
+            setting_http_version: serde_json::from_str(&setting_http_version).unwrap_or_default(),
+            setting_retry_policy: serde_json::from_str(&setting_retry_policy).unwrap_or_default(),
+            response_filter: row.get("response_filter")?,
+            response_extractions: serde_json::from_str(&response_extractions).unwrap_or_default(),
+            pre_request_steps: serde_json::from_str(&pre_request_steps).unwrap_or_default(),
+            assertions: serde_json::from_str(&assertions).unwrap_or_default(),
+            deleted_at: row.get("deleted_at")?,
         })
     }
 }
@@ -1390,6 +2058,10 @@ pub struct WebsocketRequest {
     pub description: String,
     pub headers: Vec<HttpRequestHeader>,
     pub message: String,
+    /// How the message is sent, as text or as the bytes it describes
+    pub message_format: WebsocketMessageFormat,
+    /// File whose contents are sent as a binary frame when `message_format` is `file`
+    pub message_file: Option<String>,
     pub name: String,
     pub sort_priority: f64,
     pub url: String,
@@ -1398,28 +2070,121 @@ pub struct WebsocketRequest {
     pub setting_send_cookies: InheritedBoolSetting,
     pub setting_store_cookies: InheritedBoolSetting,
     pub setting_validate_certificates: InheritedBoolSetting,
+    /// Re-establishes the connection when it drops without being closed from the app
+    pub reconnect: WebsocketReconnectPolicy,
+    /// Protocol spoken over the connection
+    pub protocol: WebsocketProtocol,
+    /// Used when `protocol` is `socket_io`
+    pub socket_io: SocketIoSettings,
+    /// Set when the request has been moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
 }
 
-impl UpsertModelInfo for WebsocketRequest {
-    fn table_name() -> impl IntoTableRef + IntoIden {
-        WebsocketRequestIden::Table
-    }
+/// How a dropped WebSocket connection is re-established. Attempts are spaced by a delay that
+/// doubles after each one, up to `max_delay_ms`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct WebsocketReconnectPolicy {
+    pub enabled: bool,
+    /// Attempts to make before giving up, or 0 to keep trying
+    pub max_attempts: u32,
+    /// Delay before the first attempt
+    pub initial_delay_ms: u32,
+    /// Longest delay between attempts
+    pub max_delay_ms: u32,
+    /// Message sent after connecting and again after every reconnect, such as a subscription or
+    /// login message. Nothing is sent when empty.
+    pub handshake_message: String,
+}
 
-    fn id_column() -> impl IntoIden + Eq + Clone {
-        WebsocketRequestIden::Id
+impl Default for WebsocketReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 5,
+            initial_delay_ms: 1000,
+            max_delay_ms: 30_000,
+            handshake_message: String::new(),
+        }
     }
+}
 
-    fn generate_id() -> String {
-        generate_prefixed_id("wr")
-    }
+/// Protocol spoken over a WebSocket connection
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum WebsocketProtocol {
+    /// Plain WebSocket frames
+    #[default]
+    Websocket,
+    /// Socket.IO over Engine.IO v4. The handshake, namespace connection, and pings are handled
+    /// automatically, and messages are sent as events.
+    SocketIo,
+}
 
-    fn order_by() -> (impl IntoColumnRef, Order) {
-        (WebsocketRequestIden::CreatedAt, Desc)
-    }
+/// How messages are sent to a Socket.IO server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct SocketIoSettings {
+    /// Namespace to connect to, like `/chat`
+    pub namespace: String,
+    /// Name of the event the message is emitted as
+    pub event: String,
+    /// Ask the server to acknowledge each emitted event
+    pub ack: bool,
+    /// JSON payload sent when connecting to the namespace, often used for auth tokens
+    pub auth: String,
+}
 
-    fn get_id(&self) -> String {
-        self.id.clone()
-    }
+impl Default for SocketIoSettings {
+    fn default() -> Self {
+        Self {
+            namespace: "/".to_string(),
+            event: "message".to_string(),
+            ack: false,
+            auth: String::new(),
+        }
+    }
+}
+
+/// Kind of frame a WebSocket message is sent as
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum WebsocketMessageFormat {
+    /// A text frame
+    #[default]
+    Text,
+    /// A binary frame with the bytes written as hex, ignoring whitespace
+    Hex,
+    /// A binary frame with the bytes written as base64
+    Base64,
+    /// A binary frame with the contents of `message_file`
+    File,
+}
+
+impl UpsertModelInfo for WebsocketRequest {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        WebsocketRequestIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        WebsocketRequestIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("wr")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (WebsocketRequestIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
 
     fn insert_values(
         self,
@@ -1436,6 +2201,8 @@ impl UpsertModelInfo for WebsocketRequest {
             (Description, self.description.into()),
             (Headers, serde_json::to_string(&self.headers)?.into()),
             (Message, self.message.into()),
+            (MessageFormat, serde_json::to_value(&self.message_format)?.as_str().into()),
+            (MessageFile, self.message_file.into()),
             (Name, self.name.trim().into()),
             (SortPriority, self.sort_priority.into()),
             (Url, self.url.into()),
@@ -1446,6 +2213,10 @@ impl UpsertModelInfo for WebsocketRequest {
                 SettingValidateCertificates,
                 serde_json::to_string(&self.setting_validate_certificates)?.into(),
             ),
+            (Reconnect, serde_json::to_string(&self.reconnect)?.into()),
+            (Protocol, serde_json::to_value(&self.protocol)?.as_str().into()),
+            (SocketIo, serde_json::to_string(&self.socket_io)?.into()),
+            (DeletedAt, self.deleted_at.into()),
         ])
     }
 
@@ -1459,6 +2230,8 @@ impl UpsertModelInfo for WebsocketRequest {
             WebsocketRequestIden::Description,
             WebsocketRequestIden::Headers,
             WebsocketRequestIden::Message,
+            WebsocketRequestIden::MessageFormat,
+            WebsocketRequestIden::MessageFile,
             WebsocketRequestIden::Name,
             WebsocketRequestIden::SortPriority,
             WebsocketRequestIden::Url,
@@ -1466,6 +2239,10 @@ impl UpsertModelInfo for WebsocketRequest {
             WebsocketRequestIden::SettingSendCookies,
             WebsocketRequestIden::SettingStoreCookies,
             WebsocketRequestIden::SettingValidateCertificates,
+            WebsocketRequestIden::Reconnect,
+            WebsocketRequestIden::Protocol,
+            WebsocketRequestIden::SocketIo,
+            WebsocketRequestIden::DeletedAt,
         ]
     }
 
@@ -1479,6 +2256,10 @@ impl UpsertModelInfo for WebsocketRequest {
         let setting_send_cookies: String = row.get("setting_send_cookies")?;
         let setting_store_cookies: String = row.get("setting_store_cookies")?;
         let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
+        let reconnect: String = row.get("reconnect")?;
+        let message_format: String = row.get("message_format")?;
+        let protocol: String = row.get("protocol")?;
+        let socket_io: String = row.get("socket_io")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -1489,6 +2270,9 @@ impl UpsertModelInfo for WebsocketRequest {
             url: row.get("url")?,
             url_parameters: serde_json::from_str(url_parameters.as_str()).unwrap_or_default(),
             message: row.get("message")?,
+            message_format: serde_json::from_str(&format!(r#""{message_format}""#))
+                .unwrap_or_default(),
+            message_file: row.get("message_file")?,
             description: row.get("description")?,
             authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
             authentication_type: row.get("authentication_type")?,
@@ -1499,6 +2283,10 @@ impl UpsertModelInfo for WebsocketRequest {
             setting_store_cookies: serde_json::from_str(&setting_store_cookies).unwrap_or_default(),
             setting_validate_certificates: serde_json::from_str(&setting_validate_certificates)
                 .unwrap_or_default(),
+            reconnect: serde_json::from_str(&reconnect).unwrap_or_default(),
+            protocol: serde_json::from_str(&format!(r#""{protocol}""#)).unwrap_or_default(),
+            socket_io: serde_json::from_str(&socket_io).unwrap_or_default(),
+            deleted_at: row.get("deleted_at")?,
         })
     }
 }
@@ -1513,6 +2301,8 @@ pub enum WebsocketEventType {
     Open,
     Ping,
     Pong,
+    /// A reconnect attempt, its failure, or its success, described by the event's message
+    Reconnect,
     Text,
 }
 
@@ -1608,24 +2398,196 @@ impl UpsertModelInfo for WebsocketEvent {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+/// Delivery guarantee of an MQTT publish or subscription
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum MqttQos {
+    /// QoS 0, delivered at most once with no acknowledgement
+    #[default]
+    AtMostOnce,
+    /// QoS 1, acknowledged and possibly delivered more than once
+    AtLeastOnce,
+    /// QoS 2, delivered exactly once
+    ExactlyOnce,
+}
+
+/// A topic filter subscribed to when an MQTT connection is established
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-pub struct HttpResponseHeader {
+pub struct MqttSubscription {
+    #[serde(default = "default_true")]
+    #[ts(optional, as = "Option<bool>")]
+    pub enabled: bool,
+    /// Topic filter, which may contain `+` and `#` wildcards
+    pub topic: String,
+    pub qos: MqttQos,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "mqtt_requests")]
+pub struct MqttRequest {
+    #[ts(type = "\"mqtt_request\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub folder_id: Option<String>,
+
+    pub description: String,
     pub name: String,
-    pub value: String,
+    pub sort_priority: f64,
+    /// Broker URL, using the `mqtt`, `mqtts`, `ws` or `wss` scheme
+    pub url: String,
+    /// Identifies the client to the broker. A random one is used when empty
+    pub client_id: String,
+    pub username: String,
+    pub password: String,
+    /// Seconds between keep-alive pings, or the broker default of 60 when zero
+    pub keep_alive: i32,
+    /// Ask the broker to keep subscriptions and queued messages between connections
+    pub persistent_session: bool,
+    pub subscriptions: Vec<MqttSubscription>,
+    /// Topic messages are published to
+    pub topic: String,
+    pub payload: String,
+    pub qos: MqttQos,
+    /// Ask the broker to keep the message for future subscribers to the topic
+    pub retain: bool,
+    pub setting_validate_certificates: InheritedBoolSetting,
+    /// Set when the request has been moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl UpsertModelInfo for MqttRequest {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        MqttRequestIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        MqttRequestIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("mq")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (MqttRequestIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use MqttRequestIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (FolderId, self.folder_id.as_ref().map(|s| s.as_str()).into()),
+            (Description, self.description.into()),
+            (Name, self.name.trim().into()),
+            (SortPriority, self.sort_priority.into()),
+            (Url, self.url.into()),
+            (ClientId, self.client_id.into()),
+            (Username, self.username.into()),
+            (Password, self.password.into()),
+            (KeepAlive, self.keep_alive.into()),
+            (PersistentSession, self.persistent_session.into()),
+            (Subscriptions, serde_json::to_string(&self.subscriptions)?.into()),
+            (Topic, self.topic.into()),
+            (Payload, self.payload.into()),
+            (Qos, serde_json::to_value(&self.qos)?.as_str().into()),
+            (Retain, self.retain.into()),
+            (
+                SettingValidateCertificates,
+                serde_json::to_string(&self.setting_validate_certificates)?.into(),
+            ),
+            (DeletedAt, self.deleted_at.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            MqttRequestIden::UpdatedAt,
+            MqttRequestIden::WorkspaceId,
+            MqttRequestIden::FolderId,
+            MqttRequestIden::Description,
+            MqttRequestIden::Name,
+            MqttRequestIden::SortPriority,
+            MqttRequestIden::Url,
+            MqttRequestIden::ClientId,
+            MqttRequestIden::Username,
+            MqttRequestIden::Password,
+            MqttRequestIden::KeepAlive,
+            MqttRequestIden::PersistentSession,
+            MqttRequestIden::Subscriptions,
+            MqttRequestIden::Topic,
+            MqttRequestIden::Payload,
+            MqttRequestIden::Qos,
+            MqttRequestIden::Retain,
+            MqttRequestIden::SettingValidateCertificates,
+            MqttRequestIden::DeletedAt,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let subscriptions: String = row.get("subscriptions")?;
+        let qos: String = row.get("qos")?;
+        let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            sort_priority: row.get("sort_priority")?,
+            workspace_id: row.get("workspace_id")?,
+            folder_id: row.get("folder_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            description: row.get("description")?,
+            name: row.get("name")?,
+            url: row.get("url")?,
+            client_id: row.get("client_id")?,
+            username: row.get("username")?,
+            password: row.get("password")?,
+            keep_alive: row.get("keep_alive")?,
+            persistent_session: row.get("persistent_session")?,
+            subscriptions: serde_json::from_str(&subscriptions).unwrap_or_default(),
+            topic: row.get("topic")?,
+            payload: row.get("payload")?,
+            qos: serde_json::from_str(&format!(r#""{qos}""#)).unwrap_or_default(),
+            retain: row.get("retain")?,
+            setting_validate_certificates: serde_json::from_str(&setting_validate_certificates)
+                .unwrap_or_default(),
+            deleted_at: row.get("deleted_at")?,
+        })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
-pub enum HttpResponseState {
+pub enum MqttConnectionState {
     Initialized,
     Connected,
+    Closing,
     Closed,
 }
 
-impl Default for HttpResponseState {
+impl Default for MqttConnectionState {
     fn default() -> Self {
         Self::Initialized
     }
@@ -1634,9 +2596,9 @@ impl Default for HttpResponseState {
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "http_responses")]
-pub struct HttpResponse {
-    #[ts(type = "\"http_response\"")]
+#[enum_def(table_name = "mqtt_connections")]
+pub struct MqttConnection {
+    #[ts(type = "\"mqtt_connection\"")]
     pub model: String,
     pub id: String,
     pub created_at: NaiveDateTime,
@@ -1644,39 +2606,29 @@ pub struct HttpResponse {
     pub workspace_id: String,
     pub request_id: String,
 
-    pub body_path: Option<String>,
-    pub content_length: Option<i32>,
-    pub content_length_compressed: Option<i32>,
     pub elapsed: i32,
-    pub elapsed_headers: i32,
-    pub elapsed_dns: i32,
     pub error: Option<String>,
-    pub headers: Vec<HttpResponseHeader>,
-    pub remote_addr: Option<String>,
-    pub request_content_length: Option<i32>,
-    pub request_headers: Vec<HttpResponseHeader>,
-    pub status: i32,
-    pub status_reason: Option<String>,
-    pub state: HttpResponseState,
+    pub state: MqttConnectionState,
     pub url: String,
-    pub version: Option<String>,
+    /// Client ID the connection was made with
+    pub client_id: String,
 }
 
-impl UpsertModelInfo for HttpResponse {
+impl UpsertModelInfo for MqttConnection {
     fn table_name() -> impl IntoTableRef + IntoIden {
-        HttpResponseIden::Table
+        MqttConnectionIden::Table
     }
 
     fn id_column() -> impl IntoIden + Eq + Clone {
-        HttpResponseIden::Id
+        MqttConnectionIden::Id
     }
 
     fn generate_id() -> String {
-        generate_prefixed_id("rs")
+        generate_prefixed_id("mc")
     }
 
     fn order_by() -> (impl IntoColumnRef, Order) {
-        (HttpResponseIden::CreatedAt, Desc)
+        (MqttConnectionIden::CreatedAt, Desc)
     }
 
     fn get_id(&self) -> String {
@@ -1687,122 +2639,727 @@ impl UpsertModelInfo for HttpResponse {
         self,
         source: &UpdateSource,
     ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use HttpResponseIden::*;
+        use MqttConnectionIden::*;
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
-            (RequestId, self.request_id.into()),
             (WorkspaceId, self.workspace_id.into()),
-            (BodyPath, self.body_path.into()),
-            (ContentLength, self.content_length.into()),
-            (ContentLengthCompressed, self.content_length_compressed.into()),
+            (RequestId, self.request_id.into()),
             (Elapsed, self.elapsed.into()),
-            (ElapsedHeaders, self.elapsed_headers.into()),
-            (ElapsedDns, self.elapsed_dns.into()),
             (Error, self.error.into()),
-            (Headers, serde_json::to_string(&self.headers)?.into()),
-            (RemoteAddr, self.remote_addr.into()),
-            (RequestHeaders, serde_json::to_string(&self.request_headers)?.into()),
-            (State, serde_json::to_value(self.state)?.as_str().into()),
-            (Status, self.status.into()),
-            (StatusReason, self.status_reason.into()),
+            (State, serde_json::to_value(&self.state)?.as_str().into()),
             (Url, self.url.into()),
-            (Version, self.version.into()),
-            (RequestContentLength, self.request_content_length.into()),
+            (ClientId, self.client_id.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
-            HttpResponseIden::UpdatedAt,
-            HttpResponseIden::BodyPath,
-            HttpResponseIden::ContentLength,
-            HttpResponseIden::ContentLengthCompressed,
-            HttpResponseIden::Elapsed,
-            HttpResponseIden::ElapsedHeaders,
-            HttpResponseIden::ElapsedDns,
-            HttpResponseIden::Error,
-            HttpResponseIden::Headers,
-            HttpResponseIden::RemoteAddr,
-            HttpResponseIden::RequestContentLength,
-            HttpResponseIden::RequestHeaders,
-            HttpResponseIden::State,
-            HttpResponseIden::Status,
-            HttpResponseIden::StatusReason,
-            HttpResponseIden::Url,
-            HttpResponseIden::Version,
+            MqttConnectionIden::UpdatedAt,
+            MqttConnectionIden::Elapsed,
+            MqttConnectionIden::Error,
+            MqttConnectionIden::State,
+            MqttConnectionIden::Url,
+            MqttConnectionIden::ClientId,
         ]
     }
 
-    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
     where
         Self: Sized,
     {
-        let headers: String = r.get("headers")?;
-        let state: String = r.get("state")?;
+        let state: String = row.get("state")?;
         Ok(Self {
-            id: r.get("id")?,
-            model: r.get("model")?,
-            workspace_id: r.get("workspace_id")?,
-            request_id: r.get("request_id")?,
-            created_at: r.get("created_at")?,
-            updated_at: r.get("updated_at")?,
-            error: r.get("error")?,
-            url: r.get("url")?,
-            content_length: r.get("content_length")?,
-            content_length_compressed: r.get("content_length_compressed").unwrap_or_default(),
-            version: r.get("version")?,
-            elapsed: r.get("elapsed")?,
-            elapsed_headers: r.get("elapsed_headers")?,
-            elapsed_dns: r.get("elapsed_dns").unwrap_or_default(),
-            remote_addr: r.get("remote_addr")?,
-            status: r.get("status")?,
-            status_reason: r.get("status_reason")?,
-            state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
-            body_path: r.get("body_path")?,
-            headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
-            request_content_length: r.get("request_content_length").unwrap_or_default(),
-            request_headers: serde_json::from_str(
-                r.get::<_, String>("request_headers").unwrap_or_default().as_str(),
-            )
-            .unwrap_or_default(),
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            request_id: row.get("request_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            url: row.get("url")?,
+            client_id: row.get("client_id")?,
+            elapsed: row.get("elapsed")?,
+            error: row.get("error")?,
+            state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap_or_default(),
         })
     }
 }
 
-/// Serializable representation of HTTP response events for DB storage.
-/// This mirrors `yaak_http::sender::HttpResponseEvent` but with serde support.
-/// The `From` impl is in yaak-http to avoid circular dependencies.
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
-#[serde(tag = "type", rename_all = "snake_case")]
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, TS)]
+#[serde(rename_all = "snake_case")]
 #[ts(export, export_to = "gen_models.ts")]
-pub enum HttpResponseEventData {
-    Setting {
-        name: String,
-        value: String,
-        #[serde(default, skip_serializing_if = "Option::is_none")]
-        #[ts(optional, as = "Option<String>")]
-        source_model: Option<String>,
-        #[serde(default, skip_serializing_if = "Option::is_none")]
-        #[ts(optional, as = "Option<String>")]
-        source_id: Option<String>,
-        #[serde(default, skip_serializing_if = "Option::is_none")]
-        #[ts(optional, as = "Option<String>")]
-        source_name: Option<String>,
-    },
-    Info {
-        message: String,
-    },
-    Redirect {
-        url: String,
-        status: u16,
-        behavior: String,
-        #[serde(default)]
-        dropped_body: bool,
-        #[serde(default)]
-        dropped_headers: Vec<String>,
-    },
-    SendUrl {
+pub enum MqttEventType {
+    Connect,
+    Disconnect,
+    Subscribe,
+    Unsubscribe,
+    Publish,
+    Error,
+}
+
+impl Default for MqttEventType {
+    fn default() -> Self {
+        Self::Publish
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "mqtt_events")]
+pub struct MqttEvent {
+    #[ts(type = "\"mqtt_event\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub request_id: String,
+    pub connection_id: String,
+    pub is_server: bool,
+
+    pub event_type: MqttEventType,
+    /// Topic a message was published to, or the topic filter of a subscription
+    pub topic: String,
+    pub message: Vec<u8>,
+    pub qos: MqttQos,
+    pub retain: bool,
+}
+
+impl UpsertModelInfo for MqttEvent {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        MqttEventIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        MqttEventIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("me")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (MqttEventIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use MqttEventIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (ConnectionId, self.connection_id.into()),
+            (RequestId, self.request_id.into()),
+            (IsServer, self.is_server.into()),
+            (EventType, serde_json::to_value(&self.event_type)?.as_str().into()),
+            (Topic, self.topic.into()),
+            (Message, self.message.into()),
+            (Qos, serde_json::to_value(&self.qos)?.as_str().into()),
+            (Retain, self.retain.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            MqttEventIden::UpdatedAt,
+            MqttEventIden::IsServer,
+            MqttEventIden::EventType,
+            MqttEventIden::Topic,
+            MqttEventIden::Message,
+            MqttEventIden::Qos,
+            MqttEventIden::Retain,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let event_type: String = row.get("event_type")?;
+        let qos: String = row.get("qos")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            request_id: row.get("request_id")?,
+            connection_id: row.get("connection_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            is_server: row.get("is_server")?,
+            event_type: serde_json::from_str(&format!(r#""{event_type}""#)).unwrap_or_default(),
+            topic: row.get("topic")?,
+            message: row.get("message")?,
+            qos: serde_json::from_str(&format!(r#""{qos}""#)).unwrap_or_default(),
+            retain: row.get("retain")?,
+        })
+    }
+}
+
+/// Transport a raw socket request is sent over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum SocketProtocol {
+    #[default]
+    Tcp,
+    Udp,
+}
+
+/// How the payload of a raw socket request is turned into bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum SocketPayloadFormat {
+    /// Sent as UTF-8 text
+    #[default]
+    Text,
+    /// Hex-encoded bytes, ignoring whitespace
+    Hex,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "socket_requests")]
+pub struct SocketRequest {
+    #[ts(type = "\"socket_request\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub folder_id: Option<String>,
+
+    pub description: String,
+    pub name: String,
+    pub sort_priority: f64,
+    pub protocol: SocketProtocol,
+    /// Address to send to, as `host:port`
+    pub url: String,
+    pub payload: String,
+    pub payload_format: SocketPayloadFormat,
+    /// Milliseconds to keep waiting for more response data after the last read, or the default
+    /// when zero
+    pub read_timeout: i32,
+    /// Set when the request has been moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+impl UpsertModelInfo for SocketRequest {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        SocketRequestIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        SocketRequestIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("sk")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (SocketRequestIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use SocketRequestIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (FolderId, self.folder_id.as_ref().map(|s| s.as_str()).into()),
+            (Description, self.description.into()),
+            (Name, self.name.trim().into()),
+            (SortPriority, self.sort_priority.into()),
+            (Protocol, serde_json::to_value(&self.protocol)?.as_str().into()),
+            (Url, self.url.into()),
+            (Payload, self.payload.into()),
+            (PayloadFormat, serde_json::to_value(&self.payload_format)?.as_str().into()),
+            (ReadTimeout, self.read_timeout.into()),
+            (DeletedAt, self.deleted_at.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            SocketRequestIden::UpdatedAt,
+            SocketRequestIden::WorkspaceId,
+            SocketRequestIden::FolderId,
+            SocketRequestIden::Description,
+            SocketRequestIden::Name,
+            SocketRequestIden::SortPriority,
+            SocketRequestIden::Protocol,
+            SocketRequestIden::Url,
+            SocketRequestIden::Payload,
+            SocketRequestIden::PayloadFormat,
+            SocketRequestIden::ReadTimeout,
+            SocketRequestIden::DeletedAt,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let protocol: String = row.get("protocol")?;
+        let payload_format: String = row.get("payload_format")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            sort_priority: row.get("sort_priority")?,
+            workspace_id: row.get("workspace_id")?,
+            folder_id: row.get("folder_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            description: row.get("description")?,
+            name: row.get("name")?,
+            protocol: serde_json::from_str(&format!(r#""{protocol}""#)).unwrap_or_default(),
+            url: row.get("url")?,
+            payload: row.get("payload")?,
+            payload_format: serde_json::from_str(&format!(r#""{payload_format}""#))
+                .unwrap_or_default(),
+            read_timeout: row.get("read_timeout")?,
+            deleted_at: row.get("deleted_at")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum SocketResponseState {
+    Initialized,
+    Closed,
+}
+
+impl Default for SocketResponseState {
+    fn default() -> Self {
+        Self::Initialized
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "socket_responses")]
+pub struct SocketResponse {
+    #[ts(type = "\"socket_response\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub request_id: String,
+
+    pub protocol: SocketProtocol,
+    pub url: String,
+    /// Resolved address the payload was sent to
+    pub remote_addr: Option<String>,
+    pub state: SocketResponseState,
+    pub error: Option<String>,
+    /// Milliseconds until the connection was established, or the datagram sent for UDP
+    pub elapsed_connect: i32,
+    /// Milliseconds until the first response bytes arrived, if any did
+    pub elapsed_first_byte: Option<i32>,
+    /// Milliseconds until the exchange finished
+    pub elapsed: i32,
+    pub bytes_sent: i32,
+    /// Every byte received, with UDP datagrams concatenated in arrival order
+    pub body: Vec<u8>,
+}
+
+impl UpsertModelInfo for SocketResponse {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        SocketResponseIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        SocketResponseIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("sr")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (SocketResponseIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use SocketResponseIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (RequestId, self.request_id.into()),
+            (Protocol, serde_json::to_value(&self.protocol)?.as_str().into()),
+            (Url, self.url.into()),
+            (RemoteAddr, self.remote_addr.into()),
+            (State, serde_json::to_value(&self.state)?.as_str().into()),
+            (Error, self.error.into()),
+            (ElapsedConnect, self.elapsed_connect.into()),
+            (ElapsedFirstByte, self.elapsed_first_byte.into()),
+            (Elapsed, self.elapsed.into()),
+            (BytesSent, self.bytes_sent.into()),
+            (Body, self.body.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            SocketResponseIden::UpdatedAt,
+            SocketResponseIden::RemoteAddr,
+            SocketResponseIden::State,
+            SocketResponseIden::Error,
+            SocketResponseIden::ElapsedConnect,
+            SocketResponseIden::ElapsedFirstByte,
+            SocketResponseIden::Elapsed,
+            SocketResponseIden::BytesSent,
+            SocketResponseIden::Body,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let protocol: String = row.get("protocol")?;
+        let state: String = row.get("state")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            request_id: row.get("request_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            protocol: serde_json::from_str(&format!(r#""{protocol}""#)).unwrap_or_default(),
+            url: row.get("url")?,
+            remote_addr: row.get("remote_addr")?,
+            state: serde_json::from_str(&format!(r#""{state}""#)).unwrap_or_default(),
+            error: row.get("error")?,
+            elapsed_connect: row.get("elapsed_connect")?,
+            elapsed_first_byte: row.get("elapsed_first_byte")?,
+            elapsed: row.get("elapsed")?,
+            bytes_sent: row.get("bytes_sent")?,
+            body: row.get("body")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseHeader {
+    pub name: String,
+    pub value: String,
+}
+
+/// A redirect response that was followed instead of being returned
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseRedirect {
+    /// URL of the request that was redirected
+    pub url: String,
+    pub method: String,
+    pub status: i32,
+    /// URL the redirect pointed to
+    pub location: String,
+}
+
+/// TLS details of the connection a response was received on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseTls {
+    /// Negotiated protocol version, like `TLSv1.3`. Unknown when certificates aren't validated
+    pub version: Option<String>,
+    /// Application protocol negotiated with ALPN, like `h2`
+    pub alpn: Option<String>,
+    /// Certificates the server presented, starting with its own
+    pub certificates: Vec<HttpResponseCertificate>,
+}
+
+/// A certificate the server presented during the TLS handshake
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct HttpResponseCertificate {
+    pub subject: String,
+    pub issuer: String,
+    /// DNS names and IP addresses the certificate is valid for
+    pub subject_alt_names: Vec<String>,
+    pub serial_number: String,
+    pub not_before: Option<NaiveDateTime>,
+    pub not_after: Option<NaiveDateTime>,
+    /// Hex-encoded SHA-256 digest of the DER certificate
+    pub sha256_fingerprint: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpResponseState {
+    Initialized,
+    Connected,
+    Closed,
+    /// Closed because a connect, read, or total timeout elapsed
+    TimedOut,
+}
+
+impl Default for HttpResponseState {
+    fn default() -> Self {
+        Self::Initialized
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "http_responses")]
+pub struct HttpResponse {
+    #[ts(type = "\"http_response\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub request_id: String,
+
+    pub body_path: Option<String>,
+    pub content_length: Option<i32>,
+    pub content_length_compressed: Option<i32>,
+    pub elapsed: i32,
+    pub elapsed_headers: i32,
+    pub elapsed_dns: i32,
+    /// Time to open the connection once DNS resolved, including the TCP connect, TLS
+    /// handshake, and any proxy setup. Zero when an existing connection was reused
+    pub elapsed_connect: i32,
+    /// Time to write a streamed request body after connecting. Buffered bodies are sent
+    /// along with the headers, so this is zero for them
+    pub elapsed_send: i32,
+    pub error: Option<String>,
+    pub headers: Vec<HttpResponseHeader>,
+    pub remote_addr: Option<String>,
+    pub request_content_length: Option<i32>,
+    pub request_headers: Vec<HttpResponseHeader>,
+    /// Request line and headers as written to the connection, once templates, authentication,
+    /// and inherited headers were applied. The body is stored separately
+    pub request_head: Option<String>,
+    pub status: i32,
+    pub status_reason: Option<String>,
+    pub state: HttpResponseState,
+    pub url: String,
+    pub version: Option<String>,
+    /// Outcome of the request's assertions, checked once the response completes
+    pub assertion_results: Vec<AssertionResult>,
+    /// Redirects followed on the way to this response, in the order they happened
+    pub redirects: Vec<HttpResponseRedirect>,
+    /// File the body was streamed to when sent in download mode. Such responses have no
+    /// `body_path`, so the file is left alone when the response is deleted
+    pub download_path: Option<String>,
+    /// TLS connection details, for responses received over HTTPS
+    pub tls: Option<HttpResponseTls>,
+}
+
+impl UpsertModelInfo for HttpResponse {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        HttpResponseIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        HttpResponseIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("rs")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (HttpResponseIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use HttpResponseIden::*;
+        let tls = match self.tls {
+            None => None,
+            Some(t) => Some(serde_json::to_string(&t)?),
+        };
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (RequestId, self.request_id.into()),
+            (WorkspaceId, self.workspace_id.into()),
+            (BodyPath, self.body_path.into()),
+            (ContentLength, self.content_length.into()),
+            (ContentLengthCompressed, self.content_length_compressed.into()),
+            (Elapsed, self.elapsed.into()),
+            (ElapsedHeaders, self.elapsed_headers.into()),
+            (ElapsedDns, self.elapsed_dns.into()),
+            (ElapsedConnect, self.elapsed_connect.into()),
+            (ElapsedSend, self.elapsed_send.into()),
+            (Error, self.error.into()),
+            (Headers, serde_json::to_string(&self.headers)?.into()),
+            (RemoteAddr, self.remote_addr.into()),
+            (RequestHeaders, serde_json::to_string(&self.request_headers)?.into()),
+            (RequestHead, self.request_head.into()),
+            (State, serde_json::to_value(self.state)?.as_str().into()),
+            (Status, self.status.into()),
+            (StatusReason, self.status_reason.into()),
+            (Url, self.url.into()),
+            (Version, self.version.into()),
+            (RequestContentLength, self.request_content_length.into()),
+            (AssertionResults, serde_json::to_string(&self.assertion_results)?.into()),
+            (Redirects, serde_json::to_string(&self.redirects)?.into()),
+            (DownloadPath, self.download_path.into()),
+            (Tls, tls.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            HttpResponseIden::UpdatedAt,
+            HttpResponseIden::BodyPath,
+            HttpResponseIden::ContentLength,
+            HttpResponseIden::ContentLengthCompressed,
+            HttpResponseIden::Elapsed,
+            HttpResponseIden::ElapsedHeaders,
+            HttpResponseIden::ElapsedDns,
+            HttpResponseIden::ElapsedConnect,
+            HttpResponseIden::ElapsedSend,
+            HttpResponseIden::Error,
+            HttpResponseIden::Headers,
+            HttpResponseIden::RemoteAddr,
+            HttpResponseIden::RequestContentLength,
+            HttpResponseIden::RequestHeaders,
+            HttpResponseIden::RequestHead,
+            HttpResponseIden::State,
+            HttpResponseIden::Status,
+            HttpResponseIden::StatusReason,
+            HttpResponseIden::Url,
+            HttpResponseIden::Version,
+            HttpResponseIden::AssertionResults,
+            HttpResponseIden::Redirects,
+            HttpResponseIden::DownloadPath,
+            HttpResponseIden::Tls,
+        ]
+    }
+
+    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let headers: String = r.get("headers")?;
+        let state: String = r.get("state")?;
+        Ok(Self {
+            id: r.get("id")?,
+            model: r.get("model")?,
+            workspace_id: r.get("workspace_id")?,
+            request_id: r.get("request_id")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            error: r.get("error")?,
+            url: r.get("url")?,
+            content_length: r.get("content_length")?,
+            content_length_compressed: r.get("content_length_compressed").unwrap_or_default(),
+            version: r.get("version")?,
+            elapsed: r.get("elapsed")?,
+            elapsed_headers: r.get("elapsed_headers")?,
+            elapsed_dns: r.get("elapsed_dns").unwrap_or_default(),
+            elapsed_connect: r.get("elapsed_connect").unwrap_or_default(),
+            elapsed_send: r.get("elapsed_send").unwrap_or_default(),
+            remote_addr: r.get("remote_addr")?,
+            status: r.get("status")?,
+            status_reason: r.get("status_reason")?,
+            state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap(),
+            body_path: r.get("body_path")?,
+            headers: serde_json::from_str(headers.as_str()).unwrap_or_default(),
+            request_content_length: r.get("request_content_length").unwrap_or_default(),
+            request_headers: serde_json::from_str(
+                r.get::<_, String>("request_headers").unwrap_or_default().as_str(),
+            )
+            .unwrap_or_default(),
+            request_head: r.get("request_head").unwrap_or_default(),
+            assertion_results: serde_json::from_str(
+                r.get::<_, String>("assertion_results").unwrap_or_default().as_str(),
+            )
+            .unwrap_or_default(),
+            redirects: serde_json::from_str(
+                r.get::<_, String>("redirects").unwrap_or_default().as_str(),
+            )
+            .unwrap_or_default(),
+            download_path: r.get("download_path").unwrap_or_default(),
+            tls: r
+                .get::<_, Option<String>>("tls")
+                .unwrap_or_default()
+                .and_then(|t| serde_json::from_str(&t).ok()),
+        })
+    }
+}
+
+/// Serializable representation of HTTP response events for DB storage.
+/// This mirrors `yaak_http::sender::HttpResponseEvent` but with serde support.
+/// The `From` impl is in yaak-http to avoid circular dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum HttpResponseEventData {
+    Setting {
+        name: String,
+        value: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional, as = "Option<String>")]
+        source_model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional, as = "Option<String>")]
+        source_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[ts(optional, as = "Option<String>")]
+        source_name: Option<String>,
+    },
+    Info {
+        message: String,
+    },
+    Redirect {
+        url: String,
+        status: u16,
+        behavior: String,
+        #[serde(default)]
+        dropped_body: bool,
+        #[serde(default)]
+        dropped_headers: Vec<String>,
+    },
+    SendUrl {
         method: String,
         #[serde(default)]
         scheme: String,
@@ -1844,44 +3401,506 @@ pub enum HttpResponseEventData {
         duration: u64,
         overridden: bool,
     },
+    Connected {
+        duration: u64,
+    },
+    ServerSentEvent {
+        event_type: String,
+        data: String,
+        id: Option<String>,
+        retry: Option<u64>,
+    },
+    Retry {
+        attempt: u32,
+        max_attempts: u32,
+        delay: u64,
+        reason: String,
+    },
+    DownloadProgress {
+        bytes: u64,
+        total: Option<u64>,
+    },
+    UploadProgress {
+        bytes: u64,
+        total: Option<u64>,
+    },
+}
+
+impl Default for HttpResponseEventData {
+    fn default() -> Self {
+        Self::Info { message: String::new() }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "http_response_events")]
+pub struct HttpResponseEvent {
+    #[ts(type = "\"http_response_event\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub response_id: String,
+    pub event: HttpResponseEventData,
+}
+
+impl UpsertModelInfo for HttpResponseEvent {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        HttpResponseEventIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        HttpResponseEventIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("re")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (HttpResponseEventIden::CreatedAt, Order::Asc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use HttpResponseEventIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (ResponseId, self.response_id.into()),
+            (Event, serde_json::to_string(&self.event)?.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            HttpResponseEventIden::UpdatedAt,
+            HttpResponseEventIden::Event,
+        ]
+    }
+
+    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let event: String = r.get("event")?;
+        Ok(Self {
+            id: r.get("id")?,
+            model: r.get("model")?,
+            workspace_id: r.get("workspace_id")?,
+            response_id: r.get("response_id")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            event: serde_json::from_str(&event).unwrap_or_default(),
+        })
+    }
+}
+
+impl HttpResponseEvent {
+    pub fn new(response_id: &str, workspace_id: &str, event: HttpResponseEventData) -> Self {
+        Self {
+            model: "http_response_event".to_string(),
+            id: Self::generate_id(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            workspace_id: workspace_id.to_string(),
+            response_id: response_id.to_string(),
+            event,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "graphql_introspections")]
+pub struct GraphQlIntrospection {
+    #[ts(type = "\"graphql_introspection\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub request_id: String,
+    pub url: Option<String>,
+    pub content: Option<String>,
+}
+
+impl UpsertModelInfo for GraphQlIntrospection {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        GraphQlIntrospectionIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        GraphQlIntrospectionIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("gi")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (GraphQlIntrospectionIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use GraphQlIntrospectionIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (WorkspaceId, self.workspace_id.into()),
+            (RequestId, self.request_id.into()),
+            (Url, self.url.into()),
+            (Content, self.content.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            GraphQlIntrospectionIden::UpdatedAt,
+            GraphQlIntrospectionIden::Url,
+            GraphQlIntrospectionIden::Content,
+        ]
+    }
+
+    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        Ok(Self {
+            id: r.get("id")?,
+            model: r.get("model")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            workspace_id: r.get("workspace_id")?,
+            request_id: r.get("request_id")?,
+            url: r.get("url")?,
+            content: r.get("content")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct GrpcMessageTemplate {
+    pub name: String,
+    pub message: String,
+    #[ts(optional, as = "Option<String>")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "grpc_requests")]
+pub struct GrpcRequest {
+    #[ts(type = "\"grpc_request\"")]
+    pub model: String,
+    pub id: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+    pub folder_id: Option<String>,
+
+    pub authentication_type: Option<String>,
+    #[ts(type = "Record<string, any>")]
+    pub authentication: BTreeMap<String, Value>,
+    pub description: String,
+    /// Labels used to group requests across folders (eg. `smoke-test`)
+    pub tags: Vec<String>,
+    pub message: String,
+    /// Named messages that can be replayed in order during a streaming session
+    pub message_templates: Vec<GrpcMessageTemplate>,
+    pub metadata: Vec<HttpRequestHeader>,
+    pub method: Option<String>,
+    pub name: String,
+    pub service: Option<String>,
+    pub sort_priority: f64,
+    /// Server URL (http for plaintext or https for secure)
+    pub url: String,
+    /// Wire protocol used to call the server
+    pub protocol: GrpcProtocol,
+    pub setting_validate_certificates: InheritedBoolSetting,
+    pub setting_request_timeout: InheritedIntSetting,
+    pub setting_connect_timeout: InheritedIntSetting,
+    pub setting_read_timeout: InheritedIntSetting,
+    pub setting_ca_certificates: InheritedCaCertificatesSetting,
+    pub setting_client_certificate: InheritedClientCertificateSetting,
+    /// Compression applied to messages sent to the server
+    pub send_compression: Option<GrpcCompression>,
+    /// Compression the server may apply to the messages it sends back
+    pub accept_compression: Vec<GrpcCompression>,
+    /// Steps that run before the request is sent
+    pub pre_request_steps: Vec<PreRequestStep>,
+    /// Set when the request has been moved to the trash.
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// Wire protocol for gRPC calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum GrpcProtocol {
+    /// Native gRPC over HTTP/2
+    #[default]
+    Grpc,
+    /// gRPC-Web over HTTP/1.1, for servers behind gateways like Envoy. Only supports unary and
+    /// server streaming methods.
+    GrpcWeb,
+    /// Connect with JSON messages. Only supports unary methods.
+    ConnectJson,
+    /// Connect with binary protobuf messages. Only supports unary methods.
+    ConnectProto,
+}
+
+/// Encoding used to compress gRPC messages
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum GrpcCompression {
+    Gzip,
+    Zstd,
+}
+
+impl UpsertModelInfo for GrpcRequest {
+    fn table_name() -> impl IntoTableRef + IntoIden {
+        GrpcRequestIden::Table
+    }
+
+    fn id_column() -> impl IntoIden + Eq + Clone {
+        GrpcRequestIden::Id
+    }
+
+    fn generate_id() -> String {
+        generate_prefixed_id("gr")
+    }
+
+    fn order_by() -> (impl IntoColumnRef, Order) {
+        (GrpcRequestIden::CreatedAt, Desc)
+    }
+
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn insert_values(
+        self,
+        source: &UpdateSource,
+    ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
+        use GrpcRequestIden::*;
+        Ok(vec![
+            (CreatedAt, upsert_date(source, self.created_at)),
+            (UpdatedAt, upsert_date(source, self.updated_at)),
+            (Name, self.name.trim().into()),
+            (Description, self.description.into()),
+            (Tags, serde_json::to_string(&self.tags)?.into()),
+            (WorkspaceId, self.workspace_id.into()),
+            (FolderId, self.folder_id.into()),
+            (SortPriority, self.sort_priority.into()),
+            (Url, self.url.into()),
+            (Service, self.service.into()),
+            (Method, self.method.into()),
+            (Message, self.message.into()),
+            (MessageTemplates, serde_json::to_string(&self.message_templates)?.into()),
+            (AuthenticationType, self.authentication_type.into()),
+            (Authentication, serde_json::to_string(&self.authentication)?.into()),
+            (Metadata, serde_json::to_string(&self.metadata)?.into()),
+            (
+                SettingValidateCertificates,
+                serde_json::to_string(&self.setting_validate_certificates)?.into(),
+            ),
+            (SettingRequestTimeout, serde_json::to_string(&self.setting_request_timeout)?.into()),
+            (SettingConnectTimeout, serde_json::to_string(&self.setting_connect_timeout)?.into()),
+            (SettingReadTimeout, serde_json::to_string(&self.setting_read_timeout)?.into()),
+            (SettingCaCertificates, serde_json::to_string(&self.setting_ca_certificates)?.into()),
+            (
+                SettingClientCertificate,
+                serde_json::to_string(&self.setting_client_certificate)?.into(),
+            ),
+            (Protocol, serde_json::to_value(&self.protocol)?.as_str().into()),
+            (SendCompression, serde_json::to_value(&self.send_compression)?.as_str().into()),
+            (AcceptCompression, serde_json::to_string(&self.accept_compression)?.into()),
+            (PreRequestSteps, serde_json::to_string(&self.pre_request_steps)?.into()),
+            (DeletedAt, self.deleted_at.into()),
+        ])
+    }
+
+    fn update_columns() -> Vec<impl IntoIden> {
+        vec![
+            GrpcRequestIden::UpdatedAt,
+            GrpcRequestIden::WorkspaceId,
+            GrpcRequestIden::Name,
+            GrpcRequestIden::Description,
+            GrpcRequestIden::Tags,
+            GrpcRequestIden::FolderId,
+            GrpcRequestIden::SortPriority,
+            GrpcRequestIden::Url,
+            GrpcRequestIden::Service,
+            GrpcRequestIden::Method,
+            GrpcRequestIden::Message,
+            GrpcRequestIden::MessageTemplates,
+            GrpcRequestIden::AuthenticationType,
+            GrpcRequestIden::Authentication,
+            GrpcRequestIden::Metadata,
+            GrpcRequestIden::SettingValidateCertificates,
+            GrpcRequestIden::SettingRequestTimeout,
+            GrpcRequestIden::SettingConnectTimeout,
+            GrpcRequestIden::SettingReadTimeout,
+            GrpcRequestIden::SettingCaCertificates,
+            GrpcRequestIden::SettingClientCertificate,
+            GrpcRequestIden::Protocol,
+            GrpcRequestIden::SendCompression,
+            GrpcRequestIden::AcceptCompression,
+            GrpcRequestIden::PreRequestSteps,
+            GrpcRequestIden::DeletedAt,
+        ]
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
+    where
+        Self: Sized,
+    {
+        let authentication: String = row.get("authentication")?;
+        let metadata: String = row.get("metadata")?;
+        let message_templates: String = row.get("message_templates")?;
+        let tags: String = row.get("tags")?;
+        let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
+        let setting_request_timeout: String = row.get("setting_request_timeout")?;
+        let setting_connect_timeout: String = row.get("setting_connect_timeout")?;
+        let setting_read_timeout: String = row.get("setting_read_timeout")?;
+        let setting_ca_certificates: String = row.get("setting_ca_certificates")?;
+        let setting_client_certificate: String = row.get("setting_client_certificate")?;
+        let protocol: String = row.get("protocol")?;
+        let send_compression: Option<String> = row.get("send_compression")?;
+        let accept_compression: String = row.get("accept_compression")?;
+        let pre_request_steps: String = row.get("pre_request_steps")?;
+        Ok(Self {
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            folder_id: row.get("folder_id")?,
+            name: row.get("name")?,
+            description: row.get("description")?,
+            tags: serde_json::from_str(tags.as_str()).unwrap_or_default(),
+            service: row.get("service")?,
+            method: row.get("method")?,
+            message: row.get("message")?,
+            message_templates: serde_json::from_str(message_templates.as_str()).unwrap_or_default(),
+            authentication_type: row.get("authentication_type")?,
+            authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
+            url: row.get("url")?,
+            sort_priority: row.get("sort_priority")?,
+            metadata: serde_json::from_str(metadata.as_str()).unwrap_or_default(),
+            protocol: serde_json::from_str(&format!(r#""{protocol}""#)).unwrap_or_default(),
+            setting_validate_certificates: serde_json::from_str(&setting_validate_certificates)
+                .unwrap_or_default(),
+            setting_request_timeout: serde_json::from_str(&setting_request_timeout)
+                .unwrap_or_default(),
+            setting_connect_timeout: serde_json::from_str(&setting_connect_timeout)
+                .unwrap_or_default(),
+            setting_read_timeout: serde_json::from_str(&setting_read_timeout).unwrap_or_default(),
+            setting_ca_certificates: serde_json::from_str(&setting_ca_certificates)
+                .unwrap_or_default(),
+            setting_client_certificate: serde_json::from_str(&setting_client_certificate)
+                .unwrap_or_default(),
+            send_compression: send_compression
+                .and_then(|c| serde_json::from_str(&format!(r#""{c}""#)).ok()),
+            accept_compression: serde_json::from_str(&accept_compression).unwrap_or_default(),
+            pre_request_steps: serde_json::from_str(&pre_request_steps).unwrap_or_default(),
+            deleted_at: row.get("deleted_at")?,
+        })
+    }
 }
 
-impl Default for HttpResponseEventData {
-    fn default() -> Self {
-        Self::Info { message: String::new() }
-    }
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_models.ts")]
+pub enum CollectionRunState {
+    #[default]
+    Running,
+    Completed,
+    /// Ended early because a request failed or the run was cancelled
+    Stopped,
+}
+
+/// Outcome of sending one request during a collection run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct CollectionRunResult {
+    /// Index of the data file row this result was sent with, or 0 without a data file
+    pub iteration: i32,
+    pub request_id: String,
+    pub request_name: String,
+    pub response_id: Option<String>,
+    pub status: i32,
+    pub elapsed: i32,
+    /// False when the request couldn't be sent, returned an error status, or failed an assertion
+    pub passed: bool,
+    pub error: Option<String>,
 }
 
+/// A single run of every HTTP request in a folder
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "http_response_events")]
-pub struct HttpResponseEvent {
-    #[ts(type = "\"http_response_event\"")]
+#[enum_def(table_name = "collection_runs")]
+pub struct CollectionRun {
+    #[ts(type = "\"collection_run\"")]
     pub model: String,
     pub id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub workspace_id: String,
-    pub response_id: String,
-    pub event: HttpResponseEventData,
+    pub folder_id: String,
+
+    pub state: CollectionRunState,
+    /// Maximum number of requests in flight at once
+    pub concurrency: i32,
+    /// Milliseconds to wait before sending each request after the first
+    pub delay: i32,
+    pub stop_on_failure: bool,
+    /// CSV or JSON file whose rows each run the folder once, with columns bound to variables
+    pub data_file: Option<String>,
+    pub elapsed: i32,
+    pub results: Vec<CollectionRunResult>,
 }
 
-impl UpsertModelInfo for HttpResponseEvent {
+impl UpsertModelInfo for CollectionRun {
     fn table_name() -> impl IntoTableRef + IntoIden {
-        HttpResponseEventIden::Table
+        CollectionRunIden::Table
     }
 
     fn id_column() -> impl IntoIden + Eq + Clone {
-        HttpResponseEventIden::Id
+        CollectionRunIden::Id
     }
 
     fn generate_id() -> String {
-        generate_prefixed_id("re")
+        generate_prefixed_id("cr")
     }
 
     fn order_by() -> (impl IntoColumnRef, Order) {
-        (HttpResponseEventIden::CreatedAt, Order::Asc)
+        (CollectionRunIden::CreatedAt, Desc)
     }
 
     fn get_id(&self) -> String {
@@ -1892,84 +3911,99 @@ impl UpsertModelInfo for HttpResponseEvent {
         self,
         source: &UpdateSource,
     ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use HttpResponseEventIden::*;
+        use CollectionRunIden::*;
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (WorkspaceId, self.workspace_id.into()),
-            (ResponseId, self.response_id.into()),
-            (Event, serde_json::to_string(&self.event)?.into()),
+            (FolderId, self.folder_id.into()),
+            (State, serde_json::to_value(&self.state)?.as_str().into()),
+            (Concurrency, self.concurrency.into()),
+            (Delay, self.delay.into()),
+            (StopOnFailure, self.stop_on_failure.into()),
+            (DataFile, self.data_file.into()),
+            (Elapsed, self.elapsed.into()),
+            (Results, serde_json::to_string(&self.results)?.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
-            HttpResponseEventIden::UpdatedAt,
-            HttpResponseEventIden::Event,
+            CollectionRunIden::UpdatedAt,
+            CollectionRunIden::State,
+            CollectionRunIden::Elapsed,
+            CollectionRunIden::Results,
         ]
     }
 
-    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
     where
         Self: Sized,
     {
-        let event: String = r.get("event")?;
+        let state: String = row.get("state")?;
+        let results: String = row.get("results")?;
         Ok(Self {
-            id: r.get("id")?,
-            model: r.get("model")?,
-            workspace_id: r.get("workspace_id")?,
-            response_id: r.get("response_id")?,
-            created_at: r.get("created_at")?,
-            updated_at: r.get("updated_at")?,
-            event: serde_json::from_str(&event).unwrap_or_default(),
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            folder_id: row.get("folder_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            state: serde_json::from_str(format!(r#""{state}""#).as_str()).unwrap_or_default(),
+            concurrency: row.get("concurrency")?,
+            delay: row.get("delay")?,
+            stop_on_failure: row.get("stop_on_failure")?,
+            data_file: row.get("data_file")?,
+            elapsed: row.get("elapsed")?,
+            results: serde_json::from_str(&results).unwrap_or_default(),
         })
     }
 }
 
-impl HttpResponseEvent {
-    pub fn new(response_id: &str, workspace_id: &str, event: HttpResponseEventData) -> Self {
-        Self {
-            model: "http_response_event".to_string(),
-            id: Self::generate_id(),
-            created_at: Utc::now().naive_utc(),
-            updated_at: Utc::now().naive_utc(),
-            workspace_id: workspace_id.to_string(),
-            response_id: response_id.to_string(),
-            event,
-        }
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+/// A request or folder that is sent on an interval in the background to check that it still works
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "graphql_introspections")]
-pub struct GraphQlIntrospection {
-    #[ts(type = "\"graphql_introspection\"")]
+#[enum_def(table_name = "monitors")]
+pub struct Monitor {
+    #[ts(type = "\"monitor\"")]
     pub model: String,
     pub id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub workspace_id: String,
-    pub request_id: String,
-    pub content: Option<String>,
+
+    pub name: String,
+    pub enabled: bool,
+    /// Folder whose HTTP requests are all sent on each run
+    pub folder_id: Option<String>,
+    /// Single HTTP request to send on each run, used when there's no folder
+    pub request_id: Option<String>,
+    pub environment_id: Option<String>,
+    pub cookie_jar_id: Option<String>,
+    /// Seconds between runs
+    pub interval: i32,
+    /// How many runs in a row must fail before the monitor is flagged as down
+    pub failure_threshold: i32,
+    pub consecutive_failures: i32,
+    pub last_run_at: Option<NaiveDateTime>,
 }
 
-impl UpsertModelInfo for GraphQlIntrospection {
+impl UpsertModelInfo for Monitor {
     fn table_name() -> impl IntoTableRef + IntoIden {
-        GraphQlIntrospectionIden::Table
+        MonitorIden::Table
     }
 
     fn id_column() -> impl IntoIden + Eq + Clone {
-        GraphQlIntrospectionIden::Id
+        MonitorIden::Id
     }
 
     fn generate_id() -> String {
-        generate_prefixed_id("gi")
+        generate_prefixed_id("mn")
     }
 
     fn order_by() -> (impl IntoColumnRef, Order) {
-        (GraphQlIntrospectionIden::CreatedAt, Desc)
+        (MonitorIden::CreatedAt, Desc)
     }
 
     fn get_id(&self) -> String {
@@ -1980,82 +4014,99 @@ impl UpsertModelInfo for GraphQlIntrospection {
         self,
         source: &UpdateSource,
     ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use GraphQlIntrospectionIden::*;
+        use MonitorIden::*;
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
             (WorkspaceId, self.workspace_id.into()),
+            (Name, self.name.trim().into()),
+            (Enabled, self.enabled.into()),
+            (FolderId, self.folder_id.into()),
             (RequestId, self.request_id.into()),
-            (Content, self.content.into()),
+            (EnvironmentId, self.environment_id.into()),
+            (CookieJarId, self.cookie_jar_id.into()),
+            (Interval, self.interval.into()),
+            (FailureThreshold, self.failure_threshold.into()),
+            (ConsecutiveFailures, self.consecutive_failures.into()),
+            (LastRunAt, self.last_run_at.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
-            GraphQlIntrospectionIden::UpdatedAt,
-            GraphQlIntrospectionIden::Content,
+            MonitorIden::UpdatedAt,
+            MonitorIden::Name,
+            MonitorIden::Enabled,
+            MonitorIden::FolderId,
+            MonitorIden::RequestId,
+            MonitorIden::EnvironmentId,
+            MonitorIden::CookieJarId,
+            MonitorIden::Interval,
+            MonitorIden::FailureThreshold,
+            MonitorIden::ConsecutiveFailures,
+            MonitorIden::LastRunAt,
         ]
     }
 
-    fn from_row(r: &Row) -> rusqlite::Result<Self>
+    fn from_row(row: &Row) -> rusqlite::Result<Self>
     where
         Self: Sized,
     {
         Ok(Self {
-            id: r.get("id")?,
-            model: r.get("model")?,
-            created_at: r.get("created_at")?,
-            updated_at: r.get("updated_at")?,
-            workspace_id: r.get("workspace_id")?,
-            request_id: r.get("request_id")?,
-            content: r.get("content")?,
+            id: row.get("id")?,
+            model: row.get("model")?,
+            workspace_id: row.get("workspace_id")?,
+            created_at: row.get("created_at")?,
+            updated_at: row.get("updated_at")?,
+            name: row.get("name")?,
+            enabled: row.get("enabled")?,
+            folder_id: row.get("folder_id")?,
+            request_id: row.get("request_id")?,
+            environment_id: row.get("environment_id")?,
+            cookie_jar_id: row.get("cookie_jar_id")?,
+            interval: row.get("interval")?,
+            failure_threshold: row.get("failure_threshold")?,
+            consecutive_failures: row.get("consecutive_failures")?,
+            last_run_at: row.get("last_run_at")?,
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default, JsonSchema, TS)]
+/// One scheduled run of a monitor
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
-#[enum_def(table_name = "grpc_requests")]
-pub struct GrpcRequest {
-    #[ts(type = "\"grpc_request\"")]
+#[enum_def(table_name = "monitor_runs")]
+pub struct MonitorRun {
+    #[ts(type = "\"monitor_run\"")]
     pub model: String,
     pub id: String,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
     pub workspace_id: String,
-    pub folder_id: Option<String>,
+    pub monitor_id: String,
 
-    pub authentication_type: Option<String>,
-    #[ts(type = "Record<string, any>")]
-    pub authentication: BTreeMap<String, Value>,
-    pub description: String,
-    pub message: String,
-    pub metadata: Vec<HttpRequestHeader>,
-    pub method: Option<String>,
-    pub name: String,
-    pub service: Option<String>,
-    pub sort_priority: f64,
-    /// Server URL (http for plaintext or https for secure)
-    pub url: String,
-    pub setting_validate_certificates: InheritedBoolSetting,
+    /// True when every request in the run passed
+    pub passed: bool,
+    pub elapsed: i32,
+    pub results: Vec<CollectionRunResult>,
 }
 
-impl UpsertModelInfo for GrpcRequest {
+impl UpsertModelInfo for MonitorRun {
     fn table_name() -> impl IntoTableRef + IntoIden {
-        GrpcRequestIden::Table
+        MonitorRunIden::Table
     }
 
     fn id_column() -> impl IntoIden + Eq + Clone {
-        GrpcRequestIden::Id
+        MonitorRunIden::Id
     }
 
     fn generate_id() -> String {
-        generate_prefixed_id("gr")
+        generate_prefixed_id("mr")
     }
 
     fn order_by() -> (impl IntoColumnRef, Order) {
-        (GrpcRequestIden::CreatedAt, Desc)
+        (MonitorRunIden::CreatedAt, Desc)
     }
 
     fn get_id(&self) -> String {
@@ -2066,45 +4117,24 @@ impl UpsertModelInfo for GrpcRequest {
         self,
         source: &UpdateSource,
     ) -> DbResult<Vec<(impl IntoIden + Eq, impl Into<SimpleExpr>)>> {
-        use GrpcRequestIden::*;
+        use MonitorRunIden::*;
         Ok(vec![
             (CreatedAt, upsert_date(source, self.created_at)),
             (UpdatedAt, upsert_date(source, self.updated_at)),
-            (Name, self.name.trim().into()),
-            (Description, self.description.into()),
             (WorkspaceId, self.workspace_id.into()),
-            (FolderId, self.folder_id.into()),
-            (SortPriority, self.sort_priority.into()),
-            (Url, self.url.into()),
-            (Service, self.service.into()),
-            (Method, self.method.into()),
-            (Message, self.message.into()),
-            (AuthenticationType, self.authentication_type.into()),
-            (Authentication, serde_json::to_string(&self.authentication)?.into()),
-            (Metadata, serde_json::to_string(&self.metadata)?.into()),
-            (
-                SettingValidateCertificates,
-                serde_json::to_string(&self.setting_validate_certificates)?.into(),
-            ),
+            (MonitorId, self.monitor_id.into()),
+            (Passed, self.passed.into()),
+            (Elapsed, self.elapsed.into()),
+            (Results, serde_json::to_string(&self.results)?.into()),
         ])
     }
 
     fn update_columns() -> Vec<impl IntoIden> {
         vec![
-            GrpcRequestIden::UpdatedAt,
-            GrpcRequestIden::WorkspaceId,
-            GrpcRequestIden::Name,
-            GrpcRequestIden::Description,
-            GrpcRequestIden::FolderId,
-            GrpcRequestIden::SortPriority,
-            GrpcRequestIden::Url,
-            GrpcRequestIden::Service,
-            GrpcRequestIden::Method,
-            GrpcRequestIden::Message,
-            GrpcRequestIden::AuthenticationType,
-            GrpcRequestIden::Authentication,
-            GrpcRequestIden::Metadata,
-            GrpcRequestIden::SettingValidateCertificates,
+            MonitorRunIden::UpdatedAt,
+            MonitorRunIden::Passed,
+            MonitorRunIden::Elapsed,
+            MonitorRunIden::Results,
         ]
     }
 
@@ -2112,28 +4142,17 @@ impl UpsertModelInfo for GrpcRequest {
     where
         Self: Sized,
     {
-        let authentication: String = row.get("authentication")?;
-        let metadata: String = row.get("metadata")?;
-        let setting_validate_certificates: String = row.get("setting_validate_certificates")?;
+        let results: String = row.get("results")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
             workspace_id: row.get("workspace_id")?,
+            monitor_id: row.get("monitor_id")?,
             created_at: row.get("created_at")?,
             updated_at: row.get("updated_at")?,
-            folder_id: row.get("folder_id")?,
-            name: row.get("name")?,
-            description: row.get("description")?,
-            service: row.get("service")?,
-            method: row.get("method")?,
-            message: row.get("message")?,
-            authentication_type: row.get("authentication_type")?,
-            authentication: serde_json::from_str(authentication.as_str()).unwrap_or_default(),
-            url: row.get("url")?,
-            sort_priority: row.get("sort_priority")?,
-            metadata: serde_json::from_str(metadata.as_str()).unwrap_or_default(),
-            setting_validate_certificates: serde_json::from_str(&setting_validate_certificates)
-                .unwrap_or_default(),
+            passed: row.get("passed")?,
+            elapsed: row.get("elapsed")?,
+            results: serde_json::from_str(&results).unwrap_or_default(),
         })
     }
 }
@@ -2145,6 +4164,8 @@ pub enum GrpcConnectionState {
     Initialized,
     Connected,
     Closed,
+    /// Closed because a connect, read, or total timeout elapsed
+    TimedOut,
 }
 
 impl Default for GrpcConnectionState {
@@ -2153,6 +4174,13 @@ impl Default for GrpcConnectionState {
     }
 }
 
+impl GrpcConnectionState {
+    /// Whether the connection has ended, for any reason
+    pub fn is_closed(&self) -> bool {
+        matches!(self, Self::Closed | Self::TimedOut)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -2174,6 +4202,8 @@ pub struct GrpcConnection {
     pub state: GrpcConnectionState,
     pub trailers: BTreeMap<String, String>,
     pub url: String,
+    /// Compression the server used for its messages, from its `grpc-encoding` header
+    pub response_encoding: Option<String>,
 }
 
 impl UpsertModelInfo for GrpcConnection {
@@ -2215,6 +4245,7 @@ impl UpsertModelInfo for GrpcConnection {
             (Error, self.error.as_ref().map(|s| s.as_str()).into()),
             (Trailers, serde_json::to_string(&self.trailers)?.into()),
             (Url, self.url.into()),
+            (ResponseEncoding, self.response_encoding.into()),
         ])
     }
 
@@ -2229,6 +4260,7 @@ impl UpsertModelInfo for GrpcConnection {
             GrpcConnectionIden::Error,
             GrpcConnectionIden::Trailers,
             GrpcConnectionIden::Url,
+            GrpcConnectionIden::ResponseEncoding,
         ]
     }
 
@@ -2253,6 +4285,7 @@ impl UpsertModelInfo for GrpcConnection {
             url: row.get("url")?,
             error: row.get("error")?,
             trailers: serde_json::from_str(trailers.as_str()).unwrap_or_default(),
+            response_encoding: row.get("response_encoding").unwrap_or_default(),
         })
     }
 }
@@ -2275,6 +4308,20 @@ impl Default for GrpcEventType {
     }
 }
 
+/// A detail message from the `grpc-status-details-bin` trailer of a failed call
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+pub struct GrpcErrorDetail {
+    /// Fully-qualified message name, like `google.rpc.BadRequest`
+    pub type_name: String,
+    /// The decoded message, or `None` when no descriptor for the type was available
+    #[ts(type = "any")]
+    pub value: Option<Value>,
+    /// Base64 of the encoded message, kept when it couldn't be decoded
+    pub raw: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_models.ts")]
@@ -2294,6 +4341,8 @@ pub struct GrpcEvent {
     pub event_type: GrpcEventType,
     pub metadata: BTreeMap<String, String>,
     pub status: Option<i32>,
+    /// Rich error details the server attached to a failed call's status
+    pub error_details: Vec<GrpcErrorDetail>,
 }
 
 impl UpsertModelInfo for GrpcEvent {
@@ -2333,6 +4382,7 @@ impl UpsertModelInfo for GrpcEvent {
             (Metadata, serde_json::to_string(&self.metadata)?.into()),
             (Status, self.status.into()),
             (Error, self.error.into()),
+            (ErrorDetails, serde_json::to_string(&self.error_details)?.into()),
         ])
     }
 
@@ -2344,6 +4394,7 @@ impl UpsertModelInfo for GrpcEvent {
             GrpcEventIden::Metadata,
             GrpcEventIden::Status,
             GrpcEventIden::Error,
+            GrpcEventIden::ErrorDetails,
         ]
     }
 
@@ -2353,6 +4404,7 @@ impl UpsertModelInfo for GrpcEvent {
     {
         let event_type: String = row.get("event_type")?;
         let metadata: String = row.get("metadata")?;
+        let error_details: String = row.get("error_details")?;
         Ok(Self {
             id: row.get("id")?,
             model: row.get("model")?,
@@ -2366,6 +4418,7 @@ impl UpsertModelInfo for GrpcEvent {
             metadata: serde_json::from_str(metadata.as_str()).unwrap_or_default(),
             status: row.get("status")?,
             error: row.get("error")?,
+            error_details: serde_json::from_str(error_details.as_str()).unwrap_or_default(),
         })
     }
 }
@@ -2510,6 +4563,8 @@ pub struct SyncState {
     pub checksum: String,
     pub rel_path: String,
     pub sync_dir: String,
+    /// Contents of the file when it was last synced, used as the base of three-way merges
+    pub base: Option<String>,
 }
 
 impl UpsertModelInfo for SyncState {
@@ -2547,6 +4602,7 @@ impl UpsertModelInfo for SyncState {
             (ModelId, self.model_id.into()),
             (RelPath, self.rel_path.into()),
             (SyncDir, self.sync_dir.into()),
+            (Base, self.base.into()),
         ])
     }
 
@@ -2557,6 +4613,7 @@ impl UpsertModelInfo for SyncState {
             SyncStateIden::Checksum,
             SyncStateIden::RelPath,
             SyncStateIden::SyncDir,
+            SyncStateIden::Base,
         ]
     }
 
@@ -2575,6 +4632,7 @@ impl UpsertModelInfo for SyncState {
             model_id: row.get("model_id")?,
             sync_dir: row.get("sync_dir")?,
             rel_path: row.get("rel_path")?,
+            base: row.get("base")?,
         })
     }
 }
@@ -2680,10 +4738,143 @@ impl<'s> TryFrom<&Row<'s>> for PluginKeyValue {
     }
 }
 
+/// Local `.proto` sources attached to a workspace, along with the descriptor set they last
+/// compiled to
+#[derive(Debug, Clone, Serialize, Deserialize, Default, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_models.ts")]
+#[enum_def(table_name = "proto_files")]
+pub struct ProtoFiles {
+    #[ts(type = "\"proto_files\"")]
+    pub model: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub workspace_id: String,
+
+    pub paths: Vec<String>,
+    pub include_dirs: Vec<String>,
+    #[serde(skip)]
+    #[ts(skip)]
+    pub descriptor_set: Option<Vec<u8>>,
+    pub compiled_at: Option<NaiveDateTime>,
+}
+
+impl ProtoFiles {
+    /// Paths in the form expected by the gRPC compiler, where directories become include paths
+    pub fn sources(&self) -> Vec<String> {
+        self.paths.iter().chain(self.include_dirs.iter()).cloned().collect()
+    }
+}
+
+impl<'s> TryFrom<&Row<'s>> for ProtoFiles {
+    type Error = rusqlite::Error;
+
+    fn try_from(r: &Row<'s>) -> std::result::Result<Self, Self::Error> {
+        let paths: String = r.get("paths")?;
+        let include_dirs: String = r.get("include_dirs")?;
+        Ok(Self {
+            model: r.get("model")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            workspace_id: r.get("workspace_id")?,
+            paths: serde_json::from_str(&paths).unwrap_or_default(),
+            include_dirs: serde_json::from_str(&include_dirs).unwrap_or_default(),
+            descriptor_set: r.get("descriptor_set")?,
+            compiled_at: r.get("compiled_at")?,
+        })
+    }
+}
+
+/// Encoded `FileDescriptorSet` from gRPC server reflection, keyed by host and a hash of the TLS
+/// configuration and metadata used to reach it
+#[derive(Debug, Clone, Default)]
+#[enum_def(table_name = "grpc_descriptor_caches")]
+pub struct GrpcDescriptorCache {
+    pub model: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+
+    pub host: String,
+    pub tls_key: String,
+    pub descriptor_set: Vec<u8>,
+    pub expires_at: NaiveDateTime,
+}
+
+impl<'s> TryFrom<&Row<'s>> for GrpcDescriptorCache {
+    type Error = rusqlite::Error;
+
+    fn try_from(r: &Row<'s>) -> std::result::Result<Self, Self::Error> {
+        Ok(Self {
+            model: r.get("model")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            host: r.get("host")?,
+            tls_key: r.get("tls_key")?,
+            descriptor_set: r.get("descriptor_set")?,
+            expires_at: r.get("expires_at")?,
+        })
+    }
+}
+
+/// An OAuth 2.0 token obtained through an auth plugin, keyed by a hash of the auth configuration
+/// it was obtained with
+#[derive(Debug, Clone, Default)]
+#[enum_def(table_name = "oauth2_tokens")]
+pub struct OAuth2Token {
+    pub model: String,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+
+    pub config_hash: String,
+    pub access_token: String,
+    pub id_token: Option<String>,
+    pub refresh_token: Option<String>,
+    pub token_type: Option<String>,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+impl OAuth2Token {
+    /// Whether the token has expired, treating tokens that expire within the next few seconds as
+    /// already expired so they aren't sent only to be rejected
+    pub fn is_expired(&self) -> bool {
+        match self.expires_at {
+            None => false,
+            Some(expires_at) => {
+                expires_at <= Utc::now().naive_utc() + chrono::Duration::seconds(10)
+            }
+        }
+    }
+}
+
+impl<'s> TryFrom<&Row<'s>> for OAuth2Token {
+    type Error = rusqlite::Error;
+
+    fn try_from(r: &Row<'s>) -> std::result::Result<Self, Self::Error> {
+        let scopes: String = r.get("scopes")?;
+        Ok(Self {
+            model: r.get("model")?,
+            created_at: r.get("created_at")?,
+            updated_at: r.get("updated_at")?,
+            config_hash: r.get("config_hash")?,
+            access_token: r.get("access_token")?,
+            id_token: r.get("id_token")?,
+            refresh_token: r.get("refresh_token")?,
+            token_type: r.get("token_type")?,
+            scopes: serde_json::from_str(&scopes).unwrap_or_default(),
+            expires_at: r.get("expires_at")?,
+        })
+    }
+}
+
 fn default_true() -> bool {
     true
 }
 
+fn default_max_redirects() -> i32 {
+    DEFAULT_MAX_REDIRECTS
+}
+
 fn default_http_method() -> String {
     "GET".to_string()
 }
@@ -2743,6 +4934,7 @@ macro_rules! define_any_model {
 }
 
 define_any_model! {
+    CollectionRun,
     CookieJar,
     Environment,
     Folder,
@@ -2754,8 +4946,15 @@ define_any_model! {
     HttpResponse,
     HttpResponseEvent,
     KeyValue,
+    Monitor,
+    MonitorRun,
+    MqttConnection,
+    MqttEvent,
+    MqttRequest,
     Plugin,
     Settings,
+    SocketRequest,
+    SocketResponse,
     SyncState,
     WebsocketConnection,
     WebsocketEvent,
@@ -2775,6 +4974,7 @@ impl<'de> Deserialize<'de> for AnyModel {
         use serde_json::from_value as fv;
 
         let model = match model.get("model") {
+            Some(m) if m == "collection_run" => CollectionRun(fv(value).unwrap()),
             Some(m) if m == "cookie_jar" => CookieJar(fv(value).unwrap()),
             Some(m) if m == "environment" => Environment(fv(value).unwrap()),
             Some(m) if m == "folder" => Folder(fv(value).unwrap()),
@@ -2786,8 +4986,15 @@ impl<'de> Deserialize<'de> for AnyModel {
             Some(m) if m == "http_response" => HttpResponse(fv(value).unwrap()),
             Some(m) if m == "http_response_event" => HttpResponseEvent(fv(value).unwrap()),
             Some(m) if m == "key_value" => KeyValue(fv(value).unwrap()),
+            Some(m) if m == "monitor" => Monitor(fv(value).unwrap()),
+            Some(m) if m == "monitor_run" => MonitorRun(fv(value).unwrap()),
+            Some(m) if m == "mqtt_connection" => MqttConnection(fv(value).unwrap()),
+            Some(m) if m == "mqtt_event" => MqttEvent(fv(value).unwrap()),
+            Some(m) if m == "mqtt_request" => MqttRequest(fv(value).unwrap()),
             Some(m) if m == "plugin" => Plugin(fv(value).unwrap()),
             Some(m) if m == "settings" => Settings(fv(value).unwrap()),
+            Some(m) if m == "socket_request" => SocketRequest(fv(value).unwrap()),
+            Some(m) if m == "socket_response" => SocketResponse(fv(value).unwrap()),
             Some(m) if m == "sync_state" => SyncState(fv(value).unwrap()),
             Some(m) if m == "websocket_connection" => WebsocketConnection(fv(value).unwrap()),
             Some(m) if m == "websocket_event" => WebsocketEvent(fv(value).unwrap()),
@@ -2825,6 +5032,8 @@ impl AnyModel {
             AnyModel::Folder(v) => v.name,
             AnyModel::GrpcRequest(v) => compute_name(&v.name, &v.url, "gRPC Request"),
             AnyModel::HttpRequest(v) => compute_name(&v.name, &v.url, "HTTP Request"),
+            AnyModel::MqttRequest(v) => compute_name(&v.name, &v.url, "MQTT Request"),
+            AnyModel::SocketRequest(v) => compute_name(&v.name, &v.url, "Socket Request"),
             AnyModel::WebsocketRequest(v) => compute_name(&v.name, &v.url, "WebSocket Request"),
             AnyModel::Workspace(v) => v.name,
             _ => "No Name".to_string(),
@@ -0,0 +1,257 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::AnyModel;
+use crate::util::{ModelChangeEvent, ModelPayload, UpdateSource};
+use rusqlite::types::Type;
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Repeated edits to the same model from the same source within this many seconds are folded into
+/// one entry, so typing in an editor doesn't log every keystroke.
+const AUDIT_LOG_COALESCE_SECONDS: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct AuditLogEntry {
+    pub id: i64,
+    pub created_at: String,
+    pub workspace_id: String,
+    pub model: String,
+    pub model_id: String,
+    /// Name of the model at the time of the change, so deleted models can still be shown
+    pub model_name: String,
+    pub change: ModelChangeEvent,
+    /// Who or what made the change
+    pub update_source: UpdateSource,
+}
+
+impl<'a> ClientDb<'a> {
+    /// List the most recent changes to models in a workspace, newest first
+    pub fn list_workspace_activity(
+        &self,
+        workspace_id: &str,
+        limit: usize,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT id, created_at, workspace_id, model, model_id, model_name, change,
+                       update_source
+                FROM audit_logs
+                WHERE workspace_id = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2
+            "#,
+        )?;
+
+        let items = stmt.query_map(params![workspace_id, limit as i64], audit_log_from_row)?;
+        Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
+    }
+
+    /// List the most recent changes to a single model, newest first
+    pub fn list_model_activity(&self, model_id: &str, limit: usize) -> Result<Vec<AuditLogEntry>> {
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT id, created_at, workspace_id, model, model_id, model_name, change,
+                       update_source
+                FROM audit_logs
+                WHERE model_id = ?1
+                ORDER BY created_at DESC, id DESC
+                LIMIT ?2
+            "#,
+        )?;
+
+        let items = stmt.query_map(params![model_id, limit as i64], audit_log_from_row)?;
+        Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
+    }
+
+    pub fn prune_audit_logs_older_than_days(&self, days: i64) -> Result<usize> {
+        let offset = format!("-{days} days");
+        Ok(self.conn().resolve().execute(
+            r#"
+                DELETE FROM audit_logs
+                WHERE created_at < STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW', ?1)
+            "#,
+            params![offset],
+        )?)
+    }
+
+    /// Log a change to a workspace model. Changes made in the background (like cookies stored
+    /// after a request is sent) and changes to models outside a workspace aren't logged.
+    pub(crate) fn record_audit_log(&self, payload: &ModelPayload) -> Result<()> {
+        if matches!(payload.update_source, UpdateSource::Background) {
+            return Ok(());
+        }
+
+        let workspace_id = match &payload.model {
+            AnyModel::CookieJar(m) => &m.workspace_id,
+            AnyModel::Environment(m) => &m.workspace_id,
+            AnyModel::Folder(m) => &m.workspace_id,
+            AnyModel::GrpcRequest(m) => &m.workspace_id,
+            AnyModel::HttpRequest(m) => &m.workspace_id,
+            AnyModel::WebsocketRequest(m) => &m.workspace_id,
+            AnyModel::MqttRequest(m) => &m.workspace_id,
+            AnyModel::SocketRequest(m) => &m.workspace_id,
+            AnyModel::Workspace(m) => &m.id,
+            _ => return Ok(()),
+        };
+        let model_name = payload.model.resolved_name();
+        let change = serde_json::to_string(&payload.change)?;
+        let update_source = serde_json::to_string(&payload.update_source)?;
+
+        let recent_id: Option<i64> = self
+            .conn()
+            .resolve()
+            .query_row(
+                r#"
+                    SELECT id
+                    FROM audit_logs
+                    WHERE model_id = ?1
+                      AND id = (SELECT MAX(id) FROM audit_logs WHERE model_id = ?1)
+                      AND change = ?2
+                      AND update_source = ?3
+                      AND created_at > STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW', ?4)
+                "#,
+                params![
+                    payload.model.id(),
+                    change,
+                    update_source,
+                    format!("-{AUDIT_LOG_COALESCE_SECONDS} seconds")
+                ],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if let Some(recent_id) = recent_id {
+            self.conn().resolve().execute(
+                r#"
+                    UPDATE audit_logs
+                    SET model_name = ?1, created_at = STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW')
+                    WHERE id = ?2
+                "#,
+                params![model_name, recent_id],
+            )?;
+            return Ok(());
+        }
+
+        self.conn().resolve().execute(
+            r#"
+                INSERT INTO audit_logs
+                    (workspace_id, model, model_id, model_name, change, update_source)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            "#,
+            params![
+                workspace_id,
+                payload.model.model(),
+                payload.model.id(),
+                model_name,
+                change,
+                update_source,
+            ],
+        )?;
+
+        Ok(())
+    }
+}
+
+fn audit_log_from_row(row: &rusqlite::Row) -> rusqlite::Result<AuditLogEntry> {
+    let change_raw: String = row.get(6)?;
+    let change = serde_json::from_str::<ModelChangeEvent>(&change_raw)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(6, Type::Text, Box::new(e)))?;
+    let update_source_raw: String = row.get(7)?;
+    let update_source = serde_json::from_str::<UpdateSource>(&update_source_raw)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(7, Type::Text, Box::new(e)))?;
+    Ok(AuditLogEntry {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        workspace_id: row.get(2)?,
+        model: row.get(3)?,
+        model_id: row.get(4)?,
+        model_name: row.get(5)?,
+        change,
+        update_source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{CookieJar, HttpRequest, Workspace};
+    use crate::util::{ModelChangeEvent, UpdateSource};
+
+    #[test]
+    fn records_workspace_activity_by_source() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let window = &UpdateSource::from_window_label("main");
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Activity Test".to_string(), ..Default::default() },
+                &UpdateSource::Cli,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    name: "First".to_string(),
+                    ..Default::default()
+                },
+                window,
+            )
+            .expect("Failed to upsert request");
+
+        // Consecutive edits from the same window are folded into one entry
+        let request = db
+            .upsert_http_request(&HttpRequest { name: "Second".to_string(), ..request }, window)
+            .expect("Failed to update request");
+        db.upsert_http_request(
+            &HttpRequest { name: "Third".to_string(), ..request.clone() },
+            window,
+        )
+        .expect("Failed to update request");
+
+        // Background changes aren't logged
+        db.upsert_cookie_jar(
+            &CookieJar { workspace_id: workspace.id.clone(), ..Default::default() },
+            &UpdateSource::Background,
+        )
+        .expect("Failed to upsert cookie jar");
+
+        db.delete_http_request_by_id(&request.id, &UpdateSource::Plugin)
+            .expect("Failed to delete request");
+
+        let activity = db.list_workspace_activity(&workspace.id, 10).expect("Failed to list");
+        let summary: Vec<_> = activity
+            .iter()
+            .map(|e| {
+                let change = match e.change {
+                    ModelChangeEvent::Upsert { created: true } => "created",
+                    ModelChangeEvent::Upsert { created: false } => "updated",
+                    ModelChangeEvent::Delete => "deleted",
+                };
+                let source = match e.update_source {
+                    UpdateSource::Cli => "cli",
+                    UpdateSource::Plugin => "plugin",
+                    UpdateSource::Window { .. } => "window",
+                    _ => "other",
+                };
+                (e.model.as_str(), e.model_name.as_str(), change, source)
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("http_request", "Third", "deleted", "plugin"),
+                ("http_request", "Third", "updated", "window"),
+                ("http_request", "First", "created", "window"),
+                ("workspace", "Activity Test", "created", "cli"),
+            ]
+        );
+
+        let request_activity = db.list_model_activity(&request.id, 10).expect("Failed to list");
+        assert_eq!(request_activity.len(), 3);
+    }
+}
@@ -1,23 +1,26 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
-use crate::models::{Environment, Folder, GrpcRequest, HttpRequest, WebsocketRequest, Workspace};
 use crate::util::{BatchUpsertResult, UpdateSource};
 use log::info;
 
 impl<'a> ClientDb<'a> {
     pub fn batch_upsert(
         &self,
-        workspaces: Vec<Workspace>,
-        environments: Vec<Environment>,
-        folders: Vec<Folder>,
-        http_requests: Vec<HttpRequest>,
-        grpc_requests: Vec<GrpcRequest>,
-        websocket_requests: Vec<WebsocketRequest>,
+        resources: BatchUpsertResult,
         source: &UpdateSource,
     ) -> Result<BatchUpsertResult> {
+        let BatchUpsertResult {
+            workspaces,
+            environments,
+            folders,
+            http_requests,
+            grpc_requests,
+            websocket_requests,
+            cookie_jars,
+        } = resources;
         let mut imported_resources = BatchUpsertResult::default();
 
-        if workspaces.len() > 0 {
+        if !workspaces.is_empty() {
             for v in workspaces {
                 let x = self.upsert_workspace(&v, source)?;
                 imported_resources.workspaces.push(x.clone());
@@ -25,7 +28,15 @@ impl<'a> ClientDb<'a> {
             info!("Upserted {} workspaces", imported_resources.workspaces.len());
         }
 
-        if http_requests.len() > 0 {
+        if !cookie_jars.is_empty() {
+            for v in cookie_jars {
+                let x = self.upsert_cookie_jar(&v, source)?;
+                imported_resources.cookie_jars.push(x.clone());
+            }
+            info!("Upserted {} cookie_jars", imported_resources.cookie_jars.len());
+        }
+
+        if !http_requests.is_empty() {
             for v in http_requests {
                 let x = self.upsert_http_request(&v, source)?;
                 imported_resources.http_requests.push(x.clone());
@@ -33,7 +44,7 @@ impl<'a> ClientDb<'a> {
             info!("Upserted Imported {} http_requests", imported_resources.http_requests.len());
         }
 
-        if grpc_requests.len() > 0 {
+        if !grpc_requests.is_empty() {
             for v in grpc_requests {
                 let x = self.upsert_grpc_request(&v, source)?;
                 imported_resources.grpc_requests.push(x.clone());
@@ -41,7 +52,7 @@ impl<'a> ClientDb<'a> {
             info!("Upserted {} grpc_requests", imported_resources.grpc_requests.len());
         }
 
-        if websocket_requests.len() > 0 {
+        if !websocket_requests.is_empty() {
             for v in websocket_requests {
                 let x = self.upsert_websocket_request(&v, source)?;
                 imported_resources.websocket_requests.push(x.clone());
@@ -51,7 +62,7 @@ impl<'a> ClientDb<'a> {
 
         // Do folders after their children so the UI doesn't render empty folders before populating
         // immediately after.
-        if folders.len() > 0 {
+        if !folders.is_empty() {
             for v in folders {
                 let x = self.upsert_folder(&v, source)?;
                 imported_resources.folders.push(x.clone());
@@ -60,7 +71,7 @@ impl<'a> ClientDb<'a> {
         }
 
         // Do environments last because they can depend on many models (requests, folders, etc)
-        if environments.len() > 0 {
+        if !environments.is_empty() {
             for x in environments {
                 let x = self.upsert_environment(&x, source)?;
                 imported_resources.environments.push(x.clone());
@@ -0,0 +1,149 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{Environment, EnvironmentVariable, KeyValue, KeyValueIden, UpsertModelInfo};
+use crate::util::UpdateSource;
+use sea_query::{Asterisk, Cond, Expr, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+/// Values extracted from responses are stored as key/values in this namespace, keyed by
+/// `<workspace_id>::<name>`
+const CHAINED_VALUES_NAMESPACE: &str = "chained_values";
+
+impl<'a> ClientDb<'a> {
+    pub fn list_chained_values(&self, workspace_id: &str) -> Result<Vec<EnvironmentVariable>> {
+        let prefix = chained_value_key(workspace_id, "");
+        let (sql, params) = Query::select()
+            .from(KeyValueIden::Table)
+            .column(Asterisk)
+            .cond_where(
+                Cond::all()
+                    .add(Expr::col(KeyValueIden::Namespace).eq(CHAINED_VALUES_NAMESPACE))
+                    .add(Expr::col(KeyValueIden::Key).like(format!("{prefix}%"))),
+            )
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        let items = stmt.query_map(&*params.as_params(), KeyValue::from_row)?;
+
+        let mut values = Vec::new();
+        for kv in items {
+            let kv = kv?;
+            // LIKE treats `_` in workspace IDs as a wildcard, so check the prefix exactly
+            let Some(name) = kv.key.strip_prefix(&prefix) else {
+                continue;
+            };
+            values.push(EnvironmentVariable {
+                enabled: true,
+                name: name.to_string(),
+                value: serde_json::from_str(&kv.value).unwrap_or_default(),
+                id: None,
+                secret: false,
+            });
+        }
+        values.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(values)
+    }
+
+    pub fn set_chained_value(
+        &self,
+        workspace_id: &str,
+        name: &str,
+        value: &str,
+        source: &UpdateSource,
+    ) -> KeyValue {
+        let key = chained_value_key(workspace_id, name);
+        self.set_key_value_str(CHAINED_VALUES_NAMESPACE, &key, value, source).0
+    }
+
+    pub fn delete_chained_value(
+        &self,
+        workspace_id: &str,
+        name: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        self.delete_key_value(
+            CHAINED_VALUES_NAMESPACE,
+            &chained_value_key(workspace_id, name),
+            source,
+        )
+    }
+
+    /// Chained values as an environment, for the end of an environment chain where every other
+    /// environment overrides them
+    pub(crate) fn chained_values_environment(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Option<Environment>> {
+        let variables = self.list_chained_values(workspace_id)?;
+        if variables.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Environment {
+            model: "environment".to_string(),
+            workspace_id: workspace_id.to_string(),
+            name: "Chained Values".to_string(),
+            parent_model: CHAINED_VALUES_NAMESPACE.to_string(),
+            variables,
+            ..Default::default()
+        }))
+    }
+}
+
+fn chained_value_key(workspace_id: &str, name: &str) -> String {
+    format!("{workspace_id}::{name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{EnvironmentVariable, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn chained_values_are_scoped_and_lowest_precedence() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let other = db
+            .upsert_workspace(
+                &Workspace { name: "Other".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+
+        db.set_chained_value(&workspace.id, "token", "abc", &source);
+        db.set_chained_value(&workspace.id, "host", "chained.example.com", &source);
+        db.set_chained_value(&workspace.id, "token", "def", &source);
+        db.set_chained_value(&other.id, "token", "other", &source);
+
+        let values = db.list_chained_values(&workspace.id).unwrap();
+        let values: Vec<(&str, &str)> =
+            values.iter().map(|v| (v.name.as_str(), v.value.as_str())).collect();
+        assert_eq!(values, vec![("host", "chained.example.com"), ("token", "def")]);
+
+        let mut base = db.get_base_environment(&workspace.id).unwrap();
+        base.variables.push(EnvironmentVariable {
+            enabled: true,
+            name: "host".to_string(),
+            value: "example.com".to_string(),
+            id: None,
+            secret: false,
+        });
+        db.upsert_environment(&base, &source).unwrap();
+
+        let variables = crate::render::make_vars_hashmap(
+            db.resolve_environments(&workspace.id, None, None).unwrap(),
+        );
+        assert_eq!(variables.get("host").map(String::as_str), Some("example.com"));
+        assert_eq!(variables.get("token").map(String::as_str), Some("def"));
+
+        db.delete_chained_value(&workspace.id, "token", &source).unwrap();
+        assert_eq!(db.list_chained_values(&workspace.id).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,131 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{CollectionRun, CollectionRunIden, CollectionRunState};
+use crate::queries::MAX_HISTORY_ITEMS;
+use crate::util::UpdateSource;
+use log::debug;
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_collection_run(&self, id: &str) -> Result<CollectionRun> {
+        self.find_one(CollectionRunIden::Id, id)
+    }
+
+    pub fn list_collection_runs(&self, workspace_id: &str) -> Result<Vec<CollectionRun>> {
+        self.find_many(CollectionRunIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn list_collection_runs_for_folder(
+        &self,
+        folder_id: &str,
+        limit: Option<u64>,
+    ) -> Result<Vec<CollectionRun>> {
+        self.find_many(CollectionRunIden::FolderId, folder_id, limit)
+    }
+
+    /// Mark runs that were still going when the app last quit as stopped
+    pub fn cancel_pending_collection_runs(&self) -> Result<()> {
+        let running = serde_json::to_value(&CollectionRunState::Running)?;
+        let stopped = serde_json::to_value(&CollectionRunState::Stopped)?;
+        let (sql, params) = Query::update()
+            .table(CollectionRunIden::Table)
+            .values([(CollectionRunIden::State, stopped.as_str().into())])
+            .cond_where(Expr::col(CollectionRunIden::State).eq(running.as_str()))
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        stmt.execute(&*params.as_params())?;
+        Ok(())
+    }
+
+    pub fn delete_collection_run(
+        &self,
+        m: &CollectionRun,
+        source: &UpdateSource,
+    ) -> Result<CollectionRun> {
+        self.delete(m, source)
+    }
+
+    pub fn upsert_collection_run(
+        &self,
+        collection_run: &CollectionRun,
+        source: &UpdateSource,
+    ) -> Result<CollectionRun> {
+        if collection_run.id.is_empty() {
+            let runs = self.list_collection_runs_for_folder(&collection_run.folder_id, None)?;
+            for m in runs.iter().skip(MAX_HISTORY_ITEMS - 1) {
+                debug!("Deleting old collection run {}", m.id);
+                self.delete_collection_run(m, source)?;
+            }
+        }
+
+        self.upsert(collection_run, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{
+        CollectionRun, CollectionRunResult, CollectionRunState, Folder, Workspace,
+    };
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn collection_runs_are_trimmed_and_cancelled() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    name: "Folder".to_string(),
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+
+        let mut last = None;
+        for _ in 0..25 {
+            let run = db
+                .upsert_collection_run(
+                    &CollectionRun {
+                        workspace_id: workspace.id.clone(),
+                        folder_id: folder.id.clone(),
+                        ..Default::default()
+                    },
+                    &source,
+                )
+                .unwrap();
+            last = Some(run);
+        }
+
+        let mut run = last.unwrap();
+        run.state = CollectionRunState::Completed;
+        run.results.push(CollectionRunResult {
+            request_id: "rq_1".to_string(),
+            status: 200,
+            passed: true,
+            ..Default::default()
+        });
+        db.upsert_collection_run(&run, &source).unwrap();
+
+        assert_eq!(db.list_collection_runs_for_folder(&folder.id, None).unwrap().len(), 20);
+        let run = db.get_collection_run(&run.id).unwrap();
+        assert_eq!(run.state, CollectionRunState::Completed);
+        assert_eq!(run.results.len(), 1);
+
+        db.cancel_pending_collection_runs().unwrap();
+        let runs = db.list_collection_runs(&workspace.id).unwrap();
+        assert!(runs.iter().all(|r| r.state != CollectionRunState::Running));
+        assert_eq!(db.get_collection_run(&run.id).unwrap().state, CollectionRunState::Completed);
+    }
+}
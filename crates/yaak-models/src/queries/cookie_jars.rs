@@ -1,6 +1,6 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
-use crate::models::{CookieJar, CookieJarIden};
+use crate::models::{Cookie, CookieJar, CookieJarIden, Environment};
 use crate::util::UpdateSource;
 
 impl<'a> ClientDb<'a> {
@@ -43,4 +43,176 @@ impl<'a> ClientDb<'a> {
     ) -> Result<CookieJar> {
         self.upsert(cookie_jar, source)
     }
+
+    /// Resolve the cookie jar for a request, given its environment chain ordered from most to least
+    /// specific. A jar selected by an environment takes precedence over the active one, and jars
+    /// that no longer exist are skipped.
+    pub fn resolve_cookie_jar(
+        &self,
+        environment_chain: &[Environment],
+        active_cookie_jar_id: Option<&str>,
+    ) -> Result<Option<CookieJar>> {
+        for id in environment_chain.iter().filter_map(|e| e.cookie_jar_id.as_deref()) {
+            if let Some(cookie_jar) = self.find_optional(CookieJarIden::Id, id) {
+                return Ok(Some(cookie_jar));
+            }
+        }
+
+        match active_cookie_jar_id {
+            Some(id) => self.get_cookie_jar(id).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn list_cookies(&self, cookie_jar_id: &str) -> Result<Vec<Cookie>> {
+        Ok(self.get_cookie_jar(cookie_jar_id)?.cookies)
+    }
+
+    /// Add a cookie to the jar, replacing any cookie with the same name, domain, and path. When
+    /// editing a cookie whose identity changed, `previous` is the cookie being replaced.
+    pub fn upsert_cookie(
+        &self,
+        cookie_jar_id: &str,
+        cookie: &Cookie,
+        previous: Option<&Cookie>,
+        source: &UpdateSource,
+    ) -> Result<CookieJar> {
+        let mut cookie_jar = self.get_cookie_jar(cookie_jar_id)?;
+        cookie_jar.cookies.retain(|c| {
+            !c.is_same_cookie(cookie) && !previous.is_some_and(|p| c.is_same_cookie(p))
+        });
+        cookie_jar.cookies.push(cookie.clone());
+        self.upsert_cookie_jar(&cookie_jar, source)
+    }
+
+    /// Add several cookies to the jar at once, replacing any with the same name, domain, and path
+    pub fn upsert_cookies(
+        &self,
+        cookie_jar_id: &str,
+        cookies: &[Cookie],
+        source: &UpdateSource,
+    ) -> Result<CookieJar> {
+        let mut cookie_jar = self.get_cookie_jar(cookie_jar_id)?;
+        for cookie in cookies {
+            cookie_jar.cookies.retain(|c| !c.is_same_cookie(cookie));
+            cookie_jar.cookies.push(cookie.clone());
+        }
+        self.upsert_cookie_jar(&cookie_jar, source)
+    }
+
+    pub fn delete_cookie(
+        &self,
+        cookie_jar_id: &str,
+        cookie: &Cookie,
+        source: &UpdateSource,
+    ) -> Result<CookieJar> {
+        let mut cookie_jar = self.get_cookie_jar(cookie_jar_id)?;
+        cookie_jar.cookies.retain(|c| !c.is_same_cookie(cookie));
+        self.upsert_cookie_jar(&cookie_jar, source)
+    }
+
+    pub fn clear_cookies(&self, cookie_jar_id: &str, source: &UpdateSource) -> Result<CookieJar> {
+        let mut cookie_jar = self.get_cookie_jar(cookie_jar_id)?;
+        cookie_jar.cookies.clear();
+        self.upsert_cookie_jar(&cookie_jar, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Cookie, CookieDomain, CookieExpires, CookieJar, Environment, Workspace};
+    use crate::util::UpdateSource;
+
+    fn cookie(name: &str, value: &str, domain: &str) -> Cookie {
+        Cookie {
+            name: name.to_string(),
+            value: value.to_string(),
+            domain: CookieDomain::HostOnly(domain.to_string()),
+            expires: CookieExpires::SessionEnd,
+            path: "/".to_string(),
+            secure: false,
+            http_only: false,
+            same_site: None,
+        }
+    }
+
+    #[test]
+    fn adds_edits_and_deletes_cookies() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Cookies".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let jar = db.list_cookie_jars(&workspace.id).unwrap().remove(0);
+
+        let session = cookie("session", "a", "yaak.app");
+        db.upsert_cookie(&jar.id, &session, None, source).unwrap();
+        db.upsert_cookie(&jar.id, &cookie("theme", "dark", "yaak.app"), None, source).unwrap();
+        db.upsert_cookie(&jar.id, &cookie("session", "b", "yaak.app"), None, source).unwrap();
+        db.upsert_cookie(&jar.id, &cookie("session", "c", "api.yaak.app"), None, source).unwrap();
+
+        let values: Vec<_> =
+            db.list_cookies(&jar.id).unwrap().into_iter().map(|c| c.value).collect();
+        assert_eq!(values, vec!["dark", "b", "c"]);
+
+        // Renaming a cookie replaces the original
+        let renamed = cookie("sid", "b", "yaak.app");
+        db.upsert_cookie(&jar.id, &renamed, Some(&session), source).unwrap();
+        let names: Vec<_> = db.list_cookies(&jar.id).unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["theme", "session", "sid"]);
+
+        let imported = [
+            cookie("theme", "light", "yaak.app"),
+            cookie("lang", "en", "yaak.app"),
+        ];
+        db.upsert_cookies(&jar.id, &imported, source).unwrap();
+        let names: Vec<_> = db.list_cookies(&jar.id).unwrap().into_iter().map(|c| c.name).collect();
+        assert_eq!(names, vec!["session", "sid", "theme", "lang"]);
+
+        let jar = db.delete_cookie(&jar.id, &renamed, source).unwrap();
+        assert_eq!(jar.cookies.len(), 3);
+
+        let jar = db.clear_cookies(&jar.id, source).unwrap();
+        assert!(jar.cookies.is_empty());
+    }
+
+    #[test]
+    fn resolves_cookie_jar_from_environments() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "Jars".to_string(), ..Default::default() }, source)
+            .expect("Failed to upsert workspace");
+        let active = db.list_cookie_jars(&workspace.id).unwrap().remove(0);
+        let admin = db
+            .upsert_cookie_jar(
+                &CookieJar {
+                    name: "Admin".to_string(),
+                    workspace_id: workspace.id.clone(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+
+        let base = Environment { workspace_id: workspace.id.clone(), ..Default::default() };
+        let sub = Environment { cookie_jar_id: Some(admin.id.clone()), ..base.clone() };
+        let missing = Environment { cookie_jar_id: Some("cj_missing".to_string()), ..base.clone() };
+
+        let resolve = |chain: &[Environment]| {
+            db.resolve_cookie_jar(chain, Some(&active.id)).unwrap().map(|j| j.id)
+        };
+        assert_eq!(resolve(&[base.clone()]), Some(active.id.clone()));
+        assert_eq!(resolve(&[sub.clone(), base.clone()]), Some(admin.id.clone()));
+        assert_eq!(resolve(&[missing, base]), Some(active.id.clone()));
+        assert_eq!(db.resolve_cookie_jar(&[sub], None).unwrap().map(|j| j.id), Some(admin.id));
+    }
 }
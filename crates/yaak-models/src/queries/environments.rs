@@ -148,6 +148,32 @@ impl<'a> ClientDb<'a> {
         )
     }
 
+    /// Set a variable in an environment, updating the first variable with the same name or
+    /// adding a new one
+    pub fn set_environment_variable(
+        &self,
+        environment: &Environment,
+        name: &str,
+        value: &str,
+        source: &UpdateSource,
+    ) -> Result<Environment> {
+        let mut environment = environment.clone();
+        match environment.variables.iter_mut().find(|v| v.name == name) {
+            Some(variable) => {
+                variable.value = value.to_string();
+                variable.enabled = true;
+            }
+            None => environment.variables.push(EnvironmentVariable {
+                enabled: true,
+                name: name.to_string(),
+                value: value.to_string(),
+                id: None,
+                secret: false,
+            }),
+        }
+        self.upsert_environment(&environment, source)
+    }
+
     pub fn resolve_environments(
         &self,
         workspace_id: &str,
@@ -182,6 +208,9 @@ impl<'a> ClientDb<'a> {
 
             // Add the base environment
             environments.push(self.get_base_environment(workspace_id)?);
+
+            // Add values extracted from responses, which everything else overrides
+            environments.extend(self.chained_values_environment(workspace_id)?);
         }
 
         Ok(environments)
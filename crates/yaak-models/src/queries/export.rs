@@ -0,0 +1,166 @@
+use crate::client_db::ClientDb;
+use crate::error::Error::ModelSerializationError;
+use crate::error::Result;
+use crate::models::{CookieJar, CookieJarIden};
+use crate::util::{
+    BatchUpsertResult, UpdateSource, WorkspaceExport, get_workspace_export_resources,
+    redact_secret_variables,
+};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_util.ts")]
+pub enum WorkspaceExportFormat {
+    #[default]
+    Json,
+    Yaml,
+}
+
+impl<'a> ClientDb<'a> {
+    /// Serialize a workspace into a single versioned document that can be re-imported on another
+    /// machine. Folders, requests, environments, and cookie jars are included, while responses and
+    /// trashed items are not.
+    pub fn export_workspace(
+        &self,
+        workspace_id: &str,
+        yaak_version: &str,
+        format: WorkspaceExportFormat,
+        include_private_environments: bool,
+    ) -> Result<String> {
+        let mut export = get_workspace_export_resources(
+            self,
+            yaak_version,
+            vec![workspace_id],
+            include_private_environments,
+        )?;
+        export.resources.cookie_jars =
+            self.find_many::<CookieJar>(CookieJarIden::WorkspaceId, workspace_id, None)?;
+        redact_secret_variables(&mut export.resources.environments);
+
+        Ok(match format {
+            WorkspaceExportFormat::Json => serde_json::to_string_pretty(&export)?,
+            WorkspaceExportFormat::Yaml => serde_yaml::to_string(&export)
+                .map_err(|e| ModelSerializationError(e.to_string()))?,
+        })
+    }
+
+    /// Load a JSON or YAML document written by [`Self::export_workspace`], keeping its IDs so the
+    /// references between its models stay intact
+    pub fn import_workspace(
+        &self,
+        contents: &str,
+        source: &UpdateSource,
+    ) -> Result<BatchUpsertResult> {
+        let export: WorkspaceExport = if contents.trim_start().starts_with('{') {
+            serde_json::from_str(contents)?
+        } else {
+            serde_yaml::from_str(contents).map_err(|e| ModelSerializationError(e.to_string()))?
+        };
+
+        self.batch_upsert(export.resources, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_in_memory;
+    use crate::models::{EnvironmentVariable, HttpRequest, Workspace};
+
+    #[test]
+    fn exports_workspace_as_json_and_yaml() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Export Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        db.upsert_http_request(
+            &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+            source,
+        )
+        .expect("Failed to upsert request");
+        db.upsert_cookie_jar(
+            &CookieJar {
+                workspace_id: workspace.id.clone(),
+                name: "Jar".to_string(),
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert cookie jar");
+
+        let json = db
+            .export_workspace(&workspace.id, "1.0.0", WorkspaceExportFormat::Json, false)
+            .expect("Failed to export JSON");
+        let yaml = db
+            .export_workspace(&workspace.id, "1.0.0", WorkspaceExportFormat::Yaml, false)
+            .expect("Failed to export YAML");
+
+        let from_json: WorkspaceExport = serde_json::from_str(&json).unwrap();
+        let from_yaml: WorkspaceExport = serde_yaml::from_str(&yaml).unwrap();
+        for export in [from_json, from_yaml] {
+            assert_eq!(export.yaak_version, "1.0.0");
+            assert_eq!(export.resources.workspaces.len(), 1);
+            assert_eq!(export.resources.http_requests.len(), 1);
+            assert_eq!(export.resources.cookie_jars.len(), 1);
+        }
+
+        for document in [json, yaml] {
+            let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+            let db = query_manager.connect();
+            let imported = db.import_workspace(&document, source).expect("Failed to import");
+            assert_eq!(imported.workspaces[0].id, workspace.id);
+            assert_eq!(db.list_http_requests(&workspace.id).unwrap().len(), 1);
+            assert_eq!(db.list_cookie_jars(&workspace.id).unwrap().len(), 1);
+        }
+    }
+
+    #[test]
+    fn redacts_secret_variables() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Export Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let mut base = db.get_base_environment(&workspace.id).unwrap();
+        base.variables = vec![
+            EnvironmentVariable {
+                enabled: true,
+                name: "host".to_string(),
+                value: "example.com".to_string(),
+                ..Default::default()
+            },
+            EnvironmentVariable {
+                enabled: true,
+                name: "token".to_string(),
+                value: "YENC_abc=".to_string(),
+                secret: true,
+                ..Default::default()
+            },
+        ];
+        db.upsert_environment(&base, source).unwrap();
+
+        let json = db
+            .export_workspace(&workspace.id, "1.0.0", WorkspaceExportFormat::Json, true)
+            .expect("Failed to export JSON");
+        assert!(!json.contains("YENC_"));
+
+        let export: WorkspaceExport = serde_json::from_str(&json).unwrap();
+        let variables = &export.resources.environments[0].variables;
+        assert_eq!(variables[0].value, "example.com");
+        assert_eq!(variables[1].value, "");
+        assert!(variables[1].secret);
+    }
+}
@@ -1,12 +1,15 @@
+use super::merge_url_parameters;
 use crate::client_db::ClientDb;
 use crate::connection_or_tx::ConnectionOrTx;
 use crate::error::Result;
 use crate::models::{
     AnyModel, Environment, EnvironmentIden, Folder, FolderIden, GrpcRequest, GrpcRequestIden,
-    HttpRequest, HttpRequestHeader, HttpRequestIden, ResolvedHttpRequestSettings, ResolvedSetting,
-    WebsocketRequest, WebsocketRequestIden,
+    HttpRequest, HttpRequestHeader, HttpRequestIden, HttpUrlParameter, Monitor, MonitorIden,
+    MqttRequest, MqttRequestIden, ResolvedHttpRequestSettings, ResolvedSetting, SocketRequest,
+    SocketRequestIden, WebsocketRequest, WebsocketRequestIden,
 };
 use crate::util::UpdateSource;
+use chrono::{NaiveDateTime, Utc};
 use serde_json::Value;
 use std::collections::BTreeMap;
 
@@ -16,7 +19,8 @@ impl<'a> ClientDb<'a> {
     }
 
     pub fn list_folders(&self, workspace_id: &str) -> Result<Vec<Folder>> {
-        self.find_many(FolderIden::WorkspaceId, workspace_id, None)
+        let folders: Vec<Folder> = self.find_many(FolderIden::WorkspaceId, workspace_id, None)?;
+        Ok(folders.into_iter().filter(|f| f.deleted_at.is_none()).collect())
     }
 
     pub fn delete_folder(&self, folder: &Folder, source: &UpdateSource) -> Result<Folder> {
@@ -38,10 +42,22 @@ impl<'a> ClientDb<'a> {
             self.delete_websocket_request(&m, source)?;
         }
 
+        for m in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, fid, None)? {
+            self.delete_mqtt_request(&m, source)?;
+        }
+
+        for m in self.find_many::<SocketRequest>(SocketRequestIden::FolderId, fid, None)? {
+            self.delete_socket_request(&m, source)?;
+        }
+
         for e in self.find_many(EnvironmentIden::ParentId, fid, None)? {
             self.delete_environment(&e, source)?;
         }
 
+        for m in self.find_many::<Monitor>(MonitorIden::FolderId, fid, None)? {
+            self.delete_monitor(&m, source)?;
+        }
+
         // Recurse down into child folders
         for folder in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
             self.delete_folder(&folder, source)?;
@@ -59,19 +75,168 @@ impl<'a> ClientDb<'a> {
         self.upsert(folder, source)
     }
 
+    /// Move a folder and everything inside it to the trash. All descendants share the folder's
+    /// timestamp so that restoring the folder brings back exactly what was trashed with it.
+    pub fn trash_folder(&self, folder: &Folder, source: &UpdateSource) -> Result<Folder> {
+        self.trash_folder_at(folder, Utc::now().naive_utc(), source)
+    }
+
+    fn trash_folder_at(
+        &self,
+        folder: &Folder,
+        at: NaiveDateTime,
+        source: &UpdateSource,
+    ) -> Result<Folder> {
+        let fid = &folder.id;
+        let deleted_at = Some(at);
+
+        for m in self.find_many::<HttpRequest>(HttpRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.upsert(&HttpRequest { deleted_at, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<GrpcRequest>(GrpcRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.upsert(&GrpcRequest { deleted_at, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<WebsocketRequest>(WebsocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.upsert(&WebsocketRequest { deleted_at, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.upsert(&MqttRequest { deleted_at, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<SocketRequest>(SocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.upsert(&SocketRequest { deleted_at, ..m }, source)?;
+            }
+        }
+
+        // Recurse down into child folders
+        for m in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
+            if m.deleted_at.is_none() {
+                self.trash_folder_at(&m, at, source)?;
+            }
+        }
+
+        self.upsert(&Folder { deleted_at, ..folder.clone() }, source)
+    }
+
+    /// Restore a folder from the trash, along with any trashed parent folders and the children
+    /// that were trashed together with it.
+    pub fn restore_folder(&self, folder: &Folder, source: &UpdateSource) -> Result<Folder> {
+        if let Some(parent_id) = folder.folder_id.as_deref() {
+            self.restore_folder_ancestors(parent_id, source)?;
+        }
+        if let Some(deleted_at) = folder.deleted_at {
+            self.restore_folder_children(&folder.id, deleted_at, source)?;
+        }
+        self.upsert(&Folder { deleted_at: None, ..folder.clone() }, source)
+    }
+
+    fn restore_folder_children(
+        &self,
+        fid: &str,
+        at: NaiveDateTime,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        let deleted_at = Some(at);
+
+        for m in self.find_many::<HttpRequest>(HttpRequestIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.upsert(&HttpRequest { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<GrpcRequest>(GrpcRequestIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.upsert(&GrpcRequest { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<WebsocketRequest>(WebsocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.upsert(&WebsocketRequest { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.upsert(&MqttRequest { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<SocketRequest>(SocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.upsert(&SocketRequest { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        for m in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
+            if m.deleted_at == deleted_at {
+                self.restore_folder_children(&m.id, at, source)?;
+                self.upsert(&Folder { deleted_at: None, ..m }, source)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Walk up from the given folder, restoring any folder that is still in the trash.
+    pub(crate) fn restore_folder_ancestors(
+        &self,
+        folder_id: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        let folder = self.get_folder(folder_id)?;
+        if folder.deleted_at.is_none() {
+            return Ok(());
+        }
+        if let Some(parent_id) = folder.folder_id.as_deref() {
+            self.restore_folder_ancestors(parent_id, source)?;
+        }
+        self.upsert(&Folder { deleted_at: None, ..folder }, source)?;
+        Ok(())
+    }
+
+    /// Deep-copy a folder, including all nested folders, requests, and folder environments. The
+    /// copy is placed directly after the original among its siblings.
     pub fn duplicate_folder(&self, src_folder: &Folder, source: &UpdateSource) -> Result<Folder> {
+        let sort_priority = self.sort_priority_after(
+            &src_folder.workspace_id,
+            src_folder.folder_id.as_deref(),
+            &src_folder.id,
+            source,
+        )?;
+        self.duplicate_folder_into(src_folder, src_folder.folder_id.clone(), sort_priority, source)
+    }
+
+    fn duplicate_folder_into(
+        &self,
+        src_folder: &Folder,
+        folder_id: Option<String>,
+        sort_priority: f64,
+        source: &UpdateSource,
+    ) -> Result<Folder> {
         let fid = &src_folder.id;
 
         let new_folder = self.upsert_folder(
-            &Folder {
-                id: "".into(),
-                sort_priority: src_folder.sort_priority + 0.001,
-                ..src_folder.clone()
-            },
+            &Folder { id: "".into(), folder_id, sort_priority, ..src_folder.clone() },
             source,
         )?;
 
         for m in self.find_many::<HttpRequest>(HttpRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
             self.upsert_http_request(
                 &HttpRequest { id: "".into(), folder_id: Some(new_folder.id.clone()), ..m },
                 source,
@@ -79,13 +244,39 @@ impl<'a> ClientDb<'a> {
         }
 
         for m in self.find_many::<WebsocketRequest>(WebsocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
             self.upsert_websocket_request(
                 &WebsocketRequest { id: "".into(), folder_id: Some(new_folder.id.clone()), ..m },
                 source,
             )?;
         }
 
+        for m in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            self.upsert_mqtt_request(
+                &MqttRequest { id: "".into(), folder_id: Some(new_folder.id.clone()), ..m },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<SocketRequest>(SocketRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            self.upsert_socket_request(
+                &SocketRequest { id: "".into(), folder_id: Some(new_folder.id.clone()), ..m },
+                source,
+            )?;
+        }
+
         for m in self.find_many::<GrpcRequest>(GrpcRequestIden::FolderId, fid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
             self.upsert_grpc_request(
                 &GrpcRequest { id: "".into(), folder_id: Some(new_folder.id.clone()), ..m },
                 source,
@@ -100,8 +291,11 @@ impl<'a> ClientDb<'a> {
         }
 
         for m in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
-            // Recurse down
-            self.duplicate_folder(&Folder { folder_id: Some(new_folder.id.clone()), ..m }, source)?;
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            // Recurse down, keeping the child's position within its (new) parent
+            self.duplicate_folder_into(&m, Some(new_folder.id.clone()), m.sort_priority, source)?;
         }
 
         Ok(new_folder)
@@ -143,6 +337,21 @@ impl<'a> ClientDb<'a> {
         Ok(headers)
     }
 
+    pub fn resolve_query_params_for_folder(
+        &self,
+        folder: &Folder,
+    ) -> Result<Vec<HttpUrlParameter>> {
+        let inherited = if let Some(folder_id) = folder.folder_id.clone() {
+            let parent_folder = self.get_folder(&folder_id)?;
+            self.resolve_query_params_for_folder(&parent_folder)?
+        } else {
+            let workspace = self.get_workspace(&folder.workspace_id)?;
+            self.resolve_query_params_for_workspace(&workspace)
+        };
+
+        Ok(merge_url_parameters(inherited, &folder.url_parameters))
+    }
+
     pub fn resolve_settings_for_folder(
         &self,
         folder: &Folder,
@@ -172,6 +381,38 @@ impl<'a> ClientDb<'a> {
             } else {
                 parent.follow_redirects
             },
+            max_redirects: if folder.setting_max_redirects.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_max_redirects.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.max_redirects
+            },
+            redirect_resend_body: if folder.setting_redirect_resend_body.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_redirect_resend_body.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.redirect_resend_body
+            },
+            redirect_strip_authorization: if folder.setting_redirect_strip_authorization.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_redirect_strip_authorization.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.redirect_strip_authorization
+            },
+            decompress_response: if folder.setting_decompress_response.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_decompress_response.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.decompress_response
+            },
             request_timeout: if folder.setting_request_timeout.enabled {
                 ResolvedSetting::from_model(
                     folder.setting_request_timeout.value,
@@ -180,6 +421,62 @@ impl<'a> ClientDb<'a> {
             } else {
                 parent.request_timeout
             },
+            connect_timeout: if folder.setting_connect_timeout.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_connect_timeout.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.connect_timeout
+            },
+            read_timeout: if folder.setting_read_timeout.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_read_timeout.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.read_timeout
+            },
+            http_version: if folder.setting_http_version.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_http_version.value,
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.http_version
+            },
+            retry_policy: if folder.setting_retry_policy.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_retry_policy.value.clone(),
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.retry_policy
+            },
+            ssh_tunnel: if folder.setting_ssh_tunnel.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_ssh_tunnel.value.clone(),
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.ssh_tunnel
+            },
+            ca_certificates: if folder.setting_ca_certificates.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_ca_certificates.value.clone(),
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.ca_certificates
+            },
+            client_certificate: if folder.setting_client_certificate.enabled {
+                ResolvedSetting::from_model(
+                    folder.setting_client_certificate.value.clone(),
+                    AnyModel::Folder(folder.clone()),
+                )
+            } else {
+                parent.client_certificate
+            },
             send_cookies: if folder.setting_send_cookies.enabled {
                 ResolvedSetting::from_model(
                     folder.setting_send_cookies.value,
@@ -1,9 +1,9 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
-use crate::models::{GraphQlIntrospection, GraphQlIntrospectionIden};
+use crate::models::{GraphQlIntrospection, GraphQlIntrospectionIden, UpsertModelInfo};
 use crate::util::UpdateSource;
 use chrono::{Duration, Utc};
-use sea_query::{Expr, Query, SqliteQueryBuilder};
+use sea_query::{Asterisk, Cond, Expr, Order, Query, SqliteQueryBuilder};
 use sea_query_rusqlite::RusqliteBinder;
 
 impl<'a> ClientDb<'a> {
@@ -11,10 +11,36 @@ impl<'a> ClientDb<'a> {
         self.find_optional(GraphQlIntrospectionIden::RequestId, request_id)
     }
 
+    /// Get the most recent introspected schema for an endpoint URL, regardless of which request
+    /// in the workspace it was introspected from
+    pub fn get_graphql_introspection_for_url(
+        &self,
+        workspace_id: &str,
+        url: &str,
+    ) -> Option<GraphQlIntrospection> {
+        let (sql, params) = Query::select()
+            .from(GraphQlIntrospectionIden::Table)
+            .column(Asterisk)
+            .cond_where(
+                Cond::all()
+                    .add(Expr::col(GraphQlIntrospectionIden::WorkspaceId).eq(workspace_id))
+                    .add(Expr::col(GraphQlIntrospectionIden::Url).eq(url))
+                    .add(Expr::col(GraphQlIntrospectionIden::Content).is_not_null()),
+            )
+            .order_by(GraphQlIntrospectionIden::UpdatedAt, Order::Desc)
+            .limit(1)
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn()
+            .resolve()
+            .query_row(sql.as_str(), &*params.as_params(), GraphQlIntrospection::from_row)
+            .ok()
+    }
+
     pub fn upsert_graphql_introspection(
         &self,
         workspace_id: &str,
         request_id: &str,
+        url: Option<String>,
         content: Option<String>,
         source: &UpdateSource,
     ) -> Result<GraphQlIntrospection> {
@@ -25,6 +51,7 @@ impl<'a> ClientDb<'a> {
             None => self.upsert(
                 &GraphQlIntrospection {
                     content,
+                    url,
                     request_id: request_id.to_string(),
                     workspace_id: workspace_id.to_string(),
                     ..Default::default()
@@ -32,7 +59,7 @@ impl<'a> ClientDb<'a> {
                 source,
             ),
             Some(introspection) => {
-                self.upsert(&GraphQlIntrospection { content, ..introspection }, source)
+                self.upsert(&GraphQlIntrospection { content, url, ..introspection }, source)
             }
         }
     }
@@ -49,3 +76,61 @@ impl<'a> ClientDb<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn shares_introspection_between_requests_with_the_same_url() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "GraphQL".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    url: "https://api.example.com/graphql".to_string(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let url = "https://api.example.com/graphql";
+        assert!(db.get_graphql_introspection_for_url(&workspace.id, url).is_none());
+
+        db.upsert_graphql_introspection(
+            &workspace.id,
+            &request.id,
+            Some(url.to_string()),
+            Some("{\"data\":{}}".to_string()),
+            source,
+        )
+        .unwrap();
+        let cached = db.get_graphql_introspection_for_url(&workspace.id, url).unwrap();
+        assert_eq!(cached.request_id, request.id);
+        assert_eq!(cached.content.as_deref(), Some("{\"data\":{}}"));
+        assert!(db.get_graphql_introspection_for_url(&workspace.id, "https://other").is_none());
+
+        // Cleared introspections are never shared
+        db.upsert_graphql_introspection(
+            &workspace.id,
+            &request.id,
+            Some(url.to_string()),
+            None,
+            source,
+        )
+        .unwrap();
+        assert!(db.get_graphql_introspection_for_url(&workspace.id, url).is_none());
+    }
+}
@@ -66,10 +66,14 @@ impl<'a> ClientDb<'a> {
 
     pub fn cancel_pending_grpc_connections(&self) -> Result<()> {
         let closed = serde_json::to_value(&GrpcConnectionState::Closed)?;
+        let timed_out = serde_json::to_value(&GrpcConnectionState::TimedOut)?;
         let (sql, params) = Query::update()
             .table(GrpcConnectionIden::Table)
             .values([(GrpcConnectionIden::State, closed.as_str().into())])
-            .cond_where(Expr::col(GrpcConnectionIden::State).ne(closed.as_str()))
+            .cond_where(
+                Expr::col(GrpcConnectionIden::State)
+                    .is_not_in([closed.as_str(), timed_out.as_str()]),
+            )
             .build_rusqlite(SqliteQueryBuilder);
         let mut stmt = self.conn().prepare(sql.as_str())?;
         stmt.execute(&*params.as_params())?;
@@ -0,0 +1,133 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{GrpcDescriptorCache, GrpcDescriptorCacheIden};
+use chrono::{Duration, Utc};
+use sea_query::Keyword::CurrentTimestamp;
+use sea_query::{Asterisk, Cond, Expr, OnConflict, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+impl<'a> ClientDb<'a> {
+    /// Get the cached descriptor set for a host and TLS configuration, unless it has expired
+    pub fn get_grpc_descriptor_cache(
+        &self,
+        host: &str,
+        tls_key: &str,
+    ) -> Option<GrpcDescriptorCache> {
+        let now = Utc::now().naive_utc();
+        let (sql, params) = Query::select()
+            .from(GrpcDescriptorCacheIden::Table)
+            .column(Asterisk)
+            .cond_where(
+                Cond::all()
+                    .add(Expr::col(GrpcDescriptorCacheIden::Host).eq(host))
+                    .add(Expr::col(GrpcDescriptorCacheIden::TlsKey).eq(tls_key))
+                    .add(Expr::col(GrpcDescriptorCacheIden::ExpiresAt).gt(now)),
+            )
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn()
+            .resolve()
+            .query_row(sql.as_str(), &*params.as_params(), |row| row.try_into())
+            .ok()
+    }
+
+    pub fn set_grpc_descriptor_cache(
+        &self,
+        host: &str,
+        tls_key: &str,
+        descriptor_set: &[u8],
+        ttl: Duration,
+    ) -> Result<GrpcDescriptorCache> {
+        // Clean up expired entries every time a new one is stored
+        self.delete_expired_grpc_descriptor_caches()?;
+
+        let expires_at = Utc::now().naive_utc() + ttl;
+        let (sql, params) = Query::insert()
+            .into_table(GrpcDescriptorCacheIden::Table)
+            .columns([
+                GrpcDescriptorCacheIden::CreatedAt,
+                GrpcDescriptorCacheIden::UpdatedAt,
+                GrpcDescriptorCacheIden::Host,
+                GrpcDescriptorCacheIden::TlsKey,
+                GrpcDescriptorCacheIden::DescriptorSet,
+                GrpcDescriptorCacheIden::ExpiresAt,
+            ])
+            .values_panic([
+                CurrentTimestamp.into(),
+                CurrentTimestamp.into(),
+                host.into(),
+                tls_key.into(),
+                descriptor_set.to_vec().into(),
+                expires_at.into(),
+            ])
+            .on_conflict(
+                OnConflict::columns([
+                    GrpcDescriptorCacheIden::Host,
+                    GrpcDescriptorCacheIden::TlsKey,
+                ])
+                .update_columns([
+                    GrpcDescriptorCacheIden::UpdatedAt,
+                    GrpcDescriptorCacheIden::DescriptorSet,
+                    GrpcDescriptorCacheIden::ExpiresAt,
+                ])
+                .to_owned(),
+            )
+            .returning_all()
+            .build_rusqlite(SqliteQueryBuilder);
+
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        Ok(stmt.query_row(&*params.as_params(), |row| row.try_into())?)
+    }
+
+    /// Invalidate every cached descriptor set for a host, regardless of TLS configuration
+    pub fn delete_grpc_descriptor_caches(&self, host: &str) -> Result<usize> {
+        let (sql, params) = Query::delete()
+            .from_table(GrpcDescriptorCacheIden::Table)
+            .cond_where(Expr::col(GrpcDescriptorCacheIden::Host).eq(host))
+            .build_rusqlite(SqliteQueryBuilder);
+        Ok(self.conn().execute(sql.as_str(), &*params.as_params())?)
+    }
+
+    pub fn delete_expired_grpc_descriptor_caches(&self) -> Result<()> {
+        let now = Utc::now().naive_utc();
+        let (sql, params) = Query::delete()
+            .from_table(GrpcDescriptorCacheIden::Table)
+            .cond_where(Expr::col(GrpcDescriptorCacheIden::ExpiresAt).lte(now))
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn().execute(sql.as_str(), &*params.as_params())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use chrono::Duration;
+
+    #[test]
+    fn caches_descriptor_sets_by_host_and_tls_key() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+
+        db.set_grpc_descriptor_cache("localhost:50051", "tls_a", b"first", Duration::hours(1))
+            .unwrap();
+        db.set_grpc_descriptor_cache("localhost:50051", "tls_b", b"other", Duration::hours(1))
+            .unwrap();
+        let updated = db
+            .set_grpc_descriptor_cache("localhost:50051", "tls_a", b"second", Duration::hours(1))
+            .unwrap();
+        assert_eq!(updated.descriptor_set, b"second");
+
+        let cached = db.get_grpc_descriptor_cache("localhost:50051", "tls_a").unwrap();
+        assert_eq!(cached.descriptor_set, b"second");
+        assert!(db.get_grpc_descriptor_cache("localhost:50051", "tls_c").is_none());
+
+        // Expired entries are never returned
+        db.set_grpc_descriptor_cache("example.com:443", "tls_a", b"stale", Duration::hours(-1))
+            .unwrap();
+        assert!(db.get_grpc_descriptor_cache("example.com:443", "tls_a").is_none());
+
+        // Invalidation drops every TLS configuration for the host
+        assert_eq!(db.delete_grpc_descriptor_caches("localhost:50051").unwrap(), 2);
+        assert!(db.get_grpc_descriptor_cache("localhost:50051", "tls_b").is_none());
+    }
+}
@@ -2,10 +2,11 @@ use super::dedupe_headers;
 use crate::client_db::ClientDb;
 use crate::error::Result;
 use crate::models::{
-    AnyModel, Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequestHeader,
-    ResolvedHttpRequestSettings, ResolvedSetting,
+    AnyModel, Folder, FolderIden, GrpcMessageTemplate, GrpcRequest, GrpcRequestIden,
+    HttpRequestHeader, ResolvedHttpRequestSettings, ResolvedSetting,
 };
-use crate::util::UpdateSource;
+use crate::util::{UpdateSource, generate_id};
+use chrono::Utc;
 use serde_json::Value;
 use std::collections::BTreeMap;
 
@@ -15,7 +16,9 @@ impl<'a> ClientDb<'a> {
     }
 
     pub fn list_grpc_requests(&self, workspace_id: &str) -> Result<Vec<GrpcRequest>> {
-        self.find_many(GrpcRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<GrpcRequest> =
+            self.find_many(GrpcRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
     }
 
     pub fn list_grpc_requests_for_folder_recursive(
@@ -27,7 +30,9 @@ impl<'a> ClientDb<'a> {
             children.extend(self.list_grpc_requests_for_folder_recursive(&folder.id)?);
         }
         for request in self.find_many::<GrpcRequest>(GrpcRequestIden::FolderId, folder_id, None)? {
-            children.push(request);
+            if request.deleted_at.is_none() {
+                children.push(request);
+            }
         }
         Ok(children)
     }
@@ -38,6 +43,7 @@ impl<'a> ClientDb<'a> {
         source: &UpdateSource,
     ) -> Result<GrpcRequest> {
         self.delete_all_grpc_connections_for_request(m.id.as_str(), source)?;
+        self.delete_request_revisions(m.id.as_str())?;
         self.delete(m, source)
     }
 
@@ -50,6 +56,28 @@ impl<'a> ClientDb<'a> {
         self.delete_grpc_request(&request, source)
     }
 
+    pub fn trash_grpc_request(
+        &self,
+        grpc_request: &GrpcRequest,
+        source: &UpdateSource,
+    ) -> Result<GrpcRequest> {
+        self.upsert(
+            &GrpcRequest { deleted_at: Some(Utc::now().naive_utc()), ..grpc_request.clone() },
+            source,
+        )
+    }
+
+    pub fn restore_grpc_request(
+        &self,
+        grpc_request: &GrpcRequest,
+        source: &UpdateSource,
+    ) -> Result<GrpcRequest> {
+        if let Some(folder_id) = grpc_request.folder_id.as_deref() {
+            self.restore_folder_ancestors(folder_id, source)?;
+        }
+        self.upsert(&GrpcRequest { deleted_at: None, ..grpc_request.clone() }, source)
+    }
+
     pub fn duplicate_grpc_request(
         &self,
         grpc_request: &GrpcRequest,
@@ -57,7 +85,12 @@ impl<'a> ClientDb<'a> {
     ) -> Result<GrpcRequest> {
         let mut request = grpc_request.clone();
         request.id = "".to_string();
-        request.sort_priority = request.sort_priority + 0.001;
+        request.sort_priority = self.sort_priority_after(
+            &grpc_request.workspace_id,
+            grpc_request.folder_id.as_deref(),
+            &grpc_request.id,
+            source,
+        )?;
         self.upsert(&request, source)
     }
 
@@ -66,7 +99,54 @@ impl<'a> ClientDb<'a> {
         grpc_request: &GrpcRequest,
         source: &UpdateSource,
     ) -> Result<GrpcRequest> {
-        self.upsert(grpc_request, source)
+        let grpc_request = self.upsert(grpc_request, source)?;
+        self.record_request_revision(&grpc_request.clone().into(), source)?;
+        Ok(grpc_request)
+    }
+
+    /// Save a named message on the request so it can be replayed during streaming sessions
+    pub fn append_grpc_message_template(
+        &self,
+        request_id: &str,
+        name: &str,
+        message: &str,
+        source: &UpdateSource,
+    ) -> Result<GrpcRequest> {
+        let mut request = self.get_grpc_request(request_id)?;
+        request.message_templates.push(GrpcMessageTemplate {
+            id: Some(generate_id()),
+            name: name.to_string(),
+            message: message.to_string(),
+        });
+        self.upsert_grpc_request(&request, source)
+    }
+
+    /// Reorder the request's message templates to match the given IDs. Templates that aren't
+    /// listed keep their relative order after the listed ones.
+    pub fn reorder_grpc_message_templates(
+        &self,
+        request_id: &str,
+        template_ids: &[String],
+        source: &UpdateSource,
+    ) -> Result<GrpcRequest> {
+        let mut request = self.get_grpc_request(request_id)?;
+        request.message_templates.sort_by_key(|t| {
+            t.id.as_ref()
+                .and_then(|id| template_ids.iter().position(|i| i == id))
+                .unwrap_or(template_ids.len())
+        });
+        self.upsert_grpc_request(&request, source)
+    }
+
+    pub fn delete_grpc_message_template(
+        &self,
+        request_id: &str,
+        template_id: &str,
+        source: &UpdateSource,
+    ) -> Result<GrpcRequest> {
+        let mut request = self.get_grpc_request(request_id)?;
+        request.message_templates.retain(|t| t.id.as_deref() != Some(template_id));
+        self.upsert_grpc_request(&request, source)
     }
 
     pub fn resolve_auth_for_grpc_request(
@@ -129,7 +209,219 @@ impl<'a> ClientDb<'a> {
             } else {
                 parent.validate_certificates
             },
+            request_timeout: if grpc_request.setting_request_timeout.enabled {
+                ResolvedSetting::from_model(
+                    grpc_request.setting_request_timeout.value,
+                    AnyModel::GrpcRequest(grpc_request.clone()),
+                )
+            } else {
+                parent.request_timeout
+            },
+            connect_timeout: if grpc_request.setting_connect_timeout.enabled {
+                ResolvedSetting::from_model(
+                    grpc_request.setting_connect_timeout.value,
+                    AnyModel::GrpcRequest(grpc_request.clone()),
+                )
+            } else {
+                parent.connect_timeout
+            },
+            read_timeout: if grpc_request.setting_read_timeout.enabled {
+                ResolvedSetting::from_model(
+                    grpc_request.setting_read_timeout.value,
+                    AnyModel::GrpcRequest(grpc_request.clone()),
+                )
+            } else {
+                parent.read_timeout
+            },
+            ca_certificates: if grpc_request.setting_ca_certificates.enabled {
+                ResolvedSetting::from_model(
+                    grpc_request.setting_ca_certificates.value.clone(),
+                    AnyModel::GrpcRequest(grpc_request.clone()),
+                )
+            } else {
+                parent.ca_certificates
+            },
+            client_certificate: if grpc_request.setting_client_certificate.enabled {
+                ResolvedSetting::from_model(
+                    grpc_request.setting_client_certificate.value.clone(),
+                    AnyModel::GrpcRequest(grpc_request.clone()),
+                )
+            } else {
+                parent.client_certificate
+            },
             ..parent
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{
+        CaCertificate, ClientCertificate, Folder, GrpcRequest, InheritedCaCertificatesSetting,
+        InheritedClientCertificateSetting, InheritedIntSetting, Workspace,
+    };
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn appends_reorders_and_deletes_message_templates() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Templates".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_grpc_request(
+                &GrpcRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        db.append_grpc_message_template(&request.id, "First", r#"{"n":1}"#, source).unwrap();
+        db.append_grpc_message_template(&request.id, "Second", r#"{"n":2}"#, source).unwrap();
+        let request =
+            db.append_grpc_message_template(&request.id, "Third", r#"{"n":3}"#, source).unwrap();
+        let ids: Vec<String> =
+            request.message_templates.iter().map(|t| t.id.clone().unwrap()).collect();
+
+        let request =
+            db.reorder_grpc_message_templates(&request.id, &[ids[2].clone()], source).unwrap();
+        let names: Vec<&str> = request.message_templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Third", "First", "Second"]);
+
+        db.delete_grpc_message_template(&request.id, &ids[0], source).unwrap();
+        let request = db.get_grpc_request(&request.id).unwrap();
+        let names: Vec<&str> = request.message_templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["Third", "Second"]);
+    }
+
+    #[test]
+    fn resolves_timeouts_through_folders() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    setting_request_timeout: 30_000,
+                    setting_connect_timeout: 5_000,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    setting_connect_timeout: InheritedIntSetting { enabled: true, value: 1_000 },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_grpc_request(
+                &GrpcRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    setting_read_timeout: InheritedIntSetting { enabled: true, value: 250 },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let resolved = db.resolve_settings_for_grpc_request(&request).unwrap();
+        assert_eq!(resolved.request_timeout.value, 30_000);
+        assert_eq!(resolved.request_timeout.source_model, "workspace");
+        assert_eq!(resolved.connect_timeout.value, 1_000);
+        assert_eq!(resolved.connect_timeout.source_id, Some(folder.id.clone()));
+        assert_eq!(resolved.read_timeout.value, 250);
+        assert_eq!(resolved.read_timeout.source_id, Some(request.id.clone()));
+    }
+
+    #[test]
+    fn resolves_certificates_through_folders() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace_ca = CaCertificate {
+            file: Some("/certs/workspace.pem".to_string()),
+            pem: None,
+            enabled: true,
+        };
+        let folder_ca =
+            CaCertificate { file: Some("/certs/folder.pem".to_string()), pem: None, enabled: true };
+        let client_certificate = ClientCertificate {
+            host: String::new(),
+            port: None,
+            crt_file: Some("/certs/client.crt".to_string()),
+            key_file: Some("/certs/client.key".to_string()),
+            pfx_file: None,
+            passphrase: None,
+            enabled: true,
+        };
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    setting_ca_certificates: vec![workspace_ca.clone()],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    setting_ca_certificates: InheritedCaCertificatesSetting {
+                        enabled: true,
+                        value: vec![folder_ca.clone()],
+                    },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_grpc_request(
+                &GrpcRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    setting_client_certificate: InheritedClientCertificateSetting {
+                        enabled: true,
+                        value: Some(client_certificate.clone()),
+                    },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let other = db
+            .upsert_grpc_request(
+                &GrpcRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let resolved = db.resolve_settings_for_grpc_request(&request).unwrap();
+        assert_eq!(resolved.ca_certificates.value, vec![folder_ca]);
+        assert_eq!(resolved.ca_certificates.source_id, Some(folder.id.clone()));
+        assert_eq!(resolved.client_certificate.value, Some(client_certificate));
+        assert_eq!(resolved.client_certificate.source_id, Some(request.id.clone()));
+
+        let resolved = db.resolve_settings_for_grpc_request(&other).unwrap();
+        assert_eq!(resolved.ca_certificates.value, vec![workspace_ca]);
+        assert_eq!(resolved.ca_certificates.source_model, "workspace");
+        assert_eq!(resolved.client_certificate.value, None);
+    }
+}
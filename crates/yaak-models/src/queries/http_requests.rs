@@ -1,13 +1,15 @@
-use super::dedupe_headers;
+use super::{dedupe_headers, merge_url_parameters};
 use crate::client_db::ClientDb;
 use crate::error::Result;
 use crate::models::{
     AnyModel, Folder, FolderIden, HttpRequest, HttpRequestHeader, HttpRequestIden,
-    ResolvedHttpRequestSettings, ResolvedSetting,
+    HttpUrlParameter, Monitor, MonitorIden, ResolvedHttpRequestSettings, ResolvedSetting,
 };
+use crate::render::make_vars_hashmap;
 use crate::util::UpdateSource;
+use chrono::Utc;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 impl<'a> ClientDb<'a> {
     pub fn get_http_request(&self, id: &str) -> Result<HttpRequest> {
@@ -15,7 +17,9 @@ impl<'a> ClientDb<'a> {
     }
 
     pub fn list_http_requests(&self, workspace_id: &str) -> Result<Vec<HttpRequest>> {
-        self.find_many(HttpRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<HttpRequest> =
+            self.find_many(HttpRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
     }
 
     pub fn delete_http_request(
@@ -24,6 +28,10 @@ impl<'a> ClientDb<'a> {
         source: &UpdateSource,
     ) -> Result<HttpRequest> {
         self.delete_all_http_responses_for_request(m.id.as_str(), source)?;
+        self.delete_request_revisions(m.id.as_str())?;
+        for monitor in self.find_many::<Monitor>(MonitorIden::RequestId, &m.id, None)? {
+            self.delete_monitor(&monitor, source)?;
+        }
         self.delete(m, source)
     }
 
@@ -36,15 +44,45 @@ impl<'a> ClientDb<'a> {
         self.delete_http_request(&http_request, source)
     }
 
+    /// Move a request to the trash. It can be brought back with [`Self::restore_http_request`]
+    /// until the trash is emptied.
+    pub fn trash_http_request(
+        &self,
+        http_request: &HttpRequest,
+        source: &UpdateSource,
+    ) -> Result<HttpRequest> {
+        self.upsert(
+            &HttpRequest { deleted_at: Some(Utc::now().naive_utc()), ..http_request.clone() },
+            source,
+        )
+    }
+
+    pub fn restore_http_request(
+        &self,
+        http_request: &HttpRequest,
+        source: &UpdateSource,
+    ) -> Result<HttpRequest> {
+        // Restore trashed parents too, so the request doesn't end up inside a hidden folder
+        if let Some(folder_id) = http_request.folder_id.as_deref() {
+            self.restore_folder_ancestors(folder_id, source)?;
+        }
+        self.upsert(&HttpRequest { deleted_at: None, ..http_request.clone() }, source)
+    }
+
     pub fn duplicate_http_request(
         &self,
         http_request: &HttpRequest,
         source: &UpdateSource,
     ) -> Result<HttpRequest> {
-        let mut http_request = http_request.clone();
-        http_request.id = "".to_string();
-        http_request.sort_priority = http_request.sort_priority + 0.001;
-        self.upsert(&http_request, source)
+        let mut request = http_request.clone();
+        request.id = "".to_string();
+        request.sort_priority = self.sort_priority_after(
+            &http_request.workspace_id,
+            http_request.folder_id.as_deref(),
+            &http_request.id,
+            source,
+        )?;
+        self.upsert(&request, source)
     }
 
     pub fn upsert_http_request(
@@ -52,7 +90,9 @@ impl<'a> ClientDb<'a> {
         http_request: &HttpRequest,
         source: &UpdateSource,
     ) -> Result<HttpRequest> {
-        self.upsert(http_request, source)
+        let http_request = self.upsert(http_request, source)?;
+        self.record_request_revision(&http_request.clone().into(), source)?;
+        Ok(http_request)
     }
 
     pub fn resolve_auth_for_http_request(
@@ -94,6 +134,39 @@ impl<'a> ClientDb<'a> {
         Ok(dedupe_headers(headers))
     }
 
+    /// Resolve URL parameters from the workspace, down through parent folders, to the request.
+    /// Parameters defined closer to the request override inherited ones with the same name.
+    pub fn resolve_query_params_for_http_request(
+        &self,
+        http_request: &HttpRequest,
+    ) -> Result<Vec<HttpUrlParameter>> {
+        let inherited = if let Some(folder_id) = http_request.folder_id.clone() {
+            let folder = self.get_folder(&folder_id)?;
+            self.resolve_query_params_for_folder(&folder)?
+        } else {
+            let workspace = self.get_workspace(&http_request.workspace_id)?;
+            self.resolve_query_params_for_workspace(&workspace)
+        };
+
+        Ok(merge_url_parameters(inherited, &http_request.url_parameters))
+    }
+
+    /// Resolve the variables available to a request, merging the workspace's base and active
+    /// environments with the environments of each ancestor folder. Variables defined closer to
+    /// the request override those with the same name further up.
+    pub fn resolve_variables_for_http_request(
+        &self,
+        http_request: &HttpRequest,
+        active_environment_id: Option<&str>,
+    ) -> Result<HashMap<String, String>> {
+        let environments = self.resolve_environments(
+            &http_request.workspace_id,
+            http_request.folder_id.as_deref(),
+            active_environment_id,
+        )?;
+        Ok(make_vars_hashmap(environments))
+    }
+
     pub fn resolve_settings_for_http_request(
         &self,
         http_request: &HttpRequest,
@@ -123,6 +196,41 @@ impl<'a> ClientDb<'a> {
             } else {
                 parent.follow_redirects
             },
+            max_redirects: if http_request.setting_max_redirects.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_max_redirects.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.max_redirects
+            },
+            redirect_resend_body: if http_request.setting_redirect_resend_body.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_redirect_resend_body.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.redirect_resend_body
+            },
+            redirect_strip_authorization: if http_request
+                .setting_redirect_strip_authorization
+                .enabled
+            {
+                ResolvedSetting::from_model(
+                    http_request.setting_redirect_strip_authorization.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.redirect_strip_authorization
+            },
+            decompress_response: if http_request.setting_decompress_response.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_decompress_response.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.decompress_response
+            },
             request_timeout: if http_request.setting_request_timeout.enabled {
                 ResolvedSetting::from_model(
                     http_request.setting_request_timeout.value,
@@ -131,6 +239,41 @@ impl<'a> ClientDb<'a> {
             } else {
                 parent.request_timeout
             },
+            connect_timeout: if http_request.setting_connect_timeout.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_connect_timeout.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.connect_timeout
+            },
+            read_timeout: if http_request.setting_read_timeout.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_read_timeout.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.read_timeout
+            },
+            http_version: if http_request.setting_http_version.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_http_version.value,
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.http_version
+            },
+            retry_policy: if http_request.setting_retry_policy.enabled {
+                ResolvedSetting::from_model(
+                    http_request.setting_retry_policy.value.clone(),
+                    AnyModel::HttpRequest(http_request.clone()),
+                )
+            } else {
+                parent.retry_policy
+            },
+            ssh_tunnel: parent.ssh_tunnel,
+            ca_certificates: parent.ca_certificates,
+            client_certificate: parent.client_certificate,
             send_cookies: if http_request.setting_send_cookies.enabled {
                 ResolvedSetting::from_model(
                     http_request.setting_send_cookies.value,
@@ -150,17 +293,287 @@ impl<'a> ClientDb<'a> {
         })
     }
 
+    /// List the HTTP requests in a folder and its subfolders in sidebar order, so a subfolder's
+    /// requests come where the subfolder sits among its siblings
     pub fn list_http_requests_for_folder_recursive(
         &self,
         folder_id: &str,
     ) -> Result<Vec<HttpRequest>> {
+        let mut folders: Vec<Folder> = self
+            .find_many::<Folder>(FolderIden::FolderId, folder_id, None)?
+            .into_iter()
+            .filter(|m| m.deleted_at.is_none())
+            .collect();
+        let mut requests: Vec<HttpRequest> = self
+            .find_many::<HttpRequest>(FolderIden::FolderId, folder_id, None)?
+            .into_iter()
+            .filter(|m| m.deleted_at.is_none())
+            .collect();
+        folders.sort_by(|a, b| a.sort_priority.total_cmp(&b.sort_priority));
+        requests.sort_by(|a, b| a.sort_priority.total_cmp(&b.sort_priority));
+
         let mut children = Vec::new();
-        for m in self.find_many::<Folder>(FolderIden::FolderId, folder_id, None)? {
-            children.extend(self.list_http_requests_for_folder_recursive(&m.id)?);
-        }
-        for m in self.find_many::<HttpRequest>(FolderIden::FolderId, folder_id, None)? {
-            children.push(m);
+        let mut requests = requests.into_iter().peekable();
+        for folder in folders {
+            while let Some(m) = requests.next_if(|m| m.sort_priority < folder.sort_priority) {
+                children.push(m);
+            }
+            children.extend(self.list_http_requests_for_folder_recursive(&folder.id)?);
         }
+        children.extend(requests);
         Ok(children)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{
+        Environment, EnvironmentVariable, Folder, HttpRequest, HttpVersion,
+        InheritedHttpVersionSetting, InheritedRetryPolicySetting, InheritedSshTunnelSetting,
+        RetryPolicy, SshTunnel, Workspace,
+    };
+    use crate::util::UpdateSource;
+
+    fn var(name: &str, value: &str) -> EnvironmentVariable {
+        EnvironmentVariable {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolves_variables_from_closest_scope() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace =
+            db.upsert_workspace(&Workspace::default(), source).expect("Failed to upsert workspace");
+        let base = db.get_base_environment(&workspace.id).unwrap();
+        db.upsert_environment(
+            &Environment {
+                variables: vec![var("host", "example.com"), var("base_path", "/")],
+                ..base
+            },
+            source,
+        )
+        .unwrap();
+        let active = db
+            .upsert_environment(
+                &Environment {
+                    workspace_id: workspace.id.clone(),
+                    parent_model: "environment".to_string(),
+                    variables: vec![var("host", "staging.example.com")],
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        let parent = db
+            .upsert_folder(
+                &Folder { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .unwrap();
+        let child = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(parent.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        for (folder, base_path) in [(&parent, "/api"), (&child, "/api/v2")] {
+            db.upsert_environment(
+                &Environment {
+                    workspace_id: workspace.id.clone(),
+                    parent_model: "folder".to_string(),
+                    parent_id: Some(folder.id.clone()),
+                    variables: vec![var("base_path", base_path)],
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        }
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(child.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+
+        let vars = db.resolve_variables_for_http_request(&request, Some(&active.id)).unwrap();
+        assert_eq!(vars.get("host").unwrap(), "staging.example.com");
+        assert_eq!(vars.get("base_path").unwrap(), "/api/v2");
+
+        let vars = db.resolve_variables_for_http_request(&request, None).unwrap();
+        assert_eq!(vars.get("host").unwrap(), "example.com");
+    }
+
+    #[test]
+    fn resolves_http_version_from_nearest_ancestor() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { setting_http_version: HttpVersion::Http1, ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    setting_http_version: InheritedHttpVersionSetting {
+                        enabled: true,
+                        value: HttpVersion::Http3,
+                    },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let other = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        assert_eq!(
+            db.get_workspace(&workspace.id).unwrap().setting_http_version,
+            HttpVersion::Http1
+        );
+
+        let resolved = db.resolve_settings_for_http_request(&request).unwrap();
+        assert_eq!(resolved.http_version.value, HttpVersion::Http3);
+        assert_eq!(resolved.http_version.source_id, Some(folder.id.clone()));
+
+        let resolved = db.resolve_settings_for_http_request(&other).unwrap();
+        assert_eq!(resolved.http_version.value, HttpVersion::Http1);
+        assert_eq!(resolved.http_version.source_model, "workspace");
+    }
+
+    #[test]
+    fn resolves_retry_policy_from_folder() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace =
+            db.upsert_workspace(&Workspace::default(), source).expect("Failed to upsert workspace");
+        let policy = RetryPolicy { max_attempts: 3, jitter: false, ..Default::default() };
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    setting_retry_policy: InheritedRetryPolicySetting {
+                        enabled: true,
+                        value: policy.clone(),
+                    },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let other = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let resolved = db.resolve_settings_for_http_request(&request).unwrap();
+        assert_eq!(resolved.retry_policy.value, policy);
+        assert_eq!(resolved.retry_policy.source_id, Some(folder.id.clone()));
+
+        let resolved = db.resolve_settings_for_http_request(&other).unwrap();
+        assert_eq!(resolved.retry_policy.value.max_attempts, 1);
+        assert_eq!(resolved.retry_policy.source_model, "default");
+    }
+
+    #[test]
+    fn resolves_ssh_tunnel_from_workspace_unless_folder_overrides() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let tunnel = SshTunnel {
+            host: "bastion.test".to_string(),
+            username: "deploy".to_string(),
+            ..Default::default()
+        };
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { setting_ssh_tunnel: Some(tunnel.clone()), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let direct_folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    setting_ssh_tunnel: InheritedSshTunnelSetting { enabled: true, value: None },
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let direct = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(direct_folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let resolved = db.resolve_settings_for_http_request(&request).unwrap();
+        assert_eq!(resolved.ssh_tunnel.value, Some(tunnel));
+        assert_eq!(resolved.ssh_tunnel.source_model, "workspace");
+
+        let resolved = db.resolve_settings_for_http_request(&direct).unwrap();
+        assert_eq!(resolved.ssh_tunnel.value, None);
+        assert_eq!(resolved.ssh_tunnel.source_id, Some(direct_folder.id.clone()));
+    }
+}
@@ -1,13 +1,38 @@
 use crate::blob_manager::BlobManager;
+use crate::body_storage::move_to_content_addressed;
 use crate::client_db::ClientDb;
 use crate::error::Result;
-use crate::models::{HttpResponse, HttpResponseIden, HttpResponseState};
+use crate::models::{
+    HttpResponse, HttpResponseIden, HttpResponseState, UpsertModelInfo, Workspace, WorkspaceIden,
+};
 use crate::queries::MAX_HISTORY_ITEMS;
 use crate::util::UpdateSource;
+use chrono::{Duration, NaiveDateTime, Utc};
 use log::{debug, error};
-use sea_query::{Expr, Query, SqliteQueryBuilder};
+use rusqlite::params;
+use sea_query::{Asterisk, Cond, Expr, Order, Query, SqliteQueryBuilder};
 use sea_query_rusqlite::RusqliteBinder;
+use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
+use ts_rs::TS;
+
+/// Pagination and filters for listing a request's response history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct HttpResponseFilter {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    /// Minimum status code, inclusive
+    pub status_min: Option<i32>,
+    /// Maximum status code, inclusive
+    pub status_max: Option<i32>,
+    /// Only include responses created at or after this time
+    pub created_after: Option<NaiveDateTime>,
+    /// Only include responses created before this time
+    pub created_before: Option<NaiveDateTime>,
+}
 
 impl<'a> ClientDb<'a> {
     pub fn get_http_response(&self, id: &str) -> Result<HttpResponse> {
@@ -22,6 +47,50 @@ impl<'a> ClientDb<'a> {
         self.find_many(HttpResponseIden::RequestId, request_id, limit)
     }
 
+    /// List a request's responses, newest first, applying the given filters in the query itself
+    /// so that large histories never need to be loaded in full.
+    pub fn filter_http_responses_for_request(
+        &self,
+        request_id: &str,
+        filter: &HttpResponseFilter,
+    ) -> Result<Vec<HttpResponse>> {
+        let mut cond = Cond::all().add(Expr::col(HttpResponseIden::RequestId).eq(request_id));
+        if let Some(status_min) = filter.status_min {
+            cond = cond.add(Expr::col(HttpResponseIden::Status).gte(status_min));
+        }
+        if let Some(status_max) = filter.status_max {
+            cond = cond.add(Expr::col(HttpResponseIden::Status).lte(status_max));
+        }
+        if let Some(created_after) = filter.created_after {
+            cond = cond.add(Expr::col(HttpResponseIden::CreatedAt).gte(created_after));
+        }
+        if let Some(created_before) = filter.created_before {
+            cond = cond.add(Expr::col(HttpResponseIden::CreatedAt).lt(created_before));
+        }
+
+        let mut query = Query::select();
+        query
+            .from(HttpResponseIden::Table)
+            .column(Asterisk)
+            .cond_where(cond)
+            .order_by(HttpResponseIden::CreatedAt, Order::Desc);
+        if let Some(limit) = filter.limit {
+            query.limit(limit.into());
+        }
+        if let Some(offset) = filter.offset {
+            // SQLite doesn't allow OFFSET without LIMIT
+            if filter.limit.is_none() {
+                query.limit(i64::MAX as u64);
+            }
+            query.offset(offset.into());
+        }
+
+        let (sql, params) = query.build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        let items = stmt.query_map(&*params.as_params(), HttpResponse::from_row)?;
+        Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
+    }
+
     pub fn list_http_responses(
         &self,
         workspace_id: &str,
@@ -37,6 +106,7 @@ impl<'a> ClientDb<'a> {
     ) -> Result<()> {
         let responses = self.list_http_responses_for_request(request_id, None)?;
         for m in responses {
+            self.delete_http_response_body_file(&m)?;
             self.delete(&m, source)?;
         }
         Ok(())
@@ -50,6 +120,7 @@ impl<'a> ClientDb<'a> {
         let responses =
             self.find_many::<HttpResponse>(HttpResponseIden::WorkspaceId, workspace_id, None)?;
         for m in responses {
+            self.delete_http_response_body_file(&m)?;
             self.delete(&m, source)?;
         }
         Ok(())
@@ -61,12 +132,7 @@ impl<'a> ClientDb<'a> {
         source: &UpdateSource,
         blob_manager: &BlobManager,
     ) -> Result<HttpResponse> {
-        // Delete the body file if it exists
-        if let Some(p) = http_response.body_path.clone() {
-            if let Err(e) = fs::remove_file(p) {
-                error!("Failed to delete body file: {}", e);
-            };
-        }
+        self.delete_http_response_body_file(http_response)?;
 
         // Delete request body blobs (pattern: {response_id}.request)
         let blob_ctx = blob_manager.connect();
@@ -84,22 +150,141 @@ impl<'a> ClientDb<'a> {
         source: &UpdateSource,
         blob_manager: &BlobManager,
     ) -> Result<HttpResponse> {
-        let responses = self.list_http_responses_for_request(&http_response.request_id, None)?;
+        let workspace: Option<Workspace> =
+            self.find_optional(WorkspaceIden::Id, http_response.workspace_id.as_str());
+        let max_responses = workspace
+            .as_ref()
+            .and_then(|w| w.setting_max_responses_per_request)
+            .filter(|max| *max > 0)
+            .map(|max| max as usize)
+            .unwrap_or(MAX_HISTORY_ITEMS);
 
-        for m in responses.iter().skip(MAX_HISTORY_ITEMS - 1) {
-            debug!("Deleting old HTTP response {}", http_response.id);
-            self.delete_http_response(&m, source, blob_manager)?;
+        // Only trim history when a new response is created. Updates to an in-flight response
+        // must never delete that same response.
+        if http_response.id.is_empty() {
+            let responses =
+                self.list_http_responses_for_request(&http_response.request_id, None)?;
+            for m in responses.iter().skip(max_responses - 1) {
+                debug!("Deleting old HTTP response {}", m.id);
+                self.delete_http_response(&m, source, blob_manager)?;
+            }
+
+            // A new response means a request was just sent, so apply the age limit too
+            if let Some(workspace) = workspace {
+                self.delete_expired_http_responses_for_workspace(&workspace, source, blob_manager)?;
+            }
         }
 
         self.upsert(http_response, source)
     }
 
+    /// Delete a workspace's responses that are older than its retention setting, returning how
+    /// many were deleted
+    pub fn delete_expired_http_responses_for_workspace(
+        &self,
+        workspace: &Workspace,
+        source: &UpdateSource,
+        blob_manager: &BlobManager,
+    ) -> Result<usize> {
+        let Some(days) = workspace.setting_response_retention_days.filter(|d| *d > 0) else {
+            return Ok(0);
+        };
+
+        let cutoff = Utc::now().naive_utc() - Duration::days(days.into());
+        let (sql, params) = Query::select()
+            .from(HttpResponseIden::Table)
+            .column(Asterisk)
+            .cond_where(
+                Cond::all()
+                    .add(Expr::col(HttpResponseIden::WorkspaceId).eq(workspace.id.as_str()))
+                    .add(Expr::col(HttpResponseIden::CreatedAt).lt(cutoff)),
+            )
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        let expired = stmt
+            .query_map(&*params.as_params(), HttpResponse::from_row)?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        for m in &expired {
+            debug!("Deleting expired HTTP response {}", m.id);
+            self.delete_http_response(m, source, blob_manager)?;
+        }
+        Ok(expired.len())
+    }
+
+    /// Apply the response retention setting of every workspace
+    pub fn delete_expired_http_responses(
+        &self,
+        source: &UpdateSource,
+        blob_manager: &BlobManager,
+    ) -> Result<usize> {
+        let mut deleted = 0;
+        for workspace in self.find_all::<Workspace>()? {
+            deleted +=
+                self.delete_expired_http_responses_for_workspace(&workspace, source, blob_manager)?;
+        }
+        Ok(deleted)
+    }
+
+    /// Move a finished response's body into content-addressed storage if it's larger than
+    /// `threshold` bytes, so identical large bodies are only stored once on disk.
+    pub fn store_http_response_body_by_hash(
+        &self,
+        http_response: &HttpResponse,
+        response_dir: &Path,
+        threshold: u64,
+        source: &UpdateSource,
+    ) -> Result<HttpResponse> {
+        let body_path = match http_response.body_path.as_deref() {
+            Some(p) => Path::new(p),
+            None => return Ok(http_response.clone()),
+        };
+        if fs::metadata(body_path)?.len() <= threshold {
+            return Ok(http_response.clone());
+        }
+
+        let stored_path = move_to_content_addressed(response_dir, body_path)?;
+        self.upsert(
+            &HttpResponse {
+                body_path: Some(stored_path.to_string_lossy().to_string()),
+                ..http_response.clone()
+            },
+            source,
+        )
+    }
+
+    /// Delete a response's body file, unless it's a content-addressed body that another response
+    /// still points to.
+    fn delete_http_response_body_file(&self, http_response: &HttpResponse) -> Result<()> {
+        let body_path = match http_response.body_path.as_deref() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+
+        let shared_count: i64 = self.conn().resolve().query_row(
+            "SELECT COUNT(*) FROM http_responses WHERE body_path = ?1 AND id != ?2",
+            params![body_path, http_response.id],
+            |row| row.get(0),
+        )?;
+        if shared_count > 0 {
+            return Ok(());
+        }
+
+        if let Err(e) = fs::remove_file(body_path) {
+            error!("Failed to delete body file: {}", e);
+        };
+        Ok(())
+    }
+
     pub fn cancel_pending_http_responses(&self) -> Result<()> {
         let closed = serde_json::to_value(&HttpResponseState::Closed)?;
+        let timed_out = serde_json::to_value(&HttpResponseState::TimedOut)?;
         let (sql, params) = Query::update()
             .table(HttpResponseIden::Table)
             .values([(HttpResponseIden::State, closed.as_str().into())])
-            .cond_where(Expr::col(HttpResponseIden::State).ne(closed.as_str()))
+            .cond_where(
+                Expr::col(HttpResponseIden::State).is_not_in([closed.as_str(), timed_out.as_str()]),
+            )
             .build_rusqlite(SqliteQueryBuilder);
         let mut stmt = self.conn().prepare(sql.as_str())?;
         stmt.execute(&*params.as_params())?;
@@ -114,3 +299,272 @@ impl<'a> ClientDb<'a> {
         if response.id.is_empty() { Ok(response.clone()) } else { self.upsert(response, source) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::body_storage::{
+        DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD, content_addressed_body_path,
+        content_addressed_body_threshold,
+    };
+    use crate::init_in_memory;
+    use crate::models::{HttpRequest, HttpResponse, Workspace};
+    use crate::util::UpdateSource;
+    use chrono::{Duration, Utc};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn applies_workspace_response_retention() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "Retention Test".to_string(),
+                    setting_max_responses_per_request: Some(2),
+                    setting_response_retention_days: Some(7),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let response = HttpResponse {
+            workspace_id: workspace.id.clone(),
+            request_id: request.id.clone(),
+            ..Default::default()
+        };
+        let old_response = HttpResponse {
+            created_at: Utc::now().naive_utc() - Duration::days(30),
+            ..response.clone()
+        };
+
+        // Imported with its original date, so it's already past the retention period
+        let old = db
+            .upsert_http_response(&old_response, &UpdateSource::Import, &blob_manager)
+            .expect("Failed to upsert response");
+
+        // Sending again deletes the expired response and keeps at most two
+        for _ in 0..3 {
+            db.upsert_http_response(&response, source, &blob_manager)
+                .expect("Failed to upsert response");
+        }
+        let responses = db.list_http_responses_for_request(&request.id, None).unwrap();
+        assert_eq!(responses.len(), 2);
+        assert!(responses.iter().all(|r| r.id != old.id));
+
+        // The periodic cleanup catches expired responses that arrive without a send
+        let old = db
+            .upsert_http_response(&old_response, &UpdateSource::Import, &blob_manager)
+            .expect("Failed to upsert response");
+        let deleted =
+            db.delete_expired_http_responses(&UpdateSource::Background, &blob_manager).unwrap();
+        assert_eq!(deleted, 1);
+        let responses = db.list_http_responses_for_request(&request.id, None).unwrap();
+        assert_eq!(responses.len(), 1);
+        assert!(responses.iter().all(|r| r.id != old.id));
+    }
+
+    #[test]
+    fn updating_response_keeps_it_with_max_of_one() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "Max One Test".to_string(),
+                    setting_max_responses_per_request: Some(1),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let previous = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                source,
+                &blob_manager,
+            )
+            .expect("Failed to upsert response");
+
+        // Sending again replaces the previous response
+        let response = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                source,
+                &blob_manager,
+            )
+            .expect("Failed to upsert response");
+
+        // Progress and final updates keep the in-flight response in place
+        for status in [0, 200] {
+            db.upsert_http_response(
+                &HttpResponse { status, ..response.clone() },
+                source,
+                &blob_manager,
+            )
+            .expect("Failed to update response");
+        }
+
+        let responses = db.list_http_responses_for_request(&request.id, None).unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].id, response.id);
+        assert_eq!(responses[0].status, 200);
+        assert_ne!(responses[0].id, previous.id);
+    }
+
+    fn create_request(db: &crate::client_db::ClientDb) -> HttpRequest {
+        let source = &UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Body Storage Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        db.upsert_http_request(
+            &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+            source,
+        )
+        .expect("Failed to upsert request")
+    }
+
+    /// Create a response whose body file, named after the response, holds `body`
+    fn response_with_body(
+        db: &crate::client_db::ClientDb,
+        blob_manager: &crate::blob_manager::BlobManager,
+        response_dir: &Path,
+        request: &HttpRequest,
+        body: &[u8],
+    ) -> HttpResponse {
+        let response = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: request.workspace_id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                &UpdateSource::Sync,
+                blob_manager,
+            )
+            .expect("Failed to upsert response");
+        let body_path = response_dir.join(&response.id);
+        fs::write(&body_path, body).unwrap();
+        db.upsert_http_response(
+            &HttpResponse { body_path: Some(body_path.to_string_lossy().to_string()), ..response },
+            &UpdateSource::Sync,
+            blob_manager,
+        )
+        .expect("Failed to upsert response")
+    }
+
+    #[test]
+    fn stores_identical_large_bodies_once() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+        let dir = tempfile::tempdir().unwrap();
+        let request = create_request(&db);
+
+        let body = b"a large body".repeat(16);
+        let first = response_with_body(&db, &blob_manager, dir.path(), &request, &body);
+        let second = response_with_body(&db, &blob_manager, dir.path(), &request, &body);
+        let first = db.store_http_response_body_by_hash(&first, dir.path(), 8, source).unwrap();
+        let second = db.store_http_response_body_by_hash(&second, dir.path(), 8, source).unwrap();
+
+        let hash =
+            crate::body_storage::hash_file(Path::new(first.body_path.as_ref().unwrap())).unwrap();
+        let stored = content_addressed_body_path(dir.path(), &hash);
+        assert_eq!(first.body_path, Some(stored.to_string_lossy().to_string()));
+        assert_eq!(second.body_path, first.body_path);
+        assert_eq!(fs::read(&stored).unwrap(), body);
+
+        // The per-response files were replaced by the shared copy
+        assert!(!dir.path().join(&first.id).exists());
+        assert!(!dir.path().join(&second.id).exists());
+    }
+
+    #[test]
+    fn keeps_shared_body_until_last_response_is_deleted() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+        let dir = tempfile::tempdir().unwrap();
+        let request = create_request(&db);
+
+        let body = b"a large body".repeat(16);
+        let first = response_with_body(&db, &blob_manager, dir.path(), &request, &body);
+        let second = response_with_body(&db, &blob_manager, dir.path(), &request, &body);
+        let first = db.store_http_response_body_by_hash(&first, dir.path(), 8, source).unwrap();
+        let second = db.store_http_response_body_by_hash(&second, dir.path(), 8, source).unwrap();
+        let stored = Path::new(first.body_path.as_ref().unwrap()).to_path_buf();
+
+        db.delete_http_response(&first, source, &blob_manager).unwrap();
+        assert!(stored.exists());
+
+        db.delete_http_response(&second, source, &blob_manager).unwrap();
+        assert!(!stored.exists());
+    }
+
+    #[test]
+    fn leaves_bodies_below_threshold_in_place() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+        let dir = tempfile::tempdir().unwrap();
+        let request = create_request(&db);
+
+        let response = response_with_body(&db, &blob_manager, dir.path(), &request, b"small");
+        let stored = db.store_http_response_body_by_hash(&response, dir.path(), 8, source).unwrap();
+        assert_eq!(stored.body_path, response.body_path);
+        assert!(dir.path().join(&response.id).exists());
+        assert!(!dir.path().join("by-hash").exists());
+    }
+
+    #[test]
+    fn uses_workspace_body_threshold() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+        let dir = tempfile::tempdir().unwrap();
+        let request = create_request(&db);
+        let mut workspace = db.get_workspace(&request.workspace_id).unwrap();
+        assert_eq!(
+            content_addressed_body_threshold(&workspace),
+            DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD
+        );
+
+        workspace.setting_content_addressed_body_threshold = Some(8);
+        let workspace = db.upsert_workspace(&workspace, source).unwrap();
+        let threshold = content_addressed_body_threshold(&workspace);
+        assert_eq!(threshold, 8);
+
+        let response = response_with_body(&db, &blob_manager, dir.path(), &request, b"medium body");
+        let stored =
+            db.store_http_response_body_by_hash(&response, dir.path(), threshold, source).unwrap();
+        assert_ne!(stored.body_path, response.body_path);
+        assert!(!dir.path().join(&response.id).exists());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use yaak_database::DbStats;
+
+impl<'a> ClientDb<'a> {
+    /// Report how much space each table and workspace takes up in the database
+    pub fn db_stats(&self) -> Result<DbStats> {
+        Ok(self.ctx.db_stats()?)
+    }
+
+    /// Give space freed by deleted rows back to the filesystem, returning the bytes reclaimed
+    pub fn vacuum(&self) -> Result<u64> {
+        Ok(self.ctx.vacuum()?)
+    }
+
+    /// Check the database for corruption, returning the problems found
+    pub fn integrity_check(&self) -> Result<Vec<String>> {
+        Ok(self.ctx.integrity_check()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn reports_stats_and_vacuums() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Stats Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let large_url = format!("https://example.com/{}", "a".repeat(100_000));
+        let mut requests = Vec::new();
+        for _ in 0..10 {
+            let request = db
+                .upsert_http_request(
+                    &HttpRequest {
+                        workspace_id: workspace.id.clone(),
+                        url: large_url.clone(),
+                        ..Default::default()
+                    },
+                    source,
+                )
+                .expect("Failed to upsert request");
+            requests.push(request);
+        }
+
+        let stats = db.db_stats().expect("Failed to get stats");
+        let table = stats.tables.iter().find(|t| t.name == "http_requests").unwrap();
+        assert_eq!(table.rows, 10);
+        assert!(table.bytes > 1_000_000);
+        let ws = stats.workspaces.iter().find(|w| w.workspace_id == workspace.id).unwrap();
+        assert!(ws.rows >= 10);
+        assert!(ws.bytes > 1_000_000);
+
+        for request in requests {
+            db.delete_http_request(&request, source).expect("Failed to delete request");
+        }
+        assert!(db.db_stats().unwrap().free_bytes > 0);
+        assert!(db.vacuum().expect("Failed to vacuum") > 0);
+        assert_eq!(db.db_stats().unwrap().free_bytes, 0);
+        assert_eq!(db.integrity_check().expect("Failed to check"), Vec::<String>::new());
+    }
+}
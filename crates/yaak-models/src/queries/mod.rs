@@ -1,32 +1,64 @@
 pub mod any_request;
+mod audit_logs;
 mod batch;
+mod chained_values;
+mod collection_runs;
 mod cookie_jars;
 mod environments;
+mod export;
 mod folders;
 mod graphql_introspections;
 mod grpc_connections;
+mod grpc_descriptor_caches;
 mod grpc_events;
 mod grpc_requests;
 mod http_requests;
 mod http_response_events;
 mod http_responses;
 mod key_values;
+mod maintenance;
 mod model_changes;
+mod monitors;
+mod mqtt_connections;
+mod mqtt_events;
+mod mqtt_requests;
+mod oauth2_tokens;
+mod pins;
 mod plugin_key_values;
 mod plugins;
+mod proto_files;
+mod request_diffs;
+mod request_revisions;
+mod response_diffs;
+mod search;
 mod settings;
+mod socket_requests;
+mod socket_responses;
+mod sort_priorities;
 mod sync_states;
+mod tags;
+mod trash;
 mod websocket_connections;
 mod websocket_events;
 mod websocket_requests;
 mod workspace_metas;
 pub mod workspaces;
+pub use audit_logs::AuditLogEntry;
+pub use export::WorkspaceExportFormat;
+pub use http_responses::HttpResponseFilter;
 pub use model_changes::PersistedModelChange;
+pub use request_diffs::{
+    FieldChange, KeyValueChange, KeyValueEntry, RequestDiff, RequestDiffTarget, diff_http_requests,
+};
+pub use request_revisions::RequestRevision;
+pub use response_diffs::{ResponseDiff, ResponseDiffOptions, diff_http_responses};
+pub use search::SearchResult;
+pub use websocket_events::WebsocketEventFilter;
 
 const MAX_HISTORY_ITEMS: usize = 20;
 
-use crate::models::HttpRequestHeader;
-use std::collections::HashMap;
+use crate::models::{HttpRequestHeader, HttpUrlParameter};
+use std::collections::{HashMap, HashSet};
 
 /// Deduplicate headers by name (case-insensitive), keeping the latest (most specific) value.
 /// Preserves the order of first occurrence for each header name.
@@ -44,3 +76,18 @@ pub(crate) fn dedupe_headers(headers: Vec<HttpRequestHeader>) -> Vec<HttpRequest
     }
     deduped
 }
+
+/// Layer a model's own URL parameters on top of those inherited from its parents. An enabled
+/// parameter overrides every inherited parameter with the same name, while names that aren't
+/// overridden are kept (query parameters may legitimately repeat).
+pub(crate) fn merge_url_parameters(
+    inherited: Vec<HttpUrlParameter>,
+    own: &[HttpUrlParameter],
+) -> Vec<HttpUrlParameter> {
+    let overridden: HashSet<&str> =
+        own.iter().filter(|p| p.enabled && !p.name.is_empty()).map(|p| p.name.as_str()).collect();
+    let mut merged: Vec<HttpUrlParameter> =
+        inherited.into_iter().filter(|p| !overridden.contains(p.name.as_str())).collect();
+    merged.extend(own.iter().cloned());
+    merged
+}
@@ -70,6 +70,16 @@ impl<'a> ClientDb<'a> {
         Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
     }
 
+    /// ID of the most recent change, or 0 when there aren't any, for starting to follow changes
+    /// from now on
+    pub fn latest_model_change_id(&self) -> Result<i64> {
+        Ok(self.conn().resolve().query_row(
+            "SELECT COALESCE(MAX(id), 0) FROM model_changes",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
     pub fn prune_model_changes_older_than_days(&self, days: i64) -> Result<usize> {
         let offset = format!("-{days} days");
         Ok(self.conn().resolve().execute(
@@ -0,0 +1,125 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{Monitor, MonitorIden, MonitorRun, MonitorRunIden};
+use crate::queries::MAX_HISTORY_ITEMS;
+use crate::util::UpdateSource;
+use log::debug;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_monitor(&self, id: &str) -> Result<Monitor> {
+        self.find_one(MonitorIden::Id, id)
+    }
+
+    pub fn list_monitors(&self, workspace_id: &str) -> Result<Vec<Monitor>> {
+        self.find_many(MonitorIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn delete_monitor(&self, m: &Monitor, source: &UpdateSource) -> Result<Monitor> {
+        for run in self.list_monitor_runs_for_monitor(&m.id, None)? {
+            self.delete_monitor_run(&run, source)?;
+        }
+        self.delete(m, source)
+    }
+
+    pub fn upsert_monitor(&self, monitor: &Monitor, source: &UpdateSource) -> Result<Monitor> {
+        self.upsert(monitor, source)
+    }
+
+    pub fn get_monitor_run(&self, id: &str) -> Result<MonitorRun> {
+        self.find_one(MonitorRunIden::Id, id)
+    }
+
+    pub fn list_monitor_runs(&self, workspace_id: &str) -> Result<Vec<MonitorRun>> {
+        self.find_many(MonitorRunIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn list_monitor_runs_for_monitor(
+        &self,
+        monitor_id: &str,
+        limit: Option<u64>,
+    ) -> Result<Vec<MonitorRun>> {
+        self.find_many(MonitorRunIden::MonitorId, monitor_id, limit)
+    }
+
+    pub fn delete_monitor_run(&self, m: &MonitorRun, source: &UpdateSource) -> Result<MonitorRun> {
+        self.delete(m, source)
+    }
+
+    pub fn upsert_monitor_run(
+        &self,
+        monitor_run: &MonitorRun,
+        source: &UpdateSource,
+    ) -> Result<MonitorRun> {
+        if monitor_run.id.is_empty() {
+            let runs = self.list_monitor_runs_for_monitor(&monitor_run.monitor_id, None)?;
+            for m in runs.iter().skip(MAX_HISTORY_ITEMS - 1) {
+                debug!("Deleting old monitor run {}", m.id);
+                self.delete_monitor_run(m, source)?;
+            }
+        }
+
+        self.upsert(monitor_run, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, Monitor, MonitorRun, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn monitor_runs_are_trimmed_and_deleted_with_folder() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    name: "Folder".to_string(),
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+        let monitor = db
+            .upsert_monitor(
+                &Monitor {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    enabled: true,
+                    interval: 60,
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+
+        for _ in 0..25 {
+            db.upsert_monitor_run(
+                &MonitorRun {
+                    workspace_id: workspace.id.clone(),
+                    monitor_id: monitor.id.clone(),
+                    passed: true,
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(db.list_monitor_runs_for_monitor(&monitor.id, None).unwrap().len(), 20);
+        assert_eq!(db.list_monitors(&workspace.id).unwrap(), vec![monitor.clone()]);
+
+        db.delete_folder(&folder, &source).unwrap();
+        assert!(db.list_monitors(&workspace.id).unwrap().is_empty());
+        assert!(db.list_monitor_runs(&workspace.id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{MqttConnection, MqttConnectionIden, MqttConnectionState};
+use crate::queries::MAX_HISTORY_ITEMS;
+use crate::util::UpdateSource;
+use log::debug;
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_mqtt_connection(&self, id: &str) -> Result<MqttConnection> {
+        self.find_one(MqttConnectionIden::Id, id)
+    }
+
+    pub fn delete_all_mqtt_connections_for_request(
+        &self,
+        request_id: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        for m in self.list_mqtt_connections_for_request(request_id)? {
+            self.delete(&m, source)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_all_mqtt_connections_for_workspace(
+        &self,
+        workspace_id: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        for m in self.list_mqtt_connections(workspace_id)? {
+            self.delete(&m, source)?;
+        }
+        Ok(())
+    }
+
+    pub fn list_mqtt_connections(&self, workspace_id: &str) -> Result<Vec<MqttConnection>> {
+        self.find_many(MqttConnectionIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn list_mqtt_connections_for_request(
+        &self,
+        request_id: &str,
+    ) -> Result<Vec<MqttConnection>> {
+        self.find_many(MqttConnectionIden::RequestId, request_id, None)
+    }
+
+    pub fn delete_mqtt_connection(
+        &self,
+        mqtt_connection: &MqttConnection,
+        source: &UpdateSource,
+    ) -> Result<MqttConnection> {
+        self.delete(mqtt_connection, source)
+    }
+
+    pub fn delete_mqtt_connection_by_id(
+        &self,
+        id: &str,
+        source: &UpdateSource,
+    ) -> Result<MqttConnection> {
+        let mqtt_connection = self.get_mqtt_connection(id)?;
+        self.delete_mqtt_connection(&mqtt_connection, source)
+    }
+
+    pub fn upsert_mqtt_connection(
+        &self,
+        mqtt_connection: &MqttConnection,
+        source: &UpdateSource,
+    ) -> Result<MqttConnection> {
+        let connections = self.list_mqtt_connections_for_request(&mqtt_connection.request_id)?;
+
+        for m in connections.iter().skip(MAX_HISTORY_ITEMS - 1) {
+            debug!("Deleting old MQTT connection {}", m.id);
+            self.delete_mqtt_connection(&m, source)?;
+        }
+
+        self.upsert(mqtt_connection, source)
+    }
+
+    pub fn cancel_pending_mqtt_connections(&self) -> Result<()> {
+        let closed = serde_json::to_value(&MqttConnectionState::Closed)?;
+        let (sql, params) = Query::update()
+            .table(MqttConnectionIden::Table)
+            .values([(MqttConnectionIden::State, closed.as_str().into())])
+            .cond_where(Expr::col(MqttConnectionIden::State).ne(closed.as_str()))
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        stmt.execute(&*params.as_params())?;
+        Ok(())
+    }
+}
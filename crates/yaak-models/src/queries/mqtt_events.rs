@@ -0,0 +1,22 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{MqttEvent, MqttEventIden};
+use crate::util::UpdateSource;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_mqtt_event(&self, id: &str) -> Result<MqttEvent> {
+        self.find_one(MqttEventIden::Id, id)
+    }
+
+    pub fn list_mqtt_events(&self, connection_id: &str) -> Result<Vec<MqttEvent>> {
+        self.find_many(MqttEventIden::ConnectionId, connection_id, None)
+    }
+
+    pub fn upsert_mqtt_event(
+        &self,
+        mqtt_event: &MqttEvent,
+        source: &UpdateSource,
+    ) -> Result<MqttEvent> {
+        self.upsert(mqtt_event, source)
+    }
+}
@@ -0,0 +1,182 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{
+    AnyModel, Folder, FolderIden, MqttRequest, MqttRequestIden, ResolvedHttpRequestSettings,
+    ResolvedSetting,
+};
+use crate::util::UpdateSource;
+use chrono::Utc;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_mqtt_request(&self, id: &str) -> Result<MqttRequest> {
+        self.find_one(MqttRequestIden::Id, id)
+    }
+
+    pub fn list_mqtt_requests(&self, workspace_id: &str) -> Result<Vec<MqttRequest>> {
+        let requests: Vec<MqttRequest> =
+            self.find_many(MqttRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
+    }
+
+    pub fn list_mqtt_requests_for_folder_recursive(
+        &self,
+        folder_id: &str,
+    ) -> Result<Vec<MqttRequest>> {
+        let mut children = Vec::new();
+        for folder in self.find_many::<Folder>(FolderIden::FolderId, folder_id, None)? {
+            children.extend(self.list_mqtt_requests_for_folder_recursive(&folder.id)?);
+        }
+        for request in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, folder_id, None)? {
+            if request.deleted_at.is_none() {
+                children.push(request);
+            }
+        }
+        Ok(children)
+    }
+
+    pub fn delete_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        self.delete_all_mqtt_connections_for_request(mqtt_request.id.as_str(), source)?;
+        self.delete(mqtt_request, source)
+    }
+
+    pub fn delete_mqtt_request_by_id(
+        &self,
+        id: &str,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        let request = self.get_mqtt_request(id)?;
+        self.delete_mqtt_request(&request, source)
+    }
+
+    pub fn trash_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        self.upsert(
+            &MqttRequest { deleted_at: Some(Utc::now().naive_utc()), ..mqtt_request.clone() },
+            source,
+        )
+    }
+
+    pub fn restore_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        if let Some(folder_id) = mqtt_request.folder_id.as_deref() {
+            self.restore_folder_ancestors(folder_id, source)?;
+        }
+        self.upsert(&MqttRequest { deleted_at: None, ..mqtt_request.clone() }, source)
+    }
+
+    pub fn duplicate_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        let mut mqtt_request = mqtt_request.clone();
+        mqtt_request.id = "".to_string();
+        mqtt_request.sort_priority = self.sort_priority_after(
+            &mqtt_request.workspace_id,
+            mqtt_request.folder_id.as_deref(),
+            &mqtt_request.id,
+            source,
+        )?;
+        self.upsert(&mqtt_request, source)
+    }
+
+    pub fn upsert_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+        source: &UpdateSource,
+    ) -> Result<MqttRequest> {
+        self.upsert(mqtt_request, source)
+    }
+
+    pub fn resolve_settings_for_mqtt_request(
+        &self,
+        mqtt_request: &MqttRequest,
+    ) -> Result<ResolvedHttpRequestSettings> {
+        let parent = if let Some(folder_id) = mqtt_request.folder_id.clone() {
+            let folder = self.get_folder(&folder_id)?;
+            self.resolve_settings_for_folder(&folder)?
+        } else {
+            let workspace = self.get_workspace(&mqtt_request.workspace_id)?;
+            self.resolve_settings_for_workspace(&workspace)
+        };
+
+        Ok(ResolvedHttpRequestSettings {
+            validate_certificates: if mqtt_request.setting_validate_certificates.enabled {
+                ResolvedSetting::from_model(
+                    mqtt_request.setting_validate_certificates.value,
+                    AnyModel::MqttRequest(mqtt_request.clone()),
+                )
+            } else {
+                parent.validate_certificates
+            },
+            ..parent
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, MqttQos, MqttRequest, MqttSubscription, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn persists_and_trashes_requests() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "MQTT".to_string(), ..Default::default() }, source)
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_mqtt_request(
+                &MqttRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    url: "mqtt://localhost:1883".to_string(),
+                    subscriptions: vec![MqttSubscription {
+                        enabled: true,
+                        topic: "sensors/+/temperature".to_string(),
+                        qos: MqttQos::AtLeastOnce,
+                        id: None,
+                    }],
+                    qos: MqttQos::ExactlyOnce,
+                    retain: true,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        assert!(request.id.starts_with("mq_"));
+
+        let stored = db.get_mqtt_request(&request.id).unwrap();
+        assert_eq!(stored, request);
+        assert_eq!(db.list_mqtt_requests_for_folder_recursive(&folder.id).unwrap().len(), 1);
+
+        db.trash_mqtt_request(&request, source).unwrap();
+        assert!(db.list_mqtt_requests(&workspace.id).unwrap().is_empty());
+        db.restore_mqtt_request(&request, source).unwrap();
+        assert_eq!(db.list_mqtt_requests(&workspace.id).unwrap().len(), 1);
+
+        // MQTT requests are removed along with their workspace
+        db.delete_workspace(&workspace, source).unwrap();
+        assert!(db.get_mqtt_request(&request.id).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{OAuth2Token, OAuth2TokenIden};
+use sea_query::Keyword::CurrentTimestamp;
+use sea_query::{Asterisk, Expr, OnConflict, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_oauth2_token(&self, config_hash: &str) -> Option<OAuth2Token> {
+        let (sql, params) = Query::select()
+            .from(OAuth2TokenIden::Table)
+            .column(Asterisk)
+            .cond_where(Expr::col(OAuth2TokenIden::ConfigHash).eq(config_hash))
+            .build_rusqlite(SqliteQueryBuilder);
+        self.conn()
+            .resolve()
+            .query_row(sql.as_str(), &*params.as_params(), |row| row.try_into())
+            .ok()
+    }
+
+    pub fn upsert_oauth2_token(&self, token: &OAuth2Token) -> Result<OAuth2Token> {
+        let (sql, params) = Query::insert()
+            .into_table(OAuth2TokenIden::Table)
+            .columns([
+                OAuth2TokenIden::CreatedAt,
+                OAuth2TokenIden::UpdatedAt,
+                OAuth2TokenIden::ConfigHash,
+                OAuth2TokenIden::AccessToken,
+                OAuth2TokenIden::IdToken,
+                OAuth2TokenIden::RefreshToken,
+                OAuth2TokenIden::TokenType,
+                OAuth2TokenIden::Scopes,
+                OAuth2TokenIden::ExpiresAt,
+            ])
+            .values_panic([
+                CurrentTimestamp.into(),
+                CurrentTimestamp.into(),
+                token.config_hash.as_str().into(),
+                token.access_token.as_str().into(),
+                token.id_token.clone().into(),
+                token.refresh_token.clone().into(),
+                token.token_type.clone().into(),
+                serde_json::to_string(&token.scopes)?.into(),
+                token.expires_at.into(),
+            ])
+            .on_conflict(
+                OnConflict::column(OAuth2TokenIden::ConfigHash)
+                    .update_columns([
+                        OAuth2TokenIden::UpdatedAt,
+                        OAuth2TokenIden::AccessToken,
+                        OAuth2TokenIden::IdToken,
+                        OAuth2TokenIden::RefreshToken,
+                        OAuth2TokenIden::TokenType,
+                        OAuth2TokenIden::Scopes,
+                        OAuth2TokenIden::ExpiresAt,
+                    ])
+                    .to_owned(),
+            )
+            .returning_all()
+            .build_rusqlite(SqliteQueryBuilder);
+
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        Ok(stmt.query_row(&*params.as_params(), |row| row.try_into())?)
+    }
+
+    pub fn delete_oauth2_token(&self, config_hash: &str) -> Result<bool> {
+        let (sql, params) = Query::delete()
+            .from_table(OAuth2TokenIden::Table)
+            .cond_where(Expr::col(OAuth2TokenIden::ConfigHash).eq(config_hash))
+            .build_rusqlite(SqliteQueryBuilder);
+        Ok(self.conn().execute(sql.as_str(), &*params.as_params())? > 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::OAuth2Token;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn stores_and_replaces_tokens_by_config_hash() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        assert!(db.get_oauth2_token("abc").is_none());
+
+        let token = db
+            .upsert_oauth2_token(&OAuth2Token {
+                config_hash: "abc".to_string(),
+                access_token: "first".to_string(),
+                refresh_token: Some("refresh".to_string()),
+                scopes: vec!["read".to_string(), "write".to_string()],
+                expires_at: Some(Utc::now().naive_utc() - Duration::minutes(1)),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(token.is_expired());
+        assert_eq!(token.scopes, vec!["read", "write"]);
+
+        let token = db
+            .upsert_oauth2_token(&OAuth2Token {
+                access_token: "second".to_string(),
+                expires_at: Some(Utc::now().naive_utc() + Duration::hours(1)),
+                ..token
+            })
+            .unwrap();
+        assert!(!token.is_expired());
+        assert_eq!(db.get_oauth2_token("abc").unwrap().access_token, "second");
+
+        assert!(db.delete_oauth2_token("abc").unwrap());
+        assert!(!db.delete_oauth2_token("abc").unwrap());
+        assert!(db.get_oauth2_token("abc").is_none());
+    }
+}
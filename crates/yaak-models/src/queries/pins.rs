@@ -0,0 +1,104 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::AnyModel;
+use crate::queries::any_request::AnyRequest;
+use rusqlite::params;
+
+impl<'a> ClientDb<'a> {
+    /// List the pinned requests of a workspace in the order they were pinned. Trashed requests
+    /// stay pinned but are left out until they are restored.
+    pub fn list_pinned_items(&self, workspace_id: &str) -> Result<Vec<AnyModel>> {
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT model_id
+                FROM pins
+                WHERE workspace_id = ?1
+                ORDER BY created_at, rowid
+            "#,
+        )?;
+        let ids = stmt
+            .query_map(params![workspace_id], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?;
+
+        let mut items: Vec<AnyModel> = Vec::new();
+        for id in ids {
+            let item: AnyModel = match self.get_any_request(&id) {
+                Ok(AnyRequest::HttpRequest(m)) if m.deleted_at.is_none() => m.into(),
+                Ok(AnyRequest::GrpcRequest(m)) if m.deleted_at.is_none() => m.into(),
+                Ok(AnyRequest::WebsocketRequest(m)) if m.deleted_at.is_none() => m.into(),
+                _ => continue,
+            };
+            items.push(item);
+        }
+
+        Ok(items)
+    }
+
+    pub fn pin_request(&self, workspace_id: &str, request_id: &str) -> Result<()> {
+        self.conn().resolve().execute(
+            "INSERT OR IGNORE INTO pins (workspace_id, model_id) VALUES (?1, ?2)",
+            params![workspace_id, request_id],
+        )?;
+        Ok(())
+    }
+
+    pub fn unpin_request(&self, workspace_id: &str, request_id: &str) -> Result<()> {
+        self.conn().resolve().execute(
+            "DELETE FROM pins WHERE workspace_id = ?1 AND model_id = ?2",
+            params![workspace_id, request_id],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{HttpRequest, WebsocketRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn pins_and_unpins_requests() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Pins Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let http_request = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let websocket_request = db
+            .upsert_websocket_request(
+                &WebsocketRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        db.pin_request(&workspace.id, &websocket_request.id).unwrap();
+        db.pin_request(&workspace.id, &http_request.id).unwrap();
+        db.pin_request(&workspace.id, &http_request.id).unwrap();
+        let pinned = db.list_pinned_items(&workspace.id).unwrap();
+        let ids: Vec<&str> = pinned.iter().map(|m| m.id()).collect();
+        assert_eq!(ids, vec![websocket_request.id.as_str(), http_request.id.as_str()]);
+
+        db.unpin_request(&workspace.id, &websocket_request.id).unwrap();
+        assert_eq!(db.list_pinned_items(&workspace.id).unwrap().len(), 1);
+
+        // Deleting a request removes its pin
+        db.delete_http_request(&http_request, source).unwrap();
+        let count: i64 = db
+            .conn()
+            .resolve()
+            .query_row("SELECT COUNT(*) FROM pins", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+}
@@ -0,0 +1,151 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::ProtoFiles;
+use rusqlite::{OptionalExtension, params};
+
+impl<'a> ClientDb<'a> {
+    pub fn get_proto_files(&self, workspace_id: &str) -> Result<Option<ProtoFiles>> {
+        Ok(self
+            .conn()
+            .resolve()
+            .query_row(
+                "SELECT * FROM proto_files WHERE workspace_id = ?1",
+                params![workspace_id],
+                |row| row.try_into(),
+            )
+            .optional()?)
+    }
+
+    /// Attach proto files and include directories to a workspace. Paths that are already attached
+    /// are ignored, and the compiled descriptor set is cleared since the sources changed.
+    pub fn attach_proto_files(
+        &self,
+        workspace_id: &str,
+        paths: &[String],
+        include_dirs: &[String],
+    ) -> Result<ProtoFiles> {
+        let existing = self.get_proto_files(workspace_id)?.unwrap_or_default();
+        let mut new_paths = existing.paths;
+        for p in paths {
+            if !new_paths.contains(p) {
+                new_paths.push(p.clone());
+            }
+        }
+        let mut new_include_dirs = existing.include_dirs;
+        for d in include_dirs {
+            if !new_include_dirs.contains(d) {
+                new_include_dirs.push(d.clone());
+            }
+        }
+        self.set_proto_file_sources(workspace_id, &new_paths, &new_include_dirs)
+    }
+
+    /// Detach proto files or include directories from a workspace, clearing the compiled
+    /// descriptor set
+    pub fn detach_proto_files(&self, workspace_id: &str, paths: &[String]) -> Result<ProtoFiles> {
+        let existing = self.get_proto_files(workspace_id)?.unwrap_or_default();
+        let new_paths: Vec<String> =
+            existing.paths.into_iter().filter(|p| !paths.contains(p)).collect();
+        let new_include_dirs: Vec<String> =
+            existing.include_dirs.into_iter().filter(|d| !paths.contains(d)).collect();
+        self.set_proto_file_sources(workspace_id, &new_paths, &new_include_dirs)
+    }
+
+    /// Store the descriptor set the workspace's proto files compiled to, so they don't need to be
+    /// compiled again until the sources change
+    pub fn set_proto_files_descriptor_set(
+        &self,
+        workspace_id: &str,
+        descriptor_set: &[u8],
+    ) -> Result<()> {
+        self.conn().resolve().execute(
+            r#"
+                UPDATE proto_files
+                SET descriptor_set = ?2,
+                    compiled_at    = CURRENT_TIMESTAMP,
+                    updated_at     = CURRENT_TIMESTAMP
+                WHERE workspace_id = ?1
+            "#,
+            params![workspace_id, descriptor_set],
+        )?;
+        Ok(())
+    }
+
+    fn set_proto_file_sources(
+        &self,
+        workspace_id: &str,
+        paths: &[String],
+        include_dirs: &[String],
+    ) -> Result<ProtoFiles> {
+        Ok(self.conn().resolve().query_row(
+            r#"
+                INSERT INTO proto_files (workspace_id, paths, include_dirs)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT (workspace_id) DO UPDATE
+                    SET paths          = excluded.paths,
+                        include_dirs   = excluded.include_dirs,
+                        descriptor_set = NULL,
+                        compiled_at    = NULL,
+                        updated_at     = CURRENT_TIMESTAMP
+                RETURNING *
+            "#,
+            params![
+                workspace_id,
+                serde_json::to_string(paths)?,
+                serde_json::to_string(include_dirs)?
+            ],
+            |row| row.try_into(),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::Workspace;
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn attaches_and_detaches_proto_files() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Proto Files".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        assert!(db.get_proto_files(&workspace.id).unwrap().is_none());
+
+        let paths = vec!["/protos/a.proto".to_string(), "/protos/b.proto".to_string()];
+        db.attach_proto_files(&workspace.id, &paths, &[]).unwrap();
+        db.set_proto_files_descriptor_set(&workspace.id, b"compiled").unwrap();
+        let compiled = db.get_proto_files(&workspace.id).unwrap().unwrap();
+        assert_eq!(compiled.descriptor_set, Some(b"compiled".to_vec()));
+        assert!(compiled.compiled_at.is_some());
+
+        // Changing the sources invalidates the compiled descriptor set
+        let proto_files = db
+            .attach_proto_files(&workspace.id, &paths[..1], &["/protos/include".to_string()])
+            .unwrap();
+        assert_eq!(proto_files.paths, paths);
+        assert_eq!(proto_files.include_dirs, vec!["/protos/include"]);
+        assert_eq!(proto_files.descriptor_set, None);
+        assert_eq!(proto_files.sources().len(), 3);
+
+        let proto_files = db
+            .detach_proto_files(
+                &workspace.id,
+                &["/protos/a.proto".to_string(), "/protos/include".to_string()],
+            )
+            .unwrap();
+        assert_eq!(proto_files.paths, vec!["/protos/b.proto"]);
+        assert!(proto_files.include_dirs.is_empty());
+
+        // Proto files are removed along with their workspace
+        db.delete_workspace(&workspace, source).unwrap();
+        assert!(db.get_proto_files(&workspace.id).unwrap().is_none());
+    }
+}
@@ -0,0 +1,361 @@
+use crate::client_db::ClientDb;
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::models::{AnyModel, HttpRequest};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use ts_rs::TS;
+
+/// One side of a request diff: a request as it is now, or one of its saved revisions
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case", tag = "type")]
+#[ts(export, export_to = "gen_util.ts")]
+pub enum RequestDiffTarget {
+    Request { id: String },
+    Revision { id: i64 },
+}
+
+/// Everything that differs between two HTTP requests. Unchanged fields and entries are left out,
+/// so an empty diff means the requests send the same thing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct RequestDiff {
+    /// Changes to the method, URL, body type, and authentication type
+    pub fields: Vec<FieldChange>,
+    pub url_parameters: Vec<KeyValueChange>,
+    pub headers: Vec<KeyValueChange>,
+    /// Changes to body keys, like `text` or `form`
+    pub body: Vec<FieldChange>,
+    /// Changes to authentication config keys, like `token` or `username`
+    pub authentication: Vec<FieldChange>,
+}
+
+impl RequestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+            && self.url_parameters.is_empty()
+            && self.headers.is_empty()
+            && self.body.is_empty()
+            && self.authentication.is_empty()
+    }
+}
+
+/// A value that was added, removed, or changed. Missing sides are `null`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct FieldChange {
+    pub name: String,
+    #[ts(type = "any")]
+    pub before: Option<Value>,
+    #[ts(type = "any")]
+    pub after: Option<Value>,
+}
+
+/// A header or URL parameter that was added, removed, toggled, or changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct KeyValueChange {
+    pub name: String,
+    pub before: Option<KeyValueEntry>,
+    pub after: Option<KeyValueEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct KeyValueEntry {
+    pub value: String,
+    pub enabled: bool,
+}
+
+impl<'a> ClientDb<'a> {
+    /// Diff two HTTP requests, or two revisions of the same request, from `a` to `b`
+    pub fn diff_requests(
+        &self,
+        a: &RequestDiffTarget,
+        b: &RequestDiffTarget,
+    ) -> Result<RequestDiff> {
+        let a = self.request_diff_snapshot(a)?;
+        let b = self.request_diff_snapshot(b)?;
+        Ok(diff_http_requests(&a, &b))
+    }
+
+    fn request_diff_snapshot(&self, target: &RequestDiffTarget) -> Result<HttpRequest> {
+        let snapshot = match target {
+            RequestDiffTarget::Request { id } => return self.get_http_request(id),
+            RequestDiffTarget::Revision { id } => self.get_request_revision(*id)?.snapshot,
+        };
+        match snapshot {
+            AnyModel::HttpRequest(r) => Ok(r),
+            m => Err(GenericError(format!("Cannot diff revision of {}", m.model()))),
+        }
+    }
+}
+
+/// Diff the parts of two HTTP requests that affect what gets sent
+pub fn diff_http_requests(a: &HttpRequest, b: &HttpRequest) -> RequestDiff {
+    let mut fields = Vec::new();
+    let mut push_field = |name: &str, before: Option<&str>, after: Option<&str>| {
+        if before != after {
+            fields.push(FieldChange {
+                name: name.to_string(),
+                before: before.map(|v| Value::String(v.to_string())),
+                after: after.map(|v| Value::String(v.to_string())),
+            });
+        }
+    };
+    push_field("method", Some(a.method.as_str()), Some(b.method.as_str()));
+    push_field("url", Some(a.url.as_str()), Some(b.url.as_str()));
+    push_field("bodyType", a.body_type.as_deref(), b.body_type.as_deref());
+    push_field(
+        "authenticationType",
+        a.authentication_type.as_deref(),
+        b.authentication_type.as_deref(),
+    );
+
+    let params = |r: &HttpRequest| -> Vec<(String, String, bool)> {
+        r.url_parameters.iter().map(|p| (p.name.clone(), p.value.clone(), p.enabled)).collect()
+    };
+    let headers = |r: &HttpRequest| -> Vec<(String, String, bool)> {
+        r.headers.iter().map(|h| (h.name.clone(), h.value.clone(), h.enabled)).collect()
+    };
+
+    RequestDiff {
+        fields,
+        url_parameters: diff_key_values(params(a), params(b), false),
+        headers: diff_key_values(headers(a), headers(b), true),
+        body: diff_maps(&a.body, &b.body),
+        authentication: diff_maps(&a.authentication, &b.authentication),
+    }
+}
+
+fn diff_maps(a: &BTreeMap<String, Value>, b: &BTreeMap<String, Value>) -> Vec<FieldChange> {
+    let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+    keys.into_iter()
+        .filter(|k| a.get(*k) != b.get(*k))
+        .map(|k| FieldChange {
+            name: k.clone(),
+            before: a.get(k).cloned(),
+            after: b.get(k).cloned(),
+        })
+        .collect()
+}
+
+/// Pair up entries with the same name in the order they appear, so a repeated header is compared
+/// with the same occurrence on the other side. Blank rows left in the editor are ignored.
+fn diff_key_values(
+    a: Vec<(String, String, bool)>,
+    b: Vec<(String, String, bool)>,
+    ignore_case: bool,
+) -> Vec<KeyValueChange> {
+    let key = |name: &str| if ignore_case { name.to_lowercase() } else { name.to_string() };
+    let not_blank =
+        |(name, value, _): &(String, String, bool)| !name.is_empty() || !value.is_empty();
+
+    let mut remaining: HashMap<String, Vec<(String, KeyValueEntry)>> = HashMap::new();
+    let mut removed_order = Vec::new();
+    for (name, value, enabled) in a.into_iter().filter(not_blank) {
+        let k = key(&name);
+        if !remaining.contains_key(&k) {
+            removed_order.push(k.clone());
+        }
+        remaining.entry(k).or_default().push((name, KeyValueEntry { value, enabled }));
+    }
+    for entries in remaining.values_mut() {
+        entries.reverse();
+    }
+
+    let mut changes = Vec::new();
+    for (name, value, enabled) in b.into_iter().filter(not_blank) {
+        let after = KeyValueEntry { value, enabled };
+        let before = remaining.get_mut(&key(&name)).and_then(|entries| entries.pop());
+        match before {
+            Some((_, before)) if before == after => {}
+            Some((_, before)) => {
+                changes.push(KeyValueChange { name, before: Some(before), after: Some(after) })
+            }
+            None => changes.push(KeyValueChange { name, before: None, after: Some(after) }),
+        }
+    }
+
+    for k in removed_order {
+        let mut entries = remaining.remove(&k).unwrap_or_default();
+        entries.reverse();
+        for (name, before) in entries {
+            changes.push(KeyValueChange { name, before: Some(before), after: None });
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_in_memory;
+    use crate::models::{HttpRequestHeader, HttpUrlParameter, Workspace};
+    use crate::util::UpdateSource;
+    use serde_json::json;
+
+    fn header(name: &str, value: &str, enabled: bool) -> HttpRequestHeader {
+        HttpRequestHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    fn entry(value: &str, enabled: bool) -> Option<KeyValueEntry> {
+        Some(KeyValueEntry { value: value.to_string(), enabled })
+    }
+
+    #[test]
+    fn diffs_http_requests() {
+        let a = HttpRequest {
+            method: "GET".to_string(),
+            url: "https://api.test/users".to_string(),
+            url_parameters: vec![HttpUrlParameter {
+                name: "page".to_string(),
+                value: "1".to_string(),
+                enabled: true,
+                ..Default::default()
+            }],
+            headers: vec![
+                header("Accept", "application/json", true),
+                header("X-Debug", "1", true),
+                header("X-Trace", "a", true),
+                header("X-Trace", "b", true),
+                header("", "", true),
+            ],
+            body_type: Some("application/json".to_string()),
+            body: BTreeMap::from([("text".to_string(), json!("{}"))]),
+            authentication_type: Some("bearer".to_string()),
+            authentication: BTreeMap::from([("token".to_string(), json!("abc"))]),
+            ..Default::default()
+        };
+        let b = HttpRequest {
+            method: "POST".to_string(),
+            headers: vec![
+                header("accept", "application/json", true),
+                header("X-Trace", "a", true),
+                header("X-Trace", "c", true),
+                header("X-Debug", "1", false),
+                header("X-New", "yes", true),
+            ],
+            url_parameters: Vec::new(),
+            body: BTreeMap::from([
+                ("text".to_string(), json!("{\"name\":\"Ada\"}")),
+                ("sendJsonComments".to_string(), json!(true)),
+            ]),
+            authentication: BTreeMap::from([("token".to_string(), json!("abc"))]),
+            ..a.clone()
+        };
+
+        let diff = diff_http_requests(&a, &b);
+        assert_eq!(
+            diff.fields,
+            vec![FieldChange {
+                name: "method".to_string(),
+                before: Some(json!("GET")),
+                after: Some(json!("POST")),
+            }]
+        );
+        assert_eq!(
+            diff.url_parameters,
+            vec![KeyValueChange {
+                name: "page".to_string(),
+                before: entry("1", true),
+                after: None
+            }]
+        );
+        assert_eq!(
+            diff.headers,
+            vec![
+                KeyValueChange {
+                    name: "X-Trace".to_string(),
+                    before: entry("b", true),
+                    after: entry("c", true),
+                },
+                KeyValueChange {
+                    name: "X-Debug".to_string(),
+                    before: entry("1", true),
+                    after: entry("1", false),
+                },
+                KeyValueChange {
+                    name: "X-New".to_string(),
+                    before: None,
+                    after: entry("yes", true)
+                },
+            ]
+        );
+        assert_eq!(
+            diff.body.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(),
+            vec!["sendJsonComments", "text"]
+        );
+        assert!(diff.authentication.is_empty());
+        assert!(diff_http_requests(&a, &a).is_empty());
+    }
+
+    #[test]
+    fn diffs_requests_against_revisions() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let window = &UpdateSource::from_window_label("main");
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Diff Test".to_string(), ..Default::default() },
+                &UpdateSource::Sync,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    url: "https://example.com/v1".to_string(),
+                    ..Default::default()
+                },
+                window,
+            )
+            .expect("Failed to upsert request");
+        let other = db
+            .upsert_http_request(
+                &HttpRequest { id: String::new(), ..request.clone() },
+                &UpdateSource::Sync,
+            )
+            .expect("Failed to upsert request");
+        db.upsert_http_request(
+            &HttpRequest { url: "https://example.com/v2".to_string(), ..request.clone() },
+            &UpdateSource::Sync,
+        )
+        .expect("Failed to upsert request");
+
+        let revision = &db.list_request_revisions(&request.id).expect("Failed to list")[0];
+        let diff = db
+            .diff_requests(
+                &RequestDiffTarget::Revision { id: revision.id },
+                &RequestDiffTarget::Request { id: request.id.clone() },
+            )
+            .expect("Failed to diff");
+        assert_eq!(
+            diff.fields,
+            vec![FieldChange {
+                name: "url".to_string(),
+                before: Some(json!("https://example.com/v1")),
+                after: Some(json!("https://example.com/v2")),
+            }]
+        );
+
+        let diff = db
+            .diff_requests(
+                &RequestDiffTarget::Revision { id: revision.id },
+                &RequestDiffTarget::Request { id: other.id.clone() },
+            )
+            .expect("Failed to diff");
+        assert!(diff.is_empty());
+    }
+}
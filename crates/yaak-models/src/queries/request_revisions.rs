@@ -0,0 +1,252 @@
+use crate::client_db::ClientDb;
+use crate::error::Error::{GenericError, ModelNotFound};
+use crate::error::Result;
+use crate::models::{AnyModel, GrpcRequest, HttpRequest};
+use crate::util::UpdateSource;
+use rusqlite::types::Type;
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Maximum number of revisions kept for a single request
+const MAX_REVISIONS_PER_REQUEST: i64 = 50;
+
+/// Edits made within this many seconds of the latest revision are folded into it, so typing in
+/// an editor doesn't produce a revision per keystroke.
+const REVISION_COALESCE_SECONDS: i64 = 60;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct RequestRevision {
+    pub id: i64,
+    pub created_at: String,
+    pub request_id: String,
+    /// The full request as it was after the change
+    pub snapshot: AnyModel,
+}
+
+impl<'a> ClientDb<'a> {
+    pub fn list_request_revisions(&self, request_id: &str) -> Result<Vec<RequestRevision>> {
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT id, created_at, request_id, snapshot
+                FROM request_revisions
+                WHERE request_id = ?1
+                ORDER BY id DESC
+            "#,
+        )?;
+
+        let items = stmt.query_map(params![request_id], revision_from_row)?;
+        Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
+    }
+
+    pub fn get_request_revision(&self, id: i64) -> Result<RequestRevision> {
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT id, created_at, request_id, snapshot
+                FROM request_revisions
+                WHERE id = ?1
+            "#,
+        )?;
+
+        stmt.query_row(params![id], revision_from_row)
+            .optional()?
+            .ok_or(ModelNotFound(format!("request_revision {id}")))
+    }
+
+    /// Overwrite a request with the contents of a previous revision. The request keeps its current
+    /// location in the tree, and the restore is recorded in the history like any other edit.
+    pub fn restore_request_revision(&self, id: i64, source: &UpdateSource) -> Result<AnyModel> {
+        let revision = self.get_request_revision(id)?;
+        let restored = match revision.snapshot {
+            AnyModel::HttpRequest(r) => {
+                let current = self.get_http_request(&r.id)?;
+                self.upsert_http_request(
+                    &HttpRequest {
+                        folder_id: current.folder_id,
+                        sort_priority: current.sort_priority,
+                        deleted_at: current.deleted_at,
+                        ..r
+                    },
+                    source,
+                )?
+                .into()
+            }
+            AnyModel::GrpcRequest(r) => {
+                let current = self.get_grpc_request(&r.id)?;
+                self.upsert_grpc_request(
+                    &GrpcRequest {
+                        folder_id: current.folder_id,
+                        sort_priority: current.sort_priority,
+                        deleted_at: current.deleted_at,
+                        ..r
+                    },
+                    source,
+                )?
+                .into()
+            }
+            m => return Err(GenericError(format!("Cannot restore revision of {}", m.model()))),
+        };
+
+        Ok(restored)
+    }
+
+    /// Record the new state of a request after a user edit. Changes coming from sync, imports,
+    /// plugins, and background work are not recorded.
+    pub(crate) fn record_request_revision(
+        &self,
+        request: &AnyModel,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        if !matches!(source, UpdateSource::Window { .. }) {
+            return Ok(());
+        }
+
+        let (request_id, workspace_id) = match request {
+            AnyModel::HttpRequest(r) => (&r.id, &r.workspace_id),
+            AnyModel::GrpcRequest(r) => (&r.id, &r.workspace_id),
+            _ => return Ok(()),
+        };
+        let snapshot = serde_json::to_string(request)?;
+
+        let recent_id: Option<i64> = self
+            .conn()
+            .resolve()
+            .query_row(
+                r#"
+                    SELECT id
+                    FROM request_revisions
+                    WHERE request_id = ?1
+                      AND id = (SELECT MAX(id) FROM request_revisions WHERE request_id = ?1)
+                      AND created_at > STRFTIME('%Y-%m-%d %H:%M:%f', 'NOW', ?2)
+                "#,
+                params![request_id, format!("-{REVISION_COALESCE_SECONDS} seconds")],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        if let Some(recent_id) = recent_id {
+            self.conn().resolve().execute(
+                "UPDATE request_revisions SET snapshot = ?1 WHERE id = ?2",
+                params![snapshot, recent_id],
+            )?;
+            return Ok(());
+        }
+
+        self.conn().resolve().execute(
+            r#"
+                INSERT INTO request_revisions (model, request_id, workspace_id, snapshot)
+                VALUES (?1, ?2, ?3, ?4)
+            "#,
+            params![request.model(), request_id, workspace_id, snapshot],
+        )?;
+
+        self.conn().resolve().execute(
+            r#"
+                DELETE FROM request_revisions
+                WHERE request_id = ?1
+                  AND id NOT IN (
+                    SELECT id FROM request_revisions
+                    WHERE request_id = ?1
+                    ORDER BY id DESC
+                    LIMIT ?2
+                  )
+            "#,
+            params![request_id, MAX_REVISIONS_PER_REQUEST],
+        )?;
+
+        Ok(())
+    }
+
+    pub(crate) fn delete_request_revisions(&self, request_id: &str) -> Result<()> {
+        self.conn()
+            .resolve()
+            .execute("DELETE FROM request_revisions WHERE request_id = ?1", params![request_id])?;
+        Ok(())
+    }
+}
+
+fn revision_from_row(row: &rusqlite::Row) -> rusqlite::Result<RequestRevision> {
+    let snapshot_raw: String = row.get(3)?;
+    let snapshot = serde_json::from_str::<AnyModel>(&snapshot_raw)
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(3, Type::Text, Box::new(e)))?;
+    Ok(RequestRevision {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        request_id: row.get(2)?,
+        snapshot,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{AnyModel, HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn records_and_restores_revisions() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let window = &UpdateSource::from_window_label("main");
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Revisions Test".to_string(), ..Default::default() },
+                &UpdateSource::Sync,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    url: "https://example.com/v1".to_string(),
+                    ..Default::default()
+                },
+                window,
+            )
+            .expect("Failed to upsert request");
+
+        // Non-user edits aren't recorded
+        db.upsert_http_request(
+            &HttpRequest { url: "https://example.com/sync".to_string(), ..request.clone() },
+            &UpdateSource::Sync,
+        )
+        .expect("Failed to upsert request");
+        let revisions = db.list_request_revisions(&request.id).expect("Failed to list");
+        assert_eq!(revisions.len(), 1);
+
+        // Rapid edits are coalesced into the latest revision
+        db.upsert_http_request(
+            &HttpRequest { url: "https://example.com/v2".to_string(), ..request.clone() },
+            window,
+        )
+        .expect("Failed to upsert request");
+        let revisions = db.list_request_revisions(&request.id).expect("Failed to list");
+        assert_eq!(revisions.len(), 1);
+
+        // Age the revision so the next edit starts a new one
+        db.conn()
+            .resolve()
+            .execute("UPDATE request_revisions SET created_at = '2000-01-01 00:00:00.000'", [])
+            .expect("Failed to age revision");
+        db.upsert_http_request(
+            &HttpRequest { url: "https://example.com/v3".to_string(), ..request.clone() },
+            window,
+        )
+        .expect("Failed to upsert request");
+        let revisions = db.list_request_revisions(&request.id).expect("Failed to list");
+        assert_eq!(revisions.len(), 2);
+
+        let restored =
+            db.restore_request_revision(revisions[1].id, window).expect("Failed to restore");
+        match restored {
+            AnyModel::HttpRequest(r) => assert_eq!(r.url, "https://example.com/v2"),
+            m => panic!("Unexpected model {m:?}"),
+        }
+
+        db.delete_http_request(&request, window).expect("Failed to delete request");
+        assert!(db.list_request_revisions(&request.id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{HttpResponse, HttpResponseHeader};
+use crate::queries::FieldChange;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::fs;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct ResponseDiffOptions {
+    /// Compare JSON arrays as unordered collections, so reordered items aren't reported
+    pub ignore_array_order: bool,
+    /// Header names to leave out of the diff, like `date` or `x-request-id`
+    pub ignored_headers: Vec<String>,
+}
+
+/// What differs between two stored responses. JSON bodies are compared value by value, and any
+/// other body is compared as a whole.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct ResponseDiff {
+    /// Changes to the status code
+    pub fields: Vec<FieldChange>,
+    /// Changes to header values, matched by name regardless of case. Repeated headers are joined
+    /// with a comma.
+    pub headers: Vec<FieldChange>,
+    /// Whether both bodies were JSON, in which case body changes are named by JSONPath
+    pub json: bool,
+    /// Changes within the body, like `$.users[0].name`, or a single `$` change holding both bodies
+    /// when they aren't JSON
+    pub body: Vec<FieldChange>,
+}
+
+impl ResponseDiff {
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty() && self.headers.is_empty() && self.body.is_empty()
+    }
+}
+
+impl<'a> ClientDb<'a> {
+    /// Diff two stored responses, like the same request sent to staging and production, from `a`
+    /// to `b`
+    pub fn diff_responses(
+        &self,
+        a_id: &str,
+        b_id: &str,
+        options: &ResponseDiffOptions,
+    ) -> Result<ResponseDiff> {
+        let a = self.get_http_response(a_id)?;
+        let b = self.get_http_response(b_id)?;
+        Ok(diff_http_responses(&a, &response_body(&a)?, &b, &response_body(&b)?, options))
+    }
+}
+
+fn response_body(response: &HttpResponse) -> Result<Vec<u8>> {
+    match &response.body_path {
+        Some(path) => Ok(fs::read(path)?),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Diff two responses given their bodies
+pub fn diff_http_responses(
+    a: &HttpResponse,
+    a_body: &[u8],
+    b: &HttpResponse,
+    b_body: &[u8],
+    options: &ResponseDiffOptions,
+) -> ResponseDiff {
+    let mut fields = Vec::new();
+    if a.status != b.status {
+        fields.push(FieldChange {
+            name: "status".to_string(),
+            before: Some(a.status.into()),
+            after: Some(b.status.into()),
+        });
+    }
+
+    let headers = diff_headers(&a.headers, &b.headers, &options.ignored_headers);
+
+    let parsed = (serde_json::from_slice::<Value>(a_body), serde_json::from_slice::<Value>(b_body));
+    let (json, body) = match parsed {
+        (Ok(a), Ok(b)) => {
+            let mut changes = Vec::new();
+            diff_json("$".to_string(), &a, &b, options.ignore_array_order, &mut changes);
+            (true, changes)
+        }
+        _ if a_body == b_body => (false, Vec::new()),
+        _ => (
+            false,
+            vec![FieldChange {
+                name: "$".to_string(),
+                before: Some(String::from_utf8_lossy(a_body).into()),
+                after: Some(String::from_utf8_lossy(b_body).into()),
+            }],
+        ),
+    };
+
+    ResponseDiff { fields, headers, json, body }
+}
+
+fn diff_headers(
+    a: &[HttpResponseHeader],
+    b: &[HttpResponseHeader],
+    ignored: &[String],
+) -> Vec<FieldChange> {
+    let is_ignored = |name: &str| ignored.iter().any(|i| i.eq_ignore_ascii_case(name));
+    let values = |headers: &[HttpResponseHeader], name: &str| {
+        let values: Vec<&str> = headers
+            .iter()
+            .filter(|h| h.name.eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str())
+            .collect();
+        if values.is_empty() { None } else { Some(values.join(", ")) }
+    };
+
+    let names: BTreeSet<String> =
+        a.iter().chain(b).map(|h| h.name.to_lowercase()).filter(|name| !is_ignored(name)).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let before = values(a, &name);
+            let after = values(b, &name);
+            (before != after).then(|| FieldChange {
+                name,
+                before: before.map(Value::String),
+                after: after.map(Value::String),
+            })
+        })
+        .collect()
+}
+
+fn diff_json(
+    path: String,
+    a: &Value,
+    b: &Value,
+    ignore_array_order: bool,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let keys: BTreeSet<&String> = a.keys().chain(b.keys()).collect();
+            for key in keys {
+                let path = json_path_key(&path, key);
+                match (a.get(key), b.get(key)) {
+                    (Some(a), Some(b)) => diff_json(path, a, b, ignore_array_order, changes),
+                    (before, after) => changes.push(FieldChange {
+                        name: path,
+                        before: before.cloned(),
+                        after: after.cloned(),
+                    }),
+                }
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if ignore_array_order => {
+            // Items are matched by value, so an edited item shows up as removed and added
+            let mut unmatched: Vec<Option<String>> =
+                a.iter().map(|v| Some(canonical_json(v))).collect();
+            let mut added = Vec::new();
+            for (i, item) in b.iter().enumerate() {
+                let item_key = canonical_json(item);
+                match unmatched.iter_mut().find(|k| k.as_deref() == Some(item_key.as_str())) {
+                    Some(k) => *k = None,
+                    None => added.push((i, item)),
+                }
+            }
+            for (i, item) in a.iter().enumerate() {
+                if unmatched[i].is_some() {
+                    changes.push(FieldChange {
+                        name: format!("{path}[{i}]"),
+                        before: Some(item.clone()),
+                        after: None,
+                    });
+                }
+            }
+            for (i, item) in added {
+                changes.push(FieldChange {
+                    name: format!("{path}[{i}]"),
+                    before: None,
+                    after: Some(item.clone()),
+                });
+            }
+        }
+        (Value::Array(a), Value::Array(b)) => {
+            for i in 0..a.len().max(b.len()) {
+                let path = format!("{path}[{i}]");
+                match (a.get(i), b.get(i)) {
+                    (Some(a), Some(b)) => diff_json(path, a, b, ignore_array_order, changes),
+                    (before, after) => changes.push(FieldChange {
+                        name: path,
+                        before: before.cloned(),
+                        after: after.cloned(),
+                    }),
+                }
+            }
+        }
+        (a, b) if a != b => changes.push(FieldChange {
+            name: path,
+            before: Some(a.clone()),
+            after: Some(b.clone()),
+        }),
+        _ => {}
+    }
+}
+
+fn json_path_key(path: &str, key: &str) -> String {
+    let plain = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain { format!("{path}.{key}") } else { format!("{path}[{}]", Value::from(key)) }
+}
+
+/// Serialize a value so that values which only differ in array order produce the same string.
+/// Object keys are already sorted.
+fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let mut items: Vec<String> = items.iter().map(canonical_json).collect();
+            items.sort();
+            format!("[{}]", items.join(","))
+        }
+        Value::Object(map) => {
+            let entries: Vec<String> = map
+                .iter()
+                .map(|(k, v)| format!("{}:{}", Value::from(k.as_str()), canonical_json(v)))
+                .collect();
+            format!("{{{}}}", entries.join(","))
+        }
+        value => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn header(name: &str, value: &str) -> HttpResponseHeader {
+        HttpResponseHeader { name: name.to_string(), value: value.to_string() }
+    }
+
+    fn names(changes: &[FieldChange]) -> Vec<&str> {
+        changes.iter().map(|c| c.name.as_str()).collect()
+    }
+
+    #[test]
+    fn diffs_status_headers_and_json_bodies() {
+        let a = HttpResponse {
+            status: 200,
+            headers: vec![
+                header("Content-Type", "application/json"),
+                header("Date", "Mon"),
+                header("Set-Cookie", "a=1"),
+                header("Set-Cookie", "b=2"),
+            ],
+            ..Default::default()
+        };
+        let b = HttpResponse {
+            status: 201,
+            headers: vec![
+                header("content-type", "application/json"),
+                header("Date", "Tue"),
+                header("Set-Cookie", "a=1"),
+                header("X-Env", "staging"),
+            ],
+            ..Default::default()
+        };
+        let a_body = br#"{"id":1,"user":{"name":"Ada","tags":["x","y"]},"my key":true}"#;
+        let b_body = br#"{"id":1,"user":{"name":"Grace","tags":["y","x"]},"count":2}"#;
+        let options =
+            ResponseDiffOptions { ignored_headers: vec!["date".to_string()], ..Default::default() };
+
+        let diff = diff_http_responses(&a, a_body, &b, b_body, &options);
+        assert_eq!(
+            diff.fields,
+            vec![FieldChange {
+                name: "status".to_string(),
+                before: Some(json!(200)),
+                after: Some(json!(201)),
+            }]
+        );
+        assert_eq!(
+            diff.headers,
+            vec![
+                FieldChange {
+                    name: "set-cookie".to_string(),
+                    before: Some(json!("a=1, b=2")),
+                    after: Some(json!("a=1")),
+                },
+                FieldChange {
+                    name: "x-env".to_string(),
+                    before: None,
+                    after: Some(json!("staging")),
+                },
+            ]
+        );
+        assert!(diff.json);
+        assert_eq!(
+            names(&diff.body),
+            vec![
+                "$.count",
+                "$[\"my key\"]",
+                "$.user.name",
+                "$.user.tags[0]",
+                "$.user.tags[1]"
+            ]
+        );
+
+        let options = ResponseDiffOptions { ignore_array_order: true, ..options };
+        let diff = diff_http_responses(&a, a_body, &b, b_body, &options);
+        assert_eq!(names(&diff.body), vec!["$.count", "$[\"my key\"]", "$.user.name"]);
+    }
+
+    #[test]
+    fn matches_unordered_arrays_by_value() {
+        let a = json!([{"id": 1, "tags": ["a", "b"]}, {"id": 2}, {"id": 3}]);
+        let b = json!([{"id": 3}, {"tags": ["b", "a"], "id": 1}, {"id": 4}]);
+        let mut changes = Vec::new();
+        diff_json("$".to_string(), &a, &b, true, &mut changes);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    name: "$[1]".to_string(),
+                    before: Some(json!({"id": 2})),
+                    after: None
+                },
+                FieldChange {
+                    name: "$[2]".to_string(),
+                    before: None,
+                    after: Some(json!({"id": 4}))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn compares_other_bodies_as_text() {
+        let response = HttpResponse::default();
+        let options = ResponseDiffOptions::default();
+
+        let diff = diff_http_responses(&response, b"<p>a</p>", &response, b"<p>b</p>", &options);
+        assert!(!diff.json);
+        assert_eq!(
+            diff.body,
+            vec![FieldChange {
+                name: "$".to_string(),
+                before: Some(json!("<p>a</p>")),
+                after: Some(json!("<p>b</p>")),
+            }]
+        );
+        assert!(diff_http_responses(&response, b"ok", &response, b"ok", &options).is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{HttpResponse, Workspace, WorkspaceIden};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+const MAX_SEARCH_RESULTS: usize = 100;
+
+/// Response bodies larger than this are not added to the search index
+const MAX_INDEXED_RESPONSE_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct SearchResult {
+    /// Model type of the match (eg. `http_request` or `http_response`)
+    pub model: String,
+    pub id: String,
+    /// Request the match belongs to. Same as `id` when the match is a request.
+    pub request_id: String,
+    /// Excerpt of the text surrounding the match
+    pub snippet: String,
+}
+
+impl<'a> ClientDb<'a> {
+    /// Search request names, URLs, bodies, and headers, as well as any indexed response bodies,
+    /// within a workspace. Each whitespace-separated term is matched as a prefix, and all terms
+    /// must match. Results are ordered by relevance.
+    pub fn search_workspace(&self, workspace_id: &str, query: &str) -> Result<Vec<SearchResult>> {
+        let query = match to_fts_query(query) {
+            Some(q) => q,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut results: Vec<(f64, SearchResult)> = Vec::new();
+
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT id, model, snippet(search_requests, -1, '', '', '…', 12), bm25(search_requests)
+                FROM search_requests
+                WHERE search_requests MATCH ?1 AND workspace_id = ?2
+                ORDER BY bm25(search_requests)
+                LIMIT ?3
+            "#,
+        )?;
+        let items =
+            stmt.query_map(params![query, workspace_id, MAX_SEARCH_RESULTS as i64], |row| {
+                let id: String = row.get(0)?;
+                let rank: f64 = row.get(3)?;
+                let result = SearchResult {
+                    model: row.get(1)?,
+                    request_id: id.clone(),
+                    id,
+                    snippet: row.get(2)?,
+                };
+                Ok((rank, result))
+            })?;
+        for item in items {
+            results.push(item?);
+        }
+
+        let mut stmt = self.conn().prepare(
+            r#"
+                SELECT r.id, s.request_id, snippet(search_http_responses, -1, '', '', '…', 12),
+                       bm25(search_http_responses)
+                FROM search_http_responses s
+                    JOIN http_responses r ON r.rowid = s.rowid
+                WHERE search_http_responses MATCH ?1 AND s.workspace_id = ?2
+                ORDER BY bm25(search_http_responses)
+                LIMIT ?3
+            "#,
+        )?;
+        let items =
+            stmt.query_map(params![query, workspace_id, MAX_SEARCH_RESULTS as i64], |row| {
+                let rank: f64 = row.get(3)?;
+                let result = SearchResult {
+                    model: "http_response".to_string(),
+                    id: row.get(0)?,
+                    request_id: row.get(1)?,
+                    snippet: row.get(2)?,
+                };
+                Ok((rank, result))
+            })?;
+        for item in items {
+            results.push(item?);
+        }
+
+        // Lower bm25 scores are better matches
+        results.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        results.truncate(MAX_SEARCH_RESULTS);
+
+        Ok(results.into_iter().map(|(_, r)| r).collect())
+    }
+
+    /// Add a finished response's body to the search index. Bodies that aren't valid UTF-8 or are
+    /// too large are skipped, as are all bodies in workspaces that turned response indexing off.
+    pub fn index_http_response_body(&self, response: &HttpResponse, body: &[u8]) -> Result<()> {
+        if body.len() > MAX_INDEXED_RESPONSE_BODY_BYTES {
+            return Ok(());
+        }
+        let workspace: Option<Workspace> =
+            self.find_optional(WorkspaceIden::Id, response.workspace_id.as_str());
+        if !workspace.map(|w| w.setting_index_response_bodies).unwrap_or(true) {
+            return Ok(());
+        }
+        let body = match std::str::from_utf8(body) {
+            Ok(b) => b,
+            Err(_) => return Ok(()),
+        };
+
+        self.conn().resolve().execute(
+            r#"
+                INSERT OR REPLACE INTO search_http_responses (rowid, workspace_id, request_id, body)
+                SELECT rowid, workspace_id, request_id, ?2
+                FROM http_responses
+                WHERE id = ?1
+            "#,
+            params![response.id, body],
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Convert free-form user input into an FTS5 query, quoting each term so that special characters
+/// can't produce a syntax error.
+fn to_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> =
+        query.split_whitespace().map(|t| format!("\"{}\"*", t.replace('"', "\"\""))).collect();
+    if terms.is_empty() { None } else { Some(terms.join(" ")) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_in_memory;
+    use crate::models::{HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn searches_requests_and_responses() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "Search Test".to_string(),
+                    setting_index_response_bodies: true,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    name: "List users".to_string(),
+                    url: "https://example.com/api/users".to_string(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let results = db.search_workspace(&workspace.id, "use").expect("Failed to search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, request.id);
+        assert_eq!(results[0].model, "http_request");
+
+        // Special characters in the query must not break the FTS syntax
+        db.search_workspace(&workspace.id, "\"foo* OR (").expect("Failed to search");
+
+        let response = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                source,
+                &blob_manager,
+            )
+            .expect("Failed to upsert response");
+        db.index_http_response_body(&response, br#"{"status":"sparkling"}"#)
+            .expect("Failed to index response");
+
+        let results = db.search_workspace(&workspace.id, "sparkl").expect("Failed to search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, response.id);
+        assert_eq!(results[0].request_id, request.id);
+
+        // Renaming re-indexes the request
+        db.upsert_http_request(&HttpRequest { name: "Renamed".to_string(), ..request }, source)
+            .expect("Failed to upsert request");
+        assert_eq!(db.search_workspace(&workspace.id, "renamed").unwrap().len(), 1);
+        assert_eq!(db.search_workspace(&workspace.id, "list").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn skips_response_bodies_when_indexing_is_off() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "No Index Test".to_string(),
+                    setting_index_response_bodies: false,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let response = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                source,
+                &blob_manager,
+            )
+            .expect("Failed to upsert response");
+        db.index_http_response_body(&response, br#"{"status":"sparkling"}"#)
+            .expect("Failed to index response");
+
+        assert!(db.search_workspace(&workspace.id, "sparkl").unwrap().is_empty());
+    }
+}
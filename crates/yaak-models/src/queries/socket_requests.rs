@@ -0,0 +1,138 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{Folder, FolderIden, SocketRequest, SocketRequestIden};
+use crate::util::UpdateSource;
+use chrono::Utc;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_socket_request(&self, id: &str) -> Result<SocketRequest> {
+        self.find_one(SocketRequestIden::Id, id)
+    }
+
+    pub fn list_socket_requests(&self, workspace_id: &str) -> Result<Vec<SocketRequest>> {
+        let requests: Vec<SocketRequest> =
+            self.find_many(SocketRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
+    }
+
+    pub fn list_socket_requests_for_folder_recursive(
+        &self,
+        folder_id: &str,
+    ) -> Result<Vec<SocketRequest>> {
+        let mut children = Vec::new();
+        for folder in self.find_many::<Folder>(FolderIden::FolderId, folder_id, None)? {
+            children.extend(self.list_socket_requests_for_folder_recursive(&folder.id)?);
+        }
+        for request in
+            self.find_many::<SocketRequest>(SocketRequestIden::FolderId, folder_id, None)?
+        {
+            if request.deleted_at.is_none() {
+                children.push(request);
+            }
+        }
+        Ok(children)
+    }
+
+    pub fn delete_socket_request(
+        &self,
+        socket_request: &SocketRequest,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        self.delete_all_socket_responses_for_request(socket_request.id.as_str(), source)?;
+        self.delete(socket_request, source)
+    }
+
+    pub fn delete_socket_request_by_id(
+        &self,
+        id: &str,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        let request = self.get_socket_request(id)?;
+        self.delete_socket_request(&request, source)
+    }
+
+    pub fn trash_socket_request(
+        &self,
+        socket_request: &SocketRequest,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        self.upsert(
+            &SocketRequest { deleted_at: Some(Utc::now().naive_utc()), ..socket_request.clone() },
+            source,
+        )
+    }
+
+    pub fn restore_socket_request(
+        &self,
+        socket_request: &SocketRequest,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        if let Some(folder_id) = socket_request.folder_id.as_deref() {
+            self.restore_folder_ancestors(folder_id, source)?;
+        }
+        self.upsert(&SocketRequest { deleted_at: None, ..socket_request.clone() }, source)
+    }
+
+    pub fn duplicate_socket_request(
+        &self,
+        socket_request: &SocketRequest,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        let mut socket_request = socket_request.clone();
+        socket_request.id = "".to_string();
+        socket_request.sort_priority = self.sort_priority_after(
+            &socket_request.workspace_id,
+            socket_request.folder_id.as_deref(),
+            &socket_request.id,
+            source,
+        )?;
+        self.upsert(&socket_request, source)
+    }
+
+    pub fn upsert_socket_request(
+        &self,
+        socket_request: &SocketRequest,
+        source: &UpdateSource,
+    ) -> Result<SocketRequest> {
+        self.upsert(socket_request, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{SocketPayloadFormat, SocketProtocol, SocketRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn persists_and_trashes_requests() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "Raw".to_string(), ..Default::default() }, source)
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_socket_request(
+                &SocketRequest {
+                    workspace_id: workspace.id.clone(),
+                    protocol: SocketProtocol::Udp,
+                    url: "localhost:9000".to_string(),
+                    payload: "de ad be ef".to_string(),
+                    payload_format: SocketPayloadFormat::Hex,
+                    read_timeout: 500,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        assert!(request.id.starts_with("sk_"));
+        assert_eq!(db.get_socket_request(&request.id).unwrap(), request);
+
+        db.trash_socket_request(&request, source).unwrap();
+        assert!(db.list_socket_requests(&workspace.id).unwrap().is_empty());
+        db.restore_socket_request(&request, source).unwrap();
+        assert_eq!(db.list_socket_requests(&workspace.id).unwrap().len(), 1);
+    }
+}
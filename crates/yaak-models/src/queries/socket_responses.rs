@@ -0,0 +1,91 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{SocketResponse, SocketResponseIden, SocketResponseState};
+use crate::queries::MAX_HISTORY_ITEMS;
+use crate::util::UpdateSource;
+use log::debug;
+use sea_query::{Expr, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+
+impl<'a> ClientDb<'a> {
+    pub fn get_socket_response(&self, id: &str) -> Result<SocketResponse> {
+        self.find_one(SocketResponseIden::Id, id)
+    }
+
+    pub fn delete_all_socket_responses_for_request(
+        &self,
+        request_id: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        for m in self.list_socket_responses_for_request(request_id)? {
+            self.delete(&m, source)?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_all_socket_responses_for_workspace(
+        &self,
+        workspace_id: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        for m in self.list_socket_responses(workspace_id)? {
+            self.delete(&m, source)?;
+        }
+        Ok(())
+    }
+
+    pub fn list_socket_responses(&self, workspace_id: &str) -> Result<Vec<SocketResponse>> {
+        self.find_many(SocketResponseIden::WorkspaceId, workspace_id, None)
+    }
+
+    pub fn list_socket_responses_for_request(
+        &self,
+        request_id: &str,
+    ) -> Result<Vec<SocketResponse>> {
+        self.find_many(SocketResponseIden::RequestId, request_id, None)
+    }
+
+    pub fn delete_socket_response(
+        &self,
+        socket_response: &SocketResponse,
+        source: &UpdateSource,
+    ) -> Result<SocketResponse> {
+        self.delete(socket_response, source)
+    }
+
+    pub fn delete_socket_response_by_id(
+        &self,
+        id: &str,
+        source: &UpdateSource,
+    ) -> Result<SocketResponse> {
+        let socket_response = self.get_socket_response(id)?;
+        self.delete_socket_response(&socket_response, source)
+    }
+
+    pub fn upsert_socket_response(
+        &self,
+        socket_response: &SocketResponse,
+        source: &UpdateSource,
+    ) -> Result<SocketResponse> {
+        let responses = self.list_socket_responses_for_request(&socket_response.request_id)?;
+
+        for m in responses.iter().skip(MAX_HISTORY_ITEMS - 1) {
+            debug!("Deleting old socket response {}", m.id);
+            self.delete_socket_response(&m, source)?;
+        }
+
+        self.upsert(socket_response, source)
+    }
+
+    pub fn cancel_pending_socket_responses(&self) -> Result<()> {
+        let closed = serde_json::to_value(&SocketResponseState::Closed)?;
+        let (sql, params) = Query::update()
+            .table(SocketResponseIden::Table)
+            .values([(SocketResponseIden::State, closed.as_str().into())])
+            .cond_where(Expr::col(SocketResponseIden::State).ne(closed.as_str()))
+            .build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        stmt.execute(&*params.as_params())?;
+        Ok(())
+    }
+}
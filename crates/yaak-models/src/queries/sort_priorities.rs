@@ -0,0 +1,328 @@
+use crate::client_db::ClientDb;
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use crate::models::{
+    AnyModel, Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden,
+    MqttRequest, MqttRequestIden, SocketRequest, SocketRequestIden, WebsocketRequest,
+    WebsocketRequestIden,
+};
+use crate::util::UpdateSource;
+
+/// Gap between the sort priorities of neighbouring items
+const SORT_PRIORITY_STEP: f64 = 1000.0;
+
+/// Siblings are renormalized before inserting between two items closer together than this, since
+/// repeatedly halving the gap eventually runs out of floating-point precision.
+const MIN_SORT_PRIORITY_GAP: f64 = 1e-6;
+
+impl<'a> ClientDb<'a> {
+    /// List the folders and requests directly inside a folder, or at the root of the workspace
+    /// when `folder_id` is `None`. Trashed items are excluded.
+    pub(crate) fn list_sidebar_children(
+        &self,
+        workspace_id: &str,
+        folder_id: Option<&str>,
+    ) -> Result<Vec<AnyModel>> {
+        let mut children: Vec<AnyModel> = Vec::new();
+
+        match folder_id {
+            Some(fid) => {
+                for m in self.find_many::<Folder>(FolderIden::FolderId, fid, None)? {
+                    children.push(m.into());
+                }
+                for m in self.find_many::<HttpRequest>(HttpRequestIden::FolderId, fid, None)? {
+                    children.push(m.into());
+                }
+                for m in self.find_many::<GrpcRequest>(GrpcRequestIden::FolderId, fid, None)? {
+                    children.push(m.into());
+                }
+                for m in
+                    self.find_many::<WebsocketRequest>(WebsocketRequestIden::FolderId, fid, None)?
+                {
+                    children.push(m.into());
+                }
+                for m in self.find_many::<MqttRequest>(MqttRequestIden::FolderId, fid, None)? {
+                    children.push(m.into());
+                }
+                for m in self.find_many::<SocketRequest>(SocketRequestIden::FolderId, fid, None)? {
+                    children.push(m.into());
+                }
+            }
+            None => {
+                for m in self.list_folders(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+                for m in self.list_http_requests(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+                for m in self.list_grpc_requests(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+                for m in self.list_websocket_requests(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+                for m in self.list_mqtt_requests(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+                for m in self.list_socket_requests(workspace_id)? {
+                    if m.folder_id.is_none() {
+                        children.push(m.into());
+                    }
+                }
+            }
+        }
+
+        children.retain(|m| !is_trashed(m));
+        children.sort_by(|a, b| sort_priority(a).total_cmp(&sort_priority(b)));
+        Ok(children)
+    }
+
+    /// Pick a sort priority that places a new item directly after the sibling `after_id` within
+    /// its parent, without jumping past the next sibling. If the gap to the next sibling has become
+    /// too small, the siblings are renormalized first.
+    pub(crate) fn sort_priority_after(
+        &self,
+        workspace_id: &str,
+        folder_id: Option<&str>,
+        after_id: &str,
+        source: &UpdateSource,
+    ) -> Result<f64> {
+        let mut children = self.list_sidebar_children(workspace_id, folder_id)?;
+        let idx = match children.iter().position(|m| m.id() == after_id) {
+            Some(idx) => idx,
+            None => {
+                let last = children.last().map(sort_priority).unwrap_or_default();
+                return Ok(last + SORT_PRIORITY_STEP);
+            }
+        };
+
+        let too_close = children.get(idx + 1).is_some_and(|next| {
+            sort_priority(next) - sort_priority(&children[idx]) < MIN_SORT_PRIORITY_GAP
+        });
+        if too_close {
+            children = self.renormalize_children(children, source)?;
+        }
+
+        let after = sort_priority(&children[idx]);
+        Ok(match children.get(idx + 1) {
+            Some(next) => after + (sort_priority(next) - after) / 2.0,
+            None => after + SORT_PRIORITY_STEP,
+        })
+    }
+
+    /// Reorder the folders and requests inside a folder, or at the root of the workspace when
+    /// `folder_id` is `None`, assigning fresh evenly spaced sort priorities. Children missing from
+    /// `ordered_ids` keep their relative order and are placed after the ones listed.
+    pub fn reorder_children(
+        &self,
+        workspace_id: &str,
+        folder_id: Option<&str>,
+        ordered_ids: &[String],
+        source: &UpdateSource,
+    ) -> Result<Vec<AnyModel>> {
+        let mut children = self.list_sidebar_children(workspace_id, folder_id)?;
+
+        let mut ordered = Vec::with_capacity(children.len());
+        for id in ordered_ids {
+            match children.iter().position(|m| m.id() == id) {
+                Some(idx) => ordered.push(children.remove(idx)),
+                None => {
+                    return Err(GenericError(format!(
+                        "Cannot reorder {id}: not a child of parent"
+                    )));
+                }
+            }
+        }
+        ordered.append(&mut children);
+
+        self.renormalize_children(ordered, source)
+    }
+
+    /// Re-space sort priorities of already-sorted siblings, only writing the ones that change.
+    fn renormalize_children(
+        &self,
+        children: Vec<AnyModel>,
+        source: &UpdateSource,
+    ) -> Result<Vec<AnyModel>> {
+        let mut updated = Vec::with_capacity(children.len());
+        for (i, m) in children.into_iter().enumerate() {
+            let priority = (i + 1) as f64 * SORT_PRIORITY_STEP;
+            if sort_priority(&m) == priority {
+                updated.push(m);
+                continue;
+            }
+            let m: AnyModel = match m {
+                AnyModel::Folder(m) => {
+                    self.upsert(&Folder { sort_priority: priority, ..m }, source)?.into()
+                }
+                AnyModel::HttpRequest(m) => {
+                    self.upsert(&HttpRequest { sort_priority: priority, ..m }, source)?.into()
+                }
+                AnyModel::GrpcRequest(m) => {
+                    self.upsert(&GrpcRequest { sort_priority: priority, ..m }, source)?.into()
+                }
+                AnyModel::WebsocketRequest(m) => {
+                    self.upsert(&WebsocketRequest { sort_priority: priority, ..m }, source)?.into()
+                }
+                AnyModel::MqttRequest(m) => {
+                    self.upsert(&MqttRequest { sort_priority: priority, ..m }, source)?.into()
+                }
+                AnyModel::SocketRequest(m) => {
+                    self.upsert(&SocketRequest { sort_priority: priority, ..m }, source)?.into()
+                }
+                m => m,
+            };
+            updated.push(m);
+        }
+        Ok(updated)
+    }
+}
+
+fn sort_priority(m: &AnyModel) -> f64 {
+    match m {
+        AnyModel::Folder(m) => m.sort_priority,
+        AnyModel::HttpRequest(m) => m.sort_priority,
+        AnyModel::GrpcRequest(m) => m.sort_priority,
+        AnyModel::WebsocketRequest(m) => m.sort_priority,
+        AnyModel::MqttRequest(m) => m.sort_priority,
+        AnyModel::SocketRequest(m) => m.sort_priority,
+        _ => 0.0,
+    }
+}
+
+fn is_trashed(m: &AnyModel) -> bool {
+    match m {
+        AnyModel::Folder(m) => m.deleted_at.is_some(),
+        AnyModel::HttpRequest(m) => m.deleted_at.is_some(),
+        AnyModel::GrpcRequest(m) => m.deleted_at.is_some(),
+        AnyModel::WebsocketRequest(m) => m.deleted_at.is_some(),
+        AnyModel::MqttRequest(m) => m.deleted_at.is_some(),
+        AnyModel::SocketRequest(m) => m.deleted_at.is_some(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn duplicates_folder_deeply_between_siblings() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Duplicate Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    sort_priority: 1000.0,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let nested = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert nested folder");
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: workspace.id.clone(),
+                folder_id: Some(nested.id.clone()),
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert request");
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: workspace.id.clone(),
+                sort_priority: 1001.0,
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert sibling request");
+
+        let copy = db.duplicate_folder(&folder, source).expect("Failed to duplicate folder");
+        assert_eq!(copy.sort_priority, 1000.5);
+        assert_eq!(db.list_folders(&workspace.id).unwrap().len(), 4);
+        assert_eq!(db.list_http_requests(&workspace.id).unwrap().len(), 3);
+        assert_eq!(db.list_http_requests_for_folder_recursive(&copy.id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn reorders_and_renormalizes_children() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Reorder Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let first = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    sort_priority: 1.0,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let second = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    sort_priority: 1.0 + 1e-9,
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        // Duplicating between two nearly-equal siblings respaces them first
+        let copy = db.duplicate_http_request(&first, source).expect("Failed to duplicate");
+        assert_eq!(copy.sort_priority, 1500.0);
+        assert_eq!(db.get_http_request(&second.id).unwrap().sort_priority, 2000.0);
+
+        let children = db
+            .reorder_children(&workspace.id, None, &[second.id.clone()], source)
+            .expect("Failed to reorder");
+        let ids: Vec<&str> = children.iter().map(|m| m.id()).collect();
+        assert_eq!(ids, vec![second.id.as_str(), first.id.as_str(), copy.id.as_str()]);
+        assert_eq!(db.get_http_request(&copy.id).unwrap().sort_priority, 3000.0);
+
+        assert!(
+            db.reorder_children(&workspace.id, None, &["missing".to_string()], source).is_err()
+        );
+    }
+}
@@ -0,0 +1,209 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{
+    AnyModel, Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden,
+};
+use crate::util::UpdateSource;
+use std::collections::{BTreeSet, HashSet};
+
+impl<'a> ClientDb<'a> {
+    /// List every tag used within a workspace, sorted alphabetically.
+    pub fn list_tags(&self, workspace_id: &str) -> Result<Vec<String>> {
+        let mut tags: BTreeSet<String> = BTreeSet::new();
+        for m in self.list_folders(workspace_id)? {
+            tags.extend(m.tags);
+        }
+        for m in self.list_http_requests(workspace_id)? {
+            tags.extend(m.tags);
+        }
+        for m in self.list_grpc_requests(workspace_id)? {
+            tags.extend(m.tags);
+        }
+        Ok(tags.into_iter().collect())
+    }
+
+    /// List the requests in a workspace carrying a tag, either directly or through one of their
+    /// ancestor folders. Trashed requests are excluded.
+    pub fn list_requests_by_tag(&self, workspace_id: &str, tag: &str) -> Result<Vec<AnyModel>> {
+        let folders = self.list_folders(workspace_id)?;
+
+        // Expand tagged folders to include all of their descendants
+        let mut tagged_folder_ids: HashSet<String> = folders
+            .iter()
+            .filter(|f| f.tags.iter().any(|t| t == tag))
+            .map(|f| f.id.clone())
+            .collect();
+        loop {
+            let descendants: Vec<String> = folders
+                .iter()
+                .filter(|f| !tagged_folder_ids.contains(&f.id))
+                .filter(|f| f.folder_id.as_ref().is_some_and(|id| tagged_folder_ids.contains(id)))
+                .map(|f| f.id.clone())
+                .collect();
+            if descendants.is_empty() {
+                break;
+            }
+            tagged_folder_ids.extend(descendants);
+        }
+
+        let is_tagged = |tags: &[String], folder_id: &Option<String>| {
+            tags.iter().any(|t| t == tag)
+                || folder_id.as_ref().is_some_and(|id| tagged_folder_ids.contains(id))
+        };
+
+        let mut requests: Vec<AnyModel> = Vec::new();
+        for m in self.list_http_requests(workspace_id)? {
+            if is_tagged(&m.tags, &m.folder_id) {
+                requests.push(m.into());
+            }
+        }
+        for m in self.list_grpc_requests(workspace_id)? {
+            if is_tagged(&m.tags, &m.folder_id) {
+                requests.push(m.into());
+            }
+        }
+
+        Ok(requests)
+    }
+
+    /// Rename a tag on every folder and request in a workspace, including trashed ones. Models that
+    /// already carry the new tag end up with a single copy of it.
+    pub fn rename_tag(
+        &self,
+        workspace_id: &str,
+        old_tag: &str,
+        new_tag: &str,
+        source: &UpdateSource,
+    ) -> Result<()> {
+        let new_tag = new_tag.trim();
+        if new_tag.is_empty() {
+            return self.delete_tag(workspace_id, old_tag, source);
+        }
+
+        self.update_tags(workspace_id, source, |tags| {
+            if !tags.iter().any(|t| t == old_tag) {
+                return None;
+            }
+            let mut renamed: Vec<String> = Vec::with_capacity(tags.len());
+            for t in tags {
+                let t = if t == old_tag { new_tag } else { t.as_str() };
+                if !renamed.iter().any(|r| r == t) {
+                    renamed.push(t.to_string());
+                }
+            }
+            Some(renamed)
+        })
+    }
+
+    /// Remove a tag from every folder and request in a workspace, including trashed ones.
+    pub fn delete_tag(&self, workspace_id: &str, tag: &str, source: &UpdateSource) -> Result<()> {
+        self.update_tags(workspace_id, source, |tags| {
+            if !tags.iter().any(|t| t == tag) {
+                return None;
+            }
+            Some(tags.iter().filter(|t| *t != tag).cloned().collect())
+        })
+    }
+
+    /// Apply `update` to the tags of every taggable model in a workspace, saving only the models
+    /// for which it returns new tags.
+    fn update_tags(
+        &self,
+        workspace_id: &str,
+        source: &UpdateSource,
+        update: impl Fn(&[String]) -> Option<Vec<String>>,
+    ) -> Result<()> {
+        for m in self.find_many::<Folder>(FolderIden::WorkspaceId, workspace_id, None)? {
+            if let Some(tags) = update(&m.tags) {
+                self.upsert_folder(&Folder { tags, ..m }, source)?;
+            }
+        }
+        for m in self.find_many::<HttpRequest>(HttpRequestIden::WorkspaceId, workspace_id, None)? {
+            if let Some(tags) = update(&m.tags) {
+                self.upsert_http_request(&HttpRequest { tags, ..m }, source)?;
+            }
+        }
+        for m in self.find_many::<GrpcRequest>(GrpcRequestIden::WorkspaceId, workspace_id, None)? {
+            if let Some(tags) = update(&m.tags) {
+                self.upsert_grpc_request(&GrpcRequest { tags, ..m }, source)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, GrpcRequest, HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn queries_renames_and_deletes_tags() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Tags Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    tags: vec!["deprecated".to_string()],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let nested = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert nested folder");
+        let inherited = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(nested.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let tagged = db
+            .upsert_grpc_request(
+                &GrpcRequest {
+                    workspace_id: workspace.id.clone(),
+                    tags: vec!["smoke-test".to_string(), "deprecated".to_string()],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let requests = db.list_requests_by_tag(&workspace.id, "deprecated").unwrap();
+        assert_eq!(requests.len(), 2);
+        let requests = db.list_requests_by_tag(&workspace.id, "smoke-test").unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].id(), tagged.id);
+
+        db.rename_tag(&workspace.id, "smoke-test", "deprecated", source).unwrap();
+        assert_eq!(db.get_grpc_request(&tagged.id).unwrap().tags, vec!["deprecated"]);
+        assert_eq!(db.list_tags(&workspace.id).unwrap(), vec!["deprecated"]);
+
+        db.delete_tag(&workspace.id, "deprecated", source).unwrap();
+        assert!(db.list_tags(&workspace.id).unwrap().is_empty());
+        assert!(db.list_requests_by_tag(&workspace.id, "deprecated").unwrap().is_empty());
+        assert!(db.get_http_request(&inherited.id).unwrap().tags.is_empty());
+    }
+}
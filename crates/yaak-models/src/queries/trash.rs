@@ -0,0 +1,179 @@
+use crate::client_db::ClientDb;
+use crate::error::Result;
+use crate::models::{
+    AnyModel, Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden,
+    MqttRequest, MqttRequestIden, SocketRequest, SocketRequestIden, WebsocketRequest,
+    WebsocketRequestIden,
+};
+use crate::util::UpdateSource;
+use chrono::NaiveDateTime;
+use std::collections::HashSet;
+
+impl<'a> ClientDb<'a> {
+    /// List everything in a workspace's trash, most recently trashed first.
+    pub fn list_trash(&self, workspace_id: &str) -> Result<Vec<AnyModel>> {
+        let mut items: Vec<AnyModel> = Vec::new();
+
+        for m in self.list_trashed_folders(workspace_id)? {
+            items.push(m.into());
+        }
+        for m in self.list_trashed_http_requests(workspace_id)? {
+            items.push(m.into());
+        }
+        for m in self.list_trashed_grpc_requests(workspace_id)? {
+            items.push(m.into());
+        }
+        for m in self.list_trashed_websocket_requests(workspace_id)? {
+            items.push(m.into());
+        }
+        for m in self.list_trashed_mqtt_requests(workspace_id)? {
+            items.push(m.into());
+        }
+        for m in self.list_trashed_socket_requests(workspace_id)? {
+            items.push(m.into());
+        }
+
+        items.sort_by(|a, b| trashed_at(b).cmp(&trashed_at(a)));
+        Ok(items)
+    }
+
+    /// Permanently delete everything in a workspace's trash.
+    pub fn empty_trash(&self, workspace_id: &str, source: &UpdateSource) -> Result<()> {
+        let folders = self.list_trashed_folders(workspace_id)?;
+        let trashed_folder_ids: HashSet<&str> = folders.iter().map(|f| f.id.as_str()).collect();
+
+        // Only delete the top-most trashed folders, since deleting a folder also deletes its
+        // descendants.
+        for folder in &folders {
+            let parent_trashed = match folder.folder_id.as_deref() {
+                Some(parent_id) => trashed_folder_ids.contains(parent_id),
+                None => false,
+            };
+            if !parent_trashed {
+                self.delete_folder(folder, source)?;
+            }
+        }
+
+        // Anything left over was trashed on its own
+        for m in self.list_trashed_http_requests(workspace_id)? {
+            self.delete_http_request(&m, source)?;
+        }
+        for m in self.list_trashed_grpc_requests(workspace_id)? {
+            self.delete_grpc_request(&m, source)?;
+        }
+        for m in self.list_trashed_websocket_requests(workspace_id)? {
+            self.delete_websocket_request(&m, source)?;
+        }
+        for m in self.list_trashed_mqtt_requests(workspace_id)? {
+            self.delete_mqtt_request(&m, source)?;
+        }
+        for m in self.list_trashed_socket_requests(workspace_id)? {
+            self.delete_socket_request(&m, source)?;
+        }
+
+        Ok(())
+    }
+
+    fn list_trashed_folders(&self, workspace_id: &str) -> Result<Vec<Folder>> {
+        let folders: Vec<Folder> = self.find_many(FolderIden::WorkspaceId, workspace_id, None)?;
+        Ok(folders.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+
+    fn list_trashed_http_requests(&self, workspace_id: &str) -> Result<Vec<HttpRequest>> {
+        let requests: Vec<HttpRequest> =
+            self.find_many(HttpRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+
+    fn list_trashed_grpc_requests(&self, workspace_id: &str) -> Result<Vec<GrpcRequest>> {
+        let requests: Vec<GrpcRequest> =
+            self.find_many(GrpcRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+
+    fn list_trashed_websocket_requests(&self, workspace_id: &str) -> Result<Vec<WebsocketRequest>> {
+        let requests: Vec<WebsocketRequest> =
+            self.find_many(WebsocketRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+
+    fn list_trashed_mqtt_requests(&self, workspace_id: &str) -> Result<Vec<MqttRequest>> {
+        let requests: Vec<MqttRequest> =
+            self.find_many(MqttRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+
+    fn list_trashed_socket_requests(&self, workspace_id: &str) -> Result<Vec<SocketRequest>> {
+        let requests: Vec<SocketRequest> =
+            self.find_many(SocketRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|m| m.deleted_at.is_some()).collect())
+    }
+}
+
+fn trashed_at(m: &AnyModel) -> Option<NaiveDateTime> {
+    match m {
+        AnyModel::Folder(m) => m.deleted_at,
+        AnyModel::HttpRequest(m) => m.deleted_at,
+        AnyModel::GrpcRequest(m) => m.deleted_at,
+        AnyModel::WebsocketRequest(m) => m.deleted_at,
+        AnyModel::MqttRequest(m) => m.deleted_at,
+        AnyModel::SocketRequest(m) => m.deleted_at,
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, HttpRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn trash_restore_and_empty() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Trash Test".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        // Trashing a folder hides it and its children
+        let folder = db.trash_folder(&folder, source).expect("Failed to trash folder");
+        assert!(db.list_folders(&workspace.id).unwrap().is_empty());
+        assert!(db.list_http_requests(&workspace.id).unwrap().is_empty());
+        assert_eq!(db.list_trash(&workspace.id).unwrap().len(), 2);
+
+        // Restoring the folder brings the children back
+        db.restore_folder(&folder, source).expect("Failed to restore folder");
+        assert_eq!(db.list_folders(&workspace.id).unwrap().len(), 1);
+        assert_eq!(db.list_http_requests(&workspace.id).unwrap().len(), 1);
+        assert!(db.list_trash(&workspace.id).unwrap().is_empty());
+
+        // Emptying the trash permanently deletes
+        let request = db.get_http_request(&request.id).unwrap();
+        db.trash_http_request(&request, source).expect("Failed to trash request");
+        db.empty_trash(&workspace.id, source).expect("Failed to empty trash");
+        assert!(db.get_http_request(&request.id).is_err());
+        assert_eq!(db.list_folders(&workspace.id).unwrap().len(), 1);
+    }
+}
@@ -1,7 +1,24 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
-use crate::models::{WebsocketEvent, WebsocketEventIden};
+use crate::models::{UpsertModelInfo, WebsocketEvent, WebsocketEventIden, WebsocketEventType};
 use crate::util::UpdateSource;
+use sea_query::{Asterisk, Cond, Expr, Order, Query, SqliteQueryBuilder};
+use sea_query_rusqlite::RusqliteBinder;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Pagination and filters for listing a connection's message history
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_util.ts")]
+pub struct WebsocketEventFilter {
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+    /// Only include messages received from the server (`true`) or sent by the client (`false`)
+    pub is_server: Option<bool>,
+    /// Only include events of these types, or events of any type when empty
+    pub message_types: Vec<WebsocketEventType>,
+}
 
 impl<'a> ClientDb<'a> {
     pub fn get_websocket_event(&self, id: &str) -> Result<WebsocketEvent> {
@@ -12,6 +29,51 @@ impl<'a> ClientDb<'a> {
         self.find_many(WebsocketEventIden::ConnectionId, connection_id, None)
     }
 
+    /// List a connection's events, newest first, applying the given filters in the query itself
+    /// so that long-running connections can be paged through without loading every frame.
+    pub fn filter_websocket_events(
+        &self,
+        connection_id: &str,
+        filter: &WebsocketEventFilter,
+    ) -> Result<Vec<WebsocketEvent>> {
+        let mut cond =
+            Cond::all().add(Expr::col(WebsocketEventIden::ConnectionId).eq(connection_id));
+        if let Some(is_server) = filter.is_server {
+            cond = cond.add(Expr::col(WebsocketEventIden::IsServer).eq(is_server));
+        }
+        if !filter.message_types.is_empty() {
+            let message_types = filter
+                .message_types
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            cond = cond.add(Expr::col(WebsocketEventIden::MessageType).is_in(message_types));
+        }
+
+        let mut query = Query::select();
+        query
+            .from(WebsocketEventIden::Table)
+            .column(Asterisk)
+            .cond_where(cond)
+            .order_by(WebsocketEventIden::CreatedAt, Order::Desc)
+            .order_by(WebsocketEventIden::Id, Order::Desc);
+        if let Some(limit) = filter.limit {
+            query.limit(limit.into());
+        }
+        if let Some(offset) = filter.offset {
+            // SQLite doesn't allow OFFSET without LIMIT
+            if filter.limit.is_none() {
+                query.limit(i64::MAX as u64);
+            }
+            query.offset(offset.into());
+        }
+
+        let (sql, params) = query.build_rusqlite(SqliteQueryBuilder);
+        let mut stmt = self.conn().prepare(sql.as_str())?;
+        let items = stmt.query_map(&*params.as_params(), WebsocketEvent::from_row)?;
+        Ok(items.collect::<std::result::Result<Vec<_>, rusqlite::Error>>()?)
+    }
+
     pub fn upsert_websocket_event(
         &self,
         websocket_event: &WebsocketEvent,
@@ -20,3 +82,93 @@ impl<'a> ClientDb<'a> {
         self.upsert(websocket_event, source)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init_in_memory;
+    use crate::models::{WebsocketConnection, WebsocketRequest, Workspace};
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn pages_through_filtered_events() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "WS".to_string(), ..Default::default() }, source)
+            .expect("Failed to upsert workspace");
+        let request = db
+            .upsert_websocket_request(
+                &WebsocketRequest { workspace_id: workspace.id.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let connection = db
+            .upsert_websocket_connection(
+                &WebsocketConnection {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert connection");
+
+        let start = Utc::now().naive_utc() - Duration::minutes(10);
+        for i in 0..5 {
+            db.upsert_websocket_event(
+                &WebsocketEvent {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    connection_id: connection.id.clone(),
+                    created_at: start + Duration::seconds(i),
+                    is_server: i % 2 == 1,
+                    message_type: match i {
+                        0 => WebsocketEventType::Open,
+                        _ => WebsocketEventType::Text,
+                    },
+                    message: format!("message {i}").into_bytes(),
+                    ..Default::default()
+                },
+                &UpdateSource::Import,
+            )
+            .expect("Failed to upsert event");
+        }
+
+        let messages = |filter: WebsocketEventFilter| {
+            db.filter_websocket_events(&connection.id, &filter)
+                .unwrap()
+                .into_iter()
+                .map(|e| String::from_utf8(e.message).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let all = messages(WebsocketEventFilter::default());
+        assert_eq!(
+            all,
+            vec![
+                "message 4",
+                "message 3",
+                "message 2",
+                "message 1",
+                "message 0"
+            ]
+        );
+
+        let page = messages(WebsocketEventFilter {
+            limit: Some(2),
+            offset: Some(1),
+            ..Default::default()
+        });
+        assert_eq!(page, vec!["message 3", "message 2"]);
+
+        let sent_text = messages(WebsocketEventFilter {
+            is_server: Some(false),
+            message_types: vec![WebsocketEventType::Text],
+            ..Default::default()
+        });
+        assert_eq!(sent_text, vec!["message 4", "message 2"]);
+    }
+}
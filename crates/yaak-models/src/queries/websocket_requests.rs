@@ -1,11 +1,12 @@
-use super::dedupe_headers;
+use super::{dedupe_headers, merge_url_parameters};
 use crate::client_db::ClientDb;
 use crate::error::Result;
 use crate::models::{
-    AnyModel, Folder, FolderIden, HttpRequestHeader, ResolvedHttpRequestSettings, ResolvedSetting,
-    WebsocketRequest, WebsocketRequestIden,
+    AnyModel, Folder, FolderIden, HttpRequestHeader, HttpUrlParameter, ResolvedHttpRequestSettings,
+    ResolvedSetting, WebsocketRequest, WebsocketRequestIden,
 };
 use crate::util::UpdateSource;
+use chrono::Utc;
 use serde_json::Value;
 use std::collections::BTreeMap;
 
@@ -15,7 +16,9 @@ impl<'a> ClientDb<'a> {
     }
 
     pub fn list_websocket_requests(&self, workspace_id: &str) -> Result<Vec<WebsocketRequest>> {
-        self.find_many(WebsocketRequestIden::WorkspaceId, workspace_id, None)
+        let requests: Vec<WebsocketRequest> =
+            self.find_many(WebsocketRequestIden::WorkspaceId, workspace_id, None)?;
+        Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
     }
 
     pub fn list_websocket_requests_for_folder_recursive(
@@ -29,7 +32,9 @@ impl<'a> ClientDb<'a> {
         for request in
             self.find_many::<WebsocketRequest>(WebsocketRequestIden::FolderId, folder_id, None)?
         {
-            children.push(request);
+            if request.deleted_at.is_none() {
+                children.push(request);
+            }
         }
         Ok(children)
     }
@@ -52,15 +57,45 @@ impl<'a> ClientDb<'a> {
         self.delete_websocket_request(&request, source)
     }
 
+    pub fn trash_websocket_request(
+        &self,
+        websocket_request: &WebsocketRequest,
+        source: &UpdateSource,
+    ) -> Result<WebsocketRequest> {
+        self.upsert(
+            &WebsocketRequest {
+                deleted_at: Some(Utc::now().naive_utc()),
+                ..websocket_request.clone()
+            },
+            source,
+        )
+    }
+
+    pub fn restore_websocket_request(
+        &self,
+        websocket_request: &WebsocketRequest,
+        source: &UpdateSource,
+    ) -> Result<WebsocketRequest> {
+        if let Some(folder_id) = websocket_request.folder_id.as_deref() {
+            self.restore_folder_ancestors(folder_id, source)?;
+        }
+        self.upsert(&WebsocketRequest { deleted_at: None, ..websocket_request.clone() }, source)
+    }
+
     pub fn duplicate_websocket_request(
         &self,
         websocket_request: &WebsocketRequest,
         source: &UpdateSource,
     ) -> Result<WebsocketRequest> {
-        let mut websocket_request = websocket_request.clone();
-        websocket_request.id = "".to_string();
-        websocket_request.sort_priority = websocket_request.sort_priority + 0.001;
-        self.upsert(&websocket_request, source)
+        let mut request = websocket_request.clone();
+        request.id = "".to_string();
+        request.sort_priority = self.sort_priority_after(
+            &websocket_request.workspace_id,
+            websocket_request.folder_id.as_deref(),
+            &websocket_request.id,
+            source,
+        )?;
+        self.upsert(&request, source)
     }
 
     pub fn upsert_websocket_request(
@@ -96,13 +131,9 @@ impl<'a> ClientDb<'a> {
         &self,
         websocket_request: &WebsocketRequest,
     ) -> Result<Vec<HttpRequestHeader>> {
-        let workspace = self.get_workspace(&websocket_request.workspace_id)?;
-
         // Resolved headers should be from furthest to closest ancestor, to override logically.
         let mut headers = Vec::new();
 
-        headers.append(&mut workspace.headers.clone());
-
         if let Some(folder_id) = websocket_request.folder_id.clone() {
             let parent_folder = self.get_folder(&folder_id)?;
             let mut folder_headers = self.resolve_headers_for_folder(&parent_folder)?;
@@ -118,6 +149,21 @@ impl<'a> ClientDb<'a> {
         Ok(dedupe_headers(headers))
     }
 
+    pub fn resolve_query_params_for_websocket_request(
+        &self,
+        websocket_request: &WebsocketRequest,
+    ) -> Result<Vec<HttpUrlParameter>> {
+        let inherited = if let Some(folder_id) = websocket_request.folder_id.clone() {
+            let folder = self.get_folder(&folder_id)?;
+            self.resolve_query_params_for_folder(&folder)?
+        } else {
+            let workspace = self.get_workspace(&websocket_request.workspace_id)?;
+            self.resolve_query_params_for_workspace(&workspace)
+        };
+
+        Ok(merge_url_parameters(inherited, &websocket_request.url_parameters))
+    }
+
     pub fn resolve_settings_for_websocket_request(
         &self,
         websocket_request: &WebsocketRequest,
@@ -159,3 +205,81 @@ impl<'a> ClientDb<'a> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{Folder, HttpRequestHeader, HttpUrlParameter, WebsocketRequest, Workspace};
+    use crate::util::UpdateSource;
+
+    #[test]
+    fn resolves_inherited_headers_and_url_parameters() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let param = |name: &str, value: &str| HttpUrlParameter {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        };
+        let header = |name: &str, value: &str| HttpRequestHeader {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+        };
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "Websockets".to_string(),
+                    headers: vec![header("X-Workspace", "1")],
+                    url_parameters: vec![param("version", "1"), param("tenant", "acme")],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    name: "Folder".to_string(),
+                    headers: vec![header("X-Folder", "1")],
+                    url_parameters: vec![param("version", "2")],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert folder");
+        let request = db
+            .upsert_websocket_request(
+                &WebsocketRequest {
+                    workspace_id: workspace.id.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    headers: vec![header("X-Workspace", "2")],
+                    url_parameters: vec![param("token", "abc")],
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+
+        let params = db.resolve_query_params_for_websocket_request(&request).unwrap();
+        let params: Vec<(&str, &str)> =
+            params.iter().map(|p| (p.name.as_str(), p.value.as_str())).collect();
+        assert_eq!(params, vec![("tenant", "acme"), ("version", "2"), ("token", "abc")]);
+
+        let headers = db.resolve_headers_for_websocket_request(&request).unwrap();
+        let headers: Vec<(&str, &str)> =
+            headers.iter().map(|h| (h.name.as_str(), h.value.as_str())).collect();
+        assert!(headers.contains(&("X-Workspace", "2")));
+        assert!(headers.contains(&("X-Folder", "1")));
+
+        // Websocket requests are removed along with their workspace
+        db.delete_workspace(&workspace, source).unwrap();
+        assert!(db.get_websocket_request(&request.id).is_err());
+    }
+}
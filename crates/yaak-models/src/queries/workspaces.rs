@@ -1,12 +1,16 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
 use crate::models::{
-    AnyModel, EnvironmentIden, FolderIden, GrpcRequestIden, HttpRequestHeader, HttpRequestIden,
-    ResolvedHttpRequestSettings, ResolvedSetting, WebsocketRequestIden, Workspace, WorkspaceIden,
+    AnyModel, CookieJar, CookieJarIden, DEFAULT_MAX_REDIRECTS, Environment, EnvironmentIden,
+    Folder, FolderIden, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestHeader,
+    HttpRequestIden, HttpUrlParameter, MqttRequest, MqttRequestIden, PreRequestStep, ProxySetting,
+    ResolvedHttpRequestSettings, ResolvedSetting, RetryPolicy, SocketRequest, SocketRequestIden,
+    UpsertModelInfo, WebsocketRequest, WebsocketRequestIden, Workspace, WorkspaceIden,
+    WorkspaceMeta,
 };
 use crate::util::UpdateSource;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 
 impl<'a> ClientDb<'a> {
     pub fn get_workspace(&self, id: &str) -> Result<Workspace> {
@@ -21,6 +25,10 @@ impl<'a> ClientDb<'a> {
                 &Workspace {
                     name: "Yaak".to_string(),
                     setting_follow_redirects: true,
+                    setting_max_redirects: DEFAULT_MAX_REDIRECTS,
+                    setting_redirect_resend_body: true,
+                    setting_redirect_strip_authorization: true,
+                    setting_decompress_response: true,
                     setting_validate_certificates: true,
                     ..Default::default()
                 },
@@ -44,10 +52,18 @@ impl<'a> ClientDb<'a> {
             self.delete_grpc_request(&m, source)?;
         }
 
-        for m in self.find_many(WebsocketRequestIden::FolderId, &workspace.id, None)? {
+        for m in self.find_many(WebsocketRequestIden::WorkspaceId, &workspace.id, None)? {
             self.delete_websocket_request(&m, source)?;
         }
 
+        for m in self.find_many(MqttRequestIden::WorkspaceId, &workspace.id, None)? {
+            self.delete_mqtt_request(&m, source)?;
+        }
+
+        for m in self.find_many(SocketRequestIden::WorkspaceId, &workspace.id, None)? {
+            self.delete_socket_request(&m, source)?;
+        }
+
         for m in self.find_many(FolderIden::WorkspaceId, &workspace.id, None)? {
             self.delete_folder(&m, source)?;
         }
@@ -68,6 +84,197 @@ impl<'a> ClientDb<'a> {
         self.upsert(w, source)
     }
 
+    /// Deep-copy a workspace with its folders, requests, environments, and cookie jars, pointing
+    /// every copied reference at the matching copy. Responses, connections, and other history are
+    /// left behind, as are items in the trash. Run this inside a transaction so a failure doesn't
+    /// leave a partial copy.
+    pub fn duplicate_workspace(
+        &self,
+        src_workspace: &Workspace,
+        source: &UpdateSource,
+    ) -> Result<Workspace> {
+        let wid = &src_workspace.id;
+        let new_workspace =
+            self.upsert_workspace(&Workspace { id: "".into(), ..src_workspace.clone() }, source)?;
+        let new_wid = &new_workspace.id;
+
+        // Secrets are encrypted with the workspace key, so the copy needs the same key to read them
+        let encryption_key = self.get_workspace_meta(wid).and_then(|m| m.encryption_key);
+        if encryption_key.is_some() {
+            let workspace_meta = self.get_or_create_workspace_meta(new_wid)?;
+            self.upsert_workspace_meta(
+                &WorkspaceMeta { encryption_key, ..workspace_meta },
+                source,
+            )?;
+        }
+
+        let mut cookie_jar_ids = HashMap::new();
+        for m in self.find_many::<CookieJar>(CookieJarIden::WorkspaceId, wid, None)? {
+            let old_id = m.id.clone();
+            let new_jar = self.upsert_cookie_jar(
+                &CookieJar { id: "".into(), workspace_id: new_wid.clone(), ..m },
+                source,
+            )?;
+            cookie_jar_ids.insert(old_id, new_jar.id);
+        }
+
+        // Parents must exist before their children, so copy folders level by level. Folders whose
+        // parent is trashed never get placed, which leaves them behind along with it.
+        let mut folder_ids: HashMap<String, String> = HashMap::new();
+        let mut pending: Vec<Folder> = self
+            .find_many::<Folder>(FolderIden::WorkspaceId, wid, None)?
+            .into_iter()
+            .filter(|m| m.deleted_at.is_none())
+            .collect();
+        loop {
+            let (ready, rest): (Vec<Folder>, Vec<Folder>) = pending
+                .into_iter()
+                .partition(|m| m.folder_id.as_ref().is_none_or(|id| folder_ids.contains_key(id)));
+            if ready.is_empty() {
+                break;
+            }
+            for m in ready {
+                let old_id = m.id.clone();
+                let folder_id = m.folder_id.as_ref().map(|id| folder_ids[id].clone());
+                let new_folder = self.upsert_folder(
+                    &Folder { id: "".into(), workspace_id: new_wid.clone(), folder_id, ..m },
+                    source,
+                )?;
+                folder_ids.insert(old_id, new_folder.id);
+            }
+            pending = rest;
+        }
+
+        // Returns None for items inside a folder that wasn't copied
+        let remap_folder = |folder_id: &Option<String>| match folder_id {
+            None => Some(None),
+            Some(id) => folder_ids.get(id).map(|new_id| Some(new_id.clone())),
+        };
+
+        // Pre-request steps can send any HTTP request in the workspace, so copies get their IDs up
+        // front and steps can point at a copy that hasn't been written yet
+        let mut http_request_ids: HashMap<String, String> = HashMap::new();
+        let mut http_requests = Vec::new();
+        for m in self.find_many::<HttpRequest>(HttpRequestIden::WorkspaceId, wid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            let Some(folder_id) = remap_folder(&m.folder_id) else {
+                continue;
+            };
+            http_request_ids.insert(m.id.clone(), HttpRequest::generate_id());
+            http_requests.push((m, folder_id));
+        }
+
+        let remap_steps = |steps: Vec<PreRequestStep>| -> Vec<PreRequestStep> {
+            steps
+                .into_iter()
+                .map(|step| match http_request_ids.get(&step.request_id) {
+                    Some(request_id) => PreRequestStep { request_id: request_id.clone(), ..step },
+                    None => step,
+                })
+                .collect()
+        };
+
+        for (m, folder_id) in http_requests {
+            let id = http_request_ids[&m.id].clone();
+            let pre_request_steps = remap_steps(m.pre_request_steps.clone());
+            self.upsert_http_request(
+                &HttpRequest {
+                    id,
+                    workspace_id: new_wid.clone(),
+                    folder_id,
+                    pre_request_steps,
+                    ..m
+                },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<GrpcRequest>(GrpcRequestIden::WorkspaceId, wid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            let Some(folder_id) = remap_folder(&m.folder_id) else {
+                continue;
+            };
+            let pre_request_steps = remap_steps(m.pre_request_steps.clone());
+            self.upsert_grpc_request(
+                &GrpcRequest {
+                    id: "".into(),
+                    workspace_id: new_wid.clone(),
+                    folder_id,
+                    pre_request_steps,
+                    ..m
+                },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<WebsocketRequest>(WebsocketRequestIden::WorkspaceId, wid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            let Some(folder_id) = remap_folder(&m.folder_id) else {
+                continue;
+            };
+            self.upsert_websocket_request(
+                &WebsocketRequest { id: "".into(), workspace_id: new_wid.clone(), folder_id, ..m },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<MqttRequest>(MqttRequestIden::WorkspaceId, wid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            let Some(folder_id) = remap_folder(&m.folder_id) else {
+                continue;
+            };
+            self.upsert_mqtt_request(
+                &MqttRequest { id: "".into(), workspace_id: new_wid.clone(), folder_id, ..m },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<SocketRequest>(SocketRequestIden::WorkspaceId, wid, None)? {
+            if m.deleted_at.is_some() {
+                continue;
+            }
+            let Some(folder_id) = remap_folder(&m.folder_id) else {
+                continue;
+            };
+            self.upsert_socket_request(
+                &SocketRequest { id: "".into(), workspace_id: new_wid.clone(), folder_id, ..m },
+                source,
+            )?;
+        }
+
+        for m in self.find_many::<Environment>(EnvironmentIden::WorkspaceId, wid, None)? {
+            let parent_id = match m.parent_model.as_str() {
+                "folder" => match remap_folder(&m.parent_id) {
+                    Some(parent_id) => parent_id,
+                    None => continue,
+                },
+                _ => m.parent_id.clone(),
+            };
+            let cookie_jar_id =
+                m.cookie_jar_id.as_ref().and_then(|id| cookie_jar_ids.get(id)).cloned();
+            self.upsert_environment(
+                &Environment {
+                    id: "".into(),
+                    workspace_id: new_wid.clone(),
+                    parent_id,
+                    cookie_jar_id,
+                    ..m
+                },
+                source,
+            )?;
+        }
+
+        Ok(new_workspace)
+    }
+
     pub fn resolve_auth_for_workspace(
         &self,
         workspace: &Workspace,
@@ -85,6 +292,19 @@ impl<'a> ClientDb<'a> {
         headers
     }
 
+    pub fn resolve_query_params_for_workspace(
+        &self,
+        workspace: &Workspace,
+    ) -> Vec<HttpUrlParameter> {
+        workspace.url_parameters.clone()
+    }
+
+    /// The workspace's own proxy setting wins over the global one, which is only used when the
+    /// workspace doesn't configure a proxy.
+    pub fn resolve_proxy_for_workspace(&self, workspace: &Workspace) -> Option<ProxySetting> {
+        workspace.setting_proxy.clone().or_else(|| self.get_settings().proxy)
+    }
+
     pub fn resolve_settings_for_workspace(
         &self,
         workspace: &Workspace,
@@ -98,10 +318,48 @@ impl<'a> ClientDb<'a> {
                 workspace.setting_follow_redirects,
                 AnyModel::Workspace(workspace.clone()),
             ),
+            max_redirects: ResolvedSetting::from_model(
+                workspace.setting_max_redirects,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            redirect_resend_body: ResolvedSetting::from_model(
+                workspace.setting_redirect_resend_body,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            redirect_strip_authorization: ResolvedSetting::from_model(
+                workspace.setting_redirect_strip_authorization,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            decompress_response: ResolvedSetting::from_model(
+                workspace.setting_decompress_response,
+                AnyModel::Workspace(workspace.clone()),
+            ),
             request_timeout: ResolvedSetting::from_model(
                 workspace.setting_request_timeout,
                 AnyModel::Workspace(workspace.clone()),
             ),
+            connect_timeout: ResolvedSetting::from_model(
+                workspace.setting_connect_timeout,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            read_timeout: ResolvedSetting::from_model(
+                workspace.setting_read_timeout,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            http_version: ResolvedSetting::from_model(
+                workspace.setting_http_version,
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            retry_policy: ResolvedSetting::default_source(RetryPolicy::default()),
+            ssh_tunnel: ResolvedSetting::from_model(
+                workspace.setting_ssh_tunnel.clone(),
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            ca_certificates: ResolvedSetting::from_model(
+                workspace.setting_ca_certificates.clone(),
+                AnyModel::Workspace(workspace.clone()),
+            ),
+            client_certificate: ResolvedSetting::default_source(None),
             send_cookies: ResolvedSetting::from_model(
                 workspace.setting_send_cookies,
                 AnyModel::Workspace(workspace.clone()),
@@ -133,3 +391,146 @@ pub fn default_headers() -> Vec<HttpRequestHeader> {
         },
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::init_in_memory;
+    use crate::models::{
+        CookieJar, Environment, Folder, GrpcRequest, GrpcRequestIden, HttpRequest, HttpRequestIden,
+        PreRequestStep, Workspace,
+    };
+    use crate::util::UpdateSource;
+    use chrono::Utc;
+
+    #[test]
+    fn duplicates_workspace_with_remapped_references() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Original".to_string(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert workspace");
+        let wid = workspace.id.clone();
+        let folder = db
+            .upsert_folder(&Folder { workspace_id: wid.clone(), ..Default::default() }, source)
+            .expect("Failed to upsert folder");
+        let nested = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: wid.clone(),
+                    folder_id: Some(folder.id.clone()),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert nested folder");
+        let login = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: wid.clone(),
+                    folder_id: Some(nested.id.clone()),
+                    name: "Login".to_string(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .expect("Failed to upsert request");
+        let login_step =
+            PreRequestStep { enabled: true, request_id: login.id.clone(), ..Default::default() };
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: wid.clone(),
+                name: "Profile".to_string(),
+                pre_request_steps: vec![login_step.clone()],
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert request");
+        db.upsert_grpc_request(
+            &GrpcRequest {
+                workspace_id: wid.clone(),
+                pre_request_steps: vec![login_step],
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert gRPC request");
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: wid.clone(),
+                name: "Trashed".to_string(),
+                deleted_at: Some(Utc::now().naive_utc()),
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert trashed request");
+        let cookie_jar = db
+            .upsert_cookie_jar(
+                &CookieJar { workspace_id: wid.clone(), ..Default::default() },
+                source,
+            )
+            .expect("Failed to upsert cookie jar");
+        db.upsert_environment(
+            &Environment {
+                workspace_id: wid.clone(),
+                parent_model: "folder".to_string(),
+                parent_id: Some(nested.id.clone()),
+                cookie_jar_id: Some(cookie_jar.id.clone()),
+                ..Default::default()
+            },
+            source,
+        )
+        .expect("Failed to upsert folder environment");
+
+        let copy =
+            db.duplicate_workspace(&workspace, source).expect("Failed to duplicate workspace");
+        assert_ne!(copy.id, wid);
+        assert_eq!(copy.name, "Original");
+
+        let folders = db.list_folders(&copy.id).unwrap();
+        let new_folder = folders.iter().find(|f| f.folder_id.is_none()).unwrap();
+        let new_nested = folders.iter().find(|f| f.folder_id.is_some()).unwrap();
+        assert_eq!(folders.len(), 2);
+        assert_eq!(new_nested.folder_id.as_ref(), Some(&new_folder.id));
+
+        // Trashed requests are left behind
+        let requests =
+            db.find_many::<HttpRequest>(HttpRequestIden::WorkspaceId, &copy.id, None).unwrap();
+        assert_eq!(requests.len(), 2);
+        let new_login = requests.iter().find(|r| r.name == "Login").unwrap();
+        assert_eq!(new_login.folder_id.as_ref(), Some(&new_nested.id));
+
+        // Pre-request steps send the copied request, not the original
+        let new_profile = requests.iter().find(|r| r.name == "Profile").unwrap();
+        assert_eq!(new_profile.pre_request_steps[0].request_id, new_login.id);
+        let grpc_requests =
+            db.find_many::<GrpcRequest>(GrpcRequestIden::WorkspaceId, &copy.id, None).unwrap();
+        assert_eq!(grpc_requests[0].pre_request_steps[0].request_id, new_login.id);
+
+        let cookie_jars = db.list_cookie_jars(&copy.id).unwrap();
+        assert_eq!(cookie_jars.len(), 1);
+        let environment = db.get_environment_by_folder_id(&new_nested.id).unwrap().unwrap();
+        assert_eq!(environment.workspace_id, copy.id);
+        assert_eq!(environment.cookie_jar_id.as_ref(), Some(&cookie_jars[0].id));
+
+        // The original is untouched
+        assert_eq!(db.list_folders(&wid).unwrap().len(), 2);
+        let profile = db
+            .find_many::<HttpRequest>(HttpRequestIden::WorkspaceId, &wid, None)
+            .unwrap()
+            .into_iter()
+            .find(|r| r.name == "Profile")
+            .unwrap();
+        assert_eq!(profile.pre_request_steps[0].request_id, login.id);
+        assert_eq!(
+            db.get_environment_by_folder_id(&nested.id).unwrap().unwrap().cookie_jar_id,
+            Some(cookie_jar.id)
+        );
+    }
+}
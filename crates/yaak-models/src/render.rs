@@ -22,8 +22,42 @@ fn add_variable_to_map(
         }
         let name = variable.name.as_str();
         let value = variable.value.as_str();
-        map.insert(name.into(), value.into());
+        if variable.secret && value.starts_with("YENC_") {
+            // Secret values are stored encrypted, so let secure() decrypt them at render time
+            map.insert(name.into(), format!("${{[ secure(value='{value}') ]}}"));
+        } else {
+            map.insert(name.into(), value.into());
+        }
     }
 
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_encrypted_secret_values() {
+        let variable = |name: &str, value: &str, secret: bool| EnvironmentVariable {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+            secret,
+        };
+        let environment = Environment {
+            variables: vec![
+                variable("token", "YENC_abc=", true),
+                variable("pending", "plain", true),
+                variable("host", "YENC_abc=", false),
+            ],
+            ..Default::default()
+        };
+
+        let variables = make_vars_hashmap(vec![environment]);
+        assert_eq!(variables["token"], "${[ secure(value='YENC_abc=') ]}");
+        assert_eq!(variables["pending"], "plain");
+        assert_eq!(variables["host"], "YENC_abc=");
+    }
+}
@@ -0,0 +1,26 @@
+use crate::blob_manager::BlobManager;
+use crate::query_manager::QueryManager;
+use crate::util::UpdateSource;
+use log::{error, info};
+use std::thread;
+use std::time::Duration;
+
+/// Delete responses past their workspace's retention period every `interval`, so they're cleaned
+/// up even when nothing is being sent. Runs for the life of the process.
+pub fn spawn_response_cleanup(
+    query_manager: QueryManager,
+    blob_manager: BlobManager,
+    interval: Duration,
+) {
+    thread::spawn(move || {
+        loop {
+            let source = &UpdateSource::Background;
+            match query_manager.connect().delete_expired_http_responses(source, &blob_manager) {
+                Ok(0) => {}
+                Ok(n) => info!("Deleted {n} expired HTTP responses"),
+                Err(e) => error!("Failed to delete expired HTTP responses: {e:?}"),
+            }
+            thread::sleep(interval);
+        }
+    });
+}
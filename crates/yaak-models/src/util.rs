@@ -1,8 +1,8 @@
 use crate::client_db::ClientDb;
 use crate::error::Result;
 use crate::models::{
-    AnyModel, Environment, Folder, GrpcRequest, HttpRequest, UpsertModelInfo, WebsocketRequest,
-    Workspace, WorkspaceIden,
+    AnyModel, CookieJar, Environment, Folder, GrpcRequest, HttpRequest, UpsertModelInfo,
+    WebsocketRequest, Workspace, WorkspaceIden,
 };
 use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -28,6 +28,7 @@ pub struct ModelPayload {
 #[ts(export, export_to = "gen_models.ts")]
 pub enum UpdateSource {
     Background,
+    Cli,
     Import,
     Plugin,
     Sync,
@@ -42,6 +43,7 @@ impl UpdateSource {
     pub fn to_db(&self) -> yaak_database::UpdateSource {
         match self {
             UpdateSource::Background => yaak_database::UpdateSource::Background,
+            UpdateSource::Cli => yaak_database::UpdateSource::Cli,
             UpdateSource::Import => yaak_database::UpdateSource::Import,
             UpdateSource::Plugin => yaak_database::UpdateSource::Plugin,
             UpdateSource::Sync => yaak_database::UpdateSource::Sync,
@@ -56,6 +58,7 @@ impl From<yaak_database::UpdateSource> for UpdateSource {
     fn from(source: yaak_database::UpdateSource) -> Self {
         match source {
             yaak_database::UpdateSource::Background => UpdateSource::Background,
+            yaak_database::UpdateSource::Cli => UpdateSource::Cli,
             yaak_database::UpdateSource::Import => UpdateSource::Import,
             yaak_database::UpdateSource::Plugin => UpdateSource::Plugin,
             yaak_database::UpdateSource::Sync => UpdateSource::Sync,
@@ -83,6 +86,7 @@ pub struct BatchUpsertResult {
     pub http_requests: Vec<HttpRequest>,
     pub grpc_requests: Vec<GrpcRequest>,
     pub websocket_requests: Vec<WebsocketRequest>,
+    pub cookie_jars: Vec<CookieJar>,
 }
 
 pub fn get_workspace_export_resources(
@@ -102,6 +106,7 @@ pub fn get_workspace_export_resources(
             http_requests: Vec::new(),
             grpc_requests: Vec::new(),
             websocket_requests: Vec::new(),
+            cookie_jars: Vec::new(),
         },
     };
 
@@ -119,10 +124,39 @@ pub fn get_workspace_export_resources(
         data.resources.grpc_requests.append(&mut db.list_grpc_requests(workspace_id)?);
         data.resources.websocket_requests.append(&mut db.list_websocket_requests(workspace_id)?);
     }
+    redact_connection_credentials(&mut data.resources);
 
     Ok(data)
 }
 
+/// Clear the proxy and SSH tunnel credentials in workspace and folder settings. Exports and the
+/// sync directory are shared with other people, so they never include them.
+pub fn redact_connection_credentials(resources: &mut BatchUpsertResult) {
+    for workspace in resources.workspaces.iter_mut() {
+        if let Some(proxy) = workspace.setting_proxy.as_mut() {
+            proxy.clear_credentials();
+        }
+        if let Some(tunnel) = workspace.setting_ssh_tunnel.as_mut() {
+            tunnel.clear_credentials();
+        }
+    }
+    for folder in resources.folders.iter_mut() {
+        if let Some(tunnel) = folder.setting_ssh_tunnel.value.as_mut() {
+            tunnel.clear_credentials();
+        }
+    }
+}
+
+/// Clear the values of secret variables before an export, since they're encrypted with a key the
+/// importer may not have
+pub fn redact_secret_variables(environments: &mut [Environment]) {
+    for variable in environments.iter_mut().flat_map(|e| e.variables.iter_mut()) {
+        if variable.secret {
+            variable.value.clear();
+        }
+    }
+}
+
 pub fn maybe_gen_id<M: UpsertModelInfo>(
     ctx: &WorkspaceContext,
     id: &str,
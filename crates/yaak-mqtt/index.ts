@@ -0,0 +1,40 @@
+import { invoke } from "@tauri-apps/api/core";
+import { MqttConnection, MqttEvent } from "@yaakapp-internal/models";
+
+export function deleteMqttConnections(requestId: string) {
+  return invoke("cmd_mqtt_delete_connections", {
+    requestId,
+  });
+}
+
+export function connectMqtt({
+  requestId,
+  environmentId,
+}: {
+  requestId: string;
+  environmentId: string | null;
+}) {
+  return invoke("cmd_mqtt_connect", {
+    requestId,
+    environmentId,
+  }) as Promise<MqttConnection>;
+}
+
+export function closeMqtt({ connectionId }: { connectionId: string }) {
+  return invoke("cmd_mqtt_close", {
+    connectionId,
+  });
+}
+
+export function publishMqtt({
+  connectionId,
+  environmentId,
+}: {
+  connectionId: string;
+  environmentId: string | null;
+}) {
+  return invoke("cmd_mqtt_publish", {
+    connectionId,
+    environmentId,
+  }) as Promise<MqttEvent>;
+}
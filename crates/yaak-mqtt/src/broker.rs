@@ -0,0 +1,89 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use url::Url;
+
+/// How the client talks to the broker
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BrokerTransport {
+    Tcp,
+    Tls,
+    Ws,
+    Wss,
+}
+
+impl BrokerTransport {
+    fn default_port(&self) -> u16 {
+        match self {
+            BrokerTransport::Tcp => 1883,
+            BrokerTransport::Tls => 8883,
+            BrokerTransport::Ws => 80,
+            BrokerTransport::Wss => 443,
+        }
+    }
+
+    pub fn is_secure(&self) -> bool {
+        matches!(self, BrokerTransport::Tls | BrokerTransport::Wss)
+    }
+}
+
+/// Where a broker is and how to reach it, parsed from a broker URL
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrokerAddress {
+    pub transport: BrokerTransport,
+    pub host: String,
+    pub port: u16,
+    /// Full URL, which WebSocket transports connect to instead of the host
+    pub url: String,
+}
+
+impl BrokerAddress {
+    /// Parse a broker URL like `mqtts://broker.example.com` or `ws://localhost:8080/mqtt`. URLs
+    /// without a scheme are treated as plain MQTT over TCP.
+    pub fn parse(url: &str) -> Result<Self> {
+        let url = url.trim();
+        let url = match url.contains("://") {
+            true => url.to_string(),
+            false => format!("mqtt://{url}"),
+        };
+        let parsed =
+            Url::parse(&url).map_err(|e| GenericError(format!("Invalid URL {url}: {e}")))?;
+
+        let transport = match parsed.scheme() {
+            "mqtt" | "tcp" => BrokerTransport::Tcp,
+            "mqtts" | "ssl" | "tls" => BrokerTransport::Tls,
+            "ws" => BrokerTransport::Ws,
+            "wss" => BrokerTransport::Wss,
+            scheme => return Err(GenericError(format!("Unsupported broker scheme {scheme}"))),
+        };
+        let host = match parsed.host_str() {
+            Some(host) if !host.is_empty() => host.to_string(),
+            _ => return Err(GenericError(format!("Missing broker host in {url}"))),
+        };
+        let port = parsed.port().unwrap_or(transport.default_port());
+
+        Ok(Self { transport, host, port, url })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_broker_urls() {
+        let broker = BrokerAddress::parse("localhost").unwrap();
+        assert_eq!(broker.transport, BrokerTransport::Tcp);
+        assert_eq!((broker.host.as_str(), broker.port), ("localhost", 1883));
+
+        let broker = BrokerAddress::parse("mqtts://broker.example.com").unwrap();
+        assert_eq!(broker.transport, BrokerTransport::Tls);
+        assert_eq!(broker.port, 8883);
+
+        let broker = BrokerAddress::parse("wss://broker.example.com:8084/mqtt").unwrap();
+        assert_eq!(broker.transport, BrokerTransport::Wss);
+        assert_eq!(broker.port, 8084);
+        assert_eq!(broker.url, "wss://broker.example.com:8084/mqtt");
+
+        assert!(BrokerAddress::parse("http://broker.example.com").is_err());
+    }
+}
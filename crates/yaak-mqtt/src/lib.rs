@@ -0,0 +1,8 @@
+mod broker;
+pub mod error;
+pub mod manager;
+pub mod render;
+
+pub use broker::{BrokerAddress, BrokerTransport};
+pub use manager::{MqttClientEvent, MqttConnectOptions, MqttManager};
+pub use render::render_mqtt_request;
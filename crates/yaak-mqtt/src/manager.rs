@@ -0,0 +1,205 @@
+use crate::broker::{BrokerAddress, BrokerTransport};
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use log::{debug, info, warn};
+use rumqttc::{
+    AsyncClient, Event, EventLoop, MqttOptions, Outgoing, Packet, QoS, TlsConfiguration, Transport,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, mpsc};
+use yaak_models::models::MqttQos;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig, get_tls_config};
+
+/// Keep-alive used when the request doesn't set one
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(60);
+
+/// Largest packet sent or received, well above the small default so that sizeable payloads work
+const MAX_PACKET_SIZE: usize = 16 * 1024 * 1024;
+
+/// Everything needed to connect to a broker
+#[derive(Clone, Default)]
+pub struct MqttConnectOptions {
+    pub url: String,
+    pub client_id: String,
+    pub username: String,
+    pub password: String,
+    /// Interval between keep-alive pings, or the default when zero
+    pub keep_alive: Duration,
+    pub clean_session: bool,
+    pub validate_certificates: bool,
+    pub client_cert: Option<ClientCertificateConfig>,
+    pub ca_certificates: Vec<CaCertificateConfig>,
+}
+
+impl MqttConnectOptions {
+    fn mqtt_options(&self) -> Result<MqttOptions> {
+        let broker = BrokerAddress::parse(&self.url)?;
+
+        // WebSocket transports connect to the full URL rather than just the host
+        let host = match broker.transport {
+            BrokerTransport::Ws | BrokerTransport::Wss => broker.url.clone(),
+            BrokerTransport::Tcp | BrokerTransport::Tls => broker.host.clone(),
+        };
+        let mut options = MqttOptions::new(&self.client_id, host, broker.port);
+        options.set_keep_alive(match self.keep_alive.is_zero() {
+            true => DEFAULT_KEEP_ALIVE,
+            false => self.keep_alive.max(Duration::from_secs(1)),
+        });
+        options.set_clean_session(self.clean_session);
+        options.set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+        if !self.username.is_empty() || !self.password.is_empty() {
+            options.set_credentials(&self.username, &self.password);
+        }
+
+        if broker.transport.is_secure() {
+            let tls_config = get_tls_config(
+                self.validate_certificates,
+                false,
+                self.client_cert.clone(),
+                &self.ca_certificates,
+            )?;
+            let tls = TlsConfiguration::Rustls(Arc::new(tls_config));
+            options.set_transport(match broker.transport {
+                BrokerTransport::Wss => Transport::wss_with_config(tls),
+                _ => Transport::tls_with_config(tls),
+            });
+        } else if broker.transport == BrokerTransport::Ws {
+            options.set_transport(Transport::Ws);
+        }
+
+        Ok(options)
+    }
+}
+
+/// Something that happened on a connection after it was established
+#[derive(Debug)]
+pub enum MqttClientEvent {
+    /// A message published to one of the subscribed topics
+    Message {
+        topic: String,
+        payload: Vec<u8>,
+        qos: MqttQos,
+        retain: bool,
+    },
+    /// The connection ended. There's no error when it was closed through the manager.
+    Disconnected { error: Option<String> },
+}
+
+#[derive(Clone, Default)]
+pub struct MqttManager {
+    clients: Arc<Mutex<HashMap<String, AsyncClient>>>,
+}
+
+impl MqttManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connect to a broker, returning once it has accepted the connection. Returns whether the
+    /// broker resumed an existing session for the client ID.
+    pub async fn connect(
+        &self,
+        id: &str,
+        options: &MqttConnectOptions,
+        event_tx: mpsc::Sender<MqttClientEvent>,
+    ) -> Result<bool> {
+        info!("Connecting to MQTT broker {}", options.url);
+        let (client, mut event_loop) = AsyncClient::new(options.mqtt_options()?, 64);
+
+        let session_present = loop {
+            match event_loop.poll().await? {
+                Event::Incoming(Packet::ConnAck(ack)) => break ack.session_present,
+                _ => continue,
+            }
+        };
+        info!("Connected to MQTT broker {} session_present={session_present}", options.url);
+
+        self.clients.lock().await.insert(id.to_string(), client);
+        tokio::spawn(poll_events(id.to_string(), event_loop, self.clients.clone(), event_tx));
+
+        Ok(session_present)
+    }
+
+    pub async fn subscribe(&self, id: &str, topic: &str, qos: MqttQos) -> Result<()> {
+        self.client(id).await?.subscribe(topic, to_qos(qos)).await?;
+        Ok(())
+    }
+
+    pub async fn publish(
+        &self,
+        id: &str,
+        topic: &str,
+        payload: Vec<u8>,
+        qos: MqttQos,
+        retain: bool,
+    ) -> Result<()> {
+        self.client(id).await?.publish(topic, to_qos(qos), retain, payload).await?;
+        Ok(())
+    }
+
+    pub async fn close(&self, id: &str) -> Result<()> {
+        let client = self.clients.lock().await.remove(id);
+        match client {
+            Some(client) => Ok(client.disconnect().await?),
+            None => Err(GenericError(format!("Failed to find connection for id {id}"))),
+        }
+    }
+
+    async fn client(&self, id: &str) -> Result<AsyncClient> {
+        match self.clients.lock().await.get(id) {
+            Some(client) => Ok(client.clone()),
+            None => Err(GenericError(format!("Failed to find connection for id {id}"))),
+        }
+    }
+}
+
+/// Drive the connection, forwarding received messages until it ends
+async fn poll_events(
+    id: String,
+    mut event_loop: EventLoop,
+    clients: Arc<Mutex<HashMap<String, AsyncClient>>>,
+    event_tx: mpsc::Sender<MqttClientEvent>,
+) {
+    let error = loop {
+        match event_loop.poll().await {
+            Ok(Event::Incoming(Packet::Publish(p))) => {
+                let event = MqttClientEvent::Message {
+                    topic: p.topic.to_string(),
+                    payload: p.payload.to_vec(),
+                    qos: from_qos(p.qos),
+                    retain: p.retain,
+                };
+                if event_tx.send(event).await.is_err() {
+                    warn!("MQTT event receiver dropped");
+                }
+            }
+            Ok(Event::Incoming(Packet::Disconnect)) => break Some("Disconnected by broker".into()),
+            Ok(Event::Outgoing(Outgoing::Disconnect)) => break None,
+            Ok(e) => debug!("MQTT event {e:?}"),
+            Err(e) => break Some(e.to_string()),
+        }
+    };
+
+    // Errors after a close requested through the manager are expected, so don't report them
+    let closed = clients.lock().await.remove(&id).is_none();
+    let error = if closed { None } else { error };
+    let _ = event_tx.send(MqttClientEvent::Disconnected { error }).await;
+}
+
+fn to_qos(qos: MqttQos) -> QoS {
+    match qos {
+        MqttQos::AtMostOnce => QoS::AtMostOnce,
+        MqttQos::AtLeastOnce => QoS::AtLeastOnce,
+        MqttQos::ExactlyOnce => QoS::ExactlyOnce,
+    }
+}
+
+fn from_qos(qos: QoS) -> MqttQos {
+    match qos {
+        QoS::AtMostOnce => MqttQos::AtMostOnce,
+        QoS::AtLeastOnce => MqttQos::AtLeastOnce,
+        QoS::ExactlyOnce => MqttQos::ExactlyOnce,
+    }
+}
@@ -0,0 +1,33 @@
+use crate::error::Result;
+use yaak_models::models::{Environment, MqttRequest, MqttSubscription};
+use yaak_models::render::make_vars_hashmap;
+use yaak_templates::{RenderOptions, TemplateCallback, parse_and_render};
+
+pub async fn render_mqtt_request<T: TemplateCallback>(
+    r: &MqttRequest,
+    environment_chain: Vec<Environment>,
+    cb: &T,
+    opt: &RenderOptions,
+) -> Result<MqttRequest> {
+    let vars = &make_vars_hashmap(environment_chain);
+
+    let mut subscriptions = Vec::new();
+    for s in r.subscriptions.clone() {
+        if !s.enabled {
+            continue;
+        }
+        subscriptions
+            .push(MqttSubscription { topic: parse_and_render(&s.topic, vars, cb, opt).await?, ..s })
+    }
+
+    Ok(MqttRequest {
+        url: parse_and_render(&r.url, vars, cb, opt).await?,
+        client_id: parse_and_render(&r.client_id, vars, cb, opt).await?,
+        username: parse_and_render(&r.username, vars, cb, opt).await?,
+        password: parse_and_render(&r.password, vars, cb, opt).await?,
+        subscriptions,
+        topic: parse_and_render(&r.topic, vars, cb, opt).await?,
+        payload: parse_and_render(&r.payload, vars, cb, opt).await?,
+        ..r.to_owned()
+    })
+}
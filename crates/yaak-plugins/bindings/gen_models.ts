@@ -1,6 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
 export type AnyModel =
+  | CollectionRun
   | CookieJar
   | Environment
   | Folder
@@ -12,8 +13,15 @@ export type AnyModel =
   | HttpResponse
   | HttpResponseEvent
   | KeyValue
+  | Monitor
+  | MonitorRun
+  | MqttConnection
+  | MqttEvent
+  | MqttRequest
   | Plugin
   | Settings
+  | SocketRequest
+  | SocketResponse
   | SyncState
   | WebsocketConnection
   | WebsocketEvent
@@ -21,6 +29,52 @@ export type AnyModel =
   | Workspace
   | WorkspaceMeta;
 
+/**
+ * A check run against every response a request receives
+ */
+export type Assertion = {
+  enabled?: boolean;
+  kind: AssertionKind;
+  /**
+   * JSONPath expression for JSONPath assertions, or the header name for header assertions
+   */
+  path: string;
+  /**
+   * Expected status or value, a regular expression for matches, or a latency in milliseconds
+   */
+  value: string;
+  id?: string;
+};
+
+export type AssertionKind =
+  | "status_equals"
+  | "json_path_equals"
+  | "json_path_matches"
+  | "header_exists"
+  | "latency_under";
+
+export type AssertionResult = {
+  /**
+   * ID of the assertion that produced this result
+   */
+  assertionId?: string;
+  /**
+   * What was checked, like `status == 200`
+   */
+  name: string;
+  passed: boolean;
+  /**
+   * The value that was seen, or why the check couldn't run
+   */
+  message: string;
+};
+
+/**
+ * A CA certificate trusted in addition to the platform's certificate store, either read from a
+ * PEM file or pasted inline
+ */
+export type CaCertificate = { file: string | null; pem: string | null; enabled?: boolean };
+
 export type ClientCertificate = {
   host: string;
   port: number | null;
@@ -31,6 +85,56 @@ export type ClientCertificate = {
   enabled?: boolean;
 };
 
+/**
+ * A single run of every HTTP request in a folder
+ */
+export type CollectionRun = {
+  model: "collection_run";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  folderId: string;
+  state: CollectionRunState;
+  /**
+   * Maximum number of requests in flight at once
+   */
+  concurrency: number;
+  /**
+   * Milliseconds to wait before sending each request after the first
+   */
+  delay: number;
+  stopOnFailure: boolean;
+  /**
+   * CSV or JSON file whose rows each run the folder once, with columns bound to variables
+   */
+  dataFile: string | null;
+  elapsed: number;
+  results: Array<CollectionRunResult>;
+};
+
+/**
+ * Outcome of sending one request during a collection run
+ */
+export type CollectionRunResult = {
+  /**
+   * Index of the data file row this result was sent with, or 0 without a data file
+   */
+  iteration: number;
+  requestId: string;
+  requestName: string;
+  responseId: string | null;
+  status: number;
+  elapsed: number;
+  /**
+   * False when the request couldn't be sent, returned an error status, or failed an assertion
+   */
+  passed: boolean;
+  error: string | null;
+};
+
+export type CollectionRunState = "running" | "completed" | "stopped";
+
 export type Cookie = {
   name: string;
   value: string;
@@ -86,9 +190,23 @@ export type Environment = {
   variables: Array<EnvironmentVariable>;
   color: string | null;
   sortPriority: number;
+  /**
+   * Cookie jar to use while this environment is active, instead of the selected one.
+   */
+  cookieJarId: string | null;
 };
 
-export type EnvironmentVariable = { enabled?: boolean; name: string; value: string; id?: string };
+export type EnvironmentVariable = {
+  enabled?: boolean;
+  name: string;
+  value: string;
+  id?: string;
+  /**
+   * Whether the value is encrypted with the workspace key before it's stored, and left out of
+   * exports
+   */
+  secret?: boolean;
+};
 
 export type Folder = {
   model: "folder";
@@ -100,14 +218,38 @@ export type Folder = {
   authentication: Record<string, any>;
   authenticationType: string | null;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   headers: Array<HttpRequestHeader>;
+  /**
+   * Default URL parameters inherited by requests in this folder
+   */
+  urlParameters: Array<HttpUrlParameter>;
   name: string;
   sortPriority: number;
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
   settingFollowRedirects: InheritedBoolSetting;
+  settingMaxRedirects: InheritedIntSetting;
+  settingRedirectResendBody: InheritedBoolSetting;
+  settingRedirectStripAuthorization: InheritedBoolSetting;
+  settingDecompressResponse: InheritedBoolSetting;
   settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingHttpVersion: InheritedHttpVersionSetting;
+  settingRetryPolicy: InheritedRetryPolicySetting;
+  settingSshTunnel: InheritedSshTunnelSetting;
+  settingCaCertificates: InheritedCaCertificatesSetting;
+  settingClientCertificate: InheritedClientCertificateSetting;
+  /**
+   * Set when the folder has been moved to the trash. Trashed folders are hidden from
+   * listings until they are restored or the trash is emptied.
+   */
+  deletedAt: string | null;
 };
 
 export type GraphQlIntrospection = {
@@ -117,9 +259,12 @@ export type GraphQlIntrospection = {
   updatedAt: string;
   workspaceId: string;
   requestId: string;
+  url: string | null;
   content: string | null;
 };
 
+export type GrpcCompression = "gzip" | "zstd";
+
 export type GrpcConnection = {
   model: "grpc_connection";
   id: string;
@@ -135,9 +280,31 @@ export type GrpcConnection = {
   state: GrpcConnectionState;
   trailers: { [key in string]?: string };
   url: string;
+  /**
+   * Compression the server used for its messages, from its `grpc-encoding` header
+   */
+  responseEncoding: string | null;
 };
 
-export type GrpcConnectionState = "initialized" | "connected" | "closed";
+export type GrpcConnectionState = "initialized" | "connected" | "closed" | "timed_out";
+
+/**
+ * A detail message from the `grpc-status-details-bin` trailer of a failed call
+ */
+export type GrpcErrorDetail = {
+  /**
+   * Fully-qualified message name, like `google.rpc.BadRequest`
+   */
+  typeName: string;
+  /**
+   * The decoded message, or `None` when no descriptor for the type was available
+   */
+  value: any;
+  /**
+   * Base64 of the encoded message, kept when it couldn't be decoded
+   */
+  raw: string | null;
+};
 
 export type GrpcEvent = {
   model: "grpc_event";
@@ -152,6 +319,10 @@ export type GrpcEvent = {
   eventType: GrpcEventType;
   metadata: { [key in string]?: string };
   status: number | null;
+  /**
+   * Rich error details the server attached to a failed call's status
+   */
+  errorDetails: Array<GrpcErrorDetail>;
 };
 
 export type GrpcEventType =
@@ -162,6 +333,13 @@ export type GrpcEventType =
   | "connection_start"
   | "connection_end";
 
+export type GrpcMessageTemplate = { name: string; message: string; id?: string };
+
+/**
+ * Wire protocol for gRPC calls
+ */
+export type GrpcProtocol = "grpc" | "grpc_web" | "connect_json" | "connect_proto";
+
 export type GrpcRequest = {
   model: "grpc_request";
   id: string;
@@ -172,7 +350,15 @@ export type GrpcRequest = {
   authenticationType: string | null;
   authentication: Record<string, any>;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   message: string;
+  /**
+   * Named messages that can be replayed in order during a streaming session
+   */
+  messageTemplates: Array<GrpcMessageTemplate>;
   metadata: Array<HttpRequestHeader>;
   method: string | null;
   name: string;
@@ -182,7 +368,32 @@ export type GrpcRequest = {
    * Server URL (http for plaintext or https for secure)
    */
   url: string;
+  /**
+   * Wire protocol used to call the server
+   */
+  protocol: GrpcProtocol;
   settingValidateCertificates: InheritedBoolSetting;
+  settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingCaCertificates: InheritedCaCertificatesSetting;
+  settingClientCertificate: InheritedClientCertificateSetting;
+  /**
+   * Compression applied to messages sent to the server
+   */
+  sendCompression: GrpcCompression | null;
+  /**
+   * Compression the server may apply to the messages it sends back
+   */
+  acceptCompression: Array<GrpcCompression>;
+  /**
+   * Steps that run before the request is sent
+   */
+  preRequestSteps: Array<PreRequestStep>;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type HttpRequest = {
@@ -197,6 +408,10 @@ export type HttpRequest = {
   body: Record<string, any>;
   bodyType: string | null;
   description: string;
+  /**
+   * Labels used to group requests across folders (eg. `smoke-test`)
+   */
+  tags: Array<string>;
   headers: Array<HttpRequestHeader>;
   method: string;
   name: string;
@@ -206,11 +421,44 @@ export type HttpRequest = {
    * URL parameters used for both path placeholders (`:id`) and query string entries.
    */
   urlParameters: Array<HttpUrlParameter>;
+  /**
+   * Path of a Unix domain socket to send the request over. The URL is still used for the
+   * Host header and path.
+   */
+  unixSocket: string | null;
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
   settingFollowRedirects: InheritedBoolSetting;
+  settingMaxRedirects: InheritedIntSetting;
+  settingRedirectResendBody: InheritedBoolSetting;
+  settingRedirectStripAuthorization: InheritedBoolSetting;
+  settingDecompressResponse: InheritedBoolSetting;
   settingRequestTimeout: InheritedIntSetting;
+  settingConnectTimeout: InheritedIntSetting;
+  settingReadTimeout: InheritedIntSetting;
+  settingHttpVersion: InheritedHttpVersionSetting;
+  settingRetryPolicy: InheritedRetryPolicySetting;
+  /**
+   * JSONPath or XPath expression last used to filter this request's responses
+   */
+  responseFilter: string | null;
+  /**
+   * Values copied from each response into variables after the request is sent
+   */
+  responseExtractions: Array<ResponseExtraction>;
+  /**
+   * Steps that run before the request is sent
+   */
+  preRequestSteps: Array<PreRequestStep>;
+  /**
+   * Checks run against each response after the request is sent
+   */
+  assertions: Array<Assertion>;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type HttpRequestHeader = { enabled?: boolean; name: string; value: string; id?: string };
@@ -228,16 +476,67 @@ export type HttpResponse = {
   elapsed: number;
   elapsedHeaders: number;
   elapsedDns: number;
+  /**
+   * Time to open the connection once DNS resolved, including the TCP connect, TLS
+   * handshake, and any proxy setup. Zero when an existing connection was reused
+   */
+  elapsedConnect: number;
+  /**
+   * Time to write a streamed request body after connecting. Buffered bodies are sent
+   * along with the headers, so this is zero for them
+   */
+  elapsedSend: number;
   error: string | null;
   headers: Array<HttpResponseHeader>;
   remoteAddr: string | null;
   requestContentLength: number | null;
   requestHeaders: Array<HttpResponseHeader>;
+  /**
+   * Request line and headers as written to the connection, once templates, authentication,
+   * and inherited headers were applied. The body is stored separately
+   */
+  requestHead: string | null;
   status: number;
   statusReason: string | null;
   state: HttpResponseState;
   url: string;
   version: string | null;
+  /**
+   * Outcome of the request's assertions, checked once the response completes
+   */
+  assertionResults: Array<AssertionResult>;
+  /**
+   * Redirects followed on the way to this response, in the order they happened
+   */
+  redirects: Array<HttpResponseRedirect>;
+  /**
+   * File the body was streamed to when sent in download mode. Such responses have no
+   * `body_path`, so the file is left alone when the response is deleted
+   */
+  downloadPath: string | null;
+  /**
+   * TLS connection details, for responses received over HTTPS
+   */
+  tls: HttpResponseTls | null;
+};
+
+/**
+ * A certificate the server presented during the TLS handshake
+ */
+export type HttpResponseCertificate = {
+  subject: string;
+  issuer: string;
+  /**
+   * DNS names and IP addresses the certificate is valid for
+   */
+  subjectAltNames: Array<string>;
+  serialNumber: string;
+  notBefore: string | null;
+  notAfter: string | null;
+  /**
+   * Hex-encoded SHA-256 digest of the DER certificate
+   */
+  sha256Fingerprint: string;
 };
 
 export type HttpResponseEvent = {
@@ -296,11 +595,56 @@ export type HttpResponseEventData =
       addresses: Array<string>;
       duration: bigint;
       overridden: boolean;
-    };
+    }
+  | { type: "connected"; duration: bigint }
+  | {
+      type: "server_sent_event";
+      event_type: string;
+      data: string;
+      id: string | null;
+      retry: bigint | null;
+    }
+  | { type: "retry"; attempt: number; max_attempts: number; delay: bigint; reason: string }
+  | { type: "download_progress"; bytes: bigint; total: bigint | null }
+  | { type: "upload_progress"; bytes: bigint; total: bigint | null };
 
 export type HttpResponseHeader = { name: string; value: string };
 
-export type HttpResponseState = "initialized" | "connected" | "closed";
+/**
+ * A redirect response that was followed instead of being returned
+ */
+export type HttpResponseRedirect = {
+  /**
+   * URL of the request that was redirected
+   */
+  url: string;
+  method: string;
+  status: number;
+  /**
+   * URL the redirect pointed to
+   */
+  location: string;
+};
+
+export type HttpResponseState = "initialized" | "connected" | "closed" | "timed_out";
+
+/**
+ * TLS details of the connection a response was received on
+ */
+export type HttpResponseTls = {
+  /**
+   * Negotiated protocol version, like `TLSv1.3`. Unknown when certificates aren't validated
+   */
+  version: string | null;
+  /**
+   * Application protocol negotiated with ALPN, like `h2`
+   */
+  alpn: string | null;
+  /**
+   * Certificates the server presented, starting with its own
+   */
+  certificates: Array<HttpResponseCertificate>;
+};
 
 export type HttpUrlParameter = {
   enabled?: boolean;
@@ -313,10 +657,44 @@ export type HttpUrlParameter = {
   id?: string;
 };
 
+/**
+ * HTTP version requests are sent with
+ */
+export type HttpVersion = "auto" | "http1" | "http2" | "http3";
+
 export type InheritedBoolSetting = { enabled?: boolean; value: boolean };
 
+export type InheritedCaCertificatesSetting = {
+  enabled?: boolean;
+  /**
+   * CA certificates trusted in place of the workspace's
+   */
+  value: Array<CaCertificate>;
+};
+
+export type InheritedClientCertificateSetting = {
+  enabled?: boolean;
+  /**
+   * Certificate presented to every server regardless of its host, or `None` to match one by
+   * host from the app settings
+   */
+  value: ClientCertificate | null;
+};
+
+export type InheritedHttpVersionSetting = { enabled?: boolean; value: HttpVersion };
+
 export type InheritedIntSetting = { enabled?: boolean; value: number };
 
+export type InheritedRetryPolicySetting = { enabled?: boolean; value: RetryPolicy };
+
+export type InheritedSshTunnelSetting = {
+  enabled?: boolean;
+  /**
+   * Tunnel for requests in the folder, or `None` to connect directly
+   */
+  value: SshTunnel | null;
+};
+
 export type KeyValue = {
   model: "key_value";
   id: string;
@@ -327,6 +705,167 @@ export type KeyValue = {
   value: string;
 };
 
+/**
+ * A request or folder that is sent on an interval in the background to check that it still works
+ */
+export type Monitor = {
+  model: "monitor";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  name: string;
+  enabled: boolean;
+  /**
+   * Folder whose HTTP requests are all sent on each run
+   */
+  folderId: string | null;
+  /**
+   * Single HTTP request to send on each run, used when there's no folder
+   */
+  requestId: string | null;
+  environmentId: string | null;
+  cookieJarId: string | null;
+  /**
+   * Seconds between runs
+   */
+  interval: number;
+  /**
+   * How many runs in a row must fail before the monitor is flagged as down
+   */
+  failureThreshold: number;
+  consecutiveFailures: number;
+  lastRunAt: string | null;
+};
+
+/**
+ * One scheduled run of a monitor
+ */
+export type MonitorRun = {
+  model: "monitor_run";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  monitorId: string;
+  /**
+   * True when every request in the run passed
+   */
+  passed: boolean;
+  elapsed: number;
+  results: Array<CollectionRunResult>;
+};
+
+export type MqttConnection = {
+  model: "mqtt_connection";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  requestId: string;
+  elapsed: number;
+  error: string | null;
+  state: MqttConnectionState;
+  url: string;
+  /**
+   * Client ID the connection was made with
+   */
+  clientId: string;
+};
+
+export type MqttConnectionState = "initialized" | "connected" | "closing" | "closed";
+
+export type MqttEvent = {
+  model: "mqtt_event";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  requestId: string;
+  connectionId: string;
+  isServer: boolean;
+  eventType: MqttEventType;
+  /**
+   * Topic a message was published to, or the topic filter of a subscription
+   */
+  topic: string;
+  message: Array<number>;
+  qos: MqttQos;
+  retain: boolean;
+};
+
+export type MqttEventType =
+  | "connect"
+  | "disconnect"
+  | "subscribe"
+  | "unsubscribe"
+  | "publish"
+  | "error";
+
+/**
+ * Delivery guarantee of an MQTT publish or subscription
+ */
+export type MqttQos = "at_most_once" | "at_least_once" | "exactly_once";
+
+export type MqttRequest = {
+  model: "mqtt_request";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  folderId: string | null;
+  description: string;
+  name: string;
+  sortPriority: number;
+  /**
+   * Broker URL, using the `mqtt`, `mqtts`, `ws` or `wss` scheme
+   */
+  url: string;
+  /**
+   * Identifies the client to the broker. A random one is used when empty
+   */
+  clientId: string;
+  username: string;
+  password: string;
+  /**
+   * Seconds between keep-alive pings, or the broker default of 60 when zero
+   */
+  keepAlive: number;
+  /**
+   * Ask the broker to keep subscriptions and queued messages between connections
+   */
+  persistentSession: boolean;
+  subscriptions: Array<MqttSubscription>;
+  /**
+   * Topic messages are published to
+   */
+  topic: string;
+  payload: string;
+  qos: MqttQos;
+  /**
+   * Ask the broker to keep the message for future subscribers to the topic
+   */
+  retain: boolean;
+  settingValidateCertificates: InheritedBoolSetting;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
+};
+
+/**
+ * A topic filter subscribed to when an MQTT connection is established
+ */
+export type MqttSubscription = {
+  enabled?: boolean;
+  /**
+   * Topic filter, which may contain `+` and `#` wildcards
+   */
+  topic: string;
+  qos: MqttQos;
+  id?: string;
+};
+
 export type Plugin = {
   model: "plugin";
   id: string;
@@ -341,6 +880,39 @@ export type Plugin = {
 
 export type PluginSource = "bundled" | "filesystem" | "registry";
 
+/**
+ * Work to do before a request is sent, like sending a login request first or running a plugin
+ * action. Steps run in order and a failing step stops the send.
+ */
+export type PreRequestStep = {
+  enabled?: boolean;
+  kind: PreRequestStepKind;
+  /**
+   * Only run the step when this variable is empty or undefined. Runs every time when blank.
+   */
+  ifEmpty: string;
+  /**
+   * HTTP request to send for send-request steps
+   */
+  requestId: string;
+  /**
+   * Chained value to write for set-variable steps
+   */
+  name: string;
+  /**
+   * Template rendered into the variable for set-variable steps
+   */
+  value: string;
+  /**
+   * Plugin name and action label for plugin-action steps
+   */
+  plugin: string;
+  action: string;
+  id?: string;
+};
+
+export type PreRequestStepKind = "send_request" | "set_variable" | "plugin_action";
+
 export type ProxySetting =
   | {
       type: "enabled";
@@ -354,6 +926,68 @@ export type ProxySetting =
 
 export type ProxySettingAuth = { user: string; password: string };
 
+/**
+ * A rule that copies a value out of each response a request receives into a variable, so
+ * one request (like a login) can feed values to the ones that follow it
+ */
+export type ResponseExtraction = {
+  enabled?: boolean;
+  /**
+   * Name of the variable to write
+   */
+  name: string;
+  source: ResponseExtractionSource;
+  /**
+   * JSONPath or XPath expression for body sources, or the header name for header sources
+   */
+  path: string;
+  /**
+   * Environment targets write to the active environment, or the base environment if none is
+   * active. Chained targets write to the workspace's chained values, which every request can
+   * use but which are overridden by environment variables with the same name.
+   */
+  target: ResponseExtractionTarget;
+  id?: string;
+};
+
+export type ResponseExtractionSource = "body" | "header" | "status";
+
+export type ResponseExtractionTarget = "environment" | "chained";
+
+/**
+ * When and how often a failed request is sent again
+ */
+export type RetryPolicy = {
+  /**
+   * Total number of attempts, including the first. 1 disables retries.
+   */
+  maxAttempts: number;
+  /**
+   * Retry when the server responds with a 5xx status
+   */
+  retryOnServerError: boolean;
+  /**
+   * Retry when a connection to the server can't be established
+   */
+  retryOnConnectionError: boolean;
+  /**
+   * Retry when a connect, read, or total timeout elapses
+   */
+  retryOnTimeout: boolean;
+  /**
+   * Milliseconds to wait before the first retry, doubled for every retry after it
+   */
+  initialDelay: number;
+  /**
+   * Upper bound in milliseconds for the delay between attempts
+   */
+  maxDelay: number;
+  /**
+   * Wait a random fraction of each delay, so clients retrying together spread out
+   */
+  jitter: boolean;
+};
+
 export type Settings = {
   model: "settings";
   id: string;
@@ -383,6 +1017,135 @@ export type Settings = {
   hotkeys: { [key in string]?: Array<string> };
 };
 
+/**
+ * How messages are sent to a Socket.IO server
+ */
+export type SocketIoSettings = {
+  /**
+   * Namespace to connect to, like `/chat`
+   */
+  namespace: string;
+  /**
+   * Name of the event the message is emitted as
+   */
+  event: string;
+  /**
+   * Ask the server to acknowledge each emitted event
+   */
+  ack: boolean;
+  /**
+   * JSON payload sent when connecting to the namespace, often used for auth tokens
+   */
+  auth: string;
+};
+
+/**
+ * How the payload of a raw socket request is turned into bytes
+ */
+export type SocketPayloadFormat = "text" | "hex";
+
+/**
+ * Transport a raw socket request is sent over
+ */
+export type SocketProtocol = "tcp" | "udp";
+
+export type SocketRequest = {
+  model: "socket_request";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  folderId: string | null;
+  description: string;
+  name: string;
+  sortPriority: number;
+  protocol: SocketProtocol;
+  /**
+   * Address to send to, as `host:port`
+   */
+  url: string;
+  payload: string;
+  payloadFormat: SocketPayloadFormat;
+  /**
+   * Milliseconds to keep waiting for more response data after the last read, or the default
+   * when zero
+   */
+  readTimeout: number;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
+};
+
+export type SocketResponse = {
+  model: "socket_response";
+  id: string;
+  createdAt: string;
+  updatedAt: string;
+  workspaceId: string;
+  requestId: string;
+  protocol: SocketProtocol;
+  url: string;
+  /**
+   * Resolved address the payload was sent to
+   */
+  remoteAddr: string | null;
+  state: SocketResponseState;
+  error: string | null;
+  /**
+   * Milliseconds until the connection was established, or the datagram sent for UDP
+   */
+  elapsedConnect: number;
+  /**
+   * Milliseconds until the first response bytes arrived, if any did
+   */
+  elapsedFirstByte: number | null;
+  /**
+   * Milliseconds until the exchange finished
+   */
+  elapsed: number;
+  bytesSent: number;
+  /**
+   * Every byte received, with UDP datagrams concatenated in arrival order
+   */
+  body: Array<number>;
+};
+
+export type SocketResponseState = "initialized" | "closed";
+
+/**
+ * SSH connection that requests are tunneled through, like a bastion in front of private APIs
+ */
+export type SshTunnel = {
+  /**
+   * SSH server to connect to, like `bastion.example.com`
+   */
+  host: string;
+  /**
+   * SSH port, or 22 when unset
+   */
+  port: number | null;
+  username: string;
+  /**
+   * Password to authenticate with when no private key file is set
+   */
+  password: string;
+  /**
+   * Path of a private key file to authenticate with, like `~/.ssh/id_ed25519`
+   */
+  privateKeyFile: string;
+  /**
+   * Passphrase of an encrypted private key
+   */
+  passphrase: string;
+  /**
+   * Comma-separated hosts reached through the tunnel, optionally with a port (eg.
+   * `api.internal, 10.0.0.5:8443`). Every request is tunneled when empty.
+   */
+  destination: string;
+  disabled: boolean;
+};
+
 export type SyncState = {
   model: "sync_state";
   id: string;
@@ -394,6 +1157,10 @@ export type SyncState = {
   checksum: string;
   relPath: string;
   syncDir: string;
+  /**
+   * Contents of the file when it was last synced, used as the base of three-way merges
+   */
+  base: string | null;
 };
 
 export type WebsocketConnection = {
@@ -426,7 +1193,50 @@ export type WebsocketEvent = {
   messageType: WebsocketEventType;
 };
 
-export type WebsocketEventType = "binary" | "close" | "frame" | "open" | "ping" | "pong" | "text";
+export type WebsocketEventType =
+  | "binary"
+  | "close"
+  | "frame"
+  | "open"
+  | "ping"
+  | "pong"
+  | "reconnect"
+  | "text";
+
+/**
+ * Kind of frame a WebSocket message is sent as
+ */
+export type WebsocketMessageFormat = "text" | "hex" | "base64" | "file";
+
+/**
+ * Protocol spoken over a WebSocket connection
+ */
+export type WebsocketProtocol = "websocket" | "socket_io";
+
+/**
+ * How a dropped WebSocket connection is re-established. Attempts are spaced by a delay that
+ * doubles after each one, up to `max_delay_ms`.
+ */
+export type WebsocketReconnectPolicy = {
+  enabled: boolean;
+  /**
+   * Attempts to make before giving up, or 0 to keep trying
+   */
+  maxAttempts: number;
+  /**
+   * Delay before the first attempt
+   */
+  initialDelayMs: number;
+  /**
+   * Longest delay between attempts
+   */
+  maxDelayMs: number;
+  /**
+   * Message sent after connecting and again after every reconnect, such as a subscription or
+   * login message. Nothing is sent when empty.
+   */
+  handshakeMessage: string;
+};
 
 export type WebsocketRequest = {
   model: "websocket_request";
@@ -440,6 +1250,14 @@ export type WebsocketRequest = {
   description: string;
   headers: Array<HttpRequestHeader>;
   message: string;
+  /**
+   * How the message is sent, as text or as the bytes it describes
+   */
+  messageFormat: WebsocketMessageFormat;
+  /**
+   * File whose contents are sent as a binary frame when `message_format` is `file`
+   */
+  messageFile: string | null;
   name: string;
   sortPriority: number;
   url: string;
@@ -450,6 +1268,22 @@ export type WebsocketRequest = {
   settingSendCookies: InheritedBoolSetting;
   settingStoreCookies: InheritedBoolSetting;
   settingValidateCertificates: InheritedBoolSetting;
+  /**
+   * Re-establishes the connection when it drops without being closed from the app
+   */
+  reconnect: WebsocketReconnectPolicy;
+  /**
+   * Protocol spoken over the connection
+   */
+  protocol: WebsocketProtocol;
+  /**
+   * Used when `protocol` is `socket_io`
+   */
+  socketIo: SocketIoSettings;
+  /**
+   * Set when the request has been moved to the trash.
+   */
+  deletedAt: string | null;
 };
 
 export type Workspace = {
@@ -461,14 +1295,72 @@ export type Workspace = {
   authenticationType: string | null;
   description: string;
   headers: Array<HttpRequestHeader>;
+  /**
+   * Default URL parameters inherited by every request in the workspace
+   */
+  urlParameters: Array<HttpUrlParameter>;
   name: string;
   encryptionKeyChallenge: string | null;
   settingValidateCertificates: boolean;
   settingFollowRedirects: boolean;
+  /**
+   * Stop following redirects after this many hops and fail the request
+   */
+  settingMaxRedirects: number;
+  /**
+   * Send the request body again when a 307 or 308 redirect preserves the method
+   */
+  settingRedirectResendBody: boolean;
+  /**
+   * Remove the Authorization header when a redirect leads to a different origin
+   */
+  settingRedirectStripAuthorization: boolean;
+  /**
+   * Decode compressed response bodies, or keep them exactly as the server sent them
+   */
+  settingDecompressResponse: boolean;
   settingRequestTimeout: number;
+  /**
+   * Milliseconds to wait for a connection to be established, or 0 to wait indefinitely
+   */
+  settingConnectTimeout: number;
+  /**
+   * Milliseconds to wait for data from the server between reads, or 0 to wait indefinitely
+   */
+  settingReadTimeout: number;
+  settingHttpVersion: HttpVersion;
   settingDnsOverrides: Array<DnsOverride>;
+  /**
+   * CA certificates trusted when validating server certificates, for internal CAs
+   */
+  settingCaCertificates: Array<CaCertificate>;
   settingSendCookies: boolean;
   settingStoreCookies: boolean;
+  /**
+   * Proxy used for requests in this workspace, overriding the global proxy setting when set
+   */
+  settingProxy: ProxySetting | null;
+  /**
+   * SSH tunnel used for requests in this workspace, unless a folder overrides it
+   */
+  settingSshTunnel: SshTunnel | null;
+  /**
+   * Keep at most this many responses per request, or the default history size when unset
+   */
+  settingMaxResponsesPerRequest: number | null;
+  /**
+   * Delete responses older than this many days, or keep them regardless of age when unset
+   */
+  settingResponseRetentionDays: number | null;
+  /**
+   * Add response bodies to the search index so workspace search can find them
+   */
+  settingIndexResponseBodies: boolean;
+  /**
+   * Store response bodies larger than this many bytes once per unique body, or use the
+   * default threshold when unset
+   */
+  settingContentAddressedBodyThreshold: number | null;
 };
 
 export type WorkspaceMeta = {
@@ -479,4 +1371,12 @@ export type WorkspaceMeta = {
   updatedAt: string;
   encryptionKey: EncryptedKey | null;
   settingSyncDir: string | null;
+  /**
+   * Folders that, along with everything inside them, are never written to the sync directory
+   */
+  settingSyncExcludeFolderIds: Array<string>;
+  /**
+   * Write secret environment variables to the sync directory without their values
+   */
+  settingSyncExcludeSecretValues: boolean;
 };
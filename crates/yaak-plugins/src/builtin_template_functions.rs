@@ -0,0 +1,342 @@
+//! Built-in template functions that are evaluated natively when a template is rendered.
+//!
+//! Unlike plugin functions, these don't need a round-trip to the plugin runtime, so they're
+//! available as soon as the app starts and cost nothing extra when a request is sent. A builtin
+//! takes precedence over a plugin function with the same name, so builtins accept the same
+//! arguments as the bundled plugin they replace.
+
+use crate::events::{
+    FormInput, FormInputBase, FormInputSelect, FormInputSelectOption, FormInputText,
+    TemplateFunction, TemplateFunctionArg, TemplateFunctionPreviewType,
+};
+use base64::Engine;
+use base64::prelude::{BASE64_STANDARD, BASE64_STANDARD_NO_PAD, BASE64_URL_SAFE_NO_PAD};
+use chrono::{SecondsFormat, Utc};
+use fake::Fake;
+use fake::faker::address::en::{
+    BuildingNumber, CityName, CountryName, StateAbbr, StateName, StreetName, ZipCode,
+};
+use fake::faker::company::en::CompanyName;
+use fake::faker::internet::en::{SafeEmail, Username};
+use fake::faker::lorem::en::{Paragraph, Sentence, Word};
+use fake::faker::name::en::{FirstName, LastName, Name};
+use fake::faker::phone_number::en::PhoneNumber;
+use rand::Rng;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use yaak_templates::error::Error::RenderError;
+use yaak_templates::error::Result;
+
+type Args = HashMap<String, Value>;
+
+struct BuiltinTemplateFunction {
+    name: &'static str,
+    description: &'static str,
+    args: fn() -> Vec<TemplateFunctionArg>,
+    preview_args: &'static [&'static str],
+    run: fn(&Args) -> Result<String>,
+}
+
+impl BuiltinTemplateFunction {
+    const fn new(
+        name: &'static str,
+        description: &'static str,
+        run: fn(&Args) -> Result<String>,
+    ) -> Self {
+        Self { name, description, args: Vec::new, preview_args: &[], run }
+    }
+
+    fn definition(&self) -> TemplateFunction {
+        TemplateFunction {
+            name: self.name.to_string(),
+            preview_type: Some(TemplateFunctionPreviewType::Live),
+            description: Some(self.description.to_string()),
+            aliases: None,
+            preview_args: match self.preview_args {
+                [] => None,
+                names => Some(names.iter().map(|n| n.to_string()).collect()),
+            },
+            args: (self.args)(),
+        }
+    }
+}
+
+const BUILTIN_TEMPLATE_FUNCTIONS: &[BuiltinTemplateFunction] = &[
+    BuiltinTemplateFunction::new("uuid.v4", "Generate a random UUID V4", |_| {
+        Ok(uuid::Uuid::new_v4().to_string())
+    }),
+    BuiltinTemplateFunction::new(
+        "timestamp.iso",
+        "Get the current time in ISO 8601 format, like `2025-01-01T00:00:00.000Z`",
+        |_| Ok(Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)),
+    ),
+    BuiltinTemplateFunction {
+        args: random_int_args,
+        preview_args: &["min", "max"],
+        ..BuiltinTemplateFunction::new(
+            "random.int",
+            "Generate a random whole number between two values, inclusive",
+            random_int_run,
+        )
+    },
+    BuiltinTemplateFunction {
+        args: hash_sha256_args,
+        ..BuiltinTemplateFunction::new("hash.sha256", "Hash a value with SHA-256", hash_sha256_run)
+    },
+    BuiltinTemplateFunction {
+        args: base64_encode_args,
+        ..BuiltinTemplateFunction::new(
+            "base64.encode",
+            "Encode a value to base64",
+            base64_encode_run,
+        )
+    },
+    BuiltinTemplateFunction {
+        args: base64_decode_args,
+        ..BuiltinTemplateFunction::new(
+            "base64.decode",
+            "Decode a value from base64",
+            base64_decode_run,
+        )
+    },
+    // Fake data, different on every send, for APIs that require unique values
+    BuiltinTemplateFunction::new("fake.name", "Generate a full name", |_| Ok(Name().fake())),
+    BuiltinTemplateFunction::new("fake.firstName", "Generate a first name", |_| {
+        Ok(FirstName().fake())
+    }),
+    BuiltinTemplateFunction::new(
+        "fake.lastName",
+        "Generate a last name",
+        |_| Ok(LastName().fake()),
+    ),
+    BuiltinTemplateFunction::new("fake.email", "Generate an email address", |_| {
+        Ok(SafeEmail().fake())
+    }),
+    BuiltinTemplateFunction::new("fake.username", "Generate a username", |_| Ok(Username().fake())),
+    BuiltinTemplateFunction::new("fake.phone", "Generate a phone number", |_| {
+        Ok(PhoneNumber().fake())
+    }),
+    BuiltinTemplateFunction::new("fake.company", "Generate a company name", |_| {
+        Ok(CompanyName().fake())
+    }),
+    BuiltinTemplateFunction::new(
+        "fake.address",
+        "Generate a street address, like `42 Main Street, Springfield, IL 62704`",
+        fake_address_run,
+    ),
+    BuiltinTemplateFunction::new("fake.street", "Generate a street name", |_| {
+        Ok(StreetName().fake())
+    }),
+    BuiltinTemplateFunction::new("fake.city", "Generate a city name", |_| Ok(CityName().fake())),
+    BuiltinTemplateFunction::new("fake.state", "Generate a state name", |_| Ok(StateName().fake())),
+    BuiltinTemplateFunction::new("fake.zip", "Generate a ZIP code", |_| Ok(ZipCode().fake())),
+    BuiltinTemplateFunction::new("fake.country", "Generate a country name", |_| {
+        Ok(CountryName().fake())
+    }),
+    BuiltinTemplateFunction::new("fake.word", "Generate a lorem ipsum word", |_| Ok(Word().fake())),
+    BuiltinTemplateFunction::new("fake.sentence", "Generate a lorem ipsum sentence", |_| {
+        Ok(Sentence(4..10).fake())
+    }),
+    BuiltinTemplateFunction::new("fake.paragraph", "Generate a lorem ipsum paragraph", |_| {
+        Ok(Paragraph(3..6).fake())
+    }),
+];
+
+/// Definitions of every builtin, for listing alongside plugin functions
+pub(crate) fn builtin_template_functions() -> Vec<TemplateFunction> {
+    BUILTIN_TEMPLATE_FUNCTIONS.iter().map(BuiltinTemplateFunction::definition).collect()
+}
+
+/// Run the builtin with the given name, or return `None` if there isn't one
+pub fn run_builtin_template_function(fn_name: &str, args: &Args) -> Option<Result<String>> {
+    BUILTIN_TEMPLATE_FUNCTIONS.iter().find(|f| f.name == fn_name).map(|f| (f.run)(args))
+}
+
+fn random_int_args() -> Vec<TemplateFunctionArg> {
+    vec![
+        text_arg("min", "Minimum", Some("0")),
+        text_arg("max", "Maximum", Some("100")),
+    ]
+}
+
+fn random_int_run(args: &Args) -> Result<String> {
+    let min = int_arg(args, "min", 0)?;
+    let max = int_arg(args, "max", 100)?;
+    if min > max {
+        return Err(RenderError(format!("Minimum {min} is greater than maximum {max}")));
+    }
+    Ok(rand::rng().random_range(min..=max).to_string())
+}
+
+fn hash_sha256_args() -> Vec<TemplateFunctionArg> {
+    vec![
+        TemplateFunctionArg::FormInput(FormInput::Text(FormInputText {
+            multi_line: Some(true),
+            ..text_input("input", "Input", None)
+        })),
+        encoding_arg(&[("Hex", "hex"), ("Base64", "base64")]),
+    ]
+}
+
+fn hash_sha256_run(args: &Args) -> Result<String> {
+    // The plugin version names the argument `input`, but `value` reads better when typed by hand
+    let input = match args.get("input") {
+        Some(_) => str_arg(args, "input"),
+        None => str_arg(args, "value"),
+    };
+    let digest = Sha256::digest(input.as_bytes());
+    match str_arg(args, "encoding").as_str() {
+        "base64" => Ok(BASE64_STANDARD.encode(digest)),
+        _ => Ok(hex::encode(digest)),
+    }
+}
+
+fn base64_encode_args() -> Vec<TemplateFunctionArg> {
+    vec![
+        encoding_arg(&[("Base64", "base64"), ("Base64 URL-safe", "base64url")]),
+        TemplateFunctionArg::FormInput(FormInput::Text(FormInputText {
+            multi_line: Some(true),
+            ..text_input("value", "Plain Text", None)
+        })),
+    ]
+}
+
+fn base64_encode_run(args: &Args) -> Result<String> {
+    let value = str_arg(args, "value");
+    match str_arg(args, "encoding").as_str() {
+        "base64url" => Ok(BASE64_URL_SAFE_NO_PAD.encode(value)),
+        _ => Ok(BASE64_STANDARD.encode(value)),
+    }
+}
+
+fn base64_decode_args() -> Vec<TemplateFunctionArg> {
+    vec![TemplateFunctionArg::FormInput(FormInput::Text(
+        FormInputText { multi_line: Some(true), ..text_input("value", "Encoded Value", None) },
+    ))]
+}
+
+fn base64_decode_run(args: &Args) -> Result<String> {
+    // Accept both alphabets, with or without padding
+    let value = str_arg(args, "value").trim().replace('-', "+").replace('_', "/");
+    let decoded = BASE64_STANDARD_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|e| RenderError(format!("Invalid base64 value: {e}")))?;
+    Ok(String::from_utf8_lossy(&decoded).to_string())
+}
+
+fn fake_address_run(_args: &Args) -> Result<String> {
+    let number: String = BuildingNumber().fake();
+    let street: String = StreetName().fake();
+    let city: String = CityName().fake();
+    let state: String = StateAbbr().fake();
+    let zip: String = ZipCode().fake();
+    Ok(format!("{number} {street}, {city}, {state} {zip}"))
+}
+
+fn text_input(name: &str, label: &str, default_value: Option<&str>) -> FormInputText {
+    FormInputText {
+        base: FormInputBase {
+            name: name.to_string(),
+            label: Some(label.to_string()),
+            default_value: default_value.map(|v| v.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+fn text_arg(name: &str, label: &str, default_value: Option<&str>) -> TemplateFunctionArg {
+    TemplateFunctionArg::FormInput(FormInput::Text(text_input(name, label, default_value)))
+}
+
+fn encoding_arg(options: &[(&str, &str)]) -> TemplateFunctionArg {
+    TemplateFunctionArg::FormInput(FormInput::Select(FormInputSelect {
+        base: FormInputBase {
+            name: "encoding".to_string(),
+            label: Some("Encoding".to_string()),
+            default_value: options.first().map(|(_, value)| value.to_string()),
+            ..Default::default()
+        },
+        options: options
+            .iter()
+            .map(|(label, value)| FormInputSelectOption {
+                label: label.to_string(),
+                value: value.to_string(),
+            })
+            .collect(),
+    }))
+}
+
+fn str_arg(args: &Args, name: &str) -> String {
+    match args.get(name) {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Null) | None => "".to_string(),
+        Some(v) => v.to_string(),
+    }
+}
+
+fn int_arg(args: &Args, name: &str, default: i64) -> Result<i64> {
+    let value = str_arg(args, name);
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(default);
+    }
+    value
+        .parse()
+        .map_err(|_| RenderError(format!("Invalid {name} {value:?}, expected a whole number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(pairs: &[(&str, &str)]) -> Args {
+        pairs.iter().map(|(k, v)| (k.to_string(), Value::String(v.to_string()))).collect()
+    }
+
+    fn run(fn_name: &str, pairs: &[(&str, &str)]) -> Result<String> {
+        run_builtin_template_function(fn_name, &args(pairs)).expect("Expected a builtin")
+    }
+
+    #[test]
+    fn hashes_and_encodes() -> Result<()> {
+        let hello_sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        assert_eq!(run("hash.sha256", &[("value", "hello")])?, hello_sha256);
+        assert_eq!(run("hash.sha256", &[("input", "hello"), ("encoding", "hex")])?, hello_sha256);
+        assert_eq!(
+            run("hash.sha256", &[("input", "hello"), ("encoding", "base64")])?,
+            "LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ="
+        );
+
+        assert_eq!(run("base64.encode", &[("value", "hi?>")])?, "aGk/Pg==");
+        assert_eq!(
+            run("base64.encode", &[("value", "hi?>"), ("encoding", "base64url")])?,
+            "aGk_Pg"
+        );
+        assert_eq!(run("base64.decode", &[("value", "aGk/Pg==")])?, "hi?>");
+        assert_eq!(run("base64.decode", &[("value", "aGk_Pg")])?, "hi?>");
+        assert!(run("base64.decode", &[("value", "not base64!")]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn generates_values() -> Result<()> {
+        assert_eq!(run("uuid.v4", &[])?.len(), 36);
+        assert!(run("timestamp.iso", &[])?.ends_with('Z'));
+
+        assert_eq!(run("random.int", &[("min", "7"), ("max", "7")])?, "7");
+        let n: i64 = run("random.int", &[("min", "-3"), ("max", "3")])?.parse().unwrap();
+        assert!((-3..=3).contains(&n));
+        assert!(run("random.int", &[("min", "5"), ("max", "1")]).is_err());
+        assert!(run("random.int", &[("min", "one")]).is_err());
+
+        for name in ["fake.name", "fake.email", "fake.address", "fake.paragraph"] {
+            assert!(!run(name, &[])?.is_empty(), "{name} returned nothing");
+        }
+        assert!(run("fake.email", &[])?.contains('@'));
+
+        assert!(run_builtin_template_function("nope", &Args::new()).is_none());
+        Ok(())
+    }
+}
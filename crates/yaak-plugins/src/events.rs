@@ -2,8 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use ts_rs::TS;
 use yaak_models::models::{
-    AnyModel, Environment, Folder, GrpcRequest, HttpRequest, HttpResponse, WebsocketRequest,
-    Workspace,
+    AnyModel, CookieJar, Environment, Folder, GrpcRequest, HttpRequest, HttpResponse,
+    WebsocketRequest, Workspace,
 };
 use yaak_models::util::generate_prefixed_id;
 
@@ -124,6 +124,12 @@ pub enum InternalEventPayload {
     CallHttpAuthenticationActionRequest(CallHttpAuthenticationActionRequest),
     CallHttpAuthenticationActionResponse(EmptyPayload),
 
+    // Code Generation
+    GetCodegenLanguagesRequest(EmptyPayload),
+    GetCodegenLanguagesResponse(GetCodegenLanguagesResponse),
+    CallCodegenRequest(CallCodegenRequest),
+    CallCodegenResponse(CallCodegenResponse),
+
     CopyTextRequest(CopyTextRequest),
     CopyTextResponse(EmptyPayload),
 
@@ -800,6 +806,29 @@ pub struct CallHttpAuthenticationResponse {
     /// new params will be added.
     #[ts(optional)]
     pub set_query_parameters: Option<Vec<HttpHeader>>,
+
+    /// Token obtained while applying the authentication. The app stores it and refreshes it
+    /// once it expires, instead of calling the plugin again on every send.
+    #[ts(optional)]
+    pub token: Option<HttpAuthenticationToken>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct HttpAuthenticationToken {
+    pub access_token: String,
+    #[ts(optional)]
+    pub id_token: Option<String>,
+    #[ts(optional)]
+    pub refresh_token: Option<String>,
+    #[ts(optional)]
+    pub token_type: Option<String>,
+    /// Number of seconds until the access token expires
+    #[ts(optional)]
+    pub expires_in: Option<u32>,
+    #[ts(optional)]
+    pub scope: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
@@ -810,6 +839,37 @@ pub struct GetTemplateFunctionSummaryResponse {
     pub plugin_ref_id: String,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct GetCodegenLanguagesResponse {
+    pub languages: Vec<CodegenLanguage>,
+    pub plugin_ref_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CodegenLanguage {
+    pub name: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallCodegenRequest {
+    pub name: String,
+    pub http_request: HttpRequest,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(default, rename_all = "camelCase")]
+#[ts(export, export_to = "gen_events.ts")]
+pub struct CallCodegenResponse {
+    pub content: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
 #[serde(default, rename_all = "camelCase")]
 #[ts(export, export_to = "gen_events.ts")]
@@ -1478,6 +1538,7 @@ pub struct ImportResources {
     pub http_requests: Vec<HttpRequest>,
     pub grpc_requests: Vec<GrpcRequest>,
     pub websocket_requests: Vec<WebsocketRequest>,
+    pub cookie_jars: Vec<CookieJar>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
@@ -7,6 +7,7 @@
 //! by yaak-app's plugins_ext module.
 
 pub mod api;
+mod builtin_template_functions;
 mod checksum;
 pub mod error;
 pub mod events;
@@ -16,6 +17,7 @@ pub mod native_template_functions;
 mod nodejs;
 pub mod plugin_handle;
 pub mod plugin_meta;
+mod secret_providers;
 mod server_ws;
 pub mod template_callback;
 mod util;
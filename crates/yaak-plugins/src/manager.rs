@@ -1,24 +1,29 @@
+use crate::builtin_template_functions::builtin_template_functions;
 use crate::error::Error::{
     self, AuthPluginNotFound, ClientNotInitializedErr, PluginErr, PluginNotFoundErr,
     UnknownEventErr,
 };
 use crate::error::Result;
 use crate::events::{
-    BootRequest, CallFolderActionRequest, CallGrpcRequestActionRequest,
-    CallHttpAuthenticationActionArgs, CallHttpAuthenticationActionRequest,
-    CallHttpAuthenticationRequest, CallHttpAuthenticationResponse, CallHttpRequestActionRequest,
+    BootRequest, CallCodegenRequest, CallFolderActionRequest, CallGrpcRequestActionArgs,
+    CallGrpcRequestActionRequest, CallHttpAuthenticationActionArgs,
+    CallHttpAuthenticationActionRequest, CallHttpAuthenticationRequest,
+    CallHttpAuthenticationResponse, CallHttpRequestActionArgs, CallHttpRequestActionRequest,
     CallTemplateFunctionArgs, CallTemplateFunctionRequest, CallTemplateFunctionResponse,
     CallWebsocketRequestActionRequest, CallWorkspaceActionRequest, EmptyPayload, ErrorResponse,
-    FilterRequest, FilterResponse, GetFolderActionsResponse, GetGrpcRequestActionsResponse,
-    GetHttpAuthenticationConfigRequest, GetHttpAuthenticationConfigResponse,
-    GetHttpAuthenticationSummaryResponse, GetHttpRequestActionsResponse,
-    GetTemplateFunctionConfigRequest, GetTemplateFunctionConfigResponse,
-    GetTemplateFunctionSummaryResponse, GetThemesRequest, GetThemesResponse,
-    GetWebsocketRequestActionsResponse, GetWorkspaceActionsResponse, ImportRequest, ImportResponse,
-    InternalEvent, InternalEventPayload, JsonPrimitive, PluginContext, RenderPurpose,
-    ShowToastRequest,
+    FilterRequest, FilterResponse, GetCodegenLanguagesResponse, GetFolderActionsResponse,
+    GetGrpcRequestActionsResponse, GetHttpAuthenticationConfigRequest,
+    GetHttpAuthenticationConfigResponse, GetHttpAuthenticationSummaryResponse,
+    GetHttpRequestActionsResponse, GetTemplateFunctionConfigRequest,
+    GetTemplateFunctionConfigResponse, GetTemplateFunctionSummaryResponse, GetThemesRequest,
+    GetThemesResponse, GetWebsocketRequestActionsResponse, GetWorkspaceActionsResponse,
+    ImportRequest, ImportResponse, InternalEvent, InternalEventPayload, JsonPrimitive,
+    PluginContext, PromptTextRequest, RenderPurpose, ShowToastRequest,
+};
+use crate::native_template_functions::{
+    template_function_keyring, template_function_prompt, template_function_secret,
+    template_function_secure,
 };
-use crate::native_template_functions::{template_function_keyring, template_function_secure};
 use crate::nodejs::start_nodejs_plugin_runtime;
 use crate::plugin_handle::PluginHandle;
 use crate::plugin_meta::get_plugin_meta;
@@ -26,7 +31,9 @@ use crate::server_ws::PluginRuntimeServerWebsocket;
 use log::{error, info, warn};
 use std::collections::{HashMap, HashSet};
 use std::env;
+use std::future::Future;
 use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::fs::read_dir;
@@ -34,7 +41,7 @@ use tokio::net::TcpListener;
 use tokio::sync::mpsc::error::TrySendError;
 use tokio::sync::{Mutex, mpsc, oneshot};
 use tokio::time::{Instant, timeout};
-use yaak_models::models::{Plugin, PluginSource};
+use yaak_models::models::{GrpcRequest, HttpRequest, Plugin, PluginSource};
 use yaak_models::query_manager::QueryManager;
 use yaak_models::util::{UpdateSource, generate_id};
 use yaak_templates::error::Error::RenderError;
@@ -52,11 +59,20 @@ pub struct PluginManager {
     dev_mode: bool,
     /// Errors from plugin initialization, retrievable once via `take_init_errors`.
     init_errors: Arc<Mutex<Vec<(String, String)>>>,
+    /// Asks the user for a value on behalf of native template functions, set by the host
+    prompt_text_handler: Arc<Mutex<Option<PromptTextHandler>>>,
 }
 
 /// Callback for plugin initialization events (e.g., toast notifications)
 pub type PluginInitCallback = Box<dyn Fn(ShowToastRequest) + Send + Sync>;
 
+pub type PromptTextFuture =
+    Pin<Box<dyn Future<Output = std::result::Result<Option<String>, String>> + Send>>;
+
+/// Callback that shows a text prompt and resolves to the entered value, or `None` if cancelled
+pub type PromptTextHandler =
+    Arc<dyn Fn(PluginContext, PromptTextRequest) -> PromptTextFuture + Send + Sync>;
+
 impl PluginManager {
     /// Create a new PluginManager with the given paths.
     ///
@@ -96,6 +112,7 @@ impl PluginManager {
             installed_plugin_dir,
             dev_mode,
             init_errors: Default::default(),
+            prompt_text_handler: Default::default(),
         };
 
         // Forward events to subscribers
@@ -413,6 +430,23 @@ impl PluginManager {
         }
     }
 
+    pub async fn set_prompt_text_handler(&self, handler: PromptTextHandler) {
+        *self.prompt_text_handler.lock().await = Some(handler);
+    }
+
+    /// Prompt the user for text through the host's handler
+    pub(crate) async fn prompt_text(
+        &self,
+        plugin_context: &PluginContext,
+        req: PromptTextRequest,
+    ) -> TemplateResult<Option<String>> {
+        let handler = self.prompt_text_handler.lock().await.clone();
+        let Some(handler) = handler else {
+            return Err(RenderError("Prompting for values is not supported here".to_string()));
+        };
+        handler(plugin_context.clone(), req).await.map_err(RenderError)
+    }
+
     pub async fn reply(
         &self,
         source_event: &InternalEvent,
@@ -772,6 +806,54 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Run a plugin's HTTP request action by its label and wait for it to finish, for callers
+    /// that need the action's side effects before continuing (eg. pre-request steps)
+    pub async fn run_http_request_action(
+        &self,
+        plugin_context: &PluginContext,
+        plugin_name: &str,
+        label: &str,
+        http_request: HttpRequest,
+    ) -> Result<()> {
+        let plugin = self
+            .get_plugin_by_name(plugin_name)
+            .await
+            .ok_or(PluginNotFoundErr(plugin_name.to_string()))?;
+        let event = self
+            .send_to_plugin_and_wait(
+                plugin_context,
+                &plugin,
+                &InternalEventPayload::GetHttpRequestActionsRequest(EmptyPayload {}),
+                Duration::from_secs(5),
+            )
+            .await?;
+        let index = match event.payload {
+            InternalEventPayload::GetHttpRequestActionsResponse(resp) => {
+                resp.actions.iter().position(|a| a.label == label)
+            }
+            _ => None,
+        }
+        .ok_or(PluginErr(format!("Plugin {plugin_name} has no request action {label}")))?;
+
+        let req = CallHttpRequestActionRequest {
+            index: index as i32,
+            plugin_ref_id: plugin.ref_id.clone(),
+            args: CallHttpRequestActionArgs { http_request },
+        };
+        let event = self
+            .send_to_plugin_and_wait(
+                plugin_context,
+                &plugin,
+                &InternalEventPayload::CallHttpRequestActionRequest(req),
+                Duration::from_secs(300), // 5 minutes for user interactions (OAuth, prompts, etc.)
+            )
+            .await?;
+        match event.payload {
+            InternalEventPayload::ErrorResponse(e) => Err(PluginErr(e.error)),
+            _ => Ok(()),
+        }
+    }
+
     pub async fn call_websocket_request_action(
         &self,
         plugin_context: &PluginContext,
@@ -840,6 +922,53 @@ impl PluginManager {
         Ok(())
     }
 
+    /// Run a plugin's gRPC request action by its label and wait for it to finish
+    pub async fn run_grpc_request_action(
+        &self,
+        plugin_context: &PluginContext,
+        plugin_name: &str,
+        label: &str,
+        grpc_request: GrpcRequest,
+    ) -> Result<()> {
+        let plugin = self
+            .get_plugin_by_name(plugin_name)
+            .await
+            .ok_or(PluginNotFoundErr(plugin_name.to_string()))?;
+        let event = self
+            .send_to_plugin_and_wait(
+                plugin_context,
+                &plugin,
+                &InternalEventPayload::GetGrpcRequestActionsRequest(EmptyPayload {}),
+                Duration::from_secs(5),
+            )
+            .await?;
+        let index = match event.payload {
+            InternalEventPayload::GetGrpcRequestActionsResponse(resp) => {
+                resp.actions.iter().position(|a| a.label == label)
+            }
+            _ => None,
+        }
+        .ok_or(PluginErr(format!("Plugin {plugin_name} has no request action {label}")))?;
+
+        let req = CallGrpcRequestActionRequest {
+            index: index as i32,
+            plugin_ref_id: plugin.ref_id.clone(),
+            args: CallGrpcRequestActionArgs { grpc_request, proto_files: Vec::new() },
+        };
+        let event = self
+            .send_to_plugin_and_wait(
+                plugin_context,
+                &plugin,
+                &InternalEventPayload::CallGrpcRequestActionRequest(req),
+                Duration::from_secs(300), // 5 minutes for user interactions (OAuth, prompts, etc.)
+            )
+            .await?;
+        match event.payload {
+            InternalEventPayload::ErrorResponse(e) => Err(PluginErr(e.error)),
+            _ => Ok(()),
+        }
+    }
+
     pub async fn get_http_authentication_summaries(
         &self,
         plugin_context: &PluginContext,
@@ -961,6 +1090,7 @@ impl PluginManager {
             return Ok(CallHttpAuthenticationResponse {
                 set_headers: None,
                 set_query_parameters: None,
+                token: None,
             });
         }
 
@@ -1007,10 +1137,22 @@ impl PluginManager {
             }
         }
 
-        // Add Rust-based functions
+        // Add Rust-based functions. Builtins replace plugin functions with the same name when
+        // rendering, so drop the plugin's copy to avoid listing it twice.
+        let builtins = builtin_template_functions();
+        for result in results.iter_mut() {
+            result.functions.retain(|f| !builtins.iter().any(|b| b.name == f.name));
+        }
+        let mut functions = vec![
+            template_function_secure(),
+            template_function_keyring(),
+            template_function_secret(),
+            template_function_prompt(),
+        ];
+        functions.extend(builtins);
         results.push(GetTemplateFunctionSummaryResponse {
             plugin_ref_id: "__NATIVE__".to_string(), // Meh
-            functions: vec![template_function_secure(), template_function_keyring()],
+            functions,
         });
 
         Ok(results)
@@ -1059,6 +1201,57 @@ impl PluginManager {
         }
     }
 
+    pub async fn get_codegen_languages(
+        &self,
+        plugin_context: &PluginContext,
+    ) -> Result<Vec<GetCodegenLanguagesResponse>> {
+        let reply_events = self
+            .send_and_wait(
+                plugin_context,
+                &InternalEventPayload::GetCodegenLanguagesRequest(EmptyPayload {}),
+                Duration::from_secs(5),
+            )
+            .await?;
+
+        let mut results = Vec::new();
+        for event in reply_events {
+            if let InternalEventPayload::GetCodegenLanguagesResponse(resp) = event.payload {
+                results.push(resp);
+            }
+        }
+
+        Ok(results)
+    }
+
+    /// Generate a code snippet with the plugin that provides the language, returning `None` if no
+    /// plugin does
+    pub async fn call_codegen(
+        &self,
+        plugin_context: &PluginContext,
+        name: &str,
+        http_request: HttpRequest,
+    ) -> Result<Option<String>> {
+        let req = CallCodegenRequest { name: name.to_string(), http_request };
+        let events = self
+            .send_and_wait(
+                plugin_context,
+                &InternalEventPayload::CallCodegenRequest(req),
+                Duration::from_secs(5),
+            )
+            .await?;
+
+        events
+            .into_iter()
+            .find_map(|e| match e.payload {
+                InternalEventPayload::CallCodegenResponse(resp) => Some(Ok(resp.content)),
+                InternalEventPayload::ErrorResponse(ErrorResponse { error }) => {
+                    Some(Err(PluginErr(error)))
+                }
+                _ => None,
+            })
+            .transpose()
+    }
+
     pub async fn import_data(
         &self,
         plugin_context: &PluginContext,
@@ -3,13 +3,16 @@
 //! These are built-in template functions that don't require plugins:
 //! - `secure()` - encrypts/decrypts values using the EncryptionManager
 //! - `keychain()` / `keyring()` - accesses system keychain
+//! - `secret()` - fetches a secret from an external provider like HashiCorp Vault
+//! - `prompt()` - asks the user for a value when the request is sent
 
 use crate::events::{
     Color, FormInput, FormInputBanner, FormInputBase, FormInputMarkdown, FormInputText,
-    PluginContext, RenderPurpose, TemplateFunction, TemplateFunctionArg,
+    PluginContext, PromptTextRequest, RenderPurpose, TemplateFunction, TemplateFunctionArg,
     TemplateFunctionPreviewType,
 };
 use crate::manager::PluginManager;
+use crate::secret_providers::resolve_secret;
 use crate::template_callback::PluginTemplateCallback;
 use base64::Engine;
 use base64::prelude::BASE64_STANDARD;
@@ -246,6 +249,60 @@ pub fn encrypt_secure_template_function(
     .to_string())
 }
 
+pub(crate) fn template_function_secret() -> TemplateFunction {
+    TemplateFunction {
+        name: "secret".to_string(),
+        preview_type: Some(TemplateFunctionPreviewType::None),
+        description: Some(
+            "Fetch a secret from HashiCorp Vault or AWS Secrets Manager when the request is sent"
+                .to_string(),
+        ),
+        aliases: None,
+        preview_args: None,
+        args: vec![
+            TemplateFunctionArg::FormInput(FormInput::Banner(FormInputBanner {
+                inputs: Some(vec![FormInput::Markdown(FormInputMarkdown {
+                    content: "Vault is configured with `VAULT_ADDR`, `VAULT_TOKEN`, and `VAULT_NAMESPACE`, and AWS with `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`, `AWS_SESSION_TOKEN`, and `AWS_REGION`".to_string(),
+                    hidden: None,
+                })]),
+                color: Some(Color::Info),
+                hidden: None,
+            })),
+            TemplateFunctionArg::FormInput(FormInput::Text(FormInputText {
+                base: FormInputBase {
+                    name: "reference".to_string(),
+                    label: Some("Reference".to_string()),
+                    description: Some(
+                        "Provider, path, and optional JSON field, like `vault:secret/data/app#password` or `aws:prod/db#password`".to_string(),
+                    ),
+                    ..Default::default()
+                },
+                placeholder: Some("vault:secret/data/app#password".to_string()),
+                ..Default::default()
+            })),
+        ],
+    }
+}
+
+pub async fn template_function_secret_run(
+    plugin_manager: &PluginManager,
+    args: HashMap<String, serde_json::Value>,
+    plugin_context: &PluginContext,
+    render_purpose: &RenderPurpose,
+) -> Result<String> {
+    let reference = args.get("reference").and_then(|v| v.as_str()).unwrap_or_default();
+    if reference.trim().is_empty() {
+        return Ok("".to_string());
+    }
+
+    // Only fetch the secret when sending, so previews never reach out to the provider or show
+    // the value
+    if !matches!(render_purpose, RenderPurpose::Send) {
+        return Ok(format!("<secret {}>", reference.trim()));
+    }
+    resolve_secret(plugin_manager, plugin_context, reference).await
+}
+
 pub fn template_function_keychain_run(args: HashMap<String, serde_json::Value>) -> Result<String> {
     let service = args.get("service").and_then(|v| v.as_str()).unwrap_or_default().to_owned();
     let user = args.get("account").and_then(|v| v.as_str()).unwrap_or_default().to_owned();
@@ -267,3 +324,63 @@ pub fn template_function_keychain_run(args: HashMap<String, serde_json::Value>)
         Err(e) => Err(RenderError(e.to_string())),
     }
 }
+
+pub(crate) fn template_function_prompt() -> TemplateFunction {
+    TemplateFunction {
+        name: "prompt".to_string(),
+        preview_type: Some(TemplateFunctionPreviewType::Live),
+        description: Some("Ask for a value each time the request is sent".to_string()),
+        aliases: None,
+        preview_args: Some(vec!["label".to_string()]),
+        args: vec![
+            TemplateFunctionArg::FormInput(FormInput::Text(FormInputText {
+                base: FormInputBase {
+                    name: "label".to_string(),
+                    label: Some("Label".to_string()),
+                    description: Some("What to ask for, like \"OTP Code\"".to_string()),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })),
+            TemplateFunctionArg::FormInput(FormInput::Text(FormInputText {
+                base: FormInputBase {
+                    name: "default".to_string(),
+                    label: Some("Default Value".to_string()),
+                    description: Some("Pre-filled in the prompt and used for previews".to_string()),
+                    optional: Some(true),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })),
+        ],
+    }
+}
+
+pub async fn template_function_prompt_run(
+    plugin_manager: &PluginManager,
+    args: HashMap<String, serde_json::Value>,
+    plugin_context: &PluginContext,
+    render_purpose: &RenderPurpose,
+) -> Result<String> {
+    let label = args.get("label").and_then(|v| v.as_str()).unwrap_or_default();
+    let default = args.get("default").and_then(|v| v.as_str()).unwrap_or_default();
+
+    // Only interrupt the user when actually sending
+    if !matches!(render_purpose, RenderPurpose::Send) {
+        return Ok(default.to_string());
+    }
+
+    let label = if label.is_empty() { "Value" } else { label };
+    let req = PromptTextRequest {
+        id: format!("prompt-{label}"),
+        title: "Enter Value".to_string(),
+        label: label.to_string(),
+        default_value: Some(default.to_string()),
+        required: Some(false),
+        ..Default::default()
+    };
+    match plugin_manager.prompt_text(plugin_context, req).await? {
+        Some(value) => Ok(value),
+        None => Err(RenderError("Prompt cancelled".to_string())),
+    }
+}
@@ -0,0 +1,315 @@
+//! External secret providers for the `secret()` template function.
+//!
+//! A reference like `vault:secret/data/app#password` names the provider, the path of the secret,
+//! and optionally a field to pick out of a JSON secret. Secrets are fetched every time a request
+//! is sent and are never stored.
+
+use crate::events::{CallHttpAuthenticationRequest, HttpHeader, JsonPrimitive, PluginContext};
+use crate::manager::PluginManager;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use yaak_templates::error::Error::RenderError;
+use yaak_templates::error::Result;
+
+/// Authentication plugin that signs requests to AWS
+const AWS_SIGNATURE_AUTHENTICATION: &str = "awsv4";
+
+/// A service that secrets can be fetched from
+pub(crate) trait SecretProvider {
+    /// Fetch the secret at a path, returning its raw value
+    async fn fetch_secret(&self, path: &str) -> Result<String>;
+}
+
+#[derive(Debug, PartialEq)]
+struct SecretReference<'a> {
+    provider: &'a str,
+    path: &'a str,
+    field: Option<&'a str>,
+}
+
+fn parse_reference(reference: &str) -> Result<SecretReference<'_>> {
+    let invalid = || {
+        RenderError(format!("Invalid secret reference '{reference}', expected provider:path#field"))
+    };
+    let (provider, rest) = reference.trim().split_once(':').ok_or_else(invalid)?;
+    let (path, field) = match rest.rsplit_once('#') {
+        Some((path, field)) => (path, Some(field)),
+        None => (rest, None),
+    };
+    if provider.is_empty() || path.is_empty() {
+        return Err(invalid());
+    }
+    Ok(SecretReference { provider, path, field })
+}
+
+/// Fetch the secret a reference points to
+pub(crate) async fn resolve_secret(
+    plugin_manager: &PluginManager,
+    plugin_context: &PluginContext,
+    reference: &str,
+) -> Result<String> {
+    let reference = parse_reference(reference)?;
+    let value = match reference.provider {
+        "vault" => VaultProvider::from_env()?.fetch_secret(reference.path).await?,
+        "aws" => {
+            AwsSecretsManagerProvider::from_env(plugin_manager, plugin_context)?
+                .fetch_secret(reference.path)
+                .await?
+        }
+        p => {
+            return Err(RenderError(format!(
+                "Unknown secret provider '{p}', expected 'vault' or 'aws'"
+            )));
+        }
+    };
+    select_field(&value, reference.field)
+}
+
+/// Pick a field out of a secret holding a JSON object, like the key/value pairs of a Vault secret
+fn select_field(value: &str, field: Option<&str>) -> Result<String> {
+    let Some(field) = field else {
+        return Ok(value.to_string());
+    };
+    let object: Value = serde_json::from_str(value).map_err(|_| {
+        RenderError(format!("Secret is not JSON, so field '{field}' can't be read"))
+    })?;
+    match object.get(field) {
+        Some(Value::String(s)) => Ok(s.clone()),
+        Some(v) => Ok(v.to_string()),
+        None => Err(RenderError(format!("Secret has no field '{field}'"))),
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// HashiCorp Vault, configured with the same `VAULT_ADDR`, `VAULT_TOKEN`, and `VAULT_NAMESPACE`
+/// environment variables as the Vault CLI
+struct VaultProvider {
+    address: String,
+    token: String,
+    namespace: Option<String>,
+}
+
+impl VaultProvider {
+    fn from_env() -> Result<Self> {
+        Ok(Self {
+            address: env_var("VAULT_ADDR").unwrap_or_else(|| "http://127.0.0.1:8200".to_string()),
+            token: env_var("VAULT_TOKEN")
+                .ok_or(RenderError("VAULT_TOKEN must be set to read Vault secrets".to_string()))?,
+            namespace: env_var("VAULT_NAMESPACE"),
+        })
+    }
+}
+
+impl SecretProvider for VaultProvider {
+    async fn fetch_secret(&self, path: &str) -> Result<String> {
+        let url = format!("{}/v1/{}", self.address.trim_end_matches('/'), path.trim_matches('/'));
+        let mut request = reqwest::Client::new().get(&url).header("X-Vault-Token", &self.token);
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| RenderError(format!("Failed to fetch Vault secret {path}: {e}")))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(RenderError(format!("Vault returned {status} for secret {path}")));
+        }
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| RenderError(format!("Failed to parse Vault secret {path}: {e}")))?;
+        Ok(vault_secret_data(&body).to_string())
+    }
+}
+
+/// The key/value pairs of a Vault response. KV version 2 nests them under `data.data`, next to
+/// the secret's metadata.
+fn vault_secret_data(body: &Value) -> &Value {
+    let data = &body["data"];
+    match (data.get("data"), data.get("metadata")) {
+        (Some(inner), Some(_)) => inner,
+        _ => data,
+    }
+}
+
+struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+/// AWS Secrets Manager, configured with the standard `AWS_ACCESS_KEY_ID`,
+/// `AWS_SECRET_ACCESS_KEY`, `AWS_SESSION_TOKEN`, and `AWS_REGION` environment variables. Requests
+/// are signed by the AWS Signature authentication plugin.
+struct AwsSecretsManagerProvider<'a> {
+    credentials: AwsCredentials,
+    region: Option<String>,
+    plugin_manager: &'a PluginManager,
+    plugin_context: &'a PluginContext,
+}
+
+impl<'a> AwsSecretsManagerProvider<'a> {
+    fn from_env(
+        plugin_manager: &'a PluginManager,
+        plugin_context: &'a PluginContext,
+    ) -> Result<Self> {
+        let missing = || {
+            RenderError(
+                "AWS_ACCESS_KEY_ID and AWS_SECRET_ACCESS_KEY must be set to read AWS secrets"
+                    .to_string(),
+            )
+        };
+        Ok(Self {
+            credentials: AwsCredentials {
+                access_key_id: env_var("AWS_ACCESS_KEY_ID").ok_or_else(missing)?,
+                secret_access_key: env_var("AWS_SECRET_ACCESS_KEY").ok_or_else(missing)?,
+                session_token: env_var("AWS_SESSION_TOKEN"),
+            },
+            region: env_var("AWS_REGION").or_else(|| env_var("AWS_DEFAULT_REGION")),
+            plugin_manager,
+            plugin_context,
+        })
+    }
+
+    /// Headers that sign a request, from the AWS Signature authentication plugin
+    async fn sign(
+        &self,
+        region: &str,
+        url: &str,
+        headers: Vec<HttpHeader>,
+    ) -> Result<Vec<HttpHeader>> {
+        let credentials = &self.credentials;
+        let mut values: HashMap<String, JsonPrimitive> = [
+            ("accessKeyId", credentials.access_key_id.as_str()),
+            ("secretAccessKey", credentials.secret_access_key.as_str()),
+            ("service", "secretsmanager"),
+            ("region", region),
+            ("signatureLocation", "headers"),
+        ]
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), JsonPrimitive::String(value.to_string())))
+        .collect();
+        if let Some(token) = &credentials.session_token {
+            values.insert("sessionToken".to_string(), JsonPrimitive::String(token.clone()));
+        }
+        let response = self
+            .plugin_manager
+            .call_http_authentication(
+                self.plugin_context,
+                AWS_SIGNATURE_AUTHENTICATION,
+                CallHttpAuthenticationRequest {
+                    context_id: AWS_SIGNATURE_AUTHENTICATION.to_string(),
+                    values,
+                    method: "POST".to_string(),
+                    url: url.to_string(),
+                    headers,
+                },
+            )
+            .await
+            .map_err(|e| RenderError(format!("Failed to sign AWS request: {e}")))?;
+        Ok(response.set_headers.unwrap_or_default())
+    }
+}
+
+impl SecretProvider for AwsSecretsManagerProvider<'_> {
+    async fn fetch_secret(&self, path: &str) -> Result<String> {
+        // ARNs include the region, which takes precedence over the configured one
+        let region = match path.strip_prefix("arn:") {
+            Some(arn) => arn.split(':').nth(2).map(|r| r.to_string()),
+            None => self.region.clone(),
+        }
+        .ok_or(RenderError("AWS_REGION must be set to read AWS secrets".to_string()))?;
+
+        let url = format!("https://secretsmanager.{region}.amazonaws.com/");
+        let body = json!({ "SecretId": path }).to_string();
+        // The plugin doesn't see the body, so it signs the body's hash from this header instead
+        let header = |name: &str, value: String| HttpHeader { name: name.to_string(), value };
+        let mut headers = vec![
+            header("content-type", "application/x-amz-json-1.1".to_string()),
+            header("x-amz-target", "secretsmanager.GetSecretValue".to_string()),
+            header("x-amz-content-sha256", hex::encode(Sha256::digest(body.as_bytes()))),
+        ];
+        let signature = self.sign(&region, &url, headers.clone()).await?;
+        for signed in signature {
+            headers.retain(|h| !h.name.eq_ignore_ascii_case(&signed.name));
+            headers.push(signed);
+        }
+
+        let mut request = reqwest::Client::new().post(&url).body(body);
+        for h in headers.iter().filter(|h| !h.name.eq_ignore_ascii_case("host")) {
+            request = request.header(&h.name, &h.value);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| RenderError(format!("Failed to fetch AWS secret {path}: {e}")))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| RenderError(format!("Failed to parse AWS secret {path}: {e}")))?;
+        if !status.is_success() {
+            let message = body["message"].as_str().or(body["Message"].as_str()).unwrap_or("");
+            return Err(RenderError(format!("AWS returned {status} for secret {path}: {message}")));
+        }
+
+        if let Some(secret) = body["SecretString"].as_str() {
+            return Ok(secret.to_string());
+        }
+        let binary = body["SecretBinary"].as_str().unwrap_or_default();
+        let bytes = BASE64_STANDARD
+            .decode(binary)
+            .map_err(|e| RenderError(format!("Failed to decode AWS secret {path}: {e}")))?;
+        String::from_utf8(bytes).map_err(|e| RenderError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_references() {
+        assert_eq!(
+            parse_reference("vault:secret/data/app#password").unwrap(),
+            SecretReference { provider: "vault", path: "secret/data/app", field: Some("password") }
+        );
+        assert_eq!(
+            parse_reference("aws:arn:aws:secretsmanager:eu-west-1:123:secret:db").unwrap(),
+            SecretReference {
+                provider: "aws",
+                path: "arn:aws:secretsmanager:eu-west-1:123:secret:db",
+                field: None,
+            }
+        );
+        assert!(parse_reference("secret/data/app").is_err());
+        assert!(parse_reference("vault:").is_err());
+    }
+
+    #[test]
+    fn selects_fields_of_json_secrets() {
+        let secret = r#"{"username":"admin","port":5432}"#;
+        assert_eq!(select_field(secret, None).unwrap(), secret);
+        assert_eq!(select_field(secret, Some("username")).unwrap(), "admin");
+        assert_eq!(select_field(secret, Some("port")).unwrap(), "5432");
+        assert!(select_field(secret, Some("password")).is_err());
+        assert!(select_field("plain", Some("username")).is_err());
+    }
+
+    #[test]
+    fn reads_vault_kv_v1_and_v2_data() {
+        let v1 = json!({ "data": { "password": "a" } });
+        let v2 = json!({ "data": { "data": { "password": "b" }, "metadata": { "version": 3 } } });
+        assert_eq!(vault_secret_data(&v1), &json!({ "password": "a" }));
+        assert_eq!(vault_secret_data(&v2), &json!({ "password": "b" }));
+    }
+}
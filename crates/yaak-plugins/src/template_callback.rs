@@ -3,11 +3,12 @@
 //! This provides a TemplateCallback implementation that delegates to plugins
 //! for template function execution.
 
+use crate::builtin_template_functions::run_builtin_template_function;
 use crate::events::{JsonPrimitive, PluginContext, RenderPurpose};
 use crate::manager::PluginManager;
 use crate::native_template_functions::{
-    template_function_keychain_run, template_function_secure_run,
-    template_function_secure_transform_arg,
+    template_function_keychain_run, template_function_prompt_run, template_function_secret_run,
+    template_function_secure_run, template_function_secure_transform_arg,
 };
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -53,6 +54,26 @@ impl TemplateCallback for PluginTemplateCallback {
             );
         } else if fn_name == "keychain" || fn_name == "keyring" {
             return template_function_keychain_run(args);
+        } else if fn_name == "secret" {
+            return template_function_secret_run(
+                &self.plugin_manager,
+                args,
+                &self.plugin_context,
+                &self.render_purpose,
+            )
+            .await;
+        } else if fn_name == "prompt" {
+            return template_function_prompt_run(
+                &self.plugin_manager,
+                args,
+                &self.plugin_context,
+                &self.render_purpose,
+            )
+            .await;
+        }
+
+        if let Some(result) = run_builtin_template_function(fn_name, &args) {
+            return result;
         }
 
         let mut primitive_args = HashMap::new();
@@ -0,0 +1,21 @@
+import { invoke } from "@tauri-apps/api/core";
+import { SocketResponse } from "@yaakapp-internal/models";
+
+export function deleteSocketResponses(requestId: string) {
+  return invoke("cmd_socket_delete_responses", {
+    requestId,
+  });
+}
+
+export function sendSocket({
+  requestId,
+  environmentId,
+}: {
+  requestId: string;
+  environmentId: string | null;
+}) {
+  return invoke("cmd_socket_send", {
+    requestId,
+    environmentId,
+  }) as Promise<SocketResponse>;
+}
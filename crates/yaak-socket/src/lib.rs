@@ -0,0 +1,8 @@
+pub mod error;
+mod payload;
+pub mod render;
+pub mod send;
+
+pub use payload::socket_payload;
+pub use render::render_socket_request;
+pub use send::{SocketExchange, SocketSendOptions, send_socket};
@@ -0,0 +1,29 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use yaak_models::models::SocketPayloadFormat;
+
+/// Turn a rendered payload into the bytes written to the socket
+pub fn socket_payload(format: SocketPayloadFormat, payload: &str) -> Result<Vec<u8>> {
+    match format {
+        SocketPayloadFormat::Text => Ok(payload.as_bytes().to_vec()),
+        SocketPayloadFormat::Hex => {
+            let hex = payload.split_whitespace().collect::<String>();
+            hex::decode(hex).map_err(|e| GenericError(format!("Invalid hex payload: {e}")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_payloads() {
+        assert_eq!(socket_payload(SocketPayloadFormat::Text, "PING\r\n").unwrap(), b"PING\r\n");
+        assert_eq!(
+            socket_payload(SocketPayloadFormat::Hex, "de ad\nBE EF").unwrap(),
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(socket_payload(SocketPayloadFormat::Hex, "abc").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use crate::error::Result;
+use yaak_models::models::{Environment, SocketRequest};
+use yaak_models::render::make_vars_hashmap;
+use yaak_templates::{RenderOptions, TemplateCallback, parse_and_render};
+
+pub async fn render_socket_request<T: TemplateCallback>(
+    r: &SocketRequest,
+    environment_chain: Vec<Environment>,
+    cb: &T,
+    opt: &RenderOptions,
+) -> Result<SocketRequest> {
+    let vars = &make_vars_hashmap(environment_chain);
+
+    Ok(SocketRequest {
+        url: parse_and_render(&r.url, vars, cb, opt).await?,
+        payload: parse_and_render(&r.payload, vars, cb, opt).await?,
+        ..r.to_owned()
+    })
+}
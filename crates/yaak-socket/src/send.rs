@@ -0,0 +1,175 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use log::debug;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket, lookup_host};
+use tokio::time::timeout;
+use yaak_models::models::SocketProtocol;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_READ_TIMEOUT: Duration = Duration::from_millis(1000);
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+pub struct SocketSendOptions {
+    pub protocol: SocketProtocol,
+    /// Address to send to, as `host:port`
+    pub url: String,
+    pub payload: Vec<u8>,
+    /// How long to wait for more data before finishing, in milliseconds. Zero uses the default.
+    pub read_timeout: i32,
+}
+
+/// Everything recorded about a single send and the data read back
+#[derive(Debug, Default)]
+pub struct SocketExchange {
+    pub remote_addr: Option<String>,
+    pub elapsed_connect: i32,
+    pub elapsed_first_byte: Option<i32>,
+    pub elapsed: i32,
+    pub bytes_sent: i32,
+    pub body: Vec<u8>,
+    /// Error hit while reading, after the payload was already sent
+    pub error: Option<String>,
+}
+
+/// Send a payload and collect whatever comes back until the peer closes the connection, goes
+/// quiet for the read timeout, or the body grows too large.
+pub async fn send_socket(opts: SocketSendOptions) -> Result<SocketExchange> {
+    let url = opts.url.trim();
+    if url.is_empty() {
+        return Err(GenericError("Missing address, expected host:port".to_string()));
+    }
+
+    let addr = lookup_host(url)
+        .await
+        .map_err(|e| GenericError(format!("Failed to resolve {url}: {e}")))?
+        .next()
+        .ok_or_else(|| GenericError(format!("No addresses found for {url}")))?;
+
+    let read_timeout = match opts.read_timeout > 0 {
+        true => Duration::from_millis(opts.read_timeout as u64),
+        false => DEFAULT_READ_TIMEOUT,
+    };
+
+    debug!("Sending {} bytes to {addr} over {:?}", opts.payload.len(), opts.protocol);
+    match opts.protocol {
+        SocketProtocol::Tcp => send_tcp(addr, &opts.payload, read_timeout).await,
+        SocketProtocol::Udp => send_udp(addr, &opts.payload, read_timeout).await,
+    }
+}
+
+async fn send_tcp(
+    addr: SocketAddr,
+    payload: &[u8],
+    read_timeout: Duration,
+) -> Result<SocketExchange> {
+    let start = Instant::now();
+    let mut stream = timeout(CONNECT_TIMEOUT, TcpStream::connect(addr))
+        .await
+        .map_err(|_| GenericError(format!("Timed out connecting to {addr}")))??;
+
+    let mut exchange = SocketExchange {
+        remote_addr: Some(addr.to_string()),
+        elapsed_connect: start.elapsed().as_millis() as i32,
+        ..Default::default()
+    };
+
+    stream.write_all(payload).await?;
+    exchange.bytes_sent = payload.len() as i32;
+
+    let mut buf = [0u8; 8192];
+    while exchange.body.len() < MAX_BODY_SIZE {
+        let n = match timeout(read_timeout, stream.read(&mut buf)).await {
+            Err(_) => break,
+            Ok(Ok(0)) => break,
+            Ok(Ok(n)) => n,
+            Ok(Err(e)) => {
+                exchange.error = Some(e.to_string());
+                break;
+            }
+        };
+        if exchange.elapsed_first_byte.is_none() {
+            exchange.elapsed_first_byte = Some(start.elapsed().as_millis() as i32);
+        }
+        exchange.body.extend_from_slice(&buf[..n]);
+    }
+
+    exchange.elapsed = start.elapsed().as_millis() as i32;
+    Ok(exchange)
+}
+
+async fn send_udp(
+    addr: SocketAddr,
+    payload: &[u8],
+    read_timeout: Duration,
+) -> Result<SocketExchange> {
+    let start = Instant::now();
+    let bind_addr = match addr {
+        SocketAddr::V4(_) => "0.0.0.0:0",
+        SocketAddr::V6(_) => "[::]:0",
+    };
+    let socket = UdpSocket::bind(bind_addr).await?;
+    socket.connect(addr).await?;
+
+    let mut exchange = SocketExchange {
+        remote_addr: Some(addr.to_string()),
+        elapsed_connect: start.elapsed().as_millis() as i32,
+        ..Default::default()
+    };
+
+    exchange.bytes_sent = socket.send(payload).await? as i32;
+
+    // Large enough for any single datagram
+    let mut buf = vec![0u8; 65536];
+    while exchange.body.len() < MAX_BODY_SIZE {
+        let n = match timeout(read_timeout, socket.recv(&mut buf)).await {
+            Err(_) => break,
+            Ok(Ok(n)) => n,
+            Ok(Err(e)) => {
+                exchange.error = Some(e.to_string());
+                break;
+            }
+        };
+        if exchange.elapsed_first_byte.is_none() {
+            exchange.elapsed_first_byte = Some(start.elapsed().as_millis() as i32);
+        }
+        exchange.body.extend_from_slice(&buf[..n]);
+    }
+
+    exchange.elapsed = start.elapsed().as_millis() as i32;
+    Ok(exchange)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn echoes_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4];
+            stream.read_exact(&mut buf).await.unwrap();
+            stream.write_all(&buf).await.unwrap();
+        });
+
+        let exchange = send_socket(SocketSendOptions {
+            protocol: SocketProtocol::Tcp,
+            url: addr.to_string(),
+            payload: b"PING".to_vec(),
+            read_timeout: 0,
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(exchange.bytes_sent, 4);
+        assert_eq!(exchange.body, b"PING");
+        assert!(exchange.elapsed_first_byte.is_some());
+        assert!(exchange.error.is_none());
+    }
+}
@@ -0,0 +1,151 @@
+use crate::sse::ServerSentEvent;
+
+/// Incremental parser for `text/event-stream` bodies. Bytes can be fed in arbitrary chunks as
+/// they arrive from the network, and complete events are returned once their terminating blank
+/// line has been received.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buf: Vec<u8>,
+    skip_lf: bool,
+    event_type: String,
+    data: String,
+    has_data: bool,
+    id: Option<String>,
+    retry: Option<u64>,
+    last_event_id: Option<String>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The ID of the last event that was dispatched, to be sent as `Last-Event-ID` when
+    /// reconnecting
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<ServerSentEvent> {
+        let mut events = Vec::new();
+        for &b in bytes {
+            // A CRLF pair is a single line ending, so ignore the LF following a CR
+            if self.skip_lf {
+                self.skip_lf = false;
+                if b == b'\n' {
+                    continue;
+                }
+            }
+            match b {
+                b'\r' | b'\n' => {
+                    self.skip_lf = b == b'\r';
+                    let line = std::mem::take(&mut self.buf);
+                    if let Some(event) = self.process_line(&String::from_utf8_lossy(&line)) {
+                        events.push(event);
+                    }
+                }
+                _ => self.buf.push(b),
+            }
+        }
+        events
+    }
+
+    /// Reset any partially received event, which is discarded when a connection drops
+    pub fn reset(&mut self) {
+        let last_event_id = self.last_event_id.take();
+        *self = Self { last_event_id, ..Default::default() };
+    }
+
+    fn process_line(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+
+        if line.starts_with(':') {
+            return None; // Comment
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event_type = value.to_string(),
+            "data" => {
+                if self.has_data {
+                    self.data.push('\n');
+                }
+                self.data.push_str(value);
+                self.has_data = true;
+            }
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            "retry" => {
+                if let Ok(retry) = value.parse::<u64>() {
+                    self.retry = Some(retry);
+                }
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        if let Some(id) = self.id.take() {
+            self.last_event_id = Some(id);
+        }
+
+        let retry = self.retry.take();
+        let event_type = std::mem::take(&mut self.event_type);
+        if !self.has_data {
+            return None;
+        }
+
+        self.has_data = false;
+        Some(ServerSentEvent {
+            event_type: if event_type.is_empty() { "message".to_string() } else { event_type },
+            data: std::mem::take(&mut self.data),
+            id: self.last_event_id.clone(),
+            retry,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SseParser;
+
+    #[test]
+    fn parses_events_split_across_chunks() {
+        let mut parser = SseParser::new();
+        assert!(parser.feed(b"event: update\r\nda").is_empty());
+        assert!(parser.feed(b"ta: first\r").is_empty());
+        let events = parser.feed(b"\ndata:second\r\nid: 1\r\n\r\n: keepalive\n\ndata: third\n\n");
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, "update");
+        assert_eq!(events[0].data, "first\nsecond");
+        assert_eq!(events[0].id.as_deref(), Some("1"));
+        assert_eq!(events[1].event_type, "message");
+        assert_eq!(events[1].data, "third");
+        assert_eq!(events[1].id.as_deref(), Some("1"));
+        assert_eq!(parser.last_event_id(), Some("1"));
+    }
+
+    #[test]
+    fn tracks_retry_and_last_event_id_across_reset() {
+        let mut parser = SseParser::new();
+        let events = parser.feed(b"retry: 2500\nid: 7\ndata: hello\n\nretry: nope\nid: 8\n\n");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].retry, Some(2500));
+
+        // An event without data still updates the last event ID
+        assert_eq!(parser.last_event_id(), Some("8"));
+
+        parser.feed(b"data: partial");
+        parser.reset();
+        assert!(parser.feed(b"\n").is_empty());
+        assert_eq!(parser.last_event_id(), Some("8"));
+    }
+}
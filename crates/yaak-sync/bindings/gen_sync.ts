@@ -1,6 +1,38 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { SyncModel, SyncState } from "./gen_models";
+import type { JsonValue } from "./serde_json/JsonValue";
+
+export type ConflictResolution = { path: Array<string>, side: MergeSide, };
 
 export type FsCandidate = { "type": "FsCandidate", model: SyncModel, relPath: string, checksum: string, };
 
-export type SyncOp = { "type": "fsCreate", model: SyncModel, } | { "type": "fsUpdate", model: SyncModel, state: SyncState, } | { "type": "fsDelete", state: SyncState, fs: FsCandidate | null, } | { "type": "dbCreate", fs: FsCandidate, } | { "type": "dbUpdate", state: SyncState, fs: FsCandidate, } | { "type": "dbDelete", model: SyncModel, state: SyncState, } | { "type": "ignorePrivate", model: SyncModel, };
+/**
+ * A field that was changed differently on both sides of a merge
+ */
+export type MergeConflict = { 
+/**
+ * Keys leading to the field within the model, like `["body", "text"]`
+ */
+path: Array<string>, 
+/**
+ * Value when the two sides were last in sync. `None` if the field (or model) didn't exist
+ */
+base: JsonValue | null, 
+/**
+ * Value in the database. `None` if the field was removed
+ */
+ours: JsonValue | null, 
+/**
+ * Value in the synced file. `None` if the field was removed
+ */
+theirs: JsonValue | null, };
+
+export type MergeResult = { 
+/**
+ * The merged model. Conflicting fields keep the database value until they're resolved.
+ */
+model: SyncModel, conflicts: Array<MergeConflict>, };
+
+export type MergeSide = "ours" | "theirs";
+
+export type SyncOp = { "type": "fsCreate", model: SyncModel, } | { "type": "fsUpdate", model: SyncModel, state: SyncState, } | { "type": "fsDelete", state: SyncState, fs: FsCandidate | null, } | { "type": "dbCreate", fs: FsCandidate, } | { "type": "dbUpdate", state: SyncState, fs: FsCandidate, } | { "type": "dbDelete", model: SyncModel, state: SyncState, } | { "type": "dbMerge", model: SyncModel, state: SyncState, } | { "type": "conflict", state: SyncState, fs: FsCandidate, merge: MergeResult, } | { "type": "ignorePrivate", model: SyncModel, };
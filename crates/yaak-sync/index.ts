@@ -1,10 +1,11 @@
 import { Channel, invoke } from "@tauri-apps/api/core";
 import { emit } from "@tauri-apps/api/event";
 import type { WatchResult } from "@yaakapp-internal/tauri-client";
-import { SyncOp } from "./bindings/gen_sync";
+import { ConflictResolution, SyncOp } from "./bindings/gen_sync";
 import { WatchEvent } from "./bindings/gen_watch";
 
 export * from "./bindings/gen_models";
+export * from "./bindings/gen_sync";
 
 export async function calculateSync(workspaceId: string, syncDir: string) {
   return invoke<SyncOp[]>("cmd_sync_calculate", {
@@ -25,6 +26,21 @@ export async function applySync(workspaceId: string, syncDir: string, syncOps: S
   });
 }
 
+export async function resolveSyncConflict(
+  workspaceId: string,
+  syncDir: string,
+  op: Extract<SyncOp, { type: "conflict" }>,
+  resolutions: ConflictResolution[],
+) {
+  return invoke<void>("cmd_sync_resolve_conflict", {
+    workspaceId,
+    syncDir,
+    state: op.state,
+    merge: op.merge,
+    resolutions,
+  });
+}
+
 export function watchWorkspaceFiles(
   workspaceId: string,
   syncDir: string,
@@ -1,4 +1,6 @@
 pub mod error;
+pub mod merge;
 pub mod models;
+pub mod rules;
 pub mod sync;
 pub mod watch;
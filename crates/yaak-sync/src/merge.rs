@@ -0,0 +1,249 @@
+use crate::error::Result;
+use crate::models::SyncModel;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+use ts_rs::TS;
+
+/// A field that was changed differently on both sides of a merge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_sync.ts")]
+pub struct MergeConflict {
+    /// Keys leading to the field within the model, like `["body", "text"]`
+    pub path: Vec<String>,
+    /// Value when the two sides were last in sync. `None` if the field (or model) didn't exist
+    pub base: Option<Value>,
+    /// Value in the database. `None` if the field was removed
+    pub ours: Option<Value>,
+    /// Value in the synced file. `None` if the field was removed
+    pub theirs: Option<Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_sync.ts")]
+pub struct MergeResult {
+    /// The merged model. Conflicting fields keep the database value until they're resolved.
+    pub model: SyncModel,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(export, export_to = "gen_sync.ts")]
+pub enum MergeSide {
+    Ours,
+    Theirs,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, export_to = "gen_sync.ts")]
+pub struct ConflictResolution {
+    pub path: Vec<String>,
+    pub side: MergeSide,
+}
+
+/// Merge changes to a model from the database (`ours`) and its synced file (`theirs`), field by
+/// field, against the version they were both based on. Fields changed on only one side take that
+/// side's value. Objects are merged key by key, while other values (including arrays) conflict
+/// when both sides changed them differently.
+pub fn merge_models(
+    base: Option<&SyncModel>,
+    ours: &SyncModel,
+    theirs: &SyncModel,
+) -> Result<MergeResult> {
+    let base = base.map(serde_json::to_value).transpose()?;
+    let ours = serde_json::to_value(ours)?;
+    let theirs = serde_json::to_value(theirs)?;
+
+    let mut conflicts = Vec::new();
+    let mut merged =
+        merge_values(&mut Vec::new(), base.as_ref(), Some(&ours), Some(&theirs), &mut conflicts)
+            .unwrap_or(Value::Null);
+
+    // Every edit bumps updatedAt, so it would always conflict. Take the newer one instead.
+    conflicts.retain(|c| c.path != ["updatedAt"]);
+    if let (Some(Value::String(a)), Some(Value::String(b))) =
+        (ours.get("updatedAt"), theirs.get("updatedAt"))
+    {
+        merged["updatedAt"] = Value::String(a.max(b).clone());
+    }
+
+    Ok(MergeResult { model: serde_json::from_value(merged)?, conflicts })
+}
+
+/// Settle the conflicts of a merge by picking a side for each. Conflicts without a resolution keep
+/// the database value.
+pub fn resolve_conflicts(
+    result: &MergeResult,
+    resolutions: &[ConflictResolution],
+) -> Result<SyncModel> {
+    let mut model = serde_json::to_value(&result.model)?;
+    for conflict in &result.conflicts {
+        let side = resolutions.iter().find(|r| r.path == conflict.path).map(|r| r.side);
+        let value = match side {
+            Some(MergeSide::Theirs) => &conflict.theirs,
+            Some(MergeSide::Ours) | None => &conflict.ours,
+        };
+        set_path(&mut model, &conflict.path, value.clone());
+    }
+    Ok(serde_json::from_value(model)?)
+}
+
+fn merge_values(
+    path: &mut Vec<String>,
+    base: Option<&Value>,
+    ours: Option<&Value>,
+    theirs: Option<&Value>,
+    conflicts: &mut Vec<MergeConflict>,
+) -> Option<Value> {
+    if ours == theirs || base == theirs {
+        return ours.cloned();
+    }
+    if base == ours {
+        return theirs.cloned();
+    }
+
+    if let (Some(Value::Object(ours)), Some(Value::Object(theirs))) = (ours, theirs) {
+        let base = base.and_then(Value::as_object);
+        let keys: BTreeSet<&String> =
+            ours.keys().chain(theirs.keys()).chain(base.into_iter().flat_map(Map::keys)).collect();
+
+        let mut merged = Map::new();
+        for key in keys {
+            path.push(key.clone());
+            let value = merge_values(
+                path,
+                base.and_then(|b| b.get(key)),
+                ours.get(key),
+                theirs.get(key),
+                conflicts,
+            );
+            path.pop();
+            if let Some(value) = value {
+                merged.insert(key.clone(), value);
+            }
+        }
+        return Some(Value::Object(merged));
+    }
+
+    conflicts.push(MergeConflict {
+        path: path.clone(),
+        base: base.cloned(),
+        ours: ours.cloned(),
+        theirs: theirs.cloned(),
+    });
+    ours.cloned()
+}
+
+fn set_path(target: &mut Value, path: &[String], value: Option<Value>) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+
+    let mut current = target;
+    for key in parents {
+        let Some(next) =
+            current.as_object_mut().map(|o| o.entry(key.clone()).or_insert(Value::Null))
+        else {
+            return;
+        };
+        if next.is_null() {
+            *next = Value::Object(Map::new());
+        }
+        current = next;
+    }
+
+    let Some(object) = current.as_object_mut() else {
+        return;
+    };
+    match value {
+        Some(value) => object.insert(last.clone(), value),
+        None => object.remove(last),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use yaak_models::models::HttpRequest;
+
+    fn request(name: &str, url: &str, body_text: &str) -> SyncModel {
+        let mut body = std::collections::BTreeMap::new();
+        body.insert("text".to_string(), json!(body_text));
+        SyncModel::HttpRequest(HttpRequest {
+            model: "http_request".to_string(),
+            id: "rq_1".to_string(),
+            workspace_id: "wk_1".to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+            body,
+            ..Default::default()
+        })
+    }
+
+    fn name_and_url(model: &SyncModel) -> (String, String) {
+        match model {
+            SyncModel::HttpRequest(r) => (r.name.clone(), r.url.clone()),
+            _ => panic!("Expected an HTTP request"),
+        }
+    }
+
+    #[test]
+    fn merges_changes_to_different_fields() -> Result<()> {
+        let base = request("Request", "https://example.com", "{}");
+        let ours = request("Renamed", "https://example.com", "{}");
+        let theirs = request("Request", "https://api.example.com", "{}");
+
+        let result = merge_models(Some(&base), &ours, &theirs)?;
+        assert_eq!(result.conflicts, Vec::new());
+        assert_eq!(
+            name_and_url(&result.model),
+            ("Renamed".to_string(), "https://api.example.com".to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reports_nested_conflicts_and_resolves_them() -> Result<()> {
+        let base = request("Request", "https://example.com", "{}");
+        let ours = request("Request", "https://example.com", r#"{"a":1}"#);
+        let theirs = request("Renamed", "https://example.com", r#"{"a":2}"#);
+
+        let result = merge_models(Some(&base), &ours, &theirs)?;
+        assert_eq!(
+            result.conflicts,
+            vec![MergeConflict {
+                path: vec!["body".to_string(), "text".to_string()],
+                base: Some(json!("{}")),
+                ours: Some(json!(r#"{"a":1}"#)),
+                theirs: Some(json!(r#"{"a":2}"#)),
+            }]
+        );
+        assert_eq!(result.model, request("Renamed", "https://example.com", r#"{"a":1}"#));
+
+        let resolved = resolve_conflicts(
+            &result,
+            &[ConflictResolution {
+                path: result.conflicts[0].path.clone(),
+                side: MergeSide::Theirs,
+            }],
+        )?;
+        assert_eq!(resolved, request("Renamed", "https://example.com", r#"{"a":2}"#));
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts_on_every_difference_without_a_base() -> Result<()> {
+        let ours = request("Ours", "https://example.com", "{}");
+        let theirs = request("Theirs", "https://example.com", "{}");
+
+        let result = merge_models(None, &ours, &theirs)?;
+        let paths: Vec<_> = result.conflicts.iter().map(|c| c.path.join(".")).collect();
+        assert_eq!(paths, vec!["name"]);
+        Ok(())
+    }
+}
@@ -203,6 +203,7 @@ impl TryFrom<AnyModel> for SyncModel {
             AnyModel::Workspace(m) => SyncModel::Workspace(m),
 
             // Non-sync models
+            AnyModel::CollectionRun(m) => return Err(UnknownModel(m.model)),
             AnyModel::CookieJar(m) => return Err(UnknownModel(m.model)),
             AnyModel::GraphQlIntrospection(m) => return Err(UnknownModel(m.model)),
             AnyModel::GrpcConnection(m) => return Err(UnknownModel(m.model)),
@@ -210,8 +211,15 @@ impl TryFrom<AnyModel> for SyncModel {
             AnyModel::HttpResponse(m) => return Err(UnknownModel(m.model)),
             AnyModel::HttpResponseEvent(m) => return Err(UnknownModel(m.model)),
             AnyModel::KeyValue(m) => return Err(UnknownModel(m.model)),
+            AnyModel::Monitor(m) => return Err(UnknownModel(m.model)),
+            AnyModel::MonitorRun(m) => return Err(UnknownModel(m.model)),
+            AnyModel::MqttConnection(m) => return Err(UnknownModel(m.model)),
+            AnyModel::MqttEvent(m) => return Err(UnknownModel(m.model)),
+            AnyModel::MqttRequest(m) => return Err(UnknownModel(m.model)),
             AnyModel::Plugin(m) => return Err(UnknownModel(m.model)),
             AnyModel::Settings(m) => return Err(UnknownModel(m.model)),
+            AnyModel::SocketRequest(m) => return Err(UnknownModel(m.model)),
+            AnyModel::SocketResponse(m) => return Err(UnknownModel(m.model)),
             AnyModel::WebsocketConnection(m) => return Err(UnknownModel(m.model)),
             AnyModel::WebsocketEvent(m) => return Err(UnknownModel(m.model)),
             AnyModel::WorkspaceMeta(m) => return Err(UnknownModel(m.model)),
@@ -0,0 +1,251 @@
+use crate::error::Result;
+use crate::models::SyncModel;
+use std::collections::HashSet;
+use yaak_models::client_db::ClientDb;
+use yaak_models::models::{Environment, Folder, Workspace, WorkspaceMeta};
+
+/// Rules from a workspace's [`WorkspaceMeta`] that decide what gets written to the sync directory.
+/// These are applied when models are serialized, so they hold regardless of any `.gitignore`.
+#[derive(Debug, Clone, Default)]
+pub struct SyncRules {
+    excluded_folder_ids: HashSet<String>,
+    exclude_secret_values: bool,
+}
+
+impl SyncRules {
+    pub fn for_workspace(db: &ClientDb, workspace_id: &str) -> Result<Self> {
+        let Some(workspace_meta) = db.get_workspace_meta(workspace_id) else {
+            return Ok(Self::default());
+        };
+        let folders = db.list_folders(workspace_id)?;
+        Ok(Self::new(&workspace_meta, &folders))
+    }
+
+    pub fn new(workspace_meta: &WorkspaceMeta, folders: &[Folder]) -> Self {
+        let mut excluded_folder_ids: HashSet<String> =
+            workspace_meta.setting_sync_exclude_folder_ids.iter().cloned().collect();
+
+        // Excluding a folder excludes its subfolders too. Folders aren't ordered by depth, so keep
+        // sweeping until a pass doesn't find any new ones.
+        loop {
+            let count = excluded_folder_ids.len();
+            for folder in folders {
+                if folder.folder_id.as_ref().is_some_and(|id| excluded_folder_ids.contains(id)) {
+                    excluded_folder_ids.insert(folder.id.clone());
+                }
+            }
+            if excluded_folder_ids.len() == count {
+                break;
+            }
+        }
+
+        Self {
+            excluded_folder_ids,
+            exclude_secret_values: workspace_meta.setting_sync_exclude_secret_values,
+        }
+    }
+
+    /// Whether the model lives in an excluded folder and should be left out of the sync directory
+    pub fn is_excluded(&self, model: &SyncModel) -> bool {
+        let folder_id = match model {
+            SyncModel::Workspace(_) => None,
+            SyncModel::Environment(m) if m.parent_model == "folder" => m.parent_id.as_ref(),
+            SyncModel::Environment(_) => None,
+            SyncModel::Folder(m) if self.excluded_folder_ids.contains(&m.id) => return true,
+            SyncModel::Folder(m) => m.folder_id.as_ref(),
+            SyncModel::HttpRequest(m) => m.folder_id.as_ref(),
+            SyncModel::GrpcRequest(m) => m.folder_id.as_ref(),
+            SyncModel::WebsocketRequest(m) => m.folder_id.as_ref(),
+        };
+        folder_id.is_some_and(|id| self.excluded_folder_ids.contains(id))
+    }
+
+    /// Strip anything the rules keep out of the sync directory before the model is written
+    pub fn redact(&self, model: &mut SyncModel) {
+        if !self.exclude_secret_values {
+            return;
+        }
+        if let SyncModel::Environment(environment) = model {
+            for variable in environment.variables.iter_mut().filter(|v| v.secret) {
+                variable.value.clear();
+            }
+        }
+    }
+
+    /// Put back values that [`SyncRules::redact`] stripped, so reading a synced file doesn't wipe
+    /// them from the database. Connection credentials are never synced, so they're always restored.
+    pub fn restore(&self, db: &ClientDb, model: &mut SyncModel) {
+        match model {
+            SyncModel::Workspace(workspace) => {
+                if let Ok(existing) = db.get_workspace(&workspace.id) {
+                    restore_connection_credentials(workspace, &existing);
+                }
+            }
+            SyncModel::Folder(folder) => {
+                if let Ok(existing) = db.get_folder(&folder.id) {
+                    restore_folder_credentials(folder, &existing);
+                }
+            }
+            SyncModel::Environment(environment) if self.exclude_secret_values => {
+                if let Ok(existing) = db.get_environment(&environment.id) {
+                    restore_secret_values(environment, &existing);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn restore_connection_credentials(workspace: &mut Workspace, existing: &Workspace) {
+    if let (Some(proxy), Some(existing)) = (&mut workspace.setting_proxy, &existing.setting_proxy) {
+        proxy.restore_credentials(existing);
+    }
+    if let (Some(tunnel), Some(existing)) =
+        (&mut workspace.setting_ssh_tunnel, &existing.setting_ssh_tunnel)
+    {
+        tunnel.restore_credentials(existing);
+    }
+}
+
+fn restore_folder_credentials(folder: &mut Folder, existing: &Folder) {
+    if let (Some(tunnel), Some(existing)) =
+        (&mut folder.setting_ssh_tunnel.value, &existing.setting_ssh_tunnel.value)
+    {
+        tunnel.restore_credentials(existing);
+    }
+}
+
+fn restore_secret_values(environment: &mut Environment, existing: &Environment) {
+    for variable in environment.variables.iter_mut() {
+        if !variable.secret || !variable.value.is_empty() {
+            continue;
+        }
+        let existing_variable = existing.variables.iter().find(|v| match (&v.id, &variable.id) {
+            (Some(a), Some(b)) => a == b,
+            _ => v.name == variable.name,
+        });
+        if let Some(existing_variable) = existing_variable {
+            variable.value = existing_variable.value.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::models::{
+        EnvironmentVariable, HttpRequest, InheritedSshTunnelSetting, ProxySetting,
+        ProxySettingAuth, SshTunnel,
+    };
+
+    fn folder(id: &str, folder_id: Option<&str>) -> Folder {
+        Folder {
+            id: id.to_string(),
+            folder_id: folder_id.map(|s| s.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn secret(name: &str, value: &str) -> EnvironmentVariable {
+        EnvironmentVariable {
+            enabled: true,
+            name: name.to_string(),
+            value: value.to_string(),
+            id: None,
+            secret: true,
+        }
+    }
+
+    #[test]
+    fn excludes_nested_folders_and_their_requests() {
+        let workspace_meta = WorkspaceMeta {
+            setting_sync_exclude_folder_ids: vec!["fl_a".to_string()],
+            ..Default::default()
+        };
+        // Children listed before their parents to make sure every level gets picked up
+        let folders = vec![
+            folder("fl_c", Some("fl_b")),
+            folder("fl_b", Some("fl_a")),
+            folder("fl_a", None),
+        ];
+        let rules = SyncRules::new(&workspace_meta, &folders);
+
+        assert!(rules.is_excluded(&SyncModel::Folder(folder("fl_a", None))));
+        assert!(rules.is_excluded(&SyncModel::Folder(folder("fl_c", Some("fl_b")))));
+        assert!(rules.is_excluded(&SyncModel::HttpRequest(HttpRequest {
+            folder_id: Some("fl_c".to_string()),
+            ..Default::default()
+        })));
+        assert!(!rules.is_excluded(&SyncModel::HttpRequest(HttpRequest::default())));
+        assert!(!rules.is_excluded(&SyncModel::Folder(folder("fl_d", None))));
+    }
+
+    #[test]
+    fn redacts_and_restores_secret_values() {
+        let workspace_meta =
+            WorkspaceMeta { setting_sync_exclude_secret_values: true, ..Default::default() };
+        let rules = SyncRules::new(&workspace_meta, &[]);
+        let existing = Environment {
+            variables: vec![
+                secret("token", "YENC_abc"),
+                EnvironmentVariable { secret: false, ..secret("host", "example.com") },
+            ],
+            ..Default::default()
+        };
+
+        let mut model = SyncModel::Environment(existing.clone());
+        rules.redact(&mut model);
+        let SyncModel::Environment(mut environment) = model else {
+            unreachable!()
+        };
+        assert_eq!(environment.variables[0].value, "");
+        assert_eq!(environment.variables[1].value, "example.com");
+
+        restore_secret_values(&mut environment, &existing);
+        assert_eq!(environment, existing);
+    }
+
+    #[test]
+    fn restores_proxy_credentials() {
+        let proxy = |user: &str, password: &str| ProxySetting::Enabled {
+            http: "proxy.example.com:8080".to_string(),
+            https: String::new(),
+            auth: Some(ProxySettingAuth { user: user.to_string(), password: password.to_string() }),
+            bypass: String::new(),
+            disabled: false,
+        };
+        let existing =
+            Workspace { setting_proxy: Some(proxy("alice", "hunter2")), ..Default::default() };
+
+        let mut workspace = existing.clone();
+        workspace.setting_proxy.as_mut().unwrap().clear_credentials();
+        assert_eq!(workspace.setting_proxy, Some(proxy("", "")));
+
+        restore_connection_credentials(&mut workspace, &existing);
+        assert_eq!(workspace, existing);
+    }
+
+    #[test]
+    fn restores_ssh_tunnel_credentials() {
+        let tunnel = SshTunnel {
+            host: "bastion.example.com".to_string(),
+            password: "hunter2".to_string(),
+            passphrase: "correct horse".to_string(),
+            ..Default::default()
+        };
+        let existing = Folder {
+            setting_ssh_tunnel: InheritedSshTunnelSetting {
+                enabled: true,
+                value: Some(tunnel.clone()),
+            },
+            ..Default::default()
+        };
+
+        let mut folder = existing.clone();
+        folder.setting_ssh_tunnel.value.as_mut().unwrap().clear_credentials();
+        assert_eq!(folder.setting_ssh_tunnel.value.as_ref().unwrap().password, "");
+
+        restore_folder_credentials(&mut folder, &existing);
+        assert_eq!(folder, existing);
+    }
+}
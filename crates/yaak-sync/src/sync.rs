@@ -1,5 +1,7 @@
 use crate::error::Result;
+use crate::merge::{ConflictResolution, MergeResult, merge_models, resolve_conflicts};
 use crate::models::SyncModel;
+use crate::rules::SyncRules;
 use chrono::Utc;
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
@@ -12,7 +14,7 @@ use std::path::{Path, PathBuf};
 use ts_rs::TS;
 use yaak_models::client_db::ClientDb;
 use yaak_models::models::{SyncState, WorkspaceMeta};
-use yaak_models::util::{UpdateSource, get_workspace_export_resources};
+use yaak_models::util::{BatchUpsertResult, UpdateSource, get_workspace_export_resources};
 
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase", tag = "type")]
@@ -40,6 +42,17 @@ pub enum SyncOp {
         model: SyncModel,
         state: SyncState,
     },
+    /// Both sides changed different fields, so the merged model is written to both
+    DbMerge {
+        model: SyncModel,
+        state: SyncState,
+    },
+    /// Both sides changed the same fields. Nothing is written until the conflicts are resolved.
+    Conflict {
+        state: SyncState,
+        fs: FsCandidate,
+        merge: MergeResult,
+    },
     IgnorePrivate {
         model: SyncModel,
     },
@@ -54,6 +67,8 @@ impl SyncOp {
             SyncOp::FsCreate { model } => model.workspace_id(),
             SyncOp::FsDelete { state, .. } => state.workspace_id.clone(),
             SyncOp::FsUpdate { state, .. } => state.workspace_id.clone(),
+            SyncOp::DbMerge { state, .. } => state.workspace_id.clone(),
+            SyncOp::Conflict { state, .. } => state.workspace_id.clone(),
             SyncOp::IgnorePrivate { model } => model.workspace_id(),
         }
     }
@@ -69,6 +84,8 @@ impl Display for SyncOp {
                 SyncOp::DbCreate { fs } => format!("db_create({})", fs.model.id()),
                 SyncOp::DbUpdate { fs, .. } => format!("db_update({})", fs.model.id()),
                 SyncOp::DbDelete { model, .. } => format!("db_delete({})", model.id()),
+                SyncOp::DbMerge { model, .. } => format!("db_merge({})", model.id()),
+                SyncOp::Conflict { fs, .. } => format!("conflict({})", fs.model.id()),
                 SyncOp::IgnorePrivate { model } => format!("ignore_private({})", model.id()),
             }
             .as_str(),
@@ -267,15 +284,9 @@ pub fn compute_sync_ops(
                 (Some(DbCandidate::Modified(model, sync_state)), Some(fs_candidate)) => {
                     if sync_state.checksum == fs_candidate.checksum {
                         SyncOp::FsUpdate { model: model.to_owned(), state: sync_state.to_owned() }
-                    } else if model.updated_at() < fs_candidate.model.updated_at() {
-                        // CONFLICT! Write to DB if the fs model is newer
-                        SyncOp::DbUpdate {
-                            state: sync_state.to_owned(),
-                            fs: fs_candidate.to_owned(),
-                        }
                     } else {
-                        // CONFLICT! Write to FS if the db model is newer
-                        SyncOp::FsUpdate { model: model.to_owned(), state: sync_state.to_owned() }
+                        // Both sides changed since the last sync, so merge them
+                        merge_sync_op(model, sync_state, fs_candidate)
                     }
                 }
 
@@ -296,6 +307,39 @@ pub fn compute_sync_ops(
         .collect()
 }
 
+fn merge_sync_op(model: &SyncModel, state: &SyncState, fs: &FsCandidate) -> SyncOp {
+    let base = state.base.as_ref().and_then(|base| {
+        match SyncModel::from_bytes(base.as_bytes().to_vec(), Path::new(&state.rel_path)) {
+            Ok(Some((base, _))) => Some(base),
+            _ => None,
+        }
+    });
+
+    match merge_models(base.as_ref(), model, &fs.model) {
+        Ok(merge) if merge.conflicts.is_empty() => {
+            SyncOp::DbMerge { model: merge.model, state: state.to_owned() }
+        }
+        Ok(merge) => SyncOp::Conflict { state: state.to_owned(), fs: fs.to_owned(), merge },
+        Err(e) => {
+            warn!("Failed to merge {}, keeping the newest side {e:?}", model.id());
+            if model.updated_at() < fs.model.updated_at() {
+                SyncOp::DbUpdate { state: state.to_owned(), fs: fs.to_owned() }
+            } else {
+                SyncOp::FsUpdate { model: model.to_owned(), state: state.to_owned() }
+            }
+        }
+    }
+}
+
+/// Turn a conflict into a merge by picking a side for each conflicting field
+pub fn resolve_sync_conflict(
+    state: SyncState,
+    merge: &MergeResult,
+    resolutions: &[ConflictResolution],
+) -> Result<SyncOp> {
+    Ok(SyncOp::DbMerge { model: resolve_conflicts(merge, resolutions)?, state })
+}
+
 fn workspace_models(db: &ClientDb, version: &str, workspace_id: &str) -> Result<Vec<SyncModel>> {
     // We want to include private environments here so that we can take them into account during
     // the sync process. Otherwise, they would be treated as deleted.
@@ -332,6 +376,13 @@ fn workspace_models(db: &ClientDb, version: &str, workspace_id: &str) -> Result<
         sync_models.push(SyncModel::WebsocketRequest(m));
     }
 
+    // Excluded models are treated as deleted, so any files already written for them get removed
+    let rules = SyncRules::for_workspace(db, workspace_id)?;
+    sync_models.retain(|m| !rules.is_excluded(m));
+    for m in sync_models.iter_mut() {
+        rules.redact(m);
+    }
+
     Ok(sync_models)
 }
 
@@ -359,6 +410,7 @@ pub fn apply_sync_ops(
     let mut http_requests_to_upsert = Vec::new();
     let mut grpc_requests_to_upsert = Vec::new();
     let mut websocket_requests_to_upsert = Vec::new();
+    let rules = SyncRules::for_workspace(db, workspace_id)?;
 
     for op in sync_ops {
         // Only apply things if workspace ID matches
@@ -373,7 +425,8 @@ pub fn apply_sync_ops(
                 let (content, checksum) = model.to_file_contents(&rel_path)?;
                 let mut f = File::create(&abs_path)?;
                 f.write_all(&content)?;
-                SyncStateOp::Create { model_id: model.id(), checksum, rel_path }
+                let base = String::from_utf8(content).ok();
+                SyncStateOp::Create { model_id: model.id(), checksum, rel_path, base }
             }
             SyncOp::FsUpdate { model, state } => {
                 // Always write the existing path
@@ -386,6 +439,7 @@ pub fn apply_sync_ops(
                     state: state.to_owned(),
                     checksum,
                     rel_path: rel_path.to_owned(),
+                    base: String::from_utf8(content).ok(),
                 }
             }
             SyncOp::FsDelete { state, fs: fs_candidate } => match fs_candidate {
@@ -398,8 +452,11 @@ pub fn apply_sync_ops(
                     SyncStateOp::Delete { state: state.to_owned() }
                 }
             },
-            SyncOp::DbCreate { fs } => {
+            SyncOp::DbCreate { fs } if rules.is_excluded(&fs.model) => SyncStateOp::NoOp,
+            SyncOp::DbCreate { mut fs } => {
                 let model_id = fs.model.id();
+                let base = file_base(&fs)?;
+                rules.restore(db, &mut fs.model);
 
                 // Push updates to arrays so we can do them all in a single
                 // batch upsert to make foreign keys happy
@@ -415,9 +472,13 @@ pub fn apply_sync_ops(
                     model_id,
                     checksum: fs.checksum.to_owned(),
                     rel_path: fs.rel_path.to_owned(),
+                    base,
                 }
             }
-            SyncOp::DbUpdate { state, fs } => {
+            SyncOp::DbUpdate { state, mut fs } => {
+                let base = file_base(&fs)?;
+                rules.restore(db, &mut fs.model);
+
                 // Push updates to arrays so we can do them all in a single
                 // batch upsert to make foreign keys happy
                 match fs.model {
@@ -432,23 +493,53 @@ pub fn apply_sync_ops(
                     state: state.to_owned(),
                     checksum: fs.checksum.to_owned(),
                     rel_path: fs.rel_path.to_owned(),
+                    base,
                 }
             }
             SyncOp::DbDelete { model, state } => {
                 delete_model(db, &model)?;
                 SyncStateOp::Delete { state: state.to_owned() }
             }
+            SyncOp::DbMerge { mut model, state } => {
+                // Write the merged model to the existing path, then to the database
+                let rel_path = Path::new(&state.rel_path);
+                let abs_path = Path::new(&state.sync_dir).join(rel_path);
+                let (content, checksum) = model.to_file_contents(rel_path)?;
+                let mut f = File::create(&abs_path)?;
+                f.write_all(&content)?;
+
+                rules.restore(db, &mut model);
+                match model {
+                    SyncModel::Environment(m) => environments_to_upsert.push(m),
+                    SyncModel::Folder(m) => folders_to_upsert.push(m),
+                    SyncModel::GrpcRequest(m) => grpc_requests_to_upsert.push(m),
+                    SyncModel::HttpRequest(m) => http_requests_to_upsert.push(m),
+                    SyncModel::WebsocketRequest(m) => websocket_requests_to_upsert.push(m),
+                    SyncModel::Workspace(m) => workspaces_to_upsert.push(m),
+                }
+                SyncStateOp::Update {
+                    state: state.to_owned(),
+                    checksum,
+                    rel_path: rel_path.to_owned(),
+                    base: String::from_utf8(content).ok(),
+                }
+            }
+            // Leave the sync state alone so the conflict comes up again until it's resolved
+            SyncOp::Conflict { .. } => SyncStateOp::NoOp,
             SyncOp::IgnorePrivate { .. } => SyncStateOp::NoOp,
         });
     }
 
     let upserted_models = db.batch_upsert(
-        workspaces_to_upsert,
-        environments_to_upsert,
-        folders_to_upsert,
-        http_requests_to_upsert,
-        grpc_requests_to_upsert,
-        websocket_requests_to_upsert,
+        BatchUpsertResult {
+            workspaces: workspaces_to_upsert,
+            environments: environments_to_upsert,
+            folders: folders_to_upsert,
+            http_requests: http_requests_to_upsert,
+            grpc_requests: grpc_requests_to_upsert,
+            websocket_requests: websocket_requests_to_upsert,
+            cookie_jars: Vec::new(),
+        },
         &UpdateSource::Sync,
     )?;
 
@@ -489,11 +580,13 @@ pub enum SyncStateOp {
         model_id: String,
         checksum: String,
         rel_path: PathBuf,
+        base: Option<String>,
     },
     Update {
         state: SyncState,
         checksum: String,
         rel_path: PathBuf,
+        base: Option<String>,
     },
     Delete {
         state: SyncState,
@@ -509,11 +602,12 @@ pub fn apply_sync_state_ops(
 ) -> Result<()> {
     for op in ops {
         match op {
-            SyncStateOp::Create { checksum, rel_path, model_id } => {
+            SyncStateOp::Create { checksum, rel_path, model_id, base } => {
                 let sync_state = SyncState {
                     workspace_id: workspace_id.to_string(),
                     model_id,
                     checksum,
+                    base,
                     sync_dir: sync_dir.to_str().unwrap().to_string(),
                     rel_path: rel_path.to_str().unwrap().to_string(),
                     flushed_at: Utc::now().naive_utc(),
@@ -521,9 +615,10 @@ pub fn apply_sync_state_ops(
                 };
                 db.upsert_sync_state(&sync_state)?;
             }
-            SyncStateOp::Update { state: sync_state, checksum, rel_path } => {
+            SyncStateOp::Update { state: sync_state, checksum, rel_path, base } => {
                 let sync_state = SyncState {
                     checksum,
+                    base,
                     sync_dir: sync_dir.to_str().unwrap().to_string(),
                     rel_path: rel_path.to_str().unwrap().to_string(),
                     flushed_at: Utc::now().naive_utc(),
@@ -542,6 +637,12 @@ pub fn apply_sync_state_ops(
     Ok(())
 }
 
+/// Contents of a synced file as they'd be written for its model, to use as a merge base
+fn file_base(fs: &FsCandidate) -> Result<Option<String>> {
+    let (content, _) = fs.model.to_file_contents(&fs.rel_path)?;
+    Ok(String::from_utf8(content).ok())
+}
+
 fn derive_model_filename(m: &SyncModel) -> PathBuf {
     let rel = format!("yaak.{}.yaml", m.id());
     Path::new(&rel).to_path_buf()
@@ -570,3 +671,91 @@ fn delete_model(db: &ClientDb, model: &SyncModel) -> Result<()> {
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge::MergeSide;
+    use yaak_models::models::HttpRequest;
+
+    fn request(name: &str, url: &str) -> SyncModel {
+        SyncModel::HttpRequest(HttpRequest {
+            model: "http_request".to_string(),
+            id: "rq_1".to_string(),
+            workspace_id: "wk_1".to_string(),
+            name: name.to_string(),
+            url: url.to_string(),
+            ..Default::default()
+        })
+    }
+
+    fn name_and_url(model: &SyncModel) -> (String, String) {
+        match model {
+            SyncModel::HttpRequest(r) => (r.name.clone(), r.url.clone()),
+            _ => panic!("Expected an HTTP request"),
+        }
+    }
+
+    /// Ops for a model changed to `ours` in the database and `theirs` in its file since `base`
+    fn ops_for_changes(
+        base: &SyncModel,
+        ours: SyncModel,
+        theirs: SyncModel,
+    ) -> Result<Vec<SyncOp>> {
+        let rel_path = PathBuf::from("yaak.rq_1.yaml");
+        let (base, checksum) = base.to_file_contents(&rel_path)?;
+        let state = SyncState {
+            workspace_id: "wk_1".to_string(),
+            model_id: "rq_1".to_string(),
+            checksum,
+            rel_path: rel_path.to_string_lossy().to_string(),
+            base: String::from_utf8(base).ok(),
+            ..Default::default()
+        };
+        let (_, fs_checksum) = theirs.to_file_contents(&rel_path)?;
+        let fs = FsCandidate { model: theirs, rel_path, checksum: fs_checksum };
+        Ok(compute_sync_ops(vec![DbCandidate::Modified(ours, state)], vec![fs]))
+    }
+
+    #[test]
+    fn merges_models_changed_on_both_sides() -> Result<()> {
+        let base = request("Request", "https://example.com");
+        let ours = request("Renamed", "https://example.com");
+        let theirs = request("Request", "https://api.example.com");
+
+        let ops = ops_for_changes(&base, ours, theirs)?;
+        match ops.as_slice() {
+            [SyncOp::DbMerge { model, .. }] => assert_eq!(
+                name_and_url(model),
+                ("Renamed".to_string(), "https://api.example.com".to_string())
+            ),
+            ops => panic!("Expected a merge, got {ops:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn reports_conflicts_until_resolved() -> Result<()> {
+        let base = request("Request", "https://example.com");
+        let ours = request("Ours", "https://example.com");
+        let theirs = request("Theirs", "https://api.example.com");
+
+        let ops = ops_for_changes(&base, ours, theirs)?;
+        let [SyncOp::Conflict { state, merge, .. }] = ops.as_slice() else {
+            panic!("Expected a conflict, got {ops:?}");
+        };
+        assert_eq!(merge.conflicts.len(), 1);
+        assert_eq!(merge.conflicts[0].path, vec!["name".to_string()]);
+
+        let resolutions =
+            vec![ConflictResolution { path: vec!["name".to_string()], side: MergeSide::Theirs }];
+        match resolve_sync_conflict(state.clone(), merge, &resolutions)? {
+            SyncOp::DbMerge { model, .. } => assert_eq!(
+                name_and_url(&model),
+                ("Theirs".to_string(), "https://api.example.com".to_string())
+            ),
+            op => panic!("Expected a merge, got {op:?}"),
+        }
+        Ok(())
+    }
+}
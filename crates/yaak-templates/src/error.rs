@@ -12,6 +12,19 @@ pub enum Error {
 
     #[error("Render Error: Max recursion depth exceeded")]
     RenderStackExceededError,
+
+    /// Variables that reference each other in a loop. `chain` lists the variables in the order
+    /// they're resolved, ending with the repeated one, and `offset` is the character offset of the
+    /// tag in `variable`'s value that closes the loop.
+    #[error(
+        "Render Error: Variable \"{variable}\" creates a cycle ({}) at offset {offset}",
+        chain.join(" → ")
+    )]
+    VariableCycle {
+        variable: String,
+        chain: Vec<String>,
+        offset: usize,
+    },
 }
 
 impl Serialize for Error {
@@ -4,6 +4,7 @@ pub mod format_json;
 pub mod parser;
 pub mod renderer;
 pub mod strip_json_comments;
+pub mod variables;
 pub mod wasm;
 
 pub use parser::*;
@@ -144,6 +144,7 @@ pub fn transform_args<T: TemplateCallback>(tokens: Tokens, cb: &T) -> Result<Tok
 #[derive(Default)]
 pub struct Parser {
     tokens: Vec<Token>,
+    tag_offsets: Vec<usize>,
     chars: Vec<char>,
     pos: usize,
     curr_text: String,
@@ -167,6 +168,7 @@ impl Parser {
             } else if self.match_str("${[") {
                 let start_curr = self.pos;
                 if let Some(t) = self.parse_tag()? {
+                    self.tag_offsets.push(start_curr - "${[".len());
                     self.push_token(t);
                 } else {
                     self.pos = start_curr;
@@ -186,6 +188,12 @@ impl Parser {
         Ok(Tokens { tokens: self.tokens.clone() })
     }
 
+    /// Character offsets where each tag of the parsed template starts, in the same order as the
+    /// tag tokens
+    pub fn tag_offsets(&self) -> &[usize] {
+        &self.tag_offsets
+    }
+
     fn parse_tag(&mut self) -> Result<Option<Token>> {
         // Parse up to first identifier
         //    ${[ my_var...
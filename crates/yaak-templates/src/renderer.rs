@@ -1,5 +1,6 @@
 use crate::error::Error::{RenderStackExceededError, VariableNotFound};
 use crate::error::Result;
+use crate::variables::variable_cycle_error;
 use crate::{Parser, Token, Tokens, Val};
 use log::warn;
 use serde_json::json;
@@ -47,16 +48,19 @@ pub async fn render_json_value_raw<T: TemplateCallback>(
     Ok(v)
 }
 
+/// `chain` holds the variables currently being resolved, outermost first, so a variable that
+/// (indirectly) references itself is reported as a cycle instead of recursing until the depth limit
 async fn parse_and_render_at_depth<T: TemplateCallback>(
     template: &str,
     vars: &HashMap<String, String>,
     cb: &T,
     opt: &RenderOptions,
     depth: usize,
+    chain: &[String],
 ) -> Result<String> {
     let mut p = Parser::new(template);
     let tokens = p.parse()?;
-    render(tokens, vars, cb, opt, depth + 1).await
+    render_at_depth(tokens, vars, cb, opt, depth + 1, chain).await
 }
 
 pub async fn parse_and_render<T: TemplateCallback>(
@@ -65,7 +69,7 @@ pub async fn parse_and_render<T: TemplateCallback>(
     cb: &T,
     opt: &RenderOptions,
 ) -> Result<String> {
-    parse_and_render_at_depth(template, vars, cb, opt, 1).await
+    parse_and_render_at_depth(template, vars, cb, opt, 1, &[]).await
 }
 
 pub enum RenderErrorBehavior {
@@ -101,11 +105,22 @@ impl RenderErrorBehavior {
 }
 
 pub async fn render<T: TemplateCallback>(
+    tokens: Tokens,
+    vars: &HashMap<String, String>,
+    cb: &T,
+    opt: &RenderOptions,
+    depth: usize,
+) -> Result<String> {
+    render_at_depth(tokens, vars, cb, opt, depth, &[]).await
+}
+
+async fn render_at_depth<T: TemplateCallback>(
     tokens: Tokens,
     vars: &HashMap<String, String>,
     cb: &T,
     opt: &RenderOptions,
     mut depth: usize,
+    chain: &[String],
 ) -> Result<String> {
     depth += 1;
     if depth > MAX_DEPTH {
@@ -118,7 +133,7 @@ pub async fn render<T: TemplateCallback>(
         match t {
             Token::Raw { text } => doc_str.push(text),
             Token::Tag { val } => {
-                let val = render_value(val, &vars, cb, opt, depth).await;
+                let val = render_value(val, &vars, cb, opt, depth, chain).await;
                 doc_str.push(opt.error_behavior.handle(val)?)
             }
             Token::Eof => {}
@@ -134,15 +149,25 @@ async fn render_value<T: TemplateCallback>(
     cb: &T,
     opt: &RenderOptions,
     depth: usize,
+    chain: &[String],
 ) -> Result<String> {
     let v = match val {
         Val::Str { text } => {
-            let r = Box::pin(parse_and_render_at_depth(&text, vars, cb, opt, depth)).await?;
+            let r = Box::pin(parse_and_render_at_depth(&text, vars, cb, opt, depth, chain)).await?;
             r.to_string()
         }
         Val::Var { name } => match vars.get(name.as_str()) {
+            Some(_) if chain.contains(&name) => {
+                let start = chain.iter().position(|n| *n == name).unwrap_or_default();
+                let mut cycle = chain[start..].to_vec();
+                cycle.push(name);
+                return Err(variable_cycle_error(cycle, vars));
+            }
             Some(v) => {
-                let r = Box::pin(parse_and_render_at_depth(v, vars, cb, opt, depth)).await?;
+                let mut chain = chain.to_vec();
+                chain.push(name);
+                let r =
+                    Box::pin(parse_and_render_at_depth(v, vars, cb, opt, depth, &chain)).await?;
                 r.to_string()
             }
             None => return Err(VariableNotFound(name)),
@@ -154,13 +179,13 @@ async fn render_value<T: TemplateCallback>(
                     Val::Bool { value } => serde_json::Value::Bool(value),
                     Val::Null => serde_json::Value::Null,
                     _ => serde_json::Value::String(
-                        Box::pin(render_value(a.value, vars, cb, opt, depth)).await?,
+                        Box::pin(render_value(a.value, vars, cb, opt, depth, chain)).await?,
                     ),
                 };
                 resolved_args.insert(a.name, v);
             }
             let result = cb.run(name.as_str(), resolved_args.clone()).await?;
-            Box::pin(parse_and_render_at_depth(&result, vars, cb, opt, depth)).await?
+            Box::pin(parse_and_render_at_depth(&result, vars, cb, opt, depth, chain)).await?
         }
         Val::Bool { value } => value.to_string(),
         Val::Null => "".into(),
@@ -171,7 +196,7 @@ async fn render_value<T: TemplateCallback>(
 
 #[cfg(test)]
 mod parse_and_render_tests {
-    use crate::error::Error::{RenderError, RenderStackExceededError, VariableNotFound};
+    use crate::error::Error::{RenderError, VariableCycle, VariableNotFound};
     use crate::error::Result;
     use crate::renderer::TemplateCallback;
     use crate::*;
@@ -279,7 +304,31 @@ mod parse_and_render_tests {
         let opt = RenderOptions { error_behavior: RenderErrorBehavior::Throw };
         assert_eq!(
             parse_and_render(template, &vars, &empty_cb, &opt).await,
-            Err(RenderStackExceededError)
+            Err(VariableCycle {
+                variable: "foo".to_string(),
+                chain: vec!["foo".to_string(), "foo".to_string()],
+                offset: 0,
+            })
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn render_indirectly_self_referencing_var() -> Result<()> {
+        let empty_cb = EmptyCB {};
+        let template = "${[ foo ]}";
+        let mut vars = HashMap::new();
+        vars.insert("foo".to_string(), "foo: ${[ bar ]}".to_string());
+        vars.insert("bar".to_string(), "${[ baz ]} bar: ${[ foo ]}".to_string());
+        vars.insert("baz".to_string(), "baz".to_string());
+        let opt = RenderOptions { error_behavior: RenderErrorBehavior::Throw };
+        assert_eq!(
+            parse_and_render(template, &vars, &empty_cb, &opt).await,
+            Err(VariableCycle {
+                variable: "bar".to_string(),
+                chain: vec!["foo".to_string(), "bar".to_string(), "foo".to_string()],
+                offset: 16,
+            })
         );
         Ok(())
     }
@@ -0,0 +1,164 @@
+use crate::error::Error::VariableCycle;
+use crate::error::{Error, Result};
+use crate::{Parser, Token, Val};
+use std::collections::HashMap;
+
+/// A variable used by a template
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableRef {
+    pub name: String,
+    /// Character offset of the tag that uses the variable
+    pub offset: usize,
+}
+
+/// List the variables a template uses, including ones nested inside function arguments
+pub fn variable_refs(template: &str) -> Result<Vec<VariableRef>> {
+    let mut parser = Parser::new(template);
+    let tokens = parser.parse()?;
+    let tags = tokens.tokens.iter().filter_map(|t| match t {
+        Token::Tag { val } => Some(val),
+        _ => None,
+    });
+
+    let mut refs = Vec::new();
+    for (val, offset) in tags.zip(parser.tag_offsets()) {
+        let mut names = Vec::new();
+        collect_variable_names(val, &mut names);
+        refs.extend(names.into_iter().map(|name| VariableRef { name, offset: *offset }));
+    }
+    Ok(refs)
+}
+
+fn collect_variable_names(val: &Val, names: &mut Vec<String>) {
+    match val {
+        Val::Var { name } => names.push(name.clone()),
+        Val::Fn { args, .. } => {
+            for arg in args {
+                collect_variable_names(&arg.value, names);
+            }
+        }
+        // String arguments are rendered as templates too
+        Val::Str { text } => {
+            names.extend(variable_refs(text).unwrap_or_default().into_iter().map(|r| r.name))
+        }
+        Val::Bool { .. } | Val::Null => {}
+    }
+}
+
+/// Build the graph of variables that reference each other and return an error for the first
+/// cycle found, like `a` → `b` → `a`. Variables that reference a missing variable aren't an error
+/// here, since they might never be rendered.
+pub fn check_variable_cycles(vars: &HashMap<String, String>) -> Result<()> {
+    let graph: HashMap<&str, Vec<String>> = vars
+        .iter()
+        .map(|(name, value)| {
+            let refs = variable_refs(value).unwrap_or_default();
+            let deps = refs.into_iter().map(|r| r.name).filter(|n| vars.contains_key(n));
+            (name.as_str(), deps.collect())
+        })
+        .collect();
+
+    // Sort so the same cycle is reported every time
+    let mut names: Vec<&str> = graph.keys().copied().collect();
+    names.sort();
+
+    let mut done: Vec<&str> = Vec::new();
+    for name in names {
+        let mut path = Vec::new();
+        if let Some(chain) = find_cycle(name, &graph, &mut path, &mut done) {
+            return Err(variable_cycle_error(chain, vars));
+        }
+    }
+    Ok(())
+}
+
+fn find_cycle<'a>(
+    name: &'a str,
+    graph: &'a HashMap<&'a str, Vec<String>>,
+    path: &mut Vec<&'a str>,
+    done: &mut Vec<&'a str>,
+) -> Option<Vec<String>> {
+    if let Some(start) = path.iter().position(|n| *n == name) {
+        let mut chain: Vec<String> = path[start..].iter().map(|n| n.to_string()).collect();
+        chain.push(name.to_string());
+        return Some(chain);
+    }
+    if done.contains(&name) {
+        return None;
+    }
+
+    path.push(name);
+    for dep in graph.get(name).into_iter().flatten() {
+        if let Some(chain) = find_cycle(dep, graph, path, done) {
+            return Some(chain);
+        }
+    }
+    path.pop();
+    done.push(name);
+    None
+}
+
+/// Describe a cycle like `["a", "b", "a"]`, pointing at the tag in `b` that uses `a` again
+pub(crate) fn variable_cycle_error(chain: Vec<String>, vars: &HashMap<String, String>) -> Error {
+    let (variable, target) = match chain.as_slice() {
+        [.., variable, target] => (variable.clone(), target.clone()),
+        _ => (chain.first().cloned().unwrap_or_default(), "".to_string()),
+    };
+    let offset = vars
+        .get(&variable)
+        .and_then(|value| variable_refs(value).ok())
+        .and_then(|refs| refs.into_iter().find(|r| r.name == target))
+        .map(|r| r.offset)
+        .unwrap_or_default();
+    VariableCycle { variable, chain, offset }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn finds_refs_with_offsets() -> Result<()> {
+        let refs = variable_refs("a ${[ foo ]} b ${[ hash(input=bar, encoding='${[ baz ]}') ]}")?;
+        let found: Vec<_> = refs.iter().map(|r| (r.name.as_str(), r.offset)).collect();
+        assert_eq!(found, vec![("foo", 2), ("bar", 15), ("baz", 15)]);
+        Ok(())
+    }
+
+    #[test]
+    fn reports_cycles_with_location() {
+        let vars = vars(&[
+            ("base", "https://example.com"),
+            ("a", "${[ base ]}/${[ b ]}"),
+            ("b", "prefix ${[ c ]}"),
+            ("c", "${[ base ]} ${[ a ]}"),
+        ]);
+        assert_eq!(
+            check_variable_cycles(&vars),
+            Err(VariableCycle {
+                variable: "c".to_string(),
+                chain: vec![
+                    "a".to_string(),
+                    "b".to_string(),
+                    "c".to_string(),
+                    "a".to_string()
+                ],
+                offset: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn allows_shared_dependencies() {
+        let vars = vars(&[
+            ("host", "example.com"),
+            ("a", "${[ host ]}"),
+            ("b", "${[ host ]}${[ a ]}${[ missing ]}"),
+        ]);
+        assert_eq!(check_variable_cycles(&vars), Ok(()));
+    }
+}
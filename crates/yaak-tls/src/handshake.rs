@@ -0,0 +1,233 @@
+use chrono::{DateTime, NaiveDateTime};
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, DistinguishedName, SignatureScheme};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use x509_parser::extensions::GeneralName;
+use x509_parser::parse_x509_certificate;
+use x509_parser::time::ASN1Time;
+use yaak_models::models::HttpResponseCertificate;
+
+/// What was learned about a server during its most recent full TLS handshake
+#[derive(Clone, Debug, Default)]
+pub struct TlsHandshake {
+    /// Negotiated protocol version, like `TLSv1.3`
+    pub version: Option<&'static str>,
+    /// Certificates the server presented, starting with its own
+    pub certificates: Vec<CertificateDer<'static>>,
+}
+
+/// Handshakes recorded by a TLS config, keyed by server name. Resumed sessions skip
+/// certificate verification, so they keep the details of the handshake that preceded them.
+#[derive(Clone, Debug, Default)]
+pub struct TlsHandshakes {
+    inner: Arc<Mutex<RecordedHandshakes>>,
+}
+
+#[derive(Debug, Default)]
+struct RecordedHandshakes {
+    by_server: HashMap<String, TlsHandshake>,
+    /// Server whose certificates were verified last, which the signature check that follows
+    /// in the same handshake belongs to
+    current: Option<String>,
+}
+
+impl TlsHandshakes {
+    /// The most recent handshake with a hostname or IP address
+    pub fn get(&self, server_name: &str) -> Option<TlsHandshake> {
+        let recorded = self.inner.lock().unwrap();
+        recorded.by_server.get(&server_name.to_lowercase()).cloned()
+    }
+
+    fn record_certificates(&self, server_name: String, certificates: Vec<CertificateDer<'static>>) {
+        let mut recorded = self.inner.lock().unwrap();
+        recorded
+            .by_server
+            .insert(server_name.clone(), TlsHandshake { version: None, certificates });
+        recorded.current = Some(server_name);
+    }
+
+    fn record_version(&self, version: &'static str) {
+        let mut recorded = self.inner.lock().unwrap();
+        if let Some(server_name) = recorded.current.take() {
+            if let Some(handshake) = recorded.by_server.get_mut(&server_name) {
+                handshake.version = Some(version);
+            }
+        }
+    }
+}
+
+/// Verifier that records each handshake before handing it to the verifier that decides
+/// whether the server is trusted
+#[derive(Debug)]
+pub(crate) struct RecordingVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    handshakes: TlsHandshakes,
+}
+
+impl RecordingVerifier {
+    pub(crate) fn new(inner: Arc<dyn ServerCertVerifier>, handshakes: TlsHandshakes) -> Self {
+        Self { inner, handshakes }
+    }
+}
+
+impl ServerCertVerifier for RecordingVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer,
+        intermediates: &[CertificateDer],
+        server_name: &ServerName,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let certificates = std::iter::once(end_entity)
+            .chain(intermediates)
+            .map(|c| c.clone().into_owned())
+            .collect();
+        self.handshakes.record_certificates(server_name_key(server_name), certificates);
+        self.inner.verify_server_cert(end_entity, intermediates, server_name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.handshakes.record_version("TLSv1.2");
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.handshakes.record_version("TLSv1.3");
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+
+    fn root_hint_subjects(&self) -> Option<&[DistinguishedName]> {
+        self.inner.root_hint_subjects()
+    }
+}
+
+fn server_name_key(server_name: &ServerName) -> String {
+    match server_name {
+        ServerName::DnsName(name) => name.as_ref().to_lowercase(),
+        ServerName::IpAddress(ip) => IpAddr::from(*ip).to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Summarize a DER certificate. Only the fingerprint is filled in when it can't be parsed.
+pub fn describe_certificate(der: &[u8]) -> HttpResponseCertificate {
+    let sha256_fingerprint = hex::encode(Sha256::digest(der));
+    let cert = match parse_x509_certificate(der) {
+        Ok((_, cert)) => cert,
+        Err(_) => return HttpResponseCertificate { sha256_fingerprint, ..Default::default() },
+    };
+
+    let subject_alt_names = match cert.subject_alternative_name() {
+        Ok(Some(san)) => san
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(dns) => Some(dns.to_string()),
+                GeneralName::IPAddress(bytes) => ip_from_bytes(bytes).map(|ip| ip.to_string()),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    HttpResponseCertificate {
+        subject: cert.subject().to_string(),
+        issuer: cert.issuer().to_string(),
+        subject_alt_names,
+        serial_number: cert.raw_serial_as_string(),
+        not_before: to_naive(cert.validity().not_before),
+        not_after: to_naive(cert.validity().not_after),
+        sha256_fingerprint,
+    }
+}
+
+fn to_naive(time: ASN1Time) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp(time.timestamp(), 0).map(|d| d.naive_utc())
+}
+
+fn ip_from_bytes(bytes: &[u8]) -> Option<IpAddr> {
+    match bytes.len() {
+        4 => Some(IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?)),
+        16 => Some(IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pem_certificates;
+
+    const CERT: &str = "-----BEGIN CERTIFICATE-----
+MIIB0jCCAXigAwIBAgICEjQwCgYIKoZIzj0EAwIwLjEYMBYGA1UEAwwPYXBpLmV4
+YW1wbGUuY29tMRIwEAYDVQQKDAlZYWFrIFRlc3QwHhcNMjYxMDE1MTY1OTI2WhcN
+MzYxMDEyMTY1OTI2WjAuMRgwFgYDVQQDDA9hcGkuZXhhbXBsZS5jb20xEjAQBgNV
+BAoMCVlhYWsgVGVzdDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABMRGaYtc+gl3
+n+H8WThXI4N0P3Q/aU9ct+UIK8Pb5Hns/XdXFIldEor65OYdeQpfc1gMDVbbELl8
+jlexVyVsG06jgYUwgYIwHQYDVR0OBBYEFPc96lASd7OyjGjGORXIwEMt6sntMB8G
+A1UdIwQYMBaAFPc96lASd7OyjGjGORXIwEMt6sntMA8GA1UdEwEB/wQFMAMBAf8w
+LwYDVR0RBCgwJoIPYXBpLmV4YW1wbGUuY29tgg0qLmV4YW1wbGUuY29thwQKAAAF
+MAoGCCqGSM49BAMCA0gAMEUCIQCSw6HMnW8AIHzdMzHK2GOxR0yHBMYUxgGoE8Xc
+dyJtfgIgKXL9f1cyONfmIYQ8vvHH27+5e84Hke/mFCm/BjXSkww=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn describes_certificate() {
+        let der = parse_pem_certificates(CERT.as_bytes()).remove(0);
+        let cert = describe_certificate(der.as_ref());
+
+        assert!(cert.subject.contains("CN=api.example.com"));
+        assert_eq!(cert.subject, cert.issuer);
+        assert_eq!(cert.subject_alt_names, vec!["api.example.com", "*.example.com", "10.0.0.5"]);
+        assert_eq!(cert.serial_number, "12:34");
+        assert_eq!(cert.not_before.unwrap().to_string(), "2026-10-15 16:59:26");
+        assert_eq!(cert.not_after.unwrap().to_string(), "2036-10-12 16:59:26");
+        assert!(cert.sha256_fingerprint.starts_with("e99bbb3a55da333f"));
+    }
+
+    #[test]
+    fn describes_unparseable_certificate() {
+        let cert = describe_certificate(&[1, 2, 3]);
+        assert_eq!(cert.subject, "");
+        assert_eq!(cert.sha256_fingerprint.len(), 64);
+    }
+
+    #[test]
+    fn records_version_for_verified_server() {
+        let handshakes = TlsHandshakes::default();
+        handshakes.record_certificates("api.example.com".to_string(), vec![]);
+        handshakes.record_version("TLSv1.3");
+
+        // A resumed handshake doesn't verify certificates, so its signature check is ignored
+        handshakes.record_version("TLSv1.2");
+
+        let handshake = handshakes.get("API.example.com").unwrap();
+        assert_eq!(handshake.version, Some("TLSv1.3"));
+        assert!(handshakes.get("other.example.com").is_none());
+    }
+}
@@ -5,7 +5,7 @@ use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, Server
 use rustls::crypto::ring;
 use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, UnixTime};
 use rustls::{ClientConfig, DigitallySignedStruct, SignatureScheme};
-use rustls_platform_verifier::BuilderVerifierExt;
+use rustls_platform_verifier::Verifier;
 use std::fs;
 use std::io::BufReader;
 use std::path::Path;
@@ -13,8 +13,11 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 pub mod error;
+mod handshake;
 
-#[derive(Clone, Default)]
+pub use handshake::{describe_certificate, TlsHandshake, TlsHandshakes};
+
+#[derive(Clone, Default, Hash)]
 pub struct ClientCertificateConfig {
     pub crt_file: Option<String>,
     pub key_file: Option<String>,
@@ -22,17 +25,53 @@ pub struct ClientCertificateConfig {
     pub passphrase: Option<String>,
 }
 
+/// A CA certificate to trust on top of the platform's store, from a PEM file or inline PEM
+#[derive(Clone, Debug, Default, PartialEq, Hash)]
+pub struct CaCertificateConfig {
+    pub file: Option<String>,
+    pub pem: Option<String>,
+}
+
 pub fn get_tls_config(
     validate_certificates: bool,
     with_alpn: bool,
     client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+) -> Result<ClientConfig> {
+    build_tls_config(validate_certificates, with_alpn, client_cert, ca_certificates, None)
+}
+
+/// Like [`get_tls_config`], but records the certificates and protocol version of each
+/// handshake so they can be shown alongside the response
+pub fn get_recording_tls_config(
+    validate_certificates: bool,
+    with_alpn: bool,
+    client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    handshakes: &TlsHandshakes,
+) -> Result<ClientConfig> {
+    build_tls_config(
+        validate_certificates,
+        with_alpn,
+        client_cert,
+        ca_certificates,
+        Some(handshakes),
+    )
+}
+
+fn build_tls_config(
+    validate_certificates: bool,
+    with_alpn: bool,
+    client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    handshakes: Option<&TlsHandshakes>,
 ) -> Result<ClientConfig> {
     let maybe_client_cert = load_client_cert(client_cert)?;
 
     let mut client = if validate_certificates {
-        build_with_validation(maybe_client_cert)
+        build_with_validation(maybe_client_cert, load_ca_certificates(ca_certificates)?, handshakes)
     } else {
-        build_without_validation(maybe_client_cert)
+        build_without_validation(maybe_client_cert, handshakes)
     }?;
 
     if with_alpn {
@@ -44,11 +83,21 @@ pub fn get_tls_config(
 
 fn build_with_validation(
     client_cert: Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>,
+    ca_certificates: Vec<CertificateDer<'static>>,
+    handshakes: Option<&TlsHandshakes>,
 ) -> Result<ClientConfig> {
     let arc_crypto_provider = Arc::new(ring::default_provider());
-    let builder = ClientConfig::builder_with_provider(arc_crypto_provider)
-        .with_safe_default_protocol_versions()?
-        .with_platform_verifier()?;
+    let builder = ClientConfig::builder_with_provider(arc_crypto_provider.clone())
+        .with_safe_default_protocol_versions()?;
+
+    let verifier = if ca_certificates.is_empty() {
+        Verifier::new(arc_crypto_provider)?
+    } else {
+        Verifier::new_with_extra_roots(ca_certificates, arc_crypto_provider)?
+    };
+    let builder = builder
+        .dangerous()
+        .with_custom_certificate_verifier(with_recording(Arc::new(verifier), handshakes));
 
     if let Some((certs, key)) = client_cert {
         return Ok(builder.with_client_auth_cert(certs, key)?);
@@ -59,12 +108,13 @@ fn build_with_validation(
 
 fn build_without_validation(
     client_cert: Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>,
+    handshakes: Option<&TlsHandshakes>,
 ) -> Result<ClientConfig> {
     let arc_crypto_provider = Arc::new(ring::default_provider());
     let builder = ClientConfig::builder_with_provider(arc_crypto_provider)
         .with_safe_default_protocol_versions()?
         .dangerous()
-        .with_custom_certificate_verifier(Arc::new(NoVerifier));
+        .with_custom_certificate_verifier(with_recording(Arc::new(NoVerifier), handshakes));
 
     if let Some((certs, key)) = client_cert {
         return Ok(builder.with_client_auth_cert(certs, key)?);
@@ -73,6 +123,47 @@ fn build_without_validation(
     Ok(builder.with_no_client_auth())
 }
 
+fn with_recording(
+    verifier: Arc<dyn ServerCertVerifier>,
+    handshakes: Option<&TlsHandshakes>,
+) -> Arc<dyn ServerCertVerifier> {
+    match handshakes {
+        Some(h) => Arc::new(handshake::RecordingVerifier::new(verifier, h.clone())),
+        None => verifier,
+    }
+}
+
+fn load_ca_certificates(
+    ca_certificates: &[CaCertificateConfig],
+) -> Result<Vec<CertificateDer<'static>>> {
+    let mut certs = Vec::new();
+    for config in ca_certificates {
+        if let Some(path) = config.file.as_deref().filter(|p| !p.is_empty()) {
+            let data = fs::read(Path::new(path))?;
+            let found = parse_pem_certificates(&data);
+            if found.is_empty() {
+                return Err(GenericError(format!("No certificates found in CA file {path}")));
+            }
+            certs.extend(found);
+        }
+
+        if let Some(pem) = config.pem.as_deref().filter(|p| !p.trim().is_empty()) {
+            let found = parse_pem_certificates(pem.as_bytes());
+            if found.is_empty() {
+                return Err(GenericError("No certificates found in CA PEM".to_string()));
+            }
+            certs.extend(found);
+        }
+    }
+
+    Ok(certs)
+}
+
+fn parse_pem_certificates(data: &[u8]) -> Vec<CertificateDer<'static>> {
+    let mut reader = BufReader::new(data);
+    rustls_pemfile::certs(&mut reader).filter_map(|r| r.ok()).collect()
+}
+
 fn load_client_cert(
     client_cert: Option<ClientCertificateConfig>,
 ) -> Result<Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>> {
@@ -103,10 +194,7 @@ fn load_pem_files(
     key_path: &str,
 ) -> Result<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)> {
     // Load certificates
-    let crt_file = fs::File::open(Path::new(crt_path))?;
-    let mut crt_reader = BufReader::new(crt_file);
-    let certs: Vec<CertificateDer<'static>> =
-        rustls_pemfile::certs(&mut crt_reader).filter_map(|r| r.ok()).collect();
+    let certs = parse_pem_certificates(&fs::read(Path::new(crt_path))?);
 
     if certs.is_empty() {
         return Err(GenericError("No certificates found in CRT file".to_string()));
@@ -265,13 +353,103 @@ pub fn find_client_certificate(
 
         // Found a matching certificate
         debug!("Found matching client certificate host={} port={}", host, port.unwrap_or(443));
-        return Some(ClientCertificateConfig {
-            crt_file: cert.crt_file.clone(),
-            key_file: cert.key_file.clone(),
-            pfx_file: cert.pfx_file.clone(),
-            passphrase: cert.passphrase.clone(),
-        });
+        return Some(client_certificate_config(cert));
     }
 
     None
 }
+
+/// The client certificate to present for a URL. A certificate set on the request or one of its
+/// ancestors is used for every host, and otherwise one is matched by host from `certificates`.
+pub fn select_client_certificate(
+    url_string: &str,
+    configured: Option<&yaak_models::models::ClientCertificate>,
+    certificates: &[yaak_models::models::ClientCertificate],
+) -> Option<ClientCertificateConfig> {
+    match configured {
+        Some(cert) if cert.enabled => Some(client_certificate_config(cert)),
+        Some(_) => None,
+        None => find_client_certificate(url_string, certificates),
+    }
+}
+
+fn client_certificate_config(
+    cert: &yaak_models::models::ClientCertificate,
+) -> ClientCertificateConfig {
+    ClientCertificateConfig {
+        crt_file: cert.crt_file.clone(),
+        key_file: cert.key_file.clone(),
+        pfx_file: cert.pfx_file.clone(),
+        passphrase: cert.passphrase.clone(),
+    }
+}
+
+/// The enabled CA certificates from a workspace, folder, or request's settings
+pub fn find_ca_certificates(
+    certificates: &[yaak_models::models::CaCertificate],
+) -> Vec<CaCertificateConfig> {
+    certificates
+        .iter()
+        .filter(|c| c.enabled)
+        .map(|c| CaCertificateConfig { file: c.file.clone(), pem: c.pem.clone() })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEM: &str = "-----BEGIN CERTIFICATE-----\nAQID\n-----END CERTIFICATE-----\n";
+
+    #[test]
+    fn loads_inline_and_file_ca_certificates() {
+        let path = std::env::temp_dir().join("yaak-tls-test-ca.pem");
+        fs::write(&path, format!("{PEM}{PEM}")).unwrap();
+
+        let certs = load_ca_certificates(&[
+            CaCertificateConfig { file: None, pem: Some(PEM.to_string()) },
+            CaCertificateConfig { file: Some(path.to_string_lossy().to_string()), pem: None },
+            CaCertificateConfig { file: Some(String::new()), pem: Some(" ".to_string()) },
+        ])
+        .unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(certs.len(), 3);
+        assert_eq!(certs[0].as_ref(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn prefers_configured_client_certificate_over_host_match() {
+        let cert = |host: &str, crt: &str, enabled: bool| yaak_models::models::ClientCertificate {
+            host: host.to_string(),
+            port: None,
+            crt_file: Some(crt.to_string()),
+            key_file: None,
+            pfx_file: None,
+            passphrase: None,
+            enabled,
+        };
+        let certificates = vec![cert("api.example.com", "host.crt", true)];
+        let url = "https://api.example.com";
+
+        let selected = select_client_certificate(url, None, &certificates).unwrap();
+        assert_eq!(selected.crt_file.as_deref(), Some("host.crt"));
+
+        let configured = cert("", "request.crt", true);
+        let selected = select_client_certificate(url, Some(&configured), &certificates).unwrap();
+        assert_eq!(selected.crt_file.as_deref(), Some("request.crt"));
+
+        // A disabled certificate on the request turns client certificates off entirely
+        let disabled = cert("", "request.crt", false);
+        assert!(select_client_certificate(url, Some(&disabled), &certificates).is_none());
+    }
+
+    #[test]
+    fn rejects_ca_without_certificates() {
+        let result = load_ca_certificates(&[CaCertificateConfig {
+            file: None,
+            pem: Some("not a certificate".to_string()),
+        }]);
+        assert!(result.is_err());
+    }
+}
@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import { WebsocketConnection } from "@yaakapp-internal/models";
+import { WebsocketConnection, WebsocketEvent } from "@yaakapp-internal/models";
 
 export function deleteWebsocketConnections(requestId: string) {
   return invoke("cmd_ws_delete_connections", {
@@ -41,3 +41,16 @@ export function sendWebsocket({
     environmentId,
   });
 }
+
+export function replayWebsocketEvent({
+  connectionId,
+  eventId,
+}: {
+  connectionId: string;
+  eventId: string;
+}) {
+  return invoke("cmd_ws_replay_event", {
+    connectionId,
+    eventId,
+  }) as Promise<WebsocketEvent>;
+}
@@ -1,16 +1,21 @@
 use crate::error::Result;
 use http::HeaderMap;
 use log::info;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::{
-    Connector, MaybeTlsStream, WebSocketStream, connect_async_tls_with_config,
+    Connector, MaybeTlsStream, WebSocketStream, client_async_tls_with_config,
+    connect_async_tls_with_config,
 };
-use yaak_tls::{ClientCertificateConfig, get_tls_config};
+use yaak_http::dns::parse_dns_overrides;
+use yaak_models::models::DnsOverride;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig, get_tls_config};
 
 // Enabling ALPN breaks websocket requests
 const WITH_ALPN: bool = false;
@@ -20,9 +25,12 @@ pub async fn ws_connect(
     headers: HeaderMap<HeaderValue>,
     validate_certificates: bool,
     client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: &[CaCertificateConfig],
+    dns_overrides: Vec<DnsOverride>,
 ) -> Result<(WebSocketStream<MaybeTlsStream<TcpStream>>, Response)> {
     info!("Connecting to WS {url}");
-    let tls_config = get_tls_config(validate_certificates, WITH_ALPN, client_cert.clone())?;
+    let tls_config =
+        get_tls_config(validate_certificates, WITH_ALPN, client_cert.clone(), ca_certificates)?;
 
     let mut req = url.into_client_request()?;
     let req_headers = req.headers_mut();
@@ -32,13 +40,28 @@ pub async fn ws_connect(
         }
     }
 
-    let (stream, response) = connect_async_tls_with_config(
-        req,
-        Some(WebSocketConfig::default()),
-        false,
-        Some(Connector::Rustls(Arc::new(tls_config))),
-    )
-    .await?;
+    // Overridden hostnames connect to fixed addresses, while TLS and the Host header still use
+    // the hostname from the URL
+    let overrides = parse_dns_overrides(dns_overrides);
+    let overridden = req.uri().host().and_then(|h| overrides.get(&h.to_lowercase()));
+    let connector = Some(Connector::Rustls(Arc::new(tls_config)));
+    let (stream, response) = match overridden {
+        Some(resolved) => {
+            let default_port = if req.uri().scheme_str() == Some("wss") { 443 } else { 80 };
+            let port = req.uri().port_u16().unwrap_or(default_port);
+            let addrs: Vec<SocketAddr> =
+                resolved.addresses().into_iter().map(|ip| SocketAddr::new(ip, port)).collect();
+            info!("Connecting to WS {url} through DNS override {addrs:?}");
+            let socket =
+                TcpStream::connect(addrs.as_slice()).await.map_err(tungstenite::Error::Io)?;
+            client_async_tls_with_config(req, socket, Some(WebSocketConfig::default()), connector)
+                .await?
+        }
+        None => {
+            connect_async_tls_with_config(req, Some(WebSocketConfig::default()), false, connector)
+                .await?
+        }
+    };
 
     info!(
         "Connected to WS {url} validate_certificates={} client_cert={}",
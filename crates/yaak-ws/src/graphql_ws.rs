@@ -0,0 +1,126 @@
+use serde_json::{Value, json};
+use url::Url;
+
+/// Subprotocol of the `graphql-ws` library, sent in the `Sec-WebSocket-Protocol` header
+pub const GRAPHQL_WS_PROTOCOL: &str = "graphql-transport-ws";
+
+/// Point a GraphQL endpoint URL at its WebSocket equivalent, so `https://` becomes `wss://`
+pub fn graphql_ws_url(url: &mut Url) {
+    let scheme = match url.scheme() {
+        "http" => "ws",
+        "https" => "wss",
+        _ => return,
+    };
+    // Switching between these special schemes always succeeds
+    let _ = url.set_scheme(scheme);
+}
+
+/// Message sent by the server over a `graphql-transport-ws` connection
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphQlWsMessage {
+    ConnectionAck,
+    Ping,
+    Pong,
+    /// A single execution result of the operation with this ID
+    Next {
+        id: String,
+        payload: Value,
+    },
+    /// The operation failed before or while executing, with a list of GraphQL errors
+    Error {
+        id: String,
+        payload: Value,
+    },
+    /// The server won't send any more results for the operation
+    Complete {
+        id: String,
+    },
+}
+
+impl GraphQlWsMessage {
+    pub fn decode(text: &str) -> Option<Self> {
+        let message: Value = serde_json::from_str(text).ok()?;
+        let id = || message.get("id").and_then(Value::as_str).unwrap_or_default().to_string();
+        let payload = || message.get("payload").cloned().unwrap_or(Value::Null);
+        Some(match message.get("type")?.as_str()? {
+            "connection_ack" => GraphQlWsMessage::ConnectionAck,
+            "ping" => GraphQlWsMessage::Ping,
+            "pong" => GraphQlWsMessage::Pong,
+            "next" => GraphQlWsMessage::Next { id: id(), payload: payload() },
+            "error" => GraphQlWsMessage::Error { id: id(), payload: payload() },
+            "complete" => GraphQlWsMessage::Complete { id: id() },
+            _ => return None,
+        })
+    }
+}
+
+/// First message of a connection, which the server answers with `connection_ack`
+pub fn graphql_ws_connection_init() -> String {
+    json!({ "type": "connection_init" }).to_string()
+}
+
+/// Start an operation. The payload holds the `query`, `variables` and `operationName`.
+pub fn graphql_ws_subscribe(id: &str, payload: Value) -> String {
+    json!({ "id": id, "type": "subscribe", "payload": payload }).to_string()
+}
+
+/// Stop an operation that is still running
+pub fn graphql_ws_complete(id: &str) -> String {
+    json!({ "id": id, "type": "complete" }).to_string()
+}
+
+pub fn graphql_ws_pong() -> String {
+    json!({ "type": "pong" }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_http_urls() {
+        let mut url = Url::parse("https://api.example.com/graphql?x=1").unwrap();
+        graphql_ws_url(&mut url);
+        assert_eq!(url.as_str(), "wss://api.example.com/graphql?x=1");
+
+        let mut url = Url::parse("http://localhost:4000/graphql").unwrap();
+        graphql_ws_url(&mut url);
+        assert_eq!(url.as_str(), "ws://localhost:4000/graphql");
+
+        let mut url = Url::parse("ws://localhost:4000/graphql").unwrap();
+        graphql_ws_url(&mut url);
+        assert_eq!(url.as_str(), "ws://localhost:4000/graphql");
+    }
+
+    #[test]
+    fn decodes_server_messages() {
+        assert_eq!(
+            GraphQlWsMessage::decode(r#"{"type":"connection_ack"}"#),
+            Some(GraphQlWsMessage::ConnectionAck)
+        );
+        assert_eq!(
+            GraphQlWsMessage::decode(r#"{"id":"1","type":"next","payload":{"data":{"n":1}}}"#),
+            Some(GraphQlWsMessage::Next { id: "1".to_string(), payload: json!({"data":{"n":1}}) })
+        );
+        assert_eq!(
+            GraphQlWsMessage::decode(r#"{"id":"1","type":"complete"}"#),
+            Some(GraphQlWsMessage::Complete { id: "1".to_string() })
+        );
+        assert_eq!(GraphQlWsMessage::decode(r#"{"type":"data"}"#), None);
+        assert_eq!(GraphQlWsMessage::decode("nope"), None);
+    }
+
+    #[test]
+    fn encodes_client_messages() {
+        let subscribe: Value = serde_json::from_str(&graphql_ws_subscribe(
+            "1",
+            json!({"query": "subscription { n }"}),
+        ))
+        .unwrap();
+        assert_eq!(
+            subscribe,
+            json!({"id": "1", "type": "subscribe", "payload": {"query": "subscription { n }"}})
+        );
+        assert_eq!(graphql_ws_complete("1"), r#"{"id":"1","type":"complete"}"#);
+    }
+}
@@ -1,12 +1,18 @@
 mod connect;
 pub mod error;
+pub mod graphql_ws;
 pub mod manager;
+mod message;
 pub mod render;
+pub mod socket_io;
 
 pub use connect::ws_connect;
-pub use manager::WebsocketManager;
+pub use manager::{ConnectionEvent, ReconnectPolicy, WebsocketManager};
+pub use message::websocket_message;
 pub use render::render_websocket_request;
+pub use socket_io::{SocketIoSession, socket_io_url};
 
 // Re-export http types needed by consumers
-pub use http::HeaderMap;
+pub use http::{HeaderMap, HeaderName};
+pub use tokio_tungstenite::tungstenite::Message;
 pub use tokio_tungstenite::tungstenite::http::HeaderValue;
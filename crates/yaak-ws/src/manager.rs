@@ -1,9 +1,12 @@
 use crate::connect::ws_connect;
+use crate::error::Error::GenericError;
 use crate::error::Result;
+use crate::socket_io::SocketIoSession;
 use futures_util::stream::SplitSink;
 use futures_util::{SinkExt, StreamExt};
 use http::HeaderMap;
 use log::{debug, info, warn};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -13,53 +16,190 @@ use tokio_tungstenite::tungstenite::Message;
 use tokio_tungstenite::tungstenite::handshake::client::Response;
 use tokio_tungstenite::tungstenite::http::HeaderValue;
 use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
-use yaak_tls::ClientCertificateConfig;
+use yaak_models::models::DnsOverride;
+use yaak_tls::{CaCertificateConfig, ClientCertificateConfig};
+
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// How to re-establish a connection that drops without being closed through the manager
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    /// Give up after this many failed attempts in a row, or never when zero
+    pub max_attempts: u32,
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    /// Sent once the connection is open, and again after every reconnect
+    pub handshake: Option<Message>,
+}
+
+impl ReconnectPolicy {
+    /// Delay before the given attempt (starting at 1), doubling each time up to the maximum
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    fn will_retry(&self, attempt: u32) -> bool {
+        self.max_attempts == 0 || attempt < self.max_attempts
+    }
+}
+
+/// Something that happened on a connection after it was opened
+#[derive(Debug)]
+pub enum ConnectionEvent {
+    /// A message received from the server
+    Message(Message),
+    /// A message sent by the manager itself, like the reconnect handshake
+    Sent(Message),
+    /// The connection dropped and will be retried after the delay
+    Reconnecting { attempt: u32, delay: Duration },
+    /// A reconnect attempt failed
+    ReconnectFailed {
+        attempt: u32,
+        error: String,
+        will_retry: bool,
+    },
+    /// The connection was re-established
+    Reconnected { attempt: u32, response: Response },
+}
+
+/// Everything needed to open the same connection again
+#[derive(Clone)]
+struct ConnectParams {
+    url: String,
+    headers: HeaderMap<HeaderValue>,
+    validate_certificates: bool,
+    client_cert: Option<ClientCertificateConfig>,
+    ca_certificates: Vec<CaCertificateConfig>,
+    dns_overrides: Vec<DnsOverride>,
+}
+
+impl ConnectParams {
+    async fn connect(&self) -> Result<(WsStream, Response)> {
+        ws_connect(
+            &self.url,
+            self.headers.clone(),
+            self.validate_certificates,
+            self.client_cert.clone(),
+            &self.ca_certificates,
+            self.dns_overrides.clone(),
+        )
+        .await
+    }
+}
 
 #[derive(Clone)]
 pub struct WebsocketManager {
-    connections:
-        Arc<Mutex<HashMap<String, SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>>,
+    connections: Arc<Mutex<HashMap<String, SplitSink<WsStream, Message>>>>,
     read_tasks: Arc<Mutex<HashMap<String, tokio::task::JoinHandle<()>>>>,
+    socket_io_sessions: Arc<Mutex<HashMap<String, SocketIoSession>>>,
 }
 
 impl WebsocketManager {
     pub fn new() -> Self {
-        WebsocketManager { connections: Default::default(), read_tasks: Default::default() }
+        WebsocketManager {
+            connections: Default::default(),
+            read_tasks: Default::default(),
+            socket_io_sessions: Default::default(),
+        }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn connect(
         &mut self,
         id: &str,
         url: &str,
         headers: HeaderMap<HeaderValue>,
-        receive_tx: mpsc::Sender<Message>,
+        receive_tx: mpsc::Sender<ConnectionEvent>,
         validate_certificates: bool,
         client_cert: Option<ClientCertificateConfig>,
+        ca_certificates: Vec<CaCertificateConfig>,
+        dns_overrides: Vec<DnsOverride>,
+        reconnect: Option<ReconnectPolicy>,
+        socket_io: Option<SocketIoSession>,
     ) -> Result<Response> {
         let tx = receive_tx.clone();
+        let params = ConnectParams {
+            url: url.to_string(),
+            headers,
+            validate_certificates,
+            client_cert,
+            ca_certificates,
+            dns_overrides,
+        };
 
-        let (stream, response) =
-            ws_connect(url, headers, validate_certificates, client_cert).await?;
-        let (write, mut read) = stream.split();
+        let (stream, response) = params.connect().await?;
+        let (mut write, mut read) = stream.split();
 
+        if let Some(handshake) = reconnect.as_ref().and_then(|r| r.handshake.clone()) {
+            send_handshake(&mut write, handshake, &tx).await;
+        }
         self.connections.lock().await.insert(id.to_string(), write);
+        if let Some(session) = socket_io.clone() {
+            self.socket_io_sessions.lock().await.insert(id.to_string(), session);
+        }
 
         let handle = {
             let connection_id = id.to_string();
             let connections = self.connections.clone();
             let read_tasks = self.read_tasks.clone();
+            let socket_io_sessions = self.socket_io_sessions.clone();
             tokio::task::spawn(async move {
-                while let Some(msg) = read.next().await {
-                    match msg {
-                        Err(e) => {
-                            warn!("Broken websocket connection: {}", e);
-                            break;
+                loop {
+                    while let Some(msg) = read.next().await {
+                        let message = match msg {
+                            Err(e) => {
+                                warn!("Broken websocket connection: {}", e);
+                                break;
+                            }
+                            Ok(message) => message,
+                        };
+
+                        let (record, reply) = match (&socket_io, &message) {
+                            (Some(session), Message::Text(text)) => session.respond(text),
+                            _ => (true, None),
+                        };
+                        if record {
+                            tx.send(ConnectionEvent::Message(message)).await.unwrap();
                         }
-                        Ok(message) => tx.send(message).await.unwrap(),
+                        if let Some(reply) = reply {
+                            let reply = Message::Text(reply.into());
+                            if let Some(write) = connections.lock().await.get_mut(&connection_id) {
+                                match write.send(reply.clone()).await {
+                                    Ok(()) if record => {
+                                        let _ = tx.send(ConnectionEvent::Sent(reply)).await;
+                                    }
+                                    Ok(()) => {}
+                                    Err(e) => warn!("Failed to reply to Socket.IO server: {e}"),
+                                }
+                            }
+                        }
+                    }
+
+                    // Closing through the manager removes the sink first, so one that's still
+                    // here means the connection dropped on its own
+                    let dropped = connections.lock().await.remove(&connection_id).is_some();
+                    let policy = match (&reconnect, dropped) {
+                        (Some(policy), true) => policy,
+                        _ => break,
+                    };
+                    let Some((attempt, stream, response)) =
+                        reconnect_with_backoff(&connection_id, &params, policy, &tx).await
+                    else {
+                        break;
+                    };
+
+                    let (mut write, new_read) = stream.split();
+                    read = new_read;
+                    let _ = tx.send(ConnectionEvent::Reconnected { attempt, response }).await;
+                    if let Some(handshake) = policy.handshake.clone() {
+                        send_handshake(&mut write, handshake, &tx).await;
                     }
+                    connections.lock().await.insert(connection_id.clone(), write);
                 }
                 debug!("Connection {} closed", connection_id);
                 connections.lock().await.remove(&connection_id);
+                socket_io_sessions.lock().await.remove(&connection_id);
                 read_tasks.lock().await.remove(&connection_id);
             })
         };
@@ -80,6 +220,24 @@ impl WebsocketManager {
         Ok(())
     }
 
+    /// Emit a Socket.IO event on a connection that was opened with a Socket.IO session, returning
+    /// the frame that was sent
+    pub async fn emit(
+        &mut self,
+        id: &str,
+        event: &str,
+        args: Vec<Value>,
+        ack: bool,
+    ) -> Result<Message> {
+        let packet = match self.socket_io_sessions.lock().await.get_mut(id) {
+            Some(session) => session.event(event, args, ack),
+            None => return Err(GenericError("Not a Socket.IO connection".to_string())),
+        };
+        let frame = Message::Text(packet.encode().into());
+        self.send(id, frame.clone()).await?;
+        Ok(frame)
+    }
+
     pub async fn close(&mut self, id: &str) -> Result<()> {
         info!("Closing websocket");
         if let Some(mut connection) = self.connections.lock().await.remove(id) {
@@ -95,7 +253,77 @@ impl WebsocketManager {
         if let Some(handle) = self.read_tasks.lock().await.remove(id) {
             handle.abort();
         }
+        self.socket_io_sessions.lock().await.remove(id);
+
+        // A reconnect may have finished while we were waiting
+        if let Some(mut connection) = self.connections.lock().await.remove(id) {
+            let _ = connection.close().await;
+        }
 
         Ok(())
     }
 }
+
+/// Keep trying to open the connection again, waiting longer after each failure. Returns `None`
+/// once the policy's attempts are used up.
+async fn reconnect_with_backoff(
+    connection_id: &str,
+    params: &ConnectParams,
+    policy: &ReconnectPolicy,
+    tx: &mpsc::Sender<ConnectionEvent>,
+) -> Option<(u32, WsStream, Response)> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let delay = policy.delay(attempt);
+        info!("Reconnecting websocket {connection_id} in {delay:?} (attempt {attempt})");
+        let _ = tx.send(ConnectionEvent::Reconnecting { attempt, delay }).await;
+        tokio::time::sleep(delay).await;
+
+        match params.connect().await {
+            Ok((stream, response)) => return Some((attempt, stream, response)),
+            Err(e) => {
+                let will_retry = policy.will_retry(attempt);
+                warn!("Failed to reconnect websocket {connection_id}: {e}");
+                let error = e.to_string();
+                let _ =
+                    tx.send(ConnectionEvent::ReconnectFailed { attempt, error, will_retry }).await;
+                if !will_retry {
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+async fn send_handshake(
+    write: &mut SplitSink<WsStream, Message>,
+    handshake: Message,
+    tx: &mpsc::Sender<ConnectionEvent>,
+) {
+    match write.send(handshake.clone()).await {
+        Ok(()) => {
+            let _ = tx.send(ConnectionEvent::Sent(handshake)).await;
+        }
+        Err(e) => warn!("Failed to send websocket handshake message: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backs_off_up_to_max_delay() {
+        let policy = ReconnectPolicy {
+            max_attempts: 0,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(3),
+            handshake: None,
+        };
+        let delays = (1..=5).map(|a| policy.delay(a).as_millis()).collect::<Vec<_>>();
+        assert_eq!(delays, vec![500, 1000, 2000, 3000, 3000]);
+        assert_eq!(policy.delay(100), Duration::from_secs(3));
+        assert!(policy.will_retry(1_000));
+    }
+}
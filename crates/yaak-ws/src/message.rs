@@ -0,0 +1,73 @@
+use crate::error::Error::GenericError;
+use crate::error::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use tokio_tungstenite::tungstenite::Message;
+use yaak_models::models::WebsocketMessageFormat;
+use yaak_templates::strip_json_comments::maybe_strip_json_comments;
+
+/// Build the frame for a request's message. Text is sent as a text frame, while every other
+/// format describes the bytes of a binary frame.
+pub async fn websocket_message(
+    format: WebsocketMessageFormat,
+    message: &str,
+    message_file: Option<&str>,
+) -> Result<Message> {
+    let bytes = match format {
+        WebsocketMessageFormat::Text => {
+            return Ok(Message::Text(maybe_strip_json_comments(message).into()));
+        }
+        WebsocketMessageFormat::Hex => {
+            let hex = message.split_whitespace().collect::<String>();
+            hex::decode(hex).map_err(|e| GenericError(format!("Invalid hex message: {e}")))?
+        }
+        WebsocketMessageFormat::Base64 => {
+            let b64 = message.split_whitespace().collect::<String>();
+            STANDARD
+                .decode(b64)
+                .map_err(|e| GenericError(format!("Invalid base64 message: {e}")))?
+        }
+        WebsocketMessageFormat::File => {
+            let path = match message_file {
+                Some(p) if !p.is_empty() => p,
+                _ => return Err(GenericError("No message file selected".to_string())),
+            };
+            tokio::fs::read(path)
+                .await
+                .map_err(|e| GenericError(format!("Failed to read message file {path}: {e}")))?
+        }
+    };
+    Ok(Message::Binary(bytes.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn bytes(format: WebsocketMessageFormat, message: &str) -> Vec<u8> {
+        match websocket_message(format, message, None).await.unwrap() {
+            Message::Binary(b) => b.to_vec(),
+            m => panic!("Expected a binary frame, got {m:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn decodes_binary_formats() {
+        assert_eq!(
+            bytes(WebsocketMessageFormat::Hex, "de ad\nBE EF").await,
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert_eq!(
+            bytes(WebsocketMessageFormat::Base64, "3q2+\n7w==").await,
+            [0xde, 0xad, 0xbe, 0xef]
+        );
+        assert!(websocket_message(WebsocketMessageFormat::Hex, "abc", None).await.is_err());
+        assert!(websocket_message(WebsocketMessageFormat::File, "", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn sends_text_as_text() {
+        let message = websocket_message(WebsocketMessageFormat::Text, "hello", None).await.unwrap();
+        assert_eq!(message, Message::Text("hello".into()));
+    }
+}
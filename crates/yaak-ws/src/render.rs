@@ -75,6 +75,29 @@ pub async fn render_websocket_request<T: TemplateCallback>(
     let url = parse_and_render(r.url.as_str(), vars, cb, opt).await?;
 
     let message = parse_and_render(&r.message.clone(), vars, cb, opt).await?;
+    let message_file = match &r.message_file {
+        Some(f) => Some(parse_and_render(f, vars, cb, opt).await?),
+        None => None,
+    };
+
+    let mut reconnect = r.reconnect.clone();
+    reconnect.handshake_message =
+        parse_and_render(&reconnect.handshake_message, vars, cb, opt).await?;
+
+    let mut socket_io = r.socket_io.clone();
+    socket_io.namespace = parse_and_render(&socket_io.namespace, vars, cb, opt).await?;
+    socket_io.event = parse_and_render(&socket_io.event, vars, cb, opt).await?;
+    socket_io.auth = parse_and_render(&socket_io.auth, vars, cb, opt).await?;
 
-    Ok(WebsocketRequest { url, url_parameters, headers, authentication, message, ..r.to_owned() })
+    Ok(WebsocketRequest {
+        url,
+        url_parameters,
+        headers,
+        authentication,
+        message,
+        message_file,
+        reconnect,
+        socket_io,
+        ..r.to_owned()
+    })
 }
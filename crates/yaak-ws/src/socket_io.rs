@@ -0,0 +1,267 @@
+use serde_json::{Value, json};
+use url::Url;
+
+/// Point a URL at a server's Socket.IO endpoint. The default `/socket.io/` path is used when the
+/// URL doesn't have one, and the Engine.IO query parameters are added unless already set.
+pub fn socket_io_url(url: &mut Url) {
+    if url.path().is_empty() || url.path() == "/" {
+        url.set_path("/socket.io/");
+    }
+    let has_param = |name: &str| url.query_pairs().any(|(n, _)| n == name);
+    let missing = [("EIO", "4"), ("transport", "websocket")]
+        .into_iter()
+        .filter(|(name, _)| !has_param(name))
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        url.query_pairs_mut().extend_pairs(missing);
+    }
+}
+
+/// Engine.IO packet, the transport layer Socket.IO packets are carried in
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnginePacket<'a> {
+    Open(&'a str),
+    Close,
+    Ping(&'a str),
+    Pong(&'a str),
+    Message(&'a str),
+    Upgrade,
+    Noop,
+}
+
+impl<'a> EnginePacket<'a> {
+    pub fn decode(frame: &'a str) -> Option<Self> {
+        let data = frame.get(1..).unwrap_or_default();
+        Some(match frame.chars().next()? {
+            '0' => EnginePacket::Open(data),
+            '1' => EnginePacket::Close,
+            '2' => EnginePacket::Ping(data),
+            '3' => EnginePacket::Pong(data),
+            '4' => EnginePacket::Message(data),
+            '5' => EnginePacket::Upgrade,
+            '6' => EnginePacket::Noop,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SocketIoPacketType {
+    Connect = 0,
+    Disconnect = 1,
+    Event = 2,
+    Ack = 3,
+    ConnectError = 4,
+    BinaryEvent = 5,
+    BinaryAck = 6,
+}
+
+/// Socket.IO packet, as carried in an Engine.IO message
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocketIoPacket {
+    pub packet_type: SocketIoPacketType,
+    pub namespace: String,
+    pub ack_id: Option<u64>,
+    pub data: Option<Value>,
+}
+
+impl SocketIoPacket {
+    pub fn connect(namespace: &str, auth: Option<Value>) -> Self {
+        Self {
+            packet_type: SocketIoPacketType::Connect,
+            namespace: namespace.to_string(),
+            ack_id: None,
+            data: auth,
+        }
+    }
+
+    /// An event with its arguments. The server acknowledges it when an ack ID is given.
+    pub fn event(namespace: &str, event: &str, args: Vec<Value>, ack_id: Option<u64>) -> Self {
+        let mut data = vec![json!(event)];
+        data.extend(args);
+        Self {
+            packet_type: SocketIoPacketType::Event,
+            namespace: namespace.to_string(),
+            ack_id,
+            data: Some(Value::Array(data)),
+        }
+    }
+
+    pub fn ack(namespace: &str, ack_id: u64, args: Vec<Value>) -> Self {
+        Self {
+            packet_type: SocketIoPacketType::Ack,
+            namespace: namespace.to_string(),
+            ack_id: Some(ack_id),
+            data: Some(Value::Array(args)),
+        }
+    }
+
+    /// Encode as an Engine.IO message frame
+    pub fn encode(&self) -> String {
+        let mut frame = format!("4{}", self.packet_type as u8);
+        if !self.namespace.is_empty() && self.namespace != "/" {
+            frame.push_str(&self.namespace);
+            frame.push(',');
+        }
+        if let Some(id) = self.ack_id {
+            frame.push_str(&id.to_string());
+        }
+        if let Some(data) = &self.data {
+            frame.push_str(&data.to_string());
+        }
+        frame
+    }
+
+    /// Decode the data of an Engine.IO message frame
+    pub fn decode(message: &str) -> Option<Self> {
+        let packet_type = match message.chars().next()? {
+            '0' => SocketIoPacketType::Connect,
+            '1' => SocketIoPacketType::Disconnect,
+            '2' => SocketIoPacketType::Event,
+            '3' => SocketIoPacketType::Ack,
+            '4' => SocketIoPacketType::ConnectError,
+            '5' => SocketIoPacketType::BinaryEvent,
+            '6' => SocketIoPacketType::BinaryAck,
+            _ => return None,
+        };
+        let mut rest = &message[1..];
+
+        // Binary packets say how many attachments follow, like `51-`
+        if matches!(packet_type, SocketIoPacketType::BinaryEvent | SocketIoPacketType::BinaryAck) {
+            let (_, after) = rest.split_once('-')?;
+            rest = after;
+        }
+
+        let mut namespace = "/";
+        if rest.starts_with('/') {
+            let (ns, after) = rest.split_once(',').unwrap_or((rest, ""));
+            namespace = ns;
+            rest = after;
+        }
+
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        let ack_id = match digits {
+            0 => None,
+            n => Some(rest[..n].parse().ok()?),
+        };
+        rest = &rest[digits..];
+
+        let data = match rest {
+            "" => None,
+            d => Some(serde_json::from_str(d).ok()?),
+        };
+
+        Some(Self { packet_type, namespace: namespace.to_string(), ack_id, data })
+    }
+}
+
+/// A Socket.IO connection's namespace and the state needed to emit events on it
+#[derive(Debug, Clone)]
+pub struct SocketIoSession {
+    pub namespace: String,
+    /// Sent with the namespace connect packet
+    pub auth: Option<Value>,
+    next_ack_id: u64,
+}
+
+impl SocketIoSession {
+    pub fn new(namespace: &str, auth: Option<Value>) -> Self {
+        let namespace = match namespace.trim() {
+            "" => "/".to_string(),
+            ns if ns.starts_with('/') => ns.to_string(),
+            ns => format!("/{ns}"),
+        };
+        Self { namespace, auth, next_ack_id: 0 }
+    }
+
+    pub(crate) fn event(&mut self, event: &str, args: Vec<Value>, ack: bool) -> SocketIoPacket {
+        let ack_id = ack.then(|| {
+            let id = self.next_ack_id;
+            self.next_ack_id += 1;
+            id
+        });
+        SocketIoPacket::event(&self.namespace, event, args, ack_id)
+    }
+
+    /// Work out how to respond to a frame from the server. Returns whether the frame belongs in
+    /// the history, and a frame to send back, if any. Replies are only recorded along with the
+    /// frame that caused them, which keeps Engine.IO pings out of the history.
+    pub(crate) fn respond(&self, frame: &str) -> (bool, Option<String>) {
+        match EnginePacket::decode(frame) {
+            // The server is ready, so join the namespace
+            Some(EnginePacket::Open(_)) => {
+                (true, Some(SocketIoPacket::connect(&self.namespace, self.auth.clone()).encode()))
+            }
+            Some(EnginePacket::Ping(data)) => (false, Some(format!("3{data}"))),
+            Some(EnginePacket::Message(message)) => match SocketIoPacket::decode(message) {
+                // Acknowledge server events that ask for it, so the server isn't left waiting
+                Some(SocketIoPacket {
+                    packet_type: SocketIoPacketType::Event,
+                    ack_id: Some(id),
+                    namespace,
+                    ..
+                }) => (true, Some(SocketIoPacket::ack(&namespace, id, Vec::new()).encode())),
+                _ => (true, None),
+            },
+            _ => (true, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_socket_io_url() {
+        let mut url = Url::parse("wss://example.com").unwrap();
+        socket_io_url(&mut url);
+        assert_eq!(url.as_str(), "wss://example.com/socket.io/?EIO=4&transport=websocket");
+
+        let mut url = Url::parse("ws://localhost:3000/custom/?EIO=3&token=x").unwrap();
+        socket_io_url(&mut url);
+        assert_eq!(url.as_str(), "ws://localhost:3000/custom/?EIO=3&token=x&transport=websocket");
+    }
+
+    #[test]
+    fn encodes_and_decodes_packets() {
+        let packet =
+            SocketIoPacket::event("/chat", "message", vec![json!({"text": "hi"})], Some(12));
+        let frame = packet.encode();
+        assert_eq!(frame, r#"42/chat,12["message",{"text":"hi"}]"#);
+        assert_eq!(SocketIoPacket::decode(&frame[1..]), Some(packet));
+
+        assert_eq!(SocketIoPacket::connect("/", None).encode(), "40");
+        assert_eq!(
+            SocketIoPacket::connect("/admin", Some(json!({"token": "t"}))).encode(),
+            r#"40/admin,{"token":"t"}"#
+        );
+
+        let ack = SocketIoPacket::decode(r#"3/chat,7["ok"]"#).unwrap();
+        assert_eq!(ack.packet_type, SocketIoPacketType::Ack);
+        assert_eq!(ack.namespace, "/chat");
+        assert_eq!(ack.ack_id, Some(7));
+
+        let binary = SocketIoPacket::decode(r#"51-["upload",{"_placeholder":true,"num":0}]"#);
+        assert_eq!(binary.unwrap().packet_type, SocketIoPacketType::BinaryEvent);
+        assert_eq!(SocketIoPacket::decode("2not json"), None);
+    }
+
+    #[test]
+    fn responds_to_server_frames() {
+        let mut session = SocketIoSession::new("chat", None);
+        assert_eq!(session.namespace, "/chat");
+
+        assert_eq!(session.respond(r#"0{"sid":"abc"}"#), (true, Some("40/chat,".to_string())));
+        assert_eq!(session.respond("2"), (false, Some("3".to_string())));
+        assert_eq!(
+            session.respond(r#"42/chat,5["ping"]"#),
+            (true, Some("43/chat,5[]".to_string()))
+        );
+        assert_eq!(session.respond(r#"42/chat,["news"]"#), (true, None));
+
+        assert_eq!(session.event("hello", vec![], true).ack_id, Some(0));
+        assert_eq!(session.event("hello", vec![], true).ack_id, Some(1));
+        assert_eq!(session.event("hello", vec![], false).ack_id, None);
+    }
+}
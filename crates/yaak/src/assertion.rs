@@ -0,0 +1,138 @@
+use crate::jsonpath::JsonPath;
+use regex::Regex;
+use serde_json::Value;
+use yaak_models::models::{Assertion, AssertionKind, AssertionResult, HttpResponse};
+
+/// Check a request's enabled assertions against one of its completed responses. Assertions that
+/// can't be evaluated (eg. a body that isn't JSON) fail with a message saying why.
+pub fn evaluate_assertions(
+    assertions: &[Assertion],
+    response: &HttpResponse,
+    body: &[u8],
+) -> Vec<AssertionResult> {
+    assertions.iter().filter(|a| a.enabled).map(|a| evaluate_assertion(a, response, body)).collect()
+}
+
+fn evaluate_assertion(
+    assertion: &Assertion,
+    response: &HttpResponse,
+    body: &[u8],
+) -> AssertionResult {
+    let path = assertion.path.trim();
+    let expected = assertion.value.trim();
+    let (name, outcome) = match assertion.kind {
+        AssertionKind::StatusEquals => {
+            let actual = response.status.to_string();
+            (
+                format!("status == {expected}"),
+                Ok((actual == expected, format!("Status was {actual}"))),
+            )
+        }
+        AssertionKind::JsonPathEquals => (
+            format!("{path} == {expected}"),
+            query_json(path, body).map(|actual| {
+                let passed = value_to_string(&actual) == expected
+                    || serde_json::from_str::<Value>(expected).is_ok_and(|v| v == actual);
+                (passed, format!("Value was {}", value_to_string(&actual)))
+            }),
+        ),
+        AssertionKind::JsonPathMatches => {
+            let outcome = match Regex::new(expected) {
+                Ok(re) => query_json(path, body).map(|actual| {
+                    let actual = value_to_string(&actual);
+                    (re.is_match(&actual), format!("Value was {actual}"))
+                }),
+                Err(e) => Err(format!("Invalid regular expression: {e}")),
+            };
+            (format!("{path} matches {expected}"), outcome)
+        }
+        AssertionKind::HeaderExists => (
+            format!("header {path} exists"),
+            Ok(match response.headers.iter().find(|h| h.name.eq_ignore_ascii_case(path)) {
+                Some(h) => (true, format!("Value was {}", h.value)),
+                None => (false, "Header not found".to_string()),
+            }),
+        ),
+        AssertionKind::LatencyUnder => (
+            format!("latency < {expected} ms"),
+            expected
+                .parse::<i32>()
+                .map_err(|_| format!("Invalid latency {expected}"))
+                .map(|max| (response.elapsed < max, format!("Took {} ms", response.elapsed))),
+        ),
+    };
+
+    let (passed, message) = outcome.unwrap_or_else(|message| (false, message));
+    AssertionResult { assertion_id: assertion.id.clone(), name, passed, message }
+}
+
+/// Select the first value matching a JSONPath expression in a JSON body
+fn query_json(path: &str, body: &[u8]) -> Result<Value, String> {
+    let json_path = JsonPath::parse(path).map_err(|e| e.to_string())?;
+    let body: Value =
+        serde_json::from_slice(body).map_err(|e| format!("Response is not valid JSON: {e}"))?;
+    json_path.query(&body).first().map(|v| (*v).clone()).ok_or(format!("Nothing matched {path}"))
+}
+
+fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::models::HttpResponseHeader;
+
+    fn assertion(kind: AssertionKind, path: &str, value: &str) -> Assertion {
+        Assertion {
+            enabled: true,
+            kind,
+            path: path.to_string(),
+            value: value.to_string(),
+            id: None,
+        }
+    }
+
+    #[test]
+    fn evaluates_assertions() {
+        let response = HttpResponse {
+            status: 200,
+            elapsed: 120,
+            headers: vec![HttpResponseHeader {
+                name: "Content-Type".to_string(),
+                value: "application/json".to_string(),
+            }],
+            ..Default::default()
+        };
+        let body = br#"{"user": {"id": 42, "email": "a@example.com", "active": true}}"#;
+        let assertions = vec![
+            assertion(AssertionKind::StatusEquals, "", "200"),
+            assertion(AssertionKind::StatusEquals, "", "201"),
+            assertion(AssertionKind::JsonPathEquals, "$.user.id", "42"),
+            assertion(AssertionKind::JsonPathEquals, "$.user.active", "true"),
+            assertion(AssertionKind::JsonPathEquals, "$.user.email", r#""a@example.com""#),
+            assertion(AssertionKind::JsonPathMatches, "$.user.email", r"@example\.com$"),
+            assertion(AssertionKind::JsonPathMatches, "$.missing", ".*"),
+            assertion(AssertionKind::HeaderExists, "content-type", ""),
+            assertion(AssertionKind::HeaderExists, "x-missing", ""),
+            assertion(AssertionKind::LatencyUnder, "", "500"),
+            assertion(AssertionKind::LatencyUnder, "", "100"),
+            Assertion { enabled: false, ..assertion(AssertionKind::StatusEquals, "", "500") },
+        ];
+
+        let results = evaluate_assertions(&assertions, &response, body);
+        let passed: Vec<bool> = results.iter().map(|r| r.passed).collect();
+        assert_eq!(
+            passed,
+            vec![
+                true, false, true, true, true, true, false, true, false, true, false
+            ]
+        );
+        assert_eq!(results[1].name, "status == 201");
+        assert_eq!(results[1].message, "Status was 200");
+        assert_eq!(results[6].message, "Nothing matched $.missing");
+    }
+}
@@ -0,0 +1,122 @@
+use crate::render::render_http_request;
+use crate::send::{
+    Result, SendHttpRequestError, apply_plugin_authentication, resolve_environment_chain,
+    resolve_inherited_request,
+};
+use std::sync::Arc;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_http::codegen::{CODEGEN_LANGUAGES, generate_snippet};
+use yaak_http::types::{SendableHttpRequest, SendableHttpRequestOptions};
+use yaak_models::models::{HttpRequest, HttpRequestHeader};
+use yaak_models::query_manager::QueryManager;
+use yaak_plugins::events::{CodegenLanguage, PluginContext, RenderPurpose};
+use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::RenderOptions;
+
+pub struct ExportAsSnippetParams<'a> {
+    pub query_manager: &'a QueryManager,
+    pub request_id: &'a str,
+    pub environment_id: Option<&'a str>,
+    pub plugin_manager: Arc<PluginManager>,
+    pub encryption_manager: Arc<EncryptionManager>,
+    pub plugin_context: &'a PluginContext,
+    /// Name of a language from `list_codegen_languages()`
+    pub language: &'a str,
+}
+
+/// Render a request as a code snippet in the given language, exactly as it would be sent, with
+/// inherited headers and parameters, rendered templates, and authentication applied. Languages
+/// not built in are handed to plugins.
+pub async fn export_as_snippet(params: ExportAsSnippetParams<'_>) -> Result<String> {
+    let request = params
+        .query_manager
+        .connect()
+        .get_http_request(params.request_id)
+        .map_err(SendHttpRequestError::LoadRequest)?;
+    let environment_chain =
+        resolve_environment_chain(params.query_manager, &request, params.environment_id)?;
+    let (resolved_request, auth_context_id) =
+        resolve_inherited_request(params.query_manager, &request)?;
+
+    let template_callback = PluginTemplateCallback::new(
+        params.plugin_manager.clone(),
+        params.encryption_manager.clone(),
+        params.plugin_context,
+        RenderPurpose::Send,
+    );
+    let rendered_request = render_http_request(
+        &resolved_request,
+        environment_chain,
+        &template_callback,
+        &RenderOptions::throw(),
+    )
+    .await
+    .map_err(SendHttpRequestError::RenderRequest)?;
+
+    // Authentication plugins only modify the URL and headers, so the body isn't needed
+    let mut sendable_request = SendableHttpRequest::from_http_request_without_body(
+        &rendered_request,
+        SendableHttpRequestOptions::default(),
+    );
+    apply_plugin_authentication(
+        &mut sendable_request,
+        &rendered_request,
+        &auth_context_id,
+        params.query_manager,
+        &params.plugin_manager,
+        params.encryption_manager.secrets(),
+        params.plugin_context,
+    )
+    .await
+    .map_err(SendHttpRequestError::PrepareSendableRequest)?;
+
+    let request = HttpRequest {
+        url: sendable_request.url,
+        url_parameters: Vec::new(),
+        headers: sendable_request
+            .headers
+            .into_iter()
+            .map(|(name, value)| HttpRequestHeader { enabled: true, name, value, id: None })
+            .collect(),
+        ..rendered_request
+    };
+
+    if let Some(snippet) = generate_snippet(params.language, &request) {
+        return Ok(snippet);
+    }
+
+    params
+        .plugin_manager
+        .call_codegen(params.plugin_context, params.language, request)
+        .await
+        .map_err(|e| SendHttpRequestError::PrepareSendableRequest(e.to_string()))?
+        .ok_or_else(|| {
+            SendHttpRequestError::PrepareSendableRequest(format!(
+                "No code generator for language {}",
+                params.language
+            ))
+        })
+}
+
+/// List the languages snippets can be generated in, built-in languages first. Plugin languages
+/// that share a name with an earlier one are skipped.
+pub async fn list_codegen_languages(
+    plugin_manager: &PluginManager,
+    plugin_context: &PluginContext,
+) -> Vec<CodegenLanguage> {
+    let mut languages: Vec<CodegenLanguage> = CODEGEN_LANGUAGES
+        .iter()
+        .map(|l| CodegenLanguage { name: l.name.to_string(), label: l.label.to_string() })
+        .collect();
+
+    // A plugin failing to answer shouldn't hide the built-in languages
+    let responses = plugin_manager.get_codegen_languages(plugin_context).await.unwrap_or_default();
+    for language in responses.into_iter().flat_map(|r| r.languages) {
+        if !languages.iter().any(|l| l.name == language.name) {
+            languages.push(language);
+        }
+    }
+
+    languages
+}
@@ -0,0 +1,31 @@
+use crate::codegen::{ExportAsSnippetParams, export_as_snippet};
+use crate::send::Result;
+use std::sync::Arc;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_models::query_manager::QueryManager;
+use yaak_plugins::events::PluginContext;
+use yaak_plugins::manager::PluginManager;
+
+pub struct ExportAsCurlParams<'a> {
+    pub query_manager: &'a QueryManager,
+    pub request_id: &'a str,
+    pub environment_id: Option<&'a str>,
+    pub plugin_manager: Arc<PluginManager>,
+    pub encryption_manager: Arc<EncryptionManager>,
+    pub plugin_context: &'a PluginContext,
+}
+
+/// Export a request as a `curl` command exactly as it would be sent, with inherited headers and
+/// parameters, rendered templates, and authentication applied.
+pub async fn export_as_curl(params: ExportAsCurlParams<'_>) -> Result<String> {
+    export_as_snippet(ExportAsSnippetParams {
+        query_manager: params.query_manager,
+        request_id: params.request_id,
+        environment_id: params.environment_id,
+        plugin_manager: params.plugin_manager,
+        encryption_manager: params.encryption_manager,
+        plugin_context: params.plugin_context,
+        language: "curl",
+    })
+    .await
+}
@@ -4,6 +4,21 @@ use thiserror::Error;
 pub enum Error {
     #[error(transparent)]
     Send(#[from] crate::send::SendHttpRequestError),
+
+    #[error(transparent)]
+    Model(#[from] yaak_models::error::Error),
+
+    #[error("{0}")]
+    GraphQlIntrospection(String),
+
+    #[error("{0}")]
+    ResponseFilter(String),
+
+    #[error("{0}")]
+    DataFile(String),
+
+    #[error("{0}")]
+    MockServer(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
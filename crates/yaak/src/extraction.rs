@@ -0,0 +1,228 @@
+use crate::error::Error::ResponseFilter;
+use crate::error::Result;
+use crate::jsonpath::JsonPath;
+use crate::xpath::extract_markup_value;
+use log::warn;
+use serde_json::Value;
+use yaak_models::models::{
+    HttpRequest, HttpResponse, HttpResponseEvent, HttpResponseEventData, ResponseExtraction,
+    ResponseExtractionSource, ResponseExtractionTarget,
+};
+use yaak_models::query_manager::QueryManager;
+use yaak_models::util::UpdateSource;
+
+/// Run a request's enabled extraction rules against one of its responses, writing each value to
+/// its target. The outcome of every rule is recorded as an event on the response, so a rule
+/// that fails doesn't fail the request.
+pub fn apply_response_extractions(
+    query_manager: &QueryManager,
+    request: &HttpRequest,
+    response: &HttpResponse,
+    environment_id: Option<&str>,
+    source: &UpdateSource,
+) {
+    for extraction in request.response_extractions.iter().filter(|e| e.enabled) {
+        if extraction.name.is_empty() {
+            continue;
+        }
+
+        let message = match extract_response_value(extraction, response).and_then(|value| {
+            store_extracted_value(
+                query_manager,
+                extraction,
+                &value,
+                request,
+                environment_id,
+                source,
+            )
+        }) {
+            Ok(target) => format!("Extracted {} into {target}", extraction.name),
+            Err(err) => format!("Failed to extract {}: {err}", extraction.name),
+        };
+
+        let event = HttpResponseEvent::new(
+            &response.id,
+            &response.workspace_id,
+            HttpResponseEventData::Info { message },
+        );
+        if let Err(err) = query_manager.connect().upsert_http_response_event(&event, source) {
+            warn!("Failed to persist response extraction event: {}", err);
+        }
+    }
+}
+
+/// Read the value a rule selects from a response. Body rules use XPath for XML and HTML bodies
+/// and JSONPath for everything else, taking the first match.
+pub fn extract_response_value(
+    extraction: &ResponseExtraction,
+    response: &HttpResponse,
+) -> Result<String> {
+    match extraction.source {
+        ResponseExtractionSource::Status => Ok(response.status.to_string()),
+        ResponseExtractionSource::Header => response
+            .headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case(extraction.path.trim()))
+            .map(|h| h.value.clone())
+            .ok_or_else(|| ResponseFilter(format!("Header {} not found", extraction.path))),
+        ResponseExtractionSource::Body => {
+            let body_path = response
+                .body_path
+                .as_ref()
+                .ok_or_else(|| ResponseFilter("Response has no body".to_string()))?;
+            let body = std::fs::read(body_path)
+                .map_err(|e| ResponseFilter(format!("Failed to read response body: {e}")))?;
+
+            let content_type = response
+                .headers
+                .iter()
+                .find(|h| h.name.eq_ignore_ascii_case("content-type"))
+                .map(|h| h.value.to_lowercase())
+                .unwrap_or_default();
+            let value = if content_type.contains("xml") || content_type.contains("html") {
+                let body = String::from_utf8_lossy(&body);
+                extract_markup_value(&body, &extraction.path, content_type.contains("html"))?
+            } else {
+                let path = JsonPath::parse(&extraction.path)?;
+                let body: Value = serde_json::from_slice(&body)
+                    .map_err(|e| ResponseFilter(format!("Response is not valid JSON: {e}")))?;
+                path.query(&body).first().map(|v| match v {
+                    Value::String(s) => s.clone(),
+                    v => v.to_string(),
+                })
+            };
+            value.ok_or_else(|| ResponseFilter(format!("Nothing matched {}", extraction.path)))
+        }
+    }
+}
+
+/// Write an extracted value, returning a description of where it went
+fn store_extracted_value(
+    query_manager: &QueryManager,
+    extraction: &ResponseExtraction,
+    value: &str,
+    request: &HttpRequest,
+    environment_id: Option<&str>,
+    source: &UpdateSource,
+) -> Result<String> {
+    let db = query_manager.connect();
+    match extraction.target {
+        ResponseExtractionTarget::Chained => {
+            db.set_chained_value(&request.workspace_id, &extraction.name, value, source);
+            Ok("chained values".to_string())
+        }
+        ResponseExtractionTarget::Environment => {
+            let environment = match environment_id {
+                Some(id) => db.get_environment(id)?,
+                None => db.get_base_environment(&request.workspace_id)?,
+            };
+            let environment =
+                db.set_environment_variable(&environment, &extraction.name, value, source)?;
+            Ok(format!("environment {}", environment.name))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::{HttpResponseHeader, Workspace};
+
+    fn response(dir: &tempfile::TempDir, content_type: &str, body: &str) -> HttpResponse {
+        let body_path = dir.path().join("body");
+        std::fs::write(&body_path, body).unwrap();
+        HttpResponse {
+            status: 201,
+            headers: vec![
+                HttpResponseHeader {
+                    name: "Content-Type".to_string(),
+                    value: content_type.to_string(),
+                },
+                HttpResponseHeader { name: "X-Request-Id".to_string(), value: "abc".to_string() },
+            ],
+            body_path: Some(body_path.to_string_lossy().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn extraction(source: ResponseExtractionSource, path: &str) -> ResponseExtraction {
+        ResponseExtraction {
+            enabled: true,
+            name: "value".to_string(),
+            source,
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn extracts_values_from_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let json = response(&dir, "application/json", r#"{"auth": {"token": "t0k", "ttl": 60}}"#);
+        let extract = |source, path: &str, response: &HttpResponse| {
+            extract_response_value(&extraction(source, path), response)
+        };
+
+        assert_eq!(extract(ResponseExtractionSource::Body, "$.auth.token", &json).unwrap(), "t0k");
+        assert_eq!(extract(ResponseExtractionSource::Body, "auth.ttl", &json).unwrap(), "60");
+        assert!(extract(ResponseExtractionSource::Body, "$.missing", &json).is_err());
+        assert_eq!(extract(ResponseExtractionSource::Status, "", &json).unwrap(), "201");
+        assert_eq!(
+            extract(ResponseExtractionSource::Header, "x-request-id", &json).unwrap(),
+            "abc"
+        );
+        assert!(extract(ResponseExtractionSource::Header, "x-missing", &json).is_err());
+
+        let xml = response(&dir, "text/xml", "<session><token>x1</token></session>");
+        assert_eq!(extract(ResponseExtractionSource::Body, "//token", &xml).unwrap(), "x1");
+    }
+
+    #[test]
+    fn writes_extracted_values_to_targets() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let source = UpdateSource::Sync;
+        let db = query_manager.connect();
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let request = HttpRequest {
+            workspace_id: workspace.id.clone(),
+            response_extractions: vec![
+                ResponseExtraction {
+                    name: "token".to_string(),
+                    ..extraction(ResponseExtractionSource::Body, "$.token")
+                },
+                ResponseExtraction {
+                    name: "status".to_string(),
+                    target: ResponseExtractionTarget::Chained,
+                    ..extraction(ResponseExtractionSource::Status, "")
+                },
+                ResponseExtraction {
+                    enabled: false,
+                    name: "disabled".to_string(),
+                    ..extraction(ResponseExtractionSource::Status, "")
+                },
+            ],
+            ..Default::default()
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let response = response(&dir, "application/json", r#"{"token": "secret"}"#);
+        drop(db);
+        apply_response_extractions(&query_manager, &request, &response, None, &source);
+
+        let db = query_manager.connect();
+        let base = db.get_base_environment(&workspace.id).unwrap();
+        let variables: Vec<(&str, &str)> =
+            base.variables.iter().map(|v| (v.name.as_str(), v.value.as_str())).collect();
+        assert_eq!(variables, vec![("token", "secret")]);
+
+        let chained = db.list_chained_values(&workspace.id).unwrap();
+        assert_eq!(chained.len(), 1);
+        assert_eq!((chained[0].name.as_str(), chained[0].value.as_str()), ("status", "201"));
+    }
+}
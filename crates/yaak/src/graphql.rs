@@ -0,0 +1,357 @@
+//! GraphQL schema introspection, run from the backend so the introspected schema can be cached
+//! per endpoint and shared with every request that targets it
+
+use crate::error::{Error, Result};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use yaak_models::models::HttpRequest;
+use yaak_templates::strip_json_comments::strip_json_comments;
+
+pub const INTROSPECTION_OPERATION_NAME: &str = "IntrospectionQuery";
+
+/// The same query `getIntrospectionQuery()` from graphql-js generates by default
+pub const INTROSPECTION_QUERY: &str = r#"
+    query IntrospectionQuery {
+      __schema {
+        queryType { name }
+        mutationType { name }
+        subscriptionType { name }
+        types {
+          ...FullType
+        }
+        directives {
+          name
+          description
+          locations
+          args {
+            ...InputValue
+          }
+        }
+      }
+    }
+
+    fragment FullType on __Type {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args {
+          ...InputValue
+        }
+        type {
+          ...TypeRef
+        }
+        isDeprecated
+        deprecationReason
+      }
+      inputFields {
+        ...InputValue
+      }
+      interfaces {
+        ...TypeRef
+      }
+      enumValues(includeDeprecated: true) {
+        name
+        description
+        isDeprecated
+        deprecationReason
+      }
+      possibleTypes {
+        ...TypeRef
+      }
+    }
+
+    fragment InputValue on __InputValue {
+      name
+      description
+      type { ...TypeRef }
+      defaultValue
+    }
+
+    fragment TypeRef on __Type {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                  ofType {
+                    kind
+                    name
+                    ofType {
+                      kind
+                      name
+                      ofType {
+                        kind
+                        name
+                      }
+                    }
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }
+"#;
+
+/// Build an ephemeral copy of a GraphQL request that sends the introspection query instead of
+/// the user's operation, keeping the URL, method, headers, and auth intact
+pub fn introspection_request(request: &HttpRequest) -> HttpRequest {
+    let mut body = BTreeMap::new();
+    body.insert("query".to_string(), Value::String(INTROSPECTION_QUERY.to_string()));
+    body.insert(
+        "operationName".to_string(),
+        Value::String(INTROSPECTION_OPERATION_NAME.to_string()),
+    );
+
+    HttpRequest {
+        id: String::new(),
+        body_type: Some("graphql".to_string()),
+        body,
+        response_extractions: Vec::new(),
+        pre_request_steps: Vec::new(),
+        assertions: Vec::new(),
+        ..request.clone()
+    }
+}
+
+/// Check that an introspection response contains a schema, returning a descriptive error if the
+/// endpoint failed or returned something else
+pub fn validate_introspection_response(status: i32, body: &str) -> Result<()> {
+    if !(200..300).contains(&status) {
+        return Err(Error::GraphQlIntrospection(format!(
+            "Request failed with status {status}.\nThe response text is:\n\n{body}"
+        )));
+    }
+
+    let parsed: Value = serde_json::from_str(body).map_err(|e| {
+        Error::GraphQlIntrospection(format!("Failed to parse introspection response: {e}"))
+    })?;
+    if parsed.pointer("/data/__schema").is_some_and(Value::is_object) {
+        return Ok(());
+    }
+
+    let message = parsed
+        .pointer("/errors/0/message")
+        .and_then(Value::as_str)
+        .unwrap_or("Response did not contain a schema");
+    Err(Error::GraphQlIntrospection(message.to_string()))
+}
+
+/// Whether a GraphQL request runs a subscription, which is sent over a WebSocket instead of a
+/// regular HTTP request
+pub fn is_graphql_subscription(request: &HttpRequest) -> bool {
+    if request.body_type.as_deref() != Some("graphql") {
+        return false;
+    }
+    let get = |key: &str| request.body.get(key).and_then(Value::as_str).unwrap_or_default();
+    is_subscription(get("query"), get("operationName"))
+}
+
+/// Whether the operation a document would run is a subscription. That's the operation named
+/// `operation_name`, or the first one in the document when no name is given.
+pub fn is_subscription(query: &str, operation_name: &str) -> bool {
+    let operations = operation_types(query);
+    let operation_name = operation_name.trim();
+    let operation = match operation_name.is_empty() {
+        true => operations.first(),
+        false => operations.iter().find(|(_, name)| name.as_deref() == Some(operation_name)),
+    };
+    operation.is_some_and(|(kind, _)| kind == "subscription")
+}
+
+/// Type and name of every operation in a document, skipping fragments
+fn operation_types(query: &str) -> Vec<(String, Option<String>)> {
+    let mut operations = Vec::new();
+    let mut depth = 0usize;
+    let mut expect_definition = true;
+    // Operation keyword that is still waiting to see whether a name follows
+    let mut pending: Option<String> = None;
+    let mut chars = query.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => while chars.next_if(|c| *c != '\n').is_some() {},
+            '"' => {
+                if chars.next_if_eq(&'"').is_some() {
+                    // Either an empty string or the start of a block string
+                    if chars.next_if_eq(&'"').is_none() {
+                        continue;
+                    }
+                    let mut quotes = 0;
+                    for c in chars.by_ref() {
+                        quotes = if c == '"' { quotes + 1 } else { 0 };
+                        if quotes == 3 {
+                            break;
+                        }
+                    }
+                } else {
+                    while let Some(c) = chars.next() {
+                        match c {
+                            '\\' => {
+                                chars.next();
+                            }
+                            '"' | '\n' => break,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+            '{' | '(' | '[' | '@' => {
+                if depth == 0 {
+                    if let Some(kind) = pending.take() {
+                        operations.push((kind, None));
+                    } else if expect_definition && c == '{' {
+                        // Shorthand query without the `query` keyword
+                        operations.push(("query".to_string(), None));
+                    }
+                    expect_definition = false;
+                }
+                if c != '@' {
+                    depth += 1;
+                }
+            }
+            '}' | ')' | ']' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 && c == '}' {
+                    expect_definition = true;
+                }
+            }
+            c if c == '_' || c.is_ascii_alphabetic() => {
+                let mut name = c.to_string();
+                while let Some(c) = chars.next_if(|c| *c == '_' || c.is_ascii_alphanumeric()) {
+                    name.push(c);
+                }
+                if depth > 0 {
+                    continue;
+                }
+                if expect_definition {
+                    expect_definition = false;
+                    if matches!(name.as_str(), "query" | "mutation" | "subscription") {
+                        pending = Some(name);
+                    }
+                } else if let Some(kind) = pending.take() {
+                    operations.push((kind, Some(name)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    operations
+}
+
+/// Build the payload of a graphql-ws `subscribe` message from a GraphQL request body
+pub fn subscription_payload(body: &BTreeMap<String, Value>) -> std::result::Result<Value, String> {
+    let get = |key: &str| body.get(key).and_then(Value::as_str).unwrap_or_default();
+    let mut payload = serde_json::Map::new();
+    payload.insert("query".to_string(), Value::String(get("query").to_string()));
+
+    let operation_name = get("operationName").trim();
+    if !operation_name.is_empty() {
+        payload.insert("operationName".to_string(), Value::String(operation_name.to_string()));
+    }
+
+    let variables = strip_json_comments(get("variables"));
+    if !variables.trim().is_empty() {
+        let variables = serde_json::from_str(&variables)
+            .map_err(|e| format!("Invalid GraphQL variables: {e}"))?;
+        payload.insert("variables".to_string(), variables);
+    }
+
+    Ok(Value::Object(payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_introspection_request_from_graphql_request() {
+        let request = HttpRequest {
+            id: "rq_1".to_string(),
+            method: "GET".to_string(),
+            url: "https://api.example.com/graphql".to_string(),
+            body_type: Some("graphql".to_string()),
+            ..Default::default()
+        };
+        let introspection = introspection_request(&request);
+        assert_eq!(introspection.id, "");
+        assert_eq!(introspection.method, "GET");
+        assert_eq!(introspection.url, request.url);
+        assert_eq!(
+            introspection.body.get("operationName"),
+            Some(&Value::String(INTROSPECTION_OPERATION_NAME.to_string()))
+        );
+    }
+
+    #[test]
+    fn validates_introspection_responses() {
+        assert!(validate_introspection_response(200, r#"{"data":{"__schema":{}}}"#).is_ok());
+
+        let err =
+            validate_introspection_response(200, r#"{"errors":[{"message":"Nope"}]}"#).unwrap_err();
+        assert_eq!(err.to_string(), "Nope");
+
+        let err = validate_introspection_response(401, "Unauthorized").unwrap_err();
+        assert!(err.to_string().starts_with("Request failed with status 401"));
+
+        assert!(validate_introspection_response(200, "<html>").is_err());
+    }
+
+    #[test]
+    fn detects_subscription_operations() {
+        assert!(is_subscription("subscription { count }", ""));
+        assert!(is_subscription("subscription OnCount($n: Int = 1) @live { count(n: $n) }", ""));
+        assert!(!is_subscription("{ count }", ""));
+        assert!(!is_subscription("query Count { count }", ""));
+        assert!(!is_subscription("# subscription\nquery { s: subscription }", ""));
+        assert!(!is_subscription(r#"query { search(q: "subscription {") }"#, ""));
+
+        let document = r#"
+            fragment Fields on Counter { count }
+            query Current { counter { ...Fields } }
+            subscription Updates { counter { ...Fields } }
+        "#;
+        assert!(!is_subscription(document, ""));
+        assert!(is_subscription(document, "Updates"));
+        assert!(!is_subscription(document, "Current"));
+        assert!(!is_subscription(document, "Missing"));
+    }
+
+    #[test]
+    fn builds_subscription_payloads() {
+        let mut body = BTreeMap::new();
+        body.insert("query".to_string(), Value::String("subscription { n }".to_string()));
+        body.insert("operationName".to_string(), Value::String(" ".to_string()));
+        body.insert(
+            "variables".to_string(),
+            Value::String("{\n  // Comment\n  \"n\": 1\n}".to_string()),
+        );
+        assert_eq!(
+            subscription_payload(&body).unwrap(),
+            serde_json::json!({"query": "subscription { n }", "variables": {"n": 1}})
+        );
+
+        body.insert("variables".to_string(), Value::String("{".to_string()));
+        assert!(subscription_payload(&body).is_err());
+    }
+}
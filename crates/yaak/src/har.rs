@@ -0,0 +1,365 @@
+//! Export of stored HTTP responses as HAR 1.2 (HTTP Archive) documents
+//!
+//! See <http://www.softwareishard.com/blog/har-12-spec/> for the format.
+
+use crate::error::Result;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use serde::Serialize;
+use url::Url;
+use yaak_models::blob_manager::BlobManager;
+use yaak_models::models::{HttpResponse, HttpResponseEventData, HttpResponseHeader};
+use yaak_models::query_manager::QueryManager;
+
+#[derive(Debug, Serialize)]
+pub struct Har {
+    pub log: HarLog,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarLog {
+    pub version: String,
+    pub creator: HarCreator,
+    pub entries: Vec<HarEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarCreator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarEntry {
+    pub started_date_time: String,
+    pub time: i32,
+    pub request: HarRequest,
+    pub response: HarResponse,
+    pub cache: HarCache,
+    pub timings: HarTimings,
+    #[serde(rename = "serverIPAddress", skip_serializing_if = "Option::is_none")]
+    pub server_ip_address: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub query_string: Vec<HarNameValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<HarPostData>,
+    pub headers_size: i32,
+    pub body_size: i32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: i32,
+    pub status_text: String,
+    pub http_version: String,
+    pub cookies: Vec<HarNameValue>,
+    pub headers: Vec<HarNameValue>,
+    pub content: HarContent,
+    #[serde(rename = "redirectURL")]
+    pub redirect_url: String,
+    pub headers_size: i32,
+    pub body_size: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarNameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarPostData {
+    pub mime_type: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarContent {
+    pub size: i32,
+    pub mime_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HarCache {}
+
+/// Phase durations in milliseconds, where `-1` means the phase doesn't apply
+#[derive(Debug, Serialize)]
+pub struct HarTimings {
+    pub blocked: i32,
+    pub dns: i32,
+    pub connect: i32,
+    pub send: i32,
+    pub wait: i32,
+    pub receive: i32,
+    pub ssl: i32,
+}
+
+/// Build a HAR document from stored responses, including their request and response bodies.
+/// Entries are ordered by when they were sent.
+pub fn export_har(
+    query_manager: &QueryManager,
+    blob_manager: &BlobManager,
+    response_ids: &[String],
+    yaak_version: &str,
+) -> Result<Har> {
+    let db = query_manager.connect();
+    let mut responses = Vec::with_capacity(response_ids.len());
+    for id in response_ids {
+        responses.push(db.get_http_response(id)?);
+    }
+    responses.sort_by_key(|r| r.created_at);
+
+    let mut entries = Vec::with_capacity(responses.len());
+    for response in responses {
+        // The method isn't stored on the response, so recover it from the send events before
+        // falling back to the request's current method
+        let sent_method =
+            db.list_http_response_events(&response.id)?.into_iter().find_map(|e| match e.event {
+                HttpResponseEventData::SendUrl { method, .. } => Some(method),
+                _ => None,
+            });
+        let method = match sent_method {
+            Some(m) => m,
+            None => db
+                .get_http_request(&response.request_id)
+                .map(|r| r.method)
+                .unwrap_or_else(|_| "GET".to_string()),
+        };
+
+        let request_body: Vec<u8> = blob_manager
+            .connect()
+            .get_chunks(&format!("{}.request", response.id))?
+            .into_iter()
+            .flat_map(|c| c.data)
+            .collect();
+        let response_body = match &response.body_path {
+            Some(path) => std::fs::read(path).ok(),
+            None => None,
+        };
+
+        entries.push(har_entry(method, response, request_body, response_body));
+    }
+
+    Ok(Har {
+        log: HarLog {
+            version: "1.2".to_string(),
+            creator: HarCreator { name: "Yaak".to_string(), version: yaak_version.to_string() },
+            entries,
+        },
+    })
+}
+
+fn har_entry(
+    method: String,
+    response: HttpResponse,
+    request_body: Vec<u8>,
+    response_body: Option<Vec<u8>>,
+) -> HarEntry {
+    let http_version = response.version.clone().unwrap_or_else(|| "HTTP/1.1".to_string());
+    let query_string = match Url::parse(&response.url) {
+        Ok(url) => url
+            .query_pairs()
+            .map(|(name, value)| HarNameValue { name: name.into(), value: value.into() })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+
+    let post_data = if request_body.is_empty() {
+        None
+    } else {
+        Some(HarPostData {
+            mime_type: header_value(&response.request_headers, "content-type").to_string(),
+            text: String::from_utf8_lossy(&request_body).to_string(),
+        })
+    };
+
+    let content = match response_body {
+        Some(body) => {
+            let (text, encoding) = match String::from_utf8(body.clone()) {
+                Ok(text) => (text, None),
+                Err(_) => (BASE64_STANDARD.encode(&body), Some("base64".to_string())),
+            };
+            HarContent {
+                size: body.len() as i32,
+                mime_type: header_value(&response.headers, "content-type").to_string(),
+                text: Some(text),
+                encoding,
+            }
+        }
+        None => HarContent {
+            size: 0,
+            mime_type: header_value(&response.headers, "content-type").to_string(),
+            text: None,
+            encoding: None,
+        },
+    };
+
+    let request_cookies = response
+        .request_headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("cookie"))
+        .flat_map(|h| h.value.split(';'))
+        .filter_map(parse_cookie_pair)
+        .collect();
+    let response_cookies = response
+        .headers
+        .iter()
+        .filter(|h| h.name.eq_ignore_ascii_case("set-cookie"))
+        .filter_map(|h| h.value.split(';').next().and_then(parse_cookie_pair))
+        .collect();
+
+    let dns = response.elapsed_dns.max(0);
+    let connect = response.elapsed_connect.max(0);
+    let send = response.elapsed_send.max(0);
+    let wait = (response.elapsed_headers - dns - connect - send).max(0);
+    let receive = (response.elapsed - response.elapsed_headers).max(0);
+
+    HarEntry {
+        started_date_time: format!("{}Z", response.created_at.format("%Y-%m-%dT%H:%M:%S%.3f")),
+        time: response.elapsed,
+        request: HarRequest {
+            method,
+            url: response.url.clone(),
+            http_version: http_version.clone(),
+            cookies: request_cookies,
+            headers: to_name_values(&response.request_headers),
+            query_string,
+            post_data,
+            headers_size: -1,
+            body_size: response.request_content_length.unwrap_or(request_body.len() as i32),
+        },
+        response: HarResponse {
+            status: response.status,
+            status_text: response.status_reason.clone().unwrap_or_default(),
+            http_version,
+            cookies: response_cookies,
+            headers: to_name_values(&response.headers),
+            redirect_url: header_value(&response.headers, "location").to_string(),
+            headers_size: -1,
+            body_size: response.content_length_compressed.or(response.content_length).unwrap_or(-1),
+            content,
+        },
+        cache: HarCache {},
+        // The TLS handshake isn't timed separately, so it's only included in `connect`
+        timings: HarTimings { blocked: -1, dns, connect, send, wait, receive, ssl: -1 },
+        server_ip_address: response.remote_addr,
+    }
+}
+
+fn to_name_values(headers: &[HttpResponseHeader]) -> Vec<HarNameValue> {
+    headers.iter().map(|h| HarNameValue { name: h.name.clone(), value: h.value.clone() }).collect()
+}
+
+fn header_value<'a>(headers: &'a [HttpResponseHeader], name: &str) -> &'a str {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+        .unwrap_or("")
+}
+
+fn parse_cookie_pair(pair: &str) -> Option<HarNameValue> {
+    let (name, value) = pair.trim().split_once('=')?;
+    Some(HarNameValue { name: name.to_string(), value: value.to_string() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::blob_manager::BodyChunk;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::{HttpRequest, Workspace};
+    use yaak_models::util::UpdateSource;
+
+    #[test]
+    fn test_export_har() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+        let dir = tempfile::tempdir().unwrap();
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "HAR".to_string(), ..Default::default() }, source)
+            .unwrap();
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    method: "POST".to_string(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+
+        let body_path = dir.path().join("body");
+        std::fs::write(&body_path, "{\"ok\":true}").unwrap();
+        let header = |name: &str, value: &str| HttpResponseHeader {
+            name: name.to_string(),
+            value: value.to_string(),
+        };
+        let response = db
+            .upsert_http_response(
+                &HttpResponse {
+                    workspace_id: workspace.id.clone(),
+                    request_id: request.id.clone(),
+                    url: "https://example.com/items?page=2".to_string(),
+                    status: 201,
+                    elapsed: 30,
+                    elapsed_headers: 20,
+                    elapsed_dns: 5,
+                    elapsed_connect: 4,
+                    elapsed_send: 1,
+                    body_path: Some(body_path.to_string_lossy().to_string()),
+                    headers: vec![
+                        header("Content-Type", "application/json"),
+                        header("Set-Cookie", "session=abc; Path=/"),
+                    ],
+                    request_headers: vec![header("Content-Type", "text/plain")],
+                    ..Default::default()
+                },
+                source,
+                &blob_manager,
+            )
+            .unwrap();
+        blob_manager
+            .connect()
+            .insert_chunk(&BodyChunk::new(format!("{}.request", response.id), 0, b"hi".to_vec()))
+            .unwrap();
+        drop(db);
+
+        let har =
+            export_har(&query_manager, &blob_manager, std::slice::from_ref(&response.id), "1.0.0")
+                .expect("Failed to export HAR");
+        let value = serde_json::to_value(&har).unwrap();
+        let entry = &value["log"]["entries"][0];
+        assert_eq!(value["log"]["version"], "1.2");
+        assert_eq!(entry["request"]["method"], "POST");
+        assert_eq!(entry["request"]["queryString"][0]["value"], "2");
+        assert_eq!(entry["request"]["postData"]["text"], "hi");
+        assert_eq!(entry["response"]["status"], 201);
+        assert_eq!(entry["response"]["content"]["text"], "{\"ok\":true}");
+        assert_eq!(entry["response"]["cookies"][0]["name"], "session");
+        assert_eq!(entry["timings"]["connect"], 4);
+        assert_eq!(entry["timings"]["send"], 1);
+        assert_eq!(entry["timings"]["wait"], 10);
+        assert_eq!(entry["timings"]["receive"], 10);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::error::Error::ResponseFilter;
+use crate::error::Result;
+use serde_json::Value;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// A parsed RFC 9535 JSONPath expression. The leading `$` is optional, so `store.book` works the
+/// same as `$.store.book`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonPath(serde_json_path::JsonPath);
+
+impl JsonPath {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let expr = match expr.chars().next() {
+            Some('$') => expr.to_string(),
+            Some('[') => format!("${expr}"),
+            _ => format!("$.{expr}"),
+        };
+        serde_json_path::JsonPath::parse(&expr)
+            .map(JsonPath)
+            .map_err(|e| ResponseFilter(format!("Invalid filter: {e}")))
+    }
+
+    /// Every node matched by the expression, in document order
+    pub fn query<'a>(&self, root: &'a Value) -> Vec<&'a Value> {
+        self.0.query(root).all()
+    }
+}
+
+/// Filter a stored JSON response body. The body is parsed from the file without first reading it
+/// into a string, but the whole document is still held in memory while it's queried. Matches are
+/// returned as a pretty-printed JSON array.
+pub fn filter_json_file(body_path: impl AsRef<Path>, filter: &str) -> Result<String> {
+    let path = JsonPath::parse(filter)?;
+    let file = File::open(body_path)
+        .map_err(|e| ResponseFilter(format!("Failed to read response body: {e}")))?;
+    let value: Value = serde_json::from_reader(BufReader::new(file))
+        .map_err(|e| ResponseFilter(format!("Response is not valid JSON: {e}")))?;
+    serde_json::to_string_pretty(&path.query(&value))
+        .map_err(|e| ResponseFilter(format!("Failed to serialize filtered response: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn query(expr: &str, value: &Value) -> Vec<Value> {
+        JsonPath::parse(expr).unwrap().query(value).into_iter().cloned().collect()
+    }
+
+    fn store() -> Value {
+        json!({
+            "store": {
+                "book": [
+                    {"category": "reference", "author": "Nigel Rees", "price": 8.95},
+                    {"category": "fiction", "author": "Evelyn Waugh", "price": 12.99},
+                    {"category": "fiction", "author": "J. R. R. Tolkien", "price": 22.99, "isbn": "0-395"}
+                ],
+                "bicycle": {"color": "red", "price": 399}
+            }
+        })
+    }
+
+    #[test]
+    fn selects_children_and_indexes() {
+        let v = store();
+        assert_eq!(query("$.store.bicycle.color", &v), vec![json!("red")]);
+        assert_eq!(query("$['store']['bicycle'][\"price\"]", &v), vec![json!(399)]);
+        assert_eq!(query("$.store.book[-1].author", &v), vec![json!("J. R. R. Tolkien")]);
+        assert_eq!(query("store.book[0].price", &v), vec![json!(8.95)]);
+        assert_eq!(query("$.store.book[5]", &v), Vec::<Value>::new());
+    }
+
+    #[test]
+    fn selects_wildcards_slices_and_unions() {
+        let v = store();
+        assert_eq!(
+            query("$.store.book[*].price", &v),
+            vec![json!(8.95), json!(12.99), json!(22.99)]
+        );
+        assert_eq!(query("$.store.book[:2].price", &v), vec![json!(8.95), json!(12.99)]);
+        assert_eq!(query("$.store.book[::-2].price", &v), vec![json!(22.99), json!(8.95)]);
+        assert_eq!(query("$.store.book[0,2].price", &v), vec![json!(8.95), json!(22.99)]);
+    }
+
+    #[test]
+    fn selects_descendants() {
+        let v = store();
+        assert_eq!(query("$..author", &v).len(), 3);
+        assert_eq!(query("$..price", &v).len(), 4);
+        assert_eq!(query("$..book[1].author", &v), vec![json!("Evelyn Waugh")]);
+    }
+
+    #[test]
+    fn applies_filters() {
+        let v = store();
+        assert_eq!(query("$.store.book[?(@.price < 10)].author", &v), vec![json!("Nigel Rees")]);
+        assert_eq!(query("$..book[?(@.isbn)].author", &v), vec![json!("J. R. R. Tolkien")]);
+        assert_eq!(
+            query("$..book[?(@.category == 'fiction' && !(@.price > 20))].author", &v),
+            vec![json!("Evelyn Waugh")]
+        );
+        assert_eq!(
+            query("$..book[?@.price >= $.store.bicycle.price || @.price == 8.95].author", &v),
+            vec![json!("Nigel Rees")]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_expressions() {
+        for expr in ["$.store[", "$.", "$[?(@.a == )]", "$.store)"] {
+            let err = JsonPath::parse(expr).unwrap_err().to_string();
+            assert!(err.starts_with("Invalid filter:"), "{expr}: {err}");
+        }
+    }
+
+    #[test]
+    fn filters_json_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("body");
+        std::fs::write(&path, store().to_string()).unwrap();
+
+        let filtered = filter_json_file(&path, "$.store.bicycle.color").unwrap();
+        assert_eq!(filtered, "[\n  \"red\"\n]");
+
+        std::fs::write(&path, "not json").unwrap();
+        let err = filter_json_file(&path, "$").unwrap_err().to_string();
+        assert!(err.starts_with("Response is not valid JSON"));
+    }
+}
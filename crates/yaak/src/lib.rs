@@ -1,7 +1,24 @@
+pub mod assertion;
+pub mod codegen;
+pub mod curl;
 pub mod error;
+pub mod extraction;
+pub mod graphql;
+pub mod har;
+pub mod jsonpath;
+pub mod mock_server;
+pub mod monitor;
+pub mod oauth2;
+pub mod openapi;
 pub mod plugin_events;
+pub mod postman;
+pub mod pre_request;
 pub mod render;
+pub mod retry;
+pub mod run_report;
+pub mod runner;
 pub mod send;
+pub mod xpath;
 
 pub use error::Error;
 pub type Result<T> = error::Result<T>;
@@ -0,0 +1,589 @@
+//! A local HTTP server that answers with the responses saved for a workspace's requests, so
+//! frontends can be built against a collection before the real API exists
+
+use crate::error::Error::MockServer;
+use crate::error::Result;
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::body::Incoming;
+use hyper::header::{HeaderName, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use log::{debug, warn};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
+use yaak_models::client_db::ClientDb;
+use yaak_models::models::{AnyModel, HttpResponse, HttpResponseState};
+use yaak_models::query_manager::QueryManager;
+
+const ROUTE_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const MODEL_CHANGES_BATCH_SIZE: usize = 200;
+
+// Saved bodies are already decoded, and hyper frames the body it sends itself
+const SKIPPED_HEADERS: [&str; 5] = [
+    "connection",
+    "content-encoding",
+    "content-length",
+    "keep-alive",
+    "transfer-encoding",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathSegment {
+    Literal(String),
+    /// A `:name` path parameter or a templated segment, either of which matches any value
+    Param,
+}
+
+/// A saved response and the method and path it's served for
+#[derive(Debug, Clone)]
+pub struct MockRoute {
+    pub request_id: String,
+    pub method: String,
+    pub response: HttpResponse,
+    segments: Vec<PathSegment>,
+    /// Whether the request URL starts with a template tag like `${[ base_url ]}`, which may
+    /// contribute leading path segments of its own
+    prefixed: bool,
+}
+
+impl MockRoute {
+    fn matches(&self, method: &str, path: &[&str]) -> bool {
+        if !self.method.eq_ignore_ascii_case(method) {
+            return false;
+        }
+        let extra = match path.len().checked_sub(self.segments.len()) {
+            Some(0) => 0,
+            Some(extra) if self.prefixed => extra,
+            _ => return false,
+        };
+        self.segments.iter().zip(&path[extra..]).all(|(segment, value)| match segment {
+            PathSegment::Literal(literal) => literal == value,
+            PathSegment::Param => true,
+        })
+    }
+
+    fn literal_count(&self) -> usize {
+        self.segments.iter().filter(|s| matches!(s, PathSegment::Literal(_))).count()
+    }
+}
+
+/// Build a route for every request in a workspace that has a successful saved response, serving
+/// its most recent one. Routes are ordered so the most specific path is matched first.
+pub fn mock_routes(db: &ClientDb, workspace_id: &str) -> Result<Vec<MockRoute>> {
+    let mut latest_responses = HashMap::new();
+    for response in db.list_http_responses(workspace_id, None)? {
+        let usable = matches!(response.state, HttpResponseState::Closed)
+            && response.error.is_none()
+            && response.status > 0;
+        if usable {
+            latest_responses.entry(response.request_id.clone()).or_insert(response);
+        }
+    }
+
+    let mut routes = Vec::new();
+    for request in db.list_http_requests(workspace_id)? {
+        let response = match latest_responses.remove(&request.id) {
+            Some(response) => response,
+            None => continue,
+        };
+        let (segments, prefixed) = path_pattern(&request.url);
+        routes.push(MockRoute {
+            request_id: request.id,
+            method: request.method.to_uppercase(),
+            response,
+            segments,
+            prefixed,
+        });
+    }
+
+    routes.sort_by_key(|r| (Reverse(r.literal_count()), Reverse(r.segments.len()), r.prefixed));
+    Ok(routes)
+}
+
+/// Find the route that serves a method and path, like `GET /users/42`
+pub fn match_mock_route<'a>(
+    routes: &'a [MockRoute],
+    method: &str,
+    path: &str,
+) -> Option<&'a MockRoute> {
+    let path: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    routes.iter().find(|r| r.matches(method, &path))
+}
+
+fn path_pattern(url: &str) -> (Vec<PathSegment>, bool) {
+    let url = url.trim().split(['?', '#']).next().unwrap_or_default();
+    let (path, prefixed) = if let Some((_, rest)) = url.split_once("://") {
+        (rest.find('/').map(|i| &rest[i..]).unwrap_or_default(), false)
+    } else if url.starts_with("${[") {
+        (url.find("]}").map(|i| &url[i + 2..]).unwrap_or_default(), true)
+    } else {
+        // Yaak sends URLs without a scheme, so anything before the first slash is the host
+        (url.find('/').map(|i| &url[i..]).unwrap_or_default(), false)
+    };
+
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            if s.starts_with(':') || s.contains("${[") {
+                PathSegment::Param
+            } else {
+                PathSegment::Literal(s.to_string())
+            }
+        })
+        .collect();
+    (segments, prefixed)
+}
+
+/// A running mock server, which stops accepting connections when dropped
+pub struct MockServerHandle {
+    pub port: u16,
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    refresh_task: JoinHandle<()>,
+}
+
+impl Drop for MockServerHandle {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        self.refresh_task.abort();
+    }
+}
+
+/// Serve a workspace's saved responses on a local port, or on any free port if `port` is 0.
+/// Routes are rebuilt when the workspace's requests or responses change, so newly saved responses
+/// are served without a restart. CORS headers are only sent to the allowed origins.
+pub async fn start_mock_server(
+    query_manager: QueryManager,
+    workspace_id: &str,
+    port: u16,
+    allowed_origins: Vec<String>,
+) -> Result<MockServerHandle> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| MockServer(format!("Failed to listen on port {port}: {e}")))?;
+    let port = listener.local_addr().map_err(|e| MockServer(e.to_string()))?.port();
+
+    let workspace_id = workspace_id.to_string();
+    let (routes, cursor) = {
+        let query_manager = query_manager.clone();
+        let workspace_id = workspace_id.clone();
+        tokio::task::spawn_blocking(move || {
+            let db = query_manager.connect();
+            // Read the cursor first, so changes made while the routes load still trigger a refresh
+            let cursor = db.latest_model_change_id()?;
+            Ok::<_, crate::error::Error>((mock_routes(&db, &workspace_id)?, cursor))
+        })
+        .await
+        .map_err(|e| MockServer(e.to_string()))??
+    };
+    let (routes_tx, routes_rx) = watch::channel(Arc::new(routes));
+    let refresh_task =
+        tokio::spawn(refresh_routes(query_manager, workspace_id.clone(), cursor, routes_tx));
+    let allowed_origins = Arc::new(allowed_origins);
+
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        loop {
+            let stream = tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!("Mock server failed to accept connection: {e}");
+                        continue;
+                    }
+                },
+                _ = &mut shutdown_rx => break,
+            };
+
+            let routes_rx = routes_rx.clone();
+            let allowed_origins = allowed_origins.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |req: Request<Incoming>| {
+                    let routes = routes_rx.borrow().clone();
+                    let allowed_origins = allowed_origins.clone();
+                    async move {
+                        Ok::<_, Infallible>(mock_response(&routes, &allowed_origins, &req).await)
+                    }
+                });
+                let connection =
+                    http1::Builder::new().serve_connection(TokioIo::new(stream), service);
+                if let Err(e) = connection.await {
+                    debug!("Mock server connection closed: {e}");
+                }
+            });
+        }
+        debug!("Mock server on port {port} stopped");
+    });
+
+    Ok(MockServerHandle { port, shutdown_tx: Some(shutdown_tx), refresh_task })
+}
+
+/// Follow the model changes and rebuild the routes whenever one of the workspace's requests or
+/// responses changes
+async fn refresh_routes(
+    query_manager: QueryManager,
+    workspace_id: String,
+    mut cursor: i64,
+    routes_tx: watch::Sender<Arc<Vec<MockRoute>>>,
+) {
+    loop {
+        tokio::time::sleep(ROUTE_REFRESH_INTERVAL).await;
+        let refreshed = {
+            let query_manager = query_manager.clone();
+            let workspace_id = workspace_id.clone();
+            tokio::task::spawn_blocking(move || {
+                changed_routes(&query_manager.connect(), &workspace_id, cursor)
+            })
+            .await
+        };
+
+        match refreshed {
+            Ok(Ok((routes, next_cursor))) => {
+                cursor = next_cursor;
+                if let Some(routes) = routes {
+                    debug!("Mock server routes for {workspace_id} changed, rebuilt them");
+                    routes_tx.send_replace(Arc::new(routes));
+                }
+            }
+            Ok(Err(e)) => warn!("Failed to refresh mock server routes: {e}"),
+            Err(e) => warn!("Failed to refresh mock server routes: {e}"),
+        }
+    }
+}
+
+/// Rebuild the routes if any of the workspace's requests or responses changed after the cursor,
+/// returning the new routes, if any, and the cursor to continue from
+fn changed_routes(
+    db: &ClientDb,
+    workspace_id: &str,
+    mut cursor: i64,
+) -> Result<(Option<Vec<MockRoute>>, i64)> {
+    let mut changed = false;
+    loop {
+        let changes = db.list_model_changes_after(cursor, MODEL_CHANGES_BATCH_SIZE)?;
+        let Some(last) = changes.last() else {
+            break;
+        };
+        cursor = last.id;
+        changed |= changes.iter().any(|c| affects_routes(&c.payload.model, workspace_id));
+        if changes.len() < MODEL_CHANGES_BATCH_SIZE {
+            break;
+        }
+    }
+    let routes = if changed { Some(mock_routes(db, workspace_id)?) } else { None };
+    Ok((routes, cursor))
+}
+
+fn affects_routes(model: &AnyModel, workspace_id: &str) -> bool {
+    match model {
+        AnyModel::HttpRequest(m) => m.workspace_id == workspace_id,
+        AnyModel::HttpResponse(m) => m.workspace_id == workspace_id,
+        _ => false,
+    }
+}
+
+async fn mock_response<B>(
+    routes: &[MockRoute],
+    allowed_origins: &[String],
+    req: &Request<B>,
+) -> Response<Full<Bytes>> {
+    let origin = req
+        .headers()
+        .get("origin")
+        .filter(|o| allowed_origins.iter().any(|a| o.as_bytes() == a.as_bytes()))
+        .cloned();
+
+    // Answer CORS preflights so pages served from the allowed origins can call the mock server
+    if let Some(origin) = origin.clone()
+        && req.method() == Method::OPTIONS
+        && req.headers().contains_key("access-control-request-method")
+    {
+        let allow_headers = req
+            .headers()
+            .get("access-control-request-headers")
+            .cloned()
+            .unwrap_or(HeaderValue::from_static("*"));
+        let mut response = Response::new(Full::default());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        let headers = response.headers_mut();
+        headers.insert("access-control-allow-origin", origin);
+        headers.insert("access-control-allow-methods", HeaderValue::from_static("*"));
+        headers.insert("access-control-allow-headers", allow_headers);
+        headers.insert("vary", HeaderValue::from_static("origin"));
+        return response;
+    }
+
+    let path = req.uri().path();
+    let mut response = match match_mock_route(routes, req.method().as_str(), path) {
+        Some(route) => {
+            debug!("Mock server serving {} {path} from {}", req.method(), route.request_id);
+            route_response(route).await
+        }
+        None => text_response(
+            StatusCode::NOT_FOUND,
+            format!("No saved response for {} {path}", req.method()),
+        ),
+    };
+    if let Some(origin) = origin {
+        let headers = response.headers_mut();
+        headers.insert("access-control-allow-origin", origin);
+        headers.append("vary", HeaderValue::from_static("origin"));
+    }
+    response
+}
+
+async fn route_response(route: &MockRoute) -> Response<Full<Bytes>> {
+    let saved = &route.response;
+    let body = match &saved.body_path {
+        Some(path) => tokio::fs::read(path).await.unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = StatusCode::from_u16(saved.status as u16).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    for header in &saved.headers {
+        let name = match HeaderName::try_from(header.name.as_str()) {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        // Saved CORS headers were for the real API's origins, not the ones allowed here
+        if SKIPPED_HEADERS.contains(&name.as_str()) || name.as_str().starts_with("access-control-")
+        {
+            continue;
+        }
+        if let Ok(value) = HeaderValue::try_from(header.value.as_str()) {
+            headers.append(name, value);
+        }
+    }
+    response
+}
+
+fn text_response(status: StatusCode, message: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(message)));
+    *response.status_mut() = status;
+    response.headers_mut().insert("content-type", HeaderValue::from_static("text/plain"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpStream;
+    use yaak_models::blob_manager::BlobManager;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::{HttpRequest, HttpResponseHeader, Workspace};
+    use yaak_models::util::UpdateSource;
+
+    fn save_response(
+        db: &ClientDb,
+        blob_manager: &BlobManager,
+        request: &HttpRequest,
+        body_path: Option<String>,
+    ) {
+        db.upsert_http_response(
+            &HttpResponse {
+                workspace_id: request.workspace_id.clone(),
+                request_id: request.id.clone(),
+                state: HttpResponseState::Closed,
+                status: 201,
+                body_path,
+                headers: vec![
+                    HttpResponseHeader {
+                        name: "Content-Type".to_string(),
+                        value: "application/json".to_string(),
+                    },
+                    HttpResponseHeader {
+                        name: "Content-Encoding".to_string(),
+                        value: "gzip".to_string(),
+                    },
+                ],
+                ..Default::default()
+            },
+            &UpdateSource::Sync,
+            blob_manager,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn parses_path_patterns() {
+        use PathSegment::{Literal, Param};
+        let users = || Literal("users".to_string());
+
+        assert_eq!(path_pattern("https://api.test/users/:id?x=1"), (vec![users(), Param], false));
+        assert_eq!(path_pattern("api.test/users/${[ id ]}#top"), (vec![users(), Param], false));
+        assert_eq!(path_pattern("${[ base_url ]}/users"), (vec![users()], true));
+        assert_eq!(path_pattern("https://api.test"), (vec![], false));
+        assert_eq!(path_pattern("localhost:3000"), (vec![], false));
+    }
+
+    #[test]
+    fn matches_most_specific_route() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let request = |method: &str, url: &str| {
+            db.upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    method: method.to_string(),
+                    url: url.to_string(),
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap()
+        };
+
+        let by_id = request("GET", "https://api.test/users/:id");
+        let me = request("GET", "https://api.test/users/me");
+        let create = request("post", "${[ base_url ]}/users");
+        let unsent = request("GET", "https://api.test/unsent");
+        for r in [&by_id, &me, &create] {
+            save_response(&db, &blob_manager, r, None);
+        }
+        db.upsert_http_response(
+            &HttpResponse {
+                workspace_id: workspace.id.clone(),
+                request_id: unsent.id.clone(),
+                state: HttpResponseState::Closed,
+                error: Some("Connection refused".to_string()),
+                ..Default::default()
+            },
+            &source,
+            &blob_manager,
+        )
+        .unwrap();
+
+        let routes = mock_routes(&db, &workspace.id).unwrap();
+        let matched = |method, path| match_mock_route(&routes, method, path).map(|r| &r.request_id);
+        assert_eq!(routes.len(), 3);
+        assert_eq!(matched("GET", "/users/me"), Some(&me.id));
+        assert_eq!(matched("GET", "/users/42/"), Some(&by_id.id));
+        assert_eq!(matched("POST", "/api/v1/users"), Some(&create.id));
+        assert_eq!(matched("POST", "/users"), Some(&create.id));
+        assert_eq!(matched("DELETE", "/users/42"), None);
+        assert_eq!(matched("GET", "/unsent"), None);
+    }
+
+    #[tokio::test]
+    async fn serves_saved_responses() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let body_path = dir.path().join("body");
+        std::fs::write(&body_path, "{\"id\":42}").unwrap();
+        let workspace_id = {
+            let db = query_manager.connect();
+            let workspace = db
+                .upsert_workspace(
+                    &Workspace { name: "Test".to_string(), ..Default::default() },
+                    &UpdateSource::Sync,
+                )
+                .unwrap();
+            let request = db
+                .upsert_http_request(
+                    &HttpRequest {
+                        workspace_id: workspace.id.clone(),
+                        method: "GET".to_string(),
+                        url: "https://api.test/users/:id".to_string(),
+                        ..Default::default()
+                    },
+                    &UpdateSource::Sync,
+                )
+                .unwrap();
+            let body_path = Some(body_path.to_string_lossy().to_string());
+            save_response(&db, &blob_manager, &request, body_path);
+            workspace.id
+        };
+
+        let allowed_origins = vec!["http://localhost:5173".to_string()];
+        let server =
+            start_mock_server(query_manager, &workspace_id, 0, allowed_origins).await.unwrap();
+        let send = |path: &'static str, origin: &'static str| async move {
+            let mut stream = TcpStream::connect(("127.0.0.1", server.port)).await.unwrap();
+            let req = format!(
+                "GET {path} HTTP/1.1\r\nHost: localhost\r\nOrigin: {origin}\r\n\
+                 Connection: close\r\n\r\n"
+            );
+            stream.write_all(req.as_bytes()).await.unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let found = send("/users/42", "http://localhost:5173").await;
+        assert!(found.starts_with("HTTP/1.1 201 Created\r\n"));
+        assert!(found.contains("content-type: application/json\r\n"));
+        assert!(found.contains("access-control-allow-origin: http://localhost:5173\r\n"));
+        assert!(!found.contains("content-encoding"));
+        assert!(found.ends_with("\r\n\r\n{\"id\":42}"));
+
+        let other_origin = send("/users/42", "https://evil.test").await;
+        assert!(other_origin.starts_with("HTTP/1.1 201 Created\r\n"));
+        assert!(!other_origin.contains("access-control-"));
+
+        let missing = send("/teams", "http://localhost:5173").await;
+        assert!(missing.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(missing.ends_with("No saved response for GET /teams"));
+    }
+
+    #[test]
+    fn rebuilds_routes_when_workspace_requests_change() {
+        let (query_manager, blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = |name: &str| {
+            db.upsert_workspace(
+                &Workspace { name: name.to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap()
+        };
+        let (workspace, other) = (workspace("Test"), workspace("Other"));
+        let cursor = db.latest_model_change_id().unwrap();
+
+        db.upsert_workspace(&Workspace { name: "Renamed".to_string(), ..other.clone() }, &source)
+            .unwrap();
+        let (routes, cursor) = changed_routes(&db, &workspace.id, cursor).unwrap();
+        assert!(routes.is_none());
+
+        let request = db
+            .upsert_http_request(
+                &HttpRequest {
+                    workspace_id: workspace.id.clone(),
+                    method: "GET".to_string(),
+                    url: "https://api.test/users".to_string(),
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+        save_response(&db, &blob_manager, &request, None);
+        let (routes, cursor) = changed_routes(&db, &workspace.id, cursor).unwrap();
+        assert_eq!(routes.unwrap().len(), 1);
+
+        let (routes, _) = changed_routes(&db, &workspace.id, cursor).unwrap();
+        assert!(routes.is_none());
+    }
+}
@@ -0,0 +1,205 @@
+use crate::error::Result;
+use crate::runner::collection_run_result;
+use crate::send::{SendHttpRequestWithPluginsParams, send_http_request_with_plugins};
+use chrono::{NaiveDateTime, Utc};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_http::manager::HttpConnectionManager;
+use yaak_models::blob_manager::BlobManager;
+use yaak_models::client_db::ClientDb;
+use yaak_models::models::{CollectionRunResult, HttpRequest, Monitor, MonitorRun};
+use yaak_models::query_manager::QueryManager;
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::PluginContext;
+use yaak_plugins::manager::PluginManager;
+
+/// Shortest interval a monitor runs on, so a typo can't hammer a server every second
+pub const MIN_MONITOR_INTERVAL: i32 = 30;
+
+pub struct MonitorRunParams<'a> {
+    pub query_manager: &'a QueryManager,
+    pub blob_manager: &'a BlobManager,
+    pub monitor_id: &'a str,
+    pub update_source: UpdateSource,
+    pub response_dir: &'a Path,
+    pub plugin_manager: Arc<PluginManager>,
+    pub encryption_manager: Arc<EncryptionManager>,
+    pub plugin_context: &'a PluginContext,
+    pub connection_manager: Option<&'a HttpConnectionManager>,
+}
+
+/// Whether an enabled monitor's interval has passed since it last ran
+pub fn monitor_is_due(monitor: &Monitor, now: NaiveDateTime) -> bool {
+    if !monitor.enabled {
+        return false;
+    }
+    let interval = monitor.interval.max(MIN_MONITOR_INTERVAL) as i64;
+    match monitor.last_run_at {
+        None => true,
+        Some(last_run_at) => (now - last_run_at).num_seconds() >= interval,
+    }
+}
+
+/// Whether a monitor has failed enough runs in a row to be considered down
+pub fn monitor_is_down(monitor: &Monitor) -> bool {
+    monitor.consecutive_failures >= monitor.failure_threshold.max(1)
+}
+
+/// Send a monitor's requests one at a time and record the outcome as a monitor run, returning the
+/// updated monitor alongside it
+pub async fn run_monitor(params: MonitorRunParams<'_>) -> Result<(Monitor, MonitorRun)> {
+    let started_at = Utc::now().naive_utc();
+    let started = Instant::now();
+    let (monitor, requests) = {
+        let db = params.query_manager.connect();
+        let monitor = db.get_monitor(params.monitor_id)?;
+        let requests = monitor_requests(&db, &monitor)?;
+        (monitor, requests)
+    };
+
+    let mut results = Vec::with_capacity(requests.len());
+    for request in requests {
+        let outcome = send_http_request_with_plugins(SendHttpRequestWithPluginsParams {
+            query_manager: params.query_manager,
+            blob_manager: params.blob_manager,
+            request: request.clone(),
+            environment_id: monitor.environment_id.as_deref(),
+            update_source: params.update_source.clone(),
+            cookie_jar_id: monitor.cookie_jar_id.clone(),
+            response_dir: params.response_dir,
+            emit_events_to: None,
+            emit_response_body_chunks_to: None,
+            existing_response: None,
+            plugin_manager: params.plugin_manager.clone(),
+            encryption_manager: params.encryption_manager.clone(),
+            plugin_context: params.plugin_context,
+            cancelled_rx: None,
+            connection_manager: params.connection_manager,
+            variable_overrides: Vec::new(),
+            download_path: None,
+        })
+        .await;
+        results.push(collection_run_result(&request, outcome));
+    }
+
+    let elapsed = started.elapsed().as_millis() as i32;
+    let db = params.query_manager.connect();
+    record_monitor_run(&db, &monitor.id, results, started_at, elapsed, &params.update_source)
+}
+
+fn monitor_requests(db: &ClientDb, monitor: &Monitor) -> Result<Vec<HttpRequest>> {
+    let requests = match (&monitor.request_id, &monitor.folder_id) {
+        (Some(request_id), _) => vec![db.get_http_request(request_id)?],
+        (None, Some(folder_id)) => db.list_http_requests_for_folder_recursive(folder_id)?,
+        (None, None) => Vec::new(),
+    };
+    Ok(requests.into_iter().filter(|r| r.deleted_at.is_none()).collect())
+}
+
+/// Save a monitor run and update the monitor's failure streak. The monitor is re-read so edits
+/// made while its requests were in flight aren't lost.
+fn record_monitor_run(
+    db: &ClientDb,
+    monitor_id: &str,
+    results: Vec<CollectionRunResult>,
+    started_at: NaiveDateTime,
+    elapsed: i32,
+    source: &UpdateSource,
+) -> Result<(Monitor, MonitorRun)> {
+    // A monitor with nothing to send can't show that anything is up
+    let passed = !results.is_empty() && results.iter().all(|r| r.passed);
+    let mut monitor = db.get_monitor(monitor_id)?;
+    monitor.consecutive_failures = if passed { 0 } else { monitor.consecutive_failures + 1 };
+    monitor.last_run_at = Some(started_at);
+    let monitor = db.upsert_monitor(&monitor, source)?;
+
+    let run = db.upsert_monitor_run(
+        &MonitorRun {
+            workspace_id: monitor.workspace_id.clone(),
+            monitor_id: monitor.id.clone(),
+            passed,
+            elapsed,
+            results,
+            ..Default::default()
+        },
+        source,
+    )?;
+    Ok((monitor, run))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::Workspace;
+
+    #[test]
+    fn checks_when_monitors_are_due() {
+        let now = Utc::now().naive_utc();
+        let monitor = Monitor { enabled: true, interval: 60, ..Default::default() };
+        assert!(monitor_is_due(&monitor, now));
+
+        let ran_recently = Monitor { last_run_at: Some(now - Duration::seconds(59)), ..monitor };
+        assert!(!monitor_is_due(&ran_recently, now));
+
+        let ran_earlier =
+            Monitor { last_run_at: Some(now - Duration::seconds(60)), ..ran_recently };
+        assert!(monitor_is_due(&ran_earlier, now));
+        assert!(!monitor_is_due(&Monitor { enabled: false, ..ran_earlier.clone() }, now));
+
+        let too_frequent = Monitor { interval: 1, ..ran_earlier };
+        assert!(!monitor_is_due(
+            &Monitor { last_run_at: Some(now - Duration::seconds(5)), ..too_frequent },
+            now
+        ));
+    }
+
+    #[test]
+    fn tracks_consecutive_failures() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let db = query_manager.connect();
+        let source = UpdateSource::Sync;
+        let workspace = db
+            .upsert_workspace(
+                &Workspace { name: "Test".to_string(), ..Default::default() },
+                &source,
+            )
+            .unwrap();
+        let monitor = db
+            .upsert_monitor(
+                &Monitor {
+                    workspace_id: workspace.id.clone(),
+                    enabled: true,
+                    failure_threshold: 2,
+                    ..Default::default()
+                },
+                &source,
+            )
+            .unwrap();
+        let passed = CollectionRunResult { passed: true, ..Default::default() };
+        let failed = CollectionRunResult { passed: false, ..Default::default() };
+        let now = Utc::now().naive_utc();
+
+        let (monitor, run) =
+            record_monitor_run(&db, &monitor.id, vec![passed.clone(), failed], now, 10, &source)
+                .unwrap();
+        assert!(!run.passed);
+        assert_eq!(monitor.consecutive_failures, 1);
+        assert_eq!(monitor.last_run_at, Some(now));
+        assert!(!monitor_is_down(&monitor));
+
+        let (monitor, _) =
+            record_monitor_run(&db, &monitor.id, Vec::new(), now, 0, &source).unwrap();
+        assert_eq!(monitor.consecutive_failures, 2);
+        assert!(monitor_is_down(&monitor));
+
+        let (monitor, run) =
+            record_monitor_run(&db, &monitor.id, vec![passed], now, 10, &source).unwrap();
+        assert!(run.passed);
+        assert_eq!(monitor.consecutive_failures, 0);
+        assert_eq!(db.list_monitor_runs_for_monitor(&monitor.id, None).unwrap().len(), 3);
+    }
+}
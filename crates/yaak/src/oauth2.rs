@@ -0,0 +1,512 @@
+//! Storage and refresh of OAuth 2.0 tokens obtained by the `oauth2` auth plugin, so expired
+//! tokens are refreshed before a request is sent instead of the request failing with a 401
+//!
+//! The token values themselves are kept in the secret store (the OS keychain by default), and only
+//! their metadata is kept in the database.
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use tokio::sync::mpsc;
+use yaak_crypto::secrets::SecretStore;
+use yaak_http::sender::{HttpSender, ReqwestSender};
+use yaak_http::types::{SendableHttpRequest, SendableHttpRequestOptions};
+use yaak_models::models::{HttpRequest, HttpRequestHeader, OAuth2Token};
+use yaak_models::query_manager::QueryManager;
+use yaak_plugins::events::HttpAuthenticationToken;
+
+pub const OAUTH2_AUTHENTICATION_TYPE: &str = "oauth2";
+
+/// Auth config values that determine which token is obtained. Anything else (like the header
+/// name) only affects how the token is sent, so it doesn't invalidate a stored token.
+const TOKEN_CONFIG_KEYS: [&str; 7] = [
+    "grantType",
+    "clientId",
+    "accessTokenUrl",
+    "authorizationUrl",
+    "scope",
+    "audience",
+    "tokenName",
+];
+
+/// Hash the parts of an auth configuration that identify a token. URLs are compared without
+/// their scheme, matching how the plugin normalizes them.
+pub fn oauth2_config_hash(context_id: &str, values: &BTreeMap<String, Value>) -> String {
+    let mut parts = vec![context_id.trim().to_string()];
+    for key in TOKEN_CONFIG_KEYS {
+        let value = string_value(values, key).trim();
+        let value =
+            value.strip_prefix("https://").or(value.strip_prefix("http://")).unwrap_or(value);
+        parts.push(value.to_string());
+    }
+    format!("{:x}", md5::compute(parts.join("\n")))
+}
+
+/// Build the header that sends a stored token, the same way the plugin would
+pub fn oauth2_token_header(
+    values: &BTreeMap<String, Value>,
+    token: &OAuth2Token,
+) -> (String, String) {
+    let name = match string_value(values, "headerName") {
+        "" => "Authorization",
+        n => n,
+    };
+    let value = match string_value(values, "tokenName") {
+        "id_token" => token.id_token.as_deref().unwrap_or_default(),
+        _ => token.access_token.as_str(),
+    };
+    let prefix = string_value(values, "headerPrefix");
+    (name.to_string(), format!("{prefix} {value}").trim().to_string())
+}
+
+/// Token values, which are kept in the secret store instead of the database
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OAuth2TokenSecrets {
+    access_token: String,
+    id_token: Option<String>,
+    refresh_token: Option<String>,
+}
+
+fn secret_key(config_hash: &str) -> String {
+    format!("oauth2.{config_hash}")
+}
+
+/// Store a token the plugin obtained, replacing any previous token for the same configuration
+pub fn store_oauth2_token(
+    query_manager: &QueryManager,
+    secrets: &dyn SecretStore,
+    config_hash: &str,
+    token: HttpAuthenticationToken,
+) -> Result<OAuth2Token, String> {
+    let now = Utc::now().naive_utc();
+    save_oauth2_token(
+        query_manager,
+        secrets,
+        OAuth2Token {
+            config_hash: config_hash.to_string(),
+            access_token: token.access_token,
+            id_token: token.id_token,
+            refresh_token: token.refresh_token,
+            token_type: token.token_type,
+            scopes: token
+                .scope
+                .unwrap_or_default()
+                .split_whitespace()
+                .map(|s| s.to_string())
+                .collect(),
+            expires_at: token.expires_in.map(|s| now + Duration::seconds(i64::from(s))),
+            ..Default::default()
+        },
+    )
+}
+
+/// Write the token values to the secret store and everything else to the database
+fn save_oauth2_token(
+    query_manager: &QueryManager,
+    secrets: &dyn SecretStore,
+    token: OAuth2Token,
+) -> Result<OAuth2Token, String> {
+    let secret = serde_json::to_string(&OAuth2TokenSecrets {
+        access_token: token.access_token,
+        id_token: token.id_token,
+        refresh_token: token.refresh_token,
+    })
+    .map_err(|e| format!("Failed to serialize OAuth 2.0 token: {e}"))?;
+    secrets
+        .set_secret(&secret_key(&token.config_hash), &secret)
+        .map_err(|e| format!("Failed to store OAuth 2.0 token secret: {e}"))?;
+
+    let stored = query_manager
+        .connect()
+        .upsert_oauth2_token(&OAuth2Token {
+            access_token: String::new(),
+            id_token: None,
+            refresh_token: None,
+            ..token
+        })
+        .map_err(|e| format!("Failed to store OAuth 2.0 token: {e}"))?;
+    with_secrets(stored, &secret)
+}
+
+/// Get the stored token for an auth configuration, without checking whether it has expired
+pub fn get_oauth2_token(
+    query_manager: &QueryManager,
+    secrets: &dyn SecretStore,
+    config_hash: &str,
+) -> Result<Option<OAuth2Token>, String> {
+    let Some(token) = query_manager.connect().get_oauth2_token(config_hash) else {
+        return Ok(None);
+    };
+
+    let secret = secrets
+        .get_secret(&secret_key(config_hash))
+        .map_err(|e| format!("Failed to read OAuth 2.0 token secret: {e}"))?;
+    match secret {
+        Some(secret) => with_secrets(token, &secret).map(Some),
+        // Tokens stored before the secret store existed are moved out of the database
+        None if !token.access_token.is_empty() => {
+            save_oauth2_token(query_manager, secrets, token).map(Some)
+        }
+        // The secret is gone (eg. the keychain was reset), so the token is unusable
+        None => {
+            delete_oauth2_token(query_manager, secrets, config_hash)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Forget the stored token for an auth configuration
+pub fn delete_oauth2_token(
+    query_manager: &QueryManager,
+    secrets: &dyn SecretStore,
+    config_hash: &str,
+) -> Result<(), String> {
+    secrets
+        .delete_secret(&secret_key(config_hash))
+        .map_err(|e| format!("Failed to delete OAuth 2.0 token secret: {e}"))?;
+    query_manager
+        .connect()
+        .delete_oauth2_token(config_hash)
+        .map_err(|e| format!("Failed to delete OAuth 2.0 token: {e}"))?;
+    Ok(())
+}
+
+fn with_secrets(token: OAuth2Token, secret: &str) -> Result<OAuth2Token, String> {
+    let secrets: OAuth2TokenSecrets = serde_json::from_str(secret)
+        .map_err(|e| format!("Failed to parse OAuth 2.0 token secret: {e}"))?;
+    Ok(OAuth2Token {
+        access_token: secrets.access_token,
+        id_token: secrets.id_token,
+        refresh_token: secrets.refresh_token,
+        ..token
+    })
+}
+
+/// Get the stored token for an auth configuration, refreshing it first if it has expired.
+/// Returns `None` when there's no usable token, meaning the plugin needs to obtain a new one.
+pub async fn resolve_oauth2_token(
+    query_manager: &QueryManager,
+    secrets: &dyn SecretStore,
+    config_hash: &str,
+    values: &BTreeMap<String, Value>,
+) -> Result<Option<OAuth2Token>, String> {
+    let Some(token) = get_oauth2_token(query_manager, secrets, config_hash)? else {
+        return Ok(None);
+    };
+
+    if !token.is_expired() {
+        return Ok(Some(token));
+    }
+
+    let refreshed = match &token.refresh_token {
+        Some(refresh_token) => refresh_oauth2_token(values, refresh_token).await?,
+        None => None,
+    };
+
+    let Some(mut refreshed) = refreshed else {
+        // The token can't be refreshed, so forget it and let the plugin start a new flow
+        delete_oauth2_token(query_manager, secrets, config_hash)?;
+        return Ok(None);
+    };
+
+    // Servers may omit the refresh token when it hasn't changed
+    if refreshed.refresh_token.is_none() {
+        refreshed.refresh_token = token.refresh_token;
+    }
+    let token = store_oauth2_token(query_manager, secrets, config_hash, refreshed)?;
+    Ok(Some(token))
+}
+
+/// Exchange a refresh token for a new access token. Returns `None` if the server rejected the
+/// refresh token, which means it has expired or been revoked.
+async fn refresh_oauth2_token(
+    values: &BTreeMap<String, Value>,
+    refresh_token: &str,
+) -> Result<Option<HttpAuthenticationToken>, String> {
+    let Some(request) = refresh_token_request(values, refresh_token) else {
+        return Ok(None);
+    };
+    let sendable_request = SendableHttpRequest::from_http_request(
+        &request,
+        SendableHttpRequestOptions { follow_redirects: true, ..Default::default() },
+    )
+    .await
+    .map_err(|e| format!("Failed to build token refresh request: {e}"))?;
+
+    let sender = ReqwestSender::new().map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let (event_tx, _event_rx) = mpsc::channel(1);
+    let response = sender
+        .send(sendable_request, event_tx)
+        .await
+        .map_err(|e| format!("Failed to refresh access token: {e}"))?;
+    let status = response.status;
+    let (body, _) =
+        response.text().await.map_err(|e| format!("Failed to read token response: {e}"))?;
+
+    // Client errors mean the refresh token is invalid, expired, or revoked
+    if (400..500).contains(&status) {
+        return Ok(None);
+    }
+    if !(200..300).contains(&status) {
+        return Err(format!("Failed to refresh access token with status={status} and body={body}"));
+    }
+
+    parse_token_response(&body).map(Some)
+}
+
+/// Build the `refresh_token` grant request, authenticating the client the same way the plugin
+/// does. Public clients (eg. PKCE without a secret) only send their `client_id`.
+fn refresh_token_request(
+    values: &BTreeMap<String, Value>,
+    refresh_token: &str,
+) -> Option<HttpRequest> {
+    let access_token_url = string_value(values, "accessTokenUrl").trim();
+    if access_token_url.is_empty() {
+        return None;
+    }
+    let access_token_url =
+        if access_token_url.starts_with("http://") || access_token_url.starts_with("https://") {
+            access_token_url.to_string()
+        } else {
+            format!("https://{access_token_url}")
+        };
+
+    let client_id = string_value(values, "clientId");
+    let client_secret = string_value(values, "clientSecret");
+    let scope = string_value(values, "scope");
+    let mut form = vec![
+        json!({ "name": "grant_type", "value": "refresh_token" }),
+        json!({ "name": "refresh_token", "value": refresh_token }),
+    ];
+    if !scope.is_empty() {
+        form.push(json!({ "name": "scope", "value": scope }));
+    }
+
+    let mut headers = vec![
+        header("User-Agent", "yaak"),
+        header("Accept", "application/x-www-form-urlencoded, application/json"),
+        header("Content-Type", "application/x-www-form-urlencoded"),
+    ];
+    if string_value(values, "credentials") == "body" || client_secret.is_empty() {
+        form.push(json!({ "name": "client_id", "value": client_id }));
+        if !client_secret.is_empty() {
+            form.push(json!({ "name": "client_secret", "value": client_secret }));
+        }
+    } else {
+        let credentials = BASE64_STANDARD.encode(format!("{client_id}:{client_secret}"));
+        headers.push(header("Authorization", &format!("Basic {credentials}")));
+    }
+
+    Some(HttpRequest {
+        method: "POST".to_string(),
+        url: access_token_url,
+        headers,
+        body_type: Some("application/x-www-form-urlencoded".to_string()),
+        body: BTreeMap::from([("form".to_string(), Value::Array(form))]),
+        ..Default::default()
+    })
+}
+
+/// Parse a token endpoint response, which is usually JSON but may be form-encoded
+fn parse_token_response(body: &str) -> Result<HttpAuthenticationToken, String> {
+    let values: BTreeMap<String, Value> = serde_json::from_str(body).unwrap_or_else(|_| {
+        url::form_urlencoded::parse(body.as_bytes())
+            .map(|(k, v)| (k.to_string(), Value::String(v.to_string())))
+            .collect()
+    });
+
+    if let Some(error) = values.get("error").and_then(Value::as_str) {
+        let description = string_value(&values, "error_description");
+        return Err(format!("Failed to refresh access token with {error} -> {description}"));
+    }
+
+    let access_token = string_value(&values, "access_token");
+    if access_token.is_empty() {
+        return Err("access_token not found in token response".to_string());
+    }
+
+    let optional = |key: &str| match string_value(&values, key) {
+        "" => None,
+        v => Some(v.to_string()),
+    };
+    Ok(HttpAuthenticationToken {
+        access_token: access_token.to_string(),
+        id_token: optional("id_token"),
+        refresh_token: optional("refresh_token"),
+        token_type: optional("token_type"),
+        expires_in: match values.get("expires_in") {
+            Some(Value::Number(n)) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+            Some(Value::String(s)) => s.parse().ok(),
+            _ => None,
+        },
+        scope: optional("scope"),
+    })
+}
+
+fn string_value<'a>(values: &'a BTreeMap<String, Value>, key: &str) -> &'a str {
+    values.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn header(name: &str, value: &str) -> HttpRequestHeader {
+    HttpRequestHeader { enabled: true, name: name.to_string(), value: value.to_string(), id: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_crypto::secrets::MemorySecretStore;
+    use yaak_models::init_in_memory;
+
+    fn values(pairs: &[(&str, &str)]) -> BTreeMap<String, Value> {
+        pairs.iter().map(|(k, v)| (k.to_string(), Value::String(v.to_string()))).collect()
+    }
+
+    #[test]
+    fn hashes_only_token_identifying_config() {
+        let a = values(&[
+            ("clientId", "abc"),
+            ("accessTokenUrl", "https://auth.example.com"),
+        ]);
+        let b = values(&[
+            ("clientId", "abc"),
+            ("accessTokenUrl", "auth.example.com"),
+            ("headerName", "X-Token"),
+        ]);
+        let c = values(&[("clientId", "xyz"), ("accessTokenUrl", "auth.example.com")]);
+
+        assert_eq!(oauth2_config_hash("ctx", &a), oauth2_config_hash("ctx", &b));
+        assert_ne!(oauth2_config_hash("ctx", &a), oauth2_config_hash("ctx", &c));
+        assert_ne!(oauth2_config_hash("ctx", &a), oauth2_config_hash("other", &a));
+    }
+
+    #[test]
+    fn builds_header_from_stored_token() {
+        let token = OAuth2Token {
+            access_token: "access".to_string(),
+            id_token: Some("id".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            oauth2_token_header(&values(&[("headerPrefix", "Bearer")]), &token),
+            ("Authorization".to_string(), "Bearer access".to_string())
+        );
+        assert_eq!(
+            oauth2_token_header(
+                &values(&[("headerName", "X-Token"), ("tokenName", "id_token")]),
+                &token
+            ),
+            ("X-Token".to_string(), "id".to_string())
+        );
+    }
+
+    #[test]
+    fn parses_json_and_form_token_responses() {
+        let token = parse_token_response(
+            r#"{"access_token":"a","refresh_token":"r","expires_in":3600,"scope":"read write"}"#,
+        )
+        .unwrap();
+        assert_eq!(token.access_token, "a");
+        assert_eq!(token.refresh_token.as_deref(), Some("r"));
+        assert_eq!(token.expires_in, Some(3600));
+        assert_eq!(token.scope.as_deref(), Some("read write"));
+
+        let token = parse_token_response("access_token=b&token_type=bearer&expires_in=60").unwrap();
+        assert_eq!(token.access_token, "b");
+        assert_eq!(token.token_type.as_deref(), Some("bearer"));
+        assert_eq!(token.expires_in, Some(60));
+
+        let err = parse_token_response(r#"{"error":"invalid_grant"}"#).unwrap_err();
+        assert!(err.contains("invalid_grant"));
+    }
+
+    #[test]
+    fn refreshes_public_clients_without_basic_auth() {
+        let form_names = |request: &HttpRequest| -> Vec<String> {
+            request.body["form"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|p| p["name"].as_str().unwrap().to_string())
+                .collect()
+        };
+        let has_basic_auth =
+            |request: &HttpRequest| request.headers.iter().any(|h| h.name == "Authorization");
+
+        let confidential = refresh_token_request(
+            &values(&[
+                ("accessTokenUrl", "auth.example.com/token"),
+                ("clientId", "abc"),
+                ("clientSecret", "secret"),
+            ]),
+            "r",
+        )
+        .unwrap();
+        assert_eq!(confidential.url, "https://auth.example.com/token");
+        assert!(has_basic_auth(&confidential));
+        assert_eq!(form_names(&confidential), vec!["grant_type", "refresh_token"]);
+
+        let public = refresh_token_request(
+            &values(&[
+                ("accessTokenUrl", "https://auth.example.com/token"),
+                ("clientId", "abc"),
+            ]),
+            "r",
+        )
+        .unwrap();
+        assert!(!has_basic_auth(&public));
+        assert_eq!(form_names(&public), vec!["grant_type", "refresh_token", "client_id"]);
+
+        assert!(refresh_token_request(&values(&[("clientId", "abc")]), "r").is_none());
+    }
+
+    #[test]
+    fn keeps_token_values_out_of_the_database() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let secrets = MemorySecretStore::default();
+        let token = HttpAuthenticationToken {
+            access_token: "access".to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_in: Some(3600),
+            ..Default::default()
+        };
+
+        let stored = store_oauth2_token(&query_manager, &secrets, "hash", token).unwrap();
+        assert_eq!(stored.access_token, "access");
+        assert_eq!(stored.refresh_token.as_deref(), Some("refresh"));
+
+        let row = query_manager.connect().get_oauth2_token("hash").unwrap();
+        assert_eq!(row.access_token, "");
+        assert_eq!(row.refresh_token, None);
+        assert!(row.expires_at.is_some());
+
+        let loaded = get_oauth2_token(&query_manager, &secrets, "hash").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+
+        delete_oauth2_token(&query_manager, &secrets, "hash").unwrap();
+        assert!(secrets.get_secret("oauth2.hash").unwrap().is_none());
+        assert!(get_oauth2_token(&query_manager, &secrets, "hash").unwrap().is_none());
+    }
+
+    #[test]
+    fn moves_plaintext_tokens_to_the_secret_store() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().unwrap();
+        let secrets = MemorySecretStore::default();
+        query_manager
+            .connect()
+            .upsert_oauth2_token(&OAuth2Token {
+                config_hash: "hash".to_string(),
+                access_token: "access".to_string(),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let loaded = get_oauth2_token(&query_manager, &secrets, "hash").unwrap().unwrap();
+        assert_eq!(loaded.access_token, "access");
+        assert!(secrets.get_secret("oauth2.hash").unwrap().is_some());
+        assert_eq!(query_manager.connect().get_oauth2_token("hash").unwrap().access_token, "");
+    }
+}
@@ -0,0 +1,347 @@
+//! Export of a workspace as an OpenAPI 3.1 document
+//!
+//! See <https://spec.openapis.org/oas/v3.1.0> for the format.
+
+use crate::error::Result;
+use regex::Regex;
+use serde_json::{Map, Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::LazyLock;
+use yaak_models::models::{Folder, HttpRequest};
+use yaak_models::queries::workspaces::default_headers;
+use yaak_models::query_manager::QueryManager;
+
+static VARIABLE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{\[\s*([A-Za-z_][\w.-]*)\s*\]\}").unwrap());
+
+/// Headers that OpenAPI describes elsewhere and ignores when listed as parameters
+const RESERVED_HEADERS: [&str; 3] = ["accept", "authorization", "content-type"];
+
+/// Build an OpenAPI skeleton from a workspace's HTTP requests. Each request becomes an operation
+/// with its parameters, headers, and an example request body, tagged with the folders it's in.
+/// Hosts become servers, using base environment values as defaults for any variables they
+/// reference. The first request wins when several share a method and path.
+pub fn export_openapi(query_manager: &QueryManager, workspace_id: &str) -> Result<Value> {
+    let db = query_manager.connect();
+    let workspace = db.get_workspace(workspace_id)?;
+    let folders = db.list_folders(workspace_id)?;
+    let variables: HashMap<String, String> = db
+        .get_base_environment(workspace_id)?
+        .variables
+        .into_iter()
+        .filter(|v| v.enabled && !v.secret)
+        .map(|v| (v.name, v.value))
+        .collect();
+
+    let mut requests = db.list_http_requests(workspace_id)?;
+    requests.sort_by(|a, b| a.sort_priority.total_cmp(&b.sort_priority));
+
+    let defaults = default_headers();
+    let mut servers: Vec<Value> = Vec::new();
+    let mut paths: BTreeMap<String, Map<String, Value>> = BTreeMap::new();
+    let mut tags: Vec<String> = Vec::new();
+    for request in requests {
+        let mut headers = db.resolve_headers_for_http_request(&request)?;
+        headers.retain(|h| !defaults.contains(h));
+        let url_parameters = db.resolve_query_params_for_http_request(&request)?;
+        let request = HttpRequest { headers, url_parameters, ..request };
+
+        let (server, path) = split_url(&request.url);
+        if !server.is_empty() {
+            let server = openapi_server(&server, &variables);
+            if !servers.contains(&server) {
+                servers.push(server);
+            }
+        }
+
+        let tag = folder_path(request.folder_id.as_deref(), &folders);
+        if let Some(tag) = tag.clone().filter(|t| !tags.contains(t)) {
+            tags.push(tag);
+        }
+
+        let (path, operation) = openapi_operation(&request, &path, tag);
+        paths.entry(path).or_default().entry(request.method.to_lowercase()).or_insert(operation);
+    }
+
+    let mut document = json!({
+        "openapi": "3.1.0",
+        "info": { "title": workspace.name, "version": "1.0.0" },
+        "paths": paths,
+    });
+    if !workspace.description.is_empty() {
+        document["info"]["description"] = json!(workspace.description);
+    }
+    if !servers.is_empty() {
+        document["servers"] = json!(servers);
+    }
+    if !tags.is_empty() {
+        document["tags"] = tags.iter().map(|name| json!({ "name": name })).collect();
+    }
+    Ok(document)
+}
+
+fn openapi_operation(request: &HttpRequest, path: &str, tag: Option<String>) -> (String, Value) {
+    let mut path = path.to_string();
+    let mut parameters = Vec::new();
+    let query = request.url.split_once('?').map(|(_, q)| q).unwrap_or_default();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+        parameters.push(with_example(json!({ "name": name, "in": "query" }), value));
+    }
+    for p in request.url_parameters.iter().filter(|p| p.enabled && !p.name.is_empty()) {
+        let parameter = match p.name.strip_prefix(':') {
+            Some(name) => {
+                path = replace_path_segment(&path, &p.name, &format!("{{{name}}}"));
+                json!({ "name": name, "in": "path", "required": true })
+            }
+            None => json!({ "name": p.name, "in": "query" }),
+        };
+        parameters.push(with_example(parameter, &p.value));
+    }
+    for h in request.headers.iter().filter(|h| h.enabled && !h.name.is_empty()) {
+        if RESERVED_HEADERS.contains(&h.name.to_lowercase().as_str()) {
+            continue;
+        }
+        parameters.push(with_example(json!({ "name": h.name, "in": "header" }), &h.value));
+    }
+
+    let mut operation = json!({
+        "summary": request.name,
+        "responses": { "default": { "description": "Response" } },
+    });
+    if !request.description.is_empty() {
+        operation["description"] = json!(request.description);
+    }
+    if let Some(tag) = tag {
+        operation["tags"] = json!([tag]);
+    }
+    if !parameters.is_empty() {
+        operation["parameters"] = json!(parameters);
+    }
+    if let Some(body) = openapi_request_body(request) {
+        operation["requestBody"] = body;
+    }
+    (path, operation)
+}
+
+fn openapi_request_body(request: &HttpRequest) -> Option<Value> {
+    let body = &request.body;
+    let text = |key: &str| body.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    let form: Vec<Value> = body
+        .get("form")
+        .and_then(|f| f.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|f| f.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true))
+        .cloned()
+        .collect();
+
+    let (content_type, media) = match request.body_type.as_deref()? {
+        "graphql" => {
+            let variables = serde_json::from_str(text("variables")).unwrap_or(Value::Null);
+            let example = json!({ "query": text("query"), "variables": variables });
+            ("application/json".to_string(), json!({ "example": example }))
+        }
+        body_type @ ("application/x-www-form-urlencoded" | "multipart/form-data") => {
+            let mut properties = Map::new();
+            for field in &form {
+                let Some(name) = field.get("name").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let schema = if field.get("file").is_some() {
+                    json!({ "type": "string", "format": "binary" })
+                } else {
+                    let value = field.get("value").and_then(|v| v.as_str()).unwrap_or_default();
+                    json!({ "type": "string", "examples": [value] })
+                };
+                properties.insert(name.to_string(), schema);
+            }
+            let schema = json!({ "type": "object", "properties": properties });
+            (body_type.to_string(), json!({ "schema": schema }))
+        }
+        "binary" => {
+            let schema = json!({ "type": "string", "format": "binary" });
+            ("application/octet-stream".to_string(), json!({ "schema": schema }))
+        }
+        body_type if body.contains_key("text") => {
+            let example = match serde_json::from_str::<Value>(text("text")) {
+                Ok(v) if body_type.contains("json") => v,
+                _ => json!(text("text")),
+            };
+            let content_type = match body_type {
+                "other" => "text/plain",
+                t => t,
+            };
+            (content_type.to_string(), json!({ "example": example }))
+        }
+        _ => return None,
+    };
+
+    Some(json!({ "content": { content_type: media } }))
+}
+
+/// Split a URL into the server it's sent to and the path, without the query string. A URL that
+/// starts with a variable, like `${[ base_url ]}/users`, treats the variable as the server.
+fn split_url(url: &str) -> (String, String) {
+    let url = url.split('?').next().unwrap_or_default();
+    let path_search_start = if let Some(i) = url.find("://") {
+        i + 3
+    } else if url.starts_with("${[") {
+        url.find("]}").map(|i| i + 2).unwrap_or_default()
+    } else {
+        0
+    };
+
+    match url[path_search_start..].find('/') {
+        Some(i) => {
+            let (server, path) = url.split_at(path_search_start + i);
+            (server.to_string(), path.to_string())
+        }
+        None => (url.to_string(), "/".to_string()),
+    }
+}
+
+/// Rewrite `${[ name ]}` in a server URL to `{name}`, declaring it as a server variable
+fn openapi_server(url: &str, variables: &HashMap<String, String>) -> Value {
+    let mut server_variables = Map::new();
+    for captures in VARIABLE_TAG.captures_iter(url) {
+        let name = &captures[1];
+        let default = variables.get(name).cloned().unwrap_or_default();
+        server_variables.insert(name.to_string(), json!({ "default": default }));
+    }
+
+    let mut server = json!({ "url": VARIABLE_TAG.replace_all(url, "{$1}") });
+    if !server_variables.is_empty() {
+        server["variables"] = Value::Object(server_variables);
+    }
+    server
+}
+
+/// Name a folder by its ancestors, like `Users / Admin`
+fn folder_path(folder_id: Option<&str>, folders: &[Folder]) -> Option<String> {
+    let mut names = Vec::new();
+    let mut next_id = folder_id;
+    while let Some(folder) = next_id.and_then(|id| folders.iter().find(|f| f.id == id)) {
+        names.insert(0, folder.name.clone());
+        next_id = folder.folder_id.as_deref();
+    }
+    if names.is_empty() { None } else { Some(names.join(" / ")) }
+}
+
+fn replace_path_segment(path: &str, from: &str, to: &str) -> String {
+    path.split('/').map(|s| if s == from { to } else { s }).collect::<Vec<_>>().join("/")
+}
+
+fn with_example(mut parameter: Value, example: &str) -> Value {
+    if !example.is_empty() {
+        parameter["example"] = json!(example);
+    }
+    parameter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::{
+        EnvironmentVariable, HttpRequestHeader, HttpUrlParameter, Workspace,
+    };
+    use yaak_models::util::UpdateSource;
+
+    #[test]
+    fn test_split_url() {
+        let split = |url: &str| {
+            let (server, path) = split_url(url);
+            format!("{server} {path}")
+        };
+        assert_eq!(split("https://api.test/v1/users?page=1"), "https://api.test /v1/users");
+        assert_eq!(split("${[ base ]}/users"), "${[ base ]} /users");
+        assert_eq!(split("https://api.test"), "https://api.test /");
+        assert_eq!(split("/users"), " /users");
+    }
+
+    #[test]
+    fn test_export_openapi() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(&Workspace { name: "Shop".to_string(), ..Default::default() }, source)
+            .unwrap();
+        let mut base = db.get_base_environment(&workspace.id).unwrap();
+        base.variables = vec![EnvironmentVariable {
+            enabled: true,
+            name: "base_url".to_string(),
+            value: "https://shop.test".to_string(),
+            ..Default::default()
+        }];
+        db.upsert_environment(&base, source).unwrap();
+
+        let users = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    name: "Users".to_string(),
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: workspace.id.clone(),
+                folder_id: Some(users.id.clone()),
+                name: "Update User".to_string(),
+                method: "PATCH".to_string(),
+                url: "${[ base_url ]}/users/:id?notify=true".to_string(),
+                url_parameters: vec![HttpUrlParameter {
+                    enabled: true,
+                    name: ":id".to_string(),
+                    value: "42".to_string(),
+                    id: None,
+                }],
+                headers: vec![
+                    HttpRequestHeader {
+                        enabled: true,
+                        name: "X-Trace".to_string(),
+                        value: "abc".to_string(),
+                        id: None,
+                    },
+                    HttpRequestHeader {
+                        enabled: true,
+                        name: "Content-Type".to_string(),
+                        value: "application/json".to_string(),
+                        id: None,
+                    },
+                ],
+                body_type: Some("application/json".to_string()),
+                body: BTreeMap::from([("text".to_string(), json!(r#"{"name":"Ada"}"#))]),
+                ..Default::default()
+            },
+            source,
+        )
+        .unwrap();
+        drop(db);
+
+        let document = export_openapi(&query_manager, &workspace.id).expect("Failed to export");
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(document["info"]["title"], "Shop");
+        assert_eq!(document["servers"][0]["url"], "{base_url}");
+        assert_eq!(document["servers"][0]["variables"]["base_url"]["default"], "https://shop.test");
+        assert_eq!(document["tags"][0]["name"], "Users");
+
+        let operation = &document["paths"]["/users/{id}"]["patch"];
+        assert_eq!(operation["summary"], "Update User");
+        assert_eq!(operation["tags"][0], "Users");
+        assert_eq!(operation["parameters"][0]["name"], "notify");
+        assert_eq!(operation["parameters"][1]["in"], "path");
+        assert_eq!(operation["parameters"][1]["example"], "42");
+        assert_eq!(operation["parameters"][2]["name"], "X-Trace");
+        assert_eq!(operation["parameters"].as_array().unwrap().len(), 3);
+        assert_eq!(
+            operation["requestBody"]["content"]["application/json"]["example"]["name"],
+            "Ada"
+        );
+    }
+}
@@ -0,0 +1,484 @@
+//! Export of a workspace as a Postman Collection v2.1
+//!
+//! See <https://schema.postman.com/collection/json/v2.1.0/draft-07/docs/index.html> for the format.
+
+use crate::error::Result;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+use yaak_models::models::{Environment, Folder, HttpRequest, HttpUrlParameter};
+use yaak_models::queries::workspaces::default_headers;
+use yaak_models::query_manager::QueryManager;
+
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+static VARIABLE_TAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\$\{\[\s*([A-Za-z_][\w.-]*)\s*\]\}").unwrap());
+
+#[derive(Debug, Serialize)]
+pub struct PostmanCollection {
+    pub info: PostmanInfo,
+    pub item: Vec<PostmanItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variable: Vec<PostmanKeyValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanInfo {
+    #[serde(rename = "_postman_id")]
+    pub postman_id: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    pub schema: String,
+}
+
+/// A folder when `item` is set, otherwise a request
+#[derive(Debug, Serialize)]
+pub struct PostmanItem {
+    pub name: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub item: Option<Vec<PostmanItem>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request: Option<PostmanRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variable: Vec<PostmanKeyValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanRequest {
+    pub method: String,
+    pub header: Vec<PostmanKeyValue>,
+    pub url: PostmanUrl,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auth: Option<Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanUrl {
+    pub raw: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<PostmanKeyValue>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub variable: Vec<PostmanKeyValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PostmanKeyValue {
+    pub key: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// Build a Postman collection from a workspace's folders and HTTP requests. The base environment
+/// becomes collection variables and folder environments become folder variables, with secret
+/// values left empty. Headers and URL parameters inherited from folders and the workspace are
+/// copied onto each request because Postman folders can't hold them, leaving out Yaak's own
+/// defaults, while authentication stays where it's defined. Other request types, and
+/// authentication types Postman has no equivalent for, are left out.
+pub fn export_postman_collection(
+    query_manager: &QueryManager,
+    workspace_id: &str,
+) -> Result<PostmanCollection> {
+    let db = query_manager.connect();
+    let workspace = db.get_workspace(workspace_id)?;
+    let folders = db.list_folders(workspace_id)?;
+    let environments = db.list_environments_ensure_base(workspace_id)?;
+
+    let defaults = default_headers();
+    let mut requests = Vec::new();
+    for request in db.list_http_requests(workspace_id)? {
+        let mut headers = db.resolve_headers_for_http_request(&request)?;
+        headers.retain(|h| !defaults.contains(h));
+        let url_parameters = db.resolve_query_params_for_http_request(&request)?;
+        requests.push(HttpRequest { headers, url_parameters, ..request });
+    }
+
+    let variable = environments
+        .iter()
+        .find(|e| e.parent_model == "workspace")
+        .map(postman_variables)
+        .unwrap_or_default();
+
+    Ok(PostmanCollection {
+        info: PostmanInfo {
+            postman_id: workspace.id.clone(),
+            name: workspace.name.clone(),
+            description: workspace.description.clone(),
+            schema: POSTMAN_SCHEMA.to_string(),
+        },
+        item: postman_items(None, &folders, &requests, &environments),
+        auth: postman_auth(&workspace.authentication_type, &workspace.authentication),
+        variable,
+    })
+}
+
+/// Build the items inside a folder, or at the top level, in sidebar order
+fn postman_items(
+    folder_id: Option<&str>,
+    folders: &[Folder],
+    requests: &[HttpRequest],
+    environments: &[Environment],
+) -> Vec<PostmanItem> {
+    let mut children: Vec<(f64, PostmanItem)> = Vec::new();
+
+    for folder in folders.iter().filter(|f| f.folder_id.as_deref() == folder_id) {
+        let variable = environments
+            .iter()
+            .find(|e| e.parent_model == "folder" && e.parent_id.as_deref() == Some(&folder.id))
+            .map(postman_variables)
+            .unwrap_or_default();
+        let item = PostmanItem {
+            name: folder.name.clone(),
+            description: folder.description.clone(),
+            item: Some(postman_items(Some(&folder.id), folders, requests, environments)),
+            request: None,
+            auth: postman_auth(&folder.authentication_type, &folder.authentication),
+            variable,
+        };
+        children.push((folder.sort_priority, item));
+    }
+
+    for request in requests.iter().filter(|r| r.folder_id.as_deref() == folder_id) {
+        let item = PostmanItem {
+            name: request.name.clone(),
+            description: request.description.clone(),
+            item: None,
+            request: Some(postman_request(request)),
+            auth: None,
+            variable: Vec::new(),
+        };
+        children.push((request.sort_priority, item));
+    }
+
+    children.sort_by(|a, b| a.0.total_cmp(&b.0));
+    children.into_iter().map(|(_, item)| item).collect()
+}
+
+fn postman_request(request: &HttpRequest) -> PostmanRequest {
+    let header = request
+        .headers
+        .iter()
+        .filter(|h| !h.name.is_empty())
+        .map(|h| key_value(&h.name, &h.value, h.enabled))
+        .collect();
+
+    PostmanRequest {
+        method: request.method.to_uppercase(),
+        header,
+        url: postman_url(&request.url, &request.url_parameters),
+        body: postman_body(request),
+        auth: postman_auth(&request.authentication_type, &request.authentication),
+    }
+}
+
+/// Path parameters like `:id` become URL variables and everything else is a query parameter,
+/// which Postman also expects to find in the raw URL
+fn postman_url(url: &str, parameters: &[HttpUrlParameter]) -> PostmanUrl {
+    let mut raw = to_postman_template(url);
+    let mut query = Vec::new();
+    let mut variable = Vec::new();
+    for p in parameters.iter().filter(|p| !p.name.is_empty()) {
+        if let Some(name) = p.name.strip_prefix(':') {
+            variable.push(key_value(name, &p.value, p.enabled));
+            continue;
+        }
+
+        let param = key_value(&p.name, &p.value, p.enabled);
+        if !param.disabled {
+            raw.push(if raw.contains('?') { '&' } else { '?' });
+            raw.push_str(&format!("{}={}", param.key, param.value));
+        }
+        query.push(param);
+    }
+
+    PostmanUrl { raw, query, variable }
+}
+
+fn postman_body(request: &HttpRequest) -> Option<Value> {
+    let body = &request.body;
+    let form = || body.get("form").and_then(|f| f.as_array()).cloned().unwrap_or_default();
+    match request.body_type.as_deref()? {
+        "graphql" => Some(json!({
+            "mode": "graphql",
+            "graphql": {
+                "query": body_str(body, "query"),
+                "variables": body_str(body, "variables"),
+            },
+        })),
+        "application/x-www-form-urlencoded" => {
+            let fields: Vec<_> = form()
+                .iter()
+                .map(|f| key_value(&field_str(f, "name"), &field_str(f, "value"), field_enabled(f)))
+                .collect();
+            Some(json!({ "mode": "urlencoded", "urlencoded": fields }))
+        }
+        "multipart/form-data" => {
+            let fields: Vec<_> = form()
+                .iter()
+                .map(|f| {
+                    let mut field = json!({
+                        "key": to_postman_template(&field_str(f, "name")),
+                        "disabled": !field_enabled(f),
+                    });
+                    match f.get("file").and_then(|v| v.as_str()) {
+                        Some(file) => {
+                            field["type"] = json!("file");
+                            field["src"] = json!(file);
+                        }
+                        None => {
+                            field["type"] = json!("text");
+                            field["value"] = json!(to_postman_template(&field_str(f, "value")));
+                        }
+                    }
+                    field
+                })
+                .collect();
+            Some(json!({ "mode": "formdata", "formdata": fields }))
+        }
+        "binary" => Some(json!({ "mode": "file", "file": { "src": body_str(body, "filePath") } })),
+        body_type if body.contains_key("text") => {
+            let language = if body_type.contains("json") {
+                "json"
+            } else if body_type.contains("xml") {
+                "xml"
+            } else {
+                "text"
+            };
+            Some(json!({
+                "mode": "raw",
+                "raw": body_str(body, "text"),
+                "options": { "raw": { "language": language } },
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Map authentication to Postman's format, where no value means it's inherited from the parent
+fn postman_auth(
+    authentication_type: &Option<String>,
+    authentication: &BTreeMap<String, Value>,
+) -> Option<Value> {
+    let authentication_type = authentication_type.as_deref()?;
+    if authentication_type == "none" || authentication.get("disabled") == Some(&Value::Bool(true)) {
+        return Some(json!({ "type": "noauth" }));
+    }
+
+    let param = |name: &str| {
+        let value = body_str(authentication, name);
+        json!({ "key": name, "value": value, "type": "string" })
+    };
+    match authentication_type {
+        "basic" => Some(json!({
+            "type": "basic",
+            "basic": [param("username"), param("password")],
+        })),
+        "bearer" => Some(json!({ "type": "bearer", "bearer": [param("token")] })),
+        "apikey" => {
+            let location = match authentication.get("location").and_then(|v| v.as_str()) {
+                Some("query") => "query",
+                Some("cookie") => return None,
+                _ => "header",
+            };
+            Some(json!({
+                "type": "apikey",
+                "apikey": [
+                    param("key"),
+                    param("value"),
+                    { "key": "in", "value": location, "type": "string" },
+                ],
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn postman_variables(environment: &Environment) -> Vec<PostmanKeyValue> {
+    environment
+        .variables
+        .iter()
+        .filter(|v| !v.name.is_empty())
+        .map(|v| {
+            let value = if v.secret { "" } else { &v.value };
+            key_value(&v.name, value, v.enabled)
+        })
+        .collect()
+}
+
+fn key_value(key: &str, value: &str, enabled: bool) -> PostmanKeyValue {
+    PostmanKeyValue {
+        key: to_postman_template(key),
+        value: to_postman_template(value),
+        disabled: !enabled,
+    }
+}
+
+fn body_str(body: &BTreeMap<String, Value>, key: &str) -> String {
+    body.get(key).and_then(|v| v.as_str()).map(to_postman_template).unwrap_or_default()
+}
+
+fn field_str(field: &Value, key: &str) -> String {
+    field.get(key).and_then(|v| v.as_str()).unwrap_or_default().to_string()
+}
+
+fn field_enabled(field: &Value) -> bool {
+    field.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true)
+}
+
+/// Rewrite variable references like `${[ host ]}` to Postman's `{{host}}`. Template function
+/// calls have no Postman equivalent and are kept as they are.
+fn to_postman_template(text: &str) -> String {
+    VARIABLE_TAG.replace_all(text, "{{$1}}").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaak_models::init_in_memory;
+    use yaak_models::models::{EnvironmentVariable, HttpRequestHeader, Workspace};
+    use yaak_models::util::UpdateSource;
+
+    #[test]
+    fn test_to_postman_template() {
+        assert_eq!(to_postman_template("${[ host ]}/users"), "{{host}}/users");
+        assert_eq!(to_postman_template("${[a]}-${[ b.c ]}"), "{{a}}-{{b.c}}");
+        assert_eq!(to_postman_template("${[ uuid.v4() ]}"), "${[ uuid.v4() ]}");
+    }
+
+    #[test]
+    fn test_export_postman_collection() {
+        let (query_manager, _blob_manager, _rx) = init_in_memory().expect("Failed to init DB");
+        let db = query_manager.connect();
+        let source = &UpdateSource::Sync;
+
+        let workspace = db
+            .upsert_workspace(
+                &Workspace {
+                    name: "Shop".to_string(),
+                    authentication_type: Some("bearer".to_string()),
+                    authentication: BTreeMap::from([("token".to_string(), json!("${[ token ]}"))]),
+                    headers: vec![HttpRequestHeader {
+                        enabled: true,
+                        name: "Accept".to_string(),
+                        value: "application/json".to_string(),
+                        id: None,
+                    }],
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        let mut base = db.get_base_environment(&workspace.id).unwrap();
+        base.variables = vec![
+            EnvironmentVariable {
+                enabled: true,
+                name: "host".to_string(),
+                value: "https://shop.test".to_string(),
+                ..Default::default()
+            },
+            EnvironmentVariable {
+                enabled: true,
+                name: "token".to_string(),
+                value: "hunter2".to_string(),
+                secret: true,
+                ..Default::default()
+            },
+        ];
+        db.upsert_environment(&base, source).unwrap();
+
+        let folder = db
+            .upsert_folder(
+                &Folder {
+                    workspace_id: workspace.id.clone(),
+                    name: "Items".to_string(),
+                    authentication_type: Some("none".to_string()),
+                    sort_priority: 2.0,
+                    ..Default::default()
+                },
+                source,
+            )
+            .unwrap();
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: workspace.id.clone(),
+                folder_id: Some(folder.id.clone()),
+                name: "Get Item".to_string(),
+                method: "get".to_string(),
+                url: "${[ host ]}/items/:id".to_string(),
+                url_parameters: vec![
+                    HttpUrlParameter {
+                        enabled: true,
+                        name: ":id".to_string(),
+                        value: "1".to_string(),
+                        id: None,
+                    },
+                    HttpUrlParameter {
+                        enabled: true,
+                        name: "expand".to_string(),
+                        value: "all".to_string(),
+                        id: None,
+                    },
+                ],
+                ..Default::default()
+            },
+            source,
+        )
+        .unwrap();
+        db.upsert_http_request(
+            &HttpRequest {
+                workspace_id: workspace.id.clone(),
+                name: "Login".to_string(),
+                method: "POST".to_string(),
+                url: "${[ host ]}/login".to_string(),
+                body_type: Some("application/x-www-form-urlencoded".to_string()),
+                body: BTreeMap::from([(
+                    "form".to_string(),
+                    json!([{ "name": "user", "value": "me" }, { "enabled": false, "name": "x" }]),
+                )]),
+                sort_priority: 1.0,
+                ..Default::default()
+            },
+            source,
+        )
+        .unwrap();
+        drop(db);
+
+        let collection =
+            export_postman_collection(&query_manager, &workspace.id).expect("Failed to export");
+        let value = serde_json::to_value(&collection).unwrap();
+        assert_eq!(value["info"]["name"], "Shop");
+        assert_eq!(value["info"]["schema"], POSTMAN_SCHEMA);
+        assert_eq!(value["auth"]["bearer"][0]["value"], "{{token}}");
+        assert_eq!(value["variable"][0]["value"], "https://shop.test");
+        assert_eq!(value["variable"][1]["value"], "");
+
+        let login = &value["item"][0];
+        assert_eq!(login["name"], "Login");
+        assert_eq!(login["request"]["url"]["raw"], "{{host}}/login");
+        assert_eq!(login["request"]["header"][0]["key"], "Accept");
+        assert_eq!(login["request"]["body"]["urlencoded"][0]["value"], "me");
+        assert_eq!(login["request"]["body"]["urlencoded"][1]["disabled"], true);
+        assert!(login["request"].get("auth").is_none());
+
+        let items = &value["item"][1];
+        assert_eq!(items["name"], "Items");
+        assert_eq!(items["auth"]["type"], "noauth");
+        let get_item = &items["item"][0]["request"];
+        assert_eq!(get_item["method"], "GET");
+        assert_eq!(get_item["url"]["raw"], "{{host}}/items/:id?expand=all");
+        assert_eq!(get_item["url"]["variable"][0]["key"], "id");
+        assert_eq!(get_item["url"]["query"][0]["key"], "expand");
+    }
+}
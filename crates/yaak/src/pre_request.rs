@@ -0,0 +1,246 @@
+use crate::send::{
+    Result, SendHttpRequestError, SendHttpRequestWithPluginsParams,
+    send_http_request_with_plugins_without_steps, variable_overrides_environment,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::watch;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_http::manager::HttpConnectionManager;
+use yaak_models::blob_manager::BlobManager;
+use yaak_models::models::{EnvironmentVariable, PreRequestStep, PreRequestStepKind};
+use yaak_models::queries::any_request::AnyRequest;
+use yaak_models::query_manager::QueryManager;
+use yaak_models::render::make_vars_hashmap;
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::{PluginContext, RenderPurpose};
+use yaak_plugins::manager::PluginManager;
+use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_templates::{RenderOptions, parse_and_render};
+
+type StepsFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+pub struct PreRequestParams<'a> {
+    pub query_manager: &'a QueryManager,
+    pub blob_manager: &'a BlobManager,
+    pub environment_id: Option<&'a str>,
+    pub update_source: UpdateSource,
+    pub cookie_jar_id: Option<String>,
+    pub response_dir: &'a Path,
+    pub plugin_manager: Arc<PluginManager>,
+    pub encryption_manager: Arc<EncryptionManager>,
+    pub plugin_context: &'a PluginContext,
+    pub cancelled_rx: Option<watch::Receiver<bool>>,
+    pub connection_manager: Option<&'a HttpConnectionManager>,
+    pub variable_overrides: Vec<EnvironmentVariable>,
+}
+
+/// Run a request's enabled pre-request steps in order. Requests sent by a step run their own
+/// steps first, and a request that (indirectly) depends on itself fails instead of looping.
+pub async fn run_pre_request_steps(
+    params: &PreRequestParams<'_>,
+    request: &AnyRequest,
+) -> Result<()> {
+    let mut stack = Vec::new();
+    run_steps(params, request, &mut stack).await
+}
+
+fn run_steps<'a>(
+    params: &'a PreRequestParams<'_>,
+    request: &'a AnyRequest,
+    stack: &'a mut Vec<(String, String)>,
+) -> StepsFuture<'a> {
+    Box::pin(async move {
+        let (id, name, workspace_id, folder_id, steps) = match request {
+            AnyRequest::HttpRequest(r) => {
+                (&r.id, &r.name, &r.workspace_id, r.folder_id.as_deref(), &r.pre_request_steps)
+            }
+            AnyRequest::GrpcRequest(r) => {
+                (&r.id, &r.name, &r.workspace_id, r.folder_id.as_deref(), &r.pre_request_steps)
+            }
+            AnyRequest::WebsocketRequest(_) => return Ok(()),
+        };
+        if steps.iter().all(|s| !s.enabled) {
+            return Ok(());
+        }
+
+        stack.push((id.clone(), name.clone()));
+        for step in steps.iter().filter(|s| s.enabled) {
+            let mut environment_chain = params
+                .query_manager
+                .connect()
+                .resolve_environments(workspace_id, folder_id, params.environment_id)
+                .map_err(SendHttpRequestError::ResolveEnvironments)?;
+            if let Some(overrides) = variable_overrides_environment(&params.variable_overrides) {
+                environment_chain.insert(0, overrides);
+            }
+            let vars = make_vars_hashmap(environment_chain);
+            if !should_run_step(step, &vars) {
+                continue;
+            }
+
+            match step.kind {
+                PreRequestStepKind::SendRequest => {
+                    send_dependency(params, step, stack).await?;
+                }
+                PreRequestStepKind::SetVariable => {
+                    let callback = PluginTemplateCallback::new(
+                        params.plugin_manager.clone(),
+                        params.encryption_manager.clone(),
+                        params.plugin_context,
+                        RenderPurpose::Send,
+                    );
+                    let value =
+                        parse_and_render(&step.value, &vars, &callback, &RenderOptions::throw())
+                            .await
+                            .map_err(SendHttpRequestError::RenderRequest)?;
+                    params.query_manager.connect().set_chained_value(
+                        workspace_id,
+                        &step.name,
+                        &value,
+                        &params.update_source,
+                    );
+                }
+                PreRequestStepKind::PluginAction => {
+                    let result = match request {
+                        AnyRequest::HttpRequest(r) => {
+                            params
+                                .plugin_manager
+                                .run_http_request_action(
+                                    params.plugin_context,
+                                    &step.plugin,
+                                    &step.action,
+                                    r.clone(),
+                                )
+                                .await
+                        }
+                        AnyRequest::GrpcRequest(r) => {
+                            params
+                                .plugin_manager
+                                .run_grpc_request_action(
+                                    params.plugin_context,
+                                    &step.plugin,
+                                    &step.action,
+                                    r.clone(),
+                                )
+                                .await
+                        }
+                        AnyRequest::WebsocketRequest(_) => Ok(()),
+                    };
+                    result.map_err(|e| {
+                        SendHttpRequestError::PreRequestStep(format!(
+                            "{} {}: {e}",
+                            step.plugin, step.action
+                        ))
+                    })?;
+                }
+            }
+        }
+        stack.pop();
+
+        Ok(())
+    })
+}
+
+async fn send_dependency(
+    params: &PreRequestParams<'_>,
+    step: &PreRequestStep,
+    stack: &mut Vec<(String, String)>,
+) -> Result<()> {
+    let dependency = params
+        .query_manager
+        .connect()
+        .get_http_request(&step.request_id)
+        .map_err(SendHttpRequestError::LoadRequest)?;
+    if let Some(cycle) = find_cycle(stack, &dependency.id, &dependency.name) {
+        return Err(SendHttpRequestError::PreRequestCycle(cycle));
+    }
+
+    run_steps(params, &AnyRequest::HttpRequest(dependency.clone()), stack).await?;
+
+    let name = display_name(&dependency.id, &dependency.name);
+    send_http_request_with_plugins_without_steps(SendHttpRequestWithPluginsParams {
+        query_manager: params.query_manager,
+        blob_manager: params.blob_manager,
+        request: dependency,
+        environment_id: params.environment_id,
+        update_source: params.update_source.clone(),
+        cookie_jar_id: params.cookie_jar_id.clone(),
+        response_dir: params.response_dir,
+        emit_events_to: None,
+        emit_response_body_chunks_to: None,
+        existing_response: None,
+        plugin_manager: params.plugin_manager.clone(),
+        encryption_manager: params.encryption_manager.clone(),
+        plugin_context: params.plugin_context,
+        cancelled_rx: params.cancelled_rx.clone(),
+        connection_manager: params.connection_manager,
+        variable_overrides: params.variable_overrides.clone(),
+        download_path: None,
+    })
+    .await
+    .map_err(|e| SendHttpRequestError::PreRequestStep(format!("{name}: {e}")))?;
+
+    Ok(())
+}
+
+/// Steps with a guard variable only run while that variable is missing or blank
+fn should_run_step(step: &PreRequestStep, vars: &HashMap<String, String>) -> bool {
+    let guard = step.if_empty.trim();
+    guard.is_empty() || vars.get(guard).is_none_or(|v| v.trim().is_empty())
+}
+
+/// Describe the chain of requests that leads back to `id`, if it's already being prepared
+fn find_cycle(stack: &[(String, String)], id: &str, name: &str) -> Option<String> {
+    let start = stack.iter().position(|(stack_id, _)| stack_id == id)?;
+    let mut names: Vec<String> =
+        stack[start..].iter().map(|(id, name)| display_name(id, name)).collect();
+    names.push(display_name(id, name));
+    Some(names.join(" -> "))
+}
+
+fn display_name(id: &str, name: &str) -> String {
+    if name.is_empty() { id.to_string() } else { name.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guards_steps_on_empty_variables() {
+        let vars = HashMap::from([
+            ("token".to_string(), "abc".to_string()),
+            ("blank".to_string(), " ".to_string()),
+        ]);
+        let step = |if_empty: &str| PreRequestStep {
+            enabled: true,
+            if_empty: if_empty.to_string(),
+            ..Default::default()
+        };
+
+        assert!(should_run_step(&step(""), &vars));
+        assert!(!should_run_step(&step("token"), &vars));
+        assert!(should_run_step(&step("blank"), &vars));
+        assert!(should_run_step(&step("missing"), &vars));
+    }
+
+    #[test]
+    fn finds_request_cycles() {
+        let stack = vec![
+            ("rq_a".to_string(), "Get Users".to_string()),
+            ("rq_b".to_string(), "Login".to_string()),
+            ("rq_c".to_string(), "".to_string()),
+        ];
+
+        assert_eq!(find_cycle(&stack, "rq_d", "Refresh"), None);
+        assert_eq!(find_cycle(&stack, "rq_b", "Login"), Some("Login -> rq_c -> Login".to_string()));
+        assert_eq!(
+            find_cycle(&stack[..1], "rq_a", "Get Users"),
+            Some("Get Users -> Get Users".to_string())
+        );
+    }
+}
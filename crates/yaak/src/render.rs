@@ -3,7 +3,8 @@ use serde_json::Value;
 use std::collections::BTreeMap;
 use yaak_http::path_placeholders::apply_path_placeholders;
 use yaak_models::models::{
-    Environment, GrpcRequest, HttpRequest, HttpRequestHeader, HttpUrlParameter,
+    Environment, GrpcRequest, HttpRequest, HttpRequestHeader, HttpUrlParameter, ProxySettingAuth,
+    SshTunnel,
 };
 use yaak_models::render::make_vars_hashmap;
 use yaak_templates::{RenderOptions, TemplateCallback, parse_and_render, render_json_value_raw};
@@ -88,7 +89,52 @@ pub async fn render_http_request<T: TemplateCallback>(
     let url = parse_and_render(request.url.clone().as_str(), vars, callback, options).await?;
     let (url, url_parameters) = apply_path_placeholders(&url, &url_parameters);
 
-    Ok(HttpRequest { url, url_parameters, headers, body, authentication, ..request.to_owned() })
+    let unix_socket = match &request.unix_socket {
+        Some(path) => Some(parse_and_render(path, vars, callback, options).await?),
+        None => None,
+    };
+
+    Ok(HttpRequest {
+        url,
+        url_parameters,
+        unix_socket,
+        headers,
+        body,
+        authentication,
+        ..request.to_owned()
+    })
+}
+
+/// Render a proxy's username and password. They're kept out of exports and the sync directory, so
+/// they're usually set from environment variables.
+pub async fn render_proxy_auth<T: TemplateCallback>(
+    auth: &ProxySettingAuth,
+    environment_chain: Vec<Environment>,
+    callback: &T,
+    options: &RenderOptions,
+) -> yaak_templates::error::Result<ProxySettingAuth> {
+    let vars = &make_vars_hashmap(environment_chain);
+    Ok(ProxySettingAuth {
+        user: parse_and_render(&auth.user, vars, callback, options).await?,
+        password: parse_and_render(&auth.password, vars, callback, options).await?,
+    })
+}
+
+/// Render an SSH tunnel's username, password, and passphrase, which are kept out of exports and
+/// the sync directory like a proxy's credentials
+pub async fn render_ssh_tunnel<T: TemplateCallback>(
+    tunnel: &SshTunnel,
+    environment_chain: Vec<Environment>,
+    callback: &T,
+    options: &RenderOptions,
+) -> yaak_templates::error::Result<SshTunnel> {
+    let vars = &make_vars_hashmap(environment_chain);
+    Ok(SshTunnel {
+        username: parse_and_render(&tunnel.username, vars, callback, options).await?,
+        password: parse_and_render(&tunnel.password, vars, callback, options).await?,
+        passphrase: parse_and_render(&tunnel.passphrase, vars, callback, options).await?,
+        ..tunnel.clone()
+    })
 }
 
 pub async fn render_grpc_request<T: TemplateCallback>(
@@ -165,6 +211,7 @@ fn strip_disabled_form_entries(v: Value) -> Value {
 mod tests {
     use super::*;
     use serde_json::json;
+    use yaak_models::models::EnvironmentVariable;
 
     #[test]
     fn test_strip_disabled_form_entries() {
@@ -214,4 +261,74 @@ mod tests {
         let result = strip_disabled_form_entries(input.clone());
         assert_eq!(result, input);
     }
+
+    struct EmptyCB;
+
+    impl TemplateCallback for EmptyCB {
+        async fn run(
+            &self,
+            _fn_name: &str,
+            _args: std::collections::HashMap<String, Value>,
+        ) -> yaak_templates::error::Result<String> {
+            unreachable!()
+        }
+
+        fn transform_arg(
+            &self,
+            _fn_name: &str,
+            _arg_name: &str,
+            arg_value: &str,
+        ) -> yaak_templates::error::Result<String> {
+            Ok(arg_value.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_render_proxy_auth() {
+        let environment = Environment {
+            variables: vec![EnvironmentVariable {
+                enabled: true,
+                name: "proxy_password".to_string(),
+                value: "hunter2".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let auth = ProxySettingAuth {
+            user: "alice".to_string(),
+            password: "${[ proxy_password ]}".to_string(),
+        };
+        let rendered =
+            render_proxy_auth(&auth, vec![environment], &EmptyCB, &RenderOptions::throw())
+                .await
+                .unwrap();
+        assert_eq!(
+            rendered,
+            ProxySettingAuth { user: "alice".to_string(), password: "hunter2".to_string() }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_render_ssh_tunnel() {
+        let environment = Environment {
+            variables: vec![EnvironmentVariable {
+                enabled: true,
+                name: "ssh_passphrase".to_string(),
+                value: "correct horse".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let tunnel = SshTunnel {
+            host: "bastion.example.com".to_string(),
+            username: "deploy".to_string(),
+            passphrase: "${[ ssh_passphrase ]}".to_string(),
+            ..Default::default()
+        };
+        let rendered =
+            render_ssh_tunnel(&tunnel, vec![environment], &EmptyCB, &RenderOptions::throw())
+                .await
+                .unwrap();
+        assert_eq!(rendered, SshTunnel { passphrase: "correct horse".to_string(), ..tunnel });
+    }
 }
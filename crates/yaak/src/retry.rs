@@ -0,0 +1,87 @@
+use rand::Rng;
+use std::time::Duration;
+use yaak_http::error::Error;
+use yaak_models::models::RetryPolicy;
+
+/// Total number of times a request is sent under a policy, counting the first attempt
+pub fn max_attempts(policy: &RetryPolicy) -> u32 {
+    policy.max_attempts.max(1).unsigned_abs()
+}
+
+/// Why a failed send should be retried, or `None` if the policy doesn't cover the error
+pub fn retry_reason_for_error(policy: &RetryPolicy, err: &Error) -> Option<String> {
+    if err.is_timeout() {
+        policy.retry_on_timeout.then(|| "timeout".to_string())
+    } else if err.is_connect() {
+        policy.retry_on_connection_error.then(|| "connection error".to_string())
+    } else {
+        None
+    }
+}
+
+/// Why a response should be retried, or `None` if the policy doesn't cover its status
+pub fn retry_reason_for_status(policy: &RetryPolicy, status: u16) -> Option<String> {
+    if policy.retry_on_server_error && (500..600).contains(&status) {
+        Some(format!("status {status}"))
+    } else {
+        None
+    }
+}
+
+/// How long to wait after the given attempt (starting at 1) before sending the next one. The
+/// delay doubles each attempt up to the policy's maximum, and jitter picks a random delay up to
+/// that so many clients retrying at once don't all hit the server together.
+pub fn retry_delay(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let initial = policy.initial_delay.max(0) as u64;
+    let max = (policy.max_delay.max(0) as u64).max(initial);
+    let exponent = attempt.saturating_sub(1).min(32);
+    let delay = initial.saturating_mul(1u64 << exponent).min(max);
+    let delay =
+        if policy.jitter && delay > 0 { rand::rng().random_range(0..=delay) } else { delay };
+    Duration::from_millis(delay)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy { max_attempts: 5, jitter: false, ..Default::default() }
+    }
+
+    #[test]
+    fn backs_off_exponentially_up_to_max_delay() {
+        let policy = RetryPolicy { initial_delay: 100, max_delay: 1_000, ..policy() };
+        let delays: Vec<u128> =
+            (1..=6).map(|attempt| retry_delay(&policy, attempt).as_millis()).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1_000, 1_000]);
+    }
+
+    #[test]
+    fn jitter_stays_within_backoff() {
+        let policy = RetryPolicy { initial_delay: 100, max_delay: 1_000, jitter: true, ..policy() };
+        for _ in 0..50 {
+            assert!(retry_delay(&policy, 3) <= Duration::from_millis(400));
+        }
+    }
+
+    #[test]
+    fn retries_only_covered_failures() {
+        let policy = RetryPolicy { retry_on_connection_error: false, ..policy() };
+        assert_eq!(retry_reason_for_status(&policy, 503).as_deref(), Some("status 503"));
+        assert_eq!(retry_reason_for_status(&policy, 404), None);
+        assert_eq!(
+            retry_reason_for_error(&policy, &Error::ReadTimeout(Duration::from_secs(1))).as_deref(),
+            Some("timeout")
+        );
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert_eq!(retry_reason_for_error(&policy, &Error::IoError(refused)), None);
+        assert_eq!(retry_reason_for_error(&policy, &Error::RequestCanceledError), None);
+    }
+
+    #[test]
+    fn always_makes_at_least_one_attempt() {
+        assert_eq!(max_attempts(&RetryPolicy { max_attempts: 0, ..policy() }), 1);
+        assert_eq!(max_attempts(&policy()), 5);
+    }
+}
@@ -0,0 +1,272 @@
+//! Reports of collection runs for CI systems: a JSON summary and JUnit-compatible XML
+//!
+//! See <https://github.com/testmoapp/junitxml> for the JUnit XML conventions followed here.
+
+use crate::error::Result;
+use serde::Serialize;
+use std::fmt::Write;
+use yaak_models::models::{AssertionResult, CollectionRun, CollectionRunState};
+use yaak_models::query_manager::QueryManager;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunReport {
+    pub run_id: String,
+    pub folder_id: String,
+    pub folder_name: String,
+    pub started_at: String,
+    pub state: CollectionRunState,
+    pub elapsed: i32,
+    pub passed: usize,
+    pub failed: usize,
+    pub results: Vec<RunReportResult>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunReportResult {
+    pub request_id: String,
+    pub request_name: String,
+    pub iteration: i32,
+    pub response_id: Option<String>,
+    pub status: i32,
+    pub elapsed: i32,
+    pub passed: bool,
+    pub error: Option<String>,
+    pub assertions: Vec<AssertionResult>,
+}
+
+/// Build a report of a collection run, pulling each request's assertion results from its response
+pub fn run_report(query_manager: &QueryManager, run: &CollectionRun) -> Result<RunReport> {
+    let db = query_manager.connect();
+    let folder_name = db.get_folder(&run.folder_id).map(|f| f.name).unwrap_or_default();
+
+    let mut results = Vec::with_capacity(run.results.len());
+    for result in &run.results {
+        // Responses can be deleted after the run, which only loses the assertion details
+        let assertions = match &result.response_id {
+            Some(id) => db.get_http_response(id).map(|r| r.assertion_results).unwrap_or_default(),
+            None => Vec::new(),
+        };
+        results.push(RunReportResult {
+            request_id: result.request_id.clone(),
+            request_name: result.request_name.clone(),
+            iteration: result.iteration,
+            response_id: result.response_id.clone(),
+            status: result.status,
+            elapsed: result.elapsed,
+            passed: result.passed,
+            error: result.error.clone(),
+            assertions,
+        });
+    }
+
+    let passed = results.iter().filter(|r| r.passed).count();
+    Ok(RunReport {
+        run_id: run.id.clone(),
+        folder_id: run.folder_id.clone(),
+        folder_name,
+        started_at: run.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+        state: run.state.clone(),
+        elapsed: run.elapsed,
+        passed,
+        failed: results.len() - passed,
+        results,
+    })
+}
+
+impl RunReport {
+    /// Render the report as a single JUnit test suite with one test case per request sent. Requests
+    /// that couldn't be sent are errors, and bad statuses or failed assertions are failures.
+    pub fn to_junit_xml(&self) -> String {
+        let suite_name =
+            if self.folder_name.is_empty() { self.folder_id.as_str() } else { &self.folder_name };
+        let errors = self.results.iter().filter(|r| r.response_id.is_none()).count();
+        let failures = self.failed - errors;
+        let time = seconds(self.elapsed);
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\">",
+            name = escape_xml(suite_name),
+            tests = self.results.len(),
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\" timestamp=\"{timestamp}\">",
+            name = escape_xml(suite_name),
+            tests = self.results.len(),
+            timestamp = self.started_at,
+        );
+
+        for result in &self.results {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{name}\" classname=\"{classname}\" time=\"{time}\"",
+                name = escape_xml(&test_case_name(self, result)),
+                classname = escape_xml(suite_name),
+                time = seconds(result.elapsed),
+            );
+            if result.passed {
+                xml.push_str("/>\n");
+                continue;
+            }
+
+            xml.push_str(">\n");
+            match (&result.response_id, &result.error) {
+                (None, error) => {
+                    let message = error.as_deref().unwrap_or("Request was not sent");
+                    let _ = writeln!(
+                        xml,
+                        "      <error message=\"{message}\" type=\"error\"/>",
+                        message = escape_xml(message),
+                    );
+                }
+                (Some(_), error) => {
+                    let failed: Vec<&AssertionResult> =
+                        result.assertions.iter().filter(|a| !a.passed).collect();
+                    let message = match (error, failed.first()) {
+                        (Some(error), _) => error.clone(),
+                        (None, Some(assertion)) => assertion.name.clone(),
+                        (None, None) => format!("Returned status {}", result.status),
+                    };
+                    let details = failed
+                        .iter()
+                        .map(|a| format!("{}: {}", a.name, a.message))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"{message}\" type=\"assertion\">{details}</failure>",
+                        message = escape_xml(&message),
+                        details = escape_xml(&details),
+                    );
+                }
+            }
+            xml.push_str("    </testcase>\n");
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+}
+
+fn test_case_name(report: &RunReport, result: &RunReportResult) -> String {
+    let name =
+        if result.request_name.is_empty() { &result.request_id } else { &result.request_name };
+    // Data-driven runs send each request once per row, and CI systems need unique names
+    if report.results.iter().any(|r| r.iteration > 0) {
+        format!("{name} #{}", result.iteration + 1)
+    } else {
+        name.to_string()
+    }
+}
+
+fn seconds(millis: i32) -> String {
+    format!("{:.3}", millis as f64 / 1000.0)
+}
+
+fn escape_xml(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            // Other control characters aren't allowed in XML 1.0 at all
+            c if c.is_control() && c != '\t' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(results: Vec<RunReportResult>) -> RunReport {
+        let passed = results.iter().filter(|r| r.passed).count();
+        RunReport {
+            run_id: "cr_1".to_string(),
+            folder_id: "fl_1".to_string(),
+            folder_name: "Smoke <Tests>".to_string(),
+            started_at: "2026-01-02T03:04:05".to_string(),
+            state: CollectionRunState::Completed,
+            elapsed: 1500,
+            passed,
+            failed: results.len() - passed,
+            results,
+        }
+    }
+
+    fn result(name: &str, passed: bool) -> RunReportResult {
+        RunReportResult {
+            request_id: format!("rq_{name}"),
+            request_name: name.to_string(),
+            iteration: 0,
+            response_id: Some(format!("rs_{name}")),
+            status: 200,
+            elapsed: 250,
+            passed,
+            error: None,
+            assertions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn renders_junit_xml() {
+        let failed_assertion = RunReportResult {
+            assertions: vec![
+                AssertionResult {
+                    name: "status == 201".to_string(),
+                    passed: false,
+                    message: "Got 200".to_string(),
+                    ..Default::default()
+                },
+                AssertionResult {
+                    name: "header ETag".to_string(),
+                    passed: true,
+                    ..Default::default()
+                },
+            ],
+            ..result("Create", false)
+        };
+        let not_sent = RunReportResult {
+            response_id: None,
+            status: 0,
+            error: Some("Connection refused".to_string()),
+            ..result("Delete", false)
+        };
+        let xml = report(vec![result("List", true), failed_assertion, not_sent]).to_junit_xml();
+
+        assert_eq!(
+            xml,
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="Smoke &lt;Tests&gt;" tests="3" failures="1" errors="1" time="1.500">
+  <testsuite name="Smoke &lt;Tests&gt;" tests="3" failures="1" errors="1" time="1.500" timestamp="2026-01-02T03:04:05">
+    <testcase name="List" classname="Smoke &lt;Tests&gt;" time="0.250"/>
+    <testcase name="Create" classname="Smoke &lt;Tests&gt;" time="0.250">
+      <failure message="status == 201" type="assertion">status == 201: Got 200</failure>
+    </testcase>
+    <testcase name="Delete" classname="Smoke &lt;Tests&gt;" time="0.250">
+      <error message="Connection refused" type="error"/>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+
+    #[test]
+    fn numbers_data_driven_test_cases() {
+        let second_row = RunReportResult { iteration: 1, ..result("List", true) };
+        let xml = report(vec![result("List", true), second_row]).to_junit_xml();
+        assert!(xml.contains(r#"<testcase name="List #1""#));
+        assert!(xml.contains(r#"<testcase name="List #2""#));
+    }
+}
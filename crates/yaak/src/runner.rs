@@ -0,0 +1,319 @@
+use crate::error::{Error, Result};
+use crate::send::{
+    SendHttpRequestResult, SendHttpRequestWithPluginsParams, send_http_request_with_plugins,
+};
+use futures::{StreamExt, stream};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use yaak_crypto::manager::EncryptionManager;
+use yaak_http::manager::HttpConnectionManager;
+use yaak_models::blob_manager::BlobManager;
+use yaak_models::models::{
+    CollectionRun, CollectionRunResult, CollectionRunState, EnvironmentVariable, HttpRequest,
+};
+use yaak_models::query_manager::QueryManager;
+use yaak_models::util::UpdateSource;
+use yaak_plugins::events::PluginContext;
+use yaak_plugins::manager::PluginManager;
+
+pub struct CollectionRunParams<'a> {
+    pub query_manager: &'a QueryManager,
+    pub blob_manager: &'a BlobManager,
+    pub folder_id: &'a str,
+    pub environment_id: Option<&'a str>,
+    pub update_source: UpdateSource,
+    pub cookie_jar_id: Option<String>,
+    pub response_dir: &'a Path,
+    pub plugin_manager: Arc<PluginManager>,
+    pub encryption_manager: Arc<EncryptionManager>,
+    pub plugin_context: &'a PluginContext,
+    pub cancelled_rx: Option<watch::Receiver<bool>>,
+    pub connection_manager: Option<&'a HttpConnectionManager>,
+    pub concurrency: i32,
+    pub delay: i32,
+    pub stop_on_failure: bool,
+    pub data_file: Option<String>,
+}
+
+/// Send every HTTP request in a folder and its subfolders, saving each outcome to a new collection
+/// run as it completes. Results keep sidebar order even when several requests are in flight.
+/// With a data file, the whole folder is sent once per row with that row's columns as variables.
+pub async fn run_collection(params: CollectionRunParams<'_>) -> Result<CollectionRun> {
+    let started = Instant::now();
+    let data_file = params.data_file.clone().filter(|f| !f.trim().is_empty());
+    let rows = match &data_file {
+        Some(path) => read_data_file(Path::new(path))?,
+        None => vec![Vec::new()],
+    };
+    let (folder, requests) = {
+        let db = params.query_manager.connect();
+        let folder = db.get_folder(params.folder_id)?;
+        let requests = db.list_http_requests_for_folder_recursive(&folder.id)?;
+        (folder, requests)
+    };
+    let jobs: Vec<(i32, Vec<EnvironmentVariable>, HttpRequest)> = rows
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, variables)| {
+            requests.iter().map(move |r| (i as i32, variables.clone(), r.clone()))
+        })
+        .collect();
+    let total = jobs.len();
+
+    let mut run = params.query_manager.connect().upsert_collection_run(
+        &CollectionRun {
+            workspace_id: folder.workspace_id,
+            folder_id: folder.id,
+            concurrency: params.concurrency.max(1),
+            delay: params.delay.max(0),
+            stop_on_failure: params.stop_on_failure,
+            data_file,
+            ..Default::default()
+        },
+        &params.update_source,
+    )?;
+
+    let stopped = AtomicBool::new(false);
+    let mut outcomes = stream::iter(jobs.into_iter().enumerate())
+        .map(|(i, (iteration, variables, request))| {
+            run_request(&params, &stopped, i, iteration, variables, request)
+        })
+        .buffered(run.concurrency as usize);
+
+    while let Some(outcome) = outcomes.next().await {
+        let Some(result) = outcome else {
+            continue;
+        };
+        run.results.push(result);
+        run.elapsed = started.elapsed().as_millis() as i32;
+        run = params.query_manager.connect().upsert_collection_run(&run, &params.update_source)?;
+    }
+
+    run.elapsed = started.elapsed().as_millis() as i32;
+    run.state = if run.results.len() == total {
+        CollectionRunState::Completed
+    } else {
+        CollectionRunState::Stopped
+    };
+    Ok(params.query_manager.connect().upsert_collection_run(&run, &params.update_source)?)
+}
+
+/// Wait out the run's delay and send one request, or return nothing if the run was stopped or
+/// cancelled before it got the chance
+async fn run_request(
+    params: &CollectionRunParams<'_>,
+    stopped: &AtomicBool,
+    index: usize,
+    iteration: i32,
+    variables: Vec<EnvironmentVariable>,
+    request: HttpRequest,
+) -> Option<CollectionRunResult> {
+    if index > 0 && params.delay > 0 {
+        tokio::time::sleep(Duration::from_millis(params.delay as u64)).await;
+    }
+    let cancelled = params.cancelled_rx.as_ref().is_some_and(|rx| *rx.borrow());
+    if cancelled || stopped.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let outcome = send_http_request_with_plugins(SendHttpRequestWithPluginsParams {
+        query_manager: params.query_manager,
+        blob_manager: params.blob_manager,
+        request: request.clone(),
+        environment_id: params.environment_id,
+        update_source: params.update_source.clone(),
+        cookie_jar_id: params.cookie_jar_id.clone(),
+        response_dir: params.response_dir,
+        emit_events_to: None,
+        emit_response_body_chunks_to: None,
+        existing_response: None,
+        plugin_manager: params.plugin_manager.clone(),
+        encryption_manager: params.encryption_manager.clone(),
+        plugin_context: params.plugin_context,
+        cancelled_rx: params.cancelled_rx.clone(),
+        connection_manager: params.connection_manager,
+        variable_overrides: variables,
+        download_path: None,
+    })
+    .await;
+
+    let result = CollectionRunResult { iteration, ..collection_run_result(&request, outcome) };
+    if !result.passed && params.stop_on_failure {
+        stopped.store(true, Ordering::Relaxed);
+    }
+    Some(result)
+}
+
+pub(crate) fn collection_run_result(
+    request: &HttpRequest,
+    outcome: crate::send::Result<SendHttpRequestResult>,
+) -> CollectionRunResult {
+    let result = CollectionRunResult {
+        request_id: request.id.clone(),
+        request_name: request.name.clone(),
+        ..Default::default()
+    };
+    match outcome {
+        Ok(SendHttpRequestResult { response, .. }) => CollectionRunResult {
+            passed: response.error.is_none()
+                && response.status < 400
+                && response.assertion_results.iter().all(|r| r.passed),
+            response_id: Some(response.id),
+            status: response.status,
+            elapsed: response.elapsed,
+            error: response.error,
+            ..result
+        },
+        Err(e) => CollectionRunResult { error: Some(e.to_string()), ..result },
+    }
+}
+
+/// Read a CSV file with a header row, or a JSON array of objects, as one set of variables per row
+pub fn read_data_file(path: &Path) -> Result<Vec<Vec<EnvironmentVariable>>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| Error::DataFile(format!("Failed to read data file {path:?}: {e}")))?;
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        parse_json_rows(&contents)
+    } else {
+        parse_csv_rows(&contents)
+    }
+}
+
+fn parse_csv_rows(contents: &str) -> Result<Vec<Vec<EnvironmentVariable>>> {
+    let invalid = |e: csv::Error| Error::DataFile(format!("Invalid CSV data file: {e}"));
+    let mut reader = csv::Reader::from_reader(contents.as_bytes());
+    let headers = reader.headers().map_err(invalid)?.clone();
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(invalid)?;
+            Ok(headers
+                .iter()
+                .zip(record.iter())
+                .map(|(name, value)| variable(name, value))
+                .collect())
+        })
+        .collect()
+}
+
+fn parse_json_rows(contents: &str) -> Result<Vec<Vec<EnvironmentVariable>>> {
+    let rows: Vec<serde_json::Map<String, Value>> = serde_json::from_str(contents)
+        .map_err(|e| Error::DataFile(format!("Expected a JSON array of objects: {e}")))?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|(name, value)| match value {
+                    Value::String(s) => variable(name, s),
+                    v => variable(name, &v.to_string()),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn variable(name: &str, value: &str) -> EnvironmentVariable {
+    EnvironmentVariable {
+        enabled: true,
+        name: name.trim().to_string(),
+        value: value.to_string(),
+        id: None,
+        secret: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::send::SendHttpRequestError;
+    use yaak_models::models::{AssertionResult, HttpResponse};
+
+    fn sent(response: HttpResponse) -> crate::send::Result<SendHttpRequestResult> {
+        Ok(SendHttpRequestResult {
+            rendered_request: HttpRequest::default(),
+            response,
+            response_body: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn marks_failed_requests() {
+        let request =
+            HttpRequest { id: "rq_1".to_string(), name: "Login".to_string(), ..Default::default() };
+        let ok = HttpResponse { id: "rs_1".to_string(), status: 200, ..Default::default() };
+        let failed_assertion = HttpResponse {
+            assertion_results: vec![AssertionResult { passed: false, ..Default::default() }],
+            ..ok.clone()
+        };
+
+        let result = collection_run_result(&request, sent(ok));
+        assert!(result.passed);
+        assert_eq!(result.request_name, "Login");
+        assert_eq!(result.response_id.as_deref(), Some("rs_1"));
+
+        let result = collection_run_result(
+            &request,
+            sent(HttpResponse { status: 500, ..Default::default() }),
+        );
+        assert!(!result.passed);
+
+        assert!(!collection_run_result(&request, sent(failed_assertion)).passed);
+
+        let result = collection_run_result(
+            &request,
+            Err(SendHttpRequestError::PreRequestStep("boom".to_string())),
+        );
+        assert!(!result.passed);
+        assert_eq!(result.response_id, None);
+        assert_eq!(result.error.as_deref(), Some("Pre-request step failed: boom"));
+    }
+
+    fn names_and_values(rows: Vec<Vec<EnvironmentVariable>>) -> Vec<Vec<(String, String)>> {
+        rows.into_iter()
+            .map(|row| {
+                let mut row: Vec<_> = row.into_iter().map(|v| (v.name, v.value)).collect();
+                row.sort();
+                row
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_data_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("users.csv");
+        std::fs::write(&csv_path, "email, id\na@example.com,1\n\"b, c@example.com\",2\n").unwrap();
+        let json_path = dir.path().join("users.JSON");
+        std::fs::write(&json_path, r#"[{"email": "a@example.com", "id": 1, "active": true}]"#)
+            .unwrap();
+
+        assert_eq!(
+            names_and_values(read_data_file(&csv_path).unwrap()),
+            vec![
+                vec![
+                    ("email".to_string(), "a@example.com".to_string()),
+                    ("id".to_string(), "1".to_string()),
+                ],
+                vec![
+                    ("email".to_string(), "b, c@example.com".to_string()),
+                    ("id".to_string(), "2".to_string()),
+                ],
+            ]
+        );
+        assert_eq!(
+            names_and_values(read_data_file(&json_path).unwrap()),
+            vec![vec![
+                ("active".to_string(), "true".to_string()),
+                ("email".to_string(), "a@example.com".to_string()),
+                ("id".to_string(), "1".to_string()),
+            ]]
+        );
+
+        std::fs::write(&json_path, r#"{"email": "a@example.com"}"#).unwrap();
+        assert!(read_data_file(&json_path).is_err());
+    }
+}
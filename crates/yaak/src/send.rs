@@ -1,6 +1,17 @@
-use crate::render::render_http_request;
+use crate::assertion::evaluate_assertions;
+use crate::extraction::apply_response_extractions;
+use crate::graphql::{is_graphql_subscription, subscription_payload};
+use crate::oauth2::{
+    OAUTH2_AUTHENTICATION_TYPE, oauth2_config_hash, oauth2_token_header, resolve_oauth2_token,
+    store_oauth2_token,
+};
+use crate::pre_request::{PreRequestParams, run_pre_request_steps};
+use crate::render::{render_http_request, render_proxy_auth, render_ssh_tunnel};
+use crate::retry;
 use async_trait::async_trait;
+use futures::{SinkExt, StreamExt};
 use log::warn;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicI32, Ordering};
@@ -10,24 +21,33 @@ use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::sync::mpsc;
 use tokio::sync::watch;
+use url::Url;
 use yaak_crypto::manager::EncryptionManager;
+use yaak_crypto::secrets::SecretStore;
 use yaak_http::client::{
     HttpConnectionOptions, HttpConnectionProxySetting, HttpConnectionProxySettingAuth,
 };
 use yaak_http::cookies::CookieStore;
 use yaak_http::manager::HttpConnectionManager;
+use yaak_http::ntlm::NtlmCredentials;
 use yaak_http::sender::{HttpResponseEvent as SenderHttpResponseEvent, ReqwestSender};
+use yaak_http::ssh_tunnel::tunnels_url;
 use yaak_http::tee_reader::TeeReader;
 use yaak_http::transaction::HttpTransaction;
 use yaak_http::types::{
     SendableBody, SendableHttpRequest, SendableHttpRequestOptions, append_query_params,
 };
 use yaak_models::blob_manager::{BlobManager, BodyChunk};
+use yaak_models::body_storage::{
+    DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD, content_addressed_body_threshold,
+};
 use yaak_models::models::{
-    ClientCertificate, CookieJar, DnsOverride, Environment, HttpRequest, HttpResponse,
-    HttpResponseEvent, HttpResponseHeader, HttpResponseState, ProxySetting, ProxySettingAuth,
-    ResolvedSetting,
+    ClientCertificate, CookieJar, DnsOverride, Environment, EnvironmentVariable, HttpRequest,
+    HttpResponse, HttpResponseEvent, HttpResponseEventData, HttpResponseHeader,
+    HttpResponseRedirect, HttpResponseState, HttpVersion, ProxySetting, ProxySettingAuth,
+    ResolvedSetting, SshTunnel,
 };
+use yaak_models::queries::any_request::AnyRequest;
 use yaak_models::query_manager::QueryManager;
 use yaak_models::util::{UpdateSource, generate_prefixed_id};
 use yaak_plugins::events::{
@@ -35,12 +55,28 @@ use yaak_plugins::events::{
 };
 use yaak_plugins::manager::PluginManager;
 use yaak_plugins::template_callback::PluginTemplateCallback;
+use yaak_sse::parser::SseParser;
+use yaak_sse::sse::ServerSentEvent;
 use yaak_templates::{RenderOptions, TemplateCallback};
-use yaak_tls::find_client_certificate;
+use yaak_tls::{CaCertificateConfig, find_ca_certificates, select_client_certificate};
+use yaak_ws::graphql_ws::{
+    GRAPHQL_WS_PROTOCOL, GraphQlWsMessage, graphql_ws_complete, graphql_ws_connection_init,
+    graphql_ws_pong, graphql_ws_subscribe, graphql_ws_url,
+};
+use yaak_ws::{HeaderMap, HeaderName, HeaderValue, Message, ws_connect};
 
 const HTTP_EVENT_CHANNEL_CAPACITY: usize = 100;
 const REQUEST_BODY_CHUNK_SIZE: usize = 1024 * 1024;
+/// Streamed request bodies larger than this, like big file uploads, are sent without keeping a
+/// copy for the request tab
+const MAX_CAPTURED_REQUEST_BODY_BYTES: u64 = 50 * 1024 * 1024;
 const RESPONSE_PROGRESS_UPDATE_INTERVAL_MS: u128 = 100;
+const EVENT_STREAM_CONTENT_TYPE: &str = "text/event-stream";
+const EVENT_STREAM_DEFAULT_RETRY_MS: u64 = 3000;
+const EVENT_STREAM_MAX_RECONNECT_ATTEMPTS: u32 = 5;
+const NTLM_AUTHENTICATION_TYPE: &str = "windows";
+/// Only one operation runs per connection, so it always gets the same ID
+const GRAPHQL_SUBSCRIPTION_ID: &str = "1";
 
 #[derive(Debug, Error)]
 pub enum SendHttpRequestError {
@@ -68,6 +104,12 @@ pub enum SendHttpRequestError {
     #[error("Failed to prepare request before send: {0}")]
     PrepareSendableRequest(String),
 
+    #[error("Pre-request step failed: {0}")]
+    PreRequestStep(String),
+
+    #[error("Pre-request steps form a cycle: {0}")]
+    PreRequestCycle(String),
+
     #[error("Failed to persist response metadata: {0}")]
     PersistResponse(#[source] yaak_models::error::Error),
 
@@ -96,6 +138,9 @@ pub enum SendHttpRequestError {
         #[source]
         source: std::io::Error,
     },
+
+    #[error("GraphQL subscription failed: {0}")]
+    GraphQlSubscription(String),
 }
 
 pub type Result<T> = std::result::Result<T, SendHttpRequestError>;
@@ -153,7 +198,9 @@ impl SendRequestExecutor for DefaultSendRequestExecutor {
 }
 
 struct PluginPrepareSendableRequest {
+    query_manager: QueryManager,
     plugin_manager: Arc<PluginManager>,
+    encryption_manager: Arc<EncryptionManager>,
     plugin_context: PluginContext,
     cancelled_rx: Option<watch::Receiver<bool>>,
 }
@@ -173,7 +220,9 @@ impl PrepareSendableRequest for PluginPrepareSendableRequest {
                     sendable_request,
                     rendered_request,
                     auth_context_id,
+                    &self.query_manager,
                     &self.plugin_manager,
+                    self.encryption_manager.secrets(),
                     &self.plugin_context,
                 ) => result,
                 _ = cancelled_rx.changed() => Err("Request canceled".to_string()),
@@ -183,7 +232,9 @@ impl PrepareSendableRequest for PluginPrepareSendableRequest {
                 sendable_request,
                 rendered_request,
                 auth_context_id,
+                &self.query_manager,
                 &self.plugin_manager,
+                self.encryption_manager.secrets(),
                 &self.plugin_context,
             )
             .await
@@ -194,8 +245,7 @@ impl PrepareSendableRequest for PluginPrepareSendableRequest {
 struct ConnectionManagerSendRequestExecutor<'a> {
     connection_manager: &'a HttpConnectionManager,
     plugin_context_id: String,
-    query_manager: QueryManager,
-    request: HttpRequest,
+    runtime_config: HttpSendRuntimeConfig,
     cancelled_rx: Option<watch::Receiver<bool>>,
 }
 
@@ -207,18 +257,54 @@ impl SendRequestExecutor for ConnectionManagerSendRequestExecutor<'_> {
         event_tx: mpsc::Sender<SenderHttpResponseEvent>,
         cookie_behavior: CookieBehavior,
     ) -> yaak_http::error::Result<yaak_http::sender::HttpResponse> {
-        let runtime_config = resolve_http_send_runtime_config(&self.query_manager, &self.request)
-            .map_err(|e| yaak_http::error::Error::RequestError(e.to_string()))?;
-        let client_certificate =
-            find_client_certificate(&sendable_request.url, &runtime_config.client_certificates);
+        let runtime_config = &self.runtime_config;
+        let client_certificate = select_client_certificate(
+            &sendable_request.url,
+            runtime_config.client_certificate.as_ref(),
+            &runtime_config.client_certificates,
+        );
+        // NTLM needs a client that keeps connections open
+        let connection_auth = sendable_request.options.ntlm.is_some();
+        let http_version = sendable_request.options.http_version;
+        let connect_timeout = sendable_request.options.connect_timeout;
+        let read_timeout = sendable_request.options.read_timeout;
+        let unix_socket = sendable_request.options.unix_socket.clone();
+        // Tunneled requests go through the tunnel's local SOCKS5 proxy instead of the usual one
+        let mut proxy = runtime_config.proxy.clone();
+        if let Some(tunnel) =
+            runtime_config.ssh_tunnel.as_ref().filter(|t| tunnels_url(t, &sendable_request.url))
+        {
+            let tunnel_proxy = self.connection_manager.open_ssh_tunnel(tunnel).await?;
+            let _ = event_tx.try_send(SenderHttpResponseEvent::Info(format!(
+                "Tunneling through SSH host {}",
+                tunnel.host.trim()
+            )));
+            proxy = HttpConnectionProxySetting::Enabled {
+                http: tunnel_proxy.url(),
+                https: tunnel_proxy.url(),
+                auth: Some(HttpConnectionProxySettingAuth {
+                    user: tunnel_proxy.user,
+                    password: tunnel_proxy.password,
+                }),
+                bypass: String::new(),
+            };
+        }
+        // Clients are cached by every option they're built with, so requests with different
+        // certificates, proxies, or timeouts never share one
         let cached_client = self
             .connection_manager
             .get_client(&HttpConnectionOptions {
                 id: self.plugin_context_id.clone(),
                 validate_certificates: runtime_config.validate_certificates,
-                proxy: runtime_config.proxy,
+                proxy,
                 client_certificate,
-                dns_overrides: runtime_config.dns_overrides,
+                ca_certificates: runtime_config.ca_certificates.clone(),
+                dns_overrides: runtime_config.dns_overrides.clone(),
+                http_version,
+                unix_socket,
+                connect_timeout,
+                read_timeout,
+                connection_auth,
             })
             .await?;
 
@@ -279,6 +365,12 @@ pub struct SendHttpRequestParams<'a, T: TemplateCallback> {
     pub existing_response: Option<HttpResponse>,
     pub prepare_sendable_request: Option<&'a dyn PrepareSendableRequest>,
     pub executor: Option<&'a dyn SendRequestExecutor>,
+    /// Variables that take precedence over every environment, like the current row of a
+    /// data-driven run
+    pub variable_overrides: Vec<EnvironmentVariable>,
+    /// Stream the body to this file instead of the response directory, without keeping it in
+    /// memory, indexing it, or evaluating assertions against it
+    pub download_path: Option<&'a Path>,
 }
 
 pub struct SendHttpRequestWithPluginsParams<'a> {
@@ -297,6 +389,8 @@ pub struct SendHttpRequestWithPluginsParams<'a> {
     pub plugin_context: &'a PluginContext,
     pub cancelled_rx: Option<watch::Receiver<bool>>,
     pub connection_manager: Option<&'a HttpConnectionManager>,
+    pub variable_overrides: Vec<EnvironmentVariable>,
+    pub download_path: Option<&'a Path>,
 }
 
 pub struct SendHttpRequestByIdWithPluginsParams<'a> {
@@ -319,6 +413,7 @@ pub struct SendHttpRequestByIdWithPluginsParams<'a> {
 pub struct SendHttpRequestResult {
     pub rendered_request: HttpRequest,
     pub response: HttpResponse,
+    /// Empty when the body was downloaded to a file
     pub response_body: Vec<u8>,
 }
 
@@ -327,7 +422,11 @@ pub struct HttpSendRuntimeConfig {
     pub validate_certificates: bool,
     pub proxy: HttpConnectionProxySetting,
     pub dns_overrides: Vec<DnsOverride>,
+    pub ssh_tunnel: Option<SshTunnel>,
+    /// Certificate set on the request's folders, presented to every host
+    pub client_certificate: Option<ClientCertificate>,
     pub client_certificates: Vec<ClientCertificate>,
+    pub ca_certificates: Vec<CaCertificateConfig>,
 }
 
 pub fn resolve_http_send_runtime_config(
@@ -341,25 +440,74 @@ pub fn resolve_http_send_runtime_config(
         .resolve_settings_for_http_request(request)
         .map_err(SendHttpRequestError::ResolveRequestInheritance)?;
     let settings = db.get_settings();
+    let proxy = db.resolve_proxy_for_workspace(&workspace);
 
     Ok(HttpSendRuntimeConfig {
         send_options: SendableHttpRequestOptions {
             follow_redirects: resolved_settings.follow_redirects.value,
-            timeout: if resolved_settings.request_timeout.value > 0 {
-                Some(std::time::Duration::from_millis(
-                    resolved_settings.request_timeout.value.unsigned_abs() as u64,
-                ))
-            } else {
-                None
-            },
+            max_redirects: Some(
+                usize::try_from(resolved_settings.max_redirects.value).unwrap_or(0),
+            ),
+            redirect_drop_body: !resolved_settings.redirect_resend_body.value,
+            redirect_keep_authorization: !resolved_settings.redirect_strip_authorization.value,
+            skip_decompression: !resolved_settings.decompress_response.value,
+            timeout: timeout_from_millis(resolved_settings.request_timeout.value),
+            connect_timeout: timeout_from_millis(resolved_settings.connect_timeout.value),
+            read_timeout: timeout_from_millis(resolved_settings.read_timeout.value),
+            http_version: resolved_settings.http_version.value,
+            ntlm: None,
+            unix_socket: None,
         },
         validate_certificates: resolved_settings.validate_certificates.value,
-        proxy: proxy_setting_from_settings(settings.proxy),
+        proxy: proxy_setting_from_settings(proxy),
+        ca_certificates: find_ca_certificates(&resolved_settings.ca_certificates.value),
         dns_overrides: workspace.setting_dns_overrides,
+        ssh_tunnel: resolved_settings.ssh_tunnel.value.filter(|t| !t.disabled),
+        client_certificate: resolved_settings.client_certificate.value,
         client_certificates: settings.client_certificates,
     })
 }
 
+/// Resolve the runtime config with the proxy and SSH tunnel credentials rendered, since they're
+/// usually set from environment variables
+async fn resolve_rendered_http_send_runtime_config<T: TemplateCallback>(
+    query_manager: &QueryManager,
+    request: &HttpRequest,
+    environment_id: Option<&str>,
+    variable_overrides: &[EnvironmentVariable],
+    template_callback: &T,
+) -> Result<HttpSendRuntimeConfig> {
+    let mut runtime_config = resolve_http_send_runtime_config(query_manager, request)?;
+    let mut environment_chain = resolve_environment_chain(query_manager, request, environment_id)?;
+    if let Some(overrides) = variable_overrides_environment(variable_overrides) {
+        environment_chain.insert(0, overrides);
+    }
+
+    if let Some(tunnel) = runtime_config.ssh_tunnel.as_mut() {
+        *tunnel = render_ssh_tunnel(
+            tunnel,
+            environment_chain.clone(),
+            template_callback,
+            &RenderOptions::throw(),
+        )
+        .await
+        .map_err(SendHttpRequestError::RenderRequest)?;
+    }
+    if let HttpConnectionProxySetting::Enabled { auth: Some(auth), .. } = &mut runtime_config.proxy
+    {
+        let rendered = render_proxy_auth(
+            &ProxySettingAuth { user: auth.user.clone(), password: auth.password.clone() },
+            environment_chain,
+            template_callback,
+            &RenderOptions::throw(),
+        )
+        .await
+        .map_err(SendHttpRequestError::RenderRequest)?;
+        *auth = HttpConnectionProxySettingAuth { user: rendered.user, password: rendered.password };
+    }
+    Ok(runtime_config)
+}
+
 pub async fn send_http_request_by_id_with_plugins(
     params: SendHttpRequestByIdWithPluginsParams<'_>,
 ) -> Result<SendHttpRequestResult> {
@@ -385,12 +533,41 @@ pub async fn send_http_request_by_id_with_plugins(
         plugin_context: params.plugin_context,
         cancelled_rx: params.cancelled_rx,
         connection_manager: params.connection_manager,
+        variable_overrides: Vec::new(),
+        download_path: None,
     })
     .await
 }
 
 pub async fn send_http_request_with_plugins(
     params: SendHttpRequestWithPluginsParams<'_>,
+) -> Result<SendHttpRequestResult> {
+    run_pre_request_steps(
+        &PreRequestParams {
+            query_manager: params.query_manager,
+            blob_manager: params.blob_manager,
+            environment_id: params.environment_id,
+            update_source: params.update_source.clone(),
+            cookie_jar_id: params.cookie_jar_id.clone(),
+            response_dir: params.response_dir,
+            plugin_manager: params.plugin_manager.clone(),
+            encryption_manager: params.encryption_manager.clone(),
+            plugin_context: params.plugin_context,
+            cancelled_rx: params.cancelled_rx.clone(),
+            connection_manager: params.connection_manager,
+            variable_overrides: params.variable_overrides.clone(),
+        },
+        &AnyRequest::HttpRequest(params.request.clone()),
+    )
+    .await?;
+
+    send_http_request_with_plugins_without_steps(params).await
+}
+
+/// Send a request with plugin support but skip its pre-request steps, for dependencies whose
+/// steps the pre-request pipeline has already run
+pub(crate) async fn send_http_request_with_plugins_without_steps(
+    params: SendHttpRequestWithPluginsParams<'_>,
 ) -> Result<SendHttpRequestResult> {
     let template_callback = PluginTemplateCallback::new(
         params.plugin_manager.clone(),
@@ -399,18 +576,28 @@ pub async fn send_http_request_with_plugins(
         RenderPurpose::Send,
     );
     let auth_hook = PluginPrepareSendableRequest {
+        query_manager: params.query_manager.clone(),
         plugin_manager: params.plugin_manager,
+        encryption_manager: params.encryption_manager,
         plugin_context: params.plugin_context.clone(),
         cancelled_rx: params.cancelled_rx.clone(),
     };
-    let executor =
-        params.connection_manager.map(|connection_manager| ConnectionManagerSendRequestExecutor {
+    let executor = match params.connection_manager {
+        Some(connection_manager) => Some(ConnectionManagerSendRequestExecutor {
             connection_manager,
             plugin_context_id: params.plugin_context.id.clone(),
-            query_manager: params.query_manager.clone(),
-            request: params.request.clone(),
+            runtime_config: resolve_rendered_http_send_runtime_config(
+                params.query_manager,
+                &params.request,
+                params.environment_id,
+                &params.variable_overrides,
+                &template_callback,
+            )
+            .await?,
             cancelled_rx: params.cancelled_rx.clone(),
-        });
+        }),
+        None => None,
+    };
 
     send_http_request(SendHttpRequestParams {
         query_manager: params.query_manager,
@@ -429,6 +616,8 @@ pub async fn send_http_request_with_plugins(
         existing_response: params.existing_response,
         prepare_sendable_request: Some(&auth_hook),
         executor: executor.as_ref().map(|e| e as &dyn SendRequestExecutor),
+        variable_overrides: params.variable_overrides,
+        download_path: params.download_path,
     })
     .await
 }
@@ -460,6 +649,8 @@ pub async fn send_http_request_by_id<T: TemplateCallback>(
         prepare_sendable_request: params.prepare_sendable_request,
         executor: params.executor,
         auth_context_id: Some(auth_context_id),
+        variable_overrides: Vec::new(),
+        download_path: None,
     })
     .await
 }
@@ -467,8 +658,11 @@ pub async fn send_http_request_by_id<T: TemplateCallback>(
 pub async fn send_http_request<T: TemplateCallback>(
     params: SendHttpRequestParams<'_, T>,
 ) -> Result<SendHttpRequestResult> {
-    let environment_chain =
+    let mut environment_chain =
         resolve_environment_chain(params.query_manager, &params.request, params.environment_id)?;
+    if let Some(overrides) = variable_overrides_environment(&params.variable_overrides) {
+        environment_chain.insert(0, overrides);
+    }
     let (resolved_request, auth_context_id) =
         if let Some(auth_context_id) = params.auth_context_id.clone() {
             (params.request.clone(), auth_context_id)
@@ -476,13 +670,15 @@ pub async fn send_http_request<T: TemplateCallback>(
             resolve_inherited_request(params.query_manager, &params.request)?
         };
     let runtime_config = resolve_http_send_runtime_config(params.query_manager, &params.request)?;
-    let send_options = params.send_options.unwrap_or(runtime_config.send_options);
+    let mut send_options =
+        params.send_options.unwrap_or_else(|| runtime_config.send_options.clone());
     let resolved_settings = params
         .query_manager
         .connect()
         .resolve_settings_for_http_request(&params.request)
         .map_err(SendHttpRequestError::ResolveRequestInheritance)?;
-    let mut cookie_jar = load_cookie_jar(params.query_manager, params.cookie_jar_id.as_deref())?;
+    let mut cookie_jar =
+        load_cookie_jar(params.query_manager, &environment_chain, params.cookie_jar_id.as_deref())?;
     let cookie_store =
         cookie_jar.as_ref().map(|jar| CookieStore::from_cookies(jar.cookies.clone()));
     let cookie_behavior = CookieBehavior {
@@ -500,8 +696,17 @@ pub async fn send_http_request<T: TemplateCallback>(
     .await
     .map_err(SendHttpRequestError::RenderRequest)?;
 
+    // Event streams stay open for as long as the server keeps sending, so the request timeout
+    // would otherwise cut them off
+    let graphql_subscription = is_graphql_subscription(&rendered_request);
+    if accepts_event_stream(&rendered_request) || graphql_subscription {
+        send_options.timeout = None;
+    }
+    send_options.unix_socket =
+        rendered_request.unix_socket.as_deref().filter(|p| !p.is_empty()).map(PathBuf::from);
+
     let mut sendable_request =
-        SendableHttpRequest::from_http_request(&rendered_request, send_options)
+        SendableHttpRequest::from_http_request(&rendered_request, send_options.clone())
             .await
             .map_err(SendHttpRequestError::BuildSendableRequest)?;
 
@@ -531,10 +736,15 @@ pub async fn send_http_request<T: TemplateCallback>(
     response.status_reason = None;
     response.headers = Vec::new();
     response.remote_addr = None;
+    response.tls = None;
+    response.request_head = None;
+    response.redirects = Vec::new();
     response.version = None;
     response.elapsed = 0;
     response.elapsed_headers = 0;
     response.elapsed_dns = 0;
+    response.elapsed_connect = 0;
+    response.elapsed_send = 0;
     let persist_response = !response.request_id.is_empty();
     if persist_response {
         response = params
@@ -560,6 +770,11 @@ pub async fn send_http_request<T: TemplateCallback>(
                     request_body_capture_error = Some(err);
                 }
             }
+            Some(SendableBody::Stream { content_length: Some(len), .. })
+                if *len > MAX_CAPTURED_REQUEST_BODY_BYTES =>
+            {
+                response.request_content_length = Some(u64_to_i32(*len));
+            }
             Some(SendableBody::Stream { data, .. }) => {
                 let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Vec<u8>>();
                 let inner = std::mem::replace(data, Box::pin(tokio::io::empty()));
@@ -584,10 +799,32 @@ pub async fn send_http_request<T: TemplateCallback>(
     let emit_events_to = params.emit_events_to.clone();
     let dns_elapsed = Arc::new(AtomicI32::new(0));
     let event_dns_elapsed = dns_elapsed.clone();
+    let connect_elapsed = Arc::new(AtomicI32::new(0));
+    let event_connect_elapsed = connect_elapsed.clone();
+    let send_elapsed = Arc::new(AtomicI32::new(0));
+    let event_send_elapsed = send_elapsed.clone();
     let event_handle = tokio::spawn(async move {
+        let mut connected_at = None;
         while let Some(event) = event_rx.recv().await {
-            if let SenderHttpResponseEvent::DnsResolved { duration, .. } = &event {
-                event_dns_elapsed.store(u64_to_i32(*duration), Ordering::Relaxed);
+            match &event {
+                SenderHttpResponseEvent::DnsResolved { duration, .. } => {
+                    event_dns_elapsed.store(u64_to_i32(*duration), Ordering::Relaxed);
+                }
+                SenderHttpResponseEvent::Connected { duration } => {
+                    // The connection time includes the DNS lookup that preceded it
+                    let dns = event_dns_elapsed.load(Ordering::Relaxed);
+                    event_connect_elapsed
+                        .store((u64_to_i32(*duration) - dns).max(0), Ordering::Relaxed);
+                    connected_at = Some(Instant::now());
+                }
+                SenderHttpResponseEvent::UploadProgress { .. } => {
+                    // The last upload event is reported once the whole body has been read
+                    if let Some(connected_at) = connected_at {
+                        event_send_elapsed
+                            .store(duration_to_i32(connected_at.elapsed()), Ordering::Relaxed);
+                    }
+                }
+                _ => {}
             }
 
             if persist_response {
@@ -615,6 +852,56 @@ pub async fn send_http_request<T: TemplateCallback>(
     let started_at = Instant::now();
     let request_started_url = sendable_request.url.clone();
 
+    if graphql_subscription {
+        let result = match subscription_payload(&rendered_request.body) {
+            Ok(payload) => {
+                send_graphql_subscription(GraphQlSubscriptionParams {
+                    query_manager: params.query_manager,
+                    blob_manager: params.blob_manager,
+                    update_source: &params.update_source,
+                    runtime_config: &runtime_config,
+                    url: &sendable_request.url,
+                    headers: &sendable_request.headers,
+                    payload,
+                    response: response.clone(),
+                    persist_response,
+                    response_dir: params.response_dir,
+                    started_at,
+                    cancelled_rx: params.cancelled_rx.clone(),
+                })
+                .await
+            }
+            Err(err) => Err(SendHttpRequestError::GraphQlSubscription(err)),
+        };
+        drop(event_tx);
+        if let Err(join_err) = event_handle.await {
+            warn!("Failed to join response event task: {}", join_err);
+        }
+        if let Some(task) = request_body_capture_task.take() {
+            let _ = task.await;
+        }
+        return match result {
+            Ok((response, response_body)) => {
+                Ok(SendHttpRequestResult { rendered_request, response, response_body })
+            }
+            Err(err) => {
+                if persist_response {
+                    let _ = persist_response_error(
+                        params.query_manager,
+                        params.blob_manager,
+                        &params.update_source,
+                        &response,
+                        started_at,
+                        HttpResponseState::Closed,
+                        err.to_string(),
+                        request_started_url,
+                    );
+                }
+                Err(err)
+            }
+        };
+    }
+
     send_setting_event(
         &event_tx,
         "validate_certificates",
@@ -627,12 +914,62 @@ pub async fn send_http_request<T: TemplateCallback>(
         sendable_request.options.follow_redirects.to_string(),
         &resolved_settings.follow_redirects,
     );
+    send_setting_event(
+        &event_tx,
+        "max_redirects",
+        resolved_settings.max_redirects.value.to_string(),
+        &resolved_settings.max_redirects,
+    );
+    send_setting_event(
+        &event_tx,
+        "redirect_resend_body",
+        resolved_settings.redirect_resend_body.value.to_string(),
+        &resolved_settings.redirect_resend_body,
+    );
+    send_setting_event(
+        &event_tx,
+        "redirect_strip_authorization",
+        resolved_settings.redirect_strip_authorization.value.to_string(),
+        &resolved_settings.redirect_strip_authorization,
+    );
     send_setting_event(
         &event_tx,
         "timeout",
         timeout_setting_value(sendable_request.options.timeout),
         &resolved_settings.request_timeout,
     );
+    send_setting_event(
+        &event_tx,
+        "connect_timeout",
+        timeout_setting_value(sendable_request.options.connect_timeout),
+        &resolved_settings.connect_timeout,
+    );
+    send_setting_event(
+        &event_tx,
+        "read_timeout",
+        timeout_setting_value(sendable_request.options.read_timeout),
+        &resolved_settings.read_timeout,
+    );
+    send_setting_event(
+        &event_tx,
+        "http_version",
+        http_version_setting_value(sendable_request.options.http_version),
+        &resolved_settings.http_version,
+    );
+    send_setting_event(
+        &event_tx,
+        "decompress_response",
+        resolved_settings.decompress_response.value.to_string(),
+        &resolved_settings.decompress_response,
+    );
+    if let Some(tunnel) = resolved_settings.ssh_tunnel.value.as_ref().filter(|t| !t.disabled) {
+        send_setting_event(
+            &event_tx,
+            "ssh_tunnel",
+            tunnel.host.trim(),
+            &resolved_settings.ssh_tunnel,
+        );
+    }
     send_setting_event(
         &event_tx,
         "send_cookies",
@@ -646,35 +983,83 @@ pub async fn send_http_request<T: TemplateCallback>(
         &resolved_settings.store_cookies,
     );
 
-    let mut http_response =
-        match executor.send(sendable_request, event_tx, cookie_behavior.clone()).await {
-            Ok(response) => response,
-            Err(err) => {
-                persist_cookie_jar(
-                    params.query_manager,
-                    cookie_jar.as_mut(),
-                    cookie_behavior.store.as_ref(),
-                )?;
-                if persist_response {
-                    let _ = persist_response_error(
-                        params.query_manager,
-                        params.blob_manager,
-                        &params.update_source,
-                        &response,
-                        started_at,
-                        err.to_string(),
-                        request_started_url,
-                    );
-                }
-                if let Err(join_err) = event_handle.await {
-                    warn!("Failed to join response event task: {}", join_err);
-                }
-                if let Some(task) = request_body_capture_task.take() {
-                    let _ = task.await;
-                }
-                return Err(SendHttpRequestError::SendRequest(err));
+    let retry_policy = resolved_settings.retry_policy.value.clone();
+    let max_attempts = retry::max_attempts(&retry_policy);
+    send_setting_event(
+        &event_tx,
+        "max_attempts",
+        max_attempts.to_string(),
+        &resolved_settings.retry_policy,
+    );
+
+    let mut attempt = 1;
+    let mut next_request = Some(sendable_request);
+    let send_result = loop {
+        let request = match next_request.take() {
+            Some(request) => request,
+            None => {
+                rebuild_sendable_request(
+                    params.prepare_sendable_request,
+                    &rendered_request,
+                    &auth_context_id,
+                    send_options.clone(),
+                    None,
+                )
+                .await?
             }
         };
+        let result = executor.send(request, event_tx.clone(), cookie_behavior.clone()).await;
+        let reason = match &result {
+            Ok(r) => retry::retry_reason_for_status(&retry_policy, r.status),
+            Err(err) => retry::retry_reason_for_error(&retry_policy, err),
+        };
+        let Some(reason) = reason.filter(|_| attempt < max_attempts) else {
+            break result;
+        };
+
+        let delay = retry::retry_delay(&retry_policy, attempt);
+        attempt += 1;
+        let _ = event_tx.try_send(SenderHttpResponseEvent::Retry {
+            attempt,
+            max_attempts,
+            delay: delay.as_millis() as u64,
+            reason,
+        });
+        if sleep_unless_cancelled(params.cancelled_rx.clone(), delay).await {
+            break result;
+        }
+    };
+
+    let mut http_response = match send_result {
+        Ok(response) => response,
+        Err(err) => {
+            persist_cookie_jar(
+                params.query_manager,
+                cookie_jar.as_mut(),
+                cookie_behavior.store.as_ref(),
+            )?;
+            if persist_response {
+                let _ = persist_response_error(
+                    params.query_manager,
+                    params.blob_manager,
+                    &params.update_source,
+                    &response,
+                    started_at,
+                    response_state_for_error(&err),
+                    err.to_string(),
+                    request_started_url,
+                );
+            }
+            drop(event_tx);
+            if let Err(join_err) = event_handle.await {
+                warn!("Failed to join response event task: {}", join_err);
+            }
+            if let Some(task) = request_body_capture_task.take() {
+                let _ = task.await;
+            }
+            return Err(SendHttpRequestError::SendRequest(err));
+        }
+    };
 
     let headers_elapsed = duration_to_i32(started_at.elapsed());
     std::fs::create_dir_all(params.response_dir).map_err(|source| {
@@ -683,7 +1068,12 @@ pub async fn send_http_request<T: TemplateCallback>(
             source,
         }
     })?;
-    let body_path = params.response_dir.join(&response.id);
+    let downloading = params.download_path.is_some();
+    let body_path = match params.download_path {
+        Some(path) => path.to_path_buf(),
+        None => params.response_dir.join(&response.id),
+    };
+    let stored_body_path = (!downloading).then(|| body_path.to_string_lossy().to_string());
     let connected_response = HttpResponse {
         state: HttpResponseState::Connected,
         elapsed_headers: headers_elapsed,
@@ -691,9 +1081,14 @@ pub async fn send_http_request<T: TemplateCallback>(
         status_reason: http_response.status_reason.clone(),
         url: http_response.url.clone(),
         remote_addr: http_response.remote_addr.clone(),
+        tls: http_response.tls.clone(),
+        request_head: http_response.request_head.clone(),
         version: http_response.version.clone(),
         elapsed_dns: dns_elapsed.load(Ordering::Relaxed),
-        body_path: Some(body_path.to_string_lossy().to_string()),
+        elapsed_connect: connect_elapsed.load(Ordering::Relaxed),
+        elapsed_send: send_elapsed.load(Ordering::Relaxed),
+        body_path: stored_body_path.clone(),
+        download_path: params.download_path.map(|p| p.to_string_lossy().to_string()),
         content_length: http_response.content_length.map(u64_to_i32),
         headers: http_response
             .headers
@@ -705,6 +1100,16 @@ pub async fn send_http_request<T: TemplateCallback>(
             .iter()
             .map(|(name, value)| HttpResponseHeader { name: name.clone(), value: value.clone() })
             .collect(),
+        redirects: http_response
+            .redirects
+            .iter()
+            .map(|hop| HttpResponseRedirect {
+                url: hop.url.clone(),
+                method: hop.method.clone(),
+                status: i32::from(hop.status),
+                location: hop.location.clone(),
+            })
+            .collect(),
         ..response
     };
     if persist_response {
@@ -723,11 +1128,19 @@ pub async fn send_http_request<T: TemplateCallback>(
         )?;
     let mut body_stream =
         http_response.into_body_stream().map_err(SendHttpRequestError::ReadResponseBody)?;
+    // Bytes read off the wire, counted separately for each event stream reconnect
+    let mut compressed_bytes = http_response.compressed_bytes_counter();
+    let mut earlier_compressed_bytes: u64 = 0;
     let mut response_body = Vec::new();
     let mut body_read_error = None;
     let mut written_bytes: usize = 0;
     let mut last_progress_update = started_at;
     let mut cancelled_rx = params.cancelled_rx.clone();
+    let expected_length = http_response.content_length;
+    let mut sse_parser =
+        (!downloading && is_event_stream(&http_response.headers)).then(SseParser::new);
+    let mut sse_retry_ms = EVENT_STREAM_DEFAULT_RETRY_MS;
+    let mut sse_reconnect_attempts: u32 = 0;
 
     loop {
         let read_result = if let Some(cancelled_rx) = cancelled_rx.as_mut() {
@@ -768,11 +1181,37 @@ pub async fn send_http_request<T: TemplateCallback>(
                 if let Some(tx) = params.emit_response_body_chunks_to.as_ref() {
                     let _ = tx.send(chunk.to_vec());
                 }
+                if let Some(parser) = sse_parser.as_mut() {
+                    for event in parser.feed(chunk) {
+                        sse_reconnect_attempts = 0;
+                        if let Some(retry) = event.retry {
+                            sse_retry_ms = retry;
+                        }
+                        if persist_response {
+                            persist_server_sent_event(
+                                params.query_manager,
+                                &params.update_source,
+                                &response,
+                                event,
+                            );
+                        }
+                    }
+                }
 
                 let now = Instant::now();
                 let should_update = now.duration_since(last_progress_update).as_millis()
                     >= RESPONSE_PROGRESS_UPDATE_INTERVAL_MS;
+                if downloading {
+                    // Only the current chunk is kept in memory, so large downloads don't pile up
+                    response_body.clear();
+                }
                 if should_update {
+                    if downloading {
+                        let _ = event_tx.try_send(SenderHttpResponseEvent::DownloadProgress {
+                            bytes: written_bytes as u64,
+                            total: expected_length,
+                        });
+                    }
                     let elapsed = duration_to_i32(started_at.elapsed());
                     let progress_response = HttpResponse {
                         elapsed,
@@ -797,13 +1236,95 @@ pub async fn send_http_request<T: TemplateCallback>(
                 }
             }
             Err(err) => {
-                body_read_error = Some(SendHttpRequestError::ReadResponseBody(
-                    yaak_http::error::Error::BodyReadError(err.to_string()),
-                ));
-                break;
+                let read_error = SendHttpRequestError::ReadResponseBody(
+                    if err.kind() == std::io::ErrorKind::TimedOut {
+                        send_options.timeout_error(started_at.elapsed(), false)
+                    } else {
+                        yaak_http::error::Error::BodyReadError(err.to_string())
+                    },
+                );
+
+                // Event streams that drop mid-stream are resumed from the last event received,
+                // the same way a browser's EventSource would
+                let can_reconnect = rendered_request.method.eq_ignore_ascii_case("GET")
+                    && sse_reconnect_attempts < EVENT_STREAM_MAX_RECONNECT_ATTEMPTS;
+                let Some(parser) = sse_parser.as_mut().filter(|_| can_reconnect) else {
+                    body_read_error = Some(read_error);
+                    break;
+                };
+                parser.reset();
+                sse_reconnect_attempts += 1;
+
+                let _ = event_tx.try_send(SenderHttpResponseEvent::Info(format!(
+                    "Event stream interrupted ({err}), reconnecting in {sse_retry_ms}ms \
+                     (attempt {sse_reconnect_attempts} of {EVENT_STREAM_MAX_RECONNECT_ATTEMPTS})"
+                )));
+                let delay = Duration::from_millis(sse_retry_ms);
+                if sleep_unless_cancelled(cancelled_rx.clone(), delay).await {
+                    break;
+                }
+
+                let mut reconnect_request = match rebuild_sendable_request(
+                    params.prepare_sendable_request,
+                    &rendered_request,
+                    &auth_context_id,
+                    send_options.clone(),
+                    parser.last_event_id(),
+                )
+                .await
+                {
+                    Ok(r) => r,
+                    Err(err) => {
+                        body_read_error = Some(err);
+                        break;
+                    }
+                };
+                reconnect_request.options.timeout = None;
+                let mut reconnected = match executor
+                    .send(reconnect_request, event_tx.clone(), cookie_behavior.clone())
+                    .await
+                {
+                    Ok(r) => r,
+                    Err(err) => {
+                        body_read_error = Some(SendHttpRequestError::SendRequest(err));
+                        break;
+                    }
+                };
+
+                // A 204 is the server's way of saying the stream is finished
+                if reconnected.status == 204 {
+                    break;
+                }
+                if !(200..300).contains(&reconnected.status)
+                    || !is_event_stream(&reconnected.headers)
+                {
+                    body_read_error = Some(SendHttpRequestError::SendRequest(
+                        yaak_http::error::Error::RequestError(format!(
+                            "Event stream reconnect returned status {} without an event stream",
+                            reconnected.status
+                        )),
+                    ));
+                    break;
+                }
+                earlier_compressed_bytes += compressed_bytes.load(Ordering::Relaxed);
+                compressed_bytes = reconnected.compressed_bytes_counter();
+                body_stream = match reconnected.into_body_stream() {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        body_read_error = Some(SendHttpRequestError::ReadResponseBody(err));
+                        break;
+                    }
+                };
             }
         }
     }
+    if downloading {
+        let _ = event_tx.try_send(SenderHttpResponseEvent::DownloadProgress {
+            bytes: written_bytes as u64,
+            total: expected_length,
+        });
+    }
+    drop(event_tx);
 
     file.flush().await.map_err(|source| SendHttpRequestError::WriteResponseBody {
         path: body_path.clone(),
@@ -834,12 +1355,18 @@ pub async fn send_http_request<T: TemplateCallback>(
 
     if let Some(err) = body_read_error {
         if persist_response {
+            let state = match &err {
+                SendHttpRequestError::SendRequest(e)
+                | SendHttpRequestError::ReadResponseBody(e) => response_state_for_error(e),
+                _ => HttpResponseState::Closed,
+            };
             let _ = persist_response_error(
                 params.query_manager,
                 params.blob_manager,
                 &params.update_source,
                 &response,
                 started_at,
+                state,
                 err.to_string(),
                 request_started_url,
             );
@@ -852,32 +1379,353 @@ pub async fn send_http_request<T: TemplateCallback>(
         return Err(err);
     }
 
-    let compressed_length = http_response.content_length.unwrap_or(written_bytes as u64);
+    let compressed_length = earlier_compressed_bytes + compressed_bytes.load(Ordering::Relaxed);
     let final_response = HttpResponse {
-        body_path: Some(body_path.to_string_lossy().to_string()),
+        body_path: stored_body_path,
         content_length: Some(usize_to_i32(written_bytes)),
         content_length_compressed: Some(u64_to_i32(compressed_length)),
         elapsed: duration_to_i32(started_at.elapsed()),
         elapsed_headers: headers_elapsed,
         elapsed_dns: dns_elapsed.load(Ordering::Relaxed),
+        elapsed_connect: connect_elapsed.load(Ordering::Relaxed),
+        elapsed_send: send_elapsed.load(Ordering::Relaxed),
         state: HttpResponseState::Closed,
         ..response
     };
+    let final_response = HttpResponse {
+        assertion_results: evaluate_assertions(
+            &params.request.assertions,
+            &final_response,
+            &response_body,
+        ),
+        ..final_response
+    };
     if persist_response {
         response = params
             .query_manager
             .connect()
             .upsert_http_response(&final_response, &params.update_source, params.blob_manager)
             .map_err(SendHttpRequestError::PersistResponse)?;
+        if !downloading {
+            let threshold = params
+                .query_manager
+                .connect()
+                .get_workspace(&response.workspace_id)
+                .map_or(DEFAULT_CONTENT_ADDRESSED_BODY_THRESHOLD, |w| {
+                    content_addressed_body_threshold(&w)
+                });
+            match params.query_manager.connect().store_http_response_body_by_hash(
+                &response,
+                params.response_dir,
+                threshold,
+                &params.update_source,
+            ) {
+                Ok(r) => response = r,
+                Err(err) => {
+                    warn!("Failed to move response body to content-addressed storage: {err}")
+                }
+            }
+            if let Err(err) =
+                params.query_manager.connect().index_http_response_body(&response, &response_body)
+            {
+                warn!("Failed to index response body for search: {err}");
+            }
+        }
     } else {
         response = final_response;
     }
 
     persist_cookie_jar(params.query_manager, cookie_jar.as_mut(), cookie_behavior.store.as_ref())?;
 
+    if persist_response {
+        apply_response_extractions(
+            params.query_manager,
+            &params.request,
+            &response,
+            params.environment_id,
+            &params.update_source,
+        );
+    }
+
     Ok(SendHttpRequestResult { rendered_request, response, response_body })
 }
 
+fn accepts_event_stream(request: &HttpRequest) -> bool {
+    request.headers.iter().any(|h| {
+        h.enabled
+            && h.name.eq_ignore_ascii_case("accept")
+            && h.value.to_ascii_lowercase().contains(EVENT_STREAM_CONTENT_TYPE)
+    })
+}
+
+fn is_event_stream(headers: &[(String, String)]) -> bool {
+    headers.iter().any(|(name, value)| {
+        name.eq_ignore_ascii_case("content-type")
+            && value.trim().to_ascii_lowercase().starts_with(EVENT_STREAM_CONTENT_TYPE)
+    })
+}
+
+/// Build a fresh sendable request for another attempt at the same rendered request, since the
+/// body of the previous one may already have been consumed
+async fn rebuild_sendable_request(
+    prepare_sendable_request: Option<&dyn PrepareSendableRequest>,
+    rendered_request: &HttpRequest,
+    auth_context_id: &str,
+    send_options: SendableHttpRequestOptions,
+    last_event_id: Option<&str>,
+) -> Result<SendableHttpRequest> {
+    let mut sendable_request =
+        SendableHttpRequest::from_http_request(rendered_request, send_options)
+            .await
+            .map_err(SendHttpRequestError::BuildSendableRequest)?;
+    if let Some(hook) = prepare_sendable_request {
+        hook.prepare_sendable_request(rendered_request, auth_context_id, &mut sendable_request)
+            .await
+            .map_err(SendHttpRequestError::PrepareSendableRequest)?;
+    }
+    if let Some(id) = last_event_id {
+        sendable_request.headers.retain(|(name, _)| !name.eq_ignore_ascii_case("last-event-id"));
+        sendable_request.headers.push(("Last-Event-ID".to_string(), id.to_string()));
+    }
+    Ok(sendable_request)
+}
+
+struct GraphQlSubscriptionParams<'a> {
+    query_manager: &'a QueryManager,
+    blob_manager: &'a BlobManager,
+    update_source: &'a UpdateSource,
+    runtime_config: &'a HttpSendRuntimeConfig,
+    url: &'a str,
+    headers: &'a [(String, String)],
+    payload: serde_json::Value,
+    response: HttpResponse,
+    persist_response: bool,
+    response_dir: &'a Path,
+    started_at: Instant,
+    cancelled_rx: Option<watch::Receiver<bool>>,
+}
+
+/// Run a GraphQL subscription over a `graphql-transport-ws` WebSocket until the server completes
+/// it or the send is cancelled. Each result is written to the body as an event stream frame and
+/// recorded as a server-sent event, so it shows up like any other event stream.
+async fn send_graphql_subscription(
+    params: GraphQlSubscriptionParams<'_>,
+) -> Result<(HttpResponse, Vec<u8>)> {
+    let upsert = |response: HttpResponse| -> Result<HttpResponse> {
+        if !params.persist_response {
+            return Ok(response);
+        }
+        params
+            .query_manager
+            .connect()
+            .upsert_http_response(&response, params.update_source, params.blob_manager)
+            .map_err(SendHttpRequestError::PersistResponse)
+    };
+
+    let mut url = Url::parse(params.url).map_err(subscription_error)?;
+    graphql_ws_url(&mut url);
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in params.headers {
+        // The body isn't sent, and the handshake headers are set by the WebSocket client
+        if name.eq_ignore_ascii_case("content-length") || name.eq_ignore_ascii_case("content-type")
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) =
+            (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+        {
+            headers.insert(name, value);
+        }
+    }
+    headers.insert(
+        HeaderName::from_static("sec-websocket-protocol"),
+        HeaderValue::from_static(GRAPHQL_WS_PROTOCOL),
+    );
+
+    let runtime_config = params.runtime_config;
+    let (mut stream, handshake) = ws_connect(
+        url.as_str(),
+        headers,
+        runtime_config.validate_certificates,
+        select_client_certificate(
+            params.url,
+            runtime_config.client_certificate.as_ref(),
+            &runtime_config.client_certificates,
+        ),
+        &runtime_config.ca_certificates,
+        runtime_config.dns_overrides.clone(),
+    )
+    .await
+    .map_err(subscription_error)?;
+
+    std::fs::create_dir_all(params.response_dir).map_err(|source| {
+        SendHttpRequestError::CreateResponseDirectory {
+            path: params.response_dir.to_path_buf(),
+            source,
+        }
+    })?;
+    let body_path = params.response_dir.join(&params.response.id);
+    let mut response = upsert(HttpResponse {
+        state: HttpResponseState::Connected,
+        elapsed_headers: duration_to_i32(params.started_at.elapsed()),
+        status: i32::from(handshake.status().as_u16()),
+        status_reason: handshake.status().canonical_reason().map(str::to_string),
+        url: url.to_string(),
+        body_path: Some(body_path.to_string_lossy().to_string()),
+        headers: handshake
+            .headers()
+            .iter()
+            .map(|(name, value)| HttpResponseHeader {
+                name: name.to_string(),
+                value: value.to_str().unwrap_or_default().to_string(),
+            })
+            .collect(),
+        ..params.response
+    })?;
+
+    let mut file = File::create(&body_path).await.map_err(|source| {
+        SendHttpRequestError::WriteResponseBody { path: body_path.clone(), source }
+    })?;
+    stream
+        .send(Message::Text(graphql_ws_connection_init().into()))
+        .await
+        .map_err(subscription_error)?;
+
+    let mut payload = Some(params.payload);
+    let mut response_body = Vec::new();
+    let mut last_progress_update = Instant::now();
+    let mut cancelled_rx = params.cancelled_rx;
+    loop {
+        let message = if let Some(cancelled_rx) = cancelled_rx.as_mut() {
+            if *cancelled_rx.borrow() {
+                None
+            } else {
+                tokio::select! {
+                    biased;
+                    _ = cancelled_rx.changed() => None,
+                    message = stream.next() => Some(message),
+                }
+            }
+        } else {
+            Some(stream.next().await)
+        };
+
+        let Some(message) = message else {
+            // Unsubscribe before closing, so the server can clean up the operation
+            let complete = graphql_ws_complete(GRAPHQL_SUBSCRIPTION_ID);
+            let _ = stream.send(Message::Text(complete.into())).await;
+            let _ = stream.close(None).await;
+            break;
+        };
+        let text = match message {
+            None | Some(Ok(Message::Close(_))) => break,
+            Some(Ok(Message::Text(text))) => text,
+            Some(Ok(_)) => continue,
+            Some(Err(err)) => {
+                response.error = Some(err.to_string());
+                break;
+            }
+        };
+
+        let (event_type, data) = match GraphQlWsMessage::decode(text.as_str()) {
+            Some(GraphQlWsMessage::ConnectionAck) => {
+                if let Some(payload) = payload.take() {
+                    let subscribe = graphql_ws_subscribe(GRAPHQL_SUBSCRIPTION_ID, payload);
+                    stream
+                        .send(Message::Text(subscribe.into()))
+                        .await
+                        .map_err(subscription_error)?;
+                }
+                continue;
+            }
+            Some(GraphQlWsMessage::Ping) => {
+                stream
+                    .send(Message::Text(graphql_ws_pong().into()))
+                    .await
+                    .map_err(subscription_error)?;
+                continue;
+            }
+            Some(GraphQlWsMessage::Next { payload, .. }) => ("next", payload.to_string()),
+            Some(GraphQlWsMessage::Error { payload, .. }) => {
+                response.error = Some(format!("Server returned errors: {payload}"));
+                ("error", payload.to_string())
+            }
+            Some(GraphQlWsMessage::Complete { .. }) => {
+                let _ = stream.close(None).await;
+                break;
+            }
+            Some(GraphQlWsMessage::Pong) | None => continue,
+        };
+
+        let frame = format!("event: {event_type}\ndata: {data}\n\n");
+        file.write_all(frame.as_bytes()).await.map_err(|source| {
+            SendHttpRequestError::WriteResponseBody { path: body_path.clone(), source }
+        })?;
+        file.flush().await.map_err(|source| SendHttpRequestError::WriteResponseBody {
+            path: body_path.clone(),
+            source,
+        })?;
+        response_body.extend_from_slice(frame.as_bytes());
+        if params.persist_response {
+            persist_server_sent_event(
+                params.query_manager,
+                params.update_source,
+                &response,
+                ServerSentEvent { event_type: event_type.to_string(), data, ..Default::default() },
+            );
+        }
+        if response.error.is_some() {
+            break;
+        }
+
+        let now = Instant::now();
+        if now.duration_since(last_progress_update).as_millis()
+            >= RESPONSE_PROGRESS_UPDATE_INTERVAL_MS
+        {
+            response = upsert(HttpResponse {
+                elapsed: duration_to_i32(params.started_at.elapsed()),
+                content_length: Some(usize_to_i32(response_body.len())),
+                ..response
+            })?;
+            last_progress_update = now;
+        }
+    }
+
+    let response = upsert(HttpResponse {
+        state: HttpResponseState::Closed,
+        elapsed: duration_to_i32(params.started_at.elapsed()),
+        content_length: Some(usize_to_i32(response_body.len())),
+        ..response
+    })?;
+    Ok((response, response_body))
+}
+
+fn subscription_error(err: impl ToString) -> SendHttpRequestError {
+    SendHttpRequestError::GraphQlSubscription(err.to_string())
+}
+
+fn persist_server_sent_event(
+    query_manager: &QueryManager,
+    update_source: &UpdateSource,
+    response: &HttpResponse,
+    event: ServerSentEvent,
+) {
+    let db_event = HttpResponseEvent::new(
+        &response.id,
+        &response.workspace_id,
+        HttpResponseEventData::ServerSentEvent {
+            event_type: event.event_type,
+            data: event.data,
+            id: event.id,
+            retry: event.retry,
+        },
+    );
+    if let Err(err) = query_manager.connect().upsert_http_response_event(&db_event, update_source) {
+        warn!("Failed to persist server-sent event: {}", err);
+    }
+}
+
 fn persist_request_body_bytes(
     blob_manager: &BlobManager,
     body_id: &str,
@@ -927,7 +1775,7 @@ fn append_error_message(existing_error: Option<String>, message: String) -> Stri
     }
 }
 
-fn resolve_environment_chain(
+pub(crate) fn resolve_environment_chain(
     query_manager: &QueryManager,
     request: &HttpRequest,
     environment_id: Option<&str>,
@@ -937,7 +1785,24 @@ fn resolve_environment_chain(
         .map_err(SendHttpRequestError::ResolveEnvironments)
 }
 
-fn resolve_inherited_request(
+/// Variable overrides as an environment, for the front of an environment chain where they
+/// override every other environment
+pub(crate) fn variable_overrides_environment(
+    variables: &[EnvironmentVariable],
+) -> Option<Environment> {
+    if variables.is_empty() {
+        return None;
+    }
+
+    Some(Environment {
+        model: "environment".to_string(),
+        name: "Variable Overrides".to_string(),
+        variables: variables.to_vec(),
+        ..Default::default()
+    })
+}
+
+pub(crate) fn resolve_inherited_request(
     query_manager: &QueryManager,
     request: &HttpRequest,
 ) -> Result<(HttpRequest, String)> {
@@ -948,27 +1813,27 @@ fn resolve_inherited_request(
     let resolved_headers = db
         .resolve_headers_for_http_request(request)
         .map_err(SendHttpRequestError::ResolveRequestInheritance)?;
+    let resolved_url_parameters = db
+        .resolve_query_params_for_http_request(request)
+        .map_err(SendHttpRequestError::ResolveRequestInheritance)?;
 
     let mut request = request.clone();
     request.authentication_type = authentication_type;
     request.authentication = authentication;
     request.headers = resolved_headers;
+    request.url_parameters = resolved_url_parameters;
 
     Ok((request, auth_context_id))
 }
 
 fn load_cookie_jar(
     query_manager: &QueryManager,
+    environment_chain: &[Environment],
     cookie_jar_id: Option<&str>,
 ) -> Result<Option<CookieJar>> {
-    let Some(cookie_jar_id) = cookie_jar_id else {
-        return Ok(None);
-    };
-
     query_manager
         .connect()
-        .get_cookie_jar(cookie_jar_id)
-        .map(Some)
+        .resolve_cookie_jar(environment_chain, cookie_jar_id)
         .map_err(SendHttpRequestError::LoadCookieJar)
 }
 
@@ -979,10 +1844,25 @@ fn persist_cookie_jar(
 ) -> Result<()> {
     match (cookie_jar, cookie_store) {
         (Some(cookie_jar), Some(cookie_store)) => {
-            cookie_jar.cookies = cookie_store.get_all_cookies();
-            query_manager
-                .connect()
-                .upsert_cookie_jar(cookie_jar, &UpdateSource::Background)
+            // Apply only what this exchange changed to the latest copy of the jar, so cookies
+            // edited while the request was in flight aren't overwritten
+            let db = query_manager.connect();
+            let mut latest = db
+                .get_cookie_jar(&cookie_jar.id)
+                .map_err(SendHttpRequestError::PersistCookieJar)?;
+            let cookies = cookie_store.get_all_cookies();
+            latest.cookies.retain(|c| {
+                let removed = cookie_jar.cookies.iter().any(|old| {
+                    old.is_same_cookie(c) && !cookies.iter().any(|new| new.is_same_cookie(old))
+                });
+                !removed
+            });
+            for cookie in cookies.iter().filter(|c| !cookie_jar.cookies.contains(c)) {
+                latest.cookies.retain(|c| !c.is_same_cookie(cookie));
+                latest.cookies.push(cookie.clone());
+            }
+            *cookie_jar = db
+                .upsert_cookie_jar(&latest, &UpdateSource::Background)
                 .map_err(SendHttpRequestError::PersistCookieJar)?;
             Ok(())
         }
@@ -1005,6 +1885,32 @@ fn send_setting_event<T>(
     });
 }
 
+/// Wait out a retry delay, returning early with `true` if the request is canceled in the meantime
+async fn sleep_unless_cancelled(
+    cancelled_rx: Option<watch::Receiver<bool>>,
+    delay: Duration,
+) -> bool {
+    let Some(mut cancelled_rx) = cancelled_rx else {
+        tokio::time::sleep(delay).await;
+        return false;
+    };
+    if *cancelled_rx.borrow() {
+        return true;
+    }
+    tokio::select! {
+        _ = cancelled_rx.changed() => true,
+        _ = tokio::time::sleep(delay) => false,
+    }
+}
+
+fn timeout_from_millis(millis: i32) -> Option<Duration> {
+    if millis > 0 { Some(Duration::from_millis(millis.unsigned_abs() as u64)) } else { None }
+}
+
+fn response_state_for_error(err: &yaak_http::error::Error) -> HttpResponseState {
+    if err.is_timeout() { HttpResponseState::TimedOut } else { HttpResponseState::Closed }
+}
+
 fn timeout_setting_value(timeout: Option<Duration>) -> String {
     match timeout {
         Some(timeout) if !timeout.is_zero() => format!("{timeout:?}"),
@@ -1012,6 +1918,16 @@ fn timeout_setting_value(timeout: Option<Duration>) -> String {
     }
 }
 
+fn http_version_setting_value(http_version: HttpVersion) -> String {
+    match http_version {
+        HttpVersion::Auto => "auto",
+        HttpVersion::Http1 => "HTTP/1.1",
+        HttpVersion::Http2 => "HTTP/2",
+        HttpVersion::Http3 => "HTTP/3",
+    }
+    .to_string()
+}
+
 fn proxy_setting_from_settings(proxy: Option<ProxySetting>) -> HttpConnectionProxySetting {
     match proxy {
         None => HttpConnectionProxySetting::System,
@@ -1037,15 +1953,49 @@ pub async fn apply_plugin_authentication(
     sendable_request: &mut SendableHttpRequest,
     request: &HttpRequest,
     auth_context_id: &str,
+    query_manager: &QueryManager,
     plugin_manager: &PluginManager,
+    secrets: &dyn SecretStore,
     plugin_context: &PluginContext,
 ) -> std::result::Result<(), String> {
     match &request.authentication_type {
         None => {}
         Some(authentication_type) if authentication_type == "none" => {}
         Some(authentication_type) => {
+            let disabled =
+                request.authentication.get("disabled") == Some(&serde_json::Value::Bool(true));
+
+            // NTLM authenticates the connection, so the handshake happens in the sender
+            if authentication_type == NTLM_AUTHENTICATION_TYPE {
+                if !disabled {
+                    sendable_request.options.ntlm = Some(ntlm_credentials(&request.authentication));
+                }
+                return Ok(());
+            }
+
+            let context_id = format!("{:x}", md5::compute(auth_context_id));
+
+            // Send a stored OAuth 2.0 token directly, refreshing it first if it has expired
+            let token_config_hash = (authentication_type == OAUTH2_AUTHENTICATION_TYPE
+                && !disabled)
+                .then(|| oauth2_config_hash(&context_id, &request.authentication));
+            if let Some(config_hash) = &token_config_hash {
+                let token = resolve_oauth2_token(
+                    query_manager,
+                    secrets,
+                    config_hash,
+                    &request.authentication,
+                )
+                .await?;
+                if let Some(token) = token {
+                    let header = oauth2_token_header(&request.authentication, &token);
+                    sendable_request.insert_header(header);
+                    return Ok(());
+                }
+            }
+
             let req = CallHttpAuthenticationRequest {
-                context_id: format!("{:x}", md5::compute(auth_context_id)),
+                context_id,
                 values: serde_json::from_value(
                     serde_json::to_value(&request.authentication)
                         .map_err(|e| format!("Failed to serialize auth values: {e}"))?,
@@ -1067,6 +2017,13 @@ pub async fn apply_plugin_authentication(
                 .await
                 .map_err(|e| format!("Failed to apply authentication plugin: {e}"))?;
 
+            if let (Some(config_hash), Some(token)) = (&token_config_hash, plugin_result.token) {
+                let stored = store_oauth2_token(query_manager, secrets, config_hash, token);
+                if let Err(err) = stored {
+                    warn!("Failed to store OAuth 2.0 token: {err}");
+                }
+            }
+
             for header in plugin_result.set_headers.unwrap_or_default() {
                 sendable_request.insert_header((header.name, header.value));
             }
@@ -1080,12 +2037,24 @@ pub async fn apply_plugin_authentication(
     Ok(())
 }
 
+fn ntlm_credentials(values: &BTreeMap<String, serde_json::Value>) -> NtlmCredentials {
+    let value = |key: &str| values.get(key).and_then(|v| v.as_str()).unwrap_or_default();
+    NtlmCredentials {
+        username: value("username").to_string(),
+        password: value("password").to_string(),
+        domain: value("domain").to_string(),
+        workstation: value("workstation").to_string(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn persist_response_error(
     query_manager: &QueryManager,
     blob_manager: &BlobManager,
     update_source: &UpdateSource,
     response: &HttpResponse,
     started_at: Instant,
+    state: HttpResponseState,
     error: String,
     fallback_url: String,
 ) -> Result<HttpResponse> {
@@ -1094,7 +2063,7 @@ fn persist_response_error(
         .connect()
         .upsert_http_response(
             &HttpResponse {
-                state: HttpResponseState::Closed,
+                state,
                 elapsed,
                 elapsed_headers: if response.elapsed_headers == 0 {
                     elapsed
@@ -0,0 +1,299 @@
+use crate::error::Error::ResponseFilter;
+use crate::error::Result;
+use scraper::{ElementRef, Html};
+use sxd_document::Package;
+use sxd_document::dom::{ChildOfElement, ChildOfRoot, Document, Element};
+use sxd_xpath::nodeset::Node;
+use sxd_xpath::{Context, Factory, Value};
+
+/// Filter an XML or HTML response body. Matched nodes are serialized the same way as `String(node)`
+/// in the XPath filter plugin, one per line, while expressions that evaluate to a string, number,
+/// or boolean return that value.
+pub fn filter_markup(body: &str, filter: &str, html: bool) -> Result<String> {
+    let package = parse_markup(body, html)?;
+    let document = package.as_document();
+    Ok(match evaluate(&document, filter)? {
+        Value::Nodeset(nodes) => {
+            nodes.document_order().into_iter().map(node_markup).collect::<Vec<_>>().join("\n")
+        }
+        value => value.string(),
+    })
+}
+
+/// Evaluate an expression against an XML or HTML body as a single string: the string value of
+/// the first matched node, or the expression's value when it doesn't select nodes
+pub fn extract_markup_value(body: &str, filter: &str, html: bool) -> Result<Option<String>> {
+    let package = parse_markup(body, html)?;
+    let document = package.as_document();
+    Ok(match evaluate(&document, filter)? {
+        Value::Nodeset(nodes) => nodes.document_order_first().map(|n| n.string_value()),
+        value => Some(value.string()),
+    })
+}
+
+fn evaluate<'d>(document: &Document<'d>, filter: &str) -> Result<Value<'d>> {
+    let xpath = Factory::new()
+        .build(filter)
+        .map_err(|e| ResponseFilter(format!("Invalid filter: {e}")))?
+        .ok_or_else(|| ResponseFilter("Invalid filter: expression is empty".to_string()))?;
+
+    // Prefixes declared anywhere in the document can be used in the filter, like `//soap:Body`
+    let mut context = Context::new();
+    let mut elements = root_elements(document);
+    while let Some(element) = elements.pop() {
+        for namespace in element.namespaces_in_scope() {
+            context.set_namespace(namespace.prefix(), namespace.uri());
+        }
+        elements.extend(element.children().into_iter().filter_map(ChildOfElement::element));
+    }
+
+    xpath
+        .evaluate(&context, document.root())
+        .map_err(|e| ResponseFilter(format!("Invalid filter: {e}")))
+}
+
+/// XML bodies must be well-formed. HTML is parsed like a browser would, then copied into an XML
+/// document so it can be queried the same way.
+fn parse_markup(body: &str, html: bool) -> Result<Package> {
+    if !html {
+        return sxd_document::parser::parse(body)
+            .map_err(|e| ResponseFilter(format!("Response is not valid XML: {e}")));
+    }
+
+    let html = Html::parse_document(body);
+    let package = Package::new();
+    let document = package.as_document();
+    document.root().append_child(copy_html_element(&document, html.root_element()));
+    Ok(package)
+}
+
+fn copy_html_element<'d>(document: &Document<'d>, html_element: ElementRef) -> Element<'d> {
+    let element = document.create_element(html_element.value().name());
+    for (name, value) in html_element.value().attrs() {
+        element.set_attribute_value(name, value);
+    }
+    for child in html_element.children() {
+        match child.value() {
+            scraper::Node::Element(_) => {
+                if let Some(c) = ElementRef::wrap(child) {
+                    element.append_child(copy_html_element(document, c));
+                }
+            }
+            scraper::Node::Text(t) => element.append_child(document.create_text(t)),
+            scraper::Node::Comment(c) => element.append_child(document.create_comment(c)),
+            _ => {}
+        }
+    }
+    element
+}
+
+fn root_elements<'d>(document: &Document<'d>) -> Vec<Element<'d>> {
+    document.root().children().into_iter().filter_map(ChildOfRoot::element).collect()
+}
+
+fn node_markup(node: Node) -> String {
+    let mut out = String::new();
+    match node {
+        Node::Root(root) => {
+            for child in root.children() {
+                match child {
+                    ChildOfRoot::Element(e) => write_element(&mut out, e, &mut Vec::new()),
+                    ChildOfRoot::Comment(c) => out.push_str(&format!("<!--{}-->", c.text())),
+                    ChildOfRoot::ProcessingInstruction(pi) => write_pi(&mut out, pi),
+                }
+            }
+        }
+        Node::Element(e) => write_element(&mut out, e, &mut Vec::new()),
+        Node::Attribute(a) => {
+            let name = qualified_name(a.preferred_prefix(), a.name().local_part());
+            out.push_str(&format!(" {name}=\"{}\"", escape_attribute(a.value())));
+        }
+        Node::Text(t) => out.push_str(&escape_text(t.text())),
+        Node::Comment(c) => out.push_str(&format!("<!--{}-->", c.text())),
+        Node::ProcessingInstruction(pi) => write_pi(&mut out, pi),
+        Node::Namespace(ns) => out.push_str(ns.uri()),
+    }
+    out
+}
+
+/// Serialize an element and its descendants, declaring each namespace the first time it's used
+/// since the serialized markup may not include the element that originally declared it
+fn write_element<'d>(out: &mut String, element: Element<'d>, declared: &mut Vec<(String, String)>) {
+    let depth = declared.len();
+    let name = element.name();
+    let prefix = element.preferred_prefix();
+    out.push('<');
+    out.push_str(&qualified_name(prefix, name.local_part()));
+
+    declare_namespace(out, declared, prefix, name.namespace_uri());
+    for attribute in element.attributes() {
+        let attr_prefix = attribute.preferred_prefix();
+        if attr_prefix.is_some() {
+            declare_namespace(out, declared, attr_prefix, attribute.name().namespace_uri());
+        }
+        let attr_name = qualified_name(attr_prefix, attribute.name().local_part());
+        out.push_str(&format!(" {attr_name}=\"{}\"", escape_attribute(attribute.value())));
+    }
+
+    let children = element.children();
+    if children.is_empty() {
+        out.push_str("/>");
+    } else {
+        out.push('>');
+        for child in children {
+            match child {
+                ChildOfElement::Element(e) => write_element(out, e, declared),
+                ChildOfElement::Text(t) => out.push_str(&escape_text(t.text())),
+                ChildOfElement::Comment(c) => out.push_str(&format!("<!--{}-->", c.text())),
+                ChildOfElement::ProcessingInstruction(pi) => write_pi(out, pi),
+            }
+        }
+        out.push_str(&format!("</{}>", qualified_name(prefix, name.local_part())));
+    }
+    declared.truncate(depth);
+}
+
+fn declare_namespace(
+    out: &mut String,
+    declared: &mut Vec<(String, String)>,
+    prefix: Option<&str>,
+    uri: Option<&str>,
+) {
+    let (Some(uri), prefix) = (uri, prefix.unwrap_or_default()) else {
+        return;
+    };
+    if declared.iter().rev().find(|(p, _)| p == prefix).is_some_and(|(_, u)| u == uri) {
+        return;
+    }
+    let attribute = qualified_name(Some("xmlns"), prefix);
+    let attribute = if prefix.is_empty() { "xmlns" } else { attribute.as_str() };
+    out.push_str(&format!(" {attribute}=\"{}\"", escape_attribute(uri)));
+    declared.push((prefix.to_string(), uri.to_string()));
+}
+
+fn write_pi(out: &mut String, pi: sxd_document::dom::ProcessingInstruction) {
+    match pi.value() {
+        Some(value) => out.push_str(&format!("<?{} {value}?>", pi.target())),
+        None => out.push_str(&format!("<?{}?>", pi.target())),
+    }
+}
+
+fn qualified_name(prefix: Option<&str>, local: &str) -> String {
+    match prefix {
+        Some(prefix) if !prefix.is_empty() => format!("{prefix}:{local}"),
+        _ => local.to_string(),
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+fn escape_attribute(value: &str) -> String {
+    escape_text(value)
+        .replace('"', "&quot;")
+        .replace('\t', "&#x9;")
+        .replace('\n', "&#xA;")
+        .replace('\r', "&#xD;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENVELOPE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+  <soap:Body>
+    <GetBooksResponse>
+      <book id="1" category="reference"><title>Sayings of the Century</title><price>8.95</price></book>
+      <book id="2" category="fiction"><title>Sword &amp; Honour</title><price>12.99</price></book>
+      <book id="3" category="fiction"><title><![CDATA[The <Lord> of the Rings]]></title><price>22.99</price></book>
+    </GetBooksResponse>
+  </soap:Body>
+</soap:Envelope>"#;
+
+    fn xml(filter: &str) -> String {
+        filter_markup(ENVELOPE, filter, false).unwrap()
+    }
+
+    #[test]
+    fn selects_paths_and_predicates() {
+        assert_eq!(xml("//book[2]/title"), "<title>Sword &amp; Honour</title>");
+        assert_eq!(xml("//book[last()]/@id"), " id=\"3\"");
+        assert_eq!(
+            xml("/soap:Envelope/soap:Body/*/book[@category='reference']/price/text()"),
+            "8.95"
+        );
+        assert_eq!(
+            xml("//soap:Body//title[contains(., 'Lord')]/text()"),
+            "The &lt;Lord&gt; of the Rings"
+        );
+        assert_eq!(
+            xml("//book[price > 10]/@id | //book[1]/@id"),
+            " id=\"1\"\n id=\"2\"\n id=\"3\""
+        );
+        assert_eq!(xml("(//book)[position() = 2]/following-sibling::book/@id"), " id=\"3\"");
+        assert_eq!(xml("//title[../@id = 1]/parent::node()/@category"), " category=\"reference\"");
+        assert_eq!(xml("//missing"), "");
+    }
+
+    #[test]
+    fn declares_namespaces_of_selected_elements() {
+        assert!(
+            xml("//soap:Body").starts_with(
+                r#"<soap:Body xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">"#
+            )
+        );
+    }
+
+    #[test]
+    fn evaluates_functions_and_operators() {
+        assert_eq!(xml("count(//book)"), "3");
+        assert_eq!(xml("sum(//book/@id) div 4"), "1.5");
+        assert_eq!(xml("string(//book[2]/title)"), "Sword & Honour");
+        assert_eq!(xml("local-name(/*)"), "Envelope");
+        assert_eq!(xml("name(/*)"), "soap:Envelope");
+        assert_eq!(xml("normalize-space('  a   b ')"), "a b");
+        assert_eq!(xml("//book[1]/price < //book[2]/price"), "true");
+        assert_eq!(xml("number('abc')"), "NaN");
+    }
+
+    #[test]
+    fn extracts_single_values() {
+        let extract = |f: &str| extract_markup_value(ENVELOPE, f, false).unwrap();
+        assert_eq!(extract("//book/title"), Some("Sayings of the Century".to_string()));
+        assert_eq!(extract("//book[3]/@id"), Some("3".to_string()));
+        assert_eq!(extract("count(//book)"), Some("3".to_string()));
+        assert_eq!(extract("//missing"), None);
+    }
+
+    #[test]
+    fn parses_lenient_html() {
+        let html = r#"<!DOCTYPE html>
+<HTML><head><title>Shop</title><script>if (a < b && c) {}</script></head>
+<body>
+  <ul id=items>
+    <li class="item"><a HREF="/one">One</a><br>
+    <li class="item sale"><a href='/two'>Two &nbsp;&#x26;</a>
+  </ul>
+  <input disabled></p>
+</body></HTML>"#;
+        let filter = |f: &str| filter_markup(html, f, true).unwrap();
+        assert_eq!(filter("//li[contains(@class, 'sale')]/a/@href"), " href=\"/two\"");
+        assert_eq!(filter("count(//li)"), "2");
+        assert_eq!(filter("//a/text()"), "One\nTwo \u{a0}&amp;");
+        assert_eq!(filter("string(//script)"), "if (a < b && c) {}");
+        assert_eq!(filter("//ul/@id"), " id=\"items\"");
+        assert_eq!(filter("//input"), "<input disabled=\"\"/>");
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        for expr in ["//book[", "//book/", "foo(1)", "bad::book", "'open"] {
+            let err = filter_markup(ENVELOPE, expr, false).unwrap_err().to_string();
+            assert!(err.starts_with("Invalid filter:"), "{expr}: {err}");
+        }
+        let err = filter_markup("<a><b></a>", "//a", false).unwrap_err().to_string();
+        assert!(err.starts_with("Response is not valid XML"), "{err}");
+    }
+}
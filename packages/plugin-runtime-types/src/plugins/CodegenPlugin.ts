@@ -0,0 +1,8 @@
+import type { CodegenLanguage } from "../bindings/gen_events";
+import type { HttpRequest } from "../bindings/gen_models";
+import type { Context } from "./Context";
+
+export type CodegenPlugin = CodegenLanguage & {
+  /** Generate a snippet for a request that's already been rendered, with authentication applied */
+  onGenerate(ctx: Context, args: { httpRequest: HttpRequest }): Promise<string> | string;
+};
@@ -12,6 +12,7 @@ export type PartialImportResources = {
   httpRequests: Array<AtLeast<ImportResources["httpRequests"][0], CommonFields>>;
   grpcRequests: Array<AtLeast<ImportResources["grpcRequests"][0], CommonFields>>;
   websocketRequests: Array<AtLeast<ImportResources["websocketRequests"][0], CommonFields>>;
+  cookieJars?: Array<AtLeast<ImportResources["cookieJars"][0], CommonFields>>;
 };
 
 export type ImportPluginResponse = null | {
@@ -1,4 +1,5 @@
 import type { AuthenticationPlugin } from "./AuthenticationPlugin";
+import type { CodegenPlugin } from "./CodegenPlugin";
 
 import type { Context } from "./Context";
 import type { FilterPlugin } from "./FilterPlugin";
@@ -13,6 +14,7 @@ import type { WorkspaceActionPlugin } from "./WorkspaceActionPlugin";
 
 export type { Context };
 export type { DynamicAuthenticationArg } from "./AuthenticationPlugin";
+export type { CodegenPlugin };
 export type { CallPromptFormDynamicArgs, DynamicPromptFormArg } from "./Context";
 export type { DynamicTemplateFunctionArg } from "./TemplateFunctionPlugin";
 export type { TemplateFunctionPlugin };
@@ -35,4 +37,5 @@ export type PluginDefinition = {
   folderActions?: FolderActionPlugin[];
   grpcRequestActions?: GrpcRequestActionPlugin[];
   templateFunctions?: TemplateFunctionPlugin[];
+  codegen?: CodegenPlugin[];
 };
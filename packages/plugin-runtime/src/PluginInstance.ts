@@ -167,6 +167,25 @@ export class PluginInstance {
         return;
       }
 
+      if (payload.type === "get_codegen_languages_request" && Array.isArray(this.#mod?.codegen)) {
+        const replyPayload: InternalEventPayload = {
+          type: "get_codegen_languages_response",
+          pluginRefId: this.#workerData.pluginRefId,
+          languages: this.#mod.codegen.map(({ name, label }) => ({ name, label })),
+        };
+        this.#sendPayload(context, replyPayload, replyId);
+        return;
+      }
+
+      if (payload.type === "call_codegen_request" && Array.isArray(this.#mod?.codegen)) {
+        const codegen = this.#mod.codegen.find((c) => c.name === payload.name);
+        if (typeof codegen?.onGenerate === "function") {
+          const content = await codegen.onGenerate(ctx, { httpRequest: payload.httpRequest });
+          this.#sendPayload(context, { type: "call_codegen_response", content }, replyId);
+          return;
+        }
+      }
+
       if (
         payload.type === "get_grpc_request_actions_request" &&
         Array.isArray(this.#mod?.grpcRequestActions)
@@ -66,6 +66,11 @@ export async function convertToCurl(request: Partial<HttpRequest>) {
   xs.push(quote(finalUrl));
   xs.push(NEWLINE);
 
+  if (request.unixSocket) {
+    xs.push("--unix-socket", quote(request.unixSocket));
+    xs.push(NEWLINE);
+  }
+
   // Add headers
   for (const h of (request.headers ?? []).filter(onlyEnabled)) {
     xs.push("--header", quote(`${h.name}: ${h.value}`));
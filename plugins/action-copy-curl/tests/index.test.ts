@@ -158,6 +158,20 @@ describe("exporter-curl", () => {
     ).toEqual([`curl ''`, `--header 'a: aaa'`, `--header 'b: bbb'`].join(" \\\n  "));
   });
 
+  test("Exports Unix socket", async () => {
+    expect(
+      await convertToCurl({
+        url: "http://localhost/v1.43/containers/json",
+        unixSocket: "/var/run/docker.sock",
+      }),
+    ).toEqual(
+      [
+        `curl 'http://localhost/v1.43/containers/json'`,
+        `--unix-socket '/var/run/docker.sock'`,
+      ].join(" \\\n  "),
+    );
+  });
+
   test("Basic auth", async () => {
     expect(
       await convertToCurl({
@@ -14,6 +14,7 @@ export const plugin: PluginDefinition = {
         options: [
           { label: "Insert Header", value: "header" },
           { label: "Append Query Parameter", value: "query" },
+          { label: "Add Cookie", value: "cookie" },
         ],
       },
       {
@@ -21,15 +22,22 @@ export const plugin: PluginDefinition = {
         name: "key",
         label: "Key",
         dynamic: (_ctx, { values }) => {
-          return values.location === "query"
-            ? {
-                label: "Parameter Name",
-                description: "The name of the query parameter to add to the request",
-              }
-            : {
-                label: "Header Name",
-                description: "The name of the header to add to the request",
-              };
+          if (values.location === "query") {
+            return {
+              label: "Parameter Name",
+              description: "The name of the query parameter to add to the request",
+            };
+          }
+          if (values.location === "cookie") {
+            return {
+              label: "Cookie Name",
+              description: "The name of the cookie to send with the request",
+            };
+          }
+          return {
+            label: "Header Name",
+            description: "The name of the header to add to the request",
+          };
         },
       },
       {
@@ -40,7 +48,7 @@ export const plugin: PluginDefinition = {
         password: true,
       },
     ],
-    async onApply(_ctx, { values }) {
+    async onApply(_ctx, { values, headers }) {
       const key = String(values.key ?? "");
       const value = String(values.value ?? "");
       const location = String(values.location);
@@ -48,6 +56,14 @@ export const plugin: PluginDefinition = {
       if (location === "query") {
         return { setQueryParameters: [{ name: key, value }] };
       }
+      if (location === "cookie") {
+        // Keep any cookies the request already sends, since setting the header replaces it
+        const existing = headers.find((h) => h.name.toLowerCase() === "cookie")?.value;
+        const cookie = `${key}=${value}`;
+        return {
+          setHeaders: [{ name: "Cookie", value: existing ? `${existing}; ${cookie}` : cookie }],
+        };
+      }
       return { setHeaders: [{ name: key, value }] };
     },
   },
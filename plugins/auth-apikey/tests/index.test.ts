@@ -0,0 +1,55 @@
+import type { Context } from "@yaakapp/api";
+import { describe, expect, test } from "vite-plus/test";
+import { plugin } from "../src";
+
+const ctx = {} as Context;
+
+describe("auth-apikey", () => {
+  test("Inserts a header", async () => {
+    expect(
+      await plugin.authentication?.onApply(ctx, {
+        values: { location: "header", key: "X-Api-Key", value: "secret" },
+        headers: [],
+        url: "https://yaak.app",
+        method: "GET",
+        contextId: "111",
+      }),
+    ).toEqual({ setHeaders: [{ name: "X-Api-Key", value: "secret" }] });
+  });
+
+  test("Appends a query parameter", async () => {
+    expect(
+      await plugin.authentication?.onApply(ctx, {
+        values: { location: "query", key: "api_key", value: "secret" },
+        headers: [],
+        url: "https://yaak.app",
+        method: "GET",
+        contextId: "111",
+      }),
+    ).toEqual({ setQueryParameters: [{ name: "api_key", value: "secret" }] });
+  });
+
+  test("Adds a cookie", async () => {
+    expect(
+      await plugin.authentication?.onApply(ctx, {
+        values: { location: "cookie", key: "api_key", value: "secret" },
+        headers: [],
+        url: "https://yaak.app",
+        method: "GET",
+        contextId: "111",
+      }),
+    ).toEqual({ setHeaders: [{ name: "Cookie", value: "api_key=secret" }] });
+  });
+
+  test("Keeps existing cookies", async () => {
+    expect(
+      await plugin.authentication?.onApply(ctx, {
+        values: { location: "cookie", key: "api_key", value: "secret" },
+        headers: [{ name: "cookie", value: "theme=dark" }],
+        url: "https://yaak.app",
+        method: "GET",
+        contextId: "111",
+      }),
+    ).toEqual({ setHeaders: [{ name: "Cookie", value: "theme=dark; api_key=secret" }] });
+  });
+});
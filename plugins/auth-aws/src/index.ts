@@ -4,6 +4,18 @@ import type { CallHttpAuthenticationResponse } from "@yaakapp-internal/plugins";
 import type { Request } from "aws4";
 import aws4 from "aws4";
 
+const SIGNATURE_LOCATION_HEADERS = "headers";
+const SIGNATURE_LOCATION_QUERY = "query";
+
+// Headers the user may have already set that need to be part of the signature
+const SIGNED_REQUEST_HEADERS = [
+  "content-type",
+  "host",
+  "x-amz-date",
+  "x-amz-security-token",
+  "x-amz-content-sha256",
+];
+
 export const plugin: PluginDefinition = {
   authentication: {
     name: "awsv4",
@@ -41,16 +53,29 @@ export const plugin: PluginDefinition = {
         optional: true,
         description: "Only required if you are using temporary credentials",
       },
+      {
+        type: "select",
+        name: "signatureLocation",
+        label: "Add Signature To",
+        defaultValue: SIGNATURE_LOCATION_HEADERS,
+        options: [
+          { label: "Headers", value: SIGNATURE_LOCATION_HEADERS },
+          { label: "Query String", value: SIGNATURE_LOCATION_QUERY },
+        ],
+        description:
+          "Signing the query string produces a pre-signed URL, as used for S3 downloads",
+      },
     ],
     onApply(_ctx, { values, ...args }): CallHttpAuthenticationResponse {
       const accessKeyId = String(values.accessKeyId || "");
       const secretAccessKey = String(values.secretAccessKey || "");
       const sessionToken = String(values.sessionToken || "") || undefined;
+      const signQuery = values.signatureLocation === SIGNATURE_LOCATION_QUERY;
 
       const url = new URL(args.url);
 
       const headers: NonNullable<Request["headers"]> = {};
-      for (const headerName of ["content-type", "host", "x-amz-date", "x-amz-security-token"]) {
+      for (const headerName of SIGNED_REQUEST_HEADERS) {
         const v = args.headers.find((h) => h.name.toLowerCase() === headerName);
         if (v != null) {
           headers[headerName] = v.value;
@@ -65,6 +90,7 @@ export const plugin: PluginDefinition = {
           service: String(values.service || "sts"),
           region: values.region ? String(values.region) : undefined,
           headers,
+          signQuery,
           doNotEncodePath: true,
         },
         {
@@ -74,6 +100,16 @@ export const plugin: PluginDefinition = {
         },
       );
 
+      if (signQuery) {
+        // The signed path contains the original query plus the X-Amz-* signing parameters
+        const signedUrl = new URL(signature.path ?? "", url.origin);
+        return {
+          setQueryParameters: [...signedUrl.searchParams.entries()]
+            .filter(([name]) => name.startsWith("X-Amz-"))
+            .map(([name, value]) => ({ name, value })),
+        };
+      }
+
       if (signature.headers == null) {
         return {};
       }
@@ -0,0 +1,69 @@
+import type { Context } from "@yaakapp/api";
+import { describe, expect, test } from "vite-plus/test";
+import { plugin } from "../src";
+
+const ctx = {} as Context;
+
+const credentials = {
+  accessKeyId: "AKIDEXAMPLE",
+  secretAccessKey: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+  service: "s3",
+  region: "eu-west-1",
+};
+
+describe("auth-aws", () => {
+  test("Signs requests with an Authorization header", async () => {
+    const result = await plugin.authentication?.onApply(ctx, {
+      values: { ...credentials, sessionToken: "my-session" },
+      headers: [{ name: "Content-Type", value: "application/json" }],
+      url: "https://bucket.s3.eu-west-1.amazonaws.com/key?list-type=2",
+      method: "GET",
+      contextId: "111",
+    });
+
+    const headers = Object.fromEntries(
+      (result?.setHeaders ?? []).map((h) => [h.name.toLowerCase(), h.value]),
+    );
+    expect(headers.authorization).toMatch(
+      /^AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE\/\d{8}\/eu-west-1\/s3\/aws4_request, SignedHeaders=.*x-amz-security-token.*, Signature=[0-9a-f]{64}$/,
+    );
+    expect(headers["x-amz-security-token"]).toBe("my-session");
+    expect(headers["content-type"]).toBeUndefined();
+    expect(result?.setQueryParameters).toBeUndefined();
+  });
+
+  test("Uses the payload hash set on the request", async () => {
+    const result = await plugin.authentication?.onApply(ctx, {
+      values: credentials,
+      headers: [{ name: "X-Amz-Content-Sha256", value: "UNSIGNED-PAYLOAD" }],
+      url: "https://bucket.s3.eu-west-1.amazonaws.com/key",
+      method: "PUT",
+      contextId: "111",
+    });
+
+    expect(result?.setHeaders).toContainEqual({
+      name: "x-amz-content-sha256",
+      value: "UNSIGNED-PAYLOAD",
+    });
+  });
+
+  test("Signs the query string", async () => {
+    const result = await plugin.authentication?.onApply(ctx, {
+      values: { ...credentials, sessionToken: "my-session", signatureLocation: "query" },
+      headers: [],
+      url: "https://bucket.s3.eu-west-1.amazonaws.com/key?versionId=3",
+      method: "GET",
+      contextId: "111",
+    });
+
+    const params = Object.fromEntries(
+      (result?.setQueryParameters ?? []).map((p) => [p.name, p.value]),
+    );
+    expect(result?.setHeaders).toBeUndefined();
+    expect(params["X-Amz-Algorithm"]).toBe("AWS4-HMAC-SHA256");
+    expect(params["X-Amz-Credential"]).toMatch(/^AKIDEXAMPLE\/\d{8}\/eu-west-1\/s3\/aws4_request$/);
+    expect(params["X-Amz-Security-Token"]).toBe("my-session");
+    expect(params["X-Amz-Signature"]).toMatch(/^[0-9a-f]{64}$/);
+    expect(params.versionId).toBeUndefined();
+  });
+});
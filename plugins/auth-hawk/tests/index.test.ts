@@ -0,0 +1,62 @@
+import type { Context } from "@yaakapp/api";
+import { describe, expect, test } from "vite-plus/test";
+import { hawkHeader, plugin } from "../src";
+
+const ctx = {} as Context;
+
+const credentials = {
+  id: "dh37fgj492je",
+  key: "werxhqb98rpaxn39848xrunpaw3489ruxnpa98w4rxn",
+  algorithm: "sha256",
+} as const;
+
+describe("auth-hawk", () => {
+  test("Computes the MAC from the reference example", () => {
+    expect(
+      hawkHeader({
+        ...credentials,
+        method: "GET",
+        url: "http://example.com:8000/resource/1?b=1&a=2",
+        ts: "1353832234",
+        nonce: "j4h3g2",
+        ext: "some-app-ext-data",
+        app: "",
+        dlg: "",
+      }),
+    ).toBe(
+      'Hawk id="dh37fgj492je", ts="1353832234", nonce="j4h3g2", ext="some-app-ext-data", mac="6R4rV5iE+NPoym+WwjeHzjAGXUtLNIxmo1vpMofpLAE="',
+    );
+  });
+
+  test("Includes app and dlg when an app is set", () => {
+    const header = hawkHeader({
+      ...credentials,
+      method: "POST",
+      url: "https://example.com/resource",
+      ts: "1353832234",
+      nonce: "j4h3g2",
+      ext: "",
+      app: "my-app",
+      dlg: "user",
+    });
+    expect(header).toMatch(/^Hawk id="dh37fgj492je", ts="1353832234", nonce="j4h3g2", mac="/);
+    expect(header).toMatch(/, app="my-app", dlg="user"$/);
+    expect(header).not.toContain("ext=");
+  });
+
+  test("Generates a timestamp and nonce when sending", async () => {
+    const result = await plugin.authentication?.onApply(ctx, {
+      values: credentials,
+      headers: [],
+      url: "https://example.com/resource",
+      method: "GET",
+      contextId: "111",
+    });
+
+    const value = result?.setHeaders?.[0]?.value ?? "";
+    const ts = Number(/ts="(\d+)"/.exec(value)?.[1]);
+    expect(result?.setHeaders?.[0]?.name).toBe("Authorization");
+    expect(Math.abs(ts - Date.now() / 1000)).toBeLessThan(5);
+    expect(value).toMatch(/nonce="[\w-]+"/);
+  });
+});
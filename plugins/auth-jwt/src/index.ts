@@ -54,6 +54,15 @@ export const plugin: PluginDefinition = {
         defaultValue: '{\n  "foo": "bar"\n}',
         placeholder: "{ }",
       },
+      {
+        type: "text",
+        name: "ttl",
+        label: "Expires In (seconds)",
+        placeholder: "Never",
+        optional: true,
+        description:
+          "Adds iat and exp claims so a fresh, short-lived token is signed for every request",
+      },
       {
         type: "accordion",
         label: "Advanced",
@@ -129,7 +138,7 @@ export const plugin: PluginDefinition = {
 
       const parsedHeaders = headers ? JSON.parse(`${headers}`) : undefined;
 
-      const token = jwt.sign(`${payload}`, secret, {
+      const token = jwt.sign(withExpiry(`${payload}`, Number(values.ttl) || 0), secret, {
         algorithm: algorithm as (typeof algorithms)[number],
         // Extra header fields are merged with the auto-generated header (which includes alg)
         header: parsedHeaders as jwt.JwtHeader | undefined,
@@ -146,3 +155,24 @@ export const plugin: PluginDefinition = {
     },
   },
 };
+
+/**
+ * Add `iat` and `exp` claims to a JSON object payload when a TTL is set. Claims already in the
+ * payload win, and other payloads are signed as-is.
+ */
+function withExpiry(payload: string, ttlSeconds: number): string {
+  if (ttlSeconds <= 0) return payload;
+
+  let claims: unknown;
+  try {
+    claims = JSON.parse(payload);
+  } catch {
+    return payload;
+  }
+  if (claims == null || typeof claims !== "object" || Array.isArray(claims)) {
+    return payload;
+  }
+
+  const now = Math.floor(Date.now() / 1000);
+  return JSON.stringify({ iat: now, exp: now + ttlSeconds, ...claims });
+}
@@ -0,0 +1,67 @@
+import { generateKeyPairSync } from "node:crypto";
+import type { Context } from "@yaakapp/api";
+import jwt from "jsonwebtoken";
+import { describe, expect, test } from "vite-plus/test";
+import { plugin } from "../src";
+
+const ctx = {} as Context;
+
+async function sign(values: Record<string, string | boolean>) {
+  const result = await plugin.authentication?.onApply(ctx, {
+    values,
+    headers: [],
+    url: "https://yaak.app",
+    method: "GET",
+    contextId: "111",
+  });
+  const header = result?.setHeaders?.[0];
+  expect(header?.name).toBe("Authorization");
+  expect(header?.value).toMatch(/^Bearer /);
+  return String(header?.value).replace(/^Bearer /, "");
+}
+
+describe("auth-jwt", () => {
+  test("Signs the payload without expiry by default", async () => {
+    const token = await sign({ algorithm: "HS256", secret: "s3cret", payload: '{"sub":"svc"}' });
+    expect(jwt.verify(token, "s3cret")).toEqual({ sub: "svc" });
+  });
+
+  test("Adds iat and exp claims when a TTL is set", async () => {
+    const before = Math.floor(Date.now() / 1000);
+    const token = await sign({
+      algorithm: "HS256",
+      secret: "s3cret",
+      payload: '{"sub":"svc"}',
+      ttl: "300",
+    });
+
+    const claims = jwt.verify(token, "s3cret") as jwt.JwtPayload;
+    expect(claims.sub).toBe("svc");
+    expect(claims.iat).toBeGreaterThanOrEqual(before);
+    expect(claims.exp).toBe((claims.iat ?? 0) + 300);
+  });
+
+  test("Keeps an exp claim from the payload", async () => {
+    const token = await sign({
+      algorithm: "HS256",
+      secret: "s3cret",
+      payload: '{"exp":4102444800}',
+      ttl: "300",
+    });
+    expect((jwt.decode(token) as jwt.JwtPayload).exp).toBe(4102444800);
+  });
+
+  test("Signs with an EC private key", async () => {
+    const { privateKey, publicKey } = generateKeyPairSync("ec", { namedCurve: "P-256" });
+    const token = await sign({
+      algorithm: "ES256",
+      secret: privateKey.export({ type: "pkcs8", format: "pem" }).toString(),
+      payload: '{"sub":"svc"}',
+      ttl: "60",
+    });
+
+    const claims = jwt.verify(token, publicKey, { algorithms: ["ES256"] }) as jwt.JwtPayload;
+    expect(claims.sub).toBe("svc");
+    expect(claims.exp).toBe((claims.iat ?? 0) + 60);
+  });
+});
@@ -51,6 +51,8 @@ export const plugin: PluginDefinition = {
         ],
       },
     ],
+    // The app runs the handshake itself so every leg shares one connection, which NTLM
+    // requires. This is only used where the handshake can't happen in the HTTP sender.
     async onApply(ctx, { values, method, url }) {
       const username = values.username ? String(values.username) : undefined;
       const password = values.password ? String(values.password) : undefined;
@@ -45,12 +45,15 @@ export async function fetchAccessToken(
       name: "client_assertion",
       value: args.clientAssertion,
     });
-  } else if (args.credentialsInBody) {
+  } else if (args.credentialsInBody || !args.clientSecret) {
+    // Public clients (eg. PKCE without a secret) only identify themselves by client_id
     httpRequest.body?.form.push({ name: "client_id", value: clientId });
-    httpRequest.body?.form.push({
-      name: "client_secret",
-      value: args.clientSecret,
-    });
+    if (args.clientSecret) {
+      httpRequest.body?.form.push({
+        name: "client_secret",
+        value: args.clientSecret,
+      });
+    }
   } else {
     const value = `Basic ${Buffer.from(`${clientId}:${args.clientSecret}`).toString("base64")}`;
     httpRequest.headers?.push({ name: "Authorization", value });
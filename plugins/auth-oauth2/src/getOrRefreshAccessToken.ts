@@ -60,9 +60,9 @@ export async function getOrRefreshAccessToken(
 
   if (scope) httpRequest.body?.form.push({ name: "scope", value: scope });
 
-  if (credentialsInBody) {
+  if (credentialsInBody || !clientSecret) {
     httpRequest.body?.form.push({ name: "client_id", value: clientId });
-    httpRequest.body?.form.push({ name: "client_secret", value: clientSecret });
+    if (clientSecret) httpRequest.body?.form.push({ name: "client_secret", value: clientSecret });
   } else {
     const value = `Basic ${Buffer.from(`${clientId}:${clientSecret}`).toString("base64")}`;
     httpRequest.headers?.push({ name: "Authorization", value });
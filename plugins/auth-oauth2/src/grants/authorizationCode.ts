@@ -81,7 +81,9 @@ export async function getAuthorizationCode(
   authorizationUrl.searchParams.set("response_type", "code");
   authorizationUrl.searchParams.set("client_id", clientId);
   if (scope) authorizationUrl.searchParams.set("scope", scope);
-  if (state) authorizationUrl.searchParams.set("state", state);
+  // Always send a state so the callback can be verified, even when one isn't configured
+  const expectedState = state || genOAuthState();
+  authorizationUrl.searchParams.set("state", expectedState);
   if (audience) authorizationUrl.searchParams.set("audience", audience);
   if (pkce) {
     authorizationUrl.searchParams.set(
@@ -101,7 +103,7 @@ export async function getAuthorizationCode(
       callbackPort: externalBrowser.callbackPort,
     });
     // Pass null to skip redirect URI matching — the callback came from our own local server
-    const extractedCode = extractCode(result.callbackUrl, null);
+    const extractedCode = extractCode(result.callbackUrl, null, expectedState);
     if (!extractedCode) {
       throw new Error("No authorization code found in callback URL");
     }
@@ -112,7 +114,13 @@ export async function getAuthorizationCode(
     if (redirectUri) {
       authorizationUrl.searchParams.set("redirect_uri", redirectUri);
     }
-    code = await getCodeViaEmbeddedBrowser(ctx, contextId, authorizationUrl, redirectUri);
+    code = await getCodeViaEmbeddedBrowser(
+      ctx,
+      contextId,
+      authorizationUrl,
+      redirectUri,
+      expectedState,
+    );
   }
 
   console.log("[oauth2] Code found");
@@ -143,6 +151,7 @@ async function getCodeViaEmbeddedBrowser(
   contextId: string,
   authorizationUrl: URL,
   redirectUri: string | null,
+  expectedState: string,
 ): Promise<string> {
   const dataDirKey = await getDataDirKey(ctx, contextId);
   const authorizationUrlStr = authorizationUrl.toString();
@@ -163,7 +172,7 @@ async function getCodeViaEmbeddedBrowser(
       async onNavigate({ url: urlStr }) {
         let code: string | null;
         try {
-          code = extractCode(urlStr, redirectUri);
+          code = extractCode(urlStr, redirectUri, expectedState);
         } catch (err) {
           reject(err);
           close();
@@ -186,6 +195,10 @@ export function genPkceCodeVerifier() {
   return encodeForPkce(randomBytes(32));
 }
 
+function genOAuthState() {
+  return encodeForPkce(randomBytes(16));
+}
+
 function pkceCodeChallenge(verifier: string, method: string) {
   if (method === "plain") {
     return verifier;
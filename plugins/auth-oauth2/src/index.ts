@@ -394,7 +394,7 @@ export const plugin: PluginDefinition = {
           },
           {
             type: "text",
-            name: "pkceCodeChallenge",
+            name: "pkceCodeVerifier",
             label: "Code Verifier",
             placeholder: "Automatically generated when not set",
             optional: true,
@@ -538,7 +538,11 @@ export const plugin: PluginDefinition = {
           pkce: values.usePkce
             ? {
                 challengeMethod: stringArg(values, "pkceChallengeMethod") || DEFAULT_PKCE_METHOD,
-                codeVerifier: stringArg(values, "pkceCodeVerifier") || genPkceCodeVerifier(),
+                codeVerifier:
+                  stringArg(values, "pkceCodeVerifier") ||
+                  // Older configs stored the verifier under the wrong name
+                  stringArg(values, "pkceCodeChallenge") ||
+                  genPkceCodeVerifier(),
               }
             : null,
           tokenName: tokenName,
@@ -595,7 +599,20 @@ export const plugin: PluginDefinition = {
 
       const headerName = stringArg(values, "headerName") || "Authorization";
       const headerValue = `${headerPrefix} ${token.response[tokenName] ?? ""}`.trim();
-      return { setHeaders: [{ name: headerName, value: headerValue }] };
+      return {
+        setHeaders: [{ name: headerName, value: headerValue }],
+        // Hand the token to the app so it can refresh it before sending once it expires
+        token: {
+          accessToken: token.response.access_token,
+          idToken: token.response.id_token,
+          refreshToken: token.response.refresh_token,
+          tokenType: token.response.token_type,
+          expiresIn: token.expiresAt
+            ? Math.max(0, Math.floor((token.expiresAt - Date.now()) / 1000))
+            : undefined,
+          scope: token.response.scope,
+        },
+      };
     },
   },
 };
@@ -4,7 +4,15 @@ export function isTokenExpired(token: AccessToken) {
   return token.expiresAt && Date.now() > token.expiresAt;
 }
 
-export function extractCode(urlStr: string, redirectUri: string | null): string | null {
+/**
+ * Extract the authorization code from a redirect URL. When `expectedState` is set, the callback
+ * must echo the same `state` back, which protects the flow against forged redirects.
+ */
+export function extractCode(
+  urlStr: string,
+  redirectUri: string | null,
+  expectedState: string | null = null,
+): string | null {
   const url = new URL(urlStr);
 
   if (!urlMatchesRedirect(url, redirectUri)) {
@@ -37,14 +45,18 @@ export function extractCode(urlStr: string, redirectUri: string | null): string
     throw new Error(message);
   }
 
-  const queryCode = query.get("code");
-  if (queryCode) return queryCode;
+  const code = query.get("code") || hashParams?.get("code");
+  if (!code) {
+    console.log("[oauth2] Code not found");
+    return null;
+  }
 
-  const hashCode = hashParams?.get("code");
-  if (hashCode) return hashCode;
+  const state = query.get("state") ?? hashParams?.get("state") ?? null;
+  if (expectedState != null && state !== expectedState) {
+    throw new Error("Failed to authorize: state did not match the authorization request");
+  }
 
-  console.log("[oauth2] Code not found");
-  return null;
+  return code;
 }
 
 export function urlMatchesRedirect(url: URL, redirectUrl: string | null): boolean {
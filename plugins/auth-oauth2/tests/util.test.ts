@@ -106,4 +106,20 @@ describe("extractCode", () => {
     const redirect = "https://app.example.com/cb";
     expect(extractCode(url, redirect)).toBe("abc");
   });
+
+  test("verifies the callback state when one is expected", () => {
+    const redirect = "https://app.example.com/cb";
+    expect(extractCode("https://app.example.com/cb?code=abc&state=xyz", redirect, "xyz")).toBe(
+      "abc",
+    );
+    expect(extractCode("https://app.example.com/cb#code=abc&state=xyz", redirect, "xyz")).toBe(
+      "abc",
+    );
+    expect(() =>
+      extractCode("https://app.example.com/cb?code=abc&state=forged", redirect, "xyz"),
+    ).toThrow("state did not match");
+    expect(() => extractCode("https://app.example.com/cb?code=abc", redirect, "xyz")).toThrow(
+      "state did not match",
+    );
+  });
 });
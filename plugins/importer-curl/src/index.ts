@@ -31,6 +31,7 @@ const SUPPORTED_FLAGS = [
   ["get", "G"], // Put the post data in the URL
   ["header", "H"],
   ["request", "X"], // Request method
+  ["unix-socket"], // Connect through a Unix domain socket
   ["url"], // Specify the URL explicitly
   ["url-query"],
   ["user", "u"], // Authentication
@@ -538,6 +539,11 @@ function importCommand(parseEntries: string[], workspaceId: string) {
     sortPriority: 0,
   };
 
+  const unixSocket = getPairValue(flagsByName, "", ["unix-socket"]);
+  if (unixSocket) {
+    request.unixSocket = unixSocket;
+  }
+
   return request;
 }
 
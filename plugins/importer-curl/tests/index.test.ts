@@ -16,6 +16,22 @@ describe("importer-curl", () => {
     });
   });
 
+  test("Imports Unix socket", () => {
+    expect(
+      convertCurl("curl --unix-socket /var/run/docker.sock http://localhost/containers/json"),
+    ).toEqual({
+      resources: {
+        workspaces: [baseWorkspace()],
+        httpRequests: [
+          baseRequest({
+            url: "http://localhost/containers/json",
+            unixSocket: "/var/run/docker.sock",
+          }),
+        ],
+      },
+    });
+  });
+
   test("Explicit URL", () => {
     expect(convertCurl("curl --url https://yaak.app")).toEqual({
       resources: {
@@ -0,0 +1,185 @@
+import type {
+  Context,
+  Folder,
+  HttpRequest,
+  HttpRequestHeader,
+  HttpUrlParameter,
+  PluginDefinition,
+  Workspace,
+} from "@yaakapp/api";
+import type { ImportPluginResponse } from "@yaakapp/api/lib/plugins/ImporterPlugin";
+
+type AtLeast<T, K extends keyof T> = Partial<T> & Pick<T, K>;
+
+interface ExportResources {
+  workspaces: AtLeast<Workspace, "name" | "id" | "model">[];
+  httpRequests: AtLeast<HttpRequest, "name" | "id" | "model" | "workspaceId">[];
+  folders: AtLeast<Folder, "name" | "id" | "model" | "workspaceId">[];
+}
+
+interface HarNameValue {
+  name?: string;
+  value?: string;
+  fileName?: string;
+  contentType?: string;
+}
+
+// Headers that are derived from the connection or body and shouldn't be sent verbatim
+const SKIPPED_HEADERS = ["content-length", "host", "connection"];
+
+export const plugin: PluginDefinition = {
+  importer: {
+    name: "HAR",
+    description: "Import requests from HTTP Archive (HAR) files",
+    onImport(_ctx: Context, args: { text: string }) {
+      return convertHar(args.text);
+    },
+  },
+};
+
+/**
+ * Convert a HAR 1.2 document into Yaak resources, creating one folder per host with a request
+ * for each entry in the order they were recorded.
+ */
+export function convertHar(contents: string): ImportPluginResponse | undefined {
+  const root = parseJSONToRecord(contents);
+  if (root == null) return;
+
+  const log = toRecord(root.log);
+  if (!Array.isArray(log.entries)) return;
+
+  const exportResources: ExportResources = {
+    workspaces: [],
+    httpRequests: [],
+    folders: [],
+  };
+
+  const workspace: ExportResources["workspaces"][0] = {
+    model: "workspace",
+    id: generateId("workspace"),
+    name: "HAR Import",
+  };
+  exportResources.workspaces.push(workspace);
+
+  const folderIdsByHost: Record<string, string> = {};
+  for (const entry of log.entries) {
+    const request = toRecord(toRecord(entry).request);
+    if (typeof request.url !== "string") continue;
+
+    let url: URL;
+    try {
+      url = new URL(request.url);
+    } catch {
+      continue;
+    }
+
+    let folderId = folderIdsByHost[url.host];
+    if (folderId == null) {
+      folderId = generateId("folder");
+      folderIdsByHost[url.host] = folderId;
+      exportResources.folders.push({
+        model: "folder",
+        id: folderId,
+        workspaceId: workspace.id,
+        name: url.host,
+        sortPriority: exportResources.folders.length,
+      });
+    }
+
+    const method = typeof request.method === "string" ? request.method.toUpperCase() : "GET";
+    const urlParameters: HttpUrlParameter[] = [...url.searchParams.entries()].map(
+      ([name, value]) => ({ name, value, enabled: true }),
+    );
+    const headers: HttpRequestHeader[] = toArray<HarNameValue>(request.headers)
+      .filter((h) => typeof h.name === "string" && !h.name.startsWith(":"))
+      .filter((h) => !SKIPPED_HEADERS.includes(String(h.name).toLowerCase()))
+      .map((h) => ({ name: String(h.name), value: String(h.value ?? ""), enabled: true }));
+
+    exportResources.httpRequests.push({
+      model: "http_request",
+      id: generateId("http_request"),
+      workspaceId: workspace.id,
+      folderId,
+      sortPriority: exportResources.httpRequests.length,
+      name: `${method} ${url.pathname}`,
+      method,
+      url: `${url.origin}${url.pathname}`,
+      urlParameters,
+      headers,
+      ...importPostData(request.postData),
+    });
+  }
+
+  return { resources: exportResources };
+}
+
+function importPostData(rawPostData: unknown): Pick<HttpRequest, "body" | "bodyType"> {
+  const postData = toRecord<unknown>(rawPostData);
+  const mimeType = (String(postData.mimeType ?? "").split(";")[0] ?? "").trim().toLowerCase();
+  const params = toArray<HarNameValue>(postData.params);
+  const text = typeof postData.text === "string" ? postData.text : "";
+
+  if (mimeType === "multipart/form-data" && params.length > 0) {
+    return {
+      bodyType: "multipart/form-data",
+      body: {
+        form: params.map((p) => ({
+          name: p.name ?? "",
+          enabled: true,
+          ...(p.fileName
+            ? { file: p.fileName, contentType: p.contentType }
+            : { value: p.value ?? "" }),
+        })),
+      },
+    };
+  }
+
+  if (mimeType === "application/x-www-form-urlencoded") {
+    const form =
+      params.length > 0
+        ? params.map((p) => ({ name: p.name ?? "", value: p.value ?? "", enabled: true }))
+        : [...new URLSearchParams(text).entries()].map(([name, value]) => ({
+            name,
+            value,
+            enabled: true,
+          }));
+    return { bodyType: "application/x-www-form-urlencoded", body: { form } };
+  }
+
+  if (text === "") {
+    return { body: {}, bodyType: null };
+  }
+
+  let bodyType = "other";
+  if (mimeType.includes("json")) bodyType = "application/json";
+  else if (mimeType.includes("xml")) bodyType = "text/xml";
+  else if (mimeType === "text/plain") bodyType = "text/plain";
+  return { bodyType, body: { text } };
+}
+
+function parseJSONToRecord<T>(jsonStr: string): Record<string, T> | null {
+  try {
+    return toRecord(JSON.parse(jsonStr));
+  } catch {
+    return null;
+  }
+}
+
+function toRecord<T>(value: unknown): Record<string, T> {
+  if (value && typeof value === "object" && !Array.isArray(value)) {
+    return value as Record<string, T>;
+  }
+  return {};
+}
+
+function toArray<T>(value: unknown): T[] {
+  if (Object.prototype.toString.call(value) === "[object Array]") return value as T[];
+  return [];
+}
+
+const idCount: Partial<Record<string, number>> = {};
+
+function generateId(model: string): string {
+  idCount[model] = (idCount[model] ?? -1) + 1;
+  return `GENERATE_ID::${model.toUpperCase()}_${idCount[model]}`;
+}
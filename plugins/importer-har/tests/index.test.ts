@@ -0,0 +1,88 @@
+import { describe, expect, test } from "vite-plus/test";
+import { convertHar } from "../src";
+
+describe("importer-har", () => {
+  test("Skips non-HAR documents", () => {
+    expect(convertHar("not json")).toBeUndefined();
+    expect(convertHar(JSON.stringify({ info: {} }))).toBeUndefined();
+  });
+
+  test("Imports entries grouped by host", () => {
+    const result = convertHar(
+      JSON.stringify({
+        log: {
+          version: "1.2",
+          entries: [
+            {
+              request: {
+                method: "get",
+                url: "https://api.example.com/users?page=2",
+                headers: [
+                  { name: ":authority", value: "api.example.com" },
+                  { name: "Accept", value: "application/json" },
+                  { name: "Content-Length", value: "0" },
+                ],
+              },
+            },
+            {
+              request: {
+                method: "POST",
+                url: "https://auth.example.com/token",
+                headers: [],
+                postData: {
+                  mimeType: "application/x-www-form-urlencoded",
+                  text: "grant_type=client_credentials&scope=read%20write",
+                },
+              },
+            },
+            {
+              request: {
+                method: "POST",
+                url: "https://api.example.com/users",
+                headers: [],
+                postData: { mimeType: "application/json; charset=utf-8", text: '{"name":"Yaak"}' },
+              },
+            },
+          ],
+        },
+      }),
+    );
+
+    const folders = result?.resources.folders ?? [];
+    const requests = result?.resources.httpRequests ?? [];
+    expect(folders.map((f) => f.name)).toEqual(["api.example.com", "auth.example.com"]);
+    expect(requests.map((r) => r.folderId)).toEqual([
+      folders[0]?.id,
+      folders[1]?.id,
+      folders[0]?.id,
+    ]);
+
+    expect(requests[0]).toEqual(
+      expect.objectContaining({
+        name: "GET /users",
+        method: "GET",
+        url: "https://api.example.com/users",
+        urlParameters: [{ name: "page", value: "2", enabled: true }],
+        headers: [{ name: "Accept", value: "application/json", enabled: true }],
+        bodyType: null,
+      }),
+    );
+    expect(requests[1]).toEqual(
+      expect.objectContaining({
+        bodyType: "application/x-www-form-urlencoded",
+        body: {
+          form: [
+            { name: "grant_type", value: "client_credentials", enabled: true },
+            { name: "scope", value: "read write", enabled: true },
+          ],
+        },
+      }),
+    );
+    expect(requests[2]).toEqual(
+      expect.objectContaining({
+        bodyType: "application/json",
+        body: { text: '{"name":"Yaak"}' },
+      }),
+    );
+  });
+});
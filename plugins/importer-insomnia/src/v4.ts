@@ -5,7 +5,8 @@ import { convertId, convertTemplateSyntax, isJSObject } from "./common";
 export function convertInsomniaV4(parsed: any) {
   if (!Array.isArray(parsed.resources)) return null;
 
-  const resources: PartialImportResources = {
+  const resources: Required<PartialImportResources> = {
+    cookieJars: [],
     environments: [],
     folders: [],
     grpcRequests: [],
@@ -34,6 +35,11 @@ export function convertInsomniaV4(parsed: any) {
       ...environmentsToImport.map((r: any) => importEnvironment(r, w._id)),
     );
 
+    const cookieJarsToImport = parsed.resources.filter(
+      (r: any) => isJSObject(r) && r._type === "cookie_jar" && r.parentId === w._id,
+    );
+    resources.cookieJars.push(...cookieJarsToImport.map((r: any) => importCookieJar(r, w._id)));
+
     const nextFolder = (parentId: string) => {
       const children = parsed.resources.filter((r: any) => r.parentId === parentId);
       for (const child of children) {
@@ -41,6 +47,8 @@ export function convertInsomniaV4(parsed: any) {
 
         if (child._type === "request_group") {
           resources.folders.push(importFolder(child, w._id));
+          const folderEnvironment = importFolderEnvironment(child, w._id);
+          if (folderEnvironment != null) resources.environments.push(folderEnvironment);
           nextFolder(child._id);
         } else if (child._type === "request") {
           resources.httpRequests.push(importHttpRequest(child, w._id));
@@ -184,6 +192,72 @@ function importFolder(f: any, workspaceId: string): PartialImportResources["fold
   };
 }
 
+/** Insomnia request groups can define variables, which map to a folder environment in Yaak */
+function importFolderEnvironment(
+  f: any,
+  workspaceId: string,
+): PartialImportResources["environments"][0] | null {
+  if (!isJSObject(f.environment) || Object.keys(f.environment).length === 0) return null;
+
+  return {
+    id: convertId(`${f._id}_environment`),
+    workspaceId: convertId(workspaceId),
+    parentModel: "folder",
+    parentId: convertId(f._id),
+    model: "environment",
+    name: f.name,
+    variables: Object.entries(f.environment).map(([name, value]) => ({
+      enabled: true,
+      name,
+      value: String(value),
+    })),
+  };
+}
+
+function importCookieJar(
+  j: any,
+  workspaceId: string,
+): NonNullable<PartialImportResources["cookieJars"]>[0] {
+  return {
+    id: convertId(j._id),
+    createdAt: j.created ? new Date(j.created).toISOString().replace("Z", "") : undefined,
+    updatedAt: j.modified ? new Date(j.modified).toISOString().replace("Z", "") : undefined,
+    workspaceId: convertId(workspaceId),
+    model: "cookie_jar",
+    name: j.name,
+    cookies: (j.cookies ?? []).filter(isJSObject).map((c: any) => {
+      // Insomnia stores cookies in tough-cookie's format, where a missing or infinite expiry
+      // means the cookie lasts for the session
+      const expiresAt = c.expires != null && c.expires !== "Infinity" ? new Date(c.expires) : null;
+      const sameSite = String(c.sameSite ?? "").toLowerCase();
+      return {
+        name: c.key ?? "",
+        value: c.value ?? "",
+        domain: !c.domain
+          ? "NotPresent"
+          : c.hostOnly
+            ? { HostOnly: c.domain }
+            : { Suffix: c.domain },
+        expires:
+          expiresAt != null && !Number.isNaN(expiresAt.getTime())
+            ? { AtUtc: String(Math.floor(expiresAt.getTime() / 1000)) }
+            : "SessionEnd",
+        path: c.path ?? "/",
+        secure: !!c.secure,
+        httpOnly: !!c.httpOnly,
+        sameSite:
+          sameSite === "strict"
+            ? "Strict"
+            : sameSite === "lax"
+              ? "Lax"
+              : sameSite === "none"
+                ? "None"
+                : null,
+      };
+    }),
+  };
+}
+
 function importEnvironment(
   e: any,
   workspaceId: string,
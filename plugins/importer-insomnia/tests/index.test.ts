@@ -24,6 +24,68 @@ describe("importer-yaak", () => {
       expect(result).toEqual(parseJsonOrYaml(expected));
     });
   }
+
+  test("Imports v4 cookies and folder variables", () => {
+    const result = convertInsomnia(
+      JSON.stringify({
+        _type: "export",
+        __export_format: 4,
+        resources: [
+          { _id: "wrk_1", _type: "workspace", name: "Cookies" },
+          {
+            _id: "fld_1",
+            _type: "request_group",
+            parentId: "wrk_1",
+            name: "Folder",
+            environment: { token: "abc" },
+          },
+          {
+            _id: "jar_1",
+            _type: "cookie_jar",
+            parentId: "wrk_1",
+            name: "Jar",
+            cookies: [
+              {
+                key: "session",
+                value: "123",
+                domain: "example.com",
+                path: "/",
+                hostOnly: true,
+                httpOnly: true,
+                expires: "2030-01-01T00:00:00.000Z",
+              },
+            ],
+          },
+        ],
+      }),
+    );
+
+    expect(result?.resources.cookieJars).toEqual([
+      expect.objectContaining({
+        id: "GENERATE_ID::jar_1",
+        workspaceId: "GENERATE_ID::wrk_1",
+        cookies: [
+          {
+            name: "session",
+            value: "123",
+            domain: { HostOnly: "example.com" },
+            expires: { AtUtc: "1893456000" },
+            path: "/",
+            secure: false,
+            httpOnly: true,
+            sameSite: null,
+          },
+        ],
+      }),
+    ]);
+    expect(result?.resources.environments).toEqual([
+      expect.objectContaining({
+        parentModel: "folder",
+        parentId: "GENERATE_ID::fld_1",
+        variables: [{ enabled: true, name: "token", value: "abc" }],
+      }),
+    ]);
+  });
 });
 
 function parseJsonOrYaml(text: string): unknown {
@@ -7,19 +7,38 @@ export const plugin: PluginDefinition = {
   importer: {
     name: "OpenAPI",
     description: "Import OpenAPI collections",
-    onImport(_ctx: Context, args: { text: string }) {
-      return convertOpenApi(args.text);
+    async onImport(ctx: Context, args: { text: string }) {
+      const warnings: string[] = [];
+      const result = await convertOpenApi(args.text, warnings);
+      if (result != null && warnings.length > 0) {
+        await ctx.toast.show({
+          message: `Some OpenAPI features could not be imported:\n${warnings.join("\n")}`,
+          icon: "alert_triangle",
+          color: "warning",
+          timeout: 10000,
+        });
+      }
+      return result;
     },
   },
 };
 
-export async function convertOpenApi(contents: string): Promise<ImportPluginResponse | undefined> {
+/**
+ * Convert an OpenAPI 3.0/3.1 document into Yaak resources, with one folder per tag and one request
+ * per operation. Path parameters become URL parameters, request bodies are generated from schema
+ * examples, and global security schemes become workspace auth.
+ */
+export async function convertOpenApi(
+  contents: string,
+  warnings: string[] = [],
+): Promise<ImportPluginResponse | undefined> {
   // oxlint-disable-next-line no-explicit-any
   let postmanCollection: any;
   try {
     postmanCollection = await new Promise((resolve, reject) => {
+      const options = { folderStrategy: "Tags", parametersResolution: "Example" } as const;
       // oxlint-disable-next-line no-explicit-any
-      convert({ type: "string", data: contents }, {}, (err, result: any) => {
+      convert({ type: "string", data: contents }, options, (err, result: any) => {
         if (err != null) reject(err);
 
         if (Array.isArray(result.output) && result.output.length > 0) {
@@ -32,5 +51,5 @@ export async function convertOpenApi(contents: string): Promise<ImportPluginResp
     return undefined;
   }
 
-  return convertPostman(JSON.stringify(postmanCollection));
+  return convertPostman(JSON.stringify(postmanCollection), warnings);
 }
@@ -46,7 +46,59 @@ describe("importer-openapi", () => {
         }),
       ]);
       expect(imported?.resources.httpRequests.length).toBe(19);
-      expect(imported?.resources.folders.length).toBe(7);
+      expect(imported?.resources.folders.map((f) => f.name)).toEqual(["pet", "store", "user"]);
+      expect(imported?.resources.httpRequests).toContainEqual(
+        expect.objectContaining({
+          name: "Find pet by ID",
+          url: expect.stringContaining("/pet/:petId"),
+          urlParameters: expect.arrayContaining([expect.objectContaining({ name: ":petId" })]),
+        }),
+      );
     });
   }
+
+  test("Generates example bodies and maps global security to workspace auth", async () => {
+    const imported = await convertOpenApi(
+      JSON.stringify({
+        openapi: "3.1.0",
+        info: { title: "Security Test", version: "1.0.0" },
+        security: [{ bearerAuth: [] }],
+        components: {
+          securitySchemes: { bearerAuth: { type: "http", scheme: "bearer" } },
+        },
+        paths: {
+          "/users": {
+            post: {
+              tags: ["users"],
+              summary: "Create user",
+              requestBody: {
+                content: {
+                  "application/json": {
+                    schema: {
+                      type: "object",
+                      properties: { name: { type: "string", example: "Ada" } },
+                    },
+                  },
+                },
+              },
+              responses: { "201": { description: "created" } },
+            },
+          },
+        },
+      }),
+    );
+
+    expect(imported?.resources.workspaces).toEqual([
+      expect.objectContaining({ authenticationType: "bearer" }),
+    ]);
+    expect(imported?.resources.folders).toEqual([expect.objectContaining({ name: "users" })]);
+    expect(imported?.resources.httpRequests).toEqual([
+      expect.objectContaining({
+        name: "Create user",
+        method: "POST",
+        bodyType: "application/json",
+        body: { text: expect.stringContaining('"Ada"') },
+      }),
+    ]);
+  });
 });
@@ -29,13 +29,30 @@ export const plugin: PluginDefinition = {
   importer: {
     name: "Postman",
     description: "Import postman collections",
-    onImport(_ctx: Context, args: { text: string }) {
-      return convertPostman(args.text);
+    async onImport(ctx: Context, args: { text: string }) {
+      const warnings: string[] = [];
+      const result = convertPostman(args.text, warnings);
+      if (result != null && warnings.length > 0) {
+        await ctx.toast.show({
+          message: `Some Postman features could not be imported:\n${warnings.join("\n")}`,
+          icon: "alert_triangle",
+          color: "warning",
+          timeout: 10000,
+        });
+      }
+      return result;
     },
   },
 };
 
-export function convertPostman(contents: string): ImportPluginResponse | undefined {
+/**
+ * Convert a Postman collection into Yaak resources. Anything that can't be represented in Yaak
+ * (eg. scripts or unsupported auth types) is described in `warnings`.
+ */
+export function convertPostman(
+  contents: string,
+  warnings: string[] = [],
+): ImportPluginResponse | undefined {
   const root = parseJSONToRecord(contents);
   if (root == null) return;
 
@@ -47,7 +64,7 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
     return;
   }
 
-  const globalAuth = importAuth(root.auth);
+  const globalAuth = importAuth(root.auth, warnings);
 
   const exportResources: ExportResources = {
     workspaces: [],
@@ -60,7 +77,12 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
     model: "workspace",
     id: generateId("workspace"),
     name: info.name ? String(info.name) : "Postman Import",
-    description: importDescription(info.description),
+    description: appendScripts(
+      importDescription(info.description),
+      root.event,
+      "collection",
+      warnings,
+    ),
     ...globalAuth,
   };
   exportResources.workspaces.push(workspace);
@@ -74,9 +96,10 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
     parentModel: "workspace",
     parentId: null,
     variables:
-      toArray<{ key: string; value: string }>(root.variable).map((v) => ({
+      toArray<{ key: string; value: string; disabled?: boolean }>(root.variable).map((v) => ({
         name: v.key,
         value: v.value,
+        enabled: v.disabled ? false : undefined,
       })) ?? [],
   };
   exportResources.environments.push(environment);
@@ -90,7 +113,14 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
         workspaceId: workspace.id,
         id: generateId("folder"),
         name: v.name,
+        description: appendScripts(
+          importDescription(v.description),
+          v.event,
+          `folder "${v.name}"`,
+          warnings,
+        ),
         folderId,
+        ...(v.auth != null ? importAuth(v.auth, warnings) : {}),
       };
       exportResources.folders.push(folder);
       for (const child of v.item) {
@@ -99,7 +129,7 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
     } else if (typeof v.name === "string" && "request" in v) {
       const r = toRecord(v.request);
       const bodyPatch = importBody(r.body);
-      const requestAuth = importAuth(r.auth);
+      const requestAuth = importAuth(r.auth, warnings);
 
       const headers: HttpRequestHeader[] = toArray<{
         key: string;
@@ -132,7 +162,12 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
         workspaceId: workspace.id,
         folderId,
         name: v.name,
-        description: importDescription(r.description),
+        description: appendScripts(
+          importDescription(r.description),
+          v.event,
+          `request "${v.name}"`,
+          warnings,
+        ),
         method: typeof r.method === "string" ? r.method : "GET",
         url,
         urlParameters,
@@ -144,7 +179,7 @@ export function convertPostman(contents: string): ImportPluginResponse | undefin
       };
       exportResources.httpRequests.push(request);
     } else {
-      console.log("Unknown item", v, folderId);
+      warnings.push(`Skipped unrecognized item ${JSON.stringify(v.name ?? null)}`);
     }
   };
 
@@ -214,7 +249,10 @@ function convertUrl(rawUrl: unknown): Pick<HttpRequest, "url" | "urlParameters">
   return { url: v, urlParameters: params };
 }
 
-function importAuth(rawAuth: unknown): Pick<HttpRequest, "authentication" | "authenticationType"> {
+function importAuth(
+  rawAuth: unknown,
+  warnings: string[] = [],
+): Pick<HttpRequest, "authentication" | "authenticationType"> {
   const auth = toRecord<Record<string, string>>(rawAuth);
 
   // Helper: Postman stores auth params as an array of { key, value, ... }
@@ -367,6 +405,10 @@ function importAuth(rawAuth: unknown): Pick<HttpRequest, "authentication" | "aut
     return { authenticationType: "oauth2", authentication };
   }
 
+  if (authType != null && authType !== "inherit") {
+    warnings.push(`Unsupported auth type "${authType}" was not imported`);
+  }
+
   return { authenticationType: null, authentication: {} };
 }
 
@@ -522,6 +564,31 @@ function importDescription(rawDescription: unknown): string | undefined {
   return String(rawDescription);
 }
 
+/**
+ * Yaak doesn't run scripts, so keep the source of any Postman scripts in the description where
+ * it can be ported by hand.
+ */
+function appendScripts(
+  description: string | undefined,
+  rawEvents: unknown,
+  owner: string,
+  warnings: string[],
+): string | undefined {
+  const sections: string[] = [];
+  for (const event of toArray<Record<string, unknown>>(rawEvents)) {
+    const script = toRecord(event.script);
+    const exec = Array.isArray(script.exec) ? script.exec.join("\n") : String(script.exec ?? "");
+    if (exec.trim() === "") continue;
+
+    const label = event.listen === "prerequest" ? "Pre-request script" : "Test script";
+    sections.push(`### Postman ${label.toLowerCase()}\n\n\`\`\`js\n${exec}\n\`\`\``);
+    warnings.push(`${label} on ${owner} was not imported (kept in description)`);
+  }
+
+  if (sections.length === 0) return description;
+  return [description, ...sections].filter(Boolean).join("\n\n");
+}
+
 /** Recursively render all nested object properties */
 function convertTemplateSyntax<T>(obj: T): T {
   if (typeof obj === "string") {
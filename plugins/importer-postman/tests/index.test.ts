@@ -57,4 +57,50 @@ describe("importer-postman", () => {
       }),
     ]);
   });
+
+  test("Keeps scripts in descriptions and reports what wasn't mapped", () => {
+    const warnings: string[] = [];
+    const result = convertPostman(
+      JSON.stringify({
+        info: {
+          name: "Scripts Test",
+          schema: "https://schema.getpostman.com/json/collection/v2.1.0/collection.json",
+        },
+        item: [
+          {
+            name: "Folder 1",
+            auth: { type: "bearer", bearer: [{ key: "token", value: "abc" }] },
+            item: [
+              {
+                name: "Request 1",
+                event: [
+                  {
+                    listen: "prerequest",
+                    script: { exec: ["pm.environment.set('a', 1);"] },
+                  },
+                ],
+                request: { method: "GET", auth: { type: "hawk" } },
+              },
+            ],
+          },
+        ],
+      }),
+      warnings,
+    );
+
+    expect(result?.resources.folders).toEqual([
+      expect.objectContaining({
+        name: "Folder 1",
+        authenticationType: "bearer",
+        authentication: { token: "abc" },
+      }),
+    ]);
+    expect(result?.resources.httpRequests[0]?.description).toContain(
+      "pm.environment.set('a', 1);",
+    );
+    expect(warnings).toEqual([
+      'Unsupported auth type "hawk" was not imported',
+      'Pre-request script on request "Request 1" was not imported (kept in description)',
+    ]);
+  });
 });
@@ -1,4 +1,5 @@
 import type { Environment, PluginDefinition } from "@yaakapp/api";
+import YAML from "yaml";
 
 export const plugin: PluginDefinition = {
   importer: {
@@ -15,6 +16,13 @@ export function migrateImport(contents: string) {
   let parsed: any;
   try {
     parsed = JSON.parse(contents);
+  } catch {
+    // Fall through
+  }
+
+  // Exports can also be written as YAML
+  try {
+    parsed = parsed ?? YAML.parse(contents);
   } catch {
     return undefined;
   }